@@ -119,6 +119,7 @@ impl ActivityIndicator {
                 &project.read(cx).environment().clone(),
                 |_, _, event, cx| match event {
                     ProjectEnvironmentEvent::ErrorsUpdated => cx.notify(),
+                    ProjectEnvironmentEvent::EnvironmentChanged(_) => {}
                 },
             )
             .detach();
@@ -714,6 +715,10 @@ impl StatusItemView for ActivityIndicator {
         _: &mut Context<Self>,
     ) {
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("activity_indicator".into())
+    }
 }
 
 #[cfg(test)]
@@ -44,7 +44,7 @@ use std::ffi::OsStr;
 use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use text::ToPoint;
 use theme::ThemeSettings;
 use ui::{
@@ -53,7 +53,10 @@ use ui::{
 };
 use util::ResultExt as _;
 use util::markdown::MarkdownCodeBlock;
-use workspace::{CollaboratorId, Workspace};
+use workspace::{
+    CollaboratorId, Workspace,
+    bell::{self, BellEvent},
+};
 use zed_actions::assistant::OpenRulesLibrary;
 use zed_llm_client::CompletionIntent;
 
@@ -83,6 +86,8 @@ pub struct ActiveThread {
     notification_subscriptions: HashMap<WindowHandle<AgentNotification>, Vec<Subscription>>,
     open_feedback_editors: HashMap<MessageId, Entity<Editor>>,
     _load_edited_message_context_task: Option<Task<()>>,
+    /// When the agent started responding to the last user message, across all its tool uses.
+    response_started_at: Option<Instant>,
 }
 
 struct RenderedMessage {
@@ -806,6 +811,7 @@ impl ActiveThread {
             notification_subscriptions: HashMap::default(),
             open_feedback_editors: HashMap::default(),
             _load_edited_message_context_task: None,
+            response_started_at: None,
         };
 
         for message in thread.read(cx).messages().cloned().collect::<Vec<_>>() {
@@ -974,9 +980,11 @@ impl ActiveThread {
                 self.last_error = Some(error.clone());
             }
             ThreadEvent::NewRequest => {
+                self.response_started_at.get_or_insert_with(Instant::now);
                 cx.notify();
             }
             ThreadEvent::CompletionCanceled => {
+                self.response_started_at = None;
                 self.thread.update(cx, |thread, cx| {
                     thread.project().update(cx, |project, cx| {
                         project.set_agent_location(None, cx);
@@ -997,7 +1005,11 @@ impl ActiveThread {
                 self.save_thread(cx);
             }
             ThreadEvent::Stopped(reason) => match reason {
+                Ok(StopReason::ToolUse) => {}
                 Ok(StopReason::EndTurn | StopReason::MaxTokens) => {
+                    if let Some(started_at) = self.response_started_at.take() {
+                        bell::ring(BellEvent::AgentResponseFinished(started_at.elapsed()), cx);
+                    }
                     let used_tools = self.thread.read(cx).used_tools_since_last_user_message();
                     self.play_notification_sound(window, cx);
                     self.show_notification(
@@ -1011,7 +1023,7 @@ impl ActiveThread {
                         cx,
                     );
                 }
-                _ => {}
+                _ => self.response_started_at = None,
             },
             ThreadEvent::ToolConfirmationNeeded => {
                 self.play_notification_sound(window, cx);
@@ -11,12 +11,15 @@ mod context_server_configuration;
 mod context_server_tool;
 mod context_store;
 mod context_strip;
+mod context_usage;
 mod debug;
 mod history_store;
 mod inline_assistant;
 mod inline_prompt_editor;
 mod message_editor;
+mod multi_file_transform;
 mod profile_selector;
+mod rule_prompt;
 mod slash_command_settings;
 mod terminal_codegen;
 mod terminal_inline_assistant;
@@ -75,6 +78,7 @@ actions!(
         ChatWithFollow,
         CycleNextInlineAssist,
         CyclePreviousInlineAssist,
+        TransformAcrossFiles,
         FocusUp,
         FocusDown,
         FocusLeft,
@@ -87,6 +91,10 @@ actions!(
         Reject,
         RejectAll,
         KeepAll,
+        KeepFile,
+        RejectFile,
+        ReviewNextFile,
+        ReviewPreviousFile,
         Follow,
         ResetTrialUpsell,
         ResetTrialEndUpsell,
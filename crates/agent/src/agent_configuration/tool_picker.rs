@@ -296,6 +296,7 @@ impl PickerDelegate for ToolPickerDelegate {
                                             )
                                         })
                                         .collect(),
+                                    model: default_profile.model,
                                 });
 
                         if let Some(server_id) = server_id {
@@ -1,4 +1,7 @@
-use crate::{Keep, KeepAll, OpenAgentDiff, Reject, RejectAll, Thread, ThreadEvent};
+use crate::{
+    Keep, KeepAll, KeepFile, OpenAgentDiff, Reject, RejectAll, RejectFile, ReviewNextFile,
+    ReviewPreviousFile, Thread, ThreadEvent,
+};
 use agent_settings::AgentSettings;
 use anyhow::Result;
 use buffer_diff::DiffHunkStatus;
@@ -14,9 +17,9 @@ use gpui::{
     WeakEntity, Window, percentage, prelude::*,
 };
 
-use language::{Buffer, Capability, DiskState, OffsetRangeExt, Point};
+use language::{Buffer, BufferId, Capability, DiskState, OffsetRangeExt, Point};
 use language_model::StopReason;
-use multi_buffer::PathKey;
+use multi_buffer::{MultiBufferDiffHunk, MultiBufferRow, PathKey};
 use project::{Project, ProjectItem, ProjectPath};
 use settings::{Settings, SettingsStore};
 use std::{
@@ -107,6 +110,12 @@ impl AgentDiffPane {
                 cx.subscribe(&thread, |this, _thread, event, cx| {
                     this.handle_thread_event(event, cx)
                 }),
+                // Keeps the review progress shown in the toolbar up to date.
+                cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::SelectionsChanged { .. } = event {
+                        cx.notify();
+                    }
+                }),
             ],
             title: SharedString::default(),
             multibuffer,
@@ -281,6 +290,213 @@ impl AgentDiffPane {
         self.thread
             .update(cx, |thread, cx| thread.keep_all_edits(cx));
     }
+
+    fn keep_file(&mut self, _: &KeepFile, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor.update(cx, |editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let ranges = hunk_ranges_in_cursor_file(editor, &snapshot);
+            move_to_next_file(editor, &snapshot, window, cx);
+            keep_edits_in_ranges(editor, &snapshot, &self.thread, ranges, window, cx);
+        });
+    }
+
+    fn reject_file(&mut self, _: &RejectFile, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor.update(cx, |editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let ranges = hunk_ranges_in_cursor_file(editor, &snapshot);
+            move_to_next_file(editor, &snapshot, window, cx);
+            reject_edits_in_ranges(editor, &snapshot, &self.thread, ranges, window, cx);
+        });
+    }
+
+    fn review_next_file(
+        &mut self,
+        _: &ReviewNextFile,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let hunks = review_hunks(editor, &snapshot);
+            let files = review_files(&hunks);
+            let target = match cursor_file_ix(editor, &snapshot, &files) {
+                Some(ix) => files.get(ix + 1),
+                None => {
+                    let cursor_row = cursor_row(editor, &snapshot);
+                    hunks
+                        .iter()
+                        .find(|hunk| hunk.row_range.start > cursor_row)
+                        .map(|hunk| &hunk.buffer_id)
+                }
+            };
+            if let Some(&buffer_id) = target {
+                select_first_hunk_of_file(editor, &hunks, buffer_id, window, cx);
+            }
+        });
+    }
+
+    fn review_previous_file(
+        &mut self,
+        _: &ReviewPreviousFile,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let hunks = review_hunks(editor, &snapshot);
+            let files = review_files(&hunks);
+            let target = match cursor_file_ix(editor, &snapshot, &files) {
+                Some(ix) => ix.checked_sub(1).and_then(|ix| files.get(ix)),
+                None => {
+                    let cursor_row = cursor_row(editor, &snapshot);
+                    hunks
+                        .iter()
+                        .rev()
+                        .find(|hunk| hunk.row_range.start < cursor_row)
+                        .map(|hunk| &hunk.buffer_id)
+                }
+            };
+            if let Some(&buffer_id) = target {
+                select_first_hunk_of_file(editor, &hunks, buffer_id, window, cx);
+            }
+        });
+    }
+
+    /// Where the cursor is among the hunks and files left to review.
+    pub fn review_progress(&self, cx: &App) -> ReviewProgress {
+        let editor = self.editor.read(cx);
+        let snapshot = self.multibuffer.read(cx).snapshot(cx);
+        let hunks = review_hunks(editor, &snapshot);
+        let files = review_files(&hunks);
+        let cursor_row = cursor_row(editor, &snapshot);
+        ReviewProgress {
+            hunk_ix: hunks
+                .iter()
+                .position(|hunk| hunk_contains_row(hunk, cursor_row)),
+            hunk_count: hunks.len(),
+            file_ix: cursor_file_ix(editor, &snapshot, &files),
+            file_count: files.len(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReviewProgress {
+    pub hunk_ix: Option<usize>,
+    pub hunk_count: usize,
+    pub file_ix: Option<usize>,
+    pub file_count: usize,
+}
+
+impl ReviewProgress {
+    fn label(&self) -> String {
+        let hunks = match self.hunk_ix {
+            Some(ix) => format!("Hunk {} of {}", ix + 1, self.hunk_count),
+            None if self.hunk_count == 1 => "1 hunk".to_string(),
+            None => format!("{} hunks", self.hunk_count),
+        };
+        let files = match self.file_ix {
+            Some(ix) => format!("File {} of {}", ix + 1, self.file_count),
+            None if self.file_count == 1 => "1 file".to_string(),
+            None => format!("{} files", self.file_count),
+        };
+        format!("{hunks} · {files}")
+    }
+}
+
+fn review_hunks(editor: &Editor, snapshot: &MultiBufferSnapshot) -> Vec<MultiBufferDiffHunk> {
+    editor
+        .diff_hunks_in_ranges(&[editor::Anchor::min()..editor::Anchor::max()], snapshot)
+        .collect()
+}
+
+/// The files with hunks left to review, in the order they're listed in.
+fn review_files(hunks: &[MultiBufferDiffHunk]) -> Vec<BufferId> {
+    let mut files = Vec::<BufferId>::new();
+    for hunk in hunks {
+        if files.last() != Some(&hunk.buffer_id) {
+            files.push(hunk.buffer_id);
+        }
+    }
+    files
+}
+
+fn cursor_row(editor: &Editor, snapshot: &MultiBufferSnapshot) -> MultiBufferRow {
+    MultiBufferRow(
+        editor
+            .selections
+            .newest_anchor()
+            .head()
+            .to_point(snapshot)
+            .row,
+    )
+}
+
+fn cursor_file_ix(
+    editor: &Editor,
+    snapshot: &MultiBufferSnapshot,
+    files: &[BufferId],
+) -> Option<usize> {
+    let cursor = editor.selections.newest_anchor().head();
+    let buffer_id = snapshot.buffer_ids_for_range(cursor..cursor).next()?;
+    files.iter().position(|file| *file == buffer_id)
+}
+
+/// Whether the cursor on `row` is on the hunk, including on the row a deletion is shown above.
+fn hunk_contains_row(hunk: &MultiBufferDiffHunk, row: MultiBufferRow) -> bool {
+    hunk.row_range.contains(&row) || hunk.row_range.start == row
+}
+
+fn hunk_ranges_in_cursor_file(
+    editor: &Editor,
+    snapshot: &MultiBufferSnapshot,
+) -> Vec<Range<editor::Anchor>> {
+    let hunks = review_hunks(editor, snapshot);
+    let files = review_files(&hunks);
+    let Some(buffer_id) = cursor_file_ix(editor, snapshot, &files).map(|ix| files[ix]) else {
+        return Vec::new();
+    };
+    hunks
+        .iter()
+        .filter(|hunk| hunk.buffer_id == buffer_id)
+        .map(|hunk| hunk.multi_buffer_range())
+        .collect()
+}
+
+/// Moves the cursor to the file to review once the one it's in has been kept or rejected: the
+/// next one, or the previous one when it's the last.
+fn move_to_next_file(
+    editor: &mut Editor,
+    snapshot: &MultiBufferSnapshot,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let hunks = review_hunks(editor, snapshot);
+    let files = review_files(&hunks);
+    let Some(ix) = cursor_file_ix(editor, snapshot, &files) else {
+        return;
+    };
+    let target = files
+        .get(ix + 1)
+        .or_else(|| ix.checked_sub(1).and_then(|ix| files.get(ix)));
+    if let Some(&buffer_id) = target {
+        select_first_hunk_of_file(editor, &hunks, buffer_id, window, cx);
+    }
+}
+
+fn select_first_hunk_of_file(
+    editor: &mut Editor,
+    hunks: &[MultiBufferDiffHunk],
+    buffer_id: BufferId,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    if let Some(hunk) = hunks.iter().find(|hunk| hunk.buffer_id == buffer_id) {
+        let start = hunk.multi_buffer_range().start;
+        editor.change_selections(Some(Autoscroll::fit()), window, cx, |selections| {
+            selections.select_anchor_ranges([start..start]);
+        });
+    }
 }
 
 fn keep_edits_in_selection(
@@ -610,6 +826,10 @@ impl Render for AgentDiffPane {
             .on_action(cx.listener(Self::reject))
             .on_action(cx.listener(Self::reject_all))
             .on_action(cx.listener(Self::keep_all))
+            .on_action(cx.listener(Self::keep_file))
+            .on_action(cx.listener(Self::reject_file))
+            .on_action(cx.listener(Self::review_next_file))
+            .on_action(cx.listener(Self::review_previous_file))
             .bg(cx.theme().colors().editor_background)
             .flex()
             .items_center()
@@ -851,7 +1071,10 @@ pub struct AgentDiffToolbar {
 }
 
 pub enum AgentDiffToolbarItem {
-    Pane(WeakEntity<AgentDiffPane>),
+    Pane {
+        pane: WeakEntity<AgentDiffPane>,
+        _pane_subscription: Subscription,
+    },
     Editor {
         editor: WeakEntity<Editor>,
         state: EditorState,
@@ -873,7 +1096,9 @@ impl AgentDiffToolbar {
         };
 
         match active_item {
-            AgentDiffToolbarItem::Pane(agent_diff) => {
+            AgentDiffToolbarItem::Pane {
+                pane: agent_diff, ..
+            } => {
                 if let Some(agent_diff) = agent_diff.upgrade() {
                     agent_diff.focus_handle(cx).focus(window);
                 }
@@ -914,7 +1139,7 @@ impl AgentDiffToolbar {
 
         match &self.active_item {
             None => ToolbarItemLocation::Hidden,
-            Some(AgentDiffToolbarItem::Pane(_)) => ToolbarItemLocation::PrimaryRight,
+            Some(AgentDiffToolbarItem::Pane { .. }) => ToolbarItemLocation::PrimaryRight,
             Some(AgentDiffToolbarItem::Editor { state, .. }) => match state {
                 EditorState::Generating | EditorState::Reviewing => {
                     ToolbarItemLocation::PrimaryRight
@@ -936,7 +1161,10 @@ impl ToolbarItemView for AgentDiffToolbar {
     ) -> ToolbarItemLocation {
         if let Some(item) = active_pane_item {
             if let Some(pane) = item.act_as::<AgentDiffPane>(cx) {
-                self.active_item = Some(AgentDiffToolbarItem::Pane(pane.downgrade()));
+                self.active_item = Some(AgentDiffToolbarItem::Pane {
+                    pane: pane.downgrade(),
+                    _pane_subscription: cx.observe(&pane, |_, _, cx| cx.notify()),
+                });
                 return self.location(cx);
             }
 
@@ -1111,7 +1339,9 @@ impl Render for AgentDiffToolbar {
                     })
                     .into_any()
             }
-            AgentDiffToolbarItem::Pane(agent_diff) => {
+            AgentDiffToolbarItem::Pane {
+                pane: agent_diff, ..
+            } => {
                 let Some(agent_diff) = agent_diff.upgrade() else {
                     return Empty.into_any();
                 };
@@ -1132,12 +1362,86 @@ impl Render for AgentDiffToolbar {
                 }
 
                 let focus_handle = agent_diff.focus_handle(cx);
+                let progress = agent_diff.read(cx).review_progress(cx);
 
                 h_group_xl()
                     .my_neg_1()
                     .py_1()
                     .items_center()
                     .flex_wrap()
+                    .child(
+                        h_group_sm()
+                            .child(
+                                Label::new(progress.label())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                IconButton::new("previous-file", IconName::ChevronUp)
+                                    .icon_size(IconSize::Small)
+                                    .disabled(progress.file_ix == Some(0))
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Previous File",
+                                        &ReviewPreviousFile,
+                                        &focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&ReviewPreviousFile, window, cx)
+                                    })),
+                            )
+                            .child(
+                                IconButton::new("next-file", IconName::ChevronDown)
+                                    .icon_size(IconSize::Small)
+                                    .disabled(
+                                        progress
+                                            .file_ix
+                                            .is_some_and(|ix| ix + 1 == progress.file_count),
+                                    )
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Next File",
+                                        &ReviewNextFile,
+                                        &focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&ReviewNextFile, window, cx)
+                                    })),
+                            ),
+                    )
+                    .child(
+                        h_group_sm()
+                            .child(
+                                Button::new("reject-file", "Reject File")
+                                    .disabled(progress.file_ix.is_none())
+                                    .key_binding({
+                                        KeyBinding::for_action_in(
+                                            &RejectFile,
+                                            &focus_handle,
+                                            window,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.)))
+                                    })
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&RejectFile, window, cx)
+                                    })),
+                            )
+                            .child(
+                                Button::new("keep-file", "Keep File")
+                                    .disabled(progress.file_ix.is_none())
+                                    .key_binding({
+                                        KeyBinding::for_action_in(
+                                            &KeepFile,
+                                            &focus_handle,
+                                            window,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.)))
+                                    })
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&KeepFile, window, cx)
+                                    })),
+                            ),
+                    )
                     .child(
                         h_group_sm()
                             .child(
@@ -1917,6 +2221,137 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_review_agent_diff_file_by_file(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+            AgentSettings::register(cx);
+            prompt_store::init(cx);
+            thread_store::init(cx);
+            workspace::init_settings(cx);
+            ThemeSettings::register(cx);
+            EditorSettings::register(cx);
+            language_model::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            json!({
+                "file1": "abc\ndef\nghi\njkl\nmno\npqr",
+                "file2": "one\ntwo\nthree",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/test").as_ref()], cx).await;
+
+        let thread_store = cx
+            .update(|cx| {
+                ThreadStore::load(
+                    project.clone(),
+                    cx.new(|_| ToolWorkingSet::default()),
+                    None,
+                    Arc::new(PromptBuilder::new(None).unwrap()),
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        let thread = thread_store.update(cx, |store, cx| store.create_thread(cx));
+        let action_log = thread.read_with(cx, |thread, _| thread.action_log().clone());
+
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let agent_diff = cx.new_window_entity(|window, cx| {
+            AgentDiffPane::new(thread.clone(), workspace.downgrade(), window, cx)
+        });
+
+        let mut buffers = Vec::new();
+        for (path, edits) in [
+            (
+                "test/file1",
+                vec![
+                    (Point::new(1, 0)..Point::new(1, 1), "D"),
+                    (Point::new(4, 0)..Point::new(4, 1), "M"),
+                ],
+            ),
+            (
+                "test/file2",
+                vec![(Point::new(1, 0)..Point::new(1, 1), "T")],
+            ),
+        ] {
+            let buffer_path = project
+                .read_with(cx, |project, cx| project.find_project_path(path, cx))
+                .unwrap();
+            let buffer = project
+                .update(cx, |project, cx| project.open_buffer(buffer_path, cx))
+                .await
+                .unwrap();
+            cx.update(|_, cx| {
+                action_log.update(cx, |log, cx| log.buffer_read(buffer.clone(), cx));
+                buffer.update(cx, |buffer, cx| buffer.edit(edits, None, cx).unwrap());
+                action_log.update(cx, |log, cx| log.buffer_edited(buffer.clone(), cx));
+            });
+            buffers.push(buffer);
+        }
+        cx.run_until_parked();
+
+        let progress = |cx: &mut VisualTestContext| {
+            agent_diff.read_with(cx, |diff, cx| diff.review_progress(cx))
+        };
+        assert_eq!(
+            progress(cx),
+            ReviewProgress {
+                hunk_ix: Some(0),
+                hunk_count: 3,
+                file_ix: Some(0),
+                file_count: 2,
+            }
+        );
+
+        agent_diff.update_in(cx, |diff, window, cx| {
+            diff.review_next_file(&ReviewNextFile, window, cx)
+        });
+        assert_eq!(
+            progress(cx),
+            ReviewProgress {
+                hunk_ix: Some(2),
+                hunk_count: 3,
+                file_ix: Some(1),
+                file_count: 2,
+            }
+        );
+
+        // Keeping a file keeps all of its hunks, and moves on to the file left to review.
+        agent_diff.update_in(cx, |diff, window, cx| {
+            diff.review_previous_file(&ReviewPreviousFile, window, cx);
+            diff.keep_file(&KeepFile, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            progress(cx),
+            ReviewProgress {
+                hunk_ix: Some(0),
+                hunk_count: 1,
+                file_ix: Some(0),
+                file_count: 1,
+            }
+        );
+        buffers[0].read_with(cx, |buffer, _| {
+            assert_eq!(buffer.text(), "abc\nDef\nghi\njkl\nMno\npqr")
+        });
+
+        agent_diff.update_in(cx, |diff, window, cx| {
+            diff.reject_file(&RejectFile, window, cx)
+        });
+        cx.run_until_parked();
+        buffers[1].read_with(cx, |buffer, _| assert_eq!(buffer.text(), "one\ntwo\nthree"));
+        assert_eq!(progress(cx).hunk_count, 0);
+    }
+
     #[gpui::test]
     async fn test_singleton_agent_diff(cx: &mut TestAppContext) {
         cx.update(|cx| {
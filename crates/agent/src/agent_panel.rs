@@ -41,7 +41,7 @@ use theme::ThemeSettings;
 use time::UtcOffset;
 use ui::utils::WithRemSize;
 use ui::{
-    Banner, CheckboxWithLabel, ContextMenu, ElevationIndex, KeyBinding, PopoverMenu,
+    Banner, ButtonLike, CheckboxWithLabel, ContextMenu, ElevationIndex, KeyBinding, PopoverMenu,
     PopoverMenuHandle, ProgressBar, Tab, Tooltip, Vector, VectorName, prelude::*,
 };
 use util::{ResultExt as _, maybe};
@@ -57,6 +57,7 @@ use zed_llm_client::{CompletionIntent, UsageLimit};
 use crate::active_thread::{self, ActiveThread, ActiveThreadEvent};
 use crate::agent_configuration::{AgentConfiguration, AssistantConfigurationEvent};
 use crate::agent_diff::AgentDiff;
+use crate::context_usage::ContextUsageBreakdown;
 use crate::history_store::{HistoryStore, RecentEntry};
 use crate::message_editor::{MessageEditor, MessageEditorEvent};
 use crate::thread::{Thread, ThreadError, ThreadId, ThreadSummary, TokenUsageRatio};
@@ -1507,8 +1508,8 @@ impl Panel for AgentPanel {
         (self.enabled(cx) && AgentSettings::get_global(cx).button).then_some(IconName::ZedAssistant)
     }
 
-    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
-        Some("Agent Panel")
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Agent Panel".into())
     }
 
     fn toggle_action(&self) -> Box<dyn Action> {
@@ -1987,8 +1988,17 @@ impl AgentPanel {
                         Label::new(humanize_token_count(total_token_usage.max))
                             .size(LabelSize::Small)
                             .color(Color::Muted),
-                    )
-                    .into_any();
+                    );
+
+                let message_editor = self.message_editor.clone();
+                let token_count = PopoverMenu::new("context-usage")
+                    .trigger(ButtonLike::new("context-usage-trigger").child(token_count))
+                    .menu(move |_window, cx| {
+                        let message_editor = message_editor.clone();
+                        Some(cx.new(|cx| ContextUsageBreakdown::new(message_editor, cx)))
+                    })
+                    .anchor(Corner::TopRight)
+                    .into_any_element();
 
                 Some(token_count)
             }
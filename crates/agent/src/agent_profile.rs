@@ -45,6 +45,9 @@ impl AgentProfile {
                 .as_ref()
                 .map(|profile| profile.enable_all_context_servers)
                 .unwrap_or_default(),
+            model: base_profile
+                .as_ref()
+                .and_then(|profile| profile.model.clone()),
             context_servers: base_profile
                 .map(|profile| profile.context_servers)
                 .unwrap_or_default(),
@@ -111,6 +114,7 @@ mod tests {
     use gpui::{AppContext, TestAppContext};
     use http_client::FakeHttpClient;
     use project::Project;
+    use semantic_index::SemanticIndexSettings;
     use settings::{Settings, SettingsStore};
     use ui::SharedString;
 
@@ -228,6 +232,7 @@ mod tests {
             Project::init_settings(cx);
             AgentSettings::register(cx);
             language_model::init_settings(cx);
+            SemanticIndexSettings::register(cx);
             ToolRegistry::default_global(cx);
             assistant_tools::init(FakeHttpClient::with_404_response(), cx);
         });
@@ -249,6 +254,7 @@ mod tests {
                     tools: IndexMap::default(),
                     enable_all_context_servers: false,
                     context_servers: IndexMap::from_iter([("mcp".into(), context_server_preset())]),
+                    model: None,
                 },
             );
             AgentSettings::override_global(agent_settings, cx);
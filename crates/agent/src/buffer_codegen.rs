@@ -1,5 +1,6 @@
 use crate::context::ContextLoadResult;
 use crate::inline_prompt_editor::CodegenStatus;
+use crate::multi_file_transform::MultiFileTransform;
 use crate::{context::load_context, context_store::ContextStore};
 use agent_settings::AgentSettings;
 use anyhow::{Context as _, Result};
@@ -145,6 +146,33 @@ impl BufferCodegen {
         cx.notify();
     }
 
+    /// A transformation of the same range that also updates the code depending on it in other
+    /// files, or `None` when the range spans several buffers.
+    pub fn multi_file_transform(
+        &self,
+        model: Arc<dyn LanguageModel>,
+        user_prompt: String,
+        prompt_builder: Arc<PromptBuilder>,
+        cx: &App,
+    ) -> Option<MultiFileTransform> {
+        let multibuffer = self.buffer.read(cx);
+        let snapshot = multibuffer.snapshot(cx);
+        let mut buffer_ranges = snapshot.range_to_buffer_ranges(self.range.clone());
+        let (buffer_snapshot, range, _) =
+            buffer_ranges.pop().filter(|_| buffer_ranges.is_empty())?;
+        let buffer = multibuffer.buffer(buffer_snapshot.remote_id())?;
+        Some(MultiFileTransform {
+            buffer,
+            range: buffer_snapshot.anchor_before(range.start)
+                ..buffer_snapshot.anchor_after(range.end),
+            user_prompt,
+            context_store: self.context_store.clone(),
+            prompt_store: self.prompt_store.clone(),
+            prompt_builder,
+            model,
+        })
+    }
+
     pub fn start(
         &mut self,
         primary_model: Arc<dyn LanguageModel>,
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::{ops::Range, path::Path, sync::Arc};
 
 use assistant_context_editor::AssistantContext;
-use assistant_tool::outline;
+use assistant_tool::{AgentIgnore, outline};
 use collections::{HashMap, HashSet};
 use editor::display_map::CreaseId;
 use editor::{Addon, Editor};
@@ -128,6 +128,22 @@ impl AgentContext {
             AgentContext::Image(context) => AgentContextHandle::Image(context.clone()),
         }
     }
+
+    /// The content this context adds to a request, which is used to estimate its size on its own.
+    pub fn request_content(&self) -> Option<MessageContent> {
+        let text = match self {
+            AgentContext::File(context) => context.to_string(),
+            AgentContext::Directory(context) => context.to_string(),
+            AgentContext::Symbol(context) => context.to_string(),
+            AgentContext::Selection(context) => context.to_string(),
+            AgentContext::FetchedUrl(context) => context.to_string(),
+            AgentContext::Thread(context) => context.to_string(),
+            AgentContext::TextThread(context) => context.to_string(),
+            AgentContext::Rules(context) => context.to_string(),
+            AgentContext::Image(context) => return context.image().map(MessageContent::Image),
+        };
+        Some(MessageContent::Text(text))
+    }
 }
 
 /// ID created at time of context add, for use in ElementId. This is not the stable identity of a
@@ -291,6 +307,22 @@ impl DirectoryContextHandle {
         self,
         project: Entity<Project>,
         cx: &mut App,
+    ) -> Task<Option<(AgentContext, Vec<Entity<Buffer>>)>> {
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
+        let ignore_loaded = agent_ignore.read(cx).loaded();
+        cx.spawn(async move |cx| {
+            ignore_loaded.await;
+            cx.update(|cx| self.load_descendants(project, &agent_ignore, cx))
+                .ok()?
+                .await
+        })
+    }
+
+    fn load_descendants(
+        self,
+        project: Entity<Project>,
+        agent_ignore: &Entity<AgentIgnore>,
+        cx: &mut App,
     ) -> Task<Option<(AgentContext, Vec<Entity<Buffer>>)>> {
         let Some(worktree) = project.read(cx).worktree_for_entry(self.entry_id, cx) else {
             return Task::ready(None);
@@ -307,7 +339,18 @@ impl DirectoryContextHandle {
         let directory_path = entry.path.clone();
         let directory_full_path = worktree_ref.full_path(&directory_path).into();
 
-        let file_paths = collect_files_in_path(worktree_ref, &directory_path);
+        let worktree_id = worktree_ref.id();
+        let agent_ignore = agent_ignore.read(cx);
+        let file_paths = collect_files_in_path(worktree_ref, &directory_path)
+            .into_iter()
+            .filter(|path| {
+                let project_path = ProjectPath {
+                    worktree_id,
+                    path: path.clone(),
+                };
+                !agent_ignore.is_ignored(&project_path, false)
+            })
+            .collect::<Vec<_>>();
         let descendants_future = future::join_all(file_paths.into_iter().map(|path| {
             let worktree_ref = worktree.read(cx);
             let worktree_id = worktree_ref.id();
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use anyhow::{Context as _, Result, anyhow};
 use assistant_context_editor::AssistantContext;
+use assistant_tool::{AGENT_IGNORE_FILE_NAME, AgentIgnore};
 use collections::{HashSet, IndexSet};
 use futures::{self, FutureExt};
 use gpui::{App, Context, Entity, EventEmitter, Image, SharedString, Task, WeakEntity};
@@ -97,19 +98,36 @@ impl ContextStore {
             return Task::ready(Err(anyhow!("failed to read project")));
         };
 
-        if is_image_file(&project, &project_path, cx) {
-            self.add_image_from_path(project_path, remove_if_exists, cx)
-        } else {
-            cx.spawn(async move |this, cx| {
-                let open_buffer_task = project.update(cx, |project, cx| {
-                    project.open_buffer(project_path.clone(), cx)
-                })?;
-                let buffer = open_buffer_task.await?;
-                this.update(cx, |this, cx| {
-                    this.add_file_from_buffer(&project_path, buffer, remove_if_exists, cx)
-                })
+        let is_image = is_image_file(&project, &project_path, cx);
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
+        cx.spawn(async move |this, cx| {
+            agent_ignore
+                .read_with(cx, |agent_ignore, _| agent_ignore.loaded())?
+                .await;
+            if agent_ignore.read_with(cx, |agent_ignore, _| {
+                agent_ignore.is_ignored(&project_path, false)
+            })? {
+                return Err(anyhow!(
+                    "{} is excluded from the agent's context by the project's `{AGENT_IGNORE_FILE_NAME}` file",
+                    project_path.path.display()
+                ));
+            }
+
+            if is_image {
+                return this
+                    .update(cx, |this, cx| {
+                        this.add_image_from_path(project_path, remove_if_exists, cx)
+                    })?
+                    .await;
+            }
+            let open_buffer_task = project.update(cx, |project, cx| {
+                project.open_buffer(project_path.clone(), cx)
+            })?;
+            let buffer = open_buffer_task.await?;
+            this.update(cx, |this, cx| {
+                this.add_file_from_buffer(&project_path, buffer, remove_if_exists, cx)
             })
-        }
+        })
     }
 
     pub fn add_file_from_buffer(
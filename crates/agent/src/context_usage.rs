@@ -0,0 +1,329 @@
+use agent_settings::AgentSettings;
+use assistant_context_editor::humanize_token_count;
+use futures::future;
+use gpui::{
+    DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Stateful, Subscription, Task,
+};
+use language_model::{
+    LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage, MessageContent, Role,
+};
+use ui::{Divider, ProgressBar, Tooltip, prelude::*};
+use util::ResultExt as _;
+
+use crate::message_editor::{MessageEditor, MessageEditorEvent};
+use crate::ui::AddedContext;
+
+struct ContextUsageItem {
+    context: AddedContext,
+    token_count: Option<usize>,
+}
+
+/// A breakdown of what the next request will send to the model, with the share of the context
+/// window each part takes and what sending it would cost.
+pub struct ContextUsageBreakdown {
+    message_editor: Entity<MessageEditor>,
+    items: Vec<ContextUsageItem>,
+    message_token_count: Option<usize>,
+    focus_handle: FocusHandle,
+    _count_tokens_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl ContextUsageBreakdown {
+    pub fn new(message_editor: Entity<MessageEditor>, cx: &mut Context<Self>) -> Self {
+        let subscription = cx.subscribe(&message_editor, |this, _, event, cx| {
+            if let MessageEditorEvent::EstimatedTokenCount = event {
+                this.count_tokens(cx);
+            }
+        });
+        let mut this = Self {
+            message_editor,
+            items: Vec::new(),
+            message_token_count: None,
+            focus_handle: cx.focus_handle(),
+            _count_tokens_task: Task::ready(()),
+            _subscription: subscription,
+        };
+        this.count_tokens(cx);
+        this
+    }
+
+    /// Counts the tokens of each context item on its own, as the count of the whole message
+    /// doesn't tell which of them are worth dropping.
+    fn count_tokens(&mut self, cx: &mut Context<Self>) {
+        let message_editor = self.message_editor.read(cx);
+        let model = message_editor.thread().read(cx).configured_model();
+        let contexts = message_editor
+            .last_loaded_context()
+            .map(|loaded_context| loaded_context.contexts.clone())
+            .unwrap_or_default();
+        let message_text = message_editor.message_text(cx);
+
+        self.items = contexts
+            .iter()
+            .map(|context| ContextUsageItem {
+                context: AddedContext::new_attached(
+                    context,
+                    model.as_ref().map(|model| &model.model),
+                    cx,
+                ),
+                token_count: None,
+            })
+            .collect();
+        self.message_token_count = None;
+
+        let Some(model) = model else {
+            self._count_tokens_task = Task::ready(());
+            return;
+        };
+        let temperature = AgentSettings::temperature_for_model(&model.model, cx);
+        let count_tasks = contexts
+            .iter()
+            .map(|context| context.request_content())
+            .chain([(!message_text.is_empty()).then(|| MessageContent::Text(message_text))])
+            .map(|content| {
+                let request = LanguageModelRequest {
+                    messages: vec![LanguageModelRequestMessage {
+                        role: Role::User,
+                        content: vec![content?],
+                        cache: false,
+                    }],
+                    temperature,
+                    ..Default::default()
+                };
+                Some(model.model.count_tokens(request, cx))
+            })
+            .collect::<Vec<_>>();
+
+        self._count_tokens_task = cx.spawn(async move |this, cx| {
+            let mut token_counts =
+                future::join_all(count_tasks.into_iter().map(|count_task| async move {
+                    match count_task {
+                        Some(count_task) => count_task.await.log_err(),
+                        None => Some(0),
+                    }
+                }))
+                .await;
+            let message_token_count = token_counts.pop().flatten();
+            this.update(cx, |this, cx| {
+                for (item, token_count) in this.items.iter_mut().zip(token_counts) {
+                    item.token_count = token_count;
+                }
+                this.message_token_count = message_token_count;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn render_row(
+        id: impl Into<ElementId>,
+        icon: Icon,
+        name: impl Into<SharedString>,
+        token_count: Option<usize>,
+    ) -> Stateful<Div> {
+        h_flex()
+            .id(id)
+            .w_full()
+            .min_h_6()
+            .gap_1p5()
+            .child(icon.size(IconSize::XSmall).color(Color::Muted))
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .child(Label::new(name).size(LabelSize::Small).truncate()),
+            )
+            .child(
+                Label::new(token_count.map_or_else(|| "…".into(), humanize_token_count))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+    }
+
+    /// The cost of sending the request to the current model and to the default model of every
+    /// other configured provider, for those priced in the `model_pricing` setting.
+    fn estimated_costs(&self, input_token_count: usize, cx: &App) -> Vec<(SharedString, f64)> {
+        let current_model = self
+            .message_editor
+            .read(cx)
+            .thread()
+            .read(cx)
+            .configured_model();
+        let other_models = LanguageModelRegistry::read_global(cx)
+            .providers()
+            .into_iter()
+            .filter(|provider| {
+                provider.is_authenticated(cx)
+                    && current_model
+                        .as_ref()
+                        .is_none_or(|model| model.provider.id() != provider.id())
+            })
+            .filter_map(|provider| Some((provider.name(), provider.default_model(cx)?)));
+
+        current_model
+            .map(|model| (model.provider.name(), model.model))
+            .into_iter()
+            .chain(other_models)
+            .filter_map(|(provider_name, model)| {
+                let cost =
+                    AgentSettings::pricing_for_model(&model, cx)?.input_cost(input_token_count);
+                Some((
+                    format!("{} ({})", model.name().0, provider_name.0).into(),
+                    cost,
+                ))
+            })
+            .collect()
+    }
+}
+
+fn format_cost(cost: f64) -> String {
+    if cost == 0. {
+        "Free".into()
+    } else if cost < 0.0001 {
+        "< $0.0001".into()
+    } else {
+        format!("${cost:.4}")
+    }
+}
+
+impl EventEmitter<DismissEvent> for ContextUsageBreakdown {}
+
+impl Focusable for ContextUsageBreakdown {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ContextUsageBreakdown {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let message_editor = self.message_editor.read(cx);
+        let context_store = message_editor.context_store().clone();
+        let thread = message_editor.thread().read(cx);
+        let history_token_count = thread.total_token_usage().map_or(0, |usage| usage.total);
+        let max_token_count = thread
+            .configured_model()
+            .map_or(0, |model| model.model.max_token_count());
+
+        let total_token_count = history_token_count
+            + self.message_token_count.unwrap_or(0)
+            + self
+                .items
+                .iter()
+                .filter_map(|item| item.token_count)
+                .sum::<usize>();
+        let is_exceeded = max_token_count > 0 && total_token_count > max_token_count;
+        let costs = self.estimated_costs(total_token_count, cx);
+
+        v_flex()
+            .id("context-usage-breakdown")
+            .key_context("ContextUsageBreakdown")
+            .track_focus(&self.focus_handle)
+            .elevation_2(cx)
+            .w(rems(22.))
+            .p_2()
+            .gap_1()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(Label::new("Context Usage").size(LabelSize::Small))
+                    .child(
+                        Label::new(format!(
+                            "{} / {}",
+                            humanize_token_count(total_token_count),
+                            humanize_token_count(max_token_count)
+                        ))
+                        .size(LabelSize::Small)
+                        .color(if is_exceeded {
+                            Color::Error
+                        } else {
+                            Color::Muted
+                        }),
+                    ),
+            )
+            .child(
+                ProgressBar::new(
+                    "context-usage-bar",
+                    total_token_count as f32,
+                    max_token_count.max(1) as f32,
+                    cx,
+                )
+                .when(is_exceeded, |bar| bar.fg_color(cx.theme().status().error)),
+            )
+            .child(Self::render_row(
+                "conversation-history",
+                Icon::new(IconName::MessageBubbles),
+                "Conversation history",
+                Some(history_token_count),
+            ))
+            .children(self.items.iter().enumerate().map(|(ix, item)| {
+                let handle = item.context.handle.clone();
+                let context_store = context_store.clone();
+                Self::render_row(
+                    ("context-item", ix),
+                    item.context.icon(),
+                    item.context.name.clone(),
+                    item.token_count,
+                )
+                .when_some(item.context.tooltip.clone(), |row, tooltip| {
+                    row.tooltip(Tooltip::text(tooltip))
+                })
+                .child(
+                    IconButton::new(("remove-context-item", ix), IconName::Close)
+                        .icon_size(IconSize::XSmall)
+                        .tooltip(Tooltip::text("Remove from Context"))
+                        .on_click(move |_, _, cx| {
+                            context_store.update(cx, |context_store, cx| {
+                                context_store.remove_context(&handle, cx);
+                            });
+                        }),
+                )
+            }))
+            .when(
+                self.message_token_count.is_some_and(|count| count > 0),
+                |this| {
+                    this.child(Self::render_row(
+                        "message",
+                        Icon::new(IconName::Pencil),
+                        "Message",
+                        self.message_token_count,
+                    ))
+                },
+            )
+            .when(!costs.is_empty(), |this| {
+                this.child(Divider::horizontal()).child(
+                    v_flex()
+                        .gap_0p5()
+                        .child(
+                            Label::new("Estimated Input Cost")
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted),
+                        )
+                        .children(costs.into_iter().map(|(model_name, cost)| {
+                            h_flex()
+                                .justify_between()
+                                .gap_2()
+                                .child(Label::new(model_name).size(LabelSize::Small).truncate())
+                                .child(
+                                    Label::new(format_cost(cost))
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                        })),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_cost() {
+        assert_eq!(format_cost(0.), "Free");
+        assert_eq!(format_cost(0.00001), "< $0.0001");
+        assert_eq!(format_cost(0.0123456), "$0.0123");
+        assert_eq!(format_cost(2.5), "$2.5000");
+    }
+}
@@ -47,6 +47,7 @@ use crate::AgentPanel;
 use crate::buffer_codegen::{BufferCodegen, CodegenAlternative, CodegenEvent};
 use crate::context_store::ContextStore;
 use crate::inline_prompt_editor::{CodegenStatus, InlineAssistId, PromptEditor, PromptEditorEvent};
+use crate::rule_prompt;
 use crate::terminal_inline_assistant::TerminalInlineAssistant;
 use crate::thread_store::TextThreadStore;
 use crate::thread_store::ThreadStore;
@@ -249,6 +250,20 @@ impl InlineAssistant {
         let text_thread_store = Some(agent_panel.text_thread_store().downgrade());
         let context_store = agent_panel.inline_assist_context_store().clone();
 
+        if let (None, InlineAssistTarget::Editor(active_editor), Some(prompt_store)) =
+            (&action.prompt, &inline_assist_target, &prompt_store)
+        {
+            if rule_prompt::assist_with_rule(
+                action.rule.as_deref(),
+                active_editor,
+                prompt_store,
+                window,
+                cx,
+            ) {
+                return;
+            }
+        }
+
         let handle_assist =
             |window: &mut Window, cx: &mut Context<Workspace>| match inline_assist_target {
                 InlineAssistTarget::Editor(active_editor) => {
@@ -759,6 +774,9 @@ impl InlineAssistant {
             PromptEditorEvent::StartRequested => {
                 self.start_assist(assist_id, window, cx);
             }
+            PromptEditorEvent::TransformAcrossFilesRequested => {
+                self.transform_across_files(assist_id, window, cx);
+            }
             PromptEditorEvent::StopRequested => {
                 self.stop_assist(assist_id, cx);
             }
@@ -1253,12 +1271,8 @@ impl InlineAssistant {
         let Some(user_prompt) = assist.user_prompt(cx) else {
             return;
         };
-
-        self.prompt_history.retain(|prompt| *prompt != user_prompt);
-        self.prompt_history.push_back(user_prompt.clone());
-        if self.prompt_history.len() > PROMPT_HISTORY_MAX_LEN {
-            self.prompt_history.pop_front();
-        }
+        let codegen = assist.codegen.clone();
+        self.push_prompt_history(&user_prompt);
 
         let Some(ConfiguredModel { model, .. }) =
             LanguageModelRegistry::read_global(cx).inline_assistant_model()
@@ -1266,12 +1280,76 @@ impl InlineAssistant {
             return;
         };
 
-        assist
-            .codegen
+        codegen
             .update(cx, |codegen, cx| codegen.start(model, user_prompt, cx))
             .log_err();
     }
 
+    /// Closes the assist and proposes its transformation, along with the changes it requires in
+    /// other files, in a multi-file diff.
+    fn transform_across_files(
+        &mut self,
+        assist_id: InlineAssistId,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(assist) = self.assists.get(&assist_id) else {
+            return;
+        };
+        let Some(user_prompt) = assist.user_prompt(cx) else {
+            return;
+        };
+        let Some(workspace) = assist.workspace.upgrade() else {
+            return;
+        };
+        let Some(ConfiguredModel { model, .. }) =
+            LanguageModelRegistry::read_global(cx).inline_assistant_model()
+        else {
+            return;
+        };
+        let Some(transform) = assist.codegen.read(cx).multi_file_transform(
+            model,
+            user_prompt.clone(),
+            self.prompt_builder.clone(),
+            cx,
+        ) else {
+            return;
+        };
+
+        self.push_prompt_history(&user_prompt);
+        self.finish_assist(assist_id, true, window, cx);
+
+        struct TransformAcrossFilesStatus;
+        let id = NotificationId::composite::<TransformAcrossFilesStatus>(assist_id.0);
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(Toast::new(id.clone(), "Transforming across files…"), cx);
+        });
+        let transform_task = transform.run(&workspace, window, cx);
+        let workspace = workspace.downgrade();
+        window
+            .spawn(cx, async move |cx| {
+                let result = transform_task.await;
+                workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.dismiss_toast(&id, cx);
+                        if let Err(error) = result {
+                            let error = format!("Inline assistant error: {error}");
+                            workspace.show_toast(Toast::new(id, error), cx);
+                        }
+                    })
+                    .ok();
+            })
+            .detach();
+    }
+
+    fn push_prompt_history(&mut self, user_prompt: &str) {
+        self.prompt_history.retain(|prompt| prompt != user_prompt);
+        self.prompt_history.push_back(user_prompt.to_string());
+        if self.prompt_history.len() > PROMPT_HISTORY_MAX_LEN {
+            self.prompt_history.pop_front();
+        }
+    }
+
     pub fn stop_assist(&mut self, assist_id: InlineAssistId, cx: &mut App) {
         let assist = if let Some(assist) = self.assists.get_mut(&assist_id) {
             assist
@@ -7,7 +7,9 @@ use crate::context_strip::{ContextStrip, ContextStripEvent, SuggestContextKind};
 use crate::message_editor::{extract_message_creases, insert_message_creases};
 use crate::terminal_codegen::TerminalCodegen;
 use crate::thread_store::{TextThreadStore, ThreadStore};
-use crate::{CycleNextInlineAssist, CyclePreviousInlineAssist, ModelUsageContext};
+use crate::{
+    CycleNextInlineAssist, CyclePreviousInlineAssist, ModelUsageContext, TransformAcrossFiles,
+};
 use crate::{RemoveAllContext, ToggleContextPicker};
 use assistant_context_editor::language_model_selector::ToggleModelSelector;
 use client::ErrorExt;
@@ -120,6 +122,7 @@ impl<T: 'static> Render for PromptEditor<T> {
                             .update(cx, |model_selector, cx| model_selector.toggle(window, cx));
                     }))
                     .on_action(cx.listener(Self::confirm))
+                    .on_action(cx.listener(Self::transform_across_files))
                     .on_action(cx.listener(Self::cancel))
                     .on_action(cx.listener(Self::move_up))
                     .on_action(cx.listener(Self::move_down))
@@ -419,6 +422,21 @@ impl<T: 'static> PromptEditor<T> {
         }
     }
 
+    fn transform_across_files(
+        &mut self,
+        _: &TransformAcrossFiles,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let PromptEditorMode::Buffer { codegen, .. } = &self.mode {
+            if !codegen.read(cx).is_insertion
+                && !matches!(self.codegen_status(cx), CodegenStatus::Pending)
+            {
+                cx.emit(PromptEditorEvent::TransformAcrossFilesRequested);
+            }
+        }
+    }
+
     fn move_up(&mut self, _: &MoveUp, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(ix) = self.prompt_history_ix {
             if ix > 0 {
@@ -478,17 +496,36 @@ impl<T: 'static> PromptEditor<T> {
 
         match codegen_status {
             CodegenStatus::Idle => {
-                vec![
-                    Button::new("start", mode.start_label())
-                        .label_size(LabelSize::Small)
-                        .icon(IconName::Return)
-                        .icon_size(IconSize::XSmall)
-                        .icon_color(Color::Muted)
-                        .on_click(
-                            cx.listener(|_, _, _, cx| cx.emit(PromptEditorEvent::StartRequested)),
-                        )
-                        .into_any_element(),
-                ]
+                let start = Button::new("start", mode.start_label())
+                    .label_size(LabelSize::Small)
+                    .icon(IconName::Return)
+                    .icon_size(IconSize::XSmall)
+                    .icon_color(Color::Muted)
+                    .on_click(cx.listener(|_, _, _, cx| cx.emit(PromptEditorEvent::StartRequested)))
+                    .into_any_element();
+
+                match (&self.mode, mode) {
+                    (PromptEditorMode::Buffer { .. }, GenerationMode::Transform) => vec![
+                        IconButton::new("transform-across-files", IconName::FileTree)
+                            .icon_color(Color::Muted)
+                            .shape(IconButtonShape::Square)
+                            .tooltip(|window, cx| {
+                                Tooltip::with_meta(
+                                    "Transform Across Files",
+                                    Some(&TransformAcrossFiles),
+                                    "Also update the code that depends on the selection",
+                                    window,
+                                    cx,
+                                )
+                            })
+                            .on_click(cx.listener(|_, _, _, cx| {
+                                cx.emit(PromptEditorEvent::TransformAcrossFilesRequested);
+                            }))
+                            .into_any_element(),
+                        start,
+                    ],
+                    _ => vec![start],
+                }
             }
             CodegenStatus::Pending => vec![
                 IconButton::new("stop", IconName::Stop)
@@ -828,6 +865,7 @@ pub enum PromptEditorMode {
 
 pub enum PromptEditorEvent {
     StartRequested,
+    TransformAcrossFilesRequested,
     StopRequested,
     ConfirmRequested { execute: bool },
     CancelRequested,
@@ -3,7 +3,9 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use crate::agent_model_selector::AgentModelSelector;
-use crate::context::{AgentContextKey, ContextCreasesAddon, ContextLoadResult, load_context};
+use crate::context::{
+    AgentContextKey, ContextCreasesAddon, ContextLoadResult, LoadedContext, load_context,
+};
 use crate::tool_compatibility::{IncompatibleToolsState, IncompatibleToolsTooltip};
 use crate::ui::{
     MaxModeTooltip,
@@ -1271,6 +1273,20 @@ impl MessageEditor {
         )
     }
 
+    pub fn thread(&self) -> &Entity<Thread> {
+        &self.thread
+    }
+
+    pub fn message_text(&self, cx: &App) -> String {
+        self.editor.read(cx).text(cx)
+    }
+
+    pub fn last_loaded_context(&self) -> Option<&LoadedContext> {
+        self.last_loaded_context
+            .as_ref()
+            .map(|context_load_result| &context_load_result.loaded_context)
+    }
+
     pub fn last_estimated_token_count(&self) -> Option<usize> {
         self.last_estimated_token_count
     }
@@ -0,0 +1,493 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use agent_settings::AgentSettings;
+use anyhow::{Context as _, Result, anyhow};
+use assistant_tool::AgentIgnore;
+use editor::{ProposedChangeLocation, ProposedChangesEditor};
+use futures::StreamExt as _;
+use gpui::{App, AsyncApp, AsyncWindowContext, Entity, Task, Window};
+use language::{Buffer, Point};
+use language_model::{
+    LanguageModel, LanguageModelRequest, LanguageModelRequestMessage, MessageContent, Role,
+};
+use project::search::{SearchQuery, SearchResult};
+use project::{Project, ProjectPath};
+use prompt_store::{
+    MultiFileTransformExcerpt, MultiFileTransformPromptContext, PromptBuilder, PromptStore,
+};
+use text::OffsetRangeExt as _;
+use util::paths::PathMatcher;
+use workspace::Workspace;
+use zed_llm_client::CompletionIntent;
+
+use crate::context::load_context;
+use crate::context_store::ContextStore;
+
+const MAX_SEARCH_TERMS: usize = 5;
+const MAX_EXCERPTS: usize = 40;
+const EXCERPT_CONTEXT_LINES: u32 = 3;
+
+/// A transformation of a selection that also updates the code depending on it in other files,
+/// like the call sites of a function being renamed.
+///
+/// The model first names the identifiers to search the project for, then proposes edits to the
+/// selection and the search results it was shown, which open in a multi-file diff where they
+/// can be reviewed and applied together.
+pub(crate) struct MultiFileTransform {
+    pub buffer: Entity<Buffer>,
+    pub range: Range<text::Anchor>,
+    pub user_prompt: String,
+    pub context_store: Entity<ContextStore>,
+    pub prompt_store: Option<Entity<PromptStore>>,
+    pub prompt_builder: Arc<PromptBuilder>,
+    pub model: Arc<dyn LanguageModel>,
+}
+
+/// A replacement of text in a file, as proposed by the model.
+#[derive(Debug, PartialEq)]
+struct ProposedEdit {
+    path: String,
+    old_text: String,
+    new_text: String,
+}
+
+impl MultiFileTransform {
+    pub fn run(
+        self,
+        workspace: &Entity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        let project = workspace.read(cx).project().clone();
+        let snapshot = self.buffer.read(cx).snapshot();
+        let range = self.range.to_offset(&snapshot);
+        let Some(path) = snapshot.file().map(|file| file.full_path(cx)) else {
+            return Task::ready(Err(anyhow!(
+                "only files of the project can be transformed across files"
+            )));
+        };
+        let language_name = snapshot
+            .language_at(range.start)
+            .filter(|language| !Arc::ptr_eq(language, &language::PLAIN_TEXT))
+            .map(|language| language.name().to_string());
+        let mut prompt_context = MultiFileTransformPromptContext {
+            language_name,
+            path: path.to_string_lossy().into_owned(),
+            rewrite_section: snapshot.text_for_range(range.clone()).collect(),
+            user_prompt: self.user_prompt.clone(),
+            related_excerpts: Vec::new(),
+        };
+        let search_prompt = match self
+            .prompt_builder
+            .generate_multi_file_search_prompt(&prompt_context)
+            .context("generating search prompt")
+        {
+            Ok(prompt) => prompt,
+            Err(error) => return Task::ready(Err(error)),
+        };
+
+        let context = self.context_store.read(cx).context().cloned().collect();
+        let load_context_task = load_context(context, &project, &self.prompt_store, cx);
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
+        let temperature = AgentSettings::temperature_for_model(&self.model, cx);
+        let workspace = workspace.downgrade();
+
+        window.spawn(cx, async move |cx| {
+            let search_request = request(vec![search_prompt.into()], temperature);
+            let search_response = complete(&self.model, search_request, cx).await?;
+            let search_terms = parse_search_terms(&search_response);
+
+            agent_ignore
+                .read_with(cx, |agent_ignore, _| agent_ignore.loaded())?
+                .await;
+            prompt_context.related_excerpts = find_related_excerpts(
+                &search_terms,
+                &self.buffer,
+                range,
+                &project,
+                &agent_ignore,
+                cx,
+            )
+            .await?;
+            let transform_prompt = self
+                .prompt_builder
+                .generate_multi_file_transform_prompt(&prompt_context)
+                .context("generating transformation prompt")?;
+
+            let mut request_message = LanguageModelRequestMessage {
+                role: Role::User,
+                content: Vec::new(),
+                cache: false,
+            };
+            load_context_task
+                .await
+                .loaded_context
+                .add_to_request_message(&mut request_message);
+            request_message.content.push(transform_prompt.into());
+            let transform_request = request(request_message.content, temperature);
+            let transform_response = complete(&self.model, transform_request, cx).await?;
+
+            let edits_by_buffer =
+                resolve_edits(parse_edits(&transform_response), &project, cx).await?;
+            anyhow::ensure!(
+                !edits_by_buffer.is_empty(),
+                "the model didn't propose any edit that applies to the project"
+            );
+
+            let title = format!(
+                "Transform: {}",
+                util::truncate_and_trailoff(&self.user_prompt, 40)
+            );
+            workspace.update_in(cx, |workspace, window, cx| {
+                let locations = edits_by_buffer
+                    .iter()
+                    .map(|(buffer, edits)| ProposedChangeLocation {
+                        buffer: buffer.clone(),
+                        ranges: edits.iter().map(|(range, _)| range.clone()).collect(),
+                    })
+                    .collect();
+                let proposed_changes_editor = cx.new(|cx| {
+                    ProposedChangesEditor::new(title, locations, Some(project.clone()), window, cx)
+                });
+                for (buffer, edits) in edits_by_buffer {
+                    // Branches start out with the text of their base, so the offsets still apply.
+                    if let Some(branch) = proposed_changes_editor
+                        .read(cx)
+                        .branch_buffer_for_base(&buffer)
+                    {
+                        branch.update(cx, |branch, cx| branch.edit(edits, None, cx));
+                    }
+                }
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(
+                        Box::new(proposed_changes_editor),
+                        true,
+                        true,
+                        None,
+                        window,
+                        cx,
+                    );
+                });
+            })
+        })
+    }
+}
+
+fn request(content: Vec<MessageContent>, temperature: Option<f32>) -> LanguageModelRequest {
+    LanguageModelRequest {
+        intent: Some(CompletionIntent::InlineAssist),
+        messages: vec![LanguageModelRequestMessage {
+            role: Role::User,
+            content,
+            cache: false,
+        }],
+        temperature,
+        ..Default::default()
+    }
+}
+
+async fn complete(
+    model: &Arc<dyn LanguageModel>,
+    request: LanguageModelRequest,
+    cx: &AsyncApp,
+) -> Result<String> {
+    let mut stream = model.stream_completion_text(request, cx).await?.stream;
+    let mut response = String::new();
+    while let Some(chunk) = stream.next().await {
+        response.push_str(&chunk?);
+    }
+    Ok(response)
+}
+
+/// Gathers the lines around the occurrences of the search terms, leaving out the selection
+/// itself and the files the agent must not read.
+async fn find_related_excerpts(
+    search_terms: &[String],
+    origin_buffer: &Entity<Buffer>,
+    origin_range: Range<usize>,
+    project: &Entity<Project>,
+    agent_ignore: &Entity<AgentIgnore>,
+    cx: &mut AsyncWindowContext,
+) -> Result<Vec<MultiFileTransformExcerpt>> {
+    let mut ranges_by_buffer: Vec<(Entity<Buffer>, Vec<Range<Point>>)> = Vec::new();
+    let mut match_count = 0;
+    'terms: for search_term in search_terms {
+        let query = SearchQuery::text(
+            search_term,
+            true,
+            true,
+            false,
+            PathMatcher::default(),
+            PathMatcher::default(),
+            false,
+            None,
+        )?;
+        let results = project.update(cx, |project, cx| project.search(query, cx))?;
+        futures::pin_mut!(results);
+        while let Some(SearchResult::Buffer { buffer, ranges }) = results.next().await {
+            let snapshot = buffer.read_with(cx, |buffer, cx| {
+                let file = buffer.file()?;
+                let project_path = ProjectPath {
+                    worktree_id: file.worktree_id(cx),
+                    path: file.path().clone(),
+                };
+                (!agent_ignore.read(cx).is_ignored(&project_path, false)).then(|| buffer.snapshot())
+            })?;
+            let Some(snapshot) = snapshot else {
+                continue;
+            };
+
+            let is_origin = &buffer == origin_buffer;
+            let excerpt_ranges = ranges
+                .into_iter()
+                .map(|range| range.to_offset(&snapshot))
+                .filter(|range| {
+                    !is_origin || range.end <= origin_range.start || range.start >= origin_range.end
+                })
+                .map(|range| {
+                    let range = range.to_point(&snapshot);
+                    let start_row = range.start.row.saturating_sub(EXCERPT_CONTEXT_LINES);
+                    let end_row =
+                        (range.end.row + EXCERPT_CONTEXT_LINES).min(snapshot.max_point().row);
+                    Point::new(start_row, 0)..Point::new(end_row, snapshot.line_len(end_row))
+                })
+                .collect::<Vec<_>>();
+            if excerpt_ranges.is_empty() {
+                continue;
+            }
+
+            let ix = match ranges_by_buffer
+                .iter()
+                .position(|(existing, _)| *existing == buffer)
+            {
+                Some(ix) => ix,
+                None => {
+                    ranges_by_buffer.push((buffer.clone(), Vec::new()));
+                    ranges_by_buffer.len() - 1
+                }
+            };
+            for range in excerpt_ranges {
+                ranges_by_buffer[ix].1.push(range);
+                match_count += 1;
+                if match_count >= MAX_EXCERPTS {
+                    break 'terms;
+                }
+            }
+        }
+    }
+
+    let mut excerpts = Vec::new();
+    for (buffer, mut ranges) in ranges_by_buffer {
+        buffer.read_with(cx, |buffer, cx| {
+            let Some(path) = buffer.file().map(|file| file.full_path(cx)) else {
+                return;
+            };
+            let path = path.to_string_lossy().into_owned();
+            ranges.sort_by_key(|range| range.start);
+            let mut merged_ranges: Vec<Range<Point>> = Vec::new();
+            for range in ranges {
+                match merged_ranges.last_mut() {
+                    Some(last) if last.end.row + 1 >= range.start.row => {
+                        last.end = last.end.max(range.end);
+                    }
+                    _ => merged_ranges.push(range),
+                }
+            }
+            for range in merged_ranges {
+                excerpts.push(MultiFileTransformExcerpt {
+                    path: path.clone(),
+                    text: buffer.text_for_range(range).collect(),
+                });
+            }
+        })?;
+    }
+    Ok(excerpts)
+}
+
+/// Finds the text each edit replaces in its file. Edits that don't apply, because their path
+/// or their text can't be found, or because they overlap another edit, are left out.
+async fn resolve_edits(
+    edits: Vec<ProposedEdit>,
+    project: &Entity<Project>,
+    cx: &mut AsyncWindowContext,
+) -> Result<Vec<(Entity<Buffer>, Vec<(Range<usize>, String)>)>> {
+    let mut edits_by_buffer: Vec<(Entity<Buffer>, Vec<(Range<usize>, String)>)> = Vec::new();
+    for edit in edits {
+        let Some(project_path) =
+            project.read_with(cx, |project, cx| project.find_project_path(&edit.path, cx))?
+        else {
+            log::warn!("proposed edit to {} is outside of the project", edit.path);
+            continue;
+        };
+        let buffer = project
+            .update(cx, |project, cx| project.open_buffer(project_path, cx))?
+            .await?;
+        let text = buffer.read_with(cx, |buffer, _| buffer.text())?;
+        let Some(range) = find_unique(&text, &edit.old_text) else {
+            log::warn!(
+                "couldn't find the text that a proposed edit to {} replaces",
+                edit.path
+            );
+            continue;
+        };
+
+        let ix = match edits_by_buffer
+            .iter()
+            .position(|(existing, _)| *existing == buffer)
+        {
+            Some(ix) => ix,
+            None => {
+                edits_by_buffer.push((buffer, Vec::new()));
+                edits_by_buffer.len() - 1
+            }
+        };
+        let buffer_edits = &mut edits_by_buffer[ix].1;
+        if buffer_edits
+            .iter()
+            .any(|(existing, _)| existing.start < range.end && range.start < existing.end)
+        {
+            log::warn!(
+                "dropping a proposed edit to {} overlapping another one",
+                edit.path
+            );
+            continue;
+        }
+        buffer_edits.push((range, edit.new_text));
+    }
+
+    for (_, edits) in &mut edits_by_buffer {
+        edits.sort_by_key(|(range, _)| range.start);
+    }
+    edits_by_buffer.retain(|(_, edits)| !edits.is_empty());
+    Ok(edits_by_buffer)
+}
+
+fn find_unique(text: &str, old_text: &str) -> Option<Range<usize>> {
+    if old_text.is_empty() {
+        return None;
+    }
+    let mut matches = text.match_indices(old_text);
+    let (start, _) = matches.next()?;
+    if matches.next().is_some() {
+        return None;
+    }
+    Some(start..start + old_text.len())
+}
+
+fn parse_search_terms(response: &str) -> Vec<String> {
+    let mut search_terms = Vec::new();
+    for line in response.lines() {
+        let term = line.trim().trim_matches('`');
+        if term.is_empty()
+            || term.contains(char::is_whitespace)
+            || search_terms.iter().any(|existing| existing == term)
+        {
+            continue;
+        }
+        search_terms.push(term.to_string());
+        if search_terms.len() == MAX_SEARCH_TERMS {
+            break;
+        }
+    }
+    search_terms
+}
+
+fn parse_edits(response: &str) -> Vec<ProposedEdit> {
+    let mut edits = Vec::new();
+    let mut rest = response;
+    while let Some(start) = rest.find("<edit>") {
+        rest = &rest[start + "<edit>".len()..];
+        let Some(end) = rest.find("</edit>") else {
+            break;
+        };
+        let edit = &rest[..end];
+        rest = &rest[end + "</edit>".len()..];
+
+        if let Some(((path, old_text), new_text)) = tag_content(edit, "path")
+            .zip(tag_content(edit, "old_text"))
+            .zip(tag_content(edit, "new_text"))
+        {
+            edits.push(ProposedEdit {
+                path: path.trim().to_string(),
+                old_text: old_text.to_string(),
+                new_text: new_text.to_string(),
+            });
+        }
+    }
+    edits
+}
+
+/// The content of the first `tag` in `text`, without the newlines that follow its opening tag
+/// and precede its closing one.
+fn tag_content<'a>(text: &'a str, tag: &str) -> Option<&'a str> {
+    let opening_tag = format!("<{tag}>");
+    let start = text.find(&opening_tag)? + opening_tag.len();
+    let end = start + text[start..].find(&format!("</{tag}>"))?;
+    let content = &text[start..end];
+    let content = content.strip_prefix('\n').unwrap_or(content);
+    Some(content.strip_suffix('\n').unwrap_or(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_edits() {
+        let response = "\
+<edit>
+<path>project/src/lib.rs</path>
+<old_text>
+fn old_name() {
+</old_text>
+<new_text>
+fn new_name() {
+</new_text>
+</edit>
+<edit>
+<path>project/src/main.rs</path>
+<old_text>
+    old_name();
+</old_text>
+<new_text>
+    new_name();
+</new_text>
+</edit>
+<edit>
+<path>project/src/incomplete.rs</path>
+<old_text>
+old_name
+";
+        assert_eq!(
+            parse_edits(response),
+            [
+                ProposedEdit {
+                    path: "project/src/lib.rs".into(),
+                    old_text: "fn old_name() {".into(),
+                    new_text: "fn new_name() {".into(),
+                },
+                ProposedEdit {
+                    path: "project/src/main.rs".into(),
+                    old_text: "    old_name();".into(),
+                    new_text: "    new_name();".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_search_terms() {
+        assert_eq!(
+            parse_search_terms("`old_name`\n\nOldName\nold_name\nnot an identifier\n"),
+            ["old_name", "OldName"]
+        );
+    }
+
+    #[test]
+    fn test_find_unique() {
+        assert_eq!(find_unique("let a = foo(); bar();", "bar()"), Some(15..20));
+        assert_eq!(find_unique("foo(); foo();", "foo()"), None);
+        assert_eq!(find_unique("foo();", ""), None);
+    }
+}
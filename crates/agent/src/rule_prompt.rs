@@ -0,0 +1,249 @@
+use std::path::PathBuf;
+
+use editor::Editor;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, WeakEntity, prelude::*};
+use prompt_store::{
+    PromptStore, PromptVariable, PromptVariableKind, fill_prompt_variables, prompt_variables,
+};
+use ui::{KeyBinding, Modal, ModalFooter, ModalHeader, Section, prelude::*};
+use ui_input::SingleLineInput;
+use workspace::{ModalView, Workspace};
+use zed_actions::assistant::InlineAssist;
+
+use crate::inline_assistant::InlineAssistant;
+
+/// What a rule's variables are filled with, captured when the assist is requested.
+struct RuleContext {
+    selected_text: String,
+    file_path: Option<PathBuf>,
+}
+
+impl RuleContext {
+    fn fill(&self, body: &str, mut text: impl FnMut(&PromptVariable) -> Option<String>) -> String {
+        fill_prompt_variables(body, |variable| match variable.kind {
+            PromptVariableKind::Selection => Some(self.selected_text.clone()),
+            PromptVariableKind::FilePath => self
+                .file_path
+                .as_ref()
+                .map(|path| path.to_string_lossy().into_owned()),
+            PromptVariableKind::Text => text(variable),
+        })
+    }
+}
+
+/// Starts an inline assist in `editor` with the prompt of a rule: the one titled `rule_title`
+/// or, when text is selected, the default rule for the selection's language. Returns false
+/// when there is no such rule, leaving the assist to start with an empty prompt.
+pub(crate) fn assist_with_rule(
+    rule_title: Option<&str>,
+    editor: &Entity<Editor>,
+    prompt_store: &Entity<PromptStore>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) -> bool {
+    let (rule_context, language_name) = editor.update(cx, |editor, cx| {
+        let selection = editor.selections.newest::<usize>(cx);
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let rule_context = RuleContext {
+            selected_text: snapshot.text_for_range(selection.range()).collect(),
+            file_path: snapshot
+                .file_at(selection.head())
+                .map(|file| file.full_path(cx)),
+        };
+        let language_name = snapshot
+            .language_at(selection.head())
+            .map(|language| language.name());
+        (rule_context, language_name)
+    });
+
+    let prompt_store = prompt_store.read(cx);
+    let rule = match rule_title {
+        Some(title) => prompt_store
+            .id_for_title(title)
+            .and_then(|id| prompt_store.metadata(id)),
+        None if !rule_context.selected_text.is_empty() => language_name
+            .and_then(|name| prompt_store.inline_default_prompt_metadata(name.as_ref())),
+        None => None,
+    };
+    let Some(rule) = rule else {
+        return false;
+    };
+    let title = rule.title.unwrap_or_else(|| "Untitled".into());
+    let load_body = prompt_store.load(rule.id, cx);
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let body = load_body.await?;
+        let text_variables = prompt_variables(&body)
+            .into_iter()
+            .filter(|variable| variable.kind == PromptVariableKind::Text)
+            .collect::<Vec<_>>();
+        workspace.update_in(cx, |workspace, window, cx| {
+            if text_variables.is_empty() {
+                let prompt = rule_context.fill(&body, |_| None);
+                assist_with_prompt(workspace, prompt, window, cx);
+            } else {
+                let workspace_handle = cx.entity().downgrade();
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    RuleVariablesModal::new(
+                        workspace_handle,
+                        title,
+                        body,
+                        rule_context,
+                        text_variables,
+                        window,
+                        cx,
+                    )
+                });
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+    true
+}
+
+fn assist_with_prompt(
+    workspace: &mut Workspace,
+    prompt: String,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    InlineAssistant::inline_assist(
+        workspace,
+        &InlineAssist {
+            prompt: Some(prompt),
+            rule: None,
+        },
+        window,
+        cx,
+    );
+}
+
+/// Asks for the values of a rule's text variables before starting the assist.
+struct RuleVariablesModal {
+    workspace: WeakEntity<Workspace>,
+    title: SharedString,
+    body: String,
+    rule_context: RuleContext,
+    inputs: Vec<(PromptVariable, Entity<SingleLineInput>)>,
+}
+
+impl RuleVariablesModal {
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        title: SharedString,
+        body: String,
+        rule_context: RuleContext,
+        text_variables: Vec<PromptVariable>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let inputs = text_variables
+            .into_iter()
+            .map(|variable| {
+                let label = variable.label();
+                let input = cx.new(|cx| SingleLineInput::new(window, cx, "").label(label));
+                (variable, input)
+            })
+            .collect();
+        Self {
+            workspace,
+            title,
+            body,
+            rule_context,
+            inputs,
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let prompt = self.rule_context.fill(&self.body, |variable| {
+            self.inputs
+                .iter()
+                .find(|(input_variable, _)| input_variable == variable)
+                .map(|(_, input)| input.read(cx).editor().read(cx).text(cx))
+        });
+        cx.emit(DismissEvent);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                assist_with_prompt(workspace, prompt, window, cx)
+            })
+            .ok();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl ModalView for RuleVariablesModal {}
+
+impl Focusable for RuleVariablesModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.inputs
+            .first()
+            .map(|(_, input)| input.focus_handle(cx))
+            .unwrap_or_else(|| cx.focus_handle())
+    }
+}
+
+impl EventEmitter<DismissEvent> for RuleVariablesModal {}
+
+impl Render for RuleVariablesModal {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle(cx);
+
+        div()
+            .elevation_3(cx)
+            .w(rems(34.))
+            .key_context("RuleVariablesModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .on_mouse_down_out(cx.listener(|_, _, _, cx| cx.emit(DismissEvent)))
+            .child(
+                Modal::new("rule-variables", None)
+                    .header(ModalHeader::new().headline(self.title.clone()))
+                    .section(
+                        Section::new().child(
+                            v_flex()
+                                .gap_2()
+                                .children(self.inputs.iter().map(|(_, input)| input.clone())),
+                        ),
+                    )
+                    .footer(
+                        ModalFooter::new().end_slot(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Button::new("cancel", "Cancel")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Cancel,
+                                                &focus_handle,
+                                                window,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.cancel(&menu::Cancel, window, cx)
+                                        })),
+                                )
+                                .child(
+                                    Button::new("assist", "Assist")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Confirm,
+                                                &focus_handle,
+                                                window,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.confirm(&menu::Confirm, window, cx)
+                                        })),
+                                ),
+                        ),
+                    ),
+            )
+    }
+}
@@ -170,6 +170,9 @@ impl TerminalInlineAssistant {
             PromptEditorEvent::DismissRequested => {
                 self.dismiss_assist(assist_id, window, cx);
             }
+            PromptEditorEvent::TransformAcrossFilesRequested => {
+                // Terminal commands don't span files
+            }
             PromptEditorEvent::Resized { height_in_lines } => {
                 self.insert_prompt_editor_into_terminal(assist_id, *height_in_lines, window, cx);
             }
@@ -20,11 +20,11 @@ use gpui::{
 };
 use language_model::{
     ConfiguredModel, LanguageModel, LanguageModelCompletionError, LanguageModelCompletionEvent,
-    LanguageModelId, LanguageModelKnownError, LanguageModelRegistry, LanguageModelRequest,
-    LanguageModelRequestMessage, LanguageModelRequestTool, LanguageModelToolResult,
-    LanguageModelToolResultContent, LanguageModelToolUseId, MessageContent,
-    ModelRequestLimitReachedError, PaymentRequiredError, RequestUsage, Role, SelectedModel,
-    StopReason, TokenUsage,
+    LanguageModelId, LanguageModelKnownError, LanguageModelProviderId, LanguageModelRegistry,
+    LanguageModelRequest, LanguageModelRequestMessage, LanguageModelRequestTool,
+    LanguageModelToolResult, LanguageModelToolResultContent, LanguageModelToolUseId,
+    MessageContent, ModelRequestLimitReachedError, PaymentRequiredError, RequestUsage, Role,
+    SelectedModel, StopReason, TokenUsage,
 };
 use postage::stream::Stream as _;
 use project::Project;
@@ -408,8 +408,9 @@ impl Thread {
         cx: &mut Context<Self>,
     ) -> Self {
         let (detailed_summary_tx, detailed_summary_rx) = postage::watch::channel();
-        let configured_model = LanguageModelRegistry::read_global(cx).default_model();
         let profile_id = AgentSettings::get_global(cx).default_profile.clone();
+        let configured_model = Self::profile_model(&profile_id, cx)
+            .or_else(|| LanguageModelRegistry::read_global(cx).default_model());
 
         Self {
             id: ThreadId::new(),
@@ -599,11 +600,30 @@ impl Thread {
 
     pub fn set_profile(&mut self, id: AgentProfileId, cx: &mut Context<Self>) {
         if &id != self.profile.id() {
+            if let Some(model) = Self::profile_model(&id, cx) {
+                self.set_configured_model(Some(model), cx);
+            }
             self.profile = AgentProfile::new(id, self.tools.clone());
             cx.emit(ThreadEvent::ProfileChanged);
         }
     }
 
+    /// The model selected by the settings of the profile, if it's available.
+    fn profile_model(profile_id: &AgentProfileId, cx: &mut App) -> Option<ConfiguredModel> {
+        let selection = AgentSettings::get_global(cx)
+            .profiles
+            .get(profile_id)?
+            .model
+            .clone()?;
+        let selected_model = SelectedModel {
+            provider: LanguageModelProviderId::from(selection.provider.0),
+            model: LanguageModelId::from(selection.model),
+        };
+        LanguageModelRegistry::global(cx).update(cx, |registry, cx| {
+            registry.select_model(&selected_model, cx)
+        })
+    }
+
     pub fn is_empty(&self) -> bool {
         self.messages.is_empty()
     }
@@ -5,6 +5,8 @@ use gpui::SharedString;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::LanguageModelSelection;
+
 pub mod builtin_profiles {
     use super::AgentProfileId;
 
@@ -46,6 +48,8 @@ pub struct AgentProfileSettings {
     pub tools: IndexMap<Arc<str>, bool>,
     pub enable_all_context_servers: bool,
     pub context_servers: IndexMap<Arc<str>, ContextServerPreset>,
+    /// The model threads switch to when they start using the profile.
+    pub model: Option<LanguageModelSelection>,
 }
 
 #[derive(Debug, Clone, Default)]
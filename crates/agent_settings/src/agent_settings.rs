@@ -109,6 +109,7 @@ pub struct AgentSettings {
     pub stream_edits: bool,
     pub single_file_review: bool,
     pub model_parameters: Vec<LanguageModelParameters>,
+    pub model_pricing: Vec<LanguageModelPricing>,
     pub preferred_completion_mode: CompletionMode,
     pub enable_feedback: bool,
 }
@@ -123,6 +124,17 @@ impl AgentSettings {
             .and_then(|m| m.temperature)
     }
 
+    pub fn pricing_for_model<'a>(
+        model: &Arc<dyn LanguageModel>,
+        cx: &'a App,
+    ) -> Option<&'a LanguageModelPricing> {
+        let settings = Self::get_global(cx);
+        settings
+            .model_pricing
+            .iter()
+            .rfind(|pricing| pricing.matches(model))
+    }
+
     pub fn set_inline_assistant_model(&mut self, provider: String, model: String) {
         self.inline_assistant_model = Some(LanguageModelSelection {
             provider: provider.into(),
@@ -154,18 +166,45 @@ pub struct LanguageModelParameters {
 
 impl LanguageModelParameters {
     pub fn matches(&self, model: &Arc<dyn LanguageModel>) -> bool {
-        if let Some(provider) = &self.provider {
-            if provider.0 != model.provider_id().0 {
-                return false;
-            }
+        matches_model(self.provider.as_ref(), self.model.as_ref(), model)
+    }
+}
+
+/// The list prices of a model, in US dollars per million tokens.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct LanguageModelPricing {
+    pub provider: Option<LanguageModelProviderSetting>,
+    pub model: Option<SharedString>,
+    pub input: f64,
+    pub output: f64,
+}
+
+impl LanguageModelPricing {
+    pub fn matches(&self, model: &Arc<dyn LanguageModel>) -> bool {
+        matches_model(self.provider.as_ref(), self.model.as_ref(), model)
+    }
+
+    pub fn input_cost(&self, input_tokens: usize) -> f64 {
+        self.input * input_tokens as f64 / 1_000_000.
+    }
+}
+
+fn matches_model(
+    provider: Option<&LanguageModelProviderSetting>,
+    setting_model: Option<&SharedString>,
+    model: &Arc<dyn LanguageModel>,
+) -> bool {
+    if let Some(provider) = provider {
+        if provider.0 != model.provider_id().0 {
+            return false;
         }
-        if let Some(setting_model) = &self.model {
-            if *setting_model != model.id().0 {
-                return false;
-            }
+    }
+    if let Some(setting_model) = setting_model {
+        if *setting_model != model.id().0 {
+            return false;
         }
-        true
     }
+    true
 }
 
 /// Agent panel settings
@@ -276,6 +315,7 @@ impl AgentSettingsContent {
                     stream_edits: None,
                     single_file_review: None,
                     model_parameters: Vec::new(),
+                    model_pricing: Vec::new(),
                     preferred_completion_mode: None,
                     enable_feedback: None,
                     play_sound_when_agent_done: None,
@@ -309,6 +349,7 @@ impl AgentSettingsContent {
                 stream_edits: None,
                 single_file_review: None,
                 model_parameters: Vec::new(),
+                model_pricing: Vec::new(),
                 preferred_completion_mode: None,
                 enable_feedback: None,
                 play_sound_when_agent_done: None,
@@ -557,6 +598,7 @@ impl AgentSettingsContent {
                             )
                         })
                         .collect(),
+                    model: profile_settings.model,
                 },
             );
 
@@ -596,6 +638,7 @@ impl Default for VersionedAgentSettingsContent {
             stream_edits: None,
             single_file_review: None,
             model_parameters: Vec::new(),
+            model_pricing: Vec::new(),
             preferred_completion_mode: None,
             enable_feedback: None,
             play_sound_when_agent_done: None,
@@ -676,6 +719,13 @@ pub struct AgentSettingsContentV2 {
     /// Default: []
     #[serde(default)]
     model_parameters: Vec<LanguageModelParameters>,
+    /// The prices of language models, in US dollars per million tokens, used
+    /// to estimate what a request will cost. As with `model_parameters`, the
+    /// last entry that matches the model's provider and name is used.
+    ///
+    /// Default: the list prices of the Anthropic and OpenAI models
+    #[serde(default)]
+    model_pricing: Vec<LanguageModelPricing>,
     /// What completion mode to enable for new threads
     ///
     /// Default: normal
@@ -727,6 +777,7 @@ impl JsonSchema for LanguageModelProviderSetting {
                 "lmstudio".into(),
                 "ollama".into(),
                 "openai".into(),
+                "openai_compatible".into(),
                 "zed.dev".into(),
                 "copilot_chat".into(),
                 "deepseek".into(),
@@ -769,6 +820,9 @@ pub struct AgentProfileContent {
     pub enable_all_context_servers: Option<bool>,
     #[serde(default)]
     pub context_servers: IndexMap<Arc<str>, ContextServerPresetContent>,
+    /// The model threads switch to when they start using the profile, such as a local model
+    /// for a profile that shouldn't send code to a hosted one.
+    pub model: Option<LanguageModelSelection>,
 }
 
 #[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -903,6 +957,9 @@ impl Settings for AgentSettings {
             settings
                 .model_parameters
                 .extend_from_slice(&value.model_parameters);
+            settings
+                .model_pricing
+                .extend_from_slice(&value.model_pricing);
 
             if let Some(profiles) = value.profiles {
                 settings
@@ -928,6 +985,7 @@ impl Settings for AgentSettings {
                                         )
                                     })
                                     .collect(),
+                                model: profile.model,
                             },
                         )
                     }));
@@ -1035,6 +1093,7 @@ mod tests {
                             single_file_review: None,
                             enable_feedback: None,
                             model_parameters: Vec::new(),
+                            model_pricing: Vec::new(),
                             preferred_completion_mode: None,
                         })),
                     }
@@ -1118,4 +1177,58 @@ mod tests {
         let agent_settings: AgentSettingsTest = serde_json::from_value(settings).unwrap();
         assert!(agent_settings.agent.is_none());
     }
+
+    #[gpui::test]
+    async fn test_model_pricing_from_settings(cx: &mut TestAppContext) {
+        let model: Arc<dyn LanguageModel> =
+            Arc::new(language_model::fake_provider::FakeLanguageModel::default());
+
+        cx.update(|cx| {
+            let mut test_settings = settings::SettingsStore::test(cx);
+            test_settings
+                .set_user_settings(
+                    r#"{
+                        "agent": {
+                            "version": "2",
+                            "model_pricing": [
+                                { "provider": "fake", "input": 3.0, "output": 15.0 },
+                                { "provider": "fake", "model": "fake", "input": 1.0, "output": 5.0 },
+                                { "provider": "other", "input": 100.0, "output": 100.0 }
+                            ]
+                        }
+                    }"#,
+                    cx,
+                )
+                .unwrap();
+            cx.set_global(test_settings);
+            AgentSettings::register(cx);
+        });
+
+        cx.update(|cx| {
+            let pricing = AgentSettings::pricing_for_model(&model, cx).unwrap();
+            assert_eq!(pricing.input, 1.0);
+            assert_eq!(pricing.output, 5.0);
+            assert_eq!(pricing.input_cost(2_000_000), 2.0);
+        });
+
+        cx.update_global::<SettingsStore, _>(|settings_store, cx| {
+            settings_store
+                .set_user_settings(
+                    r#"{
+                        "agent": {
+                            "version": "2",
+                            "model_pricing": [
+                                { "provider": "other", "input": 100.0, "output": 100.0 }
+                            ]
+                        }
+                    }"#,
+                    cx,
+                )
+                .unwrap();
+        });
+
+        cx.update(|cx| {
+            assert_eq!(AgentSettings::pricing_for_model(&model, cx), None);
+        });
+    }
 }
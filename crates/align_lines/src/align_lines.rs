@@ -0,0 +1,329 @@
+use editor::{Editor, EditorEvent};
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, SharedString,
+    Subscription, impl_actions, prelude::*,
+};
+use language::TransactionId;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use ui::{IconButtonShape, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::ModalView;
+
+/// Pads the selected lines so that a delimiter lines up across them, like the `=` of
+/// assignments or the commas of a table. Without a delimiter, this opens a modal to type it in,
+/// which previews the alignment while it's typed.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AlignLines {
+    #[serde(default)]
+    pub delimiter: Option<String>,
+    /// Whether the delimiter is a regular expression rather than the text to align on.
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl_actions!(align_lines, [AlignLines]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(AlignLinesModal::register).detach();
+}
+
+fn delimiter_regex(delimiter: &str, is_regex: bool) -> Result<Regex, regex::Error> {
+    if is_regex {
+        Regex::new(delimiter)
+    } else {
+        Regex::new(&regex::escape(delimiter))
+    }
+}
+
+pub struct AlignLinesModal {
+    delimiter_editor: Entity<Editor>,
+    active_editor: Entity<Editor>,
+    is_regex: bool,
+    /// The alignment previewed in the active editor, which is reverted unless it's confirmed.
+    preview_transaction: Option<TransactionId>,
+    error: Option<SharedString>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ModalView for AlignLinesModal {}
+
+impl Focusable for AlignLinesModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.delimiter_editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for AlignLinesModal {}
+
+impl AlignLinesModal {
+    fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+        let handle = cx.entity().downgrade();
+        editor
+            .register_action(move |action: &AlignLines, window, cx| {
+                let Some(editor) = handle.upgrade() else {
+                    return;
+                };
+                if let Some(delimiter) = &action.delimiter {
+                    let Some(delimiter) = delimiter_regex(delimiter, action.regex).log_err() else {
+                        return;
+                    };
+                    editor.update(cx, |editor, cx| {
+                        editor.align_lines(&delimiter, window, cx);
+                    });
+                    return;
+                }
+                let Some(workspace) = editor.read(cx).workspace() else {
+                    return;
+                };
+                let is_regex = action.regex;
+                workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, move |window, cx| {
+                        AlignLinesModal::new(editor, is_regex, window, cx)
+                    });
+                })
+            })
+            .detach();
+    }
+
+    pub fn new(
+        active_editor: Entity<Editor>,
+        is_regex: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delimiter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Delimiter, like = or ,", cx);
+            editor
+        });
+        let delimiter_editor_change =
+            cx.subscribe_in(&delimiter_editor, window, Self::on_delimiter_editor_event);
+        Self {
+            delimiter_editor,
+            active_editor,
+            is_regex,
+            preview_transaction: None,
+            error: None,
+            _subscriptions: vec![
+                delimiter_editor_change,
+                cx.on_release_in(window, Self::release),
+            ],
+        }
+    }
+
+    fn release(&mut self, _: &mut Window, cx: &mut App) {
+        self.revert_preview(cx);
+    }
+
+    fn on_delimiter_editor_event(
+        &mut self,
+        _: &Entity<Editor>,
+        event: &EditorEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            EditorEvent::Blurred => {
+                self.revert_preview(cx);
+                cx.emit(DismissEvent)
+            }
+            EditorEvent::BufferEdited { .. } => self.update_preview(window, cx),
+            _ => {}
+        }
+    }
+
+    fn revert_preview(&mut self, cx: &mut App) {
+        let Some(transaction_id) = self.preview_transaction.take() else {
+            return;
+        };
+        self.active_editor.update(cx, |editor, cx| {
+            editor.buffer().update(cx, |buffer, cx| {
+                buffer.undo_transaction(transaction_id, cx);
+                buffer.forget_transaction(transaction_id, cx);
+            });
+        });
+    }
+
+    fn update_preview(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.revert_preview(cx);
+        self.error = None;
+        let delimiter = self.delimiter_editor.read(cx).text(cx);
+        if !delimiter.is_empty() {
+            match delimiter_regex(&delimiter, self.is_regex) {
+                Ok(delimiter) => {
+                    self.preview_transaction = self.active_editor.update(cx, |editor, cx| {
+                        // The preview is undone on its own, so it mustn't be grouped with the
+                        // edits around it.
+                        editor
+                            .buffer()
+                            .update(cx, |buffer, cx| buffer.finalize_last_transaction(cx));
+                        let transaction_id = editor.align_lines(&delimiter, window, cx);
+                        editor
+                            .buffer()
+                            .update(cx, |buffer, cx| buffer.finalize_last_transaction(cx));
+                        transaction_id
+                    });
+                }
+                Err(_) => self.error = Some("Invalid regular expression".into()),
+            }
+        }
+        cx.notify();
+    }
+
+    fn toggle_regex(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.is_regex = !self.is_regex;
+        self.update_preview(window, cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        self.revert_preview(cx);
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.preview_transaction.take();
+        self.active_editor.focus_handle(cx).focus(window);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for AlignLinesModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let delimiter = self.delimiter_editor.read(cx).text(cx);
+        let (help_text, color) = if let Some(error) = &self.error {
+            (error.clone(), Color::Error)
+        } else if delimiter.is_empty() {
+            (
+                "Type the delimiter to align the selected lines on".into(),
+                Color::Muted,
+            )
+        } else if self.preview_transaction.is_some() {
+            (
+                format!("Align the lines on {delimiter}").into(),
+                Color::Muted,
+            )
+        } else {
+            (
+                format!("Nothing to align on {delimiter}").into(),
+                Color::Muted,
+            )
+        };
+
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("AlignLines")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                h_flex()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .child(div().flex_1().child(self.delimiter_editor.clone()))
+                    .child(
+                        IconButton::new("align-lines-regex", IconName::Regex)
+                            .shape(IconButtonShape::Square)
+                            .toggle_state(self.is_regex)
+                            .tooltip(Tooltip::text("Use Regular Expression"))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.toggle_regex(window, cx)),
+                            ),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .child(Label::new(help_text).color(color)),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::actions::SelectAll;
+    use gpui::{TestAppContext, VisualTestContext};
+    use indoc::indoc;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use util::path;
+    use workspace::{AppState, Workspace};
+
+    #[gpui::test]
+    async fn test_align_lines_preview(cx: &mut TestAppContext) {
+        init_test(cx);
+        let text = indoc! {"
+            a = 1
+            bbb = 2
+        "};
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "a.txt": text }))
+            .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, "a.txt"), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        editor.update_in(cx, |editor, window, cx| {
+            editor.select_all(&SelectAll, window, cx)
+        });
+
+        open_modal(&workspace, cx);
+        cx.simulate_input("=");
+        let aligned = indoc! {"
+            a   = 1
+            bbb = 2
+        "};
+        assert_eq!(editor.update(cx, |editor, cx| editor.text(cx)), aligned);
+        cx.dispatch_action(menu::Cancel);
+        assert_eq!(
+            editor.update(cx, |editor, cx| editor.text(cx)),
+            text,
+            "Cancelling should revert the preview"
+        );
+
+        open_modal(&workspace, cx);
+        cx.simulate_input("=");
+        cx.dispatch_action(menu::Confirm);
+        assert_eq!(editor.update(cx, |editor, cx| editor.text(cx)), aligned);
+    }
+
+    fn open_modal(workspace: &Entity<Workspace>, cx: &mut VisualTestContext) {
+        cx.dispatch_action(AlignLines::default());
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<AlignLinesModal>(cx)
+                .expect("the align lines modal should be open");
+        });
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> std::sync::Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            language::init(cx);
+            crate::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            state
+        })
+    }
+}
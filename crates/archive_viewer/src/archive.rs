@@ -0,0 +1,300 @@
+use std::{
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result};
+use async_compression::futures::bufread::GzipDecoder;
+use async_tar::Archive;
+use async_zip::base::read::seek::ZipFileReader;
+use futures::{AsyncRead, AsyncReadExt as _, StreamExt as _, io::Cursor};
+
+/// The archive formats that can be browsed without extracting them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    /// Zip-based packages like jars, wheels and VSIX files are browsed as zip archives, and
+    /// published crates as gzipped tarballs.
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".crate") {
+            return Some(Self::TarGz);
+        }
+        match name.rsplit_once('.')?.1 {
+            "tar" => Some(Self::Tar),
+            "zip" | "jar" | "war" | "ear" | "aar" | "whl" | "nupkg" | "vsix" | "apk" => {
+                Some(Self::Zip)
+            }
+            _ => None,
+        }
+    }
+
+    /// The name of the folder the archive is extracted into, next to the archive.
+    pub fn extraction_dir_name(&self, archive_path: &Path) -> Option<String> {
+        let name = archive_path.file_name()?.to_str()?;
+        let lowercase_name = name.to_ascii_lowercase();
+        let suffix_len = match self {
+            Self::TarGz if lowercase_name.ends_with(".tar.gz") => ".tar.gz".len(),
+            _ => name.rsplit_once('.')?.1.len() + 1,
+        };
+        Some(name[..name.len() - suffix_len].to_string())
+    }
+}
+
+/// A file stored in an archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    pub path: Arc<Path>,
+    pub size: u64,
+}
+
+/// Lists the files of the archive, sorted by path. Directories are only listed through the
+/// files they contain.
+pub async fn list_entries(kind: ArchiveKind, bytes: Arc<[u8]>) -> Result<Vec<ArchiveEntry>> {
+    let mut entries = Vec::new();
+    match kind {
+        ArchiveKind::Zip => {
+            let reader = ZipFileReader::new(Cursor::new(bytes))
+                .await
+                .context("reading the zip archive")?;
+            for entry in reader.file().entries() {
+                if entry.dir().context("reading zip entry metadata")? {
+                    continue;
+                }
+                let path = entry
+                    .filename()
+                    .as_str()
+                    .context("reading zip entry file name")?;
+                entries.push(ArchiveEntry {
+                    path: normalize_entry_path(path).into(),
+                    size: entry.uncompressed_size(),
+                });
+            }
+        }
+        ArchiveKind::Tar | ArchiveKind::TarGz => {
+            let mut tar_entries = Archive::new(tar_reader(kind, bytes))
+                .entries()
+                .context("reading the tar archive")?;
+            while let Some(entry) = tar_entries.next().await {
+                let entry = entry.context("reading tar entry")?;
+                if !entry.header().entry_type().is_file() {
+                    continue;
+                }
+                let path = entry.path().context("reading tar entry path")?;
+                entries.push(ArchiveEntry {
+                    path: normalize_entry_path(&path.to_string_lossy()).into(),
+                    size: entry.header().size().unwrap_or_default(),
+                });
+            }
+        }
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries.dedup_by(|a, b| a.path == b.path);
+    Ok(entries)
+}
+
+/// Reads the contents of one file of the archive.
+pub async fn read_entry(kind: ArchiveKind, bytes: Arc<[u8]>, path: &Path) -> Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    match kind {
+        ArchiveKind::Zip => {
+            let mut reader = ZipFileReader::new(Cursor::new(bytes))
+                .await
+                .context("reading the zip archive")?;
+            let index = reader
+                .file()
+                .entries()
+                .iter()
+                .position(|entry| {
+                    entry
+                        .filename()
+                        .as_str()
+                        .ok()
+                        .map(normalize_entry_path)
+                        .as_deref()
+                        == Some(path)
+                })
+                .with_context(|| format!("{path:?} is not in the archive"))?;
+            reader
+                .reader_with_entry(index)
+                .await?
+                .read_to_end_checked(&mut contents)
+                .await
+                .with_context(|| format!("reading {path:?} from the archive"))?;
+        }
+        ArchiveKind::Tar | ArchiveKind::TarGz => {
+            let mut tar_entries = Archive::new(tar_reader(kind, bytes))
+                .entries()
+                .context("reading the tar archive")?;
+            while let Some(entry) = tar_entries.next().await {
+                let mut entry = entry.context("reading tar entry")?;
+                let entry_path = normalize_entry_path(
+                    &entry
+                        .path()
+                        .context("reading tar entry path")?
+                        .to_string_lossy(),
+                );
+                if entry_path == path {
+                    entry
+                        .read_to_end(&mut contents)
+                        .await
+                        .with_context(|| format!("reading {path:?} from the archive"))?;
+                    return Ok(contents);
+                }
+            }
+            anyhow::bail!("{path:?} is not in the archive");
+        }
+    }
+    Ok(contents)
+}
+
+/// Extracts all the files of the archive into the destination directory.
+pub async fn extract(
+    kind: ArchiveKind,
+    bytes: Arc<[u8]>,
+    destination: &Path,
+    fs: &dyn fs::Fs,
+) -> Result<()> {
+    match kind {
+        ArchiveKind::Zip => util::archive::extract_zip(destination, Cursor::new(bytes)).await,
+        ArchiveKind::Tar | ArchiveKind::TarGz => {
+            let mut body = tar_reader(kind, bytes);
+            fs.extract_tar_file(destination, Archive::new(body.as_mut()))
+                .await
+        }
+    }
+}
+
+fn tar_reader(kind: ArchiveKind, bytes: Arc<[u8]>) -> Pin<Box<dyn AsyncRead + Send>> {
+    if kind == ArchiveKind::TarGz {
+        Box::pin(GzipDecoder::new(Cursor::new(bytes)))
+    } else {
+        Box::pin(Cursor::new(bytes))
+    }
+}
+
+/// Archives created on Windows may use backslashes, and tarballs often prefix their paths
+/// with `./`.
+fn normalize_entry_path(path: &str) -> PathBuf {
+    path.split(['/', '\\'])
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use async_zip::{ZipEntryBuilder, base::write::ZipFileWriter};
+
+    use super::*;
+
+    async fn build_zip(files: &[(&str, &str)]) -> Arc<[u8]> {
+        let mut out = Vec::new();
+        let mut writer = ZipFileWriter::new(&mut out);
+        for (name, contents) in files {
+            let builder = ZipEntryBuilder::new((*name).into(), async_zip::Compression::Deflate);
+            writer
+                .write_entry_whole(builder, contents.as_bytes())
+                .await
+                .unwrap();
+        }
+        writer.close().await.unwrap();
+        out.into()
+    }
+
+    async fn build_tar(files: &[(&str, &str)]) -> Arc<[u8]> {
+        let mut builder = async_tar::Builder::new(Vec::new());
+        for (name, contents) in files {
+            let mut header = async_tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, contents.as_bytes())
+                .await
+                .unwrap();
+        }
+        builder.into_inner().await.unwrap().into()
+    }
+
+    #[test]
+    fn test_archive_kind_for_path() {
+        let kind = |path: &str| ArchiveKind::for_path(Path::new(path));
+        assert_eq!(kind("lib/app.JAR"), Some(ArchiveKind::Zip));
+        assert_eq!(
+            kind("dist/pkg-1.0-py3-none-any.whl"),
+            Some(ArchiveKind::Zip)
+        );
+        assert_eq!(kind("src.tar"), Some(ArchiveKind::Tar));
+        assert_eq!(kind("src-1.2.tar.gz"), Some(ArchiveKind::TarGz));
+        assert_eq!(kind("serde-1.0.0.crate"), Some(ArchiveKind::TarGz));
+        assert_eq!(kind("notes.txt"), None);
+        assert_eq!(kind("Makefile"), None);
+
+        let dir_name = |path: &str| {
+            let path = Path::new(path);
+            ArchiveKind::for_path(path)?.extraction_dir_name(path)
+        };
+        assert_eq!(dir_name("src-1.2.tar.gz").as_deref(), Some("src-1.2"));
+        assert_eq!(dir_name("app.jar").as_deref(), Some("app"));
+        assert_eq!(dir_name("pkg.tgz").as_deref(), Some("pkg"));
+    }
+
+    #[gpui::test]
+    async fn test_zip_entries() {
+        let bytes = build_zip(&[
+            ("src/main.rs", "fn main() {}\n"),
+            ("META-INF\\MANIFEST.MF", "Manifest-Version: 1.0\n"),
+        ])
+        .await;
+
+        let entries = list_entries(ArchiveKind::Zip, bytes.clone()).await.unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                ArchiveEntry {
+                    path: Path::new("META-INF/MANIFEST.MF").into(),
+                    size: 22,
+                },
+                ArchiveEntry {
+                    path: Path::new("src/main.rs").into(),
+                    size: 13,
+                },
+            ]
+        );
+
+        let contents = read_entry(ArchiveKind::Zip, bytes, Path::new("src/main.rs"))
+            .await
+            .unwrap();
+        assert_eq!(contents, b"fn main() {}\n");
+    }
+
+    #[gpui::test]
+    async fn test_tar_entries() {
+        let bytes = build_tar(&[("./README.md", "# Hello\n"), ("src/lib.rs", "")]).await;
+
+        let entries = list_entries(ArchiveKind::Tar, bytes.clone()).await.unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.path.as_ref())
+                .collect::<Vec<_>>(),
+            vec![Path::new("README.md"), Path::new("src/lib.rs")]
+        );
+
+        let contents = read_entry(ArchiveKind::Tar, bytes.clone(), Path::new("README.md"))
+            .await
+            .unwrap();
+        assert_eq!(contents, b"# Hello\n");
+        assert!(
+            read_entry(ArchiveKind::Tar, bytes, Path::new("missing.rs"))
+                .await
+                .is_err()
+        );
+    }
+}
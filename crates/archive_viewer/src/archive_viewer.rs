@@ -0,0 +1,687 @@
+mod archive;
+
+use std::{
+    ffi::OsStr,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashSet;
+use editor::Editor;
+use file_icons::FileIcons;
+use gpui::{
+    AnyElement, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, Render, ScrollStrategy, SharedString, Task, UniformListScrollHandle,
+    WeakEntity, Window, actions, uniform_list,
+};
+use language::{Buffer, Capability, DiskState, LanguageRegistry, LineEnding, Rope, TextBuffer};
+use project::{Project, ProjectEntryId, ProjectPath, WorktreeId};
+use settings::Settings as _;
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::{
+    ResultExt as _,
+    paths::{PathExt as _, PathTreeRow, path_tree_rows},
+};
+use workspace::{
+    Item, ItemSettings, OpenOptions, OpenVisible, Pane, Toast, Workspace,
+    item::{ItemEvent, ProjectItem, TabContentParams},
+    notifications::{DetachAndPromptErr as _, NotificationId},
+};
+
+pub use crate::archive::{ArchiveEntry, ArchiveKind};
+
+actions!(archive_viewer, [ExtractArchive]);
+
+pub fn init(cx: &mut App) {
+    workspace::register_project_item::<ArchiveView>(cx);
+}
+
+/// An archive in the project, like a zip file, a jar or a source tarball, whose files are
+/// listed without extracting it.
+pub struct ArchiveItem {
+    kind: ArchiveKind,
+    abs_path: PathBuf,
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+    bytes: Arc<[u8]>,
+    entries: Vec<ArchiveEntry>,
+}
+
+impl project::ProjectItem for ArchiveItem {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        let kind = ArchiveKind::for_path(&path.path)?;
+        // Archives are read from the local file system, so the ones in remote projects are
+        // opened like any other file.
+        if !project.read(cx).is_local() {
+            return None;
+        }
+        let path = path.clone();
+        let project = project.clone();
+        let fs = project.read(cx).fs().clone();
+        Some(cx.spawn(async move |cx| {
+            let abs_path = project
+                .read_with(cx, |project, cx| project.absolute_path(&path, cx))?
+                .with_context(|| format!("finding the absolute path of {path:?}"))?;
+            let bytes: Arc<[u8]> = fs.load_bytes(&abs_path).await?.into();
+            let entries = cx
+                .background_spawn(archive::list_entries(kind, bytes.clone()))
+                .await
+                .with_context(|| format!("listing the files of {abs_path:?}"))?;
+            let entry_id = project.read_with(cx, |project, cx| {
+                project.entry_for_path(&path, cx).map(|entry| entry.id)
+            })?;
+            cx.new(|_| ArchiveItem {
+                kind,
+                abs_path,
+                project_path: path,
+                entry_id,
+                bytes,
+                entries,
+            })
+        }))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+}
+
+/// A file of an archive, opened in a read-only buffer.
+struct ArchiveEntryFile {
+    /// The path of the archive in its worktree, followed by the path of the file in the archive.
+    path: Arc<Path>,
+    full_path: PathBuf,
+    worktree_id: WorktreeId,
+}
+
+impl language::File for ArchiveEntryFile {
+    fn as_local(&self) -> Option<&dyn language::LocalFile> {
+        None
+    }
+
+    fn disk_state(&self) -> DiskState {
+        DiskState::New
+    }
+
+    fn path(&self) -> &Arc<Path> {
+        &self.path
+    }
+
+    fn full_path(&self, _: &App) -> PathBuf {
+        self.full_path.clone()
+    }
+
+    fn file_name<'a>(&'a self, _: &'a App) -> &'a OsStr {
+        self.path.file_name().unwrap_or_default()
+    }
+
+    fn worktree_id(&self, _: &App) -> WorktreeId {
+        self.worktree_id
+    }
+
+    fn to_proto(&self, _: &App) -> language::proto::File {
+        unimplemented!()
+    }
+
+    fn is_private(&self) -> bool {
+        false
+    }
+}
+
+async fn build_read_only_buffer(
+    mut text: String,
+    file: Arc<dyn language::File>,
+    language_registry: &Arc<LanguageRegistry>,
+    cx: &mut AsyncApp,
+) -> Result<Entity<Buffer>> {
+    let line_ending = LineEnding::detect(&text);
+    LineEnding::normalize(&mut text);
+    let text = Rope::from(text);
+    let language = cx.update(|cx| language_registry.language_for_file(&file, Some(&text), cx))?;
+    let language = if let Some(language) = language {
+        language_registry
+            .load_language(&language)
+            .await
+            .ok()
+            .and_then(|language| language.log_err())
+    } else {
+        None
+    };
+    cx.new(|cx| {
+        let buffer = TextBuffer::new_normalized(
+            0,
+            cx.entity_id().as_non_zero_u64().into(),
+            line_ending,
+            text,
+        );
+        let mut buffer = Buffer::build(buffer, Some(file), Capability::ReadOnly);
+        buffer.set_language(language, cx);
+        buffer
+    })
+}
+
+/// Lists the files of an archive, and opens them read-only without extracting the archive.
+pub struct ArchiveView {
+    archive: Entity<ArchiveItem>,
+    project: Entity<Project>,
+    workspace: Option<WeakEntity<Workspace>>,
+    collapsed: HashSet<Arc<Path>>,
+    rows: Vec<PathTreeRow>,
+    selected_row: Option<usize>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+}
+
+impl ArchiveView {
+    pub fn new(archive: Entity<ArchiveItem>, project: Entity<Project>, cx: &mut App) -> Self {
+        let rows = path_tree_rows(
+            archive
+                .read(cx)
+                .entries
+                .iter()
+                .map(|entry| entry.path.as_ref()),
+            &HashSet::default(),
+        );
+        Self {
+            archive,
+            project,
+            workspace: None,
+            collapsed: HashSet::default(),
+            rows,
+            selected_row: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+        }
+    }
+
+    fn toggle_directory(&mut self, path: Arc<Path>, cx: &mut Context<Self>) {
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+        self.rows = path_tree_rows(
+            self.archive
+                .read(cx)
+                .entries
+                .iter()
+                .map(|entry| entry.path.as_ref()),
+            &self.collapsed,
+        );
+        self.selected_row = self
+            .selected_row
+            .map(|ix| ix.min(self.rows.len().saturating_sub(1)));
+        cx.notify();
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_row = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let count = self.rows.len();
+        if count > 0 {
+            let ix = self.selected_row.map_or(0, |ix| (ix + 1).min(count - 1));
+            self.select(ix, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.rows.is_empty() {
+            let ix = self.selected_row.map_or(0, |ix| ix.saturating_sub(1));
+            self.select(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_row {
+            self.open_row(ix, window, cx);
+        }
+    }
+
+    fn open_row(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        match self.rows.get(ix).cloned() {
+            Some(PathTreeRow::Directory { path, .. }) => self.toggle_directory(path, cx),
+            Some(PathTreeRow::File { entry_ix, .. }) => self.open_entry(entry_ix, window, cx),
+            None => {}
+        }
+    }
+
+    fn open_entry(&mut self, entry_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let archive = self.archive.read(cx);
+        let (Some(entry), Some(workspace)) = (
+            archive.entries.get(entry_ix).cloned(),
+            self.workspace.clone(),
+        ) else {
+            return;
+        };
+        let (kind, bytes) = (archive.kind, archive.bytes.clone());
+        let file = ArchiveEntryFile {
+            path: archive.project_path.path.join(&entry.path).into(),
+            full_path: archive.abs_path.join(&entry.path),
+            worktree_id: archive.project_path.worktree_id,
+        };
+        let project_path = ProjectPath {
+            worktree_id: file.worktree_id,
+            path: file.path.clone(),
+        };
+        let project = self.project.clone();
+        let language_registry = project.read(cx).languages().clone();
+
+        let existing_editor = workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .items_of_type::<Editor>(cx)
+                    .find(|editor| editor.read(cx).project_path(cx) == Some(project_path.clone()))
+            })
+            .ok()
+            .flatten();
+        if let Some(editor) = existing_editor {
+            workspace
+                .update(cx, |workspace, cx| {
+                    workspace.activate_item(&editor, true, true, window, cx);
+                })
+                .ok();
+            return;
+        }
+
+        cx.spawn_in(window, async move |_, cx| {
+            let entry_path = entry.path.clone();
+            let contents = cx
+                .background_spawn(
+                    async move { archive::read_entry(kind, bytes, &entry_path).await },
+                )
+                .await?;
+            let Ok(text) = String::from_utf8(contents) else {
+                workspace.update(cx, |workspace, cx| {
+                    struct BinaryArchiveEntry;
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<BinaryArchiveEntry>(),
+                            format!(
+                                "{} is a binary file. Extract the archive to open it.",
+                                entry.path.display()
+                            ),
+                        )
+                        .autohide(),
+                        cx,
+                    )
+                })?;
+                return Ok(());
+            };
+            let buffer =
+                build_read_only_buffer(text, Arc::new(file), &language_registry, cx).await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project), window, cx));
+                workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+            })
+        })
+        .detach_and_prompt_err(
+            "Failed to open the archive file",
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    }
+
+    /// Extracts the archive into a folder next to it, named after the archive. When a file of
+    /// the archive is given, it's opened from there for editing, reusing the folder if the
+    /// archive was already extracted.
+    fn extract(
+        &mut self,
+        entry_to_edit: Option<Arc<Path>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let archive = self.archive.read(cx);
+        let Some(workspace) = self.workspace.clone() else {
+            return;
+        };
+        let Some(destination) = archive
+            .kind
+            .extraction_dir_name(&archive.abs_path)
+            .zip(archive.abs_path.parent())
+            .map(|(dir_name, parent)| parent.join(dir_name))
+        else {
+            return;
+        };
+        let (kind, bytes) = (archive.kind, archive.bytes.clone());
+        let fs = self.project.read(cx).fs().clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let exists = fs.metadata(&destination).await?.is_some();
+            if exists && entry_to_edit.is_none() {
+                anyhow::bail!("{} already exists", destination.compact().display());
+            }
+            if !exists {
+                cx.background_spawn({
+                    let destination = destination.clone();
+                    async move {
+                        fs.create_dir(&destination).await?;
+                        archive::extract(kind, bytes, &destination, fs.as_ref()).await
+                    }
+                })
+                .await
+                .with_context(|| format!("extracting into {destination:?}"))?;
+            }
+
+            if let Some(entry_to_edit) = entry_to_edit {
+                workspace
+                    .update_in(cx, |workspace, window, cx| {
+                        workspace.open_abs_path(
+                            destination.join(entry_to_edit),
+                            OpenOptions {
+                                visible: Some(OpenVisible::None),
+                                ..Default::default()
+                            },
+                            window,
+                            cx,
+                        )
+                    })?
+                    .await?;
+            } else {
+                workspace.update(cx, |workspace, cx| {
+                    struct ArchiveExtracted;
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<ArchiveExtracted>(),
+                            format!("Extracted into {}", destination.compact().display()),
+                        )
+                        .autohide(),
+                        cx,
+                    )
+                })?;
+            }
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to extract the archive", window, cx, |_, _, _| None);
+    }
+
+    fn extract_archive(&mut self, _: &ExtractArchive, window: &mut Window, cx: &mut Context<Self>) {
+        self.extract(None, window, cx);
+    }
+
+    fn render_rows(
+        &mut self,
+        range: Range<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| Some(self.render_row(ix, self.rows.get(ix)?, cx)))
+            .collect()
+    }
+
+    fn render_row(&self, ix: usize, row: &PathTreeRow, cx: &Context<Self>) -> AnyElement {
+        let item = ListItem::new(ix)
+            .spacing(ListItemSpacing::Dense)
+            .toggle_state(self.selected_row == Some(ix))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select(ix, cx);
+                this.open_row(ix, window, cx);
+            }));
+        match row {
+            PathTreeRow::Directory { path, depth } => {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let toggled_path = path.clone();
+                item.indent_level(*depth)
+                    .toggle(Some(!self.collapsed.contains(path)))
+                    .on_toggle(cx.listener(move |this, _, _, cx| {
+                        this.toggle_directory(toggled_path.clone(), cx)
+                    }))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Icon::new(IconName::Folder).color(Color::Muted))
+                            .child(Label::new(name).size(LabelSize::Small)),
+                    )
+                    .into_any_element()
+            }
+            PathTreeRow::File { entry_ix, depth } => {
+                let entry = &self.archive.read(cx).entries[*entry_ix];
+                let name = entry
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let entry_path = entry.path.clone();
+                item.indent_level(*depth + 1)
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(name).size(LabelSize::Small))
+                            .child(
+                                Label::new(format_size(entry.size))
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .end_hover_slot(
+                        IconButton::new(("edit-archive-file", ix), IconName::Pencil)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Extract and Edit"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.extract(Some(entry_path.clone()), window, cx)
+                            })),
+                    )
+                    .into_any_element()
+            }
+        }
+    }
+}
+
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = size as f64;
+    let mut unit = 0;
+    while size >= 1024. && unit < UNITS.len() - 1 {
+        size /= 1024.;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{size} {}", UNITS[unit])
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+impl Render for ArchiveView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let archive = self.archive.read(cx);
+        let total_size = archive.entries.iter().map(|entry| entry.size).sum();
+        let summary = format!(
+            "{} files, {} uncompressed",
+            archive.entries.len(),
+            format_size(total_size)
+        );
+        let title = archive.abs_path.compact().to_string_lossy().into_owned();
+        v_flex()
+            .key_context("ArchiveView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::extract_archive))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .gap_2()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        v_flex()
+                            .min_w_0()
+                            .child(Label::new(title).truncate())
+                            .child(
+                                Label::new(summary)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .child(
+                        Button::new("extract-archive", "Extract")
+                            .icon(IconName::Download)
+                            .icon_position(IconPosition::Start)
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.extract(None, window, cx)),
+                            ),
+                    ),
+            )
+            .map(|this| {
+                if self.rows.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("The archive is empty.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "archive-entries",
+                            self.rows.len(),
+                            Self::render_rows,
+                        )
+                        .p_1()
+                        .track_scroll(self.scroll_handle.clone())
+                        .flex_grow(),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for ArchiveView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<ItemEvent> for ArchiveView {}
+
+impl Item for ArchiveView {
+    type Event = ItemEvent;
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.archive.entity_id(), self.archive.read(cx))
+    }
+
+    fn is_singleton(&self, _cx: &App) -> bool {
+        true
+    }
+
+    fn tab_icon(&self, _: &Window, cx: &App) -> Option<Icon> {
+        let path = &self.archive.read(cx).abs_path;
+        ItemSettings::get_global(cx)
+            .file_icons
+            .then(|| FileIcons::get_icon(path, cx))
+            .flatten()
+            .map(Icon::from_path)
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        self.archive
+            .read(cx)
+            .abs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+            .into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let abs_path = self.archive.read(cx).abs_path.compact();
+        Some(abs_path.to_string_lossy().into_owned().into())
+    }
+
+    fn to_item_events(event: &ItemEvent, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Archive Viewer Opened")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+        self.workspace = Some(workspace.weak_handle());
+    }
+}
+
+impl ProjectItem for ArchiveView {
+    type Item = ArchiveItem;
+
+    fn for_project_item(
+        project: Entity<Project>,
+        _: Option<&Pane>,
+        item: Entity<Self::Item>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(item, project, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+}
@@ -2902,6 +2902,12 @@ impl FollowableItem for ContextEditor {
             .update(cx, |editor, cx| editor.set_leader_id(leader_id, window, cx))
     }
 
+    fn set_viewport_synced(&mut self, synced: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor.update(cx, |editor, cx| {
+            editor.set_viewport_synced(synced, window, cx)
+        })
+    }
+
     fn dedup(&self, existing: &Self, _window: &Window, cx: &App) -> Option<item::Dedup> {
         if existing.context.read(cx).id() == self.context.read(cx).id() {
             Some(item::Dedup::KeepExisting)
@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use anyhow::Result;
+use collections::HashMap;
+use futures::{FutureExt as _, future::Shared};
+use gpui::{
+    App, AppContext as _, AsyncApp, Context, Entity, EntityId, Global, Subscription, Task,
+    WeakEntity,
+};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use project::{Project, ProjectPath, WorktreeId};
+use util::ResultExt as _;
+
+/// The file at the root of a worktree listing the paths the agent must not read, in the
+/// `.gitignore` syntax.
+pub const AGENT_IGNORE_FILE_NAME: &str = ".ai-ignore";
+
+#[derive(Default)]
+struct GlobalAgentIgnores(HashMap<EntityId, Entity<AgentIgnore>>);
+
+impl Global for GlobalAgentIgnores {}
+
+/// The paths of a project that are kept out of the agent's context, like secrets, fixtures or
+/// vendored code, as listed by the `.ai-ignore` files of its worktrees.
+pub struct AgentIgnore {
+    project: WeakEntity<Project>,
+    snapshot: AgentIgnoreSnapshot,
+    load_task: Shared<Task<()>>,
+    _subscription: Subscription,
+}
+
+/// The `.ai-ignore` matchers of a project at one point in time, to check paths off the main
+/// thread.
+#[derive(Clone, Default)]
+pub struct AgentIgnoreSnapshot {
+    matchers: HashMap<WorktreeId, Gitignore>,
+}
+
+impl AgentIgnoreSnapshot {
+    pub fn is_ignored(&self, project_path: &ProjectPath, is_dir: bool) -> bool {
+        self.snapshot.is_ignored(project_path, is_dir)
+    }
+
+    pub fn snapshot(&self) -> AgentIgnoreSnapshot {
+        self.snapshot.clone()
+    }
+
+    /// Waits for the `.ai-ignore` files to be read, and fails when they list the file at
+    /// `project_path`, with an error starting with `action`, like "Cannot read file".
+    pub async fn ensure_not_ignored(
+        this: &Entity<Self>,
+        project_path: &ProjectPath,
+        action: &str,
+        cx: &mut AsyncApp,
+    ) -> Result<()> {
+        this.read_with(cx, |this, _| this.loaded())?.await;
+        if this.read_with(cx, |this, _| this.is_ignored(project_path, false))? {
+            anyhow::bail!(
+                "{action} because its path matches the project's `{AGENT_IGNORE_FILE_NAME}` file: {}",
+                project_path.path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+impl AgentIgnore {
+    pub fn for_project(project: &Entity<Project>, cx: &mut App) -> Entity<Self> {
+        let project_id = project.entity_id();
+        if let Some(agent_ignore) = cx
+            .try_global::<GlobalAgentIgnores>()
+            .and_then(|ignores| ignores.0.get(&project_id))
+        {
+            return agent_ignore.clone();
+        }
+
+        let agent_ignore = cx.new(|cx| {
+            let subscription = cx.subscribe(project, Self::handle_project_event);
+            let mut this = Self {
+                project: project.downgrade(),
+                snapshot: AgentIgnoreSnapshot::default(),
+                load_task: Task::ready(()).shared(),
+                _subscription: subscription,
+            };
+            this.reload(cx);
+            this
+        });
+        cx.observe_release(project, move |_, cx| {
+            cx.default_global::<GlobalAgentIgnores>()
+                .0
+                .remove(&project_id);
+        })
+        .detach();
+        cx.default_global::<GlobalAgentIgnores>()
+            .0
+            .insert(project_id, agent_ignore.clone());
+        agent_ignore
+    }
+
+    /// Resolves once the `.ai-ignore` files have been read, after which `is_ignored` is accurate.
+    pub fn loaded(&self) -> Shared<Task<()>> {
+        self.load_task.clone()
+    }
+
+    pub fn is_ignored(&self, project_path: &ProjectPath, is_dir: bool) -> bool {
+        self.matchers
+            .get(&project_path.worktree_id)
+            .is_some_and(|matcher| {
+                matcher
+                    .matched_path_or_any_parents(&project_path.path, is_dir)
+                    .is_ignore()
+            })
+    }
+
+    fn handle_project_event(
+        &mut self,
+        _: Entity<Project>,
+        event: &project::Event,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            project::Event::WorktreeAdded(_) | project::Event::WorktreeRemoved(_) => {
+                self.reload(cx);
+            }
+            project::Event::WorktreeUpdatedEntries(_, changes) => {
+                if changes
+                    .iter()
+                    .any(|(path, _, _)| path.as_ref() == Path::new(AGENT_IGNORE_FILE_NAME))
+                {
+                    self.reload(cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        let worktrees = project.read(cx).visible_worktrees(cx).collect::<Vec<_>>();
+        let loads = worktrees
+            .into_iter()
+            .filter_map(|worktree| {
+                let worktree = worktree.read(cx);
+                let entry = worktree
+                    .entry_for_path(AGENT_IGNORE_FILE_NAME)
+                    .filter(|entry| entry.is_file())?;
+                let project_path = ProjectPath {
+                    worktree_id: worktree.id(),
+                    path: entry.path.clone(),
+                };
+                Some((project_path, worktree.abs_path()))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|(project_path, root)| {
+                let worktree_id = project_path.worktree_id;
+                let open_buffer =
+                    project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+                (worktree_id, root, open_buffer)
+            })
+            .collect::<Vec<_>>();
+
+        self.load_task = cx
+            .spawn(async move |this, cx| {
+                let mut matchers = HashMap::default();
+                for (worktree_id, root, open_buffer) in loads {
+                    let Some(text) = async {
+                        let buffer = open_buffer.await?;
+                        buffer.read_with(cx, |buffer, _| buffer.text())
+                    }
+                    .await
+                    .log_err() else {
+                        continue;
+                    };
+                    if let Some(matcher) = build_matcher(&root, &text).log_err() {
+                        matchers.insert(worktree_id, matcher);
+                    }
+                }
+                this.update(cx, |this, _| {
+                    this.snapshot = AgentIgnoreSnapshot { matchers };
+                })
+                .ok();
+            })
+            .shared();
+    }
+}
+
+fn build_matcher(root: &Path, text: &str) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    for line in text.lines() {
+        builder.add_line(None, line)?;
+    }
+    Ok(builder.build()?)
+}
@@ -13,6 +13,7 @@ mod now_tool;
 mod open_tool;
 mod read_file_tool;
 mod schema;
+mod semantic_search_tool;
 mod templates;
 mod terminal_tool;
 mod thinking_tool;
@@ -27,6 +28,9 @@ use gpui::{App, Entity};
 use http_client::HttpClientWithUrl;
 use language_model::LanguageModelRegistry;
 use move_path_tool::MovePathTool;
+use semantic_index::SemanticIndexSettings;
+use semantic_search_tool::SemanticSearchTool;
+use settings::{Settings, SettingsStore};
 use web_search_tool::WebSearchTool;
 
 pub(crate) use templates::*;
@@ -79,6 +83,18 @@ pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
         },
     )
     .detach();
+
+    register_semantic_search_tool(cx);
+    cx.observe_global::<SettingsStore>(register_semantic_search_tool)
+        .detach();
+}
+
+fn register_semantic_search_tool(cx: &mut App) {
+    if SemanticIndexSettings::get_global(cx).enabled {
+        ToolRegistry::global(cx).register_tool(SemanticSearchTool);
+    } else {
+        ToolRegistry::global(cx).unregister_tool(SemanticSearchTool);
+    }
 }
 
 fn register_web_search_tool(registry: &Entity<LanguageModelRegistry>, cx: &mut App) {
@@ -135,6 +151,7 @@ mod tests {
     fn test_builtin_tool_schema_compatibility(cx: &mut App) {
         settings::init(cx);
         AgentSettings::register(cx);
+        SemanticIndexSettings::register(cx);
 
         let client = Client::new(
             Arc::new(FakeSystemClock::new()),
@@ -164,4 +181,36 @@ mod tests {
             assert_eq!(actual_schema, expected_schema, "{}", error_message)
         }
     }
+
+    #[gpui::test]
+    fn test_semantic_search_tool_follows_settings(cx: &mut App) {
+        settings::init(cx);
+        AgentSettings::register(cx);
+        SemanticIndexSettings::register(cx);
+
+        let client = Client::new(
+            Arc::new(FakeSystemClock::new()),
+            FakeHttpClient::with_200_response(),
+            cx,
+        );
+        language_model::init(client.clone(), cx);
+        crate::init(client.http_client(), cx);
+
+        let tool_name = SemanticSearchTool.name();
+        assert!(ToolRegistry::global(cx).tool(&tool_name).is_none());
+
+        SettingsStore::update_global(cx, |store, cx| {
+            store
+                .set_user_settings(r#"{ "semantic_index": { "enabled": true } }"#, cx)
+                .unwrap();
+        });
+        assert!(ToolRegistry::global(cx).tool(&tool_name).is_some());
+
+        SettingsStore::update_global(cx, |store, cx| {
+            store
+                .set_user_settings(r#"{ "semantic_index": { "enabled": false } }"#, cx)
+                .unwrap();
+        });
+        assert!(ToolRegistry::global(cx).tool(&tool_name).is_none());
+    }
 }
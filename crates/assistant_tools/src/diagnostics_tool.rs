@@ -1,6 +1,6 @@
 use crate::schema::json_schema_for;
 use anyhow::{Result, anyhow};
-use assistant_tool::{ActionLog, Tool, ToolResult};
+use assistant_tool::{ActionLog, AgentIgnore, Tool, ToolResult};
 use gpui::{AnyWindowHandle, App, Entity, Task};
 use language::{DiagnosticSeverity, OffsetRangeExt};
 use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
@@ -90,6 +90,7 @@ impl Tool for DiagnosticsTool {
         _window: Option<AnyWindowHandle>,
         cx: &mut App,
     ) -> ToolResult {
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
         match serde_json::from_value::<DiagnosticsToolInput>(input)
             .ok()
             .and_then(|input| input.path)
@@ -100,12 +101,18 @@ impl Tool for DiagnosticsTool {
                         .into();
                 };
 
-                let buffer =
-                    project.update(cx, |project, cx| project.open_buffer(project_path, cx));
-
                 cx.spawn(async move |cx| {
+                    AgentIgnore::ensure_not_ignored(
+                        &agent_ignore,
+                        &project_path,
+                        "Cannot get diagnostics",
+                        cx,
+                    )
+                    .await?;
                     let mut output = String::new();
-                    let buffer = buffer.await?;
+                    let buffer = project
+                        .update(cx, |project, cx| project.open_buffer(project_path, cx))?
+                        .await?;
                     let snapshot = buffer.read_with(cx, |buffer, _cx| buffer.snapshot())?;
 
                     for (_, group) in snapshot.diagnostic_groups(None) {
@@ -134,43 +141,54 @@ impl Tool for DiagnosticsTool {
                 })
                 .into()
             }
-            _ => {
-                let project = project.read(cx);
-                let mut output = String::new();
-                let mut has_diagnostics = false;
-
-                for (project_path, _, summary) in project.diagnostic_summaries(true, cx) {
-                    if summary.error_count > 0 || summary.warning_count > 0 {
-                        let Some(worktree) = project.worktree_for_id(project_path.worktree_id, cx)
-                        else {
-                            continue;
-                        };
-
-                        has_diagnostics = true;
-                        output.push_str(&format!(
-                            "{}: {} error(s), {} warning(s)\n",
-                            Path::new(worktree.read(cx).root_name())
-                                .join(project_path.path)
-                                .display(),
-                            summary.error_count,
-                            summary.warning_count
-                        ));
-                    }
-                }
-
-                action_log.update(cx, |action_log, _cx| {
-                    action_log.checked_project_diagnostics();
-                });
-
-                if has_diagnostics {
-                    Task::ready(Ok(output.into())).into()
-                } else {
-                    Task::ready(Ok("No errors or warnings found in the project."
-                        .to_string()
-                        .into()))
-                    .into()
-                }
-            }
+            _ => cx
+                .spawn(async move |cx| {
+                    agent_ignore
+                        .read_with(cx, |agent_ignore, _| agent_ignore.loaded())?
+                        .await;
+                    cx.update(|cx| {
+                        let agent_ignore = agent_ignore.read(cx);
+                        let project = project.read(cx);
+                        let mut output = String::new();
+                        let mut has_diagnostics = false;
+
+                        for (project_path, _, summary) in project.diagnostic_summaries(true, cx) {
+                            if agent_ignore.is_ignored(&project_path, false) {
+                                continue;
+                            }
+                            if summary.error_count > 0 || summary.warning_count > 0 {
+                                let Some(worktree) =
+                                    project.worktree_for_id(project_path.worktree_id, cx)
+                                else {
+                                    continue;
+                                };
+
+                                has_diagnostics = true;
+                                output.push_str(&format!(
+                                    "{}: {} error(s), {} warning(s)\n",
+                                    Path::new(worktree.read(cx).root_name())
+                                        .join(project_path.path)
+                                        .display(),
+                                    summary.error_count,
+                                    summary.warning_count
+                                ));
+                            }
+                        }
+
+                        action_log.update(cx, |action_log, _cx| {
+                            action_log.checked_project_diagnostics();
+                        });
+
+                        if has_diagnostics {
+                            Ok(output.into())
+                        } else {
+                            Ok("No errors or warnings found in the project."
+                                .to_string()
+                                .into())
+                        }
+                    })?
+                })
+                .into(),
         }
     }
 }
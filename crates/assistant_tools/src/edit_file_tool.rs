@@ -6,8 +6,8 @@ use crate::{
 };
 use anyhow::{Context as _, Result, anyhow};
 use assistant_tool::{
-    ActionLog, AnyToolCard, Tool, ToolCard, ToolResult, ToolResultContent, ToolResultOutput,
-    ToolUseStatus,
+    ActionLog, AgentIgnore, AnyToolCard, Tool, ToolCard, ToolResult, ToolResultContent,
+    ToolResultOutput, ToolUseStatus,
 };
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use editor::{Editor, EditorMode, MinimapVisibility, MultiBuffer, PathKey};
@@ -25,7 +25,7 @@ use language::{
 use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
 use markdown::{Markdown, MarkdownElement, MarkdownStyle};
 use project::{
-    Project, ProjectPath,
+    Project, ProjectPath, ProjectTransaction,
     lsp_store::{FormatTrigger, LspFormatTarget},
 };
 use schemars::JsonSchema;
@@ -187,6 +187,7 @@ impl Tool for EditFileTool {
             Ok(path) => path,
             Err(err) => return Task::ready(Err(anyhow!(err))).into(),
         };
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
 
         let card = window.and_then(|window| {
             window
@@ -201,6 +202,8 @@ impl Tool for EditFileTool {
         let card_clone = card.clone();
         let action_log_clone = action_log.clone();
         let task = cx.spawn(async move |cx: &mut AsyncApp| {
+            AgentIgnore::ensure_not_ignored(&agent_ignore, &project_path, "Can't edit file", cx)
+                .await?;
             let edit_agent =
                 EditAgent::new(model, project.clone(), action_log_clone, Templates::new());
 
@@ -221,6 +224,10 @@ impl Tool for EditFileTool {
             if let Some(card) = card_clone.as_ref() {
                 card.update(cx, |card, cx| card.initialize(buffer.clone(), cx))?;
             }
+            // Keep the edits of the agent from being grouped with the ones made before it.
+            buffer.update(cx, |buffer, _| {
+                buffer.finalize_last_transaction();
+            })?;
 
             let (output, mut events) = if matches!(input.mode, EditFileMode::Edit) {
                 edit_agent.edit(
@@ -240,9 +247,15 @@ impl Tool for EditFileTool {
 
             let mut hallucinated_old_text = false;
             let mut ambiguous_ranges = Vec::new();
+            let mut first_transaction_id = None;
             while let Some(event) = events.next().await {
                 match event {
                     EditAgentOutputEvent::Edited => {
+                        if first_transaction_id.is_none() {
+                            first_transaction_id = buffer.read_with(cx, |buffer, _| {
+                                buffer.peek_undo_stack().map(|entry| entry.transaction_id())
+                            })?;
+                        }
                         if let Some(card) = card_clone.as_ref() {
                             card.update(cx, |card, cx| card.update_diff(cx))?;
                         }
@@ -283,6 +296,27 @@ impl Tool for EditFileTool {
                 format_task.await.log_err();
             }
 
+            // Group the edits of the agent so that they can be reviewed and reverted as one.
+            let transaction = buffer.update(cx, |buffer, _| {
+                let transaction_id = first_transaction_id?;
+                buffer.group_until_transaction(transaction_id);
+                buffer
+                    .finalize_last_transaction()
+                    .filter(|transaction| transaction.id == transaction_id)
+                    .cloned()
+            })?;
+            if let Some(transaction) = transaction {
+                let mut project_transaction = ProjectTransaction::default();
+                project_transaction.0.insert(buffer.clone(), transaction);
+                project.update(cx, |project, cx| {
+                    project.record_workspace_edit(
+                        format!("Agent: {}", input.display_description),
+                        &project_transaction,
+                        cx,
+                    )
+                })?;
+            }
+
             project
                 .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))?
                 .await?;
@@ -1669,4 +1703,116 @@ mod tests {
             "Trailing whitespace should remain when remove_trailing_whitespace_on_save is disabled"
         );
     }
+
+    #[gpui::test]
+    async fn test_edit_agent_ignored_file(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({".ai-ignore": "*.pem\n", "key.pem": "private key"}),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+
+        let result = cx
+            .update(|cx| {
+                let input = serde_json::to_value(EditFileToolInput {
+                    display_description: "Replace the key".into(),
+                    path: "root/key.pem".into(),
+                    mode: EditFileMode::Overwrite,
+                })
+                .unwrap();
+                Arc::new(EditFileTool)
+                    .run(
+                        input,
+                        Arc::default(),
+                        project.clone(),
+                        action_log,
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await;
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("`.ai-ignore` file")
+        );
+        assert_eq!(
+            fs.load(path!("/root/key.pem").as_ref()).await.unwrap(),
+            "private key"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_records_agent_edit(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({"src": {"main.rs": "initial content"}}),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let mut events = cx.events(&project);
+
+        let edit_task = cx.update(|cx| {
+            let input = serde_json::to_value(EditFileToolInput {
+                display_description: "Replace the content".into(),
+                path: "root/src/main.rs".into(),
+                mode: EditFileMode::Overwrite,
+            })
+            .unwrap();
+            Arc::new(EditFileTool)
+                .run(
+                    input,
+                    Arc::default(),
+                    project.clone(),
+                    action_log.clone(),
+                    model.clone(),
+                    None,
+                    cx,
+                )
+                .output
+        });
+        cx.executor().run_until_parked();
+        model.stream_last_completion_response("new ".to_string());
+        cx.executor().run_until_parked();
+        model.stream_last_completion_response("content".to_string());
+        model.end_last_completion_stream();
+        edit_task.await.unwrap();
+
+        // All the edits of the agent are recorded as one transaction.
+        let mut recorded = Vec::new();
+        while let Some(Some(event)) = futures::FutureExt::now_or_never(events.next()) {
+            if let project::Event::WorkspaceEditApplied {
+                title,
+                transactions,
+            } = event
+            {
+                recorded.push((title, transactions));
+            }
+        }
+        assert_eq!(recorded.len(), 1);
+        let (title, transactions) = recorded.pop().unwrap();
+        assert_eq!(title.as_ref(), "Agent: Replace the content");
+        assert_eq!(transactions.len(), 1);
+
+        let (buffer, transaction_id) = transactions[0].clone();
+        buffer.update(cx, |buffer, cx| {
+            assert_eq!(buffer.text(), "new content");
+            assert!(buffer.undo_transaction(transaction_id, cx));
+            assert_eq!(buffer.text(), "initial content");
+        });
+    }
 }
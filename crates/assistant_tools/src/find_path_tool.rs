@@ -1,7 +1,8 @@
 use crate::{schema::json_schema_for, ui::ToolCallCardHeader};
 use anyhow::{Result, anyhow};
 use assistant_tool::{
-    ActionLog, Tool, ToolCard, ToolResult, ToolResultContent, ToolResultOutput, ToolUseStatus,
+    ActionLog, AgentIgnore, Tool, ToolCard, ToolResult, ToolResultContent, ToolResultOutput,
+    ToolUseStatus,
 };
 use editor::Editor;
 use futures::channel::oneshot::{self, Receiver};
@@ -10,7 +11,7 @@ use gpui::{
 };
 use language;
 use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
-use project::Project;
+use project::{Project, ProjectPath};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
@@ -172,18 +173,34 @@ fn search_paths(glob: &str, project: Entity<Project>, cx: &mut App) -> Task<Resu
         .worktrees(cx)
         .map(|worktree| worktree.read(cx).snapshot())
         .collect();
-
-    cx.background_spawn(async move {
-        Ok(snapshots
-            .iter()
-            .flat_map(|snapshot| {
-                let root_name = PathBuf::from(snapshot.root_name());
-                snapshot
-                    .entries(false, 0)
-                    .map(move |entry| root_name.join(&entry.path))
-                    .filter(|path| path_matcher.is_match(&path))
-            })
-            .collect())
+    let agent_ignore = AgentIgnore::for_project(&project, cx);
+
+    cx.spawn(async move |cx| {
+        agent_ignore
+            .read_with(cx, |agent_ignore, _| agent_ignore.loaded())?
+            .await;
+        let agent_ignore = agent_ignore.read_with(cx, |agent_ignore, _| agent_ignore.snapshot())?;
+        cx.background_spawn(async move {
+            Ok(snapshots
+                .iter()
+                .flat_map(|snapshot| {
+                    let root_name = PathBuf::from(snapshot.root_name());
+                    let agent_ignore = &agent_ignore;
+                    snapshot
+                        .entries(false, 0)
+                        .filter(move |entry| {
+                            let project_path = ProjectPath {
+                                worktree_id: snapshot.id(),
+                                path: entry.path.clone(),
+                            };
+                            !agent_ignore.is_ignored(&project_path, entry.is_dir())
+                        })
+                        .map(move |entry| root_name.join(&entry.path))
+                        .filter(|path| path_matcher.is_match(&path))
+                })
+                .collect())
+        })
+        .await
     })
 }
 
@@ -452,6 +469,33 @@ mod test {
         );
     }
 
+    #[gpui::test]
+    async fn test_find_path_skips_agent_ignored_paths(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            serde_json::json!({
+                ".ai-ignore": "secrets/\n",
+                "secrets": {
+                    "config.toml": "",
+                },
+                "src": {
+                    "config.toml": "",
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let matches = cx
+            .update(|cx| search_paths("**/config.toml", project.clone(), cx))
+            .await
+            .unwrap();
+        assert_eq!(matches, &[PathBuf::from("root/src/config.toml")]);
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
@@ -1,6 +1,6 @@
 use crate::schema::json_schema_for;
 use anyhow::{Result, anyhow};
-use assistant_tool::{ActionLog, Tool, ToolResult};
+use assistant_tool::{ActionLog, AgentIgnore, Tool, ToolResult};
 use futures::StreamExt;
 use gpui::{AnyWindowHandle, App, Entity, Task};
 use language::{OffsetRangeExt, ParseStatus, Point};
@@ -164,9 +164,11 @@ impl Tool for GrepTool {
         };
 
         let results = project.update(cx, |project, cx| project.search(query, cx));
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
 
         cx.spawn(async move |cx|  {
             futures::pin_mut!(results);
+            agent_ignore.read_with(cx, |agent_ignore, _| agent_ignore.loaded())?.await;
 
             let mut output = String::new();
             let mut skips_remaining = input.offset;
@@ -192,6 +194,7 @@ impl Tool for GrepTool {
                         let worktree_settings = WorktreeSettings::get(Some((&project_path).into()), cx);
                         worktree_settings.is_path_excluded(&project_path.path)
                             || worktree_settings.is_path_private(&project_path.path)
+                            || agent_ignore.read(cx).is_ignored(&project_path, false)
                     }).unwrap_or(false) {
                         continue;
                     }
@@ -1,9 +1,11 @@
 use crate::schema::json_schema_for;
 use anyhow::{Result, anyhow};
-use assistant_tool::{ActionLog, Tool, ToolResult};
+use assistant_tool::{
+    AGENT_IGNORE_FILE_NAME, ActionLog, AgentIgnore, Tool, ToolResult, ToolResultOutput,
+};
 use gpui::{AnyWindowHandle, App, Entity, Task};
 use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
-use project::{Project, WorktreeSettings};
+use project::{Project, ProjectPath, WorktreeSettings};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
@@ -168,9 +170,7 @@ impl Tool for ListDirectoryTool {
         }
         let worktree_snapshot = worktree.read(cx).snapshot();
 
-        let mut folders = Vec::new();
-        let mut files = Vec::new();
-
+        let mut children = Vec::new();
         for entry in worktree_snapshot.child_entries(&project_path.path) {
             // Skip private and excluded files and directories
             if global_settings.is_path_private(&entry.path)
@@ -197,28 +197,55 @@ impl Tool for ListDirectoryTool {
                 .join(&entry.path)
                 .display()
                 .to_string();
-            if entry.is_dir() {
-                folders.push(full_path);
-            } else {
-                files.push(full_path);
-            }
+            let child_path = ProjectPath {
+                worktree_id: project_path.worktree_id,
+                path: entry.path.clone(),
+            };
+            children.push((child_path, full_path, entry.is_dir()));
         }
 
-        let mut output = String::new();
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
+        cx.spawn(async move |cx| -> Result<ToolResultOutput> {
+            agent_ignore.read_with(cx, |agent_ignore, _| agent_ignore.loaded())?.await;
+            agent_ignore.read_with(cx, |agent_ignore, _| {
+                if agent_ignore.is_ignored(&project_path, true) {
+                    anyhow::bail!(
+                        "Cannot list directory because its path matches the project's `{AGENT_IGNORE_FILE_NAME}` file: {}",
+                        &input.path
+                    );
+                }
 
-        if !folders.is_empty() {
-            writeln!(output, "# Folders:\n{}", folders.join("\n")).unwrap();
-        }
+                let mut folders = Vec::new();
+                let mut files = Vec::new();
+                for (child_path, full_path, is_dir) in children {
+                    if agent_ignore.is_ignored(&child_path, is_dir) {
+                        continue;
+                    }
+                    if is_dir {
+                        folders.push(full_path);
+                    } else {
+                        files.push(full_path);
+                    }
+                }
 
-        if !files.is_empty() {
-            writeln!(output, "\n# Files:\n{}", files.join("\n")).unwrap();
-        }
+                let mut output = String::new();
 
-        if output.is_empty() {
-            writeln!(output, "{} is empty.", input.path).unwrap();
-        }
+                if !folders.is_empty() {
+                    writeln!(output, "# Folders:\n{}", folders.join("\n")).unwrap();
+                }
+
+                if !files.is_empty() {
+                    writeln!(output, "\n# Files:\n{}", files.join("\n")).unwrap();
+                }
+
+                if output.is_empty() {
+                    writeln!(output, "{} is empty.", input.path).unwrap();
+                }
 
-        Task::ready(Ok(output.into())).into()
+                Ok(output.into())
+            })?
+        })
+        .into()
     }
 }
 
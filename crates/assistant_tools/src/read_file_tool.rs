@@ -1,6 +1,6 @@
 use crate::schema::json_schema_for;
 use anyhow::{Context as _, Result, anyhow};
-use assistant_tool::{ActionLog, Tool, ToolResult};
+use assistant_tool::{ActionLog, AgentIgnore, Tool, ToolResult};
 use assistant_tool::{ToolResultContent, outline};
 use gpui::{AnyWindowHandle, App, Entity, Task};
 use project::{ImageItem, image_store};
@@ -145,6 +145,7 @@ impl Tool for ReadFileTool {
         }
 
         let file_path = input.path.clone();
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
 
         if image_store::is_image_file(&project, &project_path, cx) {
             if !model.supports_images() {
@@ -156,6 +157,13 @@ impl Tool for ReadFileTool {
             }
 
             let task = cx.spawn(async move |cx| -> Result<ToolResultOutput> {
+                AgentIgnore::ensure_not_ignored(
+                    &agent_ignore,
+                    &project_path,
+                    "Cannot read file",
+                    cx,
+                )
+                .await?;
                 let image_entity: Entity<ImageItem> = cx
                     .update(|cx| {
                         project.update(cx, |project, cx| {
@@ -182,6 +190,8 @@ impl Tool for ReadFileTool {
         }
 
         cx.spawn(async move |cx| {
+            AgentIgnore::ensure_not_ignored(&agent_ignore, &project_path, "Cannot read file", cx)
+                .await?;
             let buffer = cx
                 .update(|cx| {
                     project.update(cx, |project, cx| project.open_buffer(project_path, cx))
@@ -906,6 +916,64 @@ mod test {
         );
     }
 
+    #[gpui::test]
+    async fn test_read_file_ignored_by_agent_ignore_file(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".ai-ignore": "fixtures/\n*.pem\n!public.pem\n",
+                "fixtures": {
+                    "large.json": "{}"
+                },
+                "keys": {
+                    "private.pem": "private key",
+                    "public.pem": "public key"
+                },
+                "main.rs": "fn main() {}"
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+
+        let read = |path: &str, cx: &mut TestAppContext| {
+            let input = json!({ "path": path });
+            cx.update(|cx| {
+                Arc::new(ReadFileTool)
+                    .run(
+                        input,
+                        Arc::default(),
+                        project.clone(),
+                        action_log.clone(),
+                        model.clone(),
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+        };
+
+        let result = read("root/fixtures/large.json", cx).await;
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("`.ai-ignore` file"),
+            "files in an ignored directory should not be readable"
+        );
+        let result = read("root/keys/private.pem", cx).await;
+        assert!(result.is_err(), "ignored files should not be readable");
+
+        let result = read("root/keys/public.pem", cx).await;
+        assert_eq!(result.unwrap().content.as_str(), Some("public key"));
+        let result = read("root/main.rs", cx).await;
+        assert_eq!(result.unwrap().content.as_str(), Some("fn main() {}"));
+    }
+
     #[gpui::test]
     async fn test_read_file_with_multiple_worktree_settings(cx: &mut TestAppContext) {
         init_test(cx);
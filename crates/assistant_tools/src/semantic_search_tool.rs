@@ -0,0 +1,131 @@
+use crate::schema::json_schema_for;
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, AgentIgnore, Tool, ToolResult};
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::{Project, WorktreeSettings};
+use schemars::JsonSchema;
+use semantic_index::SemanticDb;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::{fmt::Write, sync::Arc};
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SemanticSearchToolInput {
+    /// A description of the code to find, like "where HTTP requests are retried",
+    /// or a snippet of code to find similar code to.
+    pub query: String,
+}
+
+const MAX_RESULTS: usize = 10;
+
+pub struct SemanticSearchTool;
+
+impl Tool for SemanticSearchTool {
+    fn name(&self) -> String {
+        "semantic_search".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./semantic_search_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::SearchCode
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<SemanticSearchToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<SemanticSearchToolInput>(input.clone()) {
+            Ok(input) => format!("Search code for {}", MarkdownInlineCode(&input.query)),
+            Err(_) => "Search code by meaning".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<SemanticSearchToolInput>(input) {
+            Ok(input) => input,
+            Err(error) => {
+                return Task::ready(Err(anyhow!("Failed to parse input: {error}"))).into();
+            }
+        };
+        let Some(project_index) = SemanticDb::index_for_project(&project, cx) else {
+            return Task::ready(Err(anyhow!(
+                "The project isn't indexed for semantic search"
+            )))
+            .into();
+        };
+        let project_index = project_index.read(cx);
+        // Some of the results may be ignored, so more are asked for than are shown.
+        let results = project_index.search(vec![input.query], MAX_RESULTS * 2, cx);
+        let fs = project_index.fs();
+        let agent_ignore = AgentIgnore::for_project(&project, cx);
+
+        cx.spawn(async move |cx| {
+            let results = SemanticDb::load_results(results.await?, &fs, cx).await?;
+            agent_ignore
+                .read_with(cx, |agent_ignore, _| agent_ignore.loaded())?
+                .await;
+
+            let results = cx.update(|cx| {
+                results
+                    .into_iter()
+                    .filter(|result| {
+                        let Some(project_path) =
+                            project.read(cx).find_project_path(&result.full_path, cx)
+                        else {
+                            return false;
+                        };
+                        let worktree_settings =
+                            WorktreeSettings::get(Some((&project_path).into()), cx);
+                        !worktree_settings.is_path_private(&project_path.path)
+                            && !agent_ignore.read(cx).is_ignored(&project_path, false)
+                    })
+                    .take(MAX_RESULTS)
+                    .collect::<Vec<_>>()
+            })?;
+
+            if results.is_empty() {
+                return Ok("No matches found".to_string().into());
+            }
+
+            let mut output = format!("Found {} matches:\n", results.len());
+            for result in results {
+                writeln!(
+                    output,
+                    "\n## {} L{}-{}",
+                    result.full_path.display(),
+                    result.row_range.start() + 1,
+                    result.row_range.end() + 1
+                )?;
+                output.push_str("```\n");
+                output.push_str(&result.excerpt_content);
+                output.push_str("\n```\n");
+            }
+            Ok(output.into())
+        })
+        .into()
+    }
+}
@@ -19,6 +19,7 @@ pub enum Sound {
     StartScreenshare,
     StopScreenshare,
     AgentDone,
+    Bell,
 }
 
 impl Sound {
@@ -31,6 +32,7 @@ impl Sound {
             Self::StartScreenshare => "start_screenshare",
             Self::StopScreenshare => "stop_screenshare",
             Self::AgentDone => "agent_done",
+            Self::Bell => "bell",
         }
     }
 }
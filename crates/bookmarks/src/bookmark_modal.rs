@@ -0,0 +1,135 @@
+use editor::{Editor, EditorEvent, actions::Tab};
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, SharedString,
+    Subscription,
+};
+use theme::ActiveTheme as _;
+use ui::prelude::*;
+use workspace::ModalView;
+
+use crate::{BookmarkId, BookmarkStore};
+
+/// Names a bookmark and writes a note about it.
+pub(crate) struct BookmarkModal {
+    store: Entity<BookmarkStore>,
+    bookmark_id: BookmarkId,
+    name_editor: Entity<Editor>,
+    note_editor: Entity<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ModalView for BookmarkModal {}
+
+impl EventEmitter<DismissEvent> for BookmarkModal {}
+
+impl Focusable for BookmarkModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl BookmarkModal {
+    pub(crate) fn new(
+        store: Entity<BookmarkStore>,
+        bookmark_id: BookmarkId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let (name, note) = store
+            .read(cx)
+            .bookmark(bookmark_id)
+            .map(|bookmark| (bookmark.name.clone(), bookmark.note.clone()))
+            .unwrap_or_default();
+
+        let note_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Note", cx);
+            if let Some(note) = note {
+                editor.set_text(note, window, cx);
+            }
+            editor
+        });
+        let note_focus_handle = note_editor.focus_handle(cx);
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Bookmark name", cx);
+            if let Some(name) = name {
+                editor.set_text(name, window, cx);
+            }
+            editor
+                .register_action::<Tab>(move |_, window, _| window.focus(&note_focus_handle))
+                .detach();
+            editor
+        });
+
+        let subscriptions = [&name_editor, &note_editor].map(|editor| {
+            cx.subscribe_in(
+                editor,
+                window,
+                |this, _, event: &EditorEvent, window, cx| {
+                    if let EditorEvent::Blurred = event {
+                        if !this.contains_focused(window, cx) {
+                            cx.emit(DismissEvent);
+                        }
+                    }
+                },
+            )
+        });
+
+        Self {
+            store,
+            bookmark_id,
+            name_editor,
+            note_editor,
+            _subscriptions: subscriptions.into(),
+        }
+    }
+
+    fn contains_focused(&self, window: &Window, cx: &App) -> bool {
+        self.name_editor.focus_handle(cx).is_focused(window)
+            || self.note_editor.focus_handle(cx).is_focused(window)
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let text = |editor: &Entity<Editor>, cx: &App| {
+            let text = editor.read(cx).text(cx);
+            let text = text.trim();
+            (!text.is_empty()).then(|| SharedString::from(text.to_string()))
+        };
+        let name = text(&self.name_editor, cx);
+        let note = text(&self.note_editor, cx);
+        self.store.update(cx, |store, cx| {
+            store.edit_bookmark(self.bookmark_id, name, note, cx)
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for BookmarkModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let editor_container = |editor: Entity<Editor>, cx: &App| {
+            div()
+                .border_b_1()
+                .border_color(cx.theme().colors().border_variant)
+                .px_2()
+                .py_1()
+                .child(editor)
+        };
+
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("BookmarkModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(editor_container(self.name_editor.clone(), cx))
+            .child(editor_container(self.note_editor.clone(), cx))
+            .child(h_flex().px_2().py_1().child(
+                Label::new("Press tab to write a note, and enter to save").color(Color::Muted),
+            ))
+    }
+}
@@ -0,0 +1,523 @@
+use std::{path::Path, sync::Arc};
+
+use collections::HashMap;
+use gpui::{
+    App, AppContext as _, Context, Entity, EntityId, Global, SharedString, Subscription, Task,
+    WeakEntity,
+};
+use language::{Anchor, Bias, Buffer, BufferEvent, BufferId, Point, ToPoint as _};
+use project::{Project, buffer_store::BufferStoreEvent};
+use util::ResultExt as _;
+use workspace::{Workspace, WorkspaceId};
+
+use crate::persistence::{BOOKMARK_DB, SerializedBookmark};
+
+#[derive(Default)]
+struct GlobalBookmarkStores(HashMap<EntityId, Entity<BookmarkStore>>);
+
+impl Global for GlobalBookmarkStores {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BookmarkId(usize);
+
+/// A line in a file that can be jumped back to.
+#[derive(Clone)]
+pub struct Bookmark {
+    pub id: BookmarkId,
+    pub abs_path: Arc<Path>,
+    /// The bookmarked row, which follows the edits made around it while its file is open.
+    pub row: u32,
+    /// The number from 1 to 9 that jumps to this bookmark.
+    pub number: Option<u8>,
+    pub name: Option<SharedString>,
+    pub note: Option<SharedString>,
+}
+
+/// An open buffer with bookmarks in it, whose rows are kept up to date as it's edited.
+struct WatchedBuffer {
+    buffer: WeakEntity<Buffer>,
+    anchors: HashMap<BookmarkId, Anchor>,
+    _subscriptions: [Subscription; 2],
+}
+
+/// The bookmarks of a workspace, which are saved with it.
+pub struct BookmarkStore {
+    project: WeakEntity<Project>,
+    workspace_id: Option<WorkspaceId>,
+    bookmarks: Vec<Bookmark>,
+    watched_buffers: HashMap<BufferId, WatchedBuffer>,
+    next_id: usize,
+    pending_serialization: Task<Option<()>>,
+    _subscription: Subscription,
+}
+
+impl BookmarkStore {
+    /// Gives the workspace a store, loading the bookmarks saved with it.
+    pub(crate) fn register_workspace(workspace: &Workspace, cx: &mut Context<Workspace>) {
+        let entity_id = cx.entity_id();
+        let project = workspace.project().clone();
+        let workspace_id = workspace.database_id();
+        let store = cx.new(|cx| {
+            let mut this = Self::new(project, workspace_id, cx);
+            this.load(cx);
+            this
+        });
+        cx.default_global::<GlobalBookmarkStores>()
+            .0
+            .insert(entity_id, store);
+        cx.observe_release(&cx.entity(), move |_, _, cx| {
+            cx.default_global::<GlobalBookmarkStores>()
+                .0
+                .remove(&entity_id);
+        })
+        .detach();
+    }
+
+    /// The store of the given workspace.
+    pub fn for_workspace(workspace: &Workspace, cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalBookmarkStores>()?
+            .0
+            .get(&workspace.weak_handle().entity_id())
+            .cloned()
+    }
+
+    pub(crate) fn new(
+        project: Entity<Project>,
+        workspace_id: Option<WorkspaceId>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let buffer_store = project.read(cx).buffer_store().clone();
+        let subscription = cx.subscribe(&buffer_store, |this, _, event, cx| {
+            if let BufferStoreEvent::BufferAdded(buffer) = event {
+                this.anchor_bookmarks_in(buffer, cx);
+            }
+        });
+
+        Self {
+            project: project.downgrade(),
+            workspace_id,
+            bookmarks: Vec::new(),
+            watched_buffers: HashMap::default(),
+            next_id: 0,
+            pending_serialization: Task::ready(None),
+            _subscription: subscription,
+        }
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        cx.spawn(async move |this, cx| {
+            let serialized_bookmarks = cx
+                .background_spawn(async move { BOOKMARK_DB.bookmarks(workspace_id) })
+                .await?;
+            this.update(cx, |this, cx| this.loaded(serialized_bookmarks, cx))
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn loaded(
+        &mut self,
+        serialized_bookmarks: Vec<SerializedBookmark>,
+        cx: &mut Context<Self>,
+    ) {
+        for bookmark in serialized_bookmarks {
+            let id = self.next_id();
+            self.bookmarks.push(Bookmark {
+                id,
+                abs_path: bookmark.abs_path.into(),
+                row: bookmark.row,
+                number: bookmark.number.and_then(|number| u8::try_from(number).ok()),
+                name: bookmark.name.map(SharedString::from),
+                note: bookmark.note.map(SharedString::from),
+            });
+        }
+        if let Some(project) = self.project.upgrade() {
+            for buffer in project.read(cx).opened_buffers(cx) {
+                self.anchor_bookmarks_in(&buffer, cx);
+            }
+        }
+        self.sort_bookmarks();
+        cx.notify();
+    }
+
+    /// All bookmarks, ordered by their path and row.
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn bookmark(&self, id: BookmarkId) -> Option<&Bookmark> {
+        self.bookmarks.iter().find(|bookmark| bookmark.id == id)
+    }
+
+    pub fn numbered_bookmark(&self, number: u8) -> Option<&Bookmark> {
+        self.bookmarks
+            .iter()
+            .find(|bookmark| bookmark.number == Some(number))
+    }
+
+    pub fn bookmark_at(&self, buffer: &Entity<Buffer>, row: u32, cx: &App) -> Option<BookmarkId> {
+        let abs_path = abs_path(buffer, cx)?;
+        self.bookmarks
+            .iter()
+            .find(|bookmark| bookmark.abs_path == abs_path && bookmark.row == row)
+            .map(|bookmark| bookmark.id)
+    }
+
+    /// The open buffer the bookmark is in, if there is one.
+    pub fn buffer_for(&self, bookmark: &Bookmark) -> Option<Entity<Buffer>> {
+        self.watched_buffers
+            .values()
+            .find(|watched| watched.anchors.contains_key(&bookmark.id))?
+            .buffer
+            .upgrade()
+    }
+
+    /// The positions of the bookmarks in open buffers.
+    pub fn anchors(&self) -> impl Iterator<Item = (BufferId, Anchor)> + '_ {
+        self.watched_buffers
+            .iter()
+            .flat_map(|(buffer_id, watched)| {
+                watched
+                    .anchors
+                    .values()
+                    .map(move |anchor| (*buffer_id, *anchor))
+            })
+    }
+
+    /// Removes the bookmark at `row`, or adds one there if there isn't one.
+    pub fn toggle_bookmark(&mut self, buffer: &Entity<Buffer>, row: u32, cx: &mut Context<Self>) {
+        match self.bookmark_at(buffer, row, cx) {
+            Some(id) => self.remove_bookmark(id, cx),
+            None => {
+                self.add_bookmark(buffer, row, cx);
+            }
+        }
+    }
+
+    /// Gives `number` to the bookmark at `row`, adding one there if needed and taking the number
+    /// from whichever bookmark had it. If the bookmark already had the number, it is removed
+    /// instead.
+    pub fn set_numbered_bookmark(
+        &mut self,
+        number: u8,
+        buffer: &Entity<Buffer>,
+        row: u32,
+        cx: &mut Context<Self>,
+    ) {
+        let id = match self.bookmark_at(buffer, row, cx) {
+            Some(id) if self.bookmark(id).is_some_and(|b| b.number == Some(number)) => {
+                self.remove_bookmark(id, cx);
+                return;
+            }
+            Some(id) => id,
+            None => match self.add_bookmark(buffer, row, cx) {
+                Some(id) => id,
+                None => return,
+            },
+        };
+        for bookmark in &mut self.bookmarks {
+            if bookmark.number == Some(number) {
+                bookmark.number = None;
+            }
+            if bookmark.id == id {
+                bookmark.number = Some(number);
+            }
+        }
+        self.bookmarks_changed(cx);
+    }
+
+    /// Adds a bookmark at `row`, or returns the one that's already there.
+    pub fn add_bookmark(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        row: u32,
+        cx: &mut Context<Self>,
+    ) -> Option<BookmarkId> {
+        if let Some(id) = self.bookmark_at(buffer, row, cx) {
+            return Some(id);
+        }
+        let abs_path = abs_path(buffer, cx)?;
+        let snapshot = buffer.read(cx).snapshot();
+        let point = snapshot.clip_point(Point::new(row, 0), Bias::Left);
+        let id = self.next_id();
+        self.watch_buffer(buffer, cx)
+            .anchors
+            .insert(id, snapshot.anchor_before(point));
+        self.bookmarks.push(Bookmark {
+            id,
+            abs_path,
+            row: point.row,
+            number: None,
+            name: None,
+            note: None,
+        });
+        self.bookmarks_changed(cx);
+        Some(id)
+    }
+
+    pub fn edit_bookmark(
+        &mut self,
+        id: BookmarkId,
+        name: Option<SharedString>,
+        note: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(bookmark) = self.bookmarks.iter_mut().find(|bookmark| bookmark.id == id) {
+            bookmark.name = name;
+            bookmark.note = note;
+            self.bookmarks_changed(cx);
+        }
+    }
+
+    pub fn remove_bookmark(&mut self, id: BookmarkId, cx: &mut Context<Self>) {
+        let len = self.bookmarks.len();
+        self.bookmarks.retain(|bookmark| bookmark.id != id);
+        for watched in self.watched_buffers.values_mut() {
+            watched.anchors.remove(&id);
+        }
+        if self.bookmarks.len() != len {
+            self.bookmarks_changed(cx);
+        }
+    }
+
+    pub fn clear_bookmarks(&mut self, cx: &mut Context<Self>) {
+        if !self.bookmarks.is_empty() {
+            self.bookmarks.clear();
+            self.watched_buffers.clear();
+            self.bookmarks_changed(cx);
+        }
+    }
+
+    fn next_id(&mut self) -> BookmarkId {
+        let id = BookmarkId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    fn anchor_bookmarks_in(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let Some(abs_path) = abs_path(buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let anchors = self
+            .bookmarks
+            .iter_mut()
+            .filter(|bookmark| bookmark.abs_path == abs_path)
+            .map(|bookmark| {
+                let point = snapshot.clip_point(Point::new(bookmark.row, 0), Bias::Left);
+                bookmark.row = point.row;
+                (bookmark.id, snapshot.anchor_before(point))
+            })
+            .collect::<Vec<_>>();
+        if !anchors.is_empty() {
+            self.watch_buffer(buffer, cx).anchors.extend(anchors);
+            cx.notify();
+        }
+    }
+
+    fn watch_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> &mut WatchedBuffer {
+        let buffer_id = buffer.read(cx).remote_id();
+        self.watched_buffers.entry(buffer_id).or_insert_with(|| {
+            let on_change = cx.subscribe(buffer, |this, buffer, event, cx| match event {
+                BufferEvent::Edited => this.buffer_edited(&buffer, cx),
+                BufferEvent::FileHandleChanged => this.buffer_renamed(&buffer, cx),
+                _ => {}
+            });
+            // Bookmarks keep their last rows once their file is closed.
+            let on_release = cx.observe_release(buffer, move |this, _, _| {
+                this.watched_buffers.remove(&buffer_id);
+            });
+            WatchedBuffer {
+                buffer: buffer.downgrade(),
+                anchors: HashMap::default(),
+                _subscriptions: [on_change, on_release],
+            }
+        })
+    }
+
+    fn buffer_edited(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let Some(watched) = self.watched_buffers.get(&buffer.read(cx).remote_id()) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let mut moved = false;
+        for bookmark in &mut self.bookmarks {
+            if let Some(anchor) = watched.anchors.get(&bookmark.id) {
+                let row = anchor.to_point(&snapshot).row;
+                moved |= bookmark.row != row;
+                bookmark.row = row;
+            }
+        }
+        if moved {
+            self.bookmarks_changed(cx);
+        }
+    }
+
+    fn buffer_renamed(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let Some(watched) = self.watched_buffers.get(&buffer.read(cx).remote_id()) else {
+            return;
+        };
+        let Some(abs_path) = abs_path(buffer, cx) else {
+            return;
+        };
+        for bookmark in &mut self.bookmarks {
+            if watched.anchors.contains_key(&bookmark.id) {
+                bookmark.abs_path = abs_path.clone();
+            }
+        }
+        self.bookmarks_changed(cx);
+    }
+
+    fn sort_bookmarks(&mut self) {
+        self.bookmarks
+            .sort_by(|a, b| (&a.abs_path, a.row).cmp(&(&b.abs_path, b.row)));
+    }
+
+    fn bookmarks_changed(&mut self, cx: &mut Context<Self>) {
+        self.sort_bookmarks();
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        let bookmarks = self
+            .bookmarks
+            .iter()
+            .map(|bookmark| SerializedBookmark {
+                abs_path: bookmark.abs_path.to_path_buf(),
+                row: bookmark.row,
+                number: bookmark.number.map(u32::from),
+                name: bookmark.name.as_ref().map(ToString::to_string),
+                note: bookmark.note.as_ref().map(ToString::to_string),
+            })
+            .collect();
+        self.pending_serialization = cx.background_spawn(async move {
+            BOOKMARK_DB
+                .save_bookmarks(workspace_id, bookmarks)
+                .await
+                .log_err()
+        });
+    }
+}
+
+fn abs_path(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+    let file = buffer.read(cx).file()?.as_local()?;
+    Some(file.abs_path(cx).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_bookmarks_follow_edits(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "a.txt": "one\ntwo\nthree\nfour" }))
+            .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let serialized_bookmarks = vec![SerializedBookmark {
+            abs_path: path!("/dir/a.txt").into(),
+            row: 3,
+            number: Some(2),
+            name: None,
+            note: None,
+        }];
+        let store = cx.new(|cx| {
+            let mut store = BookmarkStore::new(project.clone(), None, cx);
+            store.loaded(serialized_bookmarks, cx);
+            store
+        });
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+        store.update(cx, |store, cx| {
+            store.toggle_bookmark(&buffer, 1, cx);
+            store.set_numbered_bookmark(1, &buffer, 2, cx);
+        });
+        let rows = |store: &Entity<BookmarkStore>, cx: &mut TestAppContext| {
+            store.read_with(cx, |store, _| {
+                store
+                    .bookmarks()
+                    .iter()
+                    .map(|bookmark| (bookmark.row, bookmark.number))
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(rows(&store, cx), [(1, None), (2, Some(1)), (3, Some(2))]);
+
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "zero\n")], None, cx));
+        assert_eq!(rows(&store, cx), [(2, None), (3, Some(1)), (4, Some(2))]);
+
+        store.update(cx, |store, cx| {
+            store.set_numbered_bookmark(2, &buffer, 2, cx);
+            store.toggle_bookmark(&buffer, 3, cx);
+        });
+        assert_eq!(rows(&store, cx), [(2, Some(2)), (4, None)]);
+
+        store.update(cx, |store, cx| {
+            store.set_numbered_bookmark(2, &buffer, 2, cx)
+        });
+        assert_eq!(rows(&store, cx), [(4, None)]);
+    }
+
+    #[gpui::test]
+    async fn test_bookmarks_outlive_their_buffer(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "a.txt": "one\ntwo\nthree" }))
+            .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let store = cx.new(|cx| BookmarkStore::new(project.clone(), None, cx));
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+        store.update(cx, |store, cx| store.toggle_bookmark(&buffer, 1, cx));
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "zero\n")], None, cx));
+        store.read_with(cx, |store, _| {
+            assert_eq!(store.bookmarks()[0].row, 2);
+            assert!(store.buffer_for(&store.bookmarks()[0]).is_some());
+            assert_eq!(store.anchors().count(), 1);
+        });
+
+        // Closing the file keeps the row the bookmark had, rather than the buffer.
+        drop(buffer);
+        cx.run_until_parked();
+        store.read_with(cx, |store, _| {
+            assert_eq!(store.bookmarks()[0].row, 2);
+            assert!(store.buffer_for(&store.bookmarks()[0]).is_none());
+            assert_eq!(store.anchors().count(), 0);
+        });
+    }
+}
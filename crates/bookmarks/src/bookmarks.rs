@@ -0,0 +1,146 @@
+mod bookmark_modal;
+mod bookmark_store;
+mod bookmarks_panel;
+mod bookmarks_panel_settings;
+mod persistence;
+
+use editor::{Editor, scroll::Autoscroll};
+use gpui::{App, Context, Entity, Window, actions, impl_actions};
+use language::{Buffer, Point};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use settings::Settings as _;
+use workspace::{OpenOptions, Workspace};
+
+pub use bookmark_store::{Bookmark, BookmarkId, BookmarkStore};
+pub use bookmarks_panel::BookmarksPanel;
+pub use bookmarks_panel_settings::BookmarksPanelSettings;
+
+use bookmark_modal::BookmarkModal;
+
+actions!(
+    bookmarks,
+    [
+        ToggleFocus,
+        ToggleBookmark,
+        AddNamedBookmark,
+        EditBookmark,
+        RemoveBookmark,
+        ClearBookmarks
+    ]
+);
+
+/// Gives a number from 1 to 9 to the bookmark on the cursor's line, adding one there if needed.
+#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SetNumberedBookmark {
+    pub number: u8,
+}
+
+/// Jumps to the bookmark with a number from 1 to 9.
+#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GoToNumberedBookmark {
+    pub number: u8,
+}
+
+impl_actions!(bookmarks, [SetNumberedBookmark, GoToNumberedBookmark]);
+
+pub fn init(cx: &mut App) {
+    BookmarksPanelSettings::register(cx);
+    cx.observe_new(register).detach();
+}
+
+fn register(workspace: &mut Workspace, _: Option<&mut Window>, cx: &mut Context<Workspace>) {
+    BookmarkStore::register_workspace(workspace, cx);
+    workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+        workspace.toggle_panel_focus::<BookmarksPanel>(window, cx);
+    });
+    workspace.register_action(|workspace, _: &ToggleBookmark, _, cx| {
+        if let Some((store, buffer, row)) = bookmark_target(workspace, cx) {
+            store.update(cx, |store, cx| store.toggle_bookmark(&buffer, row, cx));
+        }
+    });
+    workspace.register_action(|workspace, action: &SetNumberedBookmark, _, cx| {
+        if !(1..=9).contains(&action.number) {
+            return;
+        }
+        if let Some((store, buffer, row)) = bookmark_target(workspace, cx) {
+            store.update(cx, |store, cx| {
+                store.set_numbered_bookmark(action.number, &buffer, row, cx)
+            });
+        }
+    });
+    workspace.register_action(|workspace, action: &GoToNumberedBookmark, window, cx| {
+        let Some(store) = BookmarkStore::for_workspace(workspace, cx) else {
+            return;
+        };
+        let Some(bookmark) = store.read(cx).numbered_bookmark(action.number) else {
+            return;
+        };
+        let (abs_path, row) = (bookmark.abs_path.clone(), bookmark.row);
+        open_bookmark(workspace, abs_path.to_path_buf(), row, window, cx);
+    });
+    workspace.register_action(|workspace, _: &AddNamedBookmark, window, cx| {
+        let Some((store, buffer, row)) = bookmark_target(workspace, cx) else {
+            return;
+        };
+        let Some(id) = store.update(cx, |store, cx| store.add_bookmark(&buffer, row, cx)) else {
+            return;
+        };
+        workspace.toggle_modal(window, cx, |window, cx| {
+            BookmarkModal::new(store, id, window, cx)
+        });
+    });
+    workspace.register_action(|workspace, _: &EditBookmark, window, cx| {
+        let Some((store, buffer, row)) = bookmark_target(workspace, cx) else {
+            return;
+        };
+        let Some(id) = store.read(cx).bookmark_at(&buffer, row, cx) else {
+            return;
+        };
+        workspace.toggle_modal(window, cx, |window, cx| {
+            BookmarkModal::new(store, id, window, cx)
+        });
+    });
+    workspace.register_action(|workspace, _: &ClearBookmarks, _, cx| {
+        if let Some(store) = BookmarkStore::for_workspace(workspace, cx) {
+            store.update(cx, |store, cx| store.clear_bookmarks(cx));
+        }
+    });
+}
+
+/// The workspace's bookmarks, and the buffer and row of the newest cursor in the active editor.
+fn bookmark_target(
+    workspace: &Workspace,
+    cx: &App,
+) -> Option<(Entity<BookmarkStore>, Entity<Buffer>, u32)> {
+    let store = BookmarkStore::for_workspace(workspace, cx)?;
+    let editor = workspace.active_item_as::<Editor>(cx)?.read(cx);
+    let head = editor.selections.newest_anchor().head();
+    let (buffer, point, _) = editor.buffer().read(cx).point_to_buffer_point(head, cx)?;
+    Some((store, buffer, point.row))
+}
+
+pub(crate) fn open_bookmark(
+    workspace: &mut Workspace,
+    abs_path: std::path::PathBuf,
+    row: u32,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let open_task = workspace.open_abs_path(abs_path, OpenOptions::default(), window, cx);
+    cx.spawn_in(window, async move |_, cx| {
+        let item = open_task.await?;
+        if let Some(editor) = item.downcast::<Editor>() {
+            editor.update_in(cx, |editor, window, cx| {
+                let point = Point::new(row, 0);
+                editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                    s.select_ranges([point..point])
+                });
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
@@ -0,0 +1,443 @@
+use std::{ops::Range, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, ScrollbarMarker};
+use gpui::{
+    Action, AnyElement, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, ScrollStrategy, Subscription, Task, UniformListScrollHandle, WeakEntity, Window,
+    uniform_list,
+};
+use language::{BufferId, Point};
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use theme::ActiveTheme as _;
+use ui::{ListItem, ListItemSpacing, Tab, prelude::*};
+use util::{ResultExt as _, TryFutureExt as _};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+use crate::{
+    Bookmark, BookmarkStore, BookmarksPanelSettings, EditBookmark, RemoveBookmark, ToggleFocus,
+    bookmark_modal::BookmarkModal, open_bookmark,
+};
+
+const BOOKMARKS_PANEL_KEY: &str = "BookmarksPanel";
+const BOOKMARKS_SCROLLBAR_LAYER: &str = "bookmarks";
+
+/// Lists the bookmarks of the workspace and marks them on the scrollbars of the editors showing
+/// them.
+pub struct BookmarksPanel {
+    store: Entity<BookmarkStore>,
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    width: Option<Pixels>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    selected_index: Option<usize>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedBookmarksPanel {
+    width: Option<Pixels>,
+}
+
+impl BookmarksPanel {
+    fn new(
+        workspace: &mut Workspace,
+        store: Entity<BookmarkStore>,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let workspace_entity = cx.entity();
+        cx.new(|cx| {
+            let subscriptions = vec![
+                cx.observe(&store, |this, _, cx| {
+                    this.clamp_selection(cx);
+                    this.update_scrollbar_markers(cx);
+                    cx.notify();
+                }),
+                cx.subscribe(&workspace_entity, |this, _, event, cx| {
+                    if let workspace::Event::ItemAdded { .. } = event {
+                        this.update_scrollbar_markers(cx);
+                    }
+                }),
+            ];
+            Self {
+                store,
+                workspace: workspace_entity.downgrade(),
+                fs,
+                width: None,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+                selected_index: None,
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = cx
+                .background_spawn(async move {
+                    KEY_VALUE_STORE
+                        .read_kvp(BOOKMARKS_PANEL_KEY)
+                        .log_err()
+                        .flatten()
+                })
+                .await;
+            let serialized_panel = serialized_panel
+                .map(|panel| serde_json::from_str::<SerializedBookmarksPanel>(&panel))
+                .transpose()?;
+
+            let panel = workspace.update(cx, |workspace, cx| {
+                let store = BookmarkStore::for_workspace(workspace, cx)
+                    .context("the workspace has no bookmark store")?;
+                anyhow::Ok(Self::new(workspace, store, cx))
+            })??;
+            panel.update(cx, |panel, cx| {
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.width = serialized_panel.width.map(|w| w.round());
+                }
+                // Editors restored with the workspace already show the bookmarked files.
+                panel.update_scrollbar_markers(cx);
+                cx.notify();
+            })?;
+            Ok(panel)
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        BOOKMARKS_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedBookmarksPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn update_scrollbar_markers(&self, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let color = cx.theme().colors().text_accent;
+        let anchors = self
+            .store
+            .read(cx)
+            .anchors()
+            .collect::<Vec<(BufferId, _)>>();
+        let editors = workspace
+            .read(cx)
+            .items_of_type::<Editor>(cx)
+            .collect::<Vec<_>>();
+        for editor in editors {
+            editor.update(cx, |editor, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let markers = snapshot
+                    .as_singleton()
+                    .map(|(excerpt_id, buffer_id, _)| {
+                        anchors
+                            .iter()
+                            .filter(|(bookmark_buffer_id, _)| *bookmark_buffer_id == buffer_id)
+                            .filter_map(|(_, anchor)| {
+                                let anchor = snapshot.anchor_in_excerpt(*excerpt_id, *anchor)?;
+                                Some(ScrollbarMarker {
+                                    range: anchor..anchor,
+                                    color,
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                editor.set_scrollbar_markers(BOOKMARKS_SCROLLBAR_LAYER, markers, cx);
+            });
+        }
+    }
+
+    fn clamp_selection(&mut self, cx: &App) {
+        let count = self.store.read(cx).bookmarks().len();
+        self.selected_index = match self.selected_index {
+            _ if count == 0 => None,
+            Some(ix) => Some(ix.min(count - 1)),
+            None => None,
+        };
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let count = self.store.read(cx).bookmarks().len();
+        if count > 0 {
+            let ix = self.selected_index.map_or(0, |ix| (ix + 1).min(count - 1));
+            self.select(ix, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.store.read(cx).bookmarks().is_empty() {
+            let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+            self.select(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open(ix, window, cx);
+        }
+    }
+
+    fn open(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(bookmark) = self.store.read(cx).bookmarks().get(ix) else {
+            return;
+        };
+        let (abs_path, row) = (bookmark.abs_path.to_path_buf(), bookmark.row);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                open_bookmark(workspace, abs_path, row, window, cx)
+            })
+            .log_err();
+    }
+
+    fn remove_selected(&mut self, _: &RemoveBookmark, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(bookmark) = self.selected_bookmark(cx) else {
+            return;
+        };
+        let id = bookmark.id;
+        self.store
+            .update(cx, |store, cx| store.remove_bookmark(id, cx));
+    }
+
+    fn edit_selected(&mut self, _: &EditBookmark, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(bookmark) = self.selected_bookmark(cx) else {
+            return;
+        };
+        let id = bookmark.id;
+        let store = self.store.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    BookmarkModal::new(store, id, window, cx)
+                })
+            })
+            .log_err();
+    }
+
+    fn selected_bookmark<'a>(&self, cx: &'a App) -> Option<&'a Bookmark> {
+        self.store.read(cx).bookmarks().get(self.selected_index?)
+    }
+
+    fn render_bookmarks(
+        &mut self,
+        range: Range<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let bookmarks = self.store.read(cx).bookmarks();
+        range
+            .filter_map(|ix| {
+                let bookmark = bookmarks.get(ix)?;
+                Some(self.render_bookmark(ix, bookmark, cx))
+            })
+            .collect()
+    }
+
+    fn render_bookmark(&self, ix: usize, bookmark: &Bookmark, cx: &Context<Self>) -> AnyElement {
+        let row = bookmark.row;
+        let file_name = bookmark
+            .abs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let label = bookmark.name.clone().unwrap_or_else(|| {
+            self.store
+                .read(cx)
+                .buffer_for(bookmark)
+                .map(|buffer| {
+                    let snapshot = buffer.read(cx).snapshot();
+                    let line_end = Point::new(row, snapshot.line_len(row));
+                    snapshot
+                        .text_for_range(Point::new(row, 0)..line_end)
+                        .collect::<String>()
+                        .trim()
+                        .to_string()
+                })
+                .filter(|line| !line.is_empty())
+                .unwrap_or_else(|| file_name.clone())
+                .into()
+        });
+
+        ListItem::new(ix)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(self.selected_index == Some(ix))
+            .start_slot(
+                Label::new(
+                    bookmark
+                        .number
+                        .map_or(String::new(), |number| number.to_string()),
+                )
+                .size(LabelSize::Small)
+                .color(Color::Accent),
+            )
+            .child(
+                v_flex()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(label).size(LabelSize::Small).truncate())
+                            .child(
+                                Label::new(format!("{file_name}:{}", row + 1))
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .when_some(bookmark.note.clone(), |this, note| {
+                        this.child(
+                            Label::new(note)
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    }),
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select(ix, cx);
+                this.open(ix, window, cx);
+            }))
+            .into_any_element()
+    }
+}
+
+impl Render for BookmarksPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let bookmark_count = self.store.read(cx).bookmarks().len();
+        v_flex()
+            .key_context("BookmarksPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::remove_selected))
+            .on_action(cx.listener(Self::edit_selected))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("Bookmarks"))
+                    .child(Icon::new(IconName::Bookmark)),
+            )
+            .map(|this| {
+                if bookmark_count == 0 {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("Bookmark a line to find it here.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "bookmarks",
+                            bookmark_count,
+                            Self::render_bookmarks,
+                        )
+                        .p_1()
+                        .track_scroll(self.scroll_handle.clone())
+                        .flex_grow(),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for BookmarksPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for BookmarksPanel {}
+
+impl Panel for BookmarksPanel {
+    fn persistent_name() -> &'static str {
+        "BookmarksPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        BookmarksPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<BookmarksPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| BookmarksPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        BookmarksPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Bookmark)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Bookmarks Panel".into())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        4
+    }
+}
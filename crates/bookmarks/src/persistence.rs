@@ -0,0 +1,141 @@
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use db::{define_connection, query, sqlez_macros::sql};
+use workspace::{WorkspaceDb, WorkspaceId};
+
+/// A bookmark as it's stored in the database, where it can't be anchored.
+pub(crate) struct SerializedBookmark {
+    pub abs_path: PathBuf,
+    pub row: u32,
+    pub number: Option<u32>,
+    pub name: Option<String>,
+    pub note: Option<String>,
+}
+
+define_connection! {
+    pub static ref BOOKMARK_DB: BookmarkDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE bookmarks (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                bookmark_row INTEGER NOT NULL,
+                number INTEGER,
+                name TEXT,
+                note TEXT,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        )];
+}
+
+impl BookmarkDb {
+    query! {
+        fn bookmark_rows(
+            workspace_id: WorkspaceId
+        ) -> Result<Vec<(PathBuf, u32, Option<u32>, Option<String>, Option<String>)>> {
+            SELECT path, bookmark_row, number, name, note
+            FROM bookmarks
+            WHERE workspace_id = ?
+        }
+    }
+
+    pub(crate) fn bookmarks(&self, workspace_id: WorkspaceId) -> Result<Vec<SerializedBookmark>> {
+        Ok(self
+            .bookmark_rows(workspace_id)?
+            .into_iter()
+            .map(|(abs_path, row, number, name, note)| SerializedBookmark {
+                abs_path,
+                row,
+                number,
+                name,
+                note,
+            })
+            .collect())
+    }
+
+    pub(crate) async fn save_bookmarks(
+        &self,
+        workspace_id: WorkspaceId,
+        bookmarks: Vec<SerializedBookmark>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("save_bookmarks", || {
+                conn.exec_bound(sql!(DELETE FROM bookmarks WHERE workspace_id = ?))?(workspace_id)
+                    .context("clearing old bookmarks")?;
+                for bookmark in bookmarks {
+                    conn.exec_bound(sql!(
+                        INSERT INTO bookmarks (workspace_id, path, bookmark_row, number, name, note)
+                        VALUES (?, ?, ?, ?, ?, ?)
+                    ))?((
+                        workspace_id,
+                        bookmark.abs_path.as_path(),
+                        bookmark.row,
+                        bookmark.number,
+                        bookmark.name,
+                        bookmark.note,
+                    ))
+                    .context("saving bookmark")?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_save_and_load_bookmarks() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        BOOKMARK_DB
+            .save_bookmarks(
+                workspace_id,
+                vec![
+                    SerializedBookmark {
+                        abs_path: PathBuf::from("/dir/a.txt"),
+                        row: 3,
+                        number: Some(1),
+                        name: None,
+                        note: None,
+                    },
+                    SerializedBookmark {
+                        abs_path: PathBuf::from("/dir/b.txt"),
+                        row: 0,
+                        number: None,
+                        name: Some("entry point".into()),
+                        note: Some("start reading here".into()),
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+
+        let bookmarks = BOOKMARK_DB.bookmarks(workspace_id).unwrap();
+        assert_eq!(
+            bookmarks
+                .iter()
+                .map(|bookmark| (
+                    bookmark.abs_path.clone(),
+                    bookmark.row,
+                    bookmark.number,
+                    bookmark.name.as_deref(),
+                    bookmark.note.as_deref()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (PathBuf::from("/dir/a.txt"), 3, Some(1), None, None),
+                (
+                    PathBuf::from("/dir/b.txt"),
+                    0,
+                    None,
+                    Some("entry point"),
+                    Some("start reading here")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use editor::{Anchor, Editor, scroll::Autoscroll};
+use gpui::{App, Entity, Focusable, WeakEntity, Window};
+use language::ToOffset as _;
+use project::{ProjectPath, WorktreeId};
+use ui::{ContextMenu, ContextMenuEntry, prelude::*};
+use util::ResultExt as _;
+use workspace::item::BreadcrumbText;
+
+/// A segment of the breadcrumbs and the dropdown it opens when clicked, if any.
+pub(crate) struct Crumb {
+    pub text: BreadcrumbText,
+    pub menu: Option<CrumbMenu>,
+    /// Whether this segment names the item's file, which is shown in bold while it's dirty.
+    pub is_file: bool,
+}
+
+/// What a breadcrumb's dropdown lists.
+#[derive(Clone)]
+pub(crate) enum CrumbMenu {
+    /// The entries of the directory containing `current`.
+    Entries {
+        editor: WeakEntity<Editor>,
+        worktree_id: WorktreeId,
+        parent: Arc<Path>,
+        current: Arc<Path>,
+    },
+    /// The outline items at the same level and with the same parent as the one starting at `start`.
+    Symbols {
+        editor: WeakEntity<Editor>,
+        start: Anchor,
+        depth: usize,
+    },
+}
+
+/// Splits the file segment of an editor's breadcrumbs into one segment per path component,
+/// and gives every segment a dropdown of its siblings.
+pub(crate) fn editor_crumbs(
+    editor: &Entity<Editor>,
+    segments: Vec<BreadcrumbText>,
+    cx: &App,
+) -> Vec<Crumb> {
+    let weak_editor = editor.downgrade();
+    let editor = editor.read(cx);
+    let multibuffer = editor.buffer().read(cx);
+    let cursor = editor.selections.newest_anchor().head();
+    let (buffer_id, symbols) = multibuffer.symbols_containing(cursor, None, cx).unzip();
+    let symbols = symbols.unwrap_or_default();
+
+    let mut segments = segments.into_iter();
+    let Some(file_segment) = segments.next() else {
+        return Vec::new();
+    };
+    let mut crumbs = buffer_id
+        .and_then(|buffer_id| path_crumbs(editor, &weak_editor, buffer_id, &file_segment, cx))
+        .unwrap_or_else(|| {
+            vec![Crumb {
+                text: file_segment,
+                menu: None,
+                is_file: true,
+            }]
+        });
+    crumbs.extend(segments.enumerate().map(|(ix, text)| Crumb {
+        text,
+        menu: symbols.get(ix).map(|symbol| CrumbMenu::Symbols {
+            editor: weak_editor.clone(),
+            start: symbol.range.start,
+            depth: symbol.depth,
+        }),
+        is_file: false,
+    }));
+    crumbs
+}
+
+fn path_crumbs(
+    editor: &Editor,
+    weak_editor: &WeakEntity<Editor>,
+    buffer_id: language::BufferId,
+    file_segment: &BreadcrumbText,
+    cx: &App,
+) -> Option<Vec<Crumb>> {
+    let project = editor.project.as_ref()?.read(cx);
+    let buffer = editor.buffer().read(cx).buffer(buffer_id)?.read(cx);
+    let file = buffer.file()?;
+    if file.path().as_os_str().is_empty() {
+        return None;
+    }
+    let include_root = project.visible_worktrees(cx).count() > 1;
+    // Editors can show a header of their own instead of the path of their file.
+    let resolved_path = buffer.snapshot().resolve_file_path(cx, include_root)?;
+    if resolved_path.to_string_lossy() != file_segment.text {
+        return None;
+    }
+
+    let worktree_id = file.worktree_id(cx);
+    let segment = |text: String, menu: Option<CrumbMenu>, is_file: bool| Crumb {
+        text: BreadcrumbText {
+            text,
+            highlights: None,
+            font: file_segment.font.clone(),
+        },
+        menu,
+        is_file,
+    };
+    let mut crumbs = Vec::new();
+    if include_root {
+        let root_name = resolved_path.components().next()?;
+        crumbs.push(segment(
+            root_name.as_os_str().to_string_lossy().to_string(),
+            None,
+            false,
+        ));
+    }
+    let mut path = PathBuf::new();
+    let component_count = file.path().components().count();
+    for (ix, component) in file.path().components().enumerate() {
+        let parent = Arc::<Path>::from(path.as_path());
+        path.push(component);
+        crumbs.push(segment(
+            component.as_os_str().to_string_lossy().to_string(),
+            Some(CrumbMenu::Entries {
+                editor: weak_editor.clone(),
+                worktree_id,
+                parent,
+                current: path.as_path().into(),
+            }),
+            ix + 1 == component_count,
+        ));
+    }
+    Some(crumbs)
+}
+
+impl CrumbMenu {
+    pub(crate) fn build(&self, window: &mut Window, cx: &mut App) -> Option<Entity<ContextMenu>> {
+        match self {
+            Self::Entries {
+                editor,
+                worktree_id,
+                parent,
+                current,
+            } => entries_menu(editor, *worktree_id, parent, current, window, cx),
+            Self::Symbols {
+                editor,
+                start,
+                depth,
+            } => symbols_menu(editor, *start, *depth, window, cx),
+        }
+    }
+}
+
+fn entries_menu(
+    editor: &WeakEntity<Editor>,
+    worktree_id: WorktreeId,
+    parent: &Path,
+    current: &Path,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Entity<ContextMenu>> {
+    let editor = editor.upgrade()?.read(cx);
+    let project = editor.project.clone()?;
+    let workspace = editor.workspace()?.downgrade();
+    let worktree = project.read(cx).worktree_for_id(worktree_id, cx)?;
+    let entries = worktree
+        .read(cx)
+        .snapshot()
+        .child_entries(parent)
+        .map(|entry| (entry.id, entry.path.clone(), entry.is_dir()))
+        .collect::<Vec<_>>();
+
+    Some(ContextMenu::build(window, cx, |mut menu, _, _| {
+        for (entry_id, path, is_dir) in entries {
+            let Some(name) = path.file_name() else {
+                continue;
+            };
+            let entry = ContextMenuEntry::new(name.to_string_lossy().to_string())
+                .icon(if is_dir {
+                    IconName::Folder
+                } else {
+                    IconName::File
+                })
+                .icon_position(IconPosition::Start)
+                .icon_color(if *path == *current {
+                    Color::Accent
+                } else {
+                    Color::Muted
+                })
+                .handler({
+                    let project = project.clone();
+                    let workspace = workspace.clone();
+                    move |window, cx| {
+                        if is_dir {
+                            project.update(cx, |_, cx| {
+                                cx.emit(project::Event::RevealInProjectPanel(entry_id))
+                            });
+                        } else {
+                            let project_path = ProjectPath {
+                                worktree_id,
+                                path: path.clone(),
+                            };
+                            workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace
+                                        .open_path(project_path, None, true, window, cx)
+                                        .detach_and_log_err(cx)
+                                })
+                                .log_err();
+                        }
+                    }
+                });
+            menu = menu.item(entry);
+        }
+        menu
+    }))
+}
+
+fn symbols_menu(
+    editor: &WeakEntity<Editor>,
+    start: Anchor,
+    depth: usize,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Entity<ContextMenu>> {
+    let editor = editor.upgrade()?;
+    let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+    let buffer = snapshot.buffer_for_excerpt(start.excerpt_id)?;
+    let outline = buffer.outline(None)?;
+    let start_offset = start.text_anchor.to_offset(buffer);
+    let current_ix = outline.items.iter().position(|item| {
+        item.depth == depth && item.range.start.to_offset(buffer) == start_offset
+    })?;
+    let depths = outline
+        .items
+        .iter()
+        .map(|item| item.depth)
+        .collect::<Vec<_>>();
+    let siblings = sibling_indices(&depths, current_ix)
+        .into_iter()
+        .filter_map(|ix| {
+            let item = &outline.items[ix];
+            let anchor = snapshot.anchor_in_excerpt(start.excerpt_id, item.range.start)?;
+            Some((item.text.clone(), anchor, ix == current_ix))
+        })
+        .collect::<Vec<_>>();
+
+    Some(ContextMenu::build(window, cx, |mut menu, _, _| {
+        for (text, anchor, is_current) in siblings {
+            let editor = editor.clone();
+            menu = menu.toggleable_entry(text, is_current, IconPosition::Start, None, {
+                move |window, cx| {
+                    editor.update(cx, |editor, cx| {
+                        editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                            s.select_anchor_ranges([anchor..anchor])
+                        });
+                        window.focus(&editor.focus_handle(cx));
+                    });
+                }
+            });
+        }
+        menu
+    }))
+}
+
+/// The indices of the outline items that have the same parent and depth as the one at
+/// `current_ix`, given the depth of every item in the outline.
+fn sibling_indices(depths: &[usize], current_ix: usize) -> Vec<usize> {
+    let depth = depths[current_ix];
+    let first_sibling_ix = depths[..current_ix]
+        .iter()
+        .rposition(|item_depth| *item_depth < depth)
+        .map_or(0, |parent_ix| parent_ix + 1);
+    (first_sibling_ix..depths.len())
+        .take_while(|ix| depths[*ix] >= depth)
+        .filter(|ix| depths[*ix] == depth)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use theme::ActiveTheme as _;
+    use util::path;
+    use workspace::{AppState, Workspace, item::Item as _};
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            state
+        })
+    }
+
+    #[test]
+    fn test_sibling_indices() {
+        // mod a {
+        //     fn b() {}
+        //     fn c() {
+        //         let d;
+        //     }
+        // }
+        // fn e() {}
+        let depths = [0, 1, 1, 2, 0];
+        assert_eq!(sibling_indices(&depths, 0), [0, 4]);
+        assert_eq!(sibling_indices(&depths, 2), [1, 2]);
+        assert_eq!(sibling_indices(&depths, 3), [3]);
+        assert_eq!(sibling_indices(&depths, 4), [0, 4]);
+    }
+
+    #[gpui::test]
+    async fn test_path_crumbs(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "src": {
+                    "lib": { "a.rs": "", "b.rs": "" },
+                    "main.rs": "",
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                let path = Path::new("src").join("lib").join("a.rs");
+                workspace.open_path((worktree_id, path), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+
+        let crumbs = cx.update(|_, cx| {
+            let segments = editor.read(cx).breadcrumbs(cx.theme(), cx).unwrap();
+            editor_crumbs(&editor, segments, cx)
+        });
+        assert_eq!(
+            crumbs
+                .iter()
+                .map(|crumb| (crumb.text.text.as_str(), crumb.is_file))
+                .collect::<Vec<_>>(),
+            [("src", false), ("lib", false), ("a.rs", true)]
+        );
+        let Some(CrumbMenu::Entries {
+            parent, current, ..
+        }) = &crumbs[1].menu
+        else {
+            panic!("expected the directory to list its siblings");
+        };
+        assert_eq!(parent.as_ref(), Path::new("src"));
+        assert_eq!(current.as_ref(), Path::new("src").join("lib"));
+    }
+}
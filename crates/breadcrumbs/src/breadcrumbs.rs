@@ -1,3 +1,6 @@
+mod breadcrumb_menus;
+
+use breadcrumb_menus::{Crumb, editor_crumbs};
 use editor::Editor;
 use gpui::{
     Context, Element, EventEmitter, Focusable, FontWeight, IntoElement, ParentElement, Render,
@@ -7,7 +10,7 @@ use itertools::Itertools;
 use settings::Settings;
 use std::cmp;
 use theme::ActiveTheme;
-use ui::{ButtonLike, ButtonStyle, Label, Tooltip, prelude::*};
+use ui::{ButtonLike, ButtonStyle, Label, PopoverMenu, Tooltip, prelude::*};
 use workspace::{
     TabBarSettings, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
     item::{BreadcrumbText, ItemEvent, ItemHandle},
@@ -51,28 +54,48 @@ impl Render for Breadcrumbs {
             return element;
         };
 
-        let Some(mut segments) = active_item.breadcrumbs(cx.theme(), cx) else {
+        let Some(segments) = active_item.breadcrumbs(cx.theme(), cx) else {
             return element;
         };
 
-        let prefix_end_ix = cmp::min(segments.len(), MAX_SEGMENTS / 2);
-        let suffix_start_ix = cmp::max(
-            prefix_end_ix,
-            segments.len().saturating_sub(MAX_SEGMENTS / 2),
-        );
+        let mut crumbs = match active_item.downcast::<Editor>() {
+            Some(editor) => editor_crumbs(&editor, segments, cx),
+            None => segments
+                .into_iter()
+                .enumerate()
+                .map(|(index, text)| Crumb {
+                    text,
+                    menu: None,
+                    is_file: index == 0,
+                })
+                .collect(),
+        };
+
+        let prefix_end_ix = cmp::min(crumbs.len(), MAX_SEGMENTS / 2);
+        let suffix_start_ix =
+            cmp::max(prefix_end_ix, crumbs.len().saturating_sub(MAX_SEGMENTS / 2));
 
         if suffix_start_ix > prefix_end_ix {
-            segments.splice(
+            crumbs.splice(
                 prefix_end_ix..suffix_start_ix,
-                Some(BreadcrumbText {
-                    text: "⋯".into(),
-                    highlights: None,
-                    font: None,
+                Some(Crumb {
+                    text: BreadcrumbText {
+                        text: "⋯".into(),
+                        highlights: None,
+                        font: None,
+                    },
+                    menu: None,
+                    is_file: false,
                 }),
             );
         }
 
-        let highlighted_segments = segments.into_iter().enumerate().map(|(index, segment)| {
+        let highlighted_segments = crumbs.into_iter().enumerate().map(|(index, crumb)| {
+            let Crumb {
+                text: segment,
+                menu,
+                is_file,
+            } = crumb;
             let mut text_style = window.text_style();
             if let Some(ref font) = segment.font {
                 text_style.font_family = font.family.clone();
@@ -82,16 +105,29 @@ impl Render for Breadcrumbs {
             }
             text_style.color = Color::Muted.color(cx);
 
-            if index == 0 && !TabBarSettings::get_global(cx).show && active_item.is_dirty(cx) {
-                if let Some(styled_element) = apply_dirty_filename_style(&segment, &text_style, cx)
-                {
-                    return styled_element;
-                }
-            }
+            let text =
+                if is_file && !TabBarSettings::get_global(cx).show && active_item.is_dirty(cx) {
+                    apply_dirty_filename_style(&segment, &text_style, cx)
+                } else {
+                    None
+                };
+            let text = text.unwrap_or_else(|| {
+                StyledText::new(segment.text.replace('\n', "⏎"))
+                    .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
+                    .into_any()
+            });
 
-            StyledText::new(segment.text.replace('\n', "⏎"))
-                .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
-                .into_any()
+            match menu {
+                Some(menu) => PopoverMenu::new(("breadcrumb-menu", index))
+                    .trigger(
+                        ButtonLike::new(("breadcrumb", index))
+                            .style(ButtonStyle::Transparent)
+                            .child(text),
+                    )
+                    .menu(move |window, cx| menu.build(window, cx))
+                    .into_any_element(),
+                None => text,
+            }
         });
         let breadcrumbs = Itertools::intersperse_with(highlighted_segments, || {
             Label::new("›").color(Color::Placeholder).into_any_element()
@@ -1,5 +1,6 @@
 pub mod participant;
 pub mod room;
+pub mod screen_annotations;
 
 use crate::call_settings::CallSettings;
 use anyhow::{Context as _, Result, anyhow};
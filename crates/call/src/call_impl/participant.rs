@@ -1,7 +1,7 @@
 use anyhow::{Context as _, Result};
 use client::{ParticipantIndex, User, proto};
 use collections::HashMap;
-use gpui::WeakEntity;
+use gpui::{SharedString, WeakEntity};
 use livekit_client::AudioStream;
 use project::Project;
 use std::sync::Arc;
@@ -33,6 +33,20 @@ impl ParticipantLocation {
     }
 }
 
+/// What a participant is doing right now, as they report it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParticipantActivity {
+    pub typing: bool,
+    /// The label of a task the participant is running.
+    pub running_task: Option<SharedString>,
+}
+
+impl ParticipantActivity {
+    pub fn is_idle(&self) -> bool {
+        !self.typing && self.running_task.is_none()
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct LocalParticipant {
     pub projects: Vec<proto::ParticipantProject>,
@@ -58,6 +72,7 @@ pub struct RemoteParticipant {
     pub participant_index: ParticipantIndex,
     pub muted: bool,
     pub speaking: bool,
+    pub activity: ParticipantActivity,
     pub video_tracks: HashMap<TrackSid, RemoteVideoTrack>,
     pub audio_tracks: HashMap<TrackSid, (RemoteAudioTrack, AudioStream)>,
 }
@@ -1,6 +1,7 @@
 use crate::{
     call_settings::CallSettings,
-    participant::{LocalParticipant, ParticipantLocation, RemoteParticipant},
+    participant::{LocalParticipant, ParticipantActivity, ParticipantLocation, RemoteParticipant},
+    screen_annotations::{self, ScreenAnnotations, StrokeKind},
 };
 use anyhow::{Context as _, Result, anyhow};
 use audio::{Audio, Sound};
@@ -11,7 +12,10 @@ use client::{
 use collections::{BTreeMap, HashMap, HashSet};
 use fs::Fs;
 use futures::{FutureExt, StreamExt};
-use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Task, WeakEntity};
+use gpui::{
+    App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Point, SharedString, Task,
+    WeakEntity,
+};
 use gpui_tokio::Tokio;
 use language::LanguageRegistry;
 use livekit::{LocalTrackPublication, ParticipantIdentity, RoomEvent};
@@ -19,10 +23,21 @@ use livekit_client::{self as livekit, TrackSid};
 use postage::{sink::Sink, stream::Stream, watch};
 use project::Project;
 use settings::Settings as _;
-use std::{any::Any, future::Future, mem, rc::Rc, sync::Arc, time::Duration};
+use std::{
+    any::Any,
+    future::Future,
+    mem,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use util::{ResultExt, TryFutureExt, post_inc};
 
 pub const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Limits how often the presenter's pointer position is sent to the room.
+const LASER_POINTER_THROTTLE: Duration = Duration::from_millis(33);
+/// How long after the last keystroke the local participant stops being shown as typing.
+const TYPING_TIMEOUT: Duration = Duration::from_secs(3);
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Event {
@@ -41,6 +56,9 @@ pub enum Event {
     RemoteAudioTracksChanged {
         participant_id: proto::PeerId,
     },
+    ScreenAnnotationsChanged {
+        presenter_id: proto::PeerId,
+    },
     RemoteProjectShared {
         owner: Arc<User>,
         project_id: u64,
@@ -76,6 +94,14 @@ pub struct Room {
     client: Arc<Client>,
     user_store: Entity<UserStore>,
     follows_by_leader_id_project_id: HashMap<(PeerId, u64), Vec<PeerId>>,
+    screen_annotations: HashMap<PeerId, ScreenAnnotations>,
+    next_annotation_stroke_id: u64,
+    laser_pointer: bool,
+    laser_pointer_sent_at: Option<Instant>,
+    activity: ParticipantActivity,
+    running_tasks: Vec<(usize, SharedString)>,
+    next_running_task_id: usize,
+    stop_typing: Option<Task<()>>,
     client_subscriptions: Vec<client::Subscription>,
     _subscriptions: Vec<gpui::Subscription>,
     room_update_completed_tx: watch::Sender<Option<()>>,
@@ -140,6 +166,12 @@ impl Room {
             pending_call_count: 0,
             client_subscriptions: vec![
                 client.add_message_handler(cx.weak_entity(), Self::handle_room_updated),
+                client
+                    .add_message_handler(cx.weak_entity(), Self::handle_update_screen_annotations),
+                client.add_message_handler(
+                    cx.weak_entity(),
+                    Self::handle_update_participant_activity,
+                ),
             ],
             _subscriptions: vec![
                 cx.on_release(Self::released),
@@ -150,6 +182,14 @@ impl Room {
             client,
             user_store,
             follows_by_leader_id_project_id: Default::default(),
+            screen_annotations: Default::default(),
+            next_annotation_stroke_id: 0,
+            laser_pointer: false,
+            laser_pointer_sent_at: None,
+            activity: Default::default(),
+            running_tasks: Vec::new(),
+            next_running_task_id: 0,
+            stop_typing: None,
             maintain_connection: Some(maintain_connection),
             room_update_completed_tx,
             room_update_completed_rx,
@@ -363,6 +403,11 @@ impl Room {
                     .context("room was dropped")?
                     .update(cx, |this, cx| {
                         this.status = RoomStatus::Rejoining;
+                        for project in &this.joined_projects {
+                            if let Some(project) = project.upgrade() {
+                                project.update(cx, |project, cx| project.connection_lost(cx));
+                            }
+                        }
                         cx.notify();
                     })?;
 
@@ -426,7 +471,17 @@ impl Room {
         // we leave the room and return an error.
         if let Some(this) = this.upgrade() {
             log::info!("reconnection failed, leaving room");
-            this.update(cx, |this, cx| this.leave(cx))?.await?;
+            this.update(cx, |this, cx| {
+                // Keep joined projects open rather than closing their windows, so
+                // guests can still see edits that didn't reach the host.
+                for project in this.joined_projects.drain() {
+                    if let Some(project) = project.upgrade() {
+                        project.update(cx, |project, cx| project.disconnected_from_host(cx));
+                    }
+                }
+                this.leave(cx)
+            })?
+            .await?;
         }
         anyhow::bail!("can't reconnect to room: client failed to re-establish connection");
     }
@@ -822,12 +877,17 @@ impl Room {
                                     role,
                                     muted: true,
                                     speaking: false,
+                                    activity: Default::default(),
                                     video_tracks: Default::default(),
                                     audio_tracks: Default::default(),
                                 },
                             );
 
                             Audio::play_sound(Sound::Joined, cx);
+                            // Let the newcomer know what we're in the middle of.
+                            if !this.activity.is_idle() {
+                                this.send_activity();
+                            }
                             if let Some(livekit_participants) = &livekit_participants {
                                 if let Some(livekit_participant) = livekit_participants
                                     .get(&ParticipantIdentity(user.id.to_string()))
@@ -1485,8 +1545,247 @@ impl Room {
         }
     }
 
+    /// Shows the local participant as typing until they stop for a few seconds.
+    pub fn report_typing(&mut self, cx: &mut Context<Self>) {
+        self.stop_typing = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(TYPING_TIMEOUT).await;
+            this.update(cx, |this, cx| {
+                this.stop_typing = None;
+                this.update_activity(cx);
+            })
+            .ok();
+        }));
+        self.update_activity(cx);
+    }
+
+    /// Shows the local participant as running the task until [`Self::task_finished`]
+    /// is called with the returned id.
+    pub fn task_started(&mut self, label: SharedString, cx: &mut Context<Self>) -> usize {
+        let id = post_inc(&mut self.next_running_task_id);
+        self.running_tasks.push((id, label));
+        self.update_activity(cx);
+        id
+    }
+
+    pub fn task_finished(&mut self, id: usize, cx: &mut Context<Self>) {
+        self.running_tasks.retain(|(task_id, _)| *task_id != id);
+        self.update_activity(cx);
+    }
+
+    pub fn local_activity(&self) -> &ParticipantActivity {
+        &self.activity
+    }
+
+    fn update_activity(&mut self, cx: &mut Context<Self>) {
+        let activity = ParticipantActivity {
+            typing: self.stop_typing.is_some(),
+            running_task: self.running_tasks.last().map(|(_, label)| label.clone()),
+        };
+        if activity != self.activity {
+            self.activity = activity;
+            self.send_activity();
+            cx.notify();
+        }
+    }
+
+    fn send_activity(&self) {
+        if self.status.is_offline() {
+            return;
+        }
+        self.client
+            .send(proto::UpdateParticipantActivity {
+                room_id: self.id,
+                typing: self.activity.typing,
+                running_task: self
+                    .activity
+                    .running_task
+                    .as_ref()
+                    .map(|label| redaction::redact(label).into_owned()),
+            })
+            .log_err();
+    }
+
+    async fn handle_update_participant_activity(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateParticipantActivity>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let peer_id = envelope.original_sender_id()?;
+        this.update(&mut cx, |this, cx| {
+            if let Some(participant) = this
+                .remote_participants
+                .values_mut()
+                .find(|participant| participant.peer_id == peer_id)
+            {
+                participant.activity = ParticipantActivity {
+                    typing: envelope.payload.typing,
+                    running_task: envelope.payload.running_task.map(SharedString::from),
+                };
+                cx.notify();
+            }
+        })
+    }
+
+    pub fn screen_annotations(&self, presenter_id: PeerId) -> Option<&ScreenAnnotations> {
+        self.screen_annotations.get(&presenter_id)
+    }
+
+    pub fn next_annotation_stroke_id(&mut self) -> u64 {
+        post_inc(&mut self.next_annotation_stroke_id)
+    }
+
+    /// Adds points to a stroke drawn over a participant's shared screen, for
+    /// everyone watching it.
+    pub fn annotate_screen(
+        &mut self,
+        presenter_id: PeerId,
+        stroke_id: u64,
+        kind: StrokeKind,
+        points: Vec<Point<f32>>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(author) = self.client.peer_id() else {
+            return;
+        };
+        self.send_screen_annotation(
+            presenter_id,
+            proto::update_screen_annotations::Variant::Stroke(proto::ScreenAnnotationStroke {
+                id: stroke_id,
+                kind: screen_annotations::stroke_kind_to_proto(kind) as i32,
+                points: points
+                    .iter()
+                    .copied()
+                    .map(screen_annotations::point_to_proto)
+                    .collect(),
+            }),
+        );
+        self.screen_annotations
+            .entry(presenter_id)
+            .or_default()
+            .extend_stroke(author, stroke_id, kind, points, Instant::now());
+        cx.emit(Event::ScreenAnnotationsChanged { presenter_id });
+    }
+
+    /// Removes the strokes the current user drew over a participant's shared
+    /// screen, or everyone's when it's their own screen.
+    pub fn clear_screen_annotations(&mut self, presenter_id: PeerId, cx: &mut Context<Self>) {
+        let Some(author) = self.client.peer_id() else {
+            return;
+        };
+        self.send_screen_annotation(
+            presenter_id,
+            proto::update_screen_annotations::Variant::Clear(proto::ClearScreenAnnotations {}),
+        );
+        if let Some(annotations) = self.screen_annotations.get_mut(&presenter_id) {
+            annotations.clear(author, presenter_id);
+        }
+        cx.emit(Event::ScreenAnnotationsChanged { presenter_id });
+    }
+
+    pub fn is_laser_pointer_enabled(&self) -> bool {
+        self.laser_pointer
+    }
+
+    pub fn toggle_laser_pointer(&mut self, cx: &mut Context<Self>) {
+        self.laser_pointer = !self.laser_pointer;
+        if !self.laser_pointer {
+            self.set_laser_pointer_position(None, cx);
+        }
+        cx.notify();
+    }
+
+    /// Shows where the presenter's pointer is to everyone watching their
+    /// screen, as fractions of the screen's size.
+    pub fn set_laser_pointer_position(
+        &mut self,
+        position: Option<Point<f32>>,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.is_screen_sharing() || (position.is_some() && !self.laser_pointer) {
+            return;
+        }
+        let now = Instant::now();
+        if position.is_some()
+            && self
+                .laser_pointer_sent_at
+                .is_some_and(|sent_at| now.duration_since(sent_at) < LASER_POINTER_THROTTLE)
+        {
+            return;
+        }
+        let Some(presenter_id) = self.client.peer_id() else {
+            return;
+        };
+        self.laser_pointer_sent_at = Some(now);
+        self.send_screen_annotation(
+            presenter_id,
+            proto::update_screen_annotations::Variant::Pointer(proto::ScreenAnnotationPointer {
+                position: position.map(screen_annotations::point_to_proto),
+            }),
+        );
+        cx.emit(Event::ScreenAnnotationsChanged { presenter_id });
+    }
+
+    fn send_screen_annotation(
+        &self,
+        presenter_id: PeerId,
+        variant: proto::update_screen_annotations::Variant,
+    ) {
+        self.client
+            .send(proto::UpdateScreenAnnotations {
+                room_id: self.id,
+                presenter_id: Some(presenter_id),
+                variant: Some(variant),
+            })
+            .log_err();
+    }
+
+    async fn handle_update_screen_annotations(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateScreenAnnotations>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let author = envelope.original_sender_id()?;
+        let presenter_id = envelope
+            .payload
+            .presenter_id
+            .context("missing presenter id")?;
+        let variant = envelope.payload.variant.context("missing annotation")?;
+        this.update(&mut cx, |this, cx| {
+            let annotations = this.screen_annotations.entry(presenter_id).or_default();
+            let now = Instant::now();
+            match variant {
+                proto::update_screen_annotations::Variant::Stroke(stroke) => {
+                    let kind = screen_annotations::stroke_kind_from_proto(stroke.kind());
+                    let points = stroke
+                        .points
+                        .iter()
+                        .map(screen_annotations::point_from_proto);
+                    annotations.extend_stroke(author, stroke.id, kind, points, now);
+                }
+                proto::update_screen_annotations::Variant::Pointer(pointer) => {
+                    // Only the presenter can move the laser pointer on their screen.
+                    if author == presenter_id {
+                        let position = pointer
+                            .position
+                            .as_ref()
+                            .map(screen_annotations::point_from_proto);
+                        annotations.set_pointer(position, now);
+                    }
+                }
+                proto::update_screen_annotations::Variant::Clear(_) => {
+                    annotations.clear(author, presenter_id);
+                }
+            }
+            cx.emit(Event::ScreenAnnotationsChanged { presenter_id });
+        })
+    }
+
     pub fn unshare_screen(&mut self, cx: &mut Context<Self>) -> Result<()> {
         anyhow::ensure!(!self.status.is_offline(), "room is offline");
+        if let Some(presenter_id) = self.client.peer_id() {
+            self.set_laser_pointer_position(None, cx);
+            self.clear_screen_annotations(presenter_id, cx);
+        }
 
         let live_kit = self
             .live_kit
@@ -0,0 +1,164 @@
+use std::time::{Duration, Instant};
+
+use client::proto::{self, PeerId};
+use gpui::{Point, point};
+
+/// How long a stroke stays on screen after it was last drawn to.
+pub const STROKE_LIFETIME: Duration = Duration::from_secs(4);
+/// Strokes fade out over the end of their lifetime.
+pub const STROKE_FADE: Duration = Duration::from_secs(1);
+/// How long the laser pointer stays on screen after the presenter's pointer stops moving.
+pub const POINTER_LIFETIME: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrokeKind {
+    Pen,
+    /// An arrow from the stroke's first point to its last.
+    Arrow,
+}
+
+#[derive(Clone, Debug)]
+pub struct AnnotationStroke {
+    pub author: PeerId,
+    pub id: u64,
+    pub kind: StrokeKind,
+    /// Positions as fractions of the shared screen's width and height.
+    pub points: Vec<Point<f32>>,
+    pub updated_at: Instant,
+}
+
+impl AnnotationStroke {
+    /// How visible the stroke still is, from 1 down to 0 once it has expired.
+    pub fn opacity(&self, now: Instant) -> f32 {
+        let remaining = STROKE_LIFETIME.saturating_sub(now.duration_since(self.updated_at));
+        (remaining.as_secs_f32() / STROKE_FADE.as_secs_f32()).min(1.)
+    }
+}
+
+/// The annotations drawn over one participant's shared screen.
+#[derive(Debug, Default)]
+pub struct ScreenAnnotations {
+    strokes: Vec<AnnotationStroke>,
+    pointer: Option<(Point<f32>, Instant)>,
+}
+
+impl ScreenAnnotations {
+    pub fn strokes(&self, now: Instant) -> impl Iterator<Item = &AnnotationStroke> {
+        self.strokes
+            .iter()
+            .filter(move |stroke| stroke.opacity(now) > 0.)
+    }
+
+    /// Where the presenter's pointer is, if they moved it recently.
+    pub fn pointer(&self, now: Instant) -> Option<Point<f32>> {
+        self.pointer
+            .filter(|(_, updated_at)| now.duration_since(*updated_at) < POINTER_LIFETIME)
+            .map(|(position, _)| position)
+    }
+
+    pub fn is_empty(&self, now: Instant) -> bool {
+        self.strokes(now).next().is_none() && self.pointer(now).is_none()
+    }
+
+    pub(crate) fn extend_stroke(
+        &mut self,
+        author: PeerId,
+        id: u64,
+        kind: StrokeKind,
+        points: impl IntoIterator<Item = Point<f32>>,
+        now: Instant,
+    ) {
+        self.strokes.retain(|stroke| stroke.opacity(now) > 0.);
+        let ix = match self
+            .strokes
+            .iter()
+            .position(|stroke| stroke.author == author && stroke.id == id)
+        {
+            Some(ix) => ix,
+            None => {
+                self.strokes.push(AnnotationStroke {
+                    author,
+                    id,
+                    kind,
+                    points: Vec::new(),
+                    updated_at: now,
+                });
+                self.strokes.len() - 1
+            }
+        };
+        let stroke = &mut self.strokes[ix];
+        stroke.points.extend(points);
+        stroke.updated_at = now;
+    }
+
+    pub(crate) fn set_pointer(&mut self, position: Option<Point<f32>>, now: Instant) {
+        self.pointer = position.map(|position| (position, now));
+    }
+
+    /// Removes the strokes drawn by `author`, or all of them when the
+    /// presenter clears their own screen.
+    pub(crate) fn clear(&mut self, author: PeerId, presenter_id: PeerId) {
+        if author == presenter_id {
+            self.strokes.clear();
+        } else {
+            self.strokes.retain(|stroke| stroke.author != author);
+        }
+    }
+}
+
+pub(crate) fn stroke_kind_to_proto(kind: StrokeKind) -> proto::screen_annotation_stroke::Kind {
+    match kind {
+        StrokeKind::Pen => proto::screen_annotation_stroke::Kind::Pen,
+        StrokeKind::Arrow => proto::screen_annotation_stroke::Kind::Arrow,
+    }
+}
+
+pub(crate) fn stroke_kind_from_proto(kind: proto::screen_annotation_stroke::Kind) -> StrokeKind {
+    match kind {
+        proto::screen_annotation_stroke::Kind::Pen => StrokeKind::Pen,
+        proto::screen_annotation_stroke::Kind::Arrow => StrokeKind::Arrow,
+    }
+}
+
+pub(crate) fn point_to_proto(position: Point<f32>) -> proto::ScreenAnnotationPoint {
+    proto::ScreenAnnotationPoint {
+        x: position.x,
+        y: position.y,
+    }
+}
+
+pub(crate) fn point_from_proto(position: &proto::ScreenAnnotationPoint) -> Point<f32> {
+    point(position.x.clamp(0., 1.), position.y.clamp(0., 1.))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strokes_expire() {
+        let presenter = PeerId { owner_id: 0, id: 1 };
+        let viewer = PeerId { owner_id: 0, id: 2 };
+        let start = Instant::now();
+        let mut annotations = ScreenAnnotations::default();
+
+        annotations.extend_stroke(viewer, 0, StrokeKind::Pen, [point(0.1, 0.1)], start);
+        annotations.extend_stroke(viewer, 0, StrokeKind::Pen, [point(0.2, 0.2)], start);
+        annotations.extend_stroke(presenter, 0, StrokeKind::Arrow, [point(0.5, 0.5)], start);
+        assert_eq!(annotations.strokes(start).count(), 2);
+        assert_eq!(
+            annotations.strokes(start).next().unwrap().points,
+            vec![point(0.1, 0.1), point(0.2, 0.2)]
+        );
+
+        let fading = start + STROKE_LIFETIME - STROKE_FADE / 2;
+        let stroke = annotations.strokes(fading).next().unwrap();
+        assert!((stroke.opacity(fading) - 0.5).abs() < 0.01);
+        assert!(annotations.is_empty(start + STROKE_LIFETIME));
+
+        annotations.clear(viewer, presenter);
+        assert_eq!(annotations.strokes(start).count(), 1);
+        annotations.clear(presenter, presenter);
+        assert!(annotations.is_empty(start));
+    }
+}
@@ -0,0 +1,408 @@
+use std::sync::{Arc, LazyLock};
+
+use editor::Editor;
+use fuzzy::{StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
+    Render, Styled, Task, WeakEntity, Window, actions,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+actions!(character_picker, [Toggle]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(CharacterPicker::register).detach();
+}
+
+/// A character that can be searched by name.
+struct Character {
+    text: String,
+    /// The name searched, with the shortcode of emojis, like `grinning face :grinning:`.
+    name: String,
+}
+
+static EMOJIS: LazyLock<Vec<Character>> = LazyLock::new(|| {
+    emojis::iter()
+        .map(|emoji| Character {
+            text: emoji.as_str().to_string(),
+            name: match emoji.shortcode() {
+                Some(shortcode) => format!("{} :{shortcode}:", emoji.name()),
+                None => emoji.name().to_string(),
+            },
+        })
+        .collect()
+});
+
+/// The characters with a Unicode name that aren't emojis. The characters whose name is only
+/// their code point, like `CJK UNIFIED IDEOGRAPH-4E00`, are searched by code point instead.
+static NAMED_CHARACTERS: LazyLock<Vec<Character>> = LazyLock::new(|| {
+    const NUMBERED_NAME_PREFIXES: &[&str] = &[
+        "CJK UNIFIED IDEOGRAPH-",
+        "CJK COMPATIBILITY IDEOGRAPH-",
+        "HANGUL SYLLABLE ",
+        "TANGUT IDEOGRAPH-",
+        "TANGUT COMPONENT-",
+        "KHITAN SMALL SCRIPT CHARACTER-",
+        "NUSHU CHARACTER-",
+    ];
+    (0..=char::MAX as u32)
+        .filter_map(char::from_u32)
+        .filter_map(|c| {
+            let name = unicode_names2::name(c)?.to_string();
+            if NUMBERED_NAME_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+            {
+                return None;
+            }
+            let text = c.to_string();
+            if emojis::get(&text).is_some() {
+                return None;
+            }
+            Some(Character {
+                text,
+                name: name.to_lowercase(),
+            })
+        })
+        .collect()
+});
+
+/// The candidates of both lists, whose ids index the emojis, then the named characters.
+static CANDIDATES: LazyLock<Vec<StringMatchCandidate>> = LazyLock::new(|| {
+    EMOJIS
+        .iter()
+        .chain(NAMED_CHARACTERS.iter())
+        .enumerate()
+        .map(|(id, character)| StringMatchCandidate::new(id, &character.name))
+        .collect()
+});
+
+fn character(id: usize) -> &'static Character {
+    match id.checked_sub(EMOJIS.len()) {
+        Some(ix) => &NAMED_CHARACTERS[ix],
+        None => &EMOJIS[id],
+    }
+}
+
+/// Parses a code point typed as `U+00E9` or `0xE9`.
+fn parse_code_point(query: &str) -> Option<char> {
+    let hex = query
+        .strip_prefix("U+")
+        .or_else(|| query.strip_prefix("u+"))
+        .or_else(|| query.strip_prefix("0x"))?;
+    char::from_u32(u32::from_str_radix(hex, 16).ok()?)
+}
+
+fn code_points(text: &str) -> String {
+    text.chars()
+        .map(|c| format!("U+{:04X}", c as u32))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Searches emojis and Unicode characters by name or code point, and inserts the one picked at
+/// every cursor of the editor.
+pub struct CharacterPicker {
+    picker: Entity<Picker<CharacterPickerDelegate>>,
+}
+
+impl CharacterPicker {
+    fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+        let handle = cx.entity().downgrade();
+        editor
+            .register_action(move |_: &Toggle, window, cx| {
+                let Some(editor) = handle.upgrade() else {
+                    return;
+                };
+                let Some(workspace) = editor.read(cx).workspace() else {
+                    return;
+                };
+                workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, move |window, cx| {
+                        CharacterPicker::new(editor, window, cx)
+                    });
+                })
+            })
+            .detach();
+    }
+
+    fn new(editor: Entity<Editor>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let delegate = CharacterPickerDelegate::new(cx.entity().downgrade(), editor);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for CharacterPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for CharacterPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for CharacterPicker {}
+impl ModalView for CharacterPicker {}
+
+struct CharacterMatch {
+    text: String,
+    name: String,
+    positions: Vec<usize>,
+}
+
+pub struct CharacterPickerDelegate {
+    character_picker: WeakEntity<CharacterPicker>,
+    editor: Entity<Editor>,
+    matches: Vec<CharacterMatch>,
+    selected_index: usize,
+}
+
+impl CharacterPickerDelegate {
+    fn new(character_picker: WeakEntity<CharacterPicker>, editor: Entity<Editor>) -> Self {
+        Self {
+            character_picker,
+            editor,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for CharacterPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search characters by name, or type a code point like U+00E9…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(character_match) = self.matches.get(self.selected_index) {
+            self.editor.update(cx, |editor, cx| {
+                editor.insert(&character_match.text, window, cx);
+                editor.focus_handle(cx).focus(window);
+            });
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.character_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let query = query.trim().to_string();
+            let matches = if let Some(c) = parse_code_point(&query) {
+                vec![CharacterMatch {
+                    text: c.to_string(),
+                    name: unicode_names2::name(c)
+                        .map(|name| name.to_string().to_lowercase())
+                        .unwrap_or_default(),
+                    positions: Vec::new(),
+                }]
+            } else if query.is_empty() {
+                // Only the emojis are listed before searching, so that the Unicode names don't
+                // have to be loaded to open the picker.
+                let emojis = background.spawn(async { &*EMOJIS }).await;
+                emojis
+                    .iter()
+                    .map(|emoji| CharacterMatch {
+                        text: emoji.text.clone(),
+                        name: emoji.name.clone(),
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = background.spawn(async { &*CANDIDATES }).await;
+                match_strings(
+                    candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+                .into_iter()
+                .map(|string_match| {
+                    let character = character(string_match.candidate_id);
+                    CharacterMatch {
+                        text: character.text.clone(),
+                        name: character.name.clone(),
+                        positions: string_match.positions,
+                    }
+                })
+                .collect()
+            };
+
+            this.update(cx, |this, cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = 0;
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let character_match = &self.matches[ix];
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(
+                    div()
+                        .w(rems(1.5))
+                        .child(Label::new(character_match.text.clone())),
+                )
+                .child(
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            character_match.name.clone(),
+                            character_match.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(code_points(&character_match.text))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::actions::AddSelectionBelow;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use util::path;
+    use workspace::{AppState, Workspace};
+
+    #[test]
+    fn test_parse_code_point() {
+        assert_eq!(parse_code_point("U+00E9"), Some('é'));
+        assert_eq!(parse_code_point("u+1f600"), Some('😀'));
+        assert_eq!(parse_code_point("0x41"), Some('A'));
+        assert_eq!(parse_code_point("U+D800"), None);
+        assert_eq!(parse_code_point("e9"), None);
+        assert_eq!(code_points("e\u{301}"), "U+0065 U+0301");
+    }
+
+    #[gpui::test]
+    async fn test_insert_code_point_at_every_cursor(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "a.txt": "cafe\ncafe\n" }))
+            .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, "a.txt"), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(None, window, cx, |s| s.select_ranges([3..4]));
+            editor.add_selection_below(&AddSelectionBelow, window, cx);
+        });
+
+        let picker = open_picker(&workspace, cx);
+        cx.simulate_input("U+00E9");
+        picker.update(cx, |picker, _| {
+            assert_eq!(picker.delegate.matches.len(), 1);
+            assert_eq!(
+                picker.delegate.matches[0].name,
+                "latin small letter e with acute"
+            );
+        });
+        cx.dispatch_action(menu::Confirm);
+        assert_eq!(
+            editor.update(cx, |editor, cx| editor.text(cx)),
+            "café\ncafé\n"
+        );
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<CharacterPicker>(cx).is_none());
+        });
+    }
+
+    fn open_picker(
+        workspace: &Entity<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> Entity<Picker<CharacterPickerDelegate>> {
+        cx.dispatch_action(Toggle);
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<CharacterPicker>(cx)
+                .expect("the character picker should be open")
+                .read(cx)
+                .picker
+                .clone()
+        })
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            language::init(cx);
+            crate::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            state
+        })
+    }
+}
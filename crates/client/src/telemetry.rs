@@ -416,7 +416,15 @@ impl Telemetry {
         }
     }
 
-    fn report_event(self: &Arc<Self>, event: Event) {
+    fn report_event(self: &Arc<Self>, mut event: Event) {
+        // Event properties can hold text that was typed or run, like a task's command.
+        if let Event::Flexible(event) = &mut event {
+            let redactor = redaction::current();
+            for value in event.event_properties.values_mut() {
+                redactor.redact_json(value);
+            }
+        }
+
         let mut state = self.state.lock();
         // RUST_LOG=telemetry=trace to debug telemetry events
         log::trace!(target: "telemetry", "{:?}", event);
@@ -0,0 +1,241 @@
+use std::sync::Arc;
+
+use editor::{ClipboardHistory, Editor};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, Focusable, ParentElement, Render, Styled,
+    Task, WeakEntity, Window, actions,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::{ResultExt, truncate_and_trailoff};
+use workspace::{ModalView, Workspace};
+
+actions!(clipboard_history, [Toggle]);
+
+const MAX_LABEL_LEN: usize = 120;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(ClipboardHistoryPicker::register).detach();
+}
+
+/// Lists the text recently cut or copied in editors, pasting the chosen entry into the active
+/// editor.
+pub struct ClipboardHistoryPicker {
+    picker: Entity<Picker<ClipboardHistoryDelegate>>,
+}
+
+impl ClipboardHistoryPicker {
+    fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(move |workspace, _: &Toggle, window, cx| {
+            Self::toggle(workspace, window, cx);
+        });
+    }
+
+    fn toggle(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Option<()> {
+        let editor = workspace.active_item(cx)?.act_as::<Editor>(cx)?.downgrade();
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            ClipboardHistoryPicker::new(editor, window, cx)
+        });
+        Some(())
+    }
+
+    fn new(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let delegate = ClipboardHistoryDelegate::new(cx.entity().downgrade(), editor, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl ModalView for ClipboardHistoryPicker {}
+
+impl EventEmitter<DismissEvent> for ClipboardHistoryPicker {}
+
+impl Focusable for ClipboardHistoryPicker {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for ClipboardHistoryPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+pub struct ClipboardHistoryDelegate {
+    clipboard_history_picker: WeakEntity<ClipboardHistoryPicker>,
+    editor: WeakEntity<Editor>,
+    /// One candidate per history entry, whose id is the entry's position in the history.
+    candidates: Vec<StringMatchCandidate>,
+    line_counts: Vec<usize>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl ClipboardHistoryDelegate {
+    fn new(
+        clipboard_history_picker: WeakEntity<ClipboardHistoryPicker>,
+        editor: WeakEntity<Editor>,
+        cx: &App,
+    ) -> Self {
+        let mut candidates = Vec::new();
+        let mut line_counts = Vec::new();
+        if let Some(history) = cx.try_global::<ClipboardHistory>() {
+            for (ix, item) in history.entries().enumerate() {
+                let text = item.text().unwrap_or_default();
+                let label = text
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ⏎ ");
+                candidates.push(StringMatchCandidate::new(
+                    ix,
+                    &truncate_and_trailoff(&label, MAX_LABEL_LEN),
+                ));
+                line_counts.push(text.lines().count());
+            }
+        }
+
+        Self {
+            clipboard_history_picker,
+            editor,
+            candidates,
+            line_counts,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for ClipboardHistoryDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _: &mut App) -> Arc<str> {
+        "Paste from clipboard history...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let index = mat.candidate_id;
+            self.editor
+                .update(cx, |editor, cx| {
+                    editor.paste_from_history(index, window, cx)
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.clipboard_history_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                let mut matches = match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await;
+                // Keep the most recent entries first, as they are the likeliest to be wanted.
+                matches.sort_unstable_by_key(|mat| mat.candidate_id);
+                matches
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = &self.matches[ix];
+        let line_count = self.line_counts[mat.candidate_id];
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_x_2()
+                        .justify_between()
+                        .child(HighlightedLabel::new(
+                            mat.string.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .when(line_count > 1, |item| {
+                            item.child(
+                                Label::new(format!("{line_count} lines"))
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small),
+                            )
+                        }),
+                ),
+        )
+    }
+}
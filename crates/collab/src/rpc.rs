@@ -392,6 +392,8 @@ impl Server {
             .add_request_handler(follow)
             .add_message_handler(unfollow)
             .add_message_handler(update_followers)
+            .add_message_handler(update_screen_annotations)
+            .add_message_handler(update_participant_activity)
             .add_request_handler(get_private_user_info)
             .add_request_handler(get_llm_api_token)
             .add_request_handler(accept_terms_of_service)
@@ -413,6 +415,17 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::SetIndexText>)
             .add_request_handler(forward_mutating_project_request::<proto::ToggleBreakpoint>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BreakpointsForFile>)
+            .add_request_handler(forward_read_only_project_request::<proto::CreateCommentThread>)
+            .add_request_handler(forward_read_only_project_request::<proto::ReplyToCommentThread>)
+            .add_request_handler(
+                forward_read_only_project_request::<proto::SetCommentThreadResolved>,
+            )
+            .add_message_handler(
+                broadcast_project_message_from_host::<proto::CommentThreadsForFile>,
+            )
+            .add_message_handler(
+                broadcast_project_message_from_host::<proto::UpdateGuestPermissions>,
+            )
             .add_request_handler(forward_mutating_project_request::<proto::OpenCommitMessageBuffer>)
             .add_request_handler(forward_mutating_project_request::<proto::GitDiff>)
             .add_request_handler(forward_mutating_project_request::<proto::GitCreateBranch>)
@@ -2500,6 +2513,44 @@ async fn update_followers(request: proto::UpdateFollowers, session: Session) ->
     Ok(())
 }
 
+/// Forward annotations on a shared screen to everyone else in the room.
+async fn update_screen_annotations(
+    request: proto::UpdateScreenAnnotations,
+    session: Session,
+) -> Result<()> {
+    let room_id = RoomId::from_proto(request.room_id);
+    let connection_ids = session
+        .db()
+        .await
+        .room_connection_ids(room_id, session.connection_id)
+        .await?;
+    for connection_id in connection_ids.iter().copied() {
+        session
+            .peer
+            .forward_send(session.connection_id, connection_id, request.clone())?;
+    }
+    Ok(())
+}
+
+/// Tell other participants in the room what the sender is doing.
+async fn update_participant_activity(
+    request: proto::UpdateParticipantActivity,
+    session: Session,
+) -> Result<()> {
+    let room_id = RoomId::from_proto(request.room_id);
+    let connection_ids = session
+        .db()
+        .await
+        .room_connection_ids(room_id, session.connection_id)
+        .await?;
+    for connection_id in connection_ids.iter().copied() {
+        session
+            .peer
+            .forward_send(session.connection_id, connection_id, request.clone())?;
+    }
+    Ok(())
+}
+
 /// Get public data about users.
 async fn get_users(
     request: proto::GetUsers,
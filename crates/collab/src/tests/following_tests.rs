@@ -1246,7 +1246,7 @@ async fn test_auto_unfollowing(cx_a: &mut TestAppContext, cx_b: &mut TestAppCont
     let (workspace_a, cx_a) = client_a.build_workspace(&project_a, cx_a);
     let (workspace_b, cx_b) = client_b.build_workspace(&project_b, cx_b);
 
-    let _editor_a1 = workspace_a
+    let editor_a1 = workspace_a
         .update_in(cx_a, |workspace, window, cx| {
             workspace.open_path((worktree_id, "1.txt"), None, true, window, cx)
         })
@@ -1310,10 +1310,43 @@ async fn test_auto_unfollowing(cx_a: &mut TestAppContext, cx_b: &mut TestAppCont
         Some(leader_id.into())
     );
 
-    // When client B scrolls, it automatically stops following client A.
+    // When client B scrolls, it stops tracking client A's viewport but keeps following them.
     editor_b2.update_in(cx_b, |editor, window, cx| {
         editor.set_scroll_position(point(0., 3.), window, cx)
     });
+    assert_eq!(
+        workspace_b.update(cx_b, |workspace, _| workspace.leader_for_pane(&pane_b)),
+        Some(leader_id.into())
+    );
+    assert!(workspace_b.update(cx_b, |workspace, _| {
+        workspace.is_scrolled_away_from_leader(leader_id)
+    }));
+
+    // Client A's viewport is only applied once client B re-syncs with them.
+    editor_a1.update_in(cx_a, |editor, window, cx| {
+        editor.set_scroll_position(point(0., 1.), window, cx)
+    });
+    executor.run_until_parked();
+    assert_eq!(
+        editor_b2.update(cx_b, |editor, cx| editor.scroll_position(cx)),
+        point(0., 3.)
+    );
+    workspace_b.update_in(cx_b, |workspace, window, cx| {
+        workspace.resync_with_leader(leader_id, window, cx)
+    });
+    assert!(!workspace_b.update(cx_b, |workspace, _| {
+        workspace.is_scrolled_away_from_leader(leader_id)
+    }));
+    assert_eq!(
+        editor_b2.update(cx_b, |editor, cx| editor.scroll_position(cx)),
+        point(0., 1.)
+    );
+
+    // Scrolling away and moving stops following client A.
+    editor_b2.update_in(cx_b, |editor, window, cx| {
+        editor.set_scroll_position(point(0., 3.), window, cx);
+        editor.move_right(&editor::actions::MoveRight, window, cx)
+    });
     assert_eq!(
         workspace_b.update(cx_b, |workspace, _| workspace.leader_for_pane(&pane_b)),
         None
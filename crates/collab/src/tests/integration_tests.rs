@@ -11,7 +11,7 @@ use assistant_slash_command::SlashCommandWorkingSet;
 use buffer_diff::{DiffHunkSecondaryStatus, DiffHunkStatus, assert_hunks};
 use call::{ActiveCall, ParticipantLocation, Room, room};
 use client::{RECEIVE_TIMEOUT, User};
-use collections::{HashMap, HashSet};
+use collections::{BTreeSet, HashMap, HashSet};
 use fs::{FakeFs, Fs as _, RemoveOptions};
 use futures::{StreamExt as _, channel::mpsc};
 use git::status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode};
@@ -31,7 +31,8 @@ use lsp::{LanguageServerId, OneOf};
 use parking_lot::Mutex;
 use pretty_assertions::assert_eq;
 use project::{
-    DiagnosticSummary, HoverBlockKind, Project, ProjectPath,
+    CollabSyncStatus, DiagnosticSummary, HoverBlockKind, Project, ProjectPath,
+    guest_permissions::GuestPermissions,
     lsp_store::{FormatTrigger, LspFormatTarget},
     search::{SearchQuery, SearchResult},
 };
@@ -1781,6 +1782,10 @@ async fn test_project_reconnect(
     buffer_a1.update(cx_a, |buf, cx| buf.edit([(1..1, "X")], None, cx));
     buffer_b1.update(cx_b, |buf, cx| buf.edit([(2..2, "Y")], None, cx));
     executor.run_until_parked();
+    project_b1.read_with(cx_b, |project, _| {
+        assert_eq!(project.collab_sync_status(), CollabSyncStatus::Offline);
+        assert_eq!(project.buffers_edited_offline(), 1);
+    });
 
     // While disconnected, close project 3
     cx_a.update(|_| drop(project_a3));
@@ -1830,6 +1835,10 @@ async fn test_project_reconnect(
     });
 
     project_b3.read_with(cx_b, |project, cx| assert!(project.is_disconnected(cx)));
+    project_b1.read_with(cx_b, |project, _| {
+        assert_eq!(project.collab_sync_status(), CollabSyncStatus::Synced);
+        assert_eq!(project.buffers_edited_offline(), 0);
+    });
 
     buffer_a1.read_with(cx_a, |buffer, _| assert_eq!(buffer.text(), "WXaYZ"));
 
@@ -3762,6 +3771,147 @@ async fn test_editing_while_guest_opens_buffer(
     buffer_b.read_with(cx_b, |buf, _| assert_eq!(buf.text(), text));
 }
 
+#[gpui::test]
+async fn test_guest_permissions(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                "app": { "main.rs": "fn main() {}" },
+                "docs": { "README.md": "# Docs" },
+            }),
+        )
+        .await;
+    let (project_a, app_id) = client_a.build_local_project(path!("/root/app"), cx_a).await;
+    let (docs_worktree, _) = project_a
+        .update(cx_a, |project, cx| {
+            project.find_or_create_worktree(path!("/root/docs"), true, cx)
+        })
+        .await
+        .unwrap();
+    let docs_id = docs_worktree.read_with(cx_a, |worktree, _| worktree.id());
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    let user_b = client_b.user_id().unwrap();
+
+    let main_b = project_b
+        .update(cx_b, |project, cx| {
+            project.open_buffer((app_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let readme_b = project_b
+        .update(cx_b, |project, cx| {
+            project.open_buffer((docs_id, "README.md"), cx)
+        })
+        .await
+        .unwrap();
+    assert!(!main_b.read_with(cx_b, |buffer, _| buffer.read_only()));
+    assert!(!readme_b.read_with(cx_b, |buffer, _| buffer.read_only()));
+
+    // The guest can only edit the docs.
+    project_a.update(cx_a, |project, cx| {
+        project.set_guest_permissions(
+            user_b,
+            GuestPermissions {
+                read_only: false,
+                editable_worktrees: Some(BTreeSet::from_iter([docs_id])),
+            },
+            cx,
+        )
+    });
+    executor.run_until_parked();
+    assert!(main_b.read_with(cx_b, |buffer, _| buffer.read_only()));
+    assert!(!readme_b.read_with(cx_b, |buffer, _| buffer.read_only()));
+    project_b.read_with(cx_b, |project, cx| {
+        assert!(!project.is_read_only(cx));
+        assert!(!project.can_edit_worktree(app_id, cx));
+        assert!(project.can_edit_worktree(docs_id, cx));
+    });
+
+    // The host rejects edits the guest isn't allowed to make.
+    main_b.update(cx_b, |buffer, cx| buffer.edit([(0..0, "// ")], None, cx));
+    readme_b.update(cx_b, |buffer, cx| buffer.edit([(0..0, "Hi ")], None, cx));
+    executor.run_until_parked();
+    let main_a = project_a
+        .update(cx_a, |project, cx| {
+            project.open_buffer((app_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let readme_a = project_a
+        .update(cx_a, |project, cx| {
+            project.open_buffer((docs_id, "README.md"), cx)
+        })
+        .await
+        .unwrap();
+    main_a.read_with(cx_a, |buffer, _| assert_eq!(buffer.text(), "fn main() {}"));
+    readme_a.read_with(cx_a, |buffer, _| assert_eq!(buffer.text(), "Hi # Docs"));
+
+    project_a.update(cx_a, |project, cx| {
+        project.set_guest_permissions(
+            user_b,
+            GuestPermissions {
+                read_only: true,
+                editable_worktrees: None,
+            },
+            cx,
+        )
+    });
+    executor.run_until_parked();
+    assert!(readme_b.read_with(cx_b, |buffer, _| buffer.read_only()));
+    assert!(project_b.read_with(cx_b, |project, cx| project.is_read_only(cx)));
+
+    // The host also rejects the requests a read-only guest could change files with.
+    project_b
+        .update(cx_b, |project, cx| {
+            project.save_buffer(readme_b.clone(), cx)
+        })
+        .await
+        .unwrap_err();
+    project_b
+        .update(cx_b, |project, cx| {
+            project.format(
+                HashSet::from_iter([readme_b.clone()]),
+                LspFormatTarget::Buffers,
+                true,
+                FormatTrigger::Save,
+                cx,
+            )
+        })
+        .await
+        .unwrap_err();
+    project_b
+        .update(cx_b, |project, cx| project.create_buffer(cx))
+        .await
+        .unwrap_err();
+    assert!(!project_b.read_with(cx_b, |project, cx| project.supports_terminal(cx)));
+
+    project_a.update(cx_a, |project, cx| {
+        project.set_guest_permissions(user_b, GuestPermissions::default(), cx)
+    });
+    executor.run_until_parked();
+    assert!(!main_b.read_with(cx_b, |buffer, _| buffer.read_only()));
+    assert!(!readme_b.read_with(cx_b, |buffer, _| buffer.read_only()));
+    assert!(!project_b.read_with(cx_b, |project, cx| project.is_read_only(cx)));
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_leaving_worktree_while_opening_buffer(
     executor: BackgroundExecutor,
@@ -668,6 +668,12 @@ impl FollowableItem for ChannelView {
             .update(cx, |editor, cx| editor.set_leader_id(leader_id, window, cx))
     }
 
+    fn set_viewport_synced(&mut self, synced: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor.update(cx, |editor, cx| {
+            editor.set_viewport_synced(synced, window, cx)
+        })
+    }
+
     fn is_project_item(&self, _window: &Window, _cx: &App) -> bool {
         false
     }
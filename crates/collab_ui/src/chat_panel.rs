@@ -1159,8 +1159,8 @@ impl Panel for ChatPanel {
         self.enabled(cx).then(|| ui::IconName::MessageBubbles)
     }
 
-    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
-        Some("Chat Panel")
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<SharedString> {
+        Some("Chat Panel".into())
     }
 
     fn toggle_action(&self) -> Box<dyn gpui::Action> {
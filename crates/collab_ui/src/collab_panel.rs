@@ -7,6 +7,7 @@ use anyhow::Context as _;
 use call::ActiveCall;
 use channel::{Channel, ChannelEvent, ChannelStore};
 use client::{ChannelId, Client, Contact, User, UserStore};
+use collections::BTreeSet;
 use contact_finder::ContactFinder;
 use db::kvp::KEY_VALUE_STORE;
 use editor::{Editor, EditorElement, EditorStyle};
@@ -19,7 +20,7 @@ use gpui::{
     anchored, canvas, deferred, div, fill, list, point, prelude::*, px,
 };
 use menu::{Cancel, Confirm, SecondaryConfirm, SelectNext, SelectPrevious};
-use project::{Fs, Project};
+use project::{Fs, Project, guest_permissions::GuestPermissions};
 use rpc::{
     ErrorCode, ErrorExt,
     proto::{self, ChannelVisibility, PeerId},
@@ -942,19 +943,32 @@ impl CollabPanel {
                             .ok();
                     }))
             })
-            .when(is_call_admin, |el| {
-                el.on_secondary_mouse_down(cx.listener(
-                    move |this, event: &MouseDownEvent, window, cx| {
-                        this.deploy_participant_context_menu(
-                            event.position,
-                            user_id,
-                            role,
-                            window,
-                            cx,
-                        )
-                    },
-                ))
-            })
+            .when(
+                is_call_admin || (!is_current_user && self.shared_project(cx).is_some()),
+                |el| {
+                    el.on_secondary_mouse_down(cx.listener(
+                        move |this, event: &MouseDownEvent, window, cx| {
+                            this.deploy_participant_context_menu(
+                                event.position,
+                                user_id,
+                                role,
+                                window,
+                                cx,
+                            )
+                        },
+                    ))
+                },
+            )
+    }
+
+    /// The workspace's project, when the current user is sharing it.
+    fn shared_project(&self, cx: &App) -> Option<Entity<Project>> {
+        let project = self.workspace.upgrade()?.read(cx).project().clone();
+        let is_shared = {
+            let project = project.read(cx);
+            project.is_shared() && !project.is_via_collab()
+        };
+        is_shared.then_some(project)
     }
 
     fn render_participant_project(
@@ -1127,15 +1141,31 @@ impl CollabPanel {
         cx: &mut Context<Self>,
     ) {
         let this = cx.entity().clone();
-        if !(role == proto::ChannelRole::Guest
-            || role == proto::ChannelRole::Talker
-            || role == proto::ChannelRole::Member)
-        {
+        let is_call_admin = ActiveCall::global(cx).read(cx).room().is_some_and(|room| {
+            room.read(cx).local_participant().role == proto::ChannelRole::Admin
+        });
+        let can_change_role = is_call_admin
+            && (role == proto::ChannelRole::Guest
+                || role == proto::ChannelRole::Talker
+                || role == proto::ChannelRole::Member);
+        let shared_project = self.shared_project(cx).map(|project| {
+            let permissions = project.read(cx).guest_permissions(user_id);
+            let worktrees = project
+                .read(cx)
+                .visible_worktrees(cx)
+                .map(|worktree| {
+                    let worktree = worktree.read(cx);
+                    (worktree.id(), worktree.root_name().to_string())
+                })
+                .collect::<Vec<_>>();
+            (project, permissions, worktrees)
+        });
+        if !can_change_role && shared_project.is_none() {
             return;
         }
 
         let context_menu = ContextMenu::build(window, cx, |mut context_menu, window, _| {
-            if role == proto::ChannelRole::Guest {
+            if can_change_role && role == proto::ChannelRole::Guest {
                 context_menu = context_menu.entry(
                     "Grant Mic Access",
                     None,
@@ -1162,7 +1192,9 @@ impl CollabPanel {
                     }),
                 );
             }
-            if role == proto::ChannelRole::Guest || role == proto::ChannelRole::Talker {
+            if can_change_role
+                && (role == proto::ChannelRole::Guest || role == proto::ChannelRole::Talker)
+            {
                 context_menu = context_menu.entry(
                     "Grant Write Access",
                     None,
@@ -1189,7 +1221,9 @@ impl CollabPanel {
                     }),
                 );
             }
-            if role == proto::ChannelRole::Member || role == proto::ChannelRole::Talker {
+            if can_change_role
+                && (role == proto::ChannelRole::Member || role == proto::ChannelRole::Talker)
+            {
                 let label = if role == proto::ChannelRole::Talker {
                     "Mute"
                 } else {
@@ -1222,6 +1256,65 @@ impl CollabPanel {
                 );
             }
 
+            if let Some((project, permissions, worktrees)) = shared_project {
+                if can_change_role {
+                    context_menu = context_menu.separator();
+                }
+                context_menu = context_menu.header("In Your Shared Project");
+                context_menu = context_menu.toggleable_entry(
+                    "Read-only",
+                    permissions.read_only,
+                    IconPosition::Start,
+                    None,
+                    {
+                        let project = project.clone();
+                        let permissions = permissions.clone();
+                        move |_, cx| {
+                            let permissions = GuestPermissions {
+                                read_only: !permissions.read_only,
+                                ..permissions.clone()
+                            };
+                            project.update(cx, |project, cx| {
+                                project.set_guest_permissions(user_id, permissions, cx)
+                            });
+                        }
+                    },
+                );
+                // Editing specific worktrees only matters when there are several.
+                if !permissions.read_only && worktrees.len() > 1 {
+                    let all_worktrees =
+                        worktrees.iter().map(|(id, _)| *id).collect::<BTreeSet<_>>();
+                    for (worktree_id, root_name) in worktrees {
+                        let project = project.clone();
+                        let permissions = permissions.clone();
+                        let all_worktrees = all_worktrees.clone();
+                        context_menu = context_menu.toggleable_entry(
+                            format!("Can Edit {root_name}"),
+                            permissions.can_edit(worktree_id),
+                            IconPosition::Start,
+                            None,
+                            move |_, cx| {
+                                let mut editable_worktrees = permissions
+                                    .editable_worktrees
+                                    .clone()
+                                    .unwrap_or_else(|| all_worktrees.clone());
+                                if !editable_worktrees.remove(&worktree_id) {
+                                    editable_worktrees.insert(worktree_id);
+                                }
+                                let permissions = GuestPermissions {
+                                    read_only: false,
+                                    editable_worktrees: (editable_worktrees != all_worktrees)
+                                        .then_some(editable_worktrees),
+                                };
+                                project.update(cx, |project, cx| {
+                                    project.set_guest_permissions(user_id, permissions, cx)
+                                });
+                            },
+                        );
+                    }
+                }
+            }
+
             context_menu
         });
 
@@ -3085,8 +3178,8 @@ impl Panel for CollabPanel {
             .then_some(ui::IconName::UserGroup)
     }
 
-    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
-        Some("Collab Panel")
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Collab Panel".into())
     }
 
     fn toggle_action(&self) -> Box<dyn gpui::Action> {
@@ -0,0 +1,545 @@
+//! Replaces the selected text with the output of a shell command that reads it, like `!` in vim,
+//! to reuse tools like `sort`, `jq` or `column` on the text being edited.
+
+use std::{
+    collections::BTreeMap,
+    io::Write as _,
+    ops::Range,
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result};
+use editor::{Anchor, Editor};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
+    PromptLevel, Render, Styled, Task, WeakEntity, Window, impl_actions,
+};
+use picker::{Picker, PickerDelegate};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr as _};
+
+/// Pipes each selection through a shell command and replaces it with what the command prints.
+/// Without a selection, the whole buffer is filtered. Without a command or a filter, this opens
+/// a picker of the saved filters, where a command can also be typed in.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FilterThroughCommand {
+    /// The shell command to run.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// The name of a filter saved in the `command_filters` setting, to run its command.
+    #[serde(default)]
+    pub filter: Option<String>,
+}
+
+impl_actions!(command_filter, [FilterThroughCommand]);
+
+pub fn init(cx: &mut App) {
+    CommandFilterSettings::register(cx);
+    cx.observe_new(FilterPicker::register).detach();
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CommandFilterSettings {
+    /// The saved filters, from their name to their command.
+    pub filters: BTreeMap<String, String>,
+}
+
+/// Shell commands to filter text through, by name, like `{ "Format JSON": "jq ." }`.
+///
+/// Default: {}
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+#[serde(transparent)]
+pub struct CommandFilterSettingsContent(pub BTreeMap<String, String>);
+
+impl Settings for CommandFilterSettings {
+    const KEY: Option<&'static str> = Some("command_filters");
+
+    type FileContent = CommandFilterSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        let content: CommandFilterSettingsContent = sources.json_merge()?;
+        Ok(Self { filters: content.0 })
+    }
+
+    fn import_from_vscode(_vscode: &settings::VsCodeSettings, _current: &mut Self::FileContent) {}
+}
+
+/// Runs the command on the selections of the editor, or on its whole buffer when nothing is
+/// selected. In a project with folders that aren't trusted, locally or over SSH, the command only
+/// runs once it's confirmed, since it runs in the project with its environment. So does it in a
+/// project joined in a call, where it runs on this machine rather than the host's.
+pub fn filter_through_command(
+    workspace: &Entity<Workspace>,
+    editor: &Entity<Editor>,
+    command: String,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let inputs = editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let mut ranges = editor
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .filter(|selection| !selection.is_empty())
+            .map(|selection| {
+                snapshot.anchor_before(selection.start)..snapshot.anchor_after(selection.end)
+            })
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            ranges.push(snapshot.anchor_before(0)..snapshot.anchor_after(snapshot.len()));
+        }
+        ranges
+            .into_iter()
+            .map(|range| {
+                let text = snapshot.text_for_range(range.clone()).collect::<String>();
+                (range, text)
+            })
+            .collect::<Vec<_>>()
+    });
+    let project = workspace.read(cx).project().read(cx);
+    let confirmation_detail = if project.is_via_collab() {
+        Some(format!(
+            "This project is shared from another machine, and `{command}` would run on this one."
+        ))
+    } else if project.untrusted_worktrees(cx).next().is_some() {
+        Some(format!(
+            "This project's folders aren't trusted, and `{command}` would run in them."
+        ))
+    } else {
+        None
+    };
+    let processes = inputs
+        .iter()
+        .map(|_| {
+            let mut process = project.exec_in_shell(command.clone(), cx);
+            util::set_pre_exec_to_start_new_session(&mut process);
+            process
+        })
+        .collect::<Vec<_>>();
+
+    let editor = editor.downgrade();
+    window
+        .spawn(cx, async move |cx| {
+            if let Some(detail) = confirmation_detail {
+                let answer = cx.update(|window, cx| {
+                    window.prompt(
+                        PromptLevel::Warning,
+                        "Run a command in an untrusted project?",
+                        Some(&detail),
+                        &["Run", "Cancel"],
+                        cx,
+                    )
+                })?;
+                if answer.await.ok() != Some(0) {
+                    return Ok(());
+                }
+            }
+
+            let mut edits: Vec<(Range<Anchor>, String)> = Vec::new();
+            for ((range, input), process) in inputs.into_iter().zip(processes) {
+                let output = cx
+                    .background_spawn(async move { run_filter(process, &input) })
+                    .await
+                    .with_context(|| format!("running `{command}`"))?;
+                edits.push((range, output));
+            }
+            editor.update_in(cx, |editor, window, cx| {
+                editor.transact(window, cx, |editor, _, cx| editor.edit(edits, cx));
+            })
+        })
+        .detach_and_prompt_err(
+            "Failed to filter through the command",
+            window,
+            cx,
+            |_, _, _| None,
+        );
+}
+
+/// Runs the command with the input on its standard input, and returns its standard output.
+fn run_filter(mut process: Command, input: &str) -> Result<String> {
+    process
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = process.spawn()?;
+    let mut stdin = child
+        .stdin
+        .take()
+        .context("opening the input of the command")?;
+    // The input is written while the output is read, so that a command printing as it reads
+    // doesn't fill its output and wait forever.
+    let output = std::thread::scope(|scope| {
+        scope.spawn(move || stdin.write_all(input.as_bytes()).log_err());
+        child.wait_with_output()
+    })?;
+    anyhow::ensure!(
+        output.status.success(),
+        "the command failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(adjust_trailing_newline(
+        input,
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+    ))
+}
+
+/// Most commands end their output with a newline, which is dropped when the input doesn't end
+/// with one, so that filtering part of a line doesn't break it.
+fn adjust_trailing_newline(input: &str, mut output: String) -> String {
+    if !input.ends_with('\n') {
+        if output.ends_with("\r\n") {
+            output.truncate(output.len() - 2);
+        } else if output.ends_with('\n') {
+            output.pop();
+        }
+    }
+    output
+}
+
+/// Picks a saved filter to run, or runs the command typed in.
+pub struct FilterPicker {
+    picker: Entity<Picker<FilterPickerDelegate>>,
+}
+
+impl FilterPicker {
+    fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+        let handle = cx.entity().downgrade();
+        editor
+            .register_action(move |action: &FilterThroughCommand, window, cx| {
+                let Some(editor) = handle.upgrade() else {
+                    return;
+                };
+                let Some(workspace) = editor.read(cx).workspace() else {
+                    return;
+                };
+                let command = match (&action.command, &action.filter) {
+                    (Some(command), _) => Some(command.clone()),
+                    (None, Some(filter)) => {
+                        let command = CommandFilterSettings::get_global(cx).filters.get(filter);
+                        if command.is_none() {
+                            log::error!(
+                                "no filter named {filter:?} in the command_filters setting"
+                            );
+                        }
+                        command.cloned()
+                    }
+                    (None, None) => None,
+                };
+                if let Some(command) = command {
+                    filter_through_command(&workspace, &editor, command, window, cx);
+                    return;
+                }
+                if action.filter.is_some() {
+                    return;
+                }
+                workspace.update(cx, |workspace, cx| {
+                    let weak_workspace = cx.entity().downgrade();
+                    workspace.toggle_modal(window, cx, move |window, cx| {
+                        FilterPicker::new(weak_workspace, editor, window, cx)
+                    });
+                })
+            })
+            .detach();
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        editor: Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let filters = CommandFilterSettings::get_global(cx)
+            .filters
+            .iter()
+            .map(|(name, command)| (name.clone(), command.clone()))
+            .collect();
+        let delegate =
+            FilterPickerDelegate::new(cx.entity().downgrade(), workspace, editor, filters);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for FilterPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for FilterPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for FilterPicker {}
+impl ModalView for FilterPicker {}
+
+enum FilterMatch {
+    Saved(StringMatch),
+    Run(String),
+}
+
+pub struct FilterPickerDelegate {
+    filter_picker: WeakEntity<FilterPicker>,
+    workspace: WeakEntity<Workspace>,
+    editor: Entity<Editor>,
+    /// The saved filters, as their name and their command.
+    filters: Vec<(String, String)>,
+    matches: Vec<FilterMatch>,
+    selected_index: usize,
+}
+
+impl FilterPickerDelegate {
+    fn new(
+        filter_picker: WeakEntity<FilterPicker>,
+        workspace: WeakEntity<Workspace>,
+        editor: Entity<Editor>,
+        filters: Vec<(String, String)>,
+    ) -> Self {
+        Self {
+            filter_picker,
+            workspace,
+            editor,
+            filters,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for FilterPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Pick a saved filter, or type a command to filter the selections through…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let command = match self.matches.get(self.selected_index) {
+            Some(FilterMatch::Saved(string_match)) => {
+                Some(self.filters[string_match.candidate_id].1.clone())
+            }
+            Some(FilterMatch::Run(command)) => Some(command.clone()),
+            None => None,
+        };
+        self.editor.focus_handle(cx).focus(window);
+        if let Some((command, workspace)) = command.zip(self.workspace.upgrade()) {
+            filter_through_command(&workspace, &self.editor, command, window, cx);
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.filter_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .filters
+            .iter()
+            .enumerate()
+            .map(|(id, (name, _))| StringMatchCandidate::new(id, name))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |this, cx| {
+            let query = query.trim().to_string();
+            let saved_matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = saved_matches.into_iter().map(FilterMatch::Saved).collect();
+                if !query.is_empty() {
+                    // A query that doesn't name a filter is more likely a command to run.
+                    let run_match = FilterMatch::Run(query);
+                    if delegate.matches.is_empty() {
+                        delegate.matches.push(run_match);
+                    } else {
+                        delegate.matches.insert(1, run_match);
+                    }
+                }
+                delegate.selected_index = 0;
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+        Some(match &self.matches[ix] {
+            FilterMatch::Saved(string_match) => {
+                let (_, command) = &self.filters[string_match.candidate_id];
+                item.child(
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            string_match.string.clone(),
+                            string_match.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(command.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        ),
+                )
+            }
+            FilterMatch::Run(command) => item
+                .start_slot(Icon::new(IconName::Terminal).color(Color::Muted))
+                .child(Label::new(format!("Filter Through `{command}`"))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::Buffer;
+    use project::{FakeFs, Project, TrustedFolders, WorkspaceTrustSettings};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::test::TempTree;
+    use workspace::AppState;
+
+    #[test]
+    fn test_adjust_trailing_newline() {
+        assert_eq!(adjust_trailing_newline("b\na\n", "a\nb\n".into()), "a\nb\n");
+        assert_eq!(adjust_trailing_newline("word", "WORD\n".into()), "WORD");
+        assert_eq!(adjust_trailing_newline("word", "WORD\r\n".into()), "WORD");
+        assert_eq!(adjust_trailing_newline("word", "WORD\n\n".into()), "WORD\n");
+        assert_eq!(adjust_trailing_newline("word", "".into()), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_filter() {
+        let mut command = Command::new("sort");
+        command.env("LC_ALL", "C");
+        assert_eq!(run_filter(command, "b\nc\na\n").unwrap(), "a\nb\nc\n");
+    }
+
+    #[cfg(unix)]
+    #[gpui::test]
+    async fn test_filter_through_command_in_untrusted_project(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.set_global(TrustedFolders::default());
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings::<WorkspaceTrustSettings>(cx, |settings| {
+                    settings.enabled = Some(true);
+                });
+            });
+        });
+
+        // The command runs in the project's folder, so it has to exist.
+        let dir = TempTree::new(json!({}));
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(dir.path(), json!({})).await;
+        let project = Project::test(fs, [dir.path()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let editor = workspace.update_in(cx, |_, window, cx| {
+            let buffer = cx.new(|cx| Buffer::local("b\na\n", cx));
+            cx.new(|cx| Editor::for_buffer(buffer, Some(project.clone()), window, cx))
+        });
+        let filter = |command: &str, cx: &mut VisualTestContext| {
+            cx.update(|window, cx| {
+                filter_through_command(&workspace, &editor, command.to_string(), window, cx)
+            });
+            cx.run_until_parked();
+        };
+
+        filter("tr a-z A-Z", cx);
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer("Cancel");
+        cx.run_until_parked();
+        assert_eq!(editor.update(cx, |editor, cx| editor.text(cx)), "b\na\n");
+
+        filter("tr a-z A-Z", cx);
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer("Run");
+        cx.run_until_parked();
+        assert_eq!(editor.update(cx, |editor, cx| editor.text(cx)), "B\nA\n");
+
+        cx.update(|_, cx| TrustedFolders::trust([dir.path().to_path_buf()], cx));
+        filter("tr A-Z a-z", cx);
+        assert!(!cx.has_pending_prompt());
+        assert_eq!(editor.update(cx, |editor, cx| editor.text(cx)), "b\na\n");
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            AppState::test(cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+        });
+    }
+}
@@ -1,15 +1,11 @@
 mod persistence;
 
-use std::{
-    cmp::{self, Reverse},
-    collections::HashMap,
-    sync::Arc,
-    time::Duration,
-};
+use std::{cmp, collections::HashMap, rc::Rc, sync::Arc, time::Duration};
 
 use client::parse_zed_link;
 use command_palette_hooks::{
-    CommandInterceptResult, CommandPaletteFilter, CommandPaletteInterceptor,
+    CommandArgumentPrompt, CommandInterceptResult, CommandPaletteArgumentPrompts,
+    CommandPaletteCommands, CommandPaletteFilter, CommandPaletteInterceptor,
 };
 
 use fuzzy::{StringMatch, StringMatchCandidate};
@@ -21,6 +17,7 @@ use persistence::COMMAND_PALETTE_HISTORY;
 use picker::{Picker, PickerDelegate};
 use postage::{sink::Sink, stream::Stream};
 use settings::Settings;
+use time::OffsetDateTime;
 use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, h_flex, prelude::*, v_flex};
 use util::ResultExt;
 use workspace::{ModalView, Workspace, WorkspaceSettings};
@@ -94,7 +91,7 @@ impl CommandPalette {
     ) -> Self {
         let filter = CommandPaletteFilter::try_global(cx);
 
-        let commands = window
+        let mut commands = window
             .available_actions(cx)
             .into_iter()
             .filter_map(|action| {
@@ -105,9 +102,44 @@ impl CommandPalette {
                 Some(Command {
                     name: humanize_action_name(action.name()),
                     action,
+                    alias_target: None,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let aliases = WorkspaceSettings::get_global(cx)
+            .command_aliases
+            .iter()
+            .filter_map(|(alias, target)| {
+                let command = commands.iter().find(|command| {
+                    command.action.name() == target || command.name.eq_ignore_ascii_case(target)
+                })?;
+                Some(Command {
+                    name: alias.clone(),
+                    action: command.action.boxed_clone(),
+                    alias_target: Some(command.name.clone()),
                 })
             })
-            .collect();
+            .collect::<Vec<_>>();
+        commands.extend(aliases);
+
+        let registered_commands = CommandPaletteCommands::try_global(cx)
+            .map(|registered_commands| {
+                registered_commands
+                    .iter()
+                    .map(|(name, action)| (name.to_string(), action.boxed_clone()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        for (name, action) in registered_commands {
+            if window.is_action_available(&*action, cx) {
+                commands.push(Command {
+                    name,
+                    action,
+                    alias_target: None,
+                });
+            }
+        }
 
         let delegate =
             CommandPaletteDelegate::new(cx.entity().downgrade(), commands, previous_focus_handle);
@@ -148,6 +180,8 @@ pub struct CommandPaletteDelegate {
     matches: Vec<StringMatch>,
     selected_ix: usize,
     previous_focus_handle: FocusHandle,
+    /// The command whose argument is being typed, after it was confirmed.
+    argument_command: Option<(Command, Rc<CommandArgumentPrompt>)>,
     updating_matches: Option<(
         Task<()>,
         postage::dispatch::Receiver<(Vec<Command>, Vec<StringMatch>)>,
@@ -157,6 +191,8 @@ pub struct CommandPaletteDelegate {
 struct Command {
     name: String,
     action: Box<dyn Action>,
+    /// The name of the command that this user-defined alias runs.
+    alias_target: Option<String>,
 }
 
 impl Clone for Command {
@@ -164,10 +200,27 @@ impl Clone for Command {
         Self {
             name: self.name.clone(),
             action: self.action.boxed_clone(),
+            alias_target: self.alias_target.clone(),
         }
     }
 }
 
+/// How much a command's usage can raise its fuzzy match score, relative to the match itself.
+const FRECENCY_MATCH_WEIGHT: f64 = 0.05;
+
+/// Scores a command by how often and how recently it was run from the palette, weighting its
+/// invocations by the age of the latest one.
+fn frecency(invocations: u16, last_invoked: OffsetDateTime, now: OffsetDateTime) -> f64 {
+    let recency_weight = match (now - last_invoked).whole_days() {
+        ..=3 => 100.,
+        4..=14 => 70.,
+        15..=31 => 50.,
+        32..=90 => 30.,
+        _ => 10.,
+    };
+    invocations as f64 * recency_weight
+}
+
 impl CommandPaletteDelegate {
     fn new(
         command_palette: WeakEntity<CommandPalette>,
@@ -181,11 +234,41 @@ impl CommandPaletteDelegate {
             commands,
             selected_ix: 0,
             previous_focus_handle,
+            argument_command: None,
             latest_query: String::new(),
             updating_matches: None,
         }
     }
 
+    fn argument_matches_updated(&mut self, query: String, cx: &mut Context<Picker<Self>>) {
+        let Some((_, prompt)) = self.argument_command.as_ref() else {
+            return;
+        };
+        self.updating_matches.take();
+        self.latest_query = query.clone();
+        self.commands.clear();
+        self.matches.clear();
+        self.selected_ix = 0;
+        if let Some(CommandInterceptResult {
+            action,
+            string,
+            positions,
+        }) = (prompt.build)(query.trim(), cx)
+        {
+            self.commands.push(Command {
+                name: string.clone(),
+                action,
+                alias_target: None,
+            });
+            self.matches.push(StringMatch {
+                candidate_id: 0,
+                string,
+                positions,
+                score: 0.0,
+            });
+        }
+    }
+
     fn matches_updated(
         &mut self,
         query: String,
@@ -225,6 +308,7 @@ impl CommandPaletteDelegate {
             commands.push(Command {
                 name: string.clone(),
                 action,
+                alias_target: None,
             });
             new_matches.push(StringMatch {
                 candidate_id: commands.len() - 1,
@@ -242,15 +326,19 @@ impl CommandPaletteDelegate {
             self.selected_ix = cmp::min(self.selected_ix, self.matches.len() - 1);
         }
     }
-    ///
-    /// Hit count for each command in the palette.
+
+    /// Frecency score for each command in the palette.
     /// We only account for commands triggered directly via command palette and not by e.g. keystrokes because
     /// if a user already knows a keystroke for a command, they are unlikely to use a command palette to look for it.
-    fn hit_counts(&self) -> HashMap<String, u16> {
+    fn command_scores(&self) -> HashMap<String, f64> {
+        let now = OffsetDateTime::now_utc();
         if let Ok(commands) = COMMAND_PALETTE_HISTORY.list_commands_used() {
             commands
                 .into_iter()
-                .map(|command| (command.command_name, command.invocations))
+                .map(|command| {
+                    let score = frecency(command.invocations, command.last_invoked, now);
+                    (command.command_name, score)
+                })
                 .collect()
         } else {
             HashMap::new()
@@ -262,7 +350,19 @@ impl PickerDelegate for CommandPaletteDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Execute a command...".into()
+        match &self.argument_command {
+            Some((_, prompt)) => format!("{}...", prompt.placeholder).into(),
+            None => "Execute a command...".into(),
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        match &self.argument_command {
+            Some((command, prompt)) => {
+                Some(format!("{}: {}", command.name, prompt.placeholder).into())
+            }
+            None => Some("No matches".into()),
+        }
     }
 
     fn match_count(&self) -> usize {
@@ -288,6 +388,11 @@ impl PickerDelegate for CommandPaletteDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> gpui::Task<()> {
+        if self.argument_command.is_some() {
+            self.argument_matches_updated(query, cx);
+            return Task::ready(());
+        }
+
         let settings = WorkspaceSettings::get_global(cx);
         if let Some(alias) = settings.command_aliases.get(&query) {
             query = alias.to_string();
@@ -295,15 +400,15 @@ impl PickerDelegate for CommandPaletteDelegate {
         let (mut tx, mut rx) = postage::dispatch::channel(1);
         let task = cx.background_spawn({
             let mut commands = self.all_commands.clone();
-            let hit_counts = self.hit_counts();
+            let scores = self.command_scores();
             let executor = cx.background_executor().clone();
             let query = normalize_query(query.as_str());
             async move {
-                commands.sort_by_key(|action| {
-                    (
-                        Reverse(hit_counts.get(&action.name).cloned()),
-                        action.name.clone(),
-                    )
+                let score = |command: &Command| scores.get(&command.name).copied().unwrap_or(0.);
+                commands.sort_by(|a, b| {
+                    score(b)
+                        .total_cmp(&score(a))
+                        .then_with(|| a.name.cmp(&b.name))
                 });
 
                 let candidates = commands
@@ -323,7 +428,7 @@ impl PickerDelegate for CommandPaletteDelegate {
                         })
                         .collect()
                 } else {
-                    fuzzy::match_strings(
+                    let mut matches = fuzzy::match_strings(
                         &candidates,
                         &query,
                         true,
@@ -331,7 +436,13 @@ impl PickerDelegate for CommandPaletteDelegate {
                         &Default::default(),
                         executor,
                     )
-                    .await
+                    .await;
+                    for string_match in &mut matches {
+                        let frecency = score(&commands[string_match.candidate_id]);
+                        string_match.score *= 1. + FRECENCY_MATCH_WEIGHT * frecency.ln_1p();
+                    }
+                    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+                    matches
                 };
 
                 tx.send((commands, matches)).await.log_err();
@@ -380,6 +491,29 @@ impl PickerDelegate for CommandPaletteDelegate {
         }
     }
 
+    fn confirm_update_query(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<String> {
+        if self.argument_command.is_some() {
+            return None;
+        }
+        let command = self
+            .matches
+            .get(self.selected_ix)
+            .and_then(|selected| self.commands.get(selected.candidate_id))?;
+        let prompt = CommandPaletteArgumentPrompts::try_global(cx)?.get(&*command.action)?;
+        self.argument_command = Some((command.clone(), prompt));
+        self.commands.clear();
+        self.matches.clear();
+        cx.defer_in(window, |picker, window, cx| {
+            picker.refresh_placeholder(window, cx);
+            picker.refresh(window, cx);
+        });
+        Some(String::new())
+    }
+
     fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
         self.command_palette
             .update(cx, |_, cx| cx.emit(DismissEvent))
@@ -388,19 +522,27 @@ impl PickerDelegate for CommandPaletteDelegate {
 
     fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
         if self.matches.is_empty() {
-            self.dismissed(window, cx);
+            // Keep prompting until the argument is valid.
+            if self.argument_command.is_none() {
+                self.dismissed(window, cx);
+            }
             return;
         }
         let action_ix = self.matches[self.selected_ix].candidate_id;
         let command = self.commands.swap_remove(action_ix);
+        // Commands that prompted for an argument are recorded under their own name, rather than
+        // the description of the action built from the argument.
+        let command_name = match self.argument_command.take() {
+            Some((argument_command, _)) => argument_command.name,
+            None => command.name.clone(),
+        };
         telemetry::event!(
             "Action Invoked",
             source = "command palette",
-            action = command.name
+            action = command_name
         );
         self.matches.clear();
         self.commands.clear();
-        let command_name = command.name.clone();
         let latest_query = self.latest_query.clone();
         cx.background_spawn(async move {
             COMMAND_PALETTE_HISTORY
@@ -433,10 +575,21 @@ impl PickerDelegate for CommandPaletteDelegate {
                         .w_full()
                         .py_px()
                         .justify_between()
-                        .child(HighlightedLabel::new(
-                            command.name.clone(),
-                            r#match.positions.clone(),
-                        ))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(HighlightedLabel::new(
+                                    command.name.clone(),
+                                    r#match.positions.clone(),
+                                ))
+                                .when_some(command.alias_target.as_ref(), |this, target| {
+                                    this.child(
+                                        Label::new(format!("→ {target}"))
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                }),
+                        )
                         .children(KeyBinding::for_action_in(
                             &*command.action,
                             &self.previous_focus_handle,
@@ -670,6 +823,71 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_argument_prompt(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        cx.simulate_keystrokes("cmd-n");
+
+        let editor = workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<Editor>(cx).unwrap()
+        });
+        editor.update_in(cx, |editor, window, cx| {
+            editor.set_text("1\n2\n3\n4\n5\n6\n", window, cx)
+        });
+
+        cx.simulate_keystrokes("cmd-shift-p");
+        cx.simulate_input("go to line: go to position");
+        cx.simulate_keystrokes("enter");
+
+        let palette = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<CommandPalette>(cx)
+                .expect("confirming a command with an argument prompt keeps the palette open")
+                .read(cx)
+                .picker
+                .clone()
+        });
+        palette.read_with(cx, |palette, cx| {
+            assert_eq!(palette.query(cx), "");
+            assert!(palette.delegate.matches.is_empty());
+        });
+
+        cx.simulate_input("4:2");
+        palette.read_with(cx, |palette, _| {
+            assert_eq!(
+                palette.delegate.matches[0].string,
+                "Go to line 4, character 2"
+            );
+        });
+        cx.simulate_keystrokes("enter");
+
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<CommandPalette>(cx).is_none());
+        });
+        editor.update_in(cx, |editor, window, cx| {
+            assert!(editor.focus_handle(cx).is_focused(window));
+            assert_eq!(
+                editor.selections.last::<Point>(cx).range().start,
+                Point::new(3, 1)
+            );
+        });
+    }
+
+    #[test]
+    fn test_frecency() {
+        let now = OffsetDateTime::now_utc();
+        let days_ago = |days| now - time::Duration::days(days);
+
+        assert!(frecency(3, days_ago(1), now) > frecency(3, days_ago(20), now));
+        assert!(frecency(4, days_ago(1), now) > frecency(3, days_ago(1), now));
+        // A command used often a while ago can still outrank one used once recently.
+        assert!(frecency(10, days_ago(60), now) > frecency(1, days_ago(0), now));
+    }
+
     fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
         cx.update(|cx| {
             let app_state = AppState::test(cx);
@@ -2,16 +2,18 @@
 
 #![deny(missing_docs)]
 
-use std::any::TypeId;
+use std::{any::TypeId, rc::Rc};
 
-use collections::HashSet;
+use collections::{HashMap, HashSet};
 use derive_more::{Deref, DerefMut};
-use gpui::{Action, App, BorrowAppContext, Global};
+use gpui::{Action, App, BorrowAppContext, Global, SharedString};
 
 /// Initializes the command palette hooks.
 pub fn init(cx: &mut App) {
     cx.set_global(GlobalCommandPaletteFilter::default());
     cx.set_global(GlobalCommandPaletteInterceptor::default());
+    cx.default_global::<GlobalCommandPaletteArgumentPrompts>();
+    cx.default_global::<GlobalCommandPaletteCommands>();
 }
 
 /// A filter for the command palette.
@@ -152,3 +154,140 @@ impl CommandPaletteInterceptor {
         self.0 = Some(handler);
     }
 }
+
+/// An argument that the command palette prompts for inline once a command is confirmed. The
+/// action built from the argument is dispatched in place of the command's own action.
+pub struct CommandArgumentPrompt {
+    /// Describes the expected argument while it is being typed.
+    pub placeholder: SharedString,
+    /// Builds the action for the argument typed so far, returning `None` while the argument is
+    /// incomplete or invalid.
+    pub build: Box<dyn Fn(&str, &App) -> Option<CommandInterceptResult>>,
+}
+
+/// The argument prompts for commands in the command palette, keyed by action type.
+#[derive(Default)]
+pub struct CommandPaletteArgumentPrompts(HashMap<TypeId, Rc<CommandArgumentPrompt>>);
+
+#[derive(Default)]
+struct GlobalCommandPaletteArgumentPrompts(CommandPaletteArgumentPrompts);
+
+impl Global for GlobalCommandPaletteArgumentPrompts {}
+
+impl CommandPaletteArgumentPrompts {
+    /// Returns the global [`CommandPaletteArgumentPrompts`], if they are set.
+    pub fn try_global(cx: &App) -> Option<&CommandPaletteArgumentPrompts> {
+        cx.try_global::<GlobalCommandPaletteArgumentPrompts>()
+            .map(|prompts| &prompts.0)
+    }
+
+    /// Updates the global [`CommandPaletteArgumentPrompts`] using the given closure.
+    pub fn update_global<F, R>(cx: &mut App, update: F) -> R
+    where
+        F: FnOnce(&mut Self, &mut App) -> R,
+    {
+        cx.default_global::<GlobalCommandPaletteArgumentPrompts>();
+        cx.update_global(|this: &mut GlobalCommandPaletteArgumentPrompts, cx| {
+            update(&mut this.0, cx)
+        })
+    }
+
+    /// Prompts for an argument when the command for actions of type `A` is confirmed.
+    pub fn register<A: Action>(&mut self, prompt: CommandArgumentPrompt) {
+        self.0.insert(TypeId::of::<A>(), Rc::new(prompt));
+    }
+
+    /// Returns the argument prompt for the given action, if it has one.
+    pub fn get(&self, action: &dyn Action) -> Option<Rc<CommandArgumentPrompt>> {
+        self.0.get(&action.as_any().type_id()).cloned()
+    }
+}
+
+/// Commands that are shown in the command palette under their own name, even though they
+/// dispatch an action whose type is shared with other commands, such as those contributed by
+/// extensions.
+#[derive(Default)]
+pub struct CommandPaletteCommands(Vec<RegisteredCommand>);
+
+struct RegisteredCommand {
+    id: SharedString,
+    name: SharedString,
+    action: Box<dyn Action>,
+}
+
+#[derive(Default)]
+struct GlobalCommandPaletteCommands(CommandPaletteCommands);
+
+impl Global for GlobalCommandPaletteCommands {}
+
+impl CommandPaletteCommands {
+    /// Returns the global [`CommandPaletteCommands`], if they are set.
+    pub fn try_global(cx: &App) -> Option<&CommandPaletteCommands> {
+        cx.try_global::<GlobalCommandPaletteCommands>()
+            .map(|commands| &commands.0)
+    }
+
+    /// Updates the global [`CommandPaletteCommands`] using the given closure.
+    pub fn update_global<F, R>(cx: &mut App, update: F) -> R
+    where
+        F: FnOnce(&mut Self, &mut App) -> R,
+    {
+        cx.default_global::<GlobalCommandPaletteCommands>();
+        cx.update_global(|this: &mut GlobalCommandPaletteCommands, cx| update(&mut this.0, cx))
+    }
+
+    /// Shows a command with the given name that dispatches the given action, replacing any
+    /// command that was registered with the same ID. Different commands may share a name.
+    pub fn register(
+        &mut self,
+        id: impl Into<SharedString>,
+        name: impl Into<SharedString>,
+        action: Box<dyn Action>,
+    ) {
+        let id = id.into();
+        self.unregister(&id);
+        self.0.push(RegisteredCommand {
+            id,
+            name: name.into(),
+            action,
+        });
+    }
+
+    /// Removes the command with the given ID.
+    pub fn unregister(&mut self, id: &str) {
+        self.0.retain(|command| command.id.as_ref() != id);
+    }
+
+    /// Returns the names of the registered commands and the actions they dispatch.
+    pub fn iter(&self) -> impl Iterator<Item = (&SharedString, &dyn Action)> {
+        self.0
+            .iter()
+            .map(|command| (&command.name, command.action.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::NoAction;
+
+    use super::*;
+
+    #[test]
+    fn test_commands_are_keyed_by_id() {
+        let mut commands = CommandPaletteCommands::default();
+        commands.register("first::sort", "Sort Lines", Box::new(NoAction));
+        commands.register("second::sort", "Sort Lines", Box::new(NoAction));
+        commands.register("first::sort", "Sort Lines (First)", Box::new(NoAction));
+
+        let names = |commands: &CommandPaletteCommands| {
+            commands
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&commands), ["Sort Lines", "Sort Lines (First)"]);
+
+        commands.unregister("second::sort");
+        assert_eq!(names(&commands), ["Sort Lines (First)"]);
+    }
+}
@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+use editor::{Editor, EditorEvent};
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, SharedString,
+    Subscription,
+};
+use language::{Anchor, Buffer};
+use project::comment_store::CommentStore;
+use theme::ActiveTheme as _;
+use ui::prelude::*;
+use workspace::ModalView;
+
+use crate::new_comment;
+
+/// Writes the comment starting a thread on a range of a buffer.
+pub(crate) struct CommentModal {
+    store: Entity<CommentStore>,
+    buffer: Entity<Buffer>,
+    range: Range<Anchor>,
+    author: SharedString,
+    editor: Entity<Editor>,
+    _subscription: Subscription,
+}
+
+impl ModalView for CommentModal {}
+
+impl EventEmitter<DismissEvent> for CommentModal {}
+
+impl Focusable for CommentModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl CommentModal {
+    pub(crate) fn new(
+        store: Entity<CommentStore>,
+        buffer: Entity<Buffer>,
+        range: Range<Anchor>,
+        author: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Comment", cx);
+            editor
+        });
+        let subscription = cx.subscribe_in(&editor, window, |_, _, event: &EditorEvent, _, cx| {
+            if let EditorEvent::Blurred = event {
+                cx.emit(DismissEvent);
+            }
+        });
+
+        Self {
+            store,
+            buffer,
+            range,
+            author,
+            editor,
+            _subscription: subscription,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let body = self.editor.read(cx).text(cx);
+        let body = body.trim();
+        if !body.is_empty() {
+            let comment = new_comment(self.author.clone(), body.to_string().into());
+            self.store.update(cx, |store, cx| {
+                store.create_thread(self.buffer.clone(), self.range.clone(), comment, cx)
+            });
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for CommentModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(34.))
+            .elevation_2(cx)
+            .key_context("CommentModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.editor.clone()),
+            )
+            .child(
+                h_flex().px_2().py_1().child(
+                    Label::new("Press enter to comment, visible to everyone in the project")
+                        .color(Color::Muted),
+                ),
+            )
+    }
+}
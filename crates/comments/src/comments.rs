@@ -0,0 +1,120 @@
+mod comment_modal;
+mod comments_panel;
+mod comments_panel_settings;
+mod persistence;
+
+use std::{
+    ops::Range,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use editor::{Editor, scroll::Autoscroll};
+use gpui::{App, Context, SharedString, Window, actions};
+use language::{Bias, Point};
+use project::comment_store::Comment;
+use settings::Settings as _;
+use workspace::Workspace;
+
+pub use comments_panel::CommentsPanel;
+pub use comments_panel_settings::CommentsPanelSettings;
+
+use comment_modal::CommentModal;
+
+actions!(comments, [ToggleFocus, AddComment]);
+
+pub fn init(cx: &mut App) {
+    CommentsPanelSettings::register(cx);
+    cx.observe_new(register).detach();
+}
+
+fn register(workspace: &mut Workspace, _: Option<&mut Window>, _: &mut Context<Workspace>) {
+    workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+        workspace.toggle_panel_focus::<CommentsPanel>(window, cx);
+    });
+    workspace.register_action(|workspace, _: &AddComment, window, cx| {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let target = editor.update(cx, |editor, cx| {
+            let selection = editor.selections.newest::<Point>(cx);
+            let multi_buffer = editor.buffer().read(cx);
+            let (buffer, start, _) = multi_buffer.point_to_buffer_point(selection.start, cx)?;
+            let snapshot = buffer.read(cx).snapshot();
+            // Without a selection, the comment is about the cursor's line.
+            let range = if selection.is_empty() {
+                Point::new(start.row, 0)..Point::new(start.row, snapshot.line_len(start.row))
+            } else {
+                let end = multi_buffer
+                    .point_to_buffer_point(selection.end, cx)
+                    .filter(|(end_buffer, _, _)| end_buffer == &buffer)
+                    .map_or(
+                        Point::new(start.row, snapshot.line_len(start.row)),
+                        |(_, end, _)| end,
+                    );
+                start..end
+            };
+            let range = snapshot.anchor_before(snapshot.clip_point(range.start, Bias::Left))
+                ..snapshot.anchor_after(snapshot.clip_point(range.end, Bias::Right));
+            Some((buffer, range))
+        });
+        let Some((buffer, range)) = target else {
+            return;
+        };
+        let store = workspace.project().read(cx).comment_store();
+        let author = comment_author(workspace, cx);
+        workspace.toggle_modal(window, cx, |window, cx| {
+            CommentModal::new(store, buffer, range, author, window, cx)
+        });
+    });
+}
+
+/// The GitHub login of the signed-in user, who collaborators know comments by.
+fn comment_author(workspace: &Workspace, cx: &App) -> SharedString {
+    workspace.user_store().read(cx).current_user().map_or_else(
+        || "anonymous".into(),
+        |user| user.github_login.clone().into(),
+    )
+}
+
+pub(crate) fn new_comment(author: SharedString, body: SharedString) -> Comment {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    Comment {
+        author,
+        body,
+        timestamp,
+    }
+}
+
+pub(crate) fn open_comment_thread(
+    workspace: &mut Workspace,
+    abs_path: &Path,
+    range: Range<Point>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    // Guests can't open the host's absolute paths, but can open the project path they map to.
+    let Some(project_path) = workspace
+        .project()
+        .read(cx)
+        .project_path_for_absolute_path(abs_path, cx)
+    else {
+        return;
+    };
+    let open_task = workspace.open_path(project_path, None, true, window, cx);
+    cx.spawn_in(window, async move |_, cx| {
+        let item = open_task.await?;
+        if let Some(editor) = item.downcast::<Editor>() {
+            editor.update_in(cx, |editor, window, cx| {
+                editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                    s.select_ranges([range.start..range.start])
+                });
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
@@ -0,0 +1,656 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::Result;
+use client::UserStore;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, EditorEvent};
+use gpui::{
+    Action, AnyElement, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, ScrollHandle, Subscription, Task, WeakEntity, Window,
+};
+use language::BufferId;
+use project::{
+    Fs,
+    comment_store::{Comment, CommentStore, CommentStoreEvent, CommentThread, CommentThreadId},
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use theme::ActiveTheme as _;
+use ui::{ListItem, ListItemSpacing, Tab, prelude::*};
+use util::{ResultExt as _, TryFutureExt as _};
+use workspace::{
+    Toast, Workspace, WorkspaceId,
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::NotificationId,
+};
+
+use crate::{
+    CommentsPanelSettings, ToggleFocus, comment_author, new_comment, open_comment_thread,
+    persistence::COMMENT_DB,
+};
+
+const COMMENTS_PANEL_KEY: &str = "CommentsPanel";
+
+enum CommentHighlight {}
+
+/// Lists the comment threads of the project, open ones first, and highlights the code they're
+/// about in the editors showing it.
+pub struct CommentsPanel {
+    store: Entity<CommentStore>,
+    workspace: WeakEntity<Workspace>,
+    user_store: Entity<UserStore>,
+    fs: Arc<dyn Fs>,
+    workspace_id: Option<WorkspaceId>,
+    width: Option<Pixels>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    selected_thread: Option<CommentThreadId>,
+    show_resolved: bool,
+    reply_editor: Entity<Editor>,
+    pending_serialization: Task<Option<()>>,
+    pending_threads_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedCommentsPanel {
+    width: Option<Pixels>,
+    show_resolved: Option<bool>,
+}
+
+impl CommentsPanel {
+    fn new(
+        workspace: &mut Workspace,
+        workspace_id: Option<WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let store = workspace.project().read(cx).comment_store();
+        let user_store = workspace.user_store().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace_entity = cx.entity();
+        cx.new(|cx| {
+            let reply_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Reply…", cx);
+                editor
+            });
+            let subscriptions = vec![
+                cx.subscribe(&store, Self::handle_comment_store_event),
+                cx.subscribe(&workspace_entity, |this, _, event, cx| {
+                    if let workspace::Event::ItemAdded { .. } = event {
+                        this.update_highlights(cx);
+                    }
+                }),
+                cx.subscribe(&reply_editor, |_, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        cx.notify();
+                    }
+                }),
+            ];
+            Self {
+                store,
+                workspace: workspace_entity.downgrade(),
+                user_store,
+                fs,
+                workspace_id,
+                width: None,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: ScrollHandle::new(),
+                selected_thread: None,
+                show_resolved: false,
+                reply_editor,
+                pending_serialization: Task::ready(None),
+                pending_threads_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let workspace_id = workspace.read_with(cx, |workspace, _| workspace.database_id())?;
+            let (serialized_panel, serialized_threads) = cx
+                .background_spawn(async move {
+                    let panel = KEY_VALUE_STORE
+                        .read_kvp(COMMENTS_PANEL_KEY)
+                        .log_err()
+                        .flatten();
+                    let threads = workspace_id
+                        .and_then(|workspace_id| COMMENT_DB.comment_threads(workspace_id).log_err())
+                        .unwrap_or_default();
+                    (panel, threads)
+                })
+                .await;
+            let serialized_panel = serialized_panel
+                .map(|panel| serde_json::from_str::<SerializedCommentsPanel>(&panel))
+                .transpose()?;
+
+            let panel = workspace.update_in(cx, |workspace, window, cx| {
+                Self::new(workspace, workspace_id, window, cx)
+            })?;
+            let restore_threads = panel.update(cx, |panel, cx| {
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.width = serialized_panel.width.map(|w| w.round());
+                    panel.show_resolved = serialized_panel.show_resolved.unwrap_or(false);
+                }
+                // Guests see the host's threads, so only the host restores its own.
+                let store = panel.store.clone();
+                store.update(cx, |store, cx| {
+                    if store.is_local() {
+                        store.with_serialized_threads(serialized_threads, cx)
+                    } else {
+                        Task::ready(Ok(()))
+                    }
+                })
+            })?;
+            restore_threads.await.log_err();
+            Ok(panel)
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        let show_resolved = Some(self.show_resolved);
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        COMMENTS_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedCommentsPanel {
+                            width,
+                            show_resolved,
+                        })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn serialize_threads(&mut self, cx: &mut Context<Self>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        let store = self.store.read(cx);
+        if !store.is_local() {
+            return;
+        }
+        let threads = store.serialized_threads(cx);
+        self.pending_threads_serialization = cx.background_spawn(async move {
+            COMMENT_DB
+                .save_comment_threads(workspace_id, threads)
+                .await
+                .log_err()
+        });
+    }
+
+    fn handle_comment_store_event(
+        &mut self,
+        _: Entity<CommentStore>,
+        event: &CommentStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            CommentStoreEvent::ThreadsUpdated(_) => {
+                self.serialize_threads(cx);
+                self.update_highlights(cx);
+                cx.notify();
+            }
+            CommentStoreEvent::Replied { thread_id, comment } => {
+                self.notify_reply(*thread_id, comment, cx);
+            }
+        }
+    }
+
+    /// Lets the user know of replies to the threads they've commented in.
+    fn notify_reply(&self, thread_id: CommentThreadId, comment: &Comment, cx: &mut Context<Self>) {
+        struct CommentReply;
+
+        let Some(user) = self.user_store.read(cx).current_user() else {
+            return;
+        };
+        if comment.author.as_ref() == user.github_login {
+            return;
+        }
+        let Some((_, thread)) = self.store.read(cx).thread(thread_id) else {
+            return;
+        };
+        if !thread
+            .comments
+            .iter()
+            .any(|comment| comment.author.as_ref() == user.github_login)
+        {
+            return;
+        }
+
+        let panel = cx.entity().downgrade();
+        let workspace = self.workspace.clone();
+        let message = format!(
+            "{} replied to your comment: {}",
+            comment.author, comment.body
+        );
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::composite::<CommentReply>(thread_id.0 as usize),
+                        message,
+                    )
+                    .on_click("View", move |window, cx| {
+                        workspace
+                            .update(cx, |workspace, cx| {
+                                workspace.focus_panel::<CommentsPanel>(window, cx);
+                            })
+                            .ok();
+                        panel
+                            .update(cx, |panel, cx| panel.select(thread_id, window, cx))
+                            .ok();
+                    }),
+                    cx,
+                )
+            })
+            .ok();
+    }
+
+    fn update_highlights(&self, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let ranges = self
+            .store
+            .read(cx)
+            .threads()
+            .filter(|(_, _, thread)| !thread.resolved)
+            .map(|(_, buffer, thread)| (buffer.read(cx).remote_id(), thread.range.clone()))
+            .collect::<Vec<(BufferId, _)>>();
+        let editors = workspace
+            .read(cx)
+            .items_of_type::<Editor>(cx)
+            .collect::<Vec<_>>();
+        for editor in editors {
+            editor.update(cx, |editor, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let Some((excerpt_id, buffer_id, _)) = snapshot.as_singleton() else {
+                    return;
+                };
+                let highlights = ranges
+                    .iter()
+                    .filter(|(thread_buffer_id, _)| thread_buffer_id == buffer_id)
+                    .filter_map(|(_, range)| {
+                        Some(
+                            snapshot.anchor_in_excerpt(*excerpt_id, range.start)?
+                                ..snapshot.anchor_in_excerpt(*excerpt_id, range.end)?,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                if highlights.is_empty() {
+                    editor.clear_background_highlights::<CommentHighlight>(cx);
+                } else {
+                    editor.highlight_background::<CommentHighlight>(
+                        &highlights,
+                        |colors| colors.text_accent.opacity(0.1),
+                        cx,
+                    );
+                }
+            });
+        }
+    }
+
+    /// The threads shown in the panel, open ones first.
+    fn visible_threads(&self, cx: &App) -> Vec<CommentThreadId> {
+        let store = self.store.read(cx);
+        let open = store
+            .threads()
+            .filter(|(_, _, thread)| !thread.resolved)
+            .map(|(_, _, thread)| thread.id);
+        let resolved = store
+            .threads()
+            .filter(|(_, _, thread)| thread.resolved && self.show_resolved)
+            .map(|(_, _, thread)| thread.id);
+        open.chain(resolved).collect()
+    }
+
+    fn select(&mut self, thread_id: CommentThreadId, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_thread != Some(thread_id) {
+            self.selected_thread = Some(thread_id);
+            self.reply_editor
+                .update(cx, |editor, cx| editor.clear(window, cx));
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        let threads = self.visible_threads(cx);
+        let ix = self
+            .selected_thread
+            .and_then(|id| threads.iter().position(|thread| *thread == id))
+            .map_or(0, |ix| (ix + 1).min(threads.len().saturating_sub(1)));
+        if let Some(id) = threads.get(ix) {
+            self.select(*id, window, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let threads = self.visible_threads(cx);
+        let ix = self
+            .selected_thread
+            .and_then(|id| threads.iter().position(|thread| *thread == id))
+            .map_or(0, |ix| ix.saturating_sub(1));
+        if let Some(id) = threads.get(ix) {
+            self.select(*id, window, cx);
+        }
+    }
+
+    /// Sends the reply being written, or opens the selected thread's code when there is none.
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.selected_thread else {
+            return;
+        };
+        let body = self.reply_editor.read(cx).text(cx);
+        let body = body.trim();
+        if body.is_empty() {
+            self.open(thread_id, window, cx);
+            return;
+        }
+
+        let Some(author) = self
+            .workspace
+            .read_with(cx, |workspace, cx| comment_author(workspace, cx))
+            .log_err()
+        else {
+            return;
+        };
+        let comment = new_comment(author, body.to_string().into());
+        self.store
+            .update(cx, |store, cx| store.reply(thread_id, comment, cx));
+        self.reply_editor
+            .update(cx, |editor, cx| editor.clear(window, cx));
+    }
+
+    fn open(&mut self, thread_id: CommentThreadId, window: &mut Window, cx: &mut Context<Self>) {
+        let store = self.store.read(cx);
+        let Some((path, buffer, thread)) = store
+            .threads()
+            .find(|(_, _, thread)| thread.id == thread_id)
+        else {
+            return;
+        };
+        let abs_path = path.to_path_buf();
+        let range = thread.point_range(&buffer.read(cx).snapshot());
+        self.workspace
+            .update(cx, |workspace, cx| {
+                open_comment_thread(workspace, &abs_path, range, window, cx)
+            })
+            .log_err();
+    }
+
+    fn toggle_show_resolved(&mut self, cx: &mut Context<Self>) {
+        self.show_resolved = !self.show_resolved;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn render_thread(
+        &self,
+        path: &Path,
+        thread: &CommentThread,
+        row: u32,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let thread_id = thread.id;
+        let selected = self.selected_thread == Some(thread_id);
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let render_comment = |comment: &Comment| {
+            v_flex()
+                .child(
+                    Label::new(comment.author.clone())
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .child(Label::new(comment.body.clone()).size(LabelSize::Small))
+        };
+        let reply_count = thread.comments.len().saturating_sub(1);
+
+        v_flex()
+            .child(
+                ListItem::new(("comment-thread", thread_id.0))
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(selected)
+                    .start_slot(
+                        Icon::new(if thread.resolved {
+                            IconName::Check
+                        } else {
+                            IconName::MessageBubbles
+                        })
+                        .size(IconSize::Small)
+                        .color(Color::Muted),
+                    )
+                    .child(
+                        v_flex()
+                            .child(
+                                Label::new(format!("{file_name}:{}", row + 1))
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                            .children(
+                                thread
+                                    .comments
+                                    .first()
+                                    .map(|comment| render_comment(comment)),
+                            )
+                            .when(!selected && reply_count > 0, |this| {
+                                this.child(
+                                    Label::new(if reply_count == 1 {
+                                        "1 reply".to_string()
+                                    } else {
+                                        format!("{reply_count} replies")
+                                    })
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Accent),
+                                )
+                            }),
+                    )
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select(thread_id, window, cx);
+                        this.open(thread_id, window, cx);
+                    })),
+            )
+            .when(selected, |this| {
+                let is_host = self.store.read(cx).is_local();
+                let resolved = thread.resolved;
+                this.child(
+                    v_flex()
+                        .pl_8()
+                        .pr_2()
+                        .pb_2()
+                        .gap_2()
+                        .children(thread.comments.iter().skip(1).map(render_comment))
+                        .child(
+                            div()
+                                .px_2()
+                                .py_1()
+                                .rounded_sm()
+                                .border_1()
+                                .border_color(cx.theme().colors().border_variant)
+                                .child(self.reply_editor.clone()),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Button::new(
+                                        "toggle-resolved",
+                                        if resolved { "Reopen" } else { "Resolve" },
+                                    )
+                                    .label_size(LabelSize::Small)
+                                    .on_click(cx.listener(
+                                        move |this, _, _, cx| {
+                                            this.store.update(cx, |store, cx| {
+                                                store.set_resolved(thread_id, !resolved, cx)
+                                            });
+                                        },
+                                    )),
+                                )
+                                .when(is_host, |this| {
+                                    this.child(
+                                        Button::new("delete-thread", "Delete")
+                                            .label_size(LabelSize::Small)
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.store.update(cx, |store, cx| {
+                                                    store.delete_thread(thread_id, cx)
+                                                });
+                                            })),
+                                    )
+                                }),
+                        ),
+                )
+            })
+            .into_any_element()
+    }
+}
+
+impl Render for CommentsPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let store = self.store.read(cx);
+        let thread_count = store.threads().count();
+        let resolved_count = store
+            .threads()
+            .filter(|(_, _, thread)| thread.resolved)
+            .count();
+        let visible_threads = self.visible_threads(cx);
+        let threads = visible_threads
+            .iter()
+            .filter_map(|id| {
+                let (path, buffer, thread) =
+                    store.threads().find(|(_, _, thread)| thread.id == *id)?;
+                let row = thread.point_range(&buffer.read(cx).snapshot()).start.row;
+                Some(self.render_thread(path, thread, row, cx))
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("CommentsPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("Comments"))
+                    .child(
+                        Button::new(
+                            "toggle-show-resolved",
+                            if self.show_resolved {
+                                format!("Hide Resolved ({resolved_count})")
+                            } else {
+                                format!("Show Resolved ({resolved_count})")
+                            },
+                        )
+                        .label_size(LabelSize::Small)
+                        .disabled(resolved_count == 0)
+                        .on_click(cx.listener(|this, _, _, cx| this.toggle_show_resolved(cx))),
+                    ),
+            )
+            .map(|this| {
+                if thread_count == 0 {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("Comment on a selection to start a thread here.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        v_flex()
+                            .id("comment-threads")
+                            .p_1()
+                            .flex_grow()
+                            .overflow_y_scroll()
+                            .track_scroll(&self.scroll_handle)
+                            .children(threads),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for CommentsPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for CommentsPanel {}
+
+impl Panel for CommentsPanel {
+    fn persistent_name() -> &'static str {
+        "CommentsPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        CommentsPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<CommentsPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| CommentsPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        CommentsPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::MessageBubbles)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Comments Panel".into())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        12
+    }
+}
@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use db::{define_connection, query, sqlez_macros::sql};
+use language::Point;
+use project::comment_store::{Comment, SerializedCommentThread};
+use serde::{Deserialize, Serialize};
+use workspace::{WorkspaceDb, WorkspaceId};
+
+#[derive(Serialize, Deserialize)]
+struct SerializedComment {
+    author: String,
+    body: String,
+    timestamp: u64,
+}
+
+define_connection! {
+    pub static ref COMMENT_DB: CommentDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE comment_threads (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                start_row INTEGER NOT NULL,
+                start_column INTEGER NOT NULL,
+                end_row INTEGER NOT NULL,
+                end_column INTEGER NOT NULL,
+                resolved INTEGER NOT NULL,
+                comments TEXT NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        )];
+}
+
+impl CommentDb {
+    query! {
+        fn comment_thread_rows(
+            workspace_id: WorkspaceId
+        ) -> Result<Vec<(PathBuf, u32, u32, u32, u32, bool, String)>> {
+            SELECT path, start_row, start_column, end_row, end_column, resolved, comments
+            FROM comment_threads
+            WHERE workspace_id = ?
+        }
+    }
+
+    pub(crate) fn comment_threads(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Vec<SerializedCommentThread>> {
+        self.comment_thread_rows(workspace_id)?
+            .into_iter()
+            .map(
+                |(path, start_row, start_column, end_row, end_column, resolved, comments)| {
+                    let comments = serde_json::from_str::<Vec<SerializedComment>>(&comments)
+                        .context("parsing comments")?
+                        .into_iter()
+                        .map(|comment| Comment {
+                            author: comment.author.into(),
+                            body: comment.body.into(),
+                            timestamp: comment.timestamp,
+                        })
+                        .collect();
+                    Ok(SerializedCommentThread {
+                        path: path.into(),
+                        range: Point::new(start_row, start_column)..Point::new(end_row, end_column),
+                        resolved,
+                        comments,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    pub(crate) async fn save_comment_threads(
+        &self,
+        workspace_id: WorkspaceId,
+        threads: Vec<SerializedCommentThread>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("save_comment_threads", || {
+                conn.exec_bound(sql!(DELETE FROM comment_threads WHERE workspace_id = ?))?(
+                    workspace_id,
+                )
+                .context("clearing old comment threads")?;
+                for thread in threads {
+                    let comments = serde_json::to_string(
+                        &thread
+                            .comments
+                            .iter()
+                            .map(|comment| SerializedComment {
+                                author: comment.author.to_string(),
+                                body: comment.body.to_string(),
+                                timestamp: comment.timestamp,
+                            })
+                            .collect::<Vec<_>>(),
+                    )?;
+                    conn.exec_bound(sql!(
+                        INSERT INTO comment_threads (
+                            workspace_id, path, start_row, start_column, end_row, end_column,
+                            resolved, comments
+                        )
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                    ))?((
+                        workspace_id,
+                        thread.path.as_ref(),
+                        thread.range.start.row,
+                        thread.range.start.column,
+                        thread.range.end.row,
+                        thread.range.end.column,
+                        thread.resolved,
+                        comments,
+                    ))
+                    .context("saving comment thread")?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_save_and_load_comment_threads() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let threads = vec![
+            SerializedCommentThread {
+                path: Arc::from(Path::new("/dir/a.rs")),
+                range: Point::new(1, 4)..Point::new(3, 0),
+                resolved: false,
+                comments: vec![
+                    Comment {
+                        author: "alice".into(),
+                        body: "Could this be a method?".into(),
+                        timestamp: 1,
+                    },
+                    Comment {
+                        author: "bob".into(),
+                        body: "Good idea".into(),
+                        timestamp: 2,
+                    },
+                ],
+            },
+            SerializedCommentThread {
+                path: Arc::from(Path::new("/dir/b.rs")),
+                range: Point::new(0, 0)..Point::new(0, 5),
+                resolved: true,
+                comments: vec![Comment {
+                    author: "bob".into(),
+                    body: "Typo".into(),
+                    timestamp: 3,
+                }],
+            },
+        ];
+        COMMENT_DB
+            .save_comment_threads(workspace_id, threads.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(COMMENT_DB.comment_threads(workspace_id).unwrap(), threads);
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::{Context as _, Result};
 use client::proto::{
-    self, DapChecksum, DapChecksumAlgorithm, DapEvaluateContext, DapModule, DapScope,
-    DapScopePresentationHint, DapSource, DapSourcePresentationHint, DapStackFrame, DapVariable,
+    self, DapChecksum, DapChecksumAlgorithm, DapDisassembledInstruction, DapEvaluateContext,
+    DapModule, DapScope, DapScopePresentationHint, DapSource, DapSourcePresentationHint,
+    DapStackFrame, DapVariable,
 };
 use dap_types::{OutputEventCategory, OutputEventGroup, ScopePresentationHint, Source};
 
@@ -284,6 +285,40 @@ impl ProtoConversion for dap_types::StackFrame {
     }
 }
 
+impl ProtoConversion for dap_types::DisassembledInstruction {
+    type ProtoType = DapDisassembledInstruction;
+    type Output = Self;
+
+    fn to_proto(&self) -> Self::ProtoType {
+        Self::ProtoType {
+            address: self.address.clone(),
+            instruction_bytes: self.instruction_bytes.clone(),
+            instruction: self.instruction.clone(),
+            symbol: self.symbol.clone(),
+            location: self.location.as_ref().map(|location| location.to_proto()),
+            line: self.line,
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+        }
+    }
+
+    fn from_proto(payload: Self::ProtoType) -> Self {
+        Self {
+            address: payload.address,
+            instruction_bytes: payload.instruction_bytes,
+            instruction: payload.instruction,
+            symbol: payload.symbol,
+            location: payload.location.map(dap_types::Source::from_proto),
+            line: payload.line,
+            column: payload.column,
+            end_line: payload.end_line,
+            end_column: payload.end_column,
+            presentation_hint: None, // TODO Debugger Collab
+        }
+    }
+}
+
 impl ProtoConversion for dap_types::Module {
     type ProtoType = DapModule;
     type Output = Result<Self>;
@@ -0,0 +1,541 @@
+//! Actions for JSON, YAML and TOML files: pretty-printing, minifying, sorting object keys, and
+//! converting between the formats. They apply to the selections, or to the whole buffer when
+//! nothing is selected. The values are parsed and serialized again, so comments aren't kept, and
+//! the comments of JSON are only removed once that's confirmed.
+
+use std::ops::Range;
+
+use anyhow::{Context as _, Result};
+use editor::{Editor, MultiBufferSnapshot};
+use gpui::{Action, App, AppContext as _, Context, Entity, PromptLevel, Window, actions};
+use serde::{Serialize, Serializer, ser::SerializeMap as _};
+use serde_json::Value;
+
+actions!(
+    data_tools,
+    [
+        PrettyPrint,
+        Minify,
+        SortKeys,
+        ConvertToJson,
+        ConvertToYaml,
+        ConvertToToml,
+    ]
+);
+
+const TOML_DATETIME_KEY: &str = "$__toml_private_datetime";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DataFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl DataFormat {
+    fn for_language(name: &str) -> Option<Self> {
+        match name {
+            "JSON" | "JSONC" => Some(Self::Json),
+            "YAML" => Some(Self::Yaml),
+            "TOML" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    fn language_name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Transform {
+    PrettyPrint,
+    Minify,
+    SortKeys,
+}
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(register).detach();
+}
+
+fn register(editor: &mut Editor, _: Option<&mut Window>, cx: &mut Context<Editor>) {
+    register_transform::<PrettyPrint>(editor, Transform::PrettyPrint, cx);
+    register_transform::<Minify>(editor, Transform::Minify, cx);
+    register_transform::<SortKeys>(editor, Transform::SortKeys, cx);
+    register_conversion::<ConvertToJson>(editor, DataFormat::Json, cx);
+    register_conversion::<ConvertToYaml>(editor, DataFormat::Yaml, cx);
+    register_conversion::<ConvertToToml>(editor, DataFormat::Toml, cx);
+}
+
+fn register_transform<A: Action>(
+    editor: &mut Editor,
+    transform: Transform,
+    cx: &mut Context<Editor>,
+) {
+    let handle = cx.entity().downgrade();
+    editor
+        .register_action(move |_: &A, window, cx| {
+            if let Some(editor) = handle.upgrade() {
+                transform_data(&editor, transform, window, cx);
+            }
+        })
+        .detach();
+}
+
+fn register_conversion<A: Action>(editor: &mut Editor, to: DataFormat, cx: &mut Context<Editor>) {
+    let handle = cx.entity().downgrade();
+    editor
+        .register_action(move |_: &A, window, cx| {
+            if let Some(editor) = handle.upgrade() {
+                convert_data(&editor, to, window, cx);
+            }
+        })
+        .detach();
+}
+
+/// The selections, or the whole buffer when nothing is selected.
+fn data_ranges(
+    editor: &Editor,
+    snapshot: &MultiBufferSnapshot,
+    cx: &mut Context<Editor>,
+) -> Vec<Range<usize>> {
+    let ranges = editor
+        .selections
+        .all::<usize>(cx)
+        .into_iter()
+        .filter(|selection| !selection.is_empty())
+        .map(|selection| selection.range())
+        .collect::<Vec<_>>();
+    if ranges.is_empty() {
+        vec![0..snapshot.len()]
+    } else {
+        ranges
+    }
+}
+
+fn format_at(snapshot: &MultiBufferSnapshot, offset: usize) -> Result<DataFormat> {
+    snapshot
+        .language_at(offset)
+        .and_then(|language| DataFormat::for_language(language.name().as_ref()))
+        .context("only JSON, YAML and TOML are supported")
+}
+
+fn transform_data(
+    editor: &Entity<Editor>,
+    transform: Transform,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let result = editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        let mut removes_comments = false;
+        for range in data_ranges(editor, &snapshot, cx) {
+            let format = format_at(&snapshot, range.start)?;
+            let indent = snapshot
+                .language_settings_at(range.start, cx)
+                .tab_size
+                .get();
+            let text = snapshot.text_for_range(range.clone()).collect::<String>();
+            let new_text = transform_text(&text, format, transform, indent)?;
+            if new_text != text {
+                removes_comments |= format == DataFormat::Json && has_json_comments(&text);
+                let range = snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end);
+                edits.push((range, new_text));
+            }
+        }
+        anyhow::Ok((edits, removes_comments))
+    });
+    let (edits, removes_comments) = match result {
+        Ok(result) => result,
+        Err(error) => {
+            show_error(editor, error, cx);
+            return;
+        }
+    };
+    if edits.is_empty() {
+        return;
+    }
+    if !removes_comments {
+        editor.update(cx, |editor, cx| {
+            editor.transact(window, cx, |editor, _, cx| editor.edit(edits, cx));
+        });
+        return;
+    }
+
+    let answer = window.prompt(
+        PromptLevel::Warning,
+        "Remove the comments?",
+        Some("The JSON is written again from its values, which don't include its comments."),
+        &["Remove Comments", "Cancel"],
+        cx,
+    );
+    let editor = editor.downgrade();
+    window
+        .spawn(cx, async move |cx| {
+            if answer.await.ok() != Some(0) {
+                return Ok(());
+            }
+            editor.update_in(cx, |editor, window, cx| {
+                editor.transact(window, cx, |editor, _, cx| editor.edit(edits, cx));
+            })
+        })
+        .detach_and_log_err(cx);
+}
+
+/// Opens the newest selection, or the whole buffer, converted to another format in a new
+/// buffer, since the file keeps its format.
+fn convert_data(editor: &Entity<Editor>, to: DataFormat, window: &mut Window, cx: &mut App) {
+    let Some(workspace) = editor.read(cx).workspace() else {
+        return;
+    };
+    let result = editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let selection = editor.selections.newest::<usize>(cx);
+        let range = if selection.is_empty() {
+            0..snapshot.len()
+        } else {
+            selection.range()
+        };
+        let from = format_at(&snapshot, range.start)?;
+        let indent = snapshot
+            .language_settings_at(range.start, cx)
+            .tab_size
+            .get();
+        let text = snapshot.text_for_range(range).collect::<String>();
+        let converted = convert_text(&text, from, to, indent)?;
+        anyhow::Ok((converted, editor.buffer().read(cx).title(cx).to_string()))
+    });
+    let (converted, title) = match result {
+        Ok(result) => result,
+        Err(error) => {
+            show_error(editor, error, cx);
+            return;
+        }
+    };
+
+    let languages = workspace.read(cx).app_state().languages.clone();
+    let workspace = workspace.downgrade();
+    window
+        .spawn(cx, async move |cx| {
+            let language = languages.language_for_name(to.language_name()).await.ok();
+            workspace.update_in(cx, |workspace, window, cx| {
+                let project = workspace.project().clone();
+                let buffer = project.update(cx, |project, cx| {
+                    project.create_local_buffer(&converted, language, cx)
+                });
+                let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project), window, cx));
+                editor.update(cx, |editor, _| {
+                    editor.set_breadcrumb_header(format!("{title} as {}", to.language_name()))
+                });
+                workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+}
+
+fn show_error(editor: &Entity<Editor>, error: anyhow::Error, cx: &mut App) {
+    if let Some(workspace) = editor.read(cx).workspace() {
+        workspace.update(cx, |workspace, cx| workspace.show_error(&error, cx));
+    }
+}
+
+fn transform_text(
+    text: &str,
+    format: DataFormat,
+    transform: Transform,
+    indent: u32,
+) -> Result<String> {
+    let mut value = parse(text, format)?;
+    let output = match transform {
+        Transform::PrettyPrint => serialize(&value, format, indent)?,
+        Transform::Minify => {
+            anyhow::ensure!(format == DataFormat::Json, "only JSON can be minified");
+            serde_json::to_string(&value)?
+        }
+        Transform::SortKeys => {
+            sort_keys(&mut value);
+            serialize(&value, format, indent)?
+        }
+    };
+    Ok(with_trailing_newline_of(text, output))
+}
+
+fn convert_text(text: &str, from: DataFormat, to: DataFormat, indent: u32) -> Result<String> {
+    let mut value = parse(text, from)?;
+    if to != DataFormat::Toml {
+        replace_toml_datetimes(&mut value);
+    }
+    let output = serialize(&value, to, indent)
+        .with_context(|| format!("converting to {}", to.language_name()))?;
+    Ok(with_trailing_newline_of(text, output))
+}
+
+fn parse(text: &str, format: DataFormat) -> Result<Value> {
+    match format {
+        // JSON is parsed leniently, to accept the comments and the trailing commas of JSONC.
+        DataFormat::Json => Ok(serde_json_lenient::from_str(text)?),
+        DataFormat::Yaml => Ok(serde_yaml_ng::from_str(text)?),
+        DataFormat::Toml => Ok(toml::from_str(text)?),
+    }
+}
+
+fn serialize(value: &Value, format: DataFormat, indent: u32) -> Result<String> {
+    match format {
+        DataFormat::Json => {
+            let indent = " ".repeat(indent as usize);
+            let mut output = Vec::new();
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            value.serialize(&mut serde_json::Serializer::with_formatter(
+                &mut output,
+                formatter,
+            ))?;
+            Ok(String::from_utf8(output)?)
+        }
+        DataFormat::Yaml => Ok(serde_yaml_ng::to_string(value)?),
+        DataFormat::Toml => Ok(toml::to_string_pretty(&TomlValue(value))?),
+    }
+}
+
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let mut entries = std::mem::take(map).into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (_, value) in &mut entries {
+                sort_keys(value);
+            }
+            *map = entries.into_iter().collect();
+        }
+        Value::Array(values) => values.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+/// The date or time of a map that a TOML date or time was deserialized as, with a private key.
+fn toml_datetime(map: &serde_json::Map<String, Value>) -> Option<&str> {
+    match (map.len(), map.get(TOML_DATETIME_KEY)) {
+        (1, Some(Value::String(datetime))) => Some(datetime),
+        _ => None,
+    }
+}
+
+/// Replaces the TOML dates and times by strings, for the formats that have no such values.
+fn replace_toml_datetimes(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            if let Some(datetime) = toml_datetime(map) {
+                *value = Value::String(datetime.to_string());
+                return;
+            }
+            map.values_mut().for_each(replace_toml_datetimes);
+        }
+        Value::Array(values) => values.iter_mut().for_each(replace_toml_datetimes),
+        _ => {}
+    }
+}
+
+/// Serializes a value to TOML, writing its TOML dates and times as such rather than as maps.
+struct TomlValue<'a>(&'a Value);
+
+impl Serialize for TomlValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Object(map) => {
+                if let Some(datetime) = toml_datetime(map)
+                    .and_then(|datetime| datetime.parse::<toml::value::Datetime>().ok())
+                {
+                    return datetime.serialize(serializer);
+                }
+                let mut serializer = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map {
+                    serializer.serialize_entry(key, &TomlValue(value))?;
+                }
+                serializer.end()
+            }
+            Value::Array(values) => serializer.collect_seq(values.iter().map(TomlValue)),
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+/// Whether the JSON has comments, which are lost when it's written again.
+fn has_json_comments(text: &str) -> bool {
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            '/' if !in_string && matches!(chars.peek(), Some('/' | '*')) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Ends the output with a newline only when the input ended with one, so that transforming a
+/// value within a line doesn't break it.
+fn with_trailing_newline_of(input: &str, output: String) -> String {
+    let mut output = output.trim_end().to_string();
+    if input.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_transform_json() {
+        let json = r#"{"b": 1, "a": {"d": [1, 2], "c": null}}"#;
+        assert_eq!(
+            transform_text(json, DataFormat::Json, Transform::PrettyPrint, 2).unwrap(),
+            indoc! {r#"
+                {
+                  "b": 1,
+                  "a": {
+                    "d": [
+                      1,
+                      2
+                    ],
+                    "c": null
+                  }
+                }"#}
+        );
+        assert_eq!(
+            transform_text(json, DataFormat::Json, Transform::Minify, 2).unwrap(),
+            r#"{"b":1,"a":{"d":[1,2],"c":null}}"#
+        );
+        assert_eq!(
+            transform_text(
+                "// settings\n{\"b\": 1, \"a\": {\"d\": 2, \"c\": 3,},}\n",
+                DataFormat::Json,
+                Transform::SortKeys,
+                4
+            )
+            .unwrap(),
+            indoc! {r#"
+                {
+                    "a": {
+                        "c": 3,
+                        "d": 2
+                    },
+                    "b": 1
+                }
+            "#}
+        );
+        assert!(transform_text("{", DataFormat::Json, Transform::PrettyPrint, 2).is_err());
+    }
+
+    #[test]
+    fn test_transform_yaml() {
+        let yaml = indoc! {"
+            services:
+              web:
+                ports: [8081, 8080]
+                image: nginx
+        "};
+        assert_eq!(
+            transform_text(yaml, DataFormat::Yaml, Transform::SortKeys, 2).unwrap(),
+            indoc! {"
+                services:
+                  web:
+                    image: nginx
+                    ports:
+                    - 8081
+                    - 8080
+            "}
+        );
+        assert!(transform_text(yaml, DataFormat::Yaml, Transform::Minify, 2).is_err());
+    }
+
+    #[test]
+    fn test_transform_toml() {
+        let toml = indoc! {r#"
+            title = "Example"
+            released = 2024-05-01
+
+            [owner]
+            name = "Alice"
+            born = 1979-05-27T07:32:00Z
+        "#};
+        assert_eq!(
+            transform_text(toml, DataFormat::Toml, Transform::SortKeys, 2).unwrap(),
+            indoc! {r#"
+                released = 2024-05-01
+                title = "Example"
+
+                [owner]
+                born = 1979-05-27T07:32:00Z
+                name = "Alice"
+            "#}
+        );
+        assert_eq!(
+            transform_text(toml, DataFormat::Toml, Transform::PrettyPrint, 2).unwrap(),
+            toml
+        );
+    }
+
+    #[test]
+    fn test_has_json_comments() {
+        assert!(has_json_comments("// settings\n{\"a\": 1}"));
+        assert!(has_json_comments("{\"a\": /* one */ 1}"));
+        assert!(!has_json_comments(
+            r#"{"url": "https://zed.dev", "path": "\"//\""}"#
+        ));
+    }
+
+    #[test]
+    fn test_convert() {
+        let toml = indoc! {r#"
+            title = "Example"
+            released = 2024-05-01
+
+            [owner]
+            name = "Alice"
+        "#};
+        assert_eq!(
+            convert_text(toml, DataFormat::Toml, DataFormat::Json, 2).unwrap(),
+            indoc! {r#"
+                {
+                  "title": "Example",
+                  "released": "2024-05-01",
+                  "owner": {
+                    "name": "Alice"
+                  }
+                }
+            "#}
+        );
+        assert_eq!(
+            convert_text(
+                r#"{"name": "Alice", "tags": ["a", "b"]}"#,
+                DataFormat::Json,
+                DataFormat::Yaml,
+                2
+            )
+            .unwrap(),
+            "name: Alice\ntags:\n- a\n- b"
+        );
+        assert_eq!(
+            convert_text(
+                "name: Alice\nowner:\n  id: 1\n",
+                DataFormat::Yaml,
+                DataFormat::Toml,
+                2
+            )
+            .unwrap(),
+            "name = \"Alice\"\n\n[owner]\nid = 1\n"
+        );
+        assert!(convert_text("[1, 2]", DataFormat::Json, DataFormat::Toml, 2).is_err());
+    }
+}
@@ -146,7 +146,12 @@ impl LogStore {
         message: &str,
         cx: &mut Context<Self>,
     ) {
-        self.add_debug_client_message(client_id, io_kind, message.to_string(), cx);
+        self.add_debug_client_message(
+            client_id,
+            io_kind,
+            redaction::redact(message).into_owned(),
+            cx,
+        );
     }
 
     fn on_adapter_log(
@@ -156,7 +161,12 @@ impl LogStore {
         message: &str,
         cx: &mut Context<Self>,
     ) {
-        self.add_debug_client_log(client_id, io_kind, message.to_string(), cx);
+        self.add_debug_client_log(
+            client_id,
+            io_kind,
+            redaction::redact(message).into_owned(),
+            cx,
+        );
     }
 
     pub fn add_project(&mut self, project: &Entity<Project>, cx: &mut Context<Self>) {
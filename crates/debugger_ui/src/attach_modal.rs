@@ -1,10 +1,12 @@
 use dap::{DapRegistry, DebugRequest};
+use db::kvp::KEY_VALUE_STORE;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{AppContext, DismissEvent, Entity, EventEmitter, Focusable, Render};
 use gpui::{Subscription, WeakEntity};
 use picker::{Picker, PickerDelegate};
+use serde::{Deserialize, Serialize};
 use task::ZedDebugConfig;
-use util::debug_panic;
+use util::{ResultExt as _, debug_panic};
 
 use std::sync::Arc;
 use sysinfo::System;
@@ -21,6 +23,56 @@ pub(super) struct Candidate {
     pub(super) command: Vec<String>,
 }
 
+/// The process last attached to with a given debug configuration.
+///
+/// Process ids are rarely stable across runs, so we identify the process by its name and
+/// command line instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct LastAttachedProcess {
+    name: String,
+    command: Vec<String>,
+}
+
+const LAST_ATTACHED_PROCESS_PREFIX: &str = "debugger_last_attached_process";
+
+pub(super) fn last_attached_process_key(definition: &ZedDebugConfig) -> String {
+    format!(
+        "{LAST_ATTACHED_PROCESS_PREFIX}-{}-{}",
+        definition.adapter, definition.label
+    )
+}
+
+fn read_last_attached_process(definition: &ZedDebugConfig) -> Option<LastAttachedProcess> {
+    KEY_VALUE_STORE
+        .read_kvp(&last_attached_process_key(definition))
+        .log_err()
+        .flatten()
+        .and_then(|value| serde_json::from_str(&value).log_err())
+}
+
+/// Moves the process matching `last_attached` to the front, preferring an exact command line
+/// match over a match on the name alone. Returns the pid of the process that was moved.
+fn prioritize_last_attached(
+    candidates: &mut Vec<Candidate>,
+    last_attached: &LastAttachedProcess,
+) -> Option<u32> {
+    let ix = candidates
+        .iter()
+        .position(|candidate| {
+            candidate.name.as_ref() == last_attached.name
+                && candidate.command == last_attached.command
+        })
+        .or_else(|| {
+            candidates
+                .iter()
+                .position(|candidate| candidate.name.as_ref() == last_attached.name)
+        })?;
+    let candidate = candidates.remove(ix);
+    let pid = candidate.pid;
+    candidates.insert(0, candidate);
+    Some(pid)
+}
+
 pub(crate) struct AttachModalDelegate {
     selected_index: usize,
     matches: Vec<StringMatch>,
@@ -28,6 +80,7 @@ pub(crate) struct AttachModalDelegate {
     pub(crate) definition: ZedDebugConfig,
     workspace: WeakEntity<Workspace>,
     candidates: Arc<[Candidate]>,
+    last_attached_pid: Option<u32>,
 }
 
 impl AttachModalDelegate {
@@ -36,10 +89,15 @@ impl AttachModalDelegate {
         definition: ZedDebugConfig,
         candidates: Arc<[Candidate]>,
     ) -> Self {
+        let mut candidates = candidates.to_vec();
+        let last_attached_pid = read_last_attached_process(&definition)
+            .and_then(|last_attached| prioritize_last_attached(&mut candidates, &last_attached));
+
         Self {
             workspace,
             definition,
-            candidates,
+            candidates: candidates.into(),
+            last_attached_pid,
             selected_index: 0,
             matches: Vec::default(),
             placeholder_text: Arc::from("Select the process you want to attach the debugger to"),
@@ -218,6 +276,15 @@ impl PickerDelegate for AttachModalDelegate {
             return cx.emit(DismissEvent);
         };
 
+        let key = last_attached_process_key(&self.definition);
+        let last_attached = LastAttachedProcess {
+            name: candidate.name.to_string(),
+            command: candidate.command.clone(),
+        };
+        if let Some(value) = serde_json::to_string(&last_attached).log_err() {
+            db::write_and_log(cx, move || KEY_VALUE_STORE.write_kvp(key, value));
+        }
+
         match &mut self.definition.request {
             DebugRequest::Attach(config) => {
                 config.process_id = Some(candidate.pid);
@@ -271,6 +338,13 @@ impl PickerDelegate for AttachModalDelegate {
                 .inset(true)
                 .spacing(ListItemSpacing::Sparse)
                 .toggle_state(selected)
+                .when(self.last_attached_pid == Some(candidate.pid), |this| {
+                    this.end_slot(
+                        Label::new("Last Attached")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
                 .child(
                     v_flex()
                         .items_start()
@@ -2,11 +2,11 @@ use crate::persistence::DebuggerPaneItem;
 use crate::session::DebugSession;
 use crate::session::running::RunningState;
 use crate::{
-    ClearAllBreakpoints, Continue, Detach, FocusBreakpointList, FocusConsole, FocusFrames,
-    FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables, NewProcessModal,
-    NewProcessMode, Pause, Restart, ShowStackTrace, StepBack, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleIgnoreBreakpoints, ToggleSessionPicker, ToggleThreadPicker,
-    persistence, spawn_task_or_modal,
+    ClearAllBreakpoints, Continue, Detach, FocusBreakpointList, FocusConsole, FocusDisassembly,
+    FocusFrames, FocusLoadedSources, FocusMemory, FocusModules, FocusTerminal, FocusVariables,
+    NewProcessModal, NewProcessMode, Pause, Restart, ShowStackTrace, StepBack, StepInto, StepOut,
+    StepOver, Stop, ToggleExpandItem, ToggleIgnoreBreakpoints, ToggleSessionPicker,
+    ToggleThreadPicker, persistence, spawn_task_or_modal,
 };
 use anyhow::Result;
 use command_palette_hooks::CommandPaletteFilter;
@@ -260,6 +260,22 @@ impl DebugPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        // Debug adapters run the project's code, so its folders have to be trusted first.
+        if self
+            .project
+            .read(cx)
+            .untrusted_worktrees(cx)
+            .next()
+            .is_some()
+        {
+            let workspace = self.workspace.clone();
+            cx.defer(move |cx| {
+                workspace
+                    .update(cx, |workspace, cx| workspace.ensure_trusted(cx))
+                    .ok();
+            });
+            return;
+        }
         let dap_store = self.project.read(cx).dap_store();
         let session = dap_store.update(cx, |dap_store, cx| {
             dap_store.new_session(
@@ -914,6 +930,10 @@ impl DebugPanel {
         if let Some(session) = self.active_session() {
             session.update(cx, |session, cx| {
                 session.running_state().update(cx, |running, cx| {
+                    if !item.is_supported(running.session().read(cx).capabilities()) {
+                        return;
+                    }
+                    running.ensure_pane_item(item, window, cx);
                     running.activate_item(item, window, cx);
                 });
             });
@@ -1173,9 +1193,9 @@ impl Panel for DebugPanel {
         Some(IconName::Debug)
     }
 
-    fn icon_tooltip(&self, _window: &Window, cx: &App) -> Option<&'static str> {
+    fn icon_tooltip(&self, _window: &Window, cx: &App) -> Option<SharedString> {
         if DebuggerSettings::get_global(cx).button {
-            Some("Debug Panel")
+            Some("Debug Panel".into())
         } else {
             None
         }
@@ -1325,6 +1345,24 @@ impl Render for DebugPanel {
                     .ok();
                 }
             })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusMemory, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Memory, window, cx);
+                    })
+                    .ok();
+                }
+            })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusDisassembly, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+                    })
+                    .ok();
+                }
+            })
             .on_action({
                 let this = this.clone();
                 move |_: &ToggleThreadPicker, window, cx| {
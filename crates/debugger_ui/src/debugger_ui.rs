@@ -45,6 +45,8 @@ actions!(
         FocusModules,
         FocusLoadedSources,
         FocusTerminal,
+        FocusMemory,
+        FocusDisassembly,
         ShowStackTrace,
         ToggleThreadPicker,
         ToggleSessionPicker,
@@ -1,6 +1,8 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
 pub(crate) mod loaded_source_list;
+pub(crate) mod memory_view;
 pub(crate) mod module_list;
 pub mod stack_frame_list;
 pub mod variable_list;
@@ -24,6 +26,7 @@ use dap::{
     client::SessionId,
     debugger_settings::DebuggerSettings,
 };
+use disassembly_view::DisassemblyView;
 use futures::{SinkExt, channel::mpsc};
 use gpui::{
     Action as _, AnyView, AppContext, Axis, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
@@ -31,6 +34,7 @@ use gpui::{
 };
 use language::Buffer;
 use loaded_source_list::LoadedSourceList;
+use memory_view::MemoryView;
 use module_list::ModuleList;
 use project::{
     Project, WorktreeId,
@@ -72,6 +76,8 @@ pub struct RunningState {
     loaded_sources_list: Entity<LoadedSourceList>,
     pub debug_terminal: Entity<DebugTerminal>,
     module_list: Entity<module_list::ModuleList>,
+    memory_view: Entity<MemoryView>,
+    disassembly_view: Entity<DisassemblyView>,
     console: Entity<Console>,
     breakpoint_list: Entity<BreakpointList>,
     panes: PaneGroup,
@@ -626,6 +632,13 @@ impl RunningState {
 
         let loaded_source_list = cx.new(|cx| LoadedSourceList::new(session.clone(), cx));
 
+        let memory_view = cx.new(|cx| MemoryView::new(session.clone(), window, cx));
+
+        let weak_state = cx.weak_entity();
+        let disassembly_view = cx.new(|cx| {
+            DisassemblyView::new(session.clone(), weak_state, stack_frame_list.clone(), cx)
+        });
+
         let console = cx.new(|cx| {
             Console::new(
                 session.clone(),
@@ -679,6 +692,12 @@ impl RunningState {
                         {
                             this.remove_pane_item(DebuggerPaneItem::LoadedSources, window, cx);
                         }
+                        if !capabilities.supports_read_memory_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Memory, window, cx);
+                        }
+                        if !capabilities.supports_disassemble_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+                        }
                     }
                     SessionEvent::RunInTerminal { request, sender } => this
                         .handle_run_in_terminal(request, sender.clone(), window, cx)
@@ -707,6 +726,8 @@ impl RunningState {
                 &breakpoint_list,
                 &loaded_source_list,
                 &debug_terminal,
+                &memory_view,
+                &disassembly_view,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -747,6 +768,8 @@ impl RunningState {
             panes,
             active_pane,
             module_list,
+            memory_view,
+            disassembly_view,
             console,
             breakpoint_list,
             loaded_sources_list: loaded_source_list,
@@ -1142,6 +1165,20 @@ impl RunningState {
                 None,
                 cx,
             )),
+            DebuggerPaneItem::Memory => Box::new(SubView::new(
+                self.memory_view.focus_handle(cx),
+                self.memory_view.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
         }
     }
 
@@ -1326,6 +1363,11 @@ impl RunningState {
         &self.module_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn memory_view(&self) -> &Entity<MemoryView> {
+        &self.memory_view
+    }
+
     pub(crate) fn activate_item(&self, item: DebuggerPaneItem, window: &mut Window, cx: &mut App) {
         let (variable_list_position, pane) = self
             .panes
@@ -0,0 +1,256 @@
+use dap::SteppingGranularity;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ScrollStrategy, Subscription,
+    UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent, ThreadStatus, parse_memory_address};
+use ui::{Divider, Tooltip, prelude::*};
+
+use super::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+
+const INSTRUCTIONS_BEFORE: u64 = 32;
+const INSTRUCTIONS_AFTER: u64 = 96;
+
+pub struct DisassemblyView {
+    session: Entity<Session>,
+    state: WeakEntity<RunningState>,
+    stack_frame_list: Entity<StackFrameList>,
+    instruction_pointer: Option<String>,
+    instructions: Vec<dap::DisassembledInstruction>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub fn new(
+        session: Entity<Session>,
+        state: WeakEntity<RunningState>,
+        stack_frame_list: Entity<StackFrameList>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) | SessionEvent::Disassembly => this.refresh(cx),
+                _ => {}
+            }),
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_) => this.refresh(cx),
+                StackFrameListEvent::BuiltEntries => {}
+            }),
+        ];
+
+        let mut this = Self {
+            session,
+            state,
+            stack_frame_list,
+            instruction_pointer: None,
+            instructions: Vec::new(),
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            _subscriptions,
+        };
+        this.refresh(cx);
+        this
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let instruction_pointer = self
+            .stack_frame_list
+            .read(cx)
+            .opened_stack_frame()
+            .and_then(|frame| frame.instruction_pointer_reference);
+        let Some(instruction_pointer) = instruction_pointer else {
+            self.instruction_pointer = None;
+            self.instructions.clear();
+            cx.notify();
+            return;
+        };
+        let is_new_location = self.instruction_pointer.as_ref() != Some(&instruction_pointer);
+
+        let instructions = self.session.update(cx, |session, cx| {
+            session
+                .disassemble(
+                    instruction_pointer.clone(),
+                    -(INSTRUCTIONS_BEFORE as i64),
+                    INSTRUCTIONS_BEFORE + INSTRUCTIONS_AFTER,
+                    cx,
+                )
+                .map(|instructions| instructions.to_vec())
+        });
+        self.instruction_pointer = Some(instruction_pointer);
+        if let Some(instructions) = instructions {
+            self.instructions = instructions;
+            if is_new_location {
+                if let Some(ix) = self.current_instruction_ix() {
+                    self.scroll_handle
+                        .scroll_to_item(ix, ScrollStrategy::Center);
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn current_instruction_ix(&self) -> Option<usize> {
+        let instruction_pointer = parse_memory_address(self.instruction_pointer.as_deref()?)?;
+        self.instructions.iter().position(|instruction| {
+            parse_memory_address(&instruction.address) == Some(instruction_pointer)
+        })
+    }
+
+    fn step(&mut self, kind: StepKind, cx: &mut Context<Self>) {
+        let Some(thread_id) = self
+            .state
+            .read_with(cx, |state, _| state.thread_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        self.session.update(cx, |session, cx| match kind {
+            StepKind::Over => session.step_over(thread_id, SteppingGranularity::Instruction, cx),
+            StepKind::Into => session.step_in(thread_id, SteppingGranularity::Instruction, cx),
+            StepKind::Out => session.step_out(thread_id, SteppingGranularity::Instruction, cx),
+        });
+    }
+
+    fn render_instruction(
+        &self,
+        ix: usize,
+        current_ix: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let Some(instruction) = self.instructions.get(ix) else {
+            return div().into_any();
+        };
+        let is_current = current_ix == Some(ix);
+
+        h_flex()
+            .id(("disassembly-instruction", ix))
+            .w_full()
+            .px_1()
+            .gap_4()
+            .rounded_sm()
+            .hover(|s| s.bg(cx.theme().colors().element_hover))
+            .when(is_current, |this| {
+                this.bg(cx.theme().colors().editor_debugger_active_line_background)
+            })
+            .child(div().w_3().when(is_current, |this| {
+                this.child(
+                    Icon::new(IconName::DebugStepOver)
+                        .size(IconSize::XSmall)
+                        .color(Color::Warning),
+                )
+            }))
+            .child(
+                Label::new(instruction.address.clone())
+                    .buffer_font(cx)
+                    .color(Color::Muted),
+            )
+            .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                this.child(
+                    div().w_48().child(
+                        Label::new(bytes)
+                            .buffer_font(cx)
+                            .color(Color::Muted)
+                            .truncate(),
+                    ),
+                )
+            })
+            .child(Label::new(instruction.instruction.clone()).buffer_font(cx))
+            .when_some(instruction.symbol.clone(), |this, symbol| {
+                this.child(
+                    Label::new(format!("<{symbol}>"))
+                        .buffer_font(cx)
+                        .color(Color::Accent),
+                )
+            })
+            .into_any()
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_stopped = self
+            .state
+            .read_with(cx, |state, cx| state.thread_status(cx))
+            .ok()
+            .flatten()
+            .is_some_and(|status| status == ThreadStatus::Stopped);
+
+        h_flex()
+            .p_1()
+            .gap_1()
+            .child(
+                IconButton::new("disassembly-step-over", IconName::DebugStepOver)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .tooltip(Tooltip::text("Step Over Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(StepKind::Over, cx))),
+            )
+            .child(
+                IconButton::new("disassembly-step-into", IconName::DebugStepInto)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .tooltip(Tooltip::text("Step Into Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(StepKind::Into, cx))),
+            )
+            .child(
+                IconButton::new("disassembly-step-out", IconName::DebugStepOut)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .tooltip(Tooltip::text("Step Out"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(StepKind::Out, cx))),
+            )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum StepKind {
+    Over,
+    Into,
+    Out,
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let current_ix = self.current_instruction_ix();
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_toolbar(cx))
+            .child(Divider::horizontal())
+            .child(if self.instructions.is_empty() {
+                div()
+                    .p_2()
+                    .child(
+                        Label::new("No disassembly available for the selected stack frame")
+                            .color(Color::Muted),
+                    )
+                    .into_any_element()
+            } else {
+                uniform_list(
+                    cx.entity(),
+                    "disassembly-view",
+                    self.instructions.len(),
+                    move |this, range, _window, cx| {
+                        range
+                            .map(|ix| this.render_instruction(ix, current_ix, cx))
+                            .collect()
+                    },
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .size_full()
+                .into_any_element()
+            })
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt::Write as _;
+
+use editor::{Editor, EditorElement, EditorStyle};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, Subscription, TextStyle, UniformListScrollHandle,
+    uniform_list,
+};
+use menu::Confirm;
+use project::debugger::session::{MemoryBlock, Session, SessionEvent};
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::{Divider, Tooltip, prelude::*};
+
+const BYTES_PER_ROW: usize = 16;
+const PAGE_SIZE: u64 = 1024;
+
+pub struct MemoryView {
+    session: Entity<Session>,
+    address_bar: Entity<Editor>,
+    memory_reference: Option<String>,
+    offset: i64,
+    block: Option<MemoryBlock>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl MemoryView {
+    pub fn new(session: Entity<Session>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let address_bar = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Memory reference or address", cx);
+            editor.set_use_autoclose(false);
+            editor
+        });
+
+        let _subscription = cx.subscribe(&session, |this, _, event, cx| match event {
+            SessionEvent::Stopped(_) | SessionEvent::Memory => {
+                this.refresh(cx);
+            }
+            _ => {}
+        });
+
+        Self {
+            session,
+            address_bar,
+            memory_reference: None,
+            offset: 0,
+            block: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            _subscription,
+        }
+    }
+
+    /// Shows the memory behind `memory_reference`, e.g. a variable's `memoryReference`.
+    pub(crate) fn go_to_reference(
+        &mut self,
+        memory_reference: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.address_bar.update(cx, |editor, cx| {
+            editor.set_text(memory_reference.clone(), window, cx);
+        });
+        self.memory_reference = Some(memory_reference);
+        self.offset = 0;
+        self.block = None;
+        self.refresh(cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn block(&self) -> Option<&MemoryBlock> {
+        self.block.as_ref()
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let memory_reference = self.address_bar.read(cx).text(cx).trim().to_string();
+        if memory_reference.is_empty() {
+            return;
+        }
+        self.go_to_reference(memory_reference, window, cx);
+    }
+
+    fn move_page(&mut self, pages: i64, cx: &mut Context<Self>) {
+        self.offset = self.offset.saturating_add(pages * PAGE_SIZE as i64);
+        self.block = None;
+        self.refresh(cx);
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let Some(memory_reference) = self.memory_reference.clone() else {
+            return;
+        };
+        let offset = self.offset;
+        if let Some(block) = self.session.update(cx, |session, cx| {
+            session.read_memory(memory_reference, offset, PAGE_SIZE, cx)
+        }) {
+            self.block = Some(block);
+        }
+        cx.notify();
+    }
+
+    fn row_count(&self) -> usize {
+        self.block.as_ref().map_or(0, |block| {
+            let total = block.bytes.len() + block.unreadable_bytes as usize;
+            total.div_ceil(BYTES_PER_ROW)
+        })
+    }
+
+    fn render_row(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let Some(block) = self.block.as_ref() else {
+            return div().into_any();
+        };
+
+        let row_start = ix * BYTES_PER_ROW;
+        let row_end = row_start + BYTES_PER_ROW;
+        let address = block.address.wrapping_add(row_start as u64);
+        let readable_len = block.bytes.len();
+        let total_len = readable_len + block.unreadable_bytes as usize;
+
+        let mut hex = String::with_capacity(BYTES_PER_ROW * 3);
+        let mut ascii = String::with_capacity(BYTES_PER_ROW);
+        for byte_ix in row_start..row_end {
+            if byte_ix > row_start {
+                hex.push(' ');
+            }
+            match block.bytes.get(byte_ix) {
+                Some(byte) => {
+                    write!(hex, "{byte:02x}").ok();
+                    ascii.push(if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    });
+                }
+                None if byte_ix < total_len => {
+                    hex.push_str("??");
+                    ascii.push('?');
+                }
+                None => {
+                    hex.push_str("  ");
+                    ascii.push(' ');
+                }
+            }
+        }
+
+        h_flex()
+            .id(("memory-row", ix))
+            .w_full()
+            .gap_4()
+            .px_1()
+            .font_buffer(cx)
+            .text_buffer(cx)
+            .hover(|s| s.bg(cx.theme().colors().element_hover))
+            .child(
+                Label::new(format!("{address:016x}"))
+                    .buffer_font(cx)
+                    .color(Color::Muted),
+            )
+            .child(Label::new(hex).buffer_font(cx))
+            .child(
+                Label::new(ascii)
+                    .buffer_font(cx)
+                    .color(if row_start >= readable_len {
+                        Color::Disabled
+                    } else {
+                        Color::Default
+                    }),
+            )
+            .into_any()
+    }
+
+    fn render_address_bar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let theme = cx.theme();
+        let text_style = TextStyle {
+            color: theme.colors().text,
+            font_family: settings.buffer_font.family.clone(),
+            font_features: settings.buffer_font.features.clone(),
+            font_size: settings.buffer_font_size(cx).into(),
+            font_weight: settings.buffer_font.weight,
+            line_height: relative(settings.buffer_line_height.value()),
+            ..Default::default()
+        };
+
+        h_flex()
+            .p_1()
+            .gap_1()
+            .child(div().flex_1().child(EditorElement::new(
+                &self.address_bar,
+                EditorStyle {
+                    background: theme.colors().editor_background,
+                    local_player: theme.players().local(),
+                    text: text_style,
+                    ..Default::default()
+                },
+            )))
+            .child(
+                IconButton::new("memory-previous-page", IconName::ChevronUp)
+                    .icon_size(IconSize::Small)
+                    .disabled(self.memory_reference.is_none())
+                    .tooltip(Tooltip::text("Previous Page"))
+                    .on_click(cx.listener(|this, _, _, cx| this.move_page(-1, cx))),
+            )
+            .child(
+                IconButton::new("memory-next-page", IconName::ChevronDown)
+                    .icon_size(IconSize::Small)
+                    .disabled(self.memory_reference.is_none())
+                    .tooltip(Tooltip::text("Next Page"))
+                    .on_click(cx.listener(|this, _, _, cx| this.move_page(1, cx))),
+            )
+    }
+}
+
+impl Focusable for MemoryView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for MemoryView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let row_count = self.row_count();
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .key_context("DebugMemoryView")
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .child(self.render_address_bar(cx))
+            .child(Divider::horizontal())
+            .child(if row_count == 0 {
+                div()
+                    .p_2()
+                    .child(
+                        Label::new(if self.memory_reference.is_some() {
+                            "No memory available at this location"
+                        } else {
+                            "Enter a memory reference to inspect"
+                        })
+                        .color(Color::Muted),
+                    )
+                    .into_any_element()
+            } else {
+                uniform_list(
+                    cx.entity(),
+                    "memory-view",
+                    row_count,
+                    |this, range, _window, cx| range.map(|ix| this.render_row(ix, cx)).collect(),
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .size_full()
+                .into_any_element()
+            })
+    }
+}
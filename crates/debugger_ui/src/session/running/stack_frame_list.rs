@@ -129,6 +129,13 @@ impl StackFrameList {
         self.opened_stack_frame_id
     }
 
+    pub(crate) fn opened_stack_frame(&self) -> Option<dap::StackFrame> {
+        let opened_stack_frame_id = self.opened_stack_frame_id?;
+        self.flatten_entries(true)
+            .into_iter()
+            .find(|frame| frame.id == opened_stack_frame_id)
+    }
+
     pub(super) fn schedule_refresh(
         &mut self,
         select_first: bool,
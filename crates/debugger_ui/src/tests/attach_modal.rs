@@ -179,3 +179,87 @@ async fn test_show_attach_modal_and_select_process(
         })
         .unwrap();
 }
+
+#[gpui::test]
+async fn test_attach_modal_prioritizes_last_attached_process(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let definition = task::ZedDebugConfig {
+        adapter: FakeAdapter::ADAPTER_NAME.into(),
+        request: dap::DebugRequest::Attach(AttachRequest::default()),
+        label: "attach to server".into(),
+        stop_on_entry: None,
+    };
+
+    db::kvp::KEY_VALUE_STORE
+        .write_kvp(
+            attach_modal::last_attached_process_key(&definition),
+            json!({ "name": "server", "command": ["server", "--port", "8080"] }).to_string(),
+        )
+        .await
+        .unwrap();
+
+    let attach_modal = workspace
+        .update(cx, |workspace, window, cx| {
+            let workspace_handle = cx.weak_entity();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                AttachModal::with_processes(
+                    workspace_handle,
+                    definition.clone(),
+                    vec![
+                        Candidate {
+                            pid: 1,
+                            name: "client".into(),
+                            command: vec!["client".into()],
+                        },
+                        Candidate {
+                            pid: 2,
+                            name: "server".into(),
+                            command: vec!["server".into(), "--port".into(), "9090".into()],
+                        },
+                        Candidate {
+                            pid: 3,
+                            name: "server".into(),
+                            command: vec!["server".into(), "--port".into(), "8080".into()],
+                        },
+                    ]
+                    .into_iter()
+                    .collect(),
+                    true,
+                    window,
+                    cx,
+                )
+            });
+
+            workspace.active_modal::<AttachModal>(cx).unwrap()
+        })
+        .unwrap();
+
+    cx.run_until_parked();
+
+    workspace
+        .update(cx, |_, _, cx| {
+            let names =
+                attach_modal.update(cx, |modal, cx| attach_modal::_process_names(&modal, cx));
+            assert_eq!(
+                vec![
+                    "server --port 8080 3 server".to_string(),
+                    "client 1 client".to_string(),
+                    "server --port 9090 2 server".to_string(),
+                ],
+                names
+            );
+        })
+        .unwrap();
+}
@@ -0,0 +1,115 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    StoppedEvent,
+    requests::{Initialize, ReadMemory},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{
+    FakeFs, Project,
+    debugger::session::{MemoryBlock, parse_memory_address},
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+use util::path;
+
+#[gpui::test]
+async fn test_memory_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_read_memory_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let read_memory_count = Arc::new(AtomicUsize::new(0));
+    client.on_request::<ReadMemory, _>({
+        let read_memory_count = read_memory_count.clone();
+        move |_, args| {
+            read_memory_count.fetch_add(1, Ordering::SeqCst);
+            assert_eq!("0x1000", args.memory_reference);
+            assert_eq!(Some(0), args.offset);
+
+            Ok(dap::ReadMemoryResponse {
+                address: "0x1000".into(),
+                unreadable_bytes: Some(3),
+                data: Some("SGVsbG8=".into()),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |this, window, cx| {
+        this.ensure_pane_item(DebuggerPaneItem::Memory, window, cx);
+        this.activate_item(DebuggerPaneItem::Memory, window, cx);
+        this.memory_view().update(cx, |view, cx| {
+            view.go_to_reference("0x1000".into(), window, cx);
+        });
+    });
+
+    cx.run_until_parked();
+
+    assert_eq!(1, read_memory_count.load(Ordering::SeqCst));
+
+    running_state.update(cx, |this, cx| {
+        let block = this.memory_view().read(cx).block().cloned();
+        assert_eq!(
+            Some(MemoryBlock {
+                address: 0x1000,
+                bytes: b"Hello".to_vec(),
+                unreadable_bytes: 3,
+            }),
+            block
+        );
+    });
+}
+
+#[test]
+fn test_parse_memory_address() {
+    assert_eq!(Some(0x7ffe_1234), parse_memory_address("0x7ffe1234"));
+    assert_eq!(Some(0xABCD), parse_memory_address(" 0XABCD "));
+    assert_eq!(Some(4096), parse_memory_address("4096"));
+    assert_eq!(None, parse_memory_address("main+4"));
+}
@@ -18,15 +18,37 @@ pub struct DiagnosticIndicator {
     active_editor: Option<WeakEntity<Editor>>,
     workspace: WeakEntity<Workspace>,
     current_diagnostic: Option<Diagnostic>,
+    /// How many lines in the active editor are longer than their preferred line length.
+    overlong_line_count: usize,
     _observe_active_editor: Option<Subscription>,
     diagnostics_update: Task<()>,
 }
 
 impl Render for DiagnosticIndicator {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let overlong_lines = (self.overlong_line_count > 0).then(|| {
+            let count = self.overlong_line_count;
+            ButtonLike::new("overlong-lines-indicator")
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::TextWrap)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(Label::new(count.to_string()).size(LabelSize::Small)),
+                )
+                .tooltip(Tooltip::text(if count == 1 {
+                    "1 line is longer than the preferred line length".to_string()
+                } else {
+                    format!("{count} lines are longer than the preferred line length")
+                }))
+        });
+
         let indicator = h_flex().gap_2();
         if !ProjectSettings::get_global(cx).diagnostics.button {
-            return indicator;
+            return indicator.children(overlong_lines);
         }
 
         let diagnostic_indicator = match (self.summary.error_count, self.summary.warning_count) {
@@ -116,6 +138,7 @@ impl Render for DiagnosticIndicator {
                         }
                     })),
             )
+            .children(overlong_lines)
             .children(status)
     }
 }
@@ -148,6 +171,7 @@ impl DiagnosticIndicator {
             active_editor: None,
             workspace: workspace.weak_handle(),
             current_diagnostic: None,
+            overlong_line_count: 0,
             _observe_active_editor: None,
             diagnostics_update: Task::ready(()),
         }
@@ -162,6 +186,11 @@ impl DiagnosticIndicator {
     }
 
     fn update(&mut self, editor: Entity<Editor>, window: &mut Window, cx: &mut Context<Self>) {
+        let overlong_line_count = editor.read(cx).overlong_line_count();
+        if overlong_line_count != self.overlong_line_count {
+            self.overlong_line_count = overlong_line_count;
+            cx.notify();
+        }
         let (buffer, cursor_position) = editor.update(cx, |editor, cx| {
             let buffer = editor.buffer().read(cx).snapshot(cx);
             let cursor_position = editor.selections.newest::<usize>(cx).head();
@@ -205,8 +234,13 @@ impl StatusItemView for DiagnosticIndicator {
         } else {
             self.active_editor = None;
             self.current_diagnostic = None;
+            self.overlong_line_count = 0;
             self._observe_active_editor = None;
         }
         cx.notify();
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("diagnostics".into())
+    }
 }
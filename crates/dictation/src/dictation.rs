@@ -0,0 +1,339 @@
+//! Dictates text into the focused editor, including the agent panel's message editor: speech is
+//! recorded from the microphone and transcribed by an OpenAI-compatible speech-to-text server,
+//! like a local whisper server or OpenAI's API.
+
+pub mod dictation_indicator;
+mod recorder;
+mod transcription;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use credentials_provider::CredentialsProvider;
+use editor::Editor;
+use gpui::{
+    AnyWindowHandle, App, AppContext as _, AsyncApp, Context, Global, KeyUpEvent, Task, WeakEntity,
+    Window, actions,
+};
+use http_client::HttpClient;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use util::ResultExt as _;
+use workspace::{Toast, Workspace, notifications::NotificationId};
+
+use crate::recorder::Recorder;
+use crate::transcription::{TranscriptionRequest, transcribe};
+
+actions!(dictation, [ToggleDictation, PushToTalk, CancelDictation]);
+
+/// Recordings shorter than this are most likely accidental and not worth transcribing.
+const MIN_RECORDING_SECS: f32 = 0.3;
+
+#[derive(Deserialize, Debug)]
+pub struct DictationSettings {
+    pub api_url: String,
+    pub model: String,
+    pub language: Option<String>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+#[schemars(deny_unknown_fields)]
+pub struct DictationSettingsContent {
+    /// The URL of the OpenAI-compatible server transcribing speech.
+    ///
+    /// Default: "http://localhost:8080/v1"
+    pub api_url: Option<String>,
+    /// The speech-to-text model to use.
+    ///
+    /// Default: "whisper-1"
+    pub model: Option<String>,
+    /// The language spoken, as an ISO-639-1 code like "en". Detected from the speech when unset.
+    ///
+    /// Default: null
+    pub language: Option<String>,
+}
+
+impl Settings for DictationSettings {
+    const KEY: Option<&'static str> = Some("dictation");
+
+    type FileContent = DictationSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+
+    fn import_from_vscode(_: &settings::VsCodeSettings, _: &mut Self::FileContent) {}
+}
+
+/// The dictation in progress, shared by all windows as there is a single microphone.
+#[derive(Default)]
+pub struct Dictation {
+    state: DictationState,
+}
+
+#[derive(Default)]
+enum DictationState {
+    #[default]
+    Idle,
+    Recording {
+        recorder: Recorder,
+        target: DictationTarget,
+        /// Whether recording stops when the key that started it is released.
+        push_to_talk: bool,
+    },
+    Transcribing {
+        _task: Task<()>,
+    },
+}
+
+/// The editor the transcription is inserted into, at its cursors.
+struct DictationTarget {
+    editor: WeakEntity<Editor>,
+    window: AnyWindowHandle,
+}
+
+impl Global for Dictation {}
+
+impl Dictation {
+    pub fn is_recording(&self) -> bool {
+        matches!(self.state, DictationState::Recording { .. })
+    }
+
+    pub fn is_transcribing(&self) -> bool {
+        matches!(self.state, DictationState::Transcribing { .. })
+    }
+
+    fn is_pushing_to_talk(&self) -> bool {
+        matches!(
+            self.state,
+            DictationState::Recording {
+                push_to_talk: true,
+                ..
+            }
+        )
+    }
+}
+
+pub fn init(http_client: Arc<dyn HttpClient>, cx: &mut App) {
+    DictationSettings::register(cx);
+    cx.set_global(Dictation::default());
+
+    cx.observe_new({
+        let http_client = http_client.clone();
+        move |editor: &mut Editor, _, cx: &mut Context<Editor>| {
+            let this = cx.entity().downgrade();
+            editor
+                .register_action({
+                    let this = this.clone();
+                    let http_client = http_client.clone();
+                    move |_: &ToggleDictation, window, cx| {
+                        toggle_dictation(this.clone(), http_client.clone(), window, cx);
+                    }
+                })
+                .detach();
+            editor
+                .register_action({
+                    let this = this.clone();
+                    move |_: &PushToTalk, window, cx| {
+                        // Held keys repeat, dispatching the action again while recording.
+                        if matches!(cx.global::<Dictation>().state, DictationState::Idle) {
+                            start_dictation(this.clone(), true, window, cx);
+                        }
+                    }
+                })
+                .detach();
+            editor
+                .register_key_listener({
+                    let http_client = http_client.clone();
+                    move |_: &KeyUpEvent, _, cx| {
+                        if cx.global::<Dictation>().is_pushing_to_talk() {
+                            stop_dictation(http_client.clone(), cx);
+                        }
+                    }
+                })
+                .detach();
+        }
+    })
+    .detach();
+    cx.observe_new(move |workspace: &mut Workspace, _, _| {
+        let http_client = http_client.clone();
+        workspace.register_action(move |workspace, _: &ToggleDictation, window, cx| {
+            // Reached when no editor is focused, like after clicking the status bar indicator.
+            // Stopping inserts into the editor the dictation started in, so any will do.
+            let editor = workspace
+                .active_item_as::<Editor>(cx)
+                .map(|editor| editor.downgrade());
+            match editor {
+                Some(editor) => toggle_dictation(editor, http_client.clone(), window, cx),
+                None if cx.global::<Dictation>().is_recording() => {
+                    toggle_dictation(WeakEntity::new_invalid(), http_client.clone(), window, cx)
+                }
+                None => {}
+            }
+        });
+        workspace.register_action(|_, _: &CancelDictation, _, cx| {
+            cx.update_global::<Dictation, _>(|dictation, _| {
+                if dictation.is_recording() {
+                    dictation.state = DictationState::Idle;
+                }
+            });
+        });
+    })
+    .detach();
+}
+
+fn toggle_dictation(
+    editor: WeakEntity<Editor>,
+    http_client: Arc<dyn HttpClient>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    match cx.global::<Dictation>().state {
+        DictationState::Idle => start_dictation(editor, false, window, cx),
+        DictationState::Recording { .. } => stop_dictation(http_client, cx),
+        DictationState::Transcribing { .. } => {}
+    }
+}
+
+fn start_dictation(
+    editor: WeakEntity<Editor>,
+    push_to_talk: bool,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    match Recorder::start() {
+        Ok(recorder) => {
+            cx.global_mut::<Dictation>().state = DictationState::Recording {
+                recorder,
+                target: DictationTarget {
+                    editor,
+                    window: window.window_handle(),
+                },
+                push_to_talk,
+            };
+        }
+        Err(error) => show_error(format!("Failed to start dictation: {error:#}"), window, cx),
+    }
+}
+
+fn stop_dictation(http_client: Arc<dyn HttpClient>, cx: &mut App) {
+    let DictationState::Recording {
+        recorder, target, ..
+    } = std::mem::take(&mut cx.global_mut::<Dictation>().state)
+    else {
+        return;
+    };
+    let audio = recorder.stop();
+    if audio.duration_secs() < MIN_RECORDING_SECS {
+        return;
+    }
+
+    let settings = DictationSettings::get_global(cx);
+    let api_url = settings.api_url.clone();
+    let model = settings.model.clone();
+    let language = settings.language.clone();
+    let credentials_provider = <dyn CredentialsProvider>::global(cx);
+    let task = cx.spawn(async move |cx| {
+        let result = async {
+            // Servers run locally don't need a key, while OpenAI's shares the one
+            // of its language model provider, which is saved under its URL.
+            let api_key = credentials_provider
+                .read_credentials(&api_url, cx)
+                .await
+                .log_err()
+                .flatten()
+                .and_then(|(_, api_key)| String::from_utf8(api_key).ok());
+            let wav = cx.background_spawn(async move { audio.to_wav() }).await;
+            transcribe(
+                http_client.as_ref(),
+                TranscriptionRequest {
+                    api_url: &api_url,
+                    api_key: api_key.as_deref(),
+                    model: &model,
+                    language: language.as_deref(),
+                    wav,
+                },
+            )
+            .await
+        }
+        .await;
+        finish_transcription(result, target, cx);
+    });
+    cx.global_mut::<Dictation>().state = DictationState::Transcribing { _task: task };
+}
+
+/// Inserts the transcription into the editor the dictation started in, and gets ready for the
+/// next dictation even when that editor's window was closed in the meantime.
+fn finish_transcription(result: Result<String>, target: DictationTarget, cx: &mut AsyncApp) {
+    target
+        .window
+        .update(cx, |_, window, cx| match result {
+            Ok(text) => {
+                if text.is_empty() {
+                    return;
+                }
+                target
+                    .editor
+                    .update(cx, |editor, cx| {
+                        editor.insert(&text, window, cx);
+                    })
+                    .ok();
+            }
+            Err(error) => show_error(
+                format!("Failed to transcribe dictation: {error:#}"),
+                window,
+                cx,
+            ),
+        })
+        .log_err();
+    cx.update_global::<Dictation, _>(|dictation, _| dictation.state = DictationState::Idle)
+        .ok();
+}
+
+fn show_error(message: String, window: &mut Window, cx: &mut App) {
+    struct DictationError;
+
+    let Some(workspace) = window.root::<Workspace>().flatten() else {
+        log::error!("{message}");
+        return;
+    };
+    workspace.update(cx, |workspace, cx| {
+        workspace.show_toast(
+            Toast::new(NotificationId::unique::<DictationError>(), message),
+            cx,
+        );
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{EmptyView, TestAppContext};
+
+    #[gpui::test]
+    fn test_finish_transcription_after_window_closed(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Dictation::default()));
+        let window = cx.add_window(|_, _| EmptyView);
+        cx.update(|cx| {
+            cx.global_mut::<Dictation>().state = DictationState::Transcribing {
+                _task: Task::ready(()),
+            };
+        });
+        window
+            .update(cx, |_, window, _| window.remove_window())
+            .unwrap();
+        cx.run_until_parked();
+
+        let target = DictationTarget {
+            editor: WeakEntity::new_invalid(),
+            window: window.into(),
+        };
+        finish_transcription(Ok("hello".into()), target, &mut cx.to_async());
+        cx.update(|cx| {
+            let dictation = cx.global::<Dictation>();
+            assert!(!dictation.is_transcribing());
+            assert!(!dictation.is_recording());
+        });
+    }
+}
@@ -0,0 +1,60 @@
+use gpui::{Action, Context, Render, Subscription, Window, div};
+use ui::{Tooltip, prelude::*};
+use workspace::{StatusItemView, item::ItemHandle};
+
+use crate::{Dictation, ToggleDictation};
+
+/// Shows in the status bar while speech is being recorded or transcribed.
+pub struct DictationIndicator {
+    _observe_dictation: Subscription,
+}
+
+impl DictationIndicator {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self {
+            _observe_dictation: cx.observe_global::<Dictation>(|_, cx| cx.notify()),
+        }
+    }
+}
+
+impl Render for DictationIndicator {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let dictation = cx.global::<Dictation>();
+        if dictation.is_transcribing() {
+            return Label::new("Transcribing…")
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element();
+        }
+        if !dictation.is_recording() {
+            return div().into_any_element();
+        }
+
+        Button::new("dictation-recording", "Dictating")
+            .label_size(LabelSize::Small)
+            .icon(IconName::Mic)
+            .icon_size(IconSize::XSmall)
+            .icon_color(Color::Error)
+            .icon_position(IconPosition::Start)
+            .tooltip(Tooltip::for_action_title(
+                "Stop Dictating",
+                &ToggleDictation,
+            ))
+            .on_click(|_, window, cx| window.dispatch_action(ToggleDictation.boxed_clone(), cx))
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for DictationIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("dictation".into())
+    }
+}
@@ -0,0 +1,175 @@
+use std::{sync::Arc, thread};
+
+use anyhow::{Context as _, Result, anyhow};
+use cpal::traits::{DeviceTrait as _, HostTrait as _, StreamTrait as _};
+use parking_lot::Mutex;
+
+/// Audio being captured from the default microphone, downmixed to mono.
+pub(crate) struct Recorder {
+    samples: Arc<Mutex<Vec<f32>>>,
+    sample_rate: u32,
+    /// Stops the capture thread when dropped.
+    _stop_tx: std::sync::mpsc::Sender<()>,
+}
+
+pub(crate) struct RecordedAudio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl Recorder {
+    pub fn start() -> Result<Self> {
+        let device = cpal::default_host()
+            .default_input_device()
+            .context("no microphone available")?;
+        let config = device
+            .default_input_config()
+            .context("failed to get the microphone's configuration")?;
+        let sample_rate = config.sample_rate().0;
+        let channels = config.channels() as usize;
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+        let (started_tx, started_rx) = std::sync::mpsc::channel::<Result<()>>();
+
+        // Streams can't be sent across threads on every platform, so this one lives on a
+        // thread of its own until the recorder is dropped.
+        thread::spawn({
+            let samples = samples.clone();
+            move || {
+                let on_error = |error| log::error!("error capturing dictation audio: {error:?}");
+                let stream = match config.sample_format() {
+                    cpal::SampleFormat::F32 => device.build_input_stream(
+                        &config.config(),
+                        move |data: &[f32], _: &_| push_mono(&samples, data, channels, |s| s),
+                        on_error,
+                        None,
+                    ),
+                    cpal::SampleFormat::I16 => device.build_input_stream(
+                        &config.config(),
+                        move |data: &[i16], _: &_| {
+                            push_mono(&samples, data, channels, |s| s as f32 / i16::MAX as f32)
+                        },
+                        on_error,
+                        None,
+                    ),
+                    format => {
+                        started_tx
+                            .send(Err(anyhow!(
+                                "unsupported microphone sample format {format:?}"
+                            )))
+                            .ok();
+                        return;
+                    }
+                };
+                let stream = match stream.context("failed to open the microphone") {
+                    Ok(stream) => stream,
+                    Err(error) => {
+                        started_tx.send(Err(error)).ok();
+                        return;
+                    }
+                };
+                if let Err(error) = stream.play() {
+                    started_tx.send(Err(error.into())).ok();
+                    return;
+                }
+                started_tx.send(Ok(())).ok();
+                stop_rx.recv().ok();
+            }
+        });
+
+        started_rx
+            .recv()
+            .context("the microphone stopped before recording")??;
+        Ok(Self {
+            samples,
+            sample_rate,
+            _stop_tx: stop_tx,
+        })
+    }
+
+    pub fn stop(self) -> RecordedAudio {
+        RecordedAudio {
+            samples: std::mem::take(&mut *self.samples.lock()),
+            sample_rate: self.sample_rate,
+        }
+    }
+}
+
+fn push_mono<T: Copy>(
+    samples: &Mutex<Vec<f32>>,
+    data: &[T],
+    channels: usize,
+    to_f32: impl Fn(T) -> f32,
+) {
+    let mut samples = samples.lock();
+    samples.extend(
+        data.chunks(channels.max(1)).map(|frame| {
+            frame.iter().map(|sample| to_f32(*sample)).sum::<f32>() / frame.len() as f32
+        }),
+    );
+}
+
+impl RecordedAudio {
+    pub fn duration_secs(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate as f32
+    }
+
+    /// Encodes the audio as a 16-bit PCM WAV file, which every transcription server accepts.
+    pub fn to_wav(&self) -> Vec<u8> {
+        const BITS_PER_SAMPLE: u16 = 16;
+        const CHANNELS: u16 = 1;
+        let block_align = CHANNELS * BITS_PER_SAMPLE / 8;
+        let data_len = (self.samples.len() * block_align as usize) as u32;
+
+        let mut wav = Vec::with_capacity(44 + data_len as usize);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&CHANNELS.to_le_bytes());
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(self.sample_rate * block_align as u32).to_le_bytes());
+        wav.extend_from_slice(&block_align.to_le_bytes());
+        wav.extend_from_slice(&BITS_PER_SAMPLE.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in &self.samples {
+            let sample = (sample.clamp(-1., 1.) * i16::MAX as f32) as i16;
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+        wav
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_wav() {
+        let audio = RecordedAudio {
+            samples: vec![0., 1., -1., 2.],
+            sample_rate: 16_000,
+        };
+        let wav = audio.to_wav();
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 36 + 8);
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 8);
+        let samples = wav[44..]
+            .chunks(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect::<Vec<_>>();
+        assert_eq!(samples, [0, i16::MAX, -i16::MAX, i16::MAX]);
+    }
+
+    #[test]
+    fn test_push_mono() {
+        let samples = Mutex::new(Vec::new());
+        push_mono(&samples, &[0.5f32, 0.25, -0.5, -0.25], 2, |sample| sample);
+        assert_eq!(*samples.lock(), [0.375, -0.375]);
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::{Context as _, Result};
+use futures::AsyncReadExt as _;
+use http_client::{AsyncBody, HttpClient, Method, Request as HttpRequest};
+use serde::Deserialize;
+
+const BOUNDARY: &str = "zed-dictation-boundary";
+
+/// A request to an OpenAI-compatible `audio/transcriptions` endpoint, as served by OpenAI and by
+/// local whisper servers.
+pub(crate) struct TranscriptionRequest<'a> {
+    pub api_url: &'a str,
+    pub api_key: Option<&'a str>,
+    pub model: &'a str,
+    pub language: Option<&'a str>,
+    pub wav: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+pub(crate) async fn transcribe(
+    client: &dyn HttpClient,
+    request: TranscriptionRequest<'_>,
+) -> Result<String> {
+    let uri = format!(
+        "{}/audio/transcriptions",
+        request.api_url.trim_end_matches('/')
+    );
+    let body = multipart_body(&request);
+    let mut http_request = HttpRequest::builder().method(Method::POST).uri(uri).header(
+        "Content-Type",
+        format!("multipart/form-data; boundary={BOUNDARY}"),
+    );
+    if let Some(api_key) = request.api_key {
+        http_request = http_request.header("Authorization", format!("Bearer {api_key}"));
+    }
+    let mut response = client
+        .send(http_request.body(AsyncBody::from(body))?)
+        .await?;
+
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "error transcribing speech, status: {:?}, body: {:?}",
+        response.status(),
+        body
+    );
+    let response: TranscriptionResponse =
+        serde_json::from_str(&body).context("failed to parse the transcription")?;
+    Ok(response.text.trim().to_string())
+}
+
+fn multipart_body(request: &TranscriptionRequest) -> Vec<u8> {
+    let mut body = Vec::with_capacity(request.wav.len() + 512);
+    let mut push_field = |name: &str, value: &str| {
+        body.extend_from_slice(
+            format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    };
+    push_field("model", request.model);
+    push_field("response_format", "json");
+    if let Some(language) = request.language {
+        push_field("language", language);
+    }
+    body.extend_from_slice(
+        format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"dictation.wav\"\r\nContent-Type: audio/wav\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(&request.wav);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_client::{FakeHttpClient, Response};
+
+    #[test]
+    fn test_multipart_body() {
+        let body = multipart_body(&TranscriptionRequest {
+            api_url: "http://localhost:8080/v1",
+            api_key: None,
+            model: "whisper-1",
+            language: Some("en"),
+            wav: b"RIFF".to_vec(),
+        });
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "--zed-dictation-boundary\r\n\
+            Content-Disposition: form-data; name=\"model\"\r\n\r\n\
+            whisper-1\r\n\
+            --zed-dictation-boundary\r\n\
+            Content-Disposition: form-data; name=\"response_format\"\r\n\r\n\
+            json\r\n\
+            --zed-dictation-boundary\r\n\
+            Content-Disposition: form-data; name=\"language\"\r\n\r\n\
+            en\r\n\
+            --zed-dictation-boundary\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"dictation.wav\"\r\n\
+            Content-Type: audio/wav\r\n\r\n\
+            RIFF\r\n\
+            --zed-dictation-boundary--\r\n"
+        );
+    }
+
+    #[test]
+    fn test_transcribe() {
+        let client = FakeHttpClient::create(|request| async move {
+            assert_eq!(
+                request.uri().to_string(),
+                "https://api.openai.com/v1/audio/transcriptions"
+            );
+            assert_eq!(
+                request.headers().get("Authorization").unwrap(),
+                "Bearer secret"
+            );
+            Ok(Response::builder()
+                .status(200)
+                .body(r#"{"text": " Hello, world. "}"#.into())
+                .unwrap())
+        });
+        let text = futures::executor::block_on(transcribe(
+            client.as_ref(),
+            TranscriptionRequest {
+                api_url: "https://api.openai.com/v1/",
+                api_key: Some("secret"),
+                model: "whisper-1",
+                language: None,
+                wav: Vec::new(),
+            },
+        ))
+        .unwrap();
+        assert_eq!(text, "Hello, world.");
+
+        let client = FakeHttpClient::create(|_| async move {
+            Ok(Response::builder()
+                .status(401)
+                .body(r#"{"error": "unauthorized"}"#.into())
+                .unwrap())
+        });
+        let error = futures::executor::block_on(transcribe(
+            client.as_ref(),
+            TranscriptionRequest {
+                api_url: "https://api.openai.com/v1",
+                api_key: None,
+                model: "whisper-1",
+                language: None,
+                wav: Vec::new(),
+            },
+        ))
+        .unwrap_err();
+        assert!(error.to_string().contains("401"), "{error}");
+    }
+}
@@ -210,12 +210,120 @@ pub enum UuidVersion {
     V7,
 }
 
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SetTabSize {
+    /// The tab size to save for the language at the cursor. When unset, the command palette
+    /// prompts for it.
+    #[serde(default)]
+    pub tab_size: Option<NonZeroU32>,
+}
+
+/// Increments the number, date or keyword like `true` at or after each cursor, on its line. In a
+/// selection, this increments the first one of each line.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Increment {
+    /// How much to increment by, 1 by default.
+    #[serde(default)]
+    pub count: Option<u32>,
+}
+
+/// Decrements the number, date or keyword like `true` at or after each cursor, on its line. In a
+/// selection, this decrements the first one of each line.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Decrement {
+    /// How much to decrement by, 1 by default.
+    #[serde(default)]
+    pub count: Option<u32>,
+}
+
+/// Replaces the selections with ascending numbers, in the order of the selections, to number
+/// lines with multiple cursors.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InsertSequence {
+    /// The number of the first selection, 1 by default.
+    #[serde(default)]
+    pub start: Option<i64>,
+    /// How much each number is greater than the one before, 1 by default.
+    #[serde(default)]
+    pub step: Option<i64>,
+}
+
+/// How `SortLines` orders the lines.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LineSortOrder {
+    /// Character by character.
+    #[default]
+    Lexicographic,
+    /// Runs of digits are compared as numbers, so that `item2` comes before `item10`.
+    Natural,
+    /// By the first number of each line, with the lines without a number last.
+    Numeric,
+}
+
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SortLines {
+    #[serde(default)]
+    pub order: LineSortOrder,
+    #[serde(default)]
+    pub descending: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Sorts all the lines of the buffer, rather than the lines of the selections.
+    #[serde(default)]
+    pub whole_buffer: bool,
+}
+
+/// Removes the duplicates of the lines of the selections, ignoring case.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UniqueLinesCaseInsensitive {
+    /// Removes the duplicates among all the lines of the buffer, rather than the lines of the
+    /// selections.
+    #[serde(default)]
+    pub whole_buffer: bool,
+}
+
+/// Removes the duplicates of the lines of the selections.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UniqueLinesCaseSensitive {
+    /// Removes the duplicates among all the lines of the buffer, rather than the lines of the
+    /// selections.
+    #[serde(default)]
+    pub whole_buffer: bool,
+}
+
+/// Reverses the order of the lines of the selections.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReverseLines {
+    /// Reverses all the lines of the buffer, rather than the lines of the selections.
+    #[serde(default)]
+    pub whole_buffer: bool,
+}
+
+/// Shuffles the lines of the selections.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ShuffleLines {
+    /// Shuffles all the lines of the buffer, rather than the lines of the selections.
+    #[serde(default)]
+    pub whole_buffer: bool,
+}
+
 impl_actions!(
     editor,
     [
         ComposeCompletion,
         ConfirmCodeAction,
         ConfirmCompletion,
+        Decrement,
         DeleteToBeginningOfLine,
         DeleteToNextWordEnd,
         DeleteToPreviousWordStart,
@@ -223,22 +331,30 @@ impl_actions!(
         ExpandExcerptsDown,
         ExpandExcerptsUp,
         HandleInput,
+        Increment,
+        InsertSequence,
         MoveDownByLines,
         MovePageDown,
         MovePageUp,
         MoveToBeginningOfLine,
         MoveToEndOfLine,
         MoveUpByLines,
+        ReverseLines,
         SelectDownByLines,
         SelectNext,
         SelectPrevious,
         SelectToBeginningOfLine,
         SelectToEndOfLine,
         SelectUpByLines,
+        SetTabSize,
+        ShuffleLines,
+        SortLines,
         SpawnNearestTask,
         ShowCompletions,
         ToggleCodeActions,
         ToggleComments,
+        UniqueLinesCaseInsensitive,
+        UniqueLinesCaseSensitive,
         FoldAtLevel,
     ]
 );
@@ -253,12 +369,15 @@ actions!(
         AddSelectionBelow,
         ApplyAllDiffHunks,
         ApplyDiffHunk,
+        ApplyRenamePreview,
         Backspace,
         Cancel,
         CancelFlycheck,
         CancelLanguageServerWork,
         ClearFlycheck,
+        ClearPinnedHighlights,
         ConfirmRename,
+        ConfirmRenameWithPreview,
         ConfirmCompletionInsert,
         ConfirmCompletionReplace,
         ContextMenuFirst,
@@ -269,6 +388,8 @@ actions!(
         ConvertToLowerCamelCase,
         ConvertToLowerCase,
         ConvertToOppositeCase,
+        ConvertToScreamingSnakeCase,
+        ConvertToSentenceCase,
         ConvertToSnakeCase,
         ConvertToTitleCase,
         ConvertToUpperCamelCase,
@@ -277,6 +398,7 @@ actions!(
         ConvertToRot47,
         Copy,
         CopyAndTrim,
+        CopyDataPath,
         CopyFileLocation,
         CopyHighlightJson,
         CopyFileName,
@@ -289,6 +411,8 @@ actions!(
         DeleteToEndOfLine,
         DeleteToNextSubwordEnd,
         DeleteToPreviousSubwordStart,
+        DescribeCharacter,
+        DiscardRenamePreview,
         DisplayCursorNames,
         DuplicateLineDown,
         DuplicateLineUp,
@@ -316,9 +440,11 @@ actions!(
         GoToImplementation,
         GoToImplementationSplit,
         GoToNextChange,
+        GoToNextPinnedOccurrence,
         GoToParentModule,
         GoToPreviousChange,
         GoToPreviousDiagnostic,
+        GoToPreviousPinnedOccurrence,
         GoToTypeDefinition,
         GoToTypeDefinitionSplit,
         HalfPageDown,
@@ -327,13 +453,19 @@ actions!(
         Indent,
         InsertUuidV4,
         InsertUuidV7,
+        JoinArguments,
         JoinLines,
+        KillRegion,
         KillRingCut,
+        KillRingSave,
         KillRingYank,
+        KillRingYankPop,
         LineDown,
         LineUp,
         MoveDown,
         MoveLeft,
+        MoveArgumentLeft,
+        MoveArgumentRight,
         MoveLineDown,
         MoveLineUp,
         MoveRight,
@@ -370,13 +502,17 @@ actions!(
         PageDown,
         PageUp,
         Paste,
+        PastePrevious,
+        PeekDefinition,
+        PeekReferences,
+        PinSymbolHighlight,
+        PopMark,
         PreviousEditPrediction,
         Redo,
         RedoSelection,
         Rename,
         RestartLanguageServer,
         RevealInFileManager,
-        ReverseLines,
         RevertFile,
         ReloadFile,
         Rewrap,
@@ -413,9 +549,15 @@ actions!(
         ShowEditPrediction,
         ShowSignatureHelp,
         ShowWordCompletions,
-        ShuffleLines,
         SortLinesCaseInsensitive,
+        SortLinesCaseInsensitiveDescending,
         SortLinesCaseSensitive,
+        SortLinesCaseSensitiveDescending,
+        SortLinesNatural,
+        SortLinesNaturalDescending,
+        SortLinesNumeric,
+        SortLinesNumericDescending,
+        SplitArguments,
         SplitSelectionIntoLines,
         StopLanguageServer,
         SwitchSourceHeader,
@@ -442,6 +584,7 @@ actions!(
         SwapSelectionEnds,
         SetMark,
         ToggleRelativeLineNumbers,
+        ToggleRenamePreviewEdit,
         ToggleSelectionMenu,
         ToggleSoftWrap,
         ToggleTabBar,
@@ -451,8 +594,6 @@ actions!(
         UnfoldAll,
         UnfoldLines,
         UnfoldRecursive,
-        UniqueLinesCaseInsensitive,
-        UniqueLinesCaseSensitive,
     ]
 );
 
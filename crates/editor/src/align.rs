@@ -0,0 +1,217 @@
+use gpui::{Context, Window};
+use language::{Point, TransactionId};
+use multi_buffer::MultiBufferRow;
+use regex::Regex;
+
+use crate::{Editor, HideMouseCursorOrigin};
+
+impl Editor {
+    /// Pads the selected lines so that the text matching `delimiter` lines up across them. Each
+    /// selection spanning several lines is aligned on its own, while the cursors and the
+    /// selections within a line are aligned together, to align lines that aren't next to each
+    /// other.
+    pub fn align_lines(
+        &mut self,
+        delimiter: &Regex,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<TransactionId> {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut groups = Vec::new();
+        let mut single_rows = Vec::new();
+        for selection in self.selections.all::<Point>(cx) {
+            let mut end_row = selection.end.row;
+            if selection.end.column == 0 && end_row > selection.start.row {
+                end_row -= 1;
+            }
+            if end_row == selection.start.row {
+                single_rows.push(end_row);
+            } else {
+                groups.push((selection.start.row..=end_row).collect::<Vec<_>>());
+            }
+        }
+        single_rows.dedup();
+        if single_rows.len() > 1 {
+            groups.push(single_rows);
+        }
+
+        let mut edits = Vec::new();
+        for rows in groups {
+            let lines = rows
+                .iter()
+                .map(|row| {
+                    let line_end = Point::new(*row, snapshot.line_len(MultiBufferRow(*row)));
+                    snapshot
+                        .text_for_range(Point::new(*row, 0)..line_end)
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            for ((row, line), aligned) in rows.iter().zip(&lines).zip(align_text(&lines, delimiter))
+            {
+                if *line != aligned {
+                    let line_end = Point::new(*row, line.len() as u32);
+                    edits.push((Point::new(*row, 0)..line_end, aligned));
+                }
+            }
+        }
+        if edits.is_empty() {
+            return None;
+        }
+        // Two selections can share a line, which is then aligned with the first one.
+        edits.sort_by_key(|(range, _)| range.start);
+        edits.dedup_by_key(|(range, _)| range.start);
+
+        self.transact(window, cx, |this, _, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+        })
+    }
+}
+
+/// A line split around the matches of the delimiter, with one more cell than delimiters.
+struct SplitLine<'a> {
+    cells: Vec<&'a str>,
+    delimiters: Vec<&'a str>,
+}
+
+/// Aligns the delimiters of the lines column by column: the first delimiter of each line lines
+/// up, then the second ones, and so on. The delimiters are padded before them, except for commas,
+/// colons and semicolons, which stay attached to the text before them and are padded after. Lines
+/// without a delimiter are left as they are.
+fn align_text(lines: &[String], delimiter: &Regex) -> Vec<String> {
+    let split_lines = lines
+        .iter()
+        .map(|line| {
+            let mut cells = Vec::new();
+            let mut delimiters = Vec::new();
+            let mut cell_start = 0;
+            for delimiter in delimiter.find_iter(line) {
+                if delimiter.is_empty() {
+                    continue;
+                }
+                cells.push(&line[cell_start..delimiter.start()]);
+                delimiters.push(delimiter.as_str());
+                cell_start = delimiter.end();
+            }
+            cells.push(&line[cell_start..]);
+            (!delimiters.is_empty()).then_some(SplitLine { cells, delimiters })
+        })
+        .collect::<Vec<_>>();
+    let column_count = split_lines
+        .iter()
+        .flatten()
+        .map(|line| line.delimiters.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut aligned = vec![String::new(); lines.len()];
+    for column in 0..=column_count {
+        let delimited_lines = || {
+            split_lines
+                .iter()
+                .zip(0..)
+                .filter_map(|(line, ix)| Some((line.as_ref()?, ix)))
+                .filter(|(line, _)| column < line.delimiters.len())
+        };
+        // The spaces around the delimiters are kept if any line of the column has some, so that
+        // the delimiters still line up.
+        let space_before = delimited_lines().any(|(line, _)| {
+            line.cells[column].ends_with(char::is_whitespace)
+                && !(column == 0 && line.cells[column].trim().is_empty())
+        });
+        let space_after = delimited_lines()
+            .any(|(line, _)| line.cells[column + 1].starts_with(char::is_whitespace));
+
+        let mut width = 0;
+        for (line, ix) in split_lines.iter().zip(0..) {
+            let Some(line) = line else {
+                continue;
+            };
+            let cell = if column == 0 {
+                line.cells[column].trim_end()
+            } else {
+                line.cells.get(column).map_or("", |cell| cell.trim_start())
+            };
+            if column == line.delimiters.len() {
+                aligned[ix].push_str(cell);
+                continue;
+            } else if column > line.delimiters.len() {
+                continue;
+            }
+            let aligned_line = &mut aligned[ix];
+            aligned_line.push_str(cell.trim_end());
+            let delimiter = line.delimiters[column];
+            if is_padded_after(delimiter) {
+                if space_before {
+                    aligned_line.push(' ');
+                }
+                aligned_line.push_str(delimiter);
+            }
+            width = width.max(aligned_line.chars().count());
+        }
+
+        for (line, ix) in delimited_lines() {
+            let aligned_line = &mut aligned[ix];
+            let padding = width - aligned_line.chars().count();
+            aligned_line.push_str(&" ".repeat(padding));
+            let delimiter = line.delimiters[column];
+            if !is_padded_after(delimiter) {
+                if space_before {
+                    aligned_line.push(' ');
+                }
+                aligned_line.push_str(delimiter);
+            }
+            if space_after {
+                aligned_line.push(' ');
+            }
+        }
+    }
+
+    split_lines
+        .iter()
+        .zip(lines)
+        .zip(aligned)
+        .map(|((split_line, line), aligned)| match split_line {
+            Some(_) => aligned,
+            None => line.clone(),
+        })
+        .collect()
+}
+
+fn is_padded_after(delimiter: &str) -> bool {
+    matches!(delimiter.trim(), "," | ":" | ";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn align(lines: &[&str], delimiter: &str) -> Vec<String> {
+        let lines = lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        align_text(&lines, &Regex::new(delimiter).unwrap())
+    }
+
+    #[test]
+    fn test_align_text() {
+        assert_eq!(
+            align(&["    a = 1;", "    bbb = 22;", "    cc=3;"], "="),
+            ["    a   = 1;", "    bbb = 22;", "    cc  = 3;"]
+        );
+        assert_eq!(
+            align(&["1, 22, 333", "4444, 5, 6", "7,8"], ","),
+            ["1,    22, 333", "4444, 5,  6", "7,    8"]
+        );
+        assert_eq!(
+            align(&["name: a", "kind: bb", "description: c"], ":"),
+            ["name:        a", "kind:        bb", "description: c"]
+        );
+        assert_eq!(
+            align(&["a | b | c", "no delimiter", "aaa | bb | c"], r"\|"),
+            ["a   | b  | c", "no delimiter", "aaa | bb | c"]
+        );
+    }
+}
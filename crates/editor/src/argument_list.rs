@@ -0,0 +1,343 @@
+use std::{cmp::Ordering, ops::Range};
+
+use gpui::{Context, Window};
+use language::{BufferSnapshot, IndentSize};
+use multi_buffer::{MultiBufferRow, MultiBufferSnapshot};
+
+use crate::{
+    Autoscroll, Direction, Editor, HideMouseCursorOrigin, ToOffset as _,
+    actions::{JoinArguments, MoveArgumentLeft, MoveArgumentRight, SplitArguments},
+};
+
+/// A comma-separated list between brackets, like the arguments of a call, the parameters of a
+/// function or the elements of an array.
+#[derive(Debug)]
+struct ArgumentList {
+    open: Range<usize>,
+    close: Range<usize>,
+    /// The arguments, without their commas.
+    arguments: Vec<Range<usize>>,
+    has_trailing_comma: bool,
+    /// Whether there are comments between the brackets, which splitting or joining would lose.
+    has_comments: bool,
+}
+
+impl ArgumentList {
+    /// Finds the innermost list around the offset, in multi-buffer offsets.
+    fn at(snapshot: &MultiBufferSnapshot, offset: usize) -> Option<Self> {
+        let mut excerpt = snapshot.excerpt_containing(offset..offset)?;
+        let list = Self::in_buffer(excerpt.buffer(), excerpt.map_offset_to_buffer(offset))?;
+        if !excerpt.contains_buffer_range(list.open.start..list.close.end) {
+            return None;
+        }
+        let mut to_multi_buffer = |range| excerpt.map_range_from_buffer(range);
+        Some(Self {
+            open: to_multi_buffer(list.open),
+            close: to_multi_buffer(list.close),
+            arguments: list
+                .arguments
+                .into_iter()
+                .map(&mut to_multi_buffer)
+                .collect(),
+            has_trailing_comma: list.has_trailing_comma,
+            has_comments: list.has_comments,
+        })
+    }
+
+    /// The list is the syntax node that spans exactly from one bracket to the other. The tokens
+    /// between two commas make up an argument, like `a.b.c` in `vec![a.b.c, d]`. Comments and
+    /// other extra nodes aren't part of any argument.
+    fn in_buffer(buffer: &BufferSnapshot, offset: usize) -> Option<Self> {
+        let bracket_filter = |open: Range<usize>, close: Range<usize>| {
+            open.end <= offset
+                && offset <= close.start
+                && matches!(
+                    buffer.chars_at(open.start).next(),
+                    Some('(' | '[' | '{' | '<')
+                )
+        };
+        let (open, close) =
+            buffer.innermost_enclosing_bracket_ranges(offset..offset, Some(&bracket_filter))?;
+
+        let layer = buffer.syntax_layer_at(offset)?;
+        let node = layer
+            .node()
+            .descendant_for_byte_range(open.start, close.end)?;
+        if node.byte_range() != (open.start..close.end) {
+            return None;
+        }
+        let mut cursor = node.walk();
+        let children = node.children(&mut cursor).collect::<Vec<_>>();
+        if children.len() < 2
+            || children.first()?.byte_range() != open
+            || children.last()?.byte_range() != close
+        {
+            return None;
+        }
+
+        let mut arguments = Vec::new();
+        let mut argument: Option<Range<usize>> = None;
+        let mut has_comma = false;
+        let mut has_trailing_comma = false;
+        let mut has_comments = false;
+        for child in &children[1..children.len() - 1] {
+            let range = child.byte_range();
+            if child.is_extra() || child.kind().contains("comment") {
+                has_comments = true;
+            } else if child.kind() == "," {
+                arguments.extend(argument.take());
+                has_comma = true;
+                has_trailing_comma = true;
+            } else {
+                has_trailing_comma = false;
+                argument = Some(match argument {
+                    Some(argument) => argument.start..range.end,
+                    None => range,
+                });
+            }
+        }
+        arguments.extend(argument);
+
+        // Braces without commas are more likely a block than a list with a single item.
+        let is_braced = buffer.chars_at(open.start).next() == Some('{');
+        if arguments.is_empty() || (is_braced && !has_comma) {
+            return None;
+        }
+        Some(Self {
+            open,
+            close,
+            arguments,
+            has_trailing_comma,
+            has_comments,
+        })
+    }
+
+    /// The argument that the offset is in. Between two arguments, it's the next one.
+    fn argument_at(&self, offset: usize) -> usize {
+        self.arguments
+            .iter()
+            .position(|argument| offset <= argument.end)
+            .unwrap_or(self.arguments.len() - 1)
+    }
+}
+
+impl Editor {
+    pub fn move_argument_left(
+        &mut self,
+        _: &MoveArgumentLeft,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_argument(Direction::Prev, window, cx);
+    }
+
+    pub fn move_argument_right(
+        &mut self,
+        _: &MoveArgumentRight,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_argument(Direction::Next, window, cx);
+    }
+
+    /// Swaps the argument under each cursor with its neighbor. The cursors move with their
+    /// arguments, so that repeating this keeps moving the same argument.
+    fn move_argument(&mut self, direction: Direction, window: &mut Window, cx: &mut Context<Self>) {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        let mut moved_selections = Vec::new();
+        let mut swapped_ranges: Vec<Range<usize>> = Vec::new();
+        for selection in self.selections.all::<usize>(cx) {
+            let Some(list) = ArgumentList::at(&snapshot, selection.head()) else {
+                continue;
+            };
+            let ix = list.argument_at(selection.head());
+            let neighbor_ix = match direction {
+                Direction::Prev => ix.checked_sub(1),
+                Direction::Next => Some(ix + 1).filter(|ix| *ix < list.arguments.len()),
+            };
+            let Some(neighbor_ix) = neighbor_ix else {
+                continue;
+            };
+            let argument = list.arguments[ix].clone();
+            let neighbor = list.arguments[neighbor_ix].clone();
+            let swapped_range = argument.start.min(neighbor.start)..argument.end.max(neighbor.end);
+            if swapped_ranges
+                .iter()
+                .any(|range| range.start < swapped_range.end && swapped_range.start < range.end)
+            {
+                continue;
+            }
+            swapped_ranges.push(swapped_range);
+
+            let argument_text = snapshot
+                .text_for_range(argument.clone())
+                .collect::<String>();
+            let neighbor_text = snapshot
+                .text_for_range(neighbor.clone())
+                .collect::<String>();
+            // The edge of the neighbor that the argument ends up at, and how far the start of
+            // the argument is from it once it's moved.
+            let (edge, start_delta) = match direction {
+                Direction::Prev => (snapshot.anchor_before(neighbor.start), 0),
+                Direction::Next => (snapshot.anchor_after(neighbor.end), argument_text.len()),
+            };
+            let relative_selection = selection.start.clamp(argument.start, argument.end)
+                - argument.start
+                ..selection.end.clamp(argument.start, argument.end) - argument.start;
+            moved_selections.push((edge, start_delta, relative_selection));
+            edits.push((argument, neighbor_text));
+            edits.push((neighbor, argument_text));
+        }
+        if edits.is_empty() {
+            return;
+        }
+        edits.sort_by_key(|(range, _)| range.start);
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            let snapshot = this.buffer.read(cx).snapshot(cx);
+            let ranges = moved_selections
+                .into_iter()
+                .map(|(edge, start_delta, relative_selection)| {
+                    let start = edge.to_offset(&snapshot) - start_delta;
+                    start + relative_selection.start..start + relative_selection.end
+                })
+                .collect::<Vec<_>>();
+            this.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_ranges(ranges)
+            });
+        });
+    }
+
+    /// Puts each argument of the lists under the cursors on its own line, indented one level
+    /// deeper than the line of the opening bracket. The last argument gets a trailing comma if
+    /// the list had one, or if the language prefers it. Lists with comments are left as they are.
+    pub fn split_arguments(
+        &mut self,
+        _: &SplitArguments,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        for list in argument_lists_at_selections(self, &snapshot, cx) {
+            if list.has_comments {
+                continue;
+            }
+            let row = snapshot.offset_to_point(list.open.start).row;
+            let indent = snapshot.indent_size_for_line(MultiBufferRow(row));
+            let settings = snapshot.language_settings_at(list.open.start, cx);
+            let indent_unit = if settings.hard_tabs {
+                IndentSize::tab()
+            } else {
+                IndentSize::spaces(settings.tab_size.get())
+            };
+            let argument_indent = indent
+                .with_delta(Ordering::Greater, indent_unit)
+                .chars()
+                .collect::<String>();
+            let trailing_comma = list.has_trailing_comma
+                || snapshot
+                    .language_at(list.open.start)
+                    .is_some_and(|language| language.config().split_lists_with_trailing_comma);
+
+            let mut gap_start = list.open.end;
+            for (ix, argument) in list.arguments.iter().enumerate() {
+                let separator = if ix == 0 { "" } else { "," };
+                edits.push((
+                    gap_start..argument.start,
+                    format!("{separator}\n{argument_indent}"),
+                ));
+                gap_start = argument.end;
+            }
+            edits.push((
+                gap_start..list.close.start,
+                format!(
+                    "{}\n{}",
+                    if trailing_comma { "," } else { "" },
+                    indent.chars().collect::<String>()
+                ),
+            ));
+        }
+        self.edit_argument_lists(edits, window, cx);
+    }
+
+    /// Joins the arguments of the lists under the cursors on one line, without a trailing comma
+    /// unless it's the only argument's, like in the `(x,)` tuple. Lists with comments are left as
+    /// they are.
+    pub fn join_arguments(
+        &mut self,
+        _: &JoinArguments,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        for list in argument_lists_at_selections(self, &snapshot, cx) {
+            if list.has_comments {
+                continue;
+            }
+            let padding = if snapshot.chars_at(list.open.start).next() == Some('{') {
+                " "
+            } else {
+                ""
+            };
+            let mut gap_start = list.open.end;
+            for (ix, argument) in list.arguments.iter().enumerate() {
+                let separator = if ix == 0 { padding } else { ", " };
+                edits.push((gap_start..argument.start, separator.to_string()));
+                gap_start = argument.end;
+            }
+            let trailing_comma = if list.has_trailing_comma && list.arguments.len() == 1 {
+                ","
+            } else {
+                ""
+            };
+            edits.push((
+                gap_start..list.close.start,
+                format!("{trailing_comma}{padding}"),
+            ));
+        }
+        self.edit_argument_lists(edits, window, cx);
+    }
+
+    fn edit_argument_lists(
+        &mut self,
+        mut edits: Vec<(Range<usize>, String)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if edits.is_empty() {
+            return;
+        }
+        edits.sort_by_key(|(range, _)| range.start);
+        edits.dedup_by(|a, b| a.0 == b.0);
+        self.transact(window, cx, |this, _, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.request_autoscroll(Autoscroll::fit(), cx);
+        });
+    }
+}
+
+/// The lists under the cursors, once each.
+fn argument_lists_at_selections(
+    editor: &Editor,
+    snapshot: &MultiBufferSnapshot,
+    cx: &mut Context<Editor>,
+) -> Vec<ArgumentList> {
+    let mut lists: Vec<ArgumentList> = Vec::new();
+    for selection in editor.selections.all::<usize>(cx) {
+        if let Some(list) = ArgumentList::at(snapshot, selection.head()) {
+            if !lists.iter().any(|other| other.open == list.open) {
+                lists.push(list);
+            }
+        }
+    }
+    lists
+}
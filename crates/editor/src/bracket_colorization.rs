@@ -0,0 +1,166 @@
+use std::{cmp::Reverse, ops::Range};
+
+use gpui::{Context, HighlightStyle};
+use language::Point;
+use multi_buffer::{Anchor, MultiBufferRow, ToPoint as _};
+use theme::ActiveTheme as _;
+
+use crate::Editor;
+
+/// How many rows above and below the visible ones to color, so that scrolling a little
+/// doesn't reveal uncolored brackets.
+const ROW_MARGIN: u32 = 50;
+
+enum RainbowBracketHighlight {}
+
+impl Editor {
+    /// Colors the brackets around the visible rows by their nesting depth, unless they are
+    /// colored already.
+    ///
+    /// Pass `invalidate` when the text, the syntax trees, or the settings may have changed.
+    pub(crate) fn refresh_bracket_colors(&mut self, invalidate: bool, cx: &mut Context<Self>) {
+        if invalidate {
+            self.colored_bracket_rows = None;
+        }
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let visible_start = self.scroll_manager.anchor().anchor.to_point(&snapshot).row;
+        let visible_end = visible_start + self.visible_line_count().unwrap_or(0.).ceil() as u32;
+        if self
+            .colored_bracket_rows
+            .as_ref()
+            .is_some_and(|rows| rows.start.0 <= visible_start && visible_end <= rows.end.0)
+        {
+            return;
+        }
+
+        let start_row = MultiBufferRow(visible_start.saturating_sub(ROW_MARGIN));
+        let end_row = MultiBufferRow((visible_end + ROW_MARGIN).min(snapshot.max_point().row));
+        let range = Point::new(start_row.0, 0)..Point::new(end_row.0, snapshot.line_len(end_row));
+        self.colored_bracket_rows = Some(start_row..end_row);
+
+        let accents = cx.theme().accents().clone();
+        let mut ranges_by_color = vec![Vec::<Range<Anchor>>::new(); accents.0.len()];
+        for (buffer, buffer_range, excerpt_id) in snapshot.range_to_buffer_ranges(range) {
+            if !buffer
+                .settings_at(buffer_range.start, cx)
+                .bracket_colorization
+                .enabled
+            {
+                continue;
+            }
+
+            let mut pairs = buffer
+                .bracket_ranges(buffer_range)
+                // Quotes are matched like brackets, but don't nest.
+                .filter(|pair| {
+                    buffer.chars_at(pair.open_range.start).next()
+                        != buffer.chars_at(pair.close_range.start).next()
+                })
+                .collect::<Vec<_>>();
+            pairs.sort_by_key(|pair| (pair.open_range.start, Reverse(pair.close_range.end)));
+            pairs.dedup_by(|a, b| a.open_range == b.open_range && a.close_range == b.close_range);
+
+            // The pairs enclosing the current one, by where they end.
+            let mut enclosing_ends = Vec::<usize>::new();
+            for pair in pairs {
+                while enclosing_ends
+                    .last()
+                    .is_some_and(|end| *end <= pair.open_range.start)
+                {
+                    enclosing_ends.pop();
+                }
+                let depth = enclosing_ends.len();
+                enclosing_ends.push(pair.close_range.end);
+
+                for bracket_range in [pair.open_range, pair.close_range] {
+                    let start = snapshot
+                        .anchor_in_excerpt(excerpt_id, buffer.anchor_after(bracket_range.start));
+                    let end = snapshot
+                        .anchor_in_excerpt(excerpt_id, buffer.anchor_before(bracket_range.end));
+                    if let Some((start, end)) = start.zip(end) {
+                        ranges_by_color[depth % accents.0.len()].push(start..end);
+                    }
+                }
+            }
+        }
+
+        self.clear_keyed_highlights::<RainbowBracketHighlight>(cx);
+        for (color_ix, mut ranges) in ranges_by_color.into_iter().enumerate() {
+            if ranges.is_empty() {
+                continue;
+            }
+            ranges.sort_by(|a, b| a.start.cmp(&b.start, &snapshot));
+            self.highlight_text_key::<RainbowBracketHighlight>(
+                color_ix,
+                ranges,
+                HighlightStyle {
+                    color: Some(accents.color_for_index(color_ix as u32)),
+                    ..Default::default()
+                },
+                cx,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        editor_tests::init_test,
+        test::{editor_lsp_test_context::rust_lang, editor_test_context::EditorTestContext},
+    };
+    use indoc::indoc;
+    use language::language_settings::BracketColorizationSettings;
+    use multi_buffer::ToOffset as _;
+
+    #[gpui::test]
+    async fn test_bracket_colors_by_depth(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |settings| {
+            settings.defaults.bracket_colorization = Some(BracketColorizationSettings {
+                enabled: true,
+                ..Default::default()
+            })
+        });
+
+        let mut cx = EditorTestContext::new(cx).await;
+        cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+        cx.set_state(indoc! {r#"
+            fn a(b: [u8; 2]) {
+                c(d("ˇe"));
+            }
+        "#});
+        cx.run_until_parked();
+
+        let expected = [
+            indoc! {r#"
+                fn a«(»b: [u8; 2]«)» «{»
+                    c(d("e"));
+                «}»
+            "#},
+            indoc! {r#"
+                fn a(b: «[»u8; 2«]») {
+                    c«(»d("e")«)»;
+                }
+            "#},
+            indoc! {r#"
+                fn a(b: [u8; 2]) {
+                    c(d«(»"e"«)»);
+                }
+            "#},
+        ];
+        let snapshot = cx.update_editor(|editor, window, cx| editor.snapshot(window, cx));
+        let mut highlights = snapshot.keyed_text_highlight_ranges::<RainbowBracketHighlight>();
+        highlights.sort_by_key(|(key, _)| *key);
+        assert_eq!(highlights.len(), expected.len());
+        for ((key, highlights), expected) in highlights.into_iter().zip(expected) {
+            let ranges = highlights
+                .1
+                .iter()
+                .map(|range| range.to_offset(&snapshot.buffer_snapshot))
+                .collect::<Vec<_>>();
+            assert_eq!(ranges, cx.ranges(expected), "brackets at depth {key}");
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::borrow::Cow;
+
+use collections::VecDeque;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{App, ClipboardEntry, ClipboardItem, Context, Global, Window};
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use util::ResultExt as _;
+
+use crate::{
+    ClipboardSelection, Editor, EditorSettings, HideMouseCursorOrigin, actions::PastePrevious,
+    kill_ring::YankSource,
+};
+
+const CLIPBOARD_HISTORY_KEY: &str = "clipboard_history";
+
+/// The text recently cut or copied in any editor, most recent first.
+#[derive(Default)]
+pub struct ClipboardHistory {
+    pub(crate) entries: VecDeque<ClipboardItem>,
+}
+
+impl Global for ClipboardHistory {}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedEntry {
+    text: String,
+    selections: Option<Vec<ClipboardSelection>>,
+}
+
+impl ClipboardHistory {
+    pub fn entries(&self) -> impl ExactSizeIterator<Item = &ClipboardItem> {
+        self.entries.iter()
+    }
+
+    /// Adds `item` to the front of the history, moving it there if it was already present. Items
+    /// that look like they contain a secret aren't added, since the history can be persisted.
+    pub fn push(item: ClipboardItem, cx: &mut App) {
+        let settings = EditorSettings::get_global(cx).clipboard_history;
+        if !settings.enabled || item.text().is_some_and(|text| is_likely_secret(&text)) {
+            return;
+        }
+        let history = cx.default_global::<Self>();
+        history.entries.retain(|entry| entry.text() != item.text());
+        history.entries.push_front(item);
+        history.entries.truncate(settings.max_entries);
+        if let Some(json) = settings.persist.then(|| history.serialize()).flatten() {
+            cx.background_spawn(KEY_VALUE_STORE.write_kvp(CLIPBOARD_HISTORY_KEY.to_string(), json))
+                .detach_and_log_err(cx);
+        }
+    }
+
+    pub(crate) fn position(&self, item: &ClipboardItem) -> Option<usize> {
+        let text = item.text();
+        self.entries.iter().position(|entry| entry.text() == text)
+    }
+
+    fn serialize(&self) -> Option<String> {
+        let entries = self
+            .entries
+            .iter()
+            .filter_map(|item| match item.entries().first()? {
+                ClipboardEntry::String(entry) => Some(SerializedEntry {
+                    text: entry.text().clone(),
+                    selections: entry.metadata_json(),
+                }),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        serde_json::to_string(&entries).log_err()
+    }
+}
+
+fn is_likely_secret(text: &str) -> bool {
+    matches!(redaction::redact(text), Cow::Owned(_))
+}
+
+/// Writes `item` to the system clipboard and records it in the [`ClipboardHistory`], unless it
+/// was copied from a masked editor, like a password prompt.
+pub(crate) fn write_to_clipboard(item: ClipboardItem, masked: bool, cx: &mut App) {
+    if !masked {
+        ClipboardHistory::push(item.clone(), cx);
+    }
+    cx.write_to_clipboard(item);
+}
+
+/// Restores the history saved by a previous session, if it is configured to persist.
+pub(crate) fn init(cx: &mut App) {
+    let settings = EditorSettings::get_global(cx).clipboard_history;
+    if !settings.enabled || !settings.persist {
+        return;
+    }
+    cx.spawn(async move |cx| {
+        let Some(json) = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(CLIPBOARD_HISTORY_KEY) })
+            .await?
+        else {
+            return anyhow::Ok(());
+        };
+        let entries = serde_json::from_str::<Vec<SerializedEntry>>(&json)?;
+        cx.update(|cx| {
+            let max_entries = EditorSettings::get_global(cx).clipboard_history.max_entries;
+            let history = cx.default_global::<ClipboardHistory>();
+            for entry in entries {
+                if history.entries.len() >= max_entries {
+                    break;
+                }
+                let item = match entry.selections {
+                    Some(selections) => {
+                        ClipboardItem::new_string_with_json_metadata(entry.text, selections)
+                    }
+                    None => ClipboardItem::new_string(entry.text),
+                };
+                if history.position(&item).is_none() {
+                    history.entries.push_back(item);
+                }
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+impl Editor {
+    /// Replaces the text inserted by the last paste with the entry before it in the
+    /// [`ClipboardHistory`], cycling back to the newest one after the oldest.
+    pub fn paste_previous(
+        &mut self,
+        _: &PastePrevious,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.cycle_yank(YankSource::ClipboardHistory, window, cx);
+    }
+
+    /// Pastes the entry at `index` in the [`ClipboardHistory`], where 0 is the most recent one.
+    pub fn paste_from_history(
+        &mut self,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(item) = cx
+            .try_global::<ClipboardHistory>()
+            .and_then(|history| history.entries.get(index).cloned())
+        else {
+            return;
+        };
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        self.yank(
+            item,
+            YankSource::ClipboardHistory,
+            Some(index),
+            true,
+            window,
+            cx,
+        );
+    }
+}
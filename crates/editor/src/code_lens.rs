@@ -0,0 +1,456 @@
+use std::{mem, ops::RangeInclusive, sync::Arc, time::Duration};
+
+use collections::{HashMap, HashSet};
+use futures::future::join_all;
+use gpui::{AnyElement, App, Context, Entity, Task, WeakEntity, Window};
+use language::{Buffer, BufferId, Point, ToPoint as _, language_settings::language_settings};
+use project::CodeAction;
+use ui::prelude::*;
+use util::ResultExt as _;
+
+use crate::{
+    Editor,
+    actions::{PeekReferences, ToggleCodeActions},
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+};
+
+const CODE_LENS_DEBOUNCE: Duration = Duration::from_millis(250);
+/// How long to wait after an edit before fetching the lenses of the edited lines, which is
+/// longer than other refreshes so that typing doesn't query the language server on every key.
+pub(crate) const CODE_LENS_EDIT_DEBOUNCE: Duration = Duration::from_millis(1000);
+
+/// Client-side commands that language servers attach to reference count lenses.
+const SHOW_REFERENCES_COMMANDS: &[&str] = &[
+    "editor.action.showReferences",
+    "editor.action.findReferences",
+    "rust-analyzer.showReferences",
+];
+
+/// Client-side commands that language servers attach to the lenses running a test or a binary.
+const RUN_COMMANDS: &[&str] = &[
+    "rust-analyzer.runSingle",
+    "rust-analyzer.debugSingle",
+    "rust-analyzer.runDebug",
+];
+
+/// The code lenses shown above the lines of an editor.
+#[derive(Default)]
+pub(crate) struct CodeLensState {
+    buffers: HashMap<BufferId, BufferCodeLens>,
+    /// Whether the next refresh fetches the lenses of every line rather than only the edited ones.
+    refetch_all: bool,
+    refresh_task: Option<Task<()>>,
+}
+
+/// The lenses shown for a buffer, as of the version they were fetched at.
+struct BufferCodeLens {
+    version: clock::Global,
+    blocks: Vec<CodeLensBlock>,
+}
+
+struct CodeLensBlock {
+    block_id: CustomBlockId,
+    position: text::Anchor,
+    titles: Vec<SharedString>,
+}
+
+/// The code lenses starting on the same line of a buffer.
+struct CodeLensLine {
+    buffer: Entity<Buffer>,
+    row: u32,
+    indent: u32,
+    lenses: Vec<(SharedString, CodeAction)>,
+}
+
+/// The lenses fetched for the given rows of a buffer, or all of its rows.
+struct FetchedCodeLens {
+    buffer: Entity<Buffer>,
+    version: clock::Global,
+    rows: Option<RangeInclusive<u32>>,
+    lines: Vec<CodeLensLine>,
+}
+
+impl Editor {
+    /// Fetches the lenses of every line again, as when the language server asks for it.
+    pub(crate) fn refresh_code_lens(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.code_lens.refetch_all = true;
+        self.schedule_code_lens_refresh(CODE_LENS_DEBOUNCE, window, cx);
+    }
+
+    /// Fetches the lenses of the lines edited since they were last fetched, and of buffers
+    /// without lenses yet.
+    pub(crate) fn refresh_edited_code_lens(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let debounce = if self.code_lens.refetch_all {
+            CODE_LENS_DEBOUNCE
+        } else {
+            CODE_LENS_EDIT_DEBOUNCE
+        };
+        self.schedule_code_lens_refresh(debounce, window, cx);
+    }
+
+    fn schedule_code_lens_refresh(
+        &mut self,
+        debounce: Duration,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode.is_full() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let buffers = self
+            .buffer
+            .read(cx)
+            .all_buffers()
+            .into_iter()
+            .filter(|buffer| {
+                let buffer = buffer.read(cx);
+                language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx).code_lens
+            })
+            .collect::<Vec<_>>();
+        let shown_buffer_ids = buffers
+            .iter()
+            .map(|buffer| buffer.read(cx).remote_id())
+            .collect::<HashSet<_>>();
+        let hidden_buffer_ids = self
+            .code_lens
+            .buffers
+            .keys()
+            .filter(|buffer_id| !shown_buffer_ids.contains(buffer_id))
+            .copied()
+            .collect::<Vec<_>>();
+        for buffer_id in hidden_buffer_ids {
+            self.clear_code_lens(buffer_id, None, cx);
+        }
+        if buffers.is_empty() {
+            self.code_lens.refresh_task = None;
+            return;
+        }
+
+        self.code_lens.refresh_task = Some(cx.spawn_in(window, async move |editor, cx| {
+            cx.background_executor().timer(debounce).await;
+            let Ok(requests) = editor.update(cx, |editor, cx| {
+                let refetch_all = mem::take(&mut editor.code_lens.refetch_all);
+                buffers
+                    .into_iter()
+                    .filter_map(|buffer| {
+                        let (version, rows) = {
+                            let buffer = buffer.read(cx);
+                            let version = buffer.version();
+                            match editor.code_lens.buffers.get(&buffer.remote_id()) {
+                                Some(shown) if !refetch_all => {
+                                    (version, Some(edited_rows(buffer, &shown.version)?))
+                                }
+                                _ => (version, None),
+                            }
+                        };
+                        let request = project
+                            .read(cx)
+                            .lsp_store()
+                            .update(cx, |lsp_store, cx| lsp_store.code_lens(&buffer, cx));
+                        Some((buffer, version, rows, request))
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+
+            let mut fetched = Vec::new();
+            for (buffer, version, rows, request) in requests {
+                let Some(actions) = request.await.log_err() else {
+                    continue;
+                };
+                let Ok(resolve_tasks) = project.update(cx, |project, cx| {
+                    let snapshot = buffer.read(cx).snapshot();
+                    actions
+                        .into_iter()
+                        .filter(|action| {
+                            rows.as_ref().is_none_or(|rows| {
+                                rows.contains(&action.range.start.to_point(&snapshot).row)
+                            })
+                        })
+                        .map(|action| project.resolve_code_lens(&buffer, action, cx))
+                        .collect::<Vec<_>>()
+                }) else {
+                    return;
+                };
+                let lenses = join_all(resolve_tasks)
+                    .await
+                    .into_iter()
+                    .filter_map(|r| r.log_err())
+                    .collect::<Vec<_>>();
+                let Ok(lines) = cx.update(|_, cx| group_by_line(&buffer, lenses, cx)) else {
+                    return;
+                };
+                fetched.push(FetchedCodeLens {
+                    buffer,
+                    version,
+                    rows,
+                    lines,
+                });
+            }
+
+            editor
+                .update_in(cx, |editor, _, cx| {
+                    for fetched in fetched {
+                        editor.show_code_lens(fetched, cx);
+                    }
+                })
+                .log_err();
+        }));
+    }
+
+    /// Removes the lenses of a buffer on the given rows, or all of them.
+    fn clear_code_lens(
+        &mut self,
+        buffer_id: BufferId,
+        rows: Option<&RangeInclusive<u32>>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(shown) = self.code_lens.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let block_ids = match (rows, self.buffer.read(cx).buffer(buffer_id)) {
+            (Some(rows), Some(buffer)) => {
+                let snapshot = buffer.read(cx).snapshot();
+                let mut block_ids = HashSet::default();
+                shown.blocks.retain(|block| {
+                    let row = block.position.to_point(&snapshot).row;
+                    let cleared = rows.contains(&row);
+                    if cleared {
+                        block_ids.insert(block.block_id);
+                    }
+                    !cleared
+                });
+                block_ids
+            }
+            _ => {
+                let shown = self.code_lens.buffers.remove(&buffer_id);
+                shown
+                    .into_iter()
+                    .flat_map(|shown| shown.blocks)
+                    .map(|block| block.block_id)
+                    .collect()
+            }
+        };
+        if !block_ids.is_empty() {
+            self.remove_blocks(block_ids, None, cx);
+        }
+    }
+
+    fn show_code_lens(&mut self, fetched: FetchedCodeLens, cx: &mut Context<Self>) {
+        let buffer_id = fetched.buffer.read(cx).remote_id();
+        self.clear_code_lens(buffer_id, fetched.rows.as_ref(), cx);
+
+        let editor = cx.entity().downgrade();
+        let snapshot = fetched.buffer.read(cx).snapshot();
+        let mut positions = Vec::new();
+        let mut blocks = Vec::new();
+        for line in fetched.lines {
+            let point = Point::new(line.row, 0);
+            let Some(anchor) = self
+                .buffer
+                .read(cx)
+                .buffer_point_to_anchor(&line.buffer, point, cx)
+            else {
+                continue;
+            };
+            positions.push((
+                snapshot.anchor_before(point),
+                line.lenses
+                    .iter()
+                    .map(|(title, _)| title.clone())
+                    .collect::<Vec<_>>(),
+            ));
+            let line = Arc::new(line);
+            let editor = editor.clone();
+            blocks.push(BlockProperties {
+                style: BlockStyle::Sticky,
+                placement: BlockPlacement::Above(anchor),
+                height: Some(1),
+                render: Arc::new(move |cx: &mut BlockContext| {
+                    render_code_lens_line(&editor, &line, cx)
+                }),
+                priority: 0,
+                render_in_minimap: false,
+            });
+        }
+
+        let block_ids = self.insert_blocks(blocks, None, cx);
+        let shown = self
+            .code_lens
+            .buffers
+            .entry(buffer_id)
+            .or_insert_with(|| BufferCodeLens {
+                version: clock::Global::new(),
+                blocks: Vec::new(),
+            });
+        shown.version = fetched.version;
+        shown
+            .blocks
+            .extend(
+                block_ids
+                    .into_iter()
+                    .zip(positions)
+                    .map(|(block_id, (position, titles))| CodeLensBlock {
+                        block_id,
+                        position,
+                        titles,
+                    }),
+            );
+        shown
+            .blocks
+            .sort_by_key(|block| block.position.to_point(&snapshot));
+        cx.notify();
+    }
+
+    fn run_code_lens(
+        &mut self,
+        buffer: Entity<Buffer>,
+        action: CodeAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let command = action
+            .lsp_action
+            .command()
+            .map(|command| command.command.clone())
+            .unwrap_or_default();
+        let is_show_references = SHOW_REFERENCES_COMMANDS.contains(&command.as_str());
+        let is_run = RUN_COMMANDS.contains(&command.as_str());
+        if is_show_references || is_run {
+            let position = action.range.start.to_point(&buffer.read(cx).snapshot());
+            let Some(anchor) = self
+                .buffer
+                .read(cx)
+                .buffer_point_to_anchor(&buffer, position, cx)
+            else {
+                return;
+            };
+            self.change_selections(None, window, cx, |s| {
+                s.select_anchor_ranges([anchor..anchor])
+            });
+            if is_show_references {
+                self.peek_references(&PeekReferences, window, cx);
+            } else {
+                self.toggle_code_actions(
+                    &ToggleCodeActions {
+                        deployed_from: None,
+                        quick_launch: true,
+                    },
+                    window,
+                    cx,
+                );
+            }
+            return;
+        }
+
+        let (Some(project), Some(workspace)) = (self.project.clone(), self.workspace()) else {
+            return;
+        };
+        let title = action.lsp_action.title().to_string();
+        let apply_code_lens = project.update(cx, |project, cx| {
+            project.apply_code_action(buffer, action, true, cx)
+        });
+        let workspace = workspace.downgrade();
+        cx.spawn_in(window, async move |editor, cx| {
+            let project_transaction = apply_code_lens.await?;
+            Self::open_project_transaction(&editor, workspace, project_transaction, title, cx).await
+        })
+        .detach_and_notify_err(window, cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn code_lens_titles(&self) -> Vec<Vec<SharedString>> {
+        let mut buffers = self.code_lens.buffers.iter().collect::<Vec<_>>();
+        buffers.sort_by_key(|(buffer_id, _)| **buffer_id);
+        buffers
+            .into_iter()
+            .flat_map(|(_, shown)| shown.blocks.iter().map(|block| block.titles.clone()))
+            .collect()
+    }
+}
+
+/// The rows edited since `version`, from the first to the last, if any were.
+fn edited_rows(buffer: &Buffer, version: &clock::Global) -> Option<RangeInclusive<u32>> {
+    buffer
+        .edits_since::<Point>(version)
+        .map(|edit| edit.new.start.row..=edit.new.end.row)
+        .reduce(|a, b| *a.start().min(b.start())..=*a.end().max(b.end()))
+}
+
+/// Groups the lenses of a buffer that have a title by the line they start on, in buffer order.
+fn group_by_line(buffer: &Entity<Buffer>, lenses: Vec<CodeAction>, cx: &App) -> Vec<CodeLensLine> {
+    let snapshot = buffer.read(cx).snapshot();
+    let mut lenses = lenses
+        .into_iter()
+        .filter_map(|action| {
+            let title = action.lsp_action.command()?.title.clone();
+            let start = action.range.start.to_point(&snapshot);
+            let indent = snapshot.indent_size_for_line(start.row).len;
+            Some((start, indent, title, action))
+        })
+        .collect::<Vec<_>>();
+    lenses.sort_by_key(|(start, ..)| *start);
+
+    let mut lines = Vec::<CodeLensLine>::new();
+    for (start, indent, title, action) in lenses {
+        match lines.last_mut() {
+            Some(line) if line.row == start.row => {
+                line.lenses.push((title.into(), action));
+            }
+            _ => {
+                lines.push(CodeLensLine {
+                    buffer: buffer.clone(),
+                    row: start.row,
+                    indent,
+                    lenses: vec![(title.into(), action)],
+                });
+            }
+        }
+    }
+    lines
+}
+
+fn render_code_lens_line(
+    editor: &WeakEntity<Editor>,
+    line: &Arc<CodeLensLine>,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let mut row = h_flex()
+        .h_full()
+        .gap_1()
+        .pl(cx.margins.gutter.full_width() + cx.em_width * line.indent as f32);
+    for (ix, (title, action)) in line.lenses.iter().enumerate() {
+        if ix > 0 {
+            row = row.child(
+                Label::new("|")
+                    .size(LabelSize::XSmall)
+                    .color(Color::Disabled),
+            );
+        }
+        let editor = editor.clone();
+        let buffer = line.buffer.clone();
+        let action = action.clone();
+        row = row.child(
+            div()
+                .id(ix)
+                .cursor_pointer()
+                .child(
+                    Label::new(title.clone())
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .on_click(move |_, window, cx| {
+                    editor
+                        .update(cx, |editor, cx| {
+                            editor.run_code_lens(buffer.clone(), action.clone(), window, cx)
+                        })
+                        .log_err();
+                }),
+        );
+    }
+    row.into_any_element()
+}
@@ -1,10 +1,13 @@
-use crate::EditorSettings;
-use gpui::{Context, Point, Pixels};
-use settings::Settings;
-use settings::SettingsStore;
+use crate::{HoveredCursor, display_map::DisplayPoint};
+use collections::HashMap;
+use gpui::{Pixels, Point};
 use std::time::{Duration, Instant};
 
 const ANIMATION_DURATION_MS: u64 = 120;
+/// How long a collaborator's name stays next to their cursor after it moves.
+pub(crate) const CURSOR_NAME_VISIBLE_FOR: Duration = Duration::from_millis(1500);
+/// How long the name then takes to fade out.
+const CURSOR_NAME_FADE: Duration = Duration::from_millis(400);
 
 #[derive(Clone, Debug)]
 pub struct AnimatedCursorPosition {
@@ -26,16 +29,16 @@ impl AnimatedCursorPosition {
 
     pub fn current_position(&self) -> Point<Pixels> {
         let elapsed = self.start_time.elapsed();
-        
+
         if elapsed >= self.duration {
             return self.end_point;
         }
-        
+
         let progress = elapsed.as_millis() as f32 / self.duration.as_millis() as f32;
-        
+
         // Use smooth lerp (easing)
         let smooth_progress = Self::ease_out_cubic(progress);
-        
+
         Point::new(
             self.start_point.x + (self.end_point.x - self.start_point.x) * smooth_progress,
             self.start_point.y + (self.end_point.y - self.start_point.y) * smooth_progress,
@@ -52,114 +55,139 @@ impl AnimatedCursorPosition {
     }
 }
 
-pub struct CursorAnimationManager {
-    animation_epoch: usize,
-    enabled: bool,
-    active_animation: Option<AnimatedCursorPosition>,
+#[derive(Debug)]
+struct RemoteCursor {
+    head: DisplayPoint,
+    moved_at: Option<Instant>,
+    /// Where the cursor was last laid out, relative to the top of the buffer
+    /// rather than the viewport so that scrolling doesn't animate it.
+    origin: Option<Point<Pixels>>,
+    animation: Option<AnimatedCursorPosition>,
+}
+
+/// Animates collaborators' cursors between the positions they report, and
+/// shows their names for a moment whenever they move.
+#[derive(Debug, Default)]
+pub(crate) struct CursorAnimationManager {
+    cursors: HashMap<HoveredCursor, RemoteCursor>,
 }
 
 impl CursorAnimationManager {
-    pub fn new(cx: &mut Context<Self>) -> Self {
-        // Observe settings changes to enable/disable animation
-        cx.observe_global::<SettingsStore>(move |this, cx| {
-            let enabled = EditorSettings::get_global(cx).cursor_smooth_animation;
-            if this.enabled != enabled {
-                this.enabled = enabled;
-                if !enabled {
-                    this.active_animation = None;
-                }
+    /// Records where a collaborator's cursor is, returning whether it moved
+    /// since the last time it was seen. Cursors seen for the first time
+    /// haven't moved.
+    pub fn observe_cursor(&mut self, key: HoveredCursor, head: DisplayPoint, now: Instant) -> bool {
+        match self.cursors.get_mut(&key) {
+            Some(cursor) if cursor.head == head => false,
+            Some(cursor) => {
+                cursor.head = head;
+                cursor.moved_at = Some(now);
+                true
+            }
+            None => {
+                self.cursors.insert(
+                    key,
+                    RemoteCursor {
+                        head,
+                        moved_at: None,
+                        origin: None,
+                        animation: None,
+                    },
+                );
+                false
             }
-        })
-        .detach();
-
-        Self {
-            animation_epoch: 0,
-            enabled: false,
-            active_animation: None,
-        }
-    }
-
-    fn next_animation_epoch(&mut self) -> usize {
-        self.animation_epoch += 1;
-        self.animation_epoch
-    }
-
-    pub fn start_animation(
-        &mut self,
-        start_position: Point<Pixels>,
-        end_position: Point<Pixels>,
-        cx: &mut Context<Self>,
-    ) {
-        if !self.enabled {
-            return;
-        }
-
-        // Skip animation if positions are the same
-        if start_position == end_position {
-            return;
         }
-
-        self.active_animation = Some(AnimatedCursorPosition::new(start_position, end_position));
-
-        let epoch = self.next_animation_epoch();
-        self.schedule_animation_frame(epoch, cx);
     }
 
-    fn schedule_animation_frame(&mut self, epoch: usize, cx: &mut Context<Self>) {
-        if !self.enabled {
-            return;
+    /// How visible the collaborator's name should be, from 1 right after
+    /// their cursor moved down to 0 once it has faded.
+    pub fn cursor_name_opacity(&self, key: &HoveredCursor, now: Instant) -> f32 {
+        let Some(moved_at) = self.cursors.get(key).and_then(|cursor| cursor.moved_at) else {
+            return 0.;
+        };
+        let elapsed = now.duration_since(moved_at);
+        if elapsed <= CURSOR_NAME_VISIBLE_FOR {
+            1.
+        } else {
+            let fading_for = elapsed - CURSOR_NAME_VISIBLE_FOR;
+            (1. - fading_for.as_secs_f32() / CURSOR_NAME_FADE.as_secs_f32()).max(0.)
         }
-
-        // Use a faster frame interval for smoother animation (8ms ≈ 120 FPS)
-        cx.spawn(async move |this, cx| {
-            cx.background_executor().timer(Duration::from_millis(8)).await;
-            if let Some(this) = this.upgrade() {
-                this.update(cx, |this, cx| this.update_animation(epoch, cx))
-                    .ok();
-            }
-        })
-        .detach();
     }
 
-    fn update_animation(&mut self, epoch: usize, cx: &mut Context<Self>) {
-        if epoch != self.animation_epoch || !self.enabled {
-            return;
+    /// Where to draw the cursor that belongs at `origin`, relative to the top
+    /// of the buffer, while it glides there from where it was last drawn.
+    pub fn animated_origin(&mut self, key: &HoveredCursor, origin: Point<Pixels>) -> Point<Pixels> {
+        let Some(cursor) = self.cursors.get_mut(key) else {
+            return origin;
+        };
+        if let Some(previous_origin) = cursor.origin.filter(|previous| *previous != origin) {
+            let start = cursor
+                .animation
+                .as_ref()
+                .filter(|animation| !animation.is_complete())
+                .map_or(previous_origin, |animation| animation.current_position());
+            cursor.animation = Some(AnimatedCursorPosition::new(start, origin));
         }
-
-        if let Some(ref animation) = self.active_animation {
-            if animation.is_complete() {
-                self.active_animation = None;
-            } else {
-                // Continue animation - trigger redraw
-                cx.notify();
-                self.schedule_animation_frame(epoch, cx);
+        cursor.origin = Some(origin);
+        match &cursor.animation {
+            Some(animation) if !animation.is_complete() => animation.current_position(),
+            _ => {
+                cursor.animation = None;
+                origin
             }
         }
     }
 
-    pub fn current_cursor_position(&self, static_position: Point<Pixels>) -> Point<Pixels> {
-        if !self.enabled {
-            return static_position;
-        }
-
-        if let Some(ref animation) = self.active_animation {
-            if !animation.is_complete() {
-                return animation.current_position();
-            }
-        }
-
-        static_position
+    /// Whether a cursor is still moving or a name is fading, so another
+    /// frame is needed.
+    pub fn is_animating(&self, now: Instant) -> bool {
+        self.cursors.iter().any(|(key, cursor)| {
+            let name_opacity = self.cursor_name_opacity(key, now);
+            cursor
+                .animation
+                .as_ref()
+                .is_some_and(|animation| !animation.is_complete())
+                || (name_opacity > 0. && name_opacity < 1.)
+        })
     }
 
-    pub fn is_animating(&self) -> bool {
-        self.enabled && 
-        self.active_animation.as_ref().map_or(false, |a| !a.is_complete())
+    /// Forgets cursors of collaborators who are no longer in the buffer.
+    pub fn retain(&mut self, mut keep: impl FnMut(&HoveredCursor) -> bool) {
+        self.cursors.retain(|key, _| keep(key));
     }
+}
 
-    pub fn set_enabled(&mut self, enabled: bool) {
-        self.enabled = enabled;
-        if !enabled {
-            self.active_animation = None;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_map::DisplayRow;
+
+    #[test]
+    fn test_cursor_name_fades_after_moving() {
+        let key = HoveredCursor {
+            replica_id: 1,
+            selection_id: 0,
+        };
+        let start = Instant::now();
+        let mut manager = CursorAnimationManager::default();
+
+        assert!(!manager.observe_cursor(key.clone(), DisplayPoint::new(DisplayRow(0), 0), start));
+        assert_eq!(manager.cursor_name_opacity(&key, start), 0.);
+
+        assert!(!manager.observe_cursor(key.clone(), DisplayPoint::new(DisplayRow(0), 0), start));
+        assert!(manager.observe_cursor(key.clone(), DisplayPoint::new(DisplayRow(0), 1), start));
+        assert_eq!(manager.cursor_name_opacity(&key, start), 1.);
+        assert!(!manager.is_animating(start));
+
+        let fading = start + CURSOR_NAME_VISIBLE_FOR + CURSOR_NAME_FADE / 2;
+        assert!((manager.cursor_name_opacity(&key, fading) - 0.5).abs() < 0.01);
+        assert!(manager.is_animating(fading));
+
+        let faded = start + CURSOR_NAME_VISIBLE_FOR + CURSOR_NAME_FADE;
+        assert_eq!(manager.cursor_name_opacity(&key, faded), 0.);
+        assert!(!manager.is_animating(faded));
+
+        manager.retain(|_| false);
+        assert_eq!(manager.cursor_name_opacity(&key, start), 0.);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,226 @@
+use gpui::{ClipboardItem, Context, Window};
+use language::{BufferSnapshot, Node};
+use multi_buffer::MultiBufferSnapshot;
+use text::BufferId;
+
+use crate::{Editor, actions::CopyDataPath};
+
+/// The languages of data files, whose breadcrumbs show the path to the value under the cursor
+/// rather than the outline.
+const DATA_LANGUAGES: &[&str] = &["JSON", "JSONC", "YAML", "TOML"];
+
+#[derive(Clone, Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl Editor {
+    pub fn copy_data_path(&mut self, _: &CopyDataPath, _: &mut Window, cx: &mut Context<Self>) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let head = self.selections.newest::<usize>(cx).head();
+        if let Some((_, path)) = data_path_at(&snapshot, head) {
+            cx.write_to_clipboard(ClipboardItem::new_string(path));
+        }
+    }
+}
+
+/// The path to the value under the offset in a data file, like `services.web.ports[0]`, along
+/// with the buffer it's in.
+pub(crate) fn data_path_at(
+    snapshot: &MultiBufferSnapshot,
+    offset: usize,
+) -> Option<(BufferId, String)> {
+    let (buffer, offset) = snapshot.point_to_buffer_offset(offset)?;
+    let language = buffer.language_at(offset)?;
+    let language_name = language.name();
+    if !DATA_LANGUAGES.contains(&language_name.as_ref()) {
+        return None;
+    }
+    let segments = path_segments(buffer, offset)?;
+    Some((buffer.remote_id(), format_path(&segments)))
+}
+
+/// Walks up the syntax tree from the offset, naming the keys of the pairs and the positions in
+/// the arrays that contain it. The node kinds are those of the JSON, YAML and TOML grammars.
+fn path_segments(buffer: &BufferSnapshot, offset: usize) -> Option<Vec<PathSegment>> {
+    let layer = buffer.syntax_layer_at(offset)?;
+    let mut node = layer.node().descendant_for_byte_range(offset, offset);
+    // The segments are collected from the innermost, and reversed at the end.
+    let mut segments = Vec::new();
+    while let Some(current) = node {
+        let parent = current.parent();
+        match current.kind() {
+            "pair" | "block_mapping_pair" | "flow_pair" => {
+                if let Some(key) = current
+                    .child_by_field_name("key")
+                    .or_else(|| current.named_child(0))
+                {
+                    push_keys(buffer, key, &mut segments);
+                }
+            }
+            "table" | "table_array_element" => {
+                if current.kind() == "table_array_element" {
+                    segments.push(PathSegment::Index(table_array_index(buffer, current)));
+                }
+                if let Some(header) = current.named_child(0) {
+                    push_keys(buffer, header, &mut segments);
+                }
+            }
+            _ => {}
+        }
+        if let Some(parent) = parent {
+            if matches!(parent.kind(), "array" | "flow_sequence" | "block_sequence") {
+                let mut cursor = parent.walk();
+                let index = parent
+                    .named_children(&mut cursor)
+                    .filter(|child| child.kind() != "comment")
+                    .position(|child| child.id() == current.id());
+                segments.extend(index.map(PathSegment::Index));
+            }
+        }
+        node = parent;
+    }
+    if segments.is_empty() {
+        return None;
+    }
+    segments.reverse();
+    Some(segments)
+}
+
+/// Pushes the parts of a key, from the last one, since the segments are reversed. TOML keys can
+/// be dotted, like `tool.poetry`.
+fn push_keys(buffer: &BufferSnapshot, key: Node, segments: &mut Vec<PathSegment>) {
+    if key.kind() == "dotted_key" {
+        let mut cursor = key.walk();
+        let parts = key.named_children(&mut cursor).collect::<Vec<_>>();
+        for part in parts.into_iter().rev() {
+            push_keys(buffer, part, segments);
+        }
+        return;
+    }
+    let text = buffer.text_for_range(key.byte_range()).collect::<String>();
+    let text = text.trim();
+    let text = ['"', '\'']
+        .into_iter()
+        .find_map(|quote| text.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(text);
+    segments.push(PathSegment::Key(text.to_string()));
+}
+
+/// The position of a `[[table]]` among the tables of the same array before it.
+fn table_array_index(buffer: &BufferSnapshot, table: Node) -> usize {
+    let header_text = |table: Node| {
+        table.named_child(0).map(|header| {
+            buffer
+                .text_for_range(header.byte_range())
+                .collect::<String>()
+        })
+    };
+    let header = header_text(table);
+    let mut index = 0;
+    let mut sibling = table.prev_named_sibling();
+    while let Some(previous) = sibling {
+        if previous.kind() == "table_array_element" && header_text(previous) == header {
+            index += 1;
+        }
+        sibling = previous.prev_named_sibling();
+    }
+    index
+}
+
+/// Formats the path the way `jq` and `yq` take it, quoting the keys that aren't identifiers.
+fn format_path(segments: &[PathSegment]) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            PathSegment::Key(key) => {
+                let is_identifier = key
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                    && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !is_identifier {
+                    path.push_str(&format!("[{key:?}]"));
+                } else {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                }
+            }
+            PathSegment::Index(index) => path.push_str(&format!("[{index}]")),
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+    use indoc::indoc;
+    use language::Buffer;
+
+    fn path_at_marker(text: &str, language_name: &str, cx: &mut TestAppContext) -> Option<String> {
+        let offset = text.find('ˇ').unwrap();
+        let text = text.replace('ˇ', "");
+        let language = match language_name {
+            "json" => languages::language("json", tree_sitter_json::LANGUAGE.into()),
+            _ => languages::language("yaml", tree_sitter_yaml::LANGUAGE.into()),
+        };
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(language, cx));
+        cx.executor().run_until_parked();
+        buffer.read_with(cx, |buffer, _| {
+            let segments = path_segments(&buffer.snapshot(), offset)?;
+            Some(format_path(&segments))
+        })
+    }
+
+    #[gpui::test]
+    fn test_data_path(cx: &mut TestAppContext) {
+        let json = indoc! {r#"
+            {
+              "services": {
+                "web": { "ports": [8080, ˇ8081], "my-label": "a" }
+              }
+            }
+        "#};
+        assert_eq!(
+            path_at_marker(json, "json", cx).as_deref(),
+            Some("services.web.ports[1]")
+        );
+        let json = r#"{ "services": { "web": { "my-label": "ˇa" } } }"#;
+        assert_eq!(
+            path_at_marker(json, "json", cx).as_deref(),
+            Some(r#"services.web["my-label"]"#)
+        );
+
+        let yaml = indoc! {"
+            services:
+              web:
+                ports:
+                  - 8080
+                  - ˇ8081
+        "};
+        assert_eq!(
+            path_at_marker(yaml, "yaml", cx).as_deref(),
+            Some("services.web.ports[1]")
+        );
+        assert_eq!(path_at_marker("ˇ{}", "json", cx), None);
+    }
+
+    #[test]
+    fn test_format_path() {
+        assert_eq!(
+            format_path(&[
+                PathSegment::Key("a".into()),
+                PathSegment::Index(0),
+                PathSegment::Key("b c".into()),
+                PathSegment::Key("d".into()),
+            ]),
+            r#"a[0]["b c"].d"#
+        );
+        assert_eq!(format_path(&[PathSegment::Index(2)]), "[2]");
+    }
+}
@@ -0,0 +1,136 @@
+use gpui::{ClipboardItem, Context, Window};
+use language::Point;
+use multi_buffer::MultiBufferRow;
+use ui::ContextMenu;
+use unicode_segmentation::UnicodeSegmentation as _;
+
+use crate::{
+    Autoscroll, Editor,
+    actions::DescribeCharacter,
+    mouse_context_menu::{MenuPosition, MouseContextMenu},
+};
+
+impl Editor {
+    /// Shows the code points and the UTF-8 encoding of the character under the newest cursor,
+    /// or of the character before it at the end of a line. Clicking a code point copies it.
+    pub fn describe_character(
+        &mut self,
+        _: &DescribeCharacter,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let head = self.selections.newest::<Point>(cx).head();
+        let line_end = Point::new(head.row, snapshot.line_len(MultiBufferRow(head.row)));
+        let line = snapshot
+            .text_for_range(Point::new(head.row, 0)..line_end)
+            .collect::<String>();
+        let column = head.column as usize;
+        let Some((start, grapheme)) = line
+            .grapheme_indices(true)
+            .find(|(start, grapheme)| column < start + grapheme.len())
+            .or_else(|| line.grapheme_indices(true).next_back())
+        else {
+            return;
+        };
+        let description = describe_grapheme(grapheme);
+        let source = snapshot.anchor_before(Point::new(head.row, start as u32));
+
+        let context_menu = ContextMenu::build(window, cx, |mut menu, _, _| {
+            menu = menu.header(description.title);
+            for (c, label) in description.code_points {
+                menu = menu.entry(label, None, move |_, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(c.to_string()))
+                });
+            }
+            menu.separator()
+                .label(format!("UTF-8: {}", description.utf8))
+        });
+        self.request_autoscroll(Autoscroll::newest(), cx);
+        let character_size = self.character_size(window);
+        self.mouse_context_menu = Some(MouseContextMenu::new(
+            self,
+            MenuPosition::PinnedToEditor {
+                source,
+                offset: gpui::point(character_size.width, character_size.height),
+            },
+            context_menu,
+            window,
+            cx,
+        ));
+        cx.notify();
+    }
+}
+
+struct GraphemeDescription {
+    title: String,
+    code_points: Vec<(char, String)>,
+    utf8: String,
+}
+
+/// Describes a grapheme cluster, like an emoji with its modifiers or a letter with its
+/// combining accents, code point by code point.
+fn describe_grapheme(grapheme: &str) -> GraphemeDescription {
+    let code_points = grapheme
+        .chars()
+        .map(|c| {
+            let label = match unicode_names2::name(c) {
+                Some(name) => format!("U+{:04X} {name}", c as u32),
+                None => format!("U+{:04X}", c as u32),
+            };
+            (c, label)
+        })
+        .collect::<Vec<_>>();
+    let name = emojis::get(grapheme)
+        .map(|emoji| emoji.name().to_uppercase())
+        .or_else(|| match code_points.as_slice() {
+            [(c, _)] => unicode_names2::name(*c).map(|name| name.to_string()),
+            _ => None,
+        });
+    let title = match name {
+        Some(name) if !grapheme.chars().any(char::is_control) => format!("{grapheme}  {name}"),
+        Some(name) => name,
+        None => format!("{} code points", code_points.len()),
+    };
+    let utf8 = grapheme
+        .bytes()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    GraphemeDescription {
+        title,
+        code_points,
+        utf8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_grapheme() {
+        let description = describe_grapheme("e\u{301}");
+        assert_eq!(description.title, "2 code points");
+        assert_eq!(
+            description
+                .code_points
+                .iter()
+                .map(|(_, label)| label.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "U+0065 LATIN SMALL LETTER E",
+                "U+0301 COMBINING ACUTE ACCENT"
+            ]
+        );
+        assert_eq!(description.utf8, "65 CC 81");
+
+        let description = describe_grapheme("é");
+        assert_eq!(description.title, "é  LATIN SMALL LETTER E WITH ACUTE");
+        assert_eq!(description.utf8, "C3 A9");
+
+        let description = describe_grapheme("😀");
+        assert_eq!(description.title, "😀  GRINNING FACE");
+        assert_eq!(description.utf8, "F0 9F 98 80");
+    }
+}
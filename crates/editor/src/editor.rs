@@ -13,9 +13,17 @@
 //!
 //! If you're looking to improve Vim mode, you should check out Vim crate that wraps Editor and overrides its behavior.
 pub mod actions;
+mod align;
+mod argument_list;
 mod blink_manager;
+mod bracket_colorization;
 mod clangd_ext;
+mod clipboard_history;
 pub mod code_context_menus;
+mod code_lens;
+mod cursor_animation_manager;
+mod data_path;
+mod describe_character;
 pub mod display_map;
 mod editor_settings;
 mod editor_settings_controls;
@@ -24,18 +32,28 @@ mod git;
 mod highlight_matching_bracket;
 mod hover_links;
 pub mod hover_popover;
+mod image_paste;
+pub mod increment;
 mod indent_guides;
 mod inlay_hint_cache;
 pub mod items;
 mod jsx_tag_auto_close;
+mod kill_ring;
 mod linked_editing_ranges;
 mod lsp_ext;
+mod modal_input;
 mod mouse_context_menu;
 pub mod movement;
+mod overlong_lines;
+mod peek_view;
 mod persistence;
+mod pinned_highlights;
 mod proposed_changes_editor;
+mod rename_preview;
 mod rust_analyzer_ext;
 pub mod scroll;
+mod scrollbar_markers;
+mod selection_drag;
 mod selections_collection;
 pub mod tasks;
 
@@ -56,6 +74,7 @@ use anyhow::{Context as _, Result, anyhow};
 use blink_manager::BlinkManager;
 use buffer_diff::DiffHunkStatus;
 use client::{Collaborator, ParticipantIndex};
+pub use clipboard_history::ClipboardHistory;
 use clock::{AGENT_REPLICA_ID, ReplicaId};
 use collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
@@ -86,12 +105,13 @@ use code_context_menus::{
     AvailableCodeAction, CodeActionContents, CodeActionsItem, CodeActionsMenu, CodeContextMenu,
     CompletionsMenu, ContextMenuOrigin,
 };
+use cursor_animation_manager::CursorAnimationManager;
 use git::blame::{GitBlame, GlobalBlameRenderer};
 use gpui::{
     Action, Animation, AnimationExt, AnyElement, App, AppContext, AsyncWindowContext,
     AvailableSpace, Background, Bounds, ClickEvent, ClipboardEntry, ClipboardItem, Context,
     DispatchPhase, Edges, Entity, EntityInputHandler, EventEmitter, FocusHandle, FocusOutEvent,
-    Focusable, FontId, FontWeight, Global, HighlightStyle, Hsla, KeyContext, Modifiers,
+    Focusable, FontId, FontWeight, HighlightStyle, Hsla, KeyContext, KeyEvent, Modifiers,
     MouseButton, MouseDownEvent, PaintQuad, ParentElement, Pixels, Render, ScrollHandle,
     SharedString, Size, Stateful, Styled, Subscription, Task, TextStyle, TextStyleRefinement,
     UTF16Selection, UnderlineStyle, UniformListScrollHandle, WeakEntity, WeakFocusHandle, Window,
@@ -105,8 +125,10 @@ use indent_guides::ActiveIndentGuidesState;
 use inlay_hint_cache::{InlayHintCache, InlaySplice, InvalidationStrategy};
 pub use inline_completion::Direction;
 use inline_completion::{EditPredictionProvider, InlineCompletionProviderHandle};
-pub use items::MAX_TAB_TITLE_LEN;
+use items::PendingRestorationChanges;
+pub use items::{MAX_TAB_TITLE_LEN, RestorationChange};
 use itertools::Itertools;
+use kill_ring::YankSource;
 use language::{
     AutoindentMode, BracketMatch, BracketPair, Buffer, Capability, CharKind, CodeLabel,
     CursorShape, DiagnosticEntry, DiagnosticSourceKind, DiffOptions, DocumentationConfig,
@@ -114,8 +136,8 @@ use language::{
     OffsetRangeExt, Point, Selection, SelectionGoal, TextObject, TransactionId, TreeSitterOptions,
     WordsQuery,
     language_settings::{
-        self, InlayHintSettings, LspInsertMode, RewrapBehavior, WordsCompletionMode,
-        all_language_settings, language_settings,
+        self, AllLanguageSettings, InlayHintSettings, LspInsertMode, RewrapBehavior,
+        WordsCompletionMode, all_language_settings, language_settings,
     },
     point_from_lsp, text_diff_with_options,
 };
@@ -151,6 +173,7 @@ use lsp::{
 
 use language::BufferSnapshot;
 pub use lsp_ext::lsp_tasks;
+pub use modal_input::ModalInput;
 use movement::TextLayoutDetails;
 pub use multi_buffer::{
     Anchor, AnchorRangeExt, ExcerptId, ExcerptRange, MultiBuffer, MultiBufferSnapshot, PathKey,
@@ -172,6 +195,8 @@ use project::{
 use rand::prelude::*;
 use rpc::{ErrorExt, proto::*};
 use scroll::{Autoscroll, OngoingScroll, ScrollAnchor, ScrollManager, ScrollbarAutoHide};
+pub use scrollbar_markers::ScrollbarMarker;
+use selection_drag::SelectionDragState;
 use selections_collection::{
     MutableSelectionsCollection, SelectionsCollection, resolve_selections,
 };
@@ -196,8 +221,8 @@ pub use sum_tree::Bias;
 use sum_tree::TreeMap;
 use text::{BufferId, FromAnchor, OffsetUtf16, Rope};
 use theme::{
-    ActiveTheme, PlayerColor, StatusColors, SyntaxTheme, ThemeColors, ThemeSettings,
-    observe_buffer_font_size_adjustment,
+    ActiveTheme, AppearanceController, PlayerColor, StatusColors, SyntaxTheme, ThemeColors,
+    ThemeSettings, observe_buffer_font_size_adjustment,
 };
 use ui::{
     ButtonSize, ButtonStyle, ContextMenu, Disclosure, IconButton, IconButtonShape, IconName,
@@ -226,6 +251,7 @@ const CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(500);
 const MAX_LINE_LEN: usize = 1024;
 const MIN_NAVIGATION_HISTORY_ROW_DELTA: i64 = 10;
 const MAX_SELECTION_HISTORY_LEN: usize = 1024;
+const MAX_MARK_RING_LEN: usize = 16;
 pub(crate) const CURSORS_VISIBLE_FOR: Duration = Duration::from_millis(2000);
 #[doc(hidden)]
 pub const CODE_ACTIONS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
@@ -282,6 +308,7 @@ pub enum InlayId {
     InlineCompletion(usize),
     Hint(usize),
     DebuggerValue(usize),
+    ReplacementPreview(usize),
 }
 
 impl InlayId {
@@ -290,6 +317,7 @@ impl InlayId {
             Self::InlineCompletion(id) => *id,
             Self::Hint(id) => *id,
             Self::DebuggerValue(id) => *id,
+            Self::ReplacementPreview(id) => *id,
         }
     }
 }
@@ -457,9 +485,27 @@ pub fn init_settings(cx: &mut App) {
 
 pub fn init(cx: &mut App) {
     init_settings(cx);
+    clipboard_history::init(cx);
 
     cx.set_global(GlobalBlameRenderer(Arc::new(())));
 
+    command_palette_hooks::CommandPaletteArgumentPrompts::update_global(cx, |prompts, _| {
+        prompts.register::<SetTabSize>(command_palette_hooks::CommandArgumentPrompt {
+            placeholder: "Tab size".into(),
+            build: Box::new(|argument, _| {
+                let tab_size = argument.trim().parse::<NonZeroU32>().ok()?;
+                Some(command_palette_hooks::CommandInterceptResult {
+                    action: SetTabSize {
+                        tab_size: Some(tab_size),
+                    }
+                    .boxed_clone(),
+                    string: format!("Set tab size to {tab_size}"),
+                    positions: Vec::new(),
+                })
+            }),
+        });
+    });
+
     workspace::register_project_item::<Editor>(cx);
     workspace::FollowableViewRegistry::register::<Editor>(cx);
     workspace::register_serializable_item::<Editor>(cx);
@@ -1006,6 +1052,17 @@ impl ChangeList {
     pub fn last(&self) -> Option<&[Anchor]> {
         self.changes.last().map(|anchors| anchors.as_slice())
     }
+
+    pub fn changes(&self) -> &[Vec<Anchor>] {
+        &self.changes
+    }
+
+    /// Inserts changes made before this list was created, such as ones restored from a previous
+    /// session, ahead of the existing changes.
+    pub fn prepend(&mut self, changes: Vec<Vec<Anchor>>) {
+        self.position.take();
+        self.changes.splice(0..0, changes);
+    }
 }
 
 #[derive(Clone)]
@@ -1077,12 +1134,19 @@ pub struct Editor {
     collaboration_hub: Option<Box<dyn CollaborationHub>>,
     blink_manager: Entity<BlinkManager>,
     smooth_cursor_state: SmoothCursorState,
+    /// The theme's syntax colors as last desaturated for an inactive pane, so they are only
+    /// recomputed when the theme or the saturation changes.
+    desaturated_syntax: Option<DesaturatedSyntaxTheme>,
     show_cursor_names: bool,
     hovered_cursors: HashMap<HoveredCursor, Task<()>>,
+    moved_cursors: HashMap<HoveredCursor, Task<()>>,
+    cursor_animations: CursorAnimationManager,
     pub show_local_selections: bool,
     mode: EditorMode,
     show_breadcrumbs: bool,
     show_gutter: bool,
+    /// Whether the workspace showing this editor is in zen mode.
+    zen_mode: bool,
     show_scrollbars: ScrollbarAxes,
     minimap_visibility: MinimapVisibility,
     offset_content: bool,
@@ -1101,7 +1165,9 @@ pub struct Editor {
     background_highlights: TreeMap<TypeId, BackgroundHighlight>,
     gutter_highlights: TreeMap<TypeId, GutterHighlight>,
     scrollbar_marker_state: ScrollbarMarkerState,
+    scrollbar_marker_layers: BTreeMap<SharedString, Arc<[ScrollbarMarker]>>,
     active_indent_guides_state: ActiveIndentGuidesState,
+    colored_bracket_rows: Option<Range<MultiBufferRow>>,
     nav_history: Option<ItemNavHistory>,
     context_menu: RefCell<Option<CodeContextMenu>>,
     context_menu_options: Option<ContextMenuOptions>,
@@ -1120,6 +1186,11 @@ pub struct Editor {
     linked_editing_range_task: Option<Task<Option<()>>>,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
     pending_rename: Option<RenameState>,
+    peek_view: Option<peek_view::PeekViewState>,
+    rename_preview: Option<rename_preview::RenamePreview>,
+    code_lens: code_lens::CodeLensState,
+    pinned_highlights: pinned_highlights::PinnedHighlights,
+    overlong_lines: overlong_lines::OverlongLines,
     searchable: bool,
     cursor_shape: CursorShape,
     current_line_highlight: Option<CurrentLineHighlight>,
@@ -1127,9 +1198,15 @@ pub struct Editor {
     autoindent_mode: Option<AutoindentMode>,
     workspace: Option<(WeakEntity<Workspace>, Option<WorkspaceId>)>,
     input_enabled: bool,
+    modal_input_mode: Option<SharedString>,
     use_modal_editing: bool,
     read_only: bool,
     leader_id: Option<CollaboratorId>,
+    /// Whether the leader's viewport is applied while following, which pauses when the follower
+    /// scrolls away from it.
+    viewport_synced_with_leader: bool,
+    /// The leader's latest viewport, restored when syncing with them again.
+    leader_viewport: Option<items::LeaderViewport>,
     remote_id: Option<ViewId>,
     pub hover_state: HoverState,
     pending_mouse_down: Option<Rc<RefCell<Option<MouseDownEvent>>>>,
@@ -1191,6 +1268,8 @@ pub struct Editor {
     pull_diagnostics_task: Task<()>,
     in_project_search: bool,
     previous_search_ranges: Option<Arc<[Range<Anchor>]>>,
+    /// The inlays showing what the search matches would be replaced with.
+    replacement_preview_inlays: Vec<InlayId>,
     breadcrumb_header: Option<String>,
     focused_block: Option<FocusedBlock>,
     next_scroll_position: NextScrollCursorCenterTopBottom,
@@ -1200,10 +1279,18 @@ pub struct Editor {
     /// Whether we are temporarily displaying a diff other than git's
     temporary_diff_override: bool,
     selection_mark_mode: bool,
+    selection_drag_state: SelectionDragState,
+    /// Where text dragged over this editor would be dropped.
+    drop_cursor: Option<Anchor>,
+    /// Previous cursor positions of `SetMark`, most recent first, for `PopMark` to return to.
+    mark_ring: VecDeque<Anchor>,
+    last_yank: Option<kill_ring::Yank>,
     toggle_fold_multiple_buffers: Task<()>,
     _scroll_cursor_center_top_bottom_task: Task<()>,
     serialize_selections: Task<()>,
     serialize_folds: Task<()>,
+    pending_restoration_changes: PendingRestorationChanges,
+    serialize_restoration_changes: Task<()>,
     mouse_cursor_hidden: bool,
     minimap: Option<Entity<Self>>,
     hide_mouse_mode: HideMouseMode,
@@ -1243,6 +1330,8 @@ pub struct EditorSnapshot {
     pub placeholder_text: Option<Arc<str>>,
     is_focused: bool,
     scroll_anchor: ScrollAnchor,
+    /// How far the rendered viewport is from the scroll anchor while scrolling to a leader.
+    scroll_animation_offset: gpui::Point<f32>,
     ongoing_scroll: OngoingScroll,
     current_line_highlight: CurrentLineHighlight,
     gutter_hovered: bool,
@@ -1324,6 +1413,8 @@ struct DeferredSelectionEffectsState {
     autoscroll: Option<Autoscroll>,
     old_cursor_position: Anchor,
     history_entry: SelectionHistoryEntry,
+    /// Whether the selections changed as part of an edit, like when typing.
+    edited: bool,
 }
 
 #[derive(Default)]
@@ -1334,6 +1425,7 @@ struct SelectionHistory {
     mode: SelectionHistoryMode,
     undo_stack: VecDeque<SelectionHistoryEntry>,
     redo_stack: VecDeque<SelectionHistoryEntry>,
+    last_change_was_edit: bool,
 }
 
 impl SelectionHistory {
@@ -1362,11 +1454,18 @@ impl SelectionHistory {
         self.selections_by_transaction.get_mut(&transaction_id)
     }
 
-    fn push(&mut self, entry: SelectionHistoryEntry) {
+    /// Consecutive edits are recorded as a single entry, with the selections from before the
+    /// first one, so that the selections made by hand aren't buried under a cursor position per
+    /// typed character.
+    fn push(&mut self, entry: SelectionHistoryEntry, edited: bool) {
+        let coalesce = edited && self.last_change_was_edit;
+        self.last_change_was_edit = edited && matches!(self.mode, SelectionHistoryMode::Normal);
         if !entry.selections.is_empty() {
             match self.mode {
                 SelectionHistoryMode::Normal => {
-                    self.push_undo(entry);
+                    if !coalesce {
+                        self.push_undo(entry);
+                    }
                     self.redo_stack.clear();
                 }
                 SelectionHistoryMode::Undoing => self.push_redo(entry),
@@ -1400,6 +1499,43 @@ impl SelectionHistory {
             }
         }
     }
+
+    fn pop_undo(
+        &mut self,
+        current: &[Selection<Anchor>],
+        snapshot: &MultiBufferSnapshot,
+    ) -> Option<SelectionHistoryEntry> {
+        pop_distinct_entry(&mut self.undo_stack, current, snapshot)
+    }
+
+    fn pop_redo(
+        &mut self,
+        current: &[Selection<Anchor>],
+        snapshot: &MultiBufferSnapshot,
+    ) -> Option<SelectionHistoryEntry> {
+        pop_distinct_entry(&mut self.redo_stack, current, snapshot)
+    }
+}
+
+/// Pops the most recent entry that would move the selections. Entries can resolve to the current
+/// selections after edits, like the cursor placed before typing, which ends up after the typed
+/// text.
+fn pop_distinct_entry(
+    stack: &mut VecDeque<SelectionHistoryEntry>,
+    current: &[Selection<Anchor>],
+    snapshot: &MultiBufferSnapshot,
+) -> Option<SelectionHistoryEntry> {
+    while let Some(entry) = stack.pop_back() {
+        let unchanged = entry.selections.len() == current.len()
+            && entry.selections.iter().zip(current).all(|(old, new)| {
+                old.start.cmp(&new.start, snapshot).is_eq()
+                    && old.end.cmp(&new.end, snapshot).is_eq()
+            });
+        if !unchanged {
+            return Some(entry);
+        }
+    }
+    None
 }
 
 #[derive(Clone, Copy)]
@@ -1543,14 +1679,22 @@ enum SelectSyntaxNodeScrollBehavior {
     CursorBottom,
 }
 
+/// The location an editor records in its pane's navigation history.
 #[derive(Debug)]
-pub(crate) struct NavigationData {
+pub struct NavigationData {
     cursor_anchor: Anchor,
     cursor_position: Point,
     scroll_anchor: ScrollAnchor,
     scroll_top_row: u32,
 }
 
+impl NavigationData {
+    /// The position of the newest cursor when the location was recorded.
+    pub fn cursor_position(&self) -> Point {
+        self.cursor_position
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GotoDefinitionKind {
     Symbol,
@@ -1800,7 +1944,7 @@ impl Editor {
                     window,
                     |editor, _, event, window, cx| match event {
                         project::Event::RefreshCodeLens => {
-                            // we always query lens with actions, without storing them, always refreshing them
+                            editor.refresh_code_lens(window, cx);
                         }
                         project::Event::RefreshInlayHints => {
                             editor
@@ -1813,6 +1957,7 @@ impl Editor {
                                     Some(editor.refresh_runnables(window, cx));
                             }
                             editor.pull_diagnostics(window, cx);
+                            editor.refresh_code_lens(window, cx);
                         }
                         project::Event::PullWorkspaceDiagnostics => {
                             editor.pull_diagnostics(window, cx);
@@ -1823,8 +1968,7 @@ impl Editor {
                                 if focus_handle.is_focused(window) {
                                     let snapshot = buffer.read(cx).snapshot();
                                     for (range, snippet) in snippet_edits {
-                                        let editor_range =
-                                            language::range_from_lsp(*range).to_offset(&snapshot);
+                                        let editor_range = range.to_offset(&snapshot);
                                         editor
                                             .insert_snippet(
                                                 &[editor_range],
@@ -1837,6 +1981,9 @@ impl Editor {
                                 }
                             }
                         }
+                        project::Event::RenameRequested(id, position) => {
+                            editor.rename_requested_symbol(*id, *position, window, cx);
+                        }
                         _ => {}
                     },
                 ));
@@ -1953,6 +2100,7 @@ impl Editor {
             project,
             blink_manager: blink_manager.clone(),
             smooth_cursor_state: SmoothCursorState::new(),
+            desaturated_syntax: None,
             show_local_selections: true,
             show_scrollbars: ScrollbarAxes {
                 horizontal: full_mode,
@@ -1962,6 +2110,7 @@ impl Editor {
             offset_content: !matches!(mode, EditorMode::SingleLine { .. }),
             show_breadcrumbs: EditorSettings::get_global(cx).toolbar.breadcrumbs,
             show_gutter: mode.is_full(),
+            zen_mode: false,
             show_line_numbers: None,
             use_relative_line_numbers: None,
             disable_expand_excerpt_buttons: false,
@@ -1977,7 +2126,9 @@ impl Editor {
             background_highlights: TreeMap::default(),
             gutter_highlights: TreeMap::default(),
             scrollbar_marker_state: ScrollbarMarkerState::default(),
+            scrollbar_marker_layers: BTreeMap::default(),
             active_indent_guides_state: ActiveIndentGuidesState::default(),
+            colored_bracket_rows: None,
             nav_history: None,
             context_menu: RefCell::new(None),
             context_menu_options: None,
@@ -1997,6 +2148,11 @@ impl Editor {
             document_highlights_task: None,
             linked_editing_range_task: None,
             pending_rename: None,
+            peek_view: None,
+            rename_preview: None,
+            code_lens: code_lens::CodeLensState::default(),
+            pinned_highlights: pinned_highlights::PinnedHighlights::default(),
+            overlong_lines: overlong_lines::OverlongLines::default(),
             searchable: true,
             cursor_shape: EditorSettings::get_global(cx)
                 .cursor_shape
@@ -2006,6 +2162,7 @@ impl Editor {
             collapse_matches: false,
             workspace: None,
             input_enabled: true,
+            modal_input_mode: None,
             use_modal_editing: mode.is_full(),
             read_only: mode.is_minimap(),
             use_autoclose: true,
@@ -2013,6 +2170,8 @@ impl Editor {
             auto_replace_emoji_shortcode: false,
             jsx_tag_auto_close_enabled_in_any_buffer: false,
             leader_id: None,
+            viewport_synced_with_leader: true,
+            leader_viewport: None,
             remote_id: None,
             hover_state: HoverState::default(),
             pending_mouse_down: None,
@@ -2036,6 +2195,8 @@ impl Editor {
             style: None,
             show_cursor_names: false,
             hovered_cursors: HashMap::default(),
+            moved_cursors: HashMap::default(),
+            cursor_animations: CursorAnimationManager::default(),
             next_editor_action_id: EditorActionId::default(),
             editor_actions: Rc::default(),
             inline_completions_hidden_for_vim_mode: false,
@@ -2087,6 +2248,7 @@ impl Editor {
             linked_edit_ranges: Default::default(),
             in_project_search: false,
             previous_search_ranges: None,
+            replacement_preview_inlays: Vec::new(),
             breadcrumb_header: None,
             focused_block: None,
             next_scroll_position: NextScrollCursorCenterTopBottom::default(),
@@ -2094,9 +2256,15 @@ impl Editor {
             registered_buffers: HashMap::default(),
             _scroll_cursor_center_top_bottom_task: Task::ready(()),
             selection_mark_mode: false,
+            selection_drag_state: SelectionDragState::None,
+            drop_cursor: None,
+            mark_ring: VecDeque::new(),
+            last_yank: None,
             toggle_fold_multiple_buffers: Task::ready(()),
             serialize_selections: Task::ready(()),
             serialize_folds: Task::ready(()),
+            pending_restoration_changes: PendingRestorationChanges::default(),
+            serialize_restoration_changes: Task::ready(()),
             text_style_refinement: None,
             load_diff_task: load_uncommitted_diff,
             temporary_diff_override: false,
@@ -2116,10 +2284,11 @@ impl Editor {
                 }));
         }
         editor.tasks_update_task = Some(editor.refresh_runnables(window, cx));
+        editor.refresh_overlong_lines(false, window, cx);
         editor._subscriptions.extend(project_subscriptions);
 
         // Initialize smooth cursor animation if enabled
-        if EditorSettings::get_global(cx).cursor_smooth_animation {
+        if smooth_cursor_enabled(cx) {
             let _snapshot = editor.snapshot(window, cx);
             let selection = editor.selections.newest_display(cx);
             let cursor_position = selection.head();
@@ -2137,12 +2306,13 @@ impl Editor {
                     if *local {
                         let new_anchor = editor.scroll_manager.anchor();
                         let snapshot = editor.snapshot(window, cx);
-                        editor.update_restoration_data(cx, move |data| {
-                            data.scroll_position = (
+                        editor.update_restoration_data(
+                            RestorationChange::ScrollPosition(
                                 new_anchor.top_row(&snapshot.buffer_snapshot),
                                 new_anchor.offset,
-                            );
-                        });
+                            ),
+                            cx,
+                        );
                         editor.hide_signature_help(cx, SignatureHelpHiddenBy::Escape);
                         editor.inline_blame_popover.take();
                     }
@@ -2169,6 +2339,9 @@ impl Editor {
                             .change_list
                             .push_to_change_list(pop_state, new_positions);
                     }
+                    if let Some(workspace) = editor.workspace() {
+                        workspace.update(cx, |workspace, cx| workspace.report_typing(cx));
+                    }
                 }
                 _ => (),
             },
@@ -2283,9 +2456,15 @@ impl Editor {
         }
 
         key_context.set("mode", mode);
+        if self.is_masked(cx) {
+            key_context.add("masked");
+        }
         if self.pending_rename.is_some() {
             key_context.add("renaming");
         }
+        if self.rename_preview.is_some() {
+            key_context.add("rename_preview");
+        }
 
         match self.context_menu.borrow().as_ref() {
             Some(CodeContextMenu::Completions(_)) => {
@@ -2333,6 +2512,10 @@ impl Editor {
             key_context.add("selection_mode");
         }
 
+        if let Some(mode) = &self.modal_input_mode {
+            key_context.set("input_mode", mode.clone());
+        }
+
         key_context
     }
 
@@ -2508,6 +2691,7 @@ impl Editor {
     }
 
     pub fn snapshot(&self, window: &mut Window, cx: &mut App) -> EditorSnapshot {
+        let display_snapshot = self.display_map.update(cx, |map, cx| map.snapshot(cx));
         let git_blame_gutter_max_author_length = self
             .render_git_blame_gutter(cx)
             .then(|| {
@@ -2523,14 +2707,16 @@ impl Editor {
 
         EditorSnapshot {
             mode: self.mode.clone(),
-            show_gutter: self.show_gutter,
+            show_gutter: self.show_gutter
+                && !(self.zen_mode && WorkspaceSettings::get_global(cx).zen_mode.hide_gutter),
             show_line_numbers: self.show_line_numbers,
             show_git_diff_gutter: self.show_git_diff_gutter,
             show_code_actions: self.show_code_actions,
             show_runnables: self.show_runnables,
             show_breakpoints: self.show_breakpoints,
             git_blame_gutter_max_author_length,
-            display_snapshot: self.display_map.update(cx, |map, cx| map.snapshot(cx)),
+            scroll_animation_offset: self.scroll_manager.animation_offset(&display_snapshot),
+            display_snapshot,
             scroll_anchor: self.scroll_manager.anchor(),
             ongoing_scroll: self.scroll_manager.ongoing_scroll(),
             placeholder_text: self.placeholder_text.clone(),
@@ -2964,9 +3150,10 @@ impl Editor {
                             ..text::ToPoint::to_point(&s.range().end.text_anchor, buffer_snapshot)
                     })
                     .collect();
-                self.update_restoration_data(cx, |data| {
-                    data.selections = inmemory_selections;
-                });
+                self.update_restoration_data(
+                    RestorationChange::Selections(inmemory_selections),
+                    cx,
+                );
 
                 if WorkspaceSettings::get(None, cx).restore_on_startup
                     != RestoreOnStartupBehavior::None
@@ -3004,7 +3191,7 @@ impl Editor {
     }
 
     pub fn update_smooth_cursor_target(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        if !EditorSettings::get_global(cx).cursor_smooth_animation {
+        if !smooth_cursor_enabled(cx) {
             return;
         }
         
@@ -3028,7 +3215,7 @@ impl Editor {
         window: &mut Window, 
         cx: &mut Context<Self>
     ) {
-        if !EditorSettings::get_global(cx).cursor_smooth_animation {
+        if !smooth_cursor_enabled(cx) {
             return;
         }
         
@@ -3065,7 +3252,7 @@ impl Editor {
         cursor_position: DisplayPoint,
         cx: &App,
     ) -> (f32, f32) {
-        if !EditorSettings::get_global(cx).cursor_smooth_animation {
+        if !smooth_cursor_enabled(cx) {
             return (
                 cursor_position.row().0 as f32,
                 cursor_position.column() as f32,
@@ -3084,8 +3271,59 @@ impl Editor {
     }
 
     pub fn is_smooth_cursor_animating(&self, cx: &App) -> bool {
-        EditorSettings::get_global(cx).cursor_smooth_animation
-            && self.smooth_cursor_state.is_animating.get()
+        smooth_cursor_enabled(cx) && self.smooth_cursor_state.is_animating.get()
+    }
+
+    /// Returns the saturation to show this editor's colors with, if it's in an inactive pane
+    /// and the colors of inactive panes should be desaturated.
+    fn inactive_pane_saturation(&self, cx: &Context<Self>) -> Option<f32> {
+        let saturation = WorkspaceSettings::get_global(cx)
+            .active_pane_modifiers
+            .inactive_saturation?
+            .clamp(0.0, 1.0);
+        if saturation == 1.0 || !self.mode.is_full() {
+            return None;
+        }
+        let workspace = self.workspace()?;
+        workspace
+            .read(cx)
+            .is_item_in_inactive_pane(cx.entity_id(), cx)
+            .then_some(saturation)
+    }
+
+    fn desaturated_syntax(
+        &mut self,
+        syntax: &Arc<SyntaxTheme>,
+        saturation: f32,
+    ) -> Arc<SyntaxTheme> {
+        if let Some(desaturated) = &self.desaturated_syntax {
+            if Arc::ptr_eq(&desaturated.source, syntax) && desaturated.saturation == saturation {
+                return desaturated.syntax.clone();
+            }
+        }
+
+        let desaturated = Arc::new(SyntaxTheme {
+            highlights: syntax
+                .highlights
+                .iter()
+                .map(|(name, style)| {
+                    let style = HighlightStyle {
+                        color: style.color.map(|color| desaturate(color, saturation)),
+                        background_color: style
+                            .background_color
+                            .map(|color| desaturate(color, saturation)),
+                        ..*style
+                    };
+                    (name.clone(), style)
+                })
+                .collect(),
+        });
+        self.desaturated_syntax = Some(DesaturatedSyntaxTheme {
+            source: syntax.clone(),
+            saturation,
+            syntax: desaturated.clone(),
+        });
+        desaturated
     }
 
     pub fn handle_smooth_cursor_setting_change(
@@ -3093,7 +3331,7 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if EditorSettings::get_global(cx).cursor_smooth_animation {
+        if smooth_cursor_enabled(cx) {
             self.snapshot(window, cx);
             let selection = self.selections.newest_display(cx);
             let cursor_position = selection.head();
@@ -3133,9 +3371,7 @@ impl Editor {
                 })
                 .collect()
         });
-        self.update_restoration_data(cx, |data| {
-            data.folds = inmemory_folds;
-        });
+        self.update_restoration_data(RestorationChange::Folds(inmemory_folds), cx);
 
         let Some(workspace_id) = self.workspace.as_ref().and_then(|workspace| workspace.1) else {
             return;
@@ -3259,6 +3495,7 @@ impl Editor {
                 select_prev_state: self.select_prev_state.clone(),
                 add_selections_state: self.add_selections_state.clone(),
             },
+            edited: false,
         };
         let (changed, result) = self.selections.change_with(cx, change);
         state.changed = state.changed || changed;
@@ -3299,7 +3536,8 @@ impl Editor {
         cx: &mut Context<Self>,
     ) {
         if state.changed {
-            self.selection_history.push(state.history_entry);
+            self.selection_history
+                .push(state.history_entry, state.edited);
 
             if let Some(autoscroll) = state.autoscroll {
                 self.request_autoscroll(autoscroll, cx);
@@ -3782,6 +4020,10 @@ impl Editor {
             return;
         }
 
+        if self.take_peek_view(window, cx) {
+            return;
+        }
+
         if self.mode.is_full()
             && self.change_selections(Some(Autoscroll::fit()), window, cx, |s| s.try_cancel())
         {
@@ -6161,6 +6403,11 @@ impl Editor {
         title: String,
         cx: &mut AsyncWindowContext,
     ) -> Result<()> {
+        workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.record_workspace_edit(title.clone(), &transaction, cx)
+            })
+        })?;
         let mut entries = transaction.0.into_iter().collect::<Vec<_>>();
         cx.update(|_, cx| {
             entries.sort_unstable_by_key(|(buffer, _)| {
@@ -10042,13 +10289,131 @@ impl Editor {
         })
     }
 
+    pub fn sort_lines_case_sensitive_descending(
+        &mut self,
+        _: &SortLinesCaseSensitiveDescending,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sort_lines(
+            &SortLines {
+                case_sensitive: true,
+                descending: true,
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+    }
+
+    pub fn sort_lines_case_insensitive_descending(
+        &mut self,
+        _: &SortLinesCaseInsensitiveDescending,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sort_lines(
+            &SortLines {
+                descending: true,
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+    }
+
+    pub fn sort_lines_natural(
+        &mut self,
+        _: &SortLinesNatural,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sort_lines(
+            &SortLines {
+                order: LineSortOrder::Natural,
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+    }
+
+    pub fn sort_lines_natural_descending(
+        &mut self,
+        _: &SortLinesNaturalDescending,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sort_lines(
+            &SortLines {
+                order: LineSortOrder::Natural,
+                descending: true,
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+    }
+
+    pub fn sort_lines_numeric(
+        &mut self,
+        _: &SortLinesNumeric,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sort_lines(
+            &SortLines {
+                order: LineSortOrder::Numeric,
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+    }
+
+    pub fn sort_lines_numeric_descending(
+        &mut self,
+        _: &SortLinesNumericDescending,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sort_lines(
+            &SortLines {
+                order: LineSortOrder::Numeric,
+                descending: true,
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+    }
+
+    pub fn sort_lines(&mut self, action: &SortLines, window: &mut Window, cx: &mut Context<Self>) {
+        let SortLines {
+            order,
+            descending,
+            case_sensitive,
+            whole_buffer,
+        } = *action;
+        self.manipulate_lines_in(whole_buffer, window, cx, |lines| {
+            lines.sort_by(|a, b| {
+                let ordering = compare_lines(a, b, order, case_sensitive);
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            })
+        })
+    }
+
     pub fn unique_lines_case_insensitive(
         &mut self,
-        _: &UniqueLinesCaseInsensitive,
+        action: &UniqueLinesCaseInsensitive,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.manipulate_lines(window, cx, |lines| {
+        self.manipulate_lines_in(action.whole_buffer, window, cx, |lines| {
             let mut seen = HashSet::default();
             lines.retain(|line| seen.insert(line.to_lowercase()));
         })
@@ -10056,11 +10421,11 @@ impl Editor {
 
     pub fn unique_lines_case_sensitive(
         &mut self,
-        _: &UniqueLinesCaseSensitive,
+        action: &UniqueLinesCaseSensitive,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.manipulate_lines(window, cx, |lines| {
+        self.manipulate_lines_in(action.whole_buffer, window, cx, |lines| {
             let mut seen = HashSet::default();
             lines.retain(|line| seen.insert(*line));
         })
@@ -10505,12 +10870,88 @@ impl Editor {
         }
     }
 
-    pub fn reverse_lines(&mut self, _: &ReverseLines, window: &mut Window, cx: &mut Context<Self>) {
-        self.manipulate_lines(window, cx, |lines| lines.reverse())
+    pub fn reverse_lines(
+        &mut self,
+        action: &ReverseLines,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.manipulate_lines_in(action.whole_buffer, window, cx, |lines| lines.reverse())
     }
 
-    pub fn shuffle_lines(&mut self, _: &ShuffleLines, window: &mut Window, cx: &mut Context<Self>) {
-        self.manipulate_lines(window, cx, |lines| lines.shuffle(&mut thread_rng()))
+    pub fn shuffle_lines(
+        &mut self,
+        action: &ShuffleLines,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.manipulate_lines_in(action.whole_buffer, window, cx, |lines| {
+            lines.shuffle(&mut thread_rng())
+        })
+    }
+
+    /// Manipulates all the lines of the buffer when `whole_buffer` is set, and otherwise the lines
+    /// of the selections. Multi-buffers have no whole buffer, so only their selections are used.
+    fn manipulate_lines_in<Fn>(
+        &mut self,
+        whole_buffer: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        callback: Fn,
+    ) where
+        Fn: FnMut(&mut Vec<&str>),
+    {
+        if whole_buffer && self.buffer.read(cx).is_singleton() {
+            self.manipulate_all_lines(window, cx, callback)
+        } else {
+            self.manipulate_lines(window, cx, callback)
+        }
+    }
+
+    /// Like `manipulate_lines`, but over all the lines of the buffer, except for the empty line
+    /// after a trailing newline. The selections stay on the same rows and columns.
+    fn manipulate_all_lines<Fn>(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        mut callback: Fn,
+    ) where
+        Fn: FnMut(&mut Vec<&str>),
+    {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let mut end_point = buffer.max_point();
+        if end_point.column == 0 && end_point.row > 0 {
+            let last_row = MultiBufferRow(end_point.row - 1);
+            end_point = Point::new(last_row.0, buffer.line_len(last_row));
+        }
+        let text = buffer
+            .text_for_range(Point::zero()..end_point)
+            .collect::<String>();
+        let mut lines = text.split('\n').collect_vec();
+        callback(&mut lines);
+        let new_text = lines.join("\n");
+        if new_text == text {
+            return;
+        }
+
+        let selections = self.selections.all::<Point>(cx);
+        self.transact(window, cx, |this, window, cx| {
+            let buffer = this.buffer.update(cx, |buffer, cx| {
+                buffer.edit([(Point::zero()..end_point, new_text)], None, cx);
+                buffer.snapshot(cx)
+            });
+            let selections = selections
+                .into_iter()
+                .map(|selection| Selection {
+                    start: buffer.clip_point(selection.start, Bias::Left),
+                    end: buffer.clip_point(selection.end, Bias::Left),
+                    ..selection
+                })
+                .collect();
+            this.change_selections(None, window, cx, |s| s.select(selections));
+        });
     }
 
     fn manipulate_lines<Fn>(
@@ -10644,9 +11085,28 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        let acronyms = EditorSettings::get_global(cx)
+            .case_conversion_acronyms
+            .clone();
         self.manipulate_text(window, cx, |text| {
             text.split('\n')
-                .map(|line| line.to_case(Case::Title))
+                .map(|line| to_case_with_acronyms(line, Case::Title, &acronyms))
+                .join("\n")
+        })
+    }
+
+    pub fn convert_to_sentence_case(
+        &mut self,
+        _: &ConvertToSentenceCase,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let acronyms = EditorSettings::get_global(cx)
+            .case_conversion_acronyms
+            .clone();
+        self.manipulate_text(window, cx, |text| {
+            text.split('\n')
+                .map(|line| to_case_with_acronyms(line, Case::Sentence, &acronyms))
                 .join("\n")
         })
     }
@@ -10660,6 +11120,15 @@ impl Editor {
         self.manipulate_text(window, cx, |text| text.to_case(Case::Snake))
     }
 
+    pub fn convert_to_screaming_snake_case(
+        &mut self,
+        _: &ConvertToScreamingSnakeCase,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.manipulate_text(window, cx, |text| text.to_case(Case::UpperSnake))
+    }
+
     pub fn convert_to_kebab_case(
         &mut self,
         _: &ConvertToKebabCase,
@@ -10675,9 +11144,12 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        let acronyms = EditorSettings::get_global(cx)
+            .case_conversion_acronyms
+            .clone();
         self.manipulate_text(window, cx, |text| {
             text.split('\n')
-                .map(|line| line.to_case(Case::UpperCamel))
+                .map(|line| to_case_with_acronyms(line, Case::UpperCamel, &acronyms))
                 .join("\n")
         })
     }
@@ -10688,7 +11160,12 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.manipulate_text(window, cx, |text| text.to_case(Case::Camel))
+        let acronyms = EditorSettings::get_global(cx)
+            .case_conversion_acronyms
+            .clone();
+        self.manipulate_text(window, cx, |text| {
+            to_case_with_acronyms(text, Case::Camel, &acronyms)
+        })
     }
 
     pub fn convert_to_opposite_case(
@@ -11406,39 +11883,7 @@ impl Editor {
     pub fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
         let item = self.cut_common(window, cx);
-        cx.write_to_clipboard(item);
-    }
-
-    pub fn kill_ring_cut(&mut self, _: &KillRingCut, window: &mut Window, cx: &mut Context<Self>) {
-        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
-        self.change_selections(None, window, cx, |s| {
-            s.move_with(|snapshot, sel| {
-                if sel.is_empty() {
-                    sel.end = DisplayPoint::new(sel.end.row(), snapshot.line_len(sel.end.row()))
-                }
-            });
-        });
-        let item = self.cut_common(window, cx);
-        cx.set_global(KillRing(item))
-    }
-
-    pub fn kill_ring_yank(
-        &mut self,
-        _: &KillRingYank,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
-        let (text, metadata) = if let Some(KillRing(item)) = cx.try_global() {
-            if let Some(ClipboardEntry::String(kill_ring)) = item.entries().first() {
-                (kill_ring.text().to_string(), kill_ring.metadata_json())
-            } else {
-                return;
-            }
-        } else {
-            return;
-        };
-        self.do_paste(&text, metadata, false, window, cx);
+        clipboard_history::write_to_clipboard(item, self.is_masked(cx), cx);
     }
 
     pub fn copy_and_trim(&mut self, _: &CopyAndTrim, _: &mut Window, cx: &mut Context<Self>) {
@@ -11450,6 +11895,11 @@ impl Editor {
     }
 
     fn do_copy(&self, strip_leading_indents: bool, cx: &mut Context<Self>) {
+        let item = self.copy_common(strip_leading_indents, cx);
+        clipboard_history::write_to_clipboard(item, self.is_masked(cx), cx);
+    }
+
+    fn copy_common(&self, strip_leading_indents: bool, cx: &App) -> ClipboardItem {
         let selections = self.selections.all::<Point>(cx);
         let buffer = self.buffer.read(cx).read(cx);
         let mut text = String::new();
@@ -11526,10 +11976,7 @@ impl Editor {
             }
         }
 
-        cx.write_to_clipboard(ClipboardItem::new_string_with_json_metadata(
-            text,
-            clipboard_selections,
-        ));
+        ClipboardItem::new_string_with_json_metadata(text, clipboard_selections)
     }
 
     pub fn do_paste(
@@ -11627,21 +12074,42 @@ impl Editor {
     pub fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
         if let Some(item) = cx.read_from_clipboard() {
-            let entries = item.entries();
-
-            match entries.first() {
-                // For now, we only support applying metadata if there's one string. In the future, we can incorporate all the selections
-                // of all the pasted entries.
-                Some(ClipboardEntry::String(clipboard_string)) if entries.len() == 1 => self
-                    .do_paste(
-                        clipboard_string.text(),
-                        clipboard_string.metadata_json::<Vec<ClipboardSelection>>(),
-                        true,
-                        window,
-                        cx,
-                    ),
-                _ => self.do_paste(&item.text().unwrap_or_default(), None, true, window, cx),
+            // Images are saved next to the file, so they need a project. Without one, the item's
+            // text is pasted, if it has any.
+            if let Some(ClipboardEntry::Image(image)) = item.entries().first() {
+                if self.project.is_some() {
+                    self.paste_image(image.clone(), window, cx);
+                    return;
+                }
             }
+            let history_index = cx
+                .try_global::<ClipboardHistory>()
+                .and_then(|history| history.position(&item));
+            self.record_yank(
+                YankSource::ClipboardHistory,
+                history_index,
+                cx,
+                |this, cx| {
+                    let entries = item.entries();
+
+                    match entries.first() {
+                        // For now, we only support applying metadata if there's one string. In the future, we can incorporate all the selections
+                        // of all the pasted entries.
+                        Some(ClipboardEntry::String(clipboard_string)) if entries.len() == 1 => {
+                            this.do_paste(
+                                clipboard_string.text(),
+                                clipboard_string.metadata_json::<Vec<ClipboardSelection>>(),
+                                true,
+                                window,
+                                cx,
+                            )
+                        }
+                        _ => {
+                            this.do_paste(&item.text().unwrap_or_default(), None, true, window, cx)
+                        }
+                    }
+                },
+            );
         }
     }
 
@@ -12831,6 +13299,18 @@ impl Editor {
         self.push_to_nav_history(self.selections.newest_anchor().head(), None, false, cx);
     }
 
+    /// Records a jump from `origin` to the current cursor position in the navigation history,
+    /// for jumps too short to have been recorded when the selections changed.
+    pub fn record_jump(&mut self, origin: Anchor, cx: &mut Context<Self>) {
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let origin_row = origin.to_point(&buffer).row;
+        let cursor_row = self.selections.newest_anchor().head().to_point(&buffer).row;
+        let row_delta = (cursor_row as i64 - origin_row as i64).abs();
+        if row_delta > 0 && row_delta < MIN_NAVIGATION_HISTORY_ROW_DELTA {
+            self.push_to_nav_history(origin, None, false, cx);
+        }
+    }
+
     fn push_to_nav_history(
         &mut self,
         cursor_anchor: Anchor,
@@ -13965,8 +14445,8 @@ impl Editor {
                 }
 
                 let mut new_range = old_range.clone();
-                while let Some((_node, containing_range)) =
-                    buffer.syntax_ancestor(new_range.clone())
+                while let Some(containing_range) =
+                    buffer.larger_syntax_node_range(new_range.clone())
                 {
                     new_range = match containing_range {
                         MultiOrSingleBufferOffsetRange::Single(_) => break,
@@ -14372,7 +14852,9 @@ impl Editor {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::MovementAction);
         self.end_selection(window, cx);
         self.selection_history.mode = SelectionHistoryMode::Undoing;
-        if let Some(entry) = self.selection_history.undo_stack.pop_back() {
+        let current = self.selections.disjoint_anchors();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        if let Some(entry) = self.selection_history.pop_undo(&current, &snapshot) {
             self.change_selections(None, window, cx, |s| {
                 s.select_anchors(entry.selections.to_vec())
             });
@@ -14393,7 +14875,9 @@ impl Editor {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::MovementAction);
         self.end_selection(window, cx);
         self.selection_history.mode = SelectionHistoryMode::Redoing;
-        if let Some(entry) = self.selection_history.redo_stack.pop_back() {
+        let current = self.selections.disjoint_anchors();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        if let Some(entry) = self.selection_history.pop_redo(&current, &snapshot) {
             self.change_selections(None, window, cx, |s| {
                 s.select_anchors(entry.selections.to_vec())
             });
@@ -15404,6 +15888,38 @@ impl Editor {
         });
     }
 
+    /// Prompts for the name of the symbol a language server introduced, like the function a
+    /// refactoring has just extracted.
+    fn rename_requested_symbol(
+        &mut self,
+        buffer_id: BufferId,
+        position: lsp::Position,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.focus_handle(cx).is_focused(window) {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let point = snapshot.unclipped_point_utf16_to_point(point_from_lsp(position));
+        let Some(anchor) = self
+            .buffer
+            .read(cx)
+            .buffer_point_to_anchor(&buffer, point, cx)
+        else {
+            return;
+        };
+        self.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+            s.select_anchor_ranges([anchor..anchor])
+        });
+        if let Some(rename) = self.rename(&Rename, window, cx) {
+            rename.detach_and_notify_err(window, cx);
+        }
+    }
+
     pub fn rename(
         &mut self,
         _: &Rename,
@@ -15602,6 +16118,36 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Task<Result<()>>> {
+        let (rename, workspace, title) =
+            self.perform_pending_rename(window, cx, |provider, buffer, position, new_name, cx| {
+                provider.perform_rename(buffer, position, new_name, cx)
+            })?;
+        Some(cx.spawn_in(window, async move |editor, cx| {
+            let project_transaction = rename.await?;
+            Self::open_project_transaction(&editor, workspace, project_transaction, title, cx)
+                .await?;
+
+            editor.update(cx, |editor, cx| {
+                editor.refresh_document_highlights(cx);
+            })?;
+            Ok(())
+        }))
+    }
+
+    /// Takes the pending rename and hands it to `perform`, returning the task it started along
+    /// with a title for the rename.
+    fn perform_pending_rename<T>(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        perform: impl FnOnce(
+            &dyn SemanticsProvider,
+            &Entity<Buffer>,
+            text::Anchor,
+            String,
+            &mut App,
+        ) -> Option<Task<Result<T>>>,
+    ) -> Option<(Task<Result<T>>, WeakEntity<Workspace>, String)> {
         let rename = self.take_rename(false, window, cx)?;
         let workspace = self.workspace()?.downgrade();
         let (buffer, start) = self
@@ -15619,29 +16165,18 @@ impl Editor {
         let old_name = rename.old_name;
         let new_name = rename.editor.read(cx).text(cx);
 
-        let rename = self.semantics_provider.as_ref()?.perform_rename(
+        let rename = perform(
+            self.semantics_provider.as_ref()?.as_ref(),
             &buffer,
             start,
             new_name.clone(),
             cx,
         )?;
-
-        Some(cx.spawn_in(window, async move |editor, cx| {
-            let project_transaction = rename.await?;
-            Self::open_project_transaction(
-                &editor,
-                workspace,
-                project_transaction,
-                format!("Rename: {} → {}", old_name, new_name),
-                cx,
-            )
-            .await?;
-
-            editor.update(cx, |editor, cx| {
-                editor.refresh_document_highlights(cx);
-            })?;
-            Ok(())
-        }))
+        Some((
+            rename,
+            workspace,
+            format!("Rename: {} → {}", old_name, new_name),
+        ))
     }
 
     fn take_rename(
@@ -16282,7 +16817,13 @@ impl Editor {
         self.with_selection_effects_deferred(window, cx, |this, window, cx| {
             this.start_transaction_at(Instant::now(), window, cx);
             update(this, window, cx);
-            this.end_transaction_at(Instant::now(), cx)
+            let transaction_id = this.end_transaction_at(Instant::now(), cx);
+            if transaction_id.is_some() {
+                if let Some(state) = &mut this.deferred_selection_effects_state {
+                    state.edited = true;
+                }
+            }
+            transaction_id
         })
     }
 
@@ -16330,6 +16871,9 @@ impl Editor {
     }
 
     pub fn set_mark(&mut self, _: &actions::SetMark, window: &mut Window, cx: &mut Context<Self>) {
+        let head = self.selections.newest_anchor().head();
+        self.mark_ring.push_front(head);
+        self.mark_ring.truncate(MAX_MARK_RING_LEN);
         if self.selection_mark_mode {
             self.change_selections(None, window, cx, |s| {
                 s.move_with(|_, sel| {
@@ -16358,6 +16902,19 @@ impl Editor {
         cx.notify();
     }
 
+    /// Moves the cursor to the most recent mark, and puts that mark at the back of the mark ring so
+    /// that repeating this cycles through the older ones.
+    pub fn pop_mark(&mut self, _: &actions::PopMark, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(mark) = self.mark_ring.pop_front() else {
+            return;
+        };
+        self.mark_ring.push_back(mark);
+        self.selection_mark_mode = false;
+        self.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+            s.select_anchor_ranges([mark..mark]);
+        });
+    }
+
     pub fn toggle_fold(
         &mut self,
         _: &actions::ToggleFold,
@@ -17526,6 +18083,18 @@ impl Editor {
             .update(cx, |map, cx| map.set_wrap_width(width, cx))
     }
 
+    // Called by the element, like `set_wrap_width`.
+    pub(crate) fn set_wrap_hanging_indent(&self, cx: &mut App) -> bool {
+        let hanging_indent = self
+            .buffer
+            .read(cx)
+            .language_settings(cx)
+            .soft_wrap_hanging_indent;
+        self.display_map.update(cx, |map, cx| {
+            map.set_wrap_hanging_indent(hanging_indent, cx)
+        })
+    }
+
     pub fn set_soft_wrap(&mut self) {
         self.soft_wrap_mode_override = Some(language_settings::SoftWrap::EditorWidth)
     }
@@ -17557,6 +18126,27 @@ impl Editor {
         });
     }
 
+    pub fn set_tab_size(&mut self, action: &SetTabSize, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab_size) = action.tab_size else {
+            return;
+        };
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let fs = workspace.read(cx).app_state().fs.clone();
+        let language = self
+            .buffer
+            .read(cx)
+            .language_at(self.selections.newest_anchor().head(), cx)
+            .map(|language| language.name());
+        update_settings_file::<AllLanguageSettings>(fs, cx, move |settings, _| match language {
+            Some(language) => {
+                settings.languages.entry(language).or_default().tab_size = Some(tab_size);
+            }
+            None => settings.defaults.tab_size = Some(tab_size),
+        });
+    }
+
     pub fn toggle_indent_guides(
         &mut self,
         _: &ToggleIndentGuides,
@@ -17706,6 +18296,10 @@ impl Editor {
         cx.notify();
     }
 
+    pub fn is_masked(&self, cx: &App) -> bool {
+        self.display_map.read(cx).masked
+    }
+
     pub fn set_masked(&mut self, masked: bool, cx: &mut Context<Self>) {
         if self.display_map.read(cx).masked != masked {
             self.display_map.update(cx, |map, _| map.masked = masked);
@@ -18808,7 +19402,26 @@ impl Editor {
         cx: &mut Context<Self>,
     ) {
         self.display_map.update(cx, |map, _| {
-            map.highlight_text(TypeId::of::<T>(), ranges, style)
+            map.highlight_text(HighlightKey::Type(TypeId::of::<T>()), ranges, style)
+        });
+        cx.notify();
+    }
+
+    /// Highlights text like [`Self::highlight_text`], as one of several sets of ranges of the
+    /// same type, each with a style of its own.
+    pub(crate) fn highlight_text_key<T: 'static>(
+        &mut self,
+        key: usize,
+        ranges: Vec<Range<Anchor>>,
+        style: HighlightStyle,
+        cx: &mut Context<Self>,
+    ) {
+        self.display_map.update(cx, |map, _| {
+            map.highlight_text(
+                HighlightKey::TypePlus(TypeId::of::<T>(), key),
+                ranges,
+                style,
+            )
         });
         cx.notify();
     }
@@ -18841,6 +19454,15 @@ impl Editor {
         }
     }
 
+    pub(crate) fn clear_keyed_highlights<T: 'static>(&mut self, cx: &mut Context<Self>) {
+        let cleared = self
+            .display_map
+            .update(cx, |map, _| map.clear_keyed_highlights(TypeId::of::<T>()));
+        if cleared {
+            cx.notify();
+        }
+    }
+
     pub fn show_local_cursors(&self, window: &mut Window, cx: &mut App) -> bool {
         (self.read_only(cx) || self.blink_manager.read(cx).visible())
             && self.focus_handle.is_focused(window)
@@ -18973,6 +19595,9 @@ impl Editor {
                 self.active_indent_guides_state.dirty = true;
                 self.refresh_active_diagnostics(cx);
                 self.refresh_code_actions(window, cx);
+                self.refresh_edited_code_lens(window, cx);
+                self.refresh_pinned_highlights(true, window, cx);
+                self.refresh_overlong_lines(true, window, cx);
                 self.refresh_selected_text_highlights(true, window, cx);
                 refresh_matching_bracket_highlights(self, window, cx);
                 if self.has_active_inline_completion() {
@@ -19066,12 +19691,16 @@ impl Editor {
                     excerpts: excerpts.clone(),
                 });
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                self.refresh_edited_code_lens(window, cx);
+                self.refresh_bracket_colors(true, cx);
+                self.refresh_overlong_lines(false, window, cx);
             }
             multi_buffer::Event::ExcerptsRemoved {
                 ids,
                 removed_buffer_ids,
             } => {
                 self.refresh_inlay_hints(InlayHintRefreshReason::ExcerptsRemoved(ids.clone()), cx);
+                self.refresh_overlong_lines(false, window, cx);
                 let buffer = self.buffer.read(cx);
                 self.registered_buffers
                     .retain(|buffer_id, _| buffer.buffer(*buffer_id).is_some());
@@ -19094,10 +19723,13 @@ impl Editor {
             }
             multi_buffer::Event::ExcerptsExpanded { ids } => {
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                self.refresh_bracket_colors(true, cx);
+                self.refresh_overlong_lines(false, window, cx);
                 cx.emit(EditorEvent::ExcerptsExpanded { ids: ids.clone() })
             }
             multi_buffer::Event::Reparsed(buffer_id) => {
                 self.tasks_update_task = Some(self.refresh_runnables(window, cx));
+                self.refresh_bracket_colors(true, cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
 
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
@@ -19106,6 +19738,7 @@ impl Editor {
                 self.tasks_update_task = Some(self.refresh_runnables(window, cx));
             }
             multi_buffer::Event::LanguageChanged(buffer_id) => {
+                self.refresh_overlong_lines(false, window, cx);
                 linked_editing_ranges::refresh_linked_ranges(self, window, cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
@@ -19176,6 +19809,9 @@ impl Editor {
         };
         self.set_max_diagnostics_severity(new_severity, cx);
         self.tasks_update_task = Some(self.refresh_runnables(window, cx));
+        self.refresh_edited_code_lens(window, cx);
+        self.refresh_bracket_colors(true, cx);
+        self.refresh_overlong_lines(false, window, cx);
         self.update_edit_prediction_settings(cx);
         self.refresh_inline_completion(true, false, window, cx);
         self.refresh_inlay_hints(
@@ -19500,6 +20136,11 @@ impl Editor {
         file.map_or(true, |file| project::File::from_dyn(Some(file)).is_some())
     }
 
+    /// Returns whether an input method is composing text in this editor.
+    pub fn is_composing(&self, cx: &App) -> bool {
+        self.text_highlights::<InputComposition>(cx).is_some()
+    }
+
     fn marked_text_ranges(&self, cx: &App) -> Option<Vec<Range<OffsetUtf16>>> {
         let snapshot = self.buffer.read(cx).read(cx);
         let (_, ranges) = self.text_highlights::<InputComposition>(cx)?;
@@ -19908,6 +20549,31 @@ impl Editor {
         })
     }
 
+    /// Listens to key events, like key releases, that reach the editor while it's focused.
+    pub fn register_key_listener<Event: KeyEvent>(
+        &mut self,
+        listener: impl Fn(&Event, &mut Window, &mut App) + 'static,
+    ) -> Subscription {
+        let id = self.next_editor_action_id.post_inc();
+        let listener = Arc::new(listener);
+        self.editor_actions.borrow_mut().insert(
+            id,
+            Box::new(move |window, _| {
+                let listener = listener.clone();
+                window.on_key_event(move |event: &Event, phase, window, cx| {
+                    if phase == DispatchPhase::Bubble {
+                        listener(event, window, cx)
+                    }
+                })
+            }),
+        );
+
+        let editor_actions = self.editor_actions.clone();
+        Subscription::new(move || {
+            editor_actions.borrow_mut().remove(&id);
+        })
+    }
+
     pub fn file_header_size(&self) -> u32 {
         FILE_HEADER_HEIGHT
     }
@@ -20103,6 +20769,24 @@ impl Editor {
     }
 }
 
+fn desaturate(color: Hsla, saturation: f32) -> Hsla {
+    Hsla {
+        s: color.s * saturation,
+        ..color
+    }
+}
+
+struct DesaturatedSyntaxTheme {
+    source: Arc<SyntaxTheme>,
+    saturation: f32,
+    syntax: Arc<SyntaxTheme>,
+}
+
+fn smooth_cursor_enabled(cx: &App) -> bool {
+    EditorSettings::get_global(cx).cursor_smooth_animation
+        && !AppearanceController::reduce_animations(cx)
+}
+
 fn vim_enabled(cx: &App) -> bool {
     cx.global::<SettingsStore>()
         .raw_user_settings()
@@ -20783,6 +21467,15 @@ pub trait SemanticsProvider {
         cx: &mut App,
     ) -> Option<Task<Result<ProjectTransaction>>>;
 
+    /// The edits `perform_rename` would make, without making them.
+    fn rename_edits(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        new_name: String,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<(Entity<Buffer>, Vec<(Range<text::Anchor>, Arc<str>)>)>>>>;
+
     fn pull_diagnostics_for_buffer(
         &self,
         buffer: Entity<Buffer>,
@@ -21306,6 +21999,18 @@ impl SemanticsProvider for Entity<Project> {
         }))
     }
 
+    fn rename_edits(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        new_name: String,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<(Entity<Buffer>, Vec<(Range<text::Anchor>, Arc<str>)>)>>>> {
+        Some(self.update(cx, |project, cx| {
+            project.rename_edits(buffer.clone(), position, new_name, cx)
+        }))
+    }
+
     fn pull_diagnostics_for_buffer(
         &self,
         buffer: Entity<Buffer>,
@@ -21396,6 +22101,135 @@ fn inlay_hint_settings(
     language_settings(language, file, cx).inlay_hints
 }
 
+/// Converts the case of `text` like `to_case`, but keeps the words that are acronyms spelled like
+/// they are in `acronyms`, as in `userID` rather than `userId`. The first word of camel case stays
+/// in lowercase.
+fn to_case_with_acronyms(text: &str, case: Case, acronyms: &[String]) -> String {
+    let capitalizes_words = matches!(case, Case::Camel | Case::UpperCamel);
+    let keeps_first_word_lowercase = matches!(case, Case::Camel);
+    let separates_words = matches!(case, Case::Title | Case::Sentence);
+    let converted = text.to_case(case);
+    if acronyms.is_empty() || !(capitalizes_words || separates_words) {
+        return converted;
+    }
+
+    let mut words = Vec::new();
+    let mut word_start = None;
+    for (ix, c) in converted.char_indices() {
+        if !c.is_alphanumeric() || (capitalizes_words && c.is_uppercase()) {
+            if let Some(start) = word_start.take() {
+                words.push(start..ix);
+            }
+        }
+        if c.is_alphanumeric() && word_start.is_none() {
+            word_start = Some(ix);
+        }
+    }
+    words.extend(word_start.map(|start| start..converted.len()));
+
+    let mut result = String::with_capacity(converted.len());
+    let mut last_end = 0;
+    for (ix, word) in words.into_iter().enumerate() {
+        let acronym = acronyms
+            .iter()
+            .find(|acronym| acronym.to_lowercase() == converted[word.clone()].to_lowercase());
+        let Some(acronym) = acronym else {
+            continue;
+        };
+        if ix == 0 && keeps_first_word_lowercase {
+            continue;
+        }
+        result.push_str(&converted[last_end..word.start]);
+        result.push_str(acronym);
+        last_end = word.end;
+    }
+    result.push_str(&converted[last_end..]);
+    result
+}
+
+fn compare_lines(a: &str, b: &str, order: LineSortOrder, case_sensitive: bool) -> Ordering {
+    match order {
+        LineSortOrder::Lexicographic if case_sensitive => a.cmp(b),
+        LineSortOrder::Lexicographic => a.to_lowercase().cmp(&b.to_lowercase()),
+        LineSortOrder::Natural => compare_natural(a, b, case_sensitive),
+        LineSortOrder::Numeric => match (first_number(a), first_number(b)) {
+            (Some(a_number), Some(b_number)) => a_number.total_cmp(&b_number),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+        .then_with(|| compare_lines(a, b, LineSortOrder::Lexicographic, case_sensitive)),
+    }
+}
+
+/// Compares runs of digits by their value, and the other characters one by one.
+fn compare_natural(a: &str, b: &str, case_sensitive: bool) -> Ordering {
+    fn take_digits(chars: &mut Peekable<std::str::Chars>) -> String {
+        let mut digits = String::new();
+        while let Some(c) = chars.next_if(char::is_ascii_digit) {
+            digits.push(c);
+        }
+        digits
+    }
+
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        let ordering = match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) if a_char.is_ascii_digit() && b_char.is_ascii_digit() => {
+                let a_digits = take_digits(&mut a_chars);
+                let b_digits = take_digits(&mut b_chars);
+                let a_digits = a_digits.trim_start_matches('0');
+                let b_digits = b_digits.trim_start_matches('0');
+                a_digits
+                    .len()
+                    .cmp(&b_digits.len())
+                    .then_with(|| a_digits.cmp(b_digits))
+            }
+            (Some(a_char), Some(b_char)) => {
+                a_chars.next();
+                b_chars.next();
+                if case_sensitive {
+                    a_char.cmp(&b_char)
+                } else {
+                    a_char.to_lowercase().cmp(b_char.to_lowercase())
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// The first number in the line, which can have a fractional part. It's negative when it follows
+/// a minus sign, unless the sign is a separator within a word, like in `item-3`.
+fn first_number(line: &str) -> Option<f64> {
+    let digits_start = line.find(|c: char| c.is_ascii_digit())?;
+    let is_negative = line[..digits_start]
+        .strip_suffix('-')
+        .is_some_and(|before| !before.ends_with(|c: char| c.is_alphanumeric() || c == '_'));
+    let start = if is_negative {
+        digits_start - 1
+    } else {
+        digits_start
+    };
+    let mut end = digits_start;
+    let mut seen_dot = false;
+    for c in line[digits_start..].chars() {
+        if c == '.' && !seen_dot {
+            seen_dot = true;
+        } else if !c.is_ascii_digit() {
+            break;
+        }
+        end += c.len_utf8();
+    }
+    line[start..end].trim_end_matches('.').parse().ok()
+}
+
 fn consume_contiguous_rows(
     contiguous_row_selections: &mut Vec<Selection<Point>>,
     selection: &Selection<Point>,
@@ -21570,7 +22404,11 @@ impl EditorSnapshot {
     }
 
     pub fn scroll_position(&self) -> gpui::Point<f32> {
-        self.scroll_anchor.scroll_position(&self.display_snapshot)
+        let position = self.scroll_anchor.scroll_position(&self.display_snapshot);
+        point(
+            position.x + self.scroll_animation_offset.x,
+            position.y + self.scroll_animation_offset.y,
+        )
     }
 
     fn gutter_dimensions(
@@ -21850,6 +22688,18 @@ impl Render for Editor {
         if let Some(text_style_refinement) = &self.text_style_refinement {
             text_style.refine(text_style_refinement)
         }
+        let language_settings = self.buffer.read(cx).language_settings(cx);
+        if self.mode.is_full() {
+            if let Some(font_family) = &language_settings.font_family {
+                text_style.font_family = font_family.clone().into();
+            }
+        }
+
+        let mut syntax = cx.theme().syntax().clone();
+        if let Some(saturation) = self.inactive_pane_saturation(cx) {
+            text_style.color = desaturate(text_style.color, saturation);
+            syntax = self.desaturated_syntax(&syntax, saturation);
+        }
 
         let background = match self.mode {
             EditorMode::SingleLine { .. } => cx.theme().system().transparent,
@@ -21865,7 +22715,7 @@ impl Render for Editor {
                 local_player: cx.theme().players().local(),
                 text: text_style,
                 scrollbar_width: EditorElement::SCROLLBAR_WIDTH,
-                syntax: cx.theme().syntax().clone(),
+                syntax,
                 status: cx.theme().status().clone(),
                 inlay_hints_style: make_inlay_hints_style(cx),
                 inline_completion_styles: make_suggestion_styles(cx),
@@ -22129,6 +22979,21 @@ impl EntityInputHandler for Editor {
             height: line_height,
         } = self.character_size(window);
 
+        // While the cursor glides to where it was moved, keep the IME's windows next to it
+        // rather than next to where it's going to be.
+        if let Some(cursor_center) = self
+            .pixel_position_of_newest_cursor
+            .filter(|_| self.is_smooth_cursor_animating(cx))
+        {
+            let head = self.selections.newest::<OffsetUtf16>(cx).head();
+            if head.0 == range_utf16.start || head.0 == range_utf16.end {
+                return Some(Bounds {
+                    origin: cursor_center - point(em_width / 2., line_height / 2.),
+                    size: size(em_width, line_height),
+                });
+            }
+        }
+
         let snapshot = self.snapshot(window, cx);
         let scroll_position = snapshot.scroll_position();
         let scroll_left = scroll_position.x * em_width;
@@ -22464,9 +23329,6 @@ fn collapse_multiline_range(range: Range<Point>) -> Range<Point> {
         range.start..range.start
     }
 }
-pub struct KillRing(ClipboardItem);
-impl Global for KillRing {}
-
 const UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
 
 enum BreakpointPromptEditAction {
@@ -1,3 +1,4 @@
+use collections::HashMap;
 use gpui::App;
 use language::CursorShape;
 use project::project_settings::DiagnosticSeverity;
@@ -35,6 +36,10 @@ pub struct EditorSettings {
     pub redact_private_values: bool,
     pub expand_excerpt_lines: u32,
     pub middle_click_paste: bool,
+    pub drag_and_drop_selection: bool,
+    pub clipboard_history: ClipboardHistorySettings,
+    pub pasted_image_directory: String,
+    pub case_conversion_acronyms: Vec<String>,
     #[serde(default)]
     pub double_click_in_multibuffer: DoubleClickInMultibuffer,
     pub search_wrap: bool,
@@ -106,6 +111,38 @@ pub struct JupyterContent {
     pub enabled: Option<bool>,
 }
 
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct ClipboardHistorySettings {
+    /// Whether to remember the text cut or copied in editors.
+    ///
+    /// Default: true
+    pub enabled: bool,
+    /// How many cut or copied entries to remember.
+    ///
+    /// Default: 20
+    pub max_entries: usize,
+    /// Whether to keep the clipboard history across restarts.
+    ///
+    /// Default: false
+    pub persist: bool,
+}
+
+#[derive(Default, Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ClipboardHistorySettingsContent {
+    /// Whether to remember the text cut or copied in editors.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// How many cut or copied entries to remember.
+    ///
+    /// Default: 20
+    pub max_entries: Option<usize>,
+    /// Whether to keep the clipboard history across restarts.
+    ///
+    /// Default: false
+    pub persist: Option<bool>,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct Toolbar {
     pub breadcrumbs: bool,
@@ -115,7 +152,7 @@ pub struct Toolbar {
     pub code_actions: bool,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct Scrollbar {
     pub show: ShowScrollbar,
     pub git_diff: bool,
@@ -125,6 +162,7 @@ pub struct Scrollbar {
     pub diagnostics: ScrollbarDiagnostics,
     pub cursors: bool,
     pub axes: ScrollbarAxes,
+    pub marker_layers: HashMap<String, bool>,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
@@ -133,6 +171,10 @@ pub struct Minimap {
     pub thumb: MinimapThumb,
     pub thumb_border: MinimapThumbBorder,
     pub current_line_highlight: Option<CurrentLineHighlight>,
+    pub max_width_columns: u32,
+    pub git_diff: bool,
+    pub search_results: bool,
+    pub diagnostics: ScrollbarDiagnostics,
 }
 
 impl Minimap {
@@ -450,6 +492,26 @@ pub struct EditorSettingsContent {
     /// Default: true
     pub middle_click_paste: Option<bool>,
 
+    /// Whether to move selected text by dragging it with the mouse. Holding
+    /// option on macOS, or ctrl elsewhere, when dropping copies it instead.
+    ///
+    /// Default: true
+    pub drag_and_drop_selection: Option<bool>,
+
+    /// Settings for the history of text cut or copied in editors.
+    pub clipboard_history: Option<ClipboardHistorySettingsContent>,
+
+    /// The directory, relative to a Markdown or HTML file, where images pasted into it are saved.
+    ///
+    /// Default: "assets"
+    pub pasted_image_directory: Option<String>,
+
+    /// Words that are kept spelled like this when converting the case of identifiers, like
+    /// `userID` rather than `userId` with "ID". They're matched regardless of their case.
+    ///
+    /// Default: []
+    pub case_conversion_acronyms: Option<Vec<String>>,
+
     /// What to do when multibuffer is double clicked in some of its excerpts
     /// (parts of singleton buffers).
     ///
@@ -531,7 +593,7 @@ pub struct ToolbarContent {
 }
 
 /// Scrollbar related settings
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct ScrollbarContent {
     /// When to show the scrollbar in the editor.
     ///
@@ -563,6 +625,11 @@ pub struct ScrollbarContent {
     pub cursors: Option<bool>,
     /// Forcefully enable or disable the scrollbar for each axis
     pub axes: Option<ScrollbarAxesContent>,
+    /// Whether to show the markers that other features add to the scrollbar, by the name of the
+    /// layer they're added to. Layers that aren't listed are shown.
+    ///
+    /// Default: {}
+    pub marker_layers: Option<HashMap<String, bool>>,
 }
 
 /// Minimap related settings
@@ -587,6 +654,27 @@ pub struct MinimapContent {
     ///
     /// Default: inherits editor line highlights setting
     pub current_line_highlight: Option<Option<CurrentLineHighlight>>,
+
+    /// The maximum width of the minimap, in columns of its text. In narrower editors, the minimap
+    /// takes up at most a fifth of their width.
+    ///
+    /// Default: 80
+    pub max_width_columns: Option<u32>,
+
+    /// Whether to show git diff indicators in the minimap.
+    ///
+    /// Default: true
+    pub git_diff: Option<bool>,
+
+    /// Whether to show buffer search result indicators in the minimap.
+    ///
+    /// Default: true
+    pub search_results: Option<bool>,
+
+    /// Which diagnostic indicators to show in the minimap.
+    ///
+    /// Default: all
+    pub diagnostics: Option<ScrollbarDiagnostics>,
 }
 
 /// Forcefully enable or disable the scrollbar for each axis
@@ -677,6 +765,7 @@ impl Settings for EditorSettings {
             &mut current.selection_highlight,
         );
         vscode.bool_setting("editor.hover.enabled", &mut current.hover_popover_enabled);
+        vscode.bool_setting("editor.dragAndDrop", &mut current.drag_and_drop_selection);
         vscode.u64_setting("editor.hover.delay", &mut current.hover_popover_delay);
 
         let mut gutter = GutterContent::default();
@@ -824,6 +913,7 @@ impl Settings for EditorSettings {
                 _ => None,
             },
         );
+        vscode.u32_setting("editor.minimap.maxColumn", &mut minimap.max_width_columns);
 
         if minimap != MinimapContent::default() {
             current.minimap = Some(minimap)
@@ -11,14 +11,15 @@ use crate::{
     Point, RowExt, RowRangeExt, SelectPhase, SelectedTextHighlight, Selection, SoftWrap,
     StickyHeaderExcerpt, ToPoint, ToggleFold,
     code_context_menus::{CodeActionsMenu, MENU_ASIDE_MAX_WIDTH, MENU_ASIDE_MIN_WIDTH, MENU_GAP},
+    cursor_animation_manager::CURSOR_NAME_VISIBLE_FOR,
     display_map::{
         Block, BlockContext, BlockStyle, DisplaySnapshot, EditorMargins, FoldId, HighlightedChunk,
         ToDisplayPoint,
     },
     editor_settings::{
-        CurrentLineHighlight, DoubleClickInMultibuffer, MinimapThumb, MinimapThumbBorder,
-        MultiCursorModifier, ScrollBeyondLastLine, ScrollbarAxes, ScrollbarDiagnostics,
-        ShowMinimap, ShowScrollbar,
+        CurrentLineHighlight, DoubleClickInMultibuffer, Minimap as MinimapSettings, MinimapThumb,
+        MinimapThumbBorder, MultiCursorModifier, ScrollBeyondLastLine, ScrollbarAxes,
+        ScrollbarDiagnostics, ShowMinimap, ShowScrollbar,
     },
     git::blame::{BlameRenderer, GitBlame, GlobalBlameRenderer},
     hover_popover::{
@@ -29,6 +30,7 @@ use crate::{
     items::BufferSearchHighlights,
     mouse_context_menu::{self, MenuPosition},
     scroll::{ActiveScrollbarState, ScrollbarThumbState, scroll_amount::ScrollAmount},
+    selection_drag::{SELECTION_DRAG_THRESHOLD, SelectionDragState, copies_on_drop},
 };
 use buffer_diff::{DiffHunkStatus, DiffHunkStatusKind};
 use collections::{BTreeMap, HashMap};
@@ -45,10 +47,11 @@ use gpui::{
     Element, ElementInputHandler, Entity, Focusable as _, FontId, GlobalElementId, Hitbox,
     HitboxBehavior, Hsla, InteractiveElement, IntoElement, IsZero, Keystroke, Length,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
-    ParentElement, Pixels, ScrollDelta, ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString,
-    Size, StatefulInteractiveElement, Style, Styled, TextRun, TextStyleRefinement, WeakEntity,
-    Window, anchored, deferred, div, fill, linear_color_stop, linear_gradient, outline, point, px,
-    quad, relative, size, solid_background, transparent_black,
+    ParentElement, Pixels, Rgba, ScrollDelta, ScrollHandle, ScrollWheelEvent, ShapedLine,
+    SharedString, Size, StatefulInteractiveElement, Style, Styled, TextRun, TextStyleRefinement,
+    WeakEntity, Window, accessibility_rect, accesskit, anchored, deferred, div, fill,
+    linear_color_stop, linear_gradient, outline, point, px, quad, relative, size, solid_background,
+    transparent_black,
 };
 use itertools::Itertools;
 use language::language_settings::{
@@ -56,8 +59,8 @@ use language::language_settings::{
 };
 use markdown::Markdown;
 use multi_buffer::{
-    Anchor, ExcerptId, ExcerptInfo, ExpandExcerptDirection, ExpandInfo, MultiBufferPoint,
-    MultiBufferRow, RowInfo,
+    Anchor, ExcerptId, ExcerptInfo, ExpandExcerptDirection, ExpandInfo, IndentGuide,
+    MultiBufferPoint, MultiBufferRow, RowInfo,
 };
 
 use project::{
@@ -76,17 +79,19 @@ use std::{
     ops::{Deref, Range},
     rc::Rc,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use sum_tree::Bias;
 use text::BufferId;
-use theme::{ActiveTheme, Appearance, BufferLineHeight, PlayerColor};
+use theme::{ActiveTheme, Appearance, AppearanceController, BufferLineHeight, PlayerColor, Theme};
 use ui::{ButtonLike, KeyBinding, POPOVER_Y_PADDING, Tooltip, h_flex, prelude::*};
 use unicode_segmentation::UnicodeSegmentation;
 use util::{RangeExt, ResultExt, debug_panic};
 use workspace::{CollaboratorId, Workspace, item::Item, notifications::NotifyTaskExt};
 
 const INLINE_BLAME_PADDING_EM_WIDTHS: f32 = 7.;
+const MINIMAP_MAX_WIDTH_FRACTION: f32 = 0.2;
+const MAX_ELASTIC_TABSTOP_CONTEXT_ROWS: u32 = 200;
 
 /// Determines what kinds of highlights should be applied to a lines background.
 #[derive(Clone, Copy, Default)]
@@ -105,6 +110,9 @@ struct SelectionLayout {
     range: Range<DisplayPoint>,
     active_rows: Range<DisplayRow>,
     user_name: Option<SharedString>,
+    /// Identifies a collaborator's cursor, so that it can be animated.
+    remote_cursor: Option<HoveredCursor>,
+    cursor_name_opacity: f32,
 }
 
 impl SelectionLayout {
@@ -162,6 +170,8 @@ impl SelectionLayout {
             range,
             active_rows,
             user_name,
+            remote_cursor: None,
+            cursor_name_opacity: 1.,
         }
     }
 }
@@ -220,6 +230,17 @@ impl EditorElement {
         register_action(editor, window, Editor::join_lines);
         register_action(editor, window, Editor::sort_lines_case_sensitive);
         register_action(editor, window, Editor::sort_lines_case_insensitive);
+        register_action(editor, window, Editor::sort_lines_case_sensitive_descending);
+        register_action(
+            editor,
+            window,
+            Editor::sort_lines_case_insensitive_descending,
+        );
+        register_action(editor, window, Editor::sort_lines_natural);
+        register_action(editor, window, Editor::sort_lines_natural_descending);
+        register_action(editor, window, Editor::sort_lines_numeric);
+        register_action(editor, window, Editor::sort_lines_numeric_descending);
+        register_action(editor, window, Editor::sort_lines);
         register_action(editor, window, Editor::reverse_lines);
         register_action(editor, window, Editor::shuffle_lines);
         register_action(editor, window, Editor::toggle_case);
@@ -227,12 +248,19 @@ impl EditorElement {
         register_action(editor, window, Editor::convert_to_lower_case);
         register_action(editor, window, Editor::convert_to_title_case);
         register_action(editor, window, Editor::convert_to_snake_case);
+        register_action(editor, window, Editor::convert_to_screaming_snake_case);
+        register_action(editor, window, Editor::convert_to_sentence_case);
         register_action(editor, window, Editor::convert_to_kebab_case);
         register_action(editor, window, Editor::convert_to_upper_camel_case);
         register_action(editor, window, Editor::convert_to_lower_camel_case);
         register_action(editor, window, Editor::convert_to_opposite_case);
         register_action(editor, window, Editor::convert_to_rot13);
         register_action(editor, window, Editor::convert_to_rot47);
+        register_action(editor, window, Editor::describe_character);
+        register_action(editor, window, Editor::copy_data_path);
+        register_action(editor, window, Editor::increment);
+        register_action(editor, window, Editor::decrement);
+        register_action(editor, window, Editor::insert_sequence);
         register_action(editor, window, Editor::delete_to_previous_word_start);
         register_action(editor, window, Editor::delete_to_previous_subword_start);
         register_action(editor, window, Editor::delete_to_next_word_end);
@@ -246,13 +274,21 @@ impl EditorElement {
         register_action(editor, window, Editor::move_line_up);
         register_action(editor, window, Editor::move_line_down);
         register_action(editor, window, Editor::transpose);
+        register_action(editor, window, Editor::move_argument_left);
+        register_action(editor, window, Editor::move_argument_right);
+        register_action(editor, window, Editor::split_arguments);
+        register_action(editor, window, Editor::join_arguments);
         register_action(editor, window, Editor::rewrap);
         register_action(editor, window, Editor::cut);
+        register_action(editor, window, Editor::kill_region);
         register_action(editor, window, Editor::kill_ring_cut);
+        register_action(editor, window, Editor::kill_ring_save);
         register_action(editor, window, Editor::kill_ring_yank);
+        register_action(editor, window, Editor::kill_ring_yank_pop);
         register_action(editor, window, Editor::copy);
         register_action(editor, window, Editor::copy_and_trim);
         register_action(editor, window, Editor::paste);
+        register_action(editor, window, Editor::paste_previous);
         register_action(editor, window, Editor::undo);
         register_action(editor, window, Editor::redo);
         register_action(editor, window, Editor::move_page_up);
@@ -412,6 +448,7 @@ impl EditorElement {
         register_action(editor, window, Editor::unfold_all);
         register_action(editor, window, Editor::fold_selected_ranges);
         register_action(editor, window, Editor::set_mark);
+        register_action(editor, window, Editor::pop_mark);
         register_action(editor, window, Editor::swap_selection_ends);
         register_action(editor, window, Editor::show_completions);
         register_action(editor, window, Editor::show_word_completions);
@@ -421,6 +458,7 @@ impl EditorElement {
         register_action(editor, window, Editor::open_proposed_changes_editor);
         register_action(editor, window, Editor::toggle_soft_wrap);
         register_action(editor, window, Editor::toggle_tab_bar);
+        register_action(editor, window, Editor::set_tab_size);
         register_action(editor, window, Editor::toggle_line_numbers);
         register_action(editor, window, Editor::toggle_relative_line_numbers);
         register_action(editor, window, Editor::toggle_indent_guides);
@@ -529,6 +567,16 @@ impl EditorElement {
                 cx.propagate();
             }
         });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.confirm_rename_with_preview(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            } else {
+                cx.propagate();
+            }
+        });
+        register_action(editor, window, Editor::toggle_rename_preview_edit);
+        register_action(editor, window, Editor::apply_rename_preview);
+        register_action(editor, window, Editor::discard_rename_preview);
         register_action(editor, window, |editor, action, window, cx| {
             if let Some(task) = editor.find_all_references(action, window, cx) {
                 task.detach_and_log_err(cx);
@@ -536,6 +584,12 @@ impl EditorElement {
                 cx.propagate();
             }
         });
+        register_action(editor, window, Editor::peek_definition);
+        register_action(editor, window, Editor::peek_references);
+        register_action(editor, window, Editor::pin_symbol_highlight);
+        register_action(editor, window, Editor::clear_pinned_highlights);
+        register_action(editor, window, Editor::go_to_next_pinned_occurrence);
+        register_action(editor, window, Editor::go_to_previous_pinned_occurrence);
         register_action(editor, window, Editor::show_signature_help);
         register_action(editor, window, Editor::next_edit_prediction);
         register_action(editor, window, Editor::previous_edit_prediction);
@@ -678,6 +732,19 @@ impl EditorElement {
 
         let point_for_position = position_map.point_for_position(event.position);
         let position = point_for_position.previous_valid;
+        if click_count == 1
+            && !modifiers.modified()
+            && EditorSettings::get_global(cx).drag_and_drop_selection
+            && editor.selections_contain(position, cx)
+        {
+            editor.selection_drag_state = SelectionDragState::ReadyToDrag {
+                mouse_down_position: event.position,
+                position,
+            };
+            cx.stop_propagation();
+            return;
+        }
+
         if modifiers == COLUMNAR_SELECTION_MODIFIERS {
             editor.select(
                 SelectPhase::BeginColumnar {
@@ -818,6 +885,11 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if editor.end_selection_drag(window, cx) {
+            cx.stop_propagation();
+            return;
+        }
+
         let text_hitbox = &position_map.text_hitbox;
         let end_selection = editor.has_pending_selection();
         let pending_nonempty_selections = editor.has_pending_nonempty_selection();
@@ -888,12 +960,56 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if let SelectionDragState::ReadyToDrag {
+            mouse_down_position,
+            ..
+        } = editor.selection_drag_state
+        {
+            if (event.position - mouse_down_position).magnitude() > SELECTION_DRAG_THRESHOLD {
+                editor.start_selection_drag(cx);
+            }
+            return;
+        }
+
+        if Editor::is_selection_drag_active(cx) {
+            if position_map.text_hitbox.is_hovered(window) {
+                let scroll_delta =
+                    Self::mouse_autoscroll_delta(editor, event, position_map, window, cx);
+                editor.apply_scroll_delta(scroll_delta, window, cx);
+                let point_for_position = position_map.point_for_position(event.position);
+                editor.set_drop_cursor(Some(point_for_position.previous_valid), cx);
+            } else {
+                editor.set_drop_cursor(None, cx);
+            }
+            return;
+        }
+
         if !editor.has_pending_selection() {
             return;
         }
 
-        let text_bounds = position_map.text_hitbox.bounds;
         let point_for_position = position_map.point_for_position(event.position);
+        let scroll_delta = Self::mouse_autoscroll_delta(editor, event, position_map, window, cx);
+        editor.select(
+            SelectPhase::Update {
+                position: point_for_position.previous_valid,
+                goal_column: point_for_position.exact_unclipped.column(),
+                scroll_delta,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// How far to scroll while the mouse is dragged near or past the edges of the text.
+    fn mouse_autoscroll_delta(
+        editor: &Editor,
+        event: &MouseMoveEvent,
+        position_map: &PositionMap,
+        window: &mut Window,
+        cx: &App,
+    ) -> gpui::Point<f32> {
+        let text_bounds = position_map.text_hitbox.bounds;
         let mut scroll_delta = gpui::Point::<f32>::default();
         let vertical_margin = position_map.line_height.min(text_bounds.size.height / 3.0);
         let top = text_bounds.origin.y + vertical_margin;
@@ -925,15 +1041,7 @@ impl EditorElement {
             scroll_delta.x = scale_horizontal_mouse_autoscroll_delta(event.position.x - right);
         }
 
-        editor.select(
-            SelectPhase::Update {
-                position: point_for_position.previous_valid,
-                goal_column: point_for_position.exact_unclipped.column(),
-                scroll_delta,
-            },
-            window,
-            cx,
-        );
+        scroll_delta
     }
 
     fn mouse_moved(
@@ -1194,6 +1302,8 @@ impl EditorElement {
                     }
                 }
 
+                let now = Instant::now();
+                let mut visible_cursors = HashSet::default();
                 let mut remote_selections = HashMap::default();
                 for selection in snapshot.remote_selections_in_range(
                     &(start_anchor..end_anchor),
@@ -1210,23 +1320,59 @@ impl EditorElement {
                         selection_id: selection.selection.id,
                     };
 
-                    let is_shown =
-                        editor.show_cursor_names || editor.hovered_cursors.contains_key(&key);
+                    let head = selection
+                        .selection
+                        .head()
+                        .to_display_point(&snapshot.display_snapshot);
+                    if editor
+                        .cursor_animations
+                        .observe_cursor(key.clone(), head, now)
+                    {
+                        let moved_cursor = key.clone();
+                        editor.moved_cursors.insert(
+                            key.clone(),
+                            cx.spawn(async move |editor, cx| {
+                                cx.background_executor()
+                                    .timer(CURSOR_NAME_VISIBLE_FOR)
+                                    .await;
+                                editor
+                                    .update(cx, |editor, cx| {
+                                        editor.moved_cursors.remove(&moved_cursor);
+                                        cx.notify();
+                                    })
+                                    .ok();
+                            }),
+                        );
+                    }
+                    let cursor_name_opacity =
+                        if editor.show_cursor_names || editor.hovered_cursors.contains_key(&key) {
+                            1.
+                        } else {
+                            editor.cursor_animations.cursor_name_opacity(&key, now)
+                        };
+                    let is_shown = cursor_name_opacity > 0.;
 
+                    let mut layout = SelectionLayout::new(
+                        selection.selection,
+                        selection.line_mode,
+                        selection.cursor_shape,
+                        &snapshot.display_snapshot,
+                        false,
+                        false,
+                        if is_shown { selection.user_name } else { None },
+                    );
+                    layout.remote_cursor = Some(key.clone());
+                    layout.cursor_name_opacity = cursor_name_opacity;
+                    visible_cursors.insert(key);
                     remote_selections
                         .entry(selection.replica_id)
                         .or_insert((selection.color, Vec::new()))
                         .1
-                        .push(SelectionLayout::new(
-                            selection.selection,
-                            selection.line_mode,
-                            selection.cursor_shape,
-                            &snapshot.display_snapshot,
-                            false,
-                            false,
-                            if is_shown { selection.user_name } else { None },
-                        ));
+                        .push(layout);
                 }
+                editor
+                    .cursor_animations
+                    .retain(|key| visible_cursors.contains(key));
 
                 selections.extend(remote_selections.into_values());
             } else if !editor.is_focused(window) && editor.show_cursor_when_unfocused {
@@ -1332,9 +1478,16 @@ impl EditorElement {
                         [cursor_position.row().minus(visible_display_row_range.start) as usize];
                     let cursor_column = cursor_position.column() as usize;
 
-                    let cursor_character_x = cursor_row_layout.x_for_index(cursor_column);
+                    let mut cursor_character_x = cursor_row_layout.x_for_index(cursor_column);
                     let mut block_width =
                         cursor_row_layout.x_for_index(cursor_column + 1) - cursor_character_x;
+                    if block_width < Pixels::ZERO {
+                        // Characters that read right to left extend leftwards from the cursor.
+                        block_width = -block_width;
+                        if selection.cursor_shape != CursorShape::Bar {
+                            cursor_character_x -= block_width;
+                        }
+                    }
                     if block_width == Pixels::ZERO {
                         block_width = em_advance;
                     }
@@ -1393,14 +1546,23 @@ impl EditorElement {
                         None
                     };
 
-                    let (animated_row, animated_col) =
-                        editor.get_smooth_cursor_position(cursor_position, cx);
-                    if editor.is_smooth_cursor_animating(cx) {
+                    // Collaborators' cursors are animated separately below.
+                    let smooth_cursor =
+                        selection.remote_cursor.is_none() && editor.is_smooth_cursor_animating(cx);
+                    let (animated_row, animated_col) = if selection.remote_cursor.is_none() {
+                        editor.get_smooth_cursor_position(cursor_position, cx)
+                    } else {
+                        (
+                            cursor_position.row().as_f32(),
+                            cursor_position.column() as f32,
+                        )
+                    };
+                    if smooth_cursor {
                         editor.update_smooth_cursor_animation();
                         window.request_animation_frame();
                     }
-                    
-                    let x = if editor.is_smooth_cursor_animating(cx) {
+
+                    let x = if smooth_cursor {
                         // For animated cursor, calculate x position based on animated column
                         let col_index = (animated_col as usize).min(cursor_row_layout.len);
                         let col_fraction = animated_col - animated_col.floor();
@@ -1427,13 +1589,28 @@ impl EditorElement {
                         if autoscroll_containing_element {
                             let top = text_hitbox.origin.y
                                 + (animated_row - scroll_position.y - 3.).max(0.) * line_height;
-                            let left = text_hitbox.origin.x
-                                + (animated_col - scroll_position.x - 3.).max(0.) * em_width;
-
                             let bottom = text_hitbox.origin.y
                                 + (animated_row - scroll_position.y + 4.) * line_height;
-                            let right = text_hitbox.origin.x
-                                + (animated_col - scroll_position.x + 4.) * em_width;
+                            let elastic_tabstops = snapshot
+                                .buffer_snapshot
+                                .language_settings_at(cursor_position.to_point(snapshot), cx)
+                                .elastic_tabstops;
+                            let (left, right) = if elastic_tabstops {
+                                // Elastic tabs don't span the columns they expand to, so the
+                                // cursor's column doesn't tell where it is.
+                                (
+                                    text_hitbox.origin.x + (x - 3. * em_width).max(Pixels::ZERO),
+                                    text_hitbox.origin.x + x + 4. * em_width,
+                                )
+                            } else {
+                                (
+                                    text_hitbox.origin.x
+                                        + (animated_col - scroll_position.x - 3.).max(0.)
+                                            * em_width,
+                                    text_hitbox.origin.x
+                                        + (animated_col - scroll_position.x + 4.) * em_width,
+                                )
+                            };
 
                             autoscroll_bounds =
                                 Some(Bounds::from_corners(point(left, top), point(right, bottom)))
@@ -1443,13 +1620,27 @@ impl EditorElement {
                     let static_x = cursor_character_x - scroll_pixel_position.x;
                     let static_y = (cursor_position.row().as_f32() - scroll_pixel_position.y / line_height) * line_height;
                     let static_origin = point(static_x, static_y);
-                    let animated_origin = point(x, y);
-                    
+                    let remote_cursor = selection
+                        .remote_cursor
+                        .as_ref()
+                        .filter(|_| !AppearanceController::reduce_animations(cx));
+                    let animated_origin = if let Some(key) = remote_cursor {
+                        let origin = editor
+                            .cursor_animations
+                            .animated_origin(key, static_origin + scroll_pixel_position);
+                        (origin != static_origin + scroll_pixel_position)
+                            .then(|| origin - scroll_pixel_position)
+                    } else if smooth_cursor {
+                        Some(point(x, y))
+                    } else {
+                        None
+                    };
+
                     let mut cursor = CursorLayout {
                         color: player_color.cursor,
                         block_width,
                         origin: static_origin,
-                        animated_origin: if editor.is_smooth_cursor_animating(cx) { Some(animated_origin) } else { None },
+                        animated_origin,
                         line_height,
                         shape: selection.cursor_shape,
                         block_text,
@@ -1459,12 +1650,43 @@ impl EditorElement {
                         string: name,
                         color: self.style.background,
                         is_top_row: cursor_position.row().0 == 0,
+                        opacity: selection.cursor_name_opacity,
                     });
                     cursor.layout(content_origin, cursor_name, window, cx);
                     cursors.push(cursor);
                 }
             }
 
+            if let Some(drop_cursor) = editor.drop_cursor {
+                let position = drop_cursor.to_display_point(snapshot);
+                if visible_display_row_range.contains(&position.row())
+                    && row_block_types.get(&position.row()) != Some(&true)
+                {
+                    let row_layout = &line_layouts
+                        [position.row().minus(visible_display_row_range.start) as usize];
+                    let x = row_layout.x_for_index(position.column() as usize)
+                        - scroll_pixel_position.x;
+                    let y = (position.row().as_f32() - scroll_pixel_position.y / line_height)
+                        * line_height;
+                    let mut cursor = CursorLayout {
+                        color: cx.theme().colors().text_accent,
+                        block_width: em_advance,
+                        origin: point(x, y),
+                        animated_origin: None,
+                        line_height,
+                        shape: CursorShape::Bar,
+                        block_text: None,
+                        cursor_name: None,
+                    };
+                    cursor.layout(content_origin, None, window, cx);
+                    cursors.push(cursor);
+                }
+            }
+
+            if editor.cursor_animations.is_animating(Instant::now()) {
+                window.request_animation_frame();
+            }
+
             cursors
         });
 
@@ -1503,7 +1725,7 @@ impl EditorElement {
         }
 
         let editor_settings = EditorSettings::get_global(cx);
-        let scrollbar_settings = editor_settings.scrollbar;
+        let scrollbar_settings = &editor_settings.scrollbar;
         let show_scrollbars = match scrollbar_settings.show {
             ShowScrollbar::Auto => {
                 let editor = self.editor.read(cx);
@@ -1523,6 +1745,9 @@ impl EditorElement {
                 // Diagnostics
                 (is_singleton && scrollbar_settings.diagnostics != ScrollbarDiagnostics::None && snapshot.buffer_snapshot.has_diagnostics())
                 ||
+                // Markers added by other features
+                (is_singleton && !editor.visible_scrollbar_markers(scrollbar_settings).is_empty())
+                ||
                 // Cursors out of sight
                 non_visible_cursors
                 ||
@@ -1656,6 +1881,15 @@ impl EditorElement {
             editor.set_scroll_position(point(0., minimap_scroll_top), window, cx)
         });
 
+        let markers = self.layout_minimap_markers(
+            snapshot,
+            minimap_bounds,
+            minimap_line_height,
+            minimap_scroll_top,
+            &minimap_settings,
+            cx,
+        );
+
         // Required for the drop shadow to be visible
         const PADDING_OFFSET: Pixels = px(4.);
 
@@ -1683,9 +1917,156 @@ impl EditorElement {
             minimap_line_height,
             minimap_scroll_top,
             max_scroll_top: total_editor_lines,
+            markers,
         })
     }
 
+    /// Lays out the git diff, search result and diagnostic markers for the rows visible in the
+    /// minimap. Search results are shaded across the whole row, git hunks are marked along its
+    /// left edge and diagnostics along its right edge.
+    fn layout_minimap_markers(
+        &self,
+        snapshot: &EditorSnapshot,
+        minimap_bounds: Bounds<Pixels>,
+        minimap_line_height: Pixels,
+        minimap_scroll_top: f32,
+        minimap_settings: &MinimapSettings,
+        cx: &App,
+    ) -> Vec<PaintQuad> {
+        const EDGE_MARKER_WIDTH: Pixels = px(2.);
+
+        let display_snapshot = &snapshot.display_snapshot;
+        let start_row = DisplayRow(minimap_scroll_top.floor() as u32);
+        let end_row = DisplayRow(
+            (minimap_scroll_top + minimap_bounds.size.height / minimap_line_height).ceil() as u32,
+        )
+        .min(display_snapshot.max_point().row());
+        let start = DisplayPoint::new(start_row, 0).to_point(display_snapshot);
+        let end = DisplayPoint::new(end_row, display_snapshot.line_len(end_row))
+            .to_point(display_snapshot);
+
+        let theme = cx.theme();
+        let row_quad = |rows: Range<DisplayRow>, x_range: Range<Pixels>, color: Hsla| {
+            let row_to_y = |row: DisplayRow| {
+                minimap_bounds.origin.y + (row.as_f32() - minimap_scroll_top) * minimap_line_height
+            };
+            fill(
+                Bounds::from_corners(
+                    point(x_range.start, row_to_y(rows.start)),
+                    point(x_range.end, row_to_y(rows.end.next_row())),
+                ),
+                color,
+            )
+        };
+        let full_width = minimap_bounds.left()..minimap_bounds.right();
+        let left_edge = minimap_bounds.left()..minimap_bounds.left() + EDGE_MARKER_WIDTH;
+        let right_edge = minimap_bounds.right() - EDGE_MARKER_WIDTH..minimap_bounds.right();
+
+        let mut markers = Vec::new();
+        if minimap_settings.search_results {
+            let editor = self.editor.read(cx);
+            if let Some((_, ranges)) = editor
+                .background_highlights
+                .get(&TypeId::of::<BufferSearchHighlights>())
+            {
+                let buffer = &snapshot.buffer_snapshot;
+                let start_anchor = buffer.anchor_before(start);
+                let end_anchor = buffer.anchor_after(end);
+                let first_visible =
+                    ranges.partition_point(|range| range.end.cmp(&start_anchor, buffer).is_lt());
+                markers.extend(
+                    ranges[first_visible..]
+                        .iter()
+                        .take_while(|range| range.start.cmp(&end_anchor, buffer).is_le())
+                        .map(|range| {
+                            let rows = range.start.to_display_point(display_snapshot).row()
+                                ..range.end.to_display_point(display_snapshot).row();
+                            row_quad(
+                                rows,
+                                full_width.clone(),
+                                theme.colors().search_match_background,
+                            )
+                        }),
+                );
+            }
+        }
+
+        if minimap_settings.git_diff {
+            markers.extend(
+                snapshot
+                    .buffer_snapshot
+                    .diff_hunks_in_range(start..end)
+                    .map(|hunk| {
+                        let start_row = MultiBufferPoint::new(hunk.row_range.start.0, 0)
+                            .to_display_point(display_snapshot)
+                            .row();
+                        let mut end_row = MultiBufferPoint::new(hunk.row_range.end.0, 0)
+                            .to_display_point(display_snapshot)
+                            .row();
+                        if end_row != start_row {
+                            end_row.0 -= 1;
+                        }
+                        let color = match &hunk.status().kind {
+                            DiffHunkStatusKind::Added => theme.colors().version_control_added,
+                            DiffHunkStatusKind::Modified => theme.colors().version_control_modified,
+                            DiffHunkStatusKind::Deleted => theme.colors().version_control_deleted,
+                        };
+                        row_quad(start_row..end_row, left_edge.clone(), color)
+                    }),
+            );
+        }
+
+        if minimap_settings.diagnostics != ScrollbarDiagnostics::None {
+            markers.extend(
+                snapshot
+                    .buffer_snapshot
+                    .diagnostics_in_range::<Point>(start..end)
+                    .filter(|diagnostic| {
+                        shows_diagnostic(
+                            minimap_settings.diagnostics,
+                            diagnostic.diagnostic.severity,
+                        )
+                    })
+                    // Paint the most severe diagnostics last, so that they aren't covered.
+                    .sorted_by_key(|diagnostic| std::cmp::Reverse(diagnostic.diagnostic.severity))
+                    .map(|diagnostic| {
+                        let rows = diagnostic
+                            .range
+                            .start
+                            .to_display_point(display_snapshot)
+                            .row()
+                            ..diagnostic
+                                .range
+                                .end
+                                .to_display_point(display_snapshot)
+                                .row();
+                        row_quad(
+                            rows,
+                            right_edge.clone(),
+                            diagnostic_color(diagnostic.diagnostic.severity, theme),
+                        )
+                    }),
+            );
+        }
+
+        markers
+    }
+
+    fn get_minimap_width(
+        &self,
+        font_id: FontId,
+        minimap_settings: &MinimapSettings,
+        text_width: Pixels,
+        window: &mut Window,
+    ) -> Pixels {
+        let font_size = MINIMAP_FONT_SIZE.to_pixels(window.rem_size());
+        let em_width = window
+            .text_system()
+            .em_width(font_id, font_size)
+            .unwrap_or_default();
+        minimap_width(minimap_settings.max_width_columns, em_width, text_width)
+    }
+
     fn get_minimap_line_height(
         &self,
         font_size: AbsoluteLength,
@@ -2393,6 +2774,8 @@ impl EditorElement {
         content_origin: gpui::Point<Pixels>,
         text_origin: gpui::Point<Pixels>,
         visible_buffer_range: Range<MultiBufferRow>,
+        start_row: DisplayRow,
+        line_layouts: &[LineWithInvisibles],
         scroll_pixel_position: gpui::Point<Pixels>,
         line_height: Pixels,
         snapshot: &DisplaySnapshot,
@@ -2417,9 +2800,21 @@ impl EditorElement {
                 .into_iter()
                 .enumerate()
                 .filter_map(|(i, indent_guide)| {
-                    let single_indent_width =
-                        self.column_pixels(indent_guide.tab_size as usize, window, cx);
-                    let total_width = single_indent_width * indent_guide.depth as f32;
+                    let (total_width, single_indent_width) = Self::measure_indent_guide(
+                        &indent_guide,
+                        visible_buffer_range.clone(),
+                        start_row,
+                        line_layouts,
+                        snapshot,
+                    )
+                    .unwrap_or_else(|| {
+                        let single_indent_width =
+                            self.column_pixels(indent_guide.tab_size as usize, window, cx);
+                        (
+                            single_indent_width * indent_guide.depth as f32,
+                            single_indent_width,
+                        )
+                    });
                     let start_x = content_origin.x + total_width - scroll_pixel_position.x;
                     if start_x >= text_origin.x {
                         let (offset_y, length) = Self::calculate_indent_guide_bounds(
@@ -2446,6 +2841,32 @@ impl EditorElement {
         )
     }
 
+    /// Returns the x position of the given indent guide and the width of one indent, measured
+    /// on a visible line that's indented past the guide, as spaces and tabs aren't a column wide
+    /// in proportional fonts or with elastic tabstops.
+    fn measure_indent_guide(
+        indent_guide: &IndentGuide,
+        visible_buffer_range: Range<MultiBufferRow>,
+        start_row: DisplayRow,
+        line_layouts: &[LineWithInvisibles],
+        snapshot: &DisplaySnapshot,
+    ) -> Option<(Pixels, Pixels)> {
+        let indent_level = indent_guide.indent_level() as usize;
+        let previous_indent_level = indent_level.saturating_sub(indent_guide.tab_size as usize);
+        let rows = indent_guide.start_row.0.max(visible_buffer_range.start.0)
+            ..=indent_guide.end_row.0.min(visible_buffer_range.end.0);
+        rows.into_iter().find_map(|row| {
+            let display_row = snapshot
+                .point_to_display_point(Point::new(row, 0), Bias::Left)
+                .row();
+            let line = line_layouts.get(display_row.0.checked_sub(start_row.0)? as usize)?;
+            (line.len > indent_level).then(|| {
+                let x = line.x_for_index(indent_level);
+                (x, x - line.x_for_index(previous_indent_level))
+            })
+        })
+    }
+
     fn calculate_indent_guide_bounds(
         row_range: Range<MultiBufferRow>,
         line_height: Pixels,
@@ -2986,6 +3407,7 @@ impl EditorElement {
         style: &EditorStyle,
         editor_width: Pixels,
         is_row_soft_wrapped: impl Copy + Fn(usize) -> bool,
+        ligature_break: Option<Range<DisplayPoint>>,
         window: &mut Window,
         cx: &mut App,
     ) -> Vec<LineWithInvisibles> {
@@ -3030,18 +3452,55 @@ impl EditorElement {
                 })
                 .collect()
         } else {
-            let chunks = snapshot.highlighted_chunks(rows.clone(), true, style);
-            LineWithInvisibles::from_chunks(
+            // Lines around the visible ones that share their elastic tabstops are laid out too,
+            // so that the tabs' columns don't change as they scroll into view.
+            let layout_rows = if snapshot.mode.is_full() {
+                elastic_tabstop_block_rows(rows.clone(), snapshot, cx)
+            } else {
+                rows.clone()
+            };
+            let leading_rows = (rows.start.0 - layout_rows.start.0) as usize;
+            let chunks = snapshot.highlighted_chunks(layout_rows.clone(), true, style);
+            let ligature_break = ligature_break
+                .filter(|range| rows.contains(&range.start.row()))
+                .map(|range| {
+                    (
+                        (range.start.row().0 - layout_rows.start.0) as usize,
+                        range.start.column() as usize..range.end.column() as usize,
+                    )
+                });
+            let rows_with_ligatures = rows_with_ligatures(layout_rows.clone(), snapshot, cx);
+            let rows_with_elastic_tabstops = if snapshot.mode.is_full() {
+                rows_with_elastic_tabstops(layout_rows.clone(), snapshot, cx)
+            } else {
+                Vec::new()
+            };
+            let mut lines = LineWithInvisibles::from_chunks(
                 chunks,
                 &style,
                 MAX_LINE_LEN,
-                rows.len(),
+                layout_rows.len(),
                 &snapshot.mode,
                 editor_width,
-                is_row_soft_wrapped,
+                |row| row >= leading_rows && is_row_soft_wrapped(row - leading_rows),
+                &rows_with_ligatures,
+                ligature_break,
+                &rows_with_elastic_tabstops,
                 window,
                 cx,
-            )
+            );
+            if rows_with_elastic_tabstops.contains(&true) {
+                let font_size = style.text.font_size.to_pixels(window.rem_size());
+                let font_id = window.text_system().resolve_font(&style.text.font());
+                let padding = window
+                    .text_system()
+                    .em_width(font_id, font_size)
+                    .unwrap_or(font_size);
+                align_elastic_tabstops(&mut lines, padding);
+            }
+            lines.truncate(leading_rows + rows.len());
+            lines.drain(..leading_rows);
+            lines
         }
     }
 
@@ -5884,7 +6343,8 @@ impl EditorElement {
             let background_highlights = editor.background_highlights.clone();
             let snapshot = layout.position_map.snapshot.clone();
             let theme = cx.theme().clone();
-            let scrollbar_settings = EditorSettings::get_global(cx).scrollbar;
+            let scrollbar_settings = EditorSettings::get_global(cx).scrollbar.clone();
+            let marker_layers = editor.visible_scrollbar_markers(&scrollbar_settings);
 
             editor.scrollbar_marker_state.dirty = false;
             editor.scrollbar_marker_state.pending_refresh =
@@ -5970,34 +6430,36 @@ impl EditorElement {
                                 }
                             }
 
+                            for markers in &marker_layers {
+                                let marker_row_ranges = markers.iter().map(|marker| ColoredRange {
+                                    start: marker
+                                        .range
+                                        .start
+                                        .to_display_point(&snapshot.display_snapshot)
+                                        .row(),
+                                    end: marker
+                                        .range
+                                        .end
+                                        .to_display_point(&snapshot.display_snapshot)
+                                        .row(),
+                                    color: marker.color,
+                                });
+                                marker_quads.extend(
+                                    scrollbar_layout
+                                        .marker_quads_for_ranges(marker_row_ranges, Some(1)),
+                                );
+                            }
+
                             if scrollbar_settings.diagnostics != ScrollbarDiagnostics::None {
                                 let diagnostics = snapshot
                                     .buffer_snapshot
                                     .diagnostics_in_range::<Point>(Point::zero()..max_point)
                                     // Don't show diagnostics the user doesn't care about
                                     .filter(|diagnostic| {
-                                        match (
+                                        shows_diagnostic(
                                             scrollbar_settings.diagnostics,
                                             diagnostic.diagnostic.severity,
-                                        ) {
-                                            (ScrollbarDiagnostics::All, _) => true,
-                                            (
-                                                ScrollbarDiagnostics::Error,
-                                                lsp::DiagnosticSeverity::ERROR,
-                                            ) => true,
-                                            (
-                                                ScrollbarDiagnostics::Warning,
-                                                lsp::DiagnosticSeverity::ERROR
-                                                | lsp::DiagnosticSeverity::WARNING,
-                                            ) => true,
-                                            (
-                                                ScrollbarDiagnostics::Information,
-                                                lsp::DiagnosticSeverity::ERROR
-                                                | lsp::DiagnosticSeverity::WARNING
-                                                | lsp::DiagnosticSeverity::INFORMATION,
-                                            ) => true,
-                                            (_, _) => false,
-                                        }
+                                        )
                                     })
                                     // We want to sort by severity, in order to paint the most severe diagnostics last.
                                     .sorted_by_key(|diagnostic| {
@@ -6013,12 +6475,8 @@ impl EditorElement {
                                         .range
                                         .end
                                         .to_display_point(&snapshot.display_snapshot);
-                                    let color = match diagnostic.diagnostic.severity {
-                                        lsp::DiagnosticSeverity::ERROR => theme.status().error,
-                                        lsp::DiagnosticSeverity::WARNING => theme.status().warning,
-                                        lsp::DiagnosticSeverity::INFORMATION => theme.status().info,
-                                        _ => theme.status().hint,
-                                    };
+                                    let color =
+                                        diagnostic_color(diagnostic.diagnostic.severity, &theme);
                                     ColoredRange {
                                         start: start_display.row(),
                                         end: end_display.row(),
@@ -6066,6 +6524,22 @@ impl EditorElement {
                     ..cmp::min(range.end.row().next_row(), end_row)
             };
 
+            let mixes_directions = row_range.clone().iter_rows().any(|row| {
+                layout.position_map.line_layouts[row.minus(start_row) as usize].has_rtl()
+            });
+            if mixes_directions {
+                Self::paint_bidi_highlighted_range(
+                    range,
+                    row_range,
+                    color,
+                    corner_radius,
+                    line_end_overshoot,
+                    layout,
+                    window,
+                );
+                return;
+            }
+
             let highlighted_range = HighlightedRange {
                 color,
                 line_height: layout.position_map.line_height,
@@ -6104,6 +6578,53 @@ impl EditorElement {
         }
     }
 
+    /// Paints a highlight over rows where some text reads right to left, a span at a time,
+    /// since the text in a range can be spread over several spans on those rows.
+    fn paint_bidi_highlighted_range(
+        range: Range<DisplayPoint>,
+        row_range: Range<DisplayRow>,
+        color: Hsla,
+        corner_radius: Pixels,
+        line_end_overshoot: Pixels,
+        layout: &EditorLayout,
+        window: &mut Window,
+    ) {
+        let start_row = layout.visible_display_row_range.start;
+        let origin_x = layout.content_origin.x - layout.position_map.scroll_pixel_position.x;
+        for row in row_range.iter_rows() {
+            let line_layout = &layout.position_map.line_layouts[row.minus(start_row) as usize];
+            let start_column = if row == range.start.row() {
+                range.start.column() as usize
+            } else {
+                0
+            };
+            let mut x_ranges = if row == range.end.row() {
+                line_layout.x_ranges_for_range(start_column..range.end.column() as usize)
+            } else {
+                let mut x_ranges = line_layout.x_ranges_for_range(start_column..line_layout.len);
+                x_ranges.push(line_layout.width..line_layout.width + line_end_overshoot);
+                x_ranges
+            };
+            x_ranges.retain(|x_range| x_range.start < x_range.end);
+
+            let start_y = layout.content_origin.y + row.as_f32() * layout.position_map.line_height
+                - layout.position_map.scroll_pixel_position.y;
+            for x_range in x_ranges {
+                HighlightedRange {
+                    color,
+                    line_height: layout.position_map.line_height,
+                    corner_radius,
+                    start_y,
+                    lines: vec![HighlightedRangeLine {
+                        start_x: origin_x + x_range.start,
+                        end_x: origin_x + x_range.end,
+                    }],
+                }
+                .paint(layout.position_map.text_hitbox.bounds, window);
+            }
+        }
+    }
+
     fn paint_inline_diagnostics(
         &mut self,
         layout: &mut EditorLayout,
@@ -6155,6 +6676,11 @@ impl EditorElement {
             window.paint_layer(layout.thumb_layout.hitbox.bounds, |window| {
                 window.with_element_namespace("minimap", |window| {
                     layout.minimap.paint(window, cx);
+                    window.paint_layer(minimap_hitbox.bounds, |window| {
+                        for marker in layout.markers.drain(..) {
+                            window.paint_quad(marker);
+                        }
+                    });
                     if let Some(thumb_bounds) = layout.thumb_layout.thumb_bounds {
                         let minimap_thumb_color = match layout.thumb_layout.thumb_state {
                             ScrollbarThumbState::Idle => {
@@ -6359,6 +6885,106 @@ impl EditorElement {
         }
     }
 
+    /// Describes the editor and its visible lines to assistive technologies, like screen
+    /// readers, along with the newest selection.
+    fn paint_accessibility_nodes(
+        &self,
+        layout: &EditorLayout,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let editor = self.editor.read(cx);
+        if !window.is_accessibility_active() || editor.mode().is_minimap() {
+            return;
+        }
+
+        let snapshot = &layout.position_map.snapshot;
+        let line_height = layout.position_map.line_height;
+        let scroll_top = layout.position_map.scroll_pixel_position.y;
+        let entity_id = self.editor.entity_id();
+        let lines = layout
+            .visible_display_row_range
+            .clone()
+            .map(|row| {
+                let origin = point(
+                    layout.content_origin.x,
+                    layout.content_origin.y + row.as_f32() * line_height - scroll_top,
+                );
+                let line_bounds = Bounds::new(
+                    origin,
+                    size(bounds.right() - layout.content_origin.x, line_height),
+                );
+                let id = window.accessibility_node_id((entity_id, row.0));
+                (id, row, snapshot.line(row), line_bounds)
+            })
+            .collect::<Vec<_>>();
+
+        let text_position = |point: DisplayPoint| {
+            let (id, _, text, _) = lines.iter().find(|(_, row, _, _)| *row == point.row())?;
+            let character_index = text
+                .get(..point.column() as usize)
+                .unwrap_or(text)
+                .chars()
+                .count();
+            Some(accesskit::TextPosition {
+                node: *id,
+                character_index,
+            })
+        };
+        let selection = editor.selections.newest_anchor();
+        let text_selection = text_position(selection.tail().to_display_point(snapshot))
+            .zip(text_position(selection.head().to_display_point(snapshot)))
+            .map(|(anchor, focus)| accesskit::TextSelection { anchor, focus });
+        let role = if matches!(editor.mode(), EditorMode::SingleLine { .. }) {
+            accesskit::Role::TextInput
+        } else {
+            accesskit::Role::MultilineTextInput
+        };
+        let label = editor
+            .placeholder_text()
+            .map_or_else(|| "Editor".to_string(), ToString::to_string);
+        let read_only = editor.read_only(cx);
+        let focus_handle = self.editor.focus_handle(cx);
+        let editor_node_id = window.accessibility_node_id(entity_id);
+
+        window.with_accessibility_node(
+            editor_node_id,
+            || {
+                let mut node = accesskit::Node::new(role);
+                node.set_label(label);
+                node.set_bounds(accessibility_rect(bounds));
+                node.add_action(accesskit::Action::Focus);
+                if read_only {
+                    node.set_read_only();
+                }
+                if let Some(text_selection) = text_selection {
+                    node.set_text_selection(text_selection);
+                }
+                node
+            },
+            Some(&focus_handle),
+            |window| {
+                for (id, _, text, line_bounds) in lines {
+                    window.with_accessibility_node(
+                        id,
+                        || {
+                            let mut node = accesskit::Node::new(accesskit::Role::TextRun);
+                            node.set_bounds(accessibility_rect(line_bounds));
+                            node.set_character_lengths(
+                                text.chars().map(|c| c.len_utf8() as u8).collect::<Vec<_>>(),
+                            );
+                            node.set_value(text);
+                            node
+                        },
+                        None,
+                        |_| {},
+                    );
+                }
+            },
+        );
+    }
+
     fn paint_mouse_context_menu(
         &mut self,
         layout: &mut EditorLayout,
@@ -6530,6 +7156,30 @@ impl EditorElement {
             }
         });
 
+        window.on_mouse_event({
+            let editor = self.editor.clone();
+            let position_map = layout.position_map.clone();
+
+            move |event: &MouseUpEvent, phase, window, cx| {
+                // Drop during the capture phase, so that it happens before the editor the text
+                // was dragged from ends the drag in the bubble phase.
+                if phase == DispatchPhase::Capture
+                    && Editor::is_selection_drag_active(cx)
+                    && position_map.text_hitbox.is_hovered(window)
+                {
+                    editor.update(cx, |editor, cx| {
+                        let point_for_position = position_map.point_for_position(event.position);
+                        editor.drop_selection(
+                            point_for_position.previous_valid,
+                            copies_on_drop(&event.modifiers),
+                            window,
+                            cx,
+                        );
+                    });
+                }
+            }
+        });
+
         window.on_mouse_event({
             let editor = self.editor.clone();
             let position_map = layout.position_map.clone();
@@ -6855,12 +7505,22 @@ enum LineFragment {
         size: Size<Pixels>,
         len: usize,
     },
+    /// A tab whose width is set by its elastic tabstop, rather than by the spaces it expands to.
+    Tab {
+        width: Pixels,
+        len: usize,
+    },
 }
 
 impl fmt::Debug for LineFragment {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             LineFragment::Text(shaped_line) => f.debug_tuple("Text").field(shaped_line).finish(),
+            LineFragment::Tab { width, len } => f
+                .debug_struct("Tab")
+                .field("width", width)
+                .field("len", len)
+                .finish(),
             LineFragment::Element { size, len, .. } => f
                 .debug_struct("Element")
                 .field("size", size)
@@ -6879,6 +7539,9 @@ impl LineWithInvisibles {
         editor_mode: &EditorMode,
         text_width: Pixels,
         is_row_soft_wrapped: impl Copy + Fn(usize) -> bool,
+        rows_with_ligatures: &[bool],
+        ligature_break: Option<(usize, Range<usize>)>,
+        rows_with_elastic_tabstops: &[bool],
         window: &mut Window,
         cx: &mut App,
     ) -> Vec<Self> {
@@ -6896,6 +7559,21 @@ impl LineWithInvisibles {
         let font_size = text_style.font_size.to_pixels(window.rem_size());
 
         let ellipsis = SharedString::from("⋯");
+        // `len` is where the text being shaped starts on its row.
+        let break_ligatures_in = |styles: &mut Vec<TextRun>, row: usize, len: usize| {
+            if !rows_with_ligatures.get(row).copied().unwrap_or(true) {
+                for style in styles.iter_mut() {
+                    style.font.features = style.font.features.without_ligatures();
+                }
+            } else if let Some((break_row, columns)) = &ligature_break {
+                if *break_row == row {
+                    break_ligatures(
+                        styles,
+                        columns.start.saturating_sub(len)..columns.end.saturating_sub(len),
+                    );
+                }
+            }
+        };
 
         for highlighted_chunk in chunks.chain([HighlightedChunk {
             text: "\n",
@@ -6906,6 +7584,7 @@ impl LineWithInvisibles {
         }]) {
             if let Some(replacement) = highlighted_chunk.replacement {
                 if !line.is_empty() {
+                    break_ligatures_in(&mut styles, row, len);
                     let shaped_line =
                         window
                             .text_system()
@@ -6963,9 +7642,13 @@ impl LineWithInvisibles {
                             Cow::Borrowed(text_style)
                         };
 
+                        let mut font = text_style.font();
+                        if !rows_with_ligatures.get(row).copied().unwrap_or(true) {
+                            font.features = font.features.without_ligatures();
+                        }
                         let run = TextRun {
                             len: x.len(),
-                            font: text_style.font(),
+                            font,
                             color: text_style.color,
                             background_color: text_style.background_color,
                             underline: text_style.underline,
@@ -6981,9 +7664,41 @@ impl LineWithInvisibles {
                         fragments.push(LineFragment::Text(line_layout))
                     }
                 }
+            } else if highlighted_chunk.is_tab
+                && !line_exceeded_max_len
+                && rows_with_elastic_tabstops
+                    .get(row)
+                    .copied()
+                    .unwrap_or(false)
+            {
+                if !line.is_empty() {
+                    break_ligatures_in(&mut styles, row, len);
+                    let shaped_line =
+                        window
+                            .text_system()
+                            .shape_line(line.clone().into(), font_size, &styles);
+                    width += shaped_line.width;
+                    len += shaped_line.len;
+                    fragments.push(LineFragment::Text(shaped_line));
+                    line.clear();
+                    styles.clear();
+                }
+
+                // Tabs are aligned once every line is laid out, see `align_elastic_tabstops`.
+                let tab_len = highlighted_chunk.text.len();
+                invisibles.push(Invisible::Tab {
+                    line_start_offset: len,
+                    line_end_offset: len + tab_len,
+                });
+                len += tab_len;
+                fragments.push(LineFragment::Tab {
+                    width: Pixels::ZERO,
+                    len: tab_len,
+                });
             } else {
                 for (ix, mut line_chunk) in highlighted_chunk.text.split('\n').enumerate() {
                     if ix > 0 {
+                        break_ligatures_in(&mut styles, row, len);
                         let shaped_line = window.text_system().shape_line(
                             line.clone().into(),
                             font_size,
@@ -7091,6 +7806,9 @@ impl LineWithInvisibles {
                 LineFragment::Text(line) => {
                     fragment_origin.x += line.width;
                 }
+                LineFragment::Tab { width, .. } => {
+                    fragment_origin.x += *width;
+                }
                 LineFragment::Element { element, size, .. } => {
                     let mut element = element
                         .take()
@@ -7132,12 +7850,23 @@ impl LineWithInvisibles {
                         .log_err();
                     fragment_origin.x += line.width;
                 }
+                LineFragment::Tab { width, .. } => {
+                    fragment_origin.x += *width;
+                }
                 LineFragment::Element { size, .. } => {
                     fragment_origin.x += size.width;
                 }
             }
         }
 
+        if let Some(wrap_indicator) = &layout.wrap_indicator {
+            if wraps_onto_next_row(&layout.position_map.snapshot, row) {
+                wrap_indicator
+                    .paint(fragment_origin, line_height, window, cx)
+                    .log_err();
+            }
+        }
+
         self.draw_invisibles(
             selection_ranges,
             layout,
@@ -7173,6 +7902,9 @@ impl LineWithInvisibles {
                         .log_err();
                     fragment_origin.x += line.width;
                 }
+                LineFragment::Tab { width, .. } => {
+                    fragment_origin.x += *width;
+                }
                 LineFragment::Element { size, .. } => {
                     fragment_origin.x += size.width;
                 }
@@ -7226,6 +7958,19 @@ impl LineWithInvisibles {
         match whitespace_setting {
             ShowWhitespaceSetting::None => (),
             ShowWhitespaceSetting::All => invisible_iter.for_each(|(_, paint)| paint(window, cx)),
+            ShowWhitespaceSetting::Trailing => {
+                let trailing_start = trailing_whitespace_start(&layout.position_map.snapshot, row);
+                invisible_iter.for_each(|([start, _], paint)| {
+                    let invisible_point = DisplayPoint::new(row, start as u32);
+                    if trailing_start.is_some_and(|trailing_start| start >= trailing_start)
+                        || selection_ranges.iter().any(|region| {
+                            region.start <= invisible_point && invisible_point < region.end
+                        })
+                    {
+                        paint(window, cx);
+                    }
+                })
+            }
             ShowWhitespaceSetting::Selection => invisible_iter.for_each(|([start, _], paint)| {
                 let invisible_point = DisplayPoint::new(row, start as u32);
                 if !selection_ranges
@@ -7298,6 +8043,14 @@ impl LineWithInvisibles {
                     fragment_start_x += shaped_line.width;
                     fragment_start_index = fragment_end_index;
                 }
+                LineFragment::Tab { width, len } => {
+                    let fragment_end_index = fragment_start_index + len;
+                    if index < fragment_end_index {
+                        return fragment_start_x;
+                    }
+                    fragment_start_x += *width;
+                    fragment_start_index = fragment_end_index;
+                }
                 LineFragment::Element { len, size, .. } => {
                     let fragment_end_index = fragment_start_index + len;
                     if index < fragment_end_index {
@@ -7328,6 +8081,19 @@ impl LineWithInvisibles {
                     fragment_start_x = fragment_end_x;
                     fragment_start_index += shaped_line.len;
                 }
+                LineFragment::Tab { width, len } => {
+                    let fragment_end_x = fragment_start_x + *width;
+                    if x < fragment_end_x {
+                        // Like a character, the closer edge of the tab is where the cursor goes.
+                        return Some(if x < fragment_start_x + *width / 2. {
+                            fragment_start_index
+                        } else {
+                            fragment_start_index + len
+                        });
+                    }
+                    fragment_start_index += len;
+                    fragment_start_x = fragment_end_x;
+                }
                 LineFragment::Element { len, size, .. } => {
                     let fragment_end_x = fragment_start_x + size.width;
                     if x < fragment_end_x {
@@ -7342,6 +8108,51 @@ impl LineWithInvisibles {
         None
     }
 
+    /// Whether any of this line's text reads right to left.
+    pub fn has_rtl(&self) -> bool {
+        self.fragments.iter().any(|fragment| match fragment {
+            LineFragment::Text(shaped_line) => shaped_line.has_rtl(),
+            LineFragment::Tab { .. } | LineFragment::Element { .. } => false,
+        })
+    }
+
+    /// The horizontal spans taken up by the text in the given range, from left to right.
+    pub fn x_ranges_for_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        let mut x_ranges = SmallVec::<[Range<Pixels>; 1]>::new();
+        let mut push_x_range = |x_range: Range<Pixels>| match x_ranges.last_mut() {
+            Some(last) if last.end == x_range.start => last.end = x_range.end,
+            _ => x_ranges.push(x_range),
+        };
+
+        let mut fragment_start_x = Pixels::ZERO;
+        let mut fragment_start_index = 0;
+        for fragment in &self.fragments {
+            let (width, len) = match fragment {
+                LineFragment::Text(shaped_line) => (shaped_line.width, shaped_line.len),
+                LineFragment::Tab { width, len } => (*width, *len),
+                LineFragment::Element { size, len, .. } => (size.width, *len),
+            };
+            let fragment_range = fragment_start_index..fragment_start_index + len;
+            let overlap = range.start.max(fragment_range.start)..range.end.min(fragment_range.end);
+            if !overlap.is_empty() {
+                if let LineFragment::Text(shaped_line) = fragment {
+                    let overlap =
+                        overlap.start - fragment_range.start..overlap.end - fragment_range.start;
+                    for x_range in shaped_line.x_ranges_for_range(overlap) {
+                        push_x_range(
+                            fragment_start_x + x_range.start..fragment_start_x + x_range.end,
+                        );
+                    }
+                } else {
+                    push_x_range(fragment_start_x..fragment_start_x + width);
+                }
+            }
+            fragment_start_x += width;
+            fragment_start_index = fragment_range.end;
+        }
+        x_ranges
+    }
+
     pub fn font_id_for_index(&self, index: usize) -> Option<FontId> {
         let mut fragment_start_index = 0;
 
@@ -7354,7 +8165,7 @@ impl LineWithInvisibles {
                     }
                     fragment_start_index = fragment_end_index;
                 }
-                LineFragment::Element { len, .. } => {
+                LineFragment::Tab { len, .. } | LineFragment::Element { len, .. } => {
                     let fragment_end_index = fragment_start_index + len;
                     if index < fragment_end_index {
                         return None;
@@ -7368,6 +8179,257 @@ impl LineWithInvisibles {
     }
 }
 
+/// Returns the column in `row` from which on it only shows whitespace at the end of a buffer
+/// line, if it shows the end of one rather than wrapping into the next row.
+fn trailing_whitespace_start(snapshot: &EditorSnapshot, row: DisplayRow) -> Option<usize> {
+    let display_snapshot = &snapshot.display_snapshot;
+    let buffer_snapshot = &display_snapshot.buffer_snapshot;
+    let line_end =
+        DisplayPoint::new(row, display_snapshot.line_len(row)).to_point(display_snapshot);
+    if line_end.column != buffer_snapshot.line_len(MultiBufferRow(line_end.row)) {
+        return None;
+    }
+
+    let trailing_len = buffer_snapshot
+        .reversed_chars_at(line_end)
+        .take_while(|c| *c != '\n' && c.is_whitespace())
+        .map(char::len_utf8)
+        .sum::<usize>();
+    let trailing_start = Point::new(line_end.row, line_end.column - trailing_len as u32)
+        .to_display_point(display_snapshot);
+    if trailing_start.row() < row {
+        Some(0)
+    } else {
+        Some(trailing_start.column() as usize)
+    }
+}
+
+/// Sizes the tabs on the given lines as elastic tabstops: the text before the `n`th tab on
+/// each of a block of consecutive lines that have one forms a column, and each of those tabs
+/// ends where the column's widest text ends, plus `padding`.
+fn align_elastic_tabstops(lines: &mut [LineWithInvisibles], padding: Pixels) {
+    // The width of the text before each tab on each line, since the previous tab.
+    let cell_widths = lines
+        .iter()
+        .map(|line| {
+            let mut cells = Vec::new();
+            let mut cell_width = Pixels::ZERO;
+            for fragment in &line.fragments {
+                match fragment {
+                    LineFragment::Text(shaped_line) => cell_width += shaped_line.width,
+                    LineFragment::Element { size, .. } => cell_width += size.width,
+                    LineFragment::Tab { .. } => cells.push(mem::take(&mut cell_width)),
+                }
+            }
+            cells
+        })
+        .collect::<Vec<_>>();
+
+    let mut column_widths = cell_widths
+        .iter()
+        .map(|cells| vec![Pixels::ZERO; cells.len()])
+        .collect::<Vec<_>>();
+    let max_tab_count = cell_widths.iter().map(Vec::len).max().unwrap_or(0);
+    for column in 0..max_tab_count {
+        let mut block_start = 0;
+        while block_start < lines.len() {
+            if cell_widths[block_start].len() <= column {
+                block_start += 1;
+                continue;
+            }
+            let block_end = (block_start..lines.len())
+                .find(|row| cell_widths[*row].len() <= column)
+                .unwrap_or(lines.len());
+            let column_width = cell_widths[block_start..block_end]
+                .iter()
+                .map(|cells| cells[column])
+                .fold(Pixels::ZERO, Pixels::max)
+                + padding;
+            for widths in &mut column_widths[block_start..block_end] {
+                widths[column] = column_width;
+            }
+            block_start = block_end;
+        }
+    }
+
+    for ((line, cells), column_widths) in lines.iter_mut().zip(cell_widths).zip(column_widths) {
+        let mut tab_widths = cells
+            .into_iter()
+            .zip(column_widths)
+            .map(|(cell_width, column_width)| column_width - cell_width);
+        let mut width = Pixels::ZERO;
+        for fragment in &mut line.fragments {
+            match fragment {
+                LineFragment::Text(shaped_line) => width += shaped_line.width,
+                LineFragment::Element { size, .. } => width += size.width,
+                LineFragment::Tab {
+                    width: tab_width, ..
+                } => {
+                    *tab_width = tab_widths.next().unwrap_or_default();
+                    width += *tab_width;
+                }
+            }
+        }
+        line.width = width;
+    }
+}
+
+/// Returns whether each of the given rows may show ligatures, following the `font_ligatures`
+/// setting of the language at the start of the row, as a multibuffer can mix languages.
+fn rows_with_ligatures(rows: Range<DisplayRow>, snapshot: &EditorSnapshot, cx: &App) -> Vec<bool> {
+    (rows.start.0..rows.end.0)
+        .map(|row| {
+            let point = DisplayPoint::new(DisplayRow(row), 0).to_point(snapshot);
+            snapshot
+                .buffer_snapshot
+                .language_settings_at(point, cx)
+                .font_ligatures
+        })
+        .collect()
+}
+
+/// Returns whether the tabs on each of the given rows are sized as elastic tabstops, following
+/// the `elastic_tabstops` setting of the language at the start of the row.
+fn rows_with_elastic_tabstops(
+    rows: Range<DisplayRow>,
+    snapshot: &EditorSnapshot,
+    cx: &App,
+) -> Vec<bool> {
+    (rows.start.0..rows.end.0)
+        .map(|row| {
+            let point = DisplayPoint::new(DisplayRow(row), 0).to_point(snapshot);
+            snapshot
+                .buffer_snapshot
+                .language_settings_at(point, cx)
+                .elastic_tabstops
+        })
+        .collect()
+}
+
+/// Extends the given rows to the start and end of the blocks of consecutive lines with elastic
+/// tabstops that they're part of, as every line in a block sizes its columns.
+///
+/// At most [`MAX_ELASTIC_TABSTOP_CONTEXT_ROWS`] are added on either side, so that large
+/// tab-separated files stay fast to lay out.
+fn elastic_tabstop_block_rows(
+    rows: Range<DisplayRow>,
+    snapshot: &EditorSnapshot,
+    cx: &App,
+) -> Range<DisplayRow> {
+    let has_elastic_tabstops = |row: DisplayRow| {
+        let point = DisplayPoint::new(row, 0).to_point(snapshot);
+        let buffer = &snapshot.buffer_snapshot;
+        buffer.language_settings_at(point, cx).elastic_tabstops
+            && buffer
+                .text_for_range(
+                    Point::new(point.row, 0)
+                        ..Point::new(point.row, buffer.line_len(MultiBufferRow(point.row))),
+                )
+                .any(|chunk| chunk.contains('\t'))
+    };
+
+    let mut start = rows.start;
+    while start.0 > 0
+        && rows.start.0 - start.0 < MAX_ELASTIC_TABSTOP_CONTEXT_ROWS
+        && has_elastic_tabstops(DisplayRow(start.0 - 1))
+    {
+        start.0 -= 1;
+    }
+    let mut end = rows.end;
+    while end <= snapshot.max_point().row()
+        && end.0 - rows.end.0 < MAX_ELASTIC_TABSTOP_CONTEXT_ROWS
+        && has_elastic_tabstops(end)
+    {
+        end.0 += 1;
+    }
+    start..end
+}
+
+/// Returns the text around the cursor up to the nearest whitespace, which contains every
+/// ligature the cursor could be touching.
+fn ligature_break_range(
+    snapshot: &EditorSnapshot,
+    head: DisplayPoint,
+) -> Option<Range<DisplayPoint>> {
+    let display_snapshot = &snapshot.display_snapshot;
+    let buffer_snapshot = &display_snapshot.buffer_snapshot;
+    let offset = head.to_offset(display_snapshot, Bias::Left);
+    let is_token = |c: &char| !c.is_whitespace();
+    let start = offset
+        - buffer_snapshot
+            .reversed_chars_at(offset)
+            .take_while(is_token)
+            .map(char::len_utf8)
+            .sum::<usize>();
+    let end = offset
+        + buffer_snapshot
+            .chars_at(offset)
+            .take_while(is_token)
+            .map(char::len_utf8)
+            .sum::<usize>();
+    let start = start.to_display_point(display_snapshot);
+    let end = end.to_display_point(display_snapshot);
+    (start < end && start.row() == end.row()).then_some(start..end)
+}
+
+/// Disables ligatures for the text in `range`, splitting the runs it starts or ends in.
+/// Shaping never joins text across runs in different fonts, so this also breaks ligatures
+/// that straddle the range's ends.
+fn break_ligatures(runs: &mut Vec<TextRun>, range: Range<usize>) {
+    if range.is_empty() {
+        return;
+    }
+
+    let mut split_runs = Vec::with_capacity(runs.len() + 2);
+    let mut run_start = 0;
+    for run in runs.drain(..) {
+        let run_range = run_start..run_start + run.len;
+        run_start = run_range.end;
+        let overlap = run_range.start.max(range.start)..run_range.end.min(range.end);
+        if overlap.is_empty() {
+            split_runs.push(run);
+            continue;
+        }
+
+        if run_range.start < overlap.start {
+            split_runs.push(TextRun {
+                len: overlap.start - run_range.start,
+                ..run.clone()
+            });
+        }
+        let mut broken_run = TextRun {
+            len: overlap.len(),
+            ..run.clone()
+        };
+        broken_run.font.features = broken_run.font.features.without_ligatures();
+        split_runs.push(broken_run);
+        if overlap.end < run_range.end {
+            split_runs.push(TextRun {
+                len: run_range.end - overlap.end,
+                ..run
+            });
+        }
+    }
+    *runs = split_runs;
+}
+
+/// Returns whether the given display row is soft-wrapped, continuing on the next one.
+fn wraps_onto_next_row(snapshot: &EditorSnapshot, row: DisplayRow) -> bool {
+    let display_snapshot = &snapshot.display_snapshot;
+    let line_len = display_snapshot.line_len(row);
+    if line_len == 0 || row >= display_snapshot.max_point().row() {
+        return false;
+    }
+
+    let line_end = DisplayPoint::new(row, line_len).to_point(display_snapshot);
+    let next_line_start = DisplayPoint::new(row.next_row(), 0).to_point(display_snapshot);
+    line_end.row == next_line_start.row
+        && line_end.column
+            != display_snapshot
+                .buffer_snapshot
+                .line_len(MultiBufferRow(line_end.row))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Invisible {
     /// A tab character
@@ -7478,6 +8540,7 @@ impl Element for EditorElement {
                                         &style,
                                         px(f32::MAX),
                                         |_| false, // Single lines never soft wrap
+                                        None,
                                         window,
                                         cx,
                                     )
@@ -7616,11 +8679,17 @@ impl Element for EditorElement {
                         .read(cx)
                         .minimap()
                         .is_some()
-                        .then(|| match settings.minimap.show {
-                            ShowMinimap::Auto => {
-                                scrollbars_shown.then_some(MinimapLayout::MINIMAP_WIDTH)
+                        .then(|| {
+                            let minimap_width = self.get_minimap_width(
+                                font_id,
+                                &settings.minimap,
+                                text_width,
+                                window,
+                            );
+                            match settings.minimap.show {
+                                ShowMinimap::Auto => scrollbars_shown.then_some(minimap_width),
+                                _ => Some(minimap_width),
                             }
-                            _ => Some(MinimapLayout::MINIMAP_WIDTH),
                         })
                         .flatten()
                         .filter(|minimap_width| {
@@ -7666,7 +8735,8 @@ impl Element for EditorElement {
                                 }
                             };
 
-                            if editor.set_wrap_width(wrap_width, cx) {
+                            let rewrapped = editor.set_wrap_width(wrap_width, cx);
+                            if editor.set_wrap_hanging_indent(cx) || rewrapped {
                                 editor.snapshot(window, cx)
                             } else {
                                 snapshot
@@ -7744,6 +8814,9 @@ impl Element for EditorElement {
                             cx,
                         );
                         snapshot = editor.snapshot(window, cx);
+                        if editor.scroll_manager.is_animating() {
+                            window.request_animation_frame();
+                        }
                     });
 
                     let mut scroll_position = snapshot.scroll_position();
@@ -8009,12 +9082,22 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let ligature_break = newest_selection_head
+                        .filter(|head| {
+                            self.editor.focus_handle(cx).is_focused(window)
+                                && snapshot
+                                    .buffer_snapshot
+                                    .language_settings_at(head.to_point(&snapshot), cx)
+                                    .break_ligatures_at_cursor
+                        })
+                        .and_then(|head| ligature_break_range(&snapshot, head));
                     let mut line_layouts = Self::layout_lines(
                         start_row..end_row,
                         &snapshot,
                         &self.style,
                         editor_width,
                         is_row_soft_wrapped,
+                        ligature_break,
                         window,
                         cx,
                     );
@@ -8181,6 +9264,8 @@ impl Element for EditorElement {
                         content_origin,
                         text_hitbox.origin,
                         start_buffer_row..end_buffer_row,
+                        start_row,
+                        &line_layouts,
                         scroll_pixel_position,
                         line_height,
                         &snapshot,
@@ -8519,30 +9604,58 @@ impl Element for EditorElement {
                     });
 
                     let invisible_symbol_font_size = font_size / 2.;
-                    let tab_invisible = window.text_system().shape_line(
-                        "→".into(),
-                        invisible_symbol_font_size,
-                        &[TextRun {
-                            len: "→".len(),
-                            font: self.style.text.font(),
-                            color: cx.theme().colors().editor_invisible,
-                            background_color: None,
-                            underline: None,
-                            strikethrough: None,
-                        }],
-                    );
-                    let space_invisible = window.text_system().shape_line(
-                        "•".into(),
-                        invisible_symbol_font_size,
-                        &[TextRun {
-                            len: "•".len(),
-                            font: self.style.text.font(),
-                            color: cx.theme().colors().editor_invisible,
-                            background_color: None,
-                            underline: None,
-                            strikethrough: None,
-                        }],
-                    );
+                    let whitespace_map = self
+                        .editor
+                        .read(cx)
+                        .buffer
+                        .read(cx)
+                        .language_settings(cx)
+                        .whitespace_map
+                        .clone();
+                    let invisible_color = whitespace_map
+                        .color
+                        .as_deref()
+                        .and_then(|color| Rgba::try_from(color).ok())
+                        .map(Hsla::from)
+                        .unwrap_or(cx.theme().colors().editor_invisible);
+                    let mut shape_invisible = |symbol: &str| {
+                        window.text_system().shape_line(
+                            SharedString::from(symbol.to_string()),
+                            invisible_symbol_font_size,
+                            &[TextRun {
+                                len: symbol.len(),
+                                font: self.style.text.font(),
+                                color: invisible_color,
+                                background_color: None,
+                                underline: None,
+                                strikethrough: None,
+                            }],
+                        )
+                    };
+                    let tab_invisible = shape_invisible(whitespace_map.tab());
+                    let space_invisible = shape_invisible(whitespace_map.space());
+
+                    let soft_wrap_indicator = self
+                        .editor
+                        .read(cx)
+                        .buffer
+                        .read(cx)
+                        .language_settings(cx)
+                        .soft_wrap_indicator;
+                    let wrap_indicator = (!soft_wrap_indicator.is_empty()).then(|| {
+                        window.text_system().shape_line(
+                            SharedString::from(soft_wrap_indicator.clone()),
+                            font_size,
+                            &[TextRun {
+                                len: soft_wrap_indicator.len(),
+                                font: self.style.text.font(),
+                                color: cx.theme().colors().editor_invisible,
+                                background_color: None,
+                                underline: None,
+                                strikethrough: None,
+                            }],
+                        )
+                    });
 
                     let mode = snapshot.mode.clone();
 
@@ -8620,6 +9733,7 @@ impl Element for EditorElement {
                         crease_trailers,
                         tab_invisible,
                         space_invisible,
+                        wrap_indicator,
                         sticky_buffer_header,
                         expand_toggles,
                     }
@@ -8693,6 +9807,7 @@ impl Element for EditorElement {
                     self.paint_scrollbars(layout, window, cx);
                     self.paint_inline_completion_popover(layout, window, cx);
                     self.paint_mouse_context_menu(layout, window, cx);
+                    self.paint_accessibility_nodes(layout, bounds, window, cx);
                 });
             })
         })
@@ -8805,6 +9920,7 @@ pub struct EditorLayout {
     mouse_context_menu: Option<AnyElement>,
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
+    wrap_indicator: Option<ShapedLine>,
     sticky_buffer_header: Option<AnyElement>,
 }
 
@@ -9200,6 +10316,39 @@ impl ScrollbarLayout {
     }
 }
 
+/// The minimap is as wide as `max_width_columns` of its text, but narrower in editors too
+/// narrow for it to take up at most [`MINIMAP_MAX_WIDTH_FRACTION`] of their text's width.
+fn minimap_width(max_width_columns: u32, em_width: Pixels, text_width: Pixels) -> Pixels {
+    (max_width_columns as f32 * em_width).min(text_width * MINIMAP_MAX_WIDTH_FRACTION)
+}
+
+fn shows_diagnostic(setting: ScrollbarDiagnostics, severity: lsp::DiagnosticSeverity) -> bool {
+    match (setting, severity) {
+        (ScrollbarDiagnostics::All, _) => true,
+        (ScrollbarDiagnostics::Error, lsp::DiagnosticSeverity::ERROR) => true,
+        (
+            ScrollbarDiagnostics::Warning,
+            lsp::DiagnosticSeverity::ERROR | lsp::DiagnosticSeverity::WARNING,
+        ) => true,
+        (
+            ScrollbarDiagnostics::Information,
+            lsp::DiagnosticSeverity::ERROR
+            | lsp::DiagnosticSeverity::WARNING
+            | lsp::DiagnosticSeverity::INFORMATION,
+        ) => true,
+        (_, _) => false,
+    }
+}
+
+fn diagnostic_color(severity: lsp::DiagnosticSeverity, theme: &Theme) -> Hsla {
+    match severity {
+        lsp::DiagnosticSeverity::ERROR => theme.status().error,
+        lsp::DiagnosticSeverity::WARNING => theme.status().warning,
+        lsp::DiagnosticSeverity::INFORMATION => theme.status().info,
+        _ => theme.status().hint,
+    }
+}
+
 struct MinimapLayout {
     pub minimap: AnyElement,
     pub thumb_layout: ScrollbarLayout,
@@ -9207,10 +10356,10 @@ struct MinimapLayout {
     pub minimap_line_height: Pixels,
     pub thumb_border_style: MinimapThumbBorder,
     pub max_scroll_top: f32,
+    pub markers: Vec<PaintQuad>,
 }
 
 impl MinimapLayout {
-    const MINIMAP_WIDTH: Pixels = px(100.);
     /// Calculates the scroll top offset the minimap editor has to have based on the
     /// current scroll progress.
     fn calculate_minimap_top_offset(
@@ -9324,6 +10473,7 @@ pub fn layout_line(
     cx: &mut App,
 ) -> LineWithInvisibles {
     let chunks = snapshot.highlighted_chunks(row..row + DisplayRow(1), true, style);
+    let rows_with_ligatures = rows_with_ligatures(row..row + DisplayRow(1), snapshot, cx);
     LineWithInvisibles::from_chunks(
         chunks,
         &style,
@@ -9332,6 +10482,9 @@ pub fn layout_line(
         &snapshot.mode,
         text_width,
         is_row_soft_wrapped,
+        &rows_with_ligatures,
+        None,
+        &[],
         window,
         cx,
     )
@@ -9365,6 +10518,7 @@ pub struct CursorName {
     string: SharedString,
     color: Hsla,
     is_top_row: bool,
+    opacity: f32,
 }
 
 impl CursorLayout {
@@ -9454,6 +10608,7 @@ impl CursorLayout {
                 .px_0p5()
                 .line_height(text_size + px(2.))
                 .text_color(cursor_name.color)
+                .opacity(cursor_name.opacity)
                 .child(cursor_name.string.clone())
                 .into_any_element();
 
@@ -9720,11 +10875,13 @@ mod tests {
     use crate::{
         Editor, MultiBuffer,
         display_map::{BlockPlacement, BlockProperties},
+        editor_settings::MinimapContent,
         editor_tests::{init_test, update_test_language_settings},
     };
-    use gpui::{TestAppContext, VisualTestContext};
+    use gpui::{Font, FontFeatures, TestAppContext, VisualTestContext, font};
     use language::language_settings;
     use log::info;
+    use settings::SettingsStore;
     use std::num::NonZeroU32;
     use util::test::sample_text;
 
@@ -9947,6 +11104,200 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_minimap_markers(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.minimap = Some(MinimapContent {
+                        show: Some(ShowMinimap::Always),
+                        ..Default::default()
+                    });
+                });
+            });
+        });
+
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple("one\ntwo\none\nthree\none", cx);
+            Editor::new(EditorMode::full(), buffer, None, window, cx)
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|_, cx| editor.read(cx).style().unwrap().clone());
+        window
+            .update(cx, |editor, _, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let ranges = [0..3, 8..11, 18..21].map(|range| {
+                    snapshot.anchor_after(range.start)..snapshot.anchor_before(range.end)
+                });
+                editor.highlight_background::<BufferSearchHighlights>(
+                    &ranges,
+                    |colors| colors.search_match_background,
+                    cx,
+                );
+            })
+            .unwrap();
+
+        let (_, state) = cx.draw(point(px(0.), px(0.)), size(px(1000.), px(500.)), |_, _| {
+            EditorElement::new(&editor, style.clone())
+        });
+        assert_eq!(state.minimap.as_ref().unwrap().markers.len(), 3);
+
+        cx.update(|_, cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.minimap = Some(MinimapContent {
+                        show: Some(ShowMinimap::Always),
+                        search_results: Some(false),
+                        ..Default::default()
+                    });
+                });
+            });
+        });
+        let (_, state) = cx.draw(point(px(0.), px(0.)), size(px(1000.), px(500.)), |_, _| {
+            EditorElement::new(&editor, style)
+        });
+        assert_eq!(state.minimap.as_ref().unwrap().markers.len(), 0);
+    }
+
+    #[test]
+    fn test_minimap_width() {
+        // Wide editors show as many columns as configured.
+        assert_eq!(minimap_width(80, px(1.5), px(1000.)), px(120.));
+        // Narrower ones shrink the minimap rather than giving it more of their width.
+        assert_eq!(minimap_width(80, px(1.5), px(400.)), px(80.));
+        assert_eq!(minimap_width(0, px(1.5), px(400.)), px(0.));
+    }
+
+    #[test]
+    fn test_shows_diagnostic() {
+        use lsp::DiagnosticSeverity;
+
+        let shown = |setting| {
+            [
+                DiagnosticSeverity::ERROR,
+                DiagnosticSeverity::WARNING,
+                DiagnosticSeverity::INFORMATION,
+                DiagnosticSeverity::HINT,
+            ]
+            .into_iter()
+            .filter(|severity| shows_diagnostic(setting, *severity))
+            .count()
+        };
+        assert_eq!(shown(ScrollbarDiagnostics::None), 0);
+        assert_eq!(shown(ScrollbarDiagnostics::Error), 1);
+        assert_eq!(shown(ScrollbarDiagnostics::Warning), 2);
+        assert_eq!(shown(ScrollbarDiagnostics::Information), 3);
+        assert_eq!(shown(ScrollbarDiagnostics::All), 4);
+    }
+
+    #[test]
+    fn test_align_elastic_tabstops() {
+        let cell = |width: f32| LineFragment::Element {
+            id: FoldId::default(),
+            element: None,
+            size: size(px(width), px(10.)),
+            len: 1,
+        };
+        let tab = || LineFragment::Tab {
+            width: Pixels::ZERO,
+            len: 4,
+        };
+        let line = |fragments: Vec<LineFragment>| LineWithInvisibles {
+            fragments: fragments.into_iter().collect(),
+            invisibles: Vec::new(),
+            len: 0,
+            width: Pixels::ZERO,
+            font_size: px(10.),
+        };
+        let mut lines = vec![
+            line(vec![cell(30.), tab(), cell(10.), tab()]),
+            line(vec![cell(50.), tab(), cell(5.)]),
+            line(vec![cell(20.)]),
+            line(vec![tab(), cell(10.), tab()]),
+        ];
+
+        align_elastic_tabstops(&mut lines, px(5.));
+
+        let tab_widths = lines
+            .iter()
+            .map(|line| {
+                line.fragments
+                    .iter()
+                    .filter_map(|fragment| match fragment {
+                        LineFragment::Tab { width, .. } => Some(*width),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tab_widths,
+            vec![
+                vec![px(25.), px(5.)],
+                vec![px(5.)],
+                vec![],
+                vec![px(5.), px(5.)],
+            ]
+        );
+        assert_eq!(
+            lines.iter().map(|line| line.width).collect::<Vec<_>>(),
+            vec![px(70.), px(60.), px(20.), px(20.)]
+        );
+    }
+
+    #[test]
+    fn test_break_ligatures() {
+        let run = |len, features: FontFeatures| TextRun {
+            len,
+            font: Font {
+                features,
+                ..font("Zed Mono")
+            },
+            color: Hsla::default(),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        let ligatures = FontFeatures::default();
+        let no_ligatures = ligatures.without_ligatures();
+
+        let mut runs = vec![run(4, ligatures.clone()), run(6, ligatures.clone())];
+        break_ligatures(&mut runs, 2..7);
+        assert_eq!(
+            runs,
+            vec![
+                run(2, ligatures.clone()),
+                run(2, no_ligatures.clone()),
+                run(3, no_ligatures.clone()),
+                run(3, ligatures.clone()),
+            ]
+        );
+
+        let mut runs = vec![run(4, ligatures.clone())];
+        break_ligatures(&mut runs, 4..8);
+        assert_eq!(runs, vec![run(4, ligatures)]);
+    }
+
+    #[gpui::test]
+    fn test_trailing_whitespace_start(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple("a b  \n   \nc\t\n", cx);
+            Editor::new(EditorMode::full(), buffer, None, window, cx)
+        });
+        let snapshot = window
+            .update(cx, |editor, window, cx| editor.snapshot(window, cx))
+            .unwrap();
+
+        assert_eq!(trailing_whitespace_start(&snapshot, DisplayRow(0)), Some(3));
+        assert_eq!(trailing_whitespace_start(&snapshot, DisplayRow(1)), Some(0));
+        assert_eq!(trailing_whitespace_start(&snapshot, DisplayRow(2)), Some(1));
+        assert_eq!(trailing_whitespace_start(&snapshot, DisplayRow(3)), Some(0));
+    }
+
     #[gpui::test]
     fn test_all_invisibles_drawing(cx: &mut TestAppContext) {
         const TAB_SIZE: u32 = 4;
@@ -1,6 +1,6 @@
 use crate::{Editor, RangeToAnchorExt};
-use gpui::{Context, Window};
-use language::CursorShape;
+use gpui::{Context, FontWeight, HighlightStyle, UnderlineStyle, Window, px};
+use language::{CursorShape, language_settings::EnclosingBracketEmphasis};
 
 enum MatchingBracketHighlight {}
 
@@ -10,6 +10,7 @@ pub fn refresh_matching_bracket_highlights(
     cx: &mut Context<Editor>,
 ) {
     editor.clear_background_highlights::<MatchingBracketHighlight>(cx);
+    editor.clear_highlights::<MatchingBracketHighlight>(cx);
 
     let newest_selection = editor.selections.newest::<usize>(cx);
     // Don't highlight brackets if the selection isn't empty
@@ -30,14 +31,39 @@ pub fn refresh_matching_bracket_highlights(
         .buffer_snapshot
         .innermost_enclosing_bracket_ranges(head..tail, None)
     {
+        let ranges = [
+            opening_range.to_anchors(&snapshot.buffer_snapshot),
+            closing_range.to_anchors(&snapshot.buffer_snapshot),
+        ];
         editor.highlight_background::<MatchingBracketHighlight>(
-            &[
-                opening_range.to_anchors(&snapshot.buffer_snapshot),
-                closing_range.to_anchors(&snapshot.buffer_snapshot),
-            ],
+            &ranges,
             |theme| theme.editor_document_highlight_bracket_background,
             cx,
-        )
+        );
+
+        let emphasis = snapshot
+            .buffer_snapshot
+            .language_settings_at(head, cx)
+            .bracket_colorization
+            .enclosing_pair;
+        let style = match emphasis {
+            EnclosingBracketEmphasis::None => None,
+            EnclosingBracketEmphasis::Underline => Some(HighlightStyle {
+                underline: Some(UnderlineStyle {
+                    thickness: px(1.),
+                    color: None,
+                    wavy: false,
+                }),
+                ..Default::default()
+            }),
+            EnclosingBracketEmphasis::Bold => Some(HighlightStyle {
+                font_weight: Some(FontWeight::BOLD),
+                ..Default::default()
+            }),
+        };
+        if let Some(style) = style {
+            editor.highlight_text::<MatchingBracketHighlight>(ranges.to_vec(), style, cx);
+        }
     }
 }
 
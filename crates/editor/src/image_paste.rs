@@ -0,0 +1,138 @@
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use gpui::{Context, Image, ImageFormat, Window};
+use settings::Settings as _;
+use util::paths::home_dir;
+
+use crate::{Editor, EditorSettings};
+
+/// How to link to a pasted image from the buffer it's pasted into.
+#[derive(Clone, Copy)]
+enum ImageLink {
+    Markdown,
+    Html,
+}
+
+impl ImageLink {
+    fn for_language(name: &str) -> Option<Self> {
+        match name {
+            "Markdown" | "Markdown-Inline" => Some(Self::Markdown),
+            "HTML" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    fn text(self, path: &str) -> String {
+        match self {
+            Self::Markdown => format!("![]({path})"),
+            Self::Html => format!("<img src=\"{path}\">"),
+        }
+    }
+}
+
+fn extension(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "png",
+        ImageFormat::Jpeg => "jpg",
+        ImageFormat::Webp => "webp",
+        ImageFormat::Gif => "gif",
+        ImageFormat::Svg => "svg",
+        ImageFormat::Bmp => "bmp",
+        ImageFormat::Tiff => "tiff",
+    }
+}
+
+impl Editor {
+    /// Pastes an image from the clipboard. Markdown and HTML buffers get a link to a copy of the
+    /// image saved in the `pasted_image_directory`, next to the buffer's file. Anywhere else, the
+    /// image can be saved to a file of the user's choosing.
+    pub(crate) fn paste_image(
+        &mut self,
+        image: Image,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let fs = project.read(cx).fs().clone();
+        let head = self.selections.newest_anchor().head();
+        let multi_buffer = self.buffer.read(cx);
+        let buffer_path = multi_buffer
+            .point_to_buffer_offset(head, cx)
+            .and_then(|(buffer, _)| {
+                let file = buffer.read(cx).file()?.as_local()?;
+                Some(file.abs_path(cx))
+            });
+        let link = multi_buffer
+            .snapshot(cx)
+            .language_at(head)
+            .and_then(|language| ImageLink::for_language(language.name().as_ref()));
+        let file_name = format!(
+            "image-{}.{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis(),
+            extension(image.format)
+        );
+
+        if let Some((link, buffer_dir)) = link
+            .filter(|_| !self.read_only(cx))
+            .zip(buffer_path.as_deref().and_then(Path::parent))
+        {
+            let assets_dir = EditorSettings::get_global(cx)
+                .pasted_image_directory
+                .clone();
+            let abs_dir = buffer_dir.join(&assets_dir);
+            let abs_path = abs_dir.join(&file_name);
+            let link_text = link.text(
+                &Path::new(&assets_dir)
+                    .join(&file_name)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+            );
+            let selections = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+            cx.spawn_in(window, async move |editor, cx| {
+                fs.create_dir(&abs_dir).await?;
+                fs.write(&abs_path, &image.bytes)
+                    .await
+                    .with_context(|| format!("saving pasted image to {abs_path:?}"))?;
+                editor.update_in(cx, |editor, window, cx| {
+                    editor.transact(window, cx, |editor, window, cx| {
+                        editor.change_selections(None, window, cx, |s| {
+                            s.select_anchor_ranges(selections)
+                        });
+                        editor.insert(&link_text, window, cx);
+                    });
+                })
+            })
+            .detach_and_log_err(cx);
+            return;
+        }
+
+        let directory = buffer_path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .or_else(|| {
+                let worktree = project.read(cx).visible_worktrees(cx).next()?;
+                Some(worktree.read(cx).abs_path().to_path_buf())
+            })
+            .unwrap_or_else(|| home_dir().clone());
+        let abs_path = cx.prompt_for_new_path(&directory);
+        cx.spawn_in(window, async move |_, _| {
+            let Some(abs_path) = abs_path.await?? else {
+                return anyhow::Ok(());
+            };
+            fs.write(&abs_path, &image.bytes)
+                .await
+                .with_context(|| format!("saving pasted image to {abs_path:?}"))
+        })
+        .detach_and_log_err(cx);
+    }
+}
@@ -0,0 +1,489 @@
+use std::{ops::Range, sync::LazyLock};
+
+use gpui::{Context, Window};
+use language::Point;
+use multi_buffer::{MultiBufferRow, MultiBufferSnapshot, ToOffset as _, ToPoint as _};
+use regex::Regex;
+use time::{Date, Month};
+
+use crate::{
+    Autoscroll, Editor, HideMouseCursorOrigin,
+    actions::{Decrement, Increment, InsertSequence},
+};
+
+/// The keywords that incrementing toggles, in Vim mode too.
+const BOOLEAN_PAIRS: &[&[&str]] = &[&["true", "false"], &["yes", "no"], &["on", "off"]];
+
+/// The other keywords that incrementing goes through, in order, and back to the first one.
+const KEYWORD_CYCLES: &[&[&str]] = &[
+    &["enable", "disable"],
+    &["enabled", "disabled"],
+    &[
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "friday",
+        "saturday",
+        "sunday",
+    ],
+    &[
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ],
+];
+
+static DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})\b").unwrap());
+
+impl Editor {
+    pub fn increment(&mut self, action: &Increment, window: &mut Window, cx: &mut Context<Self>) {
+        let delta = action.count.unwrap_or(1) as i64;
+        self.increment_values(delta, window, cx);
+    }
+
+    pub fn decrement(&mut self, action: &Decrement, window: &mut Window, cx: &mut Context<Self>) {
+        let delta = action.count.unwrap_or(1) as i64;
+        self.increment_values(-delta, window, cx);
+    }
+
+    /// Increments the value at or after each cursor, leaving the cursor after it, and the first
+    /// value of each line of the selections.
+    fn increment_values(&mut self, delta: i64, window: &mut Window, cx: &mut Context<Self>) {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        let mut new_selections = Vec::new();
+        for selection in self.selections.all::<Point>(cx) {
+            if selection.is_empty() {
+                let cursor = match increment_value_at(&snapshot, selection.head(), delta) {
+                    Some((range, new_text)) => {
+                        let cursor = snapshot.anchor_after(range.end);
+                        edits.push((range, new_text));
+                        cursor
+                    }
+                    None => snapshot.anchor_after(selection.head()),
+                };
+                new_selections.push(cursor..cursor);
+                continue;
+            }
+
+            for row in selection.start.row..=selection.end.row {
+                let start = if row == selection.start.row {
+                    selection.start
+                } else {
+                    Point::new(row, 0)
+                };
+                if let Some((range, new_text)) = increment_value_at(&snapshot, start, delta) {
+                    if range.start < selection.end {
+                        edits.push((range, new_text));
+                    }
+                }
+            }
+            new_selections.push(
+                snapshot.anchor_before(selection.start)..snapshot.anchor_after(selection.end),
+            );
+        }
+        if edits.is_empty() {
+            return;
+        }
+        // Several cursors can be in the same value, which is then only incremented once.
+        edits.sort_by_key(|(range, _)| range.start);
+        edits.dedup_by_key(|(range, _)| range.start);
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_anchor_ranges(new_selections)
+            });
+        });
+    }
+
+    pub fn insert_sequence(
+        &mut self,
+        action: &InsertSequence,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let start = action.start.unwrap_or(1);
+        let step = action.step.unwrap_or(1);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        let mut new_selections = Vec::new();
+        for (ix, selection) in self.selections.all::<usize>(cx).into_iter().enumerate() {
+            let number = start.saturating_add(step.saturating_mul(ix as i64));
+            edits.push((selection.start..selection.end, number.to_string()));
+            let cursor = snapshot.anchor_after(selection.end);
+            new_selections.push(cursor..cursor);
+        }
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_anchor_ranges(new_selections)
+            });
+        });
+    }
+}
+
+/// Finds the value to increment at or after `start` on its line, and returns its range with
+/// what it's replaced by. That's a date in the `YYYY-MM-DD` format, whose part under the cursor
+/// is incremented, or a decimal, hexadecimal (`0x`), octal (`0o`) or binary (`0b`) number, or
+/// else a keyword like `true`, which is replaced by the next one of its cycle.
+fn increment_value_at(
+    snapshot: &MultiBufferSnapshot,
+    start: Point,
+    delta: i64,
+) -> Option<(Range<Point>, String)> {
+    let number = find_number(snapshot, start, true);
+    let number_start = number.as_ref().map(|(range, _, _)| range.start);
+    if let Some(date) = increment_date(snapshot, start, number_start, delta) {
+        return Some(date);
+    }
+    increment_number_or_keyword(snapshot, start, number, delta, true)
+}
+
+/// Like the editor's increment, but only for decimal, hexadecimal and binary numbers, and for
+/// booleans like `true`, which is what Vim mode increments.
+pub fn increment_number_or_boolean_at(
+    snapshot: &MultiBufferSnapshot,
+    start: Point,
+    delta: i64,
+) -> Option<(Range<Point>, String)> {
+    let number = find_number(snapshot, start, false);
+    increment_number_or_keyword(snapshot, start, number, delta, false)
+}
+
+fn increment_number_or_keyword(
+    snapshot: &MultiBufferSnapshot,
+    start: Point,
+    number: Option<(Range<Point>, String, u32)>,
+    delta: i64,
+    all_keywords: bool,
+) -> Option<(Range<Point>, String)> {
+    if let Some((range, num, radix)) = number {
+        let replace = match radix {
+            10 => increment_decimal_string(&num, delta),
+            16 => increment_hex_string(&num, delta),
+            8 => increment_octal_string(&num, delta),
+            2 => increment_binary_string(&num, delta),
+            _ => unreachable!(),
+        };
+        return Some((range, replace));
+    }
+    let (range, keyword) = find_keyword(snapshot, start, all_keywords)?;
+    Some((range, cycle_keyword(&keyword, delta, all_keywords)))
+}
+
+/// Increments the date that `start` is in, or that begins with the next number after it. The
+/// year or month is incremented when `start` is in it, and otherwise the day.
+fn increment_date(
+    snapshot: &MultiBufferSnapshot,
+    start: Point,
+    number_start: Option<Point>,
+    delta: i64,
+) -> Option<(Range<Point>, String)> {
+    let row = start.row;
+    let line_end = Point::new(row, snapshot.line_len(MultiBufferRow(row)));
+    let line = snapshot
+        .text_for_range(Point::new(row, 0)..line_end)
+        .collect::<String>();
+    let column = start.column as usize;
+    let captures = DATE_REGEX.captures_iter(&line).find(|captures| {
+        let date = captures.get(0).unwrap();
+        (date.start() <= column && column < date.end())
+            || number_start == Some(Point::new(row, date.start() as u32))
+    })?;
+
+    let date = captures.get(0)?;
+    let year = captures[1].parse().ok()?;
+    let month = Month::try_from(captures[2].parse::<u8>().ok()?).ok()?;
+    let day = captures[3].parse().ok()?;
+    let old_date = Date::from_calendar_date(year, month, day).ok()?;
+    let new_date = if column >= date.start() && column < captures.get(1)?.end() {
+        add_months(old_date, delta.checked_mul(12)?)?
+    } else if column >= date.start() && column < captures.get(2)?.end() {
+        add_months(old_date, delta)?
+    } else {
+        old_date.checked_add(time::Duration::days(delta))?
+    };
+    let new_text = format!(
+        "{:04}-{:02}-{:02}",
+        new_date.year(),
+        u8::from(new_date.month()),
+        new_date.day()
+    );
+    Some((
+        Point::new(row, date.start() as u32)..Point::new(row, date.end() as u32),
+        new_text,
+    ))
+}
+
+/// Adds months to a date, moving the day to the end of the month when the month is shorter.
+fn add_months(date: Date, months: i64) -> Option<Date> {
+    let month_index = date.year() as i64 * 12 + u8::from(date.month()) as i64 - 1 + months;
+    let year = i32::try_from(month_index.div_euclid(12)).ok()?;
+    let month = Month::try_from(month_index.rem_euclid(12) as u8 + 1).ok()?;
+    let day = date.day().min(time::util::days_in_year_month(year, month));
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+fn increment_decimal_string(num: &str, delta: i64) -> String {
+    let (negative, delta, num_str) = match num.strip_prefix('-') {
+        Some(n) => (true, -delta, n),
+        None => (false, delta, num),
+    };
+    let num_length = num_str.len();
+    let leading_zero = num_str.starts_with('0');
+
+    let (result, new_negative) = match u64::from_str_radix(num_str, 10) {
+        Ok(value) => {
+            let wrapped = value.wrapping_add_signed(delta);
+            if delta < 0 && wrapped > value {
+                ((u64::MAX - wrapped).wrapping_add(1), !negative)
+            } else if delta > 0 && wrapped < value {
+                (u64::MAX - wrapped, !negative)
+            } else {
+                (wrapped, negative)
+            }
+        }
+        Err(_) => (u64::MAX, negative),
+    };
+
+    let formatted = format!("{}", result);
+    let new_significant_digits = formatted.len();
+    let padding = if leading_zero {
+        num_length.saturating_sub(new_significant_digits)
+    } else {
+        0
+    };
+
+    if new_negative && result != 0 {
+        format!("-{}{}", "0".repeat(padding), formatted)
+    } else {
+        format!("{}{}", "0".repeat(padding), formatted)
+    }
+}
+
+fn increment_hex_string(num: &str, delta: i64) -> String {
+    let result = if let Ok(val) = u64::from_str_radix(&num, 16) {
+        val.wrapping_add_signed(delta)
+    } else {
+        u64::MAX
+    };
+    if should_use_lowercase(num) {
+        format!("{:0width$x}", result, width = num.len())
+    } else {
+        format!("{:0width$X}", result, width = num.len())
+    }
+}
+
+fn should_use_lowercase(num: &str) -> bool {
+    let mut use_uppercase = false;
+    for ch in num.chars() {
+        if ch.is_ascii_lowercase() {
+            return true;
+        }
+        if ch.is_ascii_uppercase() {
+            use_uppercase = true;
+        }
+    }
+    !use_uppercase
+}
+
+fn increment_binary_string(num: &str, delta: i64) -> String {
+    let result = if let Ok(val) = u64::from_str_radix(&num, 2) {
+        val.wrapping_add_signed(delta)
+    } else {
+        u64::MAX
+    };
+    format!("{:0width$b}", result, width = num.len())
+}
+
+fn increment_octal_string(num: &str, delta: i64) -> String {
+    let result = if let Ok(val) = u64::from_str_radix(&num, 8) {
+        val.wrapping_add_signed(delta)
+    } else {
+        u64::MAX
+    };
+    format!("{:0width$o}", result, width = num.len())
+}
+
+fn find_number(
+    snapshot: &MultiBufferSnapshot,
+    start: Point,
+    allow_octal: bool,
+) -> Option<(Range<Point>, String, u32)> {
+    let mut offset = start.to_offset(snapshot);
+
+    let is_prefix = |ch: char| matches!(ch, '-' | 'b' | 'x') || (allow_octal && ch == 'o');
+    let ch0 = snapshot.chars_at(offset).next();
+    if ch0.as_ref().is_some_and(char::is_ascii_hexdigit) || ch0.is_some_and(is_prefix) {
+        // go backwards to the start of any number the selection is within
+        for ch in snapshot.reversed_chars_at(offset) {
+            if ch.is_ascii_hexdigit() || is_prefix(ch) {
+                offset -= ch.len_utf8();
+                continue;
+            }
+            break;
+        }
+    }
+
+    let mut begin = None;
+    let mut end = None;
+    let mut num = String::new();
+    let mut radix = 10;
+
+    let mut chars = snapshot.chars_at(offset).peekable();
+    // find the next number on the line (may start after the original cursor position)
+    while let Some(ch) = chars.next() {
+        if num == "0" && ch == 'b' && chars.peek().is_some() && chars.peek().unwrap().is_digit(2) {
+            radix = 2;
+            begin = None;
+            num = String::new();
+        }
+        if allow_octal && num == "0" && ch == 'o' && chars.peek().is_some_and(|ch| ch.is_digit(8)) {
+            radix = 8;
+            begin = None;
+            num = String::new();
+        }
+        if num == "0"
+            && ch == 'x'
+            && chars.peek().is_some()
+            && chars.peek().unwrap().is_ascii_hexdigit()
+        {
+            radix = 16;
+            begin = None;
+            num = String::new();
+        }
+
+        if ch.is_digit(radix)
+            || (begin.is_none()
+                && ch == '-'
+                && chars.peek().is_some()
+                && chars.peek().unwrap().is_digit(radix))
+        {
+            if begin.is_none() {
+                begin = Some(offset);
+            }
+            num.push(ch);
+        } else if begin.is_some() {
+            end = Some(offset);
+            break;
+        } else if ch == '\n' {
+            break;
+        }
+        offset += ch.len_utf8();
+    }
+    if let Some(begin) = begin {
+        let end = end.unwrap_or(offset);
+        Some((begin.to_point(snapshot)..end.to_point(snapshot), num, radix))
+    } else {
+        None
+    }
+}
+
+fn find_keyword(
+    snapshot: &MultiBufferSnapshot,
+    start: Point,
+    all_keywords: bool,
+) -> Option<(Range<Point>, String)> {
+    let mut offset = start.to_offset(snapshot);
+
+    let ch0 = snapshot.chars_at(offset).next();
+    if ch0.as_ref().is_some_and(|c| c.is_ascii_alphabetic()) {
+        for ch in snapshot.reversed_chars_at(offset) {
+            if ch.is_ascii_alphabetic() {
+                offset -= ch.len_utf8();
+                continue;
+            }
+            break;
+        }
+    }
+
+    let mut begin = None;
+    let mut end = None;
+    let mut word = String::new();
+
+    let mut chars = snapshot.chars_at(offset);
+
+    while let Some(ch) = chars.next() {
+        if ch.is_ascii_alphabetic() {
+            if begin.is_none() {
+                begin = Some(offset);
+            }
+            word.push(ch);
+        } else if begin.is_some() {
+            end = Some(offset);
+            let word_lower = word.to_lowercase();
+            if keyword_cycle(&word_lower, all_keywords).is_some() {
+                return Some((
+                    begin.unwrap().to_point(snapshot)..end.unwrap().to_point(snapshot),
+                    word,
+                ));
+            }
+            begin = None;
+            end = None;
+            word = String::new();
+        } else if ch == '\n' {
+            break;
+        }
+        offset += ch.len_utf8();
+    }
+    if let Some(begin) = begin {
+        let end = end.unwrap_or(offset);
+        let word_lower = word.to_lowercase();
+        if keyword_cycle(&word_lower, all_keywords).is_some() {
+            return Some((begin.to_point(snapshot)..end.to_point(snapshot), word));
+        }
+    }
+    None
+}
+
+/// The cycle that a lowercase keyword is in, and its index in it. Only booleans are looked for
+/// unless `all_keywords` is set.
+fn keyword_cycle(keyword: &str, all_keywords: bool) -> Option<(&'static [&'static str], usize)> {
+    let cycles = if all_keywords { KEYWORD_CYCLES } else { &[] };
+    BOOLEAN_PAIRS.iter().chain(cycles).find_map(|cycle| {
+        let ix = cycle.iter().position(|word| *word == keyword)?;
+        Some((*cycle, ix))
+    })
+}
+
+/// Moves a keyword `delta` words forward in its cycle, keeping its case. The keywords of a pair,
+/// like `true` and `false`, are toggled whatever the delta.
+fn cycle_keyword(keyword: &str, delta: i64, all_keywords: bool) -> String {
+    let lower = keyword.to_lowercase();
+    let target = match keyword_cycle(&lower, all_keywords) {
+        Some((cycle, ix)) if cycle.len() == 2 => cycle[1 - ix],
+        Some((cycle, ix)) => cycle[(ix as i64 + delta).rem_euclid(cycle.len() as i64) as usize],
+        None => keyword,
+    };
+
+    if keyword.chars().all(|c| c.is_uppercase()) {
+        // Upper case
+        target.to_uppercase()
+    } else if keyword.chars().next().unwrap_or(' ').is_uppercase() {
+        // Title case
+        let mut chars = target.chars();
+        match chars.next() {
+            None => String::new(),
+            Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
+        }
+    } else {
+        target.to_string()
+    }
+}
@@ -2,8 +2,8 @@ use std::{cmp::Ordering, ops::Range, time::Duration};
 
 use collections::HashSet;
 use gpui::{App, AppContext as _, Context, Task, Window};
-use language::language_settings::language_settings;
-use multi_buffer::{IndentGuide, MultiBufferRow, ToPoint};
+use language::language_settings::{IndentGuideActiveScope, language_settings};
+use multi_buffer::{IndentGuide, MultiBufferRow, MultiOrSingleBufferOffsetRange, ToPoint};
 use text::{LineIndent, Point};
 use util::ResultExt;
 
@@ -71,6 +71,11 @@ impl Editor {
     ) -> Option<HashSet<usize>> {
         let selection = self.selections.newest::<Point>(cx);
         let cursor_row = MultiBufferRow(selection.head().row);
+        let active_scope = snapshot
+            .buffer_snapshot
+            .language_settings_at(Point::new(cursor_row.0, 0), cx)
+            .indent_guides
+            .active_scope;
 
         let state = &mut self.active_indent_guides_state;
 
@@ -82,6 +87,7 @@ impl Editor {
                     state.cursor_row,
                     cursor_row,
                     active_indent_range,
+                    active_scope,
                     snapshot,
                 )
             })
@@ -102,7 +108,8 @@ impl Editor {
 
             let snapshot = snapshot.clone();
 
-            let task = cx.background_spawn(resolve_indented_range(snapshot, cursor_row));
+            let task =
+                cx.background_spawn(resolve_indented_range(snapshot, cursor_row, active_scope));
 
             // Try to resolve the indent in a short amount of time, otherwise move it to a background task.
             match cx
@@ -205,7 +212,14 @@ pub fn indent_guides_in_range(
 async fn resolve_indented_range(
     snapshot: DisplaySnapshot,
     buffer_row: MultiBufferRow,
+    active_scope: IndentGuideActiveScope,
 ) -> Option<ActiveIndentedRange> {
+    if active_scope == IndentGuideActiveScope::Syntax {
+        if let Some(range) = enclosing_syntax_scope(&snapshot, buffer_row) {
+            return Some(range);
+        }
+    }
+
     snapshot
         .buffer_snapshot
         .enclosing_indent(buffer_row)
@@ -213,15 +227,61 @@ async fn resolve_indented_range(
         .map(|(row_range, indent)| ActiveIndentedRange { row_range, indent })
 }
 
+/// Finds the innermost syntax node around the start of `buffer_row` whose lines are indented
+/// deeper than its first one.
+fn enclosing_syntax_scope(
+    snapshot: &DisplaySnapshot,
+    buffer_row: MultiBufferRow,
+) -> Option<ActiveIndentedRange> {
+    let buffer = &snapshot.buffer_snapshot;
+    let line_indent = buffer.line_indent_for_row(buffer_row);
+    let column = if line_indent.is_line_empty() {
+        0
+    } else {
+        line_indent.raw_len()
+    };
+    let offset = buffer.point_to_offset(Point::new(buffer_row.0, column));
+
+    let mut range = offset..offset;
+    loop {
+        let MultiOrSingleBufferOffsetRange::Multi(node_range) = buffer.syntax_ancestor(range)?.1
+        else {
+            return None;
+        };
+        let start_row = buffer.offset_to_point(node_range.start).row;
+        let end_row = buffer.offset_to_point(node_range.end).row;
+        if start_row < end_row {
+            let indent = buffer.line_indent_for_row(MultiBufferRow(start_row));
+            let body_indent = buffer
+                .line_indents(MultiBufferRow(start_row + 1), |_| true)
+                .take_while(|(row, _, _)| row.0 <= end_row)
+                .map(|(_, indent, _)| indent)
+                .find(|indent| !indent.is_line_empty());
+            if body_indent.is_some_and(|body_indent| body_indent.raw_len() > indent.raw_len()) {
+                return Some(ActiveIndentedRange {
+                    row_range: MultiBufferRow(start_row)..MultiBufferRow(end_row),
+                    indent,
+                });
+            }
+        }
+        range = node_range;
+    }
+}
+
 fn should_recalculate_indented_range(
     prev_row: MultiBufferRow,
     new_row: MultiBufferRow,
     current_indent_range: &ActiveIndentedRange,
+    active_scope: IndentGuideActiveScope,
     snapshot: &DisplaySnapshot,
 ) -> bool {
     if prev_row.0 == new_row.0 {
         return false;
     }
+    // Nodes don't follow indentation, so any other row may be in a different one.
+    if active_scope == IndentGuideActiveScope::Syntax {
+        return true;
+    }
     if snapshot.buffer_snapshot.is_singleton() {
         if !current_indent_range.row_range.contains(&new_row) {
             return true;
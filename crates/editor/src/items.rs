@@ -1,8 +1,11 @@
 use crate::{
     Anchor, Autoscroll, Editor, EditorEvent, EditorSettings, ExcerptId, ExcerptRange, FormatTarget,
-    MultiBuffer, MultiBufferSnapshot, NavigationData, SearchWithinRange, ToPoint as _,
+    InlayId, MultiBuffer, MultiBufferRow, MultiBufferSnapshot, NavigationData, SearchWithinRange,
+    ToOffset as _, ToPoint as _,
+    data_path::data_path_at,
+    display_map::Inlay,
     editor_settings::SeedQuerySetting,
-    persistence::{DB, SerializedEditor},
+    persistence::{DB, SerializedEditor, deserialize_point_ranges, serialize_point_ranges},
     scroll::ScrollAnchor,
 };
 use anyhow::{Context as _, Result, anyhow};
@@ -11,8 +14,9 @@ use file_icons::FileIcons;
 use futures::future::try_join_all;
 use git::status::GitSummary;
 use gpui::{
-    AnyElement, App, AsyncWindowContext, Context, Entity, EntityId, EventEmitter, IntoElement,
-    ParentElement, Pixels, SharedString, Styled, Task, WeakEntity, Window, point,
+    AnyElement, App, AsyncWindowContext, Context, Entity, EntityId, EventEmitter, HighlightStyle,
+    IntoElement, ParentElement, Pixels, SharedString, StrikethroughStyle, Styled, Task, WeakEntity,
+    Window, point,
 };
 use language::{
     Bias, Buffer, BufferRow, CharKind, DiskState, LocalFile, Point, SelectionGoal,
@@ -29,15 +33,16 @@ use std::{
     any::TypeId,
     borrow::Cow,
     cmp::{self, Ordering},
-    iter,
+    iter, mem,
     ops::Range,
     path::{Path, PathBuf},
     sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
 };
 use text::{BufferId, BufferSnapshot, Selection};
 use theme::{Theme, ThemeSettings};
 use ui::{IconDecorationKind, prelude::*};
-use util::{ResultExt, TryFutureExt, paths::PathExt};
+use util::{ResultExt, TryFutureExt, paths::PathExt, post_inc};
 use workspace::{
     CollaboratorId, ItemId, ItemNavHistory, ToolbarItemLocation, ViewId, Workspace, WorkspaceId,
     item::{FollowableItem, Item, ItemEvent, ProjectItem},
@@ -48,7 +53,7 @@ use workspace::{
     item::{Dedup, ItemSettings, SerializableItem, TabContentParams},
 };
 use workspace::{
-    OpenVisible, Pane, WorkspaceSettings,
+    OpenVisible, Pane, SERIALIZATION_THROTTLE_TIME, WorkspaceSettings,
     item::{BreadcrumbText, FollowEvent, ProjectItemKind},
     searchable::SearchOptions,
 };
@@ -177,6 +182,8 @@ impl FollowableItem for Editor {
         cx: &mut Context<Self>,
     ) {
         self.leader_id = leader_id;
+        self.viewport_synced_with_leader = true;
+        self.leader_viewport = None;
         if self.leader_id.is_some() {
             self.buffer.update(cx, |buffer, cx| {
                 buffer.remove_active_selections(cx);
@@ -243,6 +250,10 @@ impl FollowableItem for Editor {
     fn to_follow_event(event: &EditorEvent) -> Option<workspace::item::FollowEvent> {
         match event {
             EditorEvent::Edited { .. } => Some(FollowEvent::Unfollow),
+            EditorEvent::ScrollPositionChanged {
+                local: true,
+                autoscroll: false,
+            } => Some(FollowEvent::ScrolledAway),
             EditorEvent::SelectionsChanged { local }
             | EditorEvent::ScrollPositionChanged { local, .. } => {
                 if *local {
@@ -372,7 +383,46 @@ impl FollowableItem for Editor {
         };
         drop(buffer);
         self.set_selections_from_remote(vec![selection], None, window, cx);
-        self.request_autoscroll_remotely(Autoscroll::fit(), cx);
+        self.apply_leader_viewport(LeaderViewport::Autoscroll(Autoscroll::fit()), window, cx);
+    }
+
+    fn set_viewport_synced(&mut self, synced: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if self.viewport_synced_with_leader == synced {
+            return;
+        }
+        self.viewport_synced_with_leader = synced;
+        if let Some(viewport) = self.leader_viewport.filter(|_| synced) {
+            self.apply_leader_viewport(viewport, window, cx);
+        }
+    }
+}
+
+/// Where a leader's viewport is, as last received from them.
+#[derive(Clone, Copy)]
+pub(crate) enum LeaderViewport {
+    Scroll(ScrollAnchor),
+    Autoscroll(Autoscroll),
+}
+
+impl Editor {
+    fn apply_leader_viewport(
+        &mut self,
+        viewport: LeaderViewport,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.leader_viewport = Some(viewport);
+        if !self.viewport_synced_with_leader {
+            return;
+        }
+        match viewport {
+            LeaderViewport::Scroll(scroll_anchor) => {
+                self.set_scroll_anchor_remote(scroll_anchor, window, cx)
+            }
+            LeaderViewport::Autoscroll(autoscroll) => {
+                self.request_autoscroll_remotely(autoscroll, cx)
+            }
+        }
     }
 }
 
@@ -486,13 +536,17 @@ async fn update_editor_from_message(
     this.update_in(cx, |editor, window, cx| {
         if !selections.is_empty() || pending_selection.is_some() {
             editor.set_selections_from_remote(selections, pending_selection, window, cx);
-            editor.request_autoscroll_remotely(Autoscroll::newest(), cx);
+            editor.apply_leader_viewport(
+                LeaderViewport::Autoscroll(Autoscroll::newest()),
+                window,
+                cx,
+            );
         } else if let Some(scroll_top_anchor) = scroll_top_anchor {
-            editor.set_scroll_anchor_remote(
-                ScrollAnchor {
+            editor.apply_leader_viewport(
+                LeaderViewport::Scroll(ScrollAnchor {
                     anchor: scroll_top_anchor,
                     offset: point(message.scroll_x, message.scroll_y),
-                },
+                }),
                 window,
                 cx,
             );
@@ -932,8 +986,12 @@ impl Item for Editor {
     fn breadcrumbs(&self, variant: &Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
         let cursor = self.selections.newest_anchor().head();
         let multibuffer = &self.buffer().read(cx);
-        let (buffer_id, symbols) =
-            multibuffer.symbols_containing(cursor, Some(variant.syntax()), cx)?;
+        let snapshot = multibuffer.snapshot(cx);
+        let data_path = data_path_at(&snapshot, cursor.to_offset(&snapshot));
+        let (buffer_id, symbols) = match &data_path {
+            Some((buffer_id, _)) => (*buffer_id, Vec::new()),
+            None => multibuffer.symbols_containing(cursor, Some(variant.syntax()), cx)?,
+        };
         let buffer = multibuffer.buffer(buffer_id)?;
 
         let buffer = buffer.read(cx);
@@ -965,6 +1023,13 @@ impl Item for Editor {
             font: Some(settings.buffer_font.clone()),
         }];
 
+        if let Some((_, path)) = data_path {
+            breadcrumbs.push(BreadcrumbText {
+                text: path,
+                highlights: None,
+                font: Some(settings.buffer_font.clone()),
+            });
+        }
         breadcrumbs.extend(symbols.into_iter().map(|symbol| BreadcrumbText {
             text: symbol.text,
             highlights: Some(symbol.highlight_ranges),
@@ -980,13 +1045,22 @@ impl Item for Editor {
         cx: &mut Context<Self>,
     ) {
         self.workspace = Some((workspace.weak_handle(), workspace.database_id()));
+        self.zen_mode = workspace.zen_mode;
         if let Some(workspace) = &workspace.weak_handle().upgrade() {
-            cx.subscribe(&workspace, |editor, _, event: &workspace::Event, _cx| {
-                if matches!(event, workspace::Event::ModalOpened) {
-                    editor.mouse_context_menu.take();
-                    editor.inline_blame_popover.take();
-                }
-            })
+            cx.subscribe(
+                &workspace,
+                |editor, workspace, event: &workspace::Event, cx| match event {
+                    workspace::Event::ModalOpened => {
+                        editor.mouse_context_menu.take();
+                        editor.inline_blame_popover.take();
+                    }
+                    workspace::Event::ZenModeChanged => {
+                        editor.zen_mode = workspace.read(cx).zen_mode;
+                        cx.notify();
+                    }
+                    _ => {}
+                },
+            )
             .detach();
         }
     }
@@ -1301,13 +1375,98 @@ struct EditorRestorationData {
     entries: HashMap<PathBuf, RestorationData>,
 }
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 pub struct RestorationData {
     pub scroll_position: (BufferRow, gpui::Point<f32>),
     pub folds: Vec<Range<Point>>,
     pub selections: Vec<Range<Point>>,
 }
 
+impl RestorationData {
+    /// Reads the state of a file that was saved in the database, from any pane or workspace.
+    fn persisted(abs_path: &Path) -> Option<Self> {
+        let (top_row, horizontal_offset, vertical_offset, selections, folds) = DB
+            .get_file_restoration_data(abs_path.to_string_lossy().into_owned())
+            .log_err()??;
+        Some(Self {
+            scroll_position: (top_row, point(horizontal_offset, vertical_offset)),
+            folds: folds
+                .as_deref()
+                .map(deserialize_point_ranges)
+                .unwrap_or_default(),
+            selections: selections
+                .as_deref()
+                .map(deserialize_point_ranges)
+                .unwrap_or_default(),
+        })
+    }
+
+    fn apply(&mut self, change: RestorationChange) {
+        match change {
+            RestorationChange::ScrollPosition(top_row, offset) => {
+                self.scroll_position = (top_row, offset)
+            }
+            RestorationChange::Selections(selections) => self.selections = selections,
+            RestorationChange::Folds(folds) => self.folds = folds,
+        }
+    }
+}
+
+/// How many files to keep the state of in the database, or `None` when it's only kept per pane.
+fn max_persisted_files(cx: &App) -> Option<usize> {
+    let max_files = WorkspaceSettings::get(None, cx).restore_on_file_reopen_max_files;
+    (max_files > 0).then_some(max_files)
+}
+
+/// A change to the state of a file that's restored when the file is reopened.
+#[derive(Clone, Debug)]
+pub enum RestorationChange {
+    ScrollPosition(BufferRow, gpui::Point<f32>),
+    Selections(Vec<Range<Point>>),
+    Folds(Vec<Range<Point>>),
+}
+
+/// The changes to the state of a file that are waiting to be saved in the database. Each part of
+/// the state is saved separately, so that the parts that didn't change are kept.
+#[derive(Default)]
+pub(crate) struct PendingRestorationChanges {
+    scroll_position: Option<(BufferRow, gpui::Point<f32>)>,
+    selections: Option<Vec<Range<Point>>>,
+    folds: Option<Vec<Range<Point>>>,
+}
+
+impl PendingRestorationChanges {
+    fn push(&mut self, change: RestorationChange) {
+        match change {
+            RestorationChange::ScrollPosition(top_row, offset) => {
+                self.scroll_position = Some((top_row, offset))
+            }
+            RestorationChange::Selections(selections) => self.selections = Some(selections),
+            RestorationChange::Folds(folds) => self.folds = Some(folds),
+        }
+    }
+
+    async fn save(self, abs_path: PathBuf, max_files: usize) -> Result<()> {
+        let path = abs_path.to_string_lossy().into_owned();
+        let last_used = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as i64);
+        if let Some((top_row, offset)) = self.scroll_position {
+            DB.save_file_scroll_position(path.clone(), top_row, offset.x, offset.y, last_used)
+                .await?;
+        }
+        if let Some(selections) = self.selections {
+            DB.save_file_selections(path.clone(), serialize_point_ranges(&selections), last_used)
+                .await?;
+        }
+        if let Some(folds) = self.folds {
+            DB.save_file_folds(path, serialize_point_ranges(&folds), last_used)
+                .await?;
+        }
+        DB.trim_file_restoration_data(max_files as i64).await
+    }
+}
+
 impl ProjectItem for Editor {
     type Item = Buffer;
 
@@ -1323,36 +1482,23 @@ impl ProjectItem for Editor {
         cx: &mut Context<Self>,
     ) -> Self {
         let mut editor = Self::for_buffer(buffer.clone(), Some(project), window, cx);
-        if let Some((excerpt_id, buffer_id, snapshot)) =
-            editor.buffer().read(cx).snapshot(cx).as_singleton()
-        {
-            if WorkspaceSettings::get(None, cx).restore_on_file_reopen {
-                if let Some(restoration_data) = Self::project_item_kind()
-                    .and_then(|kind| pane.as_ref()?.project_item_restoration_data.get(&kind))
-                    .and_then(|data| data.downcast_ref::<EditorRestorationData>())
-                    .and_then(|data| {
-                        let file = project::File::from_dyn(buffer.read(cx).file())?;
-                        data.entries.get(&file.abs_path(cx))
-                    })
+        if WorkspaceSettings::get(None, cx).restore_on_file_reopen {
+            if let Some(restoration_data) = Self::project_item_kind()
+                .and_then(|kind| pane.as_ref()?.project_item_restoration_data.get(&kind))
+                .and_then(|data| data.downcast_ref::<EditorRestorationData>())
+                .and_then(|data| {
+                    let file = project::File::from_dyn(buffer.read(cx).file())?;
+                    data.entries.get(&file.abs_path(cx)).cloned()
+                })
+            {
+                editor.restore(&restoration_data, window, cx);
+            } else if max_persisted_files(cx).is_some() {
+                if let Some(abs_path) = buffer
+                    .read(cx)
+                    .file()
+                    .and_then(|file| Some(file.as_local()?.abs_path(cx)))
                 {
-                    editor.fold_ranges(
-                        clip_ranges(&restoration_data.folds, &snapshot),
-                        false,
-                        window,
-                        cx,
-                    );
-                    if !restoration_data.selections.is_empty() {
-                        editor.change_selections(None, window, cx, |s| {
-                            s.select_ranges(clip_ranges(&restoration_data.selections, &snapshot));
-                        });
-                    }
-                    let (top_row, offset) = restoration_data.scroll_position;
-                    let anchor = Anchor::in_buffer(
-                        *excerpt_id,
-                        buffer_id,
-                        snapshot.anchor_before(Point::new(top_row, 0)),
-                    );
-                    editor.set_scroll_anchor(ScrollAnchor { anchor, offset }, window, cx);
+                    editor.restore_persisted(abs_path, window, cx);
                 }
             }
         }
@@ -1377,11 +1523,7 @@ fn clip_ranges<'a>(
 impl EventEmitter<SearchEvent> for Editor {}
 
 impl Editor {
-    pub fn update_restoration_data(
-        &self,
-        cx: &mut Context<Self>,
-        write: impl for<'a> FnOnce(&'a mut RestorationData) + 'static,
-    ) {
+    pub fn update_restoration_data(&self, change: RestorationChange, cx: &mut Context<Self>) {
         if self.mode.is_minimap() || !WorkspaceSettings::get(None, cx).restore_on_file_reopen {
             return;
         }
@@ -1389,6 +1531,7 @@ impl Editor {
         let editor = cx.entity();
         cx.defer(move |cx| {
             editor.update(cx, |editor, cx| {
+                editor.persist_restoration_change(change.clone(), cx);
                 let kind = Editor::project_item_kind()?;
                 let pane = editor.workspace()?.read(cx).pane_for(&cx.entity())?;
                 let buffer = editor.buffer().read(cx).as_singleton()?;
@@ -1408,15 +1551,125 @@ impl Editor {
                     };
 
                     let data = data.entries.entry(file_abs_path).or_default();
-                    write(data);
+                    data.apply(change);
                     Some(())
                 })
             });
         });
     }
+
+    fn restore(
+        &mut self,
+        restoration_data: &RestorationData,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let multi_buffer_snapshot = self.buffer().read(cx).snapshot(cx);
+        let Some((excerpt_id, buffer_id, snapshot)) = multi_buffer_snapshot.as_singleton() else {
+            return;
+        };
+        self.fold_ranges(
+            clip_ranges(&restoration_data.folds, snapshot),
+            false,
+            window,
+            cx,
+        );
+        if !restoration_data.selections.is_empty() {
+            self.change_selections(None, window, cx, |s| {
+                s.select_ranges(clip_ranges(&restoration_data.selections, snapshot));
+            });
+        }
+        let (top_row, offset) = restoration_data.scroll_position;
+        let anchor = Anchor::in_buffer(
+            *excerpt_id,
+            buffer_id,
+            snapshot.anchor_before(Point::new(top_row, 0)),
+        );
+        self.set_scroll_anchor(ScrollAnchor { anchor, offset }, window, cx);
+    }
+
+    /// Reads the state of the file from the database in the background, and restores it unless
+    /// the selections or the scroll position were changed in the meantime.
+    fn restore_persisted(
+        &mut self,
+        abs_path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.spawn_in(window, async move |editor, cx| {
+            let Some(restoration_data) = cx
+                .background_spawn(async move { RestorationData::persisted(&abs_path) })
+                .await
+            else {
+                return;
+            };
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    let untouched = editor.selections.count() == 1
+                        && editor.selections.newest::<Point>(cx).range()
+                            == (Point::zero()..Point::zero())
+                        && editor.scroll_position(cx) == gpui::Point::default();
+                    if untouched {
+                        editor.restore(&restoration_data, window, cx);
+                    }
+                })
+                .ok();
+        })
+        .detach();
+    }
+
+    /// Saves the change in the database too, so that the file is restored in other panes and
+    /// after restarting, even when it's not opened in a workspace.
+    fn persist_restoration_change(&mut self, change: RestorationChange, cx: &mut Context<Self>) {
+        let Some(max_files) = max_persisted_files(cx) else {
+            return;
+        };
+        let Some(abs_path) = self
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .and_then(|buffer| Some(buffer.read(cx).file()?.as_local()?.abs_path(cx)))
+        else {
+            return;
+        };
+
+        self.pending_restoration_changes.push(change);
+        self.serialize_restoration_changes = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(SERIALIZATION_THROTTLE_TIME)
+                .await;
+            let Ok(changes) = editor.update(cx, |editor, _| {
+                mem::take(&mut editor.pending_restoration_changes)
+            }) else {
+                return;
+            };
+            cx.background_spawn(changes.save(abs_path, max_files))
+                .await
+                .log_err();
+        });
+    }
+
+    fn clear_replacement_previews(&mut self, cx: &mut Context<Self>) {
+        self.clear_highlights::<ReplacementPreviewHighlights>(cx);
+        if !self.replacement_preview_inlays.is_empty() {
+            let inlays = std::mem::take(&mut self.replacement_preview_inlays);
+            self.splice_inlays(&inlays, Vec::new(), cx);
+        }
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn replacement_preview_texts(&self, cx: &App) -> Vec<String> {
+        self.display_map
+            .read(cx)
+            .current_inlays()
+            .filter(|inlay| matches!(inlay.id, InlayId::ReplacementPreview(_)))
+            .map(|inlay| inlay.text.to_string())
+            .collect()
+    }
 }
 
 pub(crate) enum BufferSearchHighlights {}
+enum ReplacementPreviewHighlights {}
 impl SearchableItem for Editor {
     type Match = Range<Anchor>;
 
@@ -1429,6 +1682,7 @@ impl SearchableItem for Editor {
     }
 
     fn clear_matches(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.clear_replacement_previews(cx);
         if self
             .clear_background_highlights::<BufferSearchHighlights>(cx)
             .is_some()
@@ -1481,6 +1735,18 @@ impl SearchableItem for Editor {
         let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
         if ranges.iter().any(|s| s.start != s.end) {
             self.set_search_within_ranges(&ranges, cx);
+        } else if ranges.len() > 1 {
+            // With several carets and nothing selected, the lines of the carets are searched.
+            let snapshot = self.buffer().read(cx).snapshot(cx);
+            let line_ranges = ranges
+                .iter()
+                .map(|range| {
+                    let row = range.start.to_point(&snapshot).row;
+                    let line_end = Point::new(row, snapshot.line_len(MultiBufferRow(row)));
+                    snapshot.anchor_before(Point::new(row, 0))..snapshot.anchor_after(line_end)
+                })
+                .collect::<Vec<_>>();
+            self.set_search_within_ranges(&line_ranges, cx);
         } else if let Some(previous_search_ranges) = self.previous_search_ranges.take() {
             self.set_search_within_ranges(&previous_search_ranges, cx)
         }
@@ -1564,6 +1830,55 @@ impl SearchableItem for Editor {
             s.select_ranges(matches.iter().cloned())
         });
     }
+    fn preview_replacements(
+        &mut self,
+        matches: &[Self::Match],
+        query: Option<&SearchQuery>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.clear_replacement_previews(cx);
+        let Some(query) = query.filter(|query| query.replacement().is_some()) else {
+            return;
+        };
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut replaced_ranges = Vec::new();
+        let mut inlays = Vec::new();
+        for range in matches {
+            let text = snapshot.text_for_range(range.clone()).collect::<String>();
+            let Some(replacement) = query.replacement_for(&text) else {
+                continue;
+            };
+            if replacement == text {
+                continue;
+            }
+            if !replacement.is_empty() {
+                inlays.push(Inlay::replacement_preview(
+                    post_inc(&mut self.next_inlay_id),
+                    range.end,
+                    replacement.as_ref(),
+                ));
+            }
+            replaced_ranges.push(range.clone());
+        }
+
+        let status = cx.theme().status();
+        self.highlight_text::<ReplacementPreviewHighlights>(
+            replaced_ranges,
+            HighlightStyle {
+                background_color: Some(status.deleted_background),
+                strikethrough: Some(StrikethroughStyle {
+                    thickness: px(1.),
+                    color: Some(status.deleted),
+                }),
+                ..HighlightStyle::default()
+            },
+            cx,
+        );
+        self.replacement_preview_inlays = inlays.iter().map(|inlay| inlay.id).collect();
+        self.splice_inlays(&[], inlays, cx);
+    }
+
     fn replace(
         &mut self,
         identifier: &Self::Match,
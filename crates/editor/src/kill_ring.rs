@@ -0,0 +1,253 @@
+use std::ops::Range;
+
+use collections::VecDeque;
+use gpui::{App, ClipboardEntry, ClipboardItem, Context, EntityId, Global, Window};
+use language::SelectionGoal;
+use multi_buffer::Anchor;
+
+use crate::{
+    DisplayPoint, Editor, HideMouseCursorOrigin,
+    actions::{KillRegion, KillRingCut, KillRingSave, KillRingYank, KillRingYankPop},
+    clipboard_history::{self, ClipboardHistory},
+};
+
+const MAX_KILL_RING_LEN: usize = 120;
+
+/// The text killed in any editor, most recent first.
+#[derive(Default)]
+struct KillRing {
+    entries: VecDeque<ClipboardItem>,
+    /// Where the last kill left the cursor. A kill that starts there, with no edits in between,
+    /// is added to the last entry instead of making a new one, like repeated `kill-line` in Emacs.
+    last_kill: Option<KillLocation>,
+}
+
+impl Global for KillRing {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct KillLocation {
+    editor: EntityId,
+    edit_count: usize,
+    offset: usize,
+}
+
+impl KillRing {
+    fn push(&mut self, item: ClipboardItem, continues_last_kill: bool) {
+        match self.entries.front_mut() {
+            Some(last) if continues_last_kill => {
+                let text = last.text().unwrap_or_default() + &item.text().unwrap_or_default();
+                *last = ClipboardItem::new_string(text);
+            }
+            _ => {
+                self.entries.push_front(item);
+                self.entries.truncate(MAX_KILL_RING_LEN);
+            }
+        }
+    }
+}
+
+/// The entries that `KillRingYankPop` and `PastePrevious` cycle through.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum YankSource {
+    KillRing,
+    ClipboardHistory,
+}
+
+impl YankSource {
+    fn entries(self, cx: &App) -> Option<&VecDeque<ClipboardItem>> {
+        match self {
+            Self::KillRing => Some(&cx.try_global::<KillRing>()?.entries),
+            Self::ClipboardHistory => Some(&cx.try_global::<ClipboardHistory>()?.entries),
+        }
+    }
+}
+
+/// The text inserted by the last yank or paste, which `KillRingYankPop` or `PastePrevious`
+/// replaces with the next older entry if nothing has changed since.
+pub(crate) struct Yank {
+    source: YankSource,
+    /// The position of the inserted entry in the source, if it came from there.
+    index: Option<usize>,
+    ranges: Vec<Range<Anchor>>,
+    heads: Vec<usize>,
+    edit_count: usize,
+}
+
+impl Editor {
+    pub fn kill_ring_cut(&mut self, _: &KillRingCut, window: &mut Window, cx: &mut Context<Self>) {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let location = self.kill_location(cx);
+        self.change_selections(None, window, cx, |s| {
+            s.move_with(|snapshot, sel| {
+                if sel.is_empty() {
+                    let row = sel.end.row();
+                    let line_end = DisplayPoint::new(row, snapshot.line_len(row));
+                    sel.end = if sel.end == line_end && row < snapshot.max_point().row() {
+                        DisplayPoint::new(row + 1, 0)
+                    } else {
+                        line_end
+                    };
+                }
+            });
+        });
+        let item = self.cut_common(window, cx);
+        self.push_kill(item, location, cx);
+    }
+
+    pub fn kill_region(&mut self, _: &KillRegion, window: &mut Window, cx: &mut Context<Self>) {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let location = self.kill_location(cx);
+        let item = self.cut_common(window, cx);
+        clipboard_history::write_to_clipboard(item.clone(), self.is_masked(cx), cx);
+        self.push_kill(item, location, cx);
+        self.selection_mark_mode = false;
+    }
+
+    pub fn kill_ring_save(
+        &mut self,
+        _: &KillRingSave,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let item = self.copy_common(false, cx);
+        clipboard_history::write_to_clipboard(item.clone(), self.is_masked(cx), cx);
+        let kill_ring = cx.default_global::<KillRing>();
+        kill_ring.push(item, false);
+        kill_ring.last_kill = None;
+
+        self.selection_mark_mode = false;
+        self.change_selections(None, window, cx, |s| {
+            s.move_with(|_, sel| sel.collapse_to(sel.head(), SelectionGoal::None));
+        });
+    }
+
+    pub fn kill_ring_yank(
+        &mut self,
+        _: &KillRingYank,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        let Some(item) = cx
+            .try_global::<KillRing>()
+            .and_then(|kill_ring| kill_ring.entries.front().cloned())
+        else {
+            return;
+        };
+        self.yank(item, YankSource::KillRing, Some(0), false, window, cx);
+    }
+
+    pub fn kill_ring_yank_pop(
+        &mut self,
+        _: &KillRingYankPop,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.cycle_yank(YankSource::KillRing, window, cx);
+    }
+
+    pub(crate) fn cycle_yank(
+        &mut self,
+        source: YankSource,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(yank) = self.last_yank.take() else {
+            return;
+        };
+        let heads = self
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .map(|selection| selection.head())
+            .collect::<Vec<_>>();
+        let edit_count = self.buffer.read(cx).snapshot(cx).edit_count();
+        if yank.source != source || yank.edit_count != edit_count || yank.heads != heads {
+            return;
+        }
+
+        let Some((index, item)) = source.entries(cx).and_then(|entries| {
+            let index = yank.index.map_or(0, |index| index + 1) % entries.len().max(1);
+            Some((index, entries.get(index)?.clone()))
+        }) else {
+            return;
+        };
+        self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
+        self.change_selections(None, window, cx, |s| {
+            s.select_anchor_ranges(yank.ranges);
+        });
+        self.yank(item, source, Some(index), false, window, cx);
+    }
+
+    pub(crate) fn yank(
+        &mut self,
+        item: ClipboardItem,
+        source: YankSource,
+        index: Option<usize>,
+        handle_entire_lines: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ClipboardEntry::String(entry)) = item.entries().first() else {
+            return;
+        };
+        self.record_yank(source, index, cx, |editor, cx| {
+            editor.do_paste(
+                entry.text(),
+                entry.metadata_json(),
+                handle_entire_lines,
+                window,
+                cx,
+            )
+        });
+    }
+
+    /// Runs `paste` and remembers the text it inserted, so that it can be replaced by cycling
+    /// through `source`.
+    pub(crate) fn record_yank(
+        &mut self,
+        source: YankSource,
+        index: Option<usize>,
+        cx: &mut Context<Self>,
+        paste: impl FnOnce(&mut Self, &mut Context<Self>),
+    ) {
+        let edits = self.buffer.update(cx, |buffer, _| buffer.subscribe());
+        paste(self, cx);
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let ranges = edits
+            .consume()
+            .into_inner()
+            .into_iter()
+            .map(|edit| snapshot.anchor_before(edit.new.start)..snapshot.anchor_after(edit.new.end))
+            .collect::<Vec<_>>();
+        self.last_yank = (!ranges.is_empty()).then(|| Yank {
+            source,
+            index,
+            ranges,
+            heads: self
+                .selections
+                .all::<usize>(cx)
+                .into_iter()
+                .map(|selection| selection.head())
+                .collect(),
+            edit_count: snapshot.edit_count(),
+        });
+    }
+
+    fn push_kill(&mut self, item: ClipboardItem, started_at: KillLocation, cx: &mut Context<Self>) {
+        let ended_at = self.kill_location(cx);
+        let kill_ring = cx.default_global::<KillRing>();
+        let continues_last_kill = kill_ring.last_kill == Some(started_at);
+        kill_ring.push(item, continues_last_kill);
+        kill_ring.last_kill = Some(ended_at);
+    }
+
+    fn kill_location(&self, cx: &mut Context<Self>) -> KillLocation {
+        KillLocation {
+            editor: cx.entity_id(),
+            edit_count: self.buffer.read(cx).snapshot(cx).edit_count(),
+            offset: self.selections.newest::<usize>(cx).head(),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use gpui::{Context, SharedString};
+use language::{AutoindentMode, CursorShape};
+
+use crate::Editor;
+
+/// How an editor treats input in one mode of a modal input scheme, such as vim's normal mode.
+///
+/// Schemes built on top of the editor, like vim mode, apply their current mode with
+/// [`Editor::set_modal_input`] rather than toggling each option themselves, and restore the
+/// editor's usual behavior with [`ModalInput::default`] when they are turned off.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModalInput {
+    /// The name of the mode, set as `input_mode` in the editor's key context so that keymaps can
+    /// bind keys per mode.
+    pub mode: Option<SharedString>,
+    pub cursor_shape: CursorShape,
+    /// Whether the cursor stays on the last character of a line rather than after it.
+    pub clip_at_line_ends: bool,
+    /// Whether jumping to a search match or symbol places the cursor at its start instead of
+    /// selecting it.
+    pub collapse_matches: bool,
+    /// Whether typed text is inserted into the buffer.
+    pub input_enabled: bool,
+    pub autoindent: bool,
+    /// Whether selections extend to whole lines.
+    pub line_mode: bool,
+}
+
+impl Default for ModalInput {
+    fn default() -> Self {
+        Self {
+            mode: None,
+            cursor_shape: CursorShape::Bar,
+            clip_at_line_ends: false,
+            collapse_matches: false,
+            input_enabled: true,
+            autoindent: true,
+            line_mode: false,
+        }
+    }
+}
+
+impl Editor {
+    /// Switches the editor to the given mode of a modal input scheme.
+    pub fn set_modal_input(&mut self, modal_input: ModalInput, cx: &mut Context<Self>) {
+        self.set_cursor_shape(modal_input.cursor_shape, cx);
+        self.set_clip_at_line_ends(modal_input.clip_at_line_ends, cx);
+        self.collapse_matches = modal_input.collapse_matches;
+        self.input_enabled = modal_input.input_enabled;
+        self.autoindent_mode = modal_input.autoindent.then_some(AutoindentMode::EachLine);
+        self.selections.line_mode = modal_input.line_mode;
+        if self.modal_input_mode != modal_input.mode {
+            self.modal_input_mode = modal_input.mode;
+            cx.notify();
+        }
+    }
+
+    /// The name of the modal input scheme's current mode, if one is active.
+    pub fn modal_input_mode(&self) -> Option<&SharedString> {
+        self.modal_input_mode.as_ref()
+    }
+}
@@ -0,0 +1,167 @@
+use std::ops::Range;
+
+use gpui::{Context, Task, Window};
+use language::{BufferSnapshot, Point};
+use multi_buffer::{Anchor, ExcerptId};
+use theme::ActiveTheme as _;
+use util::ResultExt as _;
+
+use crate::{Editor, scrollbar_markers::ScrollbarMarker};
+
+const SCROLLBAR_MARKER_LAYER: &str = "overlong_lines";
+
+enum OverlongLineHighlight {}
+
+/// The lines longer than their language's preferred line length, for languages that
+/// highlight them.
+#[derive(Default)]
+pub(crate) struct OverlongLines {
+    count: usize,
+    refresh_task: Option<Task<()>>,
+}
+
+/// An excerpt to look for overlong lines in.
+struct ExcerptToCheck {
+    excerpt_id: ExcerptId,
+    buffer: BufferSnapshot,
+    range: Range<Point>,
+    line_length: u32,
+    tab_size: u32,
+}
+
+impl Editor {
+    /// Returns how many lines in this editor are longer than their preferred line length.
+    pub fn overlong_line_count(&self) -> usize {
+        self.overlong_lines.count
+    }
+
+    /// Looks for the overlong lines again, after the buffer or the settings changed.
+    pub(crate) fn refresh_overlong_lines(
+        &mut self,
+        on_buffer_edit: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let excerpts = if self.mode.is_full() {
+            snapshot
+                .excerpts()
+                .filter_map(|(excerpt_id, buffer, range)| {
+                    let settings = buffer.settings_at(range.context.start, cx);
+                    settings.highlight_overlong_lines.then(|| ExcerptToCheck {
+                        excerpt_id,
+                        buffer: buffer.clone(),
+                        range: buffer.summary_for_anchor::<Point>(&range.context.start)
+                            ..buffer.summary_for_anchor::<Point>(&range.context.end),
+                        line_length: settings.preferred_line_length,
+                        tab_size: settings.tab_size.get(),
+                    })
+                })
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+        if excerpts.is_empty() {
+            self.overlong_lines = OverlongLines::default();
+            self.clear_background_highlights::<OverlongLineHighlight>(cx);
+            self.clear_scrollbar_markers(SCROLLBAR_MARKER_LAYER, cx);
+            return;
+        }
+
+        self.overlong_lines.refresh_task = Some(cx.spawn_in(window, async move |editor, cx| {
+            if on_buffer_edit {
+                cx.background_executor()
+                    .timer(crate::SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT)
+                    .await;
+            }
+            let ranges = cx
+                .background_spawn(async move {
+                    excerpts
+                        .iter()
+                        .flat_map(overlong_ranges_in_excerpt)
+                        .collect::<Vec<_>>()
+                })
+                .await;
+            editor
+                .update(cx, |editor, cx| {
+                    editor.overlong_lines.count = ranges.len();
+                    let color = cx.theme().status().warning;
+                    editor.set_scrollbar_markers(
+                        SCROLLBAR_MARKER_LAYER,
+                        ranges.iter().map(|range| ScrollbarMarker {
+                            range: range.clone(),
+                            color,
+                        }),
+                        cx,
+                    );
+                    editor.highlight_background::<OverlongLineHighlight>(
+                        &ranges,
+                        |colors| colors.editor_wrap_guide,
+                        cx,
+                    );
+                })
+                .log_err();
+        }));
+    }
+}
+
+fn overlong_ranges_in_excerpt(excerpt: &ExcerptToCheck) -> impl Iterator<Item = Range<Anchor>> {
+    let buffer = &excerpt.buffer;
+    (excerpt.range.start.row..=excerpt.range.end.row).filter_map(move |row| {
+        let line_len = buffer.line_len(row);
+        // A character takes up at most a tab's worth of columns.
+        if line_len.saturating_mul(excerpt.tab_size) <= excerpt.line_length {
+            return None;
+        }
+        let line_start = Point::new(row, 0);
+        let overlong_start = overlong_start(
+            buffer.chars_at(line_start).take_while(|c| *c != '\n'),
+            excerpt.line_length,
+            excerpt.tab_size,
+        )?;
+        let start = buffer.anchor_after(Point::new(row, overlong_start as u32));
+        let end = buffer.anchor_before(Point::new(row, line_len));
+        Some(Anchor::range_in_buffer(
+            excerpt.excerpt_id,
+            buffer.remote_id(),
+            start..end,
+        ))
+    })
+}
+
+/// Returns the byte offset of the first character on the line that doesn't fit in
+/// `line_length` columns, if there is one.
+fn overlong_start(
+    line: impl Iterator<Item = char>,
+    line_length: u32,
+    tab_size: u32,
+) -> Option<usize> {
+    let mut column = 0;
+    let mut offset = 0;
+    for c in line {
+        if column >= line_length {
+            return Some(offset);
+        }
+        column = if c == '\t' {
+            (column / tab_size + 1) * tab_size
+        } else {
+            column + 1
+        };
+        offset += c.len_utf8();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlong_start() {
+        assert_eq!(overlong_start("abcd".chars(), 4, 4), None);
+        assert_eq!(overlong_start("abcde".chars(), 4, 4), Some(4));
+        assert_eq!(overlong_start("\tab".chars(), 4, 4), Some(1));
+        assert_eq!(overlong_start("a\tb".chars(), 3, 4), Some(2));
+        assert_eq!(overlong_start("éèêë!".chars(), 4, 4), Some(8));
+    }
+}
@@ -0,0 +1,451 @@
+use std::sync::Arc;
+
+use collections::HashMap;
+use gpui::{
+    AnyElement, App, Context, DragMoveEvent, Entity, Focusable, Render, SharedString, WeakEntity,
+    Window, div,
+};
+use language::{Buffer, Point, ToPoint as _};
+use project::{Location, LocationLink};
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+
+use crate::{
+    Editor, GotoDefinitionKind,
+    actions::{PeekDefinition, PeekReferences},
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+    hover_links::{self, HoverLink},
+    scroll::Autoscroll,
+};
+
+const DEFAULT_HEIGHT: u32 = 14;
+const MIN_HEIGHT: u32 = 5;
+const MAX_HEIGHT: u32 = 48;
+
+/// A peek view open beneath the cursor of an editor.
+pub(crate) struct PeekViewState {
+    block_id: CustomBlockId,
+    view: Entity<PeekView>,
+}
+
+enum PeekedLocation {}
+
+/// Shows the targets of a definition or references lookup in an embedded editor, with a list
+/// to switch between them when there are several.
+pub(crate) struct PeekView {
+    host: WeakEntity<Editor>,
+    title: SharedString,
+    locations: Vec<Location>,
+    labels: Vec<(SharedString, Option<SharedString>)>,
+    selected_ix: usize,
+    editor: Entity<Editor>,
+    /// The height of the block containing the view, in lines.
+    height: u32,
+}
+
+#[derive(Clone)]
+struct DraggedPeekView;
+
+impl Render for DraggedPeekView {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        gpui::Empty
+    }
+}
+
+impl Editor {
+    pub fn peek_definition(
+        &mut self,
+        _: &PeekDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(provider) = self.semantics_provider.clone() else {
+            return;
+        };
+        let head = self.selections.newest::<usize>(cx).head();
+        let Some((buffer, head)) = self.buffer.read(cx).text_anchor_for_position(head, cx) else {
+            return;
+        };
+        let Some(definitions) = provider.definitions(&buffer, head, GotoDefinitionKind::Symbol, cx)
+        else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let definitions = definitions.await?;
+            editor.update_in(cx, |editor, window, cx| {
+                let locations = definitions
+                    .into_iter()
+                    .filter(|link| hover_links::exclude_link_to_position(&buffer, &head, link, cx))
+                    .map(|link| link.target)
+                    .collect();
+                editor.show_peek_view("Definition".into(), locations, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn peek_references(
+        &mut self,
+        _: &PeekReferences,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let head = self.selections.newest::<usize>(cx).head();
+        let Some((buffer, head)) = self.buffer.read(cx).text_anchor_for_position(head, cx) else {
+            return;
+        };
+        let references = project.update(cx, |project, cx| project.references(&buffer, head, cx));
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let locations = references.await?;
+            editor.update_in(cx, |editor, window, cx| {
+                editor.show_peek_view("References".into(), locations, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn show_peek_view(
+        &mut self,
+        title: SharedString,
+        locations: Vec<Location>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if locations.is_empty() {
+            return;
+        }
+        self.take_peek_view(window, cx);
+
+        let host = cx.entity().downgrade();
+        let project = self.project.clone();
+        let view = cx.new(|cx| PeekView::new(host, project, title, locations, window, cx));
+        let anchor = self.selections.newest_anchor().head();
+        let block_id = self.insert_blocks(
+            [BlockProperties {
+                style: BlockStyle::Sticky,
+                placement: BlockPlacement::Below(anchor),
+                height: Some(DEFAULT_HEIGHT),
+                render: Arc::new({
+                    let view = view.clone();
+                    move |cx: &mut BlockContext| {
+                        let height = view.read(cx.app).height;
+                        div()
+                            .block_mouse_except_scroll()
+                            .h(cx.line_height * height as f32)
+                            .w_full()
+                            .pl(cx.margins.gutter.full_width())
+                            .child(view.clone())
+                            .into_any_element()
+                    }
+                }),
+                priority: 0,
+                render_in_minimap: false,
+            }],
+            Some(Autoscroll::fit()),
+            cx,
+        )[0];
+        window.focus(&view.read(cx).editor.focus_handle(cx));
+        self.peek_view = Some(PeekViewState { block_id, view });
+        cx.notify();
+    }
+
+    /// Closes the peek view, returning whether one was open.
+    pub(crate) fn take_peek_view(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(peek_view) = self.peek_view.take() else {
+            return false;
+        };
+        if peek_view
+            .view
+            .read(cx)
+            .editor
+            .focus_handle(cx)
+            .contains_focused(window, cx)
+        {
+            window.focus(&self.focus_handle);
+        }
+        self.remove_blocks([peek_view.block_id].into_iter().collect(), None, cx);
+        true
+    }
+
+    #[cfg(test)]
+    pub(crate) fn peeked_editor(&self, cx: &App) -> Option<Entity<Editor>> {
+        Some(self.peek_view.as_ref()?.view.read(cx).editor.clone())
+    }
+
+    fn resize_peek_view(&mut self, height: u32, cx: &mut Context<Self>) {
+        if let Some(peek_view) = &self.peek_view {
+            let heights = HashMap::from_iter([(peek_view.block_id, height)]);
+            self.resize_blocks(heights, None, cx);
+        }
+    }
+}
+
+impl PeekView {
+    fn new(
+        host: WeakEntity<Editor>,
+        project: Option<Entity<project::Project>>,
+        title: SharedString,
+        locations: Vec<Location>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let labels = locations
+            .iter()
+            .map(|location| location_label(location, cx))
+            .collect();
+        let editor = peek_editor(&locations[0], project, window, cx);
+        Self {
+            host,
+            title,
+            locations,
+            labels,
+            selected_ix: 0,
+            editor,
+            height: DEFAULT_HEIGHT,
+        }
+    }
+
+    fn select(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if ix == self.selected_ix {
+            return;
+        }
+        let location = &self.locations[ix];
+        let same_buffer = location.buffer == self.locations[self.selected_ix].buffer;
+        self.selected_ix = ix;
+        if same_buffer {
+            self.editor.update(cx, |editor, cx| {
+                reveal_location(editor, location, window, cx)
+            });
+        } else {
+            let project = self.editor.read(cx).project.clone();
+            self.editor = peek_editor(location, project, window, cx);
+        }
+        window.focus(&self.editor.focus_handle(cx));
+        cx.notify();
+    }
+
+    fn set_height(&mut self, height: u32, cx: &mut Context<Self>) {
+        let height = height.clamp(MIN_HEIGHT, MAX_HEIGHT);
+        if height == self.height {
+            return;
+        }
+        self.height = height;
+        self.host
+            .update(cx, |editor, cx| editor.resize_peek_view(height, cx))
+            .log_err();
+        cx.notify();
+    }
+
+    // Closing the view reads it, so it's deferred until the view is no longer being updated.
+    fn open_selected(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let location = self.locations[self.selected_ix].clone();
+        let host = self.host.clone();
+        window.defer(cx, move |window, cx| {
+            host.update(cx, |editor, cx| {
+                editor.take_peek_view(window, cx);
+                let link = HoverLink::Text(LocationLink {
+                    origin: None,
+                    target: location,
+                });
+                editor
+                    .navigate_to_hover_links(None, vec![link], false, window, cx)
+                    .detach_and_log_err(cx);
+            })
+            .log_err();
+        });
+    }
+
+    fn close(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let host = self.host.clone();
+        window.defer(cx, move |window, cx| {
+            host.update(cx, |editor, cx| editor.take_peek_view(window, cx))
+                .log_err();
+        });
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (label, _) = &self.labels[self.selected_ix];
+        h_flex()
+            .px_2()
+            .py_0p5()
+            .gap_2()
+            .justify_between()
+            .bg(cx.theme().colors().toolbar_background)
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(self.title.clone()).size(LabelSize::Small))
+                    .child(
+                        Label::new(label.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .when(self.locations.len() > 1, |this| {
+                        this.child(
+                            Label::new(format!(
+                                "{} of {}",
+                                self.selected_ix + 1,
+                                self.locations.len()
+                            ))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                        )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("open-peeked-location", IconName::ArrowUpRight)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Open in Editor"))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.open_selected(window, cx)),
+                            ),
+                    )
+                    .child(
+                        IconButton::new("close-peek-view", IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Close"))
+                            .on_click(cx.listener(|this, _, window, cx| this.close(window, cx))),
+                    ),
+            )
+    }
+
+    fn render_results(&self, cx: &mut Context<Self>) -> AnyElement {
+        v_flex()
+            .id("peek-view-results")
+            .w(rems(18.))
+            .h_full()
+            .overflow_y_scroll()
+            .border_l_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().panel_background)
+            .children(
+                self.labels
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, (label, preview))| {
+                        ListItem::new(ix)
+                            .spacing(ListItemSpacing::Dense)
+                            .toggle_state(ix == self.selected_ix)
+                            .child(
+                                v_flex()
+                                    .child(Label::new(label.clone()).size(LabelSize::Small))
+                                    .when_some(preview.clone(), |this, preview| {
+                                        this.child(
+                                            Label::new(preview)
+                                                .size(LabelSize::XSmall)
+                                                .color(Color::Muted)
+                                                .single_line(),
+                                        )
+                                    }),
+                            )
+                            .on_click(
+                                cx.listener(move |this, _, window, cx| this.select(ix, window, cx)),
+                            )
+                    }),
+            )
+            .into_any_element()
+    }
+}
+
+impl Render for PeekView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let line_height = window.line_height();
+        v_flex()
+            .size_full()
+            .border_y_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().editor_background)
+            .on_drag_move(cx.listener(
+                move |this, event: &DragMoveEvent<DraggedPeekView>, _, cx| {
+                    let height = (event.event.position.y - event.bounds.top()) / line_height;
+                    this.set_height(height.round().max(0.) as u32, cx);
+                },
+            ))
+            .child(self.render_header(cx))
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(div().flex_1().h_full().child(self.editor.clone()))
+                    .when(self.locations.len() > 1, |this| {
+                        this.child(self.render_results(cx))
+                    }),
+            )
+            .child(
+                div()
+                    .id("peek-view-resize-handle")
+                    .w_full()
+                    .h(px(4.))
+                    .cursor_row_resize()
+                    .on_drag(DraggedPeekView, |dragged, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| dragged.clone())
+                    }),
+            )
+    }
+}
+
+fn peek_editor(
+    location: &Location,
+    project: Option<Entity<project::Project>>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<Editor> {
+    cx.new(|cx| {
+        let mut editor = Editor::for_buffer(location.buffer.clone(), project, window, cx);
+        reveal_location(&mut editor, location, window, cx);
+        editor
+    })
+}
+
+fn reveal_location(
+    editor: &mut Editor,
+    location: &Location,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let buffer = location.buffer.read(cx).snapshot();
+    let range = location.range.start.to_point(&buffer)..location.range.end.to_point(&buffer);
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let highlight = snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end);
+    editor.highlight_background::<PeekedLocation>(
+        &[highlight],
+        |colors| colors.editor_document_highlight_read_background,
+        cx,
+    );
+    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+        s.select_ranges([range.start..range.start])
+    });
+}
+
+fn location_label(location: &Location, cx: &App) -> (SharedString, Option<SharedString>) {
+    let buffer = location.buffer.read(cx);
+    let start = location.range.start.to_point(&buffer.snapshot());
+    let name = buffer
+        .file()
+        .map(|file| file.file_name(cx).to_string_lossy().to_string())
+        .unwrap_or_else(|| "untitled".to_string());
+    (
+        format!("{name}:{}", start.row + 1).into(),
+        line_preview(buffer, start),
+    )
+}
+
+fn line_preview(buffer: &Buffer, start: Point) -> Option<SharedString> {
+    let line_end = Point::new(start.row, buffer.line_len(start.row));
+    let line = buffer
+        .text_for_range(Point::new(start.row, 0)..line_end)
+        .collect::<String>();
+    let line = line.trim();
+    (!line.is_empty()).then(|| SharedString::from(line.to_string()))
+}
@@ -3,7 +3,8 @@ use db::sqlez::bindable::{Bind, Column, StaticColumnCount};
 use db::sqlez::statement::Statement;
 use fs::MTime;
 use itertools::Itertools as _;
-use std::path::PathBuf;
+use std::{ops::Range, path::PathBuf};
+use text::Point;
 
 use db::sqlez_macros::sql;
 use db::{define_connection, query};
@@ -188,6 +189,18 @@ define_connection!(
                 ON DELETE CASCADE
             ) STRICT;
         ),
+        sql! (
+            CREATE TABLE file_restoration_data (
+                path TEXT NOT NULL,
+                scroll_top_row INTEGER NOT NULL DEFAULT 0,
+                scroll_horizontal_offset REAL NOT NULL DEFAULT 0,
+                scroll_vertical_offset REAL NOT NULL DEFAULT 0,
+                selections TEXT,
+                folds TEXT,
+                last_used INTEGER NOT NULL,
+                PRIMARY KEY(path)
+            ) STRICT;
+        ),
     ];
 );
 
@@ -322,6 +335,79 @@ VALUES {placeholders};
         Ok(())
     }
 
+    // Returns the scroll top row and offset, the selections, and the folds of a file, which are
+    // kept for the files opened in any workspace, or in none.
+    query! {
+        pub fn get_file_restoration_data(
+            path: String
+        ) -> Result<Option<(u32, f32, f32, Option<String>, Option<String>)>> {
+            SELECT
+                scroll_top_row, scroll_horizontal_offset, scroll_vertical_offset, selections, folds
+            FROM file_restoration_data
+            WHERE path = ?
+        }
+    }
+
+    query! {
+        pub async fn save_file_scroll_position(
+            path: String,
+            top_row: u32,
+            horizontal_offset: f32,
+            vertical_offset: f32,
+            last_used: i64
+        ) -> Result<()> {
+            INSERT INTO file_restoration_data
+                (path, scroll_top_row, scroll_horizontal_offset, scroll_vertical_offset, last_used)
+            VALUES
+                (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(path) DO UPDATE SET
+                scroll_top_row = ?2,
+                scroll_horizontal_offset = ?3,
+                scroll_vertical_offset = ?4,
+                last_used = ?5
+        }
+    }
+
+    query! {
+        pub async fn save_file_selections(
+            path: String,
+            selections: String,
+            last_used: i64
+        ) -> Result<()> {
+            INSERT INTO file_restoration_data (path, selections, last_used)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(path) DO UPDATE SET
+                selections = ?2,
+                last_used = ?3
+        }
+    }
+
+    query! {
+        pub async fn save_file_folds(
+            path: String,
+            folds: String,
+            last_used: i64
+        ) -> Result<()> {
+            INSERT INTO file_restoration_data (path, folds, last_used)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(path) DO UPDATE SET
+                folds = ?2,
+                last_used = ?3
+        }
+    }
+
+    // Forgets the files that were used the longest time ago, beyond the given number of files.
+    query! {
+        pub async fn trim_file_restoration_data(max_files: i64) -> Result<()> {
+            DELETE FROM file_restoration_data
+            WHERE path NOT IN (
+                SELECT path FROM file_restoration_data
+                ORDER BY last_used DESC
+                LIMIT ?
+            )
+        }
+    }
+
     pub async fn save_editor_folds(
         &self,
         editor_id: ItemId,
@@ -375,10 +461,72 @@ VALUES {placeholders};
     }
 }
 
+/// Stores point ranges as a JSON array of `[start_row, start_column, end_row, end_column]`.
+pub(crate) fn serialize_point_ranges(ranges: &[Range<Point>]) -> String {
+    let ranges = ranges
+        .iter()
+        .map(|range| {
+            [
+                range.start.row,
+                range.start.column,
+                range.end.row,
+                range.end.column,
+            ]
+        })
+        .collect::<Vec<_>>();
+    serde_json::to_string(&ranges).unwrap_or_default()
+}
+
+pub(crate) fn deserialize_point_ranges(text: &str) -> Vec<Range<Point>> {
+    serde_json::from_str::<Vec<[u32; 4]>>(text)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|[start_row, start_column, end_row, end_column]| {
+            Point::new(start_row, start_column)..Point::new(end_row, end_column)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[gpui::test]
+    async fn test_file_restoration_data() {
+        let path = "/root/restored.rs".to_string();
+        assert_eq!(DB.get_file_restoration_data(path.clone()).unwrap(), None);
+
+        let selections = vec![Point::new(1, 2)..Point::new(3, 4)];
+        let folds = vec![Point::new(5, 0)..Point::new(9, 1)];
+        DB.save_file_scroll_position(path.clone(), 7, 0.0, 0.5, 1)
+            .await
+            .unwrap();
+        DB.save_file_selections(path.clone(), serialize_point_ranges(&selections), 2)
+            .await
+            .unwrap();
+        DB.save_file_folds(path.clone(), serialize_point_ranges(&folds), 3)
+            .await
+            .unwrap();
+
+        let (top_row, horizontal_offset, vertical_offset, have_selections, have_folds) =
+            DB.get_file_restoration_data(path.clone()).unwrap().unwrap();
+        assert_eq!((top_row, horizontal_offset, vertical_offset), (7, 0.0, 0.5));
+        assert_eq!(
+            deserialize_point_ranges(&have_selections.unwrap()),
+            selections
+        );
+        assert_eq!(deserialize_point_ranges(&have_folds.unwrap()), folds);
+
+        // Only the most recently used files are kept.
+        let newer_path = "/root/newer.rs".to_string();
+        DB.save_file_scroll_position(newer_path.clone(), 0, 0.0, 0.0, 4)
+            .await
+            .unwrap();
+        DB.trim_file_restoration_data(1).await.unwrap();
+        assert_eq!(DB.get_file_restoration_data(path).unwrap(), None);
+        assert!(DB.get_file_restoration_data(newer_path).unwrap().is_some());
+    }
+
     #[gpui::test]
     async fn test_save_and_get_serialized_editor() {
         let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
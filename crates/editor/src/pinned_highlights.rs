@@ -0,0 +1,322 @@
+use std::{ops::Range, sync::Arc};
+
+use gpui::{Context, Hsla, Task, Window};
+use language::{CharKind, Point};
+use multi_buffer::{Anchor, ToOffset as _};
+use theme::{ActiveTheme as _, ThemeColors};
+use util::ResultExt as _;
+
+use crate::{
+    Editor, EditorMode,
+    actions::{
+        ClearPinnedHighlights, GoToNextPinnedOccurrence, GoToPreviousPinnedOccurrence,
+        PinSymbolHighlight,
+    },
+    scroll::Autoscroll,
+    scrollbar_markers::ScrollbarMarker,
+};
+
+const SCROLLBAR_MARKER_LAYER: &str = "pinned_highlights";
+
+/// The colors of the pins, in the order they're given out.
+const PIN_COLORS: [fn(&ThemeColors) -> Hsla; 6] = [
+    |colors| colors.terminal_ansi_yellow,
+    |colors| colors.terminal_ansi_cyan,
+    |colors| colors.terminal_ansi_magenta,
+    |colors| colors.terminal_ansi_green,
+    |colors| colors.terminal_ansi_blue,
+    |colors| colors.terminal_ansi_red,
+];
+
+enum PinnedHighlight<const SLOT: usize> {}
+
+/// The symbols whose occurrences stay highlighted until they're unpinned, each in its own color.
+#[derive(Default)]
+pub(crate) struct PinnedHighlights {
+    /// Oldest first.
+    pins: Vec<Pin>,
+    refresh_task: Option<Task<()>>,
+}
+
+struct Pin {
+    text: String,
+    /// Which of the `PIN_COLORS` the occurrences are highlighted with.
+    slot: usize,
+    occurrences: Arc<[Range<Anchor>]>,
+}
+
+impl Editor {
+    /// Pins the symbol under the cursor, or the selected text, or unpins it when it already is.
+    pub fn pin_symbol_highlight(
+        &mut self,
+        _: &PinSymbolHighlight,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if matches!(self.mode, EditorMode::SingleLine { .. }) {
+            return;
+        }
+        let Some(text) = self.text_to_pin(cx) else {
+            return;
+        };
+
+        let pins = &mut self.pinned_highlights.pins;
+        if let Some(ix) = pins.iter().position(|pin| pin.text == text) {
+            let pin = pins.remove(ix);
+            set_slot_highlights(self, pin.slot, &[], cx);
+        } else {
+            let slot = (0..PIN_COLORS.len())
+                .find(|slot| pins.iter().all(|pin| pin.slot != *slot))
+                .unwrap_or_else(|| pins.remove(0).slot);
+            pins.push(Pin {
+                text,
+                slot,
+                occurrences: Arc::default(),
+            });
+        }
+        self.refresh_pinned_highlights(false, window, cx);
+    }
+
+    pub fn clear_pinned_highlights(
+        &mut self,
+        _: &ClearPinnedHighlights,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pinned_highlights.refresh_task = None;
+        for pin in std::mem::take(&mut self.pinned_highlights.pins) {
+            set_slot_highlights(self, pin.slot, &[], cx);
+        }
+        self.clear_scrollbar_markers(SCROLLBAR_MARKER_LAYER, cx);
+    }
+
+    pub fn go_to_next_pinned_occurrence(
+        &mut self,
+        _: &GoToNextPinnedOccurrence,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_pinned_occurrence(true, window, cx);
+    }
+
+    pub fn go_to_previous_pinned_occurrence(
+        &mut self,
+        _: &GoToPreviousPinnedOccurrence,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_pinned_occurrence(false, window, cx);
+    }
+
+    /// Moves to the closest occurrence of any pinned symbol, wrapping around the end of the buffer.
+    fn go_to_pinned_occurrence(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut offsets = self
+            .pinned_highlights
+            .pins
+            .iter()
+            .flat_map(|pin| pin.occurrences.iter())
+            .map(|range| range.start.to_offset(&snapshot))
+            .collect::<Vec<_>>();
+        if offsets.is_empty() {
+            return;
+        }
+        offsets.sort_unstable();
+        offsets.dedup();
+
+        let cursor = self.selections.newest::<usize>(cx).head();
+        let target = if forward {
+            offsets
+                .iter()
+                .find(|offset| **offset > cursor)
+                .or(offsets.first())
+        } else {
+            offsets
+                .iter()
+                .rev()
+                .find(|offset| **offset < cursor)
+                .or(offsets.last())
+        };
+        if let Some(&target) = target {
+            self.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                s.select_ranges([target..target])
+            });
+        }
+    }
+
+    /// Searches again for the occurrences of every pin, after the buffer changed or a pin was
+    /// added.
+    pub(crate) fn refresh_pinned_highlights(
+        &mut self,
+        on_buffer_edit: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.pinned_highlights.pins.is_empty() {
+            self.pinned_highlights.refresh_task = None;
+            self.clear_scrollbar_markers(SCROLLBAR_MARKER_LAYER, cx);
+            return;
+        }
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let texts = self
+            .pinned_highlights
+            .pins
+            .iter()
+            .map(|pin| pin.text.clone())
+            .collect::<Vec<_>>();
+        self.pinned_highlights.refresh_task = Some(cx.spawn_in(window, async move |editor, cx| {
+            if on_buffer_edit {
+                cx.background_executor()
+                    .timer(crate::SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT)
+                    .await;
+            }
+            let search = cx.background_spawn(async move {
+                let mut occurrences = Vec::with_capacity(texts.len());
+                for text in texts {
+                    let ranges = find_occurrences(&snapshot, &text).await;
+                    occurrences.push((text, ranges));
+                }
+                occurrences
+            });
+            let occurrences = search.await;
+            editor
+                .update(cx, |editor, cx| {
+                    for (text, ranges) in occurrences {
+                        if let Some(pin) = editor
+                            .pinned_highlights
+                            .pins
+                            .iter_mut()
+                            .find(|pin| pin.text == text)
+                        {
+                            pin.occurrences = ranges.into();
+                        }
+                    }
+                    editor.apply_pinned_highlights(cx);
+                })
+                .log_err();
+        }));
+    }
+
+    fn apply_pinned_highlights(&mut self, cx: &mut Context<Self>) {
+        let colors = cx.theme().colors();
+        let mut markers = Vec::new();
+        let mut highlights = Vec::new();
+        for pin in &self.pinned_highlights.pins {
+            let color = PIN_COLORS[pin.slot](colors);
+            markers.extend(pin.occurrences.iter().map(|range| ScrollbarMarker {
+                range: range.clone(),
+                color,
+            }));
+            highlights.push((pin.slot, pin.occurrences.clone()));
+        }
+        for (slot, occurrences) in highlights {
+            set_slot_highlights(self, slot, &occurrences, cx);
+        }
+        self.set_scrollbar_markers(SCROLLBAR_MARKER_LAYER, markers, cx);
+    }
+
+    fn text_to_pin(&self, cx: &mut Context<Self>) -> Option<String> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let selection = self.selections.newest::<Point>(cx);
+        let range = if selection.is_empty() {
+            let (range, kind) = snapshot.surrounding_word(selection.head(), false);
+            if kind != Some(CharKind::Word) {
+                return None;
+            }
+            range
+        } else if selection.start.row == selection.end.row {
+            selection.start.to_offset(&snapshot)..selection.end.to_offset(&snapshot)
+        } else {
+            return None;
+        };
+        let text = snapshot.text_for_range(range).collect::<String>();
+        (!text.trim().is_empty()).then_some(text)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn pinned_highlight_texts(&self) -> Vec<(String, usize)> {
+        self.pinned_highlights
+            .pins
+            .iter()
+            .map(|pin| (pin.text.clone(), pin.occurrences.len()))
+            .collect()
+    }
+}
+
+async fn find_occurrences(
+    snapshot: &multi_buffer::MultiBufferSnapshot,
+    text: &str,
+) -> Vec<Range<Anchor>> {
+    let is_word = text.chars().all(|c| c.is_alphanumeric() || c == '_');
+    let Ok(query) = project::search::SearchQuery::text(
+        text,
+        is_word,
+        true,
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    ) else {
+        return Vec::new();
+    };
+
+    let mut occurrences = Vec::new();
+    for (buffer_snapshot, search_range, excerpt_id) in
+        snapshot.range_to_buffer_ranges(Point::zero()..snapshot.max_point())
+    {
+        for match_range in query
+            .search(&buffer_snapshot, Some(search_range.clone()))
+            .await
+        {
+            let start = buffer_snapshot.anchor_after(search_range.start + match_range.start);
+            let end = buffer_snapshot.anchor_before(search_range.start + match_range.end);
+            occurrences.push(Anchor::range_in_buffer(
+                excerpt_id,
+                buffer_snapshot.remote_id(),
+                start..end,
+            ));
+        }
+    }
+    occurrences
+}
+
+/// Highlights the occurrences of the pin using `slot`, or clears them when there are none.
+fn set_slot_highlights(
+    editor: &mut Editor,
+    slot: usize,
+    occurrences: &[Range<Anchor>],
+    cx: &mut Context<Editor>,
+) {
+    fn set<const SLOT: usize>(
+        editor: &mut Editor,
+        occurrences: &[Range<Anchor>],
+        cx: &mut Context<Editor>,
+    ) {
+        if occurrences.is_empty() {
+            editor.clear_background_highlights::<PinnedHighlight<SLOT>>(cx);
+        } else {
+            editor.highlight_background::<PinnedHighlight<SLOT>>(
+                occurrences,
+                |colors| PIN_COLORS[SLOT](colors).opacity(0.3),
+                cx,
+            );
+        }
+    }
+
+    match slot {
+        0 => set::<0>(editor, occurrences, cx),
+        1 => set::<1>(editor, occurrences, cx),
+        2 => set::<2>(editor, occurrences, cx),
+        3 => set::<3>(editor, occurrences, cx),
+        4 => set::<4>(editor, occurrences, cx),
+        5 => set::<5>(editor, occurrences, cx),
+        _ => {}
+    }
+}
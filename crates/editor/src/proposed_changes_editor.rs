@@ -7,7 +7,7 @@ use language::{Buffer, BufferEvent, Capability};
 use multi_buffer::{ExcerptRange, MultiBuffer};
 use project::Project;
 use smol::stream::StreamExt;
-use std::{any::TypeId, ops::Range, rc::Rc, time::Duration};
+use std::{any::TypeId, ops::Range, rc::Rc, sync::Arc, time::Duration};
 use text::ToOffset;
 use ui::{ButtonLike, KeyBinding, prelude::*};
 use workspace::{
@@ -523,6 +523,17 @@ impl SemanticsProvider for BranchBufferSemanticsProvider {
         None
     }
 
+    fn rename_edits(
+        &self,
+        _: &Entity<Buffer>,
+        _: text::Anchor,
+        _: String,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<(Entity<Buffer>, Vec<(Range<text::Anchor>, Arc<str>)>)>>>>
+    {
+        None
+    }
+
     fn pull_diagnostics_for_buffer(
         &self,
         _: Entity<Buffer>,
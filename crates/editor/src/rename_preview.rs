@@ -0,0 +1,490 @@
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Result;
+use collections::HashSet;
+use gpui::{
+    AnyElement, App, Context, Entity, HighlightStyle, StrikethroughStyle, Task, WeakEntity, Window,
+};
+use language::{Buffer, Capability, ToPoint as _};
+use multi_buffer::{Anchor, MultiBuffer, PathKey};
+use text::BufferId;
+use ui::{Checkbox, ToggleState, prelude::*};
+use util::{ResultExt as _, post_inc};
+use workspace::{SaveIntent, Workspace};
+
+use crate::{
+    DEFAULT_MULTIBUFFER_CONTEXT, Editor, InlayId,
+    actions::{
+        ApplyRenamePreview, ConfirmRenameWithPreview, DiscardRenamePreview, ToggleRenamePreviewEdit,
+    },
+    display_map::{
+        BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId, Inlay,
+    },
+};
+
+enum RenamePreviewHighlights {}
+
+/// The edits of a rename, shown in a multibuffer before they're applied so that some of them
+/// can be left out.
+pub(crate) struct RenamePreview {
+    edits: Vec<RenamePreviewEdit>,
+    /// The files the edits are made in, in the order they're shown.
+    files: Vec<RenamePreviewFile>,
+    blocks: HashSet<CustomBlockId>,
+    inlays: Vec<InlayId>,
+}
+
+struct RenamePreviewEdit {
+    buffer_id: BufferId,
+    range: Range<Anchor>,
+    new_text: Arc<str>,
+    included: bool,
+}
+
+struct RenamePreviewFile {
+    buffer_id: BufferId,
+    path: SharedString,
+    /// Where the first excerpt of the file starts, which is where its checkbox is shown.
+    start: Anchor,
+}
+
+/// What the blocks of a rename preview show, captured when they're inserted.
+#[derive(Clone, Copy)]
+struct EditCounts {
+    included: usize,
+    total: usize,
+}
+
+impl Editor {
+    /// Asks for the edits of the pending rename like `confirm_rename`, but opens them in a
+    /// preview to choose which of them to apply, instead of applying them all.
+    pub fn confirm_rename_with_preview(
+        &mut self,
+        _: &ConfirmRenameWithPreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let (rename_edits, workspace, title) =
+            self.perform_pending_rename(window, cx, |provider, buffer, position, new_name, cx| {
+                provider.rename_edits(buffer, position, new_name, cx)
+            })?;
+        Some(cx.spawn_in(window, async move |_, cx| {
+            let buffer_edits = rename_edits.await?;
+            cx.update(|window, cx| {
+                Self::open_rename_preview(workspace, buffer_edits, title, window, cx)
+            })?
+        }))
+    }
+
+    /// Opens a multibuffer showing each of the edits of a rename as a change that can be
+    /// applied.
+    fn open_rename_preview(
+        workspace: WeakEntity<Workspace>,
+        mut buffer_edits: Vec<(Entity<Buffer>, Vec<(Range<text::Anchor>, Arc<str>)>)>,
+        title: String,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<()> {
+        buffer_edits.retain(|(_, edits)| !edits.is_empty());
+        if buffer_edits.is_empty() {
+            return Ok(());
+        }
+        buffer_edits
+            .sort_unstable_by_key(|(buffer, _)| buffer.read(cx).file().map(|f| f.path().clone()));
+
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(Capability::ReadWrite).with_title(title);
+            for (buffer, edits) in &buffer_edits {
+                let snapshot = buffer.read(cx).snapshot();
+                let ranges = edits
+                    .iter()
+                    .map(|(range, _)| {
+                        range.start.to_point(&snapshot)..range.end.to_point(&snapshot)
+                    })
+                    .collect::<Vec<_>>();
+                multibuffer.set_excerpts_for_path(
+                    PathKey::for_buffer(buffer, cx),
+                    buffer.clone(),
+                    ranges,
+                    DEFAULT_MULTIBUFFER_CONTEXT,
+                    cx,
+                );
+            }
+            multibuffer
+        });
+
+        let mut edits = Vec::new();
+        let mut files = Vec::new();
+        for (buffer, buffer_edits) in buffer_edits {
+            let buffer = buffer.read(cx);
+            let buffer_id = buffer.remote_id();
+            let snapshot = buffer.snapshot();
+            let excerpts = multibuffer.read(cx).excerpts_for_buffer(buffer_id, cx);
+            let Some((first_excerpt_id, first_excerpt)) = excerpts.first() else {
+                continue;
+            };
+            files.push(RenamePreviewFile {
+                buffer_id,
+                path: buffer
+                    .file()
+                    .map(|file| file.path().to_string_lossy().to_string())
+                    .unwrap_or_else(|| "untitled".to_string())
+                    .into(),
+                start: Anchor::in_buffer(*first_excerpt_id, buffer_id, first_excerpt.context.start),
+            });
+            for (range, new_text) in buffer_edits {
+                let excerpt_id = excerpts.iter().find_map(|(excerpt_id, excerpt)| {
+                    (excerpt.context.start.cmp(&range.start, &snapshot).is_le()
+                        && excerpt.context.end.cmp(&range.end, &snapshot).is_ge())
+                    .then_some(*excerpt_id)
+                });
+                if let Some(excerpt_id) = excerpt_id {
+                    edits.push(RenamePreviewEdit {
+                        buffer_id,
+                        range: Anchor::range_in_buffer(excerpt_id, buffer_id, range),
+                        new_text,
+                        included: true,
+                    });
+                }
+            }
+        }
+
+        workspace.update(cx, |workspace, cx| {
+            let project = workspace.project().clone();
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+                editor.set_read_only(true);
+                editor.rename_preview = Some(RenamePreview {
+                    edits,
+                    files,
+                    blocks: HashSet::default(),
+                    inlays: Vec::new(),
+                });
+                editor.refresh_rename_preview(cx);
+                editor
+            });
+            workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+        })
+    }
+
+    /// Leaves out the edits touching the selections from the rename, or puts them back when
+    /// they all were.
+    pub fn toggle_rename_preview_edit(
+        &mut self,
+        _: &ToggleRenamePreviewEdit,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let selections = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let Some(preview) = self.rename_preview.as_mut() else {
+            return;
+        };
+        let touched = preview
+            .edits
+            .iter()
+            .enumerate()
+            .filter(|(_, edit)| {
+                selections.iter().any(|selection| {
+                    edit.range.start.cmp(&selection.end, &snapshot).is_le()
+                        && edit.range.end.cmp(&selection.start, &snapshot).is_ge()
+                })
+            })
+            .map(|(ix, _)| ix)
+            .collect::<Vec<_>>();
+        if touched.is_empty() {
+            return;
+        }
+        let include = touched.iter().all(|ix| !preview.edits[*ix].included);
+        for ix in touched {
+            preview.edits[ix].included = include;
+        }
+        self.refresh_rename_preview(cx);
+    }
+
+    fn toggle_rename_preview_file(&mut self, buffer_id: BufferId, cx: &mut Context<Self>) {
+        let Some(preview) = self.rename_preview.as_mut() else {
+            return;
+        };
+        let include = preview
+            .edits
+            .iter()
+            .filter(|edit| edit.buffer_id == buffer_id)
+            .all(|edit| !edit.included);
+        for edit in &mut preview.edits {
+            if edit.buffer_id == buffer_id {
+                edit.included = include;
+            }
+        }
+        self.refresh_rename_preview(cx);
+    }
+
+    /// Applies the edits of the previewed rename that weren't left out.
+    pub fn apply_rename_preview(
+        &mut self,
+        _: &ApplyRenamePreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(preview) = self.rename_preview.take() else {
+            return;
+        };
+        self.clear_rename_preview(preview.blocks, preview.inlays, cx);
+        self.set_read_only(false);
+        let edits = preview
+            .edits
+            .into_iter()
+            .filter(|edit| edit.included)
+            .map(|edit| (edit.range, edit.new_text))
+            .collect::<Vec<_>>();
+        if !edits.is_empty() {
+            self.transact(window, cx, |editor, _, cx| editor.edit(edits, cx));
+        }
+        cx.notify();
+    }
+
+    /// Closes the preview of a rename without applying any of its edits.
+    pub fn discard_rename_preview(
+        &mut self,
+        _: &DiscardRenamePreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(preview) = self.rename_preview.take() else {
+            return;
+        };
+        self.clear_rename_preview(preview.blocks, preview.inlays, cx);
+        let editor_id = cx.entity_id();
+        let Some(pane) = self
+            .workspace()
+            .and_then(|workspace| workspace.read(cx).pane_for(&cx.entity()))
+        else {
+            return;
+        };
+        window.defer(cx, move |window, cx| {
+            pane.update(cx, |pane, cx| {
+                pane.close_item_by_id(editor_id, SaveIntent::Skip, window, cx)
+            })
+            .detach_and_log_err(cx);
+        });
+    }
+
+    fn clear_rename_preview(
+        &mut self,
+        blocks: HashSet<CustomBlockId>,
+        inlays: Vec<InlayId>,
+        cx: &mut Context<Self>,
+    ) {
+        self.remove_blocks(blocks, None, cx);
+        self.splice_inlays(&inlays, Vec::new(), cx);
+        self.clear_highlights::<RenamePreviewHighlights>(cx);
+    }
+
+    /// Shows the edits that are included in the rename as deletions of the old text followed by
+    /// the new one, along with the blocks to include or leave out files and apply the rename.
+    fn refresh_rename_preview(&mut self, cx: &mut Context<Self>) {
+        let Some(preview) = self.rename_preview.as_mut() else {
+            return;
+        };
+        let old_blocks = std::mem::take(&mut preview.blocks);
+        let old_inlays = std::mem::take(&mut preview.inlays);
+
+        let mut highlighted_ranges = Vec::new();
+        let mut inlays = Vec::new();
+        for edit in preview.edits.iter().filter(|edit| edit.included) {
+            highlighted_ranges.push(edit.range.clone());
+            if !edit.new_text.is_empty() {
+                inlays.push(Inlay::replacement_preview(
+                    post_inc(&mut self.next_inlay_id),
+                    edit.range.end,
+                    edit.new_text.as_ref(),
+                ));
+            }
+        }
+        preview.inlays = inlays.iter().map(|inlay| inlay.id).collect();
+
+        let counts = |buffer_id: Option<BufferId>| EditCounts {
+            included: preview
+                .edits
+                .iter()
+                .filter(|edit| edit.included && buffer_id.map_or(true, |id| edit.buffer_id == id))
+                .count(),
+            total: preview
+                .edits
+                .iter()
+                .filter(|edit| buffer_id.map_or(true, |id| edit.buffer_id == id))
+                .count(),
+        };
+        let editor = cx.entity().downgrade();
+        let mut blocks = Vec::new();
+        if let Some(first_file) = preview.files.first() {
+            let editor = editor.clone();
+            let counts = counts(None);
+            let file_count = preview.files.len();
+            blocks.push(BlockProperties {
+                style: BlockStyle::Sticky,
+                placement: BlockPlacement::Above(first_file.start),
+                height: Some(2),
+                render: Arc::new(move |cx: &mut BlockContext| {
+                    render_rename_preview_header(&editor, counts, file_count, cx)
+                }),
+                priority: 0,
+                render_in_minimap: false,
+            });
+        }
+        for file in &preview.files {
+            let editor = editor.clone();
+            let counts = counts(Some(file.buffer_id));
+            let buffer_id = file.buffer_id;
+            let path = file.path.clone();
+            blocks.push(BlockProperties {
+                style: BlockStyle::Sticky,
+                placement: BlockPlacement::Above(file.start),
+                height: Some(1),
+                render: Arc::new(move |cx: &mut BlockContext| {
+                    render_rename_preview_file(&editor, buffer_id, &path, counts, cx)
+                }),
+                priority: 1,
+                render_in_minimap: false,
+            });
+        }
+
+        self.remove_blocks(old_blocks, None, cx);
+        self.splice_inlays(&old_inlays, inlays, cx);
+        let block_ids = self.insert_blocks(blocks, None, cx);
+        if let Some(preview) = self.rename_preview.as_mut() {
+            preview.blocks = block_ids.into_iter().collect();
+        }
+
+        let status = cx.theme().status();
+        self.highlight_text::<RenamePreviewHighlights>(
+            highlighted_ranges,
+            HighlightStyle {
+                background_color: Some(status.deleted_background),
+                strikethrough: Some(StrikethroughStyle {
+                    thickness: px(1.),
+                    color: Some(status.deleted),
+                }),
+                ..HighlightStyle::default()
+            },
+            cx,
+        );
+        cx.notify();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn rename_preview_edits(&self) -> Option<Vec<(String, bool)>> {
+        Some(
+            self.rename_preview
+                .as_ref()?
+                .edits
+                .iter()
+                .map(|edit| (edit.new_text.to_string(), edit.included))
+                .collect(),
+        )
+    }
+}
+
+fn toggle_state(counts: EditCounts) -> ToggleState {
+    if counts.included == 0 {
+        ToggleState::Unselected
+    } else if counts.included == counts.total {
+        ToggleState::Selected
+    } else {
+        ToggleState::Indeterminate
+    }
+}
+
+fn render_rename_preview_header(
+    editor: &WeakEntity<Editor>,
+    counts: EditCounts,
+    file_count: usize,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let apply_editor = editor.clone();
+    let discard_editor = editor.clone();
+    h_flex()
+        .id("rename-preview-header")
+        .size_full()
+        .pl(cx.margins.gutter.full_width())
+        .pr_2()
+        .gap_2()
+        .justify_between()
+        .bg(cx.theme().colors().toolbar_background)
+        .border_b_1()
+        .border_color(cx.theme().colors().border_variant)
+        .child(
+            Label::new(format!(
+                "{} of {} changes in {} {} will be applied",
+                counts.included,
+                counts.total,
+                file_count,
+                if file_count == 1 { "file" } else { "files" }
+            ))
+            .size(LabelSize::Small)
+            .color(Color::Muted),
+        )
+        .child(
+            h_flex()
+                .gap_1()
+                .child(
+                    Button::new("discard-rename-preview", "Discard")
+                        .label_size(LabelSize::Small)
+                        .on_click(move |_, window, cx| {
+                            discard_editor
+                                .update(cx, |editor, cx| {
+                                    editor.discard_rename_preview(&DiscardRenamePreview, window, cx)
+                                })
+                                .log_err();
+                        }),
+                )
+                .child(
+                    Button::new("apply-rename-preview", "Apply Rename")
+                        .label_size(LabelSize::Small)
+                        .style(ButtonStyle::Filled)
+                        .disabled(counts.included == 0)
+                        .on_click(move |_, window, cx| {
+                            apply_editor
+                                .update(cx, |editor, cx| {
+                                    editor.apply_rename_preview(&ApplyRenamePreview, window, cx)
+                                })
+                                .log_err();
+                        }),
+                ),
+        )
+        .into_any_element()
+}
+
+fn render_rename_preview_file(
+    editor: &WeakEntity<Editor>,
+    buffer_id: BufferId,
+    path: &SharedString,
+    counts: EditCounts,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let editor = editor.clone();
+    h_flex()
+        .h_full()
+        .gap_2()
+        .pl(cx.margins.gutter.full_width())
+        .child(
+            Checkbox::new(
+                ("rename-preview-file", buffer_id.to_proto() as usize),
+                toggle_state(counts),
+            )
+            .on_click(move |_, _, cx| {
+                editor
+                    .update(cx, |editor, cx| {
+                        editor.toggle_rename_preview_file(buffer_id, cx)
+                    })
+                    .log_err();
+            }),
+        )
+        .child(Label::new(path.clone()).size(LabelSize::Small))
+        .child(
+            Label::new(format!("{} of {}", counts.included, counts.total))
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+        )
+        .into_any_element()
+}
@@ -20,11 +20,13 @@ use std::{
     cmp::Ordering,
     time::{Duration, Instant},
 };
+use theme::AppearanceController;
 use util::ResultExt;
-use workspace::{ItemId, WorkspaceId};
+use workspace::{ItemId, WorkspaceId, WorkspaceSettings};
 
 pub const SCROLL_EVENT_SEPARATION: Duration = Duration::from_millis(28);
 const SCROLLBAR_SHOW_INTERVAL: Duration = Duration::from_secs(1);
+const FOLLOW_SCROLL_ANIMATION_DURATION: Duration = Duration::from_millis(150);
 
 #[derive(Default)]
 pub struct ScrollbarAutoHide(pub bool);
@@ -123,6 +125,23 @@ impl OngoingScroll {
     }
 }
 
+/// Eases the rendered viewport from where it was to where a leader scrolled, so that following
+/// them doesn't jump around. The scroll anchor itself is set right away.
+#[derive(Clone, Copy, Debug)]
+struct ScrollAnimation {
+    from: gpui::Point<f32>,
+    started_at: Instant,
+}
+
+impl ScrollAnimation {
+    fn progress(&self) -> f32 {
+        let progress = self.started_at.elapsed().as_secs_f32()
+            / FOLLOW_SCROLL_ANIMATION_DURATION.as_secs_f32();
+        // Ease out cubic: fast at first, then settling on the target.
+        1. - (1. - progress.min(1.)).powi(3)
+    }
+}
+
 #[derive(Copy, Clone, Default, PartialEq, Eq)]
 pub enum ScrollbarThumbState {
     #[default]
@@ -159,6 +178,7 @@ pub struct ScrollManager {
     visible_line_count: Option<f32>,
     forbid_vertical_scroll: bool,
     minimap_thumb_state: Option<ScrollbarThumbState>,
+    animation: Option<ScrollAnimation>,
 }
 
 impl ScrollManager {
@@ -175,6 +195,7 @@ impl ScrollManager {
             visible_line_count: None,
             forbid_vertical_scroll: false,
             minimap_thumb_state: None,
+            animation: None,
         }
     }
 
@@ -200,6 +221,27 @@ impl ScrollManager {
         self.anchor.scroll_position(snapshot)
     }
 
+    pub(crate) fn is_animating(&self) -> bool {
+        self.animation.is_some_and(|animation| {
+            animation.started_at.elapsed() < FOLLOW_SCROLL_ANIMATION_DURATION
+        })
+    }
+
+    /// How far the rendered viewport still is from the scroll position, while animating.
+    pub(crate) fn animation_offset(&self, snapshot: &DisplaySnapshot) -> gpui::Point<f32> {
+        match self.animation.filter(|_| self.is_animating()) {
+            Some(animation) => {
+                let remaining = 1. - animation.progress();
+                let target = self.scroll_position(snapshot);
+                point(
+                    (animation.from.x - target.x) * remaining,
+                    (animation.from.y - target.y) * remaining,
+                )
+            }
+            None => gpui::Point::default(),
+        }
+    }
+
     fn set_scroll_position(
         &mut self,
         scroll_position: gpui::Point<f32>,
@@ -291,6 +333,9 @@ impl ScrollManager {
         };
 
         self.anchor = adjusted_anchor;
+        if local {
+            self.animation = None;
+        }
         cx.emit(EditorEvent::ScrollPositionChanged { local, autoscroll });
         self.show_scrollbars(window, cx);
         self.autoscroll_request.take();
@@ -488,7 +533,8 @@ impl Editor {
             cx.spawn_in(window, async move |editor, cx| {
                 editor
                     .update(cx, |editor, cx| {
-                        editor.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx)
+                        editor.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                        editor.refresh_bracket_colors(false, cx);
                     })
                     .ok()
             })
@@ -588,6 +634,7 @@ impl Editor {
             scroll_position
         };
 
+        let rendered_position = self.rendered_scroll_position(&display_map);
         self.scroll_manager.set_scroll_position(
             adjusted_position,
             &display_map,
@@ -597,8 +644,12 @@ impl Editor {
             window,
             cx,
         );
+        if !local {
+            self.animate_follow_scroll(rendered_position, cx);
+        }
 
         self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+        self.refresh_bracket_colors(false, cx);
     }
 
     pub fn scroll_position(&self, cx: &mut Context<Self>) -> gpui::Point<f32> {
@@ -643,6 +694,8 @@ impl Editor {
             return;
         }
         let top_row = scroll_anchor.anchor.to_point(snapshot).row;
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let rendered_position = self.rendered_scroll_position(&display_map);
         self.scroll_manager.set_anchor(
             scroll_anchor,
             top_row,
@@ -652,6 +705,26 @@ impl Editor {
             window,
             cx,
         );
+        self.animate_follow_scroll(rendered_position, cx);
+    }
+
+    fn rendered_scroll_position(&self, display_map: &DisplaySnapshot) -> gpui::Point<f32> {
+        let position = self.scroll_manager.scroll_position(display_map);
+        let offset = self.scroll_manager.animation_offset(display_map);
+        point(position.x + offset.x, position.y + offset.y)
+    }
+
+    fn animate_follow_scroll(&mut self, from: gpui::Point<f32>, cx: &mut Context<Self>) {
+        if self.leader_id.is_some()
+            && WorkspaceSettings::get_global(cx).follow.smooth_scroll
+            && !AppearanceController::reduce_animations(cx)
+        {
+            self.scroll_manager.animation = Some(ScrollAnimation {
+                from,
+                started_at: Instant::now(),
+            });
+            cx.notify();
+        }
     }
 
     pub fn scroll_screen(
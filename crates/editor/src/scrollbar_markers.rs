@@ -0,0 +1,59 @@
+use std::{ops::Range, sync::Arc};
+
+use gpui::{Context, Hsla, SharedString};
+use multi_buffer::Anchor;
+
+use crate::{Editor, editor_settings::Scrollbar};
+
+/// A colored mark on the editor's vertical scrollbar, covering the rows of `range`.
+#[derive(Clone, Debug)]
+pub struct ScrollbarMarker {
+    pub range: Range<Anchor>,
+    pub color: Hsla,
+}
+
+impl Editor {
+    /// Replaces the markers that `layer` shows on the scrollbar. Each feature adding markers
+    /// should use a layer of its own, so that the `scrollbar.marker_layers` setting can hide it.
+    pub fn set_scrollbar_markers(
+        &mut self,
+        layer: impl Into<SharedString>,
+        markers: impl IntoIterator<Item = ScrollbarMarker>,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut markers = markers.into_iter().collect::<Vec<_>>();
+        markers.sort_by(|a, b| a.range.start.cmp(&b.range.start, &snapshot));
+        let layer = layer.into();
+        if markers.is_empty() {
+            if self.scrollbar_marker_layers.remove(&layer).is_none() {
+                return;
+            }
+        } else {
+            self.scrollbar_marker_layers
+                .insert(layer, Arc::from(markers));
+        }
+        self.scrollbar_marker_state.dirty = true;
+        cx.notify();
+    }
+
+    pub fn clear_scrollbar_markers(&mut self, layer: &str, cx: &mut Context<Self>) {
+        if self.scrollbar_marker_layers.remove(layer).is_some() {
+            self.scrollbar_marker_state.dirty = true;
+            cx.notify();
+        }
+    }
+
+    /// The markers of the layers that the settings don't hide, sorted by their start within each
+    /// layer.
+    pub(crate) fn visible_scrollbar_markers(
+        &self,
+        settings: &Scrollbar,
+    ) -> Vec<Arc<[ScrollbarMarker]>> {
+        self.scrollbar_marker_layers
+            .iter()
+            .filter(|(layer, _)| settings.marker_layers.get(layer.as_ref()) != Some(&false))
+            .map(|(_, markers)| markers.clone())
+            .collect()
+    }
+}
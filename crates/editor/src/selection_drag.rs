@@ -0,0 +1,201 @@
+use std::{mem, ops::Range};
+
+use gpui::{App, Context, Global, Modifiers, Pixels, WeakEntity, Window};
+use itertools::Itertools as _;
+use multi_buffer::{Anchor, ToOffset as _};
+
+use crate::{Bias, DisplayPoint, Editor};
+
+/// How far the mouse has to move after being pressed inside a selection before the selected
+/// text starts being dragged.
+pub(crate) const SELECTION_DRAG_THRESHOLD: f64 = 4.;
+
+#[derive(Default)]
+pub(crate) enum SelectionDragState {
+    #[default]
+    None,
+    /// The mouse was pressed inside a selection. Releasing it without moving places the cursor at
+    /// `position`, like a click outside a selection would.
+    ReadyToDrag {
+        mouse_down_position: gpui::Point<Pixels>,
+        position: DisplayPoint,
+    },
+    /// This editor's selections are being dragged.
+    Dragging,
+}
+
+/// The text being dragged, which any editor it's released over inserts.
+struct DraggedSelection {
+    source: WeakEntity<Editor>,
+    ranges: Vec<Range<Anchor>>,
+    text: String,
+}
+
+#[derive(Default)]
+struct ActiveSelectionDrag(Option<DraggedSelection>);
+
+impl Global for ActiveSelectionDrag {}
+
+/// Whether dropping dragged text copies it instead of moving it.
+pub(crate) fn copies_on_drop(modifiers: &Modifiers) -> bool {
+    if cfg!(target_os = "macos") {
+        modifiers.alt
+    } else {
+        modifiers.control
+    }
+}
+
+impl Editor {
+    pub(crate) fn is_selection_drag_active(cx: &App) -> bool {
+        cx.try_global::<ActiveSelectionDrag>()
+            .is_some_and(|drag| drag.0.is_some())
+    }
+
+    /// Whether `position` is strictly inside one of the non-empty selections.
+    pub(crate) fn selections_contain(&mut self, position: DisplayPoint, cx: &mut App) -> bool {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let offset = position.to_offset(&display_map, Bias::Left);
+        self.selections
+            .all::<usize>(cx)
+            .iter()
+            .any(|selection| selection.start < offset && offset < selection.end)
+    }
+
+    pub(crate) fn start_selection_drag(&mut self, cx: &mut Context<Self>) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let ranges = self
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .filter(|selection| !selection.is_empty())
+            .map(|selection| {
+                snapshot.anchor_after(selection.start)..snapshot.anchor_before(selection.end)
+            })
+            .collect::<Vec<_>>();
+        let text = ranges
+            .iter()
+            .map(|range| snapshot.text_for_range(range.clone()).collect::<String>())
+            .join("\n");
+        cx.set_global(ActiveSelectionDrag(Some(DraggedSelection {
+            source: cx.entity().downgrade(),
+            ranges,
+            text,
+        })));
+        self.selection_drag_state = SelectionDragState::Dragging;
+    }
+
+    /// Shows where dragged text would be dropped, or hides the indicator when `position` is `None`.
+    pub(crate) fn set_drop_cursor(
+        &mut self,
+        position: Option<DisplayPoint>,
+        cx: &mut Context<Self>,
+    ) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let drop_cursor =
+            position.map(|position| display_map.display_point_to_anchor(position, Bias::Left));
+        if self.drop_cursor != drop_cursor {
+            self.drop_cursor = drop_cursor;
+            cx.notify();
+        }
+    }
+
+    /// Inserts the dragged text at `position` and selects it, removing it from where it was dragged
+    /// from unless `copy` is set. Dropping text onto itself only moves the cursor there.
+    pub(crate) fn drop_selection(
+        &mut self,
+        position: DisplayPoint,
+        copy: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_drop_cursor(None, cx);
+        let Some(drag) = cx.default_global::<ActiveSelectionDrag>().0.take() else {
+            return;
+        };
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let offset = position.to_offset(&display_map, Bias::Left);
+        let is_source = drag.source.entity_id() == cx.entity_id();
+        if is_source
+            && drag.ranges.iter().any(|range| {
+                range.start.to_offset(&snapshot) <= offset
+                    && offset <= range.end.to_offset(&snapshot)
+            })
+        {
+            self.change_selections(None, window, cx, |s| s.select_ranges([offset..offset]));
+            return;
+        }
+        if self.read_only(cx) {
+            return;
+        }
+        // Text dragged out of a read-only editor can only be copied.
+        let copy = copy
+            || (!is_source
+                && drag
+                    .source
+                    .upgrade()
+                    .is_some_and(|source| source.read(cx).read_only(cx)));
+
+        let start = snapshot.anchor_before(offset);
+        self.transact(window, cx, |editor, window, cx| {
+            editor.buffer.update(cx, |buffer, cx| {
+                buffer.edit([(offset..offset, drag.text.as_str())], None, cx);
+                if is_source && !copy {
+                    buffer.edit(
+                        drag.ranges.iter().map(|range| (range.clone(), "")),
+                        None,
+                        cx,
+                    );
+                }
+            });
+            let snapshot = editor.buffer.read(cx).snapshot(cx);
+            let start = start.to_offset(&snapshot);
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges([start..start + drag.text.len()])
+            });
+        });
+
+        if !is_source && !copy {
+            if let Some(source) = drag.source.upgrade() {
+                source.update(cx, |source, cx| {
+                    source.transact(window, cx, |source, _, cx| {
+                        source.buffer.update(cx, |buffer, cx| {
+                            buffer.edit(drag.ranges.into_iter().map(|range| (range, "")), None, cx)
+                        });
+                    });
+                });
+            }
+        }
+    }
+
+    /// Finishes a mouse press that started inside a selection. Returns whether there was one.
+    pub(crate) fn end_selection_drag(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.set_drop_cursor(None, cx);
+        match mem::take(&mut self.selection_drag_state) {
+            SelectionDragState::None => false,
+            SelectionDragState::ReadyToDrag { position, .. } => {
+                self.change_selections(None, window, cx, |s| {
+                    s.select_display_ranges([position..position])
+                });
+                true
+            }
+            SelectionDragState::Dragging => {
+                // The text was released somewhere that couldn't take it.
+                let entity_id = cx.entity_id();
+                let drag = cx.default_global::<ActiveSelectionDrag>();
+                if drag
+                    .0
+                    .as_ref()
+                    .is_some_and(|drag| drag.source.entity_id() == entity_id)
+                {
+                    drag.0 = None;
+                }
+                true
+            }
+        }
+    }
+}
@@ -27,6 +27,7 @@ use project::project_settings::ProjectSettings;
 use prompt_store::PromptBuilder;
 use release_channel::AppVersion;
 use reqwest_client::ReqwestClient;
+use semantic_index::SemanticIndexSettings;
 use settings::{Settings, SettingsStore};
 use std::cell::RefCell;
 use std::collections::VecDeque;
@@ -431,6 +432,7 @@ pub fn init(cx: &mut App) -> Arc<AgentAppState> {
         true,
         cx,
     );
+    SemanticIndexSettings::register(cx);
     assistant_tools::init(client.http_client(), cx);
 
     SettingsStore::update_global(cx, |store, cx| {
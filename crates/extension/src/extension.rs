@@ -43,6 +43,14 @@ pub trait KeyValueStoreDelegate: Send + Sync + 'static {
     fn insert(&self, key: String, docs: String) -> Task<Result<()>>;
 }
 
+#[async_trait]
+pub trait EditorDelegate: Send + Sync + 'static {
+    fn path(&self) -> Option<String>;
+    fn text(&self) -> String;
+    fn selections(&self) -> Vec<EditorSelection>;
+    async fn edit(&self, edits: Vec<TextEdit>) -> Result<()>;
+}
+
 #[async_trait]
 pub trait Extension: Send + Sync + 'static {
     /// Returns the [`ExtensionManifest`] for this extension.
@@ -145,6 +153,20 @@ pub trait Extension: Send + Sync + 'static {
     ) -> Result<DebugAdapterBinary>;
 
     async fn get_dap_schema(&self) -> Result<serde_json::Value>;
+
+    async fn run_command(
+        &self,
+        command_id: Arc<str>,
+        editor: Option<Arc<dyn EditorDelegate>>,
+    ) -> Result<()>;
+
+    async fn load_document(&self, uri: Arc<str>) -> Result<VirtualDocument>;
+
+    async fn save_document(&self, uri: Arc<str>, text: String) -> Result<()>;
+
+    async fn render_panel(&self, panel_id: Arc<str>) -> Result<PanelView>;
+
+    async fn handle_panel_event(&self, panel_id: Arc<str>, event: PanelEvent) -> Result<PanelView>;
 }
 
 pub fn parse_wasm_extension_version(
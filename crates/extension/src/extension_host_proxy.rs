@@ -8,7 +8,10 @@ use language::{BinaryStatus, LanguageMatcher, LanguageName, LoadedLanguage};
 use lsp::LanguageServerName;
 use parking_lot::RwLock;
 
-use crate::{Extension, SlashCommand};
+use crate::{
+    CommandManifestEntry, DocumentProviderManifestEntry, Extension, PanelManifestEntry,
+    SlashCommand,
+};
 
 #[derive(Default)]
 struct GlobalExtensionHostProxy(Arc<ExtensionHostProxy>);
@@ -30,6 +33,9 @@ pub struct ExtensionHostProxy {
     context_server_proxy: RwLock<Option<Arc<dyn ExtensionContextServerProxy>>>,
     indexed_docs_provider_proxy: RwLock<Option<Arc<dyn ExtensionIndexedDocsProviderProxy>>>,
     debug_adapter_provider_proxy: RwLock<Option<Arc<dyn ExtensionDebugAdapterProviderProxy>>>,
+    panel_proxy: RwLock<Option<Arc<dyn ExtensionPanelProxy>>>,
+    command_proxy: RwLock<Option<Arc<dyn ExtensionCommandProxy>>>,
+    document_provider_proxy: RwLock<Option<Arc<dyn ExtensionDocumentProviderProxy>>>,
 }
 
 impl ExtensionHostProxy {
@@ -56,6 +62,9 @@ impl ExtensionHostProxy {
             context_server_proxy: RwLock::default(),
             indexed_docs_provider_proxy: RwLock::default(),
             debug_adapter_provider_proxy: RwLock::default(),
+            panel_proxy: RwLock::default(),
+            command_proxy: RwLock::default(),
+            document_provider_proxy: RwLock::default(),
         }
     }
 
@@ -100,6 +109,20 @@ impl ExtensionHostProxy {
             .write()
             .replace(Arc::new(proxy));
     }
+
+    pub fn register_panel_proxy(&self, proxy: impl ExtensionPanelProxy) {
+        self.panel_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_command_proxy(&self, proxy: impl ExtensionCommandProxy) {
+        self.command_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_document_provider_proxy(&self, proxy: impl ExtensionDocumentProviderProxy) {
+        self.document_provider_proxy
+            .write()
+            .replace(Arc::new(proxy));
+    }
 }
 
 pub trait ExtensionThemeProxy: Send + Sync + 'static {
@@ -423,3 +446,111 @@ impl ExtensionDebugAdapterProviderProxy for ExtensionHostProxy {
         proxy.register_debug_adapter(extension, debug_adapter_name)
     }
 }
+
+pub trait ExtensionPanelProxy: Send + Sync + 'static {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        panel: PanelManifestEntry,
+        cx: &mut App,
+    );
+
+    fn unregister_panel(&self, extension_id: Arc<str>, panel_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionPanelProxy for ExtensionHostProxy {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        panel: PanelManifestEntry,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_panel(extension, panel_id, panel, cx)
+    }
+
+    fn unregister_panel(&self, extension_id: Arc<str>, panel_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_panel(extension_id, panel_id, cx)
+    }
+}
+
+pub trait ExtensionCommandProxy: Send + Sync + 'static {
+    fn register_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command_id: Arc<str>,
+        command: CommandManifestEntry,
+        cx: &mut App,
+    );
+
+    fn unregister_command(&self, extension_id: Arc<str>, command_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionCommandProxy for ExtensionHostProxy {
+    fn register_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command_id: Arc<str>,
+        command: CommandManifestEntry,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.command_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_command(extension, command_id, command, cx)
+    }
+
+    fn unregister_command(&self, extension_id: Arc<str>, command_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.command_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_command(extension_id, command_id, cx)
+    }
+}
+
+pub trait ExtensionDocumentProviderProxy: Send + Sync + 'static {
+    fn register_document_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        scheme: Arc<str>,
+        provider: DocumentProviderManifestEntry,
+        cx: &mut App,
+    );
+
+    fn unregister_document_provider(&self, scheme: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionDocumentProviderProxy for ExtensionHostProxy {
+    fn register_document_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        scheme: Arc<str>,
+        provider: DocumentProviderManifestEntry,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.document_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_document_provider(extension, scheme, provider, cx)
+    }
+
+    fn unregister_document_provider(&self, scheme: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.document_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_document_provider(scheme, cx)
+    }
+}
@@ -89,6 +89,12 @@ pub struct ExtensionManifest {
     pub capabilities: Vec<ExtensionCapability>,
     #[serde(default)]
     pub debug_adapters: Vec<Arc<str>>,
+    #[serde(default)]
+    pub panels: BTreeMap<Arc<str>, PanelManifestEntry>,
+    #[serde(default)]
+    pub commands: BTreeMap<Arc<str>, CommandManifestEntry>,
+    #[serde(default)]
+    pub document_providers: BTreeMap<Arc<str>, DocumentProviderManifestEntry>,
 }
 
 impl ExtensionManifest {
@@ -208,6 +214,22 @@ pub struct SlashCommandManifestEntry {
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct IndexedDocsProviderEntry {}
 
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct CommandManifestEntry {
+    /// The name shown for the command in the command palette.
+    pub title: String,
+}
+
+/// A provider of virtual documents, keyed in the manifest by the URI scheme it serves.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct DocumentProviderManifestEntry {}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct PanelManifestEntry {
+    /// The title shown in the panel's tab and in the dock's tooltip.
+    pub title: String,
+}
+
 impl ExtensionManifest {
     pub async fn load(fs: Arc<dyn Fs>, extension_dir: &Path) -> Result<Self> {
         let extension_name = extension_dir
@@ -277,6 +299,9 @@ fn manifest_from_old_manifest(
         snippets: None,
         capabilities: Vec::new(),
         debug_adapters: vec![],
+        panels: BTreeMap::default(),
+        commands: BTreeMap::default(),
+        document_providers: BTreeMap::default(),
     }
 }
 
@@ -305,6 +330,9 @@ mod tests {
             snippets: None,
             capabilities: vec![],
             debug_adapters: Default::default(),
+            panels: BTreeMap::default(),
+            commands: BTreeMap::default(),
+            document_providers: BTreeMap::default(),
         }
     }
 
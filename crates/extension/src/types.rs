@@ -1,13 +1,19 @@
 mod context_server;
 mod dap;
+mod document;
+mod editor;
 mod lsp;
+mod panel;
 mod slash_command;
 
 use std::ops::Range;
 
 pub use context_server::*;
 pub use dap::*;
+pub use document::*;
+pub use editor::*;
 pub use lsp::*;
+pub use panel::*;
 pub use slash_command::*;
 
 /// A list of environment variables.
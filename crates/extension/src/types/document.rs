@@ -0,0 +1,10 @@
+/// A document served by an extension under one of its URI schemes.
+#[derive(Debug, Clone)]
+pub struct VirtualDocument {
+    /// The document's text.
+    pub text: String,
+    /// The name of the language to highlight the document as.
+    pub language: Option<String>,
+    /// Whether the document can be edited and saved back to the extension.
+    pub writable: bool,
+}
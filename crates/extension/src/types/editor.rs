@@ -0,0 +1,19 @@
+use std::ops::Range;
+
+/// A selection within an editor.
+#[derive(Debug, Clone)]
+pub struct EditorSelection {
+    /// The selected range, as byte offsets into the editor's text.
+    pub range: Range<usize>,
+    /// The selected text.
+    pub text: String,
+}
+
+/// An edit to an editor's text.
+#[derive(Debug, Clone)]
+pub struct TextEdit {
+    /// The range to replace, as byte offsets into the editor's text.
+    pub range: Range<usize>,
+    /// The text to replace the range with.
+    pub new_text: String,
+}
@@ -0,0 +1,80 @@
+/// The contents of a panel contributed by an extension.
+#[derive(Debug, Clone, Default)]
+pub struct PanelView {
+    /// The elements to display in the panel.
+    pub elements: Vec<PanelElement>,
+}
+
+/// An element within a panel.
+#[derive(Debug, Clone)]
+pub enum PanelElement {
+    /// A line of text.
+    Label(String),
+    /// A button that can be clicked.
+    Button(PanelButton),
+    /// A single-line text input.
+    Input(PanelInput),
+    /// A flat list of items that can be selected.
+    Items(Vec<PanelItem>),
+    /// A tree of items that can be selected and expanded.
+    Tree(Vec<PanelTreeItem>),
+    /// A horizontal divider.
+    Separator,
+}
+
+/// A button within a panel.
+#[derive(Debug, Clone)]
+pub struct PanelButton {
+    /// The ID reported back to the extension when the button is clicked.
+    pub id: String,
+    /// The text on the button.
+    pub label: String,
+}
+
+/// A text input within a panel.
+#[derive(Debug, Clone)]
+pub struct PanelInput {
+    /// The ID reported back to the extension when the input is submitted.
+    pub id: String,
+    /// The text to display while the input is empty.
+    pub placeholder: String,
+    /// The initial contents of the input.
+    pub value: String,
+}
+
+/// An item within a list.
+#[derive(Debug, Clone)]
+pub struct PanelItem {
+    /// The ID reported back to the extension when the item is selected.
+    pub id: String,
+    /// The text of the item.
+    pub label: String,
+    /// Secondary text to display after the label.
+    pub detail: Option<String>,
+}
+
+/// An item within a flattened tree.
+#[derive(Debug, Clone)]
+pub struct PanelTreeItem {
+    /// The ID reported back to the extension when the item is selected or toggled.
+    pub id: String,
+    /// The text of the item.
+    pub label: String,
+    /// How deeply the item is nested, starting from zero.
+    pub depth: u32,
+    /// Whether the item is expanded, or `None` if it has no children.
+    pub expanded: Option<bool>,
+}
+
+/// An interaction with a panel.
+#[derive(Debug, Clone)]
+pub enum PanelEvent {
+    /// The button with the given ID was clicked.
+    ButtonClicked(String),
+    /// The input with the given ID was submitted with the given value.
+    InputSubmitted { id: String, value: String },
+    /// The list or tree item with the given ID was selected.
+    ItemSelected(String),
+    /// The tree item with the given ID was expanded or collapsed.
+    TreeItemToggled(String),
+}
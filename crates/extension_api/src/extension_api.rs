@@ -15,9 +15,9 @@ pub use serde_json;
 // We explicitly enumerate the symbols we want to re-export, as there are some
 // that we may want to shadow to provide a cleaner Rust API.
 pub use wit::{
-    CodeLabel, CodeLabelSpan, CodeLabelSpanLiteral, Command, DownloadedFileType, EnvVars,
-    KeyValueStore, LanguageServerInstallationStatus, Project, Range, Worktree, download_file,
-    make_file_executable,
+    CodeLabel, CodeLabelSpan, CodeLabelSpanLiteral, Command, DownloadedFileType, Editor, EnvVars,
+    KeyValueStore, LanguageServerInstallationStatus, Project, Range, Selection, TextEdit,
+    VirtualDocument, Worktree, download_file, make_file_executable,
     zed::extension::context_server::ContextServerConfiguration,
     zed::extension::dap::{
         DebugAdapterBinary, DebugTaskDefinition, StartDebuggingRequestArguments,
@@ -32,6 +32,10 @@ pub use wit::{
         node_binary_path, npm_install_package, npm_package_installed_version,
         npm_package_latest_version,
     },
+    zed::extension::panel::{
+        PanelButton, PanelElement, PanelEvent, PanelInput, PanelInputSubmission, PanelItem,
+        PanelTreeItem, PanelView,
+    },
     zed::extension::platform::{Architecture, Os, current_platform},
     zed::extension::slash_command::{
         SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, SlashCommandOutputSection,
@@ -207,6 +211,35 @@ pub trait Extension: Send + Sync {
     fn dap_schema(&mut self) -> Result<serde_json::Value, String> {
         Err("`dap_schema` not implemented".to_string())
     }
+
+    /// Runs the specified command, from the active editor if there is one.
+    fn run_command(&mut self, _command_id: String, _editor: Option<&Editor>) -> Result<(), String> {
+        Err("`run_command` not implemented".to_string())
+    }
+
+    /// Returns the virtual document at the specified URI.
+    fn load_document(&mut self, _uri: String) -> Result<VirtualDocument, String> {
+        Err("`load_document` not implemented".to_string())
+    }
+
+    /// Saves the given text to the writable virtual document at the specified URI.
+    fn save_document(&mut self, _uri: String, _text: String) -> Result<(), String> {
+        Err("`save_document` not implemented".to_string())
+    }
+
+    /// Returns the contents of the specified panel.
+    fn render_panel(&mut self, _panel_id: String) -> Result<PanelView, String> {
+        Err("`render_panel` not implemented".to_string())
+    }
+
+    /// Handles an interaction with the specified panel and returns its updated contents.
+    fn handle_panel_event(
+        &mut self,
+        _panel_id: String,
+        _event: PanelEvent,
+    ) -> Result<PanelView, String> {
+        Err("`handle_panel_event` not implemented".to_string())
+    }
 }
 
 /// Registers the provided type as a Zed extension.
@@ -248,7 +281,7 @@ mod wit {
 
     wit_bindgen::generate!({
         skip: ["init-extension"],
-        path: "./wit/since_v0.6.0",
+        path: "./wit/since_v0.7.0",
     });
 }
 
@@ -404,6 +437,26 @@ impl wit::Guest for Component {
     fn dap_schema() -> Result<String, String> {
         extension().dap_schema().map(|schema| schema.to_string())
     }
+
+    fn run_command(command_id: String, editor: Option<&Editor>) -> Result<(), String> {
+        extension().run_command(command_id, editor)
+    }
+
+    fn load_document(uri: String) -> Result<VirtualDocument, String> {
+        extension().load_document(uri)
+    }
+
+    fn save_document(uri: String, text: String) -> Result<(), String> {
+        extension().save_document(uri, text)
+    }
+
+    fn render_panel(panel_id: String) -> Result<PanelView, String> {
+        extension().render_panel(panel_id)
+    }
+
+    fn handle_panel_event(panel_id: String, event: PanelEvent) -> Result<PanelView, String> {
+        extension().handle_panel_event(panel_id, event)
+    }
 }
 
 /// The ID of a language server.
@@ -139,6 +139,9 @@ fn manifest() -> ExtensionManifest {
             args: vec!["hello!".into()],
         }],
         debug_adapters: Default::default(),
+        panels: Default::default(),
+        commands: Default::default(),
+        document_providers: Default::default(),
     }
 }
 
@@ -14,10 +14,10 @@ use collections::{BTreeMap, BTreeSet, HashMap, HashSet, btree_map};
 pub use extension::ExtensionManifest;
 use extension::extension_builder::{CompileExtensionOptions, ExtensionBuilder};
 use extension::{
-    ExtensionContextServerProxy, ExtensionDebugAdapterProviderProxy, ExtensionEvents,
-    ExtensionGrammarProxy, ExtensionHostProxy, ExtensionIndexedDocsProviderProxy,
-    ExtensionLanguageProxy, ExtensionLanguageServerProxy, ExtensionSlashCommandProxy,
-    ExtensionSnippetProxy, ExtensionThemeProxy,
+    ExtensionCommandProxy, ExtensionContextServerProxy, ExtensionDebugAdapterProviderProxy,
+    ExtensionDocumentProviderProxy, ExtensionEvents, ExtensionGrammarProxy, ExtensionHostProxy,
+    ExtensionIndexedDocsProviderProxy, ExtensionLanguageProxy, ExtensionLanguageServerProxy,
+    ExtensionPanelProxy, ExtensionSlashCommandProxy, ExtensionSnippetProxy, ExtensionThemeProxy,
 };
 use fs::{Fs, RemoveOptions};
 use futures::{
@@ -100,6 +100,23 @@ pub fn is_version_compatible(
     true
 }
 
+/// Returns whether the given extension version is a pre-release, like `1.2.0-beta.1`.
+///
+/// Build metadata, like the `build-5` in `1.2.0+build-5`, doesn't make a version a pre-release.
+pub fn is_pre_release_version(version: &str) -> bool {
+    semver::Version::parse(version.trim()).is_ok_and(|version| !version.pre.is_empty())
+}
+
+/// Compares two extension versions by [semver](https://semver.org/) precedence, which orders a
+/// pre-release before the release it precedes.
+///
+/// Returns `None` if either isn't a valid version.
+pub fn compare_extension_versions(a: &str, b: &str) -> Option<Ordering> {
+    let a = semver::Version::parse(a.trim()).ok()?;
+    let b = semver::Version::parse(b.trim()).ok()?;
+    Some(a.cmp_precedence(&b))
+}
+
 pub struct ExtensionStore {
     pub proxy: Arc<ExtensionHostProxy>,
     pub builder: Arc<ExtensionBuilder>,
@@ -529,14 +546,24 @@ impl ExtensionStore {
         let schema_versions = schema_version_range();
         let wasm_api_versions = wasm_api_version_range(ReleaseChannel::global(cx));
         let extension_settings = ExtensionSettings::get_global(cx);
-        let extension_ids = self
+        let (pre_release_extension_ids, extension_ids): (Vec<_>, Vec<_>) = self
             .extension_index
             .extensions
             .iter()
             .filter(|(id, entry)| !entry.dev && extension_settings.should_auto_update(id))
-            .map(|(id, _)| id.as_ref())
+            .map(|(id, _)| id.clone())
+            .partition(|id| extension_settings.should_install_pre_releases(id));
+        let extension_ids = extension_ids
+            .iter()
+            .map(|id| id.as_ref())
             .collect::<Vec<_>>()
             .join(",");
+        // The updates endpoint only knows about each extension's latest release, so the
+        // extensions following pre-releases look through all of their versions instead.
+        let pre_release_tasks = pre_release_extension_ids
+            .iter()
+            .map(|id| self.fetch_latest_compatible_version(id, true, cx))
+            .collect::<Vec<_>>();
         let task = self.fetch_extensions_from_api(
             "/extensions/updates",
             &[
@@ -552,7 +579,10 @@ impl ExtensionStore {
             cx,
         );
         cx.spawn(async move |this, cx| {
-            let extensions = task.await?;
+            let mut extensions = task.await?;
+            for task in pre_release_tasks {
+                extensions.extend(task.await.log_err().flatten());
+            }
             this.update(cx, |this, _cx| {
                 extensions
                     .into_iter()
@@ -569,6 +599,31 @@ impl ExtensionStore {
         })
     }
 
+    /// Fetches the newest version of the given extension that is compatible with this version
+    /// of Zed, optionally including pre-releases.
+    fn fetch_latest_compatible_version(
+        &self,
+        extension_id: &str,
+        include_pre_releases: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<ExtensionMetadata>>> {
+        let release_channel = ReleaseChannel::global(cx);
+        let versions = self.fetch_extension_versions(extension_id, cx);
+        cx.background_spawn(async move {
+            Ok(versions
+                .await?
+                .into_iter()
+                .filter(|version| {
+                    (include_pre_releases || !is_pre_release_version(&version.manifest.version))
+                        && is_version_compatible(release_channel, version)
+                })
+                .max_by(|a, b| {
+                    compare_extension_versions(&a.manifest.version, &b.manifest.version)
+                        .unwrap_or(Ordering::Equal)
+                }))
+        })
+    }
+
     pub fn fetch_extension_versions(
         &self,
         extension_id: &str,
@@ -610,11 +665,143 @@ impl ExtensionStore {
     }
 
     pub fn check_for_updates(&mut self, cx: &mut Context<Self>) {
+        self.install_pinned_versions(cx);
         let task = self.fetch_extensions_with_update_available(cx);
         cx.spawn(async move |this, cx| Self::upgrade_extensions(this, task.await?, cx).await)
             .detach();
     }
 
+    /// Installs the pinned version of any installed extension that is at a different version.
+    fn install_pinned_versions(&mut self, cx: &mut Context<Self>) {
+        let release_channel = ReleaseChannel::global(cx);
+        let extension_settings = ExtensionSettings::get_global(cx);
+        let extensions_to_install = self
+            .extension_index
+            .extensions
+            .iter()
+            .filter(|(_, entry)| !entry.dev)
+            .filter_map(|(id, entry)| {
+                let pinned_version = extension_settings.pinned_version(id)?;
+                (*pinned_version != entry.manifest.version)
+                    .then(|| (id.clone(), pinned_version.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        for (extension_id, pinned_version) in extensions_to_install {
+            let versions = self.fetch_extension_versions(&extension_id, cx);
+            cx.spawn(async move |this, cx| {
+                let version = versions
+                    .await?
+                    .into_iter()
+                    .find(|version| version.manifest.version == pinned_version)
+                    .with_context(|| {
+                        format!("extension {extension_id} has no version {pinned_version}")
+                    })?;
+                if !is_version_compatible(release_channel, &version) {
+                    bail!(
+                        "extension {extension_id} is pinned to version {pinned_version}, which is incompatible with this version of Zed"
+                    );
+                }
+                this.update(cx, |this, cx| {
+                    this.upgrade_extension(extension_id, pinned_version, cx)
+                })?
+                .await
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    /// Pins the given extension to the given version, or unpins it.
+    pub fn pin_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        version: Option<Arc<str>>,
+        cx: &mut Context<Self>,
+    ) {
+        settings::update_settings_file::<ExtensionSettings>(self.fs.clone(), cx, {
+            move |settings, _| match version {
+                Some(version) => {
+                    settings.pinned_extensions.insert(extension_id, version);
+                }
+                None => {
+                    settings.pinned_extensions.remove(&extension_id);
+                }
+            }
+        });
+    }
+
+    /// Sets whether the given extension should be updated to pre-release versions.
+    pub fn set_install_pre_releases(
+        &mut self,
+        extension_id: Arc<str>,
+        install_pre_releases: bool,
+        cx: &mut Context<Self>,
+    ) {
+        settings::update_settings_file::<ExtensionSettings>(self.fs.clone(), cx, {
+            move |settings, _| {
+                if install_pre_releases {
+                    settings.pre_release_extensions.insert(extension_id, true);
+                } else {
+                    settings.pre_release_extensions.remove(&extension_id);
+                }
+            }
+        });
+    }
+
+    /// Rolls the given extension back to the newest compatible version published before the
+    /// installed one, and pins it there so that it isn't updated again.
+    pub fn roll_back_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(installed_version) = self
+            .extension_index
+            .extensions
+            .get(&extension_id)
+            .filter(|entry| !entry.dev)
+            .map(|entry| entry.manifest.version.clone())
+        else {
+            return Task::ready(Err(anyhow!("extension {extension_id} is not installed")));
+        };
+
+        let release_channel = ReleaseChannel::global(cx);
+        let include_pre_releases = ExtensionSettings::get_global(cx)
+            .should_install_pre_releases(&extension_id)
+            || is_pre_release_version(&installed_version);
+        let versions = self.fetch_extension_versions(&extension_id, cx);
+        cx.spawn(async move |this, cx| {
+            let previous_version = versions
+                .await?
+                .into_iter()
+                .filter(|version| {
+                    (include_pre_releases || !is_pre_release_version(&version.manifest.version))
+                        && is_version_compatible(release_channel, version)
+                        && compare_extension_versions(
+                            &version.manifest.version,
+                            &installed_version,
+                        ) == Some(Ordering::Less)
+                })
+                .max_by(|a, b| {
+                    compare_extension_versions(&a.manifest.version, &b.manifest.version)
+                        .unwrap_or(Ordering::Equal)
+                })
+                .with_context(|| {
+                    format!(
+                        "extension {extension_id} has no compatible version older than {installed_version}"
+                    )
+                })?;
+
+            let version = previous_version.manifest.version;
+            log::info!("rolling back extension {extension_id} from {installed_version} to {version}");
+            this.update(cx, |this, cx| {
+                this.pin_extension(extension_id.clone(), Some(version.clone()), cx);
+                this.upgrade_extension(extension_id, version, cx)
+            })?
+            .await
+        })
+    }
+
     async fn upgrade_extensions(
         this: WeakEntity<Self>,
         extensions: Vec<ExtensionMetadata>,
@@ -625,12 +812,11 @@ impl ExtensionStore {
                 if let Some(installed_extension) =
                     this.extension_index.extensions.get(&extension.id)
                 {
-                    let installed_version =
-                        SemanticVersion::from_str(&installed_extension.manifest.version).ok()?;
-                    let latest_version =
-                        SemanticVersion::from_str(&extension.manifest.version).ok()?;
-
-                    if installed_version >= latest_version {
+                    let ordering = compare_extension_versions(
+                        &installed_extension.manifest.version,
+                        &extension.manifest.version,
+                    )?;
+                    if ordering != Ordering::Less {
                         return None;
                     }
                 }
@@ -1135,6 +1321,20 @@ impl ExtensionStore {
             for (server_id, _) in extension.manifest.context_servers.iter() {
                 self.proxy.unregister_context_server(server_id.clone(), cx);
             }
+
+            for (panel_id, _) in extension.manifest.panels.iter() {
+                self.proxy
+                    .unregister_panel(extension_id.clone(), panel_id.clone(), cx);
+            }
+
+            for (command_id, _) in extension.manifest.commands.iter() {
+                self.proxy
+                    .unregister_command(extension_id.clone(), command_id.clone(), cx);
+            }
+
+            for (scheme, _) in extension.manifest.document_providers.iter() {
+                self.proxy.unregister_document_provider(scheme.clone(), cx);
+            }
         }
 
         self.wasm_extensions
@@ -1334,6 +1534,33 @@ impl ExtensionStore {
                         this.proxy
                             .register_debug_adapter(extension.clone(), debug_adapter.clone());
                     }
+
+                    for (panel_id, panel) in &manifest.panels {
+                        this.proxy.register_panel(
+                            extension.clone(),
+                            panel_id.clone(),
+                            panel.clone(),
+                            cx,
+                        );
+                    }
+
+                    for (command_id, command) in &manifest.commands {
+                        this.proxy.register_command(
+                            extension.clone(),
+                            command_id.clone(),
+                            command.clone(),
+                            cx,
+                        );
+                    }
+
+                    for (scheme, provider) in &manifest.document_providers {
+                        this.proxy.register_document_provider(
+                            extension.clone(),
+                            scheme.clone(),
+                            provider.clone(),
+                            cx,
+                        );
+                    }
                 }
 
                 this.wasm_extensions.extend(wasm_extensions);
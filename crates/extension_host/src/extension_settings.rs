@@ -18,6 +18,19 @@ pub struct ExtensionSettings {
     pub auto_install_extensions: HashMap<Arc<str>, bool>,
     #[serde(default)]
     pub auto_update_extensions: HashMap<Arc<str>, bool>,
+    /// The extensions to keep at a specific version.
+    ///
+    /// Pinned extensions are installed at the given version and never auto-updated.
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub pinned_extensions: HashMap<Arc<str>, Arc<str>>,
+    /// The extensions to update to pre-release versions, such as `1.2.0-beta.1`, as well as
+    /// to releases.
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub pre_release_extensions: HashMap<Arc<str>, bool>,
 }
 
 impl ExtensionSettings {
@@ -30,11 +43,28 @@ impl ExtensionSettings {
     }
 
     pub fn should_auto_update(&self, extension_id: &str) -> bool {
+        if self.pinned_version(extension_id).is_some() {
+            return false;
+        }
+
         self.auto_update_extensions
             .get(extension_id)
             .copied()
             .unwrap_or(true)
     }
+
+    /// Returns the version the given extension is pinned to, if any.
+    pub fn pinned_version(&self, extension_id: &str) -> Option<&Arc<str>> {
+        self.pinned_extensions.get(extension_id)
+    }
+
+    /// Returns whether the given extension should be updated to pre-release versions.
+    pub fn should_install_pre_releases(&self, extension_id: &str) -> bool {
+        self.pre_release_extensions
+            .get(extension_id)
+            .copied()
+            .unwrap_or(false)
+    }
 }
 
 impl Settings for ExtensionSettings {
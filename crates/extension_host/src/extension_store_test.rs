@@ -1,7 +1,8 @@
 use crate::{
     Event, ExtensionIndex, ExtensionIndexEntry, ExtensionIndexLanguageEntry,
     ExtensionIndexThemeEntry, ExtensionManifest, ExtensionSettings, ExtensionStore,
-    GrammarManifestEntry, RELOAD_DEBOUNCE_DURATION, SchemaVersion,
+    GrammarManifestEntry, RELOAD_DEBOUNCE_DURATION, SchemaVersion, compare_extension_versions,
+    is_pre_release_version,
 };
 use async_compression::futures::bufread::GzipEncoder;
 use collections::BTreeMap;
@@ -20,6 +21,7 @@ use reqwest_client::ReqwestClient;
 use serde_json::json;
 use settings::{Settings as _, SettingsStore};
 use std::{
+    cmp::Ordering,
     ffi::OsString,
     path::{Path, PathBuf},
     sync::Arc,
@@ -163,6 +165,9 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         snippets: None,
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
+                        panels: Default::default(),
+                        commands: Default::default(),
+                        document_providers: Default::default(),
                     }),
                     dev: false,
                 },
@@ -193,6 +198,9 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         snippets: None,
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
+                        panels: Default::default(),
+                        commands: Default::default(),
+                        document_providers: Default::default(),
                     }),
                     dev: false,
                 },
@@ -368,6 +376,9 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 snippets: None,
                 capabilities: Vec::new(),
                 debug_adapters: Default::default(),
+                panels: Default::default(),
+                commands: Default::default(),
+                document_providers: Default::default(),
             }),
             dev: false,
         },
@@ -512,6 +523,45 @@ async fn test_extension_store(cx: &mut TestAppContext) {
     });
 }
 
+#[test]
+fn test_compare_extension_versions() {
+    let versions = [
+        "0.9.10",
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+        "1.0.1",
+    ];
+    for (ix, a) in versions.iter().enumerate() {
+        for (jx, b) in versions.iter().enumerate() {
+            assert_eq!(
+                compare_extension_versions(a, b),
+                Some(ix.cmp(&jx)),
+                "comparing {a} with {b}"
+            );
+        }
+    }
+
+    assert_eq!(compare_extension_versions("1.0", "1.0.0"), None);
+    assert_eq!(
+        compare_extension_versions("1.0.0", " 1.0.0 "),
+        Some(Ordering::Equal)
+    );
+}
+
+#[test]
+fn test_is_pre_release_version() {
+    assert!(is_pre_release_version("1.0.0-beta.1"));
+    assert!(is_pre_release_version("1.0.0-rc.1+build.5"));
+    assert!(!is_pre_release_version("1.0.0"));
+    assert!(!is_pre_release_version("1.0.0+build-5"));
+    assert!(!is_pre_release_version("1.0-beta"));
+}
+
 // todo(windows)
 // Disable this test on Windows for now. Because this test hangs at
 // `let fake_server = fake_servers.next().await.unwrap();`.
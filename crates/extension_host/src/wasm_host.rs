@@ -5,8 +5,9 @@ use anyhow::{Context as _, Result, anyhow, bail};
 use async_trait::async_trait;
 use extension::{
     CodeLabel, Command, Completion, ContextServerConfiguration, DebugAdapterBinary,
-    DebugTaskDefinition, ExtensionHostProxy, KeyValueStoreDelegate, ProjectDelegate, SlashCommand,
-    SlashCommandArgumentCompletion, SlashCommandOutput, Symbol, WorktreeDelegate,
+    DebugTaskDefinition, EditorDelegate, ExtensionHostProxy, KeyValueStoreDelegate, PanelEvent,
+    PanelView, ProjectDelegate, SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput,
+    Symbol, VirtualDocument, WorktreeDelegate,
 };
 use fs::{Fs, normalize_path};
 use futures::future::LocalBoxFuture;
@@ -412,6 +413,81 @@ impl extension::Extension for WasmExtension {
         })
         .await
     }
+
+    async fn run_command(
+        &self,
+        command_id: Arc<str>,
+        editor: Option<Arc<dyn EditorDelegate>>,
+    ) -> Result<()> {
+        self.call(|extension, store| {
+            async move {
+                let resource = if let Some(editor) = editor {
+                    Some(store.data_mut().table().push(editor)?)
+                } else {
+                    None
+                };
+
+                extension
+                    .call_run_command(store, &command_id, resource)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn load_document(&self, uri: Arc<str>) -> Result<VirtualDocument> {
+        self.call(|extension, store| {
+            async move {
+                extension
+                    .call_load_document(store, &uri)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn save_document(&self, uri: Arc<str>, text: String) -> Result<()> {
+        self.call(|extension, store| {
+            async move {
+                extension
+                    .call_save_document(store, &uri, &text)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn render_panel(&self, panel_id: Arc<str>) -> Result<PanelView> {
+        self.call(|extension, store| {
+            async move {
+                extension
+                    .call_render_panel(store, &panel_id)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn handle_panel_event(&self, panel_id: Arc<str>, event: PanelEvent) -> Result<PanelView> {
+        self.call(|extension, store| {
+            async move {
+                extension
+                    .call_handle_panel_event(store, &panel_id, event)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))
+            }
+            .boxed()
+        })
+        .await
+    }
 }
 
 pub struct WasmState {
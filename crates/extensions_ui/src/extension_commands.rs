@@ -0,0 +1,364 @@
+use std::any::TypeId;
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use async_trait::async_trait;
+use collections::HashMap;
+use command_palette_hooks::{CommandPaletteCommands, CommandPaletteFilter};
+use editor::{Bias, Editor, MultiBufferSnapshot};
+use extension::{
+    CommandManifestEntry, EditorDelegate, EditorSelection, Extension, ExtensionCommandProxy,
+    ExtensionHostProxy, TextEdit,
+};
+use futures::{
+    SinkExt as _, StreamExt as _,
+    channel::{mpsc, oneshot},
+};
+use gpui::{App, Context, Entity, Global, Window, impl_actions};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use workspace::Workspace;
+
+/// Runs the command registered under `command`, in the form `extension_id::command_id`.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Run {
+    pub command: String,
+}
+
+impl_actions!(extension_command, [Run]);
+
+pub(crate) fn init(cx: &mut App) {
+    ExtensionHostProxy::default_global(cx).register_command_proxy(ExtensionCommandRegistryProxy);
+    // Each command is listed under its own title instead.
+    CommandPaletteFilter::update_global(cx, |filter, _| {
+        filter.hide_action_types(&[TypeId::of::<Run>()]);
+    });
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, action: &Run, window, cx| {
+            run_command(workspace, &action.command, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Identifies a command by its extension as well as its ID, as different extensions may use the
+/// same command IDs.
+fn command_key(extension_id: &str, command_id: &str) -> Arc<str> {
+    format!("{extension_id}::{command_id}").into()
+}
+
+#[derive(Clone)]
+struct ExtensionCommand {
+    extension: Arc<dyn Extension>,
+    command_id: Arc<str>,
+}
+
+/// The commands contributed by the loaded extensions, keyed by [`command_key`].
+#[derive(Default)]
+struct ExtensionCommandRegistry {
+    commands: HashMap<Arc<str>, ExtensionCommand>,
+}
+
+impl Global for ExtensionCommandRegistry {}
+
+struct ExtensionCommandRegistryProxy;
+
+impl ExtensionCommandProxy for ExtensionCommandRegistryProxy {
+    fn register_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command_id: Arc<str>,
+        command: CommandManifestEntry,
+        cx: &mut App,
+    ) {
+        let key = command_key(&extension.manifest().id, &command_id);
+        CommandPaletteCommands::update_global(cx, |commands, _| {
+            commands.register(
+                key.to_string(),
+                command.title,
+                Box::new(Run {
+                    command: key.to_string(),
+                }),
+            );
+        });
+        cx.default_global::<ExtensionCommandRegistry>()
+            .commands
+            .insert(
+                key,
+                ExtensionCommand {
+                    extension,
+                    command_id,
+                },
+            );
+    }
+
+    fn unregister_command(&self, extension_id: Arc<str>, command_id: Arc<str>, cx: &mut App) {
+        let key = command_key(&extension_id, &command_id);
+        cx.default_global::<ExtensionCommandRegistry>()
+            .commands
+            .remove(&key);
+        CommandPaletteCommands::update_global(cx, |commands, _| {
+            commands.unregister(&key);
+        });
+    }
+}
+
+fn run_command(
+    workspace: &mut Workspace,
+    command_id: &str,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(command) = cx
+        .try_global::<ExtensionCommandRegistry>()
+        .and_then(|registry| registry.commands.get(command_id))
+        .cloned()
+    else {
+        workspace.show_error(
+            &anyhow!("no extension provides the command `{command_id}`"),
+            cx,
+        );
+        return;
+    };
+
+    let mut apply_edits = None;
+    let editor_delegate = workspace.active_item_as::<Editor>(cx).map(|editor| {
+        let (delegate, edits) = ExtensionEditor::new(&editor, cx);
+        apply_edits = Some((editor, edits));
+        Arc::new(delegate) as Arc<dyn EditorDelegate>
+    });
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        // Edits are requested from the extension's thread, but have to be applied on this one.
+        let _apply_edits = apply_edits.map(|(editor, mut edits)| {
+            cx.spawn(async move |cx| {
+                while let Some(request) = edits.next().await {
+                    let EditRequest {
+                        snapshot,
+                        edits,
+                        done,
+                    } = request;
+                    let result = editor
+                        .update(cx, |editor, cx| {
+                            apply_edits_to(editor, &snapshot, edits, cx)
+                        })
+                        .and_then(|result| result);
+                    done.send(result).ok();
+                }
+            })
+        });
+
+        if let Err(error) = command
+            .extension
+            .run_command(command.command_id.clone(), editor_delegate)
+            .await
+        {
+            log::error!(
+                "failed to run extension command {}: {error:?}",
+                command.command_id
+            );
+            workspace
+                .update(cx, |workspace, cx| workspace.show_error(&error, cx))
+                .ok();
+        }
+    })
+    .detach();
+}
+
+struct EditRequest {
+    snapshot: MultiBufferSnapshot,
+    edits: Vec<TextEdit>,
+    done: oneshot::Sender<Result<()>>,
+}
+
+/// The active editor as it was when a command was run, which the extension reads from and
+/// sends edits back through.
+struct ExtensionEditor {
+    path: Option<String>,
+    snapshot: MultiBufferSnapshot,
+    selections: Vec<EditorSelection>,
+    edits: mpsc::UnboundedSender<EditRequest>,
+}
+
+impl ExtensionEditor {
+    fn new(editor: &Entity<Editor>, cx: &mut App) -> (Self, mpsc::UnboundedReceiver<EditRequest>) {
+        let (edits_tx, edits_rx) = mpsc::unbounded();
+        let (path, snapshot, selections) = editor.update(cx, |editor, cx| {
+            let selections = editor.selections.all::<usize>(cx);
+            let buffer = editor.buffer().read(cx);
+            let path = buffer.as_singleton().and_then(|buffer| {
+                let file = buffer.read(cx).file()?;
+                Some(file.path().to_string_lossy().to_string())
+            });
+            let snapshot = buffer.snapshot(cx);
+            let selections = selections
+                .into_iter()
+                .map(|selection| EditorSelection {
+                    text: snapshot.text_for_range(selection.range()).collect(),
+                    range: selection.range(),
+                })
+                .collect();
+            (path, snapshot, selections)
+        });
+        let delegate = Self {
+            path,
+            snapshot,
+            selections,
+            edits: edits_tx,
+        };
+        (delegate, edits_rx)
+    }
+}
+
+#[async_trait]
+impl EditorDelegate for ExtensionEditor {
+    fn path(&self) -> Option<String> {
+        self.path.clone()
+    }
+
+    fn text(&self) -> String {
+        self.snapshot.text()
+    }
+
+    fn selections(&self) -> Vec<EditorSelection> {
+        self.selections.clone()
+    }
+
+    async fn edit(&self, edits: Vec<TextEdit>) -> Result<()> {
+        let (done_tx, done_rx) = oneshot::channel();
+        self.edits
+            .clone()
+            .send(EditRequest {
+                snapshot: self.snapshot.clone(),
+                edits,
+                done: done_tx,
+            })
+            .await
+            .context("the editor was closed")?;
+        done_rx.await.context("the editor was closed")?
+    }
+}
+
+fn apply_edits_to(
+    editor: &mut Editor,
+    snapshot: &MultiBufferSnapshot,
+    mut edits: Vec<TextEdit>,
+    cx: &mut Context<Editor>,
+) -> Result<()> {
+    if editor.read_only(cx) {
+        bail!("the editor is read-only");
+    }
+
+    edits.sort_by_key(|edit| edit.range.start);
+    let mut previous_end = 0;
+    for edit in &edits {
+        let range = &edit.range;
+        if range.start > range.end
+            || range.end > snapshot.len()
+            || snapshot.clip_offset(range.start, Bias::Left) != range.start
+            || snapshot.clip_offset(range.end, Bias::Left) != range.end
+        {
+            bail!("invalid edit range {range:?}");
+        }
+        if range.start < previous_end {
+            bail!("edit ranges overlap at {}", range.start);
+        }
+        previous_end = range.end;
+    }
+
+    // Anchor the edits to the text the extension saw, so that anything typed in the meantime
+    // doesn't shift them.
+    let edits = edits
+        .into_iter()
+        .map(|edit| {
+            let range =
+                snapshot.anchor_before(edit.range.start)..snapshot.anchor_after(edit.range.end);
+            (range, edit.new_text)
+        })
+        .collect::<Vec<_>>();
+    editor.edit(edits, cx);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use settings::SettingsStore;
+
+    use crate::fake_extension::FakeExtension;
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_commands_are_namespaced_per_extension(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let workspace = cx.add_window(|window, cx| Workspace::test_new(project, window, cx));
+
+        let first = FakeExtension::new("first");
+        let second = FakeExtension::new("second");
+        let proxy = cx.update(ExtensionHostProxy::global);
+        cx.update(|cx| {
+            for extension in [first.clone(), second.clone()] {
+                proxy.register_command(
+                    extension,
+                    "sort".into(),
+                    CommandManifestEntry {
+                        title: "Sort Lines".into(),
+                    },
+                    cx,
+                );
+            }
+        });
+
+        cx.update(|cx| {
+            let commands = CommandPaletteCommands::try_global(cx).unwrap();
+            let names = commands
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["Sort Lines", "Sort Lines"]);
+        });
+
+        workspace
+            .update(cx, |workspace, window, cx| {
+                run_command(workspace, "second::sort", window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert!(first.commands_run.lock().is_empty());
+        assert_eq!(*second.commands_run.lock(), [Arc::<str>::from("sort")]);
+
+        cx.update(|cx| proxy.unregister_command("second".into(), "sort".into(), cx));
+        cx.update(|cx| {
+            assert_eq!(
+                CommandPaletteCommands::try_global(cx)
+                    .unwrap()
+                    .iter()
+                    .count(),
+                1
+            );
+            let registry = cx.global::<ExtensionCommandRegistry>();
+            assert!(registry.commands.contains_key("first::sort"));
+            assert!(!registry.commands.contains_key("second::sort"));
+        });
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init_settings(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            command_palette_hooks::init(cx);
+            init(cx);
+        });
+    }
+}
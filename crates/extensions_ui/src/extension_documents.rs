@@ -0,0 +1,378 @@
+use std::any::TypeId;
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use collections::HashMap;
+use command_palette_hooks::{
+    CommandArgumentPrompt, CommandInterceptResult, CommandPaletteArgumentPrompts,
+};
+use editor::{Editor, EditorEvent};
+use extension::{
+    DocumentProviderManifestEntry, Extension, ExtensionDocumentProviderProxy, ExtensionHostProxy,
+};
+use gpui::{
+    Action as _, AnyView, App, Context, Entity, EventEmitter, FocusHandle, Focusable, Global,
+    Subscription, Task, Window, actions, impl_actions,
+};
+use language::{Buffer, Capability, Language};
+use project::Project;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::{Item, Workspace, item::ItemEvent, searchable::SearchableItemHandle};
+
+/// Opens the document at `uri`, served by the extension that registered the URI's scheme.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Open {
+    pub uri: String,
+}
+
+impl_actions!(extension_document, [Open]);
+
+actions!(extension_document, [Refresh]);
+
+pub(crate) fn init(cx: &mut App) {
+    ExtensionHostProxy::default_global(cx)
+        .register_document_provider_proxy(ExtensionDocumentRegistryProxy);
+    CommandPaletteArgumentPrompts::update_global(cx, |prompts, _| {
+        prompts.register::<Open>(CommandArgumentPrompt {
+            placeholder: "Document URI, such as dep:serde/src/lib.rs".into(),
+            build: Box::new(|argument, _| {
+                let uri = argument.trim();
+                let (scheme, _) = uri.split_once(':')?;
+                if scheme.is_empty() {
+                    return None;
+                }
+                Some(CommandInterceptResult {
+                    action: Open {
+                        uri: uri.to_string(),
+                    }
+                    .boxed_clone(),
+                    string: format!("Open {uri}"),
+                    positions: Vec::new(),
+                })
+            }),
+        });
+    });
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, action: &Open, window, cx| {
+            open_document(workspace, Arc::from(action.uri.as_str()), window, cx);
+        });
+    })
+    .detach();
+}
+
+/// The extensions serving documents, keyed by the URI scheme they serve.
+#[derive(Default)]
+struct ExtensionDocumentRegistry {
+    providers: HashMap<Arc<str>, Arc<dyn Extension>>,
+}
+
+impl Global for ExtensionDocumentRegistry {}
+
+struct ExtensionDocumentRegistryProxy;
+
+impl ExtensionDocumentProviderProxy for ExtensionDocumentRegistryProxy {
+    fn register_document_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        scheme: Arc<str>,
+        _provider: DocumentProviderManifestEntry,
+        cx: &mut App,
+    ) {
+        cx.default_global::<ExtensionDocumentRegistry>()
+            .providers
+            .insert(scheme, extension);
+    }
+
+    fn unregister_document_provider(&self, scheme: Arc<str>, cx: &mut App) {
+        cx.default_global::<ExtensionDocumentRegistry>()
+            .providers
+            .remove(&scheme);
+    }
+}
+
+fn open_document(
+    workspace: &mut Workspace,
+    uri: Arc<str>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    if let Some(document) = workspace
+        .items_of_type::<ExtensionDocument>(cx)
+        .find(|document| document.read(cx).uri == uri)
+    {
+        workspace.activate_item(&document, true, true, window, cx);
+        return;
+    }
+
+    let extension = uri.split_once(':').and_then(|(scheme, _)| {
+        cx.try_global::<ExtensionDocumentRegistry>()?
+            .providers
+            .get(scheme)
+            .cloned()
+    });
+    let Some(extension) = extension else {
+        workspace.show_error(&anyhow!("no extension serves the document `{uri}`"), cx);
+        return;
+    };
+
+    let project = workspace.project().clone();
+    cx.spawn_in(window, async move |workspace, cx| {
+        let result = async {
+            let document = extension.load_document(uri.clone()).await?;
+            let language = match &document.language {
+                Some(name) => project
+                    .read_with(cx, |project, _| project.languages().language_for_name(name))?
+                    .await
+                    .log_err(),
+                None => None,
+            };
+            workspace.update_in(cx, |workspace, window, cx| {
+                let document = cx.new(|cx| {
+                    ExtensionDocument::new(
+                        uri.clone(),
+                        extension,
+                        document.text,
+                        language,
+                        document.writable,
+                        project,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.add_item_to_active_pane(Box::new(document), None, true, window, cx);
+            })
+        }
+        .await;
+
+        if let Err(error) = result {
+            log::error!("failed to open extension document {uri}: {error:?}");
+            workspace
+                .update(cx, |workspace, cx| workspace.show_error(&error, cx))
+                .ok();
+        }
+    })
+    .detach();
+}
+
+/// A document served by an extension, shown in an editor that is read-only unless the
+/// extension accepts edits to it.
+pub struct ExtensionDocument {
+    uri: Arc<str>,
+    extension: Arc<dyn Extension>,
+    buffer: Entity<Buffer>,
+    editor: Entity<Editor>,
+    writable: bool,
+    _editor_subscription: Subscription,
+}
+
+impl ExtensionDocument {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        uri: Arc<str>,
+        extension: Arc<dyn Extension>,
+        text: String,
+        language: Option<Arc<Language>>,
+        writable: bool,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let buffer = cx.new(|cx| {
+            let mut buffer = Buffer::local(text, cx);
+            buffer.set_language(language, cx);
+            buffer.set_capability(capability(writable), cx);
+            buffer
+        });
+        let editor = cx.new(|cx| Editor::for_buffer(buffer.clone(), Some(project), window, cx));
+        let editor_subscription = cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        });
+
+        Self {
+            uri,
+            extension,
+            buffer,
+            editor,
+            writable,
+            _editor_subscription: editor_subscription,
+        }
+    }
+
+    /// Replaces the document's text with what the extension serves now.
+    fn refresh(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let extension = self.extension.clone();
+        let uri = self.uri.clone();
+        cx.spawn(async move |this, cx| {
+            let document = extension.load_document(uri).await?;
+            this.update(cx, |this, cx| {
+                this.writable = document.writable;
+                this.buffer.update(cx, |buffer, cx| {
+                    buffer.set_capability(capability(document.writable), cx);
+                    buffer.set_text(document.text, cx);
+                    buffer.did_save(buffer.version(), None, cx);
+                });
+            })
+        })
+    }
+
+    fn refresh_action(&mut self, _: &Refresh, window: &mut Window, cx: &mut Context<Self>) {
+        let refresh = self.refresh(cx);
+        cx.spawn_in(window, async move |this, cx| {
+            if let Err(error) = refresh.await {
+                log::error!("failed to refresh extension document: {error:?}");
+                this.update(cx, |this, cx| {
+                    let Some(workspace) = this.editor.read(cx).workspace() else {
+                        return;
+                    };
+                    workspace.update(cx, |workspace, cx| workspace.show_error(&error, cx));
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+}
+
+fn capability(writable: bool) -> Capability {
+    if writable {
+        Capability::ReadWrite
+    } else {
+        Capability::ReadOnly
+    }
+}
+
+impl Render for ExtensionDocument {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .key_context("ExtensionDocument")
+            .on_action(cx.listener(Self::refresh_action))
+            .child(self.editor.clone())
+    }
+}
+
+impl Focusable for ExtensionDocument {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<EditorEvent> for ExtensionDocument {}
+
+impl Item for ExtensionDocument {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Blocks))
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        self.uri.to_string().into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(self.uri.to_string().into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            Item::added_to_workspace(editor, workspace, window, cx)
+        });
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn std::any::Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| Item::navigate(editor, data, window, cx))
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: workspace::ItemNavHistory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            Item::set_nav_history(editor, nav_history, window, cx)
+        });
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.buffer.read(cx).is_dirty()
+    }
+
+    fn can_save(&self, _cx: &App) -> bool {
+        self.writable
+    }
+
+    fn save(
+        &mut self,
+        _format: bool,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let (text, version) = {
+            let buffer = self.buffer.read(cx);
+            (buffer.text(), buffer.version())
+        };
+        let extension = self.extension.clone();
+        let uri = self.uri.clone();
+        let buffer = self.buffer.clone();
+        cx.spawn(async move |_, cx| {
+            extension.save_document(uri, text).await?;
+            buffer.update(cx, |buffer, cx| buffer.did_save(version, None, cx))
+        })
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.refresh(cx)
+    }
+}
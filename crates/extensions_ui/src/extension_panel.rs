@@ -0,0 +1,751 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use editor::Editor;
+use extension::{
+    Extension, ExtensionHostProxy, ExtensionPanelProxy, PanelElement, PanelEvent,
+    PanelManifestEntry, PanelView,
+};
+use gpui::{
+    Action, App, Context, Entity, EntityId, EventEmitter, FocusHandle, Focusable, Subscription,
+    Task, WeakEntity, Window, impl_actions,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ui::{Divider, ListItem, ListItemSpacing, Tab, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent as DockPanelEvent},
+};
+
+const EXTENSION_PANEL_KEY: &str = "ExtensionPanel";
+const DEFAULT_PANEL_SIZE: Pixels = px(300.);
+
+/// Focuses the panel registered under `panel_id`, in the form `extension_id::panel_id`, or hides
+/// it if it is already focused.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ToggleFocus {
+    pub panel_id: String,
+}
+
+impl_actions!(extension_panel, [ToggleFocus]);
+
+pub(crate) fn init(cx: &mut App) {
+    let registry = cx.new(|_| ExtensionPanelRegistry::default());
+    ExtensionHostProxy::default_global(cx).register_panel_proxy(ExtensionPanelRegistryProxy {
+        registry: registry.clone(),
+    });
+
+    cx.observe_new(move |workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+
+        let keys = registry.read(cx).panels.keys().cloned().collect::<Vec<_>>();
+        for key in keys {
+            open_extension_panel(registry.clone(), key, window, cx);
+        }
+
+        let registry = registry.clone();
+        workspace.register_action(move |workspace, action: &ToggleFocus, window, cx| {
+            let panel = registry
+                .read(cx)
+                .open_panels
+                .get(&(cx.entity_id(), Arc::from(action.panel_id.as_str())))
+                .and_then(WeakEntity::upgrade);
+            if let Some(panel) = panel {
+                workspace.toggle_panel_focus_for_id(panel.entity_id(), window, cx);
+            }
+        });
+    })
+    .detach();
+}
+
+/// Identifies a panel by its extension as well as its ID, as different extensions may use the
+/// same panel IDs.
+fn panel_key(extension_id: &str, panel_id: &str) -> Arc<str> {
+    format!("{extension_id}::{panel_id}").into()
+}
+
+#[derive(Clone)]
+struct ExtensionPanelDescriptor {
+    extension: Arc<dyn Extension>,
+    panel_id: Arc<str>,
+    title: SharedString,
+}
+
+enum ExtensionPanelRegistryEvent {
+    PanelUnregistered(Arc<str>),
+}
+
+/// The panels contributed by the loaded extensions, and the instances of them open in each
+/// workspace, keyed by [`panel_key`].
+#[derive(Default)]
+struct ExtensionPanelRegistry {
+    panels: HashMap<Arc<str>, ExtensionPanelDescriptor>,
+    open_panels: HashMap<(EntityId, Arc<str>), WeakEntity<ExtensionPanel>>,
+}
+
+impl EventEmitter<ExtensionPanelRegistryEvent> for ExtensionPanelRegistry {}
+
+struct ExtensionPanelRegistryProxy {
+    registry: Entity<ExtensionPanelRegistry>,
+}
+
+impl ExtensionPanelProxy for ExtensionPanelRegistryProxy {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        panel: PanelManifestEntry,
+        cx: &mut App,
+    ) {
+        let key = panel_key(&extension.manifest().id, &panel_id);
+        let descriptor = ExtensionPanelDescriptor {
+            extension,
+            panel_id,
+            title: panel.title.into(),
+        };
+        self.registry.update(cx, |registry, _| {
+            registry.panels.insert(key.clone(), descriptor);
+        });
+
+        for workspace in cx
+            .windows()
+            .into_iter()
+            .filter_map(|window| window.downcast::<Workspace>())
+        {
+            workspace
+                .update(cx, |_, window, cx| {
+                    open_extension_panel(self.registry.clone(), key.clone(), window, cx);
+                })
+                .log_err();
+        }
+    }
+
+    fn unregister_panel(&self, extension_id: Arc<str>, panel_id: Arc<str>, cx: &mut App) {
+        let key = panel_key(&extension_id, &panel_id);
+        self.registry.update(cx, |registry, cx| {
+            registry.panels.remove(&key);
+            registry
+                .open_panels
+                .retain(|(_, open_panel_key), _| *open_panel_key != key);
+            cx.emit(ExtensionPanelRegistryEvent::PanelUnregistered(key));
+        });
+    }
+}
+
+/// Where a panel was docked and how large it was, shared by every workspace.
+#[derive(Serialize, Deserialize)]
+struct SerializedExtensionPanel {
+    position: DockPosition,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+fn serialization_key(key: &str) -> String {
+    format!("{EXTENSION_PANEL_KEY}-{key}")
+}
+
+fn open_extension_panel(
+    registry: Entity<ExtensionPanelRegistry>,
+    key: Arc<str>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let serialization_key = serialization_key(&key);
+    cx.spawn_in(window, async move |workspace, cx| {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+            .await
+            .context("loading extension panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedExtensionPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let workspace_id = cx.entity_id();
+            let registry_state = registry.read(cx);
+            // The extension may have been unloaded or reloaded while the panel was loading.
+            let Some(descriptor) = registry_state.panels.get(&key).cloned() else {
+                return;
+            };
+            if registry_state
+                .open_panels
+                .get(&(workspace_id, key.clone()))
+                .is_some_and(|panel| panel.upgrade().is_some())
+            {
+                return;
+            }
+
+            let workspace_handle = cx.entity().downgrade();
+            let panel = cx.new(|cx| {
+                ExtensionPanel::new(
+                    key.clone(),
+                    descriptor,
+                    serialized_panel,
+                    workspace_handle,
+                    &registry,
+                    window,
+                    cx,
+                )
+            });
+            registry.update(cx, |registry, _| {
+                registry
+                    .open_panels
+                    .retain(|_, panel| panel.upgrade().is_some());
+                registry
+                    .open_panels
+                    .insert((workspace_id, key), panel.downgrade());
+            });
+            workspace.add_panel(panel, window, cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+struct PanelInputState {
+    editor: Entity<Editor>,
+    /// The value the extension last gave the input, so that the text is only replaced when the
+    /// extension changes it rather than on every update.
+    value: String,
+}
+
+/// A dockable panel whose contents are described by an extension and rendered with Zed's own
+/// UI components.
+pub struct ExtensionPanel {
+    key: Arc<str>,
+    panel_id: Arc<str>,
+    title: SharedString,
+    extension: Arc<dyn Extension>,
+    workspace: WeakEntity<Workspace>,
+    view: Option<PanelView>,
+    error: Option<SharedString>,
+    inputs: HashMap<String, PanelInputState>,
+    selected_item: Option<String>,
+    position: DockPosition,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    focus_handle: FocusHandle,
+    pending_update: Option<Task<()>>,
+    pending_serialization: Task<Option<()>>,
+    _subscription: Subscription,
+}
+
+impl ExtensionPanel {
+    fn new(
+        key: Arc<str>,
+        descriptor: ExtensionPanelDescriptor,
+        serialized_panel: Option<SerializedExtensionPanel>,
+        workspace: WeakEntity<Workspace>,
+        registry: &Entity<ExtensionPanelRegistry>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.subscribe_in(registry, window, |this, _, event, window, cx| {
+            let ExtensionPanelRegistryEvent::PanelUnregistered(key) = event;
+            if *key == this.key {
+                let panel = cx.entity();
+                let workspace = this.workspace.clone();
+                // Closing the dock deactivates the panel, which can't happen while it's updating.
+                window.defer(cx, move |window, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| {
+                            workspace.remove_panel(&panel, window, cx)
+                        })
+                        .ok();
+                });
+            }
+        });
+
+        Self {
+            key,
+            panel_id: descriptor.panel_id,
+            title: descriptor.title,
+            extension: descriptor.extension,
+            workspace,
+            view: None,
+            error: None,
+            inputs: HashMap::default(),
+            selected_item: None,
+            position: serialized_panel
+                .as_ref()
+                .map_or(DockPosition::Left, |panel| panel.position),
+            width: serialized_panel.as_ref().and_then(|panel| panel.width),
+            height: serialized_panel.as_ref().and_then(|panel| panel.height),
+            focus_handle: cx.focus_handle(),
+            pending_update: None,
+            pending_serialization: Task::ready(None),
+            _subscription: subscription,
+        }
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let serialization_key = serialization_key(&self.key);
+        let serialized_panel = SerializedExtensionPanel {
+            position: self.position,
+            width: self.width,
+            height: self.height,
+        };
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(serialization_key, serde_json::to_string(&serialized_panel)?)
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn refresh(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let extension = self.extension.clone();
+        let panel_id = self.panel_id.clone();
+        self.update_view(
+            async move { extension.render_panel(panel_id).await },
+            window,
+            cx,
+        );
+    }
+
+    fn send_event(&mut self, event: PanelEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let extension = self.extension.clone();
+        let panel_id = self.panel_id.clone();
+        self.update_view(
+            async move { extension.handle_panel_event(panel_id, event).await },
+            window,
+            cx,
+        );
+    }
+
+    fn update_view(
+        &mut self,
+        view: impl Future<Output = Result<PanelView>> + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pending_update = Some(cx.spawn_in(window, async move |this, cx| {
+            let view = view.await;
+            this.update_in(cx, |this, window, cx| {
+                this.pending_update = None;
+                match view {
+                    Ok(view) => {
+                        this.error = None;
+                        this.sync_inputs(&view, window, cx);
+                        this.view = Some(view);
+                    }
+                    Err(error) => {
+                        log::error!("failed to update extension panel {}: {error:?}", this.key);
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn sync_inputs(&mut self, view: &PanelView, window: &mut Window, cx: &mut Context<Self>) {
+        let mut inputs = HashMap::default();
+        for element in &view.elements {
+            let PanelElement::Input(input) = element else {
+                continue;
+            };
+            let state = match self.inputs.remove(&input.id) {
+                Some(state) => {
+                    if state.value != input.value {
+                        state.editor.update(cx, |editor, cx| {
+                            editor.set_text(input.value.as_str(), window, cx)
+                        });
+                    }
+                    PanelInputState {
+                        editor: state.editor,
+                        value: input.value.clone(),
+                    }
+                }
+                None => {
+                    let editor = cx.new(|cx| {
+                        let mut editor = Editor::single_line(window, cx);
+                        editor.set_placeholder_text(input.placeholder.as_str(), cx);
+                        editor.set_text(input.value.as_str(), window, cx);
+                        editor
+                    });
+                    PanelInputState {
+                        editor,
+                        value: input.value.clone(),
+                    }
+                }
+            };
+            inputs.insert(input.id.clone(), state);
+        }
+        self.inputs = inputs;
+    }
+
+    fn select_item(&mut self, id: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_item = Some(id.clone());
+        self.send_event(PanelEvent::ItemSelected(id), window, cx);
+        cx.notify();
+    }
+
+    fn render_element(
+        &self,
+        ix: usize,
+        element: &PanelElement,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        match element {
+            PanelElement::Label(text) => Label::new(text.clone())
+                .size(LabelSize::Small)
+                .into_any_element(),
+            PanelElement::Button(button) => {
+                let id = button.id.clone();
+                Button::new(
+                    SharedString::from(format!("{ix}-{id}")),
+                    button.label.clone(),
+                )
+                .style(ButtonStyle::Filled)
+                .full_width()
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.send_event(PanelEvent::ButtonClicked(id.clone()), window, cx)
+                }))
+                .into_any_element()
+            }
+            PanelElement::Input(input) => {
+                let Some(state) = self.inputs.get(&input.id) else {
+                    return div().into_any_element();
+                };
+                let id = input.id.clone();
+                let editor = state.editor.clone();
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .on_action(cx.listener(move |this, _: &menu::Confirm, window, cx| {
+                        let value = editor.read(cx).text(cx);
+                        this.send_event(
+                            PanelEvent::InputSubmitted {
+                                id: id.clone(),
+                                value,
+                            },
+                            window,
+                            cx,
+                        )
+                    }))
+                    .child(state.editor.clone())
+                    .into_any_element()
+            }
+            PanelElement::Items(items) => v_flex()
+                .children(items.iter().map(|item| {
+                    let id = item.id.clone();
+                    ListItem::new(SharedString::from(format!("{ix}-{id}")))
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(self.selected_item.as_ref() == Some(&item.id))
+                        .child(Label::new(item.label.clone()).size(LabelSize::Small))
+                        .end_slot(item.detail.clone().map(|detail| {
+                            Label::new(detail)
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted)
+                        }))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_item(id.clone(), window, cx)
+                        }))
+                }))
+                .into_any_element(),
+            PanelElement::Tree(items) => v_flex()
+                .children(items.iter().map(|item| {
+                    let id = item.id.clone();
+                    let toggled_id = item.id.clone();
+                    ListItem::new(SharedString::from(format!("{ix}-{id}")))
+                        .spacing(ListItemSpacing::Sparse)
+                        .indent_level(item.depth as usize)
+                        .indent_step_size(px(12.))
+                        .toggle(item.expanded)
+                        .toggle_state(self.selected_item.as_ref() == Some(&item.id))
+                        .on_toggle(cx.listener(move |this, _, window, cx| {
+                            this.send_event(
+                                PanelEvent::TreeItemToggled(toggled_id.clone()),
+                                window,
+                                cx,
+                            )
+                        }))
+                        .child(Label::new(item.label.clone()).size(LabelSize::Small))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_item(id.clone(), window, cx)
+                        }))
+                }))
+                .into_any_element(),
+            PanelElement::Separator => Divider::horizontal().into_any_element(),
+        }
+    }
+}
+
+impl Render for ExtensionPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let elements = self
+            .view
+            .as_ref()
+            .map(|view| {
+                view.elements
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, element)| self.render_element(ix, element, cx))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        v_flex()
+            .key_context("ExtensionPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new(self.title.clone()))
+                    .child(
+                        IconButton::new("refresh-extension-panel", IconName::RotateCw)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.pending_update.is_some())
+                            .on_click(cx.listener(|this, _, window, cx| this.refresh(window, cx))),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("extension-panel-elements")
+                    .flex_grow()
+                    .overflow_y_scroll()
+                    .p_2()
+                    .gap_1()
+                    .when_some(self.error.clone(), |this, error| {
+                        this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+                    })
+                    .children(elements),
+            )
+    }
+}
+
+impl Focusable for ExtensionPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<DockPanelEvent> for ExtensionPanel {}
+
+impl Panel for ExtensionPanel {
+    fn persistent_name() -> &'static str {
+        EXTENSION_PANEL_KEY
+    }
+
+    fn panel_key(&self) -> SharedString {
+        self.key.clone().into()
+    }
+
+    fn position(&self, _: &Window, _: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn size(&self, _: &Window, _: &App) -> Pixels {
+        match self.position {
+            DockPosition::Left | DockPosition::Right => self.width,
+            DockPosition::Bottom => self.height,
+        }
+        .unwrap_or(DEFAULT_PANEL_SIZE)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        match self.position {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, window: &mut Window, cx: &mut Context<Self>) {
+        // Extensions are only asked for a panel's contents once it is shown.
+        if active && self.view.is_none() && self.pending_update.is_none() {
+            self.refresh(window, cx);
+        }
+    }
+
+    fn icon(&self, _: &Window, _: &App) -> Option<IconName> {
+        Some(IconName::Blocks)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<SharedString> {
+        Some(self.title.clone())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus {
+            panel_id: self.key.to_string(),
+        })
+    }
+
+    fn activation_priority(&self) -> u32 {
+        12
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use settings::SettingsStore;
+
+    use crate::fake_extension::FakeExtension;
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_panels_are_keyed_per_extension(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let workspace = cx.add_window(|window, cx| Workspace::test_new(project, window, cx));
+
+        let proxy = cx.update(ExtensionHostProxy::global);
+        cx.update(|cx| {
+            for extension_id in ["first", "second"] {
+                proxy.register_panel(
+                    FakeExtension::new(extension_id),
+                    "main".into(),
+                    PanelManifestEntry {
+                        title: "Main".into(),
+                    },
+                    cx,
+                );
+            }
+        });
+        cx.run_until_parked();
+
+        workspace
+            .update(cx, |workspace, _, cx| {
+                let dock = workspace.left_dock().read(cx);
+                assert!(
+                    dock.panel_index_for_persistent_name("first::main", cx)
+                        .is_some()
+                );
+                assert!(
+                    dock.panel_index_for_persistent_name("second::main", cx)
+                        .is_some()
+                );
+            })
+            .unwrap();
+
+        cx.update(|cx| proxy.unregister_panel("first".into(), "main".into(), cx));
+        cx.run_until_parked();
+
+        workspace
+            .update(cx, |workspace, _, cx| {
+                let dock = workspace.left_dock().read(cx);
+                assert!(
+                    dock.panel_index_for_persistent_name("first::main", cx)
+                        .is_none()
+                );
+                assert!(
+                    dock.panel_index_for_persistent_name("second::main", cx)
+                        .is_some()
+                );
+            })
+            .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_panel_position_is_restored(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.executor().allow_parking();
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+
+        let proxy = cx.update(ExtensionHostProxy::global);
+        cx.update(|cx| {
+            proxy.register_panel(
+                FakeExtension::new("restored"),
+                "main".into(),
+                PanelManifestEntry {
+                    title: "Main".into(),
+                },
+                cx,
+            )
+        });
+        let workspace =
+            cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        let serialization = workspace
+            .update(cx, |workspace, window, cx| {
+                let panel = workspace
+                    .left_dock()
+                    .read(cx)
+                    .panel::<ExtensionPanel>()
+                    .unwrap();
+                panel.update(cx, |panel, cx| {
+                    panel.set_position(DockPosition::Bottom, window, cx);
+                    std::mem::replace(&mut panel.pending_serialization, Task::ready(None))
+                })
+            })
+            .unwrap();
+        serialization.await;
+
+        let workspace = cx.add_window(|window, cx| Workspace::test_new(project, window, cx));
+        cx.run_until_parked();
+
+        workspace
+            .update(cx, |workspace, _, cx| {
+                assert!(
+                    workspace
+                        .bottom_dock()
+                        .read(cx)
+                        .panel_index_for_persistent_name("restored::main", cx)
+                        .is_some()
+                );
+                assert!(
+                    workspace
+                        .left_dock()
+                        .read(cx)
+                        .panel_index_for_persistent_name("restored::main", cx)
+                        .is_none()
+                );
+            })
+            .unwrap();
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init_settings(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            init(cx);
+        });
+    }
+}
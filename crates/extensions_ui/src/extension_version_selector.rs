@@ -1,15 +1,11 @@
-use std::str::FromStr;
 use std::sync::Arc;
 
 use client::ExtensionMetadata;
-use extension_host::{ExtensionSettings, ExtensionStore};
-use fs::Fs;
+use extension_host::ExtensionStore;
 use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
 use gpui::{App, DismissEvent, Entity, EventEmitter, Focusable, Task, WeakEntity, prelude::*};
 use picker::{Picker, PickerDelegate};
 use release_channel::ReleaseChannel;
-use semantic_version::SemanticVersion;
-use settings::update_settings_file;
 use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
 use util::ResultExt;
 use workspace::ModalView;
@@ -46,7 +42,6 @@ impl ExtensionVersionSelector {
 }
 
 pub struct ExtensionVersionSelectorDelegate {
-    fs: Arc<dyn Fs>,
     selector: WeakEntity<ExtensionVersionSelector>,
     extension_versions: Vec<ExtensionMetadata>,
     selected_index: usize,
@@ -55,19 +50,18 @@ pub struct ExtensionVersionSelectorDelegate {
 
 impl ExtensionVersionSelectorDelegate {
     pub fn new(
-        fs: Arc<dyn Fs>,
         selector: WeakEntity<ExtensionVersionSelector>,
         mut extension_versions: Vec<ExtensionMetadata>,
     ) -> Self {
-        extension_versions.sort_unstable_by(|a, b| {
-            let a_version = SemanticVersion::from_str(&a.manifest.version);
-            let b_version = SemanticVersion::from_str(&b.manifest.version);
-
-            match (a_version, b_version) {
-                (Ok(a_version), Ok(b_version)) => b_version.cmp(&a_version),
-                _ => b.published_at.cmp(&a.published_at),
-            }
-        });
+        extension_versions.sort_unstable_by(
+            |a, b| match extension_host::compare_extension_versions(
+                &b.manifest.version,
+                &a.manifest.version,
+            ) {
+                Some(ordering) => ordering,
+                None => b.published_at.cmp(&a.published_at),
+            },
+        );
 
         let matches = extension_versions
             .iter()
@@ -80,7 +74,6 @@ impl ExtensionVersionSelectorDelegate {
             .collect();
 
         Self {
-            fs,
             selector,
             extension_versions,
             selected_index: 0,
@@ -182,13 +175,7 @@ impl PickerDelegate for ExtensionVersionSelectorDelegate {
             let extension_id = extension_version.id.clone();
             let version = extension_version.manifest.version.clone();
 
-            update_settings_file::<ExtensionSettings>(self.fs.clone(), cx, {
-                let extension_id = extension_id.clone();
-                move |settings, _| {
-                    settings.auto_update_extensions.insert(extension_id, false);
-                }
-            });
-
+            store.pin_extension(extension_id.clone(), Some(version.clone()), cx);
             store.install_extension(extension_id, version, cx);
         });
     }
@@ -232,6 +219,18 @@ impl PickerDelegate for ExtensionVersionSelectorDelegate {
                         .when(!is_version_compatible, |this| {
                             this.child(Label::new("Incompatible").color(Color::Muted))
                         })
+                        .when(
+                            extension_host::is_pre_release_version(
+                                &extension_version.manifest.version,
+                            ),
+                            |this| {
+                                this.child(
+                                    Label::new("Pre-release")
+                                        .color(Color::Warning)
+                                        .when(disabled, |label| label.color(Color::Muted)),
+                                )
+                            },
+                        )
                         .child(
                             Label::new(
                                 extension_version
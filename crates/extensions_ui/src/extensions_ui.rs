@@ -1,6 +1,11 @@
 mod components;
+mod extension_commands;
+mod extension_documents;
+mod extension_panel;
 mod extension_suggest;
 mod extension_version_selector;
+#[cfg(test)]
+mod fake_extension;
 
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -9,7 +14,7 @@ use std::{ops::Range, sync::Arc};
 use client::{ExtensionMetadata, ExtensionProvides};
 use collections::{BTreeMap, BTreeSet};
 use editor::{Editor, EditorElement, EditorStyle};
-use extension_host::{ExtensionManifest, ExtensionOperation, ExtensionStore};
+use extension_host::{ExtensionManifest, ExtensionOperation, ExtensionSettings, ExtensionStore};
 use fuzzy::{StringMatchCandidate, match_strings};
 use gpui::{
     Action, App, ClipboardItem, Context, Entity, EventEmitter, Flatten, Focusable,
@@ -41,6 +46,10 @@ use crate::extension_version_selector::{
 actions!(zed, [InstallDevExtension]);
 
 pub fn init(cx: &mut App) {
+    extension_commands::init(cx);
+    extension_documents::init(cx);
+    extension_panel::init(cx);
+
     cx.observe_new(move |workspace: &mut Workspace, window, cx| {
         let Some(window) = window else {
             return;
@@ -683,6 +692,9 @@ impl ExtensionsPage {
             ExtensionStatus::Installed(installed_version) => Some(installed_version),
             _ => None,
         };
+        let is_pinned = ExtensionSettings::get_global(cx)
+            .pinned_version(&extension.id)
+            .is_some();
 
         ExtensionCard::new()
             .overridden_by_dev_extension(has_dev_extension)
@@ -699,12 +711,27 @@ impl ExtensionsPage {
                             .child(Headline::new(format!("v{version}")).size(HeadlineSize::XSmall))
                             .children(
                                 installed_version
+                                    .clone()
                                     .filter(|installed_version| *installed_version != version)
                                     .map(|installed_version| {
                                         Headline::new(format!("(v{installed_version} installed)",))
                                             .size(HeadlineSize::XSmall)
                                     }),
                             )
+                            .when(extension_host::is_pre_release_version(&version), |this| {
+                                this.child(
+                                    Label::new("Pre-release")
+                                        .size(LabelSize::XSmall)
+                                        .color(Color::Warning),
+                                )
+                            })
+                            .when(is_pinned, |this| {
+                                this.child(
+                                    Label::new("Pinned")
+                                        .size(LabelSize::XSmall)
+                                        .color(Color::Muted),
+                                )
+                            })
                             .map(|parent| {
                                 if extension.manifest.provides.is_empty() {
                                     return parent;
@@ -815,6 +842,7 @@ impl ExtensionsPage {
                                     Some(Self::render_remote_extension_context_menu(
                                         &this,
                                         extension_id.clone(),
+                                        installed_version.clone(),
                                         authors.clone(),
                                         window,
                                         cx,
@@ -828,11 +856,17 @@ impl ExtensionsPage {
     fn render_remote_extension_context_menu(
         this: &Entity<Self>,
         extension_id: Arc<str>,
+        installed_version: Option<Arc<str>>,
         authors: Vec<String>,
         window: &mut Window,
         cx: &mut App,
     ) -> Entity<ContextMenu> {
-        let context_menu = ContextMenu::build(window, cx, |context_menu, window, _| {
+        let context_menu = ContextMenu::build(window, cx, |context_menu, window, cx| {
+            let extension_settings = ExtensionSettings::get_global(cx);
+            let pinned_version = extension_settings.pinned_version(&extension_id).cloned();
+            let installs_pre_releases =
+                extension_settings.should_install_pre_releases(&extension_id);
+
             context_menu
                 .entry(
                     "Install Another Version...",
@@ -844,6 +878,54 @@ impl ExtensionsPage {
                         }
                     }),
                 )
+                .when_some(installed_version, |context_menu, installed_version| {
+                    let pin_label = match &pinned_version {
+                        Some(pinned_version) => format!("Unpin from v{pinned_version}"),
+                        None => format!("Pin to v{installed_version}"),
+                    };
+                    context_menu
+                        .entry(pin_label, None, {
+                            let extension_id = extension_id.clone();
+                            move |_, cx| {
+                                let version = match pinned_version {
+                                    Some(_) => None,
+                                    None => Some(installed_version.clone()),
+                                };
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.pin_extension(extension_id.clone(), version, cx)
+                                });
+                            }
+                        })
+                        .entry(
+                            "Roll Back to Previous Version",
+                            None,
+                            window.handler_for(this, {
+                                let extension_id = extension_id.clone();
+                                move |this, _, cx| {
+                                    this.roll_back_extension(extension_id.clone(), cx)
+                                }
+                            }),
+                        )
+                        .toggleable_entry(
+                            "Install Pre-releases",
+                            installs_pre_releases,
+                            IconPosition::Start,
+                            None,
+                            {
+                                let extension_id = extension_id.clone();
+                                move |_, cx| {
+                                    ExtensionStore::global(cx).update(cx, |store, cx| {
+                                        store.set_install_pre_releases(
+                                            extension_id.clone(),
+                                            !installs_pre_releases,
+                                            cx,
+                                        )
+                                    });
+                                }
+                            },
+                        )
+                        .separator()
+                })
                 .entry("Copy Extension ID", None, {
                     let extension_id = extension_id.clone();
                     move |_, cx| {
@@ -861,6 +943,21 @@ impl ExtensionsPage {
         context_menu
     }
 
+    fn roll_back_extension(&mut self, extension_id: Arc<str>, cx: &mut Context<Self>) {
+        let task = ExtensionStore::global(cx)
+            .update(cx, |store, cx| store.roll_back_extension(extension_id, cx));
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |_, cx| {
+            if let Err(error) = task.await {
+                log::error!("failed to roll back extension: {error:?}");
+                workspace
+                    .update(cx, |workspace, cx| workspace.show_error(&error, cx))
+                    .ok();
+            }
+        })
+        .detach();
+    }
+
     fn show_extension_version_list(
         &mut self,
         extension_id: Arc<str>,
@@ -883,10 +980,8 @@ impl ExtensionsPage {
             let extension_versions = extension_versions_task.await?;
 
             workspace.update_in(cx, |workspace, window, cx| {
-                let fs = workspace.project().read(cx).fs().clone();
                 workspace.toggle_modal(window, cx, |window, cx| {
                     let delegate = ExtensionVersionSelectorDelegate::new(
-                        fs,
                         cx.entity().downgrade(),
                         extension_versions,
                     );
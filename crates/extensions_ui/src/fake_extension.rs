@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use collections::BTreeMap;
+use extension::{
+    CodeLabel, Command, Completion, ContextServerConfiguration, DebugAdapterBinary,
+    DebugTaskDefinition, EditorDelegate, Extension, ExtensionManifest, KeyValueStoreDelegate,
+    PanelEvent, PanelView, ProjectDelegate, SchemaVersion, SlashCommand,
+    SlashCommandArgumentCompletion, SlashCommandOutput, Symbol, VirtualDocument, WorktreeDelegate,
+};
+use language::{LanguageName, LanguageServerName};
+use parking_lot::Mutex;
+
+/// An extension that renders empty panels and records the commands run through it.
+pub(crate) struct FakeExtension {
+    manifest: Arc<ExtensionManifest>,
+    pub(crate) commands_run: Mutex<Vec<Arc<str>>>,
+}
+
+impl FakeExtension {
+    pub(crate) fn new(id: &str) -> Arc<Self> {
+        Arc::new(Self {
+            manifest: Arc::new(ExtensionManifest {
+                id: id.into(),
+                name: id.to_string(),
+                version: "1.0.0".into(),
+                schema_version: SchemaVersion::ZERO,
+                description: None,
+                repository: None,
+                authors: vec![],
+                lib: Default::default(),
+                themes: vec![],
+                icon_themes: vec![],
+                languages: vec![],
+                grammars: BTreeMap::default(),
+                language_servers: BTreeMap::default(),
+                context_servers: BTreeMap::default(),
+                slash_commands: BTreeMap::default(),
+                indexed_docs_providers: BTreeMap::default(),
+                snippets: None,
+                capabilities: vec![],
+                debug_adapters: vec![],
+                panels: BTreeMap::default(),
+                commands: BTreeMap::default(),
+                document_providers: BTreeMap::default(),
+            }),
+            commands_run: Mutex::default(),
+        })
+    }
+}
+
+#[async_trait]
+impl Extension for FakeExtension {
+    fn manifest(&self) -> Arc<ExtensionManifest> {
+        self.manifest.clone()
+    }
+
+    fn work_dir(&self) -> Arc<Path> {
+        Path::new("/").into()
+    }
+
+    async fn language_server_command(
+        &self,
+        _: LanguageServerName,
+        _: LanguageName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Command> {
+        bail!("unsupported")
+    }
+
+    async fn language_server_initialization_options(
+        &self,
+        _: LanguageServerName,
+        _: LanguageName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Option<String>> {
+        bail!("unsupported")
+    }
+
+    async fn language_server_workspace_configuration(
+        &self,
+        _: LanguageServerName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Option<String>> {
+        bail!("unsupported")
+    }
+
+    async fn language_server_additional_initialization_options(
+        &self,
+        _: LanguageServerName,
+        _: LanguageServerName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Option<String>> {
+        bail!("unsupported")
+    }
+
+    async fn language_server_additional_workspace_configuration(
+        &self,
+        _: LanguageServerName,
+        _: LanguageServerName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Option<String>> {
+        bail!("unsupported")
+    }
+
+    async fn labels_for_completions(
+        &self,
+        _: LanguageServerName,
+        _: Vec<Completion>,
+    ) -> Result<Vec<Option<CodeLabel>>> {
+        bail!("unsupported")
+    }
+
+    async fn labels_for_symbols(
+        &self,
+        _: LanguageServerName,
+        _: Vec<Symbol>,
+    ) -> Result<Vec<Option<CodeLabel>>> {
+        bail!("unsupported")
+    }
+
+    async fn complete_slash_command_argument(
+        &self,
+        _: SlashCommand,
+        _: Vec<String>,
+    ) -> Result<Vec<SlashCommandArgumentCompletion>> {
+        bail!("unsupported")
+    }
+
+    async fn run_slash_command(
+        &self,
+        _: SlashCommand,
+        _: Vec<String>,
+        _: Option<Arc<dyn WorktreeDelegate>>,
+    ) -> Result<SlashCommandOutput> {
+        bail!("unsupported")
+    }
+
+    async fn context_server_command(
+        &self,
+        _: Arc<str>,
+        _: Arc<dyn ProjectDelegate>,
+    ) -> Result<Command> {
+        bail!("unsupported")
+    }
+
+    async fn context_server_configuration(
+        &self,
+        _: Arc<str>,
+        _: Arc<dyn ProjectDelegate>,
+    ) -> Result<Option<ContextServerConfiguration>> {
+        bail!("unsupported")
+    }
+
+    async fn suggest_docs_packages(&self, _: Arc<str>) -> Result<Vec<String>> {
+        bail!("unsupported")
+    }
+
+    async fn index_docs(
+        &self,
+        _: Arc<str>,
+        _: Arc<str>,
+        _: Arc<dyn KeyValueStoreDelegate>,
+    ) -> Result<()> {
+        bail!("unsupported")
+    }
+
+    async fn get_dap_binary(
+        &self,
+        _: Arc<str>,
+        _: DebugTaskDefinition,
+        _: Option<PathBuf>,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<DebugAdapterBinary> {
+        bail!("unsupported")
+    }
+
+    async fn get_dap_schema(&self) -> Result<serde_json::Value> {
+        bail!("unsupported")
+    }
+
+    async fn run_command(
+        &self,
+        command_id: Arc<str>,
+        _: Option<Arc<dyn EditorDelegate>>,
+    ) -> Result<()> {
+        self.commands_run.lock().push(command_id);
+        Ok(())
+    }
+
+    async fn load_document(&self, _: Arc<str>) -> Result<VirtualDocument> {
+        bail!("unsupported")
+    }
+
+    async fn save_document(&self, _: Arc<str>, _: String) -> Result<()> {
+        bail!("unsupported")
+    }
+
+    async fn render_panel(&self, _: Arc<str>) -> Result<PanelView> {
+        Ok(PanelView::default())
+    }
+
+    async fn handle_panel_event(&self, _: Arc<str>, _: PanelEvent) -> Result<PanelView> {
+        Ok(PanelView::default())
+    }
+}
@@ -4,6 +4,7 @@ mod file_finder_tests;
 mod open_path_prompt_tests;
 
 pub mod file_finder_settings;
+mod frecency;
 mod open_path_prompt;
 
 use futures::future::join_all;
@@ -13,6 +14,7 @@ use collections::HashMap;
 use editor::Editor;
 use file_finder_settings::{FileFinderSettings, FileFinderWidth};
 use file_icons::FileIcons;
+use frecency::Frecency;
 use fuzzy::{CharBag, PathMatch, PathMatchCandidate};
 use gpui::{
     Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
@@ -21,7 +23,7 @@ use gpui::{
 };
 use open_path_prompt::OpenPathPrompt;
 use picker::{Picker, PickerDelegate};
-use project::{PathMatchCandidateSet, Project, ProjectPath, WorktreeId};
+use project::{PathMatchCandidateSet, Project, ProjectPath, Worktree, WorktreeId};
 use search::ToggleIncludeIgnored;
 use settings::Settings;
 use std::{
@@ -82,6 +84,7 @@ pub fn init_settings(cx: &mut App) {
 
 pub fn init(cx: &mut App) {
     init_settings(cx);
+    Frecency::init(cx);
     cx.observe_new(FileFinder::register).detach();
     cx.observe_new(OpenPathPrompt::register).detach();
     cx.observe_new(OpenPathPrompt::register_new_path).detach();
@@ -91,8 +94,17 @@ impl FileFinder {
     fn register(
         workspace: &mut Workspace,
         _window: Option<&mut Window>,
-        _: &mut Context<Workspace>,
+        cx: &mut Context<Workspace>,
     ) {
+        cx.subscribe_self(|workspace, event: &workspace::Event, cx| {
+            if let workspace::Event::PathOpened(project_path) = event {
+                if let Some(abs_path) = workspace.project().read(cx).absolute_path(project_path, cx)
+                {
+                    Frecency::record_opened(abs_path, cx);
+                }
+            }
+        })
+        .detach();
         workspace.register_action(
             |workspace, action: &workspace::ToggleFileFinder, window, cx| {
                 let Some(file_finder) = workspace.active_modal::<Self>(cx) else {
@@ -522,6 +534,7 @@ impl Matches {
         query: Option<&FileSearchQuery>,
         new_search_matches: impl Iterator<Item = ProjectPanelOrdMatch>,
         extend_old_matches: bool,
+        boost: impl Fn(&mut PathMatch),
     ) {
         let Some(query) = query else {
             // assuming that if there's no query, then there's no search matches.
@@ -536,7 +549,17 @@ impl Matches {
             return;
         };
 
-        let new_history_matches = matching_history_items(history_items, currently_opened, query);
+        let mut new_history_matches =
+            matching_history_items(history_items, currently_opened, query);
+        for history_match in new_history_matches.values_mut() {
+            if let Match::History {
+                panel_match: Some(panel_match),
+                ..
+            } = history_match
+            {
+                boost(&mut panel_match.0);
+            }
+        }
         let new_search_matches: Vec<Match> = new_search_matches
             .filter(|path_match| !new_history_matches.contains_key(&path_match.0.path))
             .map(Match::Search)
@@ -763,6 +786,8 @@ struct FileSearchQuery {
     raw_query: String,
     file_query_end: Option<usize>,
     path_position: PathWithPosition,
+    /// Only search the worktrees whose root names contain this, in lowercase.
+    worktree_filter: Option<String>,
 }
 
 impl FileSearchQuery {
@@ -772,6 +797,35 @@ impl FileSearchQuery {
             None => &self.raw_query,
         }
     }
+
+    fn matches_worktree(&self, worktree: &Worktree) -> bool {
+        self.worktree_filter.as_ref().is_none_or(|filter| {
+            worktree
+                .root_name()
+                .to_lowercase()
+                .contains(filter.as_str())
+        })
+    }
+}
+
+const WORKTREE_FILTER_PREFIX: &str = "worktree:";
+
+/// Splits the `worktree:name` tokens off a query, returning the last name, in lowercase, and
+/// the rest of the query.
+fn split_worktree_filter(query: &str) -> (Option<String>, String) {
+    let mut worktree_filter = None;
+    let rest = query
+        .split(' ')
+        .filter(|word| match word.strip_prefix(WORKTREE_FILTER_PREFIX) {
+            Some(name) if !name.is_empty() => {
+                worktree_filter = Some(name.to_lowercase());
+                false
+            }
+            _ => true,
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    (worktree_filter, rest)
 }
 
 impl FileFinderDelegate {
@@ -844,6 +898,10 @@ impl FileFinderDelegate {
             .visible_worktrees(cx)
             .collect::<Vec<_>>();
         let include_root_name = worktrees.len() > 1;
+        let worktrees = worktrees
+            .into_iter()
+            .filter(|worktree| query.matches_worktree(worktree.read(cx)))
+            .collect::<Vec<_>>();
         let candidate_sets = worktrees
             .into_iter()
             .map(|worktree| {
@@ -899,11 +957,11 @@ impl FileFinderDelegate {
     ) {
         if search_id >= self.latest_search_id {
             self.latest_search_id = search_id;
-            let query_changed = Some(query.path_query())
+            let query_changed = Some((query.path_query(), &query.worktree_filter))
                 != self
                     .latest_search_query
                     .as_ref()
-                    .map(|query| query.path_query());
+                    .map(|query| (query.path_query(), &query.worktree_filter));
             let extend_old_matches = self.latest_search_did_cancel && !query_changed;
 
             let selected_match = if query_changed {
@@ -912,14 +970,30 @@ impl FileFinderDelegate {
                 self.matches.get(self.selected_index).cloned()
             };
 
+            let project = self.project.read(cx);
+            let in_filtered_worktree = |found_path: &&FoundPath| {
+                query.worktree_filter.is_none()
+                    || project
+                        .worktree_for_id(found_path.project.worktree_id, cx)
+                        .is_some_and(|worktree| query.matches_worktree(worktree.read(cx)))
+            };
+            let boost = |path_match: &mut PathMatch| Frecency::boost(project, path_match, cx);
             self.matches.push_new_matches(
-                &self.history_items,
-                self.currently_opened_path.as_ref(),
+                self.history_items.iter().filter(in_filtered_worktree),
+                self.currently_opened_path
+                    .as_ref()
+                    .filter(in_filtered_worktree),
                 Some(&query),
-                matches.into_iter(),
+                matches.into_iter().map(|mut path_match| {
+                    boost(&mut path_match.0);
+                    path_match
+                }),
                 extend_old_matches,
+                &boost,
             );
-            let worktree = self.project.read(cx).visible_worktrees(cx).next();
+            let worktree = project
+                .visible_worktrees(cx)
+                .find(|worktree| query.matches_worktree(worktree.read(cx)));
             let filename = query.raw_query.to_string();
             let path = Path::new(&filename);
 
@@ -1271,6 +1345,7 @@ impl PickerDelegate for FileFinderDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Task<()> {
+        let (worktree_filter, raw_query) = split_worktree_filter(&raw_query);
         let raw_query = raw_query.replace(' ', "");
         let raw_query = raw_query.trim();
 
@@ -1315,7 +1390,7 @@ impl PickerDelegate for FileFinderDelegate {
             _ => raw_query,
         };
 
-        if raw_query.is_empty() {
+        if raw_query.is_empty() && worktree_filter.is_none() {
             // if there was no query before, and we already have some (history) matches
             // there's no need to update anything, since nothing has changed.
             // We also want to populate matches set from history entries on the first update.
@@ -1340,6 +1415,7 @@ impl PickerDelegate for FileFinderDelegate {
                     None,
                     None.into_iter(),
                     false,
+                    |_| {},
                 );
 
                 self.first_update = false;
@@ -1359,6 +1435,7 @@ impl PickerDelegate for FileFinderDelegate {
                     Some(path_position.path.to_str().unwrap().len())
                 },
                 path_position,
+                worktree_filter,
             };
 
             if Path::new(query.path_query()).is_absolute() {
@@ -9,6 +9,7 @@ pub struct FileFinderSettings {
     pub modal_max_width: Option<FileFinderWidth>,
     pub skip_focus_for_active_in_search: bool,
     pub include_ignored: Option<bool>,
+    pub frecency: bool,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
@@ -39,6 +40,11 @@ pub struct FileFinderSettingsContent {
     ///
     /// Default: None
     pub include_ignored: Option<Option<bool>>,
+    /// Whether to rank the files opened most recently and most often higher among the
+    /// matches.
+    ///
+    /// Default: true
+    pub frecency: Option<bool>,
 }
 
 impl Settings for FileFinderSettings {
@@ -7,6 +7,7 @@ use menu::{Confirm, SelectNext, SelectPrevious};
 use pretty_assertions::assert_eq;
 use project::{FS_WATCH_LATENCY, RemoveOptions};
 use serde_json::json;
+use settings::SettingsStore;
 use util::path;
 use workspace::{AppState, CloseActiveItem, OpenOptions, ToggleFileFinder, Workspace};
 
@@ -2016,6 +2017,68 @@ async fn test_search_results_refreshed_on_worktree_updates(cx: &mut gpui::TestAp
     });
 }
 
+#[test]
+fn test_split_worktree_filter() {
+    assert_eq!(
+        split_worktree_filter("main.rs"),
+        (None, "main.rs".to_string())
+    );
+    assert_eq!(
+        split_worktree_filter("worktree:Zed src/main.rs"),
+        (Some("zed".to_string()), "src/main.rs".to_string())
+    );
+    assert_eq!(
+        split_worktree_filter("main.rs worktree:"),
+        (None, "main.rs worktree:".to_string())
+    );
+}
+
+#[gpui::test]
+async fn test_worktree_filter(cx: &mut gpui::TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/test",
+            json!({
+                "frontend": { "main.rs": "" },
+                "backend": { "main.rs": "", "db.rs": "" },
+            }),
+        )
+        .await;
+    let project = Project::test(
+        app_state.fs.clone(),
+        ["/test/frontend".as_ref(), "/test/backend".as_ref()],
+        cx,
+    )
+    .await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    let picker = open_file_picker(&workspace, cx);
+    cx.simulate_input("main.rs");
+    picker.update(cx, |finder, _| {
+        assert_eq!(finder.delegate.matches.len(), 2);
+    });
+
+    picker.update_in(cx, |picker, window, cx| {
+        picker.set_query("worktree:back main.rs", window, cx);
+    });
+    cx.run_until_parked();
+    picker.update(cx, |finder, cx| {
+        let matches = collect_search_matches(finder).search_matches_only();
+        assert_eq!(matches.len(), 1);
+        let worktree = finder
+            .delegate
+            .project
+            .read(cx)
+            .worktree_for_id(WorktreeId::from_usize(matches[0].worktree_id), cx)
+            .unwrap();
+        assert_eq!(worktree.read(cx).root_name(), "backend");
+    });
+}
+
 #[gpui::test]
 async fn test_search_results_refreshed_on_adding_and_removing_worktrees(
     cx: &mut gpui::TestAppContext,
@@ -2517,6 +2580,12 @@ fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
         editor::init(cx);
         workspace::init_settings(cx);
         Project::init_settings(cx);
+        // Files opened by other tests would otherwise change the order of matches.
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<FileFinderSettings>(cx, |settings| {
+                settings.frecency = Some(false);
+            });
+        });
         state
     })
 }
@@ -2532,6 +2601,7 @@ fn test_path_position(test_str: &str) -> FileSearchQuery {
             Some(path_position.path.to_str().unwrap().len())
         },
         path_position,
+        worktree_filter: None,
     }
 }
 
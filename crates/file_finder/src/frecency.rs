@@ -0,0 +1,225 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use fuzzy::PathMatch;
+use gpui::{App, Global};
+use project::{Project, WorktreeId};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use util::ResultExt as _;
+
+use crate::file_finder_settings::FileFinderSettings;
+
+const FRECENCY_KEY: &str = "file_finder_frecency";
+const MAX_ENTRIES: usize = 1000;
+const DAY: u64 = 24 * 60 * 60;
+
+/// How much a match's score is raised by, at most, for the files opened most recently and
+/// most often.
+const MAX_BOOST: f64 = 0.5;
+/// The frecency at which a match's score is raised by half of [`MAX_BOOST`].
+const HALF_BOOST_FRECENCY: f64 = 200.;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct FrecencyEntry {
+    count: u32,
+    /// When the file was last opened, in seconds since the Unix epoch.
+    last_opened: u64,
+}
+
+impl FrecencyEntry {
+    /// Weighs how often the file was opened by how long ago it was last opened.
+    fn frecency(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_opened);
+        let weight = match age {
+            age if age < 4 * DAY => 100.,
+            age if age < 14 * DAY => 70.,
+            age if age < 31 * DAY => 50.,
+            age if age < 90 * DAY => 30.,
+            _ => 10.,
+        };
+        self.count as f64 * weight
+    }
+}
+
+/// How recently and how often files were opened in the workspace, by their absolute
+/// paths, so that their matches can be ranked higher.
+#[derive(Default)]
+pub(crate) struct Frecency {
+    entries: HashMap<PathBuf, FrecencyEntry>,
+}
+
+impl Global for Frecency {}
+
+impl Frecency {
+    pub(crate) fn init(cx: &mut App) {
+        cx.set_global(Self::default());
+        cx.spawn(async move |cx| {
+            let entries = cx
+                .background_spawn(async move {
+                    let Some(json) = KEY_VALUE_STORE.read_kvp(FRECENCY_KEY)? else {
+                        return anyhow::Ok(HashMap::default());
+                    };
+                    Ok(serde_json::from_str::<HashMap<PathBuf, FrecencyEntry>>(
+                        &json,
+                    )?)
+                })
+                .await
+                .log_err()
+                .unwrap_or_default();
+            cx.update(|cx| {
+                let frecency = cx.global_mut::<Self>();
+                for (path, entry) in entries {
+                    // Files opened before the stored entries were loaded keep their new entries.
+                    frecency.entries.entry(path).or_insert(entry);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Records that the file at the given path was opened.
+    pub(crate) fn record_opened(abs_path: PathBuf, cx: &mut App) {
+        if !cx.has_global::<Self>() {
+            return;
+        }
+        let frecency = cx.global_mut::<Self>();
+        let now = now();
+        let entry = frecency.entries.entry(abs_path).or_insert(FrecencyEntry {
+            count: 0,
+            last_opened: now,
+        });
+        entry.count = entry.count.saturating_add(1);
+        entry.last_opened = now;
+
+        if frecency.entries.len() > MAX_ENTRIES {
+            let mut entries = frecency.entries.drain().collect::<Vec<_>>();
+            entries.sort_by(|(_, a), (_, b)| b.frecency(now).total_cmp(&a.frecency(now)));
+            entries.truncate(MAX_ENTRIES);
+            frecency.entries.extend(entries);
+        }
+
+        let Some(json) = serde_json::to_string(&frecency.entries).log_err() else {
+            return;
+        };
+        cx.background_spawn(KEY_VALUE_STORE.write_kvp(FRECENCY_KEY.to_string(), json))
+            .detach_and_log_err(cx);
+    }
+
+    /// Raises the score of a match for a file in the project, by how recently and how often
+    /// it was opened.
+    pub(crate) fn boost(project: &Project, path_match: &mut PathMatch, cx: &App) {
+        if !FileFinderSettings::get_global(cx).frecency {
+            return;
+        }
+        let Some(frecency) = cx.try_global::<Self>() else {
+            return;
+        };
+        let Some(worktree) =
+            project.worktree_for_id(WorktreeId::from_usize(path_match.worktree_id), cx)
+        else {
+            return;
+        };
+        let abs_path = worktree.read(cx).abs_path().join(&path_match.path);
+        path_match.score *= 1. + frecency.boost_for(&abs_path, now());
+    }
+
+    fn boost_for(&self, abs_path: &Path, now: u64) -> f64 {
+        let Some(entry) = self.entries.get(abs_path) else {
+            return 0.;
+        };
+        let frecency = entry.frecency(now);
+        MAX_BOOST * frecency / (frecency + HALF_BOOST_FRECENCY)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::ProjectPath;
+    use serde_json::json;
+    use std::sync::Arc;
+    use util::path;
+    use workspace::{AppState, Workspace};
+
+    #[test]
+    fn test_boost_for() {
+        let now = 1000 * DAY;
+        let mut frecency = Frecency::default();
+        let entry = |count, days_ago| FrecencyEntry {
+            count,
+            last_opened: now - days_ago * DAY,
+        };
+        frecency.entries.insert("/often".into(), entry(10, 1));
+        frecency.entries.insert("/once".into(), entry(1, 1));
+        frecency.entries.insert("/long_ago".into(), entry(10, 100));
+
+        let often = frecency.boost_for(Path::new("/often"), now);
+        let once = frecency.boost_for(Path::new("/once"), now);
+        let long_ago = frecency.boost_for(Path::new("/long_ago"), now);
+        assert!(often > long_ago);
+        assert!(often > once);
+        assert!(often < MAX_BOOST);
+        assert_eq!(frecency.boost_for(Path::new("/never"), now), 0.);
+    }
+
+    #[gpui::test]
+    async fn test_paths_opened_in_workspace_are_recorded(cx: &mut TestAppContext) {
+        let app_state = cx.update(|cx| {
+            let state = AppState::test(cx);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            crate::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            state
+        });
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(path!("/frecency"), json!({ "a.txt": "", "b.txt": "" }))
+            .await;
+        let project = Project::test(app_state.fs.clone(), [path!("/frecency").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id =
+            cx.read(|cx| project.read(cx).worktrees(cx).next().unwrap().read(cx).id());
+
+        for _ in 0..2 {
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    let path = ProjectPath {
+                        worktree_id,
+                        path: Arc::from(Path::new("a.txt")),
+                    };
+                    workspace.open_path(path, None, true, window, cx)
+                })
+                .await
+                .unwrap();
+        }
+
+        cx.read(|cx| {
+            let frecency = cx.global::<Frecency>();
+            let a = frecency.entries.get(Path::new(path!("/frecency/a.txt")));
+            assert_eq!(a.map(|entry| entry.count), Some(2));
+            assert!(
+                !frecency
+                    .entries
+                    .contains_key(Path::new(path!("/frecency/b.txt")))
+            );
+        });
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use collections::HashSet;
+
+/// Parses a list of files with one path per line, like the output of `git diff --name-only`.
+/// Blank lines and lines starting with `#` are skipped, relative paths are resolved against
+/// `base_dir`, and the paths listed more than once are only kept the first time.
+pub fn parse_file_list(text: &str, base_dir: &Path) -> Vec<PathBuf> {
+    let mut seen = HashSet::default();
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base_dir.join(line))
+        .filter(|path| seen.insert(path.clone()))
+        .collect()
+}
+
+/// Formats the paths with one per line, relative to `base_dir` when they're inside it, so that
+/// the list still works when the directory is moved or checked out elsewhere.
+pub fn format_file_list(paths: &[PathBuf], base_dir: &Path) -> String {
+    let mut text = String::new();
+    for path in paths {
+        let path = path.strip_prefix(base_dir).unwrap_or(path);
+        text.push_str(&path.to_string_lossy());
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use util::path;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_file_list() {
+        let base_dir = Path::new(path!("/code/project"));
+        let text = format!(
+            "# review\nsrc/main.rs\n\n  src/lib.rs  \n{}\nsrc/main.rs\n",
+            path!("/other/notes.md")
+        );
+        assert_eq!(
+            parse_file_list(&text, base_dir),
+            vec![
+                PathBuf::from(path!("/code/project/src/main.rs")),
+                PathBuf::from(path!("/code/project/src/lib.rs")),
+                PathBuf::from(path!("/other/notes.md")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_file_list() {
+        let base_dir = Path::new(path!("/code/project"));
+        let paths = vec![
+            PathBuf::from(path!("/code/project/src/main.rs")),
+            PathBuf::from(path!("/other/notes.md")),
+        ];
+        let text = format_file_list(&paths, base_dir);
+        assert_eq!(
+            text,
+            format!(
+                "{}\n{}\n",
+                Path::new("src").join("main.rs").display(),
+                path!("/other/notes.md")
+            )
+        );
+        assert_eq!(parse_file_list(&text, base_dir), paths);
+    }
+}
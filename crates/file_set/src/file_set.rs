@@ -0,0 +1,220 @@
+mod file_list;
+mod file_set_panel;
+mod file_set_panel_settings;
+
+use std::path::{Path, PathBuf};
+
+use gpui::{App, Context, Window, actions};
+use project::DirectoryLister;
+use settings::Settings as _;
+use util::paths::home_dir;
+use workspace::{OpenOptions, OpenVisible, Workspace};
+
+pub use file_list::{format_file_list, parse_file_list};
+pub use file_set_panel::FileSetPanel;
+pub use file_set_panel_settings::FileSetPanelSettings;
+
+actions!(
+    file_set,
+    [
+        ToggleFocus,
+        OpenFileList,
+        NewFromChangedFiles,
+        AddActiveFile,
+        RemoveEntry,
+        ClearFileSet,
+        SaveFileList,
+        OpenNextFile,
+        OpenPreviousFile
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    FileSetPanelSettings::register(cx);
+    cx.observe_new(register).detach();
+}
+
+fn register(workspace: &mut Workspace, _: Option<&mut Window>, _: &mut Context<Workspace>) {
+    workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+        workspace.toggle_panel_focus::<FileSetPanel>(window, cx);
+    });
+    workspace.register_action(open_file_list);
+    workspace.register_action(|workspace, _: &NewFromChangedFiles, window, cx| {
+        let Some(panel) = workspace.panel::<FileSetPanel>(cx) else {
+            return;
+        };
+        let paths = changed_files(workspace, cx);
+        panel.update(cx, |panel, cx| {
+            panel.set_files(Some("Changed Files".into()), paths, cx)
+        });
+        workspace.focus_panel::<FileSetPanel>(window, cx);
+    });
+    workspace.register_action(|workspace, _: &AddActiveFile, _, cx| {
+        let Some(panel) = workspace.panel::<FileSetPanel>(cx) else {
+            return;
+        };
+        if let Some(abs_path) = active_file(workspace, cx) {
+            panel.update(cx, |panel, cx| panel.add_file(abs_path, cx));
+        }
+    });
+    workspace.register_action(|workspace, _: &ClearFileSet, _, cx| {
+        if let Some(panel) = workspace.panel::<FileSetPanel>(cx) {
+            panel.update(cx, |panel, cx| panel.set_files(None, Vec::new(), cx));
+        }
+    });
+    workspace.register_action(save_file_list);
+    workspace.register_action(|workspace, _: &OpenNextFile, window, cx| {
+        open_adjacent_file(workspace, 1, window, cx);
+    });
+    workspace.register_action(|workspace, _: &OpenPreviousFile, window, cx| {
+        open_adjacent_file(workspace, -1, window, cx);
+    });
+}
+
+/// Opens a file of the set without adding a worktree for its directory to the project panel,
+/// unless the file is already in one of the project's worktrees.
+pub(crate) fn open_file(
+    workspace: &mut Workspace,
+    abs_path: PathBuf,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    workspace
+        .open_abs_path(
+            abs_path,
+            OpenOptions {
+                visible: Some(OpenVisible::None),
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+        .detach_and_log_err(cx);
+}
+
+fn open_adjacent_file(
+    workspace: &mut Workspace,
+    offset: isize,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(panel) = workspace.panel::<FileSetPanel>(cx) else {
+        return;
+    };
+    let active_file = active_file(workspace, cx);
+    let Some(abs_path) = panel.update(cx, |panel, cx| {
+        panel.select_adjacent(active_file.as_deref(), offset, cx)
+    }) else {
+        return;
+    };
+    open_file(workspace, abs_path, window, cx);
+}
+
+fn open_file_list(
+    workspace: &mut Workspace,
+    _: &OpenFileList,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    if workspace.panel::<FileSetPanel>(cx).is_none() {
+        return;
+    }
+    let fs = workspace.app_state().fs.clone();
+    let prompt = workspace.prompt_for_open_path(
+        gpui::PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        },
+        DirectoryLister::Local(workspace.project().clone(), fs.clone()),
+        window,
+        cx,
+    );
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Ok(Some(mut paths)) = prompt.await else {
+            return anyhow::Ok(());
+        };
+        let Some(list_path) = paths.pop() else {
+            return Ok(());
+        };
+        let text = fs.load(&list_path).await?;
+        let base_dir = list_path.parent().unwrap_or(Path::new(""));
+        let files = parse_file_list(&text, base_dir);
+        let name = list_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned().into());
+        workspace.update_in(cx, |workspace, window, cx| {
+            if let Some(panel) = workspace.panel::<FileSetPanel>(cx) {
+                panel.update(cx, |panel, cx| panel.set_files(name, files, cx));
+                workspace.focus_panel::<FileSetPanel>(window, cx);
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+fn save_file_list(
+    workspace: &mut Workspace,
+    _: &SaveFileList,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(panel) = workspace.panel::<FileSetPanel>(cx) else {
+        return;
+    };
+    let files = panel.read(cx).files().to_vec();
+    if files.is_empty() {
+        return;
+    }
+    let fs = workspace.app_state().fs.clone();
+    let directory = workspace
+        .project()
+        .read(cx)
+        .visible_worktrees(cx)
+        .next()
+        .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+        .unwrap_or_else(|| home_dir().clone());
+    let abs_path = cx.prompt_for_new_path(&directory);
+    cx.spawn_in(window, async move |_, cx| {
+        let Some(abs_path) = abs_path.await?? else {
+            return anyhow::Ok(());
+        };
+        let base_dir = abs_path.parent().unwrap_or(Path::new(""));
+        let text = format_file_list(&files, base_dir);
+        fs.atomic_write(abs_path.clone(), text).await?;
+        let name = abs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned().into());
+        panel.update(cx, |panel, cx| panel.set_name(name, cx))
+    })
+    .detach_and_log_err(cx);
+}
+
+/// The files that have uncommitted changes in the project's repositories, except for the deleted
+/// ones.
+fn changed_files(workspace: &Workspace, cx: &App) -> Vec<PathBuf> {
+    let git_store = workspace.project().read(cx).git_store().read(cx);
+    let mut files = git_store
+        .repositories()
+        .values()
+        .flat_map(|repository| {
+            let repository = repository.read(cx);
+            repository
+                .cached_status()
+                .filter(|entry| !entry.status.is_deleted())
+                .map(|entry| repository.work_directory_abs_path.join(&entry.repo_path.0))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files.dedup();
+    files
+}
+
+fn active_file(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
+    let project_path = workspace.active_item(cx)?.project_path(cx)?;
+    workspace
+        .project()
+        .read(cx)
+        .absolute_path(&project_path, cx)
+}
@@ -0,0 +1,633 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{
+    Action, AnyElement, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, ScrollStrategy, Subscription, Task, UniformListScrollHandle, WeakEntity, Window,
+    uniform_list,
+};
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use theme::ActiveTheme as _;
+use ui::{ListItem, ListItemSpacing, Tab, prelude::*};
+use util::{ResultExt as _, TryFutureExt as _, paths::PathExt as _};
+use workspace::{
+    Workspace, WorkspaceId,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+use crate::{
+    FileSetPanelSettings, NewFromChangedFiles, OpenFileList, RemoveEntry, ToggleFocus, open_file,
+};
+
+const FILE_SET_PANEL_KEY: &str = "FileSetPanel";
+
+/// Lists a set of files from anywhere on disk, like the files changed on a branch or a saved
+/// review list, and opens them without adding their directories to the project.
+pub struct FileSetPanel {
+    workspace: WeakEntity<Workspace>,
+    workspace_id: Option<WorkspaceId>,
+    fs: Arc<dyn Fs>,
+    name: Option<SharedString>,
+    files: Vec<PathBuf>,
+    active_index: Option<usize>,
+    width: Option<Pixels>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    selected_index: Option<usize>,
+    pending_serialization: Task<Option<()>>,
+    pending_file_set_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedFileSetPanel {
+    width: Option<Pixels>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct SerializedFileSet {
+    name: Option<String>,
+    files: Vec<PathBuf>,
+}
+
+fn file_set_key(workspace_id: WorkspaceId) -> String {
+    format!("file_set_{}", i64::from(workspace_id))
+}
+
+impl FileSetPanel {
+    fn new(
+        workspace: &mut Workspace,
+        workspace_id: Option<WorkspaceId>,
+        file_set: SerializedFileSet,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let workspace_entity = cx.entity();
+        cx.new(|cx| {
+            let subscriptions =
+                vec![
+                    cx.subscribe(&workspace_entity, |this, workspace, event, cx| {
+                        if let workspace::Event::ActiveItemChanged = event {
+                            this.update_active_index(&workspace, cx);
+                        }
+                    }),
+                ];
+            Self {
+                workspace: workspace_entity.downgrade(),
+                workspace_id,
+                fs,
+                name: file_set.name.map(Into::into),
+                files: file_set.files,
+                active_index: None,
+                width: None,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+                selected_index: None,
+                pending_serialization: Task::ready(None),
+                pending_file_set_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let workspace_id = workspace.read_with(cx, |workspace, _| workspace.database_id())?;
+            let (serialized_panel, serialized_file_set) = cx
+                .background_spawn(async move {
+                    let panel = KEY_VALUE_STORE
+                        .read_kvp(FILE_SET_PANEL_KEY)
+                        .log_err()
+                        .flatten();
+                    let file_set = workspace_id.and_then(|workspace_id| {
+                        KEY_VALUE_STORE
+                            .read_kvp(&file_set_key(workspace_id))
+                            .log_err()
+                            .flatten()
+                    });
+                    (panel, file_set)
+                })
+                .await;
+            let serialized_panel = serialized_panel
+                .map(|panel| serde_json::from_str::<SerializedFileSetPanel>(&panel))
+                .transpose()?;
+            let file_set = serialized_file_set
+                .and_then(|file_set| serde_json::from_str::<SerializedFileSet>(&file_set).log_err())
+                .unwrap_or_default();
+
+            let panel = workspace.update(cx, |workspace, cx| {
+                Self::new(workspace, workspace_id, file_set, cx)
+            })?;
+            panel.update(cx, |panel, cx| {
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.width = serialized_panel.width.map(|w| w.round());
+                }
+                // Items restored with the workspace may already show one of the files.
+                if let Some(workspace) = panel.workspace.upgrade() {
+                    panel.update_active_index(&workspace, cx);
+                }
+                cx.notify();
+            })?;
+            Ok(panel)
+        })
+    }
+
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Replaces the files of the set, keeping the order they're listed in.
+    pub fn set_files(
+        &mut self,
+        name: Option<SharedString>,
+        files: Vec<PathBuf>,
+        cx: &mut Context<Self>,
+    ) {
+        self.name = name;
+        self.files = files;
+        self.selected_index = None;
+        self.active_index = None;
+        self.file_set_changed(cx);
+    }
+
+    pub fn set_name(&mut self, name: Option<SharedString>, cx: &mut Context<Self>) {
+        self.name = name;
+        self.file_set_changed(cx);
+    }
+
+    pub fn add_file(&mut self, abs_path: PathBuf, cx: &mut Context<Self>) {
+        if let Some(ix) = self.files.iter().position(|file| *file == abs_path) {
+            self.select(ix, cx);
+            return;
+        }
+        self.files.push(abs_path);
+        self.active_index = Some(self.files.len() - 1);
+        self.file_set_changed(cx);
+    }
+
+    /// Selects the file that comes `offset` files after the active one, or after the selected one
+    /// when the active item isn't in the set.
+    pub(crate) fn select_adjacent(
+        &mut self,
+        active_file: Option<&Path>,
+        offset: isize,
+        cx: &mut Context<Self>,
+    ) -> Option<PathBuf> {
+        if self.files.is_empty() {
+            return None;
+        }
+        let current = active_file
+            .and_then(|active_file| self.files.iter().position(|file| file == active_file))
+            .or(self.selected_index);
+        let ix = match current {
+            Some(ix) => ix.saturating_add_signed(offset).min(self.files.len() - 1),
+            None if offset < 0 => self.files.len() - 1,
+            None => 0,
+        };
+        self.select(ix, cx);
+        self.files.get(ix).cloned()
+    }
+
+    fn file_set_changed(&mut self, cx: &mut Context<Self>) {
+        self.clamp_selection();
+        self.serialize_file_set(cx);
+        cx.notify();
+    }
+
+    fn update_active_index(&mut self, workspace: &Entity<Workspace>, cx: &mut Context<Self>) {
+        let workspace = workspace.read(cx);
+        let active_file = workspace
+            .active_item(cx)
+            .and_then(|item| item.project_path(cx))
+            .and_then(|project_path| {
+                workspace
+                    .project()
+                    .read(cx)
+                    .absolute_path(&project_path, cx)
+            });
+        let active_index = active_file
+            .and_then(|active_file| self.files.iter().position(|file| *file == active_file));
+        if active_index != self.active_index {
+            self.active_index = active_index;
+            cx.notify();
+        }
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        FILE_SET_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedFileSetPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn serialize_file_set(&mut self, cx: &mut Context<Self>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        let file_set = SerializedFileSet {
+            name: self.name.as_ref().map(ToString::to_string),
+            files: self.files.clone(),
+        };
+        self.pending_file_set_serialization = cx.background_spawn(
+            async move {
+                let key = file_set_key(workspace_id);
+                if file_set.files.is_empty() {
+                    KEY_VALUE_STORE.delete_kvp(key).await?;
+                } else {
+                    KEY_VALUE_STORE
+                        .write_kvp(key, serde_json::to_string(&file_set)?)
+                        .await?;
+                }
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.files.len();
+        self.selected_index = match self.selected_index {
+            _ if count == 0 => None,
+            Some(ix) => Some(ix.min(count - 1)),
+            None => None,
+        };
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let count = self.files.len();
+        if count > 0 {
+            let ix = self.selected_index.map_or(0, |ix| (ix + 1).min(count - 1));
+            self.select(ix, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.files.is_empty() {
+            let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+            self.select(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open(ix, window, cx);
+        }
+    }
+
+    fn open(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(abs_path) = self.files.get(ix).cloned() else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                open_file(workspace, abs_path, window, cx)
+            })
+            .log_err();
+    }
+
+    fn remove_selected(&mut self, _: &RemoveEntry, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self.selected_index.filter(|ix| *ix < self.files.len()) else {
+            return;
+        };
+        self.files.remove(ix);
+        self.active_index = match self.active_index {
+            Some(active_ix) if active_ix == ix => None,
+            Some(active_ix) if active_ix > ix => Some(active_ix - 1),
+            active_index => active_index,
+        };
+        self.file_set_changed(cx);
+    }
+
+    fn render_files(
+        &mut self,
+        range: Range<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| {
+                let abs_path = self.files.get(ix)?;
+                Some(self.render_file(ix, abs_path, cx))
+            })
+            .collect()
+    }
+
+    fn render_file(&self, ix: usize, abs_path: &Path, cx: &Context<Self>) -> AnyElement {
+        let file_name = abs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let directory = abs_path
+            .parent()
+            .map(|directory| directory.compact().to_string_lossy().to_string())
+            .unwrap_or_default();
+        let is_active = self.active_index == Some(ix);
+
+        ListItem::new(ix)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(self.selected_index == Some(ix))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Label::new(file_name)
+                            .size(LabelSize::Small)
+                            .when(is_active, |label| label.color(Color::Accent)),
+                    )
+                    .child(
+                        Label::new(directory)
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted)
+                            .truncate(),
+                    ),
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select(ix, cx);
+                this.open(ix, window, cx);
+            }))
+            .into_any_element()
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        v_flex()
+            .p_4()
+            .gap_2()
+            .child(
+                Label::new("Open a list of files, with one path per line, to review them here.")
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            )
+            .child(
+                Button::new("open-file-list", "Open File List")
+                    .full_width()
+                    .on_click(|_, window, cx| window.dispatch_action(Box::new(OpenFileList), cx)),
+            )
+            .child(
+                Button::new("new-from-changed-files", "Changed Files")
+                    .full_width()
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(NewFromChangedFiles), cx)
+                    }),
+            )
+    }
+}
+
+impl Render for FileSetPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let file_count = self.files.len();
+        let title = self.name.clone().unwrap_or_else(|| "File Set".into());
+        v_flex()
+            .key_context("FileSetPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::remove_selected))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new(title).truncate())
+                    .when(file_count > 0, |this| {
+                        this.child(
+                            Label::new(file_count.to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+            .map(|this| {
+                if file_count == 0 {
+                    this.child(self.render_empty_state())
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "file-set",
+                            file_count,
+                            Self::render_files,
+                        )
+                        .p_1()
+                        .track_scroll(self.scroll_handle.clone())
+                        .flex_grow(),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for FileSetPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for FileSetPanel {}
+
+impl Panel for FileSetPanel {
+    fn persistent_name() -> &'static str {
+        "FileSetPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        FileSetPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<FileSetPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| FileSetPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        FileSetPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTree)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("File Set Panel".into())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        13
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use util::path;
+    use workspace::AppState;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            AppState::test(cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+        });
+    }
+
+    async fn build_panel(
+        cx: &mut TestAppContext,
+    ) -> (Entity<FileSetPanel>, &mut VisualTestContext) {
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let panel = workspace.update(cx, |workspace, cx| {
+            FileSetPanel::new(workspace, None, SerializedFileSet::default(), cx)
+        });
+        (panel, cx)
+    }
+
+    fn file(name: &str) -> PathBuf {
+        Path::new(path!("/files")).join(name)
+    }
+
+    #[gpui::test]
+    async fn test_add_and_remove_files(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (panel, cx) = build_panel(cx).await;
+
+        panel.update(cx, |panel, cx| {
+            panel.set_files(
+                Some("Review".into()),
+                vec![file("a.rs"), file("b.rs"), file("c.rs")],
+                cx,
+            );
+
+            // Adding a file that's already in the set only selects it.
+            panel.add_file(file("b.rs"), cx);
+            assert_eq!(panel.files(), [file("a.rs"), file("b.rs"), file("c.rs")]);
+            assert_eq!(panel.selected_index, Some(1));
+
+            panel.add_file(file("d.rs"), cx);
+            assert_eq!(
+                panel.files(),
+                [file("a.rs"), file("b.rs"), file("c.rs"), file("d.rs")]
+            );
+            assert_eq!(panel.active_index, Some(3));
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.remove_selected(&RemoveEntry, window, cx);
+            assert_eq!(panel.files(), [file("a.rs"), file("c.rs"), file("d.rs")]);
+            assert_eq!(panel.selected_index, Some(1));
+            assert_eq!(panel.active_index, Some(2));
+
+            panel.select(2, cx);
+            panel.remove_selected(&RemoveEntry, window, cx);
+            assert_eq!(panel.files(), [file("a.rs"), file("c.rs")]);
+            assert_eq!(panel.selected_index, Some(1));
+            assert_eq!(panel.active_index, None);
+        });
+
+        panel.update(cx, |panel, cx| {
+            panel.set_files(None, Vec::new(), cx);
+            assert!(panel.files().is_empty());
+            assert_eq!(panel.name, None);
+            assert_eq!(panel.selected_index, None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_select_adjacent_file(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (panel, cx) = build_panel(cx).await;
+
+        panel.update(cx, |panel, cx| {
+            assert_eq!(panel.select_adjacent(None, 1, cx), None);
+
+            panel.set_files(None, vec![file("a.rs"), file("b.rs"), file("c.rs")], cx);
+            // Without an active or selected file, going back starts from the end.
+            assert_eq!(panel.select_adjacent(None, -1, cx), Some(file("c.rs")));
+            assert_eq!(panel.select_adjacent(None, -1, cx), Some(file("b.rs")));
+
+            // The active file takes precedence over the selection.
+            let active = file("a.rs");
+            assert_eq!(
+                panel.select_adjacent(Some(&active), 1, cx),
+                Some(file("b.rs"))
+            );
+            let active = file("c.rs");
+            assert_eq!(
+                panel.select_adjacent(Some(&active), 1, cx),
+                Some(file("c.rs"))
+            );
+
+            // An active file outside of the set moves from the selection.
+            let active = file("other.rs");
+            assert_eq!(
+                panel.select_adjacent(Some(&active), -5, cx),
+                Some(file("a.rs"))
+            );
+            assert_eq!(panel.selected_index, Some(0));
+
+            panel.set_files(None, vec![file("a.rs"), file("b.rs")], cx);
+            assert_eq!(panel.select_adjacent(None, 1, cx), Some(file("a.rs")));
+        });
+    }
+}
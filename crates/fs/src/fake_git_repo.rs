@@ -112,6 +112,15 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn diff_working_tree_with_commit(
+        &self,
+        _commit: String,
+        _path: RepoPath,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<git::repository::CommitDiff>> {
+        unimplemented!()
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
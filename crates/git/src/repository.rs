@@ -361,6 +361,15 @@ pub trait GitRepository: Send + Sync {
     fn show(&self, commit: String) -> BoxFuture<Result<CommitDetails>>;
 
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>>;
+    /// Loads the files in `path` that differ between `commit` and the working tree, with their text
+    /// in the commit as the old text and their text in the working tree as the new text. Untracked
+    /// files aren't included.
+    fn diff_working_tree_with_commit(
+        &self,
+        commit: String,
+        path: RepoPath,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<CommitDiff>>;
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<Result<crate::blame::Blame>>;
 
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
@@ -663,6 +672,102 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn diff_working_tree_with_commit(
+        &self,
+        commit: String,
+        path: RepoPath,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<CommitDiff>> {
+        let Some(working_directory) = self.repository.lock().workdir().map(ToOwned::to_owned)
+        else {
+            return future::ready(Err(anyhow!("no working directory"))).boxed();
+        };
+        cx.background_spawn(async move {
+            let pathspec = if path.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                path.as_ref()
+            };
+            let diff_output = util::command::new_std_command("git")
+                .current_dir(&working_directory)
+                .args([
+                    "--no-optional-locks",
+                    "diff",
+                    "-z",
+                    "--no-renames",
+                    "--name-status",
+                ])
+                .arg(&commit)
+                .arg("--")
+                .arg(pathspec)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .output()
+                .context("starting git diff process")?;
+            anyhow::ensure!(
+                diff_output.status.success(),
+                "git diff failed: {}",
+                String::from_utf8_lossy(&diff_output.stderr)
+            );
+            let diff_stdout = String::from_utf8_lossy(&diff_output.stdout);
+            let changes = parse_git_diff_name_status(&diff_stdout);
+
+            let mut cat_file_process = util::command::new_std_command("git")
+                .current_dir(&working_directory)
+                .args(["--no-optional-locks", "cat-file", "--batch=%(objectsize)"])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .context("starting git cat-file process")?;
+
+            use std::io::Write as _;
+            let mut files = Vec::<CommitFile>::new();
+            let mut stdin = BufWriter::with_capacity(512, cat_file_process.stdin.take().unwrap());
+            let mut stdout = BufReader::new(cat_file_process.stdout.take().unwrap());
+            let mut info_line = String::new();
+            let mut newline = [b'\0'];
+            for (path, status_code) in changes {
+                let old_text = match status_code {
+                    StatusCode::Modified | StatusCode::Deleted => {
+                        writeln!(&mut stdin, "{commit}:{}", path.display())?;
+                        stdin.flush()?;
+
+                        info_line.clear();
+                        stdout.read_line(&mut info_line)?;
+                        let len = info_line.trim_end().parse().with_context(|| {
+                            format!("invalid object size output from cat-file {info_line}")
+                        })?;
+                        let mut text = vec![0; len];
+                        stdout.read_exact(&mut text)?;
+                        stdout.read_exact(&mut newline)?;
+                        Some(String::from_utf8_lossy(&text).to_string())
+                    }
+                    StatusCode::Added => None,
+                    _ => continue,
+                };
+                let new_text = match status_code {
+                    StatusCode::Modified | StatusCode::Added => {
+                        let text = std::fs::read(working_directory.join(path))
+                            .with_context(|| format!("reading {path:?}"))?;
+                        Some(String::from_utf8_lossy(&text).to_string())
+                    }
+                    _ => None,
+                };
+
+                files.push(CommitFile {
+                    path: path.into(),
+                    old_text,
+                    new_text,
+                })
+            }
+
+            Ok(CommitDiff { files })
+        })
+        .boxed()
+    }
+
     fn reset(
         &self,
         commit: String,
@@ -2195,6 +2300,114 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_diff_working_tree_with_commit(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+
+        smol::fs::create_dir_all(repo_dir.path().join("src/nested"))
+            .await
+            .unwrap();
+        for (path, text) in [
+            ("README.md", "readme"),
+            ("src/lib.rs", "lib"),
+            ("src/nested/removed.rs", "removed"),
+        ] {
+            smol::fs::write(repo_dir.path().join(path), text)
+                .await
+                .unwrap();
+        }
+        let paths = ["README.md", "src/lib.rs", "src/nested/removed.rs"]
+            .into_iter()
+            .map(RepoPath::from_str)
+            .collect::<Vec<_>>();
+        repo.stage_paths(paths, Arc::new(HashMap::default()))
+            .await
+            .unwrap();
+        repo.commit(
+            "Initial commit".into(),
+            None,
+            CommitOptions::default(),
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap();
+
+        smol::fs::write(repo_dir.path().join("README.md"), "changed outside")
+            .await
+            .unwrap();
+        smol::fs::write(repo_dir.path().join("src/lib.rs"), "lib changed")
+            .await
+            .unwrap();
+        smol::fs::remove_file(repo_dir.path().join("src/nested/removed.rs"))
+            .await
+            .unwrap();
+        smol::fs::write(repo_dir.path().join("src/nested/added.rs"), "added")
+            .await
+            .unwrap();
+        smol::fs::write(repo_dir.path().join("src/untracked.rs"), "untracked")
+            .await
+            .unwrap();
+        repo.stage_paths(
+            vec![
+                RepoPath::from_str("src/lib.rs"),
+                RepoPath::from_str("src/nested/added.rs"),
+                RepoPath::from_str("src/nested/removed.rs"),
+            ],
+            Arc::new(HashMap::default()),
+        )
+        .await
+        .unwrap();
+        repo.commit(
+            "Second commit".into(),
+            None,
+            CommitOptions::default(),
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap();
+
+        // The working tree is compared with the first commit rather than with HEAD.
+        let diff = repo
+            .diff_working_tree_with_commit(
+                "HEAD~1".into(),
+                RepoPath::from_str("src"),
+                cx.to_async(),
+            )
+            .await
+            .unwrap();
+        let mut files = diff
+            .files
+            .into_iter()
+            .map(|file| (file.path, file.old_text, file.new_text))
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            files,
+            vec![
+                (
+                    RepoPath::from_str("src/lib.rs"),
+                    Some("lib".to_string()),
+                    Some("lib changed".to_string())
+                ),
+                (
+                    RepoPath::from_str("src/nested/added.rs"),
+                    None,
+                    Some("added".to_string())
+                ),
+                (
+                    RepoPath::from_str("src/nested/removed.rs"),
+                    Some("removed".to_string()),
+                    None
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_branches_parsing() {
         // suppress "help: octal escapes are not supported, `\0` is always null"
@@ -1,7 +1,7 @@
 use collections::{HashMap, HashSet};
 use editor::{
     ConflictsOurs, ConflictsOursMarker, ConflictsOuter, ConflictsTheirs, ConflictsTheirsMarker,
-    Editor, EditorEvent, ExcerptId, MultiBuffer, RowHighlightOptions,
+    Editor, EditorEvent, ExcerptId, MultiBuffer, RowHighlightOptions, ScrollbarMarker,
     display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
 };
 use gpui::{
@@ -17,6 +17,9 @@ use ui::{
 };
 use util::{ResultExt as _, debug_panic, maybe};
 
+/// The scrollbar marker layer that shows where the unresolved conflicts are.
+const CONFLICTS_SCROLLBAR_LAYER: &str = "conflicts";
+
 pub(crate) struct ConflictAddon {
     buffers: HashMap<BufferId, BufferConflicts>,
 }
@@ -167,6 +170,7 @@ fn buffers_removed(editor: &mut Editor, removed_buffer_ids: &[BufferId], cx: &mu
             }
         });
     editor.remove_blocks(removed_block_ids, None, cx);
+    update_conflict_scrollbar_markers(editor, cx);
 }
 
 fn conflicts_updated(
@@ -315,6 +319,32 @@ fn conflicts_updated(
                 .zip(new_block_ids),
         );
     }
+    update_conflict_scrollbar_markers(editor, cx);
+}
+
+fn update_conflict_scrollbar_markers(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    // The scrollbar only shows markers in singleton buffers.
+    let Some((&excerpt_id, _, _)) = snapshot.as_singleton() else {
+        return;
+    };
+    let color = cx.theme().status().conflict;
+    let markers = editor
+        .addon::<ConflictAddon>()
+        .unwrap()
+        .buffers
+        .values()
+        .flat_map(|buffer_conflicts| &buffer_conflicts.block_ids)
+        .filter_map(|(range, _)| {
+            let start = snapshot.anchor_in_excerpt(excerpt_id, range.start)?;
+            let end = snapshot.anchor_in_excerpt(excerpt_id, range.end)?;
+            Some(ScrollbarMarker {
+                range: start..end,
+                color,
+            })
+        })
+        .collect::<Vec<_>>();
+    editor.set_scrollbar_markers(CONFLICTS_SCROLLBAR_LAYER, markers, cx);
 }
 
 fn update_conflict_highlighting(
@@ -0,0 +1,875 @@
+use std::{
+    collections::BTreeSet,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashSet;
+use futures::StreamExt as _;
+use git::repository::{CommitDiff, RepoPath};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, PathPromptOptions, Render, ScrollStrategy, SharedString, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, impl_actions, uniform_list,
+};
+use project::{DirectoryLister, Fs, Project, WorktreeId, git_store::Repository};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::paths::{PathExt as _, PathTreeRow, path_tree_rows};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr as _,
+};
+
+use crate::{file_diff_view::FileDiffView, picker_prompt};
+
+actions!(folder_diff, [CompareFolders]);
+
+/// Compares a folder in a git repository with the same folder in a branch or another git ref,
+/// like `HEAD~3`. When no ref is given, it's picked from the repository's branches.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CompareFolderWithRef {
+    #[serde(default)]
+    pub git_ref: Option<String>,
+}
+
+impl_actions!(folder_diff, [CompareFolderWithRef]);
+
+/// Folders with more files than this aren't compared, to avoid walking a whole disk by accident.
+const MAX_FILES_PER_FOLDER: usize = 50_000;
+
+pub(crate) fn register(workspace: &mut Workspace) {
+    workspace.register_action(compare_folders);
+    workspace.register_action(compare_folder_with_ref);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FolderDiffStatus {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FolderDiffEntry {
+    /// The path of the file, relative to the compared folders.
+    pub path: Arc<Path>,
+    pub status: FolderDiffStatus,
+    pub old_text: Option<String>,
+    pub new_text: Option<String>,
+    pub is_binary: bool,
+}
+
+impl FolderDiffEntry {
+    fn new(path: Arc<Path>, old_text: Option<Vec<u8>>, new_text: Option<Vec<u8>>) -> Self {
+        let status = match (&old_text, &new_text) {
+            (None, _) => FolderDiffStatus::Added,
+            (_, None) => FolderDiffStatus::Removed,
+            _ => FolderDiffStatus::Modified,
+        };
+        let is_binary = [&old_text, &new_text]
+            .into_iter()
+            .flatten()
+            .any(|text| text.iter().take(8000).any(|byte| *byte == 0));
+        let to_string = |text: Option<Vec<u8>>| {
+            text.filter(|_| !is_binary)
+                .map(|text| String::from_utf8_lossy(&text).into_owned())
+        };
+        Self {
+            path,
+            status,
+            old_text: to_string(old_text),
+            new_text: to_string(new_text),
+            is_binary,
+        }
+    }
+}
+
+/// Compares the files in two folders by their contents, returning the files that were added,
+/// removed, or modified in `new_folder`, sorted by path.
+pub async fn compare_folder_contents(
+    fs: &dyn Fs,
+    old_folder: &Path,
+    new_folder: &Path,
+) -> Result<Vec<FolderDiffEntry>> {
+    let old_files = list_files(fs, old_folder).await?;
+    let new_files = list_files(fs, new_folder).await?;
+    let mut entries = Vec::new();
+    for path in old_files.union(&new_files) {
+        let old_text = if old_files.contains(path) {
+            Some(fs.load_bytes(&old_folder.join(path)).await?)
+        } else {
+            None
+        };
+        let new_text = if new_files.contains(path) {
+            Some(fs.load_bytes(&new_folder.join(path)).await?)
+        } else {
+            None
+        };
+        if old_text != new_text {
+            entries.push(FolderDiffEntry::new(
+                path.as_path().into(),
+                old_text,
+                new_text,
+            ));
+        }
+    }
+    Ok(entries)
+}
+
+/// Lists the paths of the files in the folder and its subfolders, relative to the folder. Git
+/// directories and symlinks to folders are skipped.
+async fn list_files(fs: &dyn Fs, folder: &Path) -> Result<BTreeSet<PathBuf>> {
+    let mut files = BTreeSet::new();
+    let mut folders = vec![folder.to_path_buf()];
+    while let Some(dir) = folders.pop() {
+        let mut children = fs
+            .read_dir(&dir)
+            .await
+            .with_context(|| format!("reading {dir:?}"))?;
+        while let Some(child) = children.next().await {
+            let child = child?;
+            if child.file_name().is_some_and(|name| name == ".git") {
+                continue;
+            }
+            let Some(metadata) = fs.metadata(&child).await? else {
+                continue;
+            };
+            if metadata.is_dir {
+                if !metadata.is_symlink {
+                    folders.push(child);
+                }
+                continue;
+            }
+            files.insert(child.strip_prefix(folder)?.to_path_buf());
+            anyhow::ensure!(
+                files.len() <= MAX_FILES_PER_FOLDER,
+                "{folder:?} has more than {MAX_FILES_PER_FOLDER} files"
+            );
+        }
+    }
+    Ok(files)
+}
+
+fn compare_folders(
+    workspace: &mut Workspace,
+    _: &CompareFolders,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let fs = workspace.app_state().fs.clone();
+    let prompt = prompt_for_folders(workspace, true, window, cx);
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Some(mut folders) = prompt.await else {
+            return Ok(());
+        };
+        // Let the second folder be picked separately, for path prompts that can't select
+        // several folders.
+        if folders.len() == 1 {
+            let prompt = workspace.update_in(cx, |workspace, window, cx| {
+                prompt_for_folders(workspace, false, window, cx)
+            })?;
+            let Some(more_folders) = prompt.await else {
+                return Ok(());
+            };
+            folders.extend(more_folders);
+        }
+        let [old_folder, new_folder] = <[PathBuf; 2]>::try_from(folders)
+            .map_err(|_| anyhow::anyhow!("Select two folders to compare"))?;
+        let entries = compare_folder_contents(fs.as_ref(), &old_folder, &new_folder).await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            let old_label = old_folder.compact().to_string_lossy().into_owned().into();
+            let new_label = new_folder.compact().to_string_lossy().into_owned().into();
+            FolderDiffView::deploy(
+                workspace,
+                old_label,
+                new_label,
+                &new_folder,
+                entries,
+                window,
+                cx,
+            )
+        })?
+    })
+    .detach_and_prompt_err("Failed to compare folders", window, cx, |_, _, _| None);
+}
+
+fn compare_folder_with_ref(
+    workspace: &mut Workspace,
+    action: &CompareFolderWithRef,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let git_store = workspace.project().read(cx).git_store().clone();
+    let git_ref = action.git_ref.clone();
+    let prompt = prompt_for_folders(workspace, false, window, cx);
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Some(folder) = prompt.await.and_then(|mut folders| folders.pop()) else {
+            return Ok(());
+        };
+        let repository = git_store
+            .read_with(cx, |git_store, cx| {
+                git_store
+                    .repositories()
+                    .values()
+                    .filter(|repository| {
+                        folder.starts_with(&repository.read(cx).work_directory_abs_path)
+                    })
+                    .max_by_key(|repository| {
+                        repository
+                            .read(cx)
+                            .work_directory_abs_path
+                            .components()
+                            .count()
+                    })
+                    .cloned()
+            })?
+            .with_context(|| format!("{} isn't in a git repository", folder.display()))?;
+        let git_ref = match git_ref {
+            Some(git_ref) => git_ref,
+            None => {
+                let Some(git_ref) = pick_git_ref(&repository, &workspace, cx).await? else {
+                    return Ok(());
+                };
+                git_ref
+            }
+        };
+        let repo_path = repository
+            .read_with(cx, |repository, _| {
+                repository.abs_path_to_repo_path(&folder)
+            })?
+            .context("folder isn't in the repository")?;
+        let diff = repository
+            .update(cx, |repository, _| {
+                repository.diff_working_tree_with_commit(git_ref.clone(), repo_path.clone())
+            })?
+            .await??;
+        let entries = commit_diff_entries(diff, &repo_path);
+        workspace.update_in(cx, |workspace, window, cx| {
+            let folder_label = folder.compact().to_string_lossy().into_owned();
+            FolderDiffView::deploy(
+                workspace,
+                format!("{folder_label} ({git_ref})").into(),
+                folder_label.into(),
+                &folder,
+                entries,
+                window,
+                cx,
+            )
+        })?
+    })
+    .detach_and_prompt_err("Failed to compare folder", window, cx, |_, _, _| None);
+}
+
+/// Turns the files of a diff with a commit into entries with paths relative to `folder`, sorted
+/// by path.
+fn commit_diff_entries(diff: CommitDiff, folder: &RepoPath) -> Vec<FolderDiffEntry> {
+    let mut entries = diff
+        .files
+        .into_iter()
+        .filter_map(|file| {
+            let path = file.path.strip_prefix(&folder.0).ok()?.into();
+            Some(FolderDiffEntry::new(
+                path,
+                file.old_text.map(String::into_bytes),
+                file.new_text.map(String::into_bytes),
+            ))
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries
+}
+
+async fn pick_git_ref(
+    repository: &Entity<Repository>,
+    workspace: &WeakEntity<Workspace>,
+    cx: &mut gpui::AsyncWindowContext,
+) -> Result<Option<String>> {
+    let branches = repository
+        .update(cx, |repository, _| repository.branches())?
+        .await??;
+    let options = std::iter::once(SharedString::from("HEAD"))
+        .chain(
+            branches
+                .iter()
+                .filter(|branch| !branch.is_head)
+                .map(|branch| SharedString::from(branch.name().to_string())),
+        )
+        .collect::<Vec<_>>();
+    let picked = workspace
+        .update_in(cx, |_, window, cx| {
+            picker_prompt::prompt(
+                "Compare with…",
+                options.clone(),
+                workspace.clone(),
+                window,
+                cx,
+            )
+        })?
+        .await;
+    Ok(picked
+        .and_then(|ix| options.get(ix))
+        .map(ToString::to_string))
+}
+
+fn prompt_for_folders(
+    workspace: &mut Workspace,
+    multiple: bool,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) -> Task<Option<Vec<PathBuf>>> {
+    let lister = DirectoryLister::Local(
+        workspace.project().clone(),
+        workspace.app_state().fs.clone(),
+    );
+    let prompt = workspace.prompt_for_open_path(
+        PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple,
+        },
+        lister,
+        window,
+        cx,
+    );
+    cx.background_spawn(async move { prompt.await.ok().flatten() })
+}
+
+/// Lists the files that differ between two folders, or between a folder and a git ref, and
+/// opens the diff of each of them.
+pub struct FolderDiffView {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    worktree_id: WorktreeId,
+    old_label: SharedString,
+    new_label: SharedString,
+    entries: Vec<FolderDiffEntry>,
+    collapsed: HashSet<Arc<Path>>,
+    rows: Vec<PathTreeRow>,
+    selected_row: Option<usize>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+}
+
+impl FolderDiffView {
+    fn deploy(
+        workspace: &mut Workspace,
+        old_label: SharedString,
+        new_label: SharedString,
+        new_folder: &Path,
+        entries: Vec<FolderDiffEntry>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Result<()> {
+        let project = workspace.project().clone();
+        // The compared files are shown with the settings of the worktree they're in, or of the
+        // first worktree when they're outside of the project.
+        let worktree_id = project
+            .read(cx)
+            .find_worktree(new_folder, cx)
+            .map(|(worktree, _)| worktree)
+            .or_else(|| project.read(cx).worktrees(cx).next())
+            .map(|worktree| worktree.read(cx).id())
+            .context("Open a folder in the project to compare folders")?;
+        let workspace_handle = cx.entity().downgrade();
+        let view = cx.new(|cx| {
+            let rows = path_tree_rows(
+                entries.iter().map(|entry| entry.path.as_ref()),
+                &HashSet::default(),
+            );
+            Self {
+                workspace: workspace_handle,
+                project,
+                worktree_id,
+                old_label,
+                new_label,
+                entries,
+                collapsed: HashSet::default(),
+                rows,
+                selected_row: None,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+            }
+        });
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+        Ok(())
+    }
+
+    /// Compares the folders the other way around.
+    fn swap(&mut self, cx: &mut Context<Self>) {
+        std::mem::swap(&mut self.old_label, &mut self.new_label);
+        for entry in &mut self.entries {
+            std::mem::swap(&mut entry.old_text, &mut entry.new_text);
+            entry.status = match entry.status {
+                FolderDiffStatus::Added => FolderDiffStatus::Removed,
+                FolderDiffStatus::Removed => FolderDiffStatus::Added,
+                FolderDiffStatus::Modified => FolderDiffStatus::Modified,
+            };
+        }
+        cx.notify();
+    }
+
+    fn toggle_directory(&mut self, path: Arc<Path>, cx: &mut Context<Self>) {
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+        self.rows = path_tree_rows(
+            self.entries.iter().map(|entry| entry.path.as_ref()),
+            &self.collapsed,
+        );
+        self.selected_row = self
+            .selected_row
+            .map(|ix| ix.min(self.rows.len().saturating_sub(1)));
+        cx.notify();
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_row = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let count = self.rows.len();
+        if count > 0 {
+            let ix = self.selected_row.map_or(0, |ix| (ix + 1).min(count - 1));
+            self.select(ix, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.rows.is_empty() {
+            let ix = self.selected_row.map_or(0, |ix| ix.saturating_sub(1));
+            self.select(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_row {
+            self.open_row(ix, window, cx);
+        }
+    }
+
+    fn open_row(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        match self.rows.get(ix).cloned() {
+            Some(PathTreeRow::Directory { path, .. }) => self.toggle_directory(path, cx),
+            Some(PathTreeRow::File { entry_ix, .. }) => self.open_entry(entry_ix, window, cx),
+            None => {}
+        }
+    }
+
+    fn open_entry(&mut self, entry_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(entry_ix) else {
+            return;
+        };
+        if entry.is_binary {
+            return;
+        }
+        let tooltip = format!(
+            "{}: {} ↔ {}",
+            entry.path.display(),
+            self.old_label,
+            self.new_label
+        )
+        .into();
+        let (path, old_text, new_text) = (
+            entry.path.clone(),
+            entry.old_text.clone(),
+            entry.new_text.clone(),
+        );
+        let (worktree_id, project) = (self.worktree_id, self.project.clone());
+        let view = cx.new(|cx| {
+            FileDiffView::new(
+                path,
+                old_text,
+                new_text,
+                tooltip,
+                worktree_id,
+                project,
+                window,
+                cx,
+            )
+        });
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx)
+            })
+            .ok();
+    }
+
+    fn render_rows(
+        &mut self,
+        range: Range<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| Some(self.render_row(ix, self.rows.get(ix)?, cx)))
+            .collect()
+    }
+
+    fn render_row(&self, ix: usize, row: &PathTreeRow, cx: &Context<Self>) -> AnyElement {
+        let item = ListItem::new(ix)
+            .spacing(ListItemSpacing::Dense)
+            .toggle_state(self.selected_row == Some(ix))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select(ix, cx);
+                this.open_row(ix, window, cx);
+            }));
+        match row {
+            PathTreeRow::Directory { path, depth } => {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let toggled_path = path.clone();
+                item.indent_level(*depth)
+                    .toggle(Some(!self.collapsed.contains(path)))
+                    .on_toggle(cx.listener(move |this, _, _, cx| {
+                        this.toggle_directory(toggled_path.clone(), cx)
+                    }))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Icon::new(IconName::Folder).color(Color::Muted))
+                            .child(Label::new(name).size(LabelSize::Small)),
+                    )
+                    .into_any_element()
+            }
+            PathTreeRow::File { entry_ix, depth } => {
+                let entry = &self.entries[*entry_ix];
+                let name = entry
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let (status, color) = match entry.status {
+                    FolderDiffStatus::Added => ("A", Color::Created),
+                    FolderDiffStatus::Removed => ("D", Color::Deleted),
+                    FolderDiffStatus::Modified => ("M", Color::Modified),
+                };
+                item.indent_level(*depth + 1)
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(status).size(LabelSize::Small).color(color))
+                            .child(Label::new(name).size(LabelSize::Small).color(color))
+                            .when(entry.is_binary, |this| {
+                                this.child(
+                                    Label::new("binary")
+                                        .size(LabelSize::XSmall)
+                                        .color(Color::Muted),
+                                )
+                            }),
+                    )
+                    .into_any_element()
+            }
+        }
+    }
+}
+
+impl Render for FolderDiffView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let count = |status| {
+            self.entries
+                .iter()
+                .filter(|entry| entry.status == status)
+                .count()
+        };
+        let summary = format!(
+            "{} added, {} removed, {} modified",
+            count(FolderDiffStatus::Added),
+            count(FolderDiffStatus::Removed),
+            count(FolderDiffStatus::Modified)
+        );
+        v_flex()
+            .key_context("FolderDiffView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .gap_2()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        v_flex()
+                            .min_w_0()
+                            .child(
+                                Label::new(format!("{} ↔ {}", self.old_label, self.new_label))
+                                    .truncate(),
+                            )
+                            .child(
+                                Label::new(summary)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .child(
+                        Button::new("swap-folders", "Swap")
+                            .icon(IconName::ArrowRightLeft)
+                            .icon_position(IconPosition::Start)
+                            .on_click(cx.listener(|this, _, _, cx| this.swap(cx))),
+                    ),
+            )
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("The folders have the same files.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "folder-diff",
+                            self.rows.len(),
+                            Self::render_rows,
+                        )
+                        .p_1()
+                        .track_scroll(self.scroll_handle.clone())
+                        .flex_grow(),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for FolderDiffView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<ItemEvent> for FolderDiffView {}
+
+impl Item for FolderDiffView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let name = |label: &SharedString| {
+            Path::new(label.as_ref())
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| label.to_string())
+        };
+        format!("{} ↔ {}", name(&self.old_label), name(&self.new_label)).into()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("{} ↔ {}", self.old_label, self.new_label).into())
+    }
+
+    fn to_item_events(event: &ItemEvent, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Folder Diff Opened")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git::repository::CommitFile;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use util::path;
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_compare_folder_contents(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/old"),
+            json!({
+                ".git": { "HEAD": "ref: refs/heads/main" },
+                "same.txt": "same\n",
+                "changed.txt": "one\n",
+                "removed.txt": "gone\n",
+                "src": { "lib.rs": "fn a() {}\n" },
+            }),
+        )
+        .await;
+        fs.insert_tree(
+            path!("/new"),
+            json!({
+                "same.txt": "same\n",
+                "changed.txt": "two\n",
+                "src": { "lib.rs": "fn a() {}\n", "main.rs": "fn main() {}\n" },
+            }),
+        )
+        .await;
+
+        let entries = compare_folder_contents(
+            fs.as_ref(),
+            Path::new(path!("/old")),
+            Path::new(path!("/new")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.path.as_ref(), entry.status))
+                .collect::<Vec<_>>(),
+            vec![
+                (Path::new("changed.txt"), FolderDiffStatus::Modified),
+                (Path::new("removed.txt"), FolderDiffStatus::Removed),
+                (Path::new("src/main.rs"), FolderDiffStatus::Added),
+            ]
+        );
+        assert_eq!(entries[0].old_text.as_deref(), Some("one\n"));
+        assert_eq!(entries[0].new_text.as_deref(), Some("two\n"));
+    }
+
+    #[gpui::test]
+    async fn test_compare_nested_folders(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/old"),
+            json!({
+                "a": {
+                    "b": {
+                        "c": { "deep.txt": "old\n", "same.txt": "same\n" },
+                        "removed": { "one.txt": "1\n", "two.txt": "2\n" },
+                    },
+                    "empty": {},
+                },
+            }),
+        )
+        .await;
+        fs.insert_tree(
+            path!("/new"),
+            json!({
+                "a": {
+                    "b": {
+                        "c": { "deep.txt": "new\n", "same.txt": "same\n" },
+                        "added": { "nested": { "three.txt": "3\n" } },
+                    },
+                },
+            }),
+        )
+        .await;
+        fs.insert_file(path!("/old/a/image.png"), vec![0x89, 0, 1])
+            .await;
+        fs.insert_file(path!("/new/a/image.png"), vec![0x89, 0, 2])
+            .await;
+
+        let entries = compare_folder_contents(
+            fs.as_ref(),
+            Path::new(path!("/old")),
+            Path::new(path!("/new")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.path.as_ref(), entry.status, entry.is_binary))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Path::new("a/b/added/nested/three.txt"),
+                    FolderDiffStatus::Added,
+                    false
+                ),
+                (
+                    Path::new("a/b/c/deep.txt"),
+                    FolderDiffStatus::Modified,
+                    false
+                ),
+                (
+                    Path::new("a/b/removed/one.txt"),
+                    FolderDiffStatus::Removed,
+                    false
+                ),
+                (
+                    Path::new("a/b/removed/two.txt"),
+                    FolderDiffStatus::Removed,
+                    false
+                ),
+                (Path::new("a/image.png"), FolderDiffStatus::Modified, true),
+            ]
+        );
+        assert_eq!(entries[0].old_text, None);
+        assert_eq!(entries[0].new_text.as_deref(), Some("3\n"));
+        assert_eq!(entries[2].old_text.as_deref(), Some("1\n"));
+        assert_eq!(entries[2].new_text, None);
+        // The text of binary files isn't kept.
+        assert_eq!(entries[4].old_text, None);
+        assert_eq!(entries[4].new_text, None);
+    }
+
+    #[test]
+    fn test_commit_diff_entries() {
+        let file = |path: &str, old_text: Option<&str>, new_text: Option<&str>| CommitFile {
+            path: RepoPath::from_str(path),
+            old_text: old_text.map(ToString::to_string),
+            new_text: new_text.map(ToString::to_string),
+        };
+        let diff = CommitDiff {
+            files: vec![
+                file("src/lib.rs", Some("old\n"), Some("new\n")),
+                file("docs/readme.md", Some("old\n"), Some("new\n")),
+                file("src/nested/removed.rs", Some("gone\n"), None),
+                file("src/added.rs", None, Some("fn main() {}\n")),
+            ],
+        };
+
+        let entries = commit_diff_entries(diff, &RepoPath::from_str("src"));
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.path.as_ref(), entry.status))
+                .collect::<Vec<_>>(),
+            vec![
+                (Path::new("added.rs"), FolderDiffStatus::Added),
+                (Path::new("lib.rs"), FolderDiffStatus::Modified),
+                (Path::new("nested/removed.rs"), FolderDiffStatus::Removed),
+            ]
+        );
+        assert_eq!(entries[1].old_text.as_deref(), Some("old\n"));
+        assert_eq!(entries[1].new_text.as_deref(), Some("new\n"));
+    }
+}
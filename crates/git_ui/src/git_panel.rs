@@ -4323,8 +4323,8 @@ impl Panel for GitPanel {
         Some(ui::IconName::GitBranchSmall).filter(|_| GitPanelSettings::get_global(cx).button)
     }
 
-    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
-        Some("Git Panel")
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Git Panel".into())
     }
 
     fn toggle_action(&self) -> Box<dyn Action> {
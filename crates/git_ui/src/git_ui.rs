@@ -22,6 +22,8 @@ mod commit_modal;
 pub mod commit_tooltip;
 mod commit_view;
 mod conflict_view;
+mod file_diff_view;
+pub mod folder_diff;
 pub mod git_panel;
 mod git_panel_settings;
 pub mod onboarding;
@@ -44,6 +46,7 @@ pub fn init(cx: &mut App) {
 
     cx.observe_new(|workspace: &mut Workspace, _, cx| {
         ProjectDiff::register(workspace, cx);
+        folder_diff::register(workspace);
         CommitModal::register(workspace);
         git_panel::register(workspace);
         repository_selector::register(workspace);
@@ -7,7 +7,7 @@ use std::{fmt::Write, num::NonZeroU32, time::Duration};
 use text::{Point, Selection};
 use ui::{
     Button, ButtonCommon, Clickable, Context, FluentBuilder, IntoElement, LabelSize, ParentElement,
-    Render, Tooltip, Window, div,
+    Render, SharedString, Tooltip, Window, div,
 };
 use util::paths::FILE_ROW_COLUMN_DELIMITER;
 use workspace::{StatusItemView, Workspace, item::ItemHandle};
@@ -288,6 +288,10 @@ impl StatusItemView for CursorPosition {
 
         cx.notify();
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("cursor_position".into())
+    }
 }
 
 #[derive(Clone, Copy, Default, PartialEq, JsonSchema, Deserialize, Serialize)]
@@ -308,7 +312,7 @@ impl Settings for LineIndicatorFormat {
     type FileContent = Option<LineIndicatorFormatContent>;
 
     fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> anyhow::Result<Self> {
-        let format = [sources.release_channel, sources.user]
+        let format = [sources.profile, sources.release_channel, sources.user]
             .into_iter()
             .find_map(|value| value.copied().flatten())
             .unwrap_or(sources.default.ok_or_else(Self::missing_default)?);
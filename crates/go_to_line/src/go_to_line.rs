@@ -1,15 +1,20 @@
 pub mod cursor_position;
 
+use command_palette_hooks::{
+    CommandArgumentPrompt, CommandInterceptResult, CommandPaletteArgumentPrompts,
+};
 use cursor_position::{LineIndicatorFormat, UserCaretPosition};
 use editor::{
     Anchor, Editor, MultiBufferSnapshot, RowHighlightOptions, ToOffset, ToPoint, actions::Tab,
     scroll::Autoscroll,
 };
 use gpui::{
-    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, SharedString, Styled,
-    Subscription, div, prelude::*,
+    Action, App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, SharedString,
+    Styled, Subscription, div, impl_actions, prelude::*,
 };
 use language::Buffer;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use settings::Settings;
 use text::{Bias, Point};
 use theme::ActiveTheme;
@@ -17,9 +22,46 @@ use ui::prelude::*;
 use util::paths::FILE_ROW_COLUMN_DELIMITER;
 use workspace::ModalView;
 
+/// Moves the cursor to a line, and optionally a character within it, both starting at 1. Without
+/// a line, this opens the go to line modal, or prompts for the line inline in the command palette.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GoToPosition {
+    #[serde(default)]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub character: Option<u32>,
+}
+
+impl_actions!(go_to_line, [GoToPosition]);
+
 pub fn init(cx: &mut App) {
     LineIndicatorFormat::register(cx);
     cx.observe_new(GoToLine::register).detach();
+    CommandPaletteArgumentPrompts::update_global(cx, |prompts, _| {
+        prompts.register::<GoToPosition>(CommandArgumentPrompt {
+            placeholder: format!(
+                "Line number, optionally followed by {FILE_ROW_COLUMN_DELIMITER}character"
+            )
+            .into(),
+            build: Box::new(|argument, _| {
+                let (line, character) = parse_line_and_character(argument)?;
+                let string = match character {
+                    Some(character) => format!("Go to line {line}, character {character}"),
+                    None => format!("Go to line {line}"),
+                };
+                Some(CommandInterceptResult {
+                    action: GoToPosition {
+                        line: Some(line),
+                        character,
+                    }
+                    .boxed_clone(),
+                    string,
+                    positions: Vec::new(),
+                })
+            }),
+        });
+    });
 }
 
 pub struct GoToLine {
@@ -63,6 +105,26 @@ impl GoToLine {
                 })
             })
             .detach();
+
+        let handle = cx.entity().downgrade();
+        editor
+            .register_action(move |action: &GoToPosition, window, cx| {
+                let Some(line) = action.line else {
+                    window.dispatch_action(editor::actions::ToggleGoToLine.boxed_clone(), cx);
+                    return;
+                };
+                let Some(editor) = handle.upgrade() else {
+                    return;
+                };
+                editor.update(cx, |editor, cx| {
+                    let snapshot = editor.buffer().read(cx).snapshot(cx);
+                    let anchor = anchor_for_position(&snapshot, line, action.character);
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_anchor_ranges([anchor..anchor])
+                    });
+                });
+            })
+            .detach();
     }
 
     pub fn new(
@@ -198,45 +260,11 @@ impl GoToLine {
         cx: &Context<Editor>,
     ) -> Option<Anchor> {
         let (query_row, query_char) = self.line_and_char_from_query(cx)?;
-        let row = query_row.saturating_sub(1);
-        let character = query_char.unwrap_or(0).saturating_sub(1);
-
-        let start_offset = Point::new(row, 0).to_offset(snapshot);
-        const MAX_BYTES_IN_UTF_8: u32 = 4;
-        let max_end_offset = snapshot
-            .clip_point(
-                Point::new(row, character * MAX_BYTES_IN_UTF_8 + 1),
-                Bias::Right,
-            )
-            .to_offset(snapshot);
-
-        let mut chars_to_iterate = character;
-        let mut end_offset = start_offset;
-        'outer: for text_chunk in snapshot.text_for_range(start_offset..max_end_offset) {
-            let mut offset_increment = 0;
-            for c in text_chunk.chars() {
-                if chars_to_iterate == 0 {
-                    end_offset += offset_increment;
-                    break 'outer;
-                } else {
-                    chars_to_iterate -= 1;
-                    offset_increment += c.len_utf8();
-                }
-            }
-            end_offset += offset_increment;
-        }
-        Some(snapshot.anchor_before(snapshot.clip_offset(end_offset, Bias::Left)))
+        Some(anchor_for_position(snapshot, query_row, query_char))
     }
 
     fn line_and_char_from_query(&self, cx: &App) -> Option<(u32, Option<u32>)> {
-        let input = self.line_editor.read(cx).text(cx);
-        let mut components = input
-            .splitn(2, FILE_ROW_COLUMN_DELIMITER)
-            .map(str::trim)
-            .fuse();
-        let row = components.next().and_then(|row| row.parse::<u32>().ok())?;
-        let column = components.next().and_then(|col| col.parse::<u32>().ok());
-        Some((row, column))
+        parse_line_and_character(&self.line_editor.read(cx).text(cx))
     }
 
     fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
@@ -261,6 +289,52 @@ impl GoToLine {
     }
 }
 
+/// Parses a `line[:character]` query, where both numbers start at 1.
+fn parse_line_and_character(input: &str) -> Option<(u32, Option<u32>)> {
+    let mut components = input
+        .splitn(2, FILE_ROW_COLUMN_DELIMITER)
+        .map(str::trim)
+        .fuse();
+    let row = components.next().and_then(|row| row.parse::<u32>().ok())?;
+    let column = components.next().and_then(|col| col.parse::<u32>().ok());
+    Some((row, column))
+}
+
+fn anchor_for_position(
+    snapshot: &MultiBufferSnapshot,
+    line: u32,
+    character: Option<u32>,
+) -> Anchor {
+    let row = line.saturating_sub(1);
+    let character = character.unwrap_or(0).saturating_sub(1);
+
+    let start_offset = Point::new(row, 0).to_offset(snapshot);
+    const MAX_BYTES_IN_UTF_8: u32 = 4;
+    let max_end_offset = snapshot
+        .clip_point(
+            Point::new(row, character * MAX_BYTES_IN_UTF_8 + 1),
+            Bias::Right,
+        )
+        .to_offset(snapshot);
+
+    let mut chars_to_iterate = character;
+    let mut end_offset = start_offset;
+    'outer: for text_chunk in snapshot.text_for_range(start_offset..max_end_offset) {
+        let mut offset_increment = 0;
+        for c in text_chunk.chars() {
+            if chars_to_iterate == 0 {
+                end_offset += offset_increment;
+                break 'outer;
+            } else {
+                chars_to_iterate -= 1;
+                offset_increment += c.len_utf8();
+            }
+        }
+        end_offset += offset_increment;
+    }
+    snapshot.anchor_before(snapshot.clip_offset(end_offset, Bias::Left))
+}
+
 impl Render for GoToLine {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let help_text = match self.line_and_char_from_query(cx) {
@@ -0,0 +1,381 @@
+use std::{
+    hash::{Hash, Hasher},
+    mem,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use accesskit::{
+    Action, ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, Node, NodeId,
+    Rect, Role, Tree, TreeUpdate,
+};
+use collections::{FxHashMap, FxHasher};
+use parking_lot::Mutex;
+
+use crate::{Bounds, FocusHandle, Pixels, Window};
+
+pub use accesskit;
+
+/// The node that stands for the window itself, at the root of its accessibility tree.
+pub(crate) const ROOT_NODE_ID: NodeId = NodeId(0);
+
+/// A node of a window's accessibility tree, recorded while its element was painted.
+#[derive(Clone)]
+pub(crate) struct AccessibilityNodeRecord {
+    id: NodeId,
+    parent: NodeId,
+    node: Node,
+    focus_handle: Option<FocusHandle>,
+}
+
+/// What a window shares with the platform adapter that exposes its accessibility tree to
+/// assistive technologies, like screen readers.
+///
+/// The window only builds its tree while an assistive technology is reading it.
+#[derive(Default)]
+pub(crate) struct AccessibilitySource {
+    active: AtomicBool,
+    activated: AtomicBool,
+    action_requests: Mutex<Vec<ActionRequest>>,
+}
+
+impl AccessibilitySource {
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Returns whether an assistive technology started reading the window since this was last
+    /// called, so that the whole tree needs to be built.
+    pub(crate) fn take_activated(&self) -> bool {
+        self.activated.swap(false, Ordering::SeqCst)
+    }
+
+    pub(crate) fn take_action_requests(&self) -> Vec<ActionRequest> {
+        mem::take(&mut self.action_requests.lock())
+    }
+}
+
+/// Answers the platform adapter on behalf of a window's [`AccessibilitySource`].
+pub(crate) struct AccessibilityHandler(pub(crate) Arc<AccessibilitySource>);
+
+impl ActivationHandler for AccessibilityHandler {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        self.0.active.store(true, Ordering::SeqCst);
+        self.0.activated.store(true, Ordering::SeqCst);
+        // The window fills in the rest of the tree on its next frame.
+        Some(TreeUpdate {
+            nodes: vec![(ROOT_NODE_ID, Node::new(Role::Window))],
+            tree: Some(Tree::new(ROOT_NODE_ID)),
+            focus: ROOT_NODE_ID,
+        })
+    }
+}
+
+impl ActionHandler for AccessibilityHandler {
+    fn do_action(&mut self, request: ActionRequest) {
+        self.0.action_requests.lock().push(request);
+    }
+}
+
+impl DeactivationHandler for AccessibilityHandler {
+    fn deactivate_accessibility(&mut self) {
+        self.0.active.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Converts bounds in the window to the rectangles that accessibility nodes are placed with.
+pub fn accessibility_rect(bounds: Bounds<Pixels>) -> Rect {
+    Rect {
+        x0: bounds.left().0 as f64,
+        y0: bounds.top().0 as f64,
+        x1: bounds.right().0 as f64,
+        y1: bounds.bottom().0 as f64,
+    }
+}
+
+impl Window {
+    /// Returns whether an assistive technology, like a screen reader, is reading this window.
+    /// Elements only need to describe themselves with accessibility nodes while it is.
+    pub fn is_accessibility_active(&self) -> bool {
+        self.accessibility_active
+    }
+
+    /// Returns the id of the accessibility node with the given key, among the children of the
+    /// node being painted.
+    pub fn accessibility_node_id(&self, key: impl Hash) -> NodeId {
+        let mut hasher = FxHasher::default();
+        self.accessibility_node_stack
+            .last()
+            .unwrap_or(&ROOT_NODE_ID)
+            .hash(&mut hasher);
+        key.hash(&mut hasher);
+        // Keep clear of the root's id.
+        NodeId(hasher.finish().max(1))
+    }
+
+    /// Adds a node to the window's accessibility tree for the element being painted, so that
+    /// assistive technologies can read it. The nodes added in `f` become its children, and
+    /// the node gets focused along with `focus_handle`.
+    ///
+    /// Only the first node added with a given id is kept. The node is only built while
+    /// [`Window::is_accessibility_active`]. This method should only be called as part of the
+    /// paint phase of element drawing.
+    pub fn with_accessibility_node<R>(
+        &mut self,
+        id: NodeId,
+        node: impl FnOnce() -> Node,
+        focus_handle: Option<&FocusHandle>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.invalidator.debug_assert_paint();
+        if !self.accessibility_active {
+            return f(self);
+        }
+
+        let parent = *self
+            .accessibility_node_stack
+            .last()
+            .unwrap_or(&ROOT_NODE_ID);
+        self.next_frame
+            .accessibility_nodes
+            .push(AccessibilityNodeRecord {
+                id,
+                parent,
+                node: node(),
+                focus_handle: focus_handle.cloned(),
+            });
+        self.accessibility_node_stack.push(id);
+        let result = f(self);
+        self.accessibility_node_stack.pop();
+        result
+    }
+
+    /// Sends what changed in the accessibility tree since the last frame to the platform.
+    pub(crate) fn update_accessibility_tree(&mut self) {
+        if !self.accessibility_active {
+            return;
+        }
+
+        let mut root = Node::new(Role::Window);
+        // Nodes are placed in logical pixels, which the other platforms need scaled.
+        #[cfg(not(target_os = "macos"))]
+        root.set_transform(accesskit::Affine::scale(self.scale_factor() as f64));
+        let mut nodes = FxHashMap::default();
+        nodes.insert(ROOT_NODE_ID, root);
+        let mut focus = ROOT_NODE_ID;
+        for record in &self.rendered_frame.accessibility_nodes {
+            if nodes.contains_key(&record.id) || !nodes.contains_key(&record.parent) {
+                continue;
+            }
+            if record
+                .focus_handle
+                .as_ref()
+                .is_some_and(|focus_handle| focus_handle.is_focused(self))
+            {
+                focus = record.id;
+            }
+            nodes.insert(record.id, record.node.clone());
+            if let Some(parent) = nodes.get_mut(&record.parent) {
+                parent.push_child(record.id);
+            }
+        }
+
+        let changed_nodes = nodes
+            .iter()
+            .filter(|(id, node)| self.accessibility_nodes.get(id) != Some(node))
+            .map(|(id, node)| (*id, node.clone()))
+            .collect::<Vec<_>>();
+        if !changed_nodes.is_empty() || focus != self.accessibility_focus {
+            self.platform_window.update_accessibility_tree(
+                TreeUpdate {
+                    nodes: changed_nodes,
+                    tree: Some(Tree::new(ROOT_NODE_ID)),
+                    focus,
+                },
+                self.is_window_active(),
+            );
+        }
+        self.accessibility_nodes = nodes;
+        self.accessibility_focus = focus;
+    }
+
+    /// Starts building the accessibility tree when an assistive technology started reading the
+    /// window, and carries out what it asked for.
+    pub(crate) fn handle_accessibility_requests(&mut self) {
+        let source = self.accessibility_source.clone();
+        self.accessibility_active = source.is_active();
+        if source.take_activated() {
+            // Views that are cached didn't add their nodes while nothing was reading them.
+            self.accessibility_nodes.clear();
+            self.refresh();
+        }
+
+        for request in source.take_action_requests() {
+            if !matches!(request.action, Action::Focus) {
+                continue;
+            }
+            let focus_handle = self
+                .rendered_frame
+                .accessibility_nodes
+                .iter()
+                .find(|record| record.id == request.target)
+                .and_then(|record| record.focus_handle.clone());
+            if let Some(focus_handle) = focus_handle {
+                self.focus(&focus_handle);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Action, ActionRequest, NodeId, Role, TreeUpdate};
+
+    use crate::{
+        self as gpui, AppContext as _, Context, FocusHandle, InteractiveElement, IntoElement,
+        ParentElement, Render, SharedString, TestAppContext, Window, WindowHandle, div,
+    };
+
+    use super::ROOT_NODE_ID;
+
+    struct TestView {
+        focus_handle: FocusHandle,
+        label: SharedString,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div().accessibility(Role::Group, "group").child(
+                div()
+                    .track_focus(&self.focus_handle)
+                    .accessibility(Role::Button, self.label.clone()),
+            )
+        }
+    }
+
+    fn open_window(cx: &mut TestAppContext) -> WindowHandle<TestView> {
+        cx.update(|cx| {
+            cx.open_window(Default::default(), |_, cx| {
+                cx.new(|cx| TestView {
+                    focus_handle: cx.focus_handle(),
+                    label: "button".into(),
+                })
+            })
+            .unwrap()
+        })
+    }
+
+    /// Runs what happens at the start of a frame, and draws the frame if needed.
+    fn next_frame(window: WindowHandle<TestView>, cx: &mut TestAppContext) {
+        window
+            .update(cx, |_, window, _| window.handle_accessibility_requests())
+            .unwrap();
+    }
+
+    fn node_with_role(update: &TreeUpdate, role: Role) -> NodeId {
+        update
+            .nodes
+            .iter()
+            .find(|(_, node)| node.role() == role)
+            .map(|(id, _)| *id)
+            .unwrap()
+    }
+
+    #[gpui::test]
+    fn test_accessibility_tree_is_sent_while_read(cx: &mut TestAppContext) {
+        let window = open_window(cx);
+        let test_window = cx.test_window(window.into());
+        next_frame(window, cx);
+        assert!(test_window.take_accessibility_updates().is_empty());
+
+        test_window.simulate_accessibility_active(true);
+        next_frame(window, cx);
+        let updates = test_window.take_accessibility_updates();
+        assert_eq!(updates.len(), 1);
+        let update = &updates[0];
+        let group = node_with_role(update, Role::Group);
+        let button = node_with_role(update, Role::Button);
+        let node = |id| {
+            &update
+                .nodes
+                .iter()
+                .find(|(node_id, _)| *node_id == id)
+                .unwrap()
+                .1
+        };
+        assert_eq!(node(ROOT_NODE_ID).children(), &[group]);
+        assert_eq!(node(group).children(), &[button]);
+        assert_eq!(node(button).label(), Some("button"));
+        assert!(node(button).supports_action(Action::Focus));
+        assert_eq!(update.focus, ROOT_NODE_ID);
+
+        // Frames that don't change the tree don't send anything.
+        window.update(cx, |_, window, _| window.refresh()).unwrap();
+        next_frame(window, cx);
+        assert!(test_window.take_accessibility_updates().is_empty());
+
+        // Only the nodes that changed are sent.
+        window
+            .update(cx, |view, _, cx| {
+                view.label = "renamed".into();
+                cx.notify();
+            })
+            .unwrap();
+        next_frame(window, cx);
+        let updates = test_window.take_accessibility_updates();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].nodes.len(), 2);
+        let renamed = node_with_role(&updates[0], Role::Button);
+        assert_ne!(renamed, button);
+
+        test_window.simulate_accessibility_active(false);
+        window
+            .update(cx, |view, _, cx| {
+                view.label = "ignored".into();
+                cx.notify();
+            })
+            .unwrap();
+        next_frame(window, cx);
+        assert!(test_window.take_accessibility_updates().is_empty());
+    }
+
+    #[gpui::test]
+    fn test_accessibility_focus(cx: &mut TestAppContext) {
+        let window = open_window(cx);
+        let test_window = cx.test_window(window.into());
+        test_window.simulate_accessibility_active(true);
+        next_frame(window, cx);
+        let button = node_with_role(&test_window.take_accessibility_updates()[0], Role::Button);
+
+        // Focusing the element focuses its node.
+        window
+            .update(cx, |view, window, _| window.focus(&view.focus_handle))
+            .unwrap();
+        next_frame(window, cx);
+        let updates = test_window.take_accessibility_updates();
+        assert_eq!(updates.len(), 1);
+        assert!(updates[0].nodes.is_empty());
+        assert_eq!(updates[0].focus, button);
+
+        // Assistive technologies can focus the element through its node.
+        window.update(cx, |_, window, _| window.blur()).unwrap();
+        next_frame(window, cx);
+        assert_eq!(
+            test_window.take_accessibility_updates()[0].focus,
+            ROOT_NODE_ID
+        );
+        test_window.simulate_accessibility_action(ActionRequest {
+            action: Action::Focus,
+            target: button,
+            data: None,
+        });
+        next_frame(window, cx);
+        window
+            .update(cx, |view, window, _| {
+                assert!(view.focus_handle.is_focused(window))
+            })
+            .unwrap();
+    }
+}
@@ -821,6 +821,17 @@ impl App {
         self.platform.should_auto_hide_scrollbars()
     }
 
+    /// Returns whether the user has asked the platform to reduce motion, like animations.
+    pub fn prefers_reduced_motion(&self) -> bool {
+        self.platform.prefers_reduced_motion()
+    }
+
+    /// Returns whether the user has asked the platform to increase the contrast of what is
+    /// displayed.
+    pub fn prefers_increased_contrast(&self) -> bool {
+        self.platform.prefers_increased_contrast()
+    }
+
     /// Restarts the application.
     pub fn restart(&self, binary_path: Option<PathBuf>) {
         self.platform.restart(binary_path)
@@ -1334,6 +1345,11 @@ impl App {
         self.pending_effects.push_back(Effect::RefreshWindows);
     }
 
+    /// Get all key bindings in the app.
+    pub fn key_bindings(&self) -> Rc<RefCell<Keymap>> {
+        self.keymap.clone()
+    }
+
     /// Register a global listener for actions invoked via the keyboard.
     pub fn on_action<A: Action>(&mut self, listener: impl Fn(&A, &mut Self) + 'static) {
         self.global_action_listeners
@@ -21,8 +21,8 @@ use crate::{
     HitboxId, InspectorElementId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent,
     LayoutId, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
     Overflow, ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
-    StyleRefinement, Styled, Task, TooltipId, Visibility, Window, WindowControlArea, point, px,
-    size,
+    StyleRefinement, Styled, Task, TooltipId, Visibility, Window, WindowControlArea,
+    accessibility_rect, point, px, size,
 };
 use collections::HashMap;
 use refineable::Refineable;
@@ -1184,6 +1184,7 @@ pub fn div() -> Div {
         children: SmallVec::default(),
         prepaint_listener: None,
         image_cache: None,
+        accessibility: None,
     }
 }
 
@@ -1193,6 +1194,7 @@ pub struct Div {
     children: SmallVec<[AnyElement; 2]>,
     prepaint_listener: Option<Box<dyn Fn(Vec<Bounds<Pixels>>, &mut Window, &mut App) + 'static>>,
     image_cache: Option<Box<dyn ImageCacheProvider>>,
+    accessibility: Option<(accesskit::Role, SharedString)>,
 }
 
 impl Div {
@@ -1211,6 +1213,14 @@ impl Div {
         self.image_cache = Some(Box::new(cache));
         self
     }
+
+    /// Describe this div to assistive technologies, like screen readers, as an element with
+    /// the given role and label. The described elements inside it become its children, and
+    /// it is focused along with its tracked focus handle.
+    pub fn accessibility(mut self, role: accesskit::Role, label: impl Into<SharedString>) -> Self {
+        self.accessibility = Some((role, label.into()));
+        self
+    }
 }
 
 /// A frame state for a `Div` element, which contains layout IDs for its children.
@@ -1381,6 +1391,40 @@ impl Element for Div {
         hitbox: &mut Option<Hitbox>,
         window: &mut Window,
         cx: &mut App,
+    ) {
+        let Some((role, label)) = self.accessibility.clone() else {
+            self.paint_content(global_id, inspector_id, bounds, hitbox, window, cx);
+            return;
+        };
+
+        let id = window.accessibility_node_id((global_id, &label));
+        let focus_handle = self.interactivity.tracked_focus_handle.clone();
+        window.with_accessibility_node(
+            id,
+            || {
+                let mut node = accesskit::Node::new(role);
+                node.set_label(label.to_string());
+                node.set_bounds(accessibility_rect(bounds));
+                if focus_handle.is_some() {
+                    node.add_action(accesskit::Action::Focus);
+                }
+                node
+            },
+            focus_handle.as_ref(),
+            |window| self.paint_content(global_id, inspector_id, bounds, hitbox, window, cx),
+        );
+    }
+}
+
+impl Div {
+    fn paint_content(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        hitbox: &mut Option<Hitbox>,
+        window: &mut Window,
+        cx: &mut App,
     ) {
         let image_cache = self
             .image_cache
@@ -2902,6 +2946,14 @@ pub struct Stateful<E> {
     pub(crate) element: E,
 }
 
+impl Stateful<Div> {
+    /// Describe this div to assistive technologies, see [`Div::accessibility`].
+    pub fn accessibility(mut self, role: accesskit::Role, label: impl Into<SharedString>) -> Self {
+        self.element = self.element.accessibility(role, label);
+        self
+    }
+}
+
 impl<E> Styled for Stateful<E>
 where
     E: Styled,
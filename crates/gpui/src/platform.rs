@@ -31,12 +31,12 @@ mod windows;
 pub(crate) mod scap_screen_capture;
 
 use crate::{
-    Action, AnyWindowHandle, App, AsyncWindowContext, BackgroundExecutor, Bounds,
-    DEFAULT_WINDOW_SIZE, DevicePixels, DispatchEventResult, Font, FontId, FontMetrics, FontRun,
-    ForegroundExecutor, GlyphId, GpuSpecs, ImageSource, Keymap, LineLayout, Pixels, PlatformInput,
-    Point, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams, ScaledPixels, Scene,
-    ShapedGlyph, ShapedRun, SharedString, Size, SvgRenderer, SvgSize, Task, TaskLabel, Window,
-    WindowControlArea, hash, point, px, size,
+    AccessibilitySource, Action, AnyWindowHandle, App, AsyncWindowContext, BackgroundExecutor,
+    Bounds, DEFAULT_WINDOW_SIZE, DevicePixels, DispatchEventResult, Font, FontId, FontMetrics,
+    FontRun, ForegroundExecutor, GlyphId, GpuSpecs, ImageSource, Keymap, LineLayout, Pixels,
+    PlatformInput, Point, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams,
+    ScaledPixels, Scene, ShapedGlyph, ShapedRun, SharedString, Size, SvgRenderer, SvgSize, Task,
+    TaskLabel, Window, WindowControlArea, hash, point, px, size,
 };
 use anyhow::Result;
 use async_task::Runnable;
@@ -224,6 +224,12 @@ pub(crate) trait Platform: 'static {
 
     fn set_cursor_style(&self, style: CursorStyle);
     fn should_auto_hide_scrollbars(&self) -> bool;
+    fn prefers_reduced_motion(&self) -> bool {
+        false
+    }
+    fn prefers_increased_contrast(&self) -> bool {
+        false
+    }
 
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     fn write_to_primary(&self, item: ClipboardItem);
@@ -474,6 +480,13 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
 
     fn update_ime_position(&self, _bounds: Bounds<ScaledPixels>);
 
+    /// Exposes the window to assistive technologies, like screen readers, which get its
+    /// accessibility tree through `source`.
+    fn enable_accessibility(&self, _source: Arc<AccessibilitySource>) {}
+
+    /// Sends changes to the window's accessibility tree to assistive technologies.
+    fn update_accessibility_tree(&self, _update: accesskit::TreeUpdate, _is_focused: bool) {}
+
     #[cfg(any(test, feature = "test-support"))]
     fn as_test(&mut self) -> Option<&mut TestWindow> {
         None
@@ -606,6 +619,7 @@ impl PlatformTextSystem for NoopTextSystem {
                     position: point(position, px(0.)),
                     index: ix,
                     is_emoji: glyph.0 == 2,
+                    is_rtl: false,
                 });
                 if glyph.0 == 2 {
                     position += em_width * 2.0;
@@ -633,6 +647,7 @@ impl PlatformTextSystem for NoopTextSystem {
             descent: font_size * (metrics.descent / metrics.units_per_em as f32),
             runs,
             len: text.len(),
+            contains_rtl: false,
         }
     }
 }
@@ -813,7 +828,10 @@ impl PlatformInputHandler {
     }
 
     #[cfg_attr(
-        any(target_os = "linux", target_os = "freebsd", target_os = "windows"),
+        all(
+            any(target_os = "linux", target_os = "freebsd"),
+            not(feature = "wayland")
+        ),
         allow(dead_code)
     )]
     fn text_for_range(
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use accesskit::{Rect, TreeUpdate};
+
+use crate::{AccessibilityHandler, AccessibilitySource, Bounds, Pixels};
+
+/// Exposes a window's accessibility tree to assistive technologies over AT-SPI.
+pub(crate) struct LinuxAccessibility {
+    adapter: accesskit_unix::Adapter,
+    is_focused: bool,
+    bounds: Rect,
+}
+
+impl LinuxAccessibility {
+    pub(crate) fn new(source: Arc<AccessibilitySource>) -> Self {
+        Self {
+            adapter: accesskit_unix::Adapter::new(
+                AccessibilityHandler(source.clone()),
+                AccessibilityHandler(source.clone()),
+                AccessibilityHandler(source),
+            ),
+            is_focused: false,
+            bounds: Rect::ZERO,
+        }
+    }
+
+    /// Updates the tree, along with the focus and the bounds of the window it is in.
+    pub(crate) fn update(
+        &mut self,
+        update: TreeUpdate,
+        is_focused: bool,
+        bounds: Bounds<Pixels>,
+        scale_factor: f32,
+    ) {
+        if self.is_focused != is_focused {
+            self.is_focused = is_focused;
+            self.adapter.update_window_focus_state(is_focused);
+        }
+        let bounds = Rect {
+            x0: (bounds.left().0 * scale_factor) as f64,
+            y0: (bounds.top().0 * scale_factor) as f64,
+            x1: (bounds.right().0 * scale_factor) as f64,
+            y1: (bounds.bottom().0 * scale_factor) as f64,
+        };
+        if self.bounds != bounds {
+            self.bounds = bounds;
+            // Without its decorations' sizes, the window's outer bounds are its inner ones.
+            self.adapter.set_root_window_bounds(bounds, bounds);
+        }
+        self.adapter.update_if_active(|| update);
+    }
+}
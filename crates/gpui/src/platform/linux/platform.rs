@@ -90,6 +90,8 @@ pub(crate) struct LinuxCommon {
     pub(crate) text_system: Arc<dyn PlatformTextSystem>,
     pub(crate) appearance: WindowAppearance,
     pub(crate) auto_hide_scrollbars: bool,
+    pub(crate) reduced_motion: bool,
+    pub(crate) increased_contrast: bool,
     pub(crate) callbacks: PlatformHandlers,
     pub(crate) signal: LoopSignal,
     pub(crate) menus: Vec<OwnedMenu>,
@@ -116,6 +118,8 @@ impl LinuxCommon {
             text_system,
             appearance: WindowAppearance::Light,
             auto_hide_scrollbars: false,
+            reduced_motion: false,
+            increased_contrast: false,
             callbacks,
             signal,
             menus: Vec::new(),
@@ -458,6 +462,14 @@ impl<P: LinuxClient + 'static> Platform for P {
         self.with_common(|common| common.auto_hide_scrollbars)
     }
 
+    fn prefers_reduced_motion(&self) -> bool {
+        self.with_common(|common| common.reduced_motion)
+    }
+
+    fn prefers_increased_contrast(&self) -> bool {
+        self.with_common(|common| common.increased_contrast)
+    }
+
     fn write_credentials(&self, url: &str, username: &str, password: &[u8]) -> Task<Result<()>> {
         let url = url.to_string();
         let username = username.to_string();
@@ -433,6 +433,7 @@ impl CosmicTextSystemState {
                 position: point(glyph.x.into(), glyph.y.into()),
                 index: glyph.start,
                 is_emoji,
+                is_rtl: glyph.level.is_rtl(),
             };
 
             if let Some(last_run) = runs
@@ -453,6 +454,7 @@ impl CosmicTextSystemState {
             width: layout.w.into(),
             ascent: layout.max_ascent.into(),
             descent: layout.max_descent.into(),
+            contains_rtl: runs.iter().any(ShapedRun::has_rtl),
             runs,
             len: text.len(),
         }
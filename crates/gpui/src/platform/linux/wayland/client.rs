@@ -302,6 +302,7 @@ impl WaylandClientStatePtr {
                     area.size.height.0 as i32,
                 );
             }
+            set_surrounding_text(&text_input, window.get_ime_surrounding_text());
             state = client.borrow_mut();
         }
         text_input.commit();
@@ -320,18 +321,25 @@ impl WaylandClientStatePtr {
 
     pub fn update_ime_position(&self, bounds: Bounds<ScaledPixels>) {
         let client = self.get_client();
-        let mut state = client.borrow_mut();
+        let state = client.borrow();
         if state.composing || state.text_input.is_none() || state.pre_edit_text.is_some() {
             return;
         }
+        let window = state.keyboard_focused_window.clone();
+        drop(state);
+        let surrounding_text = window.and_then(|window| window.get_ime_surrounding_text());
 
-        let text_input = state.text_input.as_ref().unwrap();
+        let state = client.borrow();
+        let Some(text_input) = state.text_input.as_ref() else {
+            return;
+        };
         text_input.set_cursor_rectangle(
             bounds.origin.x.0 as i32,
             bounds.origin.y.0 as i32,
             bounds.size.width.0 as i32,
             bounds.size.height.0 as i32,
         );
+        set_surrounding_text(text_input, surrounding_text);
         text_input.commit();
     }
 
@@ -511,6 +519,16 @@ impl WaylandClient {
                             client.cursor.set_size(size);
                         }
                     }
+                    XDPEvent::ReducedMotion(reduced_motion) => {
+                        if let Some(client) = client.0.upgrade() {
+                            client.borrow_mut().common.reduced_motion = reduced_motion;
+                        }
+                    }
+                    XDPEvent::IncreasedContrast(increased_contrast) => {
+                        if let Some(client) = client.0.upgrade() {
+                            client.borrow_mut().common.increased_contrast = increased_contrast;
+                        }
+                    }
                 }
             })
             .unwrap();
@@ -1454,6 +1472,17 @@ impl Dispatch<zwp_text_input_v3::ZwpTextInputV3, ()> for WaylandClientStatePtr {
     }
 }
 
+/// Offers the selected text to the input method with the selection spanning all of it, so
+/// that the input method can reconvert it by replacing the selection with a new composition.
+fn set_surrounding_text(
+    text_input: &zwp_text_input_v3::ZwpTextInputV3,
+    selected_text: Option<String>,
+) {
+    let selected_text = selected_text.unwrap_or_default();
+    let len = selected_text.len() as i32;
+    text_input.set_surrounding_text(selected_text, len, 0);
+}
+
 fn linux_button_to_gpui(button: u32) -> Option<MouseButton> {
     // These values are coming from <linux/input-event-codes.h>.
     const BTN_LEFT: u32 = 0x110;
@@ -24,15 +24,18 @@ use wayland_protocols_plasma::blur::client::org_kde_kwin_blur;
 use crate::platform::{
     PlatformAtlas, PlatformInputHandler, PlatformWindow,
     blade::{BladeContext, BladeRenderer, BladeSurfaceConfig},
-    linux::wayland::{display::WaylandDisplay, serial::SerialKind},
+    linux::{
+        LinuxAccessibility,
+        wayland::{display::WaylandDisplay, serial::SerialKind},
+    },
 };
 use crate::scene::Scene;
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, Globals, GpuSpecs, Modifiers, Output, Pixels,
-    PlatformDisplay, PlatformInput, Point, PromptButton, PromptLevel, RequestFrameOptions,
-    ResizeEdge, ScaledPixels, Size, Tiling, WaylandClientStatePtr, WindowAppearance,
-    WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowControls, WindowDecorations,
-    WindowParams, px, size,
+    AccessibilitySource, AnyWindowHandle, Bounds, Decorations, Globals, GpuSpecs, Modifiers,
+    Output, Pixels, PlatformDisplay, PlatformInput, Point, PromptButton, PromptLevel,
+    RequestFrameOptions, ResizeEdge, ScaledPixels, Size, Tiling, WaylandClientStatePtr,
+    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowControls,
+    WindowDecorations, WindowParams, px, size,
 };
 
 #[derive(Default)]
@@ -107,6 +110,7 @@ pub struct WaylandWindowState {
     in_progress_window_controls: Option<WindowControls>,
     window_controls: WindowControls,
     inset: Option<Pixels>,
+    accessibility: Option<LinuxAccessibility>,
 }
 
 #[derive(Clone)]
@@ -181,6 +185,7 @@ impl WaylandWindowState {
             in_progress_window_controls: None,
             window_controls: WindowControls::default(),
             inset: None,
+            accessibility: None,
         })
     }
 
@@ -643,6 +648,24 @@ impl WaylandWindowStatePtr {
         bounds
     }
 
+    /// Returns the selected text, which is offered to the input method as the text surrounding
+    /// the cursor so that it can be reconverted.
+    pub fn get_ime_surrounding_text(&self) -> Option<String> {
+        let mut state = self.state.borrow_mut();
+        let mut text = None;
+        if let Some(mut input_handler) = state.input_handler.take() {
+            drop(state);
+            if let Some(selection) = input_handler.selected_text_range(false) {
+                if !selection.range.is_empty() {
+                    text = input_handler.text_for_range(selection.range, &mut None);
+                }
+            }
+            self.state.borrow_mut().input_handler = Some(input_handler);
+        }
+        // The protocol limits surrounding text to 4000 bytes.
+        text.filter(|text| text.len() < 4000)
+    }
+
     pub fn set_size_and_scale(&self, size: Option<Size<Pixels>>, scale: Option<f32>) {
         let (size, scale) = {
             let mut state = self.state.borrow_mut();
@@ -1065,6 +1088,20 @@ impl PlatformWindow for WaylandWindow {
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         self.borrow().renderer.gpu_specs().into()
     }
+
+    fn enable_accessibility(&self, source: Arc<AccessibilitySource>) {
+        self.borrow_mut().accessibility = Some(LinuxAccessibility::new(source));
+    }
+
+    fn update_accessibility_tree(&self, update: accesskit::TreeUpdate, is_focused: bool) {
+        let mut state = self.borrow_mut();
+        // Wayland doesn't tell windows where they are on the screen.
+        let bounds = Bounds::new(Point::default(), state.bounds.size);
+        let scale_factor = state.scale;
+        if let Some(accessibility) = state.accessibility.as_mut() {
+            accessibility.update(update, is_focused, bounds, scale_factor);
+        }
+    }
 }
 
 fn update_window(mut state: RefMut<WaylandWindowState>) {
@@ -432,6 +432,12 @@ impl X11Client {
                     XDPEvent::CursorTheme(_) | XDPEvent::CursorSize(_) => {
                         // noop, X11 manages this for us.
                     }
+                    XDPEvent::ReducedMotion(reduced_motion) => {
+                        client.with_common(|common| common.reduced_motion = reduced_motion);
+                    }
+                    XDPEvent::IncreasedContrast(increased_contrast) => {
+                        client.with_common(|common| common.increased_contrast = increased_contrast);
+                    }
                 }
             })
             .unwrap();
@@ -1,12 +1,14 @@
 use anyhow::{Context as _, anyhow};
 
 use crate::platform::blade::{BladeContext, BladeRenderer, BladeSurfaceConfig};
+use crate::platform::linux::LinuxAccessibility;
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor, GpuSpecs, Modifiers,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptButton, PromptLevel, RequestFrameOptions, ResizeEdge, ScaledPixels, Scene, Size,
-    Tiling, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea,
-    WindowDecorations, WindowKind, WindowParams, X11ClientStatePtr, px, size,
+    AccessibilitySource, AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor,
+    GpuSpecs, Modifiers, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PromptButton, PromptLevel, RequestFrameOptions,
+    ResizeEdge, ScaledPixels, Scene, Size, Tiling, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowControlArea, WindowDecorations, WindowKind, WindowParams,
+    X11ClientStatePtr, px, size,
 };
 
 use blade_graphics as gpu;
@@ -269,6 +271,7 @@ pub struct X11WindowState {
     edge_constraints: Option<EdgeConstraints>,
     pub handle: AnyWindowHandle,
     last_insets: [u32; 4],
+    accessibility: Option<LinuxAccessibility>,
 }
 
 impl X11WindowState {
@@ -629,6 +632,7 @@ impl X11WindowState {
                 client_side_decorations_supported,
                 decorations: WindowDecorations::Server,
                 last_insets: [0, 0, 0, 0],
+                accessibility: None,
                 edge_constraints: None,
                 counter_id: sync_request_counter,
                 last_sync_counter: None,
@@ -1606,4 +1610,17 @@ impl PlatformWindow for X11Window {
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         self.0.state.borrow().renderer.gpu_specs().into()
     }
+
+    fn enable_accessibility(&self, source: Arc<AccessibilitySource>) {
+        self.0.state.borrow_mut().accessibility = Some(LinuxAccessibility::new(source));
+    }
+
+    fn update_accessibility_tree(&self, update: accesskit::TreeUpdate, is_focused: bool) {
+        let mut state = self.0.state.borrow_mut();
+        let bounds = state.bounds;
+        let scale_factor = state.scale_factor;
+        if let Some(accessibility) = state.accessibility.as_mut() {
+            accessibility.update(update, is_focused, bounds, scale_factor);
+        }
+    }
 }
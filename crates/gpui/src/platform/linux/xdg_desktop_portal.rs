@@ -15,6 +15,8 @@ pub enum Event {
     CursorTheme(String),
     #[cfg_attr(feature = "x11", allow(dead_code))]
     CursorSize(u32),
+    ReducedMotion(bool),
+    IncreasedContrast(bool),
 }
 
 pub struct XDPEventSource {
@@ -89,6 +91,61 @@ impl XDPEventSource {
                         .detach();
                 }
 
+                if let Ok(enable_animations) = settings
+                    .read::<bool>("org.gnome.desktop.interface", "enable-animations")
+                    .await
+                {
+                    sender.send(Event::ReducedMotion(!enable_animations))?;
+                }
+
+                if let Ok(mut enable_animations_changed) = settings
+                    .receive_setting_changed_with_args::<bool>(
+                        "org.gnome.desktop.interface",
+                        "enable-animations",
+                    )
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(enable_animations) =
+                                enable_animations_changed.next().await
+                            {
+                                let enable_animations = enable_animations?;
+                                sender.send(Event::ReducedMotion(!enable_animations))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
+                // 1 stands for high contrast, 0 for no preference.
+                if let Ok(contrast) = settings
+                    .read::<u32>("org.freedesktop.appearance", "contrast")
+                    .await
+                {
+                    sender.send(Event::IncreasedContrast(contrast == 1))?;
+                }
+
+                if let Ok(mut contrast_changed) = settings
+                    .receive_setting_changed_with_args::<u32>(
+                        "org.freedesktop.appearance",
+                        "contrast",
+                    )
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(contrast) = contrast_changed.next().await {
+                                let contrast = contrast?;
+                                sender.send(Event::IncreasedContrast(contrast == 1))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
                 let mut appearance_changed = settings.receive_color_scheme_changed().await?;
                 while let Some(scheme) = appearance_changed.next().await {
                     sender.send(Event::WindowAppearance(WindowAppearance::from_native(
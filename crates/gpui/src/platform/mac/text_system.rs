@@ -25,6 +25,7 @@ use core_text::{
         kCTFontSlantTrait, kCTFontSymbolicTrait, kCTFontWeightTrait, kCTFontWidthTrait,
     },
     line::CTLine,
+    run::CTRunRef,
     string_attributes::kCTFontAttributeName,
 };
 use font_kit::{
@@ -47,6 +48,14 @@ use std::{borrow::Cow, char, cmp, convert::TryFrom, sync::Arc};
 
 use super::open_type::apply_features_and_fallbacks;
 
+#[link(name = "CoreText", kind = "framework")]
+unsafe extern "C" {
+    fn CTRunGetStatus(run: CTRunRef) -> u32;
+}
+
+#[allow(non_upper_case_globals)]
+const kCTRunStatusRightToLeft: u32 = 1 << 0;
+
 #[allow(non_upper_case_globals)]
 const kCGImageAlphaOnly: u32 = 7;
 
@@ -471,6 +480,8 @@ impl MacTextSystemState {
         let mut runs = Vec::with_capacity(glyph_runs.len() as usize);
         let mut ix_converter = StringIndexConverter::new(text);
         for run in glyph_runs.into_iter() {
+            let is_rtl =
+                unsafe { CTRunGetStatus(run.as_concrete_TypeRef()) } & kCTRunStatusRightToLeft != 0;
             let attributes = run.attributes().unwrap();
             let font = unsafe {
                 attributes
@@ -498,6 +509,7 @@ impl MacTextSystemState {
                     position: point(position.x as f32, position.y as f32).map(px),
                     index: ix_converter.utf8_ix,
                     is_emoji: self.is_emoji(font_id),
+                    is_rtl,
                 });
             }
 
@@ -505,6 +517,7 @@ impl MacTextSystemState {
         }
         let typographic_bounds = line.get_typographic_bounds();
         LineLayout {
+            contains_rtl: runs.iter().any(ShapedRun::has_rtl),
             runs,
             font_size,
             width: typographic_bounds.width.into(),
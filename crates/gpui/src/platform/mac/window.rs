@@ -1,11 +1,12 @@
 use super::{BoolExt, MacDisplay, NSRange, NSStringExt, ns_string, renderer};
 use crate::{
-    AnyWindowHandle, Bounds, DisplayLink, ExternalPaths, FileDropEvent, ForegroundExecutor,
-    KeyDownEvent, Keystroke, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent,
-    MouseMoveEvent, MouseUpEvent, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
-    PlatformWindow, Point, PromptButton, PromptLevel, RequestFrameOptions, ScaledPixels, Size,
-    Timer, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea,
-    WindowKind, WindowParams, platform::PlatformInputHandler, point, px, size,
+    AccessibilityHandler, AccessibilitySource, AnyWindowHandle, Bounds, DisplayLink, ExternalPaths,
+    FileDropEvent, ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers, ModifiersChangedEvent,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, PlatformAtlas,
+    PlatformDisplay, PlatformInput, PlatformWindow, Point, PromptButton, PromptLevel,
+    RequestFrameOptions, ScaledPixels, Size, Timer, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowControlArea, WindowKind, WindowParams, platform::PlatformInputHandler,
+    point, px, size,
 };
 use block::ConcreteBlock;
 use cocoa::{
@@ -357,6 +358,7 @@ struct MacWindowState {
     // Whether the next left-mouse click is also the focusing click.
     first_mouse: bool,
     fullscreen_restore_bounds: Bounds<Pixels>,
+    accessibility: Option<accesskit_macos::SubclassingAdapter>,
 }
 
 impl MacWindowState {
@@ -640,6 +642,7 @@ impl MacWindow {
                 external_files_dragged: false,
                 first_mouse: false,
                 fullscreen_restore_bounds: Bounds::default(),
+                accessibility: None,
             })));
 
             (*native_window).set_ivar(
@@ -1166,6 +1169,32 @@ impl PlatformWindow for MacWindow {
         None
     }
 
+    fn enable_accessibility(&self, source: Arc<AccessibilitySource>) {
+        let mut this = self.0.lock();
+        let view = this.native_view.as_ptr() as *mut c_void;
+        this.accessibility = Some(unsafe {
+            accesskit_macos::SubclassingAdapter::new(
+                view,
+                AccessibilityHandler(source.clone()),
+                AccessibilityHandler(source),
+            )
+        });
+    }
+
+    fn update_accessibility_tree(&self, update: accesskit::TreeUpdate, is_focused: bool) {
+        let mut this = self.0.lock();
+        let Some(adapter) = this.accessibility.as_mut() else {
+            return;
+        };
+        let focus_events = adapter.update_view_focus_state(is_focused);
+        let tree_events = adapter.update_if_active(|| update);
+        // Raising the events calls back into the view, which locks the window's state.
+        drop(this);
+        for events in focus_events.into_iter().chain(tree_events) {
+            events.raise();
+        }
+    }
+
     fn update_ime_position(&self, _bounds: Bounds<ScaledPixels>) {
         let executor = self.0.lock().executor.clone();
         executor
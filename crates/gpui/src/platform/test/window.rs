@@ -1,9 +1,15 @@
+#[cfg(test)]
+use crate::AccessibilityHandler;
 use crate::{
-    AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds, DispatchEventResult, GpuSpecs,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptButton, RequestFrameOptions, ScaledPixels, Size, TestPlatform, TileId,
-    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowParams,
+    AccessibilitySource, AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds,
+    DispatchEventResult, GpuSpecs, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PromptButton, RequestFrameOptions, ScaledPixels,
+    Size, TestPlatform, TileId, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControlArea, WindowParams,
 };
+use accesskit::TreeUpdate;
+#[cfg(test)]
+use accesskit::{ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler};
 use collections::HashMap;
 use parking_lot::Mutex;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
@@ -29,6 +35,9 @@ pub(crate) struct TestWindowState {
     moved_callback: Option<Box<dyn FnMut()>>,
     input_handler: Option<PlatformInputHandler>,
     is_fullscreen: bool,
+    #[cfg_attr(not(test), allow(dead_code))]
+    accessibility_source: Option<Arc<AccessibilitySource>>,
+    accessibility_updates: Vec<TreeUpdate>,
 }
 
 #[derive(Clone)]
@@ -74,6 +83,8 @@ impl TestWindow {
             moved_callback: None,
             input_handler: None,
             is_fullscreen: false,
+            accessibility_source: None,
+            accessibility_updates: Vec::new(),
         })))
     }
 
@@ -99,6 +110,31 @@ impl TestWindow {
         self.0.lock().active_status_change_callback = Some(callback);
     }
 
+    /// Simulates an assistive technology starting or stopping to read the window.
+    #[cfg(test)]
+    pub(crate) fn simulate_accessibility_active(&self, active: bool) {
+        let source = self.0.lock().accessibility_source.clone().unwrap();
+        let mut handler = AccessibilityHandler(source);
+        if active {
+            handler.request_initial_tree();
+        } else {
+            handler.deactivate_accessibility();
+        }
+    }
+
+    /// Simulates an assistive technology asking for an action on a node of the window.
+    #[cfg(test)]
+    pub(crate) fn simulate_accessibility_action(&self, request: ActionRequest) {
+        let source = self.0.lock().accessibility_source.clone().unwrap();
+        AccessibilityHandler(source).do_action(request);
+    }
+
+    /// Returns the accessibility tree updates sent since this was last called.
+    #[cfg(test)]
+    pub(crate) fn take_accessibility_updates(&self) -> Vec<TreeUpdate> {
+        std::mem::take(&mut self.0.lock().accessibility_updates)
+    }
+
     pub fn simulate_input(&mut self, event: PlatformInput) -> bool {
         let mut lock = self.0.lock();
         let Some(mut callback) = lock.input_callback.take() else {
@@ -287,6 +323,14 @@ impl PlatformWindow for TestWindow {
 
     fn update_ime_position(&self, _bounds: Bounds<ScaledPixels>) {}
 
+    fn enable_accessibility(&self, source: Arc<AccessibilitySource>) {
+        self.0.lock().accessibility_source = Some(source);
+    }
+
+    fn update_accessibility_tree(&self, update: TreeUpdate, _is_focused: bool) {
+        self.0.lock().accessibility_updates.push(update);
+    }
+
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         None
     }
@@ -598,7 +598,6 @@ impl DirectWriteState {
                 text_system: self,
                 index_converter: StringIndexConverter::new(text),
                 runs: &mut runs,
-                utf16_index: 0,
                 width: 0.0,
             };
             text_layout.Draw(
@@ -614,6 +613,7 @@ impl DirectWriteState {
                 width,
                 ascent,
                 descent,
+                contains_rtl: runs.iter().any(ShapedRun::has_rtl),
                 runs,
                 len: text.len(),
             })
@@ -1003,7 +1003,6 @@ struct RendererContext<'t, 'a, 'b> {
     text_system: &'t mut DirectWriteState,
     index_converter: StringIndexConverter<'a>,
     runs: &'b mut Vec<ShapedRun>,
-    utf16_index: usize,
     width: f32,
 }
 
@@ -1088,23 +1087,46 @@ impl IDWriteTextRenderer_Impl for TextRenderer_Impl {
             } else {
                 context.text_system.select_font(&font_struct)
             };
+            // Runs are drawn from left to right, so the runs of text that reads right to left
+            // can take the index converter backwards.
+            let text_position = desc.textPosition as usize;
+            if context.index_converter.utf16_ix > text_position {
+                context.index_converter = StringIndexConverter::new(context.index_converter.text);
+            }
+            let is_rtl = glyphrun.bidiLevel % 2 == 1;
+            let run_width = (0..glyph_count)
+                .map(|index| *glyphrun.glyphAdvances.add(index))
+                .sum::<f32>();
             let mut glyphs = Vec::with_capacity(glyph_count);
+            let mut advance = 0.0;
             for index in 0..glyph_count {
                 let id = GlyphId(*glyphrun.glyphIndices.add(index) as u32);
                 context
                     .index_converter
-                    .advance_to_utf16_ix(context.utf16_index);
+                    .advance_to_utf16_ix(text_position + index * utf16_length_per_glyph);
                 let is_emoji = color_font
                     && is_color_glyph(font_face, id, &context.text_system.components.factory);
+                let glyph_advance = *glyphrun.glyphAdvances.add(index);
+                // The glyphs of a right-to-left run are listed in reading order, starting from
+                // the run's right end.
+                let x = if is_rtl {
+                    context.width + run_width - advance - glyph_advance
+                } else {
+                    context.width + advance
+                };
                 glyphs.push(ShapedGlyph {
                     id,
-                    position: point(px(context.width), px(0.0)),
+                    position: point(px(x), px(0.0)),
                     index: context.index_converter.utf8_ix,
                     is_emoji,
+                    is_rtl,
                 });
-                context.utf16_index += utf16_length_per_glyph;
-                context.width += *glyphrun.glyphAdvances.add(index);
+                advance += glyph_advance;
+            }
+            if is_rtl {
+                glyphs.reverse();
             }
+            context.width += run_width;
             context.runs.push(ShapedRun { font_id, glyphs });
         }
         Ok(())
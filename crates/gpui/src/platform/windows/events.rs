@@ -92,6 +92,7 @@ pub(crate) fn handle_msg(
         WM_DEADCHAR => handle_dead_char_msg(wparam, state_ptr),
         WM_IME_STARTCOMPOSITION => handle_ime_position(handle, state_ptr),
         WM_IME_COMPOSITION => handle_ime_composition(handle, lparam, state_ptr),
+        WM_IME_REQUEST => handle_ime_request(wparam, lparam, state_ptr),
         WM_SETCURSOR => handle_set_cursor(lparam, state_ptr),
         WM_SETTINGCHANGE => handle_system_settings_changed(handle, lparam, state_ptr),
         WM_INPUTLANGCHANGE => handle_input_language_changed(lparam, state_ptr),
@@ -723,6 +724,67 @@ fn handle_ime_composition_inner(
     }
 }
 
+/// SEE: https://learn.microsoft.com/en-us/windows/win32/intl/wm-ime-request
+fn handle_ime_request(
+    wparam: WPARAM,
+    lparam: LPARAM,
+    state_ptr: Rc<WindowsWindowStatePtr>,
+) -> Option<isize> {
+    match wparam.0 as u32 {
+        IMR_RECONVERTSTRING => handle_reconvert_string_request(lparam, &state_ptr),
+        // The selection is reconverted as a whole, so there's nothing to adjust.
+        IMR_CONFIRMRECONVERTSTRING => Some(1),
+        _ => None,
+    }
+}
+
+/// Offers the selected text to the IME for reconversion, which then replaces it with a new
+/// composition, like it would when typing.
+///
+/// SEE: https://learn.microsoft.com/en-us/windows/win32/intl/imr-reconvertstring
+fn handle_reconvert_string_request(
+    lparam: LPARAM,
+    state_ptr: &Rc<WindowsWindowStatePtr>,
+) -> Option<isize> {
+    let text = with_input_handler(state_ptr, |input_handler| {
+        let selection = input_handler.selected_text_range(false)?;
+        if selection.range.is_empty() {
+            return None;
+        }
+        let text = input_handler.text_for_range(selection.range, &mut None)?;
+        Some(text.encode_utf16().collect::<Vec<u16>>())
+    })
+    .flatten();
+    let Some(text) = text else {
+        return Some(0);
+    };
+
+    let header_size = std::mem::size_of::<RECONVERTSTRING>();
+    let size = header_size + text.len() * std::mem::size_of::<u16>();
+    // The IME first asks for the size of the buffer to allocate.
+    if lparam.0 == 0 {
+        return Some(size as isize);
+    }
+
+    unsafe {
+        let reconvert = lparam.0 as *mut RECONVERTSTRING;
+        if ((*reconvert).dwSize as usize) < size {
+            return Some(0);
+        }
+        let text_len = text.len() as u32;
+        (*reconvert).dwVersion = 0;
+        (*reconvert).dwStrLen = text_len;
+        (*reconvert).dwStrOffset = header_size as u32;
+        (*reconvert).dwCompStrLen = text_len;
+        (*reconvert).dwCompStrOffset = 0;
+        (*reconvert).dwTargetStrLen = text_len;
+        (*reconvert).dwTargetStrOffset = 0;
+        let string = (reconvert as *mut u8).add(header_size) as *mut u16;
+        std::ptr::copy_nonoverlapping(text.as_ptr(), string, text.len());
+    }
+    Some(size as isize)
+}
+
 /// SEE: https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-nccalcsize
 fn handle_calc_client_size(
     handle: HWND,
@@ -1,5 +1,6 @@
 use std::{
     cell::RefCell,
+    ffi::c_void,
     mem::ManuallyDrop,
     path::{Path, PathBuf},
     rc::Rc,
@@ -23,7 +24,7 @@ use windows::{
         },
         Security::Credentials::*,
         System::{Com::*, LibraryLoader::*, Ole::*, SystemInformation::*, Threading::*},
-        UI::{Input::KeyboardAndMouse::*, Shell::*, WindowsAndMessaging::*},
+        UI::{Accessibility::*, Input::KeyboardAndMouse::*, Shell::*, WindowsAndMessaging::*},
     },
     core::*,
 };
@@ -593,6 +594,37 @@ impl Platform for WindowsPlatform {
         should_auto_hide_scrollbars().log_err().unwrap_or(false)
     }
 
+    fn prefers_reduced_motion(&self) -> bool {
+        let mut animations_enabled = BOOL(1);
+        unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                Some(&mut animations_enabled as *mut BOOL as *mut c_void),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS::default(),
+            )
+        }
+        .log_err();
+        !animations_enabled.as_bool()
+    }
+
+    fn prefers_increased_contrast(&self) -> bool {
+        let mut high_contrast = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            ..Default::default()
+        };
+        unsafe {
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                Some(&mut high_contrast as *mut HIGHCONTRASTW as *mut c_void),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS::default(),
+            )
+        }
+        .log_err();
+        high_contrast.dwFlags.contains(HCF_HIGHCONTRASTON)
+    }
+
     fn write_to_clipboard(&self, item: ClipboardItem) {
         write_to_clipboard(item);
     }
@@ -56,6 +56,7 @@ pub struct WindowsWindowState {
     pub display: WindowsDisplay,
     fullscreen: Option<StyleAndBounds>,
     initial_placement: Option<WindowOpenStatus>,
+    accessibility: Option<accesskit_windows::SubclassingAdapter>,
     hwnd: HWND,
 }
 
@@ -109,6 +110,7 @@ impl WindowsWindowState {
         let nc_button_pressed = None;
         let fullscreen = None;
         let initial_placement = None;
+        let accessibility = None;
 
         Ok(Self {
             origin,
@@ -131,6 +133,7 @@ impl WindowsWindowState {
             display,
             fullscreen,
             initial_placement,
+            accessibility,
             hwnd,
         })
     }
@@ -787,6 +790,29 @@ impl PlatformWindow for WindowsWindow {
         Some(self.0.state.borrow().renderer.gpu_specs())
     }
 
+    fn enable_accessibility(&self, source: Arc<AccessibilitySource>) {
+        let adapter = accesskit_windows::SubclassingAdapter::new(
+            accesskit_windows::HWND(self.0.hwnd.0),
+            AccessibilityHandler(source.clone()),
+            AccessibilityHandler(source),
+        );
+        self.0.state.borrow_mut().accessibility = Some(adapter);
+    }
+
+    fn update_accessibility_tree(&self, update: accesskit::TreeUpdate, _is_focused: bool) {
+        let events = self
+            .0
+            .state
+            .borrow_mut()
+            .accessibility
+            .as_mut()
+            .and_then(|adapter| adapter.update_if_active(|| update));
+        // Raising the events can send messages to the window, which borrow its state.
+        if let Some(events) = events {
+            events.raise();
+        }
+    }
+
     fn update_ime_position(&self, _bounds: Bounds<ScaledPixels>) {
         // todo(windows)
     }
@@ -12,6 +12,19 @@ impl FontFeatures {
         Self(Arc::new(vec![("calt".into(), 0)]))
     }
 
+    /// Returns these features, with ligatures (`calt` and `liga`) disabled.
+    pub fn without_ligatures(&self) -> Self {
+        let mut features = self
+            .0
+            .iter()
+            .filter(|(feature, _)| feature != "calt" && feature != "liga")
+            .cloned()
+            .collect::<Vec<_>>();
+        features.push(("calt".into(), 0));
+        features.push(("liga".into(), 0));
+        Self(Arc::new(features))
+    }
+
     /// Get the tag name list of the font OpenType features
     /// only enabled or disabled features are returned
     pub fn tag_value_list(&self) -> &[(String, u32)] {
@@ -55,6 +55,7 @@ impl ShapedLine {
             descent: layout.descent,
             runs: layout.runs.clone(),
             len,
+            contains_rtl: layout.contains_rtl,
         });
         self
     }
@@ -206,8 +207,10 @@ fn paint_line(
     window.paint_layer(line_bounds, |window| {
         let padding_top = (line_height - layout.ascent - layout.descent) / 2.;
         let baseline_offset = point(px(0.), padding_top + layout.ascent);
+        let all_decoration_runs = decoration_runs;
         let mut decoration_runs = decoration_runs.iter();
         let mut wraps = wrap_boundaries.iter().peekable();
+        let mut run_start = 0;
         let mut run_end = 0;
         let mut color = black();
         let mut current_underline: Option<(Point<Pixels>, UnderlineStyle)> = None;
@@ -279,6 +282,13 @@ fn paint_line(
 
                 let mut finished_underline: Option<(Point<Pixels>, UnderlineStyle)> = None;
                 let mut finished_strikethrough: Option<(Point<Pixels>, StrikethroughStyle)> = None;
+                // The glyphs of text that reads right to left come in reverse, so look for
+                // their style runs from the start.
+                if glyph.index < run_start {
+                    decoration_runs = all_decoration_runs.iter();
+                    run_start = 0;
+                    run_end = 0;
+                }
                 if glyph.index >= run_end {
                     let mut style_run = decoration_runs.next();
 
@@ -329,9 +339,11 @@ fn paint_line(
                             ));
                         }
 
+                        run_start = run_end;
                         run_end += style_run.len as usize;
                         color = style_run.color;
                     } else {
+                        run_start = run_end;
                         run_end = layout.len;
                         finished_underline = current_underline.take();
                         finished_strikethrough = current_strikethrough.take();
@@ -441,8 +453,10 @@ fn paint_line_background(
         ),
     );
     window.paint_layer(line_bounds, |window| {
+        let all_decoration_runs = decoration_runs;
         let mut decoration_runs = decoration_runs.iter();
         let mut wraps = wrap_boundaries.iter().peekable();
+        let mut run_start = 0;
         let mut run_end = 0;
         let mut current_background: Option<(Point<Pixels>, Hsla)> = None;
         let text_system = cx.text_system().clone();
@@ -496,6 +510,13 @@ fn paint_line_background(
                 prev_glyph_position = glyph.position;
 
                 let mut finished_background: Option<(Point<Pixels>, Hsla)> = None;
+                // The glyphs of text that reads right to left come in reverse, so look for
+                // their style runs from the start.
+                if glyph.index < run_start {
+                    decoration_runs = all_decoration_runs.iter();
+                    run_start = 0;
+                    run_end = 0;
+                }
                 if glyph.index >= run_end {
                     let mut style_run = decoration_runs.next();
 
@@ -520,8 +541,10 @@ fn paint_line_background(
                                 run_background,
                             ));
                         }
+                        run_start = run_end;
                         run_end += style_run.len as usize;
                     } else {
+                        run_start = run_end;
                         run_end = layout.len;
                         finished_background = current_background.take();
                     }
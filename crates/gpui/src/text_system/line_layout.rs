@@ -1,10 +1,11 @@
 use crate::{FontId, GlyphId, Pixels, PlatformTextSystem, Point, SharedString, Size, point, px};
 use collections::FxHashMap;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
-use smallvec::SmallVec;
+use smallvec::{SmallVec, smallvec};
 use std::{
     borrow::Borrow,
     hash::{Hash, Hasher},
+    iter,
     ops::Range,
     sync::Arc,
 };
@@ -26,6 +27,8 @@ pub struct LineLayout {
     pub runs: Vec<ShapedRun>,
     /// The length of the line in utf-8 bytes
     pub len: usize,
+    /// Whether any of this line's text reads right to left
+    pub contains_rtl: bool,
 }
 
 /// A run of text that has been shaped .
@@ -37,6 +40,13 @@ pub struct ShapedRun {
     pub glyphs: Vec<ShapedGlyph>,
 }
 
+impl ShapedRun {
+    /// Whether any of this run's glyphs belong to text that reads right to left.
+    pub fn has_rtl(&self) -> bool {
+        self.glyphs.iter().any(|glyph| glyph.is_rtl)
+    }
+}
+
 /// A single glyph, ready to paint.
 #[derive(Clone, Debug)]
 pub struct ShapedGlyph {
@@ -51,9 +61,17 @@ pub struct ShapedGlyph {
 
     /// Whether this glyph is an emoji
     pub is_emoji: bool,
+
+    /// Whether this glyph belongs to text that reads right to left, like Arabic or Hebrew.
+    pub is_rtl: bool,
 }
 
 impl LineLayout {
+    /// Whether any of this line's text reads right to left.
+    pub fn has_rtl(&self) -> bool {
+        self.contains_rtl
+    }
+
     /// The index for the character at the given x coordinate
     pub fn index_for_x(&self, x: Pixels) -> Option<usize> {
         if x >= self.width {
@@ -73,6 +91,10 @@ impl LineLayout {
     /// closest_index_for_x returns the character boundary closest to the given x coordinate
     /// (e.g. to handle aligning up/down arrow keys)
     pub fn closest_index_for_x(&self, x: Pixels) -> usize {
+        if self.has_rtl() {
+            return self.bidi_closest_index_for_x(x);
+        }
+
         let mut prev_index = 0;
         let mut prev_x = px(0.);
 
@@ -103,6 +125,10 @@ impl LineLayout {
 
     /// The x position of the character at the given index
     pub fn x_for_index(&self, index: usize) -> Pixels {
+        if self.has_rtl() {
+            return self.bidi_x_for_index(index);
+        }
+
         for run in &self.runs {
             for glyph in &run.glyphs {
                 if glyph.index >= index {
@@ -113,6 +139,78 @@ impl LineLayout {
         self.width
     }
 
+    /// The horizontal spans taken up by the text in the given range, from left to right. Text
+    /// that mixes directions can take up several.
+    pub fn x_ranges_for_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        if !self.has_rtl() {
+            return smallvec![self.x_for_index(range.start)..self.x_for_index(range.end)];
+        }
+
+        let mut x_ranges = SmallVec::<[Range<Pixels>; 1]>::new();
+        for (glyph, left, right) in self.glyph_spans() {
+            if !range.contains(&glyph.index) {
+                continue;
+            }
+            match x_ranges.last_mut() {
+                Some(last) if last.end == left => last.end = right,
+                _ => x_ranges.push(left..right),
+            }
+        }
+        x_ranges
+    }
+
+    /// The glyphs from left to right, along with the x coordinates of their left and right
+    /// edges.
+    fn glyph_spans(&self) -> impl Iterator<Item = (&ShapedGlyph, Pixels, Pixels)> {
+        let mut glyphs = self.runs.iter().flat_map(|run| &run.glyphs).peekable();
+        iter::from_fn(move || {
+            let glyph = glyphs.next()?;
+            let right = glyphs
+                .peek()
+                .map_or(self.width, |next_glyph| next_glyph.position.x);
+            Some((glyph, glyph.position.x, right))
+        })
+    }
+
+    /// The cursor's x position before the character at the given index, in a line whose
+    /// glyphs aren't all laid out in reading order. That's at the left edge of characters
+    /// that read left to right, and at the right edge of those that read right to left.
+    fn bidi_x_for_index(&self, index: usize) -> Pixels {
+        // The glyph of the character at the index, or else of the last one before it.
+        let mut containing_span: Option<(&ShapedGlyph, Pixels, Pixels)> = None;
+        for span in self.glyph_spans() {
+            if span.0.index <= index
+                && containing_span.is_none_or(|containing| span.0.index > containing.0.index)
+            {
+                containing_span = Some(span);
+            }
+        }
+
+        let Some((glyph, left, right)) = containing_span else {
+            return self.width;
+        };
+        let before_glyph = glyph.index == index;
+        if before_glyph != glyph.is_rtl {
+            left
+        } else {
+            right
+        }
+    }
+
+    fn bidi_closest_index_for_x(&self, x: Pixels) -> usize {
+        let mut closest_index = self.len;
+        let mut closest_distance = (self.bidi_x_for_index(self.len) - x).abs();
+        for (glyph, left, right) in self.glyph_spans() {
+            let leading_edge = if glyph.is_rtl { right } else { left };
+            let distance = (leading_edge - x).abs();
+            if distance < closest_distance {
+                closest_index = glyph.index;
+                closest_distance = distance;
+            }
+        }
+        closest_index
+    }
+
     /// The corresponding Font at the given index
     pub fn font_id_for_index(&self, index: usize) -> Option<FontId> {
         for run in &self.runs {
@@ -649,3 +747,51 @@ impl AsCacheKeyRef for CacheKeyRef<'_> {
         *self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_direction_line() {
+        // "abגד", where the Hebrew letters are laid out from right to left.
+        let glyph = |index, x, is_rtl| ShapedGlyph {
+            id: GlyphId(0),
+            position: point(px(x), px(0.)),
+            index,
+            is_emoji: false,
+            is_rtl,
+        };
+        let layout = LineLayout {
+            font_size: px(16.),
+            width: px(40.),
+            ascent: px(12.),
+            descent: px(4.),
+            runs: vec![ShapedRun {
+                font_id: FontId(0),
+                glyphs: vec![
+                    glyph(0, 0., false),
+                    glyph(1, 10., false),
+                    glyph(4, 20., true),
+                    glyph(2, 30., true),
+                ],
+            }],
+            len: 6,
+            contains_rtl: true,
+        };
+
+        assert!(layout.has_rtl());
+        assert_eq!(layout.x_for_index(0), px(0.));
+        assert_eq!(layout.x_for_index(1), px(10.));
+        assert_eq!(layout.x_for_index(2), px(40.));
+        assert_eq!(layout.x_for_index(4), px(30.));
+        assert_eq!(layout.x_for_index(6), px(20.));
+        assert_eq!(layout.closest_index_for_x(px(38.)), 2);
+        assert_eq!(layout.closest_index_for_x(px(21.)), 6);
+        assert_eq!(layout.index_for_x(px(25.)), Some(4));
+        assert_eq!(
+            layout.x_ranges_for_range(1..4).into_vec(),
+            vec![px(10.)..px(20.), px(30.)..px(40.)]
+        );
+    }
+}
@@ -34,6 +34,17 @@ impl LineWrapper {
         &'a mut self,
         fragments: &'a [LineFragment],
         wrap_width: Pixels,
+    ) -> impl Iterator<Item = Boundary> + 'a {
+        self.wrap_line_with_hanging_indent(fragments, wrap_width, 0)
+    }
+
+    /// Wrap a line of text like [`Self::wrap_line`], indenting the lines it wraps onto by
+    /// `hanging_indent` more columns than the line itself is indented.
+    pub fn wrap_line_with_hanging_indent<'a>(
+        &'a mut self,
+        fragments: &'a [LineFragment],
+        wrap_width: Pixels,
+        hanging_indent: u32,
     ) -> impl Iterator<Item = Boundary> + 'a {
         let mut width = px(0.);
         let mut first_non_whitespace_ix = None;
@@ -101,7 +112,10 @@ impl LineWrapper {
                     if let (None, Some(first_non_whitespace_ix)) = (indent, first_non_whitespace_ix)
                     {
                         indent = Some(
-                            Self::MAX_INDENT.min((first_non_whitespace_ix - last_wrap_ix) as u32),
+                            Self::MAX_INDENT.min(
+                                ((first_non_whitespace_ix - last_wrap_ix) as u32)
+                                    .saturating_add(hanging_indent),
+                            ),
                         );
                     }
 
@@ -351,6 +365,37 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn test_wrap_line_with_hanging_indent() {
+        let mut wrapper = build_wrapper();
+
+        assert_eq!(
+            wrapper
+                .wrap_line_with_hanging_indent(
+                    &[LineFragment::text("aa bbb cccc ddddd eeee")],
+                    px(72.),
+                    2
+                )
+                .collect::<Vec<_>>(),
+            &[
+                Boundary::new(7, 2),
+                Boundary::new(12, 2),
+                Boundary::new(17, 2)
+            ],
+        );
+        assert_eq!(
+            wrapper
+                .wrap_line_with_hanging_indent(
+                    &[LineFragment::text("  aa bbb cccc")],
+                    px(72.),
+                    u32::MAX
+                )
+                .next()
+                .map(|boundary| boundary.next_indent),
+            Some(LineWrapper::MAX_INDENT),
+        );
+    }
+
     #[test]
     fn test_wrap_line() {
         let mut wrapper = build_wrapper();
@@ -46,6 +46,7 @@ pub enum IconName {
     Book,
     BookCopy,
     BookPlus,
+    Bookmark,
     Brain,
     BugOff,
     CaseSensitive,
@@ -240,6 +241,7 @@ pub enum IconName {
     Tab,
     Terminal,
     TextSnippet,
+    TextWrap,
     ThumbsDown,
     ThumbsUp,
     Trash,
@@ -104,4 +104,8 @@ impl StatusItemView for ImageInfo {
         }
         cx.notify();
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("image_info".into())
+    }
 }
@@ -855,6 +855,10 @@ impl StatusItemView for InlineCompletionButton {
         }
         cx.notify();
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("edit_prediction".into())
+    }
 }
 
 impl SupermavenButtonStatus {
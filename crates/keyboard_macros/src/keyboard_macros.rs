@@ -0,0 +1,678 @@
+//! Records the keystrokes typed in a window into a keyboard macro, and replays them. Macros are
+//! recorded as keystrokes rather than actions so that they work the same with every keymap.
+
+pub mod recording_indicator;
+
+use std::{borrow::Cow, collections::BTreeMap, ops::Range};
+
+use anyhow::Result;
+use command_palette_hooks::{
+    CommandArgumentPrompt, CommandInterceptResult, CommandPaletteArgumentPrompts,
+};
+use editor::{Anchor, Editor};
+use gpui::{
+    Action, App, AsyncWindowContext, Context, Entity, Global, Keystroke, KeystrokeEvent, Window,
+    actions, impl_actions,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources, update_settings_file};
+use text::Point;
+use workspace::{Toast, Workspace, notifications::NotificationId};
+
+actions!(keyboard_macros, [ToggleRecording]);
+
+/// Replays the last recorded macro, or the one saved under `name`, `times` times in a row.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Replay {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub times: Option<usize>,
+}
+
+/// Replays the last recorded macro, or the one saved under `name`, once for each selection in the
+/// active editor, with that selection as the only one.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayOnEachSelection {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Replays the last recorded macro, or the one saved under `name`, once for each line spanned by
+/// the selections in the active editor, with the cursor at the start of that line.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayOnEachLine {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Saves the last recorded macro to the `keyboard_macros` setting under `name`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SaveLastMacro {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl_actions!(
+    keyboard_macros,
+    [
+        Replay,
+        ReplayOnEachSelection,
+        ReplayOnEachLine,
+        SaveLastMacro
+    ]
+);
+
+/// Caps the keystrokes dispatched by a single replay, so that a large repeat count can't keep the
+/// window busy indefinitely.
+const MAX_REPLAYED_KEYSTROKES: usize = 10_000;
+
+/// Saved keyboard macros by name, each a space-separated list of keystrokes in the same format as
+/// `workspace::SendKeystrokes`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct KeyboardMacroSettings(pub BTreeMap<String, String>);
+
+impl Settings for KeyboardMacroSettings {
+    const KEY: Option<&'static str> = Some("keyboard_macros");
+
+    type FileContent = Self;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        Ok(Self(
+            sources
+                .defaults_and_customizations()
+                .flat_map(|macros| macros.0.clone())
+                .collect(),
+        ))
+    }
+
+    fn import_from_vscode(_: &settings::VsCodeSettings, _: &mut Self::FileContent) {}
+}
+
+/// The state of macro recording and replay, shared by all windows.
+#[derive(Default)]
+pub struct MacroRecorder {
+    recording: Option<Recording>,
+    last_macro: Option<Vec<Keystroke>>,
+    /// The last macro as it was before the keystrokes of the modal it was stopped from were
+    /// trimmed, kept until the next keystroke in case the recording was stopped by a binding.
+    untrimmed_last_macro: Option<Vec<Keystroke>>,
+    replaying: bool,
+}
+
+impl Global for MacroRecorder {}
+
+#[derive(Default)]
+struct Recording {
+    keystrokes: Vec<Keystroke>,
+    /// How many of the last keystrokes are a prefix of a multi-keystroke binding.
+    pending_len: usize,
+    /// The index of the keystroke that opened the most recent modal, until a keystroke is made
+    /// after it closes. Recordings stopped from the command palette drop the keystrokes from here.
+    modal_start: Option<usize>,
+    modal_open: bool,
+}
+
+impl MacroRecorder {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn last_macro(&self) -> Option<&[Keystroke]> {
+        self.last_macro.as_deref()
+    }
+
+    fn start_recording(&mut self) {
+        self.recording = Some(Recording::default());
+        self.untrimmed_last_macro = None;
+    }
+
+    fn stop_recording(&mut self, modal_open: bool) {
+        let Some(mut recording) = self.recording.take() else {
+            return;
+        };
+        recording
+            .keystrokes
+            .truncate(recording.keystrokes.len() - recording.pending_len);
+        let mut keystrokes = recording.keystrokes.clone();
+        if let Some(modal_start) = recording.modal_start.filter(|_| !modal_open) {
+            keystrokes.truncate(modal_start);
+        }
+        self.last_macro = Some(keystrokes);
+        self.untrimmed_last_macro = Some(recording.keystrokes);
+    }
+
+    fn observe_keystroke(&mut self, event: &KeystrokeEvent, pending: bool, modal_open: bool) {
+        let untrimmed_last_macro = self.untrimmed_last_macro.take();
+        if self.replaying {
+            return;
+        }
+        if event
+            .action
+            .as_ref()
+            .is_some_and(|action| action.as_any().is::<ToggleRecording>())
+        {
+            // The recording was stopped by a binding rather than from a modal, so none of its
+            // keystrokes should have been trimmed.
+            if self.recording.is_none() {
+                if let Some(keystrokes) = untrimmed_last_macro {
+                    self.last_macro = Some(keystrokes);
+                }
+            }
+            return;
+        }
+        let Some(recording) = self.recording.as_mut() else {
+            return;
+        };
+
+        recording.keystrokes.push(event.keystroke.clone());
+        recording.pending_len = if pending && event.action.is_none() {
+            recording.pending_len + 1
+        } else {
+            0
+        };
+        if modal_open {
+            if !recording.modal_open && recording.modal_start.is_none() {
+                recording.modal_start = Some(recording.keystrokes.len() - 1);
+            }
+        } else if !recording.modal_open {
+            recording.modal_start = None;
+        }
+        recording.modal_open = modal_open;
+    }
+}
+
+pub fn init(cx: &mut App) {
+    KeyboardMacroSettings::register(cx);
+    cx.set_global(MacroRecorder::default());
+    cx.observe_keystrokes(|event, window, cx| {
+        if !cx.global::<MacroRecorder>().is_recording()
+            && cx.global::<MacroRecorder>().untrimmed_last_macro.is_none()
+        {
+            return;
+        }
+        let modal_open = has_active_modal(window, cx);
+        let pending = window.has_pending_keystrokes();
+        cx.update_global(|recorder: &mut MacroRecorder, _| {
+            recorder.observe_keystroke(event, pending, modal_open)
+        });
+    })
+    .detach();
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(toggle_recording);
+        workspace.register_action(|workspace, action: &Replay, window, cx| {
+            let Some(keystrokes) = macro_keystrokes(action.name.as_deref(), workspace, cx) else {
+                return;
+            };
+            // Every run replays at least one keystroke, so more runs than this would go over the
+            // limit anyway.
+            let times = action.times.unwrap_or(1).min(MAX_REPLAYED_KEYSTROKES + 1);
+            let runs = vec![None; times];
+            replay(keystrokes, runs, window, cx);
+        });
+        workspace.register_action(|workspace, action: &ReplayOnEachSelection, window, cx| {
+            replay_in_active_editor(action.name.as_deref(), false, workspace, window, cx);
+        });
+        workspace.register_action(|workspace, action: &ReplayOnEachLine, window, cx| {
+            replay_in_active_editor(action.name.as_deref(), true, workspace, window, cx);
+        });
+        workspace.register_action(save_last_macro);
+    })
+    .detach();
+
+    CommandPaletteArgumentPrompts::update_global(cx, |prompts, _| {
+        prompts.register::<Replay>(CommandArgumentPrompt {
+            placeholder: "Macro name and number of times, or empty to replay the last macro once"
+                .into(),
+            build: Box::new(|argument, _| {
+                let (name, times) = parse_replay_argument(argument);
+                let string = match (&name, times) {
+                    (Some(name), Some(times)) => format!("Replay macro `{name}` {times} times"),
+                    (Some(name), None) => format!("Replay macro `{name}`"),
+                    (None, Some(times)) => format!("Replay last macro {times} times"),
+                    (None, None) => "Replay last macro".to_string(),
+                };
+                Some(CommandInterceptResult {
+                    action: Replay { name, times }.boxed_clone(),
+                    string,
+                    positions: Vec::new(),
+                })
+            }),
+        });
+        prompts.register::<SaveLastMacro>(CommandArgumentPrompt {
+            placeholder: "Macro name".into(),
+            build: Box::new(|argument, _| {
+                if argument.is_empty() {
+                    return None;
+                }
+                Some(CommandInterceptResult {
+                    action: SaveLastMacro {
+                        name: Some(argument.to_string()),
+                    }
+                    .boxed_clone(),
+                    string: format!("Save last macro as `{argument}`"),
+                    positions: Vec::new(),
+                })
+            }),
+        });
+    });
+}
+
+fn has_active_modal(window: &mut Window, cx: &mut App) -> bool {
+    window
+        .root::<Workspace>()
+        .flatten()
+        .is_some_and(|workspace| {
+            workspace.update(cx, |workspace, cx| workspace.has_active_modal(window, cx))
+        })
+}
+
+/// Splits an argument like `name 3` into the macro's name and the number of times to replay it.
+fn parse_replay_argument(argument: &str) -> (Option<String>, Option<usize>) {
+    let (name, times) = match argument.rsplit_once(' ') {
+        Some((name, times)) => match times.parse::<usize>() {
+            Ok(times) => (name.trim(), Some(times)),
+            Err(_) => (argument, None),
+        },
+        None => match argument.parse::<usize>() {
+            Ok(times) => ("", Some(times)),
+            Err(_) => (argument, None),
+        },
+    };
+    let name = Some(name.to_string()).filter(|name| !name.is_empty());
+    (name, times)
+}
+
+fn toggle_recording(
+    workspace: &mut Workspace,
+    _: &ToggleRecording,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let modal_open = workspace.has_active_modal(window, cx);
+    cx.update_global(|recorder: &mut MacroRecorder, _| {
+        if recorder.is_recording() {
+            recorder.stop_recording(modal_open);
+        } else if !recorder.replaying {
+            recorder.start_recording();
+        }
+    });
+}
+
+fn save_last_macro(
+    workspace: &mut Workspace,
+    action: &SaveLastMacro,
+    _: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(name) = action.name.clone() else {
+        show_toast(workspace, "Keyboard macros need a name to be saved", cx);
+        return;
+    };
+    let Some(keystrokes) = cx
+        .global::<MacroRecorder>()
+        .last_macro()
+        .map(serialize_keystrokes)
+    else {
+        show_toast(workspace, "No keyboard macro has been recorded", cx);
+        return;
+    };
+    update_settings_file::<KeyboardMacroSettings>(
+        workspace.app_state().fs.clone(),
+        cx,
+        move |macros, _| {
+            macros.0.insert(name, keystrokes);
+        },
+    );
+}
+
+fn show_toast(
+    workspace: &mut Workspace,
+    message: impl Into<Cow<'static, str>>,
+    cx: &mut Context<Workspace>,
+) {
+    struct KeyboardMacroToast;
+
+    workspace.show_toast(
+        Toast::new(NotificationId::unique::<KeyboardMacroToast>(), message).autohide(),
+        cx,
+    );
+}
+
+fn macro_keystrokes(
+    name: Option<&str>,
+    workspace: &mut Workspace,
+    cx: &mut Context<Workspace>,
+) -> Option<Vec<Keystroke>> {
+    let Some(name) = name else {
+        let keystrokes = cx.global::<MacroRecorder>().last_macro().map(<[_]>::to_vec);
+        if keystrokes.is_none() {
+            show_toast(workspace, "No keyboard macro has been recorded", cx);
+        }
+        return keystrokes;
+    };
+
+    let Some(keystrokes) = KeyboardMacroSettings::get_global(cx).0.get(name) else {
+        show_toast(workspace, format!("No keyboard macro named `{name}`"), cx);
+        return None;
+    };
+    match keystrokes
+        .split_whitespace()
+        .map(Keystroke::parse)
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(keystrokes) => Some(keystrokes),
+        Err(error) => {
+            show_toast(
+                workspace,
+                format!("In keyboard macro `{name}`: {error}"),
+                cx,
+            );
+            None
+        }
+    }
+}
+
+/// Writes keystrokes in the format that `Keystroke::parse` reads, keeping the typed character of
+/// those that don't produce the one it would infer, such as `alt-o` producing `ø`.
+fn serialize_keystrokes(keystrokes: &[Keystroke]) -> String {
+    keystrokes
+        .iter()
+        .map(|keystroke| {
+            let mut inferred = keystroke.clone();
+            inferred.key_char = None;
+            match &keystroke.key_char {
+                Some(key_char)
+                    if inferred.with_simulated_ime().key_char.as_ref() != Some(key_char) =>
+                {
+                    format!("{}->{}", keystroke.unparse(), key_char)
+                }
+                _ => keystroke.unparse(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn replay_in_active_editor(
+    name: Option<&str>,
+    each_line: bool,
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        show_toast(
+            workspace,
+            "Macros can only be replayed per selection in an editor",
+            cx,
+        );
+        return;
+    };
+    let Some(keystrokes) = macro_keystrokes(name, workspace, cx) else {
+        return;
+    };
+
+    let ranges = editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let selections = editor.selections.all::<Point>(cx);
+        if !each_line {
+            return selections
+                .into_iter()
+                .map(|selection| {
+                    snapshot.anchor_before(selection.start)..snapshot.anchor_after(selection.end)
+                })
+                .collect::<Vec<_>>();
+        }
+
+        let mut rows = Vec::new();
+        for selection in selections {
+            let mut end_row = selection.end.row;
+            if selection.end.column == 0 && end_row > selection.start.row {
+                end_row -= 1;
+            }
+            let start_row = rows.last().map_or(selection.start.row, |last_row: &u32| {
+                selection.start.row.max(last_row + 1)
+            });
+            rows.extend(start_row..=end_row);
+        }
+        rows.into_iter()
+            .map(|row| {
+                let anchor = snapshot.anchor_before(Point::new(row, 0));
+                anchor..anchor
+            })
+            .collect()
+    });
+
+    let runs = ranges
+        .into_iter()
+        .map(|range| Some((editor.clone(), range)))
+        .collect();
+    replay(keystrokes, runs, window, cx);
+}
+
+/// Replays `keystrokes` once per run, first making a run's range the only selection in its
+/// editor, if it has one.
+fn replay(
+    keystrokes: Vec<Keystroke>,
+    runs: Vec<Option<(Entity<Editor>, Range<Anchor>)>>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let recorder = cx.global_mut::<MacroRecorder>();
+    if recorder.replaying || keystrokes.is_empty() {
+        return;
+    }
+    recorder.replaying = true;
+
+    window
+        .spawn(cx, async move |cx| {
+            let result = replay_runs(&keystrokes, runs, cx).await;
+            cx.update(|_, cx| cx.global_mut::<MacroRecorder>().replaying = false)?;
+            result
+        })
+        .detach_and_log_err(cx);
+}
+
+async fn replay_runs(
+    keystrokes: &[Keystroke],
+    runs: Vec<Option<(Entity<Editor>, Range<Anchor>)>>,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let mut replayed = 0;
+    for run in runs {
+        if let Some((editor, range)) = run {
+            editor.update_in(cx, |editor, window, cx| {
+                window.focus(&editor.focus_handle(cx));
+                editor.change_selections(None, window, cx, |selections| {
+                    selections.select_anchor_ranges([range])
+                });
+            })?;
+            cx.update(|window, cx| {
+                window.draw(cx);
+            })?;
+        }
+
+        for keystroke in keystrokes {
+            replayed += 1;
+            anyhow::ensure!(
+                replayed <= MAX_REPLAYED_KEYSTROKES,
+                "over {MAX_REPLAYED_KEYSTROKES} keystrokes replayed by a keyboard macro"
+            );
+            cx.update(|window, cx| {
+                let focused = window.focused(cx);
+                window.dispatch_keystroke(keystroke.clone(), cx);
+                if window.focused(cx) != focused {
+                    // Deliver the focus change before the next keystroke, see
+                    // `Workspace::send_keystrokes`.
+                    window.draw(cx);
+                }
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::test::editor_test_context::EditorTestContext;
+    use gpui::{KeyBinding, TestAppContext, VisualTestContext};
+    use indoc::indoc;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::AppState;
+
+    fn init_test(cx: &mut TestAppContext) -> std::sync::Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            language::init(cx);
+            command_palette_hooks::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+            cx.bind_keys([
+                KeyBinding::new("ctrl-q", ToggleRecording, None),
+                KeyBinding::new("ctrl-r", Replay::default(), None),
+                KeyBinding::new("ctrl-l", ReplayOnEachLine::default(), None),
+                KeyBinding::new("ctrl-s", ReplayOnEachSelection::default(), None),
+                KeyBinding::new("down", editor::actions::MoveDown, Some("Editor")),
+            ]);
+            state
+        })
+    }
+
+    async fn editor_in_workspace(text: &str, cx: &mut TestAppContext) -> EditorTestContext {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "a.txt": text }))
+            .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, "a.txt"), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        EditorTestContext::for_editor_in(editor, cx).await
+    }
+
+    fn last_macro(cx: &mut VisualTestContext) -> Option<String> {
+        cx.update(|_, cx| {
+            cx.global::<MacroRecorder>()
+                .last_macro()
+                .map(serialize_keystrokes)
+        })
+    }
+
+    #[gpui::test]
+    async fn test_record_and_replay(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut cx = editor_in_workspace("", cx).await;
+        cx.set_state("ˇ\n\n\n\n\n");
+
+        cx.simulate_keystrokes("ctrl-q - space down ctrl-q");
+        assert_eq!(last_macro(&mut cx).as_deref(), Some("- space down"));
+        cx.assert_editor_state("- \nˇ\n\n\n\n");
+
+        cx.simulate_keystrokes("ctrl-r");
+        cx.run_until_parked();
+        cx.assert_editor_state("- \n- \nˇ\n\n\n");
+
+        cx.dispatch_action(Replay {
+            name: None,
+            times: Some(2),
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state("- \n- \n- \n- \nˇ\n");
+    }
+
+    #[gpui::test]
+    async fn test_replay_on_each_line_and_selection(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut cx = editor_in_workspace("", cx).await;
+        cx.set_state("ˇone\n");
+        cx.simulate_keystrokes("ctrl-q ! space ctrl-q");
+        cx.assert_editor_state("! ˇone\n");
+
+        cx.set_state(indoc! {"
+            o«ne
+            twoˇ»
+            three
+            four
+        "});
+        cx.simulate_keystrokes("ctrl-l");
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            ! one
+            ! ˇtwo
+            three
+            four
+        "});
+
+        cx.set_state(indoc! {"
+            oneˇ
+            two
+            ˇthree
+            four
+        "});
+        cx.simulate_keystrokes("ctrl-s");
+        cx.run_until_parked();
+        cx.assert_editor_state("one! \ntwo\n! ˇthree\nfour\n");
+    }
+
+    #[gpui::test]
+    async fn test_replay_named_macro(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut cx = editor_in_workspace("", cx).await;
+        cx.update(|_, cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings::<KeyboardMacroSettings>(cx, |macros| {
+                    macros
+                        .0
+                        .insert("greet".to_string(), "h i enter".to_string());
+                });
+            });
+        });
+        cx.set_state("ˇ");
+
+        cx.dispatch_action(Replay {
+            name: Some("greet".to_string()),
+            times: Some(2),
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state("hi\nhi\nˇ");
+    }
+
+    #[test]
+    fn test_parse_replay_argument() {
+        assert_eq!(parse_replay_argument(""), (None, None));
+        assert_eq!(parse_replay_argument("3"), (None, Some(3)));
+        assert_eq!(
+            parse_replay_argument("fix"),
+            (Some("fix".to_string()), None)
+        );
+        assert_eq!(
+            parse_replay_argument("fix imports 12"),
+            (Some("fix imports".to_string()), Some(12))
+        );
+    }
+}
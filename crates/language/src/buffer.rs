@@ -3353,6 +3353,62 @@ impl BufferSnapshot {
         (start..end, word_kind)
     }
 
+    /// Returns the range to select when expanding the selection from the given range. It's the
+    /// range of the smallest syntax node that contains it, unless the language configures that
+    /// kind of node to be skipped, or to have its contents selected first.
+    pub fn larger_syntax_node_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let mut node = self.syntax_ancestor(range.clone())?;
+        let Some(config) = self
+            .language_at(range.start)
+            .map(|language| &language.config.expand_selection)
+        else {
+            return Some(node.byte_range());
+        };
+        loop {
+            let node_range = node.byte_range();
+            if config.select_inside.iter().any(|kind| kind == node.kind()) {
+                if let Some(contents) = self.syntax_node_contents(&node) {
+                    if contents.start <= range.start
+                        && contents.end >= range.end
+                        && contents.len() > range.len()
+                    {
+                        return Some(contents);
+                    }
+                }
+            }
+            if !config.skip.iter().any(|kind| kind == node.kind()) {
+                return Some(node_range);
+            }
+            match self.syntax_ancestor(node_range.clone()) {
+                Some(parent) => node = parent,
+                None => return Some(node_range),
+            }
+        }
+    }
+
+    /// The range between the first and last tokens of a node, like the quotes of a string or the
+    /// parentheses of an argument list, without the surrounding whitespace.
+    fn syntax_node_contents(&self, node: &tree_sitter::Node) -> Option<Range<usize>> {
+        let first = node.child(0)?;
+        let last = node.child(node.child_count().checked_sub(1)?)?;
+        if node.child_count() < 2 || first.is_named() || last.is_named() {
+            return None;
+        }
+        let mut start = first.end_byte();
+        let mut end = last.start_byte();
+        start += self
+            .chars_at(start)
+            .take_while(|c| c.is_whitespace())
+            .map(char::len_utf8)
+            .sum::<usize>();
+        end -= self
+            .reversed_chars_at(end)
+            .take_while(|c| c.is_whitespace())
+            .map(char::len_utf8)
+            .sum::<usize>();
+        (start < end).then_some(start..end)
+    }
+
     /// Returns the closest syntax node enclosing the given range.
     pub fn syntax_ancestor<'a, T: ToOffset>(
         &'a self,
@@ -1222,6 +1222,39 @@ fn test_range_for_syntax_ancestor(cx: &mut App) {
     }
 }
 
+#[gpui::test]
+fn test_larger_syntax_node_range(cx: &mut App) {
+    cx.new(|cx| {
+        let mut language = rust_lang();
+        language.config.expand_selection = crate::ExpandSelectionConfig {
+            select_inside: vec!["arguments".into()],
+            skip: vec!["expression_statement".into()],
+        };
+        let text = "fn a() { b(c, d); }";
+        let buffer = Buffer::local(text, cx).with_language(Arc::new(language), cx);
+        let snapshot = buffer.snapshot();
+
+        let mut range = range_of(text, "c");
+        let mut expansions = Vec::new();
+        for _ in 0..4 {
+            range = snapshot.larger_syntax_node_range(range).unwrap();
+            expansions.push(&text[range.clone()]);
+        }
+        assert_eq!(
+            expansions,
+            ["c, d", "(c, d)", "b(c, d)", "{ b(c, d); }"],
+            "expands to the contents of the arguments first, and past the statement"
+        );
+
+        buffer
+    });
+
+    fn range_of(text: &str, part: &str) -> Range<usize> {
+        let start = text.find(part).unwrap();
+        start..start + part.len()
+    }
+}
+
 #[gpui::test]
 fn test_autoindent_with_soft_tabs(cx: &mut App) {
     init_settings(cx, |_| {});
@@ -11,10 +11,12 @@ pub struct HighlightId(pub u32);
 const DEFAULT_SYNTAX_HIGHLIGHT_ID: HighlightId = HighlightId(u32::MAX);
 
 impl HighlightMap {
-    pub(crate) fn new(capture_names: &[&str], theme: &SyntaxTheme) -> Self {
+    pub(crate) fn new(capture_names: &[&str], language_name: &str, theme: &SyntaxTheme) -> Self {
         // For each capture name in the highlight query, find the longest
         // key in the theme's syntax styles that matches all of the
-        // dot-separated components of the capture name.
+        // dot-separated components of the capture name, preferring keys
+        // that only apply to this language over ones of the same length
+        // that apply to every language.
         HighlightMap(
             capture_names
                 .iter()
@@ -24,6 +26,16 @@ impl HighlightMap {
                         .iter()
                         .enumerate()
                         .filter_map(|(i, (key, _))| {
+                            let (key, is_language_specific) =
+                                match SyntaxTheme::parse_language_style_key(key) {
+                                    Some((language, key)) => {
+                                        if !language.eq_ignore_ascii_case(language_name) {
+                                            return None;
+                                        }
+                                        (key, true)
+                                    }
+                                    None => (key.as_str(), false),
+                                };
                             let mut len = 0;
                             let capture_parts = capture_name.split('.');
                             for key_part in key.split('.') {
@@ -33,9 +45,9 @@ impl HighlightMap {
                                     return None;
                                 }
                             }
-                            Some((i, len))
+                            Some((i, (len, is_language_specific)))
                         })
-                        .max_by_key(|(_, len)| *len)
+                        .max_by_key(|(_, precedence)| *precedence)
                         .map_or(DEFAULT_SYNTAX_HIGHLIGHT_ID, |(i, _)| HighlightId(i as u32))
                 })
                 .collect(),
@@ -103,9 +115,38 @@ mod tests {
             "variable.builtin.self",
         ];
 
-        let map = HighlightMap::new(capture_names, &theme);
+        let map = HighlightMap::new(capture_names, "Rust", &theme);
         assert_eq!(map.get(0).name(&theme), Some("function"));
         assert_eq!(map.get(1).name(&theme), Some("function.async"));
         assert_eq!(map.get(2).name(&theme), Some("variable.builtin"));
     }
+
+    #[test]
+    fn test_highlight_map_with_language_styles() {
+        let theme = SyntaxTheme {
+            highlights: [
+                ("string", rgba(0x100000ff)),
+                ("string.special", rgba(0x200000ff)),
+                ("YAML/string", rgba(0x300000ff)),
+                ("Rust/string.special", rgba(0x400000ff)),
+            ]
+            .iter()
+            .map(|(name, color)| (name.to_string(), (*color).into()))
+            .collect(),
+        };
+
+        let capture_names = &["string", "string.special"];
+
+        let map = HighlightMap::new(capture_names, "YAML", &theme);
+        assert_eq!(map.get(0).name(&theme), Some("YAML/string"));
+        assert_eq!(map.get(1).name(&theme), Some("string.special"));
+
+        let map = HighlightMap::new(capture_names, "rust", &theme);
+        assert_eq!(map.get(0).name(&theme), Some("string"));
+        assert_eq!(map.get(1).name(&theme), Some("Rust/string.special"));
+
+        let map = HighlightMap::new(capture_names, "JSON", &theme);
+        assert_eq!(map.get(0).name(&theme), Some("string"));
+        assert_eq!(map.get(1).name(&theme), Some("string.special"));
+    }
 }
@@ -323,6 +323,10 @@ pub trait LspAdapterDelegate: Send + Sync {
     fn http_client(&self) -> Arc<dyn HttpClient>;
     fn worktree_id(&self) -> WorktreeId;
     fn worktree_root_path(&self) -> &Path;
+    /// The subproject that the language server was started for, relative to the worktree's
+    /// root, whose settings apply to it. It's empty unless the language server has an instance
+    /// for each subproject.
+    fn subproject_path(&self) -> &Path;
     fn update_status(&self, language: LanguageServerName, status: BinaryStatus);
     fn registered_lsp_adapters(&self) -> Vec<Arc<dyn LspAdapter>>;
     async fn language_server_download_dir(&self, name: &LanguageServerName) -> Option<Arc<Path>>;
@@ -772,6 +776,13 @@ pub struct LanguageConfig {
     /// auto adding prefix on new line, adjusting the indenting , etc.
     #[serde(default)]
     pub documentation: Option<DocumentationConfig>,
+    /// How expanding the selection walks up the syntax tree.
+    #[serde(default)]
+    pub expand_selection: ExpandSelectionConfig,
+    /// Whether splitting a list of arguments or elements across lines adds a trailing comma
+    /// after the last one.
+    #[serde(default)]
+    pub split_lists_with_trailing_comma: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default, JsonSchema)]
@@ -835,6 +846,19 @@ pub struct DocumentationConfig {
     pub tab_size: NonZeroU32,
 }
 
+/// The configuration for expanding the selection to larger syntax nodes, which otherwise selects
+/// each ancestor node in turn.
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema)]
+pub struct ExpandSelectionConfig {
+    /// Kinds of nodes whose contents, between their first and last tokens like quotes or
+    /// parentheses, are selected before the whole node.
+    #[serde(default)]
+    pub select_inside: Vec<String>,
+    /// Kinds of nodes that are expanded past, rather than selected on their own.
+    #[serde(default)]
+    pub skip: Vec<String>,
+}
+
 /// Represents a language for the given range. Some languages (e.g. HTML)
 /// interleave several languages together, thus a single buffer might actually contain
 /// several nested scopes.
@@ -914,6 +938,8 @@ impl Default for LanguageConfig {
             debuggers: Default::default(),
             significant_indentation: Default::default(),
             documentation: None,
+            expand_selection: Default::default(),
+            split_lists_with_trailing_comma: false,
         }
     }
 }
@@ -959,6 +985,7 @@ pub struct FakeLspAdapter {
     pub language_server_binary: LanguageServerBinary,
 
     pub capabilities: lsp::ServerCapabilities,
+    pub manifest_name: Option<ManifestName>,
     pub initializer: Option<Box<dyn 'static + Send + Sync + Fn(&mut lsp::FakeLanguageServer)>>,
     pub label_for_completion: Option<
         Box<
@@ -1711,8 +1738,11 @@ impl Language {
     pub fn set_theme(&self, theme: &SyntaxTheme) {
         if let Some(grammar) = self.grammar.as_ref() {
             if let Some(highlights_query) = &grammar.highlights_query {
-                *grammar.highlight_map.lock() =
-                    HighlightMap::new(highlights_query.capture_names(), theme);
+                *grammar.highlight_map.lock() = HighlightMap::new(
+                    highlights_query.capture_names(),
+                    self.config.name.as_ref(),
+                    theme,
+                );
             }
         }
     }
@@ -2064,6 +2094,7 @@ impl Default for FakeLspAdapter {
         Self {
             name: "the-fake-language-server",
             capabilities: lsp::LanguageServer::full_capabilities(),
+            manifest_name: None,
             initializer: None,
             disk_based_diagnostics_progress_token: None,
             initialization_options: None,
@@ -2086,6 +2117,10 @@ impl LspAdapter for FakeLspAdapter {
         LanguageServerName(self.name.into())
     }
 
+    fn manifest_name(&self) -> Option<ManifestName> {
+        self.manifest_name.clone()
+    }
+
     async fn check_if_user_installed(
         &self,
         _: &dyn LspAdapterDelegate,
@@ -80,6 +80,23 @@ pub struct LanguageSettings {
     /// The column at which to soft-wrap lines, for buffers where soft-wrap
     /// is enabled.
     pub preferred_line_length: u32,
+    /// How many more columns than the line itself soft-wrapped rows are indented by.
+    pub soft_wrap_hanging_indent: u32,
+    /// The glyph shown where a line is soft-wrapped, or an empty string to show none.
+    pub soft_wrap_indicator: String,
+    /// Whether to highlight the text beyond the 'preferred_line_length' column, and count
+    /// the lines that exceed it.
+    pub highlight_overlong_lines: bool,
+    /// Whether to render the buffer font's ligatures.
+    pub font_ligatures: bool,
+    /// The font family to use instead of the buffer font, such as a proportional font for prose.
+    pub font_family: Option<String>,
+    /// Whether tabs are sized as elastic tabstops, aligning the text between tabs on
+    /// consecutive lines into columns.
+    pub elastic_tabstops: bool,
+    /// Whether to render the text under the cursor without ligatures, so that each of its
+    /// characters is visible while editing it.
+    pub break_ligatures_at_cursor: bool,
     /// Whether to show wrap guides (vertical rulers) in the editor.
     /// Setting this to true will show a guide at the 'preferred_line_length' value
     /// if softwrap is set to 'preferred_line_length', and will show any
@@ -89,6 +106,8 @@ pub struct LanguageSettings {
     pub wrap_guides: Vec<usize>,
     /// Indent guide related settings.
     pub indent_guides: IndentGuideSettings,
+    /// Settings for coloring brackets by their nesting depth.
+    pub bracket_colorization: BracketColorizationSettings,
     /// Whether or not to perform a buffer format before saving.
     pub format_on_save: FormatOnSave,
     /// Whether or not to remove any trailing whitespace from lines of a buffer
@@ -125,10 +144,15 @@ pub struct LanguageSettings {
     pub edit_predictions_disabled_in: Vec<String>,
     /// Whether to show tabs and spaces in the editor.
     pub show_whitespaces: ShowWhitespaceSetting,
+    /// The glyphs and color used to draw whitespace.
+    pub whitespace_map: WhitespaceMap,
     /// Whether to start a new line with a comment when a previous line is a comment as well.
     pub extend_comment_on_newline: bool,
     /// Inlay hint related settings.
     pub inlay_hints: InlayHintSettings,
+    /// Whether to show the code lenses provided by language servers above the symbols they
+    /// refer to.
+    pub code_lens: bool,
     /// Whether to automatically close brackets.
     pub use_autoclose: bool,
     /// Whether to automatically surround text with brackets.
@@ -406,6 +430,48 @@ pub struct LanguageSettingsContent {
     /// Default: 80
     #[serde(default)]
     pub preferred_line_length: Option<u32>,
+    /// How many more columns than the line itself soft-wrapped rows are indented by. Wrapped
+    /// rows are always indented as much as the line itself.
+    ///
+    /// Default: 0
+    #[serde(default)]
+    pub soft_wrap_hanging_indent: Option<u32>,
+    /// The glyph shown at the end of each row where a line is soft-wrapped, or an empty
+    /// string to show none.
+    ///
+    /// Default: ""
+    #[serde(default)]
+    pub soft_wrap_indicator: Option<String>,
+    /// Whether to highlight the text beyond the 'preferred_line_length' column, and show
+    /// how many lines exceed it in the status bar.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub highlight_overlong_lines: Option<bool>,
+    /// Whether to render the buffer font's ligatures. Unlike disabling `calt` in
+    /// `buffer_font_features`, this can be set per language.
+    ///
+    /// Default: true
+    #[serde(default)]
+    pub font_ligatures: Option<bool>,
+    /// The font family to use instead of `buffer_font_family`, such as a proportional font
+    /// for prose.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub font_family: Option<String>,
+    /// Whether tabs are sized as elastic tabstops rather than as a number of spaces, so that
+    /// the text between tabs on consecutive lines lines up in columns, whatever the font.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub elastic_tabstops: Option<bool>,
+    /// Whether to render the text under the cursor, up to the nearest whitespace, without
+    /// ligatures, so that each of its characters is visible while editing it.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub break_ligatures_at_cursor: Option<bool>,
     /// Whether to show wrap guides in the editor. Setting this to true will
     /// show a guide at the 'preferred_line_length' value if softwrap is set to
     /// 'preferred_line_length', and will show any additional guides as specified
@@ -422,6 +488,9 @@ pub struct LanguageSettingsContent {
     /// Indent guide related settings.
     #[serde(default)]
     pub indent_guides: Option<IndentGuideSettings>,
+    /// Settings for coloring brackets by their nesting depth.
+    #[serde(default)]
+    pub bracket_colorization: Option<BracketColorizationSettings>,
     /// Whether or not to perform a buffer format before saving.
     ///
     /// Default: on
@@ -494,6 +563,9 @@ pub struct LanguageSettingsContent {
     /// Whether to show tabs and spaces in the editor.
     #[serde(default)]
     pub show_whitespaces: Option<ShowWhitespaceSetting>,
+    /// The glyphs and color used to draw whitespace.
+    #[serde(default)]
+    pub whitespace_map: Option<WhitespaceMap>,
     /// Whether to start a new line with a comment when a previous line is a comment as well.
     ///
     /// Default: true
@@ -502,6 +574,12 @@ pub struct LanguageSettingsContent {
     /// Inlay hint related settings.
     #[serde(default)]
     pub inlay_hints: Option<InlayHintSettings>,
+    /// Whether to show the code lenses provided by language servers, such as reference counts
+    /// and test runners, above the symbols they refer to.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub code_lens: Option<bool>,
     /// Whether to automatically type closing characters for you. For example,
     /// when you type (, Zed will automatically add a closing ) at the correct position.
     ///
@@ -765,6 +843,41 @@ pub enum ShowWhitespaceSetting {
     /// - It is adjacent to an edge (start or end)
     /// - It is adjacent to a whitespace (left or right)
     Boundary,
+    /// Draw whitespaces at the end of lines only, as well as in the selected text.
+    Trailing,
+}
+
+/// The glyphs and color used to draw whitespace.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WhitespaceMap {
+    /// The glyph drawn for each space.
+    ///
+    /// Default: "•"
+    #[serde(default)]
+    pub space: Option<String>,
+    /// The glyph drawn for each tab.
+    ///
+    /// Default: "→"
+    #[serde(default)]
+    pub tab: Option<String>,
+    /// The color of the glyphs, as a hex string like "#808080". Uses the theme's
+    /// `editor.invisible` color when unset.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+impl WhitespaceMap {
+    /// Returns the glyph drawn for each space.
+    pub fn space(&self) -> &str {
+        self.space.as_deref().unwrap_or("•")
+    }
+
+    /// Returns the glyph drawn for each tab.
+    pub fn tab(&self) -> &str {
+        self.tab.as_deref().unwrap_or("→")
+    }
 }
 
 /// Controls which formatter should be used when formatting code.
@@ -937,6 +1050,12 @@ pub struct IndentGuideSettings {
     /// Default: Disabled
     #[serde(default)]
     pub background_coloring: IndentGuideBackgroundColoring,
+    /// Determines how the scope containing the cursor, whose indent guide is highlighted,
+    /// is detected.
+    ///
+    /// Default: Indent
+    #[serde(default)]
+    pub active_scope: IndentGuideActiveScope,
 }
 
 fn line_width() -> u32 {
@@ -971,6 +1090,48 @@ pub enum IndentGuideBackgroundColoring {
     IndentAware,
 }
 
+/// Determines how the scope whose indent guide is highlighted is detected.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IndentGuideActiveScope {
+    /// Use the lines around the cursor that are indented at least as deeply.
+    #[default]
+    Indent,
+    /// Use the innermost syntax node around the cursor whose lines are indented, falling back
+    /// to indentation when the language has no syntax tree.
+    Syntax,
+}
+
+/// The settings for coloring brackets by their nesting depth.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BracketColorizationSettings {
+    /// Whether to color each pair of brackets by how deeply it is nested, using the theme's
+    /// accent colors.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub enabled: bool,
+    /// How to emphasize the pair of brackets enclosing the cursor, on top of highlighting
+    /// its background.
+    ///
+    /// Default: none
+    #[serde(default)]
+    pub enclosing_pair: EnclosingBracketEmphasis,
+}
+
+/// Determines how the pair of brackets enclosing the cursor is emphasized.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnclosingBracketEmphasis {
+    /// Only highlight the background of the brackets.
+    #[default]
+    None,
+    /// Underline the brackets.
+    Underline,
+    /// Show the brackets in bold.
+    Bold,
+}
+
 /// The settings for inlay hints.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct InlayHintSettings {
@@ -1452,7 +1613,8 @@ impl settings::Settings for AllLanguageSettings {
         vscode.bool_setting("editor.inlineSuggest.enabled", &mut d.show_edit_predictions);
         vscode.enum_setting("editor.renderWhitespace", &mut d.show_whitespaces, |s| {
             Some(match s {
-                "boundary" | "trailing" => ShowWhitespaceSetting::Boundary,
+                "boundary" => ShowWhitespaceSetting::Boundary,
+                "trailing" => ShowWhitespaceSetting::Trailing,
                 "selection" => ShowWhitespaceSetting::Selection,
                 "all" => ShowWhitespaceSetting::All,
                 _ => ShowWhitespaceSetting::None,
@@ -1553,6 +1715,7 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
     merge(&mut settings.show_wrap_guides, src.show_wrap_guides);
     merge(&mut settings.wrap_guides, src.wrap_guides.clone());
     merge(&mut settings.indent_guides, src.indent_guides);
+    merge(&mut settings.bracket_colorization, src.bracket_colorization);
     merge(
         &mut settings.code_actions_on_format,
         src.code_actions_on_format.clone(),
@@ -1564,6 +1727,25 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         &mut settings.preferred_line_length,
         src.preferred_line_length,
     );
+    merge(
+        &mut settings.soft_wrap_hanging_indent,
+        src.soft_wrap_hanging_indent,
+    );
+    merge(
+        &mut settings.soft_wrap_indicator,
+        src.soft_wrap_indicator.clone(),
+    );
+    merge(
+        &mut settings.highlight_overlong_lines,
+        src.highlight_overlong_lines,
+    );
+    merge(&mut settings.font_ligatures, src.font_ligatures);
+    merge(&mut settings.font_family, src.font_family.clone().map(Some));
+    merge(&mut settings.elastic_tabstops, src.elastic_tabstops);
+    merge(
+        &mut settings.break_ligatures_at_cursor,
+        src.break_ligatures_at_cursor,
+    );
     merge(&mut settings.formatter, src.formatter.clone());
     merge(&mut settings.prettier, src.prettier.clone());
     merge(
@@ -1594,11 +1776,13 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.edit_predictions_disabled_in.clone(),
     );
     merge(&mut settings.show_whitespaces, src.show_whitespaces);
+    merge(&mut settings.whitespace_map, src.whitespace_map.clone());
     merge(
         &mut settings.extend_comment_on_newline,
         src.extend_comment_on_newline,
     );
     merge(&mut settings.inlay_hints, src.inlay_hints);
+    merge(&mut settings.code_lens, src.code_lens);
     merge(
         &mut settings.show_completions_on_input,
         src.show_completions_on_input,
@@ -44,6 +44,12 @@ pub struct ManifestQuery {
 pub trait ManifestProvider {
     fn name(&self) -> ManifestName;
     fn search(&self, query: ManifestQuery) -> Option<Arc<Path>>;
+    /// Whether a root can be inside of another root, like a Go module in another module's
+    /// directory. Then each query searches the whole path, rather than only the part of it
+    /// that's below the roots that were already found.
+    fn allows_nested_roots(&self) -> bool {
+        false
+    }
 }
 
 pub trait ManifestDelegate: Send + Sync {
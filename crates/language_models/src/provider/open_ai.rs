@@ -557,10 +557,16 @@ impl OpenAiEventMapper {
         }
 
         match choice.finish_reason.as_deref() {
-            Some("stop") => {
+            Some("stop") if self.tool_calls_by_index.is_empty() => {
                 events.push(Ok(LanguageModelCompletionEvent::Stop(StopReason::EndTurn)));
             }
-            Some("tool_calls") => {
+            Some("length") => {
+                events.push(Ok(LanguageModelCompletionEvent::Stop(
+                    StopReason::MaxTokens,
+                )));
+            }
+            // Some OpenAI-compatible servers finish with `stop` after streaming tool calls.
+            Some("tool_calls") | Some("stop") => {
                 events.extend(self.tool_calls_by_index.drain().map(|(_, tool_call)| {
                     match serde_json::Value::from_str(&tool_call.arguments) {
                         Ok(input) => Ok(LanguageModelCompletionEvent::ToolUse(
@@ -886,4 +892,32 @@ mod tests {
             assert!(count > 0);
         }
     }
+
+    #[test]
+    fn test_tool_calls_streamed_before_stop() {
+        // As streamed by llama.cpp, which finishes with `stop` after streaming tool calls.
+        let events = [
+            r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_1","function":{"name":"grep","arguments":"{\"regex\":"}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"fn main\"}"}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        ];
+
+        let mut mapper = OpenAiEventMapper::new();
+        let mut completion_events = Vec::new();
+        for event in events {
+            let event = serde_json::from_str::<ResponseStreamEvent>(event).unwrap();
+            completion_events.extend(mapper.map_event(event).into_iter().map(Result::unwrap));
+        }
+
+        let [
+            LanguageModelCompletionEvent::ToolUse(tool_use),
+            LanguageModelCompletionEvent::Stop(StopReason::ToolUse),
+        ] = completion_events.as_slice()
+        else {
+            panic!("unexpected events: {completion_events:?}");
+        };
+        assert_eq!(tool_use.id.to_string(), "call_1");
+        assert_eq!(&*tool_use.name, "grep");
+        assert_eq!(tool_use.input, serde_json::json!({ "regex": "fn main" }));
+    }
 }
@@ -0,0 +1,689 @@
+use anyhow::{Result, anyhow};
+use collections::{BTreeMap, HashMap};
+use credentials_provider::CredentialsProvider;
+use editor::{Editor, EditorElement, EditorStyle};
+use futures::{FutureExt, StreamExt, future::BoxFuture};
+use gpui::{
+    AnyElement, AnyView, App, AsyncApp, Context, Entity, FontStyle, Subscription, Task, TextStyle,
+    WhiteSpace,
+};
+use http_client::HttpClient;
+use language_model::{
+    AuthenticateError, LanguageModel, LanguageModelCompletionError, LanguageModelCompletionEvent,
+    LanguageModelId, LanguageModelName, LanguageModelProvider, LanguageModelProviderId,
+    LanguageModelProviderName, LanguageModelProviderState, LanguageModelRequest,
+    LanguageModelToolChoice, RateLimiter,
+};
+use open_ai::{ResponseStreamEvent, list_models, stream_completion};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use std::sync::Arc;
+use theme::ThemeSettings;
+use ui::{Indicator, List, Tooltip, prelude::*};
+use util::ResultExt;
+
+use crate::provider::open_ai::{OpenAiEventMapper, count_open_ai_tokens, into_open_ai};
+use crate::{AllLanguageModelSettings, ui::InstructionListItem};
+
+const PROVIDER_ID: &str = "openai_compatible";
+const PROVIDER_NAME: &str = "OpenAI Compatible";
+
+/// The context size assumed for the models a server lists, as the `/models` endpoint doesn't
+/// report it.
+const DEFAULT_MAX_TOKENS: usize = 8192;
+
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct OpenAiCompatibleSettings {
+    pub servers: Vec<ServerSettings>,
+}
+
+/// A server implementing the OpenAI chat completions API, like llama.cpp, vLLM or LM Studio.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ServerSettings {
+    /// The name shown next to the server's models.
+    pub name: String,
+    /// The URL the API is served at, such as `http://localhost:8080/v1`.
+    pub api_url: String,
+    /// The models to offer. When empty, the ones the server lists are used.
+    #[serde(default)]
+    pub available_models: Vec<AvailableModel>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AvailableModel {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub max_tokens: usize,
+    pub max_output_tokens: Option<u32>,
+    /// Whether the model can call tools, which the agent needs.
+    ///
+    /// Default: true
+    pub supports_tools: Option<bool>,
+    /// Whether images can be sent to the model.
+    ///
+    /// Default: false
+    pub supports_images: Option<bool>,
+}
+
+pub struct OpenAiCompatibleLanguageModelProvider {
+    http_client: Arc<dyn HttpClient>,
+    state: Entity<State>,
+}
+
+pub struct State {
+    http_client: Arc<dyn HttpClient>,
+    /// The API keys of the servers requiring one, by API URL.
+    api_keys: HashMap<String, String>,
+    /// The models listed by the servers without configured models, by API URL.
+    listed_models: HashMap<String, Vec<AvailableModel>>,
+    /// Why a server couldn't be reached, by API URL.
+    errors: HashMap<String, SharedString>,
+    _fetch_models_task: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl State {
+    fn is_authenticated(&self, cx: &App) -> bool {
+        servers(cx)
+            .iter()
+            .any(|server| !self.models_for_server(server).is_empty())
+    }
+
+    fn models_for_server<'a>(&'a self, server: &'a ServerSettings) -> &'a [AvailableModel] {
+        if server.available_models.is_empty() {
+            self.listed_models
+                .get(&server.api_url)
+                .map_or(&[][..], |models| models.as_slice())
+        } else {
+            &server.available_models
+        }
+    }
+
+    /// Reads the API keys of the servers and lists the models of those without configured
+    /// models, which also tells whether they are running.
+    fn fetch_models(&mut self, cx: &mut Context<Self>) -> Task<()> {
+        let credentials_provider = <dyn CredentialsProvider>::global(cx);
+        let http_client = self.http_client.clone();
+        let servers = servers(cx).to_vec();
+        cx.spawn(async move |this, cx| {
+            let mut api_keys = HashMap::default();
+            let mut listed_models = HashMap::default();
+            let mut errors = HashMap::default();
+            for server in servers {
+                let api_key = credentials_provider
+                    .read_credentials(&server.api_url, &cx)
+                    .await
+                    .log_err()
+                    .flatten()
+                    .and_then(|(_, api_key)| String::from_utf8(api_key).log_err());
+                if !server.available_models.is_empty() {
+                    if let Some(api_key) = api_key {
+                        api_keys.insert(server.api_url, api_key);
+                    }
+                    continue;
+                }
+
+                let models = list_models(
+                    http_client.as_ref(),
+                    &server.api_url,
+                    api_key.as_deref().unwrap_or_default(),
+                )
+                .await;
+                match models {
+                    Ok(models) => {
+                        let mut models = models
+                            .into_iter()
+                            .map(|model| AvailableModel {
+                                name: model.id,
+                                display_name: None,
+                                max_tokens: DEFAULT_MAX_TOKENS,
+                                max_output_tokens: None,
+                                supports_tools: None,
+                                supports_images: None,
+                            })
+                            .collect::<Vec<_>>();
+                        models.sort_by(|a, b| a.name.cmp(&b.name));
+                        listed_models.insert(server.api_url.clone(), models);
+                    }
+                    Err(error) => {
+                        errors.insert(server.api_url.clone(), error.to_string().into());
+                    }
+                }
+                if let Some(api_key) = api_key {
+                    api_keys.insert(server.api_url, api_key);
+                }
+            }
+
+            this.update(cx, |this, cx| {
+                this.api_keys = api_keys;
+                this.listed_models = listed_models;
+                this.errors = errors;
+                cx.notify();
+            })
+            .ok();
+        })
+    }
+
+    fn restart_fetch_models_task(&mut self, cx: &mut Context<Self>) {
+        let task = self.fetch_models(cx);
+        self._fetch_models_task = Some(task);
+    }
+
+    fn authenticate(&mut self, cx: &mut Context<Self>) -> Task<Result<(), AuthenticateError>> {
+        if self.is_authenticated(cx) {
+            return Task::ready(Ok(()));
+        }
+
+        let fetch_models = self.fetch_models(cx);
+        cx.spawn(async move |this, cx| {
+            fetch_models.await;
+            if this.update(cx, |this, cx| this.is_authenticated(cx))? {
+                Ok(())
+            } else {
+                Err(anyhow!("None of the configured {PROVIDER_NAME} servers has models").into())
+            }
+        })
+    }
+
+    fn set_api_key(
+        &mut self,
+        api_url: String,
+        api_key: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let credentials_provider = <dyn CredentialsProvider>::global(cx);
+        cx.spawn(async move |this, cx| {
+            credentials_provider
+                .write_credentials(&api_url, "Bearer", api_key.as_bytes(), &cx)
+                .await?;
+            this.update(cx, |this, cx| this.restart_fetch_models_task(cx))
+        })
+    }
+
+    fn reset_api_key(&mut self, api_url: String, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let credentials_provider = <dyn CredentialsProvider>::global(cx);
+        cx.spawn(async move |this, cx| {
+            credentials_provider
+                .delete_credentials(&api_url, &cx)
+                .await
+                .log_err();
+            this.update(cx, |this, cx| {
+                this.api_keys.remove(&api_url);
+                this.restart_fetch_models_task(cx);
+            })
+        })
+    }
+}
+
+fn servers(cx: &App) -> &[ServerSettings] {
+    &AllLanguageModelSettings::get_global(cx)
+        .openai_compatible
+        .servers
+}
+
+impl OpenAiCompatibleLanguageModelProvider {
+    pub fn new(http_client: Arc<dyn HttpClient>, cx: &mut App) -> Self {
+        let state = cx.new(|cx| {
+            let subscription = cx.observe_global::<SettingsStore>({
+                let mut settings = AllLanguageModelSettings::get_global(cx)
+                    .openai_compatible
+                    .clone();
+                move |this: &mut State, cx| {
+                    let new_settings = &AllLanguageModelSettings::get_global(cx).openai_compatible;
+                    if &settings != new_settings {
+                        settings = new_settings.clone();
+                        this.restart_fetch_models_task(cx);
+                        cx.notify();
+                    }
+                }
+            });
+
+            State {
+                http_client: http_client.clone(),
+                api_keys: HashMap::default(),
+                listed_models: HashMap::default(),
+                errors: HashMap::default(),
+                _fetch_models_task: None,
+                _subscription: subscription,
+            }
+        });
+        state.update(cx, |state, cx| state.restart_fetch_models_task(cx));
+
+        Self { http_client, state }
+    }
+}
+
+impl LanguageModelProviderState for OpenAiCompatibleLanguageModelProvider {
+    type ObservableEntity = State;
+
+    fn observable_entity(&self) -> Option<Entity<Self::ObservableEntity>> {
+        Some(self.state.clone())
+    }
+}
+
+impl LanguageModelProvider for OpenAiCompatibleLanguageModelProvider {
+    fn id(&self) -> LanguageModelProviderId {
+        LanguageModelProviderId(PROVIDER_ID.into())
+    }
+
+    fn name(&self) -> LanguageModelProviderName {
+        LanguageModelProviderName(PROVIDER_NAME.into())
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::AiOpenAi
+    }
+
+    fn default_model(&self, _: &App) -> Option<Arc<dyn LanguageModel>> {
+        // Like for other local providers, selecting a model could make the server load it.
+        None
+    }
+
+    fn default_fast_model(&self, _: &App) -> Option<Arc<dyn LanguageModel>> {
+        None
+    }
+
+    fn provided_models(&self, cx: &App) -> Vec<Arc<dyn LanguageModel>> {
+        let state = self.state.read(cx);
+        let mut models = BTreeMap::default();
+        for server in servers(cx) {
+            for model in state.models_for_server(server) {
+                let id = format!("{}:{}", server.name, model.name);
+                models.insert(
+                    id.clone(),
+                    Arc::new(OpenAiCompatibleLanguageModel {
+                        id: LanguageModelId::from(id),
+                        api_url: server.api_url.clone(),
+                        server_name: server.name.clone().into(),
+                        model: model.clone(),
+                        state: self.state.clone(),
+                        http_client: self.http_client.clone(),
+                        request_limiter: RateLimiter::new(4),
+                    }) as Arc<dyn LanguageModel>,
+                );
+            }
+        }
+        models.into_values().collect()
+    }
+
+    fn is_authenticated(&self, cx: &App) -> bool {
+        self.state.read(cx).is_authenticated(cx)
+    }
+
+    fn authenticate(&self, cx: &mut App) -> Task<Result<(), AuthenticateError>> {
+        self.state.update(cx, |state, cx| state.authenticate(cx))
+    }
+
+    fn configuration_view(&self, _window: &mut Window, cx: &mut App) -> AnyView {
+        cx.new(|cx| ConfigurationView::new(self.state.clone(), cx))
+            .into()
+    }
+
+    fn reset_credentials(&self, cx: &mut App) -> Task<Result<()>> {
+        let api_urls = servers(cx)
+            .iter()
+            .map(|server| server.api_url.clone())
+            .collect::<Vec<_>>();
+        let resets = self.state.update(cx, |state, cx| {
+            api_urls
+                .into_iter()
+                .map(|api_url| state.reset_api_key(api_url, cx))
+                .collect::<Vec<_>>()
+        });
+        cx.background_spawn(async move {
+            for reset in resets {
+                reset.await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+pub struct OpenAiCompatibleLanguageModel {
+    id: LanguageModelId,
+    api_url: String,
+    server_name: SharedString,
+    model: AvailableModel,
+    state: Entity<State>,
+    http_client: Arc<dyn HttpClient>,
+    request_limiter: RateLimiter,
+}
+
+impl OpenAiCompatibleLanguageModel {
+    fn open_ai_model(&self) -> open_ai::Model {
+        open_ai::Model::Custom {
+            name: self.model.name.clone(),
+            display_name: self.model.display_name.clone(),
+            max_tokens: self.model.max_tokens,
+            max_output_tokens: self.model.max_output_tokens,
+            max_completion_tokens: None,
+        }
+    }
+
+    fn stream_completion(
+        &self,
+        request: open_ai::Request,
+        cx: &AsyncApp,
+    ) -> BoxFuture<'static, Result<futures::stream::BoxStream<'static, Result<ResponseStreamEvent>>>>
+    {
+        let http_client = self.http_client.clone();
+        let api_url = self.api_url.clone();
+        let Ok(api_key) = cx.read_entity(&self.state, |state, _| {
+            state.api_keys.get(&api_url).cloned().unwrap_or_default()
+        }) else {
+            return futures::future::ready(Err(anyhow!("App state dropped"))).boxed();
+        };
+
+        let future = self.request_limiter.stream(async move {
+            let request = stream_completion(http_client.as_ref(), &api_url, &api_key, request);
+            let response = request.await?;
+            Ok(response)
+        });
+
+        async move { Ok(future.await?.boxed()) }.boxed()
+    }
+}
+
+impl LanguageModel for OpenAiCompatibleLanguageModel {
+    fn id(&self) -> LanguageModelId {
+        self.id.clone()
+    }
+
+    fn name(&self) -> LanguageModelName {
+        let name = self.model.display_name.as_ref().unwrap_or(&self.model.name);
+        LanguageModelName::from(format!("{name} ({})", self.server_name))
+    }
+
+    fn provider_id(&self) -> LanguageModelProviderId {
+        LanguageModelProviderId(PROVIDER_ID.into())
+    }
+
+    fn provider_name(&self) -> LanguageModelProviderName {
+        LanguageModelProviderName(PROVIDER_NAME.into())
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.model.supports_tools.unwrap_or(true)
+    }
+
+    fn supports_images(&self) -> bool {
+        self.model.supports_images.unwrap_or(false)
+    }
+
+    fn supports_tool_choice(&self, choice: LanguageModelToolChoice) -> bool {
+        self.supports_tools()
+            && match choice {
+                LanguageModelToolChoice::Auto => true,
+                LanguageModelToolChoice::Any => true,
+                LanguageModelToolChoice::None => true,
+            }
+    }
+
+    fn telemetry_id(&self) -> String {
+        format!("openai_compatible/{}", self.model.name)
+    }
+
+    fn max_token_count(&self) -> usize {
+        self.model.max_tokens
+    }
+
+    fn max_output_tokens(&self) -> Option<u32> {
+        self.model.max_output_tokens
+    }
+
+    fn count_tokens(
+        &self,
+        request: LanguageModelRequest,
+        cx: &App,
+    ) -> BoxFuture<'static, Result<usize>> {
+        count_open_ai_tokens(request, self.open_ai_model(), cx)
+    }
+
+    fn stream_completion(
+        &self,
+        request: LanguageModelRequest,
+        cx: &AsyncApp,
+    ) -> BoxFuture<
+        'static,
+        Result<
+            futures::stream::BoxStream<
+                'static,
+                Result<LanguageModelCompletionEvent, LanguageModelCompletionError>,
+            >,
+        >,
+    > {
+        let request = into_open_ai(request, &self.open_ai_model(), self.max_output_tokens());
+        let completions = self.stream_completion(request, cx);
+        async move {
+            let mapper = OpenAiEventMapper::new();
+            Ok(mapper.map_stream(completions.await?).boxed())
+        }
+        .boxed()
+    }
+}
+
+struct ConfigurationView {
+    state: Entity<State>,
+    /// The API key editors of the servers, by API URL.
+    api_key_editors: HashMap<String, Entity<Editor>>,
+}
+
+impl ConfigurationView {
+    fn new(state: Entity<State>, cx: &mut Context<Self>) -> Self {
+        cx.observe(&state, |_, _, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            state,
+            api_key_editors: HashMap::default(),
+        }
+    }
+
+    fn save_api_key(&mut self, api_url: String, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editor) = self.api_key_editors.get(&api_url) else {
+            return;
+        };
+        let api_key = editor.read(cx).text(cx);
+        if api_key.is_empty() {
+            return;
+        }
+        editor.update(cx, |editor, cx| editor.set_text("", window, cx));
+
+        let state = self.state.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            state
+                .update(cx, |state, cx| state.set_api_key(api_url, api_key, cx))?
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn reset_api_key(&mut self, api_url: String, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.state.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            state
+                .update(cx, |state, cx| state.reset_api_key(api_url, cx))?
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn api_key_editor(
+        &mut self,
+        api_url: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        self.api_key_editors
+            .entry(api_url.to_string())
+            .or_insert_with(|| {
+                cx.new(|cx| {
+                    let mut editor = Editor::single_line(window, cx);
+                    editor.set_placeholder_text("API key, if the server requires one", cx);
+                    editor
+                })
+            })
+            .clone()
+    }
+
+    fn render_api_key_editor(
+        &self,
+        editor: &Entity<Editor>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.ui_font.family.clone(),
+            font_features: settings.ui_font.features.clone(),
+            font_fallbacks: settings.ui_font.fallbacks.clone(),
+            font_size: rems(0.875).into(),
+            font_weight: settings.ui_font.weight,
+            font_style: FontStyle::Normal,
+            line_height: relative(1.3),
+            white_space: WhiteSpace::Normal,
+            ..Default::default()
+        };
+        EditorElement::new(
+            editor,
+            EditorStyle {
+                background: cx.theme().colors().editor_background,
+                local_player: cx.theme().players().local(),
+                text: text_style,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn render_server(
+        &mut self,
+        ix: usize,
+        server: &ServerSettings,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let state = self.state.read(cx);
+        let model_count = state.models_for_server(server).len();
+        let error = state.errors.get(&server.api_url).cloned();
+        let has_api_key = state.api_keys.contains_key(&server.api_url);
+        let (indicator_color, status): (_, SharedString) = if error.is_some() {
+            (Color::Error, "Unreachable".into())
+        } else if model_count > 0 {
+            (
+                Color::Success,
+                format!(
+                    "{model_count} {}",
+                    if model_count == 1 { "model" } else { "models" }
+                )
+                .into(),
+            )
+        } else {
+            (Color::Muted, "No models".into())
+        };
+
+        let row = v_flex().gap_1().child(
+            h_flex()
+                .justify_between()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(Indicator::dot().color(indicator_color))
+                        .child(Label::new(server.name.clone()))
+                        .child(
+                            Label::new(server.api_url.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+                .child(
+                    div()
+                        .id(("server-status", ix))
+                        .child(
+                            Label::new(status)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .when_some(error, |this, error| this.tooltip(Tooltip::text(error))),
+                ),
+        );
+
+        if has_api_key {
+            let api_url = server.api_url.clone();
+            row.child(
+                h_flex()
+                    .gap_1()
+                    .justify_between()
+                    .child(
+                        Label::new("API key configured.")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Button::new(("reset-key", ix), "Reset Key")
+                            .label_size(LabelSize::Small)
+                            .icon(Some(IconName::Trash))
+                            .icon_size(IconSize::Small)
+                            .icon_position(IconPosition::Start)
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.reset_api_key(api_url.clone(), window, cx)
+                            })),
+                    ),
+            )
+            .into_any_element()
+        } else {
+            let editor = self.api_key_editor(&server.api_url, window, cx);
+            let api_url = server.api_url.clone();
+            row.child(
+                h_flex()
+                    .w_full()
+                    .px_2()
+                    .py_1()
+                    .bg(cx.theme().colors().editor_background)
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_sm()
+                    .on_action(cx.listener(move |this, _: &menu::Confirm, window, cx| {
+                        this.save_api_key(api_url.clone(), window, cx)
+                    }))
+                    .child(self.render_api_key_editor(&editor, cx)),
+            )
+            .into_any_element()
+        }
+    }
+}
+
+impl Render for ConfigurationView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let servers = servers(cx).to_vec();
+        if servers.is_empty() {
+            return v_flex()
+                .gap_1()
+                .child(Label::new(
+                    "Use models served by llama.cpp, vLLM, LM Studio or any other server implementing the OpenAI API.",
+                ))
+                .child(
+                    List::new()
+                        .child(InstructionListItem::text_only(
+                            "Add the server to `language_models.openai_compatible.servers` in your settings, with its name and API URL",
+                        ))
+                        .child(InstructionListItem::text_only(
+                            "List its models under `available_models`, or leave them out to use the ones the server lists",
+                        )),
+                )
+                .into_any();
+        }
+
+        v_flex()
+            .gap_2()
+            .children(
+                servers
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, server)| self.render_server(ix, server, window, cx)),
+            )
+            .into_any()
+    }
+}
@@ -3,7 +3,7 @@ use gpui::{
     Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, Window, div,
 };
 use language::LanguageName;
-use ui::{Button, ButtonCommon, Clickable, FluentBuilder, LabelSize, Tooltip};
+use ui::{Button, ButtonCommon, Clickable, FluentBuilder, LabelSize, SharedString, Tooltip};
 use workspace::{StatusItemView, Workspace, item::ItemHandle};
 
 use crate::{LanguageSelector, Toggle};
@@ -82,4 +82,8 @@ impl StatusItemView for ActiveBufferLanguage {
 
         cx.notify();
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("language".into())
+    }
 }
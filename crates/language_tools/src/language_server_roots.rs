@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use editor::Editor;
+use gpui::{App, AppContext as _, Context, Window, actions};
+use workspace::notifications::simple_message_notification::MessageNotification;
+use workspace::{Workspace, notifications::NotificationId};
+
+actions!(dev, [ShowLanguageServerRoots]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(show_language_server_roots);
+    })
+    .detach();
+}
+
+struct LanguageServerRoots;
+
+/// Lists the language servers that the active buffer is attached to, and the subproject root of
+/// each of them, like the Cargo workspace or the Go module that the buffer belongs to.
+fn show_language_server_roots(
+    workspace: &mut Workspace,
+    _: &ShowLanguageServerRoots,
+    _: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(buffer) = workspace
+        .active_item_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+    else {
+        return;
+    };
+    let lsp_store = workspace.project().read(cx).lsp_store();
+    let roots = lsp_store.update(cx, |lsp_store, cx| {
+        buffer.update(cx, |buffer, cx| {
+            lsp_store.language_server_roots_for_local_buffer(buffer, cx)
+        })
+    });
+
+    let file_name = buffer
+        .read(cx)
+        .file()
+        .map(|file| file.file_name(cx).to_string_lossy().into_owned())
+        .unwrap_or_else(|| "This buffer".to_string());
+    let message = if roots.is_empty() {
+        format!("{file_name} isn't attached to any language servers")
+    } else {
+        let project = workspace.project().read(cx);
+        let mut message = format!("{file_name} is attached to:");
+        for (server_id, root) in roots {
+            let server_name = project
+                .language_server_statuses(cx)
+                .find(|(id, _)| *id == server_id)
+                .map(|(_, status)| status.name.clone())
+                .unwrap_or_else(|| format!("language server {server_id}"));
+            let root_name = project
+                .worktree_for_id(root.worktree_id, cx)
+                .map(|worktree| Path::new(worktree.read(cx).root_name()).join(&root.path))
+                .unwrap_or_else(|| root.path.to_path_buf());
+            message.push_str(&format!("\n{server_name}, for {}", root_name.display()));
+        }
+        message
+    };
+
+    workspace.show_notification(NotificationId::unique::<LanguageServerRoots>(), cx, |cx| {
+        cx.new(|cx| MessageNotification::new(message, cx))
+    });
+}
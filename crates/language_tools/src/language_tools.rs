@@ -1,4 +1,5 @@
 mod key_context_view;
+mod language_server_roots;
 mod lsp_log;
 mod syntax_tree_view;
 
@@ -14,4 +15,5 @@ pub fn init(cx: &mut App) {
     lsp_log::init(cx);
     syntax_tree_view::init(cx);
     key_context_view::init(cx);
+    language_server_roots::init(cx);
 }
@@ -421,7 +421,7 @@ impl LogStore {
             log_lines,
             id,
             LogMessage {
-                message: message.trim_end().to_string(),
+                message: redaction::redact(message.trim_end()).into_owned(),
                 typ,
             },
             language_server_state.log_level,
@@ -444,7 +444,7 @@ impl LogStore {
             log_lines,
             id,
             TraceMessage {
-                message: message.trim_end().to_string(),
+                message: redaction::redact(message.trim_end()).into_owned(),
             },
             (),
             LogKind::Trace,
@@ -573,7 +573,7 @@ impl LogStore {
         while rpc_log_lines.len() >= MAX_STORED_LOG_ENTRIES {
             rpc_log_lines.pop_front();
         }
-        let message = message.trim();
+        let message = redaction::redact(message.trim());
         rpc_log_lines.push_back(RpcMessage {
             message: message.to_string(),
         });
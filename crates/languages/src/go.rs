@@ -2,7 +2,7 @@ use anyhow::{Context as _, Result};
 use async_trait::async_trait;
 use collections::HashMap;
 use futures::StreamExt;
-use gpui::{App, AsyncApp, Task};
+use gpui::{App, AsyncApp, SharedString, Task};
 use http_client::github::latest_github_release;
 pub use language::*;
 use lsp::{LanguageServerBinary, LanguageServerName};
@@ -15,7 +15,7 @@ use std::{
     borrow::Cow,
     ffi::{OsStr, OsString},
     ops::Range,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Output,
     str,
     sync::{
@@ -37,6 +37,42 @@ impl GoLspAdapter {
     const SERVER_NAME: LanguageServerName = LanguageServerName::new_static("gopls");
 }
 
+pub(crate) struct GoModManifestProvider;
+
+impl ManifestProvider for GoModManifestProvider {
+    fn name(&self) -> ManifestName {
+        SharedString::new_static("go.mod").into()
+    }
+
+    fn search(
+        &self,
+        ManifestQuery {
+            path,
+            depth,
+            delegate,
+        }: ManifestQuery,
+    ) -> Option<Arc<Path>> {
+        // The modules of a workspace are built together, so its `go.work` takes precedence over
+        // their `go.mod` files.
+        let mut outermost_go_work = None;
+        let mut innermost_go_mod = None;
+        for path in path.ancestors().take(depth) {
+            if delegate.exists(&path.join("go.work"), Some(false)) {
+                outermost_go_work = Some(Arc::from(path));
+            }
+            if innermost_go_mod.is_none() && delegate.exists(&path.join("go.mod"), Some(false)) {
+                innermost_go_mod = Some(Arc::from(path));
+            }
+        }
+
+        outermost_go_work.or(innermost_go_mod)
+    }
+
+    fn allows_nested_roots(&self) -> bool {
+        true
+    }
+}
+
 static VERSION_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\d+\.\d+\.\d+").expect("Failed to create VERSION_REGEX"));
 
@@ -56,6 +92,14 @@ impl super::LspAdapter for GoLspAdapter {
         Self::SERVER_NAME.clone()
     }
 
+    fn manifest_name(&self) -> Option<ManifestName> {
+        Some(SharedString::new_static("go.mod").into())
+    }
+
+    fn attach_kind(&self) -> Attach {
+        Attach::InstancePerRoot
+    }
+
     async fn fetch_latest_server_version(
         &self,
         delegate: &dyn LspAdapterDelegate,
@@ -654,6 +698,39 @@ mod tests {
     use gpui::Hsla;
     use theme::SyntaxTheme;
 
+    #[test]
+    fn test_go_mod_manifest_search() {
+        let files = [
+            "go.mod",
+            "tools/go.mod",
+            "services/go.work",
+            "services/api/go.mod",
+            "services/worker/go.mod",
+        ];
+        let search = |path| crate::search_manifest(&GoModManifestProvider, &files, path);
+
+        assert_eq!(search("main.go").as_deref(), Some(Path::new("")));
+        assert_eq!(search("internal/db/db.go").as_deref(), Some(Path::new("")));
+        // Modules in another module's directory are roots of their own.
+        assert_eq!(
+            search("tools/gen/main.go").as_deref(),
+            Some(Path::new("tools"))
+        );
+        // The modules of a workspace share its root.
+        assert_eq!(
+            search("services/api/handlers/user.go").as_deref(),
+            Some(Path::new("services"))
+        );
+        assert_eq!(
+            search("services/worker/main.go").as_deref(),
+            Some(Path::new("services"))
+        );
+        assert_eq!(
+            crate::search_manifest(&GoModManifestProvider, &[], "main.go"),
+            None
+        );
+    }
+
     #[gpui::test]
     async fn test_go_label_for_completion() {
         let adapter = Arc::new(GoLspAdapter);
@@ -4,15 +4,23 @@ use async_tar::Archive;
 use async_trait::async_trait;
 use collections::HashMap;
 use dap::DapRegistry;
-use futures::StreamExt;
+use futures::{AsyncReadExt as _, StreamExt};
 use gpui::{App, AsyncApp};
-use http_client::github::{GitHubLspBinaryVersion, latest_github_release};
+use http_client::{
+    HttpClient,
+    github::{GitHubLspBinaryVersion, latest_github_release},
+};
 use language::{LanguageRegistry, LanguageToolchainStore, LspAdapter, LspAdapterDelegate};
 use lsp::{LanguageServerBinary, LanguageServerName};
 use node_runtime::NodeRuntime;
-use project::{ContextProviderWithTasks, Fs, lsp_store::language_server_settings};
+use project::{
+    ContextProviderWithTasks, Fs,
+    lsp_store::language_server_settings,
+    project_settings::{JsonSchemaSettings, ProjectSettings},
+};
+use serde::Deserialize;
 use serde_json::{Value, json};
-use settings::{KeymapFile, SettingsJsonSchemaParams, SettingsStore};
+use settings::{KeymapFile, Settings, SettingsJsonSchemaParams, SettingsLocation, SettingsStore};
 use smol::{
     fs::{self},
     io::BufReader,
@@ -36,6 +44,8 @@ const SERVER_PATH: &str =
 const TSCONFIG_SCHEMA: &str = include_str!("json/schemas/tsconfig.json");
 const PACKAGE_JSON_SCHEMA: &str = include_str!("json/schemas/package.json");
 
+const SCHEMASTORE_CATALOG_URL: &str = "https://www.schemastore.org/api/json/catalog.json";
+
 pub(super) fn json_task_context() -> ContextProviderWithTasks {
     ContextProviderWithTasks::new(TaskTemplates(vec![
         TaskTemplate {
@@ -63,6 +73,7 @@ pub struct JsonLspAdapter {
     node: NodeRuntime,
     languages: Arc<LanguageRegistry>,
     workspace_config: RwLock<Option<Value>>,
+    schemastore_schemas: RwLock<Option<Vec<Value>>>,
 }
 
 impl JsonLspAdapter {
@@ -73,6 +84,7 @@ impl JsonLspAdapter {
             node,
             languages,
             workspace_config: Default::default(),
+            schemastore_schemas: Default::default(),
         }
     }
 
@@ -192,6 +204,115 @@ impl JsonLspAdapter {
         writer.replace(config.clone());
         return Ok(config);
     }
+
+    /// The schemas of the SchemaStore catalog. The catalog is downloaded once per session, and
+    /// the copy from the last download is used when that fails.
+    async fn get_or_init_schemastore_schemas(
+        &self,
+        fs: &dyn Fs,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Vec<Value> {
+        {
+            let reader = self.schemastore_schemas.read().await;
+            if let Some(schemas) = reader.as_ref() {
+                return schemas.clone();
+            }
+        }
+        let mut writer = self.schemastore_schemas.write().await;
+
+        let cache_dir = paths::languages_dir().join(self.name().0.as_ref());
+        let cache_path = cache_dir.join("schemastore-catalog.json");
+        let catalog = match download_schemastore_catalog(http_client).await {
+            Ok(catalog) => {
+                if fs.create_dir(&cache_dir).await.log_err().is_some() {
+                    fs.atomic_write(cache_path, catalog.clone()).await.log_err();
+                }
+                Some(catalog)
+            }
+            Err(error) => {
+                log::warn!("failed to download the SchemaStore catalog: {error:#}");
+                fs.load(&cache_path).await.ok()
+            }
+        };
+        let schemas = catalog
+            .and_then(|catalog| schemastore_schemas(&catalog).log_err())
+            .unwrap_or_default();
+        writer.replace(schemas.clone());
+        schemas
+    }
+}
+
+async fn download_schemastore_catalog(http_client: Arc<dyn HttpClient>) -> Result<String> {
+    let mut response = http_client
+        .get(SCHEMASTORE_CATALOG_URL, Default::default(), true)
+        .await
+        .context("downloading the SchemaStore catalog")?;
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "status error {}, response: {body:?}",
+        response.status().as_u16()
+    );
+    Ok(body)
+}
+
+/// Turns the SchemaStore catalog into the schema associations of json-language-server, leaving
+/// out the schemas of the files that Zed already has a schema for.
+fn schemastore_schemas(catalog: &str) -> Result<Vec<Value>> {
+    #[derive(Deserialize)]
+    struct Catalog {
+        schemas: Vec<CatalogSchema>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CatalogSchema {
+        url: String,
+        #[serde(default)]
+        file_match: Vec<String>,
+    }
+
+    const BUILT_IN_FILE_MATCHES: &[&str] = &["tsconfig.json", "package.json"];
+
+    let catalog = serde_json::from_str::<Catalog>(catalog).context("parsing the catalog")?;
+    Ok(catalog
+        .schemas
+        .into_iter()
+        .filter(|schema| {
+            !schema.file_match.is_empty()
+                && !schema
+                    .file_match
+                    .iter()
+                    .any(|file_match| BUILT_IN_FILE_MATCHES.contains(&file_match.as_str()))
+        })
+        .map(|schema| json!({ "fileMatch": schema.file_match, "url": schema.url }))
+        .collect())
+}
+
+/// The schema settings that apply to the files of a language server.
+pub(super) fn json_schema_settings(
+    delegate: &dyn LspAdapterDelegate,
+    cx: &App,
+) -> JsonSchemaSettings {
+    let location = SettingsLocation {
+        worktree_id: delegate.worktree_id(),
+        path: delegate.subproject_path(),
+    };
+    ProjectSettings::get(Some(location), cx)
+        .json_schemas
+        .clone()
+}
+
+/// The URL of a schema from the settings, which is either a URL or a path relative to the
+/// worktree's root.
+pub(super) fn schema_url(schema: &str, worktree_root: &Path) -> String {
+    if schema.contains("://") {
+        return schema.to_string();
+    }
+    lsp::Url::from_file_path(worktree_root.join(schema))
+        .map(|url| url.to_string())
+        .unwrap_or_else(|()| schema.to_string())
 }
 
 #[cfg(debug_assertions)]
@@ -307,13 +428,34 @@ impl LspAdapter for JsonLspAdapter {
 
     async fn workspace_configuration(
         self: Arc<Self>,
-        _: &dyn Fs,
+        fs: &dyn Fs,
         delegate: &Arc<dyn LspAdapterDelegate>,
         _: Arc<dyn LanguageToolchainStore>,
         cx: &mut AsyncApp,
     ) -> Result<Value> {
         let mut config = self.get_or_init_workspace_config(cx).await?;
 
+        let schema_settings = cx.update(|cx| json_schema_settings(delegate.as_ref(), cx))?;
+        let mut extra_schemas = Vec::new();
+        if schema_settings.schemastore {
+            extra_schemas.extend(
+                self.get_or_init_schemastore_schemas(fs, delegate.http_client())
+                    .await,
+            );
+        }
+        extra_schemas.extend(schema_settings.associations.iter().map(|association| {
+            json!({
+                "fileMatch": association.file_match,
+                "url": schema_url(&association.schema, delegate.worktree_root_path()),
+            })
+        }));
+        if let Some(schemas) = config
+            .pointer_mut("/json/schemas")
+            .and_then(Value::as_array_mut)
+        {
+            schemas.extend(extra_schemas);
+        }
+
         let project_options = cx.update(|cx| {
             language_server_settings(delegate.as_ref(), &self.name(), cx)
                 .and_then(|s| s.settings.clone())
@@ -517,3 +659,50 @@ async fn get_cached_version_server_binary(container_dir: PathBuf) -> Option<Lang
     .await
     .log_err()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schemastore_schemas() {
+        let catalog = r#"{
+            "schemas": [
+                {
+                    "name": ".eslintrc",
+                    "fileMatch": [".eslintrc", ".eslintrc.json"],
+                    "url": "https://json.schemastore.org/eslintrc.json"
+                },
+                {
+                    "name": "package.json",
+                    "fileMatch": ["package.json"],
+                    "url": "https://json.schemastore.org/package.json"
+                },
+                {
+                    "name": "Without files",
+                    "url": "https://json.schemastore.org/without-files.json"
+                }
+            ]
+        }"#;
+        assert_eq!(
+            schemastore_schemas(catalog).unwrap(),
+            [json!({
+                "fileMatch": [".eslintrc", ".eslintrc.json"],
+                "url": "https://json.schemastore.org/eslintrc.json",
+            })]
+        );
+    }
+
+    #[test]
+    fn test_schema_url() {
+        assert_eq!(
+            schema_url("https://example.com/schema.json", Path::new("/root")),
+            "https://example.com/schema.json"
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            schema_url("schemas/config.json", Path::new("/project")),
+            "file:///project/schemas/config.json"
+        );
+    }
+}
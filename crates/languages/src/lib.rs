@@ -1,4 +1,5 @@
 use anyhow::Context as _;
+use go::GoModManifestProvider;
 use gpui::{App, UpdateGlobal};
 use json::json_task_context;
 use node_runtime::NodeRuntime;
@@ -8,6 +9,7 @@ use rust_embed::RustEmbed;
 use settings::SettingsStore;
 use smol::stream::StreamExt;
 use std::{str, sync::Arc};
+use typescript::PackageJsonManifestProvider;
 use util::{ResultExt, asset_str};
 
 pub use language::*;
@@ -303,8 +305,10 @@ pub fn init(languages: Arc<LanguageRegistry>, node: NodeRuntime, cx: &mut App) {
         anyhow::Ok(())
     })
     .detach();
-    let manifest_providers: [Arc<dyn ManifestProvider>; 2] = [
+    let manifest_providers: [Arc<dyn ManifestProvider>; 4] = [
         Arc::from(CargoManifestProvider),
+        Arc::from(GoModManifestProvider),
+        Arc::from(PackageJsonManifestProvider),
         Arc::from(PyprojectTomlManifestProvider),
     ];
     for provider in manifest_providers {
@@ -356,6 +360,37 @@ pub fn language(name: &str, grammar: tree_sitter::Language) -> Arc<Language> {
     )
 }
 
+/// Searches for the manifest of the file at `path` in a worktree that has the given files.
+#[cfg(test)]
+fn search_manifest(
+    provider: &dyn ManifestProvider,
+    files: &[&str],
+    path: &str,
+) -> Option<Arc<std::path::Path>> {
+    use std::path::{Path, PathBuf};
+
+    struct FakeManifestDelegate(Vec<PathBuf>);
+
+    impl ManifestDelegate for FakeManifestDelegate {
+        fn worktree_id(&self) -> settings::WorktreeId {
+            settings::WorktreeId::from_usize(0)
+        }
+
+        fn exists(&self, path: &Path, is_dir: Option<bool>) -> bool {
+            is_dir != Some(true) && self.0.iter().any(|file| file == path)
+        }
+    }
+
+    let path: Arc<Path> = Path::new(path).into();
+    provider.search(ManifestQuery {
+        depth: path.components().count() + 1,
+        path,
+        delegate: Arc::new(FakeManifestDelegate(
+            files.iter().map(PathBuf::from).collect(),
+        )),
+    })
+}
+
 fn load_config(name: &str) -> LanguageConfig {
     let config_toml = String::from_utf8(
         LanguageDir::get(&format!("{}/config.toml", name))
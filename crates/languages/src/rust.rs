@@ -89,16 +89,26 @@ impl ManifestProvider for CargoManifestProvider {
             delegate,
         }: ManifestQuery,
     ) -> Option<Arc<Path>> {
+        // The members of a workspace share its `Cargo.lock`, so a `Cargo.toml` with one next to it
+        // is the root of a workspace of its own, even in another workspace's directory, like the
+        // workspace of a repository's tools or examples.
         let mut outermost_cargo_toml = None;
         for path in path.ancestors().take(depth) {
             let p = path.join("Cargo.toml");
             if delegate.exists(&p, Some(false)) {
+                if delegate.exists(&path.join("Cargo.lock"), Some(false)) {
+                    return Some(Arc::from(path));
+                }
                 outermost_cargo_toml = Some(Arc::from(path));
             }
         }
 
         outermost_cargo_toml
     }
+
+    fn allows_nested_roots(&self) -> bool {
+        true
+    }
 }
 
 #[async_trait(?Send)]
@@ -1018,6 +1028,42 @@ mod tests {
     use theme::SyntaxTheme;
     use util::path;
 
+    #[test]
+    fn test_cargo_manifest_search() {
+        let files = [
+            "Cargo.toml",
+            "Cargo.lock",
+            "crates/app/Cargo.toml",
+            "tools/Cargo.toml",
+            "tools/Cargo.lock",
+            "tools/xtask/Cargo.toml",
+            "examples/demo/Cargo.toml",
+        ];
+        let search = |path| crate::search_manifest(&CargoManifestProvider, &files, path);
+
+        assert_eq!(
+            search("crates/app/src/main.rs").as_deref(),
+            Some(Path::new(""))
+        );
+        assert_eq!(
+            search("examples/demo/main.rs").as_deref(),
+            Some(Path::new(""))
+        );
+        // A workspace with its own `Cargo.lock` is a root of its own.
+        assert_eq!(
+            search("tools/xtask/src/main.rs").as_deref(),
+            Some(Path::new("tools"))
+        );
+
+        // Without `Cargo.lock` files, the outermost `Cargo.toml` is the root.
+        let files = ["Cargo.toml", "crates/app/Cargo.toml"];
+        assert_eq!(
+            crate::search_manifest(&CargoManifestProvider, &files, "crates/app/src/lib.rs")
+                .as_deref(),
+            Some(Path::new(""))
+        );
+    }
+
     #[gpui::test]
     async fn test_process_rust_diagnostics() {
         let mut params = lsp::PublishDiagnosticsParams {
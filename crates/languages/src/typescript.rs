@@ -4,10 +4,11 @@ use async_tar::Archive;
 use async_trait::async_trait;
 use chrono::{DateTime, Local};
 use collections::HashMap;
-use gpui::{App, AppContext, AsyncApp, Task};
+use gpui::{App, AppContext, AsyncApp, SharedString, Task};
 use http_client::github::{AssetKind, GitHubLspBinaryVersion, build_asset_url};
 use language::{
-    ContextLocation, ContextProvider, File, LanguageToolchainStore, LspAdapter, LspAdapterDelegate,
+    Attach, ContextLocation, ContextProvider, File, LanguageToolchainStore, LspAdapter,
+    LspAdapterDelegate, ManifestName, ManifestProvider, ManifestQuery,
 };
 use lsp::{CodeActionKind, LanguageServerBinary, LanguageServerName};
 use node_runtime::NodeRuntime;
@@ -467,12 +468,58 @@ struct TypeScriptVersions {
     server_version: String,
 }
 
+pub(crate) const PACKAGE_JSON_MANIFEST: &str = "package.json";
+
+pub(crate) struct PackageJsonManifestProvider;
+
+impl ManifestProvider for PackageJsonManifestProvider {
+    fn name(&self) -> ManifestName {
+        SharedString::new_static(PACKAGE_JSON_MANIFEST).into()
+    }
+
+    fn search(
+        &self,
+        ManifestQuery {
+            path,
+            depth,
+            delegate,
+        }: ManifestQuery,
+    ) -> Option<Arc<Path>> {
+        // A pnpm workspace can be inside of a directory with a `package.json`, like the package
+        // that holds a monorepo's tooling, so it takes precedence.
+        let mut outermost_pnpm_workspace = None;
+        let mut outermost_package_json = None;
+        for path in path.ancestors().take(depth) {
+            if delegate.exists(&path.join("pnpm-workspace.yaml"), Some(false)) {
+                outermost_pnpm_workspace = Some(Arc::from(path));
+            }
+            if delegate.exists(&path.join("package.json"), Some(false)) {
+                outermost_package_json = Some(Arc::from(path));
+            }
+        }
+
+        outermost_pnpm_workspace.or(outermost_package_json)
+    }
+
+    fn allows_nested_roots(&self) -> bool {
+        true
+    }
+}
+
 #[async_trait(?Send)]
 impl LspAdapter for TypeScriptLspAdapter {
     fn name(&self) -> LanguageServerName {
         Self::SERVER_NAME.clone()
     }
 
+    fn manifest_name(&self) -> Option<ManifestName> {
+        Some(SharedString::new_static(PACKAGE_JSON_MANIFEST).into())
+    }
+
+    fn attach_kind(&self) -> Attach {
+        Attach::InstancePerRoot
+    }
+
     async fn fetch_latest_server_version(
         &self,
         _: &dyn LspAdapterDelegate,
@@ -930,9 +977,42 @@ async fn handle_symlink(src_dir: PathBuf, dest_dir: PathBuf) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use gpui::{AppContext as _, TestAppContext};
     use unindent::Unindent;
 
+    use super::PackageJsonManifestProvider;
+
+    #[test]
+    fn test_package_json_manifest_search() {
+        let files = [
+            "package.json",
+            "web/package.json",
+            "web/pnpm-workspace.yaml",
+            "web/packages/ui/package.json",
+            "scripts/package.json",
+        ];
+        let search = |path| crate::search_manifest(&PackageJsonManifestProvider, &files, path);
+
+        assert_eq!(search("index.ts").as_deref(), Some(Path::new("")));
+        // Packages without a pnpm workspace belong to the outermost package.
+        assert_eq!(search("scripts/build.ts").as_deref(), Some(Path::new("")));
+        // A pnpm workspace is a root of its own, and its packages share it.
+        assert_eq!(
+            search("web/packages/ui/src/button.tsx").as_deref(),
+            Some(Path::new("web"))
+        );
+        assert_eq!(
+            search("web/vite.config.ts").as_deref(),
+            Some(Path::new("web"))
+        );
+        assert_eq!(
+            crate::search_manifest(&PackageJsonManifestProvider, &[], "index.ts"),
+            None
+        );
+    }
+
     #[gpui::test]
     async fn test_outline(cx: &mut TestAppContext) {
         let language = crate::language(
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use collections::HashMap;
-use gpui::AsyncApp;
-use language::{LanguageToolchainStore, LspAdapter, LspAdapterDelegate};
+use gpui::{AsyncApp, SharedString};
+use language::{Attach, LanguageToolchainStore, LspAdapter, LspAdapterDelegate, ManifestName};
 use lsp::{CodeActionKind, LanguageServerBinary, LanguageServerName};
 use node_runtime::NodeRuntime;
 use project::{Fs, lsp_store::language_server_settings};
@@ -15,6 +15,8 @@ use std::{
 };
 use util::{ResultExt, maybe, merge_json_value_into};
 
+use crate::typescript::PACKAGE_JSON_MANIFEST;
+
 fn typescript_server_binary_arguments(server_path: &Path) -> Vec<OsString> {
     vec![server_path.into(), "--stdio".into()]
 }
@@ -70,6 +72,14 @@ impl LspAdapter for VtslsLspAdapter {
         SERVER_NAME.clone()
     }
 
+    fn manifest_name(&self) -> Option<ManifestName> {
+        Some(SharedString::new_static(PACKAGE_JSON_MANIFEST).into())
+    }
+
+    fn attach_kind(&self) -> Attach {
+        Attach::InstancePerRoot
+    }
+
     async fn fetch_latest_server_version(
         &self,
         _: &dyn LspAdapterDelegate,
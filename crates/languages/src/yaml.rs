@@ -7,7 +7,7 @@ use language::{
 };
 use lsp::{LanguageServerBinary, LanguageServerName};
 use node_runtime::NodeRuntime;
-use project::{Fs, lsp_store::language_server_settings};
+use project::{Fs, lsp_store::language_server_settings, project_settings::JsonSchemaSettings};
 use serde_json::Value;
 use settings::{Settings, SettingsLocation};
 use smol::fs;
@@ -19,6 +19,8 @@ use std::{
 };
 use util::{ResultExt, maybe, merge_json_value_into};
 
+use crate::json::{json_schema_settings, schema_url};
+
 const SERVER_PATH: &str = "node_modules/yaml-language-server/bin/yaml-language-server";
 
 fn server_binary_arguments(server_path: &Path) -> Vec<OsString> {
@@ -138,15 +140,19 @@ impl LspAdapter for YamlLspAdapter {
             path: delegate.worktree_root_path(),
         };
 
-        let tab_size = cx.update(|cx| {
-            AllLanguageSettings::get(Some(location), cx)
+        let (tab_size, schema_settings) = cx.update(|cx| {
+            let tab_size = AllLanguageSettings::get(Some(location), cx)
                 .language(Some(location), Some(&"YAML".into()), cx)
-                .tab_size
+                .tab_size;
+            (tab_size, json_schema_settings(delegate.as_ref(), cx))
         })?;
 
         let mut options = serde_json::json!({
             "[yaml]": {"editor.tabSize": tab_size},
-            "yaml": {"format": {"enable": true}}
+            "yaml": {
+                "format": {"enable": true},
+                "schemas": yaml_schemas(&schema_settings, delegate.worktree_root_path()),
+            }
         });
 
         let project_options = cx.update(|cx| {
@@ -160,6 +166,22 @@ impl LspAdapter for YamlLspAdapter {
     }
 }
 
+/// The `yaml.schemas` setting of yaml-language-server, which maps the URL of each schema
+/// from the settings to the globs of the files it applies to.
+fn yaml_schemas(schema_settings: &JsonSchemaSettings, worktree_root: &Path) -> Value {
+    schema_settings
+        .associations
+        .iter()
+        .map(|association| {
+            (
+                schema_url(&association.schema, worktree_root),
+                Value::from(association.file_match.clone()),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
 async fn get_cached_server_binary(
     container_dir: PathBuf,
     node: &NodeRuntime,
@@ -188,3 +210,40 @@ async fn get_cached_server_binary(
     .await
     .log_err()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use project::project_settings::JsonSchemaAssociation;
+    use serde_json::json;
+
+    #[test]
+    fn test_yaml_schemas() {
+        assert_eq!(
+            yaml_schemas(&JsonSchemaSettings::default(), Path::new("/project")),
+            json!({})
+        );
+
+        let schema_settings = JsonSchemaSettings {
+            schemastore: false,
+            associations: vec![
+                JsonSchemaAssociation {
+                    schema: "https://json.schemastore.org/github-workflow.json".into(),
+                    file_match: vec![".github/workflows/*.yml".into()],
+                },
+                JsonSchemaAssociation {
+                    schema: "schemas/deploy.json".into(),
+                    file_match: vec!["deploy/*.yaml".into(), "deploy/*.yml".into()],
+                },
+            ],
+        };
+        #[cfg(unix)]
+        assert_eq!(
+            yaml_schemas(&schema_settings, Path::new("/project")),
+            json!({
+                "https://json.schemastore.org/github-workflow.json": [".github/workflows/*.yml"],
+                "file:///project/schemas/deploy.json": ["deploy/*.yaml", "deploy/*.yml"],
+            })
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use std::sync::Arc;
+
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
+    Render, Styled, Task, WeakEntity, Window, actions,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{LayoutScope, ModalView, SavedLayout, Workspace};
+
+actions!(layout_selector, [Toggle]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(LayoutSelector::register).detach();
+}
+
+/// Switches between the saved workspace layouts, or saves the current one under the name
+/// typed in.
+pub struct LayoutSelector {
+    picker: Entity<Picker<LayoutSelectorDelegate>>,
+}
+
+impl LayoutSelector {
+    fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, _: &Toggle, window, cx| {
+            let layouts = workspace.saved_layouts(cx);
+            cx.spawn_in(window, async move |workspace, cx| {
+                let layouts = layouts.await?;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let weak_workspace = cx.entity().downgrade();
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        LayoutSelector::new(weak_workspace, layouts, window, cx)
+                    });
+                })
+            })
+            .detach_and_log_err(cx);
+        });
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        layouts: Vec<SavedLayout>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = LayoutSelectorDelegate::new(cx.entity().downgrade(), workspace, layouts);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for LayoutSelector {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for LayoutSelector {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for LayoutSelector {}
+impl ModalView for LayoutSelector {}
+
+enum LayoutMatch {
+    Saved(StringMatch),
+    SaveAs { name: String, scope: LayoutScope },
+}
+
+pub struct LayoutSelectorDelegate {
+    layout_selector: WeakEntity<LayoutSelector>,
+    workspace: WeakEntity<Workspace>,
+    layouts: Vec<SavedLayout>,
+    matches: Vec<LayoutMatch>,
+    selected_index: usize,
+}
+
+impl LayoutSelectorDelegate {
+    fn new(
+        layout_selector: WeakEntity<LayoutSelector>,
+        workspace: WeakEntity<Workspace>,
+        layouts: Vec<SavedLayout>,
+    ) -> Self {
+        Self {
+            layout_selector,
+            workspace,
+            layouts,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for LayoutSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Switch to a layout, or type a name to save the current one…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(layout_match) = self.matches.get(self.selected_index) {
+            self.workspace
+                .update(cx, |workspace, cx| match layout_match {
+                    LayoutMatch::Saved(string_match) => workspace
+                        .apply_layout(string_match.string.clone(), window, cx)
+                        .detach_and_log_err(cx),
+                    LayoutMatch::SaveAs { name, scope } => workspace
+                        .save_layout(name.clone(), *scope, window, cx)
+                        .detach_and_log_err(cx),
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.layout_selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .layouts
+            .iter()
+            .enumerate()
+            .map(|(id, layout)| StringMatchCandidate::new(id, &layout.name))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |this, cx| {
+            let query = query.trim().to_string();
+            let saved_matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                let is_saved = delegate.layouts.iter().any(|layout| layout.name == query);
+                delegate.matches = saved_matches.into_iter().map(LayoutMatch::Saved).collect();
+                if !query.is_empty() {
+                    // Saving over a layout replaces it, so that it can be updated from here.
+                    let scopes = [LayoutScope::Project, LayoutScope::Global];
+                    let save_matches = scopes.into_iter().map(|scope| LayoutMatch::SaveAs {
+                        name: query.clone(),
+                        scope,
+                    });
+                    if is_saved {
+                        delegate.matches.extend(save_matches);
+                    } else {
+                        delegate.matches.splice(0..0, save_matches);
+                    }
+                }
+                delegate.selected_index = 0;
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+        Some(match &self.matches[ix] {
+            LayoutMatch::Saved(string_match) => {
+                let scope = self.layouts[string_match.candidate_id].scope;
+                item.child(
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .child(HighlightedLabel::new(
+                            string_match.string.clone(),
+                            string_match.positions.clone(),
+                        ))
+                        .when(scope == LayoutScope::Global, |this| {
+                            this.child(
+                                Label::new("All Projects")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                )
+            }
+            LayoutMatch::SaveAs { name, scope } => {
+                let label = match scope {
+                    LayoutScope::Project => format!("Save Layout “{name}” for This Project"),
+                    LayoutScope::Global => format!("Save Layout “{name}” for All Projects"),
+                };
+                item.start_slot(Icon::new(IconName::Plus).color(Color::Muted))
+                    .child(Label::new(label))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::Project;
+    use workspace::{AppState, SplitDirection};
+
+    #[gpui::test]
+    async fn test_save_and_switch_layouts(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // A name that isn't saved yet offers to save the current layout first.
+        let picker = open_layout_selector(&workspace, cx);
+        cx.simulate_input("single pane");
+        picker.update(cx, |picker, _| {
+            assert!(matches!(
+                picker.delegate.matches.first(),
+                Some(LayoutMatch::SaveAs {
+                    name,
+                    scope: LayoutScope::Project,
+                }) if name == "single pane"
+            ));
+        });
+        cx.dispatch_action(menu::Confirm);
+        cx.run_until_parked();
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.active_modal::<LayoutSelector>(cx).is_none());
+            let pane = workspace.active_pane().clone();
+            workspace.split_pane(pane, SplitDirection::Right, window, cx);
+            assert_eq!(workspace.panes().len(), 2);
+        });
+
+        // A saved name is switched to first, and can still be saved over.
+        let picker = open_layout_selector(&workspace, cx);
+        cx.simulate_input("single pane");
+        picker.update(cx, |picker, _| {
+            let matches = &picker.delegate.matches;
+            assert_eq!(matches.len(), 3);
+            assert!(matches!(
+                &matches[0],
+                LayoutMatch::Saved(string_match) if string_match.string == "single pane"
+            ));
+        });
+        cx.dispatch_action(menu::Confirm);
+        cx.run_until_parked();
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.panes().len(), 1);
+        });
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            super::init(cx);
+            state
+        })
+    }
+
+    #[track_caller]
+    fn open_layout_selector(
+        workspace: &Entity<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> Entity<Picker<LayoutSelectorDelegate>> {
+        cx.dispatch_action(Toggle);
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<LayoutSelector>(cx)
+                .expect("layout selector is not open")
+                .read(cx)
+                .picker
+                .clone()
+        })
+    }
+}
@@ -11,7 +11,7 @@ mod playback;
 
 use crate::{LocalTrack, Participant, RemoteTrack, RoomEvent, TrackPublication};
 pub use playback::AudioStream;
-pub(crate) use playback::{RemoteVideoFrame, play_remote_video_track};
+pub(crate) use playback::{RemoteVideoFrame, play_remote_video_track, video_frame_aspect_ratio};
 
 #[derive(Clone, Debug)]
 pub struct RemoteVideoTrack(livekit::track::RemoteVideoTrack);
@@ -500,6 +500,12 @@ fn create_buffer_pool(
 #[cfg(target_os = "macos")]
 pub type RemoteVideoFrame = core_video::pixel_buffer::CVPixelBuffer;
 
+#[cfg(target_os = "macos")]
+pub(crate) fn video_frame_aspect_ratio(frame: &RemoteVideoFrame) -> Option<f32> {
+    let (width, height) = (frame.get_width(), frame.get_height());
+    (height > 0).then(|| width as f32 / height as f32)
+}
+
 #[cfg(target_os = "macos")]
 fn video_frame_buffer_from_webrtc(
     pool: core_video::pixel_buffer_pool::CVPixelBufferPool,
@@ -566,6 +572,12 @@ fn video_frame_buffer_from_webrtc(
 #[cfg(not(target_os = "macos"))]
 pub type RemoteVideoFrame = Arc<gpui::RenderImage>;
 
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn video_frame_aspect_ratio(frame: &RemoteVideoFrame) -> Option<f32> {
+    let size = frame.size(0);
+    (size.height.0 > 0).then(|| size.width.0 as f32 / size.height.0 as f32)
+}
+
 #[cfg(not(target_os = "macos"))]
 fn video_frame_buffer_from_webrtc(buffer: Box<dyn VideoBuffer>) -> Option<RemoteVideoFrame> {
     use gpui::RenderImage;
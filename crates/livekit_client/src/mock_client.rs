@@ -22,6 +22,12 @@ pub struct AudioStream {}
 #[cfg(not(target_os = "macos"))]
 pub type RemoteVideoFrame = std::sync::Arc<gpui::RenderImage>;
 
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn video_frame_aspect_ratio(frame: &RemoteVideoFrame) -> Option<f32> {
+    let size = frame.size(0);
+    (size.height.0 > 0).then(|| size.width.0 as f32 / size.height.0 as f32)
+}
+
 #[cfg(target_os = "macos")]
 #[derive(Clone)]
 pub(crate) struct RemoteVideoFrame {}
@@ -31,6 +37,10 @@ impl Into<gpui::SurfaceSource> for RemoteVideoFrame {
         unimplemented!()
     }
 }
+#[cfg(target_os = "macos")]
+pub(crate) fn video_frame_aspect_ratio(_frame: &RemoteVideoFrame) -> Option<f32> {
+    None
+}
 pub(crate) fn play_remote_video_track(
     _track: &crate::RemoteVideoTrack,
 ) -> impl futures::Stream<Item = RemoteVideoFrame> + use<> {
@@ -66,6 +66,11 @@ impl RemoteVideoTrackView {
         }
     }
 
+    /// The width of the latest frame over its height.
+    pub fn aspect_ratio(&self) -> Option<f32> {
+        crate::video_frame_aspect_ratio(self.latest_frame.as_ref()?)
+    }
+
     pub fn clone(&self, window: &mut Window, cx: &mut Context<Self>) -> Entity<Self> {
         cx.new(|cx| Self::new(self.track.clone(), window, cx))
     }
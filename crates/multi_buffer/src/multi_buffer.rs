@@ -6051,6 +6051,24 @@ impl MultiBufferSnapshot {
         Some((node, range))
     }
 
+    /// Returns the range to select when expanding the selection from the given range, as
+    /// configured by the language.
+    pub fn larger_syntax_node_range<T: ToOffset>(
+        &self,
+        range: Range<T>,
+    ) -> Option<MultiOrSingleBufferOffsetRange> {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+        let mut excerpt = self.excerpt_containing(range.clone())?;
+        let node_range = excerpt
+            .buffer()
+            .larger_syntax_node_range(excerpt.map_range_to_buffer(range))?;
+        Some(if excerpt.contains_buffer_range(node_range.clone()) {
+            MultiOrSingleBufferOffsetRange::Multi(excerpt.map_range_from_buffer(node_range))
+        } else {
+            MultiOrSingleBufferOffsetRange::Single(node_range)
+        })
+    }
+
     pub fn outline(&self, theme: Option<&SyntaxTheme>) -> Option<Outline<Anchor>> {
         let (excerpt_id, _, buffer) = self.as_singleton()?;
         let outline = buffer.outline(theme)?;
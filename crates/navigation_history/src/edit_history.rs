@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+
+use collections::HashMap;
+use editor::{Editor, EditorEvent, scroll::Autoscroll};
+use gpui::{Context, Entity, EntityId, Subscription, WeakEntity, Window};
+use language::{Anchor, Buffer, Point, ToPoint as _};
+use project::ProjectPath;
+use workspace::Workspace;
+
+/// How many edit locations are remembered per workspace.
+const MAX_LOCATIONS: usize = 100;
+
+/// Where an edit was made.
+#[derive(Clone)]
+struct EditLocation {
+    editor: WeakEntity<Editor>,
+    project_path: Option<ProjectPath>,
+    buffer: WeakEntity<Buffer>,
+    anchor: Anchor,
+    /// The position of the edit when it was made, for when its buffer has been closed since.
+    point: Point,
+}
+
+impl EditLocation {
+    fn point(&self, cx: &gpui::App) -> Point {
+        self.buffer.upgrade().map_or(self.point, |buffer| {
+            self.anchor.to_point(&buffer.read(cx).snapshot())
+        })
+    }
+}
+
+/// The locations of the recent edits in a workspace's editors, across files.
+pub(crate) struct EditHistory {
+    locations: VecDeque<EditLocation>,
+    /// The location that was jumped to last, so that jumping again visits the one before it.
+    /// Cleared when an edit is made.
+    visited_ix: Option<usize>,
+    editor_subscriptions: HashMap<EntityId, Subscription>,
+    _workspace_subscription: Subscription,
+}
+
+impl EditHistory {
+    pub(crate) fn new(
+        workspace: &Entity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let workspace_subscription =
+            cx.subscribe_in(workspace, window, |this, _, event, window, cx| {
+                if let workspace::Event::ItemAdded { item } = event {
+                    if let Some(editor) = item.act_as::<Editor>(cx) {
+                        this.watch_editor(&editor, window, cx);
+                    }
+                }
+            });
+        Self {
+            locations: VecDeque::new(),
+            visited_ix: None,
+            editor_subscriptions: HashMap::default(),
+            _workspace_subscription: workspace_subscription,
+        }
+    }
+
+    fn watch_editor(
+        &mut self,
+        editor: &Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let subscription =
+            cx.subscribe_in(editor, window, |this, editor, event, _, cx| match event {
+                EditorEvent::Edited { .. } => this.record_edit(editor, cx),
+                EditorEvent::Closed => {
+                    this.editor_subscriptions.remove(&editor.entity_id());
+                }
+                _ => {}
+            });
+        self.editor_subscriptions
+            .insert(editor.entity_id(), subscription);
+    }
+
+    fn record_edit(&mut self, editor: &Entity<Editor>, cx: &mut Context<Self>) {
+        let editor_ref = editor.read(cx);
+        let head = editor_ref.selections.newest_anchor().head();
+        let Some((buffer, point, _)) = editor_ref.buffer().read(cx).point_to_buffer_point(head, cx)
+        else {
+            return;
+        };
+        let location = EditLocation {
+            editor: editor.downgrade(),
+            project_path: buffer
+                .read(cx)
+                .file()
+                .map(|file| ProjectPath::from_file(file.as_ref(), cx)),
+            anchor: buffer.read(cx).anchor_before(point),
+            buffer: buffer.downgrade(),
+            point,
+        };
+
+        // Typing moves the cursor along, so nearby edits are kept as a single location.
+        if let Some(last) = self.locations.back() {
+            if last.buffer == location.buffer && last.point(cx).row.abs_diff(point.row) <= 1 {
+                self.locations.pop_back();
+            }
+        }
+        if self.locations.len() >= MAX_LOCATIONS {
+            self.locations.pop_front();
+        }
+        self.locations.push_back(location);
+        self.visited_ix = None;
+    }
+
+    /// Moves to the location of the edit made before the one visited last, skipping the ones
+    /// at the position of the cursor.
+    pub(crate) fn go_to_last_edit(
+        this: &Entity<Self>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let cursor = workspace.active_item_as::<Editor>(cx).and_then(|editor| {
+            let editor = editor.read(cx);
+            let head = editor.selections.newest_anchor().head();
+            let (buffer, point, _) = editor.buffer().read(cx).point_to_buffer_point(head, cx)?;
+            Some((buffer.downgrade(), point.row))
+        });
+        let Some(location) = this.update(cx, |this, cx| {
+            let end = this.visited_ix.unwrap_or(this.locations.len());
+            let ix = (0..end).rev().find(|&ix| {
+                let location = &this.locations[ix];
+                let is_valid =
+                    location.editor.upgrade().is_some() || location.project_path.is_some();
+                is_valid
+                    && cursor.as_ref().map_or(true, |(buffer, row)| {
+                        location.buffer != *buffer || location.point(cx).row != *row
+                    })
+            })?;
+            this.visited_ix = Some(ix);
+            Some(this.locations[ix].clone())
+        }) else {
+            return;
+        };
+
+        let point = location.point(cx);
+        if let Some(editor) = location
+            .editor
+            .upgrade()
+            .filter(|editor| workspace.pane_for(editor).is_some())
+        {
+            workspace.activate_item(&editor, true, true, window, cx);
+            editor.update(cx, |editor, cx| {
+                let anchor = location.buffer.upgrade().and_then(|buffer| {
+                    editor
+                        .buffer()
+                        .read(cx)
+                        .buffer_point_to_anchor(&buffer, point, cx)
+                });
+                if let Some(anchor) = anchor {
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_anchor_ranges([anchor..anchor])
+                    });
+                }
+            });
+        } else if let Some(project_path) = location.project_path {
+            let open_task = workspace.open_path(project_path, None, true, window, cx);
+            cx.spawn_in(window, async move |_, cx| {
+                let item = open_task.await?;
+                if let Some(editor) = item.downcast::<Editor>() {
+                    editor.update_in(cx, |editor, window, cx| {
+                        editor.go_to_singleton_buffer_point(point, window, cx)
+                    })?;
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+}
@@ -0,0 +1,339 @@
+mod edit_history;
+
+#[cfg(test)]
+mod navigation_history_tests;
+
+use std::{sync::Arc, time::SystemTime};
+
+use edit_history::EditHistory;
+use editor::{Editor, MultiBufferRow, NavigationData};
+use fuzzy::{StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
+    Render, SharedString, Styled, Task, WeakEntity, Window, actions, rems,
+};
+use language::Point;
+use picker::{Picker, PickerDelegate};
+use project::ProjectPath;
+use time::OffsetDateTime;
+use time_format::{TimestampFormat, format_local_timestamp};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, NavigationMode, Pane, Workspace};
+
+actions!(navigation_history, [Toggle, GoToLastEdit]);
+
+const PICKER_WIDTH_REMS: f32 = 34.;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        let workspace_entity = cx.entity();
+        let edit_history = cx.new(|cx| EditHistory::new(&workspace_entity, window, cx));
+        workspace.register_action(|workspace, _: &Toggle, window, cx| {
+            NavigationHistory::toggle(workspace, window, cx);
+        });
+        workspace.register_action(move |workspace, _: &GoToLastEdit, window, cx| {
+            EditHistory::go_to_last_edit(&edit_history, workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// A picker listing the locations that going back or forward in the active pane would visit.
+pub struct NavigationHistory {
+    picker: Entity<Picker<NavigationHistoryDelegate>>,
+}
+
+impl ModalView for NavigationHistory {}
+
+impl EventEmitter<DismissEvent> for NavigationHistory {}
+
+impl Focusable for NavigationHistory {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for NavigationHistory {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("NavigationHistory")
+            .w(rems(PICKER_WIDTH_REMS))
+            .child(self.picker.clone())
+    }
+}
+
+impl NavigationHistory {
+    fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let pane = workspace.active_pane().clone();
+        let entries = history_entries(&pane, cx);
+        let workspace_handle = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            let delegate = NavigationHistoryDelegate::new(
+                cx.entity().downgrade(),
+                workspace_handle,
+                pane.downgrade(),
+                entries,
+            );
+            Self {
+                picker: cx.new(|cx| Picker::uniform_list(delegate, window, cx)),
+            }
+        });
+    }
+}
+
+/// A location in the navigation history of a pane.
+#[derive(Clone, Debug)]
+pub(crate) struct HistoryEntry {
+    pub mode: NavigationMode,
+    pub timestamp: usize,
+    pub recorded_at: SystemTime,
+    pub path: ProjectPath,
+    pub position: Option<Point>,
+    pub preview: Option<SharedString>,
+}
+
+impl HistoryEntry {
+    fn label(&self) -> String {
+        let path = self.path.path.to_string_lossy();
+        match self.position {
+            Some(position) => format!("{path}:{}", position.row + 1),
+            None => path.to_string(),
+        }
+    }
+}
+
+/// The entries that going forward would visit, furthest first, followed by the ones that going
+/// back would visit, nearest first.
+pub(crate) fn history_entries(pane: &Entity<Pane>, cx: &App) -> Vec<HistoryEntry> {
+    let nav_history = pane.read(cx).nav_history();
+    let mut entries = Vec::new();
+    for mode in [NavigationMode::GoingForward, NavigationMode::GoingBack] {
+        let first_ix = entries.len();
+        nav_history.for_each_entry_in_direction(mode, cx, |entry, (path, _)| {
+            let position = entry
+                .data
+                .as_ref()
+                .and_then(|data| data.downcast_ref::<NavigationData>())
+                .map(|data| data.cursor_position());
+            let preview = position.and_then(|position| {
+                let editor = entry.item.upgrade()?.act_as::<Editor>(cx)?;
+                line_preview(&editor, position.row, cx)
+            });
+            entries.push(HistoryEntry {
+                mode,
+                timestamp: entry.timestamp,
+                recorded_at: entry.recorded_at,
+                path,
+                position,
+                preview,
+            });
+        });
+        if let NavigationMode::GoingForward = mode {
+            entries[first_ix..].reverse();
+        }
+    }
+    entries
+}
+
+fn line_preview(editor: &Entity<Editor>, row: u32, cx: &App) -> Option<SharedString> {
+    let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+    if row > snapshot.max_point().row {
+        return None;
+    }
+    let line_end = Point::new(row, snapshot.line_len(MultiBufferRow(row)));
+    let line = snapshot
+        .text_for_range(Point::new(row, 0)..line_end)
+        .collect::<String>();
+    let line = line.trim();
+    (!line.is_empty()).then(|| SharedString::from(line.to_string()))
+}
+
+pub struct NavigationHistoryDelegate {
+    navigation_history: WeakEntity<NavigationHistory>,
+    workspace: WeakEntity<Workspace>,
+    pane: WeakEntity<Pane>,
+    entries: Vec<HistoryEntry>,
+    matches: Vec<(usize, Vec<usize>)>,
+    selected_index: usize,
+}
+
+impl NavigationHistoryDelegate {
+    fn new(
+        navigation_history: WeakEntity<NavigationHistory>,
+        workspace: WeakEntity<Workspace>,
+        pane: WeakEntity<Pane>,
+        entries: Vec<HistoryEntry>,
+    ) -> Self {
+        // Start at the location that going back would visit.
+        let selected_index = entries
+            .iter()
+            .position(|entry| matches!(entry.mode, NavigationMode::GoingBack))
+            .unwrap_or(0);
+        let matches = (0..entries.len()).map(|ix| (ix, Vec::new())).collect();
+        Self {
+            navigation_history,
+            workspace,
+            pane,
+            entries,
+            matches,
+            selected_index,
+        }
+    }
+}
+
+impl PickerDelegate for NavigationHistoryDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search navigation history…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No navigation history".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+
+    fn separators_after_indices(&self) -> Vec<usize> {
+        self.matches
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| {
+                matches!(self.entries[pair[0].0].mode, NavigationMode::GoingForward)
+                    && matches!(self.entries[pair[1].0].mode, NavigationMode::GoingBack)
+            })
+            .map(|(ix, _)| ix)
+            .collect()
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        if query.is_empty() {
+            self.matches = (0..self.entries.len()).map(|ix| (ix, Vec::new())).collect();
+            self.selected_index = self
+                .entries
+                .iter()
+                .position(|entry| matches!(entry.mode, NavigationMode::GoingBack))
+                .unwrap_or(0);
+            cx.notify();
+            return Task::ready(());
+        }
+
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| StringMatchCandidate::new(ix, &entry.label()))
+            .collect::<Vec<_>>();
+        let max_results = candidates.len();
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches = match_strings(
+                &candidates,
+                &query,
+                false,
+                max_results,
+                &Default::default(),
+                cx.background_executor().clone(),
+            )
+            .await;
+            // Keep the entries in the order they would be visited in.
+            matches.sort_by_key(|string_match| string_match.candidate_id);
+            picker
+                .update(cx, |picker, cx| {
+                    picker.delegate.matches = matches
+                        .into_iter()
+                        .map(|string_match| (string_match.candidate_id, string_match.positions))
+                        .collect();
+                    picker.delegate.selected_index = 0;
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(&(entry_ix, _)) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let entry = &self.entries[entry_ix];
+        let (mode, timestamp) = (entry.mode, entry.timestamp);
+        let pane = self.pane.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .go_to_history_entry(pane, mode, timestamp, window, cx)
+                    .detach_and_log_err(cx);
+            })
+            .log_err();
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.navigation_history
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let (entry_ix, positions) = self.matches.get(ix)?;
+        let entry = &self.entries[*entry_ix];
+        let recorded_at = format_local_timestamp(
+            OffsetDateTime::from(entry.recorded_at),
+            OffsetDateTime::now_utc(),
+            TimestampFormat::Relative,
+        );
+        let direction = match entry.mode {
+            NavigationMode::GoingForward => "forward",
+            _ => "back",
+        };
+
+        Some(
+            ListItem::new(ix)
+                .spacing(ListItemSpacing::Sparse)
+                .inset(true)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .child(HighlightedLabel::new(entry.label(), positions.clone()))
+                        .when_some(entry.preview.clone(), |this, preview| {
+                            this.child(
+                                Label::new(preview)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .single_line(),
+                            )
+                        }),
+                )
+                .end_slot(
+                    Label::new(format!("{recorded_at} · {direction}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+        )
+    }
+}
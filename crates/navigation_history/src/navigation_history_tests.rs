@@ -0,0 +1,135 @@
+use super::*;
+use gpui::{TestAppContext, VisualTestContext};
+use project::Project;
+use serde_json::json;
+use std::path::Path;
+use util::path;
+use workspace::{AppState, item::ItemHandle};
+
+#[gpui::test]
+async fn test_history_entries(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                "a.txt": "one\ntwo\nthree\n",
+                "b.txt": "1\n2\n3\n",
+            }),
+        )
+        .await;
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    let a = open_editor("a.txt", &workspace, cx).await;
+    move_cursor(&a, Point::new(1, 2), cx);
+    open_editor("b.txt", &workspace, cx).await;
+
+    let pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+    let entries = cx.update(|_, cx| history_entries(&pane, cx));
+    let backward = entries
+        .iter()
+        .filter(|entry| matches!(entry.mode, NavigationMode::GoingBack))
+        .map(|entry| (entry.label(), entry.preview.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        backward.first(),
+        Some(&("a.txt:2".to_string(), Some("two".into())))
+    );
+}
+
+#[gpui::test]
+async fn test_go_to_last_edit(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                "a.txt": "one\ntwo\nthree\n",
+                "b.txt": "1\n2\n3\n",
+            }),
+        )
+        .await;
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    let a = open_editor("a.txt", &workspace, cx).await;
+    move_cursor(&a, Point::new(2, 0), cx);
+    a.update_in(cx, |editor, window, cx| editor.insert("x", window, cx));
+
+    let b = open_editor("b.txt", &workspace, cx).await;
+    b.update_in(cx, |editor, window, cx| editor.insert("y", window, cx));
+    move_cursor(&b, Point::new(2, 0), cx);
+
+    cx.dispatch_action(GoToLastEdit);
+    cx.run_until_parked();
+    assert_eq!(active_cursor(&workspace, cx), ("b.txt".to_string(), 0));
+
+    // The location of the cursor is skipped, so the edit in the other file is visited next.
+    cx.dispatch_action(GoToLastEdit);
+    cx.run_until_parked();
+    assert_eq!(active_cursor(&workspace, cx), ("a.txt".to_string(), 2));
+
+    // There are no older edits.
+    cx.dispatch_action(GoToLastEdit);
+    cx.run_until_parked();
+    assert_eq!(active_cursor(&workspace, cx), ("a.txt".to_string(), 2));
+}
+
+fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+    cx.update(|cx| {
+        let state = AppState::test(cx);
+        theme::init(theme::LoadThemes::JustBase, cx);
+        language::init(cx);
+        super::init(cx);
+        editor::init(cx);
+        workspace::init_settings(cx);
+        Project::init_settings(cx);
+        state
+    })
+}
+
+async fn open_editor(
+    file_path: &str,
+    workspace: &Entity<Workspace>,
+    cx: &mut VisualTestContext,
+) -> Entity<Editor> {
+    let project = workspace.read_with(cx, |workspace, _| workspace.project().clone());
+    let worktree_id = project.update(cx, |project, cx| {
+        let worktree = project.worktrees(cx).last().expect("worktree not found");
+        worktree.read(cx).id()
+    });
+    let project_path = ProjectPath {
+        worktree_id,
+        path: Arc::from(Path::new(file_path)),
+    };
+    workspace
+        .update_in(cx, move |workspace, window, cx| {
+            workspace.open_path(project_path, None, true, window, cx)
+        })
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap()
+}
+
+fn move_cursor(editor: &Entity<Editor>, point: Point, cx: &mut VisualTestContext) {
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(None, window, cx, |s| s.select_ranges([point..point]))
+    });
+}
+
+fn active_cursor(workspace: &Entity<Workspace>, cx: &mut VisualTestContext) -> (String, u32) {
+    workspace.update(cx, |workspace, cx| {
+        let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+        let path = editor.project_path(cx).unwrap();
+        let row = editor.read(cx).selections.newest::<Point>(cx).head().row;
+        (path.path.to_string_lossy().into_owned(), row)
+    })
+}
@@ -0,0 +1,499 @@
+mod templates;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use collections::HashMap;
+use fs::Fs;
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+    Window,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{
+    HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Modal, ModalFooter, ModalHeader,
+    Section, prelude::*,
+};
+use ui_input::SingleLineInput;
+use util::{ResultExt as _, paths::PathExt as _};
+use workspace::{ModalView, Workspace};
+use zed_actions::new_project::Toggle;
+
+use templates::{
+    PROJECT_NAME_VARIABLE, ProjectTemplate, TemplateSource, builtin_templates, load_user_templates,
+    validate_project_name,
+};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(NewProjectModal::register).detach();
+}
+
+/// Creates a project from a template, then opens it.
+pub struct NewProjectModal {
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    default_location: PathBuf,
+    stage: Stage,
+}
+
+enum Stage {
+    SelectTemplate(Entity<Picker<TemplatePickerDelegate>>),
+    Configure(ConfigureProject),
+}
+
+struct ConfigureProject {
+    template: Arc<ProjectTemplate>,
+    name_input: Entity<SingleLineInput>,
+    location_input: Entity<SingleLineInput>,
+    variable_inputs: Vec<(String, Entity<SingleLineInput>)>,
+    error: Option<SharedString>,
+    creating: Option<Task<()>>,
+}
+
+impl NewProjectModal {
+    fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, _: &Toggle, window, cx| {
+            let fs = workspace.app_state().fs.clone();
+            let default_location = default_location(workspace, cx);
+            cx.spawn_in(window, async move |workspace, cx| {
+                let user_templates = load_user_templates(fs.clone()).await;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let weak_workspace = cx.entity().downgrade();
+                    let templates = builtin_templates()
+                        .into_iter()
+                        .chain(user_templates)
+                        .map(Arc::new)
+                        .collect();
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        NewProjectModal::new(
+                            weak_workspace,
+                            fs,
+                            templates,
+                            default_location,
+                            window,
+                            cx,
+                        )
+                    });
+                })
+            })
+            .detach_and_log_err(cx);
+        });
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        fs: Arc<dyn Fs>,
+        templates: Vec<Arc<ProjectTemplate>>,
+        default_location: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = TemplatePickerDelegate::new(cx.entity().downgrade(), templates);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self {
+            workspace,
+            fs,
+            default_location,
+            stage: Stage::SelectTemplate(picker),
+        }
+    }
+
+    fn configure(
+        &mut self,
+        template: Arc<ProjectTemplate>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name_input = new_input("Name", "my-project", "", window, cx);
+        let location_input = new_input(
+            "Location",
+            "Directory to create the project in",
+            &self.default_location.compact().to_string_lossy(),
+            window,
+            cx,
+        );
+        let variable_inputs = template
+            .variables
+            .iter()
+            .map(|(variable, default)| {
+                let input = new_input(variable, "", default, window, cx);
+                (variable.clone(), input)
+            })
+            .collect();
+        window.focus(&name_input.focus_handle(cx));
+        self.stage = Stage::Configure(ConfigureProject {
+            template,
+            name_input,
+            location_input,
+            variable_inputs,
+            error: None,
+            creating: None,
+        });
+        cx.notify();
+    }
+
+    fn create(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let fs = self.fs.clone();
+        let Stage::Configure(configure) = &mut self.stage else {
+            return;
+        };
+        if configure.creating.is_some() {
+            return;
+        }
+
+        let name = input_text(&configure.name_input, cx);
+        if let Err(error) = validate_project_name(&name) {
+            configure.error = Some(error.into());
+            cx.notify();
+            return;
+        }
+        let location = expand_home_dir(&input_text(&configure.location_input, cx));
+        let project_dir = location.join(&name);
+        let mut variables = HashMap::default();
+        variables.insert(PROJECT_NAME_VARIABLE.to_string(), name);
+        for (variable, input) in &configure.variable_inputs {
+            variables.insert(variable.clone(), input_text(input, cx));
+        }
+
+        let template = configure.template.clone();
+        configure.error = None;
+        configure.creating = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = template.source.scaffold(&project_dir, &variables, fs).await;
+            this.update_in(cx, |this, window, cx| match result {
+                Ok(()) => {
+                    this.workspace
+                        .update(cx, |workspace, cx| {
+                            workspace
+                                .open_workspace_for_paths(false, vec![project_dir], window, cx)
+                                .detach_and_log_err(cx)
+                        })
+                        .log_err();
+                    cx.emit(DismissEvent);
+                }
+                Err(error) => {
+                    if let Stage::Configure(configure) = &mut this.stage {
+                        configure.creating = None;
+                        configure.error = Some(format!("{error:#}").into());
+                        cx.notify();
+                    }
+                }
+            })
+            .log_err();
+        }));
+        cx.notify();
+    }
+
+    fn render_configure(
+        &self,
+        configure: &ConfigureProject,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let focus_handle = self.focus_handle(cx);
+        let creating = configure.creating.is_some();
+        let description = match &configure.template.source {
+            TemplateSource::Command { .. } => {
+                format!("Runs `{}`", configure.template.description)
+            }
+            TemplateSource::Directory(_) => configure.template.description.to_string(),
+        };
+
+        div()
+            .elevation_3(cx)
+            .w(rems(34.))
+            .key_context("NewProject")
+            .on_action(cx.listener(|this, _: &menu::Confirm, window, cx| {
+                this.create(window, cx)
+            }))
+            .on_action(cx.listener(|_, _: &menu::Cancel, _, cx| cx.emit(DismissEvent)))
+            .on_mouse_down_out(cx.listener(|this, _, _, cx| {
+                if !matches!(&this.stage, Stage::Configure(configure) if configure.creating.is_some())
+                {
+                    cx.emit(DismissEvent)
+                }
+            }))
+            .child(
+                Modal::new("new-project", None)
+                    .header(
+                        ModalHeader::new()
+                            .headline(format!("New {} Project", configure.template.name)),
+                    )
+                    .section(
+                        Section::new().child(
+                            v_flex()
+                                .gap_2()
+                                .child(
+                                    Label::new(description)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                                .child(configure.name_input.clone())
+                                .child(configure.location_input.clone())
+                                .children(
+                                    configure
+                                        .variable_inputs
+                                        .iter()
+                                        .map(|(_, input)| input.clone()),
+                                )
+                                .children(configure.error.clone().map(|error| {
+                                    Label::new(error).size(LabelSize::Small).color(Color::Error)
+                                })),
+                        ),
+                    )
+                    .footer(
+                        ModalFooter::new().end_slot(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Button::new("cancel", "Cancel")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Cancel,
+                                                &focus_handle,
+                                                window,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(cx.listener(|_, _, _, cx| cx.emit(DismissEvent))),
+                                )
+                                .child(
+                                    Button::new(
+                                        "create",
+                                        if creating { "Creating…" } else { "Create" },
+                                    )
+                                    .disabled(creating)
+                                    .key_binding(
+                                        KeyBinding::for_action_in(
+                                            &menu::Confirm,
+                                            &focus_handle,
+                                            window,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.create(window, cx)
+                                    })),
+                                ),
+                        ),
+                    ),
+            )
+    }
+}
+
+impl Render for NewProjectModal {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        match &self.stage {
+            Stage::SelectTemplate(picker) => v_flex()
+                .w(rems(34.))
+                .child(picker.clone())
+                .into_any_element(),
+            Stage::Configure(configure) => self
+                .render_configure(configure, window, cx)
+                .into_any_element(),
+        }
+    }
+}
+
+impl Focusable for NewProjectModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match &self.stage {
+            Stage::SelectTemplate(picker) => picker.focus_handle(cx),
+            Stage::Configure(configure) => configure.name_input.focus_handle(cx),
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for NewProjectModal {}
+impl ModalView for NewProjectModal {}
+
+fn new_input(
+    label: &str,
+    placeholder: &str,
+    text: &str,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<SingleLineInput> {
+    let input = cx.new(|cx| {
+        SingleLineInput::new(window, cx, placeholder.to_string()).label(label.to_string())
+    });
+    input.update(cx, |input, cx| {
+        input
+            .editor()
+            .update(cx, |editor, cx| editor.set_text(text, window, cx))
+    });
+    input
+}
+
+fn input_text(input: &Entity<SingleLineInput>, cx: &App) -> String {
+    input.read(cx).editor().read(cx).text(cx).trim().to_string()
+}
+
+/// Suggests creating new projects next to the current one, or in the home directory.
+fn default_location(workspace: &Workspace, cx: &App) -> PathBuf {
+    let project = workspace.project().read(cx);
+    project
+        .is_local()
+        .then(|| project.visible_worktrees(cx).next())
+        .flatten()
+        .and_then(|worktree| worktree.read(cx).abs_path().parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| paths::home_dir().clone())
+}
+
+fn expand_home_dir(location: &str) -> PathBuf {
+    match location.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            paths::home_dir().join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(location),
+    }
+}
+
+pub struct TemplatePickerDelegate {
+    modal: WeakEntity<NewProjectModal>,
+    templates: Vec<Arc<ProjectTemplate>>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl TemplatePickerDelegate {
+    fn new(modal: WeakEntity<NewProjectModal>, templates: Vec<Arc<ProjectTemplate>>) -> Self {
+        Self {
+            modal,
+            templates,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for TemplatePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Create a project from a template…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(string_match) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let template = self.templates[string_match.candidate_id].clone();
+        self.modal
+            .update(cx, |modal, cx| modal.configure(template, window, cx))
+            .log_err();
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .templates
+            .iter()
+            .enumerate()
+            .map(|(id, template)| StringMatchCandidate::new(id, &template.name))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.trim().is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    query.trim(),
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+            this.update(cx, |this, cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = 0;
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let string_match = self.matches.get(ix)?;
+        let template = &self.templates[string_match.candidate_id];
+        let icon = match template.source {
+            TemplateSource::Command { .. } => IconName::Terminal,
+            TemplateSource::Directory(_) => IconName::Folder,
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(icon).color(Color::Muted))
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_2()
+                        .justify_between()
+                        .child(HighlightedLabel::new(
+                            string_match.string.clone(),
+                            string_match.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(template.description.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        ),
+                ),
+        )
+    }
+}
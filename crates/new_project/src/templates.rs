@@ -0,0 +1,356 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use fs::Fs;
+use futures::StreamExt as _;
+use gpui::SharedString;
+use serde::Deserialize;
+use util::{ResultExt as _, command::new_smol_command, paths::PathExt as _};
+
+/// The file in a template directory that describes the template. It isn't copied into new
+/// projects.
+const MANIFEST_FILE_NAME: &str = "template.json";
+
+/// The variable that every template can refer to, set to the name of the new project.
+pub(crate) const PROJECT_NAME_VARIABLE: &str = "project_name";
+
+pub(crate) struct ProjectTemplate {
+    pub name: SharedString,
+    pub description: SharedString,
+    pub source: TemplateSource,
+    /// The variables that the template refers to besides the project name, with their default
+    /// values.
+    pub variables: Vec<(String, String)>,
+}
+
+pub(crate) enum TemplateSource {
+    /// A tool that scaffolds the project, like `cargo new`. Its arguments can refer to
+    /// variables.
+    Command {
+        program: &'static str,
+        args: &'static [&'static str],
+        /// Whether the tool is run in the new project's directory, rather than creating it.
+        in_project_dir: bool,
+    },
+    /// A directory that is copied into the new project, with variables substituted in the names
+    /// and contents of its files.
+    Directory(PathBuf),
+}
+
+/// What a user-defined template can say about itself in its `template.json`.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct TemplateManifest {
+    name: Option<String>,
+    description: Option<String>,
+    variables: BTreeMap<String, String>,
+}
+
+pub(crate) fn builtin_templates() -> Vec<ProjectTemplate> {
+    let command = |name: &'static str,
+                   description: &'static str,
+                   program: &'static str,
+                   args: &'static [&'static str],
+                   in_project_dir: bool| ProjectTemplate {
+        name: name.into(),
+        description: description.into(),
+        source: TemplateSource::Command {
+            program,
+            args,
+            in_project_dir,
+        },
+        variables: Vec::new(),
+    };
+    vec![
+        command(
+            "Rust Binary",
+            "cargo new",
+            "cargo",
+            &["new", "{{project_name}}"],
+            false,
+        ),
+        command(
+            "Rust Library",
+            "cargo new --lib",
+            "cargo",
+            &["new", "--lib", "{{project_name}}"],
+            false,
+        ),
+        command(
+            "Node.js Package",
+            "npm init",
+            "npm",
+            &["init", "--yes"],
+            true,
+        ),
+        command(
+            "Vite App",
+            "npm create vite",
+            "npm",
+            &[
+                "create",
+                "--yes",
+                "vite@latest",
+                "{{project_name}}",
+                "--",
+                "--template",
+                "vanilla-ts",
+            ],
+            false,
+        ),
+    ]
+}
+
+/// Loads the templates in the config directory's `templates` directory, one per directory.
+pub(crate) async fn load_user_templates(fs: Arc<dyn Fs>) -> Vec<ProjectTemplate> {
+    let Ok(mut entries) = fs.read_dir(paths::project_templates_dir()).await else {
+        return Vec::new();
+    };
+
+    let mut templates = Vec::new();
+    while let Some(entry) = entries.next().await {
+        let Some(path) = entry.log_err() else {
+            continue;
+        };
+        if !fs.is_dir(&path).await {
+            continue;
+        }
+        let manifest = match fs.load(&path.join(MANIFEST_FILE_NAME)).await {
+            Ok(json) => serde_json::from_str::<TemplateManifest>(&json)
+                .with_context(|| format!("parsing the template manifest in {path:?}"))
+                .log_err()
+                .unwrap_or_default(),
+            Err(_) => TemplateManifest::default(),
+        };
+        let name = manifest.name.unwrap_or_else(|| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        let description = manifest
+            .description
+            .unwrap_or_else(|| path.compact().to_string_lossy().into_owned());
+        templates.push(ProjectTemplate {
+            name: name.into(),
+            description: description.into(),
+            source: TemplateSource::Directory(path),
+            variables: manifest.variables.into_iter().collect(),
+        });
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+impl TemplateSource {
+    /// Creates the project at `project_dir` from this template, which must not exist yet.
+    pub(crate) async fn scaffold(
+        &self,
+        project_dir: &Path,
+        variables: &HashMap<String, String>,
+        fs: Arc<dyn Fs>,
+    ) -> Result<()> {
+        anyhow::ensure!(
+            fs.metadata(project_dir).await?.is_none(),
+            "{} already exists",
+            project_dir.compact().display()
+        );
+        if let Some(name) = variables.get(PROJECT_NAME_VARIABLE) {
+            validate_project_name(name).map_err(anyhow::Error::msg)?;
+        }
+        let parent_dir = project_dir
+            .parent()
+            .context("the project must be created in a directory")?;
+
+        match self {
+            Self::Command {
+                program,
+                args,
+                in_project_dir,
+            } => {
+                fs.create_dir(parent_dir).await?;
+                let working_dir = if *in_project_dir {
+                    fs.create_dir(project_dir).await?;
+                    project_dir
+                } else {
+                    parent_dir
+                };
+                let output = new_smol_command(program)
+                    .args(args.iter().map(|arg| substitute(arg, variables)))
+                    .current_dir(working_dir)
+                    .stdin(Stdio::null())
+                    .output()
+                    .await
+                    .with_context(|| format!("running {program}"))?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "{program} failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Self::Directory(template_dir) => {
+                copy_template(template_dir, project_dir, variables, fs).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn copy_template(
+    template_dir: &Path,
+    project_dir: &Path,
+    variables: &HashMap<String, String>,
+    fs: Arc<dyn Fs>,
+) -> Result<()> {
+    let mut pending_dirs = vec![(template_dir.to_path_buf(), project_dir.to_path_buf())];
+    while let Some((source_dir, target_dir)) = pending_dirs.pop() {
+        fs.create_dir(&target_dir).await?;
+        let mut entries = fs.read_dir(&source_dir).await?;
+        while let Some(source) = entries.next().await {
+            let source = source?;
+            let Some(file_name) = source.file_name() else {
+                continue;
+            };
+            if source_dir == template_dir
+                && (file_name == MANIFEST_FILE_NAME || file_name == ".git")
+            {
+                continue;
+            }
+            let target = target_dir.join(substitute(&file_name.to_string_lossy(), variables));
+            if fs.is_dir(&source).await {
+                pending_dirs.push((source, target));
+                continue;
+            }
+            let content = match String::from_utf8(fs.load_bytes(&source).await?) {
+                Ok(text) => substitute(&text, variables).into_bytes(),
+                Err(error) => error.into_bytes(),
+            };
+            fs.write(&target, &content).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a project name can be used as the name of the project's directory and passed
+/// to the tools that scaffold projects, which would take a name starting with `-` for an option.
+pub(crate) fn validate_project_name(name: &str) -> Result<(), &'static str> {
+    if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err("Enter a name for the project's directory");
+    }
+    if name.starts_with('-') {
+        return Err("The project's name can't start with \"-\"");
+    }
+    Ok(())
+}
+
+/// Replaces each `{{variable}}` in the text with the variable's value. Variables that aren't
+/// defined are left as they are.
+pub(crate) fn substitute(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+        let after_start = &rest[start + 2..];
+        let Some(end) = after_start.find("}}") else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+        match variables.get(after_start[..end].trim()) {
+            Some(value) => result.push_str(value),
+            None => result.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after_start[end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use util::path;
+
+    use super::*;
+
+    #[test]
+    fn test_substitute() {
+        let variables = HashMap::from_iter([
+            ("project_name".to_string(), "demo".to_string()),
+            ("license".to_string(), "MIT".to_string()),
+        ]);
+        assert_eq!(
+            substitute("name = \"{{project_name}}\" # {{ license }}", &variables),
+            "name = \"demo\" # MIT"
+        );
+        assert_eq!(
+            substitute("{{unknown}} and {{project_name", &variables),
+            "{{unknown}} and {{project_name"
+        );
+    }
+
+    #[test]
+    fn test_validate_project_name() {
+        assert!(validate_project_name("demo").is_ok());
+        assert!(validate_project_name("my-app.v2").is_ok());
+        assert!(validate_project_name("").is_err());
+        assert!(validate_project_name("..").is_err());
+        assert!(validate_project_name("a/b").is_err());
+        assert!(validate_project_name("--help").is_err());
+        assert!(validate_project_name("-").is_err());
+    }
+
+    #[gpui::test]
+    async fn test_scaffold_from_directory(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/templates/lib"),
+            json!({
+                "template.json": r#"{"variables": {"license": "MIT"}}"#,
+                "README.md": "# {{project_name}}\n\nLicensed under {{license}}.",
+                "src": {
+                    "{{project_name}}.rs": "pub fn hello() {}",
+                },
+            }),
+        )
+        .await;
+
+        let variables = HashMap::from_iter([
+            ("project_name".to_string(), "demo".to_string()),
+            ("license".to_string(), "Apache-2.0".to_string()),
+        ]);
+        let source = TemplateSource::Directory(PathBuf::from(path!("/templates/lib")));
+        source
+            .scaffold(Path::new(path!("/projects/demo")), &variables, fs.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs.load(Path::new(path!("/projects/demo/README.md")))
+                .await
+                .unwrap(),
+            "# demo\n\nLicensed under Apache-2.0."
+        );
+        assert!(
+            fs.is_file(Path::new(path!("/projects/demo/src/demo.rs")))
+                .await
+        );
+        assert!(
+            !fs.is_file(Path::new(path!("/projects/demo/template.json")))
+                .await
+        );
+        assert!(
+            source
+                .scaffold(Path::new(path!("/projects/demo")), &variables, fs.clone())
+                .await
+                .is_err(),
+            "an existing directory shouldn't be overwritten"
+        );
+    }
+}
@@ -374,6 +374,7 @@ pub struct ResponseMessageDelta {
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct ToolCallChunk {
+    #[serde(default)]
     pub index: usize,
     pub id: Option<String>,
 
@@ -412,7 +413,9 @@ pub enum ResponseStreamResult {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ResponseStreamEvent {
+    #[serde(default)]
     pub created: u32,
+    #[serde(default)]
     pub model: String,
     pub choices: Vec<ChoiceDelta>,
     pub usage: Option<Usage>,
@@ -678,6 +681,43 @@ pub async fn stream_completion(
     }
 }
 
+#[derive(Deserialize)]
+struct ListModelsResponse {
+    data: Vec<ListedModel>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ListedModel {
+    pub id: String,
+}
+
+/// Lists the models served at `api_url`, which OpenAI-compatible servers also implement.
+pub async fn list_models(
+    client: &dyn HttpClient,
+    api_url: &str,
+    api_key: &str,
+) -> Result<Vec<ListedModel>> {
+    let uri = format!("{api_url}/models");
+    let request = HttpRequest::builder()
+        .method(Method::GET)
+        .uri(uri)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .body(AsyncBody::default())?;
+    let mut response = client.send(request).await?;
+
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "Failed to list models at {api_url}: {} {}",
+        response.status(),
+        body,
+    );
+    let response: ListModelsResponse =
+        serde_json::from_str(&body).context("Unable to parse the list of models")?;
+    Ok(response.data)
+}
+
 #[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum OpenAiEmbeddingModel {
     #[serde(rename = "text-embedding-3-small")]
@@ -0,0 +1,275 @@
+use std::{cmp::Ordering, ops::Range};
+
+use collections::HashSet;
+use language::OutlineItem;
+use serde::{Deserialize, Serialize};
+
+/// The order of the outline items that share a parent.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlineSortMode {
+    /// In the order they appear in the buffer.
+    #[default]
+    Position,
+    /// Alphabetically by their name.
+    Name,
+    /// Grouped by their kind, then in the order they appear in the buffer.
+    Kind,
+}
+
+impl OutlineSortMode {
+    pub const ALL: [Self; 3] = [Self::Position, Self::Name, Self::Kind];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Position => "Sort by Position",
+            Self::Name => "Sort by Name",
+            Self::Kind => "Sort by Kind",
+        }
+    }
+}
+
+/// What an outline item declares, guessed from the keywords preceding its name.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Module,
+    Type,
+    Function,
+    Variable,
+    Other,
+}
+
+impl SymbolKind {
+    pub const ALL: [Self; 5] = [
+        Self::Module,
+        Self::Type,
+        Self::Function,
+        Self::Variable,
+        Self::Other,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Module => "Modules",
+            Self::Type => "Types",
+            Self::Function => "Functions",
+            Self::Variable => "Variables",
+            Self::Other => "Other",
+        }
+    }
+
+    pub fn of<T>(item: &OutlineItem<T>) -> Self {
+        let context_end = item.name_ranges.first().map_or(0, |range| range.start);
+        let context = item.text.get(..context_end).unwrap_or_default();
+        let keywords = context
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|keyword| !keyword.is_empty())
+            .collect::<Vec<_>>();
+        let has_keyword = |candidates: &[&str]| {
+            keywords
+                .iter()
+                .any(|keyword| candidates.contains(&keyword.to_lowercase().as_str()))
+        };
+
+        // Checked from the most to the least specific, so that `const fn` is treated as a function.
+        if has_keyword(&[
+            "fn", "def", "func", "function", "method", "fun", "sub", "proc",
+        ]) {
+            Self::Function
+        } else if has_keyword(&[
+            "struct",
+            "enum",
+            "class",
+            "trait",
+            "interface",
+            "type",
+            "union",
+            "impl",
+            "protocol",
+            "record",
+            "object",
+        ]) {
+            Self::Type
+        } else if has_keyword(&["mod", "module", "namespace", "package"]) {
+            Self::Module
+        } else if has_keyword(&["let", "const", "static", "var", "val"]) {
+            Self::Variable
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Sorts the siblings of an outline and drops the items of hidden kinds, keeping the ones
+/// that contain visible items so that the hierarchy stays intact.
+pub fn arrange_outlines<'a, T>(
+    outlines: &'a [OutlineItem<T>],
+    sort_mode: OutlineSortMode,
+    hidden_kinds: &HashSet<SymbolKind>,
+) -> Vec<&'a OutlineItem<T>> {
+    if sort_mode == OutlineSortMode::Position && hidden_kinds.is_empty() {
+        return outlines.iter().collect();
+    }
+
+    let mut arranged = Vec::with_capacity(outlines.len());
+    arrange_siblings(outlines, sort_mode, hidden_kinds, &mut arranged);
+    arranged
+}
+
+fn arrange_siblings<'a, T>(
+    outlines: &'a [OutlineItem<T>],
+    sort_mode: OutlineSortMode,
+    hidden_kinds: &HashSet<SymbolKind>,
+    arranged: &mut Vec<&'a OutlineItem<T>>,
+) {
+    let mut subtrees = Vec::<(Range<usize>, Vec<&'a OutlineItem<T>>)>::new();
+    let mut start = 0;
+    while start < outlines.len() {
+        let depth = outlines[start].depth;
+        let end = outlines[start + 1..]
+            .iter()
+            .position(|item| item.depth <= depth)
+            .map_or(outlines.len(), |ix| start + 1 + ix);
+
+        let mut children = Vec::new();
+        arrange_siblings(
+            &outlines[start + 1..end],
+            sort_mode,
+            hidden_kinds,
+            &mut children,
+        );
+        if !children.is_empty() || !hidden_kinds.contains(&SymbolKind::of(&outlines[start])) {
+            subtrees.push((start..end, children));
+        }
+        start = end;
+    }
+
+    subtrees.sort_by(|(a, _), (b, _)| {
+        let (a, b) = (&outlines[a.start], &outlines[b.start]);
+        match sort_mode {
+            OutlineSortMode::Position => Ordering::Equal,
+            OutlineSortMode::Name => name(a).to_lowercase().cmp(&name(b).to_lowercase()),
+            OutlineSortMode::Kind => SymbolKind::of(a).cmp(&SymbolKind::of(b)),
+        }
+    });
+    for (range, children) in subtrees {
+        arranged.push(&outlines[range.start]);
+        arranged.extend(children);
+    }
+}
+
+fn name<T>(item: &OutlineItem<T>) -> &str {
+    item.name_ranges
+        .first()
+        .and_then(|range| item.text.get(range.clone()))
+        .unwrap_or(&item.text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(depth: usize, text: &str) -> OutlineItem<usize> {
+        let name_start = text.rfind(' ').map_or(0, |ix| ix + 1);
+        OutlineItem {
+            depth,
+            range: 0..0,
+            text: text.to_string(),
+            highlight_ranges: Vec::new(),
+            name_ranges: vec![name_start..text.len()],
+            body_range: None,
+            annotation_range: None,
+        }
+    }
+
+    fn texts(items: Vec<&OutlineItem<usize>>) -> Vec<String> {
+        items
+            .into_iter()
+            .map(|item| format!("{}{}", "  ".repeat(item.depth), item.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_symbol_kinds() {
+        assert_eq!(
+            SymbolKind::of(&item(0, "pub const fn new")),
+            SymbolKind::Function
+        );
+        assert_eq!(
+            SymbolKind::of(&item(0, "impl Display for Foo")),
+            SymbolKind::Type
+        );
+        assert_eq!(SymbolKind::of(&item(0, "mod tests")), SymbolKind::Module);
+        assert_eq!(
+            SymbolKind::of(&item(0, "static COUNT")),
+            SymbolKind::Variable
+        );
+        assert_eq!(SymbolKind::of(&item(0, "field")), SymbolKind::Other);
+    }
+
+    #[test]
+    fn test_arrange_outlines() {
+        let outlines = [
+            item(0, "struct Zeta"),
+            item(1, "value"),
+            item(0, "impl Zeta"),
+            item(1, "fn zoom"),
+            item(1, "const LIMIT"),
+            item(1, "fn alpha"),
+            item(0, "const MAX"),
+            item(0, "fn beta"),
+        ];
+
+        assert_eq!(
+            texts(arrange_outlines(
+                &outlines,
+                OutlineSortMode::Name,
+                &HashSet::default()
+            )),
+            [
+                "fn beta",
+                "const MAX",
+                "struct Zeta",
+                "  value",
+                "impl Zeta",
+                "  fn alpha",
+                "  const LIMIT",
+                "  fn zoom",
+            ]
+        );
+
+        assert_eq!(
+            texts(arrange_outlines(
+                &outlines,
+                OutlineSortMode::Kind,
+                &HashSet::default()
+            )),
+            [
+                "struct Zeta",
+                "  value",
+                "impl Zeta",
+                "  fn zoom",
+                "  fn alpha",
+                "  const LIMIT",
+                "fn beta",
+                "const MAX",
+            ]
+        );
+
+        let only_functions = HashSet::from_iter([
+            SymbolKind::Module,
+            SymbolKind::Type,
+            SymbolKind::Variable,
+            SymbolKind::Other,
+        ]);
+        assert_eq!(
+            texts(arrange_outlines(
+                &outlines,
+                OutlineSortMode::Position,
+                &only_functions
+            )),
+            ["impl Zeta", "  fn zoom", "  fn alpha", "fn beta"]
+        );
+    }
+}
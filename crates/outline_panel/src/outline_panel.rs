@@ -1,3 +1,4 @@
+mod outline_arrangement;
 mod outline_panel_settings;
 
 use std::{
@@ -39,6 +40,7 @@ use itertools::Itertools;
 use language::{BufferId, BufferSnapshot, OffsetRangeExt, OutlineItem};
 use menu::{Cancel, SelectFirst, SelectLast, SelectNext, SelectPrevious};
 
+use outline_arrangement::{OutlineSortMode, SymbolKind, arrange_outlines};
 use outline_panel_settings::{OutlinePanelDockPosition, OutlinePanelSettings, ShowIndentGuides};
 use project::{File, Fs, GitEntry, GitTraversal, Project, ProjectItem};
 use search::{BufferSearchBar, ProjectSearchView};
@@ -46,7 +48,10 @@ use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
 use smol::channel;
 use theme::{SyntaxTheme, ThemeSettings};
-use ui::{DynamicSpacing, IndentGuideColors, IndentGuideLayout};
+use ui::{
+    Button, ButtonSize, ButtonStyle, DynamicSpacing, IconPosition, IndentGuideColors,
+    IndentGuideLayout, LabelSize, PopoverMenu, TintColor,
+};
 use util::{RangeExt, ResultExt, TryFutureExt, debug_panic};
 use workspace::{
     OpenInTerminal, WeakItemHandle, Workspace,
@@ -73,8 +78,12 @@ actions!(
         OpenSelectedEntry,
         RevealInFileManager,
         SelectParent,
+        SortByKind,
+        SortByName,
+        SortByPosition,
         ToggleActiveEditorPin,
         ToggleFocus,
+        ToggleFollowCursor,
         UnfoldDirectory,
     ]
 );
@@ -92,6 +101,9 @@ pub struct OutlinePanel {
     workspace: WeakEntity<Workspace>,
     active: bool,
     pinned: bool,
+    follow_cursor: bool,
+    sort_mode: OutlineSortMode,
+    hidden_symbol_kinds: HashSet<SymbolKind>,
     scroll_handle: UniformListScrollHandle,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     focus_handle: FocusHandle,
@@ -329,10 +341,14 @@ impl Excerpt {
     }
 
     fn iter_outlines(&self) -> impl Iterator<Item = &Outline> {
+        self.outlines().iter()
+    }
+
+    fn outlines(&self) -> &[Outline] {
         match &self.outlines {
-            ExcerptOutlines::Outlines(outlines) => outlines.iter(),
-            ExcerptOutlines::Invalidated(outlines) => outlines.iter(),
-            ExcerptOutlines::NotFetched => [].iter(),
+            ExcerptOutlines::Outlines(outlines) => outlines,
+            ExcerptOutlines::Invalidated(outlines) => outlines,
+            ExcerptOutlines::NotFetched => &[],
         }
     }
 
@@ -658,6 +674,9 @@ pub enum Event {
 struct SerializedOutlinePanel {
     width: Option<Pixels>,
     active: Option<bool>,
+    follow_cursor: Option<bool>,
+    sort_mode: Option<OutlineSortMode>,
+    hidden_symbol_kinds: Option<Vec<SymbolKind>>,
 }
 
 pub fn init_settings(cx: &mut App) {
@@ -671,6 +690,13 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
             workspace.toggle_panel_focus::<OutlinePanel>(window, cx);
         });
+        workspace.register_action(|workspace, action: &ToggleFollowCursor, window, cx| {
+            if let Some(outline_panel) = workspace.panel::<OutlinePanel>(cx) {
+                outline_panel.update(cx, |outline_panel, cx| {
+                    outline_panel.toggle_follow_cursor(action, window, cx)
+                });
+            }
+        });
     })
     .detach();
 }
@@ -706,6 +732,13 @@ impl OutlinePanel {
                 panel.update(cx, |panel, cx| {
                     panel.width = serialized_panel.width.map(|px| px.round());
                     panel.active = serialized_panel.active.unwrap_or(false);
+                    panel.follow_cursor = serialized_panel.follow_cursor.unwrap_or(true);
+                    panel.sort_mode = serialized_panel.sort_mode.unwrap_or_default();
+                    panel.hidden_symbol_kinds = serialized_panel
+                        .hidden_symbol_kinds
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect();
                     cx.notify();
                 });
             }
@@ -803,6 +836,9 @@ impl OutlinePanel {
                 mode: ItemsDisplayMode::Outline,
                 active: false,
                 pinned: false,
+                follow_cursor: true,
+                sort_mode: OutlineSortMode::default(),
+                hidden_symbol_kinds: HashSet::default(),
                 workspace: workspace_handle,
                 project,
                 fs: workspace.app_state().fs.clone(),
@@ -873,15 +909,17 @@ impl OutlinePanel {
         else {
             return;
         };
-        let width = self.width;
-        let active = Some(self.active);
+        let serialized_panel = SerializedOutlinePanel {
+            width: self.width,
+            active: Some(self.active),
+            follow_cursor: Some(self.follow_cursor),
+            sort_mode: Some(self.sort_mode),
+            hidden_symbol_kinds: Some(self.hidden_symbol_kinds.iter().copied().sorted().collect()),
+        };
         self.pending_serialization = cx.background_spawn(
             async move {
                 KEY_VALUE_STORE
-                    .write_kvp(
-                        serialization_key,
-                        serde_json::to_string(&SerializedOutlinePanel { width, active })?,
-                    )
+                    .write_kvp(serialization_key, serde_json::to_string(&serialized_panel)?)
                     .await?;
                 anyhow::Ok(())
             }
@@ -1928,6 +1966,7 @@ impl OutlinePanel {
         cx: &mut Context<Self>,
     ) {
         if !self.active
+            || !self.follow_cursor
             || !OutlinePanelSettings::get_global(cx).auto_reveal_entries
             || self.focus_handle.contains_focused(window, cx)
         {
@@ -4128,7 +4167,11 @@ impl OutlinePanel {
                     continue;
                 }
 
-                for outline in excerpt.iter_outlines() {
+                for outline in arrange_outlines(
+                    excerpt.outlines(),
+                    self.sort_mode,
+                    &self.hidden_symbol_kinds,
+                ) {
                     self.push_entry(
                         state,
                         track_matches,
@@ -4246,6 +4289,48 @@ impl OutlinePanel {
         cx.notify();
     }
 
+    fn toggle_follow_cursor(
+        &mut self,
+        _: &ToggleFollowCursor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.follow_cursor = !self.follow_cursor;
+        if self.follow_cursor {
+            if let Some(active_editor) = self.active_editor() {
+                self.reveal_entry_for_selection(active_editor, window, cx);
+            }
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn set_sort_mode(
+        &mut self,
+        sort_mode: OutlineSortMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.sort_mode != sort_mode {
+            self.sort_mode = sort_mode;
+            self.update_cached_entries(None, window, cx);
+            self.serialize(cx);
+        }
+    }
+
+    fn toggle_symbol_kind(
+        &mut self,
+        kind: SymbolKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.hidden_symbol_kinds.remove(&kind) {
+            self.hidden_symbol_kinds.insert(kind);
+        }
+        self.update_cached_entries(None, window, cx);
+        self.serialize(cx);
+    }
+
     fn selected_entry(&self) -> Option<&PanelEntry> {
         match &self.selected_entry {
             SelectedEntry::Invalidated(entry) => entry.as_ref(),
@@ -4661,40 +4746,114 @@ impl OutlinePanel {
         v_flex().w_full().flex_1().overflow_hidden().child(contents)
     }
 
+    fn render_outline_controls(&self, cx: &mut Context<Self>) -> Div {
+        let sort_mode = self.sort_mode;
+        let outline_panel = cx.entity().downgrade();
+        h_flex()
+            .px_2()
+            .pt_2()
+            .gap_1()
+            .flex_wrap()
+            .children(SymbolKind::ALL.into_iter().enumerate().map(|(ix, kind)| {
+                Button::new(("outline-panel-symbol-kind", ix), kind.label())
+                    .size(ButtonSize::Compact)
+                    .label_size(LabelSize::Small)
+                    .style(ButtonStyle::Subtle)
+                    .selected_style(ButtonStyle::Tinted(TintColor::Accent))
+                    .toggle_state(!self.hidden_symbol_kinds.contains(&kind))
+                    .on_click(cx.listener(move |outline_panel, _, window, cx| {
+                        outline_panel.toggle_symbol_kind(kind, window, cx);
+                    }))
+            }))
+            .child(div().flex_1())
+            .child(
+                PopoverMenu::new("outline-panel-sort-menu")
+                    .trigger_with_tooltip(
+                        IconButton::new("outline-panel-sort", IconName::ListTree)
+                            .shape(IconButtonShape::Square)
+                            .icon_size(IconSize::Small),
+                        Tooltip::text(sort_mode.label()),
+                    )
+                    .menu(move |window, cx| {
+                        let outline_panel = outline_panel.clone();
+                        Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                            for mode in OutlineSortMode::ALL {
+                                let outline_panel = outline_panel.clone();
+                                menu = menu.toggleable_entry(
+                                    mode.label(),
+                                    mode == sort_mode,
+                                    IconPosition::Start,
+                                    None,
+                                    move |window, cx| {
+                                        outline_panel
+                                            .update(cx, |outline_panel, cx| {
+                                                outline_panel.set_sort_mode(mode, window, cx)
+                                            })
+                                            .ok();
+                                    },
+                                );
+                            }
+                            menu
+                        }))
+                    }),
+            )
+            .child(
+                IconButton::new("outline-panel-follow-cursor", IconName::Crosshair)
+                    .shape(IconButtonShape::Square)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(self.follow_cursor)
+                    .tooltip(Tooltip::text(if self.follow_cursor {
+                        "Stop Following Cursor"
+                    } else {
+                        "Follow Cursor"
+                    }))
+                    .on_click(cx.listener(|outline_panel, _, window, cx| {
+                        outline_panel.toggle_follow_cursor(&ToggleFollowCursor, window, cx);
+                    })),
+            )
+    }
+
     fn render_filter_footer(&mut self, pinned: bool, cx: &mut Context<Self>) -> Div {
-        v_flex().flex_none().child(horizontal_separator(cx)).child(
-            h_flex()
-                .p_2()
-                .w_full()
-                .child(self.filter_editor.clone())
-                .child(
-                    div().child(
-                        IconButton::new(
-                            "outline-panel-menu",
-                            if pinned {
-                                IconName::Unpin
+        let show_outline_controls = matches!(self.mode, ItemsDisplayMode::Outline);
+        v_flex()
+            .flex_none()
+            .child(horizontal_separator(cx))
+            .when(show_outline_controls, |footer| {
+                footer.child(self.render_outline_controls(cx))
+            })
+            .child(
+                h_flex()
+                    .p_2()
+                    .w_full()
+                    .child(self.filter_editor.clone())
+                    .child(
+                        div().child(
+                            IconButton::new(
+                                "outline-panel-menu",
+                                if pinned {
+                                    IconName::Unpin
+                                } else {
+                                    IconName::Pin
+                                },
+                            )
+                            .tooltip(Tooltip::text(if pinned {
+                                "Unpin Outline"
                             } else {
-                                IconName::Pin
-                            },
-                        )
-                        .tooltip(Tooltip::text(if pinned {
-                            "Unpin Outline"
-                        } else {
-                            "Pin Active Outline"
-                        }))
-                        .shape(IconButtonShape::Square)
-                        .on_click(cx.listener(
-                            |outline_panel, _, window, cx| {
-                                outline_panel.toggle_active_editor_pin(
-                                    &ToggleActiveEditorPin,
-                                    window,
-                                    cx,
-                                );
-                            },
-                        )),
+                                "Pin Active Outline"
+                            }))
+                            .shape(IconButtonShape::Square)
+                            .on_click(cx.listener(
+                                |outline_panel, _, window, cx| {
+                                    outline_panel.toggle_active_editor_pin(
+                                        &ToggleActiveEditorPin,
+                                        window,
+                                        cx,
+                                    );
+                                },
+                            )),
+                        ),
                     ),
-                ),
-        )
+            )
     }
 
     fn buffers_inside_directory(
@@ -4828,8 +4987,8 @@ impl Panel for OutlinePanel {
             .then_some(IconName::ListTree)
     }
 
-    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<&'static str> {
-        Some("Outline Panel")
+    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<SharedString> {
+        Some("Outline Panel".into())
     }
 
     fn toggle_action(&self) -> Box<dyn Action> {
@@ -4938,6 +5097,18 @@ impl Render for OutlinePanel {
             .on_action(cx.listener(Self::copy_path))
             .on_action(cx.listener(Self::copy_relative_path))
             .on_action(cx.listener(Self::toggle_active_editor_pin))
+            .on_action(cx.listener(Self::toggle_follow_cursor))
+            .on_action(
+                cx.listener(|outline_panel, _: &SortByPosition, window, cx| {
+                    outline_panel.set_sort_mode(OutlineSortMode::Position, window, cx)
+                }),
+            )
+            .on_action(cx.listener(|outline_panel, _: &SortByName, window, cx| {
+                outline_panel.set_sort_mode(OutlineSortMode::Name, window, cx)
+            }))
+            .on_action(cx.listener(|outline_panel, _: &SortByKind, window, cx| {
+                outline_panel.set_sort_mode(OutlineSortMode::Kind, window, cx)
+            }))
             .on_action(cx.listener(Self::unfold_directory))
             .on_action(cx.listener(Self::fold_directory))
             .on_action(cx.listener(Self::open_excerpts))
@@ -6534,6 +6705,44 @@ outline: struct OutlineEntryExcerpt
         });
     }
 
+    #[gpui::test]
+    async fn test_toggle_follow_cursor_from_editor(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let root = path!("/root");
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(root, json!({ "lib.rs": "struct Foo;" }))
+            .await;
+        let project = Project::test(fs.clone(), [root.as_ref()], cx).await;
+        let workspace = add_outline_panel(&project, cx).await;
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let outline_panel = outline_panel(&workspace, cx);
+
+        workspace
+            .update(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    PathBuf::from(path!("/root/lib.rs")),
+                    OpenOptions {
+                        visible: Some(OpenVisible::All),
+                        ..Default::default()
+                    },
+                    window,
+                    cx,
+                )
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        outline_panel.read_with(cx, |outline_panel, _| assert!(outline_panel.follow_cursor));
+
+        // The editor is focused rather than the panel.
+        cx.dispatch_action(ToggleFollowCursor);
+        outline_panel.read_with(cx, |outline_panel, _| assert!(!outline_panel.follow_cursor));
+        cx.dispatch_action(ToggleFollowCursor);
+        outline_panel.read_with(cx, |outline_panel, _| assert!(outline_panel.follow_cursor));
+    }
+
     async fn add_outline_panel(
         project: &Entity<Project>,
         cx: &mut TestAppContext,
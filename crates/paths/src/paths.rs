@@ -265,6 +265,14 @@ pub fn snippets_dir() -> &'static PathBuf {
     SNIPPETS_DIR.get_or_init(|| config_dir().join("snippets"))
 }
 
+/// Returns the path to the project templates directory.
+///
+/// Each directory in it is a template that new projects can be created from.
+pub fn project_templates_dir() -> &'static PathBuf {
+    static PROJECT_TEMPLATES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    PROJECT_TEMPLATES_DIR.get_or_init(|| config_dir().join("templates"))
+}
+
 /// Returns the path to the contexts directory.
 ///
 /// This is where the saved contexts from the Assistant are stored.
@@ -374,6 +382,12 @@ pub fn remote_servers_dir() -> &'static PathBuf {
     REMOTE_SERVERS_DIR.get_or_init(|| data_dir().join("remote_servers"))
 }
 
+/// Returns the path to the directory where the files edited over SFTP are downloaded.
+pub fn sftp_files_dir() -> &'static PathBuf {
+    static SFTP_FILES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SFTP_FILES_DIR.get_or_init(|| temp_dir().join("sftp"))
+}
+
 /// Returns the relative path to a `.zed` folder within a project.
 pub fn local_settings_folder_relative_path() -> &'static Path {
     Path::new(".zed")
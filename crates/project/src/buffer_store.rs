@@ -1340,9 +1340,22 @@ impl BufferStore {
         mut cx: AsyncApp,
     ) -> Result<proto::BufferSaved> {
         let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
-        let (buffer, project_id) = this.read_with(&mut cx, |this, _| {
+        let (buffer, project_id) = this.read_with(&mut cx, |this, cx| {
+            let buffer = this.get_existing(buffer_id)?;
+            let worktree_store = this.worktree_store.read(cx);
+            worktree_store.authorize_guest_buffer_edit(
+                envelope.original_sender_id,
+                buffer.read(cx),
+                cx,
+            )?;
+            if let Some(new_path) = &envelope.payload.new_path {
+                worktree_store.authorize_guest_edit(
+                    envelope.original_sender_id,
+                    Some(WorktreeId::from_proto(new_path.worktree_id)),
+                )?;
+            }
             anyhow::Ok((
-                this.get_existing(buffer_id)?,
+                buffer,
                 this.downstream_client
                     .as_ref()
                     .map(|(_, project_id)| *project_id)
@@ -1486,7 +1499,13 @@ impl BufferStore {
             let mut buffers = HashSet::default();
             for buffer_id in &envelope.payload.buffer_ids {
                 let buffer_id = BufferId::new(*buffer_id)?;
-                buffers.insert(this.get_existing(buffer_id)?);
+                let buffer = this.get_existing(buffer_id)?;
+                this.worktree_store.read(cx).authorize_guest_buffer_edit(
+                    envelope.original_sender_id,
+                    buffer.read(cx),
+                    cx,
+                )?;
+                buffers.insert(buffer);
             }
             anyhow::Ok(this.reload_buffers(buffers, false, cx))
         })??;
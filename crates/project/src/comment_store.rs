@@ -0,0 +1,611 @@
+//! Review comments on code, kept in threads anchored to ranges of a buffer so they follow the
+//! edits made around them.
+//!
+//! The project's host owns the threads and broadcasts them to its guests, who send their new
+//! comments to the host instead of adding them themselves.
+use anyhow::{Context as _, Result};
+use collections::BTreeMap;
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, SharedString, Task};
+use language::{
+    Buffer, BufferSnapshot,
+    proto::{deserialize_anchor, serialize_anchor},
+};
+use rpc::{AnyProtoClient, TypedEnvelope, proto};
+use std::{ops::Range, path::Path, sync::Arc};
+use text::{Bias, Point};
+
+use crate::{Project, ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CommentThreadId(pub u64);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comment {
+    /// The GitHub login of the comment's author.
+    pub author: SharedString,
+    pub body: SharedString,
+    /// When the comment was written, in seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct CommentThread {
+    pub id: CommentThreadId,
+    pub range: Range<text::Anchor>,
+    pub resolved: bool,
+    /// The comment starting the thread, followed by its replies.
+    pub comments: Vec<Comment>,
+}
+
+/// A thread as it's stored in the database, where it can't be anchored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializedCommentThread {
+    pub path: Arc<Path>,
+    pub range: Range<Point>,
+    pub resolved: bool,
+    pub comments: Vec<Comment>,
+}
+
+#[derive(Clone)]
+struct CommentThreadsInFile {
+    buffer: Entity<Buffer>,
+    threads: Vec<CommentThread>,
+}
+
+#[derive(Clone)]
+struct LocalCommentStore {
+    worktree_store: Entity<WorktreeStore>,
+    buffer_store: Entity<BufferStore>,
+}
+
+#[derive(Clone)]
+struct RemoteCommentStore {
+    upstream_client: AnyProtoClient,
+    upstream_project_id: u64,
+}
+
+#[derive(Clone)]
+enum CommentStoreMode {
+    Local(LocalCommentStore),
+    Remote(RemoteCommentStore),
+}
+
+pub enum CommentStoreEvent {
+    ThreadsUpdated(Arc<Path>),
+    /// A reply was added to a thread, by anyone in the project.
+    Replied {
+        thread_id: CommentThreadId,
+        comment: Comment,
+    },
+}
+
+impl EventEmitter<CommentStoreEvent> for CommentStore {}
+
+pub struct CommentStore {
+    threads: BTreeMap<Arc<Path>, CommentThreadsInFile>,
+    next_thread_id: u64,
+    downstream_client: Option<(AnyProtoClient, u64)>,
+    mode: CommentStoreMode,
+}
+
+impl CommentStore {
+    pub fn init(client: &AnyProtoClient) {
+        client.add_entity_request_handler(Self::handle_create_comment_thread);
+        client.add_entity_request_handler(Self::handle_reply_to_comment_thread);
+        client.add_entity_request_handler(Self::handle_set_comment_thread_resolved);
+        client.add_entity_message_handler(Self::handle_comment_threads_for_file);
+    }
+
+    pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
+        Self {
+            threads: BTreeMap::default(),
+            next_thread_id: 0,
+            downstream_client: None,
+            mode: CommentStoreMode::Local(LocalCommentStore {
+                worktree_store,
+                buffer_store,
+            }),
+        }
+    }
+
+    pub(crate) fn remote(upstream_project_id: u64, upstream_client: AnyProtoClient) -> Self {
+        Self {
+            threads: BTreeMap::default(),
+            next_thread_id: 0,
+            downstream_client: None,
+            mode: CommentStoreMode::Remote(RemoteCommentStore {
+                upstream_client,
+                upstream_project_id,
+            }),
+        }
+    }
+
+    pub(crate) fn shared(&mut self, project_id: u64, downstream_client: AnyProtoClient) {
+        self.downstream_client = Some((downstream_client, project_id));
+    }
+
+    pub(crate) fn unshared(&mut self, cx: &mut Context<Self>) {
+        self.downstream_client.take();
+        cx.notify();
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self.mode, CommentStoreMode::Local(_))
+    }
+
+    /// All threads with the path and buffer of the file they're in, ordered by path and position.
+    pub fn threads(&self) -> impl Iterator<Item = (&Arc<Path>, &Entity<Buffer>, &CommentThread)> {
+        self.threads.iter().flat_map(|(path, threads_in_file)| {
+            threads_in_file
+                .threads
+                .iter()
+                .map(move |thread| (path, &threads_in_file.buffer, thread))
+        })
+    }
+
+    pub fn threads_for_buffer<'a>(
+        &'a self,
+        buffer: &Entity<Buffer>,
+    ) -> impl Iterator<Item = &'a CommentThread> + 'a {
+        let buffer_id = buffer.entity_id();
+        self.threads
+            .values()
+            .filter(move |threads_in_file| threads_in_file.buffer.entity_id() == buffer_id)
+            .flat_map(|threads_in_file| threads_in_file.threads.iter())
+    }
+
+    pub fn thread(&self, id: CommentThreadId) -> Option<(&Arc<Path>, &CommentThread)> {
+        self.threads.iter().find_map(|(path, threads_in_file)| {
+            let thread = threads_in_file
+                .threads
+                .iter()
+                .find(|thread| thread.id == id)?;
+            Some((path, thread))
+        })
+    }
+
+    pub fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+        worktree::File::from_dyn(buffer.read(cx).file())
+            .and_then(|file| file.worktree.read(cx).absolutize(&file.path).ok())
+            .map(Arc::<Path>::from)
+    }
+
+    /// Starts a thread on `range` with `comment`. On guests, the thread appears once the host
+    /// has added it.
+    pub fn create_thread(
+        &mut self,
+        buffer: Entity<Buffer>,
+        range: Range<text::Anchor>,
+        comment: Comment,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+        if let CommentStoreMode::Remote(remote) = &self.mode {
+            cx.background_spawn(remote.upstream_client.request(proto::CreateCommentThread {
+                project_id: remote.upstream_project_id,
+                path: abs_path.to_string_lossy().into_owned(),
+                start: Some(serialize_anchor(&range.start)),
+                end: Some(serialize_anchor(&range.end)),
+                comment: Some(comment.to_proto()),
+            }))
+            .detach();
+            return;
+        }
+
+        let id = self.next_thread_id();
+        let threads_in_file =
+            self.threads
+                .entry(abs_path.clone())
+                .or_insert_with(|| CommentThreadsInFile {
+                    buffer: buffer.clone(),
+                    threads: Vec::new(),
+                });
+        threads_in_file.threads.push(CommentThread {
+            id,
+            range,
+            resolved: false,
+            comments: vec![comment],
+        });
+        let snapshot = buffer.read(cx).snapshot();
+        threads_in_file
+            .threads
+            .sort_by(|a, b| a.range.start.cmp(&b.range.start, &snapshot));
+        self.threads_changed(abs_path, cx);
+    }
+
+    pub fn reply(&mut self, thread_id: CommentThreadId, comment: Comment, cx: &mut Context<Self>) {
+        if let CommentStoreMode::Remote(remote) = &self.mode {
+            cx.background_spawn(remote.upstream_client.request(proto::ReplyToCommentThread {
+                project_id: remote.upstream_project_id,
+                thread_id: thread_id.0,
+                comment: Some(comment.to_proto()),
+            }))
+            .detach();
+            return;
+        }
+
+        let Some((abs_path, thread)) = self.thread_mut(thread_id) else {
+            return;
+        };
+        thread.comments.push(comment.clone());
+        // Replying to a resolved thread brings the discussion back.
+        thread.resolved = false;
+        self.threads_changed(abs_path, cx);
+        cx.emit(CommentStoreEvent::Replied { thread_id, comment });
+    }
+
+    pub fn set_resolved(
+        &mut self,
+        thread_id: CommentThreadId,
+        resolved: bool,
+        cx: &mut Context<Self>,
+    ) {
+        if let CommentStoreMode::Remote(remote) = &self.mode {
+            cx.background_spawn(
+                remote
+                    .upstream_client
+                    .request(proto::SetCommentThreadResolved {
+                        project_id: remote.upstream_project_id,
+                        thread_id: thread_id.0,
+                        resolved,
+                    }),
+            )
+            .detach();
+            return;
+        }
+
+        let Some((abs_path, thread)) = self.thread_mut(thread_id) else {
+            return;
+        };
+        if thread.resolved != resolved {
+            thread.resolved = resolved;
+            self.threads_changed(abs_path, cx);
+        }
+    }
+
+    /// Deletes a thread with all its comments. Only the host can delete threads.
+    pub fn delete_thread(&mut self, thread_id: CommentThreadId, cx: &mut Context<Self>) {
+        if !self.is_local() {
+            return;
+        }
+        let Some((abs_path, _)) = self.thread_mut(thread_id) else {
+            return;
+        };
+        if let Some(threads_in_file) = self.threads.get_mut(&abs_path) {
+            threads_in_file
+                .threads
+                .retain(|thread| thread.id != thread_id);
+        }
+        self.threads_changed(abs_path, cx);
+    }
+
+    fn next_thread_id(&mut self) -> CommentThreadId {
+        let id = CommentThreadId(self.next_thread_id);
+        self.next_thread_id += 1;
+        id
+    }
+
+    fn thread_mut(&mut self, id: CommentThreadId) -> Option<(Arc<Path>, &mut CommentThread)> {
+        self.threads.iter_mut().find_map(|(path, threads_in_file)| {
+            let thread = threads_in_file
+                .threads
+                .iter_mut()
+                .find(|thread| thread.id == id)?;
+            Some((path.clone(), thread))
+        })
+    }
+
+    fn threads_changed(&mut self, abs_path: Arc<Path>, cx: &mut Context<Self>) {
+        if self
+            .threads
+            .get(&abs_path)
+            .is_some_and(|threads_in_file| threads_in_file.threads.is_empty())
+        {
+            self.threads.remove(&abs_path);
+        }
+        if let Some((client, project_id)) = &self.downstream_client {
+            client
+                .send(self.threads_for_file_message(*project_id, &abs_path))
+                .ok();
+        }
+        cx.emit(CommentStoreEvent::ThreadsUpdated(abs_path));
+        cx.notify();
+    }
+
+    fn threads_for_file_message(
+        &self,
+        project_id: u64,
+        abs_path: &Path,
+    ) -> proto::CommentThreadsForFile {
+        proto::CommentThreadsForFile {
+            project_id,
+            path: abs_path.to_string_lossy().into_owned(),
+            threads: self
+                .threads
+                .get(abs_path)
+                .map(|threads_in_file| {
+                    threads_in_file
+                        .threads
+                        .iter()
+                        .map(CommentThread::to_proto)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Sends every thread to the guests, like when one joins the project.
+    pub(crate) fn broadcast(&self) {
+        if let Some((client, project_id)) = &self.downstream_client {
+            for abs_path in self.threads.keys() {
+                client
+                    .send(self.threads_for_file_message(*project_id, abs_path))
+                    .ok();
+            }
+        }
+    }
+
+    pub fn serialized_threads(&self, cx: &App) -> Vec<SerializedCommentThread> {
+        self.threads
+            .iter()
+            .flat_map(|(path, threads_in_file)| {
+                let snapshot = threads_in_file.buffer.read(cx).snapshot();
+                threads_in_file
+                    .threads
+                    .iter()
+                    .map(move |thread| SerializedCommentThread {
+                        path: path.clone(),
+                        range: thread.point_range(&snapshot),
+                        resolved: thread.resolved,
+                        comments: thread.comments.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Restores the threads saved with the workspace, opening the files they're in to anchor
+    /// them.
+    pub fn with_serialized_threads(
+        &self,
+        threads: Vec<SerializedCommentThread>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let CommentStoreMode::Local(mode) = &self.mode else {
+            return Task::ready(Ok(()));
+        };
+        let mode = mode.clone();
+        cx.spawn(async move |this, cx| {
+            let mut threads_by_path =
+                BTreeMap::<Arc<Path>, Vec<SerializedCommentThread>>::default();
+            for thread in threads {
+                threads_by_path
+                    .entry(thread.path.clone())
+                    .or_default()
+                    .push(thread);
+            }
+
+            for (path, threads) in threads_by_path {
+                let (worktree, relative_path) = mode
+                    .worktree_store
+                    .update(cx, |this, cx| {
+                        this.find_or_create_worktree(&path, false, cx)
+                    })?
+                    .await?;
+                let buffer = mode
+                    .buffer_store
+                    .update(cx, |this, cx| {
+                        let path = ProjectPath {
+                            worktree_id: worktree.read(cx).id(),
+                            path: relative_path.into(),
+                        };
+                        this.open_buffer(path, cx)
+                    })?
+                    .await;
+                let Ok(buffer) = buffer else {
+                    log::error!(
+                        "skipping comment threads in a file that can't be opened: {path:?}"
+                    );
+                    continue;
+                };
+                let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+
+                this.update(cx, |this, cx| {
+                    let threads = threads
+                        .into_iter()
+                        .map(|thread| {
+                            let start = snapshot.clip_point(thread.range.start, Bias::Left);
+                            let end = snapshot.clip_point(thread.range.end, Bias::Right);
+                            CommentThread {
+                                id: this.next_thread_id(),
+                                range: snapshot.anchor_before(start)..snapshot.anchor_after(end),
+                                resolved: thread.resolved,
+                                comments: thread.comments,
+                            }
+                        })
+                        .collect();
+                    this.threads
+                        .insert(path.clone(), CommentThreadsInFile { buffer, threads });
+                    this.threads_changed(path, cx);
+                })?;
+            }
+            Ok(())
+        })
+    }
+
+    async fn handle_comment_threads_for_file(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::CommentThreadsForFile>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let comment_store = this.read_with(&cx, |this, _| this.comment_store())?;
+        let abs_path = Arc::<Path>::from(Path::new(&message.payload.path));
+        if message.payload.threads.is_empty() {
+            comment_store.update(&mut cx, |this, cx| {
+                if this.threads.remove(&abs_path).is_some() {
+                    cx.emit(CommentStoreEvent::ThreadsUpdated(abs_path));
+                    cx.notify();
+                }
+            })?;
+            return Ok(());
+        }
+
+        let buffer = this
+            .update(&mut cx, |this, cx| {
+                let path = this.project_path_for_absolute_path(&abs_path, cx)?;
+                Some(this.open_buffer(path, cx))
+            })?
+            .context("invalid project path")?
+            .await?;
+
+        comment_store.update(&mut cx, |this, cx| {
+            let threads = message
+                .payload
+                .threads
+                .into_iter()
+                .filter_map(CommentThread::from_proto)
+                .collect::<Vec<_>>();
+            let old_threads = this
+                .threads
+                .insert(abs_path.clone(), CommentThreadsInFile { buffer, threads })
+                .map(|threads_in_file| threads_in_file.threads)
+                .unwrap_or_default();
+
+            let mut replies = Vec::new();
+            for thread in &this.threads[&abs_path].threads {
+                // Replies are only ever appended, so new ones come after the comments known so far.
+                let Some(old_thread) = old_threads.iter().find(|old| old.id == thread.id) else {
+                    continue;
+                };
+                for comment in thread.comments.iter().skip(old_thread.comments.len()) {
+                    replies.push((thread.id, comment.clone()));
+                }
+            }
+
+            cx.emit(CommentStoreEvent::ThreadsUpdated(abs_path));
+            for (thread_id, comment) in replies {
+                cx.emit(CommentStoreEvent::Replied { thread_id, comment });
+            }
+            cx.notify();
+        })?;
+        Ok(())
+    }
+
+    async fn handle_create_comment_thread(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::CreateCommentThread>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let comment_store = this.read_with(&cx, |this, _| this.comment_store())?;
+        let buffer = this
+            .update(&mut cx, |this, cx| {
+                let path =
+                    this.project_path_for_absolute_path(message.payload.path.as_ref(), cx)?;
+                Some(this.open_buffer(path, cx))
+            })?
+            .context("could not resolve provided abs path")?
+            .await?;
+        let start = message
+            .payload
+            .start
+            .and_then(deserialize_anchor)
+            .context("invalid comment thread start")?;
+        let end = message
+            .payload
+            .end
+            .and_then(deserialize_anchor)
+            .context("invalid comment thread end")?;
+        let comment = message
+            .payload
+            .comment
+            .map(Comment::from_proto)
+            .context("comment not present in RPC payload")?;
+
+        comment_store.update(&mut cx, |this, cx| {
+            this.create_thread(buffer, start..end, comment, cx)
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_reply_to_comment_thread(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::ReplyToCommentThread>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let comment_store = this.read_with(&cx, |this, _| this.comment_store())?;
+        let comment = message
+            .payload
+            .comment
+            .map(Comment::from_proto)
+            .context("comment not present in RPC payload")?;
+        comment_store.update(&mut cx, |this, cx| {
+            this.reply(CommentThreadId(message.payload.thread_id), comment, cx)
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_set_comment_thread_resolved(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::SetCommentThreadResolved>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let comment_store = this.read_with(&cx, |this, _| this.comment_store())?;
+        comment_store.update(&mut cx, |this, cx| {
+            this.set_resolved(
+                CommentThreadId(message.payload.thread_id),
+                message.payload.resolved,
+                cx,
+            )
+        })?;
+        Ok(proto::Ack {})
+    }
+}
+
+impl CommentThread {
+    pub fn point_range(&self, snapshot: &BufferSnapshot) -> Range<Point> {
+        self.range.start.summary::<Point>(snapshot)..self.range.end.summary::<Point>(snapshot)
+    }
+
+    fn to_proto(&self) -> proto::CommentThread {
+        proto::CommentThread {
+            id: self.id.0,
+            start: Some(serialize_anchor(&self.range.start)),
+            end: Some(serialize_anchor(&self.range.end)),
+            resolved: self.resolved,
+            comments: self.comments.iter().map(Comment::to_proto).collect(),
+        }
+    }
+
+    fn from_proto(thread: proto::CommentThread) -> Option<Self> {
+        Some(Self {
+            id: CommentThreadId(thread.id),
+            range: deserialize_anchor(thread.start?)?..deserialize_anchor(thread.end?)?,
+            resolved: thread.resolved,
+            comments: thread
+                .comments
+                .into_iter()
+                .map(Comment::from_proto)
+                .collect(),
+        })
+    }
+}
+
+impl Comment {
+    fn to_proto(&self) -> proto::Comment {
+        proto::Comment {
+            author: self.author.to_string(),
+            body: self.body.to_string(),
+            timestamp: self.timestamp,
+        }
+    }
+
+    fn from_proto(comment: proto::Comment) -> Self {
+        Self {
+            author: comment.author.into(),
+            body: comment.body.into(),
+            timestamp: comment.timestamp,
+        }
+    }
+}
@@ -273,6 +273,11 @@ impl BreakpointStore {
                 this.project_path_for_absolute_path(message.payload.path.as_ref(), cx)
             })?
             .context("Could not resolve provided abs path")?;
+        this.read_with(&mut cx, |this, cx| {
+            this.worktree_store()
+                .read(cx)
+                .authorize_guest_edit(message.original_sender_id, Some(path.worktree_id))
+        })??;
         let buffer = this
             .update(&mut cx, |this, cx| {
                 this.buffer_store().read(cx).get_by_path(&path, cx)
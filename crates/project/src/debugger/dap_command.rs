@@ -1177,6 +1177,177 @@ impl DapCommand for LoadedSourcesCommand {
     }
 }
 
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct ReadMemoryCommand {
+    pub memory_reference: String,
+    pub offset: Option<i64>,
+    pub count: u64,
+}
+
+impl LocalDapCommand for ReadMemoryCommand {
+    type Response = dap::ReadMemoryResponse;
+    type DapRequest = dap::requests::ReadMemory;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_read_memory_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::ReadMemoryArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            count: self.count,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
+impl DapCommand for ReadMemoryCommand {
+    type ProtoRequest = proto::DapReadMemoryRequest;
+    type ProtoResponse = proto::DapReadMemoryResponse;
+    const CACHEABLE: bool = true;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            memory_reference: request.memory_reference.clone(),
+            offset: request.offset,
+            count: request.count,
+        }
+    }
+
+    fn to_proto(
+        &self,
+        debug_client_id: SessionId,
+        upstream_project_id: u64,
+    ) -> proto::DapReadMemoryRequest {
+        proto::DapReadMemoryRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            count: self.count,
+        }
+    }
+
+    fn response_to_proto(
+        debug_client_id: SessionId,
+        message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::DapReadMemoryResponse {
+            client_id: debug_client_id.to_proto(),
+            address: message.address,
+            unreadable_bytes: message.unreadable_bytes,
+            data: message.data,
+        }
+    }
+
+    fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(dap::ReadMemoryResponse {
+            address: message.address,
+            unreadable_bytes: message.unreadable_bytes,
+            data: message.data,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct DisassembleCommand {
+    pub memory_reference: String,
+    pub offset: Option<i64>,
+    pub instruction_offset: Option<i64>,
+    pub instruction_count: u64,
+}
+
+impl LocalDapCommand for DisassembleCommand {
+    type Response = Vec<dap::DisassembledInstruction>;
+    type DapRequest = dap::requests::Disassemble;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_disassemble_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::DisassembleArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            instruction_offset: self.instruction_offset,
+            instruction_count: self.instruction_count,
+            resolve_symbols: Some(true),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.instructions)
+    }
+}
+
+impl DapCommand for DisassembleCommand {
+    type ProtoRequest = proto::DapDisassembleRequest;
+    type ProtoResponse = proto::DapDisassembleResponse;
+    const CACHEABLE: bool = true;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            memory_reference: request.memory_reference.clone(),
+            offset: request.offset,
+            instruction_offset: request.instruction_offset,
+            instruction_count: request.instruction_count,
+        }
+    }
+
+    fn to_proto(
+        &self,
+        debug_client_id: SessionId,
+        upstream_project_id: u64,
+    ) -> proto::DapDisassembleRequest {
+        proto::DapDisassembleRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            instruction_offset: self.instruction_offset,
+            instruction_count: self.instruction_count,
+        }
+    }
+
+    fn response_to_proto(
+        debug_client_id: SessionId,
+        message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::DapDisassembleResponse {
+            client_id: debug_client_id.to_proto(),
+            instructions: message.to_proto(),
+        }
+    }
+
+    fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(Vec::<dap::DisassembledInstruction>::from_proto(
+            message.instructions,
+        ))
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub(crate) struct StackTraceCommand {
     pub thread_id: u64,
@@ -4,15 +4,16 @@ use super::breakpoint_store::{
     BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand, StackTraceCommand,
-    StepBackCommand, StepCommand, StepInCommand, StepOutCommand, TerminateCommand,
-    TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisassembleCommand,
+    DisconnectCommand, EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand,
+    LocationsCommand, ModulesCommand, NextCommand, PauseCommand, ReadMemoryCommand, RestartCommand,
+    RestartStackFrameCommand, ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand,
+    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
+    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use anyhow::{Context as _, Result, anyhow};
+use base64::Engine as _;
 use collections::{HashMap, HashSet, IndexMap, IndexSet};
 use dap::adapters::{DebugAdapterBinary, DebugAdapterName};
 use dap::messages::Response;
@@ -80,6 +81,47 @@ impl From<dap::StackFrame> for StackFrame {
     }
 }
 
+/// A contiguous region of debuggee memory returned by a `readMemory` request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryBlock {
+    pub address: u64,
+    pub bytes: Vec<u8>,
+    /// Number of bytes following `bytes` that the adapter could not read.
+    pub unreadable_bytes: u64,
+}
+
+impl MemoryBlock {
+    fn from_dap(response: dap::ReadMemoryResponse) -> Result<Self> {
+        let address = parse_memory_address(&response.address)
+            .with_context(|| format!("Invalid memory address {:?}", response.address))?;
+        let bytes = match response.data {
+            Some(data) => base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .context("Decoding memory contents")?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            address,
+            bytes,
+            unreadable_bytes: response.unreadable_bytes.unwrap_or_default(),
+        })
+    }
+}
+
+/// Parses an address as reported by a debug adapter, which is usually hexadecimal with a `0x` prefix.
+pub fn parse_memory_address(address: &str) -> Option<u64> {
+    let address = address.trim();
+    if let Some(hex) = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16).ok()
+    } else {
+        address.parse().ok()
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum ThreadStatus {
     #[default]
@@ -591,6 +633,8 @@ pub struct Session {
     variables: HashMap<VariableReference, Vec<dap::Variable>>,
     stack_frames: IndexMap<StackFrameId, StackFrame>,
     locations: HashMap<u64, dap::LocationsResponse>,
+    memory: HashMap<ReadMemoryCommand, MemoryBlock>,
+    disassembly: HashMap<DisassembleCommand, Vec<dap::DisassembledInstruction>>,
     is_session_terminated: bool,
     requests: HashMap<TypeId, HashMap<RequestSlot, Shared<Task<Option<()>>>>>,
     pub(crate) breakpoint_store: Entity<BreakpointStore>,
@@ -675,6 +719,8 @@ impl CompletionsQuery {
 pub enum SessionEvent {
     Modules,
     LoadedSources,
+    Memory,
+    Disassembly,
     Stopped(Option<ThreadId>),
     StackTrace,
     Variables,
@@ -755,6 +801,8 @@ impl Session {
                 threads: IndexMap::default(),
                 background_tasks: Vec::default(),
                 locations: Default::default(),
+                memory: Default::default(),
+                disassembly: Default::default(),
                 is_session_terminated: false,
                 ignore_breakpoints: false,
                 breakpoint_store,
@@ -1457,6 +1505,8 @@ impl Session {
         self.invalidate_command_type::<ModulesCommand>();
         self.invalidate_command_type::<LoadedSourcesCommand>();
         self.invalidate_command_type::<ThreadsCommand>();
+        self.invalidate_command_type::<ReadMemoryCommand>();
+        self.invalidate_command_type::<DisassembleCommand>();
     }
 
     fn invalidate_state(&mut self, key: &RequestSlot) {
@@ -1613,6 +1663,74 @@ impl Session {
         &self.loaded_sources
     }
 
+    /// Returns the last known contents of `count` bytes at `offset` from `memory_reference`,
+    /// fetching them from the adapter if they are missing or stale.
+    pub fn read_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        count: u64,
+        cx: &mut Context<Self>,
+    ) -> Option<MemoryBlock> {
+        let command = ReadMemoryCommand {
+            memory_reference,
+            offset: Some(offset),
+            count,
+        };
+        self.fetch(
+            command.clone(),
+            {
+                let command = command.clone();
+                move |this, response, cx| {
+                    let response = response.log_err()?;
+                    let block = MemoryBlock::from_dap(response.clone()).log_err()?;
+                    this.memory.insert(command, block);
+                    cx.emit(SessionEvent::Memory);
+                    cx.notify();
+                    Some(response)
+                }
+            },
+            cx,
+        );
+
+        self.memory.get(&command).cloned()
+    }
+
+    /// Returns `instruction_count` disassembled instructions starting `instruction_offset`
+    /// instructions away from `memory_reference`.
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Option<&[dap::DisassembledInstruction]> {
+        let command = DisassembleCommand {
+            memory_reference,
+            offset: None,
+            instruction_offset: Some(instruction_offset),
+            instruction_count,
+        };
+        self.fetch(
+            command.clone(),
+            {
+                let command = command.clone();
+                move |this, instructions, cx| {
+                    let instructions = instructions.log_err()?;
+                    this.disassembly.insert(command, instructions.clone());
+                    cx.emit(SessionEvent::Disassembly);
+                    cx.notify();
+                    Some(instructions)
+                }
+            },
+            cx,
+        );
+
+        self.disassembly
+            .get(&command)
+            .map(|instructions| instructions.as_slice())
+    }
+
     fn fallback_to_manual_restart(
         &mut self,
         res: Result<()>,
@@ -1,39 +1,125 @@
 use futures::{FutureExt, future::Shared};
 use language::Buffer;
-use std::{path::Path, sync::Arc};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use util::ResultExt;
 use worktree::Worktree;
 
 use collections::HashMap;
-use gpui::{AppContext as _, Context, Entity, EventEmitter, Task};
-use settings::Settings as _;
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Task};
+use settings::{Settings as _, SettingsLocation};
 
 use crate::{
+    TrustedFolders,
     project_settings::{DirenvSettings, ProjectSettings},
-    worktree_store::WorktreeStore,
+    worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 
+/// The files that configure direnv, mise, or asdf, or that pick a tool's version, which make a
+/// directory's environment differ from its parent's.
+const ENVIRONMENT_FILE_NAMES: &[&str] = &[
+    ".envrc",
+    "mise.toml",
+    ".mise.toml",
+    "mise.local.toml",
+    ".mise.local.toml",
+    ".tool-versions",
+    ".nvmrc",
+    ".node-version",
+    ".python-version",
+    ".ruby-version",
+];
+
+/// The files that configure mise, including the asdf ones that it reads too. mise only runs in
+/// directories that have one of them or are below one that does.
+const MISE_FILE_NAMES: &[&str] = &[
+    "mise.toml",
+    ".mise.toml",
+    "mise.local.toml",
+    ".mise.local.toml",
+    ".tool-versions",
+];
+
 pub struct ProjectEnvironment {
     cli_environment: Option<HashMap<String, String>>,
+    worktree_store: Entity<WorktreeStore>,
     environments: HashMap<Arc<Path>, Shared<Task<Option<HashMap<String, String>>>>>,
+    /// The variables that direnv and mise set in each directory whose environment was loaded.
+    tool_variables: HashMap<Arc<Path>, HashMap<String, String>>,
+    terminal_variables: HashMap<Arc<Path>, HashMap<String, String>>,
     environment_error_messages: HashMap<Arc<Path>, EnvironmentErrorMessage>,
 }
 
 pub enum ProjectEnvironmentEvent {
     ErrorsUpdated,
+    /// A configuration file changed in the directory, so the environments of it and the
+    /// directories below it will be loaded again.
+    EnvironmentChanged(Arc<Path>),
 }
 
 impl EventEmitter<ProjectEnvironmentEvent> for ProjectEnvironment {}
 
 impl ProjectEnvironment {
-    pub fn new(cli_environment: Option<HashMap<String, String>>) -> Self {
+    pub fn new(
+        cli_environment: Option<HashMap<String, String>>,
+        worktree_store: Entity<WorktreeStore>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        cx.subscribe(&worktree_store, Self::on_worktree_store_event)
+            .detach();
+        // Trusting or revoking trust in folders changes whether their direnv and mise
+        // configurations are loaded.
+        cx.observe_global::<TrustedFolders>(|this, _| {
+            this.environments.clear();
+            this.tool_variables.clear();
+        })
+        .detach();
         Self {
             cli_environment,
+            worktree_store,
             environments: Default::default(),
+            tool_variables: Default::default(),
+            terminal_variables: Default::default(),
             environment_error_messages: Default::default(),
         }
     }
 
+    fn on_worktree_store_event(
+        &mut self,
+        worktree_store: Entity<WorktreeStore>,
+        event: &WorktreeStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        let WorktreeStoreEvent::WorktreeUpdatedEntries(worktree_id, changes) = event else {
+            return;
+        };
+        let Some(worktree) = worktree_store.read(cx).worktree_for_id(*worktree_id, cx) else {
+            return;
+        };
+        let root = worktree.read(cx).abs_path();
+        for (path, _, _) in changes.iter() {
+            if !is_environment_file(path) {
+                continue;
+            }
+            let directory = root.join(path.parent().unwrap_or(Path::new("")));
+            // The directories below it can use its configuration too, through direnv's
+            // `source_up` or mise's parent configuration files.
+            self.environments
+                .retain(|abs_path, _| !abs_path.starts_with(&directory));
+            self.tool_variables
+                .retain(|abs_path, _| !abs_path.starts_with(&directory));
+            self.terminal_variables
+                .retain(|abs_path, _| !abs_path.starts_with(&directory));
+            log::info!("reloading the environment of {directory:?} after {path:?} changed");
+            cx.emit(ProjectEnvironmentEvent::EnvironmentChanged(
+                directory.into(),
+            ));
+        }
+    }
+
     /// Returns the inherited CLI environment, if this project was opened from the Zed CLI.
     pub(crate) fn get_cli_environment(&self) -> Option<HashMap<String, String>> {
         if let Some(mut env) = self.cli_environment.clone() {
@@ -63,25 +149,36 @@ impl ProjectEnvironment {
         worktree_store: &Entity<WorktreeStore>,
         cx: &mut Context<Self>,
     ) -> Shared<Task<Option<HashMap<String, String>>>> {
-        if cfg!(any(test, feature = "test-support")) {
-            return Task::ready(Some(HashMap::default())).shared();
-        }
-
-        if let Some(cli_environment) = self.get_cli_environment() {
-            log::debug!("using project environment variables from CLI");
-            return Task::ready(Some(cli_environment)).shared();
-        }
-
-        let Some(worktree) = buffer
-            .read(cx)
-            .file()
-            .map(|f| f.worktree_id(cx))
-            .and_then(|worktree_id| worktree_store.read(cx).worktree_for_id(worktree_id, cx))
-        else {
-            return Task::ready(None).shared();
+        let Some((file, worktree)) = buffer.read(cx).file().and_then(|file| {
+            let worktree = worktree_store
+                .read(cx)
+                .worktree_for_id(file.worktree_id(cx), cx)?;
+            Some((file, worktree))
+        }) else {
+            if cfg!(any(test, feature = "test-support")) {
+                return Task::ready(Some(HashMap::default())).shared();
+            }
+            return Task::ready(self.get_cli_environment()).shared();
         };
 
-        self.get_worktree_environment(worktree, cx)
+        // Use the buffer's directory, so that it gets the environment of its package in a
+        // monorepo.
+        let directory = {
+            let worktree = worktree.read(cx);
+            if worktree.is_local() && !worktree.is_single_file() {
+                worktree
+                    .abs_path()
+                    .join(file.path())
+                    .parent()
+                    .map(Arc::<Path>::from)
+            } else {
+                None
+            }
+        };
+        match directory {
+            Some(directory) => self.get_directory_environment(directory, cx),
+            None => self.get_worktree_environment(worktree, cx),
+        }
     }
 
     pub(crate) fn get_worktree_environment(
@@ -89,16 +186,11 @@ impl ProjectEnvironment {
         worktree: Entity<Worktree>,
         cx: &mut Context<Self>,
     ) -> Shared<Task<Option<HashMap<String, String>>>> {
-        if cfg!(any(test, feature = "test-support")) {
-            return Task::ready(Some(HashMap::default())).shared();
-        }
-
-        if let Some(cli_environment) = self.get_cli_environment() {
-            log::debug!("using project environment variables from CLI");
-            return Task::ready(Some(cli_environment)).shared();
+        let mut abs_path = worktree.read(cx).abs_path();
+        if cfg!(any(test, feature = "test-support")) || self.cli_environment.is_some() {
+            return self.get_directory_environment(abs_path, cx);
         }
 
-        let mut abs_path = worktree.read(cx).abs_path();
         if !worktree.read(cx).is_local() {
             log::error!(
                 "attempted to get project environment for a non-local worktree at {abs_path:?}"
@@ -117,11 +209,74 @@ impl ProjectEnvironment {
     /// Returns the project environment, if possible.
     /// If the project was opened from the CLI, then the inherited CLI environment is returned.
     /// If it wasn't opened from the CLI, and an absolute path is given, then a shell is spawned in
-    /// that directory, to get environment variables as if the user has `cd`'d there.
+    /// the closest directory with a direnv or mise configuration, to get environment variables as
+    /// if the user has `cd`'d there. Either way, the variables from the `env` setting are applied
+    /// on top.
     pub(crate) fn get_directory_environment(
         &mut self,
         abs_path: Arc<Path>,
         cx: &mut Context<Self>,
+    ) -> Shared<Task<Option<HashMap<String, String>>>> {
+        let environment = self.get_inherited_environment(abs_path.clone(), cx);
+        let Some(layers) = self.project_variable_layers(&abs_path, cx) else {
+            return environment;
+        };
+        cx.background_spawn(async move {
+            let mut environment = environment.await?;
+            apply_project_variables(layers, &mut environment).await;
+            Some(environment)
+        })
+        .shared()
+    }
+
+    /// Resolves the variables that direnv, mise, and the `env` setting set in the directory, and
+    /// remembers them for [`Self::terminal_variables`].
+    pub(crate) fn load_terminal_variables(
+        &mut self,
+        abs_path: Arc<Path>,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let layers = self.project_variable_layers(&abs_path, cx);
+        let settings = ProjectSettings::get_global(cx);
+        let loads_tools = (settings.load_mise && self.has_mise_configuration(&abs_path, cx))
+            || matches!(settings.load_direnv, DirenvSettings::Direct);
+        // Waiting for a shell to load the environment would only slow down the terminal.
+        if layers.is_none() && !loads_tools {
+            self.terminal_variables.remove(&abs_path);
+            return Task::ready(());
+        }
+        let environment = self.get_inherited_environment(abs_path.clone(), cx);
+        let directory = self.environment_directory(abs_path.clone(), cx);
+        cx.spawn(async move |this, cx| {
+            let mut environment = environment.await.unwrap_or_default();
+            let project_variables = match layers {
+                Some(layers) => apply_project_variables(layers, &mut environment).await,
+                None => HashMap::default(),
+            };
+            this.update(cx, |this, _| {
+                let mut variables = this
+                    .tool_variables
+                    .get(&directory)
+                    .cloned()
+                    .unwrap_or_default();
+                variables.extend(project_variables);
+                this.terminal_variables.insert(abs_path, variables);
+            })
+            .ok();
+        })
+    }
+
+    /// The variables that direnv, mise, and the `env` setting set in the directory when they
+    /// were last loaded with [`Self::load_terminal_variables`]. Shells load the rest of their
+    /// environment themselves, so terminals only need these.
+    pub(crate) fn terminal_variables(&self, abs_path: &Path) -> Option<&HashMap<String, String>> {
+        self.terminal_variables.get(abs_path)
+    }
+
+    fn get_inherited_environment(
+        &mut self,
+        abs_path: Arc<Path>,
+        cx: &mut Context<Self>,
     ) -> Shared<Task<Option<HashMap<String, String>>>> {
         if cfg!(any(test, feature = "test-support")) {
             return Task::ready(Some(HashMap::default())).shared();
@@ -132,11 +287,226 @@ impl ProjectEnvironment {
             return Task::ready(Some(cli_environment)).shared();
         }
 
+        let directory = self.environment_directory(abs_path, cx);
+        let has_mise_configuration = self.has_mise_configuration(&directory, cx);
         self.environments
-            .entry(abs_path.clone())
-            .or_insert_with(|| get_directory_env_impl(abs_path.clone(), cx).shared())
+            .entry(directory.clone())
+            .or_insert_with(|| {
+                get_directory_env_impl(directory, has_mise_configuration, cx).shared()
+            })
             .clone()
     }
+
+    /// The directory whose shell environment applies to the path: the closest one in its
+    /// worktree with a direnv or mise configuration, so that each package of a monorepo gets its
+    /// own tools, or otherwise the worktree's root.
+    fn environment_directory(&self, abs_path: Arc<Path>, cx: &App) -> Arc<Path> {
+        let Some((worktree, path)) = self.worktree_store.read(cx).find_worktree(&abs_path, cx)
+        else {
+            return abs_path;
+        };
+        let worktree = worktree.read(cx);
+        if worktree.is_single_file() {
+            return abs_path;
+        }
+        let directory = path.ancestors().find(|directory| {
+            ENVIRONMENT_FILE_NAMES
+                .iter()
+                .any(|name| worktree.entry_for_path(directory.join(name)).is_some())
+        });
+        match directory {
+            Some(directory) if !directory.as_os_str().is_empty() => {
+                worktree.abs_path().join(directory).into()
+            }
+            _ => worktree.abs_path(),
+        }
+    }
+
+    /// Whether mise has a configuration to load in the directory, in it or in one of the
+    /// directories above it in its worktree.
+    fn has_mise_configuration(&self, abs_path: &Path, cx: &App) -> bool {
+        let Some((worktree, path)) = self.worktree_store.read(cx).find_worktree(abs_path, cx)
+        else {
+            return false;
+        };
+        let worktree = worktree.read(cx);
+        path.ancestors().any(|directory| {
+            MISE_FILE_NAMES
+                .iter()
+                .any(|name| worktree.entry_for_path(directory.join(name)).is_some())
+        })
+    }
+
+    /// The `.env` files and variables of the `env` setting that's in effect for the directory, or
+    /// `None` if it doesn't set any.
+    fn project_variable_layers(&self, abs_path: &Path, cx: &App) -> Option<Vec<EnvLayer>> {
+        let worktree_store = self.worktree_store.read(cx);
+        // The `.env` files are read from this machine, where remote projects' files aren't.
+        if worktree_store.fs().is_none() {
+            return None;
+        }
+        let (root, settings) = match worktree_store.find_worktree(abs_path, cx) {
+            Some((worktree, path)) => {
+                let worktree = worktree.read(cx);
+                let root = if worktree.is_single_file() {
+                    abs_path.parent().unwrap_or(abs_path).to_path_buf()
+                } else {
+                    worktree.abs_path().to_path_buf()
+                };
+                let location = SettingsLocation {
+                    worktree_id: worktree.id(),
+                    path: &path,
+                };
+                (root, &ProjectSettings::get(Some(location), cx).env)
+            }
+            None => (abs_path.to_path_buf(), &ProjectSettings::get_global(cx).env),
+        };
+        if settings.is_empty() {
+            return None;
+        }
+
+        Some(
+            settings
+                .layers()
+                .map(|(files, variables)| EnvLayer {
+                    files: files.iter().map(|file| root.join(file)).collect(),
+                    variables: variables
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.clone()))
+                        .collect(),
+                })
+                .collect(),
+        )
+    }
+}
+
+fn is_environment_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| ENVIRONMENT_FILE_NAMES.contains(&name))
+}
+
+struct EnvLayer {
+    files: Vec<PathBuf>,
+    variables: Vec<(String, String)>,
+}
+
+/// Applies the layers to the environment, returning the variables that they set. The `.env`
+/// files are read again each time, so that edits to them apply to the next terminal or task.
+async fn apply_project_variables(
+    layers: Vec<EnvLayer>,
+    environment: &mut HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut project_variables = HashMap::default();
+    for layer in layers {
+        for file in layer.files {
+            load_dotenv_file(&file, environment, &mut project_variables).await;
+        }
+        // Values refer to the variables from before the layer, so that they don't depend on the
+        // order of the map.
+        let expanded = layer
+            .variables
+            .into_iter()
+            .map(|(name, value)| (name, expand_variables(&value, environment)))
+            .collect::<Vec<_>>();
+        for (name, value) in expanded {
+            environment.insert(name.clone(), value.clone());
+            project_variables.insert(name, value);
+        }
+    }
+    project_variables
+}
+
+async fn load_dotenv_file(
+    path: &Path,
+    environment: &mut HashMap<String, String>,
+    project_variables: &mut HashMap<String, String>,
+) {
+    let text = match smol::fs::read_to_string(path).await {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+        Err(error) => {
+            log::error!("failed to load environment variables from {path:?}: {error}");
+            return;
+        }
+    };
+    for (name, value, expand) in parse_dotenv(&text) {
+        let value = if expand {
+            expand_variables(&value, environment)
+        } else {
+            value
+        };
+        environment.insert(name.clone(), value.clone());
+        project_variables.insert(name, value);
+    }
+}
+
+/// Parses the `NAME=value` lines of a `.env` file, returning whether each value can refer to
+/// other variables, which single-quoted values can't.
+fn parse_dotenv(text: &str) -> Vec<(String, String, bool)> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return None;
+            }
+            let value = value.trim();
+            let (value, expand) = if let Some(value) = unquote(value, '\'') {
+                (value.to_string(), false)
+            } else if let Some(value) = unquote(value, '"') {
+                (value.replace("\\n", "\n").replace("\\\"", "\""), true)
+            } else {
+                let value = match value.find(" #") {
+                    Some(comment_start) => value[..comment_start].trim_end(),
+                    None => value,
+                };
+                (value.to_string(), true)
+            };
+            Some((name.to_string(), value, expand))
+        })
+        .collect()
+}
+
+fn unquote(value: &str, quote: char) -> Option<&str> {
+    let value = value.strip_prefix(quote)?;
+    let end = value.rfind(quote)?;
+    Some(&value[..end])
+}
+
+/// Replaces each `${NAME}` and `$NAME` in the value with the variable's value, or with nothing
+/// when the variable isn't set, like a shell would.
+fn expand_variables(value: &str, environment: &HashMap<String, String>) -> String {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        let after_dollar = &rest[dollar + 1..];
+        let (name, after_name) = if let Some(braced) = after_dollar.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", after_dollar),
+            }
+        } else {
+            let end = after_dollar
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after_dollar.len());
+            (&after_dollar[..end], &after_dollar[end..])
+        };
+        if name.is_empty() {
+            expanded.push('$');
+        } else if let Some(value) = environment.get(name) {
+            expanded.push_str(value);
+        }
+        rest = after_name;
+    }
+    expanded.push_str(rest);
+    expanded
 }
 
 fn set_origin_marker(env: &mut HashMap<String, String>, origin: EnvironmentOrigin) {
@@ -175,13 +545,19 @@ impl EnvironmentErrorMessage {
     }
 }
 
+#[derive(Default)]
+struct LoadedEnvironment {
+    shell_env: Option<HashMap<String, String>>,
+    /// The variables that direnv and mise set, which are included in `shell_env`.
+    tool_variables: HashMap<String, String>,
+    error_message: Option<EnvironmentErrorMessage>,
+}
+
 async fn load_directory_shell_environment(
     abs_path: &Path,
     load_direnv: &DirenvSettings,
-) -> (
-    Option<HashMap<String, String>>,
-    Option<EnvironmentErrorMessage>,
-) {
+    load_mise: bool,
+) -> LoadedEnvironment {
     match smol::fs::metadata(abs_path).await {
         Ok(meta) => {
             let dir = if meta.is_dir() {
@@ -189,25 +565,25 @@ async fn load_directory_shell_environment(
             } else if let Some(parent) = abs_path.parent() {
                 parent
             } else {
-                return (
-                    None,
-                    Some(EnvironmentErrorMessage(format!(
+                return LoadedEnvironment {
+                    error_message: Some(EnvironmentErrorMessage(format!(
                         "Failed to load shell environment in {}: not a directory",
                         abs_path.display()
                     ))),
-                );
+                    ..Default::default()
+                };
             };
 
-            load_shell_environment(&dir, load_direnv).await
+            load_shell_environment(&dir, load_direnv, load_mise).await
         }
-        Err(err) => (
-            None,
-            Some(EnvironmentErrorMessage(format!(
+        Err(err) => LoadedEnvironment {
+            error_message: Some(EnvironmentErrorMessage(format!(
                 "Failed to load shell environment in {}: {}",
                 abs_path.display(),
                 err
             ))),
-        ),
+            ..Default::default()
+        },
     }
 }
 
@@ -215,37 +591,34 @@ async fn load_directory_shell_environment(
 async fn load_shell_environment(
     _dir: &Path,
     _load_direnv: &DirenvSettings,
-) -> (
-    Option<HashMap<String, String>>,
-    Option<EnvironmentErrorMessage>,
-) {
+    _load_mise: bool,
+) -> LoadedEnvironment {
     let fake_env = [("ZED_FAKE_TEST_ENV".into(), "true".into())]
         .into_iter()
         .collect();
-    (Some(fake_env), None)
+    LoadedEnvironment {
+        shell_env: Some(fake_env),
+        ..Default::default()
+    }
 }
 
 #[cfg(all(target_os = "windows", not(any(test, feature = "test-support"))))]
 async fn load_shell_environment(
     _dir: &Path,
     _load_direnv: &DirenvSettings,
-) -> (
-    Option<HashMap<String, String>>,
-    Option<EnvironmentErrorMessage>,
-) {
+    _load_mise: bool,
+) -> LoadedEnvironment {
     // TODO the current code works with Unix $SHELL only, implement environment loading on windows
-    (None, None)
+    LoadedEnvironment::default()
 }
 
 #[cfg(not(any(target_os = "windows", test, feature = "test-support")))]
 async fn load_shell_environment(
     dir: &Path,
     load_direnv: &DirenvSettings,
-) -> (
-    Option<HashMap<String, String>>,
-    Option<EnvironmentErrorMessage>,
-) {
-    use crate::direnv::load_direnv_environment;
+    load_mise: bool,
+) -> LoadedEnvironment {
+    use crate::{direnv::load_direnv_environment, mise::load_mise_environment};
     use util::shell_env;
 
     let dir_ = dir.to_owned();
@@ -253,12 +626,12 @@ async fn load_shell_environment(
         Ok(envs) => envs,
         Err(err) => {
             util::log_err(&err);
-            return (
-                None,
-                Some(EnvironmentErrorMessage::from_str(
+            return LoadedEnvironment {
+                error_message: Some(EnvironmentErrorMessage::from_str(
                     "Failed to load environment variables. See log for details",
                 )),
-            );
+                ..Default::default()
+            };
         }
     };
 
@@ -273,27 +646,68 @@ async fn load_shell_environment(
             Err(err) => (None, err.into()),
         },
     };
+    let mut tool_variables = HashMap::default();
     if let Some(direnv_environment) = direnv_environment {
-        envs.extend(direnv_environment);
+        envs.extend(direnv_environment.clone());
+        tool_variables.extend(direnv_environment);
+    }
+
+    // mise runs after direnv, so that it sees the `PATH` that direnv set up.
+    let (mise_environment, mise_error) = if load_mise {
+        match load_mise_environment(&envs, dir).await {
+            Ok(env) => (Some(env), None),
+            Err(err) => (None, err.into()),
+        }
+    } else {
+        (None, None)
+    };
+    if let Some(mise_environment) = mise_environment {
+        envs.extend(mise_environment.clone());
+        tool_variables.extend(mise_environment);
     }
 
-    (Some(envs), direnv_error)
+    LoadedEnvironment {
+        shell_env: Some(envs),
+        tool_variables,
+        error_message: direnv_error.or(mise_error),
+    }
 }
 
 fn get_directory_env_impl(
     abs_path: Arc<Path>,
+    has_mise_configuration: bool,
     cx: &Context<ProjectEnvironment>,
 ) -> Task<Option<HashMap<String, String>>> {
-    let load_direnv = ProjectSettings::get_global(cx).load_direnv.clone();
+    let settings = ProjectSettings::get_global(cx);
+    // Loading direnv and mise configurations runs the commands in them, so only the shell's own
+    // hooks load them in folders that aren't trusted.
+    let trusted = TrustedFolders::is_trusted(&abs_path, cx);
+    let load_direnv = if trusted {
+        settings.load_direnv.clone()
+    } else {
+        DirenvSettings::ShellHook
+    };
+    let load_mise = settings.load_mise && has_mise_configuration && trusted;
 
     cx.spawn(async move |this, cx| {
-        let (mut shell_env, error_message) = cx
+        let LoadedEnvironment {
+            mut shell_env,
+            tool_variables,
+            error_message,
+        } = cx
             .background_spawn({
                 let abs_path = abs_path.clone();
-                async move { load_directory_shell_environment(&abs_path, &load_direnv).await }
+                async move {
+                    load_directory_shell_environment(&abs_path, &load_direnv, load_mise).await
+                }
             })
             .await;
 
+        this.update(cx, |this, _| {
+            this.tool_variables.insert(abs_path.clone(), tool_variables);
+        })
+        .log_err();
+
         if let Some(shell_env) = shell_env.as_mut() {
             let path = shell_env
                 .get("PATH")
@@ -320,3 +734,197 @@ fn get_directory_env_impl(
         shell_env
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Project, project_tests::init_test};
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use std::{cell::RefCell, rc::Rc};
+    use util::path;
+
+    async fn monorepo(cx: &mut TestAppContext) -> (Arc<FakeFs>, Entity<ProjectEnvironment>) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/monorepo"),
+            json!({
+                ".envrc": "dotenv",
+                "packages": {
+                    "app": {
+                        "mise.toml": "[tools]\nnode = \"20\"",
+                        "src": { "main.js": "" },
+                    },
+                    "lib": { "index.js": "" },
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/monorepo").as_ref()], cx).await;
+        let environment = project.read_with(cx, |project, _| project.environment().clone());
+        (fs, environment)
+    }
+
+    #[gpui::test]
+    async fn test_environment_directory(cx: &mut TestAppContext) {
+        let (_, environment) = monorepo(cx).await;
+        environment.read_with(cx, |environment, cx| {
+            let directory =
+                |path: &str| environment.environment_directory(Path::new(path).into(), cx);
+            assert_eq!(
+                directory(path!("/monorepo/packages/app/src")).as_ref(),
+                Path::new(path!("/monorepo/packages/app"))
+            );
+            assert_eq!(
+                directory(path!("/monorepo/packages/app")).as_ref(),
+                Path::new(path!("/monorepo/packages/app"))
+            );
+            assert_eq!(
+                directory(path!("/monorepo/packages/lib")).as_ref(),
+                Path::new(path!("/monorepo"))
+            );
+            assert_eq!(
+                directory(path!("/elsewhere")).as_ref(),
+                Path::new(path!("/elsewhere"))
+            );
+
+            assert!(
+                environment
+                    .has_mise_configuration(Path::new(path!("/monorepo/packages/app/src")), cx)
+            );
+            assert!(
+                !environment.has_mise_configuration(Path::new(path!("/monorepo/packages/lib")), cx)
+            );
+            assert!(!environment.has_mise_configuration(Path::new(path!("/monorepo")), cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_environment_reloads_when_configuration_changes(cx: &mut TestAppContext) {
+        let (fs, environment) = monorepo(cx).await;
+        let changed_directories = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|cx| {
+            let changed_directories = changed_directories.clone();
+            cx.subscribe(&environment, move |_, event, _| {
+                if let ProjectEnvironmentEvent::EnvironmentChanged(directory) = event {
+                    changed_directories.borrow_mut().push(directory.clone());
+                }
+            })
+            .detach();
+        });
+
+        let directories = [
+            path!("/monorepo"),
+            path!("/monorepo/packages/app"),
+            path!("/monorepo/packages/app/src"),
+            path!("/monorepo/packages/lib"),
+        ];
+        environment.update(cx, |environment, _| {
+            for directory in directories {
+                let directory: Arc<Path> = Path::new(directory).into();
+                environment
+                    .environments
+                    .insert(directory.clone(), Task::ready(None).shared());
+                environment
+                    .tool_variables
+                    .insert(directory.clone(), HashMap::default());
+                environment
+                    .terminal_variables
+                    .insert(directory, HashMap::default());
+            }
+        });
+
+        let loaded_directories = |environment: &ProjectEnvironment| {
+            let mut directories = [
+                environment.environments.keys().collect::<Vec<_>>(),
+                environment.tool_variables.keys().collect(),
+                environment.terminal_variables.keys().collect(),
+            ]
+            .map(|directories| {
+                directories
+                    .into_iter()
+                    .map(|directory| directory.to_path_buf())
+                    .collect::<Vec<_>>()
+            });
+            for directories in &mut directories {
+                directories.sort();
+            }
+            directories
+        };
+
+        // Files that don't configure the environment don't reload it.
+        fs.insert_file(
+            path!("/monorepo/packages/lib/index.js"),
+            b"export {}".to_vec(),
+        )
+        .await;
+        cx.run_until_parked();
+        assert!(changed_directories.borrow().is_empty());
+
+        fs.insert_file(
+            path!("/monorepo/packages/app/mise.toml"),
+            b"[tools]\nnode = \"22\"".to_vec(),
+        )
+        .await;
+        cx.run_until_parked();
+        assert_eq!(
+            *changed_directories.borrow(),
+            [Arc::<Path>::from(Path::new(path!(
+                "/monorepo/packages/app"
+            )))]
+        );
+        let remaining = vec![
+            PathBuf::from(path!("/monorepo")),
+            PathBuf::from(path!("/monorepo/packages/lib")),
+        ];
+        environment.read_with(cx, |environment, _| {
+            assert_eq!(
+                loaded_directories(environment),
+                [remaining.clone(), remaining.clone(), remaining]
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let text = r#"
+# Database
+export DATABASE_URL=postgres://localhost/dev
+GREETING="hello\nworld"
+LITERAL='${HOME}'
+PORT=8080 # the dev server's port
+not a variable
+"#;
+        assert_eq!(
+            parse_dotenv(text),
+            vec![
+                (
+                    "DATABASE_URL".to_string(),
+                    "postgres://localhost/dev".to_string(),
+                    true
+                ),
+                ("GREETING".to_string(), "hello\nworld".to_string(), true),
+                ("LITERAL".to_string(), "${HOME}".to_string(), false),
+                ("PORT".to_string(), "8080".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_variables() {
+        let environment = HashMap::from_iter([
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("USER".to_string(), "zed".to_string()),
+        ]);
+        assert_eq!(
+            expand_variables("${PATH}:./bin", &environment),
+            "/usr/bin:./bin"
+        );
+        assert_eq!(
+            expand_variables("$USER-$MISSING-${USER}s $ {", &environment),
+            "zed--zeds $ {"
+        );
+    }
+}
@@ -1540,8 +1540,14 @@ impl GitStore {
     ) -> Result<proto::Ack> {
         let path: Arc<Path> = PathBuf::from(envelope.payload.abs_path).into();
         let name = envelope.payload.fallback_branch_name;
-        cx.update(|cx| this.read(cx).git_init(path, name, cx))?
-            .await?;
+        cx.update(|cx| {
+            let this = this.read(cx);
+            this.worktree_store
+                .read(cx)
+                .authorize_guest_repository_edit(envelope.original_sender_id, &path, cx)?;
+            anyhow::Ok(this.git_init(path, name, cx))
+        })??
+        .await?;
 
         Ok(proto::Ack {})
     }
@@ -1552,7 +1558,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::RemoteMessageResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
         let fetch_options = FetchOptions::from_proto(envelope.payload.remote);
         let askpass_id = envelope.payload.askpass_id;
 
@@ -1582,7 +1593,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::RemoteMessageResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
 
         let askpass_id = envelope.payload.askpass_id;
         let askpass = make_remote_delegate(
@@ -1622,7 +1638,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::RemoteMessageResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
         let askpass_id = envelope.payload.askpass_id;
         let askpass = make_remote_delegate(
             this,
@@ -1653,7 +1674,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
 
         let entries = envelope
             .payload
@@ -1677,7 +1703,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
 
         let entries = envelope
             .payload
@@ -1702,7 +1733,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
         let repo_path = RepoPath::from_str(&envelope.payload.path);
 
         repository_handle
@@ -1724,7 +1760,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
 
         let message = SharedString::from(envelope.payload.message);
         let name = envelope.payload.name.map(SharedString::from);
@@ -1797,7 +1838,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
         let branch_name = envelope.payload.branch_name;
 
         repository_handle
@@ -1815,7 +1861,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
         let branch_name = envelope.payload.branch_name;
 
         repository_handle
@@ -1881,7 +1932,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
 
         let mode = match envelope.payload.mode() {
             git_reset::ResetMode::Soft => ResetMode::Soft,
@@ -1902,7 +1958,12 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_guest_edit(
+            &this,
+            repository_id,
+            envelope.original_sender_id,
+            &mut cx,
+        )?;
         let paths = envelope
             .payload
             .paths
@@ -2184,6 +2245,30 @@ impl GitStore {
         })?
     }
 
+    /// Like [`Self::repository_for_request`], but rejects guests the host
+    /// hasn't allowed to change the repository.
+    fn repository_for_guest_edit(
+        this: &Entity<Self>,
+        id: RepositoryId,
+        peer_id: Option<proto::PeerId>,
+        cx: &mut AsyncApp,
+    ) -> Result<Entity<Repository>> {
+        this.read_with(cx, |this, cx| {
+            let repository = this
+                .repositories
+                .get(&id)
+                .context("missing repository handle")?;
+            this.worktree_store
+                .read(cx)
+                .authorize_guest_repository_edit(
+                    peer_id,
+                    &repository.read(cx).snapshot.work_directory_abs_path,
+                    cx,
+                )?;
+            anyhow::Ok(repository.clone())
+        })?
+    }
+
     pub fn repo_snapshots(&self, cx: &App) -> HashMap<RepositoryId, RepositorySnapshot> {
         self.repositories
             .iter()
@@ -3301,6 +3386,25 @@ impl Repository {
         })
     }
 
+    /// Loads the files in `path` that differ between `commit` and the working tree. Remote
+    /// repositories aren't supported yet.
+    pub fn diff_working_tree_with_commit(
+        &mut self,
+        commit: String,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<CommitDiff>> {
+        self.send_job(None, move |git_repo, cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => {
+                    backend
+                        .diff_working_tree_with_commit(commit, path, cx)
+                        .await
+                }
+                RepositoryState::Remote { .. } => anyhow::bail!("not implemented yet"),
+            }
+        })
+    }
+
     fn buffer_store(&self, cx: &App) -> Option<Entity<BufferStore>> {
         Some(self.git_store.upgrade()?.read(cx).buffer_store.clone())
     }
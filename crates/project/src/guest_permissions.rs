@@ -0,0 +1,59 @@
+use std::collections::BTreeSet;
+
+use rpc::proto;
+use worktree::WorktreeId;
+
+/// What a guest of a shared project is allowed to change, as set by the host.
+///
+/// These narrow the guest's channel role: a guest who can't write to the
+/// project gains nothing from them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GuestPermissions {
+    pub read_only: bool,
+    /// The worktrees the guest can edit files in, or `None` for all of them.
+    pub editable_worktrees: Option<BTreeSet<WorktreeId>>,
+}
+
+impl GuestPermissions {
+    pub fn can_edit(&self, worktree_id: WorktreeId) -> bool {
+        !self.read_only
+            && self
+                .editable_worktrees
+                .as_ref()
+                .map_or(true, |worktrees| worktrees.contains(&worktree_id))
+    }
+
+    /// Whether the guest can make changes that may reach any worktree, like
+    /// renaming a symbol or applying a code action.
+    pub fn can_edit_project(&self) -> bool {
+        !self.read_only && self.editable_worktrees.is_none()
+    }
+
+    pub fn to_proto(&self, user_id: u64) -> proto::GuestPermissions {
+        proto::GuestPermissions {
+            user_id,
+            read_only: self.read_only,
+            restrict_worktrees: self.editable_worktrees.is_some(),
+            editable_worktree_ids: self
+                .editable_worktrees
+                .iter()
+                .flatten()
+                .map(|worktree_id| worktree_id.to_proto())
+                .collect(),
+        }
+    }
+
+    pub fn from_proto(permissions: &proto::GuestPermissions) -> Self {
+        Self {
+            read_only: permissions.read_only,
+            editable_worktrees: permissions.restrict_worktrees.then(|| {
+                permissions
+                    .editable_worktree_ids
+                    .iter()
+                    .copied()
+                    .map(WorktreeId::from_proto)
+                    .collect()
+            }),
+        }
+    }
+}
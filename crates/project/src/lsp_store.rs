@@ -5,9 +5,9 @@ pub mod rust_analyzer_ext;
 use crate::{
     CodeAction, Completion, CompletionResponse, CompletionSource, CoreCompletion, Hover, InlayHint,
     LspAction, LspPullDiagnostics, ProjectItem, ProjectPath, ProjectTransaction, ResolveState,
-    Symbol, ToolchainStore,
+    Symbol, ToolchainStore, TrustedFolders,
     buffer_store::{BufferStore, BufferStoreEvent},
-    environment::ProjectEnvironment,
+    environment::{ProjectEnvironment, ProjectEnvironmentEvent},
     lsp_command::{self, *},
     lsp_store,
     manifest_tree::{
@@ -139,6 +139,9 @@ pub struct LocalLspStore {
     fs: Arc<dyn Fs>,
     languages: Arc<LanguageRegistry>,
     language_server_ids: HashMap<(WorktreeId, LanguageServerName), BTreeSet<LanguageServerId>>,
+    /// The subprojects that language servers with an instance for each subproject were started
+    /// for, relative to their worktree's root.
+    language_server_subprojects: HashMap<LanguageServerId, Arc<Path>>,
     yarn: Entity<YarnPathStore>,
     pub language_servers: HashMap<LanguageServerId, LanguageServerState>,
     buffers_being_formatted: HashSet<BufferId>,
@@ -188,11 +191,12 @@ impl LocalLspStore {
         delegate: Arc<LocalLspAdapterDelegate>,
         adapter: Arc<CachedLspAdapter>,
         settings: Arc<LspSettings>,
+        root: &Path,
         cx: &mut App,
     ) -> LanguageServerId {
         let worktree = worktree_handle.read(cx);
         let worktree_id = worktree.id();
-        let root_path = worktree.abs_path();
+        let root_path = worktree.abs_path().join(root);
         let key = (worktree_id, adapter.name.clone());
 
         let override_options = settings.initialization_options.clone();
@@ -205,6 +209,16 @@ impl LocalLspStore {
             adapter.name.0
         );
 
+        // The subproject gets its own settings and environment, from the `.zed/settings.json`
+        // and `.envrc` files in it and in the directories above it.
+        let delegate = if root.as_os_str().is_empty() {
+            delegate
+        } else {
+            self.language_server_subprojects
+                .insert(server_id, root.into());
+            delegate.for_subproject(root.into(), &self.environment, cx)
+        };
+
         let binary = self.get_language_server_binary(adapter.clone(), delegate.clone(), true, cx);
         let pending_workspace_folders: Arc<Mutex<BTreeSet<Url>>> = Default::default();
         let pending_server = cx.spawn({
@@ -394,7 +408,10 @@ impl LocalLspStore {
         )
         .lsp
         .get(&adapter.name)
-        .and_then(|s| s.binary.clone());
+        .and_then(|s| s.binary.clone())
+        // Overrides could point at a binary in the folder itself, so folders have to be trusted
+        // for them to be used.
+        .filter(|_| TrustedFolders::is_trusted(delegate.worktree_root_path(), cx));
 
         if settings.as_ref().is_some_and(|b| b.path.is_some()) {
             let settings = settings.unwrap();
@@ -924,6 +941,36 @@ impl LocalLspStore {
                 }
             })
             .detach();
+        language_server
+            .on_request::<lsp_ext_command::LspExtTypeScriptRename, _, _>({
+                let this = this.clone();
+                let name = name.clone();
+                move |params, cx| {
+                    let this = this.clone();
+                    let name = name.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        let buffer = this
+                            .update(&mut cx, |this, cx| {
+                                this.open_local_buffer_via_lsp(
+                                    params.text_document.uri,
+                                    server_id,
+                                    name,
+                                    cx,
+                                )
+                            })?
+                            .await?;
+                        this.update(&mut cx, |_, cx| {
+                            cx.emit(LspStoreEvent::RenameRequested {
+                                buffer_id: buffer.read(cx).remote_id(),
+                                position: params.position,
+                            });
+                        })?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
         language_server
             .on_notification::<lsp::notification::ShowMessage, _>({
                 let this = this.clone();
@@ -1107,6 +1154,40 @@ impl LocalLspStore {
         }
     }
 
+    fn language_server_roots_for_buffer(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> Vec<(LanguageServerId, ProjectPath)> {
+        let Some((file, language)) = File::from_dyn(buffer.file()).zip(buffer.language()) else {
+            return Vec::new();
+        };
+        let worktree_id = file.worktree_id(cx);
+        let Some(worktree) = self
+            .worktree_store
+            .read(cx)
+            .worktree_for_id(worktree_id, cx)
+        else {
+            return Vec::new();
+        };
+        let path: Arc<Path> = file
+            .path()
+            .parent()
+            .map(Arc::from)
+            .unwrap_or_else(|| file.path().clone());
+        let delegate = Arc::new(ManifestQueryDelegate::new(worktree.read(cx).snapshot()));
+        self.lsp_tree.update(cx, |this, cx| {
+            this.get(
+                ProjectPath { worktree_id, path },
+                AdapterQuery::Language(&language.name()),
+                delegate,
+                cx,
+            )
+            .filter_map(|node| Some((node.server_id()?, node.root_path()?)))
+            .collect()
+        })
+    }
+
     fn language_servers_for_buffer<'a>(
         &'a self,
         buffer: &'a Buffer,
@@ -2372,28 +2453,21 @@ impl LocalLspStore {
                          path,
                          settings,
                      }| match attach {
+                        // Each root's node is initialized once, so it gets its own instance.
                         language::Attach::InstancePerRoot => {
-                            // todo: handle instance per root proper.
-                            if let Some(server_ids) = self
-                                .language_server_ids
-                                .get(&(worktree_id, server_name.clone()))
-                            {
-                                server_ids.iter().cloned().next().unwrap()
-                            } else {
-                                let language_name = language.name();
-
-                                self.start_language_server(
-                                    &worktree,
-                                    delegate.clone(),
-                                    self.languages
-                                        .lsp_adapters(&language_name)
-                                        .into_iter()
-                                        .find(|adapter| &adapter.name() == server_name)
-                                        .expect("To find LSP adapter"),
-                                    settings,
-                                    cx,
-                                )
-                            }
+                            let language_name = language.name();
+                            self.start_language_server(
+                                &worktree,
+                                delegate.clone(),
+                                self.languages
+                                    .lsp_adapters(&language_name)
+                                    .into_iter()
+                                    .find(|adapter| &adapter.name() == server_name)
+                                    .expect("To find LSP adapter"),
+                                settings,
+                                &path.path,
+                                cx,
+                            )
                         }
                         language::Attach::Shared => {
                             let uri = Url::from_file_path(
@@ -2411,6 +2485,7 @@ impl LocalLspStore {
                                         .find(|adapter| &adapter.name() == server_name)
                                         .expect("To find LSP adapter"),
                                     settings,
+                                    Path::new(""),
                                     cx,
                                 );
                             }
@@ -2844,28 +2919,8 @@ impl LocalLspStore {
     ) -> Result<ProjectTransaction> {
         let fs = this.read_with(cx, |this, _| this.as_local().unwrap().fs.clone())?;
 
-        let mut operations = Vec::new();
-        if let Some(document_changes) = edit.document_changes {
-            match document_changes {
-                lsp::DocumentChanges::Edits(edits) => {
-                    operations.extend(edits.into_iter().map(lsp::DocumentChangeOperation::Edit))
-                }
-                lsp::DocumentChanges::Operations(ops) => operations = ops,
-            }
-        } else if let Some(changes) = edit.changes {
-            operations.extend(changes.into_iter().map(|(uri, edits)| {
-                lsp::DocumentChangeOperation::Edit(lsp::TextDocumentEdit {
-                    text_document: lsp::OptionalVersionedTextDocumentIdentifier {
-                        uri,
-                        version: None,
-                    },
-                    edits: edits.into_iter().map(Edit::Plain).collect(),
-                })
-            }));
-        }
-
         let mut project_transaction = ProjectTransaction::default();
-        for operation in operations {
+        for operation in workspace_edit_operations(edit) {
             match operation {
                 lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Create(op)) => {
                     let abs_path = op
@@ -2993,17 +3048,17 @@ impl LocalLspStore {
                             }
                             if !snippet_edits.is_empty() {
                                 let buffer_id = buffer_to_edit.read(cx).remote_id();
-                                let version = if let Some(buffer_version) = op.text_document.version
-                                {
-                                    local
-                                        .buffer_snapshot_for_lsp_version(
-                                            &buffer_to_edit,
-                                            language_server.server_id(),
-                                            Some(buffer_version),
-                                            cx,
-                                        )
+                                let snapshot = local.buffer_snapshot_for_lsp_version(
+                                    &buffer_to_edit,
+                                    language_server.server_id(),
+                                    op.text_document.version,
+                                    cx,
+                                );
+                                let version = if op.text_document.version.is_some() {
+                                    snapshot
+                                        .as_ref()
                                         .ok()
-                                        .map(|snapshot| snapshot.version)
+                                        .map(|snapshot| snapshot.version.clone())
                                 } else {
                                     Some(buffer_to_edit.read(cx).saved_version().clone())
                                 };
@@ -3013,10 +3068,30 @@ impl LocalLspStore {
                                 });
                                 // Check if the edit that triggered that edit has been made by this participant.
 
-                                if let Some(most_recent_edit) = most_recent_edit {
+                                if let (Some(most_recent_edit), Ok(snapshot)) =
+                                    (most_recent_edit, snapshot)
+                                {
+                                    // The ranges of the snippets are relative to the document the
+                                    // server saw, so they're anchored there to follow the other
+                                    // edits of the operation.
+                                    let edits = snippet_edits
+                                        .into_iter()
+                                        .map(|(range, snippet)| {
+                                            let range = range_from_lsp(range);
+                                            let start =
+                                                snapshot.clip_point_utf16(range.start, Bias::Left);
+                                            let end =
+                                                snapshot.clip_point_utf16(range.end, Bias::Right);
+                                            (
+                                                snapshot.anchor_before(start)
+                                                    ..snapshot.anchor_after(end),
+                                                snippet,
+                                            )
+                                        })
+                                        .collect();
                                     cx.emit(LspStoreEvent::SnippetEdit {
                                         buffer_id,
-                                        edits: snippet_edits,
+                                        edits,
                                         most_recent_edit,
                                     });
                                 }
@@ -3060,6 +3135,87 @@ impl LocalLspStore {
         Ok(project_transaction)
     }
 
+    /// Computes the edits of a workspace edit without applying them, opening the buffers they're
+    /// made in.
+    ///
+    /// Fails when the workspace edit also creates, renames or deletes files, since those can't be
+    /// shown as edits.
+    async fn workspace_edit_to_buffer_edits(
+        this: Entity<LspStore>,
+        edit: lsp::WorkspaceEdit,
+        lsp_adapter: Arc<CachedLspAdapter>,
+        language_server: Arc<LanguageServer>,
+        cx: &mut AsyncApp,
+    ) -> Result<Vec<(Entity<Buffer>, Vec<(Range<Anchor>, Arc<str>)>)>> {
+        let operations = workspace_edit_operations(edit);
+        if operations
+            .iter()
+            .any(|operation| matches!(operation, lsp::DocumentChangeOperation::Op(_)))
+        {
+            return Err(anyhow!(
+                "{} wants to create, rename or delete files, which can't be previewed",
+                language_server.name()
+            ));
+        }
+
+        let mut buffer_edits = Vec::new();
+        for operation in operations {
+            let lsp::DocumentChangeOperation::Edit(op) = operation else {
+                continue;
+            };
+            let buffer = this
+                .update(cx, |this, cx| {
+                    this.open_local_buffer_via_lsp(
+                        op.text_document.uri.clone(),
+                        language_server.server_id(),
+                        lsp_adapter.name.clone(),
+                        cx,
+                    )
+                })?
+                .await?;
+
+            let mut text_edits = Vec::new();
+            for edit in op.edits {
+                let edit = match edit {
+                    Edit::Plain(edit) => edit,
+                    Edit::Annotated(edit) => edit.text_edit,
+                    Edit::Snippet(edit) => {
+                        let Ok(snippet) = Snippet::parse(&edit.snippet.value) else {
+                            continue;
+                        };
+                        TextEdit {
+                            range: edit.range,
+                            new_text: snippet.text,
+                        }
+                    }
+                };
+                if !text_edits.contains(&edit) {
+                    text_edits.push(edit);
+                }
+            }
+            let edits = this
+                .update(cx, |this, cx| {
+                    this.as_local_mut().unwrap().edits_from_lsp(
+                        &buffer,
+                        text_edits,
+                        language_server.server_id(),
+                        op.text_document.version,
+                        cx,
+                    )
+                })?
+                .await?;
+            if let Some((_, existing_edits)) = buffer_edits
+                .iter_mut()
+                .find(|(existing_buffer, _)| *existing_buffer == buffer)
+            {
+                existing_edits.extend(edits);
+            } else if !edits.is_empty() {
+                buffer_edits.push((buffer, edits));
+            }
+        }
+        Ok(buffer_edits)
+    }
+
     async fn on_lsp_workspace_edit(
         this: WeakEntity<LspStore>,
         params: lsp::ApplyWorkspaceEditParams,
@@ -3081,8 +3237,21 @@ impl LocalLspStore {
         )
         .await
         .log_err();
-        this.update(cx, |this, _| {
+        this.update(cx, |this, cx| {
             if let Some(transaction) = transaction {
+                if !transaction.0.is_empty() {
+                    let title = params
+                        .label
+                        .unwrap_or_else(|| format!("Edit from {}", language_server.name()));
+                    cx.emit(LspStoreEvent::WorkspaceEditApplied {
+                        title: title.into(),
+                        transactions: transaction
+                            .0
+                            .iter()
+                            .map(|(buffer, transaction)| (buffer.clone(), transaction.id))
+                            .collect(),
+                    });
+                }
                 this.as_local_mut()
                     .unwrap()
                     .last_workspace_edits_by_language_server
@@ -3129,6 +3298,8 @@ impl LocalLspStore {
                 .remove(&server_id_to_remove);
             self.last_workspace_edits_by_language_server
                 .remove(&server_id_to_remove);
+            self.language_server_subprojects
+                .remove(&server_id_to_remove);
             self.language_servers.remove(&server_id_to_remove);
             cx.emit(LspStoreEvent::LanguageServerRemoved(*server_id_to_remove));
         }
@@ -3494,9 +3665,20 @@ pub enum LspStoreEvent {
     },
     SnippetEdit {
         buffer_id: BufferId,
-        edits: Vec<(lsp::Range, Snippet)>,
+        edits: Vec<(Range<Anchor>, Snippet)>,
         most_recent_edit: clock::Lamport,
     },
+    /// A language server applied a workspace edit on its own, like the ones it sends while
+    /// executing a command.
+    WorkspaceEditApplied {
+        title: SharedString,
+        transactions: Vec<(Entity<Buffer>, text::TransactionId)>,
+    },
+    /// A language server asked for the symbol at `position` to be renamed by the user.
+    RenameRequested {
+        buffer_id: BufferId,
+        position: lsp::Position,
+    },
     PullWorkspaceDiagnostics,
 }
 
@@ -3556,7 +3738,7 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentSymbols>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetReferences>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
-        client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
+        client.add_entity_request_handler(Self::handle_perform_rename);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
 
         client.add_entity_request_handler(Self::handle_lsp_ext_cancel_flycheck);
@@ -3634,6 +3816,8 @@ impl LspStore {
             .detach();
         cx.subscribe(&toolchain_store, Self::on_toolchain_store_event)
             .detach();
+        cx.subscribe(&environment, Self::on_environment_event)
+            .detach();
         if let Some(extension_events) = extension::ExtensionEvents::try_global(cx).as_ref() {
             cx.subscribe(
                 extension_events,
@@ -3662,6 +3846,7 @@ impl LspStore {
                 supplementary_language_servers: Default::default(),
                 languages: languages.clone(),
                 language_server_ids: Default::default(),
+                language_server_subprojects: Default::default(),
                 language_servers: Default::default(),
                 last_workspace_edits_by_language_server: Default::default(),
                 language_server_watched_paths: Default::default(),
@@ -3858,6 +4043,74 @@ impl LspStore {
         }
     }
 
+    fn on_environment_event(
+        &mut self,
+        _: Entity<ProjectEnvironment>,
+        event: &ProjectEnvironmentEvent,
+        cx: &mut Context<Self>,
+    ) {
+        let ProjectEnvironmentEvent::EnvironmentChanged(directory) = event else {
+            return;
+        };
+        let Some(local) = self.as_local() else {
+            return;
+        };
+        // Language servers keep the environment they were started with, so the ones started in
+        // the directory or below it are restarted to pick up its new environment.
+        let worktree_store = local.worktree_store.read(cx);
+        let servers_to_restart = local
+            .language_server_ids
+            .iter()
+            .flat_map(|((worktree_id, _), server_ids)| {
+                let worktree_root = worktree_store
+                    .worktree_for_id(*worktree_id, cx)
+                    .map(|worktree| worktree.read(cx).abs_path());
+                server_ids.iter().copied().filter(move |server_id| {
+                    let Some(worktree_root) = &worktree_root else {
+                        return false;
+                    };
+                    match local.language_server_subprojects.get(server_id) {
+                        Some(subproject) => worktree_root.join(subproject).starts_with(directory),
+                        None => worktree_root.starts_with(directory),
+                    }
+                })
+            })
+            .collect::<BTreeSet<_>>();
+        if servers_to_restart.is_empty() {
+            return;
+        }
+        let buffers = self
+            .buffer_store
+            .read(cx)
+            .buffers()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter(|buffer| {
+                buffer.update(cx, |buffer, cx| {
+                    local
+                        .language_server_ids_for_buffer(buffer, cx)
+                        .iter()
+                        .any(|server_id| servers_to_restart.contains(server_id))
+                })
+            })
+            .collect::<Vec<_>>();
+        log::info!(
+            "restarting {} language servers after the environment of {directory:?} changed",
+            servers_to_restart.len()
+        );
+        let stop_task = self.stop_local_language_servers(servers_to_restart, cx);
+        cx.spawn(async move |this, cx| {
+            stop_task.await;
+            this.update(cx, |this, cx| {
+                for buffer in buffers {
+                    this.register_buffer_with_language_servers(&buffer, true, cx);
+                }
+            })
+            .ok()
+        })
+        .detach();
+    }
+
     fn request_workspace_config_refresh(&mut self) {
         *self._maintain_workspace_config.1.borrow_mut() = ();
     }
@@ -4418,6 +4671,58 @@ impl LspStore {
         });
     }
 
+    /// Asks the first language server of the buffer that can rename symbols for the edits
+    /// renaming the one at `position` to `new_name` would make, without making them.
+    pub fn rename_edits(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: PointUtf16,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<(Entity<Buffer>, Vec<(Range<Anchor>, Arc<str>)>)>>> {
+        let request = PerformRename {
+            position,
+            new_name,
+            push_to_history: false,
+        };
+        let Some(local) = self.as_local() else {
+            return Task::ready(Err(anyhow!(
+                "renames can only be previewed in local projects"
+            )));
+        };
+        let Some((adapter, language_server)) = buffer.update(cx, |buffer, cx| {
+            local
+                .language_servers_for_buffer(buffer, cx)
+                .find(|(_, server)| {
+                    request.check_capabilities(server.adapter_server_capabilities())
+                })
+                .map(|(adapter, server)| (adapter.clone(), server.clone()))
+        }) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let Some(file) = File::from_dyn(buffer.read(cx).file()).and_then(File::as_local) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let lsp_params =
+            match request.to_lsp(&file.abs_path(cx), buffer.read(cx), &language_server, cx) {
+                Ok(lsp_params) => lsp_params,
+                Err(err) => return Task::ready(Err(err)),
+            };
+
+        cx.spawn(async move |this, cx| {
+            let edit = language_server
+                .request::<lsp::request::Rename>(lsp_params)
+                .await
+                .into_response()
+                .with_context(|| format!("Rename via {} failed", language_server.name()))?;
+            let Some(edit) = edit else {
+                return Ok(Vec::new());
+            };
+            let this = this.upgrade().context("no app context")?;
+            Self::workspace_edit_to_buffer_edits(this, edit, adapter, language_server, cx).await
+        })
+    }
+
     fn on_settings_changed(&mut self, cx: &mut Context<Self>) {
         let mut language_formatters_to_check = Vec::new();
         for buffer in self.buffer_store.read(cx).buffers() {
@@ -4539,30 +4844,20 @@ impl LspStore {
                                          path,
                                          settings,
                                      }| match attach {
-                                        language::Attach::InstancePerRoot => {
-                                            // todo: handle instance per root proper.
-                                            if let Some(server_ids) = local
-                                                .language_server_ids
-                                                .get(&(worktree_id, server_name.clone()))
-                                            {
-                                                server_ids.iter().cloned().next().unwrap()
-                                            } else {
-                                                local.start_language_server(
-                                                    &worktree,
-                                                    delegate.clone(),
-                                                    local
-                                                        .languages
-                                                        .lsp_adapters(&language)
-                                                        .into_iter()
-                                                        .find(|adapter| {
-                                                            &adapter.name() == server_name
-                                                        })
-                                                        .expect("To find LSP adapter"),
-                                                    settings,
-                                                    cx,
-                                                )
-                                            }
-                                        }
+                                        language::Attach::InstancePerRoot => local
+                                            .start_language_server(
+                                                &worktree,
+                                                delegate.clone(),
+                                                local
+                                                    .languages
+                                                    .lsp_adapters(&language)
+                                                    .into_iter()
+                                                    .find(|adapter| &adapter.name() == server_name)
+                                                    .expect("To find LSP adapter"),
+                                                settings,
+                                                &path.path,
+                                                cx,
+                                            ),
                                         language::Attach::Shared => {
                                             let uri = Url::from_file_path(
                                                 worktree.read(cx).abs_path().join(&path.path),
@@ -4580,6 +4875,7 @@ impl LspStore {
                                                     .find(|adapter| &adapter.name() == server_name)
                                                     .expect("To find LSP adapter"),
                                                 settings,
+                                                Path::new(""),
                                                 cx,
                                             );
                                             if let Some(state) =
@@ -4643,9 +4939,12 @@ impl LspStore {
                 LocalLspStore::try_resolve_code_action(&lang_server, &mut action)
                     .await
                     .context("resolving a code action")?;
+                // A code action with both an edit and a command has its edit applied first, and
+                // then its command executed.
+                let mut project_transaction = ProjectTransaction::default();
                 if let Some(edit) = action.lsp_action.edit() {
                     if edit.changes.is_some() || edit.document_changes.is_some() {
-                        return LocalLspStore::deserialize_workspace_edit(
+                        project_transaction = LocalLspStore::deserialize_workspace_edit(
                             this.upgrade().context("no app present")?,
                             edit.clone(),
                             push_to_history,
@@ -4653,7 +4952,7 @@ impl LspStore {
                             lang_server.clone(),
                             cx,
                         )
-                        .await;
+                        .await?;
                     }
                 }
 
@@ -4681,19 +4980,22 @@ impl LspStore {
                             .await.into_response()
                             .context("execute command")?;
 
-                        return this.update(cx, |this, _| {
+                        let command_transaction = this.update(cx, |this, _| {
                             this.as_local_mut()
                                 .unwrap()
                                 .last_workspace_edits_by_language_server
                                 .remove(&lang_server.server_id())
                                 .unwrap_or_default()
-                        });
+                        })?;
+                        for (buffer, transaction) in command_transaction.0 {
+                            project_transaction.0.entry(buffer).or_insert(transaction);
+                        }
                     } else {
                         log::warn!("Cannot execute a command {} not listed in the language server capabilities", command.command);
                     }
                 }
 
-                Ok(ProjectTransaction::default())
+                Ok(project_transaction)
             })
         } else {
             Task::ready(Err(anyhow!("no upstream client and not local")))
@@ -5109,6 +5411,33 @@ impl LspStore {
         }
     }
 
+    /// Fills in the command of a code lens, which language servers may leave out of the
+    /// initial response to resolve it lazily.
+    pub fn resolve_code_lens(
+        &self,
+        buffer_handle: &Entity<Buffer>,
+        mut action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CodeAction>> {
+        // Lenses with a command already have a title to show, and remote lenses are resolved by
+        // the host once they are applied.
+        if !self.mode.is_local() || action.resolved || action.lsp_action.command().is_some() {
+            return Task::ready(Ok(action));
+        }
+        let Some(lang_server) = buffer_handle.update(cx, |buffer, cx| {
+            self.language_server_for_local_buffer(buffer, action.server_id, cx)
+                .map(|(_, server)| server.clone())
+        }) else {
+            return Task::ready(Ok(action));
+        };
+        cx.background_spawn(async move {
+            LocalLspStore::try_resolve_code_action(&lang_server, &mut action)
+                .await
+                .context("resolving a code lens")?;
+            Ok(action)
+        })
+    }
+
     #[inline(never)]
     pub fn completions(
         &self,
@@ -6400,20 +6729,33 @@ impl LspStore {
                                 )
                             });
 
-                            server_ids.iter().filter_map(move |server_id| {
-                                let states = local.language_servers.get(server_id)?;
-
-                                match states {
-                                    LanguageServerState::Starting { .. } => None,
-                                    LanguageServerState::Running {
-                                        adapter, server, ..
-                                    } => Some((
-                                        adapter.adapter.clone(),
-                                        server.clone(),
-                                        delegate.clone()? as Arc<dyn LspAdapterDelegate>,
-                                    )),
-                                }
-                            })
+                            server_ids
+                                .iter()
+                                .filter_map(|server_id| {
+                                    let states = local.language_servers.get(server_id)?;
+                                    let delegate = delegate.clone()?;
+                                    let delegate =
+                                        match local.language_server_subprojects.get(server_id) {
+                                            Some(path) => delegate.for_subproject(
+                                                path.clone(),
+                                                &local.environment,
+                                                cx,
+                                            ),
+                                            None => delegate,
+                                        };
+
+                                    match states {
+                                        LanguageServerState::Starting { .. } => None,
+                                        LanguageServerState::Running {
+                                            adapter, server, ..
+                                        } => Some((
+                                            adapter.adapter.clone(),
+                                            server.clone(),
+                                            delegate as Arc<dyn LspAdapterDelegate>,
+                                        )),
+                                    }
+                                })
+                                .collect::<Vec<_>>()
                         })
                         .collect::<Vec<_>>()
                 })
@@ -6473,6 +6815,18 @@ impl LspStore {
         })
     }
 
+    /// Returns the language servers of a local project that the buffer is attached to, with the
+    /// root of the subproject that each of them serves the buffer for.
+    pub fn language_server_roots_for_local_buffer(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> Vec<(LanguageServerId, ProjectPath)> {
+        self.as_local()
+            .map(|local| local.language_server_roots_for_buffer(buffer, cx))
+            .unwrap_or_default()
+    }
+
     pub fn language_servers_for_local_buffer<'a>(
         &'a self,
         buffer: &Buffer,
@@ -7020,6 +7374,19 @@ impl LspStore {
         })?
     }
 
+    async fn handle_perform_rename(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::PerformRename>,
+        cx: AsyncApp,
+    ) -> Result<proto::PerformRenameResponse> {
+        this.read_with(&cx, |this, cx| {
+            this.worktree_store
+                .read(cx)
+                .authorize_guest_project_edit(envelope.original_sender_id)
+        })??;
+        Self::handle_lsp_command::<PerformRename>(this, envelope, cx).await
+    }
+
     async fn handle_multi_lsp_query(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::MultiLspQuery>,
@@ -7258,6 +7625,9 @@ impl LspStore {
         let action =
             Self::deserialize_code_action(envelope.payload.action.context("invalid action")?)?;
         let apply_code_action = this.update(&mut cx, |this, cx| {
+            this.worktree_store
+                .read(cx)
+                .authorize_guest_project_edit(envelope.original_sender_id)?;
             let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
             let buffer = this.buffer_store.read(cx).get_existing(buffer_id)?;
             anyhow::Ok(this.apply_code_action(buffer, action, false, cx))
@@ -7356,6 +7726,11 @@ impl LspStore {
                     })
             })?
             .context("worktree not found")?;
+        this.read_with(&cx, |this, cx| {
+            this.worktree_store
+                .read(cx)
+                .authorize_guest_edit(envelope.original_sender_id, Some(worktree_id))
+        })??;
         let (old_abs_path, new_abs_path) = {
             let root_path = worktree.read_with(&mut cx, |this, _| this.abs_path())?;
             let new_path = PathBuf::from_proto(envelope.payload.new_path.clone());
@@ -8140,6 +8515,11 @@ impl LspStore {
         let on_type_formatting = this.update(&mut cx, |this, cx| {
             let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
             let buffer = this.buffer_store.read(cx).get_existing(buffer_id)?;
+            this.worktree_store.read(cx).authorize_guest_buffer_edit(
+                envelope.original_sender_id,
+                buffer.read(cx),
+                cx,
+            )?;
             let position = envelope
                 .payload
                 .position
@@ -8355,9 +8735,13 @@ impl LspStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         this.update(&mut cx, |this, cx| {
+            this.worktree_store
+                .read(cx)
+                .authorize_guest_edit(envelope.original_sender_id, None)?;
             let buffers = this.buffer_ids_to_buffers(envelope.payload.buffer_ids.into_iter(), cx);
             this.restart_language_servers_for_buffers(buffers, cx);
-        })?;
+            anyhow::Ok(())
+        })??;
 
         Ok(proto::Ack {})
     }
@@ -8368,9 +8752,13 @@ impl LspStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         this.update(&mut cx, |this, cx| {
+            this.worktree_store
+                .read(cx)
+                .authorize_guest_edit(envelope.original_sender_id, None)?;
             let buffers = this.buffer_ids_to_buffers(envelope.payload.buffer_ids.into_iter(), cx);
             this.stop_language_servers_for_buffers(buffers, cx);
-        })?;
+            anyhow::Ok(())
+        })??;
 
         Ok(proto::Ack {})
     }
@@ -8422,6 +8810,11 @@ impl LspStore {
         let (buffer, completion) = this.update(&mut cx, |this, cx| {
             let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
             let buffer = this.buffer_store.read(cx).get_existing(buffer_id)?;
+            this.worktree_store.read(cx).authorize_guest_buffer_edit(
+                envelope.original_sender_id,
+                buffer.read(cx),
+                cx,
+            )?;
             let completion = Self::deserialize_completion(
                 envelope.payload.completion.context("invalid completion")?,
             )?;
@@ -8613,7 +9006,13 @@ impl LspStore {
             let mut buffers = HashSet::default();
             for buffer_id in &envelope.payload.buffer_ids {
                 let buffer_id = BufferId::new(*buffer_id)?;
-                buffers.insert(this.buffer_store.read(cx).get_existing(buffer_id)?);
+                let buffer = this.buffer_store.read(cx).get_existing(buffer_id)?;
+                this.worktree_store.read(cx).authorize_guest_buffer_edit(
+                    envelope.original_sender_id,
+                    buffer.read(cx),
+                    cx,
+                )?;
+                buffers.insert(buffer);
             }
             let trigger = FormatTrigger::from_proto(envelope.payload.trigger);
             anyhow::Ok(this.format(buffers, LspFormatTarget::Buffers, false, trigger, cx))
@@ -8641,6 +9040,9 @@ impl LspStore {
     ) -> Result<proto::ApplyCodeActionKindResponse> {
         let sender_id = envelope.original_sender_id().unwrap_or_default();
         let format = this.update(&mut cx, |this, cx| {
+            this.worktree_store
+                .read(cx)
+                .authorize_guest_project_edit(envelope.original_sender_id)?;
             let mut buffers = HashSet::default();
             for buffer_id in &envelope.payload.buffer_ids {
                 let buffer_id = BufferId::new(*buffer_id)?;
@@ -8791,6 +9193,7 @@ impl LspStore {
             });
         }
         local.language_server_watched_paths.remove(&server_id);
+        local.language_server_subprojects.remove(&server_id);
         let server_state = local.language_servers.remove(&server_id);
         cx.notify();
         cx.emit(LspStoreEvent::LanguageServerRemoved(server_id));
@@ -8865,6 +9268,17 @@ impl LspStore {
                 })
             })
             .collect::<BTreeSet<_>>();
+        self.stop_local_language_servers(language_servers_to_stop, cx)
+    }
+
+    fn stop_local_language_servers(
+        &mut self,
+        language_servers_to_stop: BTreeSet<LanguageServerId>,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let Some(local) = self.as_local_mut() else {
+            return Task::ready(());
+        };
         local.lsp_tree.update(cx, |this, _| {
             this.remove_nodes(&language_servers_to_stop);
         });
@@ -10309,7 +10723,7 @@ pub fn language_server_settings<'a>(
     language_server_settings_for(
         SettingsLocation {
             worktree_id: delegate.worktree_id(),
-            path: delegate.worktree_root_path(),
+            path: delegate.subproject_path(),
         },
         language,
         cx,
@@ -10331,6 +10745,7 @@ pub struct LocalLspAdapterDelegate {
     http_client: Arc<dyn HttpClient>,
     language_registry: Arc<LanguageRegistry>,
     load_shell_env_task: Shared<Task<Option<HashMap<String, String>>>>,
+    subproject_path: Arc<Path>,
 }
 
 impl LocalLspAdapterDelegate {
@@ -10354,6 +10769,30 @@ impl LocalLspAdapterDelegate {
             http_client,
             language_registry,
             load_shell_env_task,
+            subproject_path: Arc::from(Path::new("")),
+        })
+    }
+
+    /// Returns a delegate for a language server that was started for the subproject at the
+    /// path, with the subproject's environment.
+    fn for_subproject(
+        &self,
+        subproject_path: Arc<Path>,
+        environment: &Entity<ProjectEnvironment>,
+        cx: &mut App,
+    ) -> Arc<Self> {
+        let abs_path = self.worktree.abs_path().join(&subproject_path);
+        let load_shell_env_task = environment.update(cx, |environment, cx| {
+            environment.get_directory_environment(abs_path.into(), cx)
+        });
+        Arc::new(Self {
+            lsp_store: self.lsp_store.clone(),
+            worktree: self.worktree.clone(),
+            fs: self.fs.clone(),
+            http_client: self.http_client.clone(),
+            language_registry: self.language_registry.clone(),
+            load_shell_env_task,
+            subproject_path,
         })
     }
 
@@ -10396,6 +10835,10 @@ impl LspAdapterDelegate for LocalLspAdapterDelegate {
         self.worktree.abs_path().as_ref()
     }
 
+    fn subproject_path(&self) -> &Path {
+        &self.subproject_path
+    }
+
     async fn shell_env(&self) -> HashMap<String, String> {
         let task = self.load_shell_env_task.clone();
         task.await.unwrap_or_default()
@@ -10591,6 +11034,28 @@ async fn populate_labels_for_symbols(
     }
 }
 
+/// Flattens the changes of a workspace edit, in whichever form the server sent them, into a list
+/// of operations.
+fn workspace_edit_operations(edit: lsp::WorkspaceEdit) -> Vec<lsp::DocumentChangeOperation> {
+    let mut operations = Vec::new();
+    if let Some(document_changes) = edit.document_changes {
+        match document_changes {
+            lsp::DocumentChanges::Edits(edits) => {
+                operations.extend(edits.into_iter().map(lsp::DocumentChangeOperation::Edit))
+            }
+            lsp::DocumentChanges::Operations(ops) => operations = ops,
+        }
+    } else if let Some(changes) = edit.changes {
+        operations.extend(changes.into_iter().map(|(uri, edits)| {
+            lsp::DocumentChangeOperation::Edit(lsp::TextDocumentEdit {
+                text_document: lsp::OptionalVersionedTextDocumentIdentifier { uri, version: None },
+                edits: edits.into_iter().map(Edit::Plain).collect(),
+            })
+        }));
+    }
+    operations
+}
+
 fn include_text(server: &lsp::LanguageServer) -> Option<bool> {
     match server.capabilities().text_document_sync.as_ref()? {
         lsp::TextDocumentSyncCapability::Kind(kind) => match *kind {
@@ -10723,9 +11188,74 @@ fn ensure_uniform_list_compatible_label(label: &mut CodeLabel) {
 
 #[cfg(test)]
 mod tests {
+    use fs::FakeFs;
     use language::HighlightId;
+    use serde_json::json;
+    use util::path;
 
     use super::*;
+    use crate::{Project, project_tests::init_test};
+
+    #[gpui::test]
+    async fn test_subproject_language_server_settings(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                ".zed": {
+                    "settings.json": r#"{
+                        "lsp": { "the-server": { "settings": { "level": "root" } } },
+                        "env": { "variables": { "LEVEL": "root", "ROOT": "1" } }
+                    }"#,
+                },
+                "packages": {
+                    "app": {
+                        ".zed": {
+                            "settings.json": r#"{
+                                "lsp": { "the-server": { "settings": { "level": "app" } } },
+                                "env": { "variables": { "LEVEL": "app" } }
+                            }"#,
+                        },
+                        "main.rs": "",
+                    },
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        cx.run_until_parked();
+
+        let server_name = LanguageServerName::new_static("the-server");
+        let (delegate, subproject_delegate) = project.update(cx, |project, cx| {
+            let worktree = project.worktrees(cx).next().unwrap();
+            project.lsp_store().update(cx, |lsp_store, cx| {
+                let local = lsp_store.as_local().unwrap();
+                let delegate = LocalLspAdapterDelegate::from_local_lsp(local, &worktree, cx);
+                let subproject_delegate = delegate.for_subproject(
+                    Path::new("packages/app").into(),
+                    &local.environment,
+                    cx,
+                );
+                (delegate, subproject_delegate)
+            })
+        });
+
+        cx.update(|cx| {
+            let level = |delegate: &LocalLspAdapterDelegate| {
+                language_server_settings(delegate, &server_name, cx)
+                    .and_then(|settings| settings.settings.clone())
+            };
+            assert_eq!(level(&delegate), Some(json!({ "level": "root" })));
+            assert_eq!(level(&subproject_delegate), Some(json!({ "level": "app" })));
+        });
+
+        let env = delegate.shell_env().await;
+        assert_eq!(env.get("LEVEL").map(String::as_str), Some("root"));
+        let env = subproject_delegate.shell_env().await;
+        assert_eq!(env.get("LEVEL").map(String::as_str), Some("app"));
+        assert_eq!(env.get("ROOT").map(String::as_str), Some("1"));
+    }
 
     #[test]
     fn test_glob_literal_prefix() {
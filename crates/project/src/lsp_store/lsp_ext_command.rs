@@ -783,3 +783,13 @@ impl lsp::notification::Notification for LspExtClearFlycheck {
     type Params = ();
     const METHOD: &'static str = "rust-analyzer/clearFlycheck";
 }
+
+/// Sent by typescript-language-server once a refactoring that introduces a symbol, like
+/// extracting a function, has been applied, so that the user is prompted for its name.
+pub enum LspExtTypeScriptRename {}
+
+impl lsp::request::Request for LspExtTypeScriptRename {
+    type Params = lsp::TextDocumentPositionParams;
+    type Result = ();
+    const METHOD: &'static str = "_typescript.rename";
+}
@@ -151,27 +151,25 @@ impl ManifestTree {
         });
 
         for (manifest_name, (root_path, presence)) in &mut roots {
-            if *presence == LabelPresence::Present {
+            let Some(provider) = ManifestProviders::global(cx).get(manifest_name.borrow()) else {
+                log::warn!("Manifest provider `{}` not found", manifest_name.as_ref());
+                continue;
+            };
+            let nested = provider.allows_nested_roots();
+            if *presence == LabelPresence::Present && !nested {
                 continue;
             }
 
-            let depth = root_path
-                .as_ref()
-                .map(|root_path| {
-                    path.strip_prefix(&root_path.path)
-                        .unwrap()
-                        .components()
-                        .count()
-                })
-                .unwrap_or_else(|| path.components().count() + 1);
+            let depth = match root_path.as_ref() {
+                Some(root_path) if !nested => path
+                    .strip_prefix(&root_path.path)
+                    .unwrap()
+                    .components()
+                    .count(),
+                _ => path.components().count() + 1,
+            };
 
             if depth > 0 {
-                let Some(provider) = ManifestProviders::global(cx).get(manifest_name.borrow())
-                else {
-                    log::warn!("Manifest provider `{}` not found", manifest_name.as_ref());
-                    continue;
-                };
-
                 let root = provider.search(ManifestQuery {
                     path: path.clone(),
                     depth,
@@ -188,6 +186,11 @@ impl ManifestTree {
                             path: known_root,
                         });
                     }),
+                    // The root that was found before doesn't have a manifest anymore.
+                    None if *presence == LabelPresence::Present => {
+                        *presence = LabelPresence::KnownAbsent;
+                        *root_path = None;
+                    }
                     None => worktree_roots.update(cx, |this, _| {
                         this.roots
                             .insert(&key, manifest_name.clone(), LabelPresence::KnownAbsent);
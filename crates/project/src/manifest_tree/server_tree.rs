@@ -74,6 +74,10 @@ impl LanguageServerTreeNode {
     pub(crate) fn server_id(&self) -> Option<LanguageServerId> {
         self.0.upgrade()?.id.get().copied()
     }
+    /// Returns the root of the subproject that this node is for.
+    pub(crate) fn root_path(&self) -> Option<ProjectPath> {
+        Some(self.0.upgrade()?.path.clone())
+    }
     /// Returns a language server ID for this node if it has already been initialized; otherwise runs the provided closure to initialize the language server node in a tree.
     /// May return None if the node no longer belongs to the server tree it was created in.
     pub(crate) fn server_id_or_init(
@@ -217,7 +221,11 @@ impl LanguageServerTree {
                     .and_then(|name| roots.get(&name))
                     .cloned()
                     .unwrap_or_else(|| root_path.clone());
-                let attach = adapter.attach_kind();
+                let attach = match settings.instance_per_root {
+                    Some(true) => Attach::InstancePerRoot,
+                    Some(false) => Attach::Shared,
+                    None => adapter.attach_kind(),
+                };
 
                 let inner_node = self
                     .instances
@@ -0,0 +1,130 @@
+use crate::environment::EnvironmentErrorMessage;
+use std::process::ExitStatus;
+
+#[cfg(not(target_os = "windows"))]
+use {collections::HashMap, std::path::Path, util::ResultExt};
+
+#[derive(Clone)]
+pub enum MiseError {
+    NotFound,
+    FailedRun,
+    NonZeroExit(ExitStatus, Vec<u8>),
+    InvalidJson,
+}
+
+impl From<MiseError> for Option<EnvironmentErrorMessage> {
+    fn from(value: MiseError) -> Self {
+        match value {
+            MiseError::NotFound => None,
+            MiseError::FailedRun | MiseError::NonZeroExit(_, _) => Some(EnvironmentErrorMessage(
+                String::from("Failed to run mise. See logs for more info"),
+            )),
+            MiseError::InvalidJson => Some(EnvironmentErrorMessage(String::from(
+                "Mise returned invalid json. See logs for more info",
+            ))),
+        }
+    }
+}
+
+/// Returns the variables that mise sets in the directory for the tools in its `mise.toml` or
+/// `.tool-versions` files, like the `PATH` that includes them.
+#[cfg(not(target_os = "windows"))]
+#[cfg_attr(feature = "test-support", allow(dead_code))]
+pub async fn load_mise_environment(
+    env: &HashMap<String, String>,
+    dir: &Path,
+) -> Result<HashMap<String, String>, MiseError> {
+    // mise is often installed in a directory that only the shell's `PATH` includes.
+    let Ok(mise_path) = which::which_in("mise", env.get("PATH"), dir) else {
+        return Err(MiseError::NotFound);
+    };
+
+    let Some(mise_output) = smol::process::Command::new(mise_path)
+        .args(["env", "--json"])
+        .envs(env)
+        .env("TERM", "dumb")
+        .current_dir(dir)
+        .output()
+        .await
+        .log_err()
+    else {
+        return Err(MiseError::FailedRun);
+    };
+
+    if !mise_output.status.success() {
+        log::error!(
+            "Loading mise environment failed ({}), stderr: {}",
+            mise_output.status,
+            String::from_utf8_lossy(&mise_output.stderr)
+        );
+        return Err(MiseError::NonZeroExit(
+            mise_output.status,
+            mise_output.stderr,
+        ));
+    }
+
+    let output = String::from_utf8_lossy(&mise_output.stdout);
+    let Some(env) = serde_json::from_str(&output).log_err() else {
+        return Err(MiseError::InvalidJson);
+    };
+
+    Ok(env)
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::os::unix::fs::PermissionsExt as _;
+    use util::test::TempTree;
+
+    /// Puts a `mise` script on the `PATH` that prints the given output and exits with the given
+    /// status when it's run with `env --json`.
+    fn fake_mise(output: &str, status: i32) -> (TempTree, HashMap<String, String>) {
+        let tree = TempTree::new(json!({
+            "bin": {
+                "mise": format!(
+                    "#!/bin/sh\n[ \"$*\" = \"env --json\" ] || exit 2\necho '{output}'\nexit {status}\n"
+                ),
+            },
+            "project": { "mise.toml": "" },
+        }));
+        let mise_path = tree.path().join("bin/mise");
+        std::fs::set_permissions(&mise_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let env = HashMap::from_iter([(
+            "PATH".to_string(),
+            tree.path().join("bin").to_string_lossy().into_owned(),
+        )]);
+        (tree, env)
+    }
+
+    #[test]
+    fn test_load_mise_environment() {
+        let (tree, env) = fake_mise(
+            r#"{"NODE_ENV": "development", "PATH": "/mise/node/bin"}"#,
+            0,
+        );
+        let environment = smol::block_on(load_mise_environment(&env, &tree.path().join("project")));
+        assert_eq!(
+            environment.ok(),
+            Some(HashMap::from_iter([
+                ("NODE_ENV".to_string(), "development".to_string()),
+                ("PATH".to_string(), "/mise/node/bin".to_string()),
+            ]))
+        );
+
+        let (tree, env) = fake_mise("mise ERROR untrusted config", 1);
+        let environment = smol::block_on(load_mise_environment(&env, &tree.path().join("project")));
+        assert!(matches!(environment, Err(MiseError::NonZeroExit(..))));
+
+        let (tree, env) = fake_mise("not json", 0);
+        let environment = smol::block_on(load_mise_environment(&env, &tree.path().join("project")));
+        assert!(matches!(environment, Err(MiseError::InvalidJson)));
+
+        let environment = smol::block_on(load_mise_environment(
+            &HashMap::from_iter([("PATH".to_string(), "/nonexistent".to_string())]),
+            &tree.path().join("project"),
+        ));
+        assert!(matches!(environment, Err(MiseError::NotFound)));
+    }
+}
@@ -25,7 +25,7 @@ use smol::stream::StreamExt;
 use util::{ResultExt, TryFutureExt};
 
 use crate::{
-    File, PathChange, ProjectEntryId, Worktree, lsp_store::WorktreeId,
+    File, PathChange, ProjectEntryId, TrustedFolders, Worktree, lsp_store::WorktreeId,
     worktree_store::WorktreeStore,
 };
 
@@ -119,9 +119,15 @@ impl PrettierStore {
             Some((worktree_id, buffer_path)) => {
                 let fs = Arc::clone(&self.fs);
                 let installed_prettiers = self.prettier_instances.keys().cloned().collect();
+                // The project's own Prettier runs the plugins it's configured with, so folders
+                // have to be trusted for it to be used instead of the default one.
+                let trusted = TrustedFolders::is_trusted(&buffer_path, cx);
                 cx.spawn(async move |lsp_store, cx| {
                     match cx
                         .background_spawn(async move {
+                            if !trusted {
+                                return Ok(ControlFlow::Continue(None));
+                            }
                             Prettier::locate_prettier_installation(
                                 fs.as_ref(),
                                 &installed_prettiers,
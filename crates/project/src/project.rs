@@ -1,10 +1,12 @@
 pub mod buffer_store;
 mod color_extractor;
+pub mod comment_store;
 pub mod connection_manager;
 pub mod context_server_store;
 pub mod debounced_delay;
 pub mod debugger;
 pub mod git_store;
+pub mod guest_permissions;
 pub mod image_store;
 pub mod lsp_command;
 pub mod lsp_store;
@@ -16,6 +18,7 @@ mod task_inventory;
 pub mod task_store;
 pub mod terminals;
 pub mod toolchain_store;
+pub mod trusted_folders;
 pub mod worktree_store;
 
 #[cfg(test)]
@@ -23,6 +26,7 @@ mod project_tests;
 
 mod direnv;
 mod environment;
+mod mise;
 use buffer_diff::BufferDiff;
 use context_server_store::ContextServerStore;
 pub use environment::{EnvironmentErrorMessage, ProjectEnvironmentEvent};
@@ -40,9 +44,12 @@ pub use manifest_tree::ManifestTree;
 use anyhow::{Context as _, Result, anyhow};
 use buffer_store::{BufferStore, BufferStoreEvent};
 use client::{
-    Client, Collaborator, PendingEntitySubscription, ProjectId, TypedEnvelope, UserStore, proto,
+    Client, Collaborator, PendingEntitySubscription, ProjectId, TypedEnvelope, UserId, UserStore,
+    proto,
 };
 use clock::ReplicaId;
+use comment_store::CommentStore;
+use guest_permissions::GuestPermissions;
 
 use dap::{DapRegistry, client::DebugAdapterClient};
 
@@ -110,7 +117,7 @@ use std::{
 
 use task_store::TaskStore;
 use terminals::Terminals;
-use text::{Anchor, BufferId};
+use text::{Anchor, BufferId, TransactionId};
 use toolchain_store::EmptyToolchainStore;
 use util::{
     ResultExt as _,
@@ -138,6 +145,7 @@ pub use lsp_store::{
     SERVER_PROGRESS_THROTTLE_TIMEOUT,
 };
 pub use toolchain_store::ToolchainStore;
+pub use trusted_folders::{TrustedFolders, WorkspaceTrustSettings};
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
 const MAX_SEARCH_RESULT_FILES: usize = 5_000;
 const MAX_SEARCH_RESULT_RANGES: usize = 10_000;
@@ -174,6 +182,7 @@ pub struct Project {
     dap_store: Entity<DapStore>,
 
     breakpoint_store: Entity<BreakpointStore>,
+    comment_store: Entity<CommentStore>,
     client: Arc<client::Client>,
     join_project_response_message_id: u32,
     task_store: Entity<TaskStore>,
@@ -183,6 +192,10 @@ pub struct Project {
     client_state: ProjectClientState,
     git_store: Entity<GitStore>,
     collaborators: HashMap<proto::PeerId, Collaborator>,
+    /// Set by the host for each guest; guests only need their own.
+    guest_permissions: HashMap<UserId, GuestPermissions>,
+    collab_sync_status: CollabSyncStatus,
+    buffers_edited_offline: HashSet<BufferId>,
     client_subscriptions: Vec<client::Subscription>,
     worktree_store: Entity<WorktreeStore>,
     buffer_store: Entity<BufferStore>,
@@ -267,6 +280,18 @@ enum ProjectClientState {
     },
 }
 
+/// How a guest's copy of a shared project stands with the host's.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollabSyncStatus {
+    #[default]
+    Synced,
+    /// The connection to the host was lost. Local edits are kept and sent
+    /// once it returns.
+    Offline,
+    /// Reconnected, and exchanging the edits made while offline.
+    Syncing,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Event {
     LanguageServerAdded(LanguageServerId, LanguageServerName, Option<WorktreeId>),
@@ -311,10 +336,17 @@ pub enum Event {
     HostReshared,
     Reshared,
     Rejoined,
+    CollabSyncStatusChanged,
     RefreshInlayHints,
     RefreshCodeLens,
     RevealInProjectPanel(ProjectEntryId),
-    SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
+    SnippetEdit(BufferId, Vec<(Range<Anchor>, Snippet)>),
+    RenameRequested(BufferId, lsp::Position),
+    /// An edit spanning the workspace was applied, like a rename or a code action.
+    WorkspaceEditApplied {
+        title: SharedString,
+        transactions: Vec<(Entity<Buffer>, TransactionId)>,
+    },
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
     AgentLocationChanged,
     PullWorkspaceDiagnostics,
@@ -636,7 +668,7 @@ impl LspAction {
         }
     }
 
-    fn command(&self) -> Option<&lsp::Command> {
+    pub fn command(&self) -> Option<&lsp::Command> {
         match self {
             Self::Action(action) => action.command.as_ref(),
             Self::Command(command) => Some(command),
@@ -894,6 +926,7 @@ impl Project {
     pub fn init_settings(cx: &mut App) {
         WorktreeSettings::register(cx);
         ProjectSettings::register(cx);
+        WorkspaceTrustSettings::register(cx);
     }
 
     pub fn init(client: &Arc<Client>, cx: &mut App) {
@@ -904,6 +937,7 @@ impl Project {
         client.add_entity_message_handler(Self::handle_add_collaborator);
         client.add_entity_message_handler(Self::handle_update_project_collaborator);
         client.add_entity_message_handler(Self::handle_remove_collaborator);
+        client.add_entity_message_handler(Self::handle_update_guest_permissions);
         client.add_entity_message_handler(Self::handle_update_project);
         client.add_entity_message_handler(Self::handle_unshare_project);
         client.add_entity_request_handler(Self::handle_update_buffer);
@@ -925,6 +959,7 @@ impl Project {
         ToolchainStore::init(&client);
         DapStore::init(&client, cx);
         BreakpointStore::init(&client);
+        CommentStore::init(&client);
         context_server_store::init(cx);
     }
 
@@ -949,7 +984,7 @@ impl Project {
             let context_server_store =
                 cx.new(|cx| ContextServerStore::new(worktree_store.clone(), cx));
 
-            let environment = cx.new(|_| ProjectEnvironment::new(env));
+            let environment = cx.new(|cx| ProjectEnvironment::new(env, worktree_store.clone(), cx));
             let manifest_tree = ManifestTree::new(worktree_store.clone(), cx);
             let toolchain_store = cx.new(|cx| {
                 ToolchainStore::local(
@@ -967,6 +1002,8 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::local(worktree_store.clone(), buffer_store.clone()));
+            let comment_store =
+                cx.new(|_| CommentStore::local(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_local(
@@ -1047,6 +1084,9 @@ impl Project {
             Self {
                 buffer_ordered_messages_tx: tx,
                 collaborators: Default::default(),
+                guest_permissions: Default::default(),
+                collab_sync_status: CollabSyncStatus::Synced,
+                buffers_edited_offline: Default::default(),
                 worktree_store,
                 buffer_store,
                 image_store,
@@ -1067,6 +1107,7 @@ impl Project {
                 fs,
                 ssh_client: None,
                 breakpoint_store,
+                comment_store,
                 dap_store,
 
                 buffers_needing_diff: Default::default(),
@@ -1157,7 +1198,8 @@ impl Project {
             cx.subscribe(&settings_observer, Self::on_settings_observer_event)
                 .detach();
 
-            let environment = cx.new(|_| ProjectEnvironment::new(None));
+            let environment =
+                cx.new(|cx| ProjectEnvironment::new(None, worktree_store.clone(), cx));
 
             let lsp_store = cx.new(|cx| {
                 LspStore::new_remote(
@@ -1175,6 +1217,10 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::remote(SSH_PROJECT_ID, ssh_proto.clone()));
+            // Comments are shared with collaborators rather than with the remote server, so they
+            // live on this side of the connection.
+            let comment_store =
+                cx.new(|_| CommentStore::local(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_ssh(
@@ -1192,15 +1238,19 @@ impl Project {
 
             cx.subscribe(&ssh, Self::on_ssh_event).detach();
 
-            let this = Self {
+            let mut this = Self {
                 buffer_ordered_messages_tx: tx,
                 collaborators: Default::default(),
+                guest_permissions: Default::default(),
+                collab_sync_status: CollabSyncStatus::Synced,
+                buffers_edited_offline: Default::default(),
                 worktree_store,
                 buffer_store,
                 image_store,
                 lsp_store,
                 context_server_store,
                 breakpoint_store,
+                comment_store,
                 dap_store,
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
@@ -1208,6 +1258,8 @@ impl Project {
                 client_subscriptions: Vec::new(),
                 _subscriptions: vec![
                     cx.on_release(Self::release),
+                    cx.observe_global::<TrustedFolders>(Self::send_trusted_folders),
+                    cx.observe_global::<SettingsStore>(Self::send_trusted_folders),
                     cx.on_app_quit(|this, cx| {
                         let shutdown = this.ssh_client.take().and_then(|client| {
                             client.read(cx).shutdown_processes(
@@ -1266,6 +1318,7 @@ impl Project {
             ssh_proto.add_entity_request_handler(Self::handle_language_server_prompt_request);
             ssh_proto.add_entity_message_handler(Self::handle_hide_toast);
             ssh_proto.add_entity_request_handler(Self::handle_update_buffer_from_ssh);
+            this.send_trusted_folders(cx);
             BufferStore::init(&ssh_proto);
             LspStore::init(&ssh_proto);
             SettingsObserver::init(&ssh_proto);
@@ -1365,10 +1418,11 @@ impl Project {
         let context_server_store =
             cx.new(|cx| ContextServerStore::new(worktree_store.clone(), cx))?;
 
-        let environment = cx.new(|_| ProjectEnvironment::new(None))?;
+        let environment = cx.new(|cx| ProjectEnvironment::new(None, worktree_store.clone(), cx))?;
 
         let breakpoint_store =
             cx.new(|_| BreakpointStore::remote(remote_id, client.clone().into()))?;
+        let comment_store = cx.new(|_| CommentStore::remote(remote_id, client.clone().into()))?;
         let dap_store = cx.new(|cx| {
             DapStore::new_collab(
                 remote_id,
@@ -1460,6 +1514,9 @@ impl Project {
                 context_server_store,
                 active_entry: None,
                 collaborators: Default::default(),
+                guest_permissions: Default::default(),
+                collab_sync_status: CollabSyncStatus::Synced,
+                buffers_edited_offline: Default::default(),
                 join_project_response_message_id: response.message_id,
                 languages,
                 user_store: user_store.clone(),
@@ -1478,6 +1535,7 @@ impl Project {
                     replica_id,
                 },
                 breakpoint_store,
+                comment_store,
                 dap_store: dap_store.clone(),
                 git_store: git_store.clone(),
                 buffers_needing_diff: Default::default(),
@@ -1672,6 +1730,10 @@ impl Project {
         self.breakpoint_store.clone()
     }
 
+    pub fn comment_store(&self) -> Entity<CommentStore> {
+        self.comment_store.clone()
+    }
+
     pub fn active_debug_session(&self, cx: &App) -> Option<(Entity<Session>, ActiveStackFrame)> {
         let active_position = self.breakpoint_store.read(cx).active_position()?;
         let session = self
@@ -2137,6 +2199,9 @@ impl Project {
             self.client
                 .subscribe_to_entity(project_id)?
                 .set_entity(&self.breakpoint_store, &mut cx.to_async()),
+            self.client
+                .subscribe_to_entity(project_id)?
+                .set_entity(&self.comment_store, &mut cx.to_async()),
             self.client
                 .subscribe_to_entity(project_id)?
                 .set_entity(&self.git_store, &mut cx.to_async()),
@@ -2154,6 +2219,9 @@ impl Project {
         self.breakpoint_store.update(cx, |breakpoint_store, _| {
             breakpoint_store.shared(project_id, self.client.clone().into())
         });
+        self.comment_store.update(cx, |comment_store, _| {
+            comment_store.shared(project_id, self.client.clone().into())
+        });
         self.dap_store.update(cx, |dap_store, cx| {
             dap_store.shared(project_id, self.client.clone().into(), cx);
         });
@@ -2220,10 +2288,41 @@ impl Project {
         });
         self.enqueue_buffer_ordered_message(BufferOrderedMessage::Resync)
             .unwrap();
+        self.set_collab_sync_status(CollabSyncStatus::Syncing, cx);
         cx.emit(Event::Rejoined);
         Ok(())
     }
 
+    /// Called when the connection to the server drops while this project is
+    /// joined, to keep accepting edits until it can rejoin.
+    pub fn connection_lost(&mut self, cx: &mut Context<Self>) {
+        if self.is_via_collab() && !self.is_disconnected(cx) {
+            self.set_collab_sync_status(CollabSyncStatus::Offline, cx);
+        }
+    }
+
+    pub fn collab_sync_status(&self) -> CollabSyncStatus {
+        self.collab_sync_status
+    }
+
+    /// How many buffers were edited since the connection to the host was
+    /// lost, and haven't been synced since.
+    pub fn buffers_edited_offline(&self) -> usize {
+        self.buffers_edited_offline.len()
+    }
+
+    fn set_collab_sync_status(&mut self, status: CollabSyncStatus, cx: &mut Context<Self>) {
+        if self.collab_sync_status == status {
+            return;
+        }
+        self.collab_sync_status = status;
+        if status == CollabSyncStatus::Synced {
+            self.buffers_edited_offline.clear();
+        }
+        cx.emit(Event::CollabSyncStatusChanged);
+        cx.notify();
+    }
+
     pub fn unshare(&mut self, cx: &mut Context<Self>) -> Result<()> {
         self.unshare_internal(cx)?;
         cx.emit(Event::RemoteIdChanged(None));
@@ -2239,6 +2338,7 @@ impl Project {
         if let ProjectClientState::Shared { remote_id, .. } = self.client_state {
             self.client_state = ProjectClientState::Local;
             self.collaborators.clear();
+            self.guest_permissions.clear();
             self.client_subscriptions.clear();
             self.worktree_store.update(cx, |store, cx| {
                 store.unshared(cx);
@@ -2253,6 +2353,9 @@ impl Project {
             self.breakpoint_store.update(cx, |breakpoint_store, cx| {
                 breakpoint_store.unshared(cx);
             });
+            self.comment_store.update(cx, |comment_store, cx| {
+                comment_store.unshared(cx);
+            });
             self.dap_store.update(cx, |dap_store, cx| {
                 dap_store.unshared(cx);
             });
@@ -2295,8 +2398,100 @@ impl Project {
             }
 
             *capability = new_capability;
-            for buffer in self.opened_buffers(cx) {
-                buffer.update(cx, |buffer, cx| buffer.set_capability(new_capability, cx));
+            self.update_buffer_capabilities(cx);
+        }
+    }
+
+    /// The permissions the host has given a guest of this shared project.
+    pub fn guest_permissions(&self, user_id: UserId) -> GuestPermissions {
+        self.guest_permissions
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_guest_permissions(
+        &mut self,
+        user_id: UserId,
+        permissions: GuestPermissions,
+        cx: &mut Context<Self>,
+    ) {
+        if self.is_via_collab() {
+            return;
+        }
+        if permissions == GuestPermissions::default() {
+            self.guest_permissions.remove(&user_id);
+        } else {
+            self.guest_permissions.insert(user_id, permissions);
+        }
+        self.guest_permissions_changed(cx);
+        cx.notify();
+    }
+
+    fn guest_permissions_changed(&self, cx: &mut App) {
+        let permissions_by_peer = self
+            .collaborators
+            .values()
+            .filter_map(|collaborator| {
+                let permissions = self.guest_permissions.get(&collaborator.user_id)?;
+                Some((collaborator.peer_id, permissions.clone()))
+            })
+            .collect();
+        self.worktree_store.update(cx, |worktree_store, _| {
+            worktree_store.set_guest_permissions(permissions_by_peer)
+        });
+
+        if let ProjectClientState::Shared { remote_id } = self.client_state {
+            self.client
+                .send(proto::UpdateGuestPermissions {
+                    project_id: remote_id,
+                    permissions: self
+                        .guest_permissions
+                        .iter()
+                        .map(|(user_id, permissions)| permissions.to_proto(*user_id))
+                        .collect(),
+                })
+                .log_err();
+        }
+    }
+
+    /// The permissions the host has given the current user, when they're a guest.
+    fn own_guest_permissions(&self, cx: &App) -> Option<&GuestPermissions> {
+        if !self.is_via_collab() {
+            return None;
+        }
+        let user_id = self.user_store.read(cx).current_user()?.id;
+        self.guest_permissions.get(&user_id)
+    }
+
+    /// Whether the current user can edit files in the given worktree.
+    pub fn can_edit_worktree(&self, worktree_id: WorktreeId, cx: &App) -> bool {
+        !self.is_read_only(cx)
+            && self
+                .own_guest_permissions(cx)
+                .map_or(true, |permissions| permissions.can_edit(worktree_id))
+    }
+
+    fn buffer_capability(&self, buffer: &Buffer, cx: &App) -> Capability {
+        let can_edit = match buffer.file() {
+            Some(file) => self.can_edit_worktree(file.worktree_id(cx), cx),
+            None => !self.is_read_only(cx),
+        };
+        if can_edit {
+            Capability::ReadWrite
+        } else {
+            Capability::ReadOnly
+        }
+    }
+
+    fn update_buffer_capabilities(&self, cx: &mut App) {
+        if !self.is_via_collab() {
+            return;
+        }
+        for buffer in self.opened_buffers(cx) {
+            let capability = self.buffer_capability(buffer.read(cx), cx);
+            if buffer.read(cx).capability() != capability {
+                buffer.update(cx, |buffer, cx| buffer.set_capability(capability, cx));
             }
         }
     }
@@ -2350,7 +2545,11 @@ impl Project {
     }
 
     pub fn is_read_only(&self, cx: &App) -> bool {
-        self.is_disconnected(cx) || self.capability() == Capability::ReadOnly
+        self.is_disconnected(cx)
+            || self.capability() == Capability::ReadOnly
+            || self
+                .own_guest_permissions(cx)
+                .is_some_and(|permissions| permissions.read_only)
     }
 
     pub fn is_local(&self) -> bool {
@@ -2362,6 +2561,51 @@ impl Project {
         }
     }
 
+    /// Returns the worktrees that aren't trusted, whose tasks can't run and whose project
+    /// settings don't apply.
+    pub fn untrusted_worktrees<'a>(
+        &'a self,
+        cx: &'a App,
+    ) -> impl 'a + Iterator<Item = Entity<Worktree>> {
+        self.visible_worktrees(cx).filter(move |worktree| {
+            self.worktree_trust_path(worktree.read(cx), cx)
+                .is_some_and(|trust_path| !TrustedFolders::is_trusted(&trust_path, cx))
+        })
+    }
+
+    /// The path that the worktree is trusted by, or `None` in projects joined in a call, whose
+    /// host decides whether to trust them.
+    pub fn worktree_trust_path(&self, worktree: &Worktree, cx: &App) -> Option<PathBuf> {
+        if self.is_via_collab() {
+            return None;
+        }
+        match self.ssh_connection_string(cx) {
+            Some(connection) => Some(trusted_folders::remote_trust_path(
+                &connection,
+                &worktree.abs_path(),
+            )),
+            None => Some(worktree.abs_path().to_path_buf()),
+        }
+    }
+
+    /// Tells the remote server which of its folders are trusted, since it applies their settings
+    /// and starts their language servers.
+    fn send_trusted_folders(&mut self, cx: &mut Context<Self>) {
+        let Some(ssh_client) = &self.ssh_client else {
+            return;
+        };
+        let ssh_client = ssh_client.read(cx);
+        let trusted_folders = TrustedFolders::remote_folders(&ssh_client.connection_string(), cx);
+        ssh_client
+            .proto_client()
+            .send(proto::UpdateTrustedFolders {
+                project_id: SSH_PROJECT_ID,
+                trust_all: trusted_folders.is_none(),
+                abs_paths: trusted_folders.unwrap_or_default(),
+            })
+            .log_err();
+    }
+
     pub fn is_via_ssh(&self) -> bool {
         match &self.client_state {
             ProjectClientState::Local | ProjectClientState::Shared { .. } => {
@@ -2651,7 +2895,17 @@ impl Project {
         let mut changes = rx.ready_chunks(MAX_BATCH_SIZE);
 
         while let Some(changes) = changes.next().await {
-            let is_local = this.read_with(cx, |this, _| this.is_local())?;
+            let (is_local, is_offline) = this.read_with(cx, |this, _| {
+                (
+                    this.is_local(),
+                    this.collab_sync_status == CollabSyncStatus::Offline,
+                )
+            })?;
+            // Requests would fail while offline. The operations stay in each
+            // buffer's history and are sent to the host when resyncing.
+            if is_offline {
+                needs_resync_with_host = true;
+            }
 
             for change in changes {
                 match change {
@@ -2671,13 +2925,23 @@ impl Project {
 
                     BufferOrderedMessage::Resync => {
                         operations_by_buffer_id.clear();
-                        if this
+                        let synced = this
                             .update(cx, |this, cx| this.synchronize_remote_buffers(cx))?
                             .await
-                            .is_ok()
-                        {
+                            .is_ok();
+                        if synced {
                             needs_resync_with_host = false;
                         }
+                        this.update(cx, |this, cx| {
+                            if this.collab_sync_status == CollabSyncStatus::Syncing {
+                                let status = if synced {
+                                    CollabSyncStatus::Synced
+                                } else {
+                                    CollabSyncStatus::Offline
+                                };
+                                this.set_collab_sync_status(status, cx);
+                            }
+                        })?;
                     }
 
                     BufferOrderedMessage::LanguageServerUpdate {
@@ -2730,6 +2994,12 @@ impl Project {
         match event {
             BufferStoreEvent::BufferAdded(buffer) => {
                 self.register_buffer(buffer, cx).log_err();
+                if self.is_via_collab() {
+                    let capability = self.buffer_capability(buffer.read(cx), cx);
+                    if buffer.read(cx).capability() != capability {
+                        buffer.update(cx, |buffer, cx| buffer.set_capability(capability, cx));
+                    }
+                }
             }
             BufferStoreEvent::BufferDropped(buffer_id) => {
                 if let Some(ref ssh_client) = self.ssh_client {
@@ -2855,6 +3125,17 @@ impl Project {
                     cx.emit(Event::SnippetEdit(*buffer_id, edits.clone()))
                 }
             }
+            LspStoreEvent::RenameRequested {
+                buffer_id,
+                position,
+            } => cx.emit(Event::RenameRequested(*buffer_id, *position)),
+            LspStoreEvent::WorkspaceEditApplied {
+                title,
+                transactions,
+            } => cx.emit(Event::WorkspaceEditApplied {
+                title: title.clone(),
+                transactions: transactions.clone(),
+            }),
         }
     }
 
@@ -2993,6 +3274,12 @@ impl Project {
             } => {
                 let operation = language::proto::serialize_operation(operation);
 
+                if self.collab_sync_status == CollabSyncStatus::Offline
+                    && self.buffers_edited_offline.insert(buffer_id)
+                {
+                    cx.emit(Event::CollabSyncStatusChanged);
+                }
+
                 if let Some(ssh) = &self.ssh_client {
                     ssh.read(cx)
                         .proto_client()
@@ -3555,6 +3842,17 @@ impl Project {
         })
     }
 
+    pub fn resolve_code_lens(
+        &self,
+        buffer_handle: &Entity<Buffer>,
+        action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CodeAction>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.resolve_code_lens(buffer_handle, action, cx)
+        })
+    }
+
     pub fn apply_code_action(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -3567,6 +3865,27 @@ impl Project {
         })
     }
 
+    /// Lets the observers of the project know that the edits of `transaction` were applied
+    /// together, under `title`.
+    pub fn record_workspace_edit(
+        &mut self,
+        title: impl Into<SharedString>,
+        transaction: &ProjectTransaction,
+        cx: &mut Context<Self>,
+    ) {
+        if transaction.0.is_empty() {
+            return;
+        }
+        cx.emit(Event::WorkspaceEditApplied {
+            title: title.into(),
+            transactions: transaction
+                .0
+                .iter()
+                .map(|(buffer, transaction)| (buffer.clone(), transaction.id))
+                .collect(),
+        });
+    }
+
     pub fn apply_code_action_kind(
         &self,
         buffers: HashSet<Entity<Buffer>>,
@@ -3623,6 +3942,20 @@ impl Project {
         )
     }
 
+    /// The edits renaming the symbol at `position` would make, computed without applying them.
+    pub fn rename_edits<T: ToPointUtf16>(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: T,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<(Entity<Buffer>, Vec<(Range<Anchor>, Arc<str>)>)>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.rename_edits(buffer, position, new_name, cx)
+        })
+    }
+
     pub fn on_type_format<T: ToPointUtf16>(
         &mut self,
         buffer: Entity<Buffer>,
@@ -4384,9 +4717,11 @@ impl Project {
                 buffer_store.forget_shared_buffers_for(&collaborator.peer_id);
             });
             this.breakpoint_store.read(cx).broadcast();
+            this.comment_store.read(cx).broadcast();
             cx.emit(Event::CollaboratorJoined(collaborator.peer_id));
             this.collaborators
                 .insert(collaborator.peer_id, collaborator);
+            this.guest_permissions_changed(cx);
         })?;
 
         Ok(())
@@ -4412,6 +4747,7 @@ impl Project {
                 .context("received UpdateProjectCollaborator for unknown peer")?;
             let is_host = collaborator.is_host;
             this.collaborators.insert(new_peer_id, collaborator);
+            this.guest_permissions_changed(cx);
 
             log::info!("peer {} became {}", old_peer_id, new_peer_id,);
             this.buffer_store.update(cx, |buffer_store, _| {
@@ -4590,17 +4926,66 @@ impl Project {
         cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let buffer_store = this.read_with(&cx, |this, cx| {
+            this.authorize_buffer_update(&envelope, cx)?;
             if let Some(ssh) = &this.ssh_client {
                 let mut payload = envelope.payload.clone();
                 payload.project_id = SSH_PROJECT_ID;
                 cx.background_spawn(ssh.read(cx).proto_client().request(payload))
                     .detach_and_log_err(cx);
             }
-            this.buffer_store.clone()
-        })?;
+            anyhow::Ok(this.buffer_store.clone())
+        })??;
         BufferStore::handle_update_buffer(buffer_store, envelope, cx).await
     }
 
+    /// Rejects edits from guests that the host hasn't allowed to make them.
+    fn authorize_buffer_update(
+        &self,
+        envelope: &TypedEnvelope<proto::UpdateBuffer>,
+        cx: &App,
+    ) -> Result<()> {
+        let is_edit = envelope.payload.operations.iter().any(|operation| {
+            !matches!(
+                operation.variant,
+                None | Some(proto::operation::Variant::UpdateSelections(_))
+            )
+        });
+        if !is_edit {
+            return Ok(());
+        }
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let worktree_id = self
+            .buffer_store
+            .read(cx)
+            .get(buffer_id)
+            .and_then(|buffer| Some(buffer.read(cx).file()?.worktree_id(cx)));
+        self.worktree_store
+            .read(cx)
+            .authorize_guest_edit(envelope.original_sender_id, worktree_id)
+    }
+
+    async fn handle_update_guest_permissions(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateGuestPermissions>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            this.guest_permissions = envelope
+                .payload
+                .permissions
+                .iter()
+                .map(|permissions| {
+                    (
+                        permissions.user_id,
+                        GuestPermissions::from_proto(permissions),
+                    )
+                })
+                .collect();
+            this.update_buffer_capabilities(cx);
+            cx.notify();
+        })
+    }
+
     fn retain_remotely_created_models(
         &mut self,
         cx: &mut Context<Self>,
@@ -4717,7 +5102,12 @@ impl Project {
         mut cx: AsyncApp,
     ) -> Result<proto::OpenBufferResponse> {
         let buffer = this
-            .update(&mut cx, |this, cx| this.create_buffer(cx))?
+            .update(&mut cx, |this, cx| {
+                this.worktree_store
+                    .read(cx)
+                    .authorize_guest_edit(envelope.original_sender_id, None)?;
+                anyhow::Ok(this.create_buffer(cx))
+            })??
             .await?;
         let peer_id = envelope.original_sender_id()?;
 
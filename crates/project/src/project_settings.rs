@@ -1,10 +1,10 @@
 use anyhow::Context as _;
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use context_server::ContextServerCommand;
 use dap::adapters::DebugAdapterName;
 use fs::Fs;
 use futures::StreamExt as _;
-use gpui::{App, AsyncApp, BorrowAppContext, Context, Entity, EventEmitter, Task};
+use gpui::{App, AsyncApp, BorrowAppContext, Context, Entity, EventEmitter, Subscription, Task};
 use lsp::LanguageServerName;
 use paths::{
     EDITORCONFIG_NAME, local_debug_file_relative_path, local_settings_file_relative_path,
@@ -32,6 +32,7 @@ use worktree::{PathChange, UpdatedEntriesSet, Worktree, WorktreeId};
 
 use crate::{
     task_store::{TaskSettingsLocation, TaskStore},
+    trusted_folders::TrustedFolders,
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 
@@ -73,6 +74,23 @@ pub struct ProjectSettings {
     #[serde(default)]
     pub load_direnv: DirenvSettings,
 
+    /// Whether to load the environment that mise sets up for the tools in a directory's
+    /// `mise.toml` or `.tool-versions` file, using `mise env`. mise only runs in directories
+    /// that have one of these files or are below one that does.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub load_mise: bool,
+
+    /// Environment variables to set for the project's terminals, tasks, debug adapters, and
+    /// language servers, on top of the ones they inherit.
+    #[serde(default)]
+    pub env: ProjectEnvSettings,
+
+    /// The JSON schemas that JSON and YAML files are validated and completed against.
+    #[serde(default)]
+    pub json_schemas: JsonSchemaSettings,
+
     /// Configuration for session-related features
     #[serde(default)]
     pub session: SessionSettings,
@@ -118,6 +136,54 @@ pub enum DirenvSettings {
     Direct,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ProjectEnvSettings {
+    /// The `.env` files to load, relative to the worktree's root. Files that don't exist are
+    /// skipped, and variables in later files override the ones in earlier files.
+    ///
+    /// Default: []
+    pub files: Vec<String>,
+    /// Variables to set, which override the ones from `files`. Values can refer to the variables
+    /// set before them with `${NAME}`, like `"PATH": "${PATH}:./bin"`.
+    ///
+    /// Default: {}
+    pub variables: HashMap<String, String>,
+    /// The profile whose files and variables are applied after these ones.
+    ///
+    /// Default: null
+    pub profile: Option<String>,
+    /// Named sets of files and variables, one of which can be picked with `profile`.
+    ///
+    /// Default: {}
+    pub profiles: HashMap<String, EnvProfile>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct EnvProfile {
+    pub files: Vec<String>,
+    pub variables: HashMap<String, String>,
+}
+
+impl ProjectEnvSettings {
+    /// The files and variables to apply, in order: the ones for every profile, and then the ones
+    /// for the active profile.
+    pub fn layers(&self) -> impl Iterator<Item = (&[String], &HashMap<String, String>)> {
+        let profile = self
+            .profile
+            .as_ref()
+            .and_then(|profile| self.profiles.get(profile));
+        std::iter::once((self.files.as_slice(), &self.variables))
+            .chain(profile.map(|profile| (profile.files.as_slice(), &profile.variables)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers()
+            .all(|(files, variables)| files.is_empty() && variables.is_empty())
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct DiagnosticsSettings {
@@ -386,6 +452,11 @@ pub struct LspSettings {
     /// Default: true
     #[serde(default = "default_true")]
     pub enable_lsp_tasks: bool,
+    /// Whether to start an instance of the language server for each of the worktree's
+    /// subprojects, like each Go module or pnpm workspace, rather than one instance that has
+    /// them all as workspace folders.
+    /// Default: depends on the language server
+    pub instance_per_root: Option<bool>,
 }
 
 impl Default for LspSettings {
@@ -395,10 +466,36 @@ impl Default for LspSettings {
             initialization_options: None,
             settings: None,
             enable_lsp_tasks: true,
+            instance_per_root: None,
         }
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonSchemaSettings {
+    /// Whether to apply the schemas from the SchemaStore catalog (https://www.schemastore.org)
+    /// to the JSON files they're made for, like `.eslintrc.json`. yaml-language-server
+    /// applies the catalog to YAML files on its own.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub schemastore: bool,
+    /// Schemas to apply to the JSON and YAML files matching the globs, on top of the ones
+    /// that files name themselves with a `$schema` key.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub associations: Vec<JsonSchemaAssociation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonSchemaAssociation {
+    /// The URL of the schema, or its path, relative to the worktree's root.
+    pub schema: String,
+    /// Globs of the files the schema applies to, like `config/*.json`.
+    pub file_match: Vec<String>,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SessionSettings {
     /// Whether or not to restore unsaved buffers on restart.
@@ -484,6 +581,45 @@ impl Settings for ProjectSettings {
                 }));
         }
 
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct VsCodeJsonSchema {
+            file_match: Vec<String>,
+            url: String,
+        }
+        if let Some(schemas) = vscode.read_value("json.schemas").and_then(|v| v.as_array()) {
+            current
+                .json_schemas
+                .associations
+                .extend(schemas.iter().filter_map(|schema| {
+                    let schema = serde_json::from_value::<VsCodeJsonSchema>(schema.clone()).ok()?;
+                    Some(JsonSchemaAssociation {
+                        schema: schema.url,
+                        file_match: schema.file_match,
+                    })
+                }));
+        }
+        if let Some(schemas) = vscode
+            .read_value("yaml.schemas")
+            .and_then(|v| v.as_object())
+        {
+            current
+                .json_schemas
+                .associations
+                .extend(schemas.iter().map(|(schema, file_match)| {
+                    let file_match = match file_match {
+                        serde_json::Value::String(glob) => vec![glob.clone()],
+                        file_match => {
+                            serde_json::from_value(file_match.clone()).unwrap_or_default()
+                        }
+                    };
+                    JsonSchemaAssociation {
+                        schema: schema.clone(),
+                        file_match,
+                    }
+                }));
+        }
+
         // TODO: translate lsp settings for rust-analyzer and other popular ones to old.lsp
     }
 }
@@ -507,7 +643,12 @@ pub struct SettingsObserver {
     worktree_store: Entity<WorktreeStore>,
     project_id: u64,
     task_store: Entity<TaskStore>,
+    /// The contents of the settings, tasks, and debug files in local worktrees, so that they can
+    /// be applied once a worktree is trusted, and removed when it stops being trusted.
+    local_files: HashMap<WorktreeId, HashMap<(Arc<Path>, LocalSettingsKind), String>>,
+    untrusted_worktrees: HashSet<WorktreeId>,
     _global_task_config_watcher: Task<()>,
+    _trust_subscriptions: Vec<Subscription>,
 }
 
 /// SettingsObserver observers changes to .zed/{settings, task}.json files in local worktrees
@@ -535,11 +676,17 @@ impl SettingsObserver {
             mode: SettingsObserverMode::Local(fs.clone()),
             downstream_client: None,
             project_id: 0,
+            local_files: HashMap::default(),
+            untrusted_worktrees: HashSet::default(),
             _global_task_config_watcher: Self::subscribe_to_global_task_file_changes(
                 fs.clone(),
                 paths::tasks_file().clone(),
                 cx,
             ),
+            _trust_subscriptions: vec![
+                cx.observe_global::<TrustedFolders>(Self::on_trust_changed),
+                cx.observe_global::<SettingsStore>(Self::on_trust_changed),
+            ],
         }
     }
 
@@ -555,11 +702,14 @@ impl SettingsObserver {
             mode: SettingsObserverMode::Remote,
             downstream_client: None,
             project_id: 0,
+            local_files: HashMap::default(),
+            untrusted_worktrees: HashSet::default(),
             _global_task_config_watcher: Self::subscribe_to_global_task_file_changes(
                 fs.clone(),
                 paths::tasks_file().clone(),
                 cx,
             ),
+            _trust_subscriptions: Vec::new(),
         }
     }
 
@@ -807,11 +957,14 @@ impl SettingsObserver {
                 futures::future::join_all(settings_contents).await;
             cx.update(|cx| {
                 this.update(cx, |this, cx| {
-                    this.update_settings(
+                    this.update_local_files(
                         worktree,
-                        settings_contents.into_iter().map(|(path, kind, content)| {
-                            (path, kind, content.and_then(|c| c.log_err()))
-                        }),
+                        settings_contents
+                            .into_iter()
+                            .map(|(path, kind, content)| {
+                                (path, kind, content.and_then(|c| c.log_err()))
+                            })
+                            .collect(),
                         cx,
                     )
                 })
@@ -820,6 +973,68 @@ impl SettingsObserver {
         .detach();
     }
 
+    /// Applies the local worktree's files, unless the worktree isn't trusted. Editorconfig files
+    /// only affect formatting, so they apply either way.
+    fn update_local_files(
+        &mut self,
+        worktree: Entity<Worktree>,
+        settings_contents: Vec<(Arc<Path>, LocalSettingsKind, Option<String>)>,
+        cx: &mut Context<Self>,
+    ) {
+        let worktree_id = worktree.read(cx).id();
+        let files = self.local_files.entry(worktree_id).or_default();
+        for (directory, kind, content) in &settings_contents {
+            if *kind == LocalSettingsKind::Editorconfig {
+                continue;
+            }
+            let key = (directory.clone(), *kind);
+            match content {
+                Some(content) => files.insert(key, content.clone()),
+                None => files.remove(&key),
+            };
+        }
+
+        if TrustedFolders::is_trusted(&worktree.read(cx).abs_path(), cx) {
+            self.untrusted_worktrees.remove(&worktree_id);
+            self.update_settings(worktree, settings_contents, cx);
+        } else {
+            self.untrusted_worktrees.insert(worktree_id);
+            self.update_settings(
+                worktree,
+                settings_contents
+                    .into_iter()
+                    .filter(|(_, kind, _)| *kind == LocalSettingsKind::Editorconfig),
+                cx,
+            );
+        }
+    }
+
+    fn on_trust_changed(&mut self, cx: &mut Context<Self>) {
+        let worktrees = self.worktree_store.read(cx).worktrees().collect::<Vec<_>>();
+        for worktree in worktrees {
+            let worktree_id = worktree.read(cx).id();
+            let trusted = TrustedFolders::is_trusted(&worktree.read(cx).abs_path(), cx);
+            if trusted != self.untrusted_worktrees.contains(&worktree_id) {
+                continue;
+            }
+            let files = self
+                .local_files
+                .get(&worktree_id)
+                .into_iter()
+                .flatten()
+                .map(|((directory, kind), content)| {
+                    (directory.clone(), *kind, trusted.then(|| content.clone()))
+                })
+                .collect::<Vec<_>>();
+            if trusted {
+                self.untrusted_worktrees.remove(&worktree_id);
+            } else {
+                self.untrusted_worktrees.insert(worktree_id);
+            }
+            self.update_settings(worktree, files, cx);
+        }
+    }
+
     fn update_settings(
         &mut self,
         worktree: Entity<Worktree>,
@@ -1009,3 +1224,51 @@ pub fn local_settings_kind_to_proto(kind: LocalSettingsKind) -> proto::LocalSett
         LocalSettingsKind::Debug => proto::LocalSettingsKind::Debug,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings::{VsCodeSettings, VsCodeSettingsSource};
+
+    #[test]
+    fn test_import_json_schemas_from_vscode() {
+        let vscode = VsCodeSettings::from_str(
+            r#"{
+                "json.schemas": [
+                    {
+                        "fileMatch": ["*/*.luarc.json"],
+                        "url": "https://example.com/luarc.json"
+                    },
+                    { "url": "https://example.com/without-files.json" }
+                ],
+                "yaml.schemas": {
+                    "https://json.schemastore.org/github-workflow.json": ".github/workflows/*.yml",
+                    "schemas/deploy.json": ["deploy/*.yaml", "deploy/*.yml"]
+                }
+            }"#,
+            VsCodeSettingsSource::VsCode,
+        )
+        .unwrap();
+
+        let mut settings = ProjectSettings::default();
+        ProjectSettings::import_from_vscode(&vscode, &mut settings);
+        assert!(!settings.json_schemas.schemastore);
+        assert_eq!(
+            settings.json_schemas.associations,
+            [
+                JsonSchemaAssociation {
+                    schema: "https://example.com/luarc.json".into(),
+                    file_match: vec!["*/*.luarc.json".into()],
+                },
+                JsonSchemaAssociation {
+                    schema: "https://json.schemastore.org/github-workflow.json".into(),
+                    file_match: vec![".github/workflows/*.yml".into()],
+                },
+                JsonSchemaAssociation {
+                    schema: "schemas/deploy.json".into(),
+                    file_match: vec!["deploy/*.yaml".into(), "deploy/*.yml".into()],
+                },
+            ]
+        );
+    }
+}
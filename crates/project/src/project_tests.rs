@@ -20,7 +20,8 @@ use gpui::{App, BackgroundExecutor, SemanticVersion, UpdateGlobal};
 use http_client::Url;
 use language::{
     Diagnostic, DiagnosticEntry, DiagnosticSet, DiskState, FakeLspAdapter, LanguageConfig,
-    LanguageMatcher, LanguageName, LineEnding, OffsetRangeExt, Point, ToPoint,
+    LanguageMatcher, LanguageName, LineEnding, ManifestName, ManifestProvider, ManifestQuery,
+    OffsetRangeExt, Point, ToPoint,
     language_settings::{AllLanguageSettings, LanguageSettingsContent, language_settings},
     tree_sitter_rust, tree_sitter_typescript,
 };
@@ -487,6 +488,81 @@ async fn test_managing_project_specific_settings(cx: &mut gpui::TestAppContext)
     );
 }
 
+#[gpui::test]
+async fn test_project_settings_of_untrusted_folders(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.set_global(TrustedFolders::default());
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<WorkspaceTrustSettings>(cx, |settings| {
+                settings.enabled = Some(true);
+            });
+        });
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".editorconfig": "[*.rs]\nindent_style = tab\n",
+            ".zed": {
+                "settings.json": r#"{ "tab_size": 8 }"#,
+            },
+            "a.rs": "fn a() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let worktree = project.update(cx, |project, cx| project.worktrees(cx).next().unwrap());
+    cx.executor().run_until_parked();
+
+    let settings_for_a = |cx: &mut gpui::TestAppContext| {
+        cx.update(|cx| {
+            let tree = worktree.read(cx);
+            let file_a = File::for_entry(
+                tree.entry_for_path("a.rs").unwrap().clone(),
+                worktree.clone(),
+            ) as _;
+            let settings = language_settings(None, Some(&file_a), cx);
+            (settings.tab_size.get(), settings.hard_tabs)
+        })
+    };
+    let untrusted_worktree_count = |cx: &mut gpui::TestAppContext| {
+        project.read_with(cx, |project, cx| project.untrusted_worktrees(cx).count())
+    };
+
+    assert_eq!(
+        settings_for_a(cx),
+        (4, true),
+        "only the .editorconfig applies in an untrusted folder"
+    );
+    assert_eq!(untrusted_worktree_count(cx), 1);
+
+    cx.update(|cx| TrustedFolders::trust([PathBuf::from(path!("/dir"))], cx));
+    cx.executor().run_until_parked();
+    assert_eq!(settings_for_a(cx), (8, true));
+    assert_eq!(untrusted_worktree_count(cx), 0);
+
+    // Files that change while the folder isn't trusted apply once it's trusted again.
+    cx.update(|cx| TrustedFolders::revoke([PathBuf::from(path!("/dir"))], cx));
+    cx.executor().run_until_parked();
+    assert_eq!(settings_for_a(cx), (4, true));
+    fs.save(
+        path!("/dir/.zed/settings.json").as_ref(),
+        &r#"{ "tab_size": 2 }"#.into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+    assert_eq!(settings_for_a(cx), (4, true));
+
+    cx.update(|cx| TrustedFolders::trust([PathBuf::from(path!("/dir"))], cx));
+    cx.executor().run_until_parked();
+    assert_eq!(settings_for_a(cx), (2, true));
+}
+
 #[gpui::test]
 async fn test_fallback_to_single_worktree_tasks(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1879,6 +1955,177 @@ async fn test_restarted_server_reporting_invalid_buffer_version(cx: &mut gpui::T
     assert_eq!(notification.version, 0);
 }
 
+#[gpui::test]
+async fn test_language_servers_restart_when_environment_changes(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "",
+            "docs": { "notes.txt": "" },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+    let (_buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    // The server was started at the root, whose environment doesn't use the new `.envrc`.
+    fs.insert_file(path!("/dir/docs/.envrc"), b"export DOCS=1".to_vec())
+        .await;
+    cx.executor().run_until_parked();
+    assert!(fake_servers.try_next().is_err());
+
+    fs.insert_file(path!("/dir/.envrc"), b"use flake".to_vec())
+        .await;
+    cx.executor().run_until_parked();
+    let mut fake_server = fake_servers.next().await.unwrap();
+    let notification = fake_server
+        .receive_notification::<lsp::notification::DidOpenTextDocument>()
+        .await
+        .text_document;
+    assert_eq!(
+        notification.uri,
+        lsp::Url::from_file_path(path!("/dir/a.rs")).unwrap()
+    );
+}
+
+#[gpui::test]
+async fn test_language_server_instance_per_root(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "module.toml": "",
+            "main.rs": "",
+            "tools": {
+                "module.toml": "",
+                "gen.rs": "",
+            },
+            "src": { "lib.rs": "" },
+        }),
+    )
+    .await;
+
+    // Each file's root is the closest directory with a `module.toml`, even in another root.
+    let roots = language_server_roots(fs.clone(), true, cx).await;
+    assert_eq!(roots["main.rs"].1, Path::new(""));
+    assert_eq!(roots["src/lib.rs"].1, Path::new(""));
+    assert_eq!(roots["tools/gen.rs"].1, Path::new("tools"));
+    assert_eq!(roots["main.rs"].0, roots["src/lib.rs"].0);
+    assert_ne!(roots["main.rs"].0, roots["tools/gen.rs"].0);
+
+    // Without nested roots, the outermost one is the root of every file in it.
+    let roots = language_server_roots(fs, false, cx).await;
+    assert_eq!(roots["tools/gen.rs"].1, Path::new(""));
+    assert_eq!(roots["main.rs"].0, roots["tools/gen.rs"].0);
+}
+
+/// Opens the Rust files in `/dir` with a language server that has an instance for each
+/// `module.toml` root, and returns the server and root that each of them is attached to.
+async fn language_server_roots(
+    fs: Arc<FakeFs>,
+    allows_nested_roots: bool,
+    cx: &mut gpui::TestAppContext,
+) -> HashMap<&'static str, (LanguageServerId, Arc<Path>)> {
+    struct ModuleManifestProvider {
+        allows_nested_roots: bool,
+    }
+
+    impl ManifestProvider for ModuleManifestProvider {
+        fn name(&self) -> ManifestName {
+            SharedString::new_static("module.toml").into()
+        }
+
+        fn search(&self, query: ManifestQuery) -> Option<Arc<Path>> {
+            let mut roots = query.path.ancestors().take(query.depth).filter(|path| {
+                query
+                    .delegate
+                    .exists(&path.join("module.toml"), Some(false))
+            });
+            let root = if self.allows_nested_roots {
+                roots.next()
+            } else {
+                roots.last()
+            };
+            root.map(Arc::from)
+        }
+
+        fn allows_nested_roots(&self) -> bool {
+            self.allows_nested_roots
+        }
+    }
+
+    cx.update(|cx| {
+        ManifestProviders::global(cx).register(Arc::new(ModuleManifestProvider {
+            allows_nested_roots,
+        }));
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<ProjectSettings>(cx, |settings| {
+                settings.lsp.insert(
+                    "the-fake-language-server".into(),
+                    project_settings::LspSettings {
+                        instance_per_root: Some(true),
+                        ..Default::default()
+                    },
+                );
+            });
+        });
+    });
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let _fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            manifest_name: Some(SharedString::new_static("module.toml").into()),
+            ..Default::default()
+        },
+    );
+
+    let mut roots = HashMap::default();
+    let mut handles = Vec::new();
+    for path in ["main.rs", "src/lib.rs", "tools/gen.rs"] {
+        let (buffer, handle) = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer_with_lsp(Path::new(path!("/dir")).join(path), cx)
+            })
+            .await
+            .unwrap();
+        cx.executor().run_until_parked();
+        let buffer_roots = project.update(cx, |project, cx| {
+            project.lsp_store().update(cx, |lsp_store, cx| {
+                buffer.update(cx, |buffer, cx| {
+                    lsp_store.language_server_roots_for_local_buffer(buffer, cx)
+                })
+            })
+        });
+        let [(server_id, root)] = buffer_roots.as_slice() else {
+            panic!("expected one language server for {path}, got {buffer_roots:?}");
+        };
+        roots.insert(path, (*server_id, root.path.clone()));
+        handles.push(handle);
+    }
+    roots
+}
+
 #[gpui::test]
 async fn test_cancel_language_server_work(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -3570,6 +3817,260 @@ async fn test_apply_code_actions_with_commands(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_apply_code_action_with_edit_and_command(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.ts": "a + b",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
+                execute_command_provider: Some(lsp::ExecuteCommandOptions {
+                    commands: vec!["_typescript.applyRefactoring".to_string()],
+                    ..lsp::ExecuteCommandOptions::default()
+                }),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_language_servers.next().await.unwrap();
+    let uri = lsp::Url::from_file_path(path!("/dir/a.ts")).unwrap();
+
+    let actions = project.update(cx, |project, cx| {
+        project.code_actions(&buffer, 0..0, None, cx)
+    });
+    fake_server
+        .set_request_handler::<lsp::request::CodeActionRequest, _, _>({
+            let uri = uri.clone();
+            move |_, _| {
+                let uri = uri.clone();
+                async move {
+                    Ok(Some(vec![lsp::CodeActionOrCommand::CodeAction(
+                        lsp::CodeAction {
+                            title: "Extract to constant".into(),
+                            edit: Some(lsp::WorkspaceEdit {
+                                changes: Some(
+                                    [(
+                                        uri,
+                                        vec![lsp::TextEdit {
+                                            range: lsp::Range::new(
+                                                lsp::Position::new(0, 0),
+                                                lsp::Position::new(0, 5),
+                                            ),
+                                            new_text: "const newLocal = a + b;\nnewLocal".into(),
+                                        }],
+                                    )]
+                                    .into_iter()
+                                    .collect(),
+                                ),
+                                ..Default::default()
+                            }),
+                            command: Some(lsp::Command {
+                                title: "Rename".into(),
+                                command: "_typescript.applyRefactoring".into(),
+                                arguments: None,
+                            }),
+                            ..lsp::CodeAction::default()
+                        },
+                    )]))
+                }
+            }
+        })
+        .next()
+        .await;
+
+    let action = actions.await.unwrap()[0].clone();
+    let mut events = cx.events(&project);
+    let apply = project.update(cx, |project, cx| {
+        project.apply_code_action(buffer.clone(), action, true, cx)
+    });
+
+    // The command runs once the edit is applied, and asks for the extracted constant to be named.
+    fake_server
+        .set_request_handler::<lsp::request::ExecuteCommand, _, _>({
+            let fake = fake_server.clone();
+            let uri = uri.clone();
+            move |_, _| {
+                let fake = fake.clone();
+                let uri = uri.clone();
+                async move {
+                    fake.server
+                        .request::<crate::lsp_store::lsp_ext_command::LspExtTypeScriptRename>(
+                            lsp::TextDocumentPositionParams {
+                                text_document: lsp::TextDocumentIdentifier::new(uri),
+                                position: lsp::Position::new(0, 6),
+                            },
+                        )
+                        .await
+                        .into_response()
+                        .unwrap();
+                    Ok(Some(json!(null)))
+                }
+            }
+        })
+        .next()
+        .await;
+
+    let transaction = apply.await.unwrap();
+    assert!(transaction.0.contains_key(&buffer));
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(buffer.text(), "const newLocal = a + b;\nnewLocal");
+    });
+
+    let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+    let mut rename_requests = Vec::new();
+    while let Some(Some(event)) = futures::FutureExt::now_or_never(events.next()) {
+        if let Event::RenameRequested(id, position) = event {
+            rename_requests.push((id, position));
+        }
+    }
+    assert_eq!(rename_requests, [(buffer_id, lsp::Position::new(0, 6))]);
+}
+
+#[gpui::test]
+async fn test_apply_code_action_with_snippet_edit(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.ts": "a + c",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_language_servers.next().await.unwrap();
+    let uri = lsp::Url::from_file_path(path!("/dir/a.ts")).unwrap();
+
+    // Snippets are only inserted in the file being edited, after an edit of this participant.
+    project.update(cx, |project, cx| {
+        let worktree_id = project.worktrees(cx).next().unwrap().read(cx).id();
+        project.set_active_path(
+            Some(ProjectPath {
+                worktree_id,
+                path: Path::new("a.ts").into(),
+            }),
+            cx,
+        );
+    });
+    buffer.update(cx, |buffer, cx| buffer.edit([(4..5, "b")], None, cx));
+    cx.executor().run_until_parked();
+
+    let actions = project.update(cx, |project, cx| {
+        project.code_actions(&buffer, 0..0, None, cx)
+    });
+    fake_server
+        .set_request_handler::<lsp::request::CodeActionRequest, _, _>({
+            let uri = uri.clone();
+            move |_, _| {
+                let uri = uri.clone();
+                async move {
+                    let edit = serde_json::from_value::<lsp::WorkspaceEdit>(json!({
+                        "documentChanges": [{
+                            "textDocument": { "uri": uri, "version": 1 },
+                            "edits": [
+                                {
+                                    "range": {
+                                        "start": { "line": 0, "character": 0 },
+                                        "end": { "line": 0, "character": 0 },
+                                    },
+                                    "newText": "x = ",
+                                },
+                                {
+                                    "range": {
+                                        "start": { "line": 0, "character": 4 },
+                                        "end": { "line": 0, "character": 5 },
+                                    },
+                                    "snippet": { "kind": "snippet", "value": "${1:b}" },
+                                },
+                            ],
+                        }],
+                    }))
+                    .unwrap();
+                    Ok(Some(vec![lsp::CodeActionOrCommand::CodeAction(
+                        lsp::CodeAction {
+                            title: "Assign to variable".into(),
+                            edit: Some(edit),
+                            ..lsp::CodeAction::default()
+                        },
+                    )]))
+                }
+            }
+        })
+        .next()
+        .await;
+
+    let action = actions.await.unwrap()[0].clone();
+    let mut events = cx.events(&project);
+    project
+        .update(cx, |project, cx| {
+            project.apply_code_action(buffer.clone(), action, true, cx)
+        })
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| assert_eq!(buffer.text(), "x = a + b"));
+
+    // The snippet's range follows the plain edit made before it.
+    let mut snippet_edits = Vec::new();
+    while let Some(Some(event)) = futures::FutureExt::now_or_never(events.next()) {
+        if let Event::SnippetEdit(_, edits) = event {
+            snippet_edits.extend(edits);
+        }
+    }
+    buffer.read_with(cx, |buffer, _| {
+        let ranges = snippet_edits
+            .iter()
+            .map(|(range, snippet)| (range.to_offset(buffer), snippet.text.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(8..9, "b".to_string())]);
+    });
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_save_file(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -8767,6 +9268,69 @@ async fn search(
         .collect())
 }
 
+#[gpui::test]
+async fn test_comment_threads(cx: &mut gpui::TestAppContext) {
+    use comment_store::{Comment, SerializedCommentThread};
+
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({ "a.rs": "fn one() {}\nfn two() {}\n" }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let comment_store = project.read_with(cx, |project, _| project.comment_store());
+    let comment = |author: &str, body: &str| Comment {
+        author: author.to_string().into(),
+        body: body.to_string().into(),
+        timestamp: 0,
+    };
+
+    comment_store.update(cx, |store, cx| {
+        let snapshot = buffer.read(cx).snapshot();
+        let range =
+            snapshot.anchor_before(Point::new(1, 3))..snapshot.anchor_after(Point::new(1, 6));
+        store.create_thread(buffer.clone(), range, comment("alice", "Rename this?"), cx);
+    });
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "// two\n")], None, cx));
+
+    let thread_id = comment_store.update(cx, |store, cx| {
+        let thread_id = store.threads().next().unwrap().2.id;
+        store.reply(thread_id, comment("bob", "Sure"), cx);
+        store.set_resolved(thread_id, true, cx);
+        thread_id
+    });
+    comment_store.read_with(cx, |store, cx| {
+        assert_eq!(
+            store.serialized_threads(cx),
+            [SerializedCommentThread {
+                path: Path::new(path!("/dir/a.rs")).into(),
+                range: Point::new(2, 3)..Point::new(2, 6),
+                resolved: true,
+                comments: vec![comment("alice", "Rename this?"), comment("bob", "Sure")],
+            }]
+        );
+    });
+
+    // Replying reopens a resolved thread.
+    comment_store.update(cx, |store, cx| {
+        store.reply(thread_id, comment("alice", "Done"), cx)
+    });
+    comment_store.read_with(cx, |store, _| {
+        let (_, thread) = store.thread(thread_id).unwrap();
+        assert!(!thread.resolved);
+        assert_eq!(thread.comments.len(), 3);
+    });
+}
+
 pub fn init_test(cx: &mut gpui::TestAppContext) {
     zlog::init_test();
 
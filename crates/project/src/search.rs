@@ -1,7 +1,7 @@
 use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
 use anyhow::Result;
 use client::proto;
-use fancy_regex::{Captures, Regex, RegexBuilder};
+use fancy_regex::{Regex, RegexBuilder};
 use gpui::Entity;
 use language::{Buffer, BufferSnapshot, CharKind};
 use smol::future::yield_now;
@@ -169,7 +169,14 @@ impl SearchQuery {
             query = word_query
         }
 
-        let multiline = query.contains('\n') || query.contains("\\n");
+        // With the `s` flag set inline, `.` matches newlines too.
+        static DOT_MATCHES_NEWLINE_FLAG_REGEX: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"\(\?[imxU]*s[imsxU]*(-[imsxU]*)?[:)]").unwrap());
+        let multiline = query.contains('\n')
+            || query.contains("\\n")
+            || DOT_MATCHES_NEWLINE_FLAG_REGEX
+                .is_match(&query)
+                .unwrap_or(false);
         let regex = RegexBuilder::new(&query)
             .case_insensitive(!case_sensitive)
             .build()?;
@@ -303,18 +310,22 @@ impl SearchQuery {
                 regex, replacement, ..
             } => {
                 if let Some(replacement) = replacement {
-                    static TEXT_REPLACEMENT_SPECIAL_CHARACTERS_REGEX: LazyLock<Regex> =
-                        LazyLock::new(|| Regex::new(r"\\\\|\\n|\\t").unwrap());
-                    let replacement = TEXT_REPLACEMENT_SPECIAL_CHARACTERS_REGEX.replace_all(
-                        replacement,
-                        |c: &Captures| match c.get(0).unwrap().as_str() {
-                            r"\\" => "\\",
-                            r"\n" => "\n",
-                            r"\t" => "\t",
-                            x => unreachable!("Unexpected escape sequence: {}", x),
-                        },
-                    );
-                    Some(regex.replace(text, replacement))
+                    let Some(captures) = regex.captures(text).ok().flatten() else {
+                        return Some(Cow::Borrowed(text));
+                    };
+                    let mat = captures.get(0)?;
+                    let mut replaced = text[..mat.start()].to_string();
+                    for (segment, case) in replacement_segments(replacement) {
+                        let mut expanded = String::new();
+                        captures.expand(&segment, &mut expanded);
+                        match case {
+                            ReplacementCase::Unchanged => replaced.push_str(&expanded),
+                            ReplacementCase::Upper => replaced.push_str(&expanded.to_uppercase()),
+                            ReplacementCase::Lower => replaced.push_str(&expanded.to_lowercase()),
+                        }
+                    }
+                    replaced.push_str(&text[mat.end()..]);
+                    Some(Cow::Owned(replaced))
                 } else {
                     None
                 }
@@ -517,6 +528,62 @@ impl SearchQuery {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ReplacementCase {
+    Unchanged,
+    Upper,
+    Lower,
+}
+
+/// Splits a regex replacement at its `\U`, `\L` and `\E` case conversion escapes, unescaping
+/// `\\`, `\n` and `\t` along the way. Each segment still has its capture group references, so
+/// that they're converted once they're expanded.
+fn replacement_segments(replacement: &str) -> Vec<(String, ReplacementCase)> {
+    let mut segments = Vec::new();
+    let mut segment = String::new();
+    let mut case = ReplacementCase::Unchanged;
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            segment.push(c);
+            continue;
+        }
+        let new_case = match chars.peek() {
+            Some('U') => ReplacementCase::Upper,
+            Some('L') => ReplacementCase::Lower,
+            Some('E') => ReplacementCase::Unchanged,
+            Some('\\') => {
+                chars.next();
+                segment.push('\\');
+                continue;
+            }
+            Some('n') => {
+                chars.next();
+                segment.push('\n');
+                continue;
+            }
+            Some('t') => {
+                chars.next();
+                segment.push('\t');
+                continue;
+            }
+            _ => {
+                segment.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        if !segment.is_empty() {
+            segments.push((std::mem::take(&mut segment), case));
+        }
+        case = new_case;
+    }
+    if !segment.is_empty() {
+        segments.push((segment, case));
+    }
+    segments
+}
+
 pub fn deserialize_path_matches(glob_set: &str) -> anyhow::Result<PathMatcher> {
     let globs = glob_set
         .split(',')
@@ -550,6 +617,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn regex_replacement_case_conversion() {
+        let query = SearchQuery::regex(
+            r"(\w+)@(\w+)",
+            false,
+            true,
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            false,
+            None,
+        )
+        .unwrap();
+        for (replacement, expected) in [
+            (r"$2.$1", "example.Jane"),
+            (r"\U$2\E.$1", "EXAMPLE.Jane"),
+            (r"\L$1\E-\U$2", "jane-EXAMPLE"),
+            (r"\U${1}_x\L\t$2", "JANE_X\texample"),
+            (r"\\U$1", "\\UJane"),
+        ] {
+            let query = query.clone().with_replacement(replacement.to_string());
+            assert_eq!(
+                query.replacement_for("Jane@example").as_deref(),
+                Some(expected),
+                "replacement {replacement:?}"
+            );
+        }
+
+        // The text around the match is kept once, however many segments the replacement has.
+        let query = query.with_replacement(r"\U$2\E.$1".to_string());
+        assert_eq!(
+            query.replacement_for("to: Jane@example!").as_deref(),
+            Some("to: EXAMPLE.Jane!")
+        );
+        assert_eq!(query.replacement_for("nobody").as_deref(), Some("nobody"));
+    }
+
+    #[test]
+    fn regex_with_inline_dot_matches_newline_flag_is_multiline() {
+        for (query_text, expected_multiline) in [
+            ("a.b", false),
+            ("(?s)a.b", true),
+            ("(?is)a.b", true),
+            ("(?i)a.b", false),
+            ("(?s:a.b)", true),
+            ("a\\nb", true),
+        ] {
+            let query = SearchQuery::regex(
+                query_text,
+                false,
+                false,
+                false,
+                false,
+                Default::default(),
+                Default::default(),
+                false,
+                None,
+            )
+            .unwrap();
+            let SearchQuery::Regex { multiline, .. } = query else {
+                panic!("expected a regex query");
+            };
+            assert_eq!(multiline, expected_multiline, "query {query_text:?}");
+        }
+    }
+
     #[test]
     fn path_matcher_creation_for_globs() {
         for invalid_glob in ["dir/[].txt", "dir/[a-z.txt", "dir/{file"] {
@@ -88,6 +88,11 @@ impl Project {
         window: AnyWindowHandle,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
+        if !self.supports_terminal(cx) {
+            return Task::ready(Err(anyhow::anyhow!(
+                "guests of a shared project can't open terminals"
+            )));
+        }
         let path: Option<Arc<Path>> = match &kind {
             TerminalKind::Shell(path) => path.as_ref().map(|path| Arc::from(path.as_ref())),
             TerminalKind::Task(spawn_task) => {
@@ -114,6 +119,13 @@ impl Project {
 
         cx.spawn(async move |project, cx| {
             let python_venv_directory = if let Some(path) = path {
+                project
+                    .update(cx, |this, cx| {
+                        this.environment.update(cx, |environment, cx| {
+                            environment.load_terminal_variables(path.clone(), cx)
+                        })
+                    })?
+                    .await;
                 project
                     .update(cx, |this, cx| this.python_venv_directory(path, venv, cx))?
                     .await
@@ -190,6 +202,10 @@ impl Project {
         window: AnyWindowHandle,
         cx: &mut Context<Self>,
     ) -> Result<Entity<Terminal>> {
+        anyhow::ensure!(
+            self.supports_terminal(cx),
+            "guests of a shared project can't open terminals"
+        );
         let this = &mut *self;
         let path: Option<Arc<Path>> = match &kind {
             TerminalKind::Shell(path) => path.as_ref().map(|path| Arc::from(path.as_ref())),
@@ -217,11 +233,16 @@ impl Project {
         let (completion_tx, completion_rx) = bounded(1);
 
         // Start with the environment that we might have inherited from the Zed CLI.
-        let mut env = this
-            .environment
-            .read(cx)
-            .get_cli_environment()
-            .unwrap_or_default();
+        let environment = this.environment.read(cx);
+        let mut env = environment.get_cli_environment().unwrap_or_default();
+        // Then the variables from direnv, mise, and the project's settings, which were loaded
+        // for the directory before the terminal was created.
+        if let Some(terminal_variables) = path
+            .as_ref()
+            .and_then(|path| environment.terminal_variables(path))
+        {
+            env.extend(terminal_variables.clone());
+        }
         // Then extend it with the explicit env variables from the settings, so they take
         // precedence.
         env.extend(settings.env.clone());
@@ -0,0 +1,292 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{App, Global};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use util::{ResultExt as _, paths::home_dir};
+
+const TRUSTED_FOLDERS_KEY: &str = "trusted_folders";
+
+#[derive(Clone, Debug)]
+pub struct WorkspaceTrustSettings {
+    pub enabled: bool,
+    pub trusted_folders: Vec<PathBuf>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct WorkspaceTrustSettingsContent {
+    /// Whether folders have to be trusted before their tasks can run, and before their project
+    /// settings, language server binary overrides, and tools apply.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// Folders that are always trusted, along with the folders in them. Paths can start with `~`.
+    ///
+    /// Default: []
+    pub trusted_folders: Option<Vec<String>>,
+}
+
+impl Settings for WorkspaceTrustSettings {
+    const KEY: Option<&'static str> = Some("workspace_trust");
+
+    type FileContent = WorkspaceTrustSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        let content: WorkspaceTrustSettingsContent = sources.json_merge()?;
+        Ok(Self {
+            enabled: content.enabled.unwrap_or(true),
+            trusted_folders: content
+                .trusted_folders
+                .unwrap_or_default()
+                .iter()
+                .map(|folder| expand_home_dir(folder))
+                .collect(),
+        })
+    }
+
+    fn import_from_vscode(vscode: &settings::VsCodeSettings, current: &mut Self::FileContent) {
+        vscode.bool_setting("security.workspace.trust.enabled", &mut current.enabled);
+    }
+}
+
+/// The folders that were trusted from the trust prompt, which are stored in the database.
+///
+/// Until this is initialized, no folder is trusted. On a remote server, these are the folders
+/// that the connected client trusts on the server.
+#[derive(Default)]
+pub struct TrustedFolders {
+    folders: Vec<PathBuf>,
+    /// Whether the client connected to this remote server doesn't require folders to be trusted.
+    trust_all: bool,
+}
+
+impl Global for TrustedFolders {}
+
+impl TrustedFolders {
+    pub fn init(cx: &mut App) {
+        let folders = KEY_VALUE_STORE
+            .read_kvp(TRUSTED_FOLDERS_KEY)
+            .log_err()
+            .flatten()
+            .and_then(|json| serde_json::from_str::<Vec<PathBuf>>(&json).log_err())
+            .unwrap_or_default();
+        cx.set_global(Self {
+            folders,
+            trust_all: false,
+        });
+    }
+
+    /// Replaces the folders that are trusted on this remote server with the ones that the
+    /// connected client trusts, or trusts every folder when the client doesn't require trust.
+    pub fn set_remote(folders: Option<Vec<PathBuf>>, cx: &mut App) {
+        let this = Self {
+            trust_all: folders.is_none(),
+            folders: folders.unwrap_or_default(),
+        };
+        if let Some(current) = cx.try_global::<Self>() {
+            if current.trust_all == this.trust_all && current.folders == this.folders {
+                return;
+            }
+        }
+        cx.set_global(this);
+    }
+
+    /// The folders that the client trusts on the remote server with the given connection string,
+    /// as paths on the server, or `None` when folders don't have to be trusted.
+    pub fn remote_folders(connection: &str, cx: &App) -> Option<Vec<String>> {
+        let settings = WorkspaceTrustSettings::get_global(cx);
+        if !settings.enabled {
+            return None;
+        }
+        let prefix = remote_trust_path(connection, Path::new(""));
+        let folders = cx
+            .try_global::<Self>()
+            .map(|this| this.folders.as_slice())
+            .unwrap_or_default();
+        Some(
+            settings
+                .trusted_folders
+                .iter()
+                .chain(folders)
+                .filter_map(|folder| {
+                    let path = folder.strip_prefix(&prefix).ok()?;
+                    let components = path
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>();
+                    Some(format!("/{}", components.join("/")))
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns whether the folder's tasks, project settings, language server binary overrides,
+    /// and tools can be used.
+    pub fn is_trusted(abs_path: &Path, cx: &App) -> bool {
+        let settings = WorkspaceTrustSettings::get_global(cx);
+        if !settings.enabled {
+            return true;
+        }
+        let Some(this) = cx.try_global::<Self>() else {
+            return false;
+        };
+        if this.trust_all {
+            return true;
+        }
+        settings
+            .trusted_folders
+            .iter()
+            .chain(&this.folders)
+            .any(|folder| abs_path.starts_with(folder))
+    }
+
+    /// Trusts the folders, and the folders in them.
+    pub fn trust(abs_paths: impl IntoIterator<Item = PathBuf>, cx: &mut App) {
+        Self::update(cx, |folders| {
+            for abs_path in abs_paths {
+                if !folders.iter().any(|folder| abs_path.starts_with(folder)) {
+                    folders.retain(|folder| !folder.starts_with(&abs_path));
+                    folders.push(abs_path);
+                }
+            }
+        });
+    }
+
+    /// Stops trusting the folders and the folders in them, including through the folders that
+    /// contain them. Folders that are trusted in the settings stay trusted.
+    pub fn revoke(abs_paths: impl IntoIterator<Item = PathBuf>, cx: &mut App) {
+        let abs_paths = abs_paths.into_iter().collect::<Vec<_>>();
+        Self::update(cx, |folders| {
+            folders.retain(|folder| {
+                !abs_paths
+                    .iter()
+                    .any(|abs_path| abs_path.starts_with(folder) || folder.starts_with(abs_path))
+            });
+        });
+    }
+
+    fn update(cx: &mut App, update: impl FnOnce(&mut Vec<PathBuf>)) {
+        if !cx.has_global::<Self>() {
+            return;
+        }
+        let this = cx.global_mut::<Self>();
+        update(&mut this.folders);
+        let Some(json) = serde_json::to_string(&this.folders).log_err() else {
+            return;
+        };
+        cx.background_spawn(KEY_VALUE_STORE.write_kvp(TRUSTED_FOLDERS_KEY.to_string(), json))
+            .detach_and_log_err(cx);
+    }
+}
+
+/// The path that a folder on a remote server is trusted by: its path on the server, under the
+/// server's connection string, so that trusting a folder on one server doesn't trust the folder
+/// at the same path on another.
+pub fn remote_trust_path(connection: &str, abs_path: &Path) -> PathBuf {
+    let relative_path = abs_path
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect::<PathBuf>();
+    Path::new(&format!("ssh://{connection}")).join(relative_path)
+}
+
+fn expand_home_dir(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => home_dir().join(rest),
+        None if path == "~" => home_dir().clone(),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+    use settings::SettingsStore;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            WorkspaceTrustSettings::register(cx);
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings::<WorkspaceTrustSettings>(cx, |settings| {
+                    settings.enabled = Some(true);
+                    settings.trusted_folders = Some(vec!["/always".to_string()]);
+                });
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_trusting_and_revoking_folders(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            assert!(
+                !TrustedFolders::is_trusted(Path::new("/always"), cx),
+                "no folder is trusted before the trusted folders are loaded"
+            );
+
+            cx.set_global(TrustedFolders::default());
+            assert!(TrustedFolders::is_trusted(Path::new("/always/project"), cx));
+            assert!(!TrustedFolders::is_trusted(Path::new("/code/project"), cx));
+
+            TrustedFolders::trust([PathBuf::from("/code")], cx);
+            assert!(TrustedFolders::is_trusted(Path::new("/code/project"), cx));
+            assert!(!TrustedFolders::is_trusted(Path::new("/codes"), cx));
+
+            TrustedFolders::revoke([PathBuf::from("/code/project")], cx);
+            assert!(!TrustedFolders::is_trusted(Path::new("/code/other"), cx));
+            TrustedFolders::revoke([PathBuf::from("/always")], cx);
+            assert!(
+                TrustedFolders::is_trusted(Path::new("/always"), cx),
+                "folders trusted in the settings stay trusted"
+            );
+
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings::<WorkspaceTrustSettings>(cx, |settings| {
+                    settings.enabled = Some(false);
+                });
+            });
+            assert!(TrustedFolders::is_trusted(Path::new("/code/project"), cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_remote_folders(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.set_global(TrustedFolders::default());
+            let project = remote_trust_path("user@host", Path::new("/home/user/project"));
+            assert!(!TrustedFolders::is_trusted(&project, cx));
+
+            TrustedFolders::trust(
+                [
+                    project.clone(),
+                    remote_trust_path("user@other", Path::new("/srv")),
+                    PathBuf::from("/home/user/project"),
+                ],
+                cx,
+            );
+            assert!(TrustedFolders::is_trusted(&project, cx));
+            assert!(!TrustedFolders::is_trusted(
+                &remote_trust_path("user@other", Path::new("/home/user/project")),
+                cx
+            ));
+            assert_eq!(
+                TrustedFolders::remote_folders("user@host", cx),
+                Some(vec!["/home/user/project".to_string()])
+            );
+
+            TrustedFolders::set_remote(Some(vec![PathBuf::from("/srv")]), cx);
+            assert!(TrustedFolders::is_trusted(Path::new("/srv/app"), cx));
+            assert!(!TrustedFolders::is_trusted(Path::new("/home"), cx));
+            TrustedFolders::set_remote(None, cx);
+            assert!(TrustedFolders::is_trusted(Path::new("/home"), cx));
+        });
+    }
+}
@@ -15,6 +15,7 @@ use futures::{
 use gpui::{
     App, AppContext as _, AsyncApp, Context, Entity, EntityId, EventEmitter, Task, WeakEntity,
 };
+use language::Buffer;
 use postage::oneshot;
 use rpc::{
     AnyProtoClient, ErrorExt, TypedEnvelope,
@@ -31,7 +32,7 @@ use worktree::{
     WorktreeSettings,
 };
 
-use crate::{ProjectPath, search::SearchQuery};
+use crate::{ProjectPath, guest_permissions::GuestPermissions, search::SearchQuery};
 
 struct MatchingEntry {
     worktree_path: Arc<Path>,
@@ -59,6 +60,8 @@ pub struct WorktreeStore {
     loading_worktrees:
         HashMap<SanitizedPath, Shared<Task<Result<Entity<Worktree>, Arc<anyhow::Error>>>>>,
     state: WorktreeStoreState,
+    /// The host's restrictions on each guest, by the guest's peer id.
+    guest_permissions: HashMap<proto::PeerId, GuestPermissions>,
 }
 
 #[derive(Debug)]
@@ -88,6 +91,7 @@ impl WorktreeStore {
         Self {
             next_entry_id: Default::default(),
             loading_worktrees: Default::default(),
+            guest_permissions: Default::default(),
             downstream_client: None,
             worktrees: Vec::new(),
             worktrees_reordered: false,
@@ -104,6 +108,7 @@ impl WorktreeStore {
         Self {
             next_entry_id: Default::default(),
             loading_worktrees: Default::default(),
+            guest_permissions: Default::default(),
             downstream_client: None,
             worktrees: Vec::new(),
             worktrees_reordered: false,
@@ -628,6 +633,7 @@ impl WorktreeStore {
     pub fn unshared(&mut self, cx: &mut Context<Self>) {
         self.retain_worktrees = false;
         self.downstream_client.take();
+        self.guest_permissions.clear();
 
         // When not shared, only retain the visible worktrees
         for worktree_handle in self.worktrees.iter_mut() {
@@ -905,6 +911,72 @@ impl WorktreeStore {
         Ok(())
     }
 
+    pub(crate) fn set_guest_permissions(
+        &mut self,
+        guest_permissions: HashMap<proto::PeerId, GuestPermissions>,
+    ) {
+        self.guest_permissions = guest_permissions;
+    }
+
+    /// Rejects changes to a worktree, or to a buffer outside of any, from a
+    /// guest the host hasn't allowed to make them.
+    pub(crate) fn authorize_guest_edit(
+        &self,
+        peer_id: Option<proto::PeerId>,
+        worktree_id: Option<WorktreeId>,
+    ) -> Result<()> {
+        let Some(permissions) = peer_id.and_then(|peer_id| self.guest_permissions.get(&peer_id))
+        else {
+            return Ok(());
+        };
+        let allowed = match worktree_id {
+            Some(worktree_id) => permissions.can_edit(worktree_id),
+            None => !permissions.read_only,
+        };
+        anyhow::ensure!(allowed, "the host hasn't allowed you to edit this");
+        Ok(())
+    }
+
+    pub(crate) fn authorize_guest_buffer_edit(
+        &self,
+        peer_id: Option<proto::PeerId>,
+        buffer: &Buffer,
+        cx: &App,
+    ) -> Result<()> {
+        self.authorize_guest_edit(peer_id, buffer.file().map(|file| file.worktree_id(cx)))
+    }
+
+    /// Rejects changes that may reach any worktree, like renames and code
+    /// actions, from a guest the host has restricted in any way.
+    pub(crate) fn authorize_guest_project_edit(
+        &self,
+        peer_id: Option<proto::PeerId>,
+    ) -> Result<()> {
+        let Some(permissions) = peer_id.and_then(|peer_id| self.guest_permissions.get(&peer_id))
+        else {
+            return Ok(());
+        };
+        anyhow::ensure!(
+            permissions.can_edit_project(),
+            "the host hasn't allowed you to edit this"
+        );
+        Ok(())
+    }
+
+    /// Rejects changes to a repository from a guest who can't edit the
+    /// worktree containing it.
+    pub(crate) fn authorize_guest_repository_edit(
+        &self,
+        peer_id: Option<proto::PeerId>,
+        work_directory: &Path,
+        cx: &App,
+    ) -> Result<()> {
+        match self.find_worktree(work_directory, cx) {
+            Some((worktree, _)) => self.authorize_guest_edit(peer_id, Some(worktree.read(cx).id())),
+            None => self.authorize_guest_project_edit(peer_id),
+        }
+    }
+
     pub async fn handle_create_project_entry(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::CreateProjectEntry>,
@@ -912,6 +984,7 @@ impl WorktreeStore {
     ) -> Result<proto::ProjectEntryResponse> {
         let worktree = this.update(&mut cx, |this, cx| {
             let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+            this.authorize_guest_edit(envelope.original_sender_id, Some(worktree_id))?;
             this.worktree_for_id(worktree_id, cx)
                 .context("worktree not found")
         })??;
@@ -925,8 +998,11 @@ impl WorktreeStore {
     ) -> Result<proto::ProjectEntryResponse> {
         let entry_id = ProjectEntryId::from_proto(envelope.payload.entry_id);
         let worktree = this.update(&mut cx, |this, cx| {
-            this.worktree_for_entry(entry_id, cx)
-                .context("worktree not found")
+            let worktree = this
+                .worktree_for_entry(entry_id, cx)
+                .context("worktree not found")?;
+            this.authorize_guest_edit(envelope.original_sender_id, Some(worktree.read(cx).id()))?;
+            anyhow::Ok(worktree)
         })??;
         Worktree::handle_copy_entry(worktree, envelope.payload, cx).await
     }
@@ -938,8 +1014,11 @@ impl WorktreeStore {
     ) -> Result<proto::ProjectEntryResponse> {
         let entry_id = ProjectEntryId::from_proto(envelope.payload.entry_id);
         let worktree = this.update(&mut cx, |this, cx| {
-            this.worktree_for_entry(entry_id, cx)
-                .context("worktree not found")
+            let worktree = this
+                .worktree_for_entry(entry_id, cx)
+                .context("worktree not found")?;
+            this.authorize_guest_edit(envelope.original_sender_id, Some(worktree.read(cx).id()))?;
+            anyhow::Ok(worktree)
         })??;
         Worktree::handle_delete_entry(worktree, envelope.payload, cx).await
     }
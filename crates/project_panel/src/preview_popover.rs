@@ -0,0 +1,128 @@
+use editor::Editor;
+use gpui::{AnyElement, Context, Corner, Entity, Pixels, Point, Window, anchored, deferred, point};
+use project::{ProjectEntryId, image_store::is_image_file};
+use ui::prelude::*;
+use util::ResultExt as _;
+
+use crate::ProjectPanel;
+
+/// A read-only look at a file that was single-clicked in the project panel, shown instead of
+/// opening the file in a tab.
+pub(crate) struct PreviewPopover {
+    entry_id: ProjectEntryId,
+    title: SharedString,
+    position: Point<Pixels>,
+    pub(crate) editor: Entity<Editor>,
+}
+
+impl ProjectPanel {
+    pub(crate) fn show_preview_popover(
+        &mut self,
+        entry_id: ProjectEntryId,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = self.project.read(cx).path_for_entry(entry_id, cx) else {
+            return;
+        };
+        // Files that can't be shown as text open in a preview tab, like they would otherwise.
+        if is_image_file(&self.project, &project_path, cx) {
+            self.open_entry(entry_id, false, true, cx);
+            return;
+        }
+
+        let title = project_path
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+            .into();
+        let open_buffer = self
+            .project
+            .update(cx, |project, cx| project.open_buffer(project_path, cx));
+        self.preview_popover_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let buffer = open_buffer.await;
+            this.update_in(cx, |this, window, cx| {
+                // Opening the file in a tab reports why it couldn't be loaded.
+                let Ok(buffer) = buffer else {
+                    this.open_entry(entry_id, false, true, cx);
+                    return;
+                };
+                let editor = cx.new(|cx| {
+                    let mut editor = Editor::for_buffer(buffer, None, window, cx);
+                    editor.set_read_only(true);
+                    editor.set_show_breakpoints(false, cx);
+                    editor.set_show_code_actions(false, cx);
+                    editor.set_show_runnables(false, cx);
+                    editor
+                });
+                this.preview_popover = Some(PreviewPopover {
+                    entry_id,
+                    title,
+                    position,
+                    editor,
+                });
+                cx.notify();
+            })
+            .log_err();
+        }));
+    }
+
+    /// Closes the preview popover, returning whether one was open.
+    pub(crate) fn dismiss_preview_popover(&mut self, cx: &mut Context<Self>) -> bool {
+        self.preview_popover_task = None;
+        if self.preview_popover.take().is_some() {
+            cx.notify();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn render_preview_popover(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let popover = self.preview_popover.as_ref()?;
+        let entry_id = popover.entry_id;
+        Some(
+            deferred(
+                anchored()
+                    .position(popover.position + point(px(12.), px(0.)))
+                    .anchor(Corner::TopLeft)
+                    .snap_to_window_with_margin(px(8.))
+                    .child(
+                        v_flex()
+                            .id((
+                                "project-panel-preview-popover",
+                                entry_id.to_proto() as usize,
+                            ))
+                            .occlude()
+                            .w(rems(40.))
+                            .h(rems(24.))
+                            .overflow_hidden()
+                            .elevation_2(cx)
+                            .on_mouse_down_out(cx.listener(|this, _, _, cx| {
+                                this.dismiss_preview_popover(cx);
+                            }))
+                            .child(
+                                h_flex()
+                                    .px_2()
+                                    .py_1()
+                                    .gap_2()
+                                    .justify_between()
+                                    .border_b_1()
+                                    .border_color(cx.theme().colors().border_variant)
+                                    .child(Label::new(popover.title.clone()).size(LabelSize::Small))
+                                    .child(
+                                        Label::new("Double-click to open")
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    ),
+                            )
+                            .child(div().flex_1().min_h_0().child(popover.editor.clone())),
+                    ),
+            )
+            .with_priority(1)
+            .into_any_element(),
+        )
+    }
+}
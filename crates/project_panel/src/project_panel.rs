@@ -1,3 +1,4 @@
+mod preview_popover;
 mod project_panel_settings;
 mod utils;
 
@@ -28,6 +29,7 @@ use gpui::{
 use indexmap::IndexMap;
 use language::DiagnosticSeverity;
 use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use preview_popover::PreviewPopover;
 use project::{
     Entry, EntryKind, Fs, GitEntry, GitEntryRef, GitTraversal, Project, ProjectEntryId,
     ProjectPath, Worktree, WorktreeId,
@@ -93,6 +95,8 @@ pub struct ProjectPanel {
     selection: Option<SelectedEntry>,
     marked_entries: BTreeSet<SelectedEntry>,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    preview_popover: Option<PreviewPopover>,
+    preview_popover_task: Option<Task<()>>,
     edit_state: Option<EditState>,
     filename_editor: Entity<Editor>,
     clipboard: Option<ClipboardEntry>,
@@ -488,6 +492,8 @@ impl ProjectPanel {
                 marked_entries: Default::default(),
                 edit_state: None,
                 context_menu: None,
+                preview_popover: None,
+                preview_popover_task: None,
                 filename_editor,
                 clipboard: None,
                 _dragged_entry_destination: None,
@@ -764,7 +770,7 @@ impl ProjectPanel {
             let is_dir = entry.is_dir();
             let is_foldable = auto_fold_dirs && self.is_foldable(entry, worktree);
             let is_unfoldable = auto_fold_dirs && self.is_unfoldable(entry, worktree);
-            let is_read_only = project.is_read_only(cx);
+            let is_read_only = !project.can_edit_worktree(worktree.id(), cx);
             let is_remote = project.is_via_collab();
             let is_local = project.is_local();
 
@@ -1382,6 +1388,9 @@ impl ProjectPanel {
     }
 
     fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.dismiss_preview_popover(cx) {
+            return;
+        }
         if cx.stop_active_drag(window) {
             self.drag_target_entry.take();
             self.hover_expand_task.take();
@@ -4134,8 +4143,12 @@ impl ProjectPanel {
                             this.toggle_expanded(entry_id, window, cx);
                         }
                     } else {
-                        let preview_tabs_enabled = PreviewTabsSettings::get_global(cx).enabled;
                         let click_count = event.up.click_count;
+                        if click_count == 1 && ProjectPanelSettings::get_global(cx).preview_popover {
+                            this.show_preview_popover(entry_id, event.up.position, window, cx);
+                            return;
+                        }
+                        let preview_tabs_enabled = PreviewTabsSettings::get_global(cx).enabled;
                         let focus_opened_item = !preview_tabs_enabled || click_count > 1;
                         let allow_preview = preview_tabs_enabled && click_count == 1;
                         this.open_entry(entry_id, focus_opened_item, allow_preview, cx);
@@ -4999,6 +5012,7 @@ impl Render for ProjectPanel {
                 .when_some(self.render_horizontal_scrollbar(cx), |this, scrollbar| {
                     this.pb_4().child(scrollbar)
                 })
+                .children(self.render_preview_popover(cx))
                 .children(self.context_menu.as_ref().map(|(menu, position, _)| {
                     deferred(
                         anchored()
@@ -5142,8 +5156,8 @@ impl Panel for ProjectPanel {
             .then_some(IconName::FileTree)
     }
 
-    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
-        Some("Project Panel")
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Project Panel".into())
     }
 
     fn toggle_action(&self) -> Box<dyn Action> {
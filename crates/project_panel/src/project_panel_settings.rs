@@ -42,6 +42,7 @@ pub struct ProjectPanelSettings {
     pub indent_guides: IndentGuidesSettings,
     pub auto_reveal_entries: bool,
     pub auto_fold_dirs: bool,
+    pub preview_popover: bool,
     pub scrollbar: ScrollbarSettings,
     pub show_diagnostics: ShowDiagnostics,
 }
@@ -137,6 +138,11 @@ pub struct ProjectPanelSettingsContent {
     ///
     /// Default: true
     pub auto_fold_dirs: Option<bool>,
+    /// Whether single-clicking a file shows it in a read-only popover, instead of opening it
+    /// in a tab. Double-clicking the file still opens it.
+    ///
+    /// Default: false
+    pub preview_popover: Option<bool>,
     /// Scrollbar-related settings
     pub scrollbar: Option<ScrollbarSettingsContent>,
     /// Which files containing diagnostic errors/warnings to mark in the project panel.
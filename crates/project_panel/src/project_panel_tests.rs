@@ -5297,6 +5297,65 @@ async fn test_highlight_entry_for_selection_drag(cx: &mut gpui::TestAppContext)
     });
 }
 
+#[gpui::test]
+async fn test_preview_popover(cx: &mut gpui::TestAppContext) {
+    init_test_with_editor(cx);
+
+    let fs = FakeFs::new(cx.executor().clone());
+    fs.insert_tree(
+        "/root1",
+        json!({
+            "a.txt": "hello",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/root1".as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let panel = workspace.update(cx, ProjectPanel::new).unwrap();
+    let entry_id = find_project_entry(&panel, "root1/a.txt", cx).unwrap();
+
+    panel.update_in(cx, |panel, window, cx| {
+        panel.show_preview_popover(entry_id, point(px(10.), px(10.)), window, cx);
+    });
+    cx.run_until_parked();
+
+    panel.update(cx, |panel, cx| {
+        let editor = panel.preview_popover.as_ref().unwrap().editor.read(cx);
+        assert_eq!(editor.text(cx), "hello");
+        assert!(editor.read_only(cx), "The preview should be read-only");
+    });
+    workspace
+        .read_with(cx, |workspace, cx| {
+            assert!(
+                workspace.active_pane().read(cx).items().next().is_none(),
+                "Previewing a file should not open it in a tab"
+            );
+        })
+        .unwrap();
+
+    panel.update_in(cx, |panel, window, cx| {
+        panel.cancel(&menu::Cancel, window, cx)
+    });
+    panel.update(cx, |panel, _| assert!(panel.preview_popover.is_none()));
+
+    panel.update_in(cx, |panel, window, cx| {
+        panel.show_preview_popover(entry_id, point(px(10.), px(10.)), window, cx);
+        assert!(
+            !panel.dismiss_preview_popover(cx),
+            "The popover should not be open before its buffer loads"
+        );
+    });
+    cx.run_until_parked();
+    panel.update(cx, |panel, _| {
+        assert!(
+            panel.preview_popover.is_none(),
+            "Dismissing the popover while it loads should cancel it"
+        );
+    });
+}
+
 fn select_path(panel: &Entity<ProjectPanel>, path: impl AsRef<Path>, cx: &mut VisualTestContext) {
     let path = path.as_ref();
     panel.update(cx, |panel, cx| {
@@ -1,3 +1,4 @@
+mod prompt_variables;
 mod prompts;
 
 use anyhow::{Context as _, Result, anyhow};
@@ -14,6 +15,7 @@ use heed::{
     types::{SerdeBincode, SerdeJson, Str},
 };
 use parking_lot::RwLock;
+pub use prompt_variables::*;
 pub use prompts::*;
 use rope::Rope;
 use serde::{Deserialize, Serialize};
@@ -49,6 +51,10 @@ pub struct PromptMetadata {
     pub title: Option<SharedString>,
     pub default: bool,
     pub saved_at: DateTime<Utc>,
+    /// The languages in which this prompt prefills the inline assistant when transforming a
+    /// selection.
+    #[serde(default)]
+    pub inline_default_languages: Vec<SharedString>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -250,6 +256,7 @@ impl PromptStore {
                         title: metadata_v1.title.clone(),
                         default: metadata_v1.default,
                         saved_at: metadata_v1.saved_at,
+                        inline_default_languages: Vec::new(),
                     },
                 )?;
                 bodies_db.put(&mut txn, &prompt_id_v2, &body_v1)?;
@@ -287,6 +294,22 @@ impl PromptStore {
             .collect::<Vec<_>>();
     }
 
+    /// The prompt prefilling the inline assistant when transforming a selection in the given
+    /// language.
+    pub fn inline_default_prompt_metadata(&self, language_name: &str) -> Option<PromptMetadata> {
+        self.metadata_cache
+            .read()
+            .metadata
+            .iter()
+            .find(|metadata| {
+                metadata
+                    .inline_default_languages
+                    .iter()
+                    .any(|language| language.eq_ignore_ascii_case(language_name))
+            })
+            .cloned()
+    }
+
     pub fn delete(&self, id: PromptId, cx: &Context<Self>) -> Task<Result<()>> {
         self.metadata_cache.write().remove(id);
 
@@ -383,13 +406,20 @@ impl PromptStore {
             return Task::ready(Err(anyhow!("built-in prompts cannot be saved")));
         }
 
+        let mut cache = self.metadata_cache.write();
         let prompt_metadata = PromptMetadata {
             id,
             title,
             default,
             saved_at: Utc::now(),
+            inline_default_languages: cache
+                .metadata_by_id
+                .get(&id)
+                .map(|metadata| metadata.inline_default_languages.clone())
+                .unwrap_or_default(),
         };
-        self.metadata_cache.write().insert(prompt_metadata.clone());
+        cache.insert(prompt_metadata.clone());
+        drop(cache);
 
         let db_connection = self.env.clone();
         let bodies = self.bodies;
@@ -434,9 +464,47 @@ impl PromptStore {
             title,
             default,
             saved_at: Utc::now(),
+            inline_default_languages: cache
+                .metadata_by_id
+                .get(&id)
+                .map(|metadata| metadata.inline_default_languages.clone())
+                .unwrap_or_default(),
         };
 
         cache.insert(prompt_metadata.clone());
+        drop(cache);
+
+        let db_connection = self.env.clone();
+        let metadata = self.metadata;
+
+        let task = cx.background_spawn(async move {
+            let mut txn = db_connection.write_txn()?;
+            metadata.put(&mut txn, &id, &prompt_metadata)?;
+            txn.commit()?;
+
+            anyhow::Ok(())
+        });
+
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |_, cx| cx.emit(PromptsUpdatedEvent)).ok();
+            anyhow::Ok(())
+        })
+    }
+
+    pub fn save_inline_default_languages(
+        &self,
+        id: PromptId,
+        languages: Vec<SharedString>,
+        cx: &Context<Self>,
+    ) -> Task<Result<()>> {
+        let mut cache = self.metadata_cache.write();
+        let Some(mut prompt_metadata) = cache.metadata_by_id.get(&id).cloned() else {
+            return Task::ready(Err(anyhow!("prompt not found")));
+        };
+        prompt_metadata.inline_default_languages = languages;
+        cache.insert(prompt_metadata.clone());
+        drop(cache);
 
         let db_connection = self.env.clone();
         let metadata = self.metadata;
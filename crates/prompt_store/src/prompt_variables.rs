@@ -0,0 +1,138 @@
+use std::ops::Range;
+
+/// A placeholder in the body of a prompt that's filled in when the prompt is used, written as
+/// `{{kind}}` or `{{kind:name}}`, like `{{selection}}` or `{{text:target language}}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PromptVariable {
+    pub kind: PromptVariableKind,
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PromptVariableKind {
+    /// The text selected in the editor.
+    Selection,
+    /// The path of the file being edited.
+    FilePath,
+    /// Text entered by the user.
+    Text,
+}
+
+impl PromptVariable {
+    /// How the variable is referred to when asking for its value.
+    pub fn label(&self) -> String {
+        match (&self.name, self.kind) {
+            (Some(name), _) => name.clone(),
+            (None, PromptVariableKind::Selection) => "Selection".into(),
+            (None, PromptVariableKind::FilePath) => "File path".into(),
+            (None, PromptVariableKind::Text) => "Text".into(),
+        }
+    }
+
+    fn parse(placeholder: &str) -> Option<Self> {
+        let (kind, name) = match placeholder.split_once(':') {
+            Some((kind, name)) => (kind.trim(), Some(name.trim().to_string())),
+            None => (placeholder.trim(), None),
+        };
+        let kind = match kind {
+            "selection" => PromptVariableKind::Selection,
+            "file_path" => PromptVariableKind::FilePath,
+            "text" => PromptVariableKind::Text,
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            name: name.filter(|name| !name.is_empty()),
+        })
+    }
+}
+
+/// The variables of a prompt, in the order they first appear.
+pub fn prompt_variables(body: &str) -> Vec<PromptVariable> {
+    let mut variables = Vec::new();
+    for (_, variable) in placeholders(body) {
+        if !variables.contains(&variable) {
+            variables.push(variable);
+        }
+    }
+    variables
+}
+
+/// Replaces the variables of a prompt with their values, leaving those without one as they are.
+pub fn fill_prompt_variables(
+    body: &str,
+    mut value: impl FnMut(&PromptVariable) -> Option<String>,
+) -> String {
+    let mut filled = String::with_capacity(body.len());
+    let mut last_end = 0;
+    for (range, variable) in placeholders(body) {
+        if let Some(value) = value(&variable) {
+            filled.push_str(&body[last_end..range.start]);
+            filled.push_str(&value);
+            last_end = range.end;
+        }
+    }
+    filled.push_str(&body[last_end..]);
+    filled
+}
+
+fn placeholders(body: &str) -> Vec<(Range<usize>, PromptVariable)> {
+    let mut placeholders = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = body[offset..].find("{{").map(|start| offset + start) {
+        let Some(end) = body[start..].find("}}").map(|end| start + end + 2) else {
+            break;
+        };
+        match PromptVariable::parse(&body[start + 2..end - 2]) {
+            Some(variable) => {
+                placeholders.push((start..end, variable));
+                offset = end;
+            }
+            None => offset = start + 2,
+        }
+    }
+    placeholders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_variables() {
+        let body = "Translate {{selection}} in {{file_path}} to {{text: language}}, \
+            keeping {{ unknown }} and {{text}} as is, in {{text:language}}.";
+        assert_eq!(
+            prompt_variables(body),
+            [
+                PromptVariable {
+                    kind: PromptVariableKind::Selection,
+                    name: None,
+                },
+                PromptVariable {
+                    kind: PromptVariableKind::FilePath,
+                    name: None,
+                },
+                PromptVariable {
+                    kind: PromptVariableKind::Text,
+                    name: Some("language".into()),
+                },
+                PromptVariable {
+                    kind: PromptVariableKind::Text,
+                    name: None,
+                },
+            ]
+        );
+
+        let filled = fill_prompt_variables(body, |variable| match variable.kind {
+            PromptVariableKind::Selection => Some("the comments".into()),
+            PromptVariableKind::FilePath => Some("src/main.rs".into()),
+            PromptVariableKind::Text => variable.name.as_ref().map(|_| "French".into()),
+        });
+        assert_eq!(
+            filled,
+            "Translate the comments in src/main.rs to French, \
+            keeping {{ unknown }} and {{text}} as is, in French."
+        );
+    }
+}
@@ -106,6 +106,21 @@ pub struct ContentPromptContext {
     pub diagnostic_errors: Vec<ContentPromptDiagnosticContext>,
 }
 
+#[derive(Serialize)]
+pub struct MultiFileTransformExcerpt {
+    pub path: String,
+    pub text: String,
+}
+
+#[derive(Serialize)]
+pub struct MultiFileTransformPromptContext {
+    pub language_name: Option<String>,
+    pub path: String,
+    pub rewrite_section: String,
+    pub user_prompt: String,
+    pub related_excerpts: Vec<MultiFileTransformExcerpt>,
+}
+
 #[derive(Serialize)]
 pub struct TerminalAssistantPromptContext {
     pub os: String,
@@ -423,6 +438,26 @@ impl PromptBuilder {
         self.handlebars.lock().render("content_prompt", &context)
     }
 
+    /// The prompt asking which identifiers to search for to find the code that a transformation
+    /// of a selection also affects.
+    pub fn generate_multi_file_search_prompt(
+        &self,
+        context: &MultiFileTransformPromptContext,
+    ) -> Result<String, RenderError> {
+        self.handlebars
+            .lock()
+            .render("multi_file_search_prompt", context)
+    }
+
+    pub fn generate_multi_file_transform_prompt(
+        &self,
+        context: &MultiFileTransformPromptContext,
+    ) -> Result<String, RenderError> {
+        self.handlebars
+            .lock()
+            .render("multi_file_transform_prompt", context)
+    }
+
     pub fn generate_terminal_assistant_prompt(
         &self,
         user_prompt: &str,
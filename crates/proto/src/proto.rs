@@ -246,6 +246,10 @@ messages!(
     (SyncExtensionsResponse, Background),
     (BreakpointsForFile, Background),
     (ToggleBreakpoint, Foreground),
+    (CommentThreadsForFile, Background),
+    (CreateCommentThread, Foreground),
+    (ReplyToCommentThread, Foreground),
+    (SetCommentThreadResolved, Foreground),
     (SynchronizeBuffers, Foreground),
     (SynchronizeBuffersResponse, Foreground),
     (SynchronizeContexts, Foreground),
@@ -272,13 +276,17 @@ messages!(
     (UpdateInviteInfo, Foreground),
     (UpdateLanguageServer, Foreground),
     (UpdateNotification, Foreground),
+    (UpdateParticipantActivity, Foreground),
     (UpdateParticipantLocation, Foreground),
+    (UpdateScreenAnnotations, Foreground),
     (UpdateProject, Foreground),
     (UpdateProjectCollaborator, Foreground),
+    (UpdateGuestPermissions, Foreground),
     (UpdateUserChannels, Foreground),
     (UpdateUserPlan, Foreground),
     (UpdateWorktree, Foreground),
     (UpdateWorktreeSettings, Foreground),
+    (UpdateTrustedFolders, Foreground),
     (UpdateRepository, Foreground),
     (RemoveRepository, Foreground),
     (UsersResponse, Foreground),
@@ -470,6 +478,9 @@ request_messages!(
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
     (ToggleBreakpoint, Ack),
+    (CreateCommentThread, Ack),
+    (ReplyToCommentThread, Ack),
+    (SetCommentThreadResolved, Ack),
     (GetDebugAdapterBinary, DebugAdapterBinary),
     (RunDebugLocators, DebugRequest),
     (GetDocumentDiagnostics, GetDocumentDiagnosticsResponse),
@@ -548,10 +559,12 @@ entity_messages!(
     UpdateLanguageServer,
     UpdateProject,
     UpdateProjectCollaborator,
+    UpdateGuestPermissions,
     UpdateWorktree,
     UpdateRepository,
     RemoveRepository,
     UpdateWorktreeSettings,
+    UpdateTrustedFolders,
     LspExtExpandMacro,
     LspExtOpenDocs,
     LspExtRunnables,
@@ -597,6 +610,10 @@ entity_messages!(
     GitInit,
     BreakpointsForFile,
     ToggleBreakpoint,
+    CommentThreadsForFile,
+    CreateCommentThread,
+    ReplyToCommentThread,
+    SetCommentThreadResolved,
     RunDebugLocators,
     GetDebugAdapterBinary,
     LogToDebugConsole,
@@ -5,11 +5,13 @@ mod ssh_connections;
 
 pub use ssh_connections::{is_connecting_over_ssh, open_ssh_project};
 
+use collections::{HashMap, HashSet};
 use disconnected_overlay::DisconnectedOverlay;
+use editor::Editor;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
     Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
-    Subscription, Task, WeakEntity, Window,
+    Subscription, Task, WeakEntity, Window, actions,
 };
 use ordered_float::OrderedFloat;
 use picker::{
@@ -31,6 +33,8 @@ use workspace::{
 };
 use zed_actions::{OpenRecent, OpenRemote};
 
+actions!(recent_projects, [TogglePin, EditTags, RemoveStale]);
+
 pub fn init(cx: &mut App) {
     SshSettings::register(cx);
     cx.on_action(|open_recent: &OpenRecent, cx| {
@@ -99,9 +103,14 @@ impl RecentProjects {
                 .await
                 .log_err()
                 .unwrap_or_default();
+            let annotations = WORKSPACE_DB
+                .workspace_annotations()
+                .log_err()
+                .unwrap_or_default();
             this.update_in(cx, move |this, window, cx| {
                 this.picker.update(cx, move |picker, cx| {
                     picker.delegate.set_workspaces(workspaces);
+                    picker.delegate.set_annotations(annotations);
                     picker.update_matches(picker.query(cx), window, cx)
                 })
             })
@@ -141,7 +150,25 @@ impl Focusable for RecentProjects {
 impl Render for RecentProjects {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
+            .key_context("RecentProjects")
             .w(rems(self.rem_width))
+            .on_action(cx.listener(|this, _: &TogglePin, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    let ix = picker.delegate.selected_index();
+                    picker.delegate.toggle_pin(ix, window, cx);
+                })
+            }))
+            .on_action(cx.listener(|this, _: &EditTags, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    let ix = picker.delegate.selected_index();
+                    picker.delegate.edit_tags(ix, window, cx);
+                })
+            }))
+            .on_action(cx.listener(|this, _: &RemoveStale, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.remove_stale_projects(window, cx);
+                })
+            }))
             .child(self.picker.clone())
             .on_mouse_down_out(cx.listener(|this, _, window, cx| {
                 this.picker.update(cx, |this, cx| {
@@ -161,6 +188,11 @@ pub struct RecentProjectsDelegate {
     // Flag to reset index when there is a new query vs not reset index when user delete an item
     reset_selected_match_index: bool,
     has_any_non_local_projects: bool,
+    pinned: HashSet<WorkspaceId>,
+    tags: HashMap<WorkspaceId, Vec<String>>,
+    /// The match indices after which the pinned, local, and remote projects are separated.
+    group_separators: Vec<usize>,
+    tag_editor: Option<(WorkspaceId, Entity<Editor>)>,
 }
 
 impl RecentProjectsDelegate {
@@ -174,6 +206,10 @@ impl RecentProjectsDelegate {
             render_paths,
             reset_selected_match_index: true,
             has_any_non_local_projects: false,
+            pinned: HashSet::default(),
+            tags: HashMap::default(),
+            group_separators: Vec::new(),
+            tag_editor: None,
         }
     }
 
@@ -184,6 +220,37 @@ impl RecentProjectsDelegate {
             .iter()
             .all(|(_, location)| matches!(location, SerializedWorkspaceLocation::Local(_, _)));
     }
+
+    /// Sets which projects are pinned and how they are tagged, as stored in the database.
+    pub fn set_annotations(
+        &mut self,
+        annotations: Vec<(WorkspaceId, Option<bool>, Option<String>)>,
+    ) {
+        self.pinned.clear();
+        self.tags.clear();
+        for (workspace_id, pinned, tags) in annotations {
+            if pinned == Some(true) {
+                self.pinned.insert(workspace_id);
+            }
+            let tags = tags.as_deref().map(parse_tags).unwrap_or_default();
+            if !tags.is_empty() {
+                self.tags.insert(workspace_id, tags);
+            }
+        }
+    }
+
+    /// Orders pinned projects first, then local ones, then remote ones, each by how recently
+    /// they were opened.
+    fn group(&self, candidate_id: usize) -> usize {
+        let (workspace_id, location) = &self.workspaces[candidate_id];
+        if self.pinned.contains(workspace_id) {
+            0
+        } else if matches!(location, SerializedWorkspaceLocation::Local(_, _)) {
+            1
+        } else {
+            2
+        }
+    }
 }
 impl EventEmitter<DismissEvent> for RecentProjectsDelegate {}
 impl PickerDelegate for RecentProjectsDelegate {
@@ -214,6 +281,10 @@ impl PickerDelegate for RecentProjectsDelegate {
         self.selected_match_index
     }
 
+    fn separators_after_indices(&self) -> Vec<usize> {
+        self.group_separators.clone()
+    }
+
     fn set_selected_index(
         &mut self,
         ix: usize,
@@ -229,13 +300,21 @@ impl PickerDelegate for RecentProjectsDelegate {
         _: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> gpui::Task<()> {
-        let query = query.trim_start();
+        let (tag_filters, query) = parse_query(&query);
+        let query = query.as_str();
         let smart_case = query.chars().any(|c| c.is_uppercase());
         let candidates = self
             .workspaces
             .iter()
             .enumerate()
             .filter(|(_, (id, _))| !self.is_current_workspace(*id, cx))
+            .filter(|(_, (id, _))| {
+                let tags = self.tags.get(id).map(Vec::as_slice).unwrap_or_default();
+                tag_filters.iter().all(|filter| {
+                    tags.iter()
+                        .any(|tag| tag.to_lowercase().starts_with(filter.as_str()))
+                })
+            })
             .map(|(id, (_, location))| {
                 let combined_string = location
                     .sorted_paths()
@@ -255,7 +334,18 @@ impl PickerDelegate for RecentProjectsDelegate {
             &Default::default(),
             cx.background_executor().clone(),
         ));
-        self.matches.sort_unstable_by_key(|m| m.candidate_id);
+        let groups = (0..self.workspaces.len())
+            .map(|candidate_id| self.group(candidate_id))
+            .collect::<Vec<_>>();
+        self.matches
+            .sort_unstable_by_key(|m| (groups[m.candidate_id], m.candidate_id));
+        self.group_separators = self
+            .matches
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| groups[pair[0].candidate_id] != groups[pair[1].candidate_id])
+            .map(|(ix, _)| ix)
+            .collect();
 
         if self.reset_selected_match_index {
             self.selected_match_index = self
@@ -381,7 +471,9 @@ impl PickerDelegate for RecentProjectsDelegate {
     ) -> Option<Self::ListItem> {
         let hit = self.matches.get(ix)?;
 
-        let (_, location) = self.workspaces.get(hit.candidate_id)?;
+        let (workspace_id, location) = self.workspaces.get(hit.candidate_id)?;
+        let is_pinned = self.pinned.contains(workspace_id);
+        let tags = self.tags.get(workspace_id).cloned().unwrap_or_default();
 
         let mut path_start_offset = 0;
 
@@ -430,10 +522,67 @@ impl PickerDelegate for RecentProjectsDelegate {
                                 highlighted.paths.clear();
                             }
                             highlighted.render(window, cx)
+                        })
+                        .when(is_pinned || !tags.is_empty(), |this| {
+                            this.child(
+                                h_flex()
+                                    .gap_1()
+                                    .when(is_pinned, |this| {
+                                        this.child(
+                                            Icon::new(IconName::Pin)
+                                                .size(IconSize::XSmall)
+                                                .color(Color::Muted),
+                                        )
+                                    })
+                                    .children(tags.iter().map(|tag| {
+                                        Label::new(format!("#{tag}"))
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted)
+                                    })),
+                            )
                         }),
                 )
                 .map(|el| {
-                    let delete_button = div()
+                    let buttons = h_flex()
+                        .gap_1()
+                        .child(
+                            IconButton::new(
+                                "pin",
+                                if is_pinned {
+                                    IconName::Unpin
+                                } else {
+                                    IconName::Pin
+                                },
+                            )
+                            .icon_size(IconSize::Small)
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                cx.stop_propagation();
+                                window.prevent_default();
+
+                                this.delegate.toggle_pin(ix, window, cx)
+                            }))
+                            .tooltip(move |window, cx| {
+                                Tooltip::for_action(
+                                    if is_pinned { "Unpin" } else { "Pin to Top" },
+                                    &TogglePin,
+                                    window,
+                                    cx,
+                                )
+                            }),
+                        )
+                        .child(
+                            IconButton::new("tags", IconName::Hash)
+                                .icon_size(IconSize::Small)
+                                .on_click(cx.listener(move |this, _event, window, cx| {
+                                    cx.stop_propagation();
+                                    window.prevent_default();
+
+                                    this.delegate.edit_tags(ix, window, cx)
+                                }))
+                                .tooltip(|window, cx| {
+                                    Tooltip::for_action("Edit Tags", &EditTags, window, cx)
+                                }),
+                        )
                         .child(
                             IconButton::new("delete", IconName::Close)
                                 .icon_size(IconSize::Small)
@@ -448,9 +597,9 @@ impl PickerDelegate for RecentProjectsDelegate {
                         .into_any_element();
 
                     if self.selected_index() == ix {
-                        el.end_slot::<AnyElement>(delete_button)
+                        el.end_slot::<AnyElement>(buttons)
                     } else {
-                        el.end_hover_slot::<AnyElement>(delete_button)
+                        el.end_hover_slot::<AnyElement>(buttons)
                     }
                 })
                 .tooltip(move |_, cx| {
@@ -468,6 +617,30 @@ impl PickerDelegate for RecentProjectsDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Option<AnyElement> {
+        if let Some((_, tag_editor)) = &self.tag_editor {
+            return Some(
+                h_flex()
+                    .w_full()
+                    .p_2()
+                    .gap_2()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    // The picker would open the project on confirm, so these have to be handled
+                    // before they reach it.
+                    .on_action(cx.listener(|picker, _: &menu::Confirm, window, cx| {
+                        picker.delegate.save_tags(window, cx)
+                    }))
+                    .on_action(cx.listener(|picker, _: &menu::Cancel, window, cx| {
+                        picker.delegate.tag_editor = None;
+                        picker.focus(window, cx);
+                        cx.notify();
+                    }))
+                    .child(Icon::new(IconName::Hash).color(Color::Muted))
+                    .child(div().flex_1().child(tag_editor.clone()))
+                    .into_any(),
+            );
+        }
+
         Some(
             h_flex()
                 .w_full()
@@ -476,6 +649,16 @@ impl PickerDelegate for RecentProjectsDelegate {
                 .justify_end()
                 .border_t_1()
                 .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("stale", "Remove Stale")
+                        .key_binding(KeyBinding::for_action(&RemoveStale, window, cx))
+                        .tooltip(Tooltip::text(
+                            "Remove projects that aren't pinned and haven't been opened for 90 days",
+                        ))
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            picker.delegate.remove_stale_projects(window, cx)
+                        })),
+                )
                 .child(
                     Button::new("remote", "Open Remote Folder")
                         .key_binding(KeyBinding::for_action(
@@ -509,6 +692,41 @@ impl PickerDelegate for RecentProjectsDelegate {
     }
 }
 
+/// Splits the `#tag` filters out of the query, lowercased and without their `#`.
+fn parse_query(query: &str) -> (Vec<String>, String) {
+    let query = query.trim_start();
+    if !query.split_whitespace().any(is_tag_filter) {
+        return (Vec::new(), query.to_string());
+    }
+    let (tag_filters, rest): (Vec<_>, Vec<_>) = query
+        .split_whitespace()
+        .partition(|token| is_tag_filter(token));
+    let tag_filters = tag_filters
+        .into_iter()
+        .map(|filter| filter[1..].to_lowercase())
+        .collect();
+    (tag_filters, rest.join(" "))
+}
+
+fn is_tag_filter(token: &str) -> bool {
+    token.len() > 1 && token.starts_with('#')
+}
+
+fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::<String>::new();
+    for tag in text.split(',') {
+        let tag = tag.trim().trim_start_matches('#').trim();
+        if !tag.is_empty()
+            && !tags
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(tag))
+        {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
 // Compute the highlighted text for the name and path
 fn highlights_for_path(
     path: &Path,
@@ -573,8 +791,13 @@ impl RecentProjectsDelegate {
                     .recent_workspaces_on_disk()
                     .await
                     .unwrap_or_default();
+                let annotations = WORKSPACE_DB
+                    .workspace_annotations()
+                    .log_err()
+                    .unwrap_or_default();
                 this.update_in(cx, move |picker, window, cx| {
                     picker.delegate.set_workspaces(workspaces);
+                    picker.delegate.set_annotations(annotations);
                     picker
                         .delegate
                         .set_selected_index(ix.saturating_sub(1), window, cx);
@@ -592,6 +815,105 @@ impl RecentProjectsDelegate {
         }
     }
 
+    fn toggle_pin(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(selected_match) = self.matches.get(ix) else {
+            return;
+        };
+        let (workspace_id, _) = self.workspaces[selected_match.candidate_id];
+        let pinned = !self.pinned.contains(&workspace_id);
+        if pinned {
+            self.pinned.insert(workspace_id);
+        } else {
+            self.pinned.remove(&workspace_id);
+        }
+        cx.background_spawn(WORKSPACE_DB.set_workspace_pinned(workspace_id, pinned))
+            .detach_and_log_err(cx);
+        self.refresh_matches_selecting(workspace_id, window, cx);
+    }
+
+    fn edit_tags(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(selected_match) = self.matches.get(ix) else {
+            return;
+        };
+        let (workspace_id, _) = self.workspaces[selected_match.candidate_id];
+        let tags = self
+            .tags
+            .get(&workspace_id)
+            .map(|tags| tags.join(", "))
+            .unwrap_or_default();
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Tags, separated by commas", cx);
+            editor.set_text(tags, window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+            editor
+        });
+        window.focus(&editor.focus_handle(cx));
+        self.tag_editor = Some((workspace_id, editor));
+        cx.notify();
+    }
+
+    fn save_tags(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some((workspace_id, editor)) = self.tag_editor.take() else {
+            return;
+        };
+        let tags = parse_tags(&editor.read(cx).text(cx));
+        let stored_tags = (!tags.is_empty()).then(|| tags.join(","));
+        if tags.is_empty() {
+            self.tags.remove(&workspace_id);
+        } else {
+            self.tags.insert(workspace_id, tags);
+        }
+        cx.background_spawn(WORKSPACE_DB.set_workspace_tags(workspace_id, stored_tags))
+            .detach_and_log_err(cx);
+        cx.focus_self(window);
+        self.refresh_matches_selecting(workspace_id, window, cx);
+    }
+
+    fn remove_stale_projects(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.spawn_in(window, async move |this, cx| {
+            let stale_ids = WORKSPACE_DB.stale_workspace_ids()?;
+            for workspace_id in &stale_ids {
+                WORKSPACE_DB.delete_workspace_by_id(*workspace_id).await?;
+            }
+            let workspaces = WORKSPACE_DB.recent_workspaces_on_disk().await?;
+            this.update_in(cx, move |picker, window, cx| {
+                picker.delegate.set_workspaces(workspaces);
+                picker.update_matches(picker.query(cx), window, cx);
+                if let Some(history_manager) = HistoryManager::global(cx) {
+                    history_manager.update(cx, |history_manager, cx| {
+                        for workspace_id in stale_ids {
+                            history_manager.delete_history(workspace_id, cx);
+                        }
+                    });
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Updates the matches after a project moved between groups, keeping it selected.
+    fn refresh_matches_selecting(
+        &mut self,
+        workspace_id: WorkspaceId,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        cx.defer_in(window, move |picker, window, cx| {
+            picker.delegate.reset_selected_match_index = false;
+            picker.update_matches(picker.query(cx), window, cx);
+            let delegate = &mut picker.delegate;
+            if let Some(ix) = delegate
+                .matches
+                .iter()
+                .position(|m| delegate.workspaces[m.candidate_id].0 == workspace_id)
+            {
+                picker.set_selected_index(ix, None, true, window, cx);
+            }
+            cx.notify();
+        });
+    }
+
     fn is_current_workspace(
         &self,
         workspace_id: WorkspaceId,
@@ -743,6 +1065,23 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_parse_query_and_tags() {
+        assert_eq!(parse_query("  zed"), (Vec::new(), "zed".to_string()));
+        assert_eq!(
+            parse_query("#Work zed #rust"),
+            (
+                vec!["work".to_string(), "rust".to_string()],
+                "zed".to_string()
+            )
+        );
+        assert_eq!(parse_query("#"), (Vec::new(), "#".to_string()));
+        assert_eq!(
+            parse_tags("work, #rust,, Work ,side project"),
+            vec!["work", "rust", "side project"]
+        );
+    }
+
     fn open_recent_projects(
         workspace: &WindowHandle<Workspace>,
         cx: &mut TestAppContext,
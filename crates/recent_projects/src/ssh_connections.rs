@@ -599,6 +599,7 @@ pub async fn open_ssh_project(
             cx.new(|cx| {
                 let mut workspace = Workspace::new(None, project, app_state.clone(), window, cx);
                 workspace.centered_layout = workspace_position.centered_layout;
+                workspace.zen_mode = workspace_position.zen_mode;
                 workspace
             })
         })?
@@ -10,7 +10,7 @@ use language::{Buffer, BufferEvent, LanguageRegistry, proto::serialize_operation
 use node_runtime::NodeRuntime;
 use project::{
     LspStore, LspStoreEvent, ManifestTree, PrettierStore, ProjectEnvironment, ProjectPath,
-    ToolchainStore, WorktreeId,
+    ToolchainStore, TrustedFolders, WorktreeId,
     buffer_store::{BufferStore, BufferStoreEvent},
     debugger::{breakpoint_store::BreakpointStore, dap_store::DapStore},
     git_store::GitStore,
@@ -86,7 +86,7 @@ impl HeadlessProject {
             store
         });
 
-        let environment = cx.new(|_| ProjectEnvironment::new(None));
+        let environment = cx.new(|cx| ProjectEnvironment::new(None, worktree_store.clone(), cx));
         let manifest_tree = ManifestTree::new(worktree_store.clone(), cx);
         let toolchain_store = cx.new(|cx| {
             ToolchainStore::local(
@@ -230,6 +230,7 @@ impl HeadlessProject {
         client.add_entity_request_handler(Self::handle_open_new_buffer);
         client.add_entity_request_handler(Self::handle_find_search_candidates);
         client.add_entity_request_handler(Self::handle_open_server_settings);
+        client.add_entity_message_handler(Self::handle_update_trusted_folders);
 
         client.add_entity_request_handler(BufferStore::handle_update_buffer);
         client.add_entity_message_handler(BufferStore::handle_close_buffer);
@@ -655,6 +656,22 @@ impl HeadlessProject {
         Ok(proto::Ack {})
     }
 
+    pub async fn handle_update_trusted_folders(
+        _this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateTrustedFolders>,
+        cx: AsyncApp,
+    ) -> Result<()> {
+        let folders = (!envelope.payload.trust_all).then(|| {
+            envelope
+                .payload
+                .abs_paths
+                .into_iter()
+                .map(PathBuf::from)
+                .collect()
+        });
+        cx.update(|cx| TrustedFolders::set_remote(folders, cx))
+    }
+
     pub async fn handle_ping(
         _this: Entity<Self>,
         _envelope: TypedEnvelope<proto::Ping>,
@@ -154,6 +154,7 @@ pub struct RulesLibrary {
 struct RuleEditor {
     title_editor: Entity<Editor>,
     body_editor: Entity<Editor>,
+    languages_editor: Entity<Editor>,
     token_count: Option<usize>,
     pending_token_count: Task<Option<()>>,
     next_title_and_body_to_save: Option<(String, Rope)>,
@@ -622,6 +623,20 @@ impl RulesLibrary {
                             }
                             editor
                         });
+                        let languages_editor = cx.new(|cx| {
+                            let mut editor = Editor::single_line(window, cx);
+                            editor.set_placeholder_text("Rust, Python, …", cx);
+                            let languages = rule_metadata
+                                .inline_default_languages
+                                .iter()
+                                .map(|language| language.to_string())
+                                .collect::<Vec<_>>();
+                            editor.set_text(languages.join(", "), window, cx);
+                            if prompt_id.is_built_in() {
+                                editor.set_read_only(true);
+                            }
+                            editor
+                        });
                         let _subscriptions = vec![
                             cx.subscribe_in(
                                 &title_editor,
@@ -641,12 +656,18 @@ impl RulesLibrary {
                                     )
                                 },
                             ),
+                            cx.subscribe(&languages_editor, move |this, editor, event, cx| {
+                                this.handle_rule_languages_editor_event(
+                                    prompt_id, editor, event, cx,
+                                )
+                            }),
                         ];
                         this.rule_editors.insert(
                             prompt_id,
                             RuleEditor {
                                 title_editor,
                                 body_editor,
+                                languages_editor,
                                 next_title_and_body_to_save: None,
                                 pending_save: None,
                                 token_count: None,
@@ -916,6 +937,30 @@ impl RulesLibrary {
         }
     }
 
+    fn handle_rule_languages_editor_event(
+        &mut self,
+        prompt_id: PromptId,
+        languages_editor: Entity<Editor>,
+        event: &EditorEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let EditorEvent::BufferEdited = event {
+            let languages = languages_editor
+                .read(cx)
+                .text(cx)
+                .split(',')
+                .map(str::trim)
+                .filter(|language| !language.is_empty())
+                .map(|language| SharedString::from(language.to_string()))
+                .collect();
+            self.store
+                .update(cx, |store, cx| {
+                    store.save_inline_default_languages(prompt_id, languages, cx)
+                })
+                .detach_and_log_err(cx);
+        }
+    }
+
     fn count_tokens(&mut self, prompt_id: PromptId, window: &mut Window, cx: &mut Context<Self>) {
         let Some(ConfiguredModel { model, .. }) =
             LanguageModelRegistry::read_global(cx).default_model()
@@ -1199,6 +1244,27 @@ impl RulesLibrary {
                                         ),
                                 ),
                         )
+                        .child(
+                            h_flex()
+                                .px_2p5()
+                                .pt_1()
+                                .gap_2()
+                                .child(
+                                    Label::new("Inline default for")
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                                .child(
+                                    div()
+                                        .id("inline-default-languages")
+                                        .flex_1()
+                                        .tooltip(Tooltip::text(
+                                            "Prefill the inline assistant with this rule when \
+                                            transforming a selection in these languages.",
+                                        ))
+                                        .child(rule_editor.languages_editor.clone()),
+                                ),
+                        )
                         .child(
                             div()
                                 .on_action(cx.listener(Self::focus_picker))
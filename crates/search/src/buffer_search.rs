@@ -685,8 +685,12 @@ impl BufferSearchBar {
         cx.subscribe_in(&query_editor, window, Self::on_query_editor_event)
             .detach();
         let replacement_editor = cx.new(|cx| Editor::single_line(window, cx));
-        cx.subscribe(&replacement_editor, Self::on_replacement_editor_event)
-            .detach();
+        cx.subscribe_in(
+            &replacement_editor,
+            window,
+            Self::on_replacement_editor_event,
+        )
+        .detach();
 
         let search_options = SearchOptions::from_settings(&EditorSettings::get_global(cx).search);
         if let Some(languages) = languages {
@@ -783,7 +787,13 @@ impl BufferSearchBar {
                     cx,
                 );
             }
-            self.search_suggested(window, cx);
+            if deploy.selection_search_enabled {
+                // The selection is what's searched within rather than what's searched for, so the
+                // query is kept and searched for again within it.
+                drop(self.update_matches(false, window, cx));
+            } else {
+                self.search_suggested(window, cx);
+            }
             self.smartcase(window, cx);
             self.replace_enabled = deploy.replace_enabled;
             self.selection_search_enabled = deploy.selection_search_enabled;
@@ -1128,17 +1138,43 @@ impl BufferSearchBar {
 
     fn on_replacement_editor_event(
         &mut self,
-        _: Entity<Editor>,
+        _: &Entity<Editor>,
         event: &editor::EditorEvent,
-        _: &mut Context<Self>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         match event {
             editor::EditorEvent::Focused => self.replacement_editor_focused = true,
             editor::EditorEvent::Blurred => self.replacement_editor_focused = false,
+            editor::EditorEvent::Edited { .. } => self.refresh_replacement_preview(window, cx),
             _ => {}
         }
     }
 
+    /// Shows what the matches would be replaced with while replacing is enabled.
+    fn refresh_replacement_preview(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(searchable_item) = self.active_searchable_item.as_ref() else {
+            return;
+        };
+        let Some(matches) = self
+            .searchable_items_with_matches
+            .get(&searchable_item.downgrade())
+        else {
+            return;
+        };
+        let query = self
+            .active_search
+            .as_ref()
+            .filter(|_| self.replace_enabled && !self.dismissed)
+            .map(|query| {
+                query
+                    .as_ref()
+                    .clone()
+                    .with_replacement(self.replacement(cx))
+            });
+        searchable_item.preview_replacements(matches, query.as_ref(), window, cx);
+    }
+
     fn on_active_searchable_item_event(
         &mut self,
         event: &SearchEvent,
@@ -1315,6 +1351,7 @@ impl BufferSearchBar {
                                 } else {
                                     active_searchable_item.update_matches(matches, window, cx);
                                 }
+                                this.refresh_replacement_preview(window, cx);
                                 let _ = done_tx.send(());
                             }
                             cx.notify();
@@ -1438,6 +1475,7 @@ impl BufferSearchBar {
                 self.query_editor.focus_handle(cx)
             };
             self.focus(&handle, window, cx);
+            self.refresh_replacement_preview(window, cx);
             cx.notify();
         }
     }
@@ -2595,6 +2633,50 @@ mod tests {
         .await;
     }
 
+    #[gpui::test]
+    async fn test_replacement_preview(cx: &mut TestAppContext) {
+        let (editor, search_bar, cx) = init_test(cx);
+
+        search_bar
+            .update_in(cx, |search_bar, window, cx| {
+                search_bar.search(r"(\w+)-(\w+)", Some(SearchOptions::REGEX), window, cx)
+            })
+            .await
+            .unwrap();
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.toggle_replace(&ToggleReplace, window, cx);
+            search_bar.replacement_editor.update(cx, |editor, cx| {
+                editor.set_text(r"\U$2\E_$1", window, cx);
+            });
+        });
+        cx.run_until_parked();
+        editor.update(cx, |editor, cx| {
+            assert_eq!(
+                editor.replacement_preview_texts(cx),
+                vec!["SEARCHING_string".to_string()]
+            );
+            // Nothing is replaced until the replacement is applied.
+            assert!(editor.text(cx).contains("string-searching"));
+        });
+
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.toggle_replace(&ToggleReplace, window, cx);
+        });
+        editor.update(cx, |editor, cx| {
+            assert!(editor.replacement_preview_texts(cx).is_empty());
+        });
+
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.toggle_replace(&ToggleReplace, window, cx);
+            search_bar.replace_all(&ReplaceAll, window, cx);
+        });
+        cx.run_until_parked();
+        editor.update(cx, |editor, cx| {
+            assert!(editor.text(cx).contains("SEARCHING_string algorithms"));
+            assert!(editor.replacement_preview_texts(cx).is_empty());
+        });
+    }
+
     #[gpui::test]
     async fn test_find_matches_in_selections_singleton_buffer_multiple_selections(
         cx: &mut TestAppContext,
@@ -2747,6 +2829,133 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_find_matches_in_lines_of_multiple_carets(cx: &mut TestAppContext) {
+        init_globals(cx);
+        let buffer = cx.new(|cx| {
+            Buffer::local(
+                r#"
+                aaa bbb aaa ccc
+                aaa bbb aaa ccc
+                aaa bbb aaa ccc
+                aaa bbb aaa ccc
+                "#
+                .unindent(),
+                cx,
+            )
+        });
+        let cx = cx.add_empty_window();
+        let editor =
+            cx.new_window_entity(|window, cx| Editor::for_buffer(buffer.clone(), None, window, cx));
+
+        let search_bar = cx.new_window_entity(|window, cx| {
+            let mut search_bar = BufferSearchBar::new(None, window, cx);
+            search_bar.set_active_pane_item(Some(&editor), window, cx);
+            search_bar.show(window, cx);
+            search_bar
+        });
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges(vec![
+                    Point::new(1, 5)..Point::new(1, 5),
+                    Point::new(3, 0)..Point::new(3, 0),
+                ])
+            })
+        });
+
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            let deploy = Deploy {
+                focus: true,
+                replace_enabled: false,
+                selection_search_enabled: true,
+            };
+            search_bar.deploy(&deploy, window, cx);
+        });
+
+        cx.run_until_parked();
+
+        search_bar
+            .update_in(cx, |search_bar, window, cx| {
+                search_bar.search("aaa", None, window, cx)
+            })
+            .await
+            .unwrap();
+
+        editor.update(cx, |editor, cx| {
+            assert_eq!(
+                editor.search_background_highlights(cx),
+                &[
+                    Point::new(1, 0)..Point::new(1, 3),
+                    Point::new(1, 8)..Point::new(1, 11),
+                    Point::new(3, 0)..Point::new(3, 3),
+                    Point::new(3, 8)..Point::new(3, 11),
+                ]
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_search_in_selection_keeps_query(cx: &mut TestAppContext) {
+        init_globals(cx);
+        let buffer = cx.new(|cx| {
+            Buffer::local(
+                r#"
+                aaa bbb aaa ccc
+                aaa bbb aaa ccc
+                "#
+                .unindent(),
+                cx,
+            )
+        });
+        let cx = cx.add_empty_window();
+        let editor =
+            cx.new_window_entity(|window, cx| Editor::for_buffer(buffer.clone(), None, window, cx));
+
+        let search_bar = cx.new_window_entity(|window, cx| {
+            let mut search_bar = BufferSearchBar::new(None, window, cx);
+            search_bar.set_active_pane_item(Some(&editor), window, cx);
+            search_bar.show(window, cx);
+            search_bar
+        });
+
+        search_bar
+            .update_in(cx, |search_bar, window, cx| {
+                search_bar.search("aaa", None, window, cx)
+            })
+            .await
+            .unwrap();
+
+        // A single line selection would otherwise become the query.
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges(vec![Point::new(1, 0)..Point::new(1, 11)])
+            })
+        });
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            let deploy = Deploy {
+                focus: true,
+                replace_enabled: false,
+                selection_search_enabled: true,
+            };
+            search_bar.deploy(&deploy, window, cx);
+        });
+        cx.run_until_parked();
+
+        search_bar.update(cx, |search_bar, cx| {
+            assert_eq!(search_bar.query(cx), "aaa");
+        });
+        editor.update(cx, |editor, cx| {
+            assert_eq!(
+                editor.search_background_highlights(cx),
+                &[
+                    Point::new(1, 0)..Point::new(1, 3),
+                    Point::new(1, 8)..Point::new(1, 11),
+                ]
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_invalid_regexp_search_after_valid(cx: &mut TestAppContext) {
         let (editor, search_bar, cx) = init_test(cx);
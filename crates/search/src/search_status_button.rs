@@ -2,7 +2,7 @@ use editor::EditorSettings;
 use settings::Settings as _;
 use ui::{
     ButtonCommon, ButtonLike, Clickable, Color, Context, Icon, IconName, IconSize, ParentElement,
-    Render, Styled, Tooltip, Window, h_flex,
+    Render, SharedString, Styled, Tooltip, Window, h_flex,
 };
 use workspace::{ItemHandle, StatusItemView};
 
@@ -51,4 +51,8 @@ impl StatusItemView for SearchButton {
         _cx: &mut Context<Self>,
     ) {
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("search".into())
+    }
 }
@@ -4,16 +4,21 @@ mod embedding_index;
 mod indexing;
 mod project_index;
 mod project_index_debug_view;
+mod semantic_index_settings;
+mod semantic_search;
 mod summary_backlog;
 mod summary_index;
 mod worktree_index;
 
 use anyhow::{Context as _, Result};
 use collections::HashMap;
+use credentials_provider::CredentialsProvider;
 use fs::Fs;
 use gpui::{App, AppContext as _, AsyncApp, BorrowAppContext, Context, Entity, Global, WeakEntity};
+use http_client::{HttpClient, HttpClientWithUrl};
 use language::LineEnding;
 use project::{Project, Worktree};
+use settings::{Settings as _, SettingsStore};
 use std::{
     cmp::Ordering,
     path::{Path, PathBuf},
@@ -25,8 +30,90 @@ use workspace::Workspace;
 pub use embedding::*;
 pub use project_index::{LoadedSearchResult, ProjectIndex, SearchResult, Status};
 pub use project_index_debug_view::ProjectIndexDebugView;
+pub use semantic_index_settings::*;
+pub use semantic_search::{FindSimilarCode, SemanticSearch};
 pub use summary_index::FileSummary;
 
+const OPENAI_API_KEY_VAR: &str = "OPENAI_API_KEY";
+
+pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
+    SemanticIndexSettings::register(cx);
+    cx.observe_new(semantic_search::SemanticSearchModal::register)
+        .detach();
+
+    let mut current_provider = None;
+    update_semantic_db(&mut current_provider, &http_client, cx);
+    cx.observe_global::<SettingsStore>(move |cx| {
+        update_semantic_db(&mut current_provider, &http_client, cx);
+    })
+    .detach();
+}
+
+/// Opens the embeddings database of the configured provider, or closes it when indexing gets
+/// disabled.
+fn update_semantic_db(
+    current_provider: &mut Option<EmbeddingProviderKind>,
+    http_client: &Arc<HttpClientWithUrl>,
+    cx: &mut App,
+) {
+    let settings = SemanticIndexSettings::get_global(cx);
+    let provider = settings.enabled.then_some(settings.provider);
+    if provider == *current_provider {
+        return;
+    }
+    *current_provider = provider;
+
+    if cx.has_global::<SemanticDb>() {
+        cx.remove_global::<SemanticDb>();
+    }
+    let Some(provider) = provider else {
+        return;
+    };
+
+    let http_client: Arc<dyn HttpClient> = http_client.clone();
+    let credentials_provider = <dyn CredentialsProvider>::global(cx);
+    cx.spawn(async move |cx| {
+        let embedding_provider: Arc<dyn EmbeddingProvider> = match provider {
+            EmbeddingProviderKind::Ollama => Arc::new(OllamaEmbeddingProvider::new(
+                http_client,
+                OllamaEmbeddingModel::NomicEmbedText,
+            )),
+            EmbeddingProviderKind::LmStudio => Arc::new(LmStudioEmbeddingProvider::new(
+                http_client,
+                LmStudioEmbeddingModel::NomicEmbedText,
+            )),
+            EmbeddingProviderKind::OpenAi => {
+                let api_key = if let Ok(api_key) = std::env::var(OPENAI_API_KEY_VAR) {
+                    api_key
+                } else {
+                    let (_, api_key) = credentials_provider
+                        .read_credentials(open_ai::OPEN_AI_API_URL, &cx)
+                        .await?
+                        .context("no OpenAI API key to compute embeddings with")?;
+                    String::from_utf8(api_key).context("invalid OpenAI API key")?
+                };
+                Arc::new(OpenAiEmbeddingProvider::new(
+                    http_client,
+                    OpenAiEmbeddingModel::TextEmbedding3Small,
+                    open_ai::OPEN_AI_API_URL.to_string(),
+                    api_key,
+                ))
+            }
+        };
+
+        // Embeddings of different providers can't be compared, so each gets its own database.
+        let db_path = paths::embeddings_dir().join(provider.database_name());
+        let semantic_db = SemanticDb::new(db_path, embedding_provider, cx).await?;
+        cx.update(|cx| {
+            let settings = SemanticIndexSettings::get_global(cx);
+            if settings.enabled && settings.provider == provider {
+                cx.set_global(semantic_db);
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 pub struct SemanticDb {
     embedding_provider: Arc<dyn EmbeddingProvider>,
     db_connection: Option<heed::Env>,
@@ -61,7 +148,9 @@ impl SemanticDb {
 
                     if cx.has_global::<SemanticDb>() {
                         cx.update_global::<SemanticDb, _>(|this, cx| {
-                            this.create_project_index(project, cx);
+                            if !this.project_indices.contains_key(&project.downgrade()) {
+                                this.create_project_index(project, cx);
+                            }
                         })
                     } else {
                         log::info!("No SemanticDb, skipping project index")
@@ -204,6 +293,24 @@ impl SemanticDb {
         Ok(loaded_results)
     }
 
+    /// The index of the project, if indexing is enabled. Projects opened before it was enabled
+    /// are indexed from their first use.
+    pub fn index_for_project(
+        project: &Entity<Project>,
+        cx: &mut App,
+    ) -> Option<Entity<ProjectIndex>> {
+        if !cx.has_global::<SemanticDb>() {
+            return None;
+        }
+        cx.update_global::<SemanticDb, _>(|this, cx| {
+            let project_index = match this.project_index(project.clone(), cx) {
+                Some(project_index) => project_index,
+                None => this.create_project_index(project.clone(), cx),
+            };
+            Some(project_index)
+        })
+    }
+
     pub fn project_index(
         &mut self,
         project: Entity<Project>,
@@ -0,0 +1,62 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug)]
+pub struct SemanticIndexSettings {
+    pub enabled: bool,
+    pub provider: EmbeddingProviderKind,
+}
+
+/// The service computing the embeddings of the indexed files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum EmbeddingProviderKind {
+    /// The `nomic-embed-text` model, served by a local Ollama.
+    #[default]
+    #[serde(rename = "ollama")]
+    Ollama,
+    /// The `nomic-embed-text` model, served by a local LM Studio.
+    #[serde(rename = "lmstudio")]
+    LmStudio,
+    /// OpenAI's `text-embedding-3-small`, using the OpenAI API key.
+    #[serde(rename = "openai")]
+    OpenAi,
+}
+
+impl EmbeddingProviderKind {
+    pub(crate) fn database_name(&self) -> &'static str {
+        match self {
+            Self::Ollama => "ollama",
+            Self::LmStudio => "lmstudio",
+            Self::OpenAi => "openai",
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+#[schemars(deny_unknown_fields)]
+pub struct SemanticIndexSettingsContent {
+    /// Whether to index the project's files as embeddings, for semantic search.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The service computing the embeddings.
+    ///
+    /// Default: ollama
+    pub provider: Option<EmbeddingProviderKind>,
+}
+
+impl Settings for SemanticIndexSettings {
+    const KEY: Option<&'static str> = Some("semantic_index");
+
+    type FileContent = SemanticIndexSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::App,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+
+    fn import_from_vscode(_vscode: &settings::VsCodeSettings, _current: &mut Self::FileContent) {}
+}
@@ -0,0 +1,315 @@
+use std::{ops::Range, path::PathBuf, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use editor::Editor;
+use gpui::{
+    App, AsyncApp, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task,
+    WeakEntity, Window, actions,
+};
+use language::Point;
+use picker::{Picker, PickerDelegate};
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::{LoadedSearchResult, ProjectIndex, SemanticDb, Status};
+
+actions!(semantic_index, [SemanticSearch, FindSimilarCode]);
+
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(250);
+const SEARCH_RESULT_LIMIT: usize = 32;
+
+/// The code whose neighbours are searched for by `FindSimilarCode`.
+struct SimilarTo {
+    text: String,
+    full_path: Option<PathBuf>,
+    rows: Range<u32>,
+}
+
+pub(crate) struct SemanticSearchModal {
+    picker: Entity<Picker<SemanticSearchDelegate>>,
+}
+
+impl SemanticSearchModal {
+    pub(crate) fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _cx: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, _: &SemanticSearch, window, cx| {
+            Self::toggle(workspace, None, window, cx);
+        });
+        workspace.register_action(|workspace, _: &FindSimilarCode, window, cx| {
+            let similar_to = workspace.active_item_as::<Editor>(cx).and_then(|editor| {
+                editor.update(cx, |editor, cx| {
+                    let selection = editor.selections.newest::<Point>(cx);
+                    if selection.is_empty() {
+                        return None;
+                    }
+                    let snapshot = editor.buffer().read(cx).snapshot(cx);
+                    Some(SimilarTo {
+                        text: snapshot.text_for_range(selection.range()).collect(),
+                        full_path: snapshot
+                            .file_at(selection.start)
+                            .map(|file| file.full_path(cx)),
+                        rows: selection.start.row..selection.end.row + 1,
+                    })
+                })
+            });
+            Self::toggle(workspace, similar_to, window, cx);
+        });
+    }
+
+    fn toggle(
+        workspace: &mut Workspace,
+        similar_to: Option<SimilarTo>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(project_index) = SemanticDb::index_for_project(workspace.project(), cx) else {
+            workspace.show_error(
+                &anyhow::anyhow!(
+                    "Semantic search needs the project to be indexed. \
+                    Enable it with the `semantic_index.enabled` setting."
+                ),
+                cx,
+            );
+            return;
+        };
+        let workspace_handle = cx.entity().downgrade();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            let delegate = SemanticSearchDelegate {
+                modal: cx.entity().downgrade(),
+                workspace: workspace_handle,
+                project_index,
+                similar_to,
+                matches: Vec::new(),
+                selected_index: 0,
+            };
+            let picker = cx.new(|cx| {
+                Picker::uniform_list(delegate, window, cx).max_height(Some(vh(0.75, window)))
+            });
+            Self { picker }
+        });
+    }
+}
+
+impl ModalView for SemanticSearchModal {}
+
+impl EventEmitter<DismissEvent> for SemanticSearchModal {}
+
+impl Focusable for SemanticSearchModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for SemanticSearchModal {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("SemanticSearchModal")
+            .w(rems(40.))
+            .child(self.picker.clone())
+    }
+}
+
+struct SemanticSearchDelegate {
+    modal: WeakEntity<SemanticSearchModal>,
+    workspace: WeakEntity<Workspace>,
+    project_index: Entity<ProjectIndex>,
+    similar_to: Option<SimilarTo>,
+    matches: Vec<LoadedSearchResult>,
+    selected_index: usize,
+}
+
+async fn search(
+    project_index: Entity<ProjectIndex>,
+    query: String,
+    cx: &AsyncApp,
+) -> Result<Vec<LoadedSearchResult>> {
+    let (results, fs) = project_index.read_with(cx, |project_index, cx| {
+        (
+            project_index.search(vec![query], SEARCH_RESULT_LIMIT, cx),
+            project_index.fs(),
+        )
+    })?;
+    SemanticDb::load_results(results.await?, &fs, cx).await
+}
+
+impl PickerDelegate for SemanticSearchDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        if self.similar_to.is_some() {
+            "Showing code similar to the selection. Type to search instead…".into()
+        } else {
+            "Search the project by meaning…".into()
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No matches".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let query = query.trim().to_string();
+        let (query, excluded) = match &self.similar_to {
+            Some(similar_to) if query.is_empty() => (
+                similar_to.text.clone(),
+                Some((similar_to.full_path.clone(), similar_to.rows.clone())),
+            ),
+            _ if query.is_empty() => {
+                self.matches.clear();
+                return Task::ready(());
+            }
+            _ => (query, None),
+        };
+
+        let project_index = self.project_index.clone();
+        cx.spawn_in(window, async move |picker, cx| {
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            let Some(mut matches) = search(project_index, query, cx).await.log_err() else {
+                return;
+            };
+            // The selection is always the closest match to itself.
+            if let Some((full_path, rows)) = excluded {
+                matches.retain(|result| {
+                    Some(&result.full_path) != full_path.as_ref()
+                        || *result.row_range.end() < rows.start
+                        || *result.row_range.start() >= rows.end
+                });
+            }
+            picker
+                .update(cx, |picker, cx| {
+                    picker.delegate.matches = matches;
+                    picker.delegate.selected_index = 0;
+                    cx.notify();
+                })
+                .ok();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(result) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let point = Point::new(*result.row_range.start(), 0);
+        let full_path = result.full_path.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let Some(project_path) = workspace
+                    .project()
+                    .read(cx)
+                    .find_project_path(&full_path, cx)
+                else {
+                    return;
+                };
+                let open_item = workspace.open_path(project_path, None, true, window, cx);
+                cx.spawn_in(window, async move |_, cx| {
+                    let item = open_item.await?;
+                    if let Some(editor) = item.downcast::<Editor>() {
+                        editor.update_in(cx, |editor, window, cx| {
+                            editor.go_to_singleton_buffer_point(point, window, cx);
+                        })?;
+                    }
+                    anyhow::Ok(())
+                })
+                .detach_and_log_err(cx);
+            })
+            .ok();
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_header(
+        &self,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        let status = match self.project_index.read(cx).status() {
+            Status::Idle => return None,
+            Status::Loading => "Loading the index…".to_string(),
+            Status::Scanning { remaining_count } => {
+                format!("Indexing, {remaining_count} files remaining…")
+            }
+        };
+        Some(
+            h_flex()
+                .px_3()
+                .pt_2()
+                .child(
+                    Label::new(status)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element(),
+        )
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let result = self.matches.get(ix)?;
+        let location = format!(
+            "{}:{}",
+            result.full_path.display(),
+            result.row_range.start() + 1
+        );
+        let first_line = result
+            .excerpt_content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .min_w_0()
+                        .child(Label::new(location).truncate())
+                        .child(
+                            Label::new(first_line)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        ),
+                ),
+        )
+    }
+}
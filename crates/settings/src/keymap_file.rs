@@ -1,9 +1,9 @@
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::{BTreeMap, HashMap, IndexMap};
 use fs::Fs;
 use gpui::{
     Action, ActionBuildError, App, InvalidKeystrokeError, KEYSTROKE_PARSE_EXPECTED_MESSAGE,
-    KeyBinding, KeyBindingContextPredicate, NoAction, SharedString,
+    KeyBinding, KeyBindingContextPredicate, KeyBindingMetaIndex, Keystroke, NoAction, SharedString,
 };
 use schemars::{
     JsonSchema,
@@ -12,13 +12,16 @@ use schemars::{
 };
 use serde::Deserialize;
 use serde_json::Value;
-use std::{any::TypeId, fmt::Write, rc::Rc, sync::Arc, sync::LazyLock};
+use std::{any::TypeId, fmt::Write, ops::Range, rc::Rc, sync::Arc, sync::LazyLock};
 use util::{
     asset_str,
     markdown::{MarkdownEscaped, MarkdownInlineCode, MarkdownString},
 };
 
-use crate::{SettingsAssets, settings_store::parse_json_with_comments};
+use crate::{
+    SettingsAssets, SettingsCondition,
+    settings_store::{parse_json_with_comments, to_pretty_json},
+};
 
 pub trait KeyBindingValidator: Send + Sync {
     fn action_type_id(&self) -> TypeId;
@@ -69,6 +72,12 @@ pub struct KeymapSection {
     /// on macOS. See the documentation for more details.
     #[serde(default)]
     use_key_equivalents: bool,
+    /// Restricts this section to particular machines, for example `{"os": "macos"}` or
+    /// `{"hostname": ["laptop", "desktop"]}`. Every field that is set has to match for the
+    /// bindings to be used, and a field matches when any of its values does.
+    #[serde(default)]
+    #[schemars(with = "Option<SettingsCondition>")]
+    when: Option<Value>,
     /// This keymap section's bindings, as a JSON object mapping keystrokes to actions. The
     /// keystrokes key is a string representing a sequence of keystrokes to type, where the
     /// keystrokes are separated by whitespace. Each keystroke is a sequence of modifiers (`ctrl`,
@@ -221,10 +230,28 @@ impl KeymapFile {
         for KeymapSection {
             context,
             use_key_equivalents,
+            when,
             bindings,
             unrecognized_fields,
         } in keymap_file.0.iter()
         {
+            if let Some(when) = when {
+                match serde_json::from_value::<SettingsCondition>(when.clone()) {
+                    Ok(condition) => {
+                        if !condition.is_satisfied() {
+                            continue;
+                        }
+                    }
+                    Err(err) => {
+                        errors.push((
+                            context,
+                            format!(" Parse error in section `when` field: {}", err),
+                        ));
+                        continue;
+                    }
+                }
+            }
+
             let context_predicate: Option<Rc<KeyBindingContextPredicate>> = if context.is_empty() {
                 None
             } else {
@@ -387,7 +414,13 @@ impl KeymapFile {
             },
         };
 
-        let key_binding = match KeyBinding::load(keystrokes, action, context, key_equivalents) {
+        let key_binding = match KeyBinding::load(
+            keystrokes,
+            action,
+            context,
+            key_equivalents,
+            action_input_string.map(SharedString::from),
+        ) {
             Ok(key_binding) => key_binding,
             Err(InvalidKeystrokeError { keystroke }) => {
                 return Err(format!(
@@ -606,11 +639,307 @@ impl KeymapFile {
             }
         }
     }
+
+    /// Applies `operation` to the contents of a user keymap file, preserving the comments and
+    /// formatting of the parts of the file that are not affected.
+    pub fn update_keybinding<'a>(
+        operation: KeybindUpdateOperation<'a>,
+        mut keymap_contents: String,
+        tab_size: usize,
+    ) -> Result<String> {
+        let (target, source) = match operation {
+            KeybindUpdateOperation::Add(target) => (target, None),
+            KeybindUpdateOperation::Replace {
+                source,
+                target,
+                target_keybind_source,
+            } => {
+                // Bindings from the user's keymap are edited in place, as long as they stay in the
+                // same context.
+                if target_keybind_source == KeybindSource::User
+                    && contexts_equal(source.context, target.context)?
+                {
+                    if let Some(range) = find_binding_in_keymap(&keymap_contents, &source)? {
+                        let replacement = target.binding_json()?;
+                        keymap_contents.replace_range(range, &replacement);
+                        return Ok(keymap_contents);
+                    }
+                }
+                (target, Some((source, target_keybind_source)))
+            }
+        };
+
+        let mut bindings = serde_json::Map::new();
+        bindings.insert(target.keystrokes_unparsed(), target.action_value()?);
+        if let Some((source, source_keybind_source)) = source {
+            // Built-in bindings can't be removed, so the old keystrokes are explicitly unbound.
+            let source_keystrokes = source.keystrokes_unparsed();
+            if source_keybind_source != KeybindSource::User
+                && source_keystrokes != target.keystrokes_unparsed()
+                && contexts_equal(source.context, target.context)?
+            {
+                bindings.insert(source_keystrokes, Value::Null);
+            }
+        }
+
+        let mut section = serde_json::Map::new();
+        if let Some(context) = target.context.filter(|context| !context.is_empty()) {
+            section.insert("context".into(), Value::String(context.to_string()));
+        }
+        section.insert("bindings".into(), Value::Object(bindings));
+
+        append_section_to_keymap(&mut keymap_contents, &Value::Object(section), tab_size)?;
+        Ok(keymap_contents)
+    }
+}
+
+/// Where a loaded key binding came from, stored in [`KeyBinding::meta`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeybindSource {
+    User,
+    Default,
+    Base,
+    Vim,
+}
+
+impl KeybindSource {
+    const BASE: KeyBindingMetaIndex = KeyBindingMetaIndex(0);
+    const DEFAULT: KeyBindingMetaIndex = KeyBindingMetaIndex(1);
+    const VIM: KeyBindingMetaIndex = KeyBindingMetaIndex(2);
+    const USER: KeyBindingMetaIndex = KeyBindingMetaIndex(3);
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeybindSource::User => "User",
+            KeybindSource::Default => "Default",
+            KeybindSource::Base => "Base",
+            KeybindSource::Vim => "Vim",
+        }
+    }
+
+    pub fn meta(&self) -> KeyBindingMetaIndex {
+        match self {
+            KeybindSource::User => Self::USER,
+            KeybindSource::Default => Self::DEFAULT,
+            KeybindSource::Base => Self::BASE,
+            KeybindSource::Vim => Self::VIM,
+        }
+    }
+
+    pub fn from_meta(index: KeyBindingMetaIndex) -> Self {
+        match index {
+            Self::USER => KeybindSource::User,
+            Self::BASE => KeybindSource::Base,
+            Self::VIM => KeybindSource::Vim,
+            _ => KeybindSource::Default,
+        }
+    }
+}
+
+pub enum KeybindUpdateOperation<'a> {
+    /// Rebinds `source`, which was loaded from `target_keybind_source`, to `target`.
+    Replace {
+        source: KeybindUpdateTarget<'a>,
+        target: KeybindUpdateTarget<'a>,
+        target_keybind_source: KeybindSource,
+    },
+    Add(KeybindUpdateTarget<'a>),
+}
+
+pub struct KeybindUpdateTarget<'a> {
+    pub context: Option<&'a str>,
+    pub keystrokes: &'a [Keystroke],
+    pub action_name: &'a str,
+    /// The action input as a JSON string, see [`KeyBinding::action_input`].
+    pub action_input: Option<&'a str>,
+}
+
+impl KeybindUpdateTarget<'_> {
+    fn keystrokes_unparsed(&self) -> String {
+        self.keystrokes
+            .iter()
+            .map(|keystroke| keystroke.unparse())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn action_value(&self) -> Result<Value> {
+        let action_name = Value::String(self.action_name.to_string());
+        let Some(action_input) = self.action_input else {
+            return Ok(action_name);
+        };
+        let action_input = serde_json_lenient::from_str(action_input)
+            .with_context(|| format!("Invalid input for action {}", self.action_name))?;
+        Ok(Value::Array(vec![action_name, action_input]))
+    }
+
+    fn binding_json(&self) -> Result<String> {
+        Ok(format!(
+            "{}: {}",
+            serde_json::to_string(&self.keystrokes_unparsed())?,
+            serde_json::to_string(&self.action_value()?)?
+        ))
+    }
+}
+
+fn parse_context(context: Option<&str>) -> Result<Option<KeyBindingContextPredicate>> {
+    context
+        .filter(|context| !context.is_empty())
+        .map(KeyBindingContextPredicate::parse)
+        .transpose()
+}
+
+fn contexts_equal(a: Option<&str>, b: Option<&str>) -> Result<bool> {
+    Ok(parse_context(a)? == parse_context(b)?)
+}
+
+fn normalize_keystrokes(keystrokes: &str) -> Option<String> {
+    keystrokes
+        .split_whitespace()
+        .map(|keystroke| Keystroke::parse(keystroke).ok().map(|k| k.unparse()))
+        .collect::<Option<Vec<_>>>()
+        .map(|keystrokes| keystrokes.join(" "))
+}
+
+fn parse_keymap_syntax_tree(text: &str) -> Result<tree_sitter::Tree> {
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&tree_sitter_json::LANGUAGE.into())?;
+    parser.parse(text, None).context("Failed to parse keymap")
+}
+
+fn top_level_array(root: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    let mut cursor = root.walk();
+    root.named_children(&mut cursor)
+        .find(|node| node.kind() == "array")
+}
+
+fn json_string(text: &str, node: tree_sitter::Node) -> Option<String> {
+    if node.kind() != "string" {
+        return None;
+    }
+    serde_json_lenient::from_str(text.get(node.byte_range())?).ok()
+}
+
+/// Returns the range of the `"keystrokes": action` pair for `target` in the keymap text.
+fn find_binding_in_keymap(
+    text: &str,
+    target: &KeybindUpdateTarget,
+) -> Result<Option<Range<usize>>> {
+    let tree = parse_keymap_syntax_tree(text)?;
+    let Some(array) = top_level_array(tree.root_node()) else {
+        return Ok(None);
+    };
+    let target_context = parse_context(target.context)?;
+    let target_keystrokes = target.keystrokes_unparsed();
+    let target_action = target.action_value()?;
+
+    let mut sections = array.walk();
+    for section in array.named_children(&mut sections) {
+        if section.kind() != "object" {
+            continue;
+        }
+
+        let mut context = None;
+        let mut bindings = None;
+        let mut pairs = section.walk();
+        for pair in section.named_children(&mut pairs) {
+            let (Some(key), Some(value)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            match json_string(text, key).as_deref() {
+                Some("context") => context = json_string(text, value),
+                Some("bindings") if value.kind() == "object" => bindings = Some(value),
+                _ => {}
+            }
+        }
+
+        let Some(bindings) = bindings else {
+            continue;
+        };
+        // Sections with invalid contexts are never matched, as they aren't loaded.
+        match parse_context(context.as_deref()) {
+            Ok(context) if context == target_context => {}
+            _ => continue,
+        }
+
+        let mut pairs = bindings.walk();
+        for pair in bindings.named_children(&mut pairs) {
+            let (Some(key), Some(value)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            let keystrokes = json_string(text, key).and_then(|key| normalize_keystrokes(&key));
+            if keystrokes.as_ref() != Some(&target_keystrokes) {
+                continue;
+            }
+            let action = text
+                .get(value.byte_range())
+                .and_then(|value| serde_json_lenient::from_str::<Value>(value).ok());
+            if action.as_ref() == Some(&target_action) {
+                return Ok(Some(pair.byte_range()));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn append_section_to_keymap(text: &mut String, section: &Value, tab_size: usize) -> Result<()> {
+    let indent = " ".repeat(tab_size);
+    let section_text = to_pretty_json(section, tab_size, tab_size);
+
+    if text.trim().is_empty() {
+        *text = format!("[\n{indent}{section_text}\n]\n");
+        return Ok(());
+    }
+
+    let tree = parse_keymap_syntax_tree(text)?;
+    let array =
+        top_level_array(tree.root_node()).context("Keymap file must contain a top-level array")?;
+    let close_bracket = array.end_byte().saturating_sub(1);
+    anyhow::ensure!(
+        text.get(close_bracket..array.end_byte()) == Some("]"),
+        "Keymap file's top-level array is not closed"
+    );
+
+    let mut cursor = array.walk();
+    let last_section = array
+        .named_children(&mut cursor)
+        .filter(|node| node.kind() == "object")
+        .last();
+    match last_section {
+        Some(last_section) => {
+            let after_last_section = &text[last_section.end_byte()..close_bracket];
+            let trimmed = after_last_section.trim_start();
+            if trimmed.starts_with(',') {
+                // Keep the existing trailing comma between the sections.
+                let comma = last_section.end_byte() + after_last_section.len() - trimmed.len();
+                text.insert_str(comma + 1, &format!("\n{indent}{section_text}"));
+            } else {
+                text.insert_str(
+                    last_section.end_byte(),
+                    &format!(",\n{indent}{section_text}"),
+                );
+            }
+        }
+        None => {
+            text.insert_str(array.start_byte() + 1, &format!("\n{indent}{section_text}"));
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::KeymapFile;
+    use gpui::Keystroke;
+    use pretty_assertions::assert_eq;
+
+    use crate::{KeybindSource, KeybindUpdateOperation, KeybindUpdateTarget, KeymapFile};
 
     #[test]
     fn can_deserialize_keymap_with_trailing_comma() {
@@ -626,4 +955,149 @@ mod tests {
         };
         KeymapFile::parse(json).unwrap();
     }
+
+    fn keystrokes(keystrokes: &str) -> Vec<Keystroke> {
+        keystrokes
+            .split_whitespace()
+            .map(|keystroke| Keystroke::parse(keystroke).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_update_user_keybinding_in_place() {
+        let keymap = indoc::indoc! {r#"
+            [
+              {
+                "context": "Editor",
+                "bindings": {
+                  // Select everything
+                  "ctrl-a": "editor::SelectAll",
+                  "ctrl-x": ["editor::Cut", { "whole_line": true }]
+                }
+              }
+            ]
+        "#};
+        let old = keystrokes("ctrl-x");
+        let new = keystrokes("ctrl-k ctrl-x");
+        let result = KeymapFile::update_keybinding(
+            KeybindUpdateOperation::Replace {
+                source: KeybindUpdateTarget {
+                    context: Some("Editor"),
+                    keystrokes: &old,
+                    action_name: "editor::Cut",
+                    action_input: Some(r#"{"whole_line":true}"#),
+                },
+                target: KeybindUpdateTarget {
+                    context: Some("Editor"),
+                    keystrokes: &new,
+                    action_name: "editor::Cut",
+                    action_input: Some(r#"{"whole_line":true}"#),
+                },
+                target_keybind_source: KeybindSource::User,
+            },
+            keymap.to_string(),
+            2,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            indoc::indoc! {r#"
+                [
+                  {
+                    "context": "Editor",
+                    "bindings": {
+                      // Select everything
+                      "ctrl-a": "editor::SelectAll",
+                      "ctrl-k ctrl-x": ["editor::Cut",{"whole_line":true}]
+                    }
+                  }
+                ]
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_update_default_keybinding_appends_section() {
+        let keymap = indoc::indoc! {r#"
+            [
+              {
+                "context": "Workspace",
+                "bindings": {
+                  // "shift shift": "file_finder::Toggle"
+                }
+              },
+            ]
+        "#};
+        let old = keystrokes("ctrl-a");
+        let new = keystrokes("ctrl-b");
+        let result = KeymapFile::update_keybinding(
+            KeybindUpdateOperation::Replace {
+                source: KeybindUpdateTarget {
+                    context: Some("Editor"),
+                    keystrokes: &old,
+                    action_name: "editor::SelectAll",
+                    action_input: None,
+                },
+                target: KeybindUpdateTarget {
+                    context: Some("Editor"),
+                    keystrokes: &new,
+                    action_name: "editor::SelectAll",
+                    action_input: None,
+                },
+                target_keybind_source: KeybindSource::Default,
+            },
+            keymap.to_string(),
+            2,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            indoc::indoc! {r#"
+                [
+                  {
+                    "context": "Workspace",
+                    "bindings": {
+                      // "shift shift": "file_finder::Toggle"
+                    }
+                  },
+                  {
+                    "context": "Editor",
+                    "bindings": {
+                      "ctrl-b": "editor::SelectAll",
+                      "ctrl-a": null
+                    }
+                  }
+                ]
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_add_keybinding_to_empty_keymap() {
+        let new = keystrokes("alt-up");
+        let add = |keymap: &str| {
+            KeymapFile::update_keybinding(
+                KeybindUpdateOperation::Add(KeybindUpdateTarget {
+                    context: None,
+                    keystrokes: &new,
+                    action_name: "editor::MoveUp",
+                    action_input: None,
+                }),
+                keymap.to_string(),
+                2,
+            )
+            .unwrap()
+        };
+        let expected = indoc::indoc! {r#"
+            [
+              {
+                "bindings": {
+                  "alt-up": "editor::MoveUp"
+                }
+              }
+            ]
+        "#};
+        assert_eq!(add(""), expected);
+        assert_eq!(add("[\n]\n"), expected);
+    }
 }
@@ -2,6 +2,7 @@ mod editable_setting_control;
 mod json_schema;
 mod key_equivalents;
 mod keymap_file;
+mod settings_condition;
 mod settings_file;
 mod settings_store;
 mod vscode_import;
@@ -15,8 +16,10 @@ pub use editable_setting_control::*;
 pub use json_schema::*;
 pub use key_equivalents::*;
 pub use keymap_file::{
-    KeyBindingValidator, KeyBindingValidatorRegistration, KeymapFile, KeymapFileLoadResult,
+    KeyBindingValidator, KeyBindingValidatorRegistration, KeybindSource, KeybindUpdateOperation,
+    KeybindUpdateTarget, KeymapFile, KeymapFileLoadResult,
 };
+pub use settings_condition::{ConditionValues, SettingsCondition};
 pub use settings_file::*;
 pub use settings_store::{
     InvalidSettingsError, LocalSettingsKind, Settings, SettingsLocation, SettingsSources,
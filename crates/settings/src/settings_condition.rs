@@ -0,0 +1,189 @@
+use anyhow::{Context as _, Result};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::LazyLock;
+use util::merge_non_null_json_value_into;
+
+/// The key of the top-level array of conditional sections in a settings file.
+pub(crate) const CONDITIONAL_SETTINGS_KEY: &str = "conditional";
+
+/// Restricts a section of a settings or keymap file to particular machines. Every field that is
+/// set has to match for the section to apply, and a field matches when any of its values does.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SettingsCondition {
+    /// The operating system, one of `macos`, `linux`, `windows` or `freebsd`.
+    #[serde(default)]
+    pub os: Option<ConditionValues>,
+    /// The CPU architecture, such as `x86_64` or `aarch64`.
+    #[serde(default)]
+    pub arch: Option<ConditionValues>,
+    /// The hostname of the machine.
+    #[serde(default)]
+    pub hostname: Option<ConditionValues>,
+}
+
+/// A single value, or a list of values of which any one has to match.
+#[derive(Debug, Clone, PartialEq, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ConditionValues {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl ConditionValues {
+    fn matches(&self, actual: Option<&str>) -> bool {
+        let Some(actual) = actual else {
+            return false;
+        };
+        match self {
+            Self::One(value) => value.eq_ignore_ascii_case(actual),
+            Self::Any(values) => values
+                .iter()
+                .any(|value| value.eq_ignore_ascii_case(actual)),
+        }
+    }
+}
+
+/// The properties of this machine that conditions are checked against.
+struct Machine {
+    os: &'static str,
+    arch: &'static str,
+    hostname: Option<String>,
+}
+
+static CURRENT_MACHINE: LazyLock<Machine> = LazyLock::new(|| Machine {
+    os: std::env::consts::OS,
+    arch: std::env::consts::ARCH,
+    hostname: sysinfo::System::host_name(),
+});
+
+impl SettingsCondition {
+    /// Whether the condition holds on the machine Zed is running on.
+    pub fn is_satisfied(&self) -> bool {
+        self.is_satisfied_on(&CURRENT_MACHINE)
+    }
+
+    fn is_satisfied_on(&self, machine: &Machine) -> bool {
+        self.os
+            .as_ref()
+            .is_none_or(|os| os.matches(Some(machine.os)))
+            && self
+                .arch
+                .as_ref()
+                .is_none_or(|arch| arch.matches(Some(machine.arch)))
+            && self
+                .hostname
+                .as_ref()
+                .is_none_or(|hostname| hostname.matches(machine.hostname.as_deref()))
+    }
+}
+
+/// A section of the `conditional` array in a settings file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConditionalSettings {
+    when: SettingsCondition,
+    #[serde(default)]
+    settings: Value,
+}
+
+/// Removes the `conditional` sections from a settings file's contents, merging the settings of
+/// those whose conditions hold on this machine over the rest of the file, in order.
+pub(crate) fn resolve_conditional_settings(settings: &mut Value) -> Result<()> {
+    resolve_conditional_settings_on(settings, &CURRENT_MACHINE)
+}
+
+fn resolve_conditional_settings_on(settings: &mut Value, machine: &Machine) -> Result<()> {
+    let Some(conditional) = settings
+        .as_object_mut()
+        .and_then(|settings| settings.remove(CONDITIONAL_SETTINGS_KEY))
+    else {
+        return Ok(());
+    };
+
+    let sections = serde_json::from_value::<Vec<ConditionalSettings>>(conditional)
+        .with_context(|| format!("invalid `{CONDITIONAL_SETTINGS_KEY}` settings"))?;
+    for section in sections {
+        anyhow::ensure!(
+            section.settings.is_object(),
+            "conditional settings must be an object"
+        );
+        if section.when.is_satisfied_on(machine) {
+            merge_non_null_json_value_into(section.settings, settings);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn machine() -> Machine {
+        Machine {
+            os: "linux",
+            arch: "x86_64",
+            hostname: Some("workstation".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_condition_matching() {
+        let condition = |value: Value| serde_json::from_value::<SettingsCondition>(value).unwrap();
+        let machine = machine();
+
+        assert!(condition(json!({})).is_satisfied_on(&machine));
+        assert!(condition(json!({ "os": "linux" })).is_satisfied_on(&machine));
+        assert!(condition(json!({ "os": ["macos", "linux"] })).is_satisfied_on(&machine));
+        assert!(!condition(json!({ "os": "macos" })).is_satisfied_on(&machine));
+        assert!(
+            condition(json!({ "os": "linux", "hostname": "Workstation" }))
+                .is_satisfied_on(&machine)
+        );
+        assert!(!condition(json!({ "os": "linux", "arch": "aarch64" })).is_satisfied_on(&machine));
+        assert!(
+            !condition(json!({ "hostname": "laptop" })).is_satisfied_on(&Machine {
+                hostname: None,
+                ..machine
+            })
+        );
+        assert!(serde_json::from_value::<SettingsCondition>(json!({ "distro": "arch" })).is_err());
+    }
+
+    #[test]
+    fn test_resolve_conditional_settings() {
+        let mut settings = json!({
+            "tab_size": 4,
+            "terminal": { "font_size": 12 },
+            "conditional": [
+                {
+                    "when": { "os": "linux" },
+                    "settings": { "terminal": { "font_size": 14 }, "vim_mode": true }
+                },
+                {
+                    "when": { "os": "macos" },
+                    "settings": { "tab_size": 8 }
+                },
+                {
+                    "when": { "hostname": "workstation" },
+                    "settings": { "terminal": { "font_size": 16 } }
+                }
+            ]
+        });
+        resolve_conditional_settings_on(&mut settings, &machine()).unwrap();
+        assert_eq!(
+            settings,
+            json!({
+                "tab_size": 4,
+                "terminal": { "font_size": 16 },
+                "vim_mode": true
+            })
+        );
+
+        let mut settings = json!({ "conditional": [{ "settings": { "tab_size": 8 } }] });
+        assert!(resolve_conditional_settings_on(&mut settings, &machine()).is_err());
+    }
+}
@@ -25,6 +25,8 @@ pub fn test_settings() -> String {
             "buffer_font_size": 14,
             "buffer_font_fallback": [],
             "theme": EMPTY_THEME_NAME,
+            "workspace_trust": { "enabled": false },
+            "restore_on_file_reopen_max_files": 0,
         }),
         &mut value,
     );
@@ -40,6 +42,8 @@ pub fn test_settings() -> String {
             "buffer_font_size": 14,
             "buffer_font_fallback": [],
             "theme": EMPTY_THEME_NAME,
+            "workspace_trust": { "enabled": false },
+            "restore_on_file_reopen_max_files": 0,
         }),
         &mut value,
     );
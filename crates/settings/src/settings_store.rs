@@ -26,7 +26,12 @@ use util::{ResultExt as _, merge_non_null_json_value_into};
 
 pub type EditorconfigProperties = ec4rs::Properties;
 
-use crate::{SettingsJsonSchemaParams, VsCodeSettings, WorktreeId};
+use crate::{
+    SettingsJsonSchemaParams, VsCodeSettings, WorktreeId,
+    settings_condition::{
+        CONDITIONAL_SETTINGS_KEY, SettingsCondition, resolve_conditional_settings,
+    },
+};
 
 /// A value that can be defined as a user setting.
 ///
@@ -128,6 +133,8 @@ pub struct SettingsSources<'a, T> {
     pub user: Option<&'a T>,
     /// The user settings for the current release channel.
     pub release_channel: Option<&'a T>,
+    /// The user settings for the active settings profile.
+    pub profile: Option<&'a T>,
     /// The server's settings.
     pub server: Option<&'a T>,
     /// The project settings, ordered from least specific to most specific.
@@ -147,6 +154,7 @@ impl<'a, T: Serialize> SettingsSources<'a, T> {
             .chain(self.extensions)
             .chain(self.user)
             .chain(self.release_channel)
+            .chain(self.profile)
             .chain(self.server)
             .chain(self.project.iter().copied())
     }
@@ -188,6 +196,7 @@ pub struct SettingsStore {
     raw_server_settings: Option<Value>,
     raw_extension_settings: Value,
     raw_local_settings: BTreeMap<(WorktreeId, Arc<Path>), Value>,
+    active_profile: Option<String>,
     raw_editorconfig_settings: BTreeMap<(WorktreeId, Arc<Path>), (String, Option<Editorconfig>)>,
     tab_size_callback: Option<(
         TypeId,
@@ -282,6 +291,7 @@ impl SettingsStore {
             raw_server_settings: None,
             raw_extension_settings: serde_json::json!({}),
             raw_local_settings: Default::default(),
+            active_profile: None,
             raw_editorconfig_settings: BTreeMap::default(),
             tab_size_callback: Default::default(),
             setting_file_updates_tx,
@@ -332,6 +342,12 @@ impl SettingsStore {
                     .log_err();
             }
 
+            let profile_value = self.raw_profile_settings().and_then(|profile_settings| {
+                setting_value
+                    .deserialize_setting(profile_settings)
+                    .log_err()
+            });
+
             let server_value = self
                 .raw_server_settings
                 .as_ref()
@@ -351,6 +367,7 @@ impl SettingsStore {
                         extensions: extension_value.as_ref(),
                         user: user_value.as_ref(),
                         release_channel: release_channel_value.as_ref(),
+                        profile: profile_value.as_ref(),
                         server: server_value.as_ref(),
                         project: &[],
                     },
@@ -418,6 +435,47 @@ impl SettingsStore {
         self.raw_global_settings.as_ref()
     }
 
+    /// Access the raw JSON value of the default settings.
+    pub fn raw_default_settings(&self) -> &Value {
+        &self.raw_default_settings
+    }
+
+    /// The names of the settings profiles defined in the user's settings, in the order they
+    /// appear in the file.
+    pub fn profile_names(&self) -> Vec<String> {
+        self.raw_user_settings
+            .get("profiles")
+            .and_then(Value::as_object)
+            .map(|profiles| profiles.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The name of the settings profile whose settings are currently layered over the user settings.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.active_profile.as_deref()
+    }
+
+    /// Activates the settings profile with the given name, or deactivates the current one when
+    /// `profile` is `None`.
+    pub fn set_active_profile(&mut self, profile: Option<String>, cx: &mut App) -> Result<()> {
+        if self.active_profile == profile {
+            return Ok(());
+        }
+        self.active_profile = profile;
+        self.recompute_values(None, cx)?;
+        Ok(())
+    }
+
+    fn raw_profile_settings(&self) -> Option<&Value> {
+        let profile = self.active_profile.as_deref()?;
+        self.raw_user_settings.get("profiles")?.get(profile)
+    }
+
+    /// Access the raw JSON value of the local settings in the given directory of a worktree.
+    pub fn raw_local_settings(&self, root_id: WorktreeId, directory: &Path) -> Option<&Value> {
+        self.raw_local_settings.get(&(root_id, directory.into()))
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn test(cx: &mut App) -> Self {
         let mut this = Self::new(cx);
@@ -559,6 +617,27 @@ impl SettingsStore {
         new_text
     }
 
+    /// Sets the value at `key_path` in a JSON file, or removes it when `new_value` is `None`,
+    /// returning the new text for that JSON file.
+    pub fn new_text_for_key_path(
+        &self,
+        mut old_text: String,
+        key_path: &[&str],
+        new_value: Option<&Value>,
+    ) -> String {
+        let (range, replacement) = match new_value {
+            Some(new_value) => {
+                replace_value_in_json_text(&old_text, key_path, self.json_tab_size(), new_value)
+            }
+            None => match remove_value_in_json_text(&old_text, key_path) {
+                Some(range) => (range, String::new()),
+                None => return old_text,
+            },
+        };
+        old_text.replace_range(range, &replacement);
+        old_text
+    }
+
     pub fn get_vscode_edits(&self, mut old_text: String, vscode: &VsCodeSettings) -> String {
         let mut new_text = old_text.clone();
         let mut edits: Vec<(Range<usize>, String)> = Vec::new();
@@ -666,13 +745,14 @@ impl SettingsStore {
         user_settings_content: &str,
         cx: &mut App,
     ) -> Result<Value> {
-        let settings: Value = if user_settings_content.is_empty() {
+        let mut settings: Value = if user_settings_content.is_empty() {
             parse_json_with_comments("{}")?
         } else {
             parse_json_with_comments(user_settings_content)?
         };
 
         anyhow::ensure!(settings.is_object(), "settings must be an object");
+        resolve_conditional_settings(&mut settings)?;
         self.raw_user_settings = settings.clone();
         self.recompute_values(None, cx)?;
         Ok(settings)
@@ -684,13 +764,14 @@ impl SettingsStore {
         global_settings_content: &str,
         cx: &mut App,
     ) -> Result<Value> {
-        let settings: Value = if global_settings_content.is_empty() {
+        let mut settings: Value = if global_settings_content.is_empty() {
             parse_json_with_comments("{}")?
         } else {
             parse_json_with_comments(global_settings_content)?
         };
 
         anyhow::ensure!(settings.is_object(), "settings must be an object");
+        resolve_conditional_settings(&mut settings)?;
         self.raw_global_settings = Some(settings.clone());
         self.recompute_values(None, cx)?;
         Ok(settings)
@@ -759,12 +840,14 @@ impl SettingsStore {
                     .remove(&(root_id, directory_path.clone()));
             }
             (LocalSettingsKind::Settings, Some(settings_contents)) => {
-                let new_settings =
-                    parse_json_with_comments::<Value>(settings_contents).map_err(|e| {
-                        InvalidSettingsError::LocalSettings {
-                            path: directory_path.join(local_settings_file_relative_path()),
-                            message: e.to_string(),
-                        }
+                let new_settings = parse_json_with_comments::<Value>(settings_contents)
+                    .and_then(|mut settings| {
+                        resolve_conditional_settings(&mut settings)?;
+                        Ok(settings)
+                    })
+                    .map_err(|e| InvalidSettingsError::LocalSettings {
+                        path: directory_path.join(local_settings_file_relative_path()),
+                        message: e.to_string(),
                     })?;
                 match self
                     .raw_local_settings
@@ -879,7 +962,7 @@ impl SettingsStore {
     pub fn json_schema(&self, schema_params: &SettingsJsonSchemaParams, cx: &App) -> Value {
         use schemars::{
             r#gen::SchemaSettings,
-            schema::{Schema, SchemaObject},
+            schema::{ArrayValidation, InstanceType, ObjectValidation, Schema, SchemaObject},
         };
 
         let settings = SchemaSettings::draft07().with(|settings| {
@@ -967,6 +1050,54 @@ impl SettingsStore {
             }
         }
 
+        let conditional_settings_schema = combined_schema.schema.clone();
+        let condition_schema = generator.root_schema_for::<SettingsCondition>();
+        combined_schema
+            .definitions
+            .extend(condition_schema.definitions);
+        combined_schema.schema.object().properties.insert(
+            CONDITIONAL_SETTINGS_KEY.to_string(),
+            SchemaObject {
+                instance_type: Some(InstanceType::Array.into()),
+                array: Some(Box::new(ArrayValidation {
+                    items: Some(
+                        Schema::Object(SchemaObject {
+                            instance_type: Some(InstanceType::Object.into()),
+                            object: Some(Box::new(ObjectValidation {
+                                properties: [
+                                    ("when".to_string(), condition_schema.schema.into()),
+                                    ("settings".to_string(), conditional_settings_schema.into()),
+                                ]
+                                .into_iter()
+                                .collect(),
+                                required: ["when".to_string()].into_iter().collect(),
+                                ..Default::default()
+                            })),
+                            ..Default::default()
+                        })
+                        .into(),
+                    ),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            }
+            .into(),
+        );
+
+        let profile_schema = combined_schema.schema.clone();
+        combined_schema.schema.object().properties.insert(
+            "profiles".to_string(),
+            SchemaObject {
+                instance_type: Some(InstanceType::Object.into()),
+                object: Some(Box::new(ObjectValidation {
+                    additional_properties: Some(Box::new(profile_schema.into())),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            }
+            .into(),
+        );
+
         for release_stage in ["dev", "nightly", "stable", "preview"] {
             let schema = combined_schema.schema.clone();
             combined_schema
@@ -1030,6 +1161,12 @@ impl SettingsStore {
                 }
             }
 
+            let profile_settings = self.raw_profile_settings().and_then(|profile_settings| {
+                setting_value
+                    .deserialize_setting(profile_settings)
+                    .log_err()
+            });
+
             // If the global settings file changed, reload the global value for the field.
             if changed_local_path.is_none() {
                 if let Some(value) = setting_value
@@ -1040,6 +1177,7 @@ impl SettingsStore {
                             extensions: extension_settings.as_ref(),
                             user: user_settings.as_ref(),
                             release_channel: release_channel_settings.as_ref(),
+                            profile: profile_settings.as_ref(),
                             server: server_settings.as_ref(),
                             project: &[],
                         },
@@ -1092,6 +1230,7 @@ impl SettingsStore {
                                     extensions: extension_settings.as_ref(),
                                     user: user_settings.as_ref(),
                                     release_channel: release_channel_settings.as_ref(),
+                                    profile: profile_settings.as_ref(),
                                     server: server_settings.as_ref(),
                                     project: &project_settings_stack.iter().collect::<Vec<_>>(),
                                 },
@@ -1217,6 +1356,9 @@ impl<T: Settings> AnySettingValue for SettingValue<T> {
                 release_channel: values
                     .release_channel
                     .map(|value| value.0.downcast_ref::<T::FileContent>().unwrap()),
+                profile: values
+                    .profile
+                    .map(|value| value.0.downcast_ref::<T::FileContent>().unwrap()),
                 server: values
                     .server
                     .map(|value| value.0.downcast_ref::<T::FileContent>().unwrap()),
@@ -1515,7 +1657,78 @@ fn replace_value_in_json_text(
     }
 }
 
-fn to_pretty_json(value: &impl Serialize, indent_size: usize, indent_prefix_len: usize) -> String {
+/// Returns the range to delete in order to remove the value at `key_path`, including its key
+/// and separating comma.
+fn remove_value_in_json_text(text: &str, key_path: &[&str]) -> Option<Range<usize>> {
+    fn sibling<'a>(node: tree_sitter::Node<'a>, forward: bool) -> Option<tree_sitter::Node<'a>> {
+        let mut sibling = if forward {
+            node.next_sibling()
+        } else {
+            node.prev_sibling()
+        };
+        while let Some(node) = sibling {
+            if node.kind() != "comment" {
+                return Some(node);
+            }
+            sibling = if forward {
+                node.next_sibling()
+            } else {
+                node.prev_sibling()
+            };
+        }
+        None
+    }
+
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_json::LANGUAGE.into())
+        .ok()?;
+    let syntax_tree = parser.parse(text, None)?;
+
+    let mut cursor = syntax_tree.walk();
+    let mut object = syntax_tree
+        .root_node()
+        .named_children(&mut cursor)
+        .find(|node| node.kind() == "object")?;
+    let mut pair = None;
+    for (depth, key) in key_path.iter().enumerate() {
+        if depth > 0 {
+            object = pair?
+                .child_by_field_name("value")
+                .filter(|value| value.kind() == "object")?;
+        }
+        let quoted_key = format!("\"{key}\"");
+        let mut cursor = object.walk();
+        pair = Some(object.named_children(&mut cursor).find(|node| {
+            node.kind() == "pair"
+                && node
+                    .child_by_field_name("key")
+                    .and_then(|key| text.get(key.byte_range()))
+                    == Some(quoted_key.as_str())
+        })?);
+    }
+    let pair = pair?;
+
+    if let Some(comma) = sibling(pair, true).filter(|node| node.kind() == ",") {
+        // Remove up to whatever follows the comma, so that the next entry keeps the indentation
+        // of the removed one.
+        let end = comma
+            .next_sibling()
+            .filter(|node| node.kind() != "}")
+            .map_or(comma.end_byte(), |node| node.start_byte());
+        Some(pair.start_byte()..end)
+    } else if let Some(comma) = sibling(pair, false).filter(|node| node.kind() == ",") {
+        Some(comma.start_byte()..pair.end_byte())
+    } else {
+        Some(pair.byte_range())
+    }
+}
+
+pub(crate) fn to_pretty_json(
+    value: &impl Serialize,
+    indent_size: usize,
+    indent_prefix_len: usize,
+) -> String {
     const SPACES: [u8; 32] = [b' '; 32];
 
     debug_assert!(indent_size <= SPACES.len());
@@ -2008,6 +2221,183 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_setting_store_update_key_path(cx: &mut App) {
+        let store = SettingsStore::new(cx);
+
+        let old_json = r#"{
+            "languages": {
+                "Rust": {
+                    // Use tabs for Rust
+                    "hard_tabs": true
+                }
+            }
+        }"#
+        .unindent();
+        let new_json = store.new_text_for_key_path(
+            old_json,
+            &["languages", "Rust", "tab_size"],
+            Some(&serde_json::json!(8)),
+        );
+        pretty_assertions::assert_eq!(
+            new_json,
+            r#"{
+                "languages": {
+                    "Rust": {
+                        // Use tabs for Rust
+                        "tab_size": 8,
+                        "hard_tabs": true
+                    }
+                }
+            }"#
+            .unindent()
+        );
+
+        let new_json =
+            store.new_text_for_key_path(new_json, &["languages", "Rust", "hard_tabs"], None);
+        pretty_assertions::assert_eq!(
+            new_json,
+            r#"{
+                "languages": {
+                    "Rust": {
+                        // Use tabs for Rust
+                        "tab_size": 8
+                    }
+                }
+            }"#
+            .unindent()
+        );
+
+        let new_json =
+            store.new_text_for_key_path(new_json, &["languages", "Rust", "tab_size"], None);
+        assert!(!new_json.contains("tab_size"));
+        assert!(new_json.contains("// Use tabs for Rust"));
+
+        // Removing a missing key leaves the text untouched.
+        pretty_assertions::assert_eq!(
+            store.new_text_for_key_path(new_json.clone(), &["languages", "Go"], None),
+            new_json
+        );
+    }
+
+    #[gpui::test]
+    fn test_settings_profiles(cx: &mut App) {
+        let mut store = SettingsStore::new(cx);
+        store.register_setting::<UserSettings>(cx);
+        store.register_setting::<TurboSetting>(cx);
+        store
+            .set_default_settings(
+                r#"{
+                    "turbo": false,
+                    "user": {
+                        "name": "John Doe",
+                        "age": 30,
+                        "staff": false
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+        store
+            .set_user_settings(
+                r#"{
+                    "user": { "age": 31 },
+                    "profiles": {
+                        "presentation": {
+                            "turbo": true,
+                            "user": { "staff": true }
+                        },
+                        "low-power": {}
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+
+        assert_eq!(store.profile_names(), vec!["presentation", "low-power"]);
+        assert_eq!(store.active_profile(), None);
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(false));
+
+        store
+            .set_active_profile(Some("presentation".to_string()), cx)
+            .unwrap();
+        assert_eq!(store.active_profile(), Some("presentation"));
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(true));
+        assert_eq!(
+            store.get::<UserSettings>(None),
+            &UserSettings {
+                name: "John Doe".to_string(),
+                age: 31,
+                staff: true,
+            }
+        );
+
+        // Project settings still take precedence over the active profile.
+        store
+            .set_local_settings(
+                WorktreeId::from_usize(1),
+                Path::new("/root1").into(),
+                LocalSettingsKind::Settings,
+                Some(r#"{ "user": { "staff": false } }"#),
+                cx,
+            )
+            .unwrap();
+        assert_eq!(
+            store.get::<UserSettings>(Some(SettingsLocation {
+                worktree_id: WorktreeId::from_usize(1),
+                path: Path::new("/root1/something"),
+            })),
+            &UserSettings {
+                name: "John Doe".to_string(),
+                age: 31,
+                staff: false,
+            }
+        );
+
+        store.set_active_profile(None, cx).unwrap();
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(false));
+        assert_eq!(
+            store.get::<UserSettings>(None),
+            &UserSettings {
+                name: "John Doe".to_string(),
+                age: 31,
+                staff: false,
+            }
+        );
+    }
+
+    #[gpui::test]
+    fn test_conditional_user_settings(cx: &mut App) {
+        let mut store = SettingsStore::new(cx);
+        store.register_setting::<TurboSetting>(cx);
+        store
+            .set_default_settings(r#"{ "turbo": false }"#, cx)
+            .unwrap();
+
+        let user_settings = serde_json::json!({
+            "conditional": [
+                { "when": { "os": std::env::consts::OS }, "settings": { "turbo": true } },
+                { "when": { "os": "plan9" }, "settings": { "turbo": false } }
+            ]
+        });
+        let raw_settings = store
+            .set_user_settings(&user_settings.to_string(), cx)
+            .unwrap();
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(true));
+        assert_eq!(raw_settings, serde_json::json!({ "turbo": true }));
+
+        let invalid_settings = serde_json::json!({
+            "turbo": false,
+            "conditional": [{ "when": { "platform": "linux" }, "settings": {} }]
+        });
+        assert!(
+            store
+                .set_user_settings(&invalid_settings.to_string(), cx)
+                .is_err()
+        );
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(true));
+    }
+
     fn check_settings_update<T: Settings>(
         store: &mut SettingsStore,
         old_json: String,
@@ -0,0 +1,739 @@
+use std::{ops::Range, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use collections::{HashMap, HashSet};
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use gpui::{
+    AnyElement, App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyDownEvent,
+    KeymapVersion, Keystroke, ScrollStrategy, Subscription, UniformListScrollHandle, WeakEntity,
+    is_no_action, uniform_list,
+};
+use settings::{KeybindSource, KeybindUpdateOperation, KeybindUpdateTarget, KeymapFile};
+use ui::{Divider, KeyBinding, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    ModalView, Workspace,
+    item::{Item, ItemEvent},
+};
+
+/// Keymap files are written with the same indentation as the initial keymap.
+const KEYMAP_TAB_SIZE: usize = 2;
+
+/// The longest chord that can be recorded for a binding.
+const MAX_RECORDED_KEYSTROKES: usize = 4;
+
+#[derive(Clone)]
+struct ProcessedKeybinding {
+    keystrokes: Vec<Keystroke>,
+    ui_key_binding: Option<KeyBinding>,
+    action_name: SharedString,
+    action_input: Option<SharedString>,
+    context: Option<SharedString>,
+    source: Option<KeybindSource>,
+    /// Another action bound to the same keystrokes in the same context by the same source.
+    conflicting_action: Option<SharedString>,
+}
+
+impl ProcessedKeybinding {
+    fn keystroke_text(&self) -> String {
+        keystrokes_text(&self.keystrokes)
+    }
+
+    fn matches_filter(&self, query: &str) -> bool {
+        query.is_empty()
+            || self.action_name.to_lowercase().contains(query)
+            || self.keystroke_text().to_lowercase().contains(query)
+            || self
+                .context
+                .as_ref()
+                .is_some_and(|context| context.to_lowercase().contains(query))
+    }
+}
+
+fn keystrokes_text(keystrokes: &[Keystroke]) -> String {
+    keystrokes
+        .iter()
+        .map(|keystroke| keystroke.unparse())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub struct KeymapEditor {
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    filter_editor: Entity<Editor>,
+    keymap_version: Option<KeymapVersion>,
+    keybindings: Vec<ProcessedKeybinding>,
+    matches: Vec<usize>,
+    selected_index: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    _filter_subscription: Subscription,
+}
+
+impl KeymapEditor {
+    pub fn new(
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter actions, keystrokes, or contexts…", cx);
+            editor
+        });
+        let _filter_subscription =
+            cx.subscribe(&filter_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.update_matches(cx);
+                }
+            });
+
+        let mut this = Self {
+            workspace,
+            focus_handle: cx.focus_handle(),
+            filter_editor,
+            keymap_version: None,
+            keybindings: Vec::new(),
+            matches: Vec::new(),
+            selected_index: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            _filter_subscription,
+        };
+        this.process_bindings(cx);
+        this
+    }
+
+    fn process_bindings(&mut self, cx: &mut Context<Self>) {
+        let key_bindings = cx.key_bindings();
+        let keymap = key_bindings.borrow();
+        self.keymap_version = Some(keymap.version());
+
+        let mut bound_actions = HashSet::default();
+        let mut keybindings = Vec::new();
+        for key_binding in keymap.bindings() {
+            if is_no_action(key_binding.action()) {
+                continue;
+            }
+            let action_name = SharedString::from(key_binding.action().name().to_string());
+            bound_actions.insert(action_name.clone());
+            keybindings.push(ProcessedKeybinding {
+                keystrokes: key_binding.keystrokes().to_vec(),
+                ui_key_binding: Some(KeyBinding::new(key_binding.clone(), cx)),
+                action_name,
+                action_input: key_binding.action_input(),
+                context: key_binding
+                    .predicate()
+                    .map(|predicate| predicate.to_string().into()),
+                source: key_binding.meta().map(KeybindSource::from_meta),
+                conflicting_action: None,
+            });
+        }
+        drop(keymap);
+
+        for action_name in cx.all_action_names() {
+            if bound_actions.contains(action_name) {
+                continue;
+            }
+            keybindings.push(ProcessedKeybinding {
+                keystrokes: Vec::new(),
+                ui_key_binding: None,
+                action_name: action_name.clone(),
+                action_input: None,
+                context: None,
+                source: None,
+                conflicting_action: None,
+            });
+        }
+
+        mark_conflicts(&mut keybindings);
+        self.keybindings = keybindings;
+        self.update_matches(cx);
+    }
+
+    fn update_matches(&mut self, cx: &mut Context<Self>) {
+        let query = self.filter_editor.read(cx).text(cx).trim().to_lowercase();
+        self.matches = self
+            .keybindings
+            .iter()
+            .enumerate()
+            .filter(|(_, keybinding)| keybinding.matches_filter(&query))
+            .map(|(ix, _)| ix)
+            .collect();
+        self.selected_index = self
+            .selected_index
+            .filter(|ix| *ix < self.matches.len())
+            .or_else(|| (!self.matches.is_empty()).then_some(0));
+        cx.notify();
+    }
+
+    /// Returns the action already bound to `keystrokes` in `context`, ignoring the binding at
+    /// `editing_ix`.
+    fn conflict_for(
+        &self,
+        context: Option<&SharedString>,
+        keystrokes: &[Keystroke],
+        editing_ix: usize,
+    ) -> Option<SharedString> {
+        let editing = self.keybindings.get(editing_ix)?;
+        let keystrokes = keystrokes_text(keystrokes);
+        self.keybindings
+            .iter()
+            .enumerate()
+            .find(|(ix, keybinding)| {
+                *ix != editing_ix
+                    && keybinding.context.as_ref() == context
+                    && keybinding.keystroke_text() == keystrokes
+                    && (keybinding.action_name != editing.action_name
+                        || keybinding.action_input != editing.action_input)
+            })
+            .map(|(_, keybinding)| keybinding.action_name.clone())
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_index
+            .map_or(0, |ix| (ix + 1).min(self.matches.len() - 1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.edit_binding(ix, window, cx);
+        }
+    }
+
+    fn edit_binding(&mut self, match_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(&keybinding_ix) = self.matches.get(match_ix) else {
+            return;
+        };
+        let Some(keybinding) = self.keybindings.get(keybinding_ix).cloned() else {
+            return;
+        };
+        let keymap_editor = cx.weak_entity();
+        let fs = <dyn Fs>::global(cx);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    KeybindingEditorModal::new(
+                        keybinding,
+                        keybinding_ix,
+                        keymap_editor,
+                        fs,
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .log_err();
+    }
+
+    fn render_keybinding_row(&self, match_ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let Some(keybinding) = self
+            .matches
+            .get(match_ix)
+            .and_then(|ix| self.keybindings.get(*ix))
+        else {
+            return div().into_any();
+        };
+        let is_selected = self.selected_index == Some(match_ix);
+
+        h_flex()
+            .id(("keymap-editor-row", match_ix))
+            .w_full()
+            .px_2()
+            .py_0p5()
+            .gap_2()
+            .rounded_sm()
+            .cursor_pointer()
+            .when(is_selected, |this| {
+                this.bg(cx.theme().colors().element_selected)
+            })
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .on_click(
+                cx.listener(move |this, event: &gpui::ClickEvent, window, cx| {
+                    this.select_index(match_ix, cx);
+                    if event.down.click_count > 1 {
+                        this.edit_binding(match_ix, window, cx);
+                    }
+                }),
+            )
+            .child(
+                div()
+                    .w_1_3()
+                    .child(Label::new(keybinding.action_name.clone()).truncate()),
+            )
+            .child(
+                h_flex()
+                    .w_1_4()
+                    .gap_1()
+                    .children(keybinding.ui_key_binding.clone())
+                    .when_some(
+                        keybinding.conflicting_action.clone(),
+                        |this, conflicting_action| {
+                            this.child(
+                                div()
+                                    .id(("keymap-editor-conflict", match_ix))
+                                    .child(
+                                        Icon::new(IconName::Warning)
+                                            .size(IconSize::Small)
+                                            .color(Color::Warning),
+                                    )
+                                    .tooltip(Tooltip::text(format!(
+                                        "Conflicts with {conflicting_action}"
+                                    ))),
+                            )
+                        },
+                    ),
+            )
+            .child(
+                div().flex_1().child(
+                    Label::new(
+                        keybinding
+                            .context
+                            .clone()
+                            .unwrap_or_else(|| "<global>".into()),
+                    )
+                    .color(Color::Muted)
+                    .truncate(),
+                ),
+            )
+            .child(
+                div().w_16().child(
+                    Label::new(keybinding.source.map_or("", |source| source.name()))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            )
+            .into_any()
+    }
+
+    fn render_header(&self) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_2()
+            .gap_2()
+            .child(
+                div()
+                    .w_1_3()
+                    .child(Label::new("Action").color(Color::Muted)),
+            )
+            .child(
+                div()
+                    .w_1_4()
+                    .child(Label::new("Keystrokes").color(Color::Muted)),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .child(Label::new("Context").color(Color::Muted)),
+            )
+            .child(div().w_16().child(Label::new("Source").color(Color::Muted)))
+    }
+}
+
+/// Flags bindings that share keystrokes and context with a binding for a different action from
+/// the same source. Bindings from different sources are expected to override each other.
+fn mark_conflicts(keybindings: &mut [ProcessedKeybinding]) {
+    let mut groups = HashMap::<(Option<SharedString>, String), Vec<usize>>::default();
+    for (ix, keybinding) in keybindings.iter().enumerate() {
+        if keybinding.keystrokes.is_empty() {
+            continue;
+        }
+        groups
+            .entry((keybinding.context.clone(), keybinding.keystroke_text()))
+            .or_default()
+            .push(ix);
+    }
+
+    for indices in groups.into_values() {
+        for &ix in &indices {
+            let conflicting_action = indices
+                .iter()
+                .filter(|&&other_ix| other_ix != ix)
+                .map(|&other_ix| &keybindings[other_ix])
+                .find(|other| {
+                    other.source == keybindings[ix].source
+                        && (other.action_name != keybindings[ix].action_name
+                            || other.action_input != keybindings[ix].action_input)
+                })
+                .map(|other| other.action_name.clone());
+            keybindings[ix].conflicting_action = conflicting_action;
+        }
+    }
+}
+
+impl EventEmitter<ItemEvent> for KeymapEditor {}
+
+impl Focusable for KeymapEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.filter_editor.focus_handle(cx)
+    }
+}
+
+impl Item for KeymapEditor {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Keyboard))
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Keymap Editor".into()
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for KeymapEditor {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let keymap_version = cx.key_bindings().borrow().version();
+        if self.keymap_version != Some(keymap_version) {
+            self.process_bindings(cx);
+        }
+
+        v_flex()
+            .key_context("KeymapEditor")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .p_2()
+            .gap_2()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .p_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .child(self.filter_editor.clone()),
+            )
+            .child(self.render_header())
+            .child(Divider::horizontal())
+            .child(
+                uniform_list(
+                    cx.entity(),
+                    "keymap-editor-table",
+                    self.matches.len(),
+                    |this, range: Range<usize>, _window, cx| {
+                        range.map(|ix| this.render_keybinding_row(ix, cx)).collect()
+                    },
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .flex_grow(),
+            )
+    }
+}
+
+struct KeybindingEditorModal {
+    editing_keybind: ProcessedKeybinding,
+    editing_keybind_ix: usize,
+    keymap_editor: WeakEntity<KeymapEditor>,
+    keystroke_input: Entity<KeystrokeInput>,
+    fs: Arc<dyn Fs>,
+    error: Option<SharedString>,
+}
+
+impl KeybindingEditorModal {
+    fn new(
+        editing_keybind: ProcessedKeybinding,
+        editing_keybind_ix: usize,
+        keymap_editor: WeakEntity<KeymapEditor>,
+        fs: Arc<dyn Fs>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let keystroke_input = cx.new(|cx| KeystrokeInput::new(window, cx));
+        cx.observe(&keystroke_input, |this, _, cx| {
+            this.error = None;
+            cx.notify();
+        })
+        .detach();
+        keystroke_input.focus_handle(cx).focus(window);
+
+        Self {
+            editing_keybind,
+            editing_keybind_ix,
+            keymap_editor,
+            keystroke_input,
+            fs,
+            error: None,
+        }
+    }
+
+    fn conflicting_action(&self, cx: &App) -> Option<SharedString> {
+        let keystrokes = &self.keystroke_input.read(cx).keystrokes;
+        if keystrokes.is_empty() {
+            return None;
+        }
+        self.keymap_editor.upgrade()?.read(cx).conflict_for(
+            self.editing_keybind.context.as_ref(),
+            keystrokes,
+            self.editing_keybind_ix,
+        )
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let new_keystrokes = self.keystroke_input.read(cx).keystrokes.clone();
+        if new_keystrokes.is_empty() {
+            self.error = Some("Record a keystroke to bind".into());
+            cx.notify();
+            return;
+        }
+
+        let fs = self.fs.clone();
+        let existing_keybind = self.editing_keybind.clone();
+        cx.spawn(async move |this, cx| {
+            match save_keybinding_update(existing_keybind, &new_keystrokes, &fs).await {
+                Ok(()) => this.update(cx, |_, cx| cx.emit(DismissEvent)).ok(),
+                Err(error) => this
+                    .update(cx, |this, cx| {
+                        this.error = Some(error.to_string().into());
+                        cx.notify();
+                    })
+                    .ok(),
+            };
+        })
+        .detach();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+async fn save_keybinding_update(
+    existing: ProcessedKeybinding,
+    new_keystrokes: &[Keystroke],
+    fs: &Arc<dyn Fs>,
+) -> Result<()> {
+    let keymap_contents = KeymapFile::load_keymap_file(fs)
+        .await
+        .context("Failed to load keymap file")?;
+
+    let target = KeybindUpdateTarget {
+        context: existing.context.as_deref(),
+        keystrokes: new_keystrokes,
+        action_name: &existing.action_name,
+        action_input: existing.action_input.as_deref(),
+    };
+    let operation = if existing.keystrokes.is_empty() {
+        KeybindUpdateOperation::Add(target)
+    } else {
+        KeybindUpdateOperation::Replace {
+            source: KeybindUpdateTarget {
+                context: existing.context.as_deref(),
+                keystrokes: &existing.keystrokes,
+                action_name: &existing.action_name,
+                action_input: existing.action_input.as_deref(),
+            },
+            target,
+            target_keybind_source: existing.source.unwrap_or(KeybindSource::Default),
+        }
+    };
+
+    let updated_keymap_contents =
+        KeymapFile::update_keybinding(operation, keymap_contents, KEYMAP_TAB_SIZE)
+            .context("Failed to update keybinding")?;
+    fs.atomic_write(paths::keymap_file().clone(), updated_keymap_contents)
+        .await
+        .context("Failed to write keymap file")?;
+    Ok(())
+}
+
+impl EventEmitter<DismissEvent> for KeybindingEditorModal {}
+
+impl ModalView for KeybindingEditorModal {}
+
+impl Focusable for KeybindingEditorModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.keystroke_input.focus_handle(cx)
+    }
+}
+
+impl Render for KeybindingEditorModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let conflicting_action = self.conflicting_action(cx);
+
+        v_flex()
+            .key_context("KeybindingEditorModal")
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(34.))
+            .p_4()
+            .gap_2()
+            .child(Headline::new(format!(
+                "Edit Binding for {}",
+                self.editing_keybind.action_name
+            )))
+            .when_some(self.editing_keybind.context.clone(), |this, context| {
+                this.child(Label::new(format!("Context: {context}")).color(Color::Muted))
+            })
+            .child(self.keystroke_input.clone())
+            .when_some(conflicting_action, |this, conflicting_action| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(
+                            Label::new(format!(
+                                "Already bound to {conflicting_action} in this context"
+                            ))
+                            .color(Color::Warning),
+                        ),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(Label::new(error).color(Color::Error))
+            })
+            .child(
+                h_flex()
+                    .w_full()
+                    .justify_end()
+                    .gap_1()
+                    .child(
+                        Button::new("keybinding-editor-clear", "Clear").on_click(cx.listener(
+                            |this, _, _, cx| {
+                                this.keystroke_input.update(cx, |input, cx| input.clear(cx));
+                            },
+                        )),
+                    )
+                    .child(
+                        Button::new("keybinding-editor-cancel", "Cancel")
+                            .on_click(cx.listener(|_, _, _, cx| cx.emit(DismissEvent))),
+                    )
+                    .child(
+                        Button::new("keybinding-editor-save", "Save")
+                            .style(ButtonStyle::Filled)
+                            .on_click(cx.listener(|this, _, _, cx| this.save(cx))),
+                    ),
+            )
+    }
+}
+
+/// Records keystrokes while focused, bypassing the keymap so that keystrokes which are already
+/// bound can be recorded.
+struct KeystrokeInput {
+    keystrokes: Vec<Keystroke>,
+    focus_handle: FocusHandle,
+    _focus_subscriptions: [Subscription; 2],
+}
+
+impl KeystrokeInput {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        let _focus_subscriptions = [
+            cx.on_focus(&focus_handle, window, |this, window, _| {
+                window.capture_raw_keystrokes(&this.focus_handle);
+            }),
+            cx.on_blur(&focus_handle, window, |_, window, _| {
+                window.stop_capturing_raw_keystrokes();
+            }),
+        ];
+        Self {
+            keystrokes: Vec::new(),
+            focus_handle,
+            _focus_subscriptions,
+        }
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.keystrokes.clear();
+        cx.notify();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if event.is_held {
+            return;
+        }
+        if self.keystrokes.len() >= MAX_RECORDED_KEYSTROKES {
+            self.keystrokes.clear();
+        }
+        self.keystrokes.push(event.keystroke.clone());
+        cx.stop_propagation();
+        cx.notify();
+    }
+}
+
+impl Focusable for KeystrokeInput {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for KeystrokeInput {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_focused = self.focus_handle.is_focused(window);
+        let colors = cx.theme().colors();
+
+        h_flex()
+            .id("keystroke-input")
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .w_full()
+            .min_h_8()
+            .px_2()
+            .gap_1()
+            .rounded_md()
+            .border_1()
+            .border_color(if is_focused {
+                colors.border_focused
+            } else {
+                colors.border
+            })
+            .bg(colors.editor_background)
+            .map(|this| {
+                if self.keystrokes.is_empty() {
+                    this.child(
+                        Label::new(if is_focused {
+                            "Press the keys to bind…"
+                        } else {
+                            "Click here to record keystrokes"
+                        })
+                        .color(Color::Placeholder),
+                    )
+                } else {
+                    this.children(
+                        self.keystrokes
+                            .iter()
+                            .map(|keystroke| Label::new(keystroke.unparse()).buffer_font(cx)),
+                    )
+                }
+            })
+    }
+}
@@ -0,0 +1,127 @@
+use collections::IndexMap;
+use serde_json::Value;
+use ui::SharedString;
+
+/// The settings file that a setting is read from and written to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SettingsScope {
+    Default,
+    User,
+    Project,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SettingModification {
+    /// The setting isn't set by any lower scope.
+    Added,
+    /// The setting overrides the value from a lower scope with a different one.
+    Modified,
+}
+
+/// A single leaf setting, such as `languages.Rust.tab_size`, with its value in each scope.
+pub(crate) struct SettingEntry {
+    pub key_path: Vec<String>,
+    pub name: SharedString,
+    pub default_value: Option<Value>,
+    pub user_value: Option<Value>,
+    pub project_value: Option<Value>,
+}
+
+impl SettingEntry {
+    pub fn value(&self, scope: SettingsScope) -> Option<&Value> {
+        match scope {
+            SettingsScope::Default => self.default_value.as_ref(),
+            SettingsScope::User => self.user_value.as_ref(),
+            SettingsScope::Project => self.project_value.as_ref(),
+        }
+    }
+
+    /// The value that applies in `scope` when the setting isn't set there.
+    pub fn inherited_value(&self, scope: SettingsScope) -> Option<&Value> {
+        match scope {
+            SettingsScope::Default => None,
+            SettingsScope::User => self.default_value.as_ref(),
+            SettingsScope::Project => self.user_value.as_ref().or(self.default_value.as_ref()),
+        }
+    }
+
+    pub fn effective_value(&self, scope: SettingsScope) -> Option<&Value> {
+        self.value(scope).or_else(|| self.inherited_value(scope))
+    }
+
+    pub fn modification(&self, scope: SettingsScope) -> Option<SettingModification> {
+        if scope == SettingsScope::Default {
+            return None;
+        }
+        let value = self.value(scope)?;
+        match self.inherited_value(scope) {
+            None => Some(SettingModification::Added),
+            Some(inherited) if inherited != value => Some(SettingModification::Modified),
+            Some(_) => None,
+        }
+    }
+
+    pub fn matches_query(&self, query: &str, scope: SettingsScope) -> bool {
+        query.is_empty()
+            || self.name.to_lowercase().contains(query)
+            || self
+                .effective_value(scope)
+                .is_some_and(|value| value.to_string().to_lowercase().contains(query))
+    }
+}
+
+/// Flattens the settings files into one entry per leaf setting, in the order they appear in the
+/// default settings followed by any settings that only appear in the user or project files.
+pub(crate) fn setting_entries(
+    default_settings: &Value,
+    user_settings: &Value,
+    project_settings: Option<&Value>,
+) -> Vec<SettingEntry> {
+    let mut entries = IndexMap::<Vec<String>, SettingEntry>::default();
+    let sources = [
+        (SettingsScope::Default, Some(default_settings)),
+        (SettingsScope::User, Some(user_settings)),
+        (SettingsScope::Project, project_settings),
+    ];
+    for (scope, settings) in sources {
+        let Some(settings) = settings else {
+            continue;
+        };
+        flatten_json(settings, &mut Vec::new(), &mut |key_path, value| {
+            let entry = entries
+                .entry(key_path.to_vec())
+                .or_insert_with(|| SettingEntry {
+                    key_path: key_path.to_vec(),
+                    name: key_path.join(".").into(),
+                    default_value: None,
+                    user_value: None,
+                    project_value: None,
+                });
+            let slot = match scope {
+                SettingsScope::Default => &mut entry.default_value,
+                SettingsScope::User => &mut entry.user_value,
+                SettingsScope::Project => &mut entry.project_value,
+            };
+            *slot = Some(value.clone());
+        });
+    }
+
+    entries.into_values().collect()
+}
+
+fn flatten_json(value: &Value, key_path: &mut Vec<String>, f: &mut dyn FnMut(&[String], &Value)) {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (key, value) in object {
+                key_path.push(key.clone());
+                flatten_json(value, key_path, f);
+                key_path.pop();
+            }
+        }
+        _ => {
+            if !key_path.is_empty() {
+                f(key_path, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, Focusable, Render, Task, WeakEntity, Window,
+    actions,
+};
+use picker::{Picker, PickerDelegate};
+use settings::SettingsStore;
+use std::sync::Arc;
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, ui::HighlightedLabel};
+
+actions!(settings_profile_selector, [Toggle]);
+
+const NO_PROFILE_LABEL: &str = "No Profile";
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
+        workspace.register_action(toggle);
+    })
+    .detach();
+}
+
+fn toggle(workspace: &mut Workspace, _: &Toggle, window: &mut Window, cx: &mut Context<Workspace>) {
+    workspace.toggle_modal(window, cx, |window, cx| {
+        SettingsProfileSelector::new(
+            SettingsProfileSelectorDelegate::new(cx.entity().downgrade(), cx),
+            window,
+            cx,
+        )
+    });
+}
+
+pub struct SettingsProfileSelector {
+    picker: Entity<Picker<SettingsProfileSelectorDelegate>>,
+}
+
+impl Focusable for SettingsProfileSelector {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for SettingsProfileSelector {}
+impl ModalView for SettingsProfileSelector {}
+
+impl SettingsProfileSelector {
+    fn new(
+        delegate: SettingsProfileSelectorDelegate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for SettingsProfileSelector {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+pub struct SettingsProfileSelectorDelegate {
+    selector: WeakEntity<SettingsProfileSelector>,
+    /// The selectable profiles, where `None` deactivates the active profile.
+    profiles: Vec<Option<String>>,
+    active_profile: Option<String>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl SettingsProfileSelectorDelegate {
+    fn new(selector: WeakEntity<SettingsProfileSelector>, cx: &App) -> Self {
+        let store = cx.global::<SettingsStore>();
+        let active_profile = store.active_profile().map(ToString::to_string);
+        let profiles = std::iter::once(None)
+            .chain(store.profile_names().into_iter().map(Some))
+            .collect::<Vec<_>>();
+        let selected_index = profiles
+            .iter()
+            .position(|profile| *profile == active_profile)
+            .unwrap_or(0);
+        Self {
+            selector,
+            profiles,
+            active_profile,
+            matches: Vec::new(),
+            selected_index,
+        }
+    }
+
+    fn label(profile: &Option<String>) -> &str {
+        profile.as_deref().unwrap_or(NO_PROFILE_LABEL)
+    }
+}
+
+impl PickerDelegate for SettingsProfileSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a settings profile...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(id, profile)| StringMatchCandidate::new(id, Self::label(profile)))
+            .collect::<Vec<_>>();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, _| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = this
+                    .delegate
+                    .selected_index
+                    .min(this.delegate.matches.len().saturating_sub(1));
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(profile) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|profile_match| self.profiles.get(profile_match.candidate_id))
+            .cloned()
+        {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.set_active_profile(profile, cx).log_err();
+            });
+        }
+
+        self.selector
+            .update(cx, |_, cx| {
+                cx.emit(DismissEvent);
+            })
+            .ok();
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.selector
+            .update(cx, |_, cx| {
+                cx.emit(DismissEvent);
+            })
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let profile_match = &self.matches[ix];
+        let is_active = self
+            .profiles
+            .get(profile_match.candidate_id)
+            .is_some_and(|profile| *profile == self.active_profile);
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(HighlightedLabel::new(
+                    profile_match.string.clone(),
+                    profile_match.positions.clone(),
+                ))
+                .when(is_active, |item| {
+                    item.end_slot(
+                        Icon::new(IconName::Check)
+                            .size(IconSize::Small)
+                            .color(Color::Accent),
+                    )
+                }),
+        )
+    }
+}
@@ -1,24 +1,32 @@
 mod appearance_settings_controls;
+mod keymap_editor;
+mod setting_entries;
+mod settings_profile_selector;
 
 use std::any::TypeId;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use command_palette_hooks::CommandPaletteFilter;
-use editor::EditorSettingsControls;
+use editor::{Editor, EditorEvent, EditorSettingsControls};
 use feature_flags::{FeatureFlag, FeatureFlagViewExt};
 use fs::Fs;
 use gpui::{
-    App, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable, Task, actions,
-    impl_actions,
+    AnyElement, App, AsyncApp, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, actions, impl_actions, uniform_list,
 };
 use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::Value;
 use settings::{SettingsStore, VsCodeSettingsSource};
-use ui::prelude::*;
+use ui::{ToggleButton, Tooltip, prelude::*};
 use workspace::item::{Item, ItemEvent};
 use workspace::{Workspace, with_active_or_new_workspace};
 
 use crate::appearance_settings_controls::AppearanceSettingsControls;
+use crate::keymap_editor::KeymapEditor;
+use crate::setting_entries::{SettingEntry, SettingModification, SettingsScope, setting_entries};
 
 pub struct SettingsUiFeatureFlag;
 
@@ -39,9 +47,11 @@ pub struct ImportCursorSettings {
 }
 
 impl_actions!(zed, [ImportVsCodeSettings, ImportCursorSettings]);
-actions!(zed, [OpenSettingsEditor]);
+actions!(zed, [OpenSettingsEditor, OpenKeymapEditor]);
 
 pub fn init(cx: &mut App) {
+    settings_profile_selector::init(cx);
+
     cx.on_action(|_: &OpenSettingsEditor, cx| {
         with_active_or_new_workspace(cx, move |workspace, window, cx| {
             let existing = workspace
@@ -53,12 +63,30 @@ pub fn init(cx: &mut App) {
             if let Some(existing) = existing {
                 workspace.activate_item(&existing, true, true, window, cx);
             } else {
-                let settings_page = SettingsPage::new(workspace, cx);
+                let settings_page = SettingsPage::new(workspace, window, cx);
                 workspace.add_item_to_active_pane(Box::new(settings_page), None, true, window, cx)
             }
         });
     });
 
+    cx.on_action(|_: &OpenKeymapEditor, cx| {
+        with_active_or_new_workspace(cx, move |workspace, window, cx| {
+            let existing = workspace
+                .active_pane()
+                .read(cx)
+                .items()
+                .find_map(|item| item.downcast::<KeymapEditor>());
+
+            if let Some(existing) = existing {
+                workspace.activate_item(&existing, true, true, window, cx);
+            } else {
+                let weak_workspace = cx.entity().downgrade();
+                let keymap_editor = cx.new(|cx| KeymapEditor::new(weak_workspace, window, cx));
+                workspace.add_item_to_active_pane(Box::new(keymap_editor), None, true, window, cx)
+            }
+        });
+    });
+
     cx.observe_new(|workspace: &mut Workspace, window, cx| {
         let Some(window) = window else {
             return;
@@ -98,7 +126,10 @@ pub fn init(cx: &mut App) {
                 .detach();
         });
 
-        let settings_ui_actions = [TypeId::of::<OpenSettingsEditor>()];
+        let settings_ui_actions = [
+            TypeId::of::<OpenSettingsEditor>(),
+            TypeId::of::<OpenKeymapEditor>(),
+        ];
 
         CommandPaletteFilter::update_global(cx, |filter, _cx| {
             filter.hide_action_types(&settings_ui_actions);
@@ -174,22 +205,318 @@ async fn handle_import_vscode_settings(
 }
 
 pub struct SettingsPage {
+    workspace: WeakEntity<Workspace>,
     focus_handle: FocusHandle,
+    search_editor: Entity<Editor>,
+    value_editor: Entity<Editor>,
+    scope: SettingsScope,
+    entries: Vec<SettingEntry>,
+    matches: Vec<usize>,
+    editing_entry: Option<usize>,
+    error: Option<SharedString>,
+    scroll_handle: UniformListScrollHandle,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl SettingsPage {
-    pub fn new(_workspace: &Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
-        cx.new(|cx| Self {
-            focus_handle: cx.focus_handle(),
+    pub fn new(
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let workspace = workspace.weak_handle();
+        cx.new(|cx| {
+            let search_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Search settings…", cx);
+                editor
+            });
+            let value_editor = cx.new(|cx| Editor::single_line(window, cx));
+
+            let _subscriptions = vec![
+                cx.subscribe(&search_editor, |this, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.update_matches(cx);
+                    }
+                }),
+                cx.observe_global::<SettingsStore>(|this, cx| this.update_entries(cx)),
+            ];
+
+            let mut this = Self {
+                workspace,
+                focus_handle: cx.focus_handle(),
+                search_editor,
+                value_editor,
+                scope: SettingsScope::User,
+                entries: Vec::new(),
+                matches: Vec::new(),
+                editing_entry: None,
+                error: None,
+                scroll_handle: UniformListScrollHandle::new(),
+                _subscriptions,
+            };
+            this.update_entries(cx);
+            this
         })
     }
+
+    /// The worktree whose `.zed/settings.json` is edited in the project scope.
+    fn project_settings_root(&self, cx: &App) -> Option<(settings::WorktreeId, Arc<Path>)> {
+        let workspace = self.workspace.upgrade()?;
+        let project = workspace.read(cx).project().read(cx);
+        if !project.is_local() {
+            return None;
+        }
+        let worktree = project.visible_worktrees(cx).next()?;
+        let worktree = worktree.read(cx);
+        Some((
+            settings::WorktreeId::from_usize(worktree.id().to_usize()),
+            worktree.abs_path(),
+        ))
+    }
+
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        let project_root = self.project_settings_root(cx);
+        let store = cx.global::<SettingsStore>();
+        let project_settings = project_root
+            .as_ref()
+            .and_then(|(worktree_id, _)| store.raw_local_settings(*worktree_id, Path::new("")));
+        self.entries = setting_entries(
+            store.raw_default_settings(),
+            store.raw_user_settings(),
+            project_settings,
+        );
+        if project_root.is_none() && self.scope == SettingsScope::Project {
+            self.scope = SettingsScope::User;
+        }
+        self.editing_entry = None;
+        self.update_matches(cx);
+    }
+
+    fn update_matches(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx).trim().to_lowercase();
+        let scope = self.scope;
+        self.matches = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.matches_query(&query, scope))
+            .map(|(ix, _)| ix)
+            .collect();
+        cx.notify();
+    }
+
+    fn set_scope(&mut self, scope: SettingsScope, cx: &mut Context<Self>) {
+        self.scope = scope;
+        self.editing_entry = None;
+        self.error = None;
+        self.update_matches(cx);
+    }
+
+    fn start_editing(&mut self, entry_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.scope == SettingsScope::Default {
+            return;
+        }
+        let Some(entry) = self.entries.get(entry_ix) else {
+            return;
+        };
+        let text = entry
+            .effective_value(self.scope)
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        self.value_editor.update(cx, |editor, cx| {
+            editor.set_text(text, window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+        });
+        self.value_editor.focus_handle(cx).focus(window);
+        self.editing_entry = Some(entry_ix);
+        self.error = None;
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry_ix) = self.editing_entry.take() else {
+            return;
+        };
+        let text = self.value_editor.read(cx).text(cx);
+        // Allow strings to be entered without quotes.
+        let value = settings::parse_json_with_comments::<Value>(&text)
+            .unwrap_or_else(|_| Value::String(text.trim().to_string()));
+        self.write_setting(entry_ix, Some(value), cx);
+        self.focus_handle.focus(window);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_entry.take().is_some() {
+            self.focus_handle.focus(window);
+            cx.notify();
+        }
+    }
+
+    fn write_setting(&mut self, entry_ix: usize, value: Option<Value>, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(entry_ix) else {
+            return;
+        };
+        let settings_path = match self.scope {
+            SettingsScope::Default => return,
+            SettingsScope::User => paths::settings_file().clone(),
+            SettingsScope::Project => match self.project_settings_root(cx) {
+                Some((_, root)) => root.join(paths::local_settings_file_relative_path()),
+                None => return,
+            },
+        };
+        let key_path = entry.key_path.clone();
+        let scope = self.scope;
+        let fs = <dyn Fs>::global(cx);
+        cx.spawn(async move |this, cx| {
+            let result = write_setting_to_file(fs, scope, settings_path, key_path, value, cx).await;
+            this.update(cx, |this, cx| {
+                this.error = result.err().map(|error| format!("{error:#}").into());
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn render_scope_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_project = self.project_settings_root(cx).is_some();
+        let button = |id: &'static str, label: &'static str, scope: SettingsScope| {
+            ToggleButton::new(id, label)
+                .style(ButtonStyle::Filled)
+                .toggle_state(self.scope == scope)
+                .on_click(cx.listener(move |this, _, _, cx| this.set_scope(scope, cx)))
+        };
+
+        h_flex()
+            .child(button("settings-scope-default", "Default", SettingsScope::Default).first())
+            .child(button("settings-scope-user", "User", SettingsScope::User).middle())
+            .child(
+                button("settings-scope-project", "Project", SettingsScope::Project)
+                    .disabled(!has_project)
+                    .last(),
+            )
+    }
+
+    fn render_entry(&self, match_ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let Some((entry_ix, entry)) = self
+            .matches
+            .get(match_ix)
+            .and_then(|ix| Some((*ix, self.entries.get(*ix)?)))
+        else {
+            return div().into_any();
+        };
+        let scope = self.scope;
+        let is_editing = self.editing_entry == Some(entry_ix);
+        let is_set_in_scope = entry.value(scope).is_some();
+        let modification = entry.modification(scope);
+        let inherited = entry
+            .inherited_value(scope)
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "not set".to_string());
+        let value = entry
+            .effective_value(scope)
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        let colors = cx.theme().status();
+        let indicator_color = match modification {
+            Some(SettingModification::Added) => Some(colors.created),
+            Some(SettingModification::Modified) => Some(colors.modified),
+            None => None,
+        };
+
+        h_flex()
+            .id(("setting-entry", match_ix))
+            .w_full()
+            .gap_2()
+            .pr_2()
+            .rounded_sm()
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .when(scope != SettingsScope::Default, |this| {
+                this.cursor_pointer()
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.start_editing(entry_ix, window, cx);
+                    }))
+            })
+            .child(
+                div()
+                    .id(("setting-modified", match_ix))
+                    .w_1()
+                    .h_5()
+                    .when_some(indicator_color, |this, color| this.bg(color))
+                    .when(modification.is_some(), |this| {
+                        this.tooltip(Tooltip::text(format!("Overrides {inherited}")))
+                    }),
+            )
+            .child(
+                div()
+                    .w_2_5()
+                    .child(Label::new(entry.name.clone()).buffer_font(cx).truncate()),
+            )
+            .child(div().flex_1().map(|this| {
+                if is_editing {
+                    this.child(self.value_editor.clone())
+                } else {
+                    this.child(
+                        Label::new(value)
+                            .buffer_font(cx)
+                            .color(if is_set_in_scope {
+                                Color::Default
+                            } else {
+                                Color::Muted
+                            })
+                            .truncate(),
+                    )
+                }
+            }))
+            .when(scope != SettingsScope::Default && is_set_in_scope, |this| {
+                this.child(
+                    IconButton::new(("setting-reset", match_ix), IconName::RotateCcw)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Reset to Inherited Value"))
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.write_setting(entry_ix, None, cx);
+                        })),
+                )
+            })
+            .into_any()
+    }
+}
+
+async fn write_setting_to_file(
+    fs: Arc<dyn Fs>,
+    scope: SettingsScope,
+    settings_path: PathBuf,
+    key_path: Vec<String>,
+    value: Option<Value>,
+    cx: &mut AsyncApp,
+) -> anyhow::Result<()> {
+    let old_text = match scope {
+        SettingsScope::User => SettingsStore::load_settings(&fs).await?,
+        _ if fs.is_file(&settings_path).await => fs.load(&settings_path).await?,
+        _ => settings::initial_project_settings_content().to_string(),
+    };
+    let key_path = key_path.iter().map(String::as_str).collect::<Vec<_>>();
+    let new_text = cx.read_global(|store: &SettingsStore, _| {
+        store.new_text_for_key_path(old_text, &key_path, value.as_ref())
+    })?;
+
+    let settings_path = if fs.is_file(&settings_path).await {
+        fs.canonicalize(&settings_path).await?
+    } else {
+        if let Some(parent) = settings_path.parent() {
+            fs.create_dir(parent).await?;
+        }
+        settings_path
+    };
+    fs.atomic_write(settings_path, new_text).await
 }
 
 impl EventEmitter<ItemEvent> for SettingsPage {}
 
 impl Focusable for SettingsPage {
-    fn focus_handle(&self, _cx: &App) -> FocusHandle {
-        self.focus_handle.clone()
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.search_editor.focus_handle(cx)
     }
 }
 
@@ -215,24 +542,64 @@ impl Item for SettingsPage {
 
 impl Render for SettingsPage {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let show_controls = self.scope == SettingsScope::User
+            && self.search_editor.read(cx).text(cx).trim().is_empty();
+
         v_flex()
+            .key_context("SettingsPage")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
             .p_4()
             .size_full()
             .gap_4()
             .child(Label::new("Settings").size(LabelSize::Large))
             .child(
-                v_flex().gap_1().child(Label::new("Appearance")).child(
-                    v_flex()
-                        .elevation_2(cx)
-                        .child(AppearanceSettingsControls::new()),
-                ),
+                h_flex()
+                    .w_full()
+                    .gap_4()
+                    .child(
+                        h_flex()
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .rounded_md()
+                            .child(self.search_editor.clone()),
+                    )
+                    .child(self.render_scope_toggle(cx)),
             )
+            .when(show_controls, |this| {
+                this.child(
+                    v_flex().gap_1().child(Label::new("Appearance")).child(
+                        v_flex()
+                            .elevation_2(cx)
+                            .child(AppearanceSettingsControls::new()),
+                    ),
+                )
+                .child(
+                    v_flex().gap_1().child(Label::new("Editor")).child(
+                        v_flex()
+                            .elevation_2(cx)
+                            .child(EditorSettingsControls::new()),
+                    ),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(Label::new(error).color(Color::Error))
+            })
             .child(
-                v_flex().gap_1().child(Label::new("Editor")).child(
-                    v_flex()
-                        .elevation_2(cx)
-                        .child(EditorSettingsControls::new()),
-                ),
+                uniform_list(
+                    cx.entity(),
+                    "settings-entries",
+                    self.matches.len(),
+                    |this, range: Range<usize>, _window, cx| {
+                        range.map(|ix| this.render_entry(ix, cx)).collect()
+                    },
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .flex_grow(),
             )
     }
 }
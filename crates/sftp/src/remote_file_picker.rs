@@ -0,0 +1,307 @@
+use std::{sync::Arc, time::Duration};
+
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
+    Render, SharedString, Styled, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::{
+    open_remote_file,
+    sftp_client::{self, RemoteEntry, SftpHost, parse_remote_location},
+};
+
+/// Browses the directories of a host over SFTP, from a location typed like
+/// `user@host:/path/to/file`, and opens one of its files.
+pub struct RemoteFilePicker {
+    picker: Entity<Picker<RemoteFileDelegate>>,
+}
+
+impl RemoteFilePicker {
+    pub fn new(
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = RemoteFileDelegate {
+            remote_file_picker: cx.entity().downgrade(),
+            workspace,
+            listing: None,
+            filter: String::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+            _listing_task: None,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for RemoteFilePicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for RemoteFilePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for RemoteFilePicker {}
+impl ModalView for RemoteFilePicker {}
+
+/// A directory of the host, which is listed once for all the file names typed in it.
+struct DirectoryListing {
+    host: SftpHost,
+    path: String,
+    /// `None` while the directory is being listed.
+    entries: Option<Result<Vec<RemoteEntry>, SharedString>>,
+}
+
+pub struct RemoteFileDelegate {
+    remote_file_picker: WeakEntity<RemoteFilePicker>,
+    workspace: WeakEntity<Workspace>,
+    listing: Option<DirectoryListing>,
+    filter: String,
+    matches: Vec<usize>,
+    selected_index: usize,
+    _listing_task: Option<Task<()>>,
+}
+
+impl RemoteFileDelegate {
+    fn entries(&self) -> &[RemoteEntry] {
+        match &self.listing {
+            Some(DirectoryListing {
+                entries: Some(Ok(entries)),
+                ..
+            }) => entries,
+            _ => &[],
+        }
+    }
+
+    fn selected_entry(&self) -> Option<(&DirectoryListing, &RemoteEntry)> {
+        let entry = self
+            .entries()
+            .get(*self.matches.get(self.selected_index)?)?;
+        Some((self.listing.as_ref()?, entry))
+    }
+
+    /// Hidden files are only listed when the typed name starts with a dot.
+    fn update_filtered_matches(&mut self) {
+        let filter = self.filter.to_lowercase();
+        let matches = self
+            .entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                (filter.starts_with('.') || !entry.name.starts_with('.'))
+                    && entry.name.to_lowercase().starts_with(&filter)
+            })
+            .map(|(ix, _)| ix)
+            .collect();
+        self.matches = matches;
+        self.selected_index = 0;
+    }
+}
+
+/// Splits the path typed in into the directory to list, ending with a slash unless it's the
+/// home directory, and the beginning of the file name to look for in it.
+fn split_typed_path(path: &str) -> (&str, &str) {
+    match path.rfind('/') {
+        Some(ix) => (&path[..=ix], &path[ix + 1..]),
+        None => ("", path),
+    }
+}
+
+impl PickerDelegate for RemoteFileDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "user@host:/path/to/file".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some(match &self.listing {
+            None => "Type a location like user@host:/path or sftp://user@host:port/path".into(),
+            Some(DirectoryListing { entries: None, .. }) => "Listing the directory…".into(),
+            Some(DirectoryListing {
+                entries: Some(Err(error)),
+                ..
+            }) => error.clone(),
+            Some(DirectoryListing {
+                entries: Some(Ok(_)),
+                ..
+            }) => "No matching files".into(),
+        })
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some((host, path)) = parse_remote_location(query.trim()) else {
+            self.listing = None;
+            self._listing_task = None;
+            self.matches.clear();
+            cx.notify();
+            return Task::ready(());
+        };
+        let (directory, filter) = split_typed_path(&path);
+        self.filter = filter.to_string();
+        if self
+            .listing
+            .as_ref()
+            .is_some_and(|listing| listing.host == host && listing.path == directory)
+        {
+            self.update_filtered_matches();
+            cx.notify();
+            return Task::ready(());
+        }
+
+        let directory = directory.to_string();
+        self.listing = Some(DirectoryListing {
+            host: host.clone(),
+            path: directory.clone(),
+            entries: None,
+        });
+        self.matches.clear();
+        // The listing outlives the queries that only change the file name, so it's not returned
+        // to the picker, which would drop it on the next query.
+        self._listing_task = Some(cx.spawn_in(window, async move |picker, cx| {
+            // Each listing connects to the host, so it waits for the location to be typed.
+            cx.background_executor()
+                .timer(Duration::from_millis(300))
+                .await;
+            let entries = sftp_client::list_dir(&host, &directory)
+                .await
+                .map_err(|error| SharedString::from(format!("{error:#}")));
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    if let Some(listing) = delegate.listing.as_mut() {
+                        listing.entries = Some(entries);
+                    }
+                    delegate.update_filtered_matches();
+                    cx.notify();
+                })
+                .log_err();
+        }));
+        cx.notify();
+        Task::ready(())
+    }
+
+    fn confirm_completion(
+        &mut self,
+        _query: String,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<String> {
+        let (listing, entry) = self.selected_entry()?;
+        let path = format!(
+            "{}{}{}",
+            listing.path,
+            entry.name,
+            if entry.is_dir { "/" } else { "" }
+        );
+        Some(listing.host.location(&path))
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some((listing, entry)) = self.selected_entry() else {
+            return;
+        };
+        let host = listing.host.clone();
+        let path = format!("{}{}", listing.path, entry.name);
+        if entry.is_dir {
+            let query = host.location(&format!("{path}/"));
+            cx.defer_in(window, move |picker, window, cx| {
+                picker.set_query(query, window, cx);
+            });
+            return;
+        }
+        self.workspace
+            .update(cx, |workspace, cx| {
+                open_remote_file(workspace, host, path, window, cx);
+            })
+            .log_err();
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.remote_file_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = self.entries().get(*self.matches.get(ix)?)?;
+        let icon = if entry.is_dir {
+            IconName::Folder
+        } else {
+            IconName::File
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(icon).color(Color::Muted))
+                .child(
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .child(Label::new(entry.name.clone()))
+                        .when(!entry.is_dir, |this| {
+                            this.child(
+                                Label::new(entry.version.modified.clone())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_typed_path() {
+        assert_eq!(split_typed_path("/etc/ho"), ("/etc/", "ho"));
+        assert_eq!(split_typed_path("/etc/"), ("/etc/", ""));
+        assert_eq!(split_typed_path("notes"), ("", "notes"));
+        assert_eq!(split_typed_path(""), ("", ""));
+    }
+}
@@ -0,0 +1,186 @@
+mod remote_file_picker;
+mod sftp_client;
+
+use std::path::PathBuf;
+
+use collections::HashMap;
+use editor::Editor;
+use gpui::{App, Context, Global, PromptLevel, Subscription, Window, actions};
+use language::BufferEvent;
+use workspace::{
+    OpenOptions, OpenVisible, Toast, Workspace,
+    notifications::{DetachAndPromptErr as _, NotificationId},
+};
+
+pub use remote_file_picker::RemoteFilePicker;
+pub use sftp_client::{SftpHost, parse_remote_location};
+
+use sftp_client::RemoteVersion;
+
+actions!(sftp, [OpenRemoteFile]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(register).detach();
+}
+
+fn register(workspace: &mut Workspace, _: Option<&mut Window>, _: &mut Context<Workspace>) {
+    workspace.register_action(|workspace, _: &OpenRemoteFile, window, cx| {
+        let weak_workspace = cx.entity().downgrade();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            RemoteFilePicker::new(weak_workspace, window, cx)
+        });
+    });
+}
+
+/// A remote file that was downloaded to be edited, and that's uploaded back to its host when
+/// it's saved.
+struct RemoteFile {
+    host: SftpHost,
+    remote_path: String,
+    /// The version of the remote file when it was last downloaded or uploaded.
+    version: RemoteVersion,
+    _buffer_subscription: Option<Subscription>,
+}
+
+/// The remote files being edited, by the path of their local copy.
+#[derive(Default)]
+struct RemoteFiles(HashMap<PathBuf, RemoteFile>);
+
+impl Global for RemoteFiles {}
+
+/// Downloads a file of the host and opens it. Saving it uploads it back to the host. It's only
+/// downloaded the first time it's opened, so that the local changes that couldn't be uploaded
+/// aren't lost.
+pub fn open_remote_file(
+    workspace: &mut Workspace,
+    host: SftpHost,
+    remote_path: String,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let local_path = sftp_client::local_copy_path(&host, &remote_path);
+    let fs = workspace.app_state().fs.clone();
+    let is_downloaded = cx
+        .try_global::<RemoteFiles>()
+        .is_some_and(|files| files.0.contains_key(&local_path));
+    cx.spawn_in(window, async move |workspace, cx| {
+        if !is_downloaded {
+            // The version is read before downloading, so that a change made in the meantime is
+            // detected as a conflict when uploading.
+            let version = sftp_client::stat(&host, &remote_path).await?.version;
+            if let Some(parent) = local_path.parent() {
+                fs.create_dir(parent).await?;
+            }
+            sftp_client::download(&host, &remote_path, &local_path).await?;
+            cx.update(|_, cx| {
+                cx.default_global::<RemoteFiles>().0.insert(
+                    local_path.clone(),
+                    RemoteFile {
+                        host,
+                        remote_path,
+                        version,
+                        _buffer_subscription: None,
+                    },
+                );
+            })?;
+        }
+
+        let item = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    local_path.clone(),
+                    OpenOptions {
+                        visible: Some(OpenVisible::None),
+                        ..Default::default()
+                    },
+                    window,
+                    cx,
+                )
+            })?
+            .await?;
+        workspace.update_in(cx, |_, window, cx| {
+            let Some(buffer) = item
+                .downcast::<Editor>()
+                .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+            else {
+                return;
+            };
+            let saved_path = local_path.clone();
+            let subscription =
+                cx.subscribe_in(&buffer, window, move |workspace, _, event, window, cx| {
+                    if let BufferEvent::Saved = event {
+                        upload_remote_file(workspace, saved_path.clone(), window, cx);
+                    }
+                });
+            if let Some(file) = cx.default_global::<RemoteFiles>().0.get_mut(&local_path) {
+                file._buffer_subscription = Some(subscription);
+            }
+        })
+    })
+    .detach_and_prompt_err("Failed to open the remote file", window, cx, |_, _, _| None);
+}
+
+/// Uploads the local copy of a remote file, asking before overwriting the changes made on the
+/// host since it was downloaded.
+fn upload_remote_file(
+    _: &mut Workspace,
+    local_path: PathBuf,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(file) = cx
+        .try_global::<RemoteFiles>()
+        .and_then(|files| files.0.get(&local_path))
+    else {
+        return;
+    };
+    let (host, remote_path, version) = (
+        file.host.clone(),
+        file.remote_path.clone(),
+        file.version.clone(),
+    );
+    cx.spawn_in(window, async move |workspace, cx| {
+        let remote_version = sftp_client::stat(&host, &remote_path)
+            .await
+            .ok()
+            .map(|entry| entry.version);
+        if remote_version.as_ref() != Some(&version) {
+            let detail =
+                format!("{remote_path} was changed or removed on {host} since it was downloaded.");
+            let answer = workspace.update_in(cx, |_, window, cx| {
+                window.prompt(
+                    PromptLevel::Warning,
+                    "The remote file has changed",
+                    Some(&detail),
+                    &["Overwrite", "Keep Local Changes Only"],
+                    cx,
+                )
+            })?;
+            if answer.await.ok() != Some(0) {
+                return anyhow::Ok(());
+            }
+        }
+
+        sftp_client::upload(&host, &local_path, &remote_path).await?;
+        let version = sftp_client::stat(&host, &remote_path).await?.version;
+        cx.update(|_, cx| {
+            if let Some(file) = cx.default_global::<RemoteFiles>().0.get_mut(&local_path) {
+                file.version = version;
+            }
+        })?;
+        workspace.update(cx, |workspace, cx| {
+            struct RemoteFileUploaded;
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<RemoteFileUploaded>(),
+                    format!("Uploaded {}", host.location(&remote_path)),
+                )
+                .autohide(),
+                cx,
+            )
+        })
+    })
+    .detach_and_prompt_err("Failed to upload the remote file", window, cx, |_, _, _| {
+        None
+    });
+}
@@ -0,0 +1,383 @@
+use std::{
+    fmt,
+    path::{Component, Path, PathBuf},
+    process::Stdio,
+};
+
+use anyhow::{Context as _, Result};
+use smol::io::AsyncWriteExt as _;
+
+/// A host whose files are browsed and edited through its SFTP server, without installing the
+/// remote server on it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SftpHost {
+    pub user: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+}
+
+impl SftpHost {
+    fn destination(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{user}@{}", self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// Formats a path on the host the way it's typed in, as `user@host:path` or, when a port
+    /// is needed, as `sftp://user@host:port/path`.
+    pub fn location(&self, path: &str) -> String {
+        match self.port {
+            None => format!("{}:{path}", self.destination()),
+            Some(port) if path.starts_with('/') => {
+                format!("sftp://{}:{port}{path}", self.destination())
+            }
+            Some(port) => format!("sftp://{}:{port}/~/{path}", self.destination()),
+        }
+    }
+}
+
+impl fmt::Display for SftpHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.port {
+            Some(port) => write!(f, "{}:{port}", self.destination()),
+            None => write!(f, "{}", self.destination()),
+        }
+    }
+}
+
+/// Splits a location like `user@host:/etc/hosts` or `sftp://user@host:2222/etc/hosts` into
+/// the host and the path on it. Relative paths, like in `host:notes.txt` or
+/// `sftp://host/~/notes.txt`, are relative to the home directory of the user.
+pub fn parse_remote_location(text: &str) -> Option<(SftpHost, String)> {
+    let (authority, path, allows_port) = if let Some(url) = text.strip_prefix("sftp://") {
+        let (authority, path) = url.split_at(url.find('/')?);
+        let path = if path == "/~" {
+            ""
+        } else {
+            path.strip_prefix("/~/").unwrap_or(path)
+        };
+        (authority, path, true)
+    } else {
+        let (authority, path) = text.split_once(':')?;
+        (authority, path, false)
+    };
+
+    let (user, host_and_port) = match authority.rsplit_once('@') {
+        Some((user, rest)) => (Some(user.to_string()), rest),
+        None => (None, authority),
+    };
+    let (host, port) = match host_and_port.rsplit_once(':') {
+        Some((host, port)) if allows_port => (host, Some(port.parse().ok()?)),
+        _ => (host_and_port, None),
+    };
+    // A host or user that starts with `-` would be read as an option of `sftp` and `ssh`.
+    if host.is_empty()
+        || host.starts_with('-')
+        || host.contains(['/', '\\'])
+        || authority.contains(char::is_whitespace)
+        || user
+            .as_deref()
+            .is_some_and(|user| user.is_empty() || user.starts_with('-'))
+    {
+        return None;
+    }
+    Some((
+        SftpHost {
+            user,
+            host: host.to_string(),
+            port,
+        },
+        path.to_string(),
+    ))
+}
+
+/// The version of a remote file, as listed by the SFTP server. It's used to detect that the
+/// file was changed on the host since it was downloaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteVersion {
+    pub size: u64,
+    /// The modification time as listed by `ls -l`, which is only precise to the minute.
+    pub modified: String,
+    /// The modification time in seconds since the Unix epoch, for files whose host lets it be
+    /// read over SSH.
+    pub modified_seconds: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteEntry {
+    pub name: String,
+    pub is_dir: bool,
+    pub version: RemoteVersion,
+}
+
+/// Lists a directory of the host, with the directories first.
+pub async fn list_dir(host: &SftpHost, path: &str) -> Result<Vec<RemoteEntry>> {
+    let path = if path.is_empty() { "." } else { path };
+    let output = run_batch(host, &format!("ls -la {}\n", quote(path))).await?;
+    let mut entries = parse_listing(&output);
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// Lists a single file of the host, with its exact modification time when the host allows
+/// reading it.
+pub async fn stat(host: &SftpHost, path: &str) -> Result<RemoteEntry> {
+    let output = run_batch(host, &format!("ls -l {}\n", quote(path))).await?;
+    let mut entry = parse_listing(&output)
+        .into_iter()
+        .find(|entry| !entry.is_dir)
+        .with_context(|| format!("{path} is not a file on {host}"))?;
+    entry.version.modified_seconds = modified_seconds(host, path).await;
+    Ok(entry)
+}
+
+/// Reads the modification time of a file with `stat` over SSH, since `sftp` only lists it to the
+/// minute, which misses changes made within the minute a file was downloaded. Returns `None` on
+/// hosts that only allow SFTP.
+async fn modified_seconds(host: &SftpHost, path: &str) -> Option<u64> {
+    let path = shlex::try_quote(path).ok()?;
+    // GNU and BSD `stat` take different options.
+    let remote_command =
+        format!("stat -c %Y -- {path} 2>/dev/null || stat -f %m -- {path} 2>/dev/null");
+    let mut command = util::command::new_smol_command("ssh");
+    command.args(["-o", "BatchMode=yes", "-o", "ConnectTimeout=10"]);
+    if let Some(port) = host.port {
+        command.arg("-p").arg(port.to_string());
+    }
+    let output = command
+        .arg("--")
+        .arg(host.destination())
+        .arg(remote_command)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+pub async fn download(host: &SftpHost, remote_path: &str, local_path: &Path) -> Result<()> {
+    let local_path = local_path.to_string_lossy();
+    run_batch(
+        host,
+        &format!("get {} {}\n", quote(remote_path), quote(&local_path)),
+    )
+    .await
+    .with_context(|| format!("downloading {remote_path} from {host}"))?;
+    Ok(())
+}
+
+pub async fn upload(host: &SftpHost, local_path: &Path, remote_path: &str) -> Result<()> {
+    let local_path = local_path.to_string_lossy();
+    run_batch(
+        host,
+        &format!("put {} {}\n", quote(&local_path), quote(remote_path)),
+    )
+    .await
+    .with_context(|| format!("uploading {remote_path} to {host}"))?;
+    Ok(())
+}
+
+/// Where a remote file is downloaded to be edited, under a directory per host.
+pub fn local_copy_path(host: &SftpHost, remote_path: &str) -> PathBuf {
+    let host_dir = host.to_string().replace([':', '/', '\\'], "_");
+    let root = if remote_path.starts_with('/') {
+        "root"
+    } else {
+        "home"
+    };
+    let mut path = paths::sftp_files_dir().join(host_dir).join(root);
+    path.extend(
+        Path::new(remote_path)
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(component) => Some(component),
+                _ => None,
+            }),
+    );
+    path
+}
+
+/// Runs `sftp` in batch mode, which needs the host to be reachable without typing a password,
+/// like with a key loaded in the SSH agent.
+async fn run_batch(host: &SftpHost, commands: &str) -> Result<String> {
+    let mut command = util::command::new_smol_command("sftp");
+    command.args(["-q", "-b", "-", "-o", "ConnectTimeout=10"]);
+    if let Some(port) = host.port {
+        command.arg("-P").arg(port.to_string());
+    }
+    command
+        .arg("--")
+        .arg(host.destination())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = command.spawn().context("running sftp")?;
+    let mut stdin = child.stdin.take().context("opening the input of sftp")?;
+    stdin.write_all(commands.as_bytes()).await?;
+    drop(stdin);
+    let output = child.output().await.context("running sftp")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "sftp failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Quotes a path for a command of `sftp`, escaping the characters that it would otherwise
+/// expand as globs.
+fn quote(path: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in path.chars() {
+        if matches!(c, '"' | '\\' | '*' | '?' | '[' | ']') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Parses the output of `ls -l` in `sftp`, which lists one entry per line, like
+/// `-rw-r--r--    1 user  group   1234 Mar  4 10:15 /etc/hosts`. Symlinks are listed as files.
+fn parse_listing(output: &str) -> Vec<RemoteEntry> {
+    output.lines().filter_map(parse_listing_line).collect()
+}
+
+fn parse_listing_line(line: &str) -> Option<RemoteEntry> {
+    let is_dir = match line.chars().next()? {
+        'd' => true,
+        '-' | 'l' => false,
+        _ => return None,
+    };
+    let mut rest = line;
+    let mut fields = Vec::with_capacity(8);
+    for _ in 0..8 {
+        rest = rest.trim_start();
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    let name = rest.trim().rsplit('/').next()?;
+    if name.is_empty() || name == "." || name == ".." {
+        return None;
+    }
+    Some(RemoteEntry {
+        name: name.to_string(),
+        is_dir,
+        version: RemoteVersion {
+            size: fields[4].parse().ok()?,
+            modified: fields[5..8].join(" "),
+            modified_seconds: None,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_location() {
+        let host = |user: Option<&str>, host: &str, port| SftpHost {
+            user: user.map(str::to_string),
+            host: host.to_string(),
+            port,
+        };
+        assert_eq!(
+            parse_remote_location("alice@example.com:/etc/hosts"),
+            Some((
+                host(Some("alice"), "example.com", None),
+                "/etc/hosts".into()
+            ))
+        );
+        assert_eq!(
+            parse_remote_location("example.com:notes.txt"),
+            Some((host(None, "example.com", None), "notes.txt".into()))
+        );
+        assert_eq!(
+            parse_remote_location("sftp://bob@10.0.0.2:2222/var/log/"),
+            Some((
+                host(Some("bob"), "10.0.0.2", Some(2222)),
+                "/var/log/".into()
+            ))
+        );
+        assert_eq!(
+            parse_remote_location("sftp://bob@10.0.0.2/~/notes.txt"),
+            Some((host(Some("bob"), "10.0.0.2", None), "notes.txt".into()))
+        );
+        assert_eq!(parse_remote_location("sftp://example.com"), None);
+        assert_eq!(parse_remote_location("example.com"), None);
+        assert_eq!(parse_remote_location(":/etc/hosts"), None);
+        assert_eq!(parse_remote_location("-oProxyCommand=sh:/etc/hosts"), None);
+        assert_eq!(
+            parse_remote_location("sftp://-oProxyCommand=sh/etc/hosts"),
+            None
+        );
+        assert_eq!(parse_remote_location("-F@example.com:/etc/hosts"), None);
+
+        let with_port = host(None, "example.com", Some(2222));
+        for path in ["/etc/hosts", "notes.txt"] {
+            assert_eq!(
+                parse_remote_location(&with_port.location(path)),
+                Some((with_port.clone(), path.into()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_listing() {
+        let output = "\
+sftp> ls -la \"/srv/app/\"
+drwxr-xr-x    4 deploy   deploy       4096 Mar  4 10:15 /srv/app/.
+drwxr-xr-x    3 root     root         4096 Jan 12  2024 /srv/app/..
+-rw-r--r--    1 deploy   deploy        812 Mar  4 10:15 /srv/app/config file.toml
+drwxr-xr-x    2 deploy   deploy       4096 Feb 28 09:01 /srv/app/logs
+lrwxrwxrwx    1 deploy   deploy         11 Feb 28 09:01 /srv/app/current
+";
+        assert_eq!(
+            parse_listing(output),
+            vec![
+                RemoteEntry {
+                    name: "config file.toml".into(),
+                    is_dir: false,
+                    version: RemoteVersion {
+                        size: 812,
+                        modified: "Mar 4 10:15".into(),
+                        modified_seconds: None,
+                    },
+                },
+                RemoteEntry {
+                    name: "logs".into(),
+                    is_dir: true,
+                    version: RemoteVersion {
+                        size: 4096,
+                        modified: "Feb 28 09:01".into(),
+                        modified_seconds: None,
+                    },
+                },
+                RemoteEntry {
+                    name: "current".into(),
+                    is_dir: false,
+                    version: RemoteVersion {
+                        size: 11,
+                        modified: "Feb 28 09:01".into(),
+                        modified_seconds: None,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("/srv/app/a b.txt"), "\"/srv/app/a b.txt\"");
+        assert_eq!(quote("say \"hi\"*.md"), "\"say \\\"hi\\\"\\*.md\"");
+    }
+}
@@ -106,6 +106,10 @@ pub enum Event {
     BreadcrumbsChanged,
     CloseTerminal,
     Bell,
+    /// The task running in the terminal exited.
+    TaskFinished {
+        success: bool,
+    },
     Wakeup,
     BlinkChanged(bool),
     SelectionsChanged,
@@ -1797,6 +1801,9 @@ impl Terminal {
             unsafe { append_text_to_term(&mut self.term.lock(), &lines_to_show) };
         }
 
+        cx.emit(Event::TaskFinished {
+            success: finished_successfully,
+        });
         match task.hide {
             HideStrategy::Never => {}
             HideStrategy::Always => {
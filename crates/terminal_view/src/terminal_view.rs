@@ -39,7 +39,9 @@ use ui::{
 use util::{ResultExt, debug_panic, paths::PathWithPosition};
 use workspace::{
     CloseActiveItem, NewCenterTerminal, NewTerminal, OpenOptions, OpenVisible, ToolbarItemLocation,
-    Workspace, WorkspaceId, delete_unloaded_items,
+    Workspace, WorkspaceId,
+    bell::{self, BellEvent},
+    delete_unloaded_items,
     item::{
         BreadcrumbText, Item, ItemEvent, SerializableItem, TabContentParams, TabTooltipContent,
     },
@@ -965,6 +967,31 @@ fn subscribe_for_terminal_events(
                 Event::Bell => {
                     terminal_view.has_bell = true;
                     cx.emit(Event::Wakeup);
+                    // Tasks that keep running, like watch tasks, never exit to report a failed
+                    // run, so a bell they ring is taken as one.
+                    let task_running = terminal
+                        .read(cx)
+                        .task()
+                        .is_some_and(|task| task.status == TaskStatus::Running);
+                    bell::ring(
+                        if task_running {
+                            BellEvent::TaskFailed
+                        } else {
+                            BellEvent::Terminal
+                        },
+                        cx,
+                    );
+                }
+
+                Event::TaskFinished { success } => {
+                    bell::ring(
+                        if *success {
+                            BellEvent::TaskSucceeded
+                        } else {
+                            BellEvent::TaskFailed
+                        },
+                        cx,
+                    );
                 }
 
                 Event::BlinkChanged(blinking) => {
@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use chrono::Local;
+use gpui::{App, Global, Subscription, Task};
+use settings::{Settings, SettingsStore};
+
+use crate::{Appearance, SystemAppearance, ThemeSettings};
+
+/// How often to check the time of day, the power source, and the system's preferences for
+/// motion and contrast.
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Decides the appearance that themes in `system` mode follow, whether animations should be
+/// reduced, and whether themes should have a higher contrast.
+///
+/// The appearance is the system's, unless the theme schedule picks one by the time of day, or
+/// the battery saver switches to dark while running on battery. Motion and contrast follow the
+/// system's preferences, unless the settings override them.
+pub struct AppearanceController {
+    window_appearance: Appearance,
+    on_battery: bool,
+    system_reduced_motion: bool,
+    system_high_contrast: bool,
+    reduce_animations: bool,
+    high_contrast: bool,
+    _poll_task: Task<()>,
+    _settings_subscription: Subscription,
+}
+
+impl Global for AppearanceController {}
+
+impl AppearanceController {
+    /// Starts following the system's appearance, the time of day, and the power source.
+    pub fn init(cx: &mut App) {
+        let poll_task = cx.spawn(async move |cx| {
+            loop {
+                let Ok(battery_saver_enabled) =
+                    cx.update(|cx| ThemeSettings::get_global(cx).battery_saver.enabled)
+                else {
+                    break;
+                };
+                // Checking the power source runs a command on some platforms, so it's only done
+                // when the battery saver needs it.
+                let on_battery = battery_saver_enabled
+                    && cx
+                        .background_executor()
+                        .spawn(async { is_on_battery() })
+                        .await;
+                let updated = cx.update(|cx| {
+                    let reduced_motion = cx.prefers_reduced_motion();
+                    let high_contrast = cx.prefers_increased_contrast();
+                    Self::update(cx, |controller| {
+                        controller.on_battery = on_battery;
+                        controller.system_reduced_motion = reduced_motion;
+                        controller.system_high_contrast = high_contrast;
+                    })
+                });
+                if updated.is_err() {
+                    break;
+                }
+                cx.background_executor().timer(POLL_INTERVAL).await;
+            }
+        });
+        let settings_subscription =
+            cx.observe_global::<SettingsStore>(|cx| Self::update(cx, |_| {}));
+
+        cx.set_global(Self {
+            window_appearance: SystemAppearance::default_global(cx).0,
+            on_battery: false,
+            system_reduced_motion: cx.prefers_reduced_motion(),
+            system_high_contrast: cx.prefers_increased_contrast(),
+            reduce_animations: false,
+            high_contrast: false,
+            _poll_task: poll_task,
+            _settings_subscription: settings_subscription,
+        });
+        Self::update(cx, |_| {});
+    }
+
+    /// Records the system's appearance, as reported by a window.
+    pub fn set_window_appearance(appearance: Appearance, cx: &mut App) {
+        if cx.has_global::<Self>() {
+            Self::update(cx, |controller| controller.window_appearance = appearance);
+        } else {
+            set_appearance(appearance, cx);
+        }
+    }
+
+    /// Returns whether animations, like smooth cursor movement, should be skipped, either to save
+    /// power or because less motion was asked for.
+    pub fn reduce_animations(cx: &App) -> bool {
+        cx.try_global::<Self>()
+            .is_some_and(|controller| controller.reduce_animations)
+    }
+
+    /// Returns whether the system asks for a higher contrast, which themes follow unless the
+    /// settings override it.
+    pub fn system_prefers_high_contrast(cx: &App) -> bool {
+        cx.try_global::<Self>().map_or_else(
+            || cx.prefers_increased_contrast(),
+            |controller| controller.system_high_contrast,
+        )
+    }
+
+    fn update(cx: &mut App, update: impl FnOnce(&mut Self)) {
+        let theme_settings = ThemeSettings::get_global(cx);
+        let schedule = theme_settings.theme_schedule.clone();
+        let battery_saver = theme_settings.battery_saver;
+        let reduce_motion = theme_settings.reduce_motion;
+        let high_contrast = theme_settings.high_contrast;
+
+        let controller = cx.global_mut::<Self>();
+        update(controller);
+
+        let battery_saver_active = battery_saver.enabled && controller.on_battery;
+        let appearance = if battery_saver_active && battery_saver.dark_theme {
+            Appearance::Dark
+        } else {
+            schedule
+                .and_then(|schedule| schedule.appearance_at(Local::now().time()))
+                .unwrap_or(controller.window_appearance)
+        };
+        let reduce_animations = (battery_saver_active && battery_saver.reduce_animations)
+            || reduce_motion.is_on(controller.system_reduced_motion);
+        let animations_changed = controller.reduce_animations != reduce_animations;
+        controller.reduce_animations = reduce_animations;
+        let high_contrast = high_contrast.is_on(controller.system_high_contrast);
+        let contrast_changed = controller.high_contrast != high_contrast;
+        controller.high_contrast = high_contrast;
+
+        if SystemAppearance::global(cx).0 != appearance || contrast_changed {
+            set_appearance(appearance, cx);
+        } else if animations_changed {
+            cx.refresh_windows();
+        }
+    }
+}
+
+fn set_appearance(appearance: Appearance, cx: &mut App) {
+    *SystemAppearance::global_mut(cx) = SystemAppearance(appearance);
+    ThemeSettings::reload_current_theme(cx);
+    ThemeSettings::reload_current_icon_theme(cx);
+}
+
+/// Returns whether this machine is running on battery, as far as we can tell.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+fn is_on_battery() -> bool {
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+
+    let mut on_battery = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let read = |name: &str| {
+            std::fs::read_to_string(path.join(name))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+        match read("type").as_str() {
+            "Mains" | "USB" if read("online") == "1" => return false,
+            "Battery" if read("status") == "Discharging" => on_battery = true,
+            _ => {}
+        }
+    }
+    on_battery
+}
+
+/// Returns whether this machine is running on battery, as far as we can tell.
+#[cfg(target_os = "macos")]
+fn is_on_battery() -> bool {
+    util::command::new_std_command("pmset")
+        .args(["-g", "ps"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("'Battery Power'"))
+}
+
+/// Returns whether this machine is running on battery, as far as we can tell.
+#[cfg(not(any(target_os = "linux", target_os = "freebsd", target_os = "macos")))]
+fn is_on_battery() -> bool {
+    false
+}
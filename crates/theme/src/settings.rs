@@ -1,14 +1,18 @@
 use crate::fallback_themes::zed_default_dark;
 use crate::{
-    Appearance, DEFAULT_ICON_THEME_NAME, IconTheme, IconThemeNotFoundError, SyntaxTheme, Theme,
-    ThemeNotFoundError, ThemeRegistry, ThemeStyleContent,
+    Appearance, AppearanceController, DEFAULT_ICON_THEME_NAME, HighlightStyleContent, IconTheme,
+    IconThemeNotFoundError, SyntaxTheme, Theme, ThemeNotFoundError, ThemeRegistry,
+    ThemeStyleContent, syntax_styles,
 };
 use anyhow::Result;
+use chrono::NaiveTime;
+use collections::HashMap;
 use derive_more::{Deref, DerefMut};
 use gpui::{
-    App, Context, Font, FontFallbacks, FontFeatures, FontStyle, FontWeight, Global, Pixels,
+    App, Context, Font, FontFallbacks, FontFeatures, FontStyle, FontWeight, Global, Hsla, Pixels,
     Subscription, Window, px,
 };
+use indexmap::IndexMap;
 use refineable::Refineable;
 use schemars::{
     JsonSchema,
@@ -40,7 +44,6 @@ const MIN_LINE_HEIGHT: f32 = 1.0;
 )]
 
 /// Specifies the density of the UI.
-/// Note: This setting is still experimental. See [this tracking issue](https://github.com/zed-industries/zed/issues/18078)
 #[serde(rename_all = "snake_case")]
 pub enum UiDensity {
     /// A denser UI with tighter spacing and smaller elements.
@@ -65,6 +68,15 @@ impl UiDensity {
             UiDensity::Comfortable => 1.25,
         }
     }
+
+    /// How many pixels to add to fixed UI text sizes at this density.
+    pub fn text_size_offset(self) -> f32 {
+        match self {
+            UiDensity::Compact => -1.,
+            UiDensity::Default => 0.,
+            UiDensity::Comfortable => 1.,
+        }
+    }
 }
 
 impl From<String> for UiDensity {
@@ -122,12 +134,27 @@ pub struct ThemeSettings {
     ///
     /// Note: This setting is still experimental. See [this tracking issue](https://github.com/zed-industries/zed/issues/18078)
     pub theme_overrides: Option<ThemeStyleContent>,
+    /// Manual overrides for the syntax styles of the active theme in particular languages,
+    /// keyed by language name.
+    ///
+    /// Note: This setting is still experimental. See [this tracking issue](https://github.com/zed-industries/zed/issues/18078)
+    pub language_syntax_overrides: HashMap<String, IndexMap<String, HighlightStyleContent>>,
     /// The current icon theme selection.
     pub icon_theme_selection: Option<IconThemeSelection>,
     /// The active icon theme.
     pub active_icon_theme: Arc<IconTheme>,
-    /// The density of the UI.
-    /// Note: This setting is still experimental. See [this tracking issue](
+    /// The times of day at which themes in `system` mode switch between light and dark.
+    pub theme_schedule: Option<ThemeSchedule>,
+    /// How the appearance changes while running on battery.
+    pub battery_saver: BatterySaver,
+    /// Whether to skip animations, like smooth cursor movement and scrolling.
+    pub reduce_motion: SystemPreference,
+    /// Whether to make selections and diagnostics stand out more.
+    pub high_contrast: SystemPreference,
+    /// Whether the active theme has been given a higher contrast, by [`Self::high_contrast`]
+    /// and the system's preference.
+    pub(crate) high_contrast_active: bool,
+    /// The density of the UI, which scales paddings, row heights, and text sizes.
     pub ui_density: UiDensity,
     /// The amount of fading applied to unnecessary code.
     pub unnecessary_code_fade: f32,
@@ -151,6 +178,9 @@ impl ThemeSettings {
     /// taking into account the current [`SystemAppearance`].
     pub fn reload_current_theme(cx: &mut App) {
         let mut theme_settings = ThemeSettings::get_global(cx).clone();
+        theme_settings.high_contrast_active = theme_settings
+            .high_contrast
+            .is_on(AppearanceController::system_prefers_high_contrast(cx));
         let system_appearance = SystemAppearance::global(cx);
 
         if let Some(theme_selection) = theme_settings.theme_selection.clone() {
@@ -258,6 +288,18 @@ pub(crate) struct AgentFontSize(Pixels);
 
 impl Global for AgentFontSize {}
 
+/// A temporary factor applied to all font sizes, on top of their adjustments.
+struct FontScale(f32);
+
+impl Global for FontScale {}
+
+fn scale_font_size(size: Pixels, cx: &App) -> Pixels {
+    match cx.try_global::<FontScale>() {
+        Some(FontScale(scale)) => size * *scale,
+        None => size,
+    }
+}
+
 /// Represents the selection of a theme, which can be either static or dynamic.
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(untagged)]
@@ -297,11 +339,95 @@ pub enum ThemeMode {
     /// Use the specified `dark` theme.
     Dark,
 
-    /// Use the theme based on the system's appearance.
+    /// Use the theme based on the system's appearance, or on `theme_schedule` and
+    /// `battery_saver` when they are set.
     #[default]
     System,
 }
 
+/// The times of day at which themes in [`ThemeMode::System`] switch between light and dark,
+/// instead of following the system's appearance.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ThemeSchedule {
+    /// When to switch to the light theme, as `HH:MM` in local time.
+    pub light: String,
+    /// When to switch to the dark theme, as `HH:MM` in local time.
+    pub dark: String,
+}
+
+impl ThemeSchedule {
+    /// Returns the appearance scheduled for the given time of day, or `None` if the schedule
+    /// can't be parsed.
+    pub fn appearance_at(&self, time: NaiveTime) -> Option<Appearance> {
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+        let light = parse(&self.light)?;
+        let dark = parse(&self.dark)?;
+        let is_light = if light <= dark {
+            light <= time && time < dark
+        } else {
+            // The light period wraps around midnight.
+            time >= light || time < dark
+        };
+        Some(if is_light {
+            Appearance::Light
+        } else {
+            Appearance::Dark
+        })
+    }
+}
+
+/// How the appearance changes while running on battery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatterySaver {
+    /// Whether the battery saver is enabled.
+    pub enabled: bool,
+    /// Whether to switch themes in `system` mode to dark.
+    pub dark_theme: bool,
+    /// Whether to skip animations, like smooth cursor movement.
+    pub reduce_animations: bool,
+}
+
+/// How the appearance changes while running on battery.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BatterySaverContent {
+    /// Whether the battery saver is enabled.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// Whether to switch themes in `system` mode to dark.
+    ///
+    /// Default: false
+    pub dark_theme: Option<bool>,
+    /// Whether to skip animations, like smooth cursor movement.
+    ///
+    /// Default: true
+    pub reduce_animations: Option<bool>,
+}
+
+/// Whether to follow a preference of the system, like reduced motion, or to override it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemPreference {
+    /// Follow the system's preference.
+    #[default]
+    System,
+    /// Always on.
+    On,
+    /// Always off.
+    Off,
+}
+
+impl SystemPreference {
+    /// Returns whether the preference is on, given whether the system prefers it.
+    pub fn is_on(self, system_prefers: bool) -> bool {
+        match self {
+            Self::System => system_prefers,
+            Self::On => true,
+            Self::Off => false,
+        }
+    }
+}
+
 impl ThemeSelection {
     /// Returns the theme name for the selected [ThemeMode].
     pub fn theme(&self, system_appearance: Appearance) -> &str {
@@ -425,11 +551,28 @@ pub struct ThemeSettingsContent {
     /// The name of the icon theme to use.
     #[serde(default)]
     pub icon_theme: Option<IconThemeSelection>,
+    /// The times of day at which themes in `system` mode switch between light and dark,
+    /// instead of following the system's appearance.
+    #[serde(default)]
+    pub theme_schedule: Option<ThemeSchedule>,
+    /// How the appearance changes while running on battery.
+    #[serde(default)]
+    pub battery_saver: Option<BatterySaverContent>,
+    /// Whether to skip animations, like smooth cursor movement and scrolling.
+    ///
+    /// Default: system
+    #[serde(default)]
+    pub reduce_motion: Option<SystemPreference>,
+    /// Whether to make selections and diagnostics stand out more.
+    ///
+    /// Default: system
+    #[serde(default)]
+    pub high_contrast: Option<SystemPreference>,
 
-    /// UNSTABLE: Expect many elements to be broken.
+    /// The density of the UI, which scales paddings, row heights, and text sizes.
     ///
-    // Controls the density of the UI.
-    #[serde(rename = "unstable.ui_density", default)]
+    /// Default: default
+    #[serde(alias = "unstable.ui_density", default)]
     pub ui_density: Option<UiDensity>,
 
     /// How much to fade out unused code.
@@ -441,6 +584,13 @@ pub struct ThemeSettingsContent {
     /// These values will override the ones on the current theme specified in `theme`.
     #[serde(rename = "experimental.theme_overrides", default)]
     pub theme_overrides: Option<ThemeStyleContent>,
+
+    /// EXPERIMENTAL: Overrides for the syntax styles of the current theme in particular
+    /// languages, keyed by language name.
+    ///
+    /// These apply on top of `experimental.theme_overrides`, to only the given language.
+    #[serde(rename = "experimental.language_syntax_overrides", default)]
+    pub language_syntax_overrides: HashMap<String, IndexMap<String, HighlightStyleContent>>,
 }
 
 fn default_font_features() -> Option<FontFeatures> {
@@ -589,7 +739,7 @@ impl ThemeSettings {
             .try_global::<BufferFontSize>()
             .map(|size| size.0)
             .unwrap_or(self.buffer_font_size);
-        clamp_font_size(font_size)
+        clamp_font_size(scale_font_size(font_size, cx))
     }
 
     /// Returns the UI font size.
@@ -598,7 +748,7 @@ impl ThemeSettings {
             .try_global::<UiFontSize>()
             .map(|size| size.0)
             .unwrap_or(self.ui_font_size);
-        clamp_font_size(font_size)
+        clamp_font_size(scale_font_size(font_size, cx))
     }
 
     /// Returns the UI font size.
@@ -607,7 +757,7 @@ impl ThemeSettings {
             .try_global::<AgentFontSize>()
             .map(|size| size.0)
             .unwrap_or(self.agent_font_size);
-        clamp_font_size(font_size)
+        clamp_font_size(scale_font_size(font_size, cx))
     }
 
     /// Returns the buffer font size, read from the settings.
@@ -658,9 +808,15 @@ impl ThemeSettings {
 
     /// Applies the theme overrides, if there are any, to the current theme.
     pub fn apply_theme_overrides(&mut self) {
-        if let Some(theme_overrides) = &self.theme_overrides {
-            let mut base_theme = (*self.active_theme).clone();
+        if self.theme_overrides.is_none()
+            && self.language_syntax_overrides.is_empty()
+            && !self.high_contrast_active
+        {
+            return;
+        }
 
+        let mut base_theme = (*self.active_theme).clone();
+        if let Some(theme_overrides) = &self.theme_overrides {
             if let Some(window_background_appearance) = theme_overrides.window_background_appearance
             {
                 base_theme.styles.window_background_appearance =
@@ -679,9 +835,36 @@ impl ThemeSettings {
             base_theme.styles.accents.merge(&theme_overrides.accents);
             base_theme.styles.syntax =
                 SyntaxTheme::merge(base_theme.styles.syntax, theme_overrides.syntax_overrides());
+        }
+
+        for (language, syntax) in &self.language_syntax_overrides {
+            let overrides = syntax_styles(syntax);
+            // Language styles start out as the theme's style for the same node, so that
+            // they only change what they set.
+            let inherited = overrides
+                .iter()
+                .map(|(name, _)| {
+                    (
+                        SyntaxTheme::language_style_key(language, name),
+                        base_theme.styles.syntax.get(name),
+                    )
+                })
+                .collect();
+            let overrides = overrides
+                .into_iter()
+                .map(|(name, style)| (SyntaxTheme::language_style_key(language, &name), style))
+                .collect();
+            base_theme.styles.syntax = SyntaxTheme::merge(
+                SyntaxTheme::merge(base_theme.styles.syntax, inherited),
+                overrides,
+            );
+        }
 
-            self.active_theme = Arc::new(base_theme);
+        if self.high_contrast_active {
+            increase_contrast(&mut base_theme);
         }
+
+        self.active_theme = Arc::new(base_theme);
     }
 
     /// Switches to the icon theme with the given name, if it exists.
@@ -721,7 +904,7 @@ pub fn adjusted_font_size(size: Pixels, cx: &App) -> Pixels {
         } else {
             size
         };
-    clamp_font_size(adjusted_font_size)
+    clamp_font_size(scale_font_size(adjusted_font_size, cx))
 }
 
 /// Adjusts the buffer font size.
@@ -759,7 +942,7 @@ pub fn setup_ui_font(window: &mut Window, cx: &mut App) -> gpui::Font {
 
 /// Sets the adjusted UI font size.
 pub fn adjust_ui_font_size(cx: &mut App, mut f: impl FnMut(&mut Pixels)) {
-    let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
+    let ui_font_size = ThemeSettings::get_global(cx).ui_font_size;
     let mut adjusted_size = cx
         .try_global::<UiFontSize>()
         .map_or(ui_font_size, |adjusted_size| adjusted_size.0);
@@ -779,7 +962,7 @@ pub fn reset_ui_font_size(cx: &mut App) {
 
 /// Sets the adjusted UI font size.
 pub fn adjust_agent_font_size(cx: &mut App, mut f: impl FnMut(&mut Pixels)) {
-    let agent_font_size = ThemeSettings::get_global(cx).agent_font_size(cx);
+    let agent_font_size = ThemeSettings::get_global(cx).agent_font_size;
     let mut adjusted_size = cx
         .try_global::<AgentFontSize>()
         .map_or(agent_font_size, |adjusted_size| adjusted_size.0);
@@ -797,6 +980,21 @@ pub fn reset_agent_font_size(cx: &mut App) {
     }
 }
 
+/// Temporarily scales the UI, buffer, and agent font sizes by the given factor, keeping any
+/// adjustments made to them.
+pub fn set_font_scale(scale: f32, cx: &mut App) {
+    cx.set_global(FontScale(scale.max(0.1)));
+    cx.refresh_windows();
+}
+
+/// Stops scaling the font sizes, restoring them to what they were before [`set_font_scale`].
+pub fn reset_font_scale(cx: &mut App) {
+    if cx.has_global::<FontScale>() {
+        cx.remove_global::<FontScale>();
+        cx.refresh_windows();
+    }
+}
+
 /// Ensures font size is within the valid range.
 pub fn clamp_font_size(size: Pixels) -> Pixels {
     size.max(MIN_FONT_SIZE)
@@ -847,6 +1045,7 @@ impl settings::Settings for ThemeSettings {
                 .or(themes.get(&zed_default_dark().name))
                 .unwrap(),
             theme_overrides: None,
+            language_syntax_overrides: HashMap::default(),
             icon_theme_selection: defaults.icon_theme.clone(),
             active_icon_theme: defaults
                 .icon_theme
@@ -857,6 +1056,18 @@ impl settings::Settings for ThemeSettings {
                         .ok()
                 })
                 .unwrap_or_else(|| themes.get_icon_theme(DEFAULT_ICON_THEME_NAME).unwrap()),
+            theme_schedule: defaults.theme_schedule.clone(),
+            battery_saver: {
+                let defaults = defaults.battery_saver.clone().unwrap_or_default();
+                BatterySaver {
+                    enabled: defaults.enabled.unwrap_or(false),
+                    dark_theme: defaults.dark_theme.unwrap_or(false),
+                    reduce_animations: defaults.reduce_animations.unwrap_or(true),
+                }
+            },
+            reduce_motion: defaults.reduce_motion.unwrap_or_default(),
+            high_contrast: defaults.high_contrast.unwrap_or_default(),
+            high_contrast_active: false,
             ui_density: defaults.ui_density.unwrap_or(UiDensity::Default),
             unnecessary_code_fade: defaults.unnecessary_code_fade.unwrap_or(0.0),
         };
@@ -865,6 +1076,7 @@ impl settings::Settings for ThemeSettings {
             .user
             .into_iter()
             .chain(sources.release_channel)
+            .chain(sources.profile)
             .chain(sources.server)
         {
             if let Some(value) = value.ui_density {
@@ -914,7 +1126,14 @@ impl settings::Settings for ThemeSettings {
                 }
             }
 
+            merge(&mut this.reduce_motion, value.reduce_motion);
+            merge(&mut this.high_contrast, value.high_contrast);
+            this.high_contrast_active = this
+                .high_contrast
+                .is_on(AppearanceController::system_prefers_high_contrast(cx));
             this.theme_overrides.clone_from(&value.theme_overrides);
+            this.language_syntax_overrides
+                .clone_from(&value.language_syntax_overrides);
             this.apply_theme_overrides();
 
             if let Some(value) = &value.icon_theme {
@@ -934,6 +1153,18 @@ impl settings::Settings for ThemeSettings {
                 }
             }
 
+            if let Some(value) = &value.theme_schedule {
+                this.theme_schedule = Some(value.clone());
+            }
+            if let Some(value) = &value.battery_saver {
+                merge(&mut this.battery_saver.enabled, value.enabled);
+                merge(&mut this.battery_saver.dark_theme, value.dark_theme);
+                merge(
+                    &mut this.battery_saver.reduce_animations,
+                    value.reduce_animations,
+                );
+            }
+
             merge(&mut this.ui_font_size, value.ui_font_size.map(Into::into));
             this.ui_font_size = this.ui_font_size.clamp(px(6.), px(100.));
 
@@ -1017,6 +1248,57 @@ impl settings::Settings for ThemeSettings {
     }
 }
 
+/// Makes selections, highlights, and diagnostics stand out more from the background.
+///
+/// Only ever raises opacities, so that applying it again changes nothing.
+fn increase_contrast(theme: &mut Theme) {
+    fn raise_opacity(color: &mut Hsla, min_opacity: f32) {
+        color.a = color.a.max(min_opacity);
+    }
+
+    for player in &mut theme.styles.player.0 {
+        raise_opacity(&mut player.selection, 0.5);
+    }
+
+    let colors = &mut theme.styles.colors;
+    for color in [
+        &mut colors.editor_document_highlight_read_background,
+        &mut colors.editor_document_highlight_write_background,
+        &mut colors.editor_document_highlight_bracket_background,
+        &mut colors.search_match_background,
+    ] {
+        raise_opacity(color, 0.4);
+    }
+
+    let status = &mut theme.styles.status;
+    for (color, background, border) in [
+        (
+            &mut status.error,
+            &mut status.error_background,
+            &mut status.error_border,
+        ),
+        (
+            &mut status.warning,
+            &mut status.warning_background,
+            &mut status.warning_border,
+        ),
+        (
+            &mut status.info,
+            &mut status.info_background,
+            &mut status.info_border,
+        ),
+        (
+            &mut status.hint,
+            &mut status.hint_background,
+            &mut status.hint_border,
+        ),
+    ] {
+        raise_opacity(color, 1.);
+        raise_opacity(background, 0.35);
+        raise_opacity(border, 1.);
+    }
+}
+
 fn merge<T: Copy>(target: &mut T, value: Option<T>) {
     if let Some(value) = value {
         *target = value;
@@ -1028,6 +1310,53 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_theme_schedule_appearance() {
+        let at = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+
+        let day = ThemeSchedule {
+            light: "08:00".into(),
+            dark: "19:00".into(),
+        };
+        assert_eq!(day.appearance_at(at(7, 59)), Some(Appearance::Dark));
+        assert_eq!(day.appearance_at(at(8, 0)), Some(Appearance::Light));
+        assert_eq!(day.appearance_at(at(18, 59)), Some(Appearance::Light));
+        assert_eq!(day.appearance_at(at(19, 0)), Some(Appearance::Dark));
+
+        let night_shift = ThemeSchedule {
+            light: "22:00".into(),
+            dark: "06:30".into(),
+        };
+        assert_eq!(
+            night_shift.appearance_at(at(23, 0)),
+            Some(Appearance::Light)
+        );
+        assert_eq!(night_shift.appearance_at(at(3, 0)), Some(Appearance::Light));
+        assert_eq!(night_shift.appearance_at(at(12, 0)), Some(Appearance::Dark));
+
+        let invalid = ThemeSchedule {
+            light: "8am".into(),
+            dark: "19:00".into(),
+        };
+        assert_eq!(invalid.appearance_at(at(12, 0)), None);
+    }
+
+    #[test]
+    fn test_increase_contrast() {
+        let mut theme = zed_default_dark();
+        theme.styles.player.0[0].selection.a = 0.2;
+        theme.styles.status.error_background.a = 0.6;
+
+        increase_contrast(&mut theme);
+        assert_eq!(theme.styles.player.0[0].selection.a, 0.5);
+        assert_eq!(theme.styles.status.error_background.a, 0.6);
+
+        let increased = theme.clone();
+        increase_contrast(&mut theme);
+        assert_eq!(theme.styles.player.0, increased.styles.player.0);
+        assert_eq!(theme.styles.status, increased.styles.status);
+    }
+
     #[test]
     fn test_buffer_line_height_deserialize_valid() {
         assert_eq!(
@@ -4,6 +4,10 @@ use std::sync::Arc;
 
 use gpui::{HighlightStyle, Hsla};
 
+/// Separates the language from the syntax node in the keys of styles that only apply to one
+/// language, like `YAML/string`. Syntax node names never contain it.
+const LANGUAGE_STYLE_SEPARATOR: char = '/';
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct SyntaxTheme {
     pub highlights: Vec<(String, HighlightStyle)>,
@@ -46,6 +50,18 @@ impl SyntaxTheme {
         self.get(name).color.unwrap_or_default()
     }
 
+    /// Returns the key for a style that only applies to syntax nodes named `name` in the
+    /// language named `language`.
+    pub fn language_style_key(language: &str, name: &str) -> String {
+        format!("{language}{LANGUAGE_STYLE_SEPARATOR}{name}")
+    }
+
+    /// Splits the key of a style that only applies to one language into the name of that
+    /// language and the name of the syntax node.
+    pub fn parse_language_style_key(key: &str) -> Option<(&str, &str)> {
+        key.split_once(LANGUAGE_STYLE_SEPARATOR)
+    }
+
     pub fn highlight_id(&self, name: &str) -> Option<u32> {
         let ix = self.highlights.iter().position(|entry| entry.0 == name)?;
         Some(ix as u32)
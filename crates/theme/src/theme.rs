@@ -8,6 +8,7 @@
 //!
 //! A theme is a collection of colors used to build a consistent appearance for UI components across the application.
 
+mod appearance_controller;
 mod default_colors;
 mod fallback_themes;
 mod font_family_cache;
@@ -34,6 +35,7 @@ use gpui::{
 use serde::Deserialize;
 use uuid::Uuid;
 
+pub use crate::appearance_controller::*;
 pub use crate::default_colors::*;
 pub use crate::font_family_cache::*;
 pub use crate::icon_theme::*;
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use call::{ActiveCall, ParticipantLocation, Room};
+use call::{ActiveCall, ParticipantLocation, Room, participant::ParticipantActivity};
 use client::{User, proto::PeerId};
 use gpui::{AnyElement, Hsla, IntoElement, MouseButton, Path, Styled, canvas, point};
 use gpui::{App, Task, Window, actions};
@@ -13,7 +13,13 @@ use crate::TitleBar;
 
 actions!(
     collab,
-    [ToggleScreenSharing, ToggleMute, ToggleDeafen, LeaveCall]
+    [
+        ToggleScreenSharing,
+        ToggleLaserPointer,
+        ToggleMute,
+        ToggleDeafen,
+        LeaveCall
+    ]
 );
 
 fn toggle_screen_sharing(_: &ToggleScreenSharing, window: &mut Window, cx: &mut App) {
@@ -40,6 +46,12 @@ fn toggle_screen_sharing(_: &ToggleScreenSharing, window: &mut Window, cx: &mut
     }
 }
 
+fn toggle_laser_pointer(_: &ToggleLaserPointer, cx: &mut App) {
+    if let Some(room) = ActiveCall::global(cx).read(cx).room().cloned() {
+        room.update(cx, |room, cx| room.toggle_laser_pointer(cx));
+    }
+}
+
 fn toggle_mute(_: &ToggleMute, cx: &mut App) {
     let call = ActiveCall::global(cx).read(cx);
     if let Some(room) = call.room().cloned() {
@@ -123,6 +135,7 @@ impl TitleBar {
                         true,
                         room.is_speaking(),
                         room.is_muted(),
+                        room.local_activity(),
                         None,
                         room,
                         project_id,
@@ -154,6 +167,7 @@ impl TitleBar {
                             is_present,
                             collaborator.speaking,
                             collaborator.muted,
+                            &collaborator.activity,
                             is_following.then_some(player_color.selection),
                             room,
                             project_id,
@@ -198,6 +212,7 @@ impl TitleBar {
         is_present: bool,
         is_speaking: bool,
         is_muted: bool,
+        activity: &ParticipantActivity,
         leader_selection_color: Option<Hsla>,
         room: &Room,
         project_id: Option<u64>,
@@ -212,10 +227,25 @@ impl TitleBar {
         let followers = project_id.map_or(&[] as &[_], |id| room.followers_for(peer_id, id));
         let extra_count = followers.len().saturating_sub(FACEPILE_LIMIT);
 
+        let activity_badge = if let Some(task) = &activity.running_task {
+            Some((
+                IconName::Play,
+                format!("{} is running {task}", user.github_login),
+            ))
+        } else if activity.typing {
+            Some((
+                IconName::Keyboard,
+                format!("{} is typing", user.github_login),
+            ))
+        } else {
+            None
+        };
+
         Some(
-            div()
+            h_flex()
                 .m_0p5()
                 .p_0p5()
+                .gap_0p5()
                 // When the collaborator is not followed, still draw this wrapper div, but leave
                 // it transparent, so that it does not shift the layout when following.
                 .when_some(leader_selection_color, |div, color| {
@@ -271,7 +301,15 @@ impl TitleBar {
                         } else {
                             None
                         }),
-                ),
+                )
+                .when_some(activity_badge, |this, (icon, tooltip)| {
+                    this.child(
+                        div()
+                            .id(("collaborator-activity", user.id))
+                            .child(Icon::new(icon).size(IconSize::XSmall).color(Color::Muted))
+                            .tooltip(Tooltip::text(tooltip)),
+                    )
+                }),
         )
     }
 
@@ -297,6 +335,7 @@ impl TitleBar {
         let muted_by_user = room.muted_by_user();
         let is_deafened = room.is_deafened().unwrap_or(false);
         let is_screen_sharing = room.is_screen_sharing();
+        let is_laser_pointer_enabled = room.is_laser_pointer_enabled();
         let can_use_microphone = room.can_use_microphone();
         let can_share_projects = room.can_share_projects();
         let screen_sharing_supported = cx.is_screen_capture_supported();
@@ -439,6 +478,23 @@ impl TitleBar {
             );
         }
 
+        if is_screen_sharing {
+            children.push(
+                IconButton::new("laser-pointer", ui::IconName::Crosshair)
+                    .style(ButtonStyle::Subtle)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(is_laser_pointer_enabled)
+                    .selected_style(ButtonStyle::Tinted(TintColor::Accent))
+                    .tooltip(Tooltip::text(if is_laser_pointer_enabled {
+                        "Hide Laser Pointer"
+                    } else {
+                        "Show Laser Pointer"
+                    }))
+                    .on_click(move |_, _, cx| toggle_laser_pointer(&Default::default(), cx))
+                    .into_any_element(),
+            );
+        }
+
         children.push(div().pr_2().into_any_element());
 
         children
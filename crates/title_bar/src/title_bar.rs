@@ -23,10 +23,10 @@ use gpui::{
     Action, AnyElement, App, Context, Corner, Decorations, Element, Entity, InteractiveElement,
     Interactivity, IntoElement, MouseButton, ParentElement, Render, Stateful,
     StatefulInteractiveElement, Styled, Subscription, WeakEntity, Window, WindowControlArea,
-    actions, div, px,
+    accesskit::Role, actions, div, px,
 };
 use onboarding_banner::OnboardingBanner;
-use project::Project;
+use project::{CollabSyncStatus, Project};
 use rpc::proto;
 use settings::Settings as _;
 use smallvec::SmallVec;
@@ -143,6 +143,7 @@ impl Render for TitleBar {
         };
 
         h_flex()
+            .accessibility(Role::Banner, "Title Bar")
             .id("titlebar")
             .window_control_area(WindowControlArea::Drag)
             .w_full()
@@ -467,17 +468,59 @@ impl TitleBar {
             return self.render_ssh_project_host(cx);
         }
 
-        if self.project.read(cx).is_disconnected(cx) {
+        let project = self.project.read(cx);
+        let edited_offline = match project.buffers_edited_offline() {
+            0 => None,
+            1 => Some("1 file".to_string()),
+            count => Some(format!("{count} files")),
+        };
+        if project.is_disconnected(cx) {
             return Some(
                 Button::new("disconnected", "Disconnected")
                     .disabled(true)
                     .color(Color::Disabled)
                     .style(ButtonStyle::Subtle)
                     .label_size(LabelSize::Small)
+                    .when_some(edited_offline, |button, files| {
+                        button.tooltip(Tooltip::text(format!(
+                            "Edits to {files} made while offline didn't reach the host"
+                        )))
+                    })
                     .into_any_element(),
             );
         }
 
+        match project.collab_sync_status() {
+            CollabSyncStatus::Synced => {}
+            CollabSyncStatus::Offline => {
+                let message = match edited_offline {
+                    Some(files) => format!(
+                        "Lost connection to the host. Edits to {files} will sync when you reconnect."
+                    ),
+                    None => "Lost connection to the host. Your edits will sync when you reconnect."
+                        .to_string(),
+                };
+                return Some(
+                    Button::new("collab-offline", "Offline")
+                        .color(Color::Warning)
+                        .style(ButtonStyle::Subtle)
+                        .label_size(LabelSize::Small)
+                        .tooltip(Tooltip::text(message))
+                        .into_any_element(),
+                );
+            }
+            CollabSyncStatus::Syncing => {
+                return Some(
+                    Button::new("collab-syncing", "Syncing…")
+                        .disabled(true)
+                        .color(Color::Muted)
+                        .style(ButtonStyle::Subtle)
+                        .label_size(LabelSize::Small)
+                        .into_any_element(),
+                );
+            }
+        }
+
         let host = self.project.read(cx).host()?;
         let host_user = self.user_store.read(cx).get_cached_user(host.user_id)?;
         let participant_index = self
@@ -0,0 +1,484 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use collections::{HashMap, HashSet};
+use gpui::{Context, Entity, SharedString, Subscription, Task, WeakEntity};
+use language::{Buffer, BufferEvent, BufferId, BufferSnapshot};
+use project::{
+    PathChange, Project, ProjectPath, UpdatedEntriesSet, WorktreeId,
+    buffer_store::BufferStoreEvent,
+    search::{SearchQuery, SearchResult},
+};
+use regex::Regex;
+use util::ResultExt as _;
+
+/// How long to wait after the worktrees change before searching them again.
+const RESCAN_DEBOUNCE: Duration = Duration::from_secs(1);
+/// How long to wait after files change on disk before scanning them again.
+const CHANGED_PATHS_DEBOUNCE: Duration = Duration::from_millis(500);
+/// How long to wait after a buffer is edited before scanning it again.
+const BUFFER_SCAN_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// A line marked with one of the tags.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TodoItem {
+    pub tag: SharedString,
+    pub text: SharedString,
+    pub row: u32,
+}
+
+/// The tagged lines of the project's files, found by searching the worktrees and kept up to date
+/// as the open buffers are edited.
+pub struct TodoIndex {
+    project: Entity<Project>,
+    tags: Vec<String>,
+    pattern: Option<Regex>,
+    files: BTreeMap<ProjectPath, Vec<TodoItem>>,
+    rescan_task: Task<()>,
+    /// Files changed on disk since they were last scanned.
+    changed_paths: HashSet<ProjectPath>,
+    changed_paths_task: Task<()>,
+    buffer_scans: HashMap<BufferId, Task<()>>,
+    buffer_subscriptions: HashMap<BufferId, Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TodoIndex {
+    pub fn new(project: Entity<Project>, tags: Vec<String>, cx: &mut Context<Self>) -> Self {
+        let buffer_store = project.read(cx).buffer_store().clone();
+        let subscriptions = vec![
+            cx.subscribe(&buffer_store, |this, _, event, cx| match event {
+                BufferStoreEvent::BufferAdded(buffer) => this.watch_buffer(buffer, cx),
+                BufferStoreEvent::BufferDropped(buffer_id) => {
+                    this.buffer_subscriptions.remove(buffer_id);
+                    this.buffer_scans.remove(buffer_id);
+                }
+                _ => {}
+            }),
+            cx.subscribe(&project, |this, _, event, cx| match event {
+                project::Event::WorktreeAdded(_) => this.rescan(Some(RESCAN_DEBOUNCE), cx),
+                project::Event::WorktreeRemoved(worktree_id) => {
+                    this.files
+                        .retain(|path, _| path.worktree_id != *worktree_id);
+                    cx.notify();
+                }
+                project::Event::WorktreeUpdatedEntries(worktree_id, changes) => {
+                    this.update_changed_entries(*worktree_id, changes, cx)
+                }
+                _ => {}
+            }),
+        ];
+
+        let mut this = Self {
+            pattern: tag_pattern(&tags),
+            project: project.clone(),
+            tags,
+            files: BTreeMap::new(),
+            rescan_task: Task::ready(()),
+            changed_paths: HashSet::default(),
+            changed_paths_task: Task::ready(()),
+            buffer_scans: HashMap::default(),
+            buffer_subscriptions: HashMap::default(),
+            _subscriptions: subscriptions,
+        };
+        for buffer in project.read(cx).opened_buffers(cx) {
+            this.watch_buffer(&buffer, cx);
+        }
+        this.rescan(None, cx);
+        this
+    }
+
+    /// The files with tagged lines, ordered by their path.
+    pub fn files(&self) -> impl Iterator<Item = (&ProjectPath, &[TodoItem])> {
+        self.files
+            .iter()
+            .map(|(path, items)| (path, items.as_slice()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn set_tags(&mut self, tags: Vec<String>, cx: &mut Context<Self>) {
+        if self.tags != tags {
+            self.pattern = tag_pattern(&tags);
+            self.tags = tags;
+            self.rescan(None, cx);
+        }
+    }
+
+    fn rescan(&mut self, delay: Option<Duration>, cx: &mut Context<Self>) {
+        let Some(pattern) = self.pattern.clone() else {
+            self.rescan_task = Task::ready(());
+            self.files.clear();
+            cx.notify();
+            return;
+        };
+        let query = SearchQuery::regex(
+            search_pattern(&self.tags),
+            false,
+            true,
+            false,
+            true,
+            Default::default(),
+            Default::default(),
+            false,
+            None,
+        );
+        let Some(query) = query.log_err() else {
+            return;
+        };
+        let project = self.project.clone();
+        self.rescan_task = cx.spawn(async move |this, cx| {
+            if let Some(delay) = delay {
+                cx.background_executor().timer(delay).await;
+            }
+            let Ok(results) = project.update(cx, |project, cx| project.search(query, cx)) else {
+                return;
+            };
+            let mut files = BTreeMap::new();
+            while let Ok(result) = results.recv().await {
+                let SearchResult::Buffer { buffer, .. } = result else {
+                    continue;
+                };
+                let Some((path, snapshot)) = buffer_snapshot(&buffer, cx) else {
+                    continue;
+                };
+                let pattern = pattern.clone();
+                let items = cx
+                    .background_spawn(async move { scan_buffer(&snapshot, &pattern) })
+                    .await;
+                if !items.is_empty() {
+                    files.insert(path, items);
+                }
+            }
+            this.update(cx, |this, cx| {
+                this.files = files;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// Scans the files that changed on disk again, or searches the whole worktree again while it's
+    /// still being scanned for the first time or can't load files.
+    fn update_changed_entries(
+        &mut self,
+        worktree_id: WorktreeId,
+        changes: &UpdatedEntriesSet,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(worktree) = self.project.read(cx).worktree_for_id(worktree_id, cx) else {
+            return;
+        };
+        if !worktree.read(cx).is_local()
+            || changes
+                .iter()
+                .any(|(_, _, change)| *change == PathChange::Loaded)
+        {
+            self.rescan(Some(RESCAN_DEBOUNCE), cx);
+            return;
+        }
+
+        for (path, _, change) in changes.iter() {
+            if *change == PathChange::Removed {
+                self.files.retain(|file, _| {
+                    file.worktree_id != worktree_id || !file.path.starts_with(path)
+                });
+                cx.notify();
+            } else {
+                self.changed_paths.insert(ProjectPath {
+                    worktree_id,
+                    path: path.clone(),
+                });
+            }
+        }
+        self.scan_changed_paths_later(cx);
+    }
+
+    fn scan_changed_paths_later(&mut self, cx: &mut Context<Self>) {
+        let Some(pattern) = self.pattern.clone() else {
+            return;
+        };
+        let project = self.project.clone();
+        self.changed_paths_task = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(CHANGED_PATHS_DEBOUNCE).await;
+            let Ok(loads) = this.update(cx, |this, cx| {
+                let paths = this.changed_paths.drain().collect::<Vec<_>>();
+                paths
+                    .into_iter()
+                    .filter_map(|path| {
+                        let project = project.read(cx);
+                        // Open buffers are scanned as they're edited and reloaded.
+                        if project.get_open_buffer(&path, cx).is_some() {
+                            return None;
+                        }
+                        let worktree = project.worktree_for_id(path.worktree_id, cx)?;
+                        let entry = worktree.read(cx).entry_for_path(&path.path)?;
+                        if !entry.is_file() {
+                            return None;
+                        }
+                        if entry.is_ignored {
+                            return Some((path, None));
+                        }
+                        let load =
+                            worktree.update(cx, |worktree, cx| worktree.load_file(&path.path, cx));
+                        Some((path, Some(load)))
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+
+            for (path, load) in loads {
+                let items = match load {
+                    Some(load) => match load.await {
+                        Ok(loaded) => {
+                            let pattern = pattern.clone();
+                            cx.background_spawn(async move { scan_text(&loaded.text, &pattern) })
+                                .await
+                        }
+                        Err(_) => Vec::new(),
+                    },
+                    None => Vec::new(),
+                };
+                this.update(cx, |this, cx| {
+                    if items.is_empty() {
+                        this.files.remove(&path);
+                    } else {
+                        this.files.insert(path, items);
+                    }
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
+    }
+
+    fn watch_buffer(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let subscription = cx.subscribe(buffer, |this, buffer, event, cx| match event {
+            BufferEvent::Edited | BufferEvent::Reloaded | BufferEvent::FileHandleChanged => {
+                this.scan_buffer_later(buffer.downgrade(), cx)
+            }
+            _ => {}
+        });
+        self.buffer_subscriptions
+            .insert(buffer.read(cx).remote_id(), subscription);
+    }
+
+    fn scan_buffer_later(&mut self, buffer: WeakEntity<Buffer>, cx: &mut Context<Self>) {
+        let Some(pattern) = self.pattern.clone() else {
+            return;
+        };
+        let Some(buffer_id) = buffer.read_with(cx, |buffer, _| buffer.remote_id()).ok() else {
+            return;
+        };
+        let task = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(BUFFER_SCAN_DEBOUNCE).await;
+            let Some((path, snapshot)) = buffer
+                .upgrade()
+                .and_then(|buffer| buffer_snapshot(&buffer, cx))
+            else {
+                return;
+            };
+            let items = cx
+                .background_spawn(async move { scan_buffer(&snapshot, &pattern) })
+                .await;
+            this.update(cx, |this, cx| {
+                if items.is_empty() {
+                    this.files.remove(&path);
+                } else {
+                    this.files.insert(path, items);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        self.buffer_scans.insert(buffer_id, task);
+    }
+}
+
+fn buffer_snapshot(
+    buffer: &Entity<Buffer>,
+    cx: &mut gpui::AsyncApp,
+) -> Option<(ProjectPath, BufferSnapshot)> {
+    buffer
+        .read_with(cx, |buffer, cx| {
+            let path = ProjectPath::from_file(buffer.file()?.as_ref(), cx);
+            Some((path, buffer.snapshot()))
+        })
+        .ok()
+        .flatten()
+}
+
+fn escaped_tags(tags: &[String]) -> Option<String> {
+    let tags = tags
+        .iter()
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .map(regex::escape)
+        .collect::<Vec<_>>();
+    (!tags.is_empty()).then(|| tags.join("|"))
+}
+
+fn search_pattern(tags: &[String]) -> String {
+    format!(r"\b(?:{})\b", escaped_tags(tags).unwrap_or_default())
+}
+
+/// Matches a tag, an optional `(author)` after it, and captures the text that follows.
+fn tag_pattern(tags: &[String]) -> Option<Regex> {
+    let tags = escaped_tags(tags)?;
+    Regex::new(&format!(r"\b({tags})\b(?:\([^)]*\))?:?\s*(.*)")).log_err()
+}
+
+fn scan_buffer(snapshot: &BufferSnapshot, pattern: &Regex) -> Vec<TodoItem> {
+    scan_text(&snapshot.text(), pattern)
+}
+
+fn scan_text(text: &str, pattern: &Regex) -> Vec<TodoItem> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(row, line)| {
+            let captures = pattern.captures(line)?;
+            Some(TodoItem {
+                tag: captures[1].to_string().into(),
+                text: captures[2].trim_end().to_string().into(),
+                row: row as u32,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_scan_text() {
+        let pattern = tag_pattern(&tags(&["TODO", "FIXME", "NOTE"])).unwrap();
+        let text = "\
+            // TODO: write this\n\
+            // TODOS are not tags\n\
+            /* FIXME(alice) handle errors */\n\
+            let todo = 1; // NOTE: todo is lowercase\n";
+        assert_eq!(
+            scan_text(text, &pattern),
+            [
+                TodoItem {
+                    tag: "TODO".into(),
+                    text: "write this".into(),
+                    row: 0,
+                },
+                TodoItem {
+                    tag: "FIXME".into(),
+                    text: "handle errors */".into(),
+                    row: 2,
+                },
+                TodoItem {
+                    tag: "NOTE".into(),
+                    text: "todo is lowercase".into(),
+                    row: 3,
+                },
+            ]
+        );
+        assert!(tag_pattern(&tags(&["", " "])).is_none());
+    }
+
+    #[gpui::test]
+    async fn test_index_follows_buffer_edits(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "a.rs": "// TODO: first\nfn a() {}\n",
+                "b.rs": "fn b() {}\n",
+                "c.rs": "// HACK: third\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let index = cx.new(|cx| TodoIndex::new(project.clone(), tags(&["TODO", "FIXME"]), cx));
+        cx.executor().advance_clock(RESCAN_DEBOUNCE);
+        cx.run_until_parked();
+
+        let files = |index: &Entity<TodoIndex>, cx: &mut TestAppContext| {
+            index.read_with(cx, |index, _| {
+                index
+                    .files()
+                    .map(|(path, items)| {
+                        (
+                            path.path.to_string_lossy().into_owned(),
+                            items
+                                .iter()
+                                .map(|item| (item.tag.to_string(), item.row))
+                                .collect::<Vec<_>>(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            files(&index, cx),
+            [("a.rs".to_string(), vec![("TODO".to_string(), 0)])]
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/b.rs"), cx)
+            })
+            .await
+            .unwrap();
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(0..0, "// FIXME: second\n")], None, cx)
+        });
+        cx.executor().advance_clock(BUFFER_SCAN_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            files(&index, cx),
+            [
+                ("a.rs".to_string(), vec![("TODO".to_string(), 0)]),
+                ("b.rs".to_string(), vec![("FIXME".to_string(), 0)]),
+            ]
+        );
+
+        fs.insert_file(path!("/dir/c.rs"), "// TODO: third\n".into())
+            .await;
+        fs.remove_file(path!("/dir/a.rs").as_ref(), Default::default())
+            .await
+            .unwrap();
+        cx.executor().advance_clock(CHANGED_PATHS_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            files(&index, cx),
+            [
+                ("b.rs".to_string(), vec![("FIXME".to_string(), 0)]),
+                ("c.rs".to_string(), vec![("TODO".to_string(), 0)]),
+            ]
+        );
+
+        fs.insert_file(path!("/dir/c.rs"), "// HACK: third\n".into())
+            .await;
+        cx.executor().advance_clock(CHANGED_PATHS_DEBOUNCE);
+        cx.run_until_parked();
+        index.update(cx, |index, cx| index.set_tags(tags(&["HACK"]), cx));
+        cx.run_until_parked();
+        assert_eq!(
+            files(&index, cx),
+            [("c.rs".to_string(), vec![("HACK".to_string(), 0)])]
+        );
+    }
+}
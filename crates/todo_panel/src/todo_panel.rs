@@ -0,0 +1,492 @@
+mod todo_index;
+mod todo_panel_settings;
+
+use std::{collections::BTreeMap, ops::Range, sync::Arc};
+
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, scroll::Autoscroll};
+use gpui::{
+    Action, AnyElement, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, ScrollStrategy, SharedString, Subscription, Task, UniformListScrollHandle,
+    WeakEntity, Window, actions, uniform_list,
+};
+use language::Point;
+use project::{Fs, ProjectPath};
+use serde::{Deserialize, Serialize};
+use settings::{Settings as _, SettingsStore};
+use theme::ActiveTheme as _;
+use ui::{IconButton, ListItem, ListItemSpacing, Tab, Tooltip, prelude::*};
+use util::{ResultExt as _, TryFutureExt as _};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+pub use todo_index::{TodoIndex, TodoItem};
+pub use todo_panel_settings::TodoPanelSettings;
+
+actions!(todo_panel, [ToggleFocus, ToggleGroupByTag]);
+
+const TODO_PANEL_KEY: &str = "TodoPanel";
+
+pub fn init(cx: &mut App) {
+    TodoPanelSettings::register(cx);
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<TodoPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Lists the lines of the project marked with tags like `TODO`, grouped by their file or by
+/// their tag.
+pub struct TodoPanel {
+    index: Entity<TodoIndex>,
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    width: Option<Pixels>,
+    group_by_tag: bool,
+    entries: Vec<ListEntry>,
+    selected_index: Option<usize>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTodoPanel {
+    width: Option<Pixels>,
+    #[serde(default)]
+    group_by_tag: bool,
+}
+
+enum ListEntry {
+    Group { label: SharedString, count: usize },
+    Todo { path: ProjectPath, item: TodoItem },
+}
+
+impl TodoPanel {
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let weak_workspace = workspace.weak_handle();
+        cx.new(|cx| {
+            let tags = TodoPanelSettings::get_global(cx).tags.clone();
+            let index = cx.new(|cx| TodoIndex::new(project, tags, cx));
+            let subscriptions = vec![
+                cx.observe(&index, |this, _, cx| this.update_entries(cx)),
+                cx.observe_global::<SettingsStore>(|this, cx| {
+                    let tags = TodoPanelSettings::get_global(cx).tags.clone();
+                    this.index.update(cx, |index, cx| index.set_tags(tags, cx));
+                }),
+            ];
+            Self {
+                index,
+                workspace: weak_workspace,
+                fs,
+                width: None,
+                group_by_tag: false,
+                entries: Vec::new(),
+                selected_index: None,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = if let Some(panel) = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(TODO_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedTodoPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|w| w.round());
+                        panel.group_by_tag = serialized_panel.group_by_tag;
+                        panel.update_entries(cx);
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        let group_by_tag = self.group_by_tag;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        TODO_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedTodoPanel {
+                            width,
+                            group_by_tag,
+                        })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        let index = self.index.read(cx);
+        let selected_item = self
+            .selected_index
+            .and_then(|ix| match self.entries.get(ix)? {
+                ListEntry::Todo { path, item } => Some((path.clone(), item.row)),
+                ListEntry::Group { .. } => None,
+            });
+
+        self.entries.clear();
+        if self.group_by_tag {
+            let mut by_tag = BTreeMap::<SharedString, Vec<(ProjectPath, TodoItem)>>::new();
+            for (path, items) in index.files() {
+                for item in items {
+                    by_tag
+                        .entry(item.tag.clone())
+                        .or_default()
+                        .push((path.clone(), item.clone()));
+                }
+            }
+            for (tag, items) in by_tag {
+                self.entries.push(ListEntry::Group {
+                    label: tag,
+                    count: items.len(),
+                });
+                self.entries.extend(
+                    items
+                        .into_iter()
+                        .map(|(path, item)| ListEntry::Todo { path, item }),
+                );
+            }
+        } else {
+            for (path, items) in index.files() {
+                self.entries.push(ListEntry::Group {
+                    label: path.path.to_string_lossy().to_string().into(),
+                    count: items.len(),
+                });
+                self.entries
+                    .extend(items.iter().map(|item| ListEntry::Todo {
+                        path: path.clone(),
+                        item: item.clone(),
+                    }));
+            }
+        }
+
+        self.selected_index = selected_item.and_then(|(selected_path, selected_row)| {
+            self.entries.iter().position(|entry| {
+                matches!(entry, ListEntry::Todo { path, item }
+                    if *path == selected_path && item.row == selected_row)
+            })
+        });
+        cx.notify();
+    }
+
+    fn toggle_group_by_tag(
+        &mut self,
+        _: &ToggleGroupByTag,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.group_by_tag = !self.group_by_tag;
+        self.update_entries(cx);
+        self.serialize(cx);
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn todo_indices(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| matches!(entry, ListEntry::Todo { .. }))
+            .map(|(ix, _)| ix)
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next = match self.selected_index {
+            Some(selected) => self.todo_indices().find(|ix| *ix > selected),
+            None => self.todo_indices().next(),
+        };
+        if let Some(ix) = next {
+            self.select(ix, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous = match self.selected_index {
+            Some(selected) => self.todo_indices().rev().find(|ix| *ix < selected),
+            None => self.todo_indices().next(),
+        };
+        if let Some(ix) = previous {
+            self.select(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open(ix, window, cx);
+        }
+    }
+
+    fn open(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ListEntry::Todo { path, item }) = self.entries.get(ix) else {
+            return;
+        };
+        let (path, row) = (path.clone(), item.row);
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let open_task = workspace.update(cx, |workspace, cx| {
+            workspace.open_path(path, None, true, window, cx)
+        });
+        cx.spawn_in(window, async move |_, cx| {
+            let item = open_task.await?;
+            if let Some(editor) = item.downcast::<Editor>() {
+                editor.update_in(cx, |editor, window, cx| {
+                    let point = Point::new(row, 0);
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_ranges([point..point])
+                    });
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_entries(
+        &mut self,
+        range: Range<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| Some(self.render_entry(ix, self.entries.get(ix)?, cx)))
+            .collect()
+    }
+
+    fn render_entry(&self, ix: usize, entry: &ListEntry, cx: &Context<Self>) -> AnyElement {
+        match entry {
+            ListEntry::Group { label, count } => h_flex()
+                .px_2()
+                .pt_2()
+                .gap_2()
+                .child(Label::new(label.clone()).size(LabelSize::Small).truncate())
+                .child(
+                    Label::new(count.to_string())
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .into_any_element(),
+            ListEntry::Todo { path, item } => {
+                let location = if self.group_by_tag {
+                    format!(
+                        "{}:{}",
+                        path.path
+                            .file_name()
+                            .map(|name| name.to_string_lossy())
+                            .unwrap_or_default(),
+                        item.row + 1
+                    )
+                } else {
+                    (item.row + 1).to_string()
+                };
+                ListItem::new(ix)
+                    .spacing(ListItemSpacing::Sparse)
+                    .inset(true)
+                    .toggle_state(self.selected_index == Some(ix))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .when(!self.group_by_tag, |this| {
+                                this.child(
+                                    Label::new(item.tag.clone())
+                                        .size(LabelSize::Small)
+                                        .color(Color::Accent),
+                                )
+                            })
+                            .child(
+                                Label::new(item.text.clone())
+                                    .size(LabelSize::Small)
+                                    .truncate(),
+                            )
+                            .child(
+                                Label::new(location)
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select(ix, cx);
+                        this.open(ix, window, cx);
+                    }))
+                    .into_any_element()
+            }
+        }
+    }
+}
+
+impl Render for TodoPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let group_by_tag = self.group_by_tag;
+        v_flex()
+            .key_context("TodoPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::toggle_group_by_tag))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("TODOs"))
+                    .child(
+                        IconButton::new(
+                            "group-by",
+                            if group_by_tag {
+                                IconName::ListTree
+                            } else {
+                                IconName::ListTodo
+                            },
+                        )
+                        .icon_size(IconSize::Small)
+                        .tooltip(move |window, cx| {
+                            Tooltip::for_action(
+                                if group_by_tag {
+                                    "Group by File"
+                                } else {
+                                    "Group by Tag"
+                                },
+                                &ToggleGroupByTag,
+                                window,
+                                cx,
+                            )
+                        })
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.toggle_group_by_tag(&ToggleGroupByTag, window, cx)
+                        })),
+                    ),
+            )
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("No tagged comments found.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "todo-entries",
+                            self.entries.len(),
+                            Self::render_entries,
+                        )
+                        .p_1()
+                        .track_scroll(self.scroll_handle.clone())
+                        .flex_grow(),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for TodoPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for TodoPanel {}
+
+impl Panel for TodoPanel {
+    fn persistent_name() -> &'static str {
+        "TodoPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        TodoPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<TodoPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| TodoPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        TodoPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTodo)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("TODO Panel".into())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
@@ -236,4 +236,8 @@ impl StatusItemView for ActiveToolchain {
         }
         cx.notify();
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("toolchain".into())
+    }
 }
@@ -2,8 +2,9 @@ use std::sync::Arc;
 
 use gpui::{AnyElement, AnyView, ClickEvent, MouseButton, MouseDownEvent, Pixels, px};
 use smallvec::SmallVec;
+use theme::UiDensity;
 
-use crate::{Disclosure, prelude::*};
+use crate::{Disclosure, prelude::*, ui_density};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
 pub enum ListItemSpacing {
@@ -249,10 +250,15 @@ impl RenderOnce for ListItem {
                     .relative()
                     .gap_1()
                     .px(DynamicSpacing::Base06.rems(cx))
-                    .map(|this| match self.spacing {
-                        ListItemSpacing::Dense => this,
-                        ListItemSpacing::ExtraDense => this.py_neg_px(),
-                        ListItemSpacing::Sparse => this.py_1(),
+                    .map(|this| match (self.spacing, ui_density(cx)) {
+                        (ListItemSpacing::Dense, UiDensity::Compact) => this.py_neg_px(),
+                        (ListItemSpacing::Dense, UiDensity::Default) => this,
+                        (ListItemSpacing::Dense, UiDensity::Comfortable) => this.py_0p5(),
+                        (ListItemSpacing::ExtraDense, UiDensity::Comfortable) => this,
+                        (ListItemSpacing::ExtraDense, _) => this.py_neg_px(),
+                        (ListItemSpacing::Sparse, UiDensity::Compact) => this.py_0p5(),
+                        (ListItemSpacing::Sparse, UiDensity::Default) => this.py_1(),
+                        (ListItemSpacing::Sparse, UiDensity::Comfortable) => this.py_1p5(),
                     })
                     .when(self.inset && !self.disabled, |this| {
                         this
@@ -1,6 +1,6 @@
 use std::cmp::Ordering;
 
-use gpui::{AnyElement, IntoElement, Stateful};
+use gpui::{AnyElement, IntoElement, Stateful, accesskit::Role};
 use smallvec::SmallVec;
 
 use crate::prelude::*;
@@ -53,6 +53,12 @@ impl Tab {
         }
     }
 
+    /// Sets the name that assistive technologies, like screen readers, read for this tab.
+    pub fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.div = self.div.accessibility(Role::Tab, label);
+        self
+    }
+
     pub fn position(mut self, position: TabPosition) -> Self {
         self.position = position;
         self
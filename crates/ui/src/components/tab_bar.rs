@@ -1,4 +1,4 @@
-use gpui::{AnyElement, ScrollHandle};
+use gpui::{AnyElement, ScrollHandle, accesskit::Role};
 use smallvec::SmallVec;
 
 use crate::Tab;
@@ -92,6 +92,7 @@ impl ParentElement for TabBar {
 impl RenderOnce for TabBar {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
         div()
+            .accessibility(Role::TabList, "Tabs")
             .id(self.id)
             .group("tab_bar")
             .flex()
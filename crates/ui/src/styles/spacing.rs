@@ -50,6 +50,6 @@ derive_dynamic_spacing![
 /// Do not use this to calculate spacing values.
 ///
 /// Always use [DynamicSpacing] for spacing values.
-pub fn ui_density(cx: &mut App) -> UiDensity {
+pub fn ui_density(cx: &App) -> UiDensity {
     ThemeSettings::get_global(cx).ui_density
 }
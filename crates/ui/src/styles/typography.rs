@@ -132,14 +132,18 @@ pub enum TextSize {
 
 impl TextSize {
     /// Returns the text size in rems.
+    ///
+    /// The fixed sizes shrink or grow by a pixel with the UI density.
     pub fn rems(self, cx: &App) -> Rems {
         let theme_settings = ThemeSettings::get_global(cx);
 
+        let offset = theme_settings.ui_density.text_size_offset();
+
         match self {
-            Self::Large => rems_from_px(16.),
-            Self::Default => rems_from_px(14.),
-            Self::Small => rems_from_px(12.),
-            Self::XSmall => rems_from_px(10.),
+            Self::Large => rems_from_px(16. + offset),
+            Self::Default => rems_from_px(14. + offset),
+            Self::Small => rems_from_px(12. + offset),
+            Self::XSmall => rems_from_px(10. + offset),
             Self::Ui => rems_from_px(theme_settings.ui_font_size(cx).into()),
             Self::Editor => rems_from_px(theme_settings.buffer_font_size(cx).into()),
         }
@@ -7,6 +7,7 @@ use std::{
     sync::LazyLock,
 };
 
+use HashSet;
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -502,10 +503,90 @@ pub fn compare_paths(
     }
 }
 
+/// A row of a tree of files, as laid out by [`path_tree_rows`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathTreeRow {
+    Directory { path: Arc<Path>, depth: usize },
+    File { entry_ix: usize, depth: usize },
+}
+
+/// Lays out the files, which are sorted by path, as a tree of their directories, leaving out
+/// the contents of the collapsed directories. Each file's row refers to its index in `paths`.
+pub fn path_tree_rows<'a>(
+    paths: impl IntoIterator<Item = &'a Path>,
+    collapsed: &HashSet<Arc<Path>>,
+) -> Vec<PathTreeRow> {
+    let mut rows = Vec::new();
+    let mut directories = Vec::<&Path>::new();
+    for (entry_ix, path) in paths.into_iter().enumerate() {
+        let mut parents = path
+            .ancestors()
+            .skip(1)
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .collect::<Vec<_>>();
+        parents.reverse();
+        let common_len = directories
+            .iter()
+            .zip(&parents)
+            .take_while(|(directory, parent)| directory == parent)
+            .count();
+        directories.truncate(common_len);
+        for parent in &parents[common_len..] {
+            if !directories.iter().any(|dir| collapsed.contains(*dir)) {
+                rows.push(PathTreeRow::Directory {
+                    path: (*parent).into(),
+                    depth: directories.len(),
+                });
+            }
+            directories.push(*parent);
+        }
+        if !directories.iter().any(|dir| collapsed.contains(*dir)) {
+            rows.push(PathTreeRow::File {
+                entry_ix,
+                depth: directories.len(),
+            });
+        }
+    }
+    rows
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_path_tree_rows() {
+        let paths = ["a/b/c.rs", "a/b/d.rs", "a/e.rs", "f.rs"].map(Path::new);
+        let directory = |path: &str, depth| PathTreeRow::Directory {
+            path: Path::new(path).into(),
+            depth,
+        };
+        let file = |entry_ix, depth| PathTreeRow::File { entry_ix, depth };
+
+        assert_eq!(
+            path_tree_rows(paths, &HashSet::default()),
+            vec![
+                directory("a", 0),
+                directory("a/b", 1),
+                file(0, 2),
+                file(1, 2),
+                file(2, 1),
+                file(3, 0),
+            ]
+        );
+
+        let collapsed = HashSet::from_iter([Arc::from(Path::new("a/b"))]);
+        assert_eq!(
+            path_tree_rows(paths, &collapsed),
+            vec![
+                directory("a", 0),
+                directory("a/b", 1),
+                file(2, 1),
+                file(3, 0)
+            ]
+        );
+    }
+
     #[test]
     fn compare_paths_with_dots() {
         let mut paths = vec![
@@ -1,7 +1,10 @@
 use editor::{Bias, Direction, Editor, display_map::ToDisplayPoint, movement, scroll::Autoscroll};
-use gpui::{Context, Window, actions};
+use gpui::{App, Context, Entity, Window, actions};
 
-use crate::{Vim, state::Mode};
+use crate::{
+    Vim,
+    state::{MarksState, Mode},
+};
 
 actions!(vim, [ChangeListOlder, ChangeListNewer]);
 
@@ -23,6 +26,7 @@ impl Vim {
     ) {
         let count = Vim::take_count(cx).unwrap_or(1);
         Vim::take_forced_motion(cx);
+        self.restore_change_list(window, cx);
         self.update_editor(window, cx, |_, editor, window, cx| {
             if let Some(selections) = editor
                 .change_list
@@ -40,40 +44,70 @@ impl Vim {
         });
     }
 
-    pub(crate) fn push_to_change_list(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let Some((new_positions, buffer)) = self.update_editor(window, cx, |vim, editor, _, cx| {
-            let (map, selections) = editor.selections.all_adjusted_display(cx);
-            let buffer = editor.buffer().clone();
-
-            let pop_state = editor
-                .change_list
-                .last()
-                .map(|previous| {
-                    previous.len() == selections.len()
-                        && previous.iter().enumerate().all(|(ix, p)| {
-                            p.to_display_point(&map).row() == selections[ix].head().row()
-                        })
-                })
-                .unwrap_or(false);
-
-            let new_positions = selections
-                .into_iter()
-                .map(|s| {
-                    let point = if vim.mode == Mode::Insert {
-                        movement::saturating_left(&map, s.head())
-                    } else {
-                        s.head()
-                    };
-                    map.display_point_to_anchor(point, Bias::Left)
-                })
-                .collect::<Vec<_>>();
+    /// Puts the changes made to the file in earlier sessions before the ones made since it was
+    /// opened, the first time the editor's change list is used.
+    fn restore_change_list(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.restored_change_list {
+            return;
+        }
+        let Some(marks_state) = self.marks_state(window, cx) else {
+            return;
+        };
+        self.restored_change_list = true;
+        self.update_editor(window, cx, |_, editor, _, cx| {
+            if let Some(changes) = marks_state.read(cx).change_list(editor.buffer(), cx) {
+                editor.change_list.prepend(changes);
+            }
+        });
+    }
 
-            editor
-                .change_list
-                .push_to_change_list(pop_state, new_positions.clone());
+    fn marks_state(&self, window: &mut Window, cx: &mut App) -> Option<Entity<MarksState>> {
+        let workspace_id = self.workspace(window)?.entity_id();
+        Vim::globals(cx).marks.get(&workspace_id).cloned()
+    }
 
-            (new_positions, buffer)
-        }) else {
+    pub(crate) fn push_to_change_list(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.restore_change_list(window, cx);
+        let Some((new_positions, buffer)) =
+            self.update_editor(window, cx, |vim, editor, window, cx| {
+                let (map, selections) = editor.selections.all_adjusted_display(cx);
+                let buffer = editor.buffer().clone();
+
+                let pop_state = editor
+                    .change_list
+                    .last()
+                    .map(|previous| {
+                        previous.len() == selections.len()
+                            && previous.iter().enumerate().all(|(ix, p)| {
+                                p.to_display_point(&map).row() == selections[ix].head().row()
+                            })
+                    })
+                    .unwrap_or(false);
+
+                let new_positions = selections
+                    .into_iter()
+                    .map(|s| {
+                        let point = if vim.mode == Mode::Insert {
+                            movement::saturating_left(&map, s.head())
+                        } else {
+                            s.head()
+                        };
+                        map.display_point_to_anchor(point, Bias::Left)
+                    })
+                    .collect::<Vec<_>>();
+
+                editor
+                    .change_list
+                    .push_to_change_list(pop_state, new_positions.clone());
+                if let Some(marks_state) = vim.marks_state(window, cx) {
+                    marks_state.update(cx, |marks_state, cx| {
+                        marks_state.set_change_list(&buffer, editor.change_list.changes(), cx)
+                    });
+                }
+
+                (new_positions, buffer)
+            })
+        else {
             return;
         };
 
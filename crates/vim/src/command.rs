@@ -1,9 +1,12 @@
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
 use command_palette_hooks::CommandInterceptResult;
 use editor::{
     Bias, Editor, ToPoint,
-    actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive},
+    actions::{
+        SortLinesCaseInsensitive, SortLinesCaseSensitive, SortLinesCaseSensitiveDescending,
+        SortLinesNumeric,
+    },
     display_map::ToDisplayPoint,
     scroll::Autoscroll,
 };
@@ -647,7 +650,6 @@ impl VimCommand {
         }
     }
 
-    // TODO: ranges with search queries
     fn parse_range(query: &str) -> (Option<CommandRange>, String) {
         let mut chars = query.chars().peekable();
 
@@ -733,6 +735,36 @@ impl VimCommand {
                     offset: Self::parse_offset(chars),
                 })
             }
+            '/' | '?' => {
+                // Without a closing delimiter, this is a search rather than a range.
+                let mut pattern_chars = chars.clone();
+                let delimiter = pattern_chars.next()?;
+                let (pattern, terminated) = parse_pattern(&mut pattern_chars, delimiter);
+                if !terminated {
+                    return None;
+                }
+                *chars = pattern_chars;
+                Some(Position::Pattern {
+                    pattern,
+                    backwards: delimiter == '?',
+                    offset: Self::parse_offset(chars),
+                })
+            }
+            '\\' => {
+                let mut pattern_chars = chars.clone();
+                pattern_chars.next();
+                let backwards = match pattern_chars.next()? {
+                    '/' => false,
+                    '?' => true,
+                    _ => return None,
+                };
+                *chars = pattern_chars;
+                Some(Position::Pattern {
+                    pattern: String::new(),
+                    backwards,
+                    offset: Self::parse_offset(chars),
+                })
+            }
             _ => None,
         }
     }
@@ -764,10 +796,27 @@ impl VimCommand {
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq)]
 enum Position {
-    Line { row: u32, offset: i32 },
-    Mark { name: char, offset: i32 },
-    LastLine { offset: i32 },
-    CurrentLine { offset: i32 },
+    Line {
+        row: u32,
+        offset: i32,
+    },
+    Mark {
+        name: char,
+        offset: i32,
+    },
+    LastLine {
+        offset: i32,
+    },
+    CurrentLine {
+        offset: i32,
+    },
+    /// The next line after the cursor that matches `pattern`, or the previous one when
+    /// `backwards`, wrapping around the end of the buffer. An empty pattern is the last search.
+    Pattern {
+        pattern: String,
+        backwards: bool,
+        offset: i32,
+    },
 }
 
 impl Position {
@@ -821,6 +870,50 @@ impl Position {
                 .to_point(&snapshot.buffer_snapshot)
                 .row
                 .saturating_add_signed(*offset),
+            Position::Pattern {
+                pattern,
+                backwards,
+                offset,
+            } => {
+                let pattern = if pattern.is_empty() {
+                    last_search_query(vim, window, cx)
+                        .filter(|query| !query.is_empty())
+                        .context("No previous regular expression")?
+                } else {
+                    pattern.clone()
+                };
+                let regex = Regex::new(&pattern)?;
+                let buffer = &snapshot.buffer_snapshot;
+                let current_row = editor
+                    .selections
+                    .newest_anchor()
+                    .head()
+                    .to_point(buffer)
+                    .row;
+                let max_row = buffer.max_row().0;
+                let rows = if *backwards {
+                    (0..current_row)
+                        .rev()
+                        .chain((current_row..=max_row).rev())
+                        .collect::<Vec<_>>()
+                } else {
+                    (current_row + 1..=max_row)
+                        .chain(0..=current_row)
+                        .collect::<Vec<_>>()
+                };
+                rows.into_iter()
+                    .find(|row| {
+                        let line = buffer
+                            .text_for_range(
+                                Point::new(*row, 0)
+                                    ..Point::new(*row, buffer.line_len(MultiBufferRow(*row))),
+                            )
+                            .collect::<String>();
+                        regex.is_match(&line)
+                    })
+                    .with_context(|| format!("Pattern not found: {pattern}"))?
+                    .saturating_add_signed(*offset)
+            }
         };
 
         Ok(MultiBufferRow(target).min(snapshot.buffer_snapshot.max_row()))
@@ -1080,8 +1173,11 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
         VimCommand::new(("delm", "arks"), ArgumentRequired)
             .bang(DeleteMarks::AllLocal)
             .args(|_, args| Some(DeleteMarks::Marks(args).boxed_clone())),
-        VimCommand::new(("sor", "t"), SortLinesCaseSensitive).range(select_range),
+        VimCommand::new(("sor", "t"), SortLinesCaseSensitive)
+            .bang(SortLinesCaseSensitiveDescending)
+            .range(select_range),
         VimCommand::new(("sort i", ""), SortLinesCaseInsensitive).range(select_range),
+        VimCommand::new(("sort n", ""), SortLinesNumeric).range(select_range),
         VimCommand::str(("E", "xplore"), "project_panel::ToggleFocus"),
         VimCommand::str(("H", "explore"), "project_panel::ToggleFocus"),
         VimCommand::str(("L", "explore"), "project_panel::ToggleFocus"),
@@ -1200,15 +1296,21 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
             substitute.next();
             query.next();
         }
-        if let Some(replacement) = Replacement::parse(query) {
-            let range = range.clone().unwrap_or(CommandRange {
-                start: Position::CurrentLine { offset: 0 },
-                end: None,
-            });
-            Some(ReplaceCommand { replacement, range }.boxed_clone())
-        } else {
-            None
-        }
+        Replacement::parse(query).map(|replacement| {
+            ReplaceCommand {
+                replacement,
+                range: range.clone(),
+            }
+            .boxed_clone()
+        })
+    } else if let Some(replacement) = Replacement::parse_repeat(query) {
+        Some(
+            ReplaceCommand {
+                replacement,
+                range: range.clone(),
+            }
+            .boxed_clone(),
+        )
     } else if query.starts_with('g') || query.starts_with('v') {
         let mut global = "global".chars().peekable();
         let mut query = query.chars().peekable();
@@ -1289,6 +1391,49 @@ fn generate_positions(string: &str, query: &str) -> Vec<usize> {
     positions
 }
 
+/// Reads a pattern up to an unescaped `delimiter`, flipping `\(` and `\)` with `(` and `)` so
+/// that groups written the vim way work. Returns whether the closing delimiter was found.
+fn parse_pattern(chars: &mut Peekable<Chars>, delimiter: char) -> (String, bool) {
+    let mut pattern = String::new();
+    let mut escaped = false;
+
+    for c in chars.by_ref() {
+        if escaped {
+            escaped = false;
+            // unescape escaped parens
+            if c != '(' && c != ')' && c != delimiter {
+                pattern.push('\\')
+            }
+            pattern.push(c)
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            return (pattern, true);
+        } else {
+            // escape unescaped parens
+            if c == '(' || c == ')' {
+                pattern.push('\\')
+            }
+            pattern.push(c)
+        }
+    }
+
+    (pattern, false)
+}
+
+/// The query of the buffer search bar in the active pane, which is vim's last search pattern.
+fn last_search_query(vim: &Vim, window: &mut Window, cx: &App) -> Option<String> {
+    let workspace = vim.workspace(window)?;
+    let search_bar = workspace
+        .read(cx)
+        .active_pane()
+        .read(cx)
+        .toolbar()
+        .read(cx)
+        .item_of_type::<BufferSearchBar>()?;
+    Some(search_bar.read(cx).query(cx))
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct OnMatchingLines {
     range: CommandRange,
@@ -1300,8 +1445,7 @@ pub(crate) struct OnMatchingLines {
 impl OnMatchingLines {
     // convert a vim query into something more usable by zed.
     // we don't attempt to fully convert between the two regex syntaxes,
-    // but we do flip \( and \) to ( and ) (and vice-versa) in the pattern,
-    // and convert \0..\9 to $0..$9 in the replacement so that common idioms work.
+    // but we do flip \( and \) to ( and ) (and vice-versa) in the pattern.
     pub(crate) fn parse(
         mut chars: Peekable<Chars>,
         invert: bool,
@@ -1312,30 +1456,7 @@ impl OnMatchingLines {
             !c.is_alphanumeric() && *c != '"' && *c != '|' && *c != '\'' && *c != '!'
         })?;
 
-        let mut search = String::new();
-        let mut escaped = false;
-
-        while let Some(c) = chars.next() {
-            if escaped {
-                escaped = false;
-                // unescape escaped parens
-                if c != '(' && c != ')' && c != delimiter {
-                    search.push('\\')
-                }
-                search.push(c)
-            } else if c == '\\' {
-                escaped = true;
-            } else if c == delimiter {
-                break;
-            } else {
-                // escape unescaped parens
-                if c == '(' || c == ')' {
-                    search.push('\\')
-                }
-                search.push(c)
-            }
-        }
-
+        let (search, _) = parse_pattern(&mut chars, delimiter);
         let command: String = chars.collect();
 
         let action = WrappedAction(
@@ -2214,4 +2335,152 @@ mod test {
         });
         assert!(mark.is_none())
     }
+
+    #[gpui::test]
+    async fn test_command_pattern_ranges(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            ˇa
+            b
+            c
+            d
+            e"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": / b / , / d / d enter");
+        cx.assert_state(
+            indoc! {"
+            a
+            ˇe"},
+            Mode::Normal,
+        );
+
+        cx.set_state(
+            indoc! {"
+            a
+            b
+            a
+            ˇc"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": ? a ? s / a / x enter");
+        cx.assert_state(
+            indoc! {"
+            a
+            b
+            ˇx
+            c"},
+            Mode::Normal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_command_substitute_flags(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            ˇfoo foo
+            bar foo
+            baz"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": % s / f o o / x / g enter");
+        cx.assert_state(
+            indoc! {"
+            x x
+            ˇbar x
+            baz"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes("u");
+        assert_eq!(cx.buffer_text(), "foo foo\nbar foo\nbaz");
+
+        cx.simulate_keystrokes(": % s / / y / & enter");
+        assert_eq!(cx.buffer_text(), "y y\nbar y\nbaz");
+
+        cx.simulate_keystrokes(": % s / y / ~ ~ / g enter");
+        assert_eq!(cx.buffer_text(), "yy yy\nbar yy\nbaz");
+
+        cx.simulate_keystrokes(": % s / y y / / g n enter");
+        assert_eq!(cx.buffer_text(), "yy yy\nbar yy\nbaz");
+        let status_label = cx.update_editor(|editor, _, cx| {
+            let vim = editor.addon::<VimAddon>().unwrap().entity.clone();
+            vim.read(cx).status_label.clone()
+        });
+        assert_eq!(status_label.as_deref(), Some("3 matches on 2 lines"));
+
+        cx.simulate_keystrokes(": % s / q u x / z / e enter");
+        assert_eq!(cx.buffer_text(), "yy yy\nbar yy\nbaz");
+    }
+
+    #[gpui::test]
+    async fn test_command_substitute_repeat(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            ˇa a
+            a a
+            a a"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": s / a / b / g enter");
+        cx.simulate_keystrokes("j : & & enter");
+        cx.simulate_keystrokes("j : & enter");
+        cx.assert_state(
+            indoc! {"
+            b b
+            b b
+            ˇb a"},
+            Mode::Normal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_command_substitute_replacement(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            ˇhello world
+            goodbye world"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(
+            ": % s / \\ ( \\ w + \\ ) space \\ ( \\ w + \\ ) / \\ u \\ 2 space \\ U \\ 1 / enter",
+        );
+        cx.assert_state(
+            indoc! {"
+            World HELLO
+            ˇWorld GOODBYE"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes(": % s / W o r l d / [ & ] / enter");
+        cx.assert_state(
+            indoc! {"
+            [World] HELLO
+            ˇ[World] GOODBYE"},
+            Mode::Normal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_command_matching_lines_substitute(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            ˇa1
+            b1
+            a2"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": g / a / s / \\ d / x / enter");
+        assert_eq!(cx.buffer_text(), "ax\nb1\nax");
+    }
 }
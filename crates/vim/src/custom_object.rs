@@ -0,0 +1,406 @@
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Result;
+use collections::HashMap;
+use editor::{Editor, MultiBufferSnapshot, ToPoint};
+use gpui::{App, Global, SharedString, impl_actions};
+use language::Point;
+use multi_buffer::MultiBufferRow;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::Context;
+use util::ResultExt;
+
+use crate::{Vim, VimSettings, object::Object};
+
+/// Selects the text object registered or configured under `name`, after `i` or `a`.
+#[derive(Clone, Deserialize, JsonSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct CustomObject {
+    name: String,
+}
+
+impl_actions!(vim, [CustomObject]);
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(
+        editor,
+        cx,
+        |vim, action: &CustomObject, window, cx| match resolve(&action.name, cx) {
+            Some(object) => vim.object(Object::Custom(object), window, cx),
+            None => vim.clear_operator(window, cx),
+        },
+    );
+}
+
+/// A text object that is not built into vim mode, such as one provided by another crate with
+/// [`register_text_object`] or configured in the `vim.custom_text_objects` setting.
+pub trait CustomTextObject: Send + Sync + 'static {
+    /// The range of the object around the `cursor` offset, either including (`around`) or
+    /// excluding its surroundings.
+    fn range(
+        &self,
+        buffer: &MultiBufferSnapshot,
+        cursor: usize,
+        around: bool,
+    ) -> Option<Range<usize>>;
+
+    /// Whether selecting the object in visual mode switches to visual line mode.
+    fn is_linewise(&self, _around: bool) -> bool {
+        false
+    }
+}
+
+/// The custom text objects available in an app, either registered by crates or built from the
+/// settings.
+#[derive(Default)]
+struct CustomTextObjectRegistry {
+    registered: HashMap<SharedString, Arc<dyn CustomTextObject>>,
+    configured: HashMap<String, (CustomTextObjectContent, Arc<dyn CustomTextObject>)>,
+}
+
+impl Global for CustomTextObjectRegistry {}
+
+/// Makes `object` available to `vim::CustomObject` bindings under `name`, replacing any object
+/// previously registered with that name. Objects configured in the settings take precedence.
+pub fn register_text_object(
+    name: impl Into<SharedString>,
+    object: impl CustomTextObject,
+    cx: &mut App,
+) {
+    cx.default_global::<CustomTextObjectRegistry>()
+        .registered
+        .insert(name.into(), Arc::new(object));
+}
+
+/// Finds the object for `name`, preferring the settings' definition to a registered one.
+fn resolve(name: &str, cx: &mut App) -> Option<ResolvedCustomObject> {
+    let content = VimSettings::get_global(cx)
+        .custom_text_objects
+        .get(name)
+        .cloned();
+    let registry = cx.default_global::<CustomTextObjectRegistry>();
+    let object = match content {
+        Some(content) => match registry.configured.get(name) {
+            Some((built_from, object)) if *built_from == content => object.clone(),
+            _ => {
+                let object = content.build().log_err()?;
+                registry
+                    .configured
+                    .insert(name.to_string(), (content, object.clone()));
+                object
+            }
+        },
+        None => registry.registered.get(name)?.clone(),
+    };
+    Some(ResolvedCustomObject {
+        name: name.to_string().into(),
+        object,
+    })
+}
+
+/// A custom text object looked up in the registry when its binding was triggered, so that
+/// computing its range doesn't need access to the app.
+#[derive(Clone)]
+pub struct ResolvedCustomObject {
+    name: SharedString,
+    object: Arc<dyn CustomTextObject>,
+}
+
+impl ResolvedCustomObject {
+    pub(crate) fn range(
+        &self,
+        buffer: &MultiBufferSnapshot,
+        cursor: usize,
+        around: bool,
+    ) -> Option<Range<usize>> {
+        self.object.range(buffer, cursor, around)
+    }
+
+    pub(crate) fn is_linewise(&self, around: bool) -> bool {
+        self.object.is_linewise(around)
+    }
+}
+
+impl PartialEq for ResolvedCustomObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Arc::ptr_eq(&self.object, &other.object)
+    }
+}
+
+impl Eq for ResolvedCustomObject {}
+
+impl std::fmt::Debug for ResolvedCustomObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ResolvedCustomObject")
+            .field(&self.name)
+            .finish()
+    }
+}
+
+/// A text object defined in the `vim.custom_text_objects` setting.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum CustomTextObjectContent {
+    /// The match of a regex on the cursor's line that contains the cursor, or else the next one.
+    /// `i` and `a` select the same text.
+    Pattern { pattern: String },
+    /// The text between the closest match of `start` before the cursor and the following match of
+    /// `end`. `a` includes the matches themselves, which can't span lines.
+    Delimited { start: String, end: String },
+    /// The innermost syntax node containing the cursor that has one of the given kinds. `i`
+    /// selects from its first to its last named child, leaving out punctuation like brackets.
+    SyntaxNode { syntax_nodes: Vec<String> },
+}
+
+impl CustomTextObjectContent {
+    fn build(&self) -> Result<Arc<dyn CustomTextObject>> {
+        Ok(match self {
+            Self::Pattern { pattern } => Arc::new(PatternObject {
+                pattern: Regex::new(pattern)?,
+            }),
+            Self::Delimited { start, end } => Arc::new(DelimitedObject {
+                start: Regex::new(start)?,
+                end: Regex::new(end)?,
+            }),
+            Self::SyntaxNode { syntax_nodes } => Arc::new(SyntaxNodeObject {
+                kinds: syntax_nodes.clone(),
+            }),
+        })
+    }
+}
+
+pub type CustomTextObjects = HashMap<String, CustomTextObjectContent>;
+
+struct PatternObject {
+    pattern: Regex,
+}
+
+impl CustomTextObject for PatternObject {
+    fn range(
+        &self,
+        buffer: &MultiBufferSnapshot,
+        cursor: usize,
+        _around: bool,
+    ) -> Option<Range<usize>> {
+        let (line_start, line) = line_at(buffer, cursor.to_point(buffer).row);
+        let column = cursor - line_start;
+        let found = self
+            .pattern
+            .find_iter(&line)
+            .find(|found| found.end() > column && found.start() < found.end())?;
+        Some(line_start + found.start()..line_start + found.end())
+    }
+}
+
+/// Searches line by line outwards from the cursor, so the delimiters can't span lines.
+struct DelimitedObject {
+    start: Regex,
+    end: Regex,
+}
+
+impl CustomTextObject for DelimitedObject {
+    fn range(
+        &self,
+        buffer: &MultiBufferSnapshot,
+        cursor: usize,
+        around: bool,
+    ) -> Option<Range<usize>> {
+        let cursor_row = cursor.to_point(buffer).row;
+        let (start_row, start) = (0..=cursor_row).rev().find_map(|row| {
+            let (line_start, line) = line_at(buffer, row);
+            let found = self
+                .start
+                .find_iter(&line)
+                .take_while(|found| line_start + found.start() <= cursor)
+                .last()?;
+            Some((row, line_start + found.start()..line_start + found.end()))
+        })?;
+
+        let end = (start_row..=buffer.max_point().row).find_map(|row| {
+            let (line_start, line) = line_at(buffer, row);
+            let search_from = start.end.saturating_sub(line_start).min(line.len());
+            let found = self.end.find_at(&line, search_from)?;
+            Some(line_start + found.start()..line_start + found.end())
+        })?;
+        if end.end <= cursor {
+            return None;
+        }
+        if around {
+            Some(start.start..end.end)
+        } else {
+            Some(start.end..end.start)
+        }
+    }
+}
+
+/// The offset at which `row` starts and its text, without the newline.
+fn line_at(buffer: &MultiBufferSnapshot, row: u32) -> (usize, String) {
+    let line_start = buffer.point_to_offset(Point::new(row, 0));
+    let line_end = buffer.point_to_offset(Point::new(row, buffer.line_len(MultiBufferRow(row))));
+    let line = buffer
+        .text_for_range(line_start..line_end)
+        .collect::<String>();
+    (line_start, line)
+}
+
+struct SyntaxNodeObject {
+    kinds: Vec<String>,
+}
+
+impl CustomTextObject for SyntaxNodeObject {
+    fn range(
+        &self,
+        buffer: &MultiBufferSnapshot,
+        cursor: usize,
+        around: bool,
+    ) -> Option<Range<usize>> {
+        let mut excerpt = buffer.excerpt_containing(cursor..cursor)?;
+        let offset = excerpt.map_offset_to_buffer(cursor);
+        let layer = excerpt.buffer().syntax_layer_at(offset)?;
+        let mut node = layer.node().descendant_for_byte_range(offset, offset)?;
+        while !self.kinds.iter().any(|kind| kind == node.kind()) {
+            node = node.parent()?;
+        }
+
+        let mut range = node.byte_range();
+        if !around {
+            let mut tree_cursor = node.walk();
+            let mut named_children = node.named_children(&mut tree_cursor);
+            if let Some(first) = named_children.next() {
+                let last = named_children.last().unwrap_or(first);
+                range = first.start_byte()..last.end_byte();
+            }
+        }
+
+        excerpt
+            .contains_buffer_range(range.clone())
+            .then(|| excerpt.map_range_from_buffer(range))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use settings::SettingsStore;
+
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[gpui::test]
+    async fn test_custom_text_objects(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<crate::VimSettings>(cx, |settings| {
+                settings.custom_text_objects = Some(
+                    serde_json::from_value(serde_json::json!({
+                        "date": { "pattern": "\\d{4}-\\d{2}-\\d{2}" },
+                        "fence": { "start": "<<", "end": ">>" },
+                    }))
+                    .unwrap(),
+                );
+            });
+        });
+        cx.update(|_, cx| {
+            cx.bind_keys([
+                gpui::KeyBinding::new(
+                    "d",
+                    super::CustomObject {
+                        name: "date".into(),
+                    },
+                    Some("vim_operator == a || vim_operator == i"),
+                ),
+                gpui::KeyBinding::new(
+                    "z",
+                    super::CustomObject {
+                        name: "fence".into(),
+                    },
+                    Some("vim_operator == a || vim_operator == i"),
+                ),
+            ])
+        });
+
+        cx.set_state("due ˇon 2024-01-31 or later", Mode::Normal);
+        cx.simulate_keystrokes("d i d");
+        cx.assert_state("due on ˇ or later", Mode::Normal);
+
+        cx.set_state(
+            indoc! {"
+                a << one
+                twˇo >> b"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("c i z x escape");
+        cx.assert_state("a <<ˇx>> b", Mode::Normal);
+
+        cx.set_state("a << oˇne >> b", Mode::Normal);
+        cx.simulate_keystrokes("v a z");
+        cx.assert_state("a «<< one >>ˇ» b", Mode::Visual);
+    }
+
+    struct Digits;
+
+    impl super::CustomTextObject for Digits {
+        fn range(
+            &self,
+            buffer: &editor::MultiBufferSnapshot,
+            cursor: usize,
+            _around: bool,
+        ) -> Option<std::ops::Range<usize>> {
+            let is_digit = |c: &char| c.is_ascii_digit();
+            let start = cursor
+                - buffer
+                    .reversed_chars_at(cursor)
+                    .take_while(is_digit)
+                    .count();
+            let end = cursor + buffer.chars_at(cursor).take_while(is_digit).count();
+            (start < end).then_some(start..end)
+        }
+    }
+
+    #[gpui::test]
+    async fn test_registered_text_objects(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.update(|_, cx| {
+            super::register_text_object("number", Digits, cx);
+            cx.bind_keys([gpui::KeyBinding::new(
+                "n",
+                super::CustomObject {
+                    name: "number".into(),
+                },
+                Some("vim_operator == a || vim_operator == i"),
+            )])
+        });
+
+        cx.set_state("abc 12ˇ34 def", Mode::Normal);
+        cx.simulate_keystrokes("d i n");
+        cx.assert_state("abc ˇ def", Mode::Normal);
+
+        // An object configured in the settings shadows the registered one while it exists.
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<crate::VimSettings>(cx, |settings| {
+                settings.custom_text_objects = Some(
+                    serde_json::from_value(serde_json::json!({
+                        "number": { "pattern": "[a-z]+" },
+                    }))
+                    .unwrap(),
+                );
+            });
+        });
+        cx.set_state("abc 1234 dˇef ghi", Mode::Normal);
+        cx.simulate_keystrokes("d i n");
+        cx.assert_state("abc 1234 ˇ ghi", Mode::Normal);
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<crate::VimSettings>(cx, |settings| {
+                settings.custom_text_objects = None;
+            });
+        });
+        cx.set_state("abc 12ˇ34 def", Mode::Normal);
+        cx.simulate_keystrokes("d i n");
+        cx.assert_state("abc ˇ def", Mode::Normal);
+    }
+}
@@ -4,13 +4,16 @@ use gpui::{Context, Window};
 use language::{CharClassifier, CharKind};
 
 use crate::motion::MotionKind;
+use crate::object::Object;
+use crate::state::Operator;
 use crate::{Vim, motion::Motion, state::Mode};
 
-actions!(vim, [HelixNormalAfter, HelixDelete]);
+actions!(vim, [HelixNormalAfter, HelixDelete, HelixCollapseSelection]);
 
 pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::helix_normal_after);
     Vim::action(editor, cx, Vim::helix_delete);
+    Vim::action(editor, cx, Vim::helix_collapse_selection);
 }
 
 impl Vim {
@@ -293,6 +296,58 @@ impl Vim {
         }
     }
 
+    /// Selects the object around each cursor after `m i` or `m a`, replacing the selection.
+    pub fn helix_select_object(
+        &mut self,
+        object: Object,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(Operator::Object { around }) = self.active_operator() else {
+            return;
+        };
+        self.pop_operator(window, cx);
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|map, selection| {
+                    // The head of a forward selection is after its last character.
+                    let cursor = if selection.is_empty() || selection.reversed {
+                        selection.head()
+                    } else {
+                        movement::left(map, selection.head())
+                    };
+                    let mut cursor_selection = selection.clone();
+                    cursor_selection.collapse_to(cursor, selection.goal);
+                    if let Some(range) = object.range(map, cursor_selection, around) {
+                        selection.start = range.start;
+                        selection.end = range.end;
+                        selection.reversed = false;
+                    }
+                });
+            });
+        });
+    }
+
+    fn helix_collapse_selection(
+        &mut self,
+        _: &HelixCollapseSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|map, selection| {
+                    let cursor = if selection.is_empty() || selection.reversed {
+                        selection.head()
+                    } else {
+                        movement::left(map, selection.head())
+                    };
+                    selection.collapse_to(cursor, selection.goal);
+                });
+            });
+        });
+    }
+
     pub fn helix_delete(&mut self, _: &HelixDelete, window: &mut Window, cx: &mut Context<Self>) {
         self.store_visual_marks(window, cx);
         self.update_editor(window, cx, |vim, editor, window, cx| {
@@ -318,6 +373,8 @@ impl Vim {
 #[cfg(test)]
 mod test {
     use indoc::indoc;
+    use settings::SettingsStore;
+    use vim_mode_setting::HelixModeSetting;
 
     use crate::{state::Mode, test::VimTestContext};
 
@@ -356,6 +413,40 @@ mod test {
         );
     }
 
+    #[gpui::test]
+    async fn test_select_object(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("The quˇick brown fox", Mode::HelixNormal);
+        cx.simulate_keystrokes("m i w");
+        cx.assert_state("The «quickˇ» brown fox", Mode::HelixNormal);
+
+        cx.simulate_keystrokes(";");
+        cx.assert_state("The quicˇk brown fox", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("m a w");
+        cx.assert_state("The «quick ˇ»brown fox", Mode::HelixNormal);
+
+        cx.set_state("call(oneˇ, two)", Mode::HelixNormal);
+        cx.simulate_keystrokes("m a b");
+        cx.assert_state("call«(one, two)ˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_helix_mode_setting(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<HelixModeSetting>(cx, |setting| *setting = Some(true));
+        });
+
+        cx.set_state("The ˇquick brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("i escape");
+        assert_eq!(cx.mode(), Mode::HelixNormal);
+
+        cx.simulate_keystrokes("v l escape");
+        assert_eq!(cx.mode(), Mode::HelixNormal);
+    }
+
     // #[gpui::test]
     // async fn test_delete(cx: &mut gpui::TestAppContext) {
     //     let mut cx = VimTestContext::new(cx, true).await;
@@ -1,8 +1,10 @@
 use gpui::{Context, Element, Entity, Render, Subscription, WeakEntity, Window, div};
+use settings::Settings;
 use ui::text_for_keystrokes;
+use vim_mode_setting::HelixModeSetting;
 use workspace::{StatusItemView, item::ItemHandle, ui::prelude::*};
 
-use crate::{Vim, VimEvent, VimGlobals};
+use crate::{Vim, VimEvent, VimGlobals, state::Mode};
 
 /// The ModeIndicator displays the current mode in the status bar.
 pub struct ModeIndicator {
@@ -100,11 +102,16 @@ impl Render for ModeIndicator {
         let label = if let Some(label) = vim_readable.status_label.clone() {
             label
         } else {
-            let mode = if vim_readable.temp_mode {
-                format!("(insert) {}", vim_readable.mode)
+            let mode = if HelixModeSetting::get_global(cx).0 {
+                helix_mode_name(vim_readable.mode).to_string()
             } else {
                 vim_readable.mode.to_string()
             };
+            let mode = if vim_readable.temp_mode {
+                format!("(insert) {}", mode)
+            } else {
+                mode
+            };
 
             let current_operators_description = self.current_operators_description(vim.clone(), cx);
             let pending = self
@@ -121,6 +128,15 @@ impl Render for ModeIndicator {
     }
 }
 
+/// The names Helix gives its modes, shown in place of vim's in Helix mode.
+fn helix_mode_name(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Normal | Mode::HelixNormal => "NOR",
+        Mode::Insert | Mode::Replace => "INS",
+        Mode::Visual | Mode::VisualLine | Mode::VisualBlock => "SEL",
+    }
+}
+
 impl StatusItemView for ModeIndicator {
     fn set_active_pane_item(
         &mut self,
@@ -129,4 +145,8 @@ impl StatusItemView for ModeIndicator {
         _cx: &mut Context<Self>,
     ) {
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some("vim_mode".into())
+    }
 }
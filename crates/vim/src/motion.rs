@@ -649,6 +649,11 @@ impl Vim {
                 }
                 Mode::Normal | Mode::Replace | Mode::Insert => {
                     if self.active_operator().is_none() {
+                        if let Some(origin) = prior_selections.last() {
+                            self.update_editor(window, cx, |_, editor, _, cx| {
+                                editor.record_jump(origin.start, cx)
+                            });
+                        }
                         return;
                     }
                 }
@@ -672,6 +677,13 @@ impl Vim {
         let count = Vim::take_count(cx);
         let forced_motion = Vim::take_forced_motion(cx);
         let active_operator = self.active_operator();
+        let jump_origin = if motion.is_jump() && active_operator.is_none() {
+            self.update_editor(window, cx, |_, editor, _, _| {
+                editor.selections.newest_anchor().head()
+            })
+        } else {
+            None
+        };
         let mut waiting_operator: Option<Operator> = None;
         match self.mode {
             Mode::Normal | Mode::Replace | Mode::Insert => {
@@ -696,6 +708,11 @@ impl Vim {
 
             Mode::HelixNormal => self.helix_normal_motion(motion.clone(), count, window, cx),
         }
+        if let Some(origin) = jump_origin {
+            self.update_editor(window, cx, |_, editor, _, cx| {
+                editor.record_jump(origin, cx)
+            });
+        }
         self.clear_operator(window, cx);
         if let Some(operator) = waiting_operator {
             self.push_operator(operator, window, cx);
@@ -776,6 +793,31 @@ impl Motion {
         }
     }
 
+    /// Whether the motion is recorded in the jumplist, see `:help jump-motions`. Jumps to marks
+    /// are recorded when the mark is looked up.
+    pub(crate) fn is_jump(&self) -> bool {
+        use Motion::*;
+        matches!(
+            self,
+            StartOfDocument
+                | EndOfDocument
+                | Matching
+                | GoToPercentage
+                | SentenceBackward
+                | SentenceForward
+                | StartOfParagraph
+                | EndOfParagraph
+                | WindowTop
+                | WindowMiddle
+                | WindowBottom
+                | NextSectionStart
+                | NextSectionEnd
+                | PreviousSectionStart
+                | PreviousSectionEnd
+                | ZedSearchResult { .. }
+        )
+    }
+
     fn skip_exclusive_special_case(&self) -> bool {
         match self {
             Motion::WrappingLeft | Motion::WrappingRight => true,
@@ -333,7 +333,7 @@ impl Vim {
                 waiting_operator = Some(Operator::DeleteSurrounds);
             }
             Some(Operator::ChangeSurrounds { target: None }) => {
-                if self.check_and_move_to_valid_bracket_pair(object, window, cx) {
+                if self.check_and_move_to_valid_bracket_pair(object.clone(), window, cx) {
                     waiting_operator = Some(Operator::ChangeSurrounds {
                         target: Some(object),
                     });
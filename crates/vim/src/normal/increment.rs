@@ -1,14 +1,11 @@
-use editor::{Editor, MultiBufferSnapshot, ToOffset, ToPoint, scroll::Autoscroll};
+use editor::{Editor, ToPoint, increment::increment_number_or_boolean_at, scroll::Autoscroll};
 use gpui::{Context, Window, impl_actions};
 use language::{Bias, Point};
 use schemars::JsonSchema;
 use serde::Deserialize;
-use std::ops::Range;
 
 use crate::{Vim, state::Mode};
 
-const BOOLEAN_PAIRS: &[(&str, &str)] = &[("true", "false"), ("yes", "no"), ("on", "off")];
-
 #[derive(Clone, Deserialize, JsonSchema, PartialEq)]
 #[serde(deny_unknown_fields)]
 struct Increment {
@@ -69,20 +66,9 @@ impl Vim {
                         Point::new(row, 0)
                     };
 
-                    if let Some((range, num, radix)) = find_number(&snapshot, start) {
-                        let replace = match radix {
-                            10 => increment_decimal_string(&num, delta),
-                            16 => increment_hex_string(&num, delta),
-                            2 => increment_binary_string(&num, delta),
-                            _ => unreachable!(),
-                        };
-                        delta += step as i64;
-                        edits.push((range.clone(), replace));
-                        if selection.is_empty() {
-                            new_anchors.push((false, snapshot.anchor_after(range.end)))
-                        }
-                    } else if let Some((range, boolean)) = find_boolean(&snapshot, start) {
-                        let replace = toggle_boolean(&boolean);
+                    if let Some((range, replace)) =
+                        increment_number_or_boolean_at(&snapshot, start, delta)
+                    {
                         delta += step as i64;
                         edits.push((range.clone(), replace));
                         if selection.is_empty() {
@@ -115,235 +101,6 @@ impl Vim {
     }
 }
 
-fn increment_decimal_string(num: &str, delta: i64) -> String {
-    let (negative, delta, num_str) = match num.strip_prefix('-') {
-        Some(n) => (true, -delta, n),
-        None => (false, delta, num),
-    };
-    let num_length = num_str.len();
-    let leading_zero = num_str.starts_with('0');
-
-    let (result, new_negative) = match u64::from_str_radix(num_str, 10) {
-        Ok(value) => {
-            let wrapped = value.wrapping_add_signed(delta);
-            if delta < 0 && wrapped > value {
-                ((u64::MAX - wrapped).wrapping_add(1), !negative)
-            } else if delta > 0 && wrapped < value {
-                (u64::MAX - wrapped, !negative)
-            } else {
-                (wrapped, negative)
-            }
-        }
-        Err(_) => (u64::MAX, negative),
-    };
-
-    let formatted = format!("{}", result);
-    let new_significant_digits = formatted.len();
-    let padding = if leading_zero {
-        num_length.saturating_sub(new_significant_digits)
-    } else {
-        0
-    };
-
-    if new_negative && result != 0 {
-        format!("-{}{}", "0".repeat(padding), formatted)
-    } else {
-        format!("{}{}", "0".repeat(padding), formatted)
-    }
-}
-
-fn increment_hex_string(num: &str, delta: i64) -> String {
-    let result = if let Ok(val) = u64::from_str_radix(&num, 16) {
-        val.wrapping_add_signed(delta)
-    } else {
-        u64::MAX
-    };
-    if should_use_lowercase(num) {
-        format!("{:0width$x}", result, width = num.len())
-    } else {
-        format!("{:0width$X}", result, width = num.len())
-    }
-}
-
-fn should_use_lowercase(num: &str) -> bool {
-    let mut use_uppercase = false;
-    for ch in num.chars() {
-        if ch.is_ascii_lowercase() {
-            return true;
-        }
-        if ch.is_ascii_uppercase() {
-            use_uppercase = true;
-        }
-    }
-    !use_uppercase
-}
-
-fn increment_binary_string(num: &str, delta: i64) -> String {
-    let result = if let Ok(val) = u64::from_str_radix(&num, 2) {
-        val.wrapping_add_signed(delta)
-    } else {
-        u64::MAX
-    };
-    format!("{:0width$b}", result, width = num.len())
-}
-
-fn find_number(
-    snapshot: &MultiBufferSnapshot,
-    start: Point,
-) -> Option<(Range<Point>, String, u32)> {
-    let mut offset = start.to_offset(snapshot);
-
-    let ch0 = snapshot.chars_at(offset).next();
-    if ch0.as_ref().is_some_and(char::is_ascii_hexdigit) || matches!(ch0, Some('-' | 'b' | 'x')) {
-        // go backwards to the start of any number the selection is within
-        for ch in snapshot.reversed_chars_at(offset) {
-            if ch.is_ascii_hexdigit() || ch == '-' || ch == 'b' || ch == 'x' {
-                offset -= ch.len_utf8();
-                continue;
-            }
-            break;
-        }
-    }
-
-    let mut begin = None;
-    let mut end = None;
-    let mut num = String::new();
-    let mut radix = 10;
-
-    let mut chars = snapshot.chars_at(offset).peekable();
-    // find the next number on the line (may start after the original cursor position)
-    while let Some(ch) = chars.next() {
-        if num == "0" && ch == 'b' && chars.peek().is_some() && chars.peek().unwrap().is_digit(2) {
-            radix = 2;
-            begin = None;
-            num = String::new();
-        }
-        if num == "0"
-            && ch == 'x'
-            && chars.peek().is_some()
-            && chars.peek().unwrap().is_ascii_hexdigit()
-        {
-            radix = 16;
-            begin = None;
-            num = String::new();
-        }
-
-        if ch.is_digit(radix)
-            || (begin.is_none()
-                && ch == '-'
-                && chars.peek().is_some()
-                && chars.peek().unwrap().is_digit(radix))
-        {
-            if begin.is_none() {
-                begin = Some(offset);
-            }
-            num.push(ch);
-        } else if begin.is_some() {
-            end = Some(offset);
-            break;
-        } else if ch == '\n' {
-            break;
-        }
-        offset += ch.len_utf8();
-    }
-    if let Some(begin) = begin {
-        let end = end.unwrap_or(offset);
-        Some((begin.to_point(snapshot)..end.to_point(snapshot), num, radix))
-    } else {
-        None
-    }
-}
-
-fn find_boolean(snapshot: &MultiBufferSnapshot, start: Point) -> Option<(Range<Point>, String)> {
-    let mut offset = start.to_offset(snapshot);
-
-    let ch0 = snapshot.chars_at(offset).next();
-    if ch0.as_ref().is_some_and(|c| c.is_ascii_alphabetic()) {
-        for ch in snapshot.reversed_chars_at(offset) {
-            if ch.is_ascii_alphabetic() {
-                offset -= ch.len_utf8();
-                continue;
-            }
-            break;
-        }
-    }
-
-    let mut begin = None;
-    let mut end = None;
-    let mut word = String::new();
-
-    let mut chars = snapshot.chars_at(offset);
-
-    while let Some(ch) = chars.next() {
-        if ch.is_ascii_alphabetic() {
-            if begin.is_none() {
-                begin = Some(offset);
-            }
-            word.push(ch);
-        } else if begin.is_some() {
-            end = Some(offset);
-            let word_lower = word.to_lowercase();
-            if BOOLEAN_PAIRS
-                .iter()
-                .any(|(a, b)| word_lower == *a || word_lower == *b)
-            {
-                return Some((
-                    begin.unwrap().to_point(snapshot)..end.unwrap().to_point(snapshot),
-                    word,
-                ));
-            }
-            begin = None;
-            end = None;
-            word = String::new();
-        } else if ch == '\n' {
-            break;
-        }
-        offset += ch.len_utf8();
-    }
-    if let Some(begin) = begin {
-        let end = end.unwrap_or(offset);
-        let word_lower = word.to_lowercase();
-        if BOOLEAN_PAIRS
-            .iter()
-            .any(|(a, b)| word_lower == *a || word_lower == *b)
-        {
-            return Some((begin.to_point(snapshot)..end.to_point(snapshot), word));
-        }
-    }
-    None
-}
-
-fn toggle_boolean(boolean: &str) -> String {
-    let lower = boolean.to_lowercase();
-
-    let target = BOOLEAN_PAIRS
-        .iter()
-        .find_map(|(a, b)| {
-            if lower == *a {
-                Some(b)
-            } else if lower == *b {
-                Some(a)
-            } else {
-                None
-            }
-        })
-        .unwrap_or(&boolean);
-
-    if boolean.chars().all(|c| c.is_uppercase()) {
-        // Upper case
-        target.to_uppercase()
-    } else if boolean.chars().next().unwrap_or(' ').is_uppercase() {
-        // Title case
-        let mut chars = target.chars();
-        match chars.next() {
-            None => String::new(),
-            Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
-        }
-    } else {
-        target.to_string()
-    }
-}
-
 #[cfg(test)]
 mod test {
     use indoc::indoc;
@@ -704,6 +461,24 @@ mod test {
             30"});
     }
 
+    #[gpui::test]
+    async fn test_increment_like_vim(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        // Dates, octal numbers and keyword cycles are only incremented outside of Vim mode.
+        cx.set_state("ˇdue: 2024-12-31", Mode::Normal);
+        cx.simulate_keystrokes("ctrl-a");
+        cx.assert_state("due: 202ˇ5-12-31", Mode::Normal);
+
+        cx.set_state("ˇlet mode = 0o777;", Mode::Normal);
+        cx.simulate_keystrokes("ctrl-a");
+        cx.assert_state("let mode = ˇ1o777;", Mode::Normal);
+
+        cx.set_state("on ˇfriday", Mode::Normal);
+        cx.simulate_keystrokes("3 ctrl-a");
+        cx.assert_state("on ˇfriday", Mode::Normal);
+    }
+
     #[gpui::test]
     async fn test_toggle_boolean(cx: &mut gpui::TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
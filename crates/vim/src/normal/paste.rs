@@ -218,7 +218,7 @@ impl Vim {
             });
         });
 
-        self.switch_mode(self.default_mode(cx), true, window, cx);
+        self.switch_mode(Vim::default_mode(cx), true, window, cx);
     }
 
     pub fn replace_with_register_object(
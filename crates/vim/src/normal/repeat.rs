@@ -157,8 +157,12 @@ impl Vim {
         cx: &mut Context<Self>,
     ) {
         let globals = Vim::globals(cx);
-        globals.recording_register = Some(register);
-        globals.recordings.remove(&register);
+        // Like when yanking, recording into an uppercase register appends to the lowercase one.
+        let lower = register.to_ascii_lowercase();
+        if lower == register {
+            globals.recordings.remove(&register);
+        }
+        globals.recording_register = Some(lower);
         globals.ignore_current_insertion = true;
         self.clear_operator(window, cx)
     }
@@ -180,6 +184,7 @@ impl Vim {
             };
             register = last;
         }
+        register = register.to_ascii_lowercase();
         let Some(actions) = globals.recordings.get(&register) else {
             return;
         };
@@ -779,4 +784,21 @@ mod test {
         cx.simulate_shared_keystrokes("@ b").await;
         cx.shared_state().await.assert_eq("aaaaaaabbbˇd");
     }
+
+    #[gpui::test]
+    async fn test_record_append(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇhello world", Mode::Normal);
+        cx.simulate_keystrokes("q a r a l q");
+        cx.assert_state("aˇello world", Mode::Normal);
+        cx.simulate_keystrokes("q shift-a r b l q");
+        cx.assert_state("abˇllo world", Mode::Normal);
+        cx.simulate_keystrokes("@ a");
+        cx.assert_state("ababˇo world", Mode::Normal);
+        cx.simulate_keystrokes("@ shift-a");
+        cx.assert_state("abababˇworld", Mode::Normal);
+        cx.simulate_keystrokes("@ @");
+        cx.assert_state("ababababˇrld", Mode::Normal);
+    }
 }
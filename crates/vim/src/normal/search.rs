@@ -1,9 +1,14 @@
-use editor::Editor;
+use anyhow::Context as _;
+use editor::{Editor, EditorSettings, scroll::Autoscroll};
 use gpui::{Context, Window, actions, impl_actions, impl_internal_actions};
+use itertools::Itertools;
 use language::Point;
+use multi_buffer::MultiBufferRow;
+use regex::{Captures, RegexBuilder};
 use schemars::JsonSchema;
 use search::{BufferSearchBar, SearchOptions, buffer_search};
 use serde_derive::Deserialize;
+use settings::Settings;
 use std::{iter::Peekable, str::Chars};
 use util::serde::default_true;
 use workspace::{notifications::NotifyResultExt, searchable::Direction};
@@ -55,16 +60,33 @@ pub struct FindCommand {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ReplaceCommand {
-    pub(crate) range: CommandRange,
+    /// The lines to substitute on, or the line of each cursor when the command had no range.
+    pub(crate) range: Option<CommandRange>,
     pub(crate) replacement: Replacement,
 }
 
+/// A `:substitute` as it was typed, which can refer back to the previous substitution.
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct Replacement {
+    /// `None` reuses the pattern of the previous substitution, and an empty pattern is the last
+    /// search.
+    search: Option<String>,
+    /// `None` reuses the replacement of the previous substitution.
+    replacement: Option<String>,
+    flags: String,
+    count: Option<u32>,
+}
+
+/// A substitution with the parts it reuses from the previous one filled in.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Substitution {
     search: String,
+    /// The replacement in vim's syntax, with any `~` already expanded.
     replacement: String,
-    should_replace_all: bool,
-    is_case_sensitive: bool,
+    replace_all: bool,
+    case_sensitive: Option<bool>,
+    ignore_missing: bool,
+    report_only: bool,
 }
 
 actions!(vim, [SearchSubmit, MoveToNextMatch, MoveToPreviousMatch]);
@@ -425,100 +447,107 @@ impl Vim {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let replacement = action.replacement.clone();
-        let Some(((pane, workspace), editor)) = self
-            .pane(window, cx)
-            .zip(self.workspace(window))
-            .zip(self.editor())
-        else {
+        let Some(workspace) = self.workspace(window) else {
             return;
         };
-        if let Some(result) = self.update_editor(window, cx, |vim, editor, window, cx| {
-            let range = action.range.buffer_range(vim, editor, window, cx)?;
-            let snapshot = &editor.snapshot(window, cx).buffer_snapshot;
-            let end_point = Point::new(range.end.0, snapshot.line_len(range.end));
-            let range = snapshot.anchor_before(Point::new(range.start.0, 0))
-                ..snapshot.anchor_after(end_point);
-            editor.set_search_within_ranges(&[range], cx);
-            anyhow::Ok(())
-        }) {
-            workspace.update(cx, |workspace, cx| {
-                result.notify_err(workspace, cx);
-            })
-        }
-        let vim = cx.entity().clone();
-        pane.update(cx, |pane, cx| {
-            let mut options = SearchOptions::REGEX;
-
-            let Some(search_bar) = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>() else {
+        self.switch_mode(Mode::Normal, false, window, cx);
+        let search_bar = self.pane(window, cx).and_then(|pane| {
+            pane.read(cx)
+                .toolbar()
+                .read(cx)
+                .item_of_type::<BufferSearchBar>()
+        });
+        let last_search = search_bar
+            .as_ref()
+            .map(|search_bar| search_bar.read(cx).query(cx));
+        let previous = Vim::globals(cx).last_substitution.clone();
+        let substitution = match action.replacement.resolve(previous.as_ref(), last_search) {
+            Ok(substitution) => substitution,
+            Err(error) => {
+                workspace.update(cx, |workspace, cx| {
+                    Err::<(), _>(error).notify_err(workspace, cx);
+                });
                 return;
-            };
-            let search = search_bar.update(cx, |search_bar, cx| {
-                if !search_bar.show(window, cx) {
-                    return None;
-                }
+            }
+        };
+        Vim::globals(cx).last_substitution = Some(substitution.clone());
 
-                if replacement.is_case_sensitive {
-                    options.set(SearchOptions::CASE_SENSITIVE, true)
-                }
-                let search = if replacement.search.is_empty() {
-                    search_bar.query(cx)
-                } else {
-                    replacement.search
-                };
-                if search_bar.should_use_smartcase_search(cx) {
-                    options.set(
-                        SearchOptions::CASE_SENSITIVE,
-                        search_bar.is_contains_uppercase(&search),
-                    );
+        let case_sensitive = substitution.case_sensitive.unwrap_or_else(|| {
+            !EditorSettings::get_global(cx).use_smartcase_search
+                || substitution.search.chars().any(char::is_uppercase)
+        });
+        let result = self.update_editor(window, cx, |vim, editor, window, cx| {
+            let rows = action.rows(vim, editor, window, cx)?;
+            substitution.apply(rows, case_sensitive, editor, window, cx)
+        });
+        match result {
+            Some(Ok(report)) => {
+                if let Some(report) = report {
+                    self.status_label = Some(report.into());
                 }
+            }
+            Some(Err(error)) => {
+                workspace.update(cx, |workspace, cx| {
+                    Err::<(), _>(error).notify_err(workspace, cx);
+                });
+                return;
+            }
+            None => return,
+        }
 
-                if !replacement.should_replace_all {
-                    options.set(SearchOptions::ONE_MATCH_PER_LINE, true);
+        // Like in vim, `n` continues with the substituted pattern.
+        if let Some(search_bar) = search_bar {
+            let mut options = SearchOptions::REGEX;
+            options.set(SearchOptions::CASE_SENSITIVE, case_sensitive);
+            search_bar.update(cx, |search_bar, cx| {
+                if search_bar.show(window, cx) {
+                    drop(search_bar.search(&substitution.search, Some(options), window, cx));
                 }
-
-                search_bar.set_replacement(Some(&replacement.replacement), cx);
-                Some(search_bar.search(&search, Some(options), window, cx))
             });
-            let Some(search) = search else { return };
-            let search_bar = search_bar.downgrade();
-            cx.spawn_in(window, async move |_, cx| {
-                search.await?;
-                search_bar.update_in(cx, |search_bar, window, cx| {
-                    search_bar.select_last_match(window, cx);
-                    search_bar.replace_all(&Default::default(), window, cx);
-                    editor.update(cx, |editor, cx| editor.clear_search_within_ranges(cx));
-                    let _ = search_bar.search(&search_bar.query(cx), None, window, cx);
-                    vim.update(cx, |vim, cx| {
-                        vim.move_cursor(
-                            Motion::StartOfLine {
-                                display_lines: false,
-                            },
-                            None,
-                            window,
-                            cx,
-                        )
-                    });
+        }
+    }
+}
 
-                    // Disable the `ONE_MATCH_PER_LINE` search option when finished, as
-                    // this is not properly supported outside of vim mode, and
-                    // not disabling it makes the "Replace All Matches" button
-                    // actually replace only the first match on each line.
-                    options.set(SearchOptions::ONE_MATCH_PER_LINE, false);
-                    search_bar.set_search_options(options, cx);
-                })?;
-                anyhow::Ok(())
-            })
-            .detach_and_log_err(cx);
-        })
+impl ReplaceCommand {
+    fn rows(
+        &self,
+        vim: &Vim,
+        editor: &mut Editor,
+        window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) -> anyhow::Result<Vec<MultiBufferRow>> {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let mut rows: Vec<MultiBufferRow> = match &self.range {
+            Some(range) => {
+                let range = range.buffer_range(vim, editor, window, cx)?;
+                (range.start.0..=range.end.0).map(MultiBufferRow).collect()
+            }
+            None => editor
+                .selections
+                .all::<Point>(cx)
+                .into_iter()
+                .map(|selection| MultiBufferRow(selection.head().row))
+                .dedup()
+                .collect(),
+        };
+        // A count substitutes on that many lines, starting with the last line of the range.
+        if let Some(count) = self.replacement.count {
+            let start = rows.last().copied().unwrap_or(MultiBufferRow(0));
+            let end = start
+                .0
+                .saturating_add(count.saturating_sub(1))
+                .min(snapshot.max_row().0);
+            rows = (start.0..=end).map(MultiBufferRow).collect();
+        }
+        Ok(rows)
     }
 }
 
 impl Replacement {
     // convert a vim query into something more usable by zed.
     // we don't attempt to fully convert between the two regex syntaxes,
-    // but we do flip \( and \) to ( and ) (and vice-versa) in the pattern,
-    // and convert \0..\9 to $0..$9 in the replacement so that common idioms work.
+    // but we do flip \( and \) to ( and ) (and vice-versa) in the pattern.
+    // The replacement is kept as is, and expanded by `expand_replacement` for each match.
     pub(crate) fn parse(mut chars: Peekable<Chars>) -> Option<Replacement> {
         let delimiter = chars
             .next()
@@ -539,10 +568,8 @@ impl Replacement {
         for c in chars {
             if escaped {
                 escaped = false;
-                if phase == 1 && c.is_ascii_digit() {
-                    buffer.push('$')
                 // unescape escaped parens
-                } else if phase == 0 && (c == '(' || c == ')') {
+                if phase == 0 && (c == '(' || c == ')') {
                 } else if c != delimiter {
                     buffer.push('\\')
                 }
@@ -568,25 +595,247 @@ impl Replacement {
             }
         }
 
-        let mut replacement = Replacement {
+        let (flags, count) = Self::parse_flags(&flags);
+        Some(Replacement {
+            search: Some(search),
+            replacement: Some(replacement),
+            flags,
+            count,
+        })
+    }
+
+    /// Parses `:&`, which repeats the previous substitution, and `:~`, which repeats it with the
+    /// last search pattern. Neither keep the previous flags unless given the `&` flag.
+    pub(crate) fn parse_repeat(query: &str) -> Option<Replacement> {
+        let (search, flags) = if let Some(flags) = query.strip_prefix('&') {
+            (None, flags)
+        } else if let Some(flags) = query.strip_prefix('~') {
+            (Some(String::new()), flags)
+        } else {
+            return None;
+        };
+        let (flags, count) = Self::parse_flags(flags);
+        Some(Replacement {
             search,
-            replacement,
-            should_replace_all: false,
-            is_case_sensitive: true,
+            replacement: None,
+            flags,
+            count,
+        })
+    }
+
+    fn parse_flags(flags: &str) -> (String, Option<u32>) {
+        let flags = flags.trim_start();
+        let flags_len = flags
+            .find(|c: char| !"&cegiInp#lr".contains(c))
+            .unwrap_or(flags.len());
+        let (flags, count) = flags.split_at(flags_len);
+        (flags.to_string(), count.trim().parse().ok())
+    }
+
+    pub(crate) fn resolve(
+        &self,
+        previous: Option<&Substitution>,
+        last_search: Option<String>,
+    ) -> anyhow::Result<Substitution> {
+        let search = match &self.search {
+            None => previous
+                .map(|previous| previous.search.clone())
+                .context("No previous substitute regular expression")?,
+            Some(search) if search.is_empty() => last_search
+                .filter(|search| !search.is_empty())
+                .context("No previous regular expression")?,
+            Some(search) => search.clone(),
+        };
+        let replacement = match &self.replacement {
+            None => previous
+                .map(|previous| previous.replacement.clone())
+                .context("No previous substitute")?,
+            Some(replacement) => expand_tilde(
+                replacement,
+                previous.map_or("", |previous| &previous.replacement),
+            ),
         };
 
-        for c in flags.chars() {
-            match c {
-                'g' => replacement.should_replace_all = true,
-                'c' | 'n' => replacement.should_replace_all = false,
-                'i' => replacement.is_case_sensitive = false,
-                'I' => replacement.is_case_sensitive = true,
+        let mut substitution = Substitution {
+            search,
+            replacement,
+            replace_all: false,
+            case_sensitive: None,
+            ignore_missing: false,
+            report_only: false,
+        };
+        for (ix, flag) in self.flags.chars().enumerate() {
+            match flag {
+                '&' if ix == 0 => {
+                    if let Some(previous) = previous {
+                        substitution.replace_all = previous.replace_all;
+                        substitution.case_sensitive = previous.case_sensitive;
+                        substitution.ignore_missing = previous.ignore_missing;
+                    }
+                }
+                'g' => substitution.replace_all = true,
+                'i' => substitution.case_sensitive = Some(false),
+                'I' => substitution.case_sensitive = Some(true),
+                'e' => substitution.ignore_missing = true,
+                'n' => substitution.report_only = true,
                 _ => {}
             }
         }
+        Ok(substitution)
+    }
+}
 
-        Some(replacement)
+impl Substitution {
+    /// Substitutes on `rows` in a single transaction, leaving the cursor at the first non-blank
+    /// character of the last line that changed. With the `n` flag nothing is substituted, and the
+    /// number of matches is returned instead.
+    fn apply(
+        &self,
+        rows: Vec<MultiBufferRow>,
+        case_sensitive: bool,
+        editor: &mut Editor,
+        window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) -> anyhow::Result<Option<String>> {
+        let regex = RegexBuilder::new(&self.search)
+            .case_insensitive(!case_sensitive)
+            .build()?;
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+
+        let mut edits = Vec::new();
+        let mut matched_rows = 0;
+        let mut last_row = None;
+        for row in rows {
+            let line_start = Point::new(row.0, 0);
+            let line = snapshot
+                .text_for_range(line_start..Point::new(row.0, snapshot.line_len(row)))
+                .collect::<String>();
+            let line_offset = snapshot.point_to_offset(line_start);
+            let edit_count = edits.len();
+            for captures in regex.captures_iter(&line) {
+                let Some(matched) = captures.get(0) else {
+                    continue;
+                };
+                edits.push((
+                    line_offset + matched.start()..line_offset + matched.end(),
+                    expand_replacement(&self.replacement, &captures),
+                ));
+                if !self.replace_all {
+                    break;
+                }
+            }
+            if edits.len() > edit_count {
+                matched_rows += 1;
+                last_row = Some(row);
+            }
+        }
+
+        let Some(last_row) = last_row else {
+            anyhow::ensure!(
+                self.ignore_missing || self.report_only,
+                "Pattern not found: {}",
+                self.search
+            );
+            return Ok(None);
+        };
+        if self.report_only {
+            let matches = edits.len();
+            return Ok(Some(format!(
+                "{matches} match{} on {matched_rows} line{}",
+                if matches == 1 { "" } else { "es" },
+                if matched_rows == 1 { "" } else { "s" },
+            )));
+        }
+
+        editor.transact(window, cx, |editor, window, cx| {
+            editor.edit(edits, cx);
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let indent = snapshot.indent_size_for_line(last_row).len;
+            let cursor = Point::new(last_row.0, indent);
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_ranges([cursor..cursor]);
+            });
+        });
+        Ok(None)
+    }
+}
+
+/// Replaces each unescaped `~` in a replacement with the previous replacement.
+fn expand_tilde(replacement: &str, previous: &str) -> String {
+    let mut expanded = String::new();
+    let mut chars = replacement.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                expanded.push(c);
+                expanded.extend(chars.next());
+            }
+            '~' => expanded.push_str(previous),
+            _ => expanded.push(c),
+        }
+    }
+    expanded
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CaseConversion {
+    None,
+    Upper,
+    Lower,
+}
+
+/// Expands a replacement in vim's syntax for one match: `&` and `\0` insert the whole match,
+/// `\1`..`\9` a group, `\r` and `\n` a newline, `\t` a tab, and `\u`, `\l`, `\U`, `\L` and `\E`
+/// change the case of what follows.
+fn expand_replacement(replacement: &str, captures: &Captures) -> String {
+    let mut expanded = String::new();
+    let mut next_char = CaseConversion::None;
+    let mut until_end = CaseConversion::None;
+    let mut push = |text: &str, next_char: &mut CaseConversion, until_end: CaseConversion| {
+        for c in text.chars() {
+            let conversion = match std::mem::replace(next_char, CaseConversion::None) {
+                CaseConversion::None => until_end,
+                conversion => conversion,
+            };
+            match conversion {
+                CaseConversion::None => expanded.push(c),
+                CaseConversion::Upper => expanded.extend(c.to_uppercase()),
+                CaseConversion::Lower => expanded.extend(c.to_lowercase()),
+            }
+        }
+    };
+
+    let mut chars = replacement.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => push(
+                captures.get(0).map_or("", |group| group.as_str()),
+                &mut next_char,
+                until_end,
+            ),
+            '\\' => match chars.next() {
+                Some(digit @ '0'..='9') => {
+                    let group = digit as usize - '0' as usize;
+                    push(
+                        captures.get(group).map_or("", |group| group.as_str()),
+                        &mut next_char,
+                        until_end,
+                    )
+                }
+                Some('r' | 'n') => push("\n", &mut next_char, until_end),
+                Some('t') => push("\t", &mut next_char, until_end),
+                Some('u') => next_char = CaseConversion::Upper,
+                Some('l') => next_char = CaseConversion::Lower,
+                Some('U') => until_end = CaseConversion::Upper,
+                Some('L') => until_end = CaseConversion::Lower,
+                Some('E' | 'e') => until_end = CaseConversion::None,
+                Some(c) => push(c.encode_utf8(&mut [0; 4]), &mut next_char, until_end),
+                None => {}
+            },
+            c => push(c.encode_utf8(&mut [0; 4]), &mut next_char, until_end),
+        }
     }
+    expanded
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ use std::ops::Range;
 
 use crate::{
     Vim,
+    custom_object::ResolvedCustomObject,
     motion::right,
     state::{Mode, Operator},
 };
@@ -18,11 +19,15 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use ui::Context;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Object {
-    Word { ignore_punctuation: bool },
-    Subword { ignore_punctuation: bool },
+    Word {
+        ignore_punctuation: bool,
+    },
+    Subword {
+        ignore_punctuation: bool,
+    },
     Sentence,
     Paragraph,
     Quotes,
@@ -38,12 +43,16 @@ pub enum Object {
     CurlyBrackets,
     AngleBrackets,
     Argument,
-    IndentObj { include_below: bool },
+    IndentObj {
+        include_below: bool,
+    },
     Tag,
     Method,
     Class,
     Comment,
     EntireFile,
+    #[serde(skip)]
+    Custom(ResolvedCustomObject),
 }
 
 #[derive(Clone, Deserialize, JsonSchema, PartialEq)]
@@ -371,13 +380,14 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
 }
 
 impl Vim {
-    fn object(&mut self, object: Object, window: &mut Window, cx: &mut Context<Self>) {
+    pub(crate) fn object(&mut self, object: Object, window: &mut Window, cx: &mut Context<Self>) {
         match self.mode {
             Mode::Normal => self.normal_object(object, window, cx),
             Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
                 self.visual_object(object, window, cx)
             }
-            Mode::Insert | Mode::Replace | Mode::HelixNormal => {
+            Mode::HelixNormal => self.helix_select_object(object, window, cx),
+            Mode::Insert | Mode::Replace => {
                 // Shouldn't execute a text object in insert mode. Ignoring
             }
         }
@@ -385,8 +395,8 @@ impl Vim {
 }
 
 impl Object {
-    pub fn is_multiline(self) -> bool {
-        match self {
+    pub fn is_multiline(&self) -> bool {
+        match *self {
             Object::Word { .. }
             | Object::Subword { .. }
             | Object::Quotes
@@ -409,12 +419,13 @@ impl Object {
             | Object::Class
             | Object::EntireFile
             | Object::Comment
-            | Object::IndentObj { .. } => true,
+            | Object::IndentObj { .. }
+            | Object::Custom(_) => true,
         }
     }
 
-    pub fn always_expands_both_ways(self) -> bool {
-        match self {
+    pub fn always_expands_both_ways(&self) -> bool {
+        match *self {
             Object::Word { .. }
             | Object::Subword { .. }
             | Object::Sentence
@@ -437,12 +448,13 @@ impl Object {
             | Object::Comment
             | Object::EntireFile
             | Object::CurlyBrackets
-            | Object::AngleBrackets => true,
+            | Object::AngleBrackets
+            | Object::Custom(_) => true,
         }
     }
 
-    pub fn target_visual_mode(self, current_mode: Mode, around: bool) -> Mode {
-        match self {
+    pub fn target_visual_mode(&self, current_mode: Mode, around: bool) -> Mode {
+        match *self {
             Object::Word { .. }
             | Object::Subword { .. }
             | Object::Sentence
@@ -476,17 +488,24 @@ impl Object {
                 }
             }
             Object::Paragraph | Object::EntireFile => Mode::VisualLine,
+            Object::Custom(ref object) => {
+                if object.is_linewise(around) {
+                    Mode::VisualLine
+                } else {
+                    Mode::Visual
+                }
+            }
         }
     }
 
     pub fn range(
-        self,
+        &self,
         map: &DisplaySnapshot,
         selection: Selection<DisplayPoint>,
         around: bool,
     ) -> Option<Range<DisplayPoint>> {
         let relative_to = selection.head();
-        match self {
+        match *self {
             Object::Word { ignore_punctuation } => {
                 if around {
                     around_word(map, relative_to, ignore_punctuation)
@@ -683,11 +702,16 @@ impl Object {
             Object::Argument => argument(map, relative_to, around),
             Object::IndentObj { include_below } => indent(map, relative_to, around, include_below),
             Object::EntireFile => entire_file(map),
+            Object::Custom(ref object) => {
+                let cursor = relative_to.to_offset(map, Bias::Left);
+                let range = object.range(&map.buffer_snapshot, cursor, around)?;
+                Some(range.start.to_display_point(map)..range.end.to_display_point(map))
+            }
         }
     }
 
     pub fn expand_selection(
-        self,
+        &self,
         map: &DisplaySnapshot,
         selection: &mut Selection<DisplayPoint>,
         around: bool,
@@ -1,7 +1,8 @@
 use crate::command::command_interceptor;
 use crate::motion::MotionKind;
 use crate::normal::repeat::Replayer;
-use crate::surrounds::SurroundsType;
+use crate::normal::search::Substitution;
+use crate::surrounds::{SurroundsType, TagSurroundsTarget};
 use crate::{ToggleMarksView, ToggleRegistersView, UseSystemClipboard, Vim, VimAddon, VimSettings};
 use crate::{motion::Motion, object::Object};
 use anyhow::Result;
@@ -13,7 +14,8 @@ use editor::display_map::{is_invisible, replacement};
 use editor::{Anchor, ClipboardSelection, Editor, MultiBuffer, ToPoint as EditorToPoint};
 use gpui::{
     Action, App, AppContext, BorrowAppContext, ClipboardEntry, ClipboardItem, DismissEvent, Entity,
-    EntityId, Global, HighlightStyle, StyledText, Subscription, Task, TextStyle, WeakEntity,
+    EntityId, Global, HighlightStyle, Modifiers, StyledText, Subscription, Task, TextStyle,
+    WeakEntity,
 };
 use language::{Buffer, BufferEvent, BufferId, Chunk, Point};
 use multi_buffer::MultiBufferRow;
@@ -67,6 +69,17 @@ impl Mode {
             Self::Normal | Self::Insert | Self::Replace | Self::HelixNormal => false,
         }
     }
+
+    /// The name of the mode in the `vim_mode` key context.
+    pub fn key_context_name(&self) -> &'static str {
+        match self {
+            Mode::Normal => "normal",
+            Mode::Visual | Mode::VisualLine | Mode::VisualBlock => "visual",
+            Mode::Insert => "insert",
+            Mode::Replace => "replace",
+            Mode::HelixNormal => "helix_normal",
+        }
+    }
 }
 
 impl Default for Mode {
@@ -104,6 +117,10 @@ pub enum Operator {
         target: Option<Object>,
     },
     DeleteSurrounds,
+    SurroundTag {
+        target: TagSurroundsTarget,
+        tag: String,
+    },
     Mark,
     Jump {
         line: bool,
@@ -194,6 +211,7 @@ impl From<String> for Register {
 #[derive(Default)]
 pub struct VimGlobals {
     pub last_find: Option<Motion>,
+    pub(crate) last_substitution: Option<Substitution>,
 
     pub dot_recording: bool,
     pub dot_replaying: bool,
@@ -233,10 +251,14 @@ pub struct MarksState {
 
     serialized_marks: HashMap<Arc<Path>, HashMap<String, Vec<Point>>>,
     global_marks: HashMap<String, MarkLocation>,
+    serialized_change_lists: HashMap<Arc<Path>, Vec<Vec<Point>>>,
 
     _subscription: Subscription,
 }
 
+/// How many changes are kept per file, matching vim's changelist.
+const MAX_CHANGE_LIST_LEN: usize = 100;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MarkLocation {
     Buffer(EntityId),
@@ -271,6 +293,7 @@ impl MarksState {
                 watched_buffers: HashMap::default(),
                 serialized_marks: HashMap::default(),
                 global_marks: HashMap::default(),
+                serialized_change_lists: HashMap::default(),
                 _subscription: subscription,
             };
 
@@ -297,14 +320,18 @@ impl MarksState {
             let Some(workspace_id) = this.update(cx, |this, cx| this.workspace_id(cx))? else {
                 return Ok(());
             };
-            let (marks, paths) = cx
+            let (marks, paths, change_lists) = cx
                 .background_spawn(async move {
                     let marks = DB.get_marks(workspace_id)?;
                     let paths = DB.get_global_marks_paths(workspace_id)?;
-                    anyhow::Ok((marks, paths))
+                    let change_lists = DB.get_change_lists(workspace_id)?;
+                    anyhow::Ok((marks, paths, change_lists))
                 })
                 .await?;
-            this.update(cx, |this, cx| this.loaded(marks, paths, cx))
+            this.update(cx, |this, cx| {
+                this.serialized_change_lists.extend(change_lists);
+                this.loaded(marks, paths, cx)
+            })
         })
         .detach_and_log_err(cx);
     }
@@ -613,6 +640,68 @@ impl MarksState {
             }
         }
     }
+
+    /// The changes recorded for the file open in `multi_buffer` in earlier sessions.
+    pub fn change_list(
+        &self,
+        multi_buffer: &Entity<MultiBuffer>,
+        cx: &App,
+    ) -> Option<Vec<Vec<Anchor>>> {
+        let singleton = multi_buffer.read(cx).as_singleton()?;
+        let path = self.path_for_buffer(&singleton, cx)?;
+        let changes = self.serialized_change_lists.get(&path)?;
+        let snapshot = multi_buffer.read(cx).snapshot(cx);
+        Some(
+            changes
+                .iter()
+                .map(|points| {
+                    points
+                        .iter()
+                        .map(|point| {
+                            snapshot.anchor_before(snapshot.clip_point(*point, Bias::Left))
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    pub fn set_change_list(
+        &mut self,
+        multi_buffer: &Entity<MultiBuffer>,
+        changes: &[Vec<Anchor>],
+        cx: &mut Context<Self>,
+    ) {
+        let Some(singleton) = multi_buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let Some(path) = self.path_for_buffer(&singleton, cx) else {
+            return;
+        };
+        let snapshot = multi_buffer.read(cx).snapshot(cx);
+        let changes = changes[changes.len().saturating_sub(MAX_CHANGE_LIST_LEN)..]
+            .iter()
+            .map(|anchors| {
+                anchors
+                    .iter()
+                    .map(|anchor| anchor.to_point(&snapshot))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        if self.serialized_change_lists.get(&path) == Some(&changes) {
+            return;
+        }
+        self.serialized_change_lists
+            .insert(path.clone(), changes.clone());
+
+        if let Some(workspace_id) = self.workspace_id(cx) {
+            cx.background_spawn(
+                async move { DB.set_change_list(workspace_id, path, changes).await },
+            )
+            .detach_and_log_err(cx);
+        }
+    }
+
     pub fn delete_mark(
         &mut self,
         mark_name: String,
@@ -1003,6 +1092,14 @@ impl Operator {
             Operator::AddSurrounds { .. } => "ys",
             Operator::ChangeSurrounds { .. } => "cs",
             Operator::DeleteSurrounds => "ds",
+            Operator::SurroundTag {
+                target: TagSurroundsTarget::Add(_),
+                ..
+            } => "ys",
+            Operator::SurroundTag {
+                target: TagSurroundsTarget::Change(_),
+                ..
+            } => "cs",
             Operator::Mark => "m",
             Operator::Jump { line: true } => "'",
             Operator::Jump { line: false } => "`",
@@ -1033,6 +1130,7 @@ impl Operator {
             Operator::Literal {
                 prefix: Some(prefix),
             } => format!("^V{prefix}"),
+            Operator::SurroundTag { tag, .. } => format!("<{tag}"),
             Operator::AutoIndent => "=".to_string(),
             Operator::ShellCommand => "=".to_string(),
             _ => self.id().to_string(),
@@ -1055,7 +1153,8 @@ impl Operator {
             | Operator::Digraph { .. }
             | Operator::Literal { .. }
             | Operator::ChangeSurrounds { target: Some(_) }
-            | Operator::DeleteSurrounds => true,
+            | Operator::DeleteSurrounds
+            | Operator::SurroundTag { .. } => true,
             Operator::Change
             | Operator::Delete
             | Operator::Yank
@@ -1109,6 +1208,7 @@ impl Operator {
             | Operator::Literal { .. }
             | Operator::AddSurrounds { .. }
             | Operator::ChangeSurrounds { .. }
+            | Operator::SurroundTag { .. }
             | Operator::Jump { .. }
             | Operator::Register
             | Operator::RecordRegister
@@ -1120,6 +1220,8 @@ impl Operator {
 struct RegisterMatch {
     name: char,
     contents: SharedString,
+    /// Whether `contents` describes a recording replayed with `@`.
+    is_recording: bool,
 }
 
 pub struct RegistersViewDelegate {
@@ -1174,7 +1276,11 @@ impl PickerDelegate for RegistersViewDelegate {
 
         let mut output = String::new();
         let mut runs = Vec::new();
-        output.push('"');
+        output.push(if register_match.is_recording {
+            '@'
+        } else {
+            '"'
+        });
         output.push(register_match.name);
         runs.push((
             0..output.len(),
@@ -1268,6 +1374,7 @@ impl RegistersView {
                     matches.push(RegisterMatch {
                         name,
                         contents: register.text.clone(),
+                        is_recording: false,
                     })
                 }
             }
@@ -1279,6 +1386,7 @@ impl RegistersView {
                     matches.push(RegisterMatch {
                         name: '%',
                         contents: register.text.clone(),
+                        is_recording: false,
                     })
                 }
             }
@@ -1289,10 +1397,18 @@ impl RegistersView {
                 matches.push(RegisterMatch {
                     name: *name,
                     contents: register.text.clone(),
+                    is_recording: false,
+                })
+            }
+            for (name, actions) in globals.recordings.iter() {
+                matches.push(RegisterMatch {
+                    name: *name,
+                    contents: recording_keys(actions, window).into(),
+                    is_recording: true,
                 })
             }
         });
-        matches.sort_by(|a, b| a.name.cmp(&b.name));
+        matches.sort_by_key(|register_match| (register_match.is_recording, register_match.name));
         let delegate = RegistersViewDelegate {
             selected_index: 0,
             matches,
@@ -1304,6 +1420,34 @@ impl RegistersView {
     }
 }
 
+/// Describes a recording as the keys that replay it, using the key binding of each action.
+fn recording_keys(actions: &[ReplayableAction], window: &Window) -> String {
+    let mut keys = String::new();
+    for action in actions {
+        match action {
+            ReplayableAction::Action(action) => {
+                let Some(binding) = window.highest_precedence_binding_for_action(action.as_ref())
+                else {
+                    keys.push_str(&format!("<{}>", action.name()));
+                    continue;
+                };
+                for keystroke in binding.keystrokes() {
+                    let is_char = keystroke.key.chars().count() == 1;
+                    if is_char && !keystroke.modifiers.modified() {
+                        keys.push_str(&keystroke.key);
+                    } else if is_char && keystroke.modifiers == Modifiers::shift() {
+                        keys.push_str(&keystroke.key.to_uppercase());
+                    } else {
+                        keys.push_str(&format!("<{}>", keystroke.unparse()));
+                    }
+                }
+            }
+            ReplayableAction::Insertion { text, .. } => keys.push_str(text),
+        }
+    }
+    keys
+}
+
 enum MarksMatchInfo {
     Path(Arc<Path>),
     Title(String),
@@ -1684,6 +1828,15 @@ define_connection! (
             CREATE UNIQUE INDEX idx_vim_global_marks_paths
             ON vim_global_marks_paths(workspace_id, mark_name);
         ),
+        sql! (
+            CREATE TABLE vim_change_lists(
+                workspace_id INTEGER,
+                path BLOB,
+                value TEXT
+            );
+            CREATE UNIQUE INDEX idx_vim_change_lists
+            ON vim_change_lists(workspace_id, path);
+        ),
     ];
 );
 
@@ -1788,6 +1941,62 @@ impl VimDb {
         ))?(workspace_id)
     }
 
+    pub(crate) async fn set_change_list(
+        &self,
+        workspace_id: WorkspaceId,
+        path: Arc<Path>,
+        changes: Vec<Vec<Point>>,
+    ) -> Result<()> {
+        let changes: Vec<Vec<(u32, u32)>> = changes
+            .into_iter()
+            .map(|points| {
+                points
+                    .into_iter()
+                    .map(|point| (point.row, point.column))
+                    .collect()
+            })
+            .collect();
+        let serialized = serde_json::to_string(&changes)?;
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                INSERT OR REPLACE INTO vim_change_lists
+                    (workspace_id, path, value)
+                VALUES
+                    (?, ?, ?)
+            ))?((workspace_id, path, serialized))
+        })
+        .await
+    }
+
+    fn get_change_lists(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Vec<(Arc<Path>, Vec<Vec<Point>>)>> {
+        let result: Vec<(Arc<Path>, String)> = self.select_bound(sql!(
+            SELECT path, value FROM vim_change_lists
+                WHERE workspace_id = ?
+        ))?(workspace_id)?;
+
+        Ok(result
+            .into_iter()
+            .filter_map(|(path, value)| {
+                let changes: Vec<Vec<(u32, u32)>> = serde_json::from_str(&value).log_err()?;
+                Some((
+                    path,
+                    changes
+                        .into_iter()
+                        .map(|pairs| {
+                            pairs
+                                .into_iter()
+                                .map(|(row, column)| Point { row, column })
+                                .collect()
+                        })
+                        .collect(),
+                ))
+            })
+            .collect())
+    }
+
     pub(crate) async fn delete_global_marks_path(
         &self,
         workspace_id: WorkspaceId,
@@ -1802,3 +2011,40 @@ impl VimDb {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_change_list_persistence() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let path: Arc<Path> = Path::new("/root/changed.rs").into();
+        assert_eq!(DB.get_change_lists(workspace_id).unwrap(), Vec::new());
+
+        let changes = vec![
+            vec![Point::new(1, 2)],
+            vec![Point::new(3, 0), Point::new(4, 5)],
+        ];
+        DB.set_change_list(workspace_id, path.clone(), changes.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_change_lists(workspace_id).unwrap(),
+            vec![(path.clone(), changes)]
+        );
+
+        // Saving the change list of a file again replaces the earlier one.
+        let changes = vec![vec![Point::new(7, 1)]];
+        DB.set_change_list(workspace_id, path.clone(), changes.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_change_lists(workspace_id).unwrap(),
+            vec![(path, changes)]
+        );
+
+        let other_workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        assert_eq!(DB.get_change_lists(other_workspace_id).unwrap(), Vec::new());
+    }
+}
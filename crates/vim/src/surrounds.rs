@@ -17,6 +17,13 @@ pub enum SurroundsType {
     Selection,
 }
 
+/// What a tag typed after `t` in `ys`, `cs` or visual mode surrounds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TagSurroundsTarget {
+    Add(SurroundsType),
+    Change(Object),
+}
+
 impl Vim {
     pub fn add_surrounds(
         &mut self,
@@ -34,17 +41,7 @@ impl Vim {
             editor.transact(window, cx, |editor, window, cx| {
                 editor.set_clip_at_line_ends(false, cx);
 
-                let pair = match find_surround_pair(&all_support_surround_pair(), &text) {
-                    Some(pair) => pair.clone(),
-                    None => BracketPair {
-                        start: text.to_string(),
-                        end: text.to_string(),
-                        close: true,
-                        surround: true,
-                        newline: false,
-                    },
-                };
-                let surround = pair.end != surround_alias((*text).as_ref());
+                let (pair, surround) = surround_pair(&text);
                 let (display_map, display_selections) = editor.selections.all_adjusted_display(cx);
                 let mut edits = Vec::new();
                 let mut anchors = Vec::new();
@@ -128,6 +125,10 @@ impl Vim {
         cx: &mut Context<Self>,
     ) {
         self.stop_recording(cx);
+        if text.as_ref() == "t" {
+            self.replace_surrounding_tags(None, window, cx);
+            return;
+        }
 
         // only legitimate surrounds can be removed
         let pair = match find_surround_pair(&all_support_surround_pair(), &text) {
@@ -138,7 +139,7 @@ impl Vim {
             Some(pair_object) => pair_object,
             None => return,
         };
-        let surround = pair.end != *text;
+        let surround = pair.end != surround_alias(&text);
 
         self.update_editor(window, cx, |_, editor, window, cx| {
             editor.transact(window, cx, |editor, window, cx| {
@@ -224,23 +225,16 @@ impl Vim {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(will_replace_pair) = object_to_bracket_pair(target) {
+        if target == Object::Tag {
+            self.stop_recording(cx);
+            self.replace_surrounding_tags(Some(surround_pair(&text)), window, cx);
+        } else if let Some(will_replace_pair) = object_to_bracket_pair(&target) {
             self.stop_recording(cx);
             self.update_editor(window, cx, |_, editor, window, cx| {
                 editor.transact(window, cx, |editor, window, cx| {
                     editor.set_clip_at_line_ends(false, cx);
 
-                    let pair = match find_surround_pair(&all_support_surround_pair(), &text) {
-                        Some(pair) => pair.clone(),
-                        None => BracketPair {
-                            start: text.to_string(),
-                            end: text.to_string(),
-                            close: true,
-                            surround: true,
-                            newline: false,
-                        },
-                    };
-                    let surround = pair.end != surround_alias((*text).as_ref());
+                    let (pair, surround) = surround_pair(&text);
                     let (display_map, selections) = editor.selections.all_adjusted_display(cx);
                     let mut edits = Vec::new();
                     let mut anchors = Vec::new();
@@ -325,12 +319,66 @@ impl Vim {
         }
     }
 
+    /// Replaces the opening and closing tags of the element around each cursor with `pair`,
+    /// padding the element's contents with spaces if the pair's flag is set, or removes them.
+    fn replace_surrounding_tags(
+        &mut self,
+        pair: Option<(BracketPair, bool)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                editor.set_clip_at_line_ends(false, cx);
+
+                let (display_map, selections) = editor.selections.all_adjusted_display(cx);
+                let (open_str, close_str) = match &pair {
+                    Some((pair, true)) => (format!("{} ", pair.start), format!(" {}", pair.end)),
+                    Some((pair, false)) => (pair.start.clone(), pair.end.clone()),
+                    None => (String::new(), String::new()),
+                };
+                let mut edits = Vec::new();
+                let mut anchors = Vec::new();
+
+                for selection in &selections {
+                    let start = display_map
+                        .buffer_snapshot
+                        .anchor_before(selection.start.to_offset(&display_map, Bias::Left));
+                    let ranges = Object::Tag
+                        .range(&display_map, selection.clone(), true)
+                        .zip(Object::Tag.range(&display_map, selection.clone(), false));
+                    let Some((around, inside)) = ranges else {
+                        anchors.push(start..start);
+                        continue;
+                    };
+                    let around = around.start.to_offset(&display_map, Bias::Left)
+                        ..around.end.to_offset(&display_map, Bias::Left);
+                    let inside = inside.start.to_offset(&display_map, Bias::Left)
+                        ..inside.end.to_offset(&display_map, Bias::Left);
+                    edits.push((around.start..inside.start, open_str.clone()));
+                    edits.push((inside.end..around.end, close_str.clone()));
+                    let start = display_map.buffer_snapshot.anchor_before(around.start);
+                    anchors.push(start..start);
+                }
+
+                edits.sort_by_key(|(range, _)| range.start);
+                edits.dedup_by_key(|(range, _)| range.start);
+                editor.edit(edits, cx);
+                editor.set_clip_at_line_ends(true, cx);
+                editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                    s.select_anchor_ranges(anchors);
+                });
+            });
+        });
+    }
+
     /// Checks if any of the current cursors are surrounded by a valid pair of brackets.
     ///
     /// This method supports multiple cursors and checks each cursor for a valid pair of brackets.
     /// A pair of brackets is considered valid if it is well-formed and properly closed.
     ///
     /// If a valid pair of brackets is found, the method returns `true` and the cursor is automatically moved to the start of the bracket pair.
+    /// Cursors inside a tag pair are left where they are.
     /// If no valid pair of brackets is found for any cursor, the method returns `false`.
     pub fn check_and_move_to_valid_bracket_pair(
         &mut self,
@@ -338,8 +386,19 @@ impl Vim {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> bool {
+        if object == Object::Tag {
+            return self
+                .update_editor(window, cx, |_, editor, _, cx| {
+                    let (display_map, selections) = editor.selections.all_adjusted_display(cx);
+                    selections
+                        .into_iter()
+                        .any(|selection| Object::Tag.range(&display_map, selection, true).is_some())
+                })
+                .unwrap_or(false);
+        }
+
         let mut valid = false;
-        if let Some(pair) = object_to_bracket_pair(object) {
+        if let Some(pair) = object_to_bracket_pair(&object) {
             self.update_editor(window, cx, |_, editor, window, cx| {
                 editor.transact(window, cx, |editor, window, cx| {
                     editor.set_clip_at_line_ends(false, cx);
@@ -386,6 +445,41 @@ impl Vim {
     }
 }
 
+/// The pair that surrounds text for the typed `text`, and whether to add spaces inside it.
+///
+/// `text` is either a character or alias naming a pair, or a whole opening tag such as
+/// `<div class="x">`, which is closed with the tag's name alone.
+fn surround_pair(text: &str) -> (BracketPair, bool) {
+    if let Some(tag) = text
+        .strip_prefix('<')
+        .and_then(|tag| tag.strip_suffix('>'))
+        .filter(|tag| !tag.trim().is_empty())
+    {
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        let pair = BracketPair {
+            start: text.to_string(),
+            end: format!("</{name}>"),
+            close: true,
+            surround: true,
+            newline: false,
+        };
+        return (pair, false);
+    }
+
+    let pair = match find_surround_pair(&all_support_surround_pair(), text) {
+        Some(pair) => pair.clone(),
+        None => BracketPair {
+            start: text.to_string(),
+            end: text.to_string(),
+            close: true,
+            surround: true,
+            newline: false,
+        },
+    };
+    let surround = pair.end != surround_alias(text);
+    (pair, surround)
+}
+
 fn find_surround_pair<'a>(pairs: &'a [BracketPair], ch: &str) -> Option<&'a BracketPair> {
     pairs
         .iter()
@@ -484,7 +578,7 @@ fn pair_to_object(pair: &BracketPair) -> Option<Object> {
     }
 }
 
-fn object_to_bracket_pair(object: Object) -> Option<BracketPair> {
+fn object_to_bracket_pair(object: &Object) -> Option<BracketPair> {
     match object {
         Object::Quotes => Some(BracketPair {
             start: "'".to_string(),
@@ -1405,4 +1499,38 @@ mod test {
             Mode::Normal,
         );
     }
+
+    #[gpui::test]
+    async fn test_surround_tags(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new_html(cx).await;
+        cx.update(|_, cx| {
+            cx.bind_keys([KeyBinding::new(
+                "shift-s",
+                PushAddSurrounds {},
+                Some("vim_mode == visual"),
+            )])
+        });
+
+        cx.set_state("<p>The quˇick brown</p>", Mode::Normal);
+        cx.simulate_keystrokes("y s i w t e m >");
+        cx.assert_state("<p>The ˇ<em>quick</em> brown</p>", Mode::Normal);
+
+        cx.set_state("The quˇick brown", Mode::Normal);
+        cx.simulate_keystrokes("y s s t p enter");
+        cx.assert_state("ˇ<p>The quick brown</p>", Mode::Normal);
+
+        cx.set_state("The quˇick brown", Mode::Normal);
+        cx.simulate_keystrokes("v i w shift-s t b >");
+        cx.assert_state("The ˇ<b>quick</b> brown", Mode::Normal);
+
+        cx.set_state("<div><b>hˇi</b></div>", Mode::Normal);
+        cx.simulate_keystrokes("c s t t e m >");
+        cx.assert_state("<div>ˇ<em>hi</em></div>", Mode::Normal);
+        cx.simulate_keystrokes("c s t b");
+        cx.assert_state("<div>ˇ(hi)</div>", Mode::Normal);
+        cx.simulate_keystrokes("c s b t i >");
+        cx.assert_state("<div>ˇ<i>hi</i></div>", Mode::Normal);
+        cx.simulate_keystrokes("d s t");
+        cx.assert_state("<div>ˇhi</div>", Mode::Normal);
+    }
 }
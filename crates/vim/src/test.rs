@@ -11,7 +11,7 @@ use editor::{
     display_map::DisplayRow, test::editor_test_context::EditorTestContext,
 };
 use futures::StreamExt;
-use gpui::{KeyBinding, Modifiers, MouseButton, TestAppContext};
+use gpui::{EntityInputHandler, KeyBinding, Modifiers, MouseButton, TestAppContext};
 use language::Point;
 pub use neovim_backed_test_context::*;
 use settings::SettingsStore;
@@ -43,6 +43,25 @@ async fn test_neovim(cx: &mut gpui::TestAppContext) {
     cx.assert_editor_state("ˇtest");
 }
 
+#[gpui::test]
+async fn test_keys_go_to_input_method_while_composing(cx: &mut gpui::TestAppContext) {
+    let mut cx = VimTestContext::new(cx, true).await;
+
+    cx.set_state("ˇhello", Mode::Insert);
+    cx.update_editor(|editor, window, cx| {
+        editor.replace_and_mark_text_in_range(None, "か", None, window, cx);
+    });
+    cx.simulate_keystrokes("escape");
+    assert_eq!(cx.mode(), Mode::Insert);
+
+    cx.update_editor(|editor, window, cx| {
+        editor.replace_text_in_range(None, "か", window, cx);
+    });
+    cx.simulate_keystrokes("escape");
+    assert_eq!(cx.mode(), Mode::Normal);
+    cx.assert_editor_state("ˇかhello");
+}
+
 #[gpui::test]
 async fn test_toggle_through_settings(cx: &mut gpui::TestAppContext) {
     let mut cx = VimTestContext::new(cx, true).await;
@@ -1168,6 +1187,26 @@ async fn test_lowercase_marks(cx: &mut TestAppContext) {
         .assert_eq("line one\nˇtwo\nline three");
 }
 
+#[gpui::test]
+async fn test_jumplist(cx: &mut TestAppContext) {
+    let mut cx = VimTestContext::new(cx, true).await;
+
+    cx.set_state("ˇone\ntwo\nthree\nfour", Mode::Normal);
+    cx.simulate_keystrokes("G");
+    cx.assert_state("one\ntwo\nthree\nˇfour", Mode::Normal);
+    cx.simulate_keystrokes("ctrl-o");
+    cx.assert_state("ˇone\ntwo\nthree\nfour", Mode::Normal);
+    cx.simulate_keystrokes("ctrl-i");
+    cx.assert_state("one\ntwo\nthree\nˇfour", Mode::Normal);
+
+    // Moves that aren't jumps are not recorded.
+    cx.simulate_keystrokes("k k ctrl-o");
+    cx.assert_state("ˇone\ntwo\nthree\nfour", Mode::Normal);
+
+    cx.simulate_keystrokes("shift-l ` `");
+    cx.assert_state("ˇone\ntwo\nthree\nfour", Mode::Normal);
+}
+
 #[gpui::test]
 async fn test_lt_gt_marks(cx: &mut TestAppContext) {
     let mut cx = NeovimBackedTestContext::new(cx).await;
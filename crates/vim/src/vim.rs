@@ -5,6 +5,7 @@ mod test;
 
 mod change_list;
 mod command;
+mod custom_object;
 mod digraph;
 mod helix;
 mod indent;
@@ -21,8 +22,10 @@ mod visual;
 
 use anyhow::Result;
 use collections::HashMap;
+use custom_object::CustomTextObjects;
+pub use custom_object::{CustomTextObject, register_text_object};
 use editor::{
-    Anchor, Bias, Editor, EditorEvent, EditorSettings, HideMouseCursorOrigin, ToPoint,
+    Anchor, Bias, Editor, EditorEvent, EditorSettings, HideMouseCursorOrigin, ModalInput, ToPoint,
     movement::{self, FindRange},
 };
 use gpui::{
@@ -41,10 +44,10 @@ use serde_derive::Serialize;
 use settings::{Settings, SettingsSources, SettingsStore, update_settings_file};
 use state::{Mode, Operator, RecordedSelection, SearchState, VimGlobals};
 use std::{mem, ops::Range, sync::Arc};
-use surrounds::SurroundsType;
+use surrounds::{SurroundsType, TagSurroundsTarget};
 use theme::ThemeSettings;
 use ui::{IntoElement, SharedString, px};
-use vim_mode_setting::VimModeSetting;
+use vim_mode_setting::{HelixModeSetting, VimModeSetting};
 use workspace::{self, Pane, Workspace};
 
 use crate::state::ReplayableAction;
@@ -166,7 +169,7 @@ actions!(
 );
 
 // in the workspace namespace so it's not filtered out when vim is disabled.
-actions!(workspace, [ToggleVimMode,]);
+actions!(workspace, [ToggleVimMode, ToggleHelixMode]);
 
 impl_actions!(
     vim,
@@ -203,6 +206,14 @@ pub fn init(cx: &mut App) {
             })
         });
 
+        workspace.register_action(|workspace, _: &ToggleHelixMode, _, cx| {
+            let fs = workspace.app_state().fs.clone();
+            let currently_enabled = HelixModeSetting::get_global(cx).0;
+            update_settings_file::<HelixModeSetting>(fs, cx, move |setting, _| {
+                *setting = Some(!currently_enabled)
+            })
+        });
+
         workspace.register_action(|_, _: &OpenDefaultKeymap, _, cx| {
             cx.emit(workspace::Event::OpenBundledFile {
                 text: settings::vim_keymap(),
@@ -211,8 +222,8 @@ pub fn init(cx: &mut App) {
             });
         });
 
-        workspace.register_action(|workspace, _: &ResetPaneSizes, _, cx| {
-            workspace.reset_pane_sizes(cx);
+        workspace.register_action(|workspace, _: &ResetPaneSizes, window, cx| {
+            workspace.reset_pane_sizes(window, cx);
         });
 
         workspace.register_action(|workspace, _: &MaximizePane, window, cx| {
@@ -327,6 +338,7 @@ pub(crate) struct Vim {
     pub(crate) current_tx: Option<TransactionId>,
     pub(crate) current_anchor: Option<Selection<Anchor>>,
     pub(crate) undo_modes: HashMap<TransactionId, Mode>,
+    pub(crate) restored_change_list: bool,
 
     selected_register: Option<char>,
     pub search: SearchState,
@@ -360,7 +372,7 @@ impl Vim {
         let editor = cx.entity().clone();
 
         cx.new(|cx| Vim {
-            mode: VimSettings::get_global(cx).default_mode,
+            mode: Vim::default_mode(cx),
             last_mode: Mode::Normal,
             temp_mode: false,
             exit_temporary_mode: false,
@@ -371,6 +383,7 @@ impl Vim {
             current_tx: None,
             current_anchor: None,
             undo_modes: HashMap::default(),
+            restored_change_list: false,
 
             status_label: None,
             selected_register: None,
@@ -445,7 +458,7 @@ impl Vim {
 
         vim.update(cx, |_, cx| {
             Vim::action(editor, cx, |vim, _: &SwitchToNormalMode, window, cx| {
-                vim.switch_mode(vim.default_mode(cx), false, window, cx)
+                vim.switch_mode(Vim::default_mode(cx), false, window, cx)
             });
 
             Vim::action(editor, cx, |vim, _: &SwitchToInsertMode, window, cx| {
@@ -542,7 +555,7 @@ impl Vim {
                 |vim, action: &PushChangeSurrounds, window, cx| {
                     vim.push_operator(
                         Operator::ChangeSurrounds {
-                            target: action.target,
+                            target: action.target.clone(),
                         },
                         window,
                         cx,
@@ -712,6 +725,7 @@ impl Vim {
             indent::register(editor, cx);
             rewrap::register(editor, cx);
             object::register(editor, cx);
+            custom_object::register(editor, cx);
             visual::register(editor, cx);
             change_list::register(editor, cx);
             digraph::register(editor, cx);
@@ -723,12 +737,7 @@ impl Vim {
     }
 
     fn deactivate(editor: &mut Editor, cx: &mut Context<Editor>) {
-        editor.set_cursor_shape(CursorShape::Bar, cx);
-        editor.set_clip_at_line_ends(false, cx);
-        editor.set_collapse_matches(false);
-        editor.set_input_enabled(true);
-        editor.set_autoindent(true);
-        editor.selections.line_mode = false;
+        editor.set_modal_input(ModalInput::default(), cx);
         editor.unregister_addon::<VimAddon>();
         editor.set_relative_line_number(None, cx);
         if let Some(vim) = Vim::globals(cx).focused_vim() {
@@ -744,12 +753,27 @@ impl Vim {
         cx: &mut Context<Vim>,
         f: impl Fn(&mut Vim, &A, &mut Window, &mut Context<Vim>) + 'static,
     ) {
-        let subscription = editor.register_action(cx.listener(f));
+        let subscription = editor.register_action(cx.listener(move |vim, action, window, cx| {
+            // While an input method composes text, keys like escape or backspace are
+            // meant for it rather than for vim.
+            if vim
+                .editor()
+                .is_some_and(|editor| editor.read(cx).is_composing(cx))
+            {
+                cx.propagate();
+                return;
+            }
+            f(vim, action, window, cx)
+        }));
         cx.on_release(|_, _| drop(subscription)).detach();
     }
 
-    pub fn default_mode(&self, cx: &App) -> Mode {
-        VimSettings::get_global(cx).default_mode
+    pub fn default_mode(cx: &App) -> Mode {
+        if HelixModeSetting::get_global(cx).0 {
+            Mode::HelixNormal
+        } else {
+            VimSettings::get_global(cx).default_mode
+        }
     }
 
     pub fn editor(&self) -> Option<Entity<Editor>> {
@@ -766,7 +790,7 @@ impl Vim {
     }
 
     pub fn enabled(cx: &mut App) -> bool {
-        VimModeSetting::get_global(cx).0
+        VimModeSetting::get_global(cx).0 || HelixModeSetting::get_global(cx).0
     }
 
     /// Called whenever an keystroke is typed so vim can observe all actions
@@ -877,6 +901,7 @@ impl Vim {
             Operator::AddSurrounds { .. }
                 | Operator::ChangeSurrounds { .. }
                 | Operator::DeleteSurrounds
+                | Operator::SurroundTag { .. }
                 | Operator::Exchange
         ) {
             self.operator_stack.clear();
@@ -903,6 +928,14 @@ impl Vim {
             self.temp_mode = false;
         }
 
+        // Helix mode has no use for vim's normal mode, so anything that returns to it ends up in
+        // Helix's instead.
+        let mode = if mode == Mode::Normal && HelixModeSetting::get_global(cx).0 {
+            Mode::HelixNormal
+        } else {
+            mode
+        };
+
         let last_mode = self.mode;
         let prior_mode = self.last_mode;
         let prior_tx = self.current_tx;
@@ -1097,14 +1130,7 @@ impl Vim {
     }
 
     pub fn extend_key_context(&self, context: &mut KeyContext, cx: &App) {
-        let mut mode = match self.mode {
-            Mode::Normal => "normal",
-            Mode::Visual | Mode::VisualLine | Mode::VisualBlock => "visual",
-            Mode::Insert => "insert",
-            Mode::Replace => "replace",
-            Mode::HelixNormal => "helix_normal",
-        }
-        .to_string();
+        let mut mode = self.mode.key_context_name().to_string();
 
         let mut operator_id = "none";
 
@@ -1593,28 +1619,70 @@ impl Vim {
             Some(Operator::Literal { prefix }) => {
                 self.handle_literal_input(prefix.unwrap_or_default(), &text, window, cx)
             }
-            Some(Operator::AddSurrounds { target }) => match self.mode {
-                Mode::Normal => {
-                    if let Some(target) = target {
+            Some(Operator::AddSurrounds { target }) => {
+                let target = match self.mode {
+                    Mode::Normal => target,
+                    Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
+                        Some(SurroundsType::Selection)
+                    }
+                    _ => return self.clear_operator(window, cx),
+                };
+                if let Some(target) = target {
+                    if text.as_ref() == "t" {
+                        self.pop_operator(window, cx);
+                        self.push_operator(
+                            Operator::SurroundTag {
+                                target: TagSurroundsTarget::Add(target),
+                                tag: String::new(),
+                            },
+                            window,
+                            cx,
+                        );
+                    } else {
                         self.add_surrounds(text, target, window, cx);
                         self.clear_operator(window, cx);
                     }
                 }
-                Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
-                    self.add_surrounds(text, SurroundsType::Selection, window, cx);
-                    self.clear_operator(window, cx);
-                }
-                _ => self.clear_operator(window, cx),
-            },
+            }
             Some(Operator::ChangeSurrounds { target }) => match self.mode {
                 Mode::Normal => {
                     if let Some(target) = target {
-                        self.change_surrounds(text, target, window, cx);
-                        self.clear_operator(window, cx);
+                        if text.as_ref() == "t" {
+                            self.pop_operator(window, cx);
+                            self.push_operator(
+                                Operator::SurroundTag {
+                                    target: TagSurroundsTarget::Change(target),
+                                    tag: String::new(),
+                                },
+                                window,
+                                cx,
+                            );
+                        } else {
+                            self.change_surrounds(text, target, window, cx);
+                            self.clear_operator(window, cx);
+                        }
                     }
                 }
                 _ => self.clear_operator(window, cx),
             },
+            Some(Operator::SurroundTag { target, mut tag }) => {
+                if text.as_ref() == ">" || text.as_ref() == "\n" {
+                    let text = format!("<{tag}>").into();
+                    match target {
+                        TagSurroundsTarget::Add(target) => {
+                            self.add_surrounds(text, target, window, cx)
+                        }
+                        TagSurroundsTarget::Change(target) => {
+                            self.change_surrounds(text, target, window, cx)
+                        }
+                    }
+                    self.clear_operator(window, cx);
+                } else {
+                    tag.push_str(&text);
+                    self.pop_operator(window, cx);
+                    self.push_operator(Operator::SurroundTag { target, tag }, window, cx);
+                }
+            }
             Some(Operator::DeleteSurrounds) => match self.mode {
                 Mode::Normal => {
                     self.delete_surrounds(text, window, cx);
@@ -1671,12 +1739,18 @@ impl Vim {
 
     fn sync_vim_settings(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.update_editor(window, cx, |vim, editor, window, cx| {
-            editor.set_cursor_shape(vim.cursor_shape(cx), cx);
-            editor.set_clip_at_line_ends(vim.clip_at_line_ends(), cx);
-            editor.set_collapse_matches(true);
-            editor.set_input_enabled(vim.editor_input_enabled());
-            editor.set_autoindent(vim.should_autoindent());
-            editor.selections.line_mode = matches!(vim.mode, Mode::VisualLine);
+            editor.set_modal_input(
+                ModalInput {
+                    mode: Some(vim.mode.key_context_name().into()),
+                    cursor_shape: vim.cursor_shape(cx),
+                    clip_at_line_ends: vim.clip_at_line_ends(),
+                    collapse_matches: true,
+                    input_enabled: vim.editor_input_enabled(),
+                    autoindent: vim.should_autoindent(),
+                    line_mode: matches!(vim.mode, Mode::VisualLine),
+                },
+                cx,
+            );
 
             let hide_inline_completions = match vim.mode {
                 Mode::Insert | Mode::Replace => false,
@@ -1731,6 +1805,7 @@ struct VimSettings {
     pub custom_digraphs: HashMap<String, Arc<str>>,
     pub highlight_on_yank_duration: u64,
     pub cursor_shape: CursorShapeSettings,
+    pub custom_text_objects: CustomTextObjects,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -1743,6 +1818,7 @@ struct VimSettingsContent {
     pub custom_digraphs: Option<HashMap<String, Arc<str>>>,
     pub highlight_on_yank_duration: Option<u64>,
     pub cursor_shape: Option<CursorShapeSettings>,
+    pub custom_text_objects: Option<CustomTextObjects>,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -1802,6 +1878,9 @@ impl Settings for VimSettings {
                 .highlight_on_yank_duration
                 .ok_or_else(Self::missing_default)?,
             cursor_shape: settings.cursor_shape.ok_or_else(Self::missing_default)?,
+            custom_text_objects: settings
+                .custom_text_objects
+                .ok_or_else(Self::missing_default)?,
         })
     }
 
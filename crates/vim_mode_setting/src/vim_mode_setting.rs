@@ -1,4 +1,5 @@
-//! Contains the [`VimModeSetting`] used to enable/disable Vim mode.
+//! Contains the [`VimModeSetting`] and [`HelixModeSetting`] used to
+//! enable/disable Vim and Helix mode.
 //!
 //! This is in its own crate as we want other crates to be able to enable or
 //! disable Vim mode without having to depend on the `vim` crate in its
@@ -11,6 +12,7 @@ use settings::{Settings, SettingsSources};
 /// Initializes the `vim_mode_setting` crate.
 pub fn init(cx: &mut App) {
     VimModeSetting::register(cx);
+    HelixModeSetting::register(cx);
 }
 
 /// Whether or not to enable Vim mode.
@@ -38,3 +40,28 @@ impl Settings for VimModeSetting {
         // TODO: could possibly check if any of the `vim.<foo>` keys are set?
     }
 }
+
+/// Whether or not to enable Helix mode, which uses Vim mode's key bindings with
+/// selection-first editing in place of Vim's normal mode.
+///
+/// Default: false
+pub struct HelixModeSetting(pub bool);
+
+impl Settings for HelixModeSetting {
+    const KEY: Option<&'static str> = Some("helix_mode");
+
+    type FileContent = Option<bool>;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        Ok(Self(
+            sources
+                .user
+                .or(sources.server)
+                .copied()
+                .flatten()
+                .unwrap_or(sources.default.ok_or_else(Self::missing_default)?),
+        ))
+    }
+
+    fn import_from_vscode(_vscode: &settings::VsCodeSettings, _current: &mut Self::FileContent) {}
+}
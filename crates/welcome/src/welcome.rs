@@ -138,6 +138,17 @@ impl Render for WelcomePage {
                                                 .color(Color::Muted),
                                         ),
                                     )
+                                    .child(
+                                        Button::new("new-project", "New Project")
+                                            .icon(IconName::Plus)
+                                            .icon_size(IconSize::XSmall)
+                                            .icon_color(Color::Muted)
+                                            .icon_position(IconPosition::Start)
+                                            .on_click(cx.listener(|_, _, window, cx| {
+                                                telemetry::event!("Welcome New Project Clicked");
+                                                window.dispatch_action(zed_actions::new_project::Toggle.boxed_clone(), cx);
+                                            })),
+                                    )
                                     .child(
                                         Button::new("choose-theme", "Choose a Theme")
                                             .icon(IconName::SwatchBook)
@@ -0,0 +1,172 @@
+use std::time::{Duration, Instant};
+
+use audio::{Audio, Sound};
+use gpui::{Global, Task};
+use settings::Settings;
+use ui::prelude::*;
+
+use crate::{WorkspaceSettings, workspace_settings::BellStyle};
+
+/// How long the screen stays flashed after the bell rings.
+const FLASH_DURATION: Duration = Duration::from_millis(150);
+/// How long after ringing the bell stays quiet, so that a program ringing the terminal bell in a
+/// loop doesn't play a stream of sounds.
+const MIN_RING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// An event that can ring the bell, depending on the `bell` settings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BellEvent {
+    /// A task exited successfully.
+    TaskSucceeded,
+    /// A task exited with an error, or a task that keeps running rang the terminal bell.
+    TaskFailed,
+    /// A program in a terminal rang the terminal bell.
+    Terminal,
+    /// The agent finished responding, after taking this long.
+    AgentResponseFinished(Duration),
+}
+
+/// Rings the bell for the event, by playing a sound or flashing the screen, as the settings ask.
+pub fn ring(event: BellEvent, cx: &mut App) {
+    let settings = &WorkspaceSettings::get_global(cx).bell;
+    let style = match event {
+        BellEvent::TaskSucceeded => settings.task_succeeded,
+        BellEvent::TaskFailed => settings.task_failed,
+        BellEvent::Terminal => settings.terminal,
+        BellEvent::AgentResponseFinished(duration) => {
+            if duration < Duration::from_secs(settings.agent_response_min_seconds) {
+                return;
+            }
+            settings.agent_response
+        }
+    };
+    if style == BellStyle::None {
+        return;
+    }
+
+    let now = cx.background_executor().now();
+    if cx
+        .try_global::<LastRing>()
+        .is_some_and(|last_ring| now.duration_since(last_ring.0) < MIN_RING_INTERVAL)
+    {
+        return;
+    }
+    cx.set_global(LastRing(now));
+
+    if matches!(style, BellStyle::Sound | BellStyle::SoundAndFlash) {
+        Audio::play_sound(Sound::Bell, cx);
+    }
+    if matches!(style, BellStyle::Flash | BellStyle::SoundAndFlash) {
+        Flash::start(cx);
+    }
+}
+
+/// When the bell last rang.
+struct LastRing(Instant);
+
+impl Global for LastRing {}
+
+/// Flashes the screen of every window, until it is cleared.
+struct Flash {
+    _clear: Task<()>,
+}
+
+impl Global for Flash {}
+
+impl Flash {
+    fn start(cx: &mut App) {
+        let clear = cx.spawn(async move |cx| {
+            cx.background_executor().timer(FLASH_DURATION).await;
+            cx.update(|cx| {
+                cx.remove_global::<Self>();
+                cx.refresh_windows();
+            })
+            .ok();
+        });
+        cx.set_global(Self { _clear: clear });
+        cx.refresh_windows();
+    }
+}
+
+pub(crate) fn render_flash(cx: &App) -> Option<impl IntoElement> {
+    cx.has_global::<Flash>().then(|| {
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .bg(cx.theme().colors().text.opacity(0.1))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use settings::SettingsStore;
+
+    fn set_bell_settings(cx: &mut TestAppContext, style: BellStyle) {
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                    settings.bell = Some(crate::workspace_settings::BellSettingsContent {
+                        task_failed: Some(style),
+                        agent_response: Some(style),
+                        agent_response_min_seconds: Some(10),
+                        ..Default::default()
+                    });
+                });
+            });
+        });
+    }
+
+    fn flashing(cx: &mut TestAppContext) -> bool {
+        cx.update(|cx| cx.has_global::<Flash>())
+    }
+
+    #[gpui::test]
+    async fn test_bell_follows_event_settings(cx: &mut TestAppContext) {
+        crate::tests::init_test(cx);
+        set_bell_settings(cx, BellStyle::Flash);
+
+        cx.update(|cx| ring(BellEvent::TaskSucceeded, cx));
+        assert!(!flashing(cx), "task_succeeded is not set to ring");
+
+        cx.update(|cx| ring(BellEvent::AgentResponseFinished(Duration::from_secs(5)), cx));
+        assert!(
+            !flashing(cx),
+            "responses shorter than the minimum don't ring"
+        );
+
+        cx.update(|cx| {
+            ring(
+                BellEvent::AgentResponseFinished(Duration::from_secs(20)),
+                cx,
+            )
+        });
+        assert!(flashing(cx));
+
+        cx.executor().advance_clock(FLASH_DURATION);
+        cx.run_until_parked();
+        assert!(!flashing(cx));
+    }
+
+    #[gpui::test]
+    async fn test_bell_rings_at_most_once_per_interval(cx: &mut TestAppContext) {
+        crate::tests::init_test(cx);
+        set_bell_settings(cx, BellStyle::Flash);
+
+        cx.update(|cx| ring(BellEvent::TaskFailed, cx));
+        assert!(flashing(cx));
+        cx.executor().advance_clock(FLASH_DURATION);
+        cx.run_until_parked();
+
+        cx.update(|cx| ring(BellEvent::TaskFailed, cx));
+        assert!(!flashing(cx), "the bell rang again too soon");
+
+        cx.executor().advance_clock(MIN_RING_INTERVAL);
+        cx.run_until_parked();
+        cx.update(|cx| ring(BellEvent::TaskFailed, cx));
+        assert!(flashing(cx));
+    }
+}
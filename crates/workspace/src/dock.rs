@@ -6,8 +6,8 @@ use client::proto;
 use gpui::{
     Action, AnyView, App, Axis, Context, Corner, Entity, EntityId, EventEmitter, FocusHandle,
     Focusable, IntoElement, KeyContext, MouseButton, MouseDownEvent, MouseUpEvent, ParentElement,
-    Render, SharedString, StyleRefinement, Styled, Subscription, WeakEntity, Window, deferred, div,
-    px,
+    Render, SharedString, StyleRefinement, Styled, Subscription, WeakEntity, Window,
+    accesskit::Role, deferred, div, px,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -29,13 +29,18 @@ pub use proto::PanelId;
 
 pub trait Panel: Focusable + EventEmitter<PanelEvent> + Render + Sized {
     fn persistent_name() -> &'static str;
+    /// The name this panel's dock state is saved and restored under. Panel types that are added
+    /// to a dock more than once need a different key for each instance.
+    fn panel_key(&self) -> SharedString {
+        SharedString::new_static(Self::persistent_name())
+    }
     fn position(&self, window: &Window, cx: &App) -> DockPosition;
     fn position_is_valid(&self, position: DockPosition) -> bool;
     fn set_position(&mut self, position: DockPosition, window: &mut Window, cx: &mut Context<Self>);
     fn size(&self, window: &Window, cx: &App) -> Pixels;
     fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>);
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName>;
-    fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<&'static str>;
+    fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<SharedString>;
     fn toggle_action(&self) -> Box<dyn Action>;
     fn icon_label(&self, _window: &Window, _: &App) -> Option<String> {
         None
@@ -62,7 +67,7 @@ pub trait Panel: Focusable + EventEmitter<PanelEvent> + Render + Sized {
 
 pub trait PanelHandle: Send + Sync {
     fn panel_id(&self) -> EntityId;
-    fn persistent_name(&self) -> &'static str;
+    fn panel_key(&self, cx: &App) -> SharedString;
     fn position(&self, window: &Window, cx: &App) -> DockPosition;
     fn position_is_valid(&self, position: DockPosition, cx: &App) -> bool;
     fn set_position(&self, position: DockPosition, window: &mut Window, cx: &mut App);
@@ -74,7 +79,7 @@ pub trait PanelHandle: Send + Sync {
     fn size(&self, window: &Window, cx: &App) -> Pixels;
     fn set_size(&self, size: Option<Pixels>, window: &mut Window, cx: &mut App);
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName>;
-    fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<&'static str>;
+    fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<SharedString>;
     fn toggle_action(&self, window: &Window, cx: &App) -> Box<dyn Action>;
     fn icon_label(&self, window: &Window, cx: &App) -> Option<String>;
     fn panel_focus_handle(&self, cx: &App) -> FocusHandle;
@@ -106,8 +111,8 @@ where
         Entity::entity_id(self)
     }
 
-    fn persistent_name(&self) -> &'static str {
-        T::persistent_name()
+    fn panel_key(&self, cx: &App) -> SharedString {
+        self.read(cx).panel_key()
     }
 
     fn position(&self, window: &Window, cx: &App) -> DockPosition {
@@ -154,7 +159,7 @@ where
         self.read(cx).icon(window, cx)
     }
 
-    fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<&'static str> {
+    fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<SharedString> {
         self.read(cx).icon_tooltip(window, cx)
     }
 
@@ -242,6 +247,7 @@ struct PanelEntry {
 
 pub struct PanelButtons {
     dock: Entity<Dock>,
+    position: DockPosition,
 }
 
 impl Dock {
@@ -349,10 +355,16 @@ impl Dock {
             .position(|entry| entry.panel.to_any().downcast::<T>().is_ok())
     }
 
-    pub fn panel_index_for_persistent_name(&self, ui_name: &str, _cx: &App) -> Option<usize> {
+    pub fn panel_index_for_id(&self, panel_id: EntityId) -> Option<usize> {
+        self.panel_entries
+            .iter()
+            .position(|entry| entry.panel.panel_id() == panel_id)
+    }
+
+    pub fn panel_index_for_persistent_name(&self, ui_name: &str, cx: &App) -> Option<usize> {
         self.panel_entries
             .iter()
-            .position(|entry| entry.panel.persistent_name() == ui_name)
+            .position(|entry| entry.panel.panel_key(cx).as_ref() == ui_name)
     }
 
     pub fn panel_index_for_proto_id(&self, panel_id: PanelId) -> Option<usize> {
@@ -790,7 +802,12 @@ impl Render for Dock {
                 }
             };
 
+            let panel_name = entry
+                .panel
+                .icon_tooltip(window, cx)
+                .unwrap_or_else(|| entry.panel.panel_key(cx));
             div()
+                .accessibility(Role::Complementary, panel_name)
                 .key_context(dispatch_context)
                 .track_focus(&self.focus_handle(cx))
                 .flex()
@@ -833,7 +850,8 @@ impl Render for Dock {
 impl PanelButtons {
     pub fn new(dock: Entity<Dock>, cx: &mut Context<Self>) -> Self {
         cx.observe(&dock, |_, _, cx| cx.notify()).detach();
-        Self { dock }
+        let position = dock.read(cx).position;
+        Self { dock, position }
     }
 }
 
@@ -856,7 +874,7 @@ impl Render for PanelButtons {
             .filter_map(|(i, entry)| {
                 let icon = entry.panel.icon(window, cx)?;
                 let icon_tooltip = entry.panel.icon_tooltip(window, cx)?;
-                let name = entry.panel.persistent_name();
+                let name = entry.panel.panel_key(cx);
                 let panel = entry.panel.clone();
 
                 let is_active_button = Some(i) == active_index && is_open;
@@ -870,11 +888,11 @@ impl Render for PanelButtons {
                 } else {
                     let action = entry.panel.toggle_action(window, cx);
 
-                    (action, icon_tooltip.into())
+                    (action, icon_tooltip)
                 };
 
                 Some(
-                    right_click_menu(name)
+                    right_click_menu(name.clone())
                         .menu(move |window, cx| {
                             const POSITIONS: [DockPosition; 3] = [
                                 DockPosition::Left,
@@ -941,6 +959,14 @@ impl StatusItemView for PanelButtons {
     ) {
         // Nothing to do, panel buttons don't depend on the active center item
     }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some(SharedString::new_static(match self.position {
+            DockPosition::Left => "left_dock_buttons",
+            DockPosition::Bottom => "bottom_dock_buttons",
+            DockPosition::Right => "right_dock_buttons",
+        }))
+    }
 }
 
 #[cfg(any(test, feature = "test-support"))]
@@ -1007,7 +1033,7 @@ pub mod test {
             None
         }
 
-        fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
             None
         }
 
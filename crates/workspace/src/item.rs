@@ -47,6 +47,17 @@ pub struct PreviewTabsSettings {
     pub enabled: bool,
     pub enable_preview_from_file_finder: bool,
     pub enable_preview_from_code_navigation: bool,
+    pub promote_on: PreviewTabsPromotion,
+}
+
+/// Which actions turn a preview tab into a regular tab.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct PreviewTabsPromotion {
+    pub edit: bool,
+    pub save: bool,
+    pub tab_double_click: bool,
+    pub tab_drag: bool,
+    pub reopen: bool,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -128,6 +139,35 @@ pub struct PreviewTabsSettingsContent {
     ///
     /// Default: false
     enable_preview_from_code_navigation: Option<bool>,
+    /// Which actions turn a preview tab into a regular tab.
+    promote_on: Option<PreviewTabsPromotionContent>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PreviewTabsPromotionContent {
+    /// Whether editing the file keeps its tab open. A preview tab with unsaved edits is never
+    /// replaced.
+    ///
+    /// Default: true
+    edit: Option<bool>,
+    /// Whether saving the file keeps its tab open.
+    ///
+    /// Default: true
+    save: Option<bool>,
+    /// Whether double-clicking the tab keeps it open.
+    ///
+    /// Default: true
+    tab_double_click: Option<bool>,
+    /// Whether dragging the tab to another position in its pane keeps it open.
+    /// Moving it to another pane always does.
+    ///
+    /// Default: true
+    tab_drag: Option<bool>,
+    /// Whether opening the file again, not as a preview, keeps its tab open,
+    /// like double-clicking it in the project panel.
+    ///
+    /// Default: true
+    reopen: Option<bool>,
 }
 
 impl Settings for ItemSettings {
@@ -805,8 +845,14 @@ impl<T: Item> ItemHandle for Entity<T> {
                         let leader_id = workspace.leader_for_pane(&pane);
 
                         if let Some(leader_id) = leader_id {
-                            if let Some(FollowEvent::Unfollow) = item.to_follow_event(event) {
-                                workspace.unfollow(leader_id, window, cx);
+                            match item.to_follow_event(event) {
+                                Some(FollowEvent::Unfollow) => {
+                                    workspace.unfollow(leader_id, window, cx);
+                                }
+                                Some(FollowEvent::ScrolledAway) => {
+                                    workspace.scroll_away_from_leader(leader_id, window, cx);
+                                }
+                                None => {}
                             }
                         }
 
@@ -1134,6 +1180,9 @@ pub trait ProjectItem: Item {
 #[derive(Debug)]
 pub enum FollowEvent {
     Unfollow,
+    /// The follower scrolled away from the leader's viewport, which stops tracking it until
+    /// they re-sync, while still following the leader.
+    ScrolledAway,
 }
 
 pub enum Dedup {
@@ -1181,6 +1230,15 @@ pub trait FollowableItem: Item {
         _cx: &mut Context<Self>,
     ) {
     }
+    /// Whether the leader's viewport is applied to this view. While it isn't, the view keeps
+    /// track of the last one to restore it when synced again.
+    fn set_viewport_synced(
+        &mut self,
+        _synced: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
 }
 
 pub trait FollowableItemHandle: ItemHandle {
@@ -1216,6 +1274,7 @@ pub trait FollowableItemHandle: ItemHandle {
         cx: &mut App,
     ) -> Option<Dedup>;
     fn update_agent_location(&self, location: language::Anchor, window: &mut Window, cx: &mut App);
+    fn set_viewport_synced(&self, synced: bool, window: &mut Window, cx: &mut App);
 }
 
 impl<T: FollowableItem> FollowableItemHandle for Entity<T> {
@@ -1290,6 +1349,10 @@ impl<T: FollowableItem> FollowableItemHandle for Entity<T> {
             this.update_agent_location(location, window, cx)
         })
     }
+
+    fn set_viewport_synced(&self, synced: bool, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| this.set_viewport_synced(synced, window, cx))
+    }
 }
 
 pub trait WeakFollowableItemHandle: Send + Sync {
@@ -0,0 +1,738 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{
+    AnyWindowHandle, App, AppContext as _, Bounds, Context, DragMoveEvent, Entity, Point,
+    SharedString, Window, WindowHandle,
+};
+use serde::{Deserialize, Serialize};
+use util::ResultExt as _;
+
+use crate::{
+    DraggedTab, MoveItemToMainWindow, MoveItemToNewWindow, OpenOptions, OpenVisible, Pane,
+    SaveIntent, Toast, Workspace, WorkspaceId, item::ItemHandle, notifications::NotificationId,
+};
+
+/// The files and panel open in a window that was opened from another one by moving an item to
+/// it, saved with the main window's workspace so that the window reopens along with it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct SerializedItemWindow {
+    paths: Vec<PathBuf>,
+    panel: Option<String>,
+}
+
+impl Workspace {
+    /// Moves the focused panel, or else the active item, to a new window that shares this
+    /// window's project, so that its buffers, language servers, and tasks are shared too.
+    pub fn move_item_to_new_window(
+        &mut self,
+        _: &MoveItemToNewWindow,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(panel_key) = self.focused_panel_key(window, cx) {
+            if let Some(new_window) = self.open_item_window(window, cx) {
+                self.move_panel_to_window(panel_key, new_window, window, cx);
+            }
+            return;
+        }
+
+        let pane = self.active_pane.clone();
+        let Some(item) = pane.read(cx).active_item() else {
+            return;
+        };
+        self.move_item_to_new_window_from(pane, item, window, cx);
+    }
+
+    /// Moves the focused panel, or else the active item, back to the window that this one was
+    /// opened from, closing this window once it has nothing left.
+    pub fn move_item_to_main_window(
+        &mut self,
+        _: &MoveItemToMainWindow,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(main_window) = self.main_window(cx) else {
+            return;
+        };
+        if let Some(panel_key) = self.focused_panel_key(window, cx) {
+            self.move_panel_to_window(panel_key, main_window, window, cx);
+            return;
+        }
+
+        let pane = self.active_pane.clone();
+        let Some(item) = pane.read(cx).active_item() else {
+            return;
+        };
+        self.move_item_to_window(pane, item, main_window, window, cx);
+    }
+
+    /// The window that this one was opened from by moving an item to it, if it is still open.
+    pub(crate) fn main_window(&self, cx: &App) -> Option<WindowHandle<Workspace>> {
+        self.main_window
+            .filter(|main_window| cx.windows().contains(&(*main_window).into()))
+    }
+
+    /// Remembers a tab being dragged outside of the window, so that dropping it there moves it
+    /// to a new window.
+    pub(crate) fn handle_tab_drag_move(
+        &mut self,
+        event: &DragMoveEvent<DraggedTab>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let window_bounds = Bounds::new(Point::default(), window.viewport_size());
+        self.tab_dragged_out = if window_bounds.contains(&event.event.position) {
+            None
+        } else {
+            Some(event.drag(cx).clone())
+        };
+    }
+
+    pub(crate) fn handle_tab_dropped_out(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(dragged_tab) = self.tab_dragged_out.take() {
+            self.move_item_to_new_window_from(dragged_tab.pane, dragged_tab.item, window, cx);
+        }
+    }
+
+    fn move_item_to_new_window_from(
+        &mut self,
+        pane: Entity<Pane>,
+        item: Box<dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if item.project_path(cx).is_none() {
+            self.show_item_not_movable_toast(cx);
+            return;
+        }
+        if let Some(new_window) = self.open_item_window(window, cx) {
+            self.move_item_to_window(pane, item, new_window, window, cx);
+        }
+    }
+
+    /// Opens an empty window that shares this window's project. Windows opened from a window
+    /// that was itself opened this way share its main window.
+    fn open_item_window(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<WindowHandle<Workspace>> {
+        let main_window = self
+            .main_window(cx)
+            .or_else(|| window.window_handle().downcast::<Self>())?;
+        let project = self.project.clone();
+        let app_state = self.app_state.clone();
+        let display = window.display(cx).and_then(|display| display.uuid().ok());
+        let options = (app_state.build_window_options)(display, cx);
+        let new_window = cx.open_window(options, |window, cx| {
+            cx.new(|cx| {
+                let mut workspace = Workspace::new(None, project, app_state, window, cx);
+                workspace.main_window = Some(main_window);
+                workspace
+            })
+        });
+        match new_window {
+            Ok(new_window) => Some(new_window),
+            Err(error) => {
+                self.show_error(&error, cx);
+                None
+            }
+        }
+    }
+
+    /// Opens the item's file in the given window's active pane, then closes the item here. The
+    /// buffer is kept open by the project in the meantime, so unsaved changes move along.
+    fn move_item_to_window(
+        &mut self,
+        pane: Entity<Pane>,
+        item: Box<dyn ItemHandle>,
+        target: WindowHandle<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = item.project_path(cx) else {
+            self.show_item_not_movable_toast(cx);
+            return;
+        };
+        let Some(source) = window.window_handle().downcast::<Self>() else {
+            return;
+        };
+        let item_id = item.item_id();
+        cx.spawn(async move |_, cx| {
+            let open_item = target.update(cx, |workspace, window, cx| {
+                window.activate_window();
+                workspace.open_path(project_path, None, true, window, cx)
+            })?;
+            open_item.await?;
+            let close_item = source.update(cx, |_, window, cx| {
+                pane.update(cx, |pane, cx| {
+                    pane.close_item_by_id(item_id, SaveIntent::Skip, window, cx)
+                })
+            })?;
+            close_item.await?;
+            source.update(cx, |workspace, window, cx| {
+                workspace.close_if_empty_item_window(window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Opens the panel in the given window, then closes it here.
+    fn move_panel_to_window(
+        &mut self,
+        panel_key: SharedString,
+        target: WindowHandle<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        for dock in self.all_docks() {
+            let shows_panel = dock.read(cx).is_open()
+                && dock
+                    .read(cx)
+                    .active_panel()
+                    .is_some_and(|panel| panel.panel_key(cx) == panel_key);
+            if shows_panel {
+                dock.update(cx, |dock, cx| dock.set_open(false, window, cx));
+            }
+        }
+        if self.moved_panel.as_ref() == Some(&panel_key) {
+            self.moved_panel = None;
+        }
+
+        target
+            .update(cx, |workspace, window, cx| {
+                window.activate_window();
+                if workspace.main_window.is_some() {
+                    workspace.moved_panel = Some(panel_key.clone());
+                }
+                // The panels of a new window are loaded after it opens, in which case the panel
+                // is opened once it is added.
+                workspace.open_panel(&panel_key, window, cx);
+            })
+            .log_err();
+        self.close_if_empty_item_window(window, cx);
+    }
+
+    /// Opens and focuses the panel with the given key, returning whether it was found.
+    pub(crate) fn open_panel(
+        &mut self,
+        panel_key: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        for dock in self.all_docks() {
+            let Some(ix) = dock.read(cx).panel_index_for_persistent_name(panel_key, cx) else {
+                continue;
+            };
+            let focus_handle = dock.update(cx, |dock, cx| {
+                dock.activate_panel(ix, window, cx);
+                dock.set_open(true, window, cx);
+                dock.active_panel()
+                    .map(|panel| panel.panel_focus_handle(cx))
+            });
+            if let Some(focus_handle) = focus_handle {
+                window.focus(&focus_handle);
+            }
+            return true;
+        }
+        false
+    }
+
+    fn focused_panel_key(&self, window: &Window, cx: &App) -> Option<SharedString> {
+        self.all_docks().into_iter().find_map(|dock| {
+            dock.read(cx)
+                .active_panel()
+                .filter(|panel| panel.panel_focus_handle(cx).contains_focused(window, cx))
+                .map(|panel| panel.panel_key(cx))
+        })
+    }
+
+    /// Closes this window if it was opened by moving an item to it and nothing moved to it is
+    /// left.
+    fn close_if_empty_item_window(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(main_window) = self.main_window(cx) {
+            if self.items(cx).next().is_none() && self.moved_panel.is_none() {
+                window.remove_window();
+            }
+            serialize_item_windows(main_window, cx);
+        }
+    }
+
+    fn show_item_not_movable_toast(&mut self, cx: &mut Context<Self>) {
+        struct ItemNotMovable;
+        self.show_toast(
+            Toast::new(
+                NotificationId::unique::<ItemNotMovable>(),
+                "Only files and panels can be moved to another window",
+            )
+            .autohide(),
+            cx,
+        );
+    }
+
+    /// Saves the files and panels of the windows that were opened from this one, so that they
+    /// reopen with it.
+    fn save_item_windows(&mut self, window: &Window, cx: &mut Context<Self>) {
+        let Some(database_id) = self.database_id() else {
+            return;
+        };
+        let main_window = window.window_handle();
+        let project = self.project.read(cx);
+        let item_windows = cx
+            .windows()
+            .into_iter()
+            .filter_map(|handle| handle.downcast::<Workspace>()?.read(cx).ok())
+            .filter(|workspace| {
+                workspace
+                    .main_window
+                    .is_some_and(|handle| AnyWindowHandle::from(handle) == main_window)
+            })
+            .map(|workspace| SerializedItemWindow {
+                paths: workspace
+                    .items(cx)
+                    .filter_map(|item| project.absolute_path(&item.project_path(cx)?, cx))
+                    .collect(),
+                panel: workspace.moved_panel.as_ref().map(ToString::to_string),
+            })
+            .collect::<Vec<_>>();
+        cx.background_spawn(write_item_windows(database_id, item_windows))
+            .detach_and_log_err(cx);
+    }
+
+    /// Reopens the windows that were open alongside this one when its workspace was last saved.
+    pub(crate) fn restore_item_windows(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(database_id) = self.database_id() else {
+            return;
+        };
+        cx.spawn_in(window, async move |this, cx| {
+            let item_windows = cx
+                .background_spawn(async move { read_item_windows(database_id) })
+                .await?;
+            for item_window in item_windows {
+                let new_window =
+                    this.update_in(cx, |this, window, cx| this.open_item_window(window, cx))?;
+                let Some(new_window) = new_window else {
+                    continue;
+                };
+                let open_paths = new_window.update(cx, |workspace, window, cx| {
+                    if let Some(panel) = item_window.panel {
+                        let panel_key = SharedString::from(panel);
+                        workspace.open_panel(&panel_key, window, cx);
+                        workspace.moved_panel = Some(panel_key);
+                    }
+                    workspace.open_paths(
+                        item_window.paths,
+                        OpenOptions {
+                            visible: Some(OpenVisible::None),
+                            ..Default::default()
+                        },
+                        None,
+                        window,
+                        cx,
+                    )
+                })?;
+                open_paths.await;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+/// Saves the item windows of the given main window once the current update is done, since
+/// windows can't be read while they are being updated.
+pub(crate) fn serialize_item_windows(main_window: WindowHandle<Workspace>, cx: &mut App) {
+    cx.defer(move |cx| {
+        main_window
+            .update(cx, |workspace, window, cx| {
+                workspace.save_item_windows(window, cx)
+            })
+            .ok();
+    });
+}
+
+fn item_windows_key(workspace_id: WorkspaceId) -> String {
+    format!("item_windows-{}", workspace_id.0)
+}
+
+fn read_item_windows(workspace_id: WorkspaceId) -> Result<Vec<SerializedItemWindow>> {
+    match KEY_VALUE_STORE.read_kvp(&item_windows_key(workspace_id))? {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(Vec::new()),
+    }
+}
+
+async fn write_item_windows(
+    workspace_id: WorkspaceId,
+    item_windows: Vec<SerializedItemWindow>,
+) -> Result<()> {
+    let key = item_windows_key(workspace_id);
+    if item_windows.is_empty() {
+        KEY_VALUE_STORE.delete_kvp(key).await
+    } else {
+        KEY_VALUE_STORE
+            .write_kvp(key, serde_json::to_string(&item_windows)?)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Item, ProjectItem,
+        dock::{DockPosition, test::TestPanel},
+        register_project_item,
+    };
+    use fs::FakeFs;
+    use gpui::{
+        EventEmitter, FocusHandle, Focusable, Modifiers, MouseButton, Render, Task, TestAppContext,
+        VisualTestContext, point, px,
+    };
+    use project::{Project, ProjectEntryId, ProjectPath};
+    use serde_json::json;
+
+    struct TestFile {
+        project_path: ProjectPath,
+    }
+
+    impl project::ProjectItem for TestFile {
+        fn try_open(
+            _: &Entity<Project>,
+            path: &ProjectPath,
+            cx: &mut App,
+        ) -> Option<Task<Result<Entity<Self>>>> {
+            let project_path = path.clone();
+            Some(cx.spawn(async move |cx| cx.new(|_| TestFile { project_path })))
+        }
+
+        fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+            None
+        }
+
+        fn project_path(&self, _: &App) -> Option<ProjectPath> {
+            Some(self.project_path.clone())
+        }
+
+        fn is_dirty(&self) -> bool {
+            false
+        }
+    }
+
+    struct TestFileView {
+        file: Entity<TestFile>,
+        focus_handle: FocusHandle,
+    }
+
+    impl Item for TestFileView {
+        type Event = ();
+
+        fn tab_content_text(&self, _: usize, _: &App) -> SharedString {
+            "file".into()
+        }
+
+        fn for_each_project_item(
+            &self,
+            cx: &App,
+            f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+        ) {
+            f(self.file.entity_id(), self.file.read(cx))
+        }
+
+        fn is_singleton(&self, _: &App) -> bool {
+            true
+        }
+    }
+
+    impl EventEmitter<()> for TestFileView {}
+
+    impl Focusable for TestFileView {
+        fn focus_handle(&self, _: &App) -> FocusHandle {
+            self.focus_handle.clone()
+        }
+    }
+
+    impl Render for TestFileView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl gpui::IntoElement {
+            gpui::Empty
+        }
+    }
+
+    impl ProjectItem for TestFileView {
+        type Item = TestFile;
+
+        fn for_project_item(
+            _: Entity<Project>,
+            _: Option<&Pane>,
+            file: Entity<TestFile>,
+            _: &mut Window,
+            cx: &mut Context<Self>,
+        ) -> Self {
+            Self {
+                file,
+                focus_handle: cx.focus_handle(),
+            }
+        }
+    }
+
+    async fn init_test(
+        cx: &mut TestAppContext,
+    ) -> (Entity<Project>, Entity<Workspace>, &mut VisualTestContext) {
+        crate::tests::init_test(cx);
+        cx.update(register_project_item::<TestFileView>);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({ "one.txt": "", "two.txt": "" }))
+            .await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        (project, workspace, cx)
+    }
+
+    fn open_file(
+        workspace: &Entity<Workspace>,
+        project: &Entity<Project>,
+        path: &str,
+        cx: &mut VisualTestContext,
+    ) {
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let path = path.to_string();
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, path), None, true, window, cx)
+            })
+            .detach();
+        cx.run_until_parked();
+    }
+
+    /// Waits for the database writes started so far, since they happen in order.
+    async fn flush_db_writes() {
+        KEY_VALUE_STORE
+            .write_kvp("item_windows_test_flush".into(), String::new())
+            .await
+            .unwrap();
+    }
+
+    fn open_item_windows(cx: &mut VisualTestContext) -> Vec<WindowHandle<Workspace>> {
+        cx.update(|_, cx| {
+            cx.windows()
+                .into_iter()
+                .filter_map(|window| window.downcast::<Workspace>())
+                .filter(|window| {
+                    window
+                        .read(cx)
+                        .is_ok_and(|workspace| workspace.main_window.is_some())
+                })
+                .collect()
+        })
+    }
+
+    fn item_paths(window: WindowHandle<Workspace>, cx: &mut VisualTestContext) -> Vec<String> {
+        window
+            .read_with(cx, |workspace, cx| {
+                workspace
+                    .items(cx)
+                    .filter_map(|item| item.project_path(cx))
+                    .map(|path| path.path.to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap()
+    }
+
+    #[gpui::test]
+    async fn test_move_item_to_new_window_and_back(cx: &mut TestAppContext) {
+        let (project, workspace, cx) = init_test(cx).await;
+        let main_window = cx.window_handle().downcast::<Workspace>().unwrap();
+        open_file(&workspace, &project, "one.txt", cx);
+        open_file(&workspace, &project, "two.txt", cx);
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.move_item_to_new_window(&MoveItemToNewWindow, window, cx)
+        });
+        cx.run_until_parked();
+        let item_windows = open_item_windows(cx);
+        assert_eq!(item_windows.len(), 1);
+        let item_window = item_windows[0];
+        assert_eq!(item_paths(item_window, cx), ["two.txt"]);
+        assert_eq!(item_paths(main_window, cx), ["one.txt"]);
+
+        item_window
+            .update(cx, |workspace, window, cx| {
+                workspace.move_item_to_main_window(&MoveItemToMainWindow, window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(item_paths(main_window, cx), ["one.txt", "two.txt"]);
+        assert!(
+            open_item_windows(cx).is_empty(),
+            "the window closes once its last item moves back"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_drag_tab_out_of_window(cx: &mut TestAppContext) {
+        let (project, workspace, cx) = init_test(cx).await;
+        open_file(&workspace, &project, "one.txt", cx);
+        open_file(&workspace, &project, "two.txt", cx);
+
+        let tab_bounds = cx.debug_bounds("TAB-0").unwrap();
+        cx.simulate_mouse_down(tab_bounds.center(), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(
+            tab_bounds.center() + point(px(20.), px(0.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            point(px(-50.), px(-50.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_up(
+            point(px(-50.), px(-50.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.run_until_parked();
+
+        let item_windows = open_item_windows(cx);
+        assert_eq!(item_windows.len(), 1);
+        assert_eq!(item_paths(item_windows[0], cx), ["one.txt"]);
+    }
+
+    #[gpui::test]
+    async fn test_move_panel_to_new_window_and_back(cx: &mut TestAppContext) {
+        let (_, workspace, cx) = init_test(cx).await;
+        let main_window = cx.window_handle().downcast::<Workspace>().unwrap();
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel, window, cx);
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.move_item_to_new_window(&MoveItemToNewWindow, window, cx)
+        });
+        cx.run_until_parked();
+        assert!(!workspace.read_with(cx, |workspace, cx| {
+            workspace.right_dock().read(cx).is_open()
+        }));
+        let item_window = open_item_windows(cx)[0];
+
+        // The panels of the new window are added after it opens.
+        item_window
+            .update(cx, |workspace, window, cx| {
+                assert!(!workspace.right_dock().read(cx).is_open());
+                let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+                workspace.add_panel(panel, window, cx);
+                assert!(workspace.right_dock().read(cx).is_open());
+            })
+            .unwrap();
+
+        item_window
+            .update(cx, |workspace, window, cx| {
+                workspace.move_item_to_main_window(&MoveItemToMainWindow, window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert!(open_item_windows(cx).is_empty());
+        main_window
+            .read_with(cx, |workspace, cx| {
+                assert!(workspace.right_dock().read(cx).is_open())
+            })
+            .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_main_window_closed(cx: &mut TestAppContext) {
+        let (project, workspace, cx) = init_test(cx).await;
+        open_file(&workspace, &project, "one.txt", cx);
+        open_file(&workspace, &project, "two.txt", cx);
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.move_item_to_new_window(&MoveItemToNewWindow, window, cx)
+        });
+        cx.run_until_parked();
+        let item_window = open_item_windows(cx)[0];
+
+        cx.update(|window, _| window.remove_window());
+        cx.run_until_parked();
+        item_window
+            .update(cx, |workspace, window, cx| {
+                assert!(workspace.main_window(cx).is_none());
+                workspace.move_item_to_main_window(&MoveItemToMainWindow, window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(item_paths(item_window, cx), ["two.txt"]);
+
+        item_window
+            .update(cx, |workspace, window, cx| {
+                workspace.move_item_to_new_window(&MoveItemToNewWindow, window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        let new_window = open_item_windows(cx)
+            .into_iter()
+            .find(|window| *window != item_window)
+            .unwrap();
+        assert_eq!(item_paths(new_window, cx), ["two.txt"]);
+        new_window
+            .read_with(cx, |workspace, _| {
+                assert_eq!(workspace.main_window, Some(item_window))
+            })
+            .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_item_windows_are_restored(cx: &mut TestAppContext) {
+        let (project, workspace, cx) = init_test(cx).await;
+        cx.executor().allow_parking();
+        let workspace_id = WorkspaceId(42);
+        workspace.update(cx, |workspace, _| {
+            workspace.database_id = Some(workspace_id)
+        });
+        open_file(&workspace, &project, "one.txt", cx);
+        open_file(&workspace, &project, "two.txt", cx);
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.move_item_to_new_window(&MoveItemToNewWindow, window, cx)
+        });
+        cx.run_until_parked();
+        let item_window = open_item_windows(cx)[0];
+        // Items are saved with their workspace after a short delay.
+        cx.executor()
+            .advance_clock(std::time::Duration::from_millis(200));
+        cx.run_until_parked();
+        flush_db_writes().await;
+        assert_eq!(
+            read_item_windows(workspace_id).unwrap(),
+            [SerializedItemWindow {
+                paths: vec![PathBuf::from("/root/two.txt")],
+                panel: None,
+            }]
+        );
+
+        item_window
+            .update(cx, |_, window, _| window.remove_window())
+            .unwrap();
+        cx.run_until_parked();
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.restore_item_windows(window, cx)
+        });
+        cx.run_until_parked();
+        let item_windows = open_item_windows(cx);
+        assert_eq!(item_windows.len(), 1);
+        assert_eq!(item_paths(item_windows[0], cx), ["two.txt"]);
+
+        item_windows[0]
+            .update(cx, |workspace, window, cx| {
+                workspace.close_window(&crate::CloseWindow, window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        flush_db_writes().await;
+        assert!(read_item_windows(workspace_id).unwrap().is_empty());
+    }
+}
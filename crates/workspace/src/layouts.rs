@@ -0,0 +1,600 @@
+use anyhow::{Context as _, Result};
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{App, Axis, Context, Entity, Focusable as _, Task, Window, impl_actions, px};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Event, Pane, Workspace,
+    dock::Dock,
+    move_item,
+    pane_group::{Member, PaneAxis, PaneGroup},
+};
+
+const GLOBAL_LAYOUTS_KEY: &str = "workspace_layouts";
+
+/// Saves the workspace's layout under the given name, replacing the one saved before.
+#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SaveLayout {
+    pub name: String,
+    /// Whether to save the layout for all projects rather than for this one.
+    #[serde(default)]
+    pub global: bool,
+}
+
+/// Switches to the layout with the given name, preferring the project's over the global one.
+#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ApplyLayout {
+    pub name: String,
+}
+
+/// Deletes the layout with the given name.
+#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteLayout {
+    pub name: String,
+    #[serde(default)]
+    pub global: bool,
+}
+
+impl_actions!(workspace, [SaveLayout, ApplyLayout, DeleteLayout]);
+
+/// Where a layout is saved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutScope {
+    /// Saved for the workspace's project only.
+    Project,
+    /// Saved for every project.
+    Global,
+}
+
+impl LayoutScope {
+    fn for_global(global: bool) -> Self {
+        if global { Self::Global } else { Self::Project }
+    }
+}
+
+/// A layout saved under a name.
+#[derive(Clone, Debug)]
+pub struct SavedLayout {
+    pub name: String,
+    pub scope: LayoutScope,
+}
+
+/// The sizes and visibility of the docks, and how the center is split into panes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct WorkspaceLayout {
+    name: String,
+    left_dock: DockLayout,
+    right_dock: DockLayout,
+    bottom_dock: DockLayout,
+    center: CenterLayout,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct DockLayout {
+    open: bool,
+    active_panel: Option<String>,
+    size: Option<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum CenterLayout {
+    Pane,
+    Axis {
+        axis: Axis,
+        members: Vec<CenterLayout>,
+        flexes: Vec<f32>,
+    },
+}
+
+impl Workspace {
+    pub(crate) fn save_layout_action(
+        &mut self,
+        action: &SaveLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let scope = LayoutScope::for_global(action.global);
+        self.save_layout(action.name.clone(), scope, window, cx)
+            .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn apply_layout_action(
+        &mut self,
+        action: &ApplyLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.apply_layout(action.name.clone(), window, cx)
+            .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn delete_layout_action(
+        &mut self,
+        action: &DeleteLayout,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let scope = LayoutScope::for_global(action.global);
+        self.delete_layout(action.name.clone(), scope, cx)
+            .detach_and_log_err(cx);
+    }
+
+    /// Returns the names of the saved layouts, the project's first. Global layouts that a
+    /// project layout has the name of are left out.
+    pub fn saved_layouts(&self, cx: &App) -> Task<Result<Vec<SavedLayout>>> {
+        let project_key = self.layouts_key(LayoutScope::Project);
+        cx.background_spawn(async move {
+            let project_layouts = match project_key {
+                Some(key) => read_layouts(&key)?,
+                None => Vec::new(),
+            };
+            let global_layouts = read_layouts(GLOBAL_LAYOUTS_KEY)?;
+            let mut layouts = project_layouts
+                .iter()
+                .map(|layout| SavedLayout {
+                    name: layout.name.clone(),
+                    scope: LayoutScope::Project,
+                })
+                .collect::<Vec<_>>();
+            layouts.extend(
+                global_layouts
+                    .into_iter()
+                    .filter(|layout| {
+                        !project_layouts
+                            .iter()
+                            .any(|project_layout| project_layout.name == layout.name)
+                    })
+                    .map(|layout| SavedLayout {
+                        name: layout.name,
+                        scope: LayoutScope::Global,
+                    }),
+            );
+            Ok(layouts)
+        })
+    }
+
+    /// Saves the current layout under `name`. Workspaces that aren't stored, like those of
+    /// windows that items were moved to, save their layouts globally.
+    pub fn save_layout(
+        &mut self,
+        name: String,
+        scope: LayoutScope,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let layout = WorkspaceLayout {
+            name,
+            left_dock: dock_layout(&self.left_dock, window, cx),
+            right_dock: dock_layout(&self.right_dock, window, cx),
+            bottom_dock: dock_layout(&self.bottom_dock, window, cx),
+            center: center_layout(&self.center.root),
+        };
+        let key = self
+            .layouts_key(scope)
+            .unwrap_or_else(|| GLOBAL_LAYOUTS_KEY.to_string());
+        cx.background_spawn(async move {
+            let mut layouts = read_layouts(&key)?;
+            layouts.retain(|saved_layout| saved_layout.name != layout.name);
+            layouts.push(layout);
+            write_layouts(key, &layouts).await
+        })
+    }
+
+    /// Switches to the layout saved under `name`, for the project or else globally.
+    pub fn apply_layout(
+        &mut self,
+        name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let keys = [
+            self.layouts_key(LayoutScope::Project),
+            Some(GLOBAL_LAYOUTS_KEY.to_string()),
+        ];
+        cx.spawn_in(window, async move |this, cx| {
+            let layout = cx
+                .background_spawn(async move {
+                    for key in keys.into_iter().flatten() {
+                        let layout = read_layouts(&key)?
+                            .into_iter()
+                            .find(|layout| layout.name == name);
+                        if layout.is_some() {
+                            return anyhow::Ok(layout);
+                        }
+                    }
+                    Ok(None)
+                })
+                .await?
+                .context("no layout with this name was saved")?;
+            this.update_in(cx, |workspace, window, cx| {
+                workspace.restore_layout(&layout, window, cx)
+            })
+        })
+    }
+
+    /// Deletes the layout saved under `name`.
+    pub fn delete_layout(
+        &mut self,
+        name: String,
+        scope: LayoutScope,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let key = self
+            .layouts_key(scope)
+            .unwrap_or_else(|| GLOBAL_LAYOUTS_KEY.to_string());
+        cx.background_spawn(async move {
+            let mut layouts = read_layouts(&key)?;
+            layouts.retain(|layout| layout.name != name);
+            write_layouts(key, &layouts).await
+        })
+    }
+
+    fn layouts_key(&self, scope: LayoutScope) -> Option<String> {
+        match scope {
+            LayoutScope::Project => Some(format!("{GLOBAL_LAYOUTS_KEY}_{}", self.database_id?.0)),
+            LayoutScope::Global => Some(GLOBAL_LAYOUTS_KEY.to_string()),
+        }
+    }
+
+    fn restore_layout(
+        &mut self,
+        layout: &WorkspaceLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        for (dock, dock_layout) in [
+            (self.left_dock.clone(), &layout.left_dock),
+            (self.right_dock.clone(), &layout.right_dock),
+            (self.bottom_dock.clone(), &layout.bottom_dock),
+        ] {
+            dock.update(cx, |dock, cx| {
+                if let Some(panel_ix) = dock_layout
+                    .active_panel
+                    .as_ref()
+                    .and_then(|name| dock.panel_index_for_persistent_name(name, cx))
+                {
+                    dock.activate_panel(panel_ix, window, cx);
+                }
+                dock.set_open(dock_layout.open, window, cx);
+                if let Some(size) = dock_layout.size {
+                    dock.resize_active_panel(Some(px(size)), window, cx);
+                }
+            });
+        }
+
+        let active_pane = self.active_pane.clone();
+        // The current panes fill the layout's panes in order, keeping their items. Those left
+        // over hand their items to the last one.
+        let mut current_panes = self.center.panes().into_iter().cloned().collect::<Vec<_>>();
+        current_panes.reverse();
+        let root = self.build_center(&layout.center, &mut current_panes, window, cx);
+        let center = PaneGroup::with_root(root);
+        let last_pane = center.last_pane();
+        for pane in current_panes.into_iter().rev() {
+            let item_ids = pane
+                .read(cx)
+                .items()
+                .map(|item| item.item_id())
+                .collect::<Vec<_>>();
+            for item_id in item_ids {
+                let destination_index = last_pane.read(cx).items_len();
+                move_item(
+                    &pane,
+                    &last_pane,
+                    item_id,
+                    destination_index,
+                    false,
+                    window,
+                    cx,
+                );
+            }
+            self.force_remove_pane(&pane, &None, window, cx);
+            cx.emit(Event::PaneRemoved);
+        }
+        self.center = center;
+
+        let active_pane = if self.center.panes().contains(&&active_pane) {
+            active_pane
+        } else {
+            self.center.first_pane()
+        };
+        self.set_active_pane(&active_pane, window, cx);
+        window.focus(&active_pane.focus_handle(cx));
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    fn build_center(
+        &mut self,
+        layout: &CenterLayout,
+        current_panes: &mut Vec<Entity<Pane>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Member {
+        match layout {
+            CenterLayout::Pane => Member::Pane(
+                current_panes
+                    .pop()
+                    .unwrap_or_else(|| self.add_pane(window, cx)),
+            ),
+            CenterLayout::Axis {
+                axis,
+                members,
+                flexes,
+            } => {
+                let members = members
+                    .iter()
+                    .map(|member| self.build_center(member, current_panes, window, cx))
+                    .collect();
+                Member::Axis(PaneAxis::load(*axis, members, Some(flexes.clone())))
+            }
+        }
+    }
+}
+
+fn dock_layout(dock: &Entity<Dock>, window: &Window, cx: &App) -> DockLayout {
+    let dock = dock.read(cx);
+    DockLayout {
+        open: dock.is_open(),
+        active_panel: dock
+            .active_panel()
+            .map(|panel| panel.panel_key(cx).to_string()),
+        size: dock.active_panel_size(window, cx).map(|size| size.0),
+    }
+}
+
+fn center_layout(member: &Member) -> CenterLayout {
+    match member {
+        Member::Pane(_) => CenterLayout::Pane,
+        Member::Axis(axis) => CenterLayout::Axis {
+            axis: axis.axis,
+            members: axis.members.iter().map(center_layout).collect(),
+            flexes: axis.flexes.lock().clone(),
+        },
+    }
+}
+
+fn read_layouts(key: &str) -> Result<Vec<WorkspaceLayout>> {
+    match KEY_VALUE_STORE.read_kvp(key)? {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(Vec::new()),
+    }
+}
+
+async fn write_layouts(key: String, layouts: &[WorkspaceLayout]) -> Result<()> {
+    if layouts.is_empty() {
+        KEY_VALUE_STORE.delete_kvp(key).await
+    } else {
+        KEY_VALUE_STORE
+            .write_kvp(key, serde_json::to_string(layouts)?)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        SplitDirection,
+        dock::{DockPosition, test::TestPanel},
+        item::test::TestItem,
+    };
+    use gpui::{AppContext as _, TestAppContext};
+    use project::Project;
+
+    #[gpui::test]
+    async fn test_restore_layout_splits(cx: &mut TestAppContext) {
+        crate::tests::init_test(cx);
+        let fs = fs::FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let two_columns = workspace.update_in(cx, |workspace, window, cx| {
+            let first_pane = workspace.active_pane().clone();
+            let item = cx.new(TestItem::new);
+            workspace.add_item_to_active_pane(Box::new(item), None, true, window, cx);
+            workspace.split_pane(first_pane, SplitDirection::Right, window, cx);
+            center_layout(&workspace.center.root)
+        });
+        assert_eq!(
+            two_columns,
+            CenterLayout::Axis {
+                axis: Axis::Horizontal,
+                members: vec![CenterLayout::Pane, CenterLayout::Pane],
+                flexes: vec![1., 1.],
+            }
+        );
+
+        let layout = |center| WorkspaceLayout {
+            name: "test".into(),
+            left_dock: dock_layout_closed(),
+            right_dock: dock_layout_closed(),
+            bottom_dock: dock_layout_closed(),
+            center,
+        };
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.restore_layout(&layout(CenterLayout::Pane), window, cx);
+            assert_eq!(workspace.panes().len(), 1);
+            assert_eq!(workspace.active_pane().read(cx).items_len(), 1);
+
+            workspace.restore_layout(&layout(two_columns.clone()), window, cx);
+            assert_eq!(workspace.panes().len(), 2);
+            assert_eq!(center_layout(&workspace.center.root), two_columns);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_save_and_apply_layout(cx: &mut TestAppContext) {
+        crate::tests::init_test(cx);
+        let fs = fs::FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel, window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            let first_pane = workspace.active_pane().clone();
+            let item = cx.new(TestItem::new);
+            workspace.add_item_to_active_pane(Box::new(item), None, true, window, cx);
+            workspace.split_pane(first_pane, SplitDirection::Right, window, cx);
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.save_layout("two columns".into(), LayoutScope::Project, window, cx)
+            })
+            .await
+            .unwrap();
+
+        // Switch away from the saved layout.
+        workspace.update_in(cx, |workspace, window, cx| {
+            let single_pane = WorkspaceLayout {
+                name: "single pane".into(),
+                left_dock: dock_layout_closed(),
+                right_dock: dock_layout_closed(),
+                bottom_dock: dock_layout_closed(),
+                center: CenterLayout::Pane,
+            };
+            workspace.restore_layout(&single_pane, window, cx);
+            assert_eq!(workspace.panes().len(), 1);
+            assert!(!workspace.left_dock().read(cx).is_open());
+        });
+
+        let saved_layouts = workspace
+            .read_with(cx, |workspace, cx| workspace.saved_layouts(cx))
+            .await
+            .unwrap();
+        assert!(
+            saved_layouts
+                .iter()
+                .any(|layout| layout.name == "two columns")
+        );
+
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.apply_layout("two columns".into(), window, cx)
+            })
+            .await
+            .unwrap();
+        workspace.update_in(cx, |workspace, _, cx| {
+            assert_eq!(workspace.panes().len(), 2);
+            assert_eq!(items_per_pane(workspace, cx), vec![1, 0]);
+            let left_dock = workspace.left_dock().read(cx);
+            assert!(left_dock.is_open());
+            assert_eq!(
+                left_dock
+                    .active_panel()
+                    .map(|panel| panel.panel_key(cx).to_string()),
+                Some("TestPanel".to_string())
+            );
+        });
+
+        workspace
+            .update_in(cx, |workspace, _, cx| {
+                workspace.delete_layout("two columns".into(), LayoutScope::Project, cx)
+            })
+            .await
+            .unwrap();
+        let result = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.apply_layout("two columns".into(), window, cx)
+            })
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[gpui::test]
+    async fn test_apply_layout_with_missing_panes_and_panels(cx: &mut TestAppContext) {
+        crate::tests::init_test(cx);
+        let fs = fs::FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            let first_pane = workspace.active_pane().clone();
+            let second_pane =
+                workspace.split_pane(first_pane.clone(), SplitDirection::Right, window, cx);
+            workspace.split_pane(second_pane, SplitDirection::Down, window, cx);
+            for pane in workspace.panes().to_vec() {
+                let item = cx.new(TestItem::new);
+                pane.update(cx, |pane, cx| {
+                    pane.add_item(Box::new(item), true, true, None, window, cx)
+                });
+            }
+            panel
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.save_layout("three panes".into(), LayoutScope::Global, window, cx)
+            })
+            .await
+            .unwrap();
+
+        // Close the items and the panes that the layout was saved with, and remove its panel.
+        workspace.update_in(cx, |workspace, window, cx| {
+            for pane in workspace.panes().to_vec() {
+                let item_ids = pane
+                    .read(cx)
+                    .items()
+                    .map(|item| item.item_id())
+                    .collect::<Vec<_>>();
+                pane.update(cx, |pane, cx| {
+                    for item_id in item_ids {
+                        pane.remove_item(item_id, false, false, window, cx);
+                    }
+                });
+            }
+            workspace.remove_panel(&panel, window, cx);
+            let single_pane = WorkspaceLayout {
+                name: "single pane".into(),
+                left_dock: dock_layout_closed(),
+                right_dock: dock_layout_closed(),
+                bottom_dock: dock_layout_closed(),
+                center: CenterLayout::Pane,
+            };
+            workspace.restore_layout(&single_pane, window, cx);
+            assert_eq!(workspace.panes().len(), 1);
+        });
+
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.apply_layout("three panes".into(), window, cx)
+            })
+            .await
+            .unwrap();
+        workspace.update_in(cx, |workspace, _, cx| {
+            // The missing panes are created again, empty, and the missing panel is skipped.
+            assert_eq!(workspace.panes().len(), 3);
+            assert_eq!(items_per_pane(workspace, cx), vec![0, 0, 0]);
+            assert!(workspace.panes().contains(workspace.active_pane()));
+            assert!(workspace.left_dock().read(cx).active_panel().is_none());
+        });
+    }
+
+    fn items_per_pane(workspace: &Workspace, cx: &App) -> Vec<usize> {
+        workspace
+            .center
+            .panes()
+            .into_iter()
+            .map(|pane| pane.read(cx).items_len())
+            .collect()
+    }
+
+    fn dock_layout_closed() -> DockLayout {
+        DockLayout {
+            open: false,
+            active_panel: None,
+            size: None,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
-    CloseWindow, NewFile, NewTerminal, OpenInTerminal, OpenOptions, OpenTerminal, OpenVisible,
-    SplitDirection, ToggleFileFinder, ToggleProjectSymbols, ToggleZoom, Workspace,
-    WorkspaceItemBuilder,
+    CloseWindow, MoveItemToMainWindow, MoveItemToNewWindow, NewFile, NewTerminal, OpenInTerminal,
+    OpenOptions, OpenTerminal, OpenVisible, SplitDirection, ToggleFileFinder, ToggleProjectSymbols,
+    ToggleZoom, Workspace, WorkspaceItemBuilder,
     item::{
         ActivateOnClose, ClosePosition, Item, ItemHandle, ItemSettings, PreviewTabsSettings,
         ProjectItemKind, ShowCloseButton, ShowDiagnostics, TabContentParams, TabTooltipContent,
@@ -39,6 +39,7 @@ use std::{
         Arc,
         atomic::{AtomicUsize, Ordering},
     },
+    time::SystemTime,
 };
 use theme::ThemeSettings;
 use ui::{
@@ -383,6 +384,7 @@ pub struct NavigationEntry {
     pub item: Arc<dyn WeakItemHandle>,
     pub data: Option<Box<dyn Any + Send>>,
     pub timestamp: usize,
+    pub recorded_at: SystemTime,
     pub is_preview: bool,
 }
 
@@ -808,7 +810,10 @@ impl Pane {
 
     pub fn handle_item_edit(&mut self, item_id: EntityId, cx: &App) {
         if let Some(preview_item) = self.preview_item() {
-            if preview_item.item_id() == item_id && !preview_item.preserve_preview(cx) {
+            if preview_item.item_id() == item_id
+                && !preview_item.preserve_preview(cx)
+                && PreviewTabsSettings::get_global(cx).promote_on.edit
+            {
                 self.set_preview_item_id(None, cx);
             }
         }
@@ -851,7 +856,10 @@ impl Pane {
             // and we are not allowing items to open as preview, mark the item as persistent.
             if let Some(preview_item_id) = self.preview_item_id {
                 if let Some(tab) = self.items.get(index) {
-                    if tab.item_id() == preview_item_id && !allow_preview {
+                    if tab.item_id() == preview_item_id
+                        && !allow_preview
+                        && PreviewTabsSettings::get_global(cx).promote_on.reopen
+                    {
                         self.set_preview_item_id(None, cx);
                     }
                 }
@@ -895,6 +903,11 @@ impl Pane {
     ) -> Option<usize> {
         let item_idx = self.preview_item_idx()?;
         let id = self.preview_item_id()?;
+        if self.items[item_idx].is_dirty(cx) {
+            // Replacing the preview would lose its edits, so keep it open instead.
+            self.set_preview_item_id(None, cx);
+            return None;
+        }
 
         let prev_active_item_index = self.active_item_index;
         self.remove_item(id, false, false, window, cx);
@@ -1920,7 +1933,9 @@ impl Pane {
 
             if can_save {
                 pane.update_in(cx, |pane, window, cx| {
-                    if pane.is_active_preview_item(item.item_id()) {
+                    if pane.is_active_preview_item(item.item_id())
+                        && PreviewTabsSettings::get_global(cx).promote_on.save
+                    {
                         pane.set_preview_item_id(None, cx);
                     }
                     item.save(should_format, project, window, cx)
@@ -2278,6 +2293,7 @@ impl Pane {
         let position_relative_to_active_item = ix.cmp(&self.active_item_index);
 
         let tab = Tab::new(ix)
+            .accessibility_label(item.tab_content_text(detail, cx))
             .position(if is_first_item {
                 TabPosition::First
             } else if is_last_item {
@@ -2305,7 +2321,12 @@ impl Pane {
                 MouseButton::Left,
                 cx.listener(move |pane, event: &MouseDownEvent, _, cx| {
                     if let Some(id) = pane.preview_item_id {
-                        if id == item_id && event.click_count > 1 {
+                        if id == item_id
+                            && event.click_count > 1
+                            && PreviewTabsSettings::get_global(cx)
+                                .promote_on
+                                .tab_double_click
+                        {
                             pane.set_preview_item_id(None, cx);
                         }
                     }
@@ -2588,6 +2609,10 @@ impl Pane {
 
                             let visible_in_project_panel = relative_path.is_some()
                                 && worktree.is_some_and(|worktree| worktree.read(cx).is_visible());
+                            let in_moved_items_window =
+                                pane.read(cx).workspace.upgrade().is_some_and(|workspace| {
+                                    workspace.read(cx).main_window(cx).is_some()
+                                });
 
                             let entry_id = entry.to_proto();
                             menu = menu
@@ -2616,6 +2641,21 @@ impl Pane {
                                 })
                                 .map(pin_tab_entries)
                                 .separator()
+                                .map(|menu| {
+                                    let (label, action) = if in_moved_items_window {
+                                        ("Move to Main Window", MoveItemToMainWindow.boxed_clone())
+                                    } else {
+                                        ("Move to New Window", MoveItemToNewWindow.boxed_clone())
+                                    };
+                                    menu.entry(
+                                        label,
+                                        Some(action.boxed_clone()),
+                                        window.handler_for(&pane, move |pane, window, cx| {
+                                            pane.activate_item(ix, true, true, window, cx);
+                                            window.dispatch_action(action.boxed_clone(), cx);
+                                        }),
+                                    )
+                                })
                                 .when(visible_in_project_panel, |menu| {
                                     menu.entry(
                                         "Reveal In Project Panel",
@@ -2880,7 +2920,8 @@ impl Pane {
         let split_direction = self.drag_split_direction;
         let item_id = dragged_tab.item.item_id();
         if let Some(preview_item_id) = self.preview_item_id {
-            if item_id == preview_item_id {
+            if item_id == preview_item_id && PreviewTabsSettings::get_global(cx).promote_on.tab_drag
+            {
                 self.set_preview_item_id(None, cx);
             }
         }
@@ -3289,7 +3330,12 @@ impl Render for Pane {
         }
 
         let should_display_tab_bar = self.should_display_tab_bar.clone();
-        let display_tab_bar = should_display_tab_bar(window, cx);
+        let pane = cx.entity();
+        let display_tab_bar = should_display_tab_bar(window, cx)
+            && !self
+                .workspace
+                .upgrade()
+                .is_some_and(|workspace| workspace.read(cx).zen_mode_hides_tab_bar(&pane, cx));
         let Some(project) = self.project.upgrade() else {
             return div().track_focus(&self.focus_handle(cx));
         };
@@ -3614,6 +3660,31 @@ impl NavHistory {
             })
     }
 
+    /// Calls `f` with the entries that going back or forward would visit, nearest first.
+    pub fn for_each_entry_in_direction(
+        &self,
+        mode: NavigationMode,
+        cx: &App,
+        mut f: impl FnMut(&NavigationEntry, (ProjectPath, Option<PathBuf>)),
+    ) {
+        let borrowed_history = self.0.lock();
+        let stack = match mode {
+            NavigationMode::GoingBack => &borrowed_history.backward_stack,
+            NavigationMode::GoingForward => &borrowed_history.forward_stack,
+            _ => return,
+        };
+        for entry in stack.iter().rev() {
+            if let Some(project_and_abs_path) = borrowed_history.paths_by_item.get(&entry.item.id())
+            {
+                f(entry, project_and_abs_path.clone());
+            } else if let Some(item) = entry.item.upgrade() {
+                if let Some(path) = item.project_path(cx) {
+                    f(entry, (path, None));
+                }
+            }
+        }
+    }
+
     pub fn set_mode(&mut self, mode: NavigationMode) {
         self.0.lock().mode = mode;
     }
@@ -3647,6 +3718,50 @@ impl NavHistory {
         entry
     }
 
+    /// Pops the entries in the direction of `mode` up to and including the one with the given
+    /// timestamp, returning it along with the entries that were skipped to reach it.
+    pub fn pop_to(
+        &mut self,
+        mode: NavigationMode,
+        timestamp: usize,
+        cx: &mut App,
+    ) -> Option<(NavigationEntry, Vec<NavigationEntry>)> {
+        let mut state = self.0.lock();
+        let stack = match mode {
+            NavigationMode::GoingBack => &mut state.backward_stack,
+            NavigationMode::GoingForward => &mut state.forward_stack,
+            _ => return None,
+        };
+        let ix = stack
+            .iter()
+            .position(|entry| entry.timestamp == timestamp)?;
+        let mut skipped = stack.split_off(ix + 1);
+        skipped.make_contiguous().reverse();
+        let entry = stack.pop_back()?;
+        state.did_update(cx);
+        Some((entry, skipped.into()))
+    }
+
+    /// Puts the entries skipped by [`Self::pop_to`] onto the opposite stack, so that navigating
+    /// in the other direction visits them again.
+    pub fn push_skipped(&mut self, mode: NavigationMode, skipped: Vec<NavigationEntry>) {
+        if skipped.is_empty() {
+            return;
+        }
+        let mut state = self.0.lock();
+        let stack = match mode {
+            NavigationMode::GoingBack => &mut state.forward_stack,
+            NavigationMode::GoingForward => &mut state.backward_stack,
+            _ => return,
+        };
+        for entry in skipped {
+            if stack.len() >= MAX_NAVIGATION_HISTORY_LEN {
+                stack.pop_front();
+            }
+            stack.push_back(entry);
+        }
+    }
+
     pub fn push<D: 'static + Send + Any>(
         &mut self,
         data: Option<D>,
@@ -3665,6 +3780,7 @@ impl NavHistory {
                     item,
                     data: data.map(|data| Box::new(data) as Box<dyn Any + Send>),
                     timestamp: state.next_timestamp.fetch_add(1, Ordering::SeqCst),
+                    recorded_at: SystemTime::now(),
                     is_preview,
                 });
                 state.forward_stack.clear();
@@ -3677,6 +3793,7 @@ impl NavHistory {
                     item,
                     data: data.map(|data| Box::new(data) as Box<dyn Any + Send>),
                     timestamp: state.next_timestamp.fetch_add(1, Ordering::SeqCst),
+                    recorded_at: SystemTime::now(),
                     is_preview,
                 });
             }
@@ -3688,6 +3805,7 @@ impl NavHistory {
                     item,
                     data: data.map(|data| Box::new(data) as Box<dyn Any + Send>),
                     timestamp: state.next_timestamp.fetch_add(1, Ordering::SeqCst),
+                    recorded_at: SystemTime::now(),
                     is_preview,
                 });
             }
@@ -3699,6 +3817,7 @@ impl NavHistory {
                     item,
                     data: data.map(|data| Box::new(data) as Box<dyn Any + Send>),
                     timestamp: state.next_timestamp.fetch_add(1, Ordering::SeqCst),
+                    recorded_at: SystemTime::now(),
                     is_preview,
                 });
             }
@@ -5973,6 +6092,157 @@ mod tests {
         .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_preview_tab_promotion_settings(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        add_labeled_item(&pane, "A", false, cx);
+        let b = pane.update_in(cx, |pane, window, cx| {
+            let item = Box::new(cx.new(|cx| {
+                TestItem::new(cx)
+                    .with_label("B")
+                    .with_project_items(&[TestProjectItem::new(1, "B.txt", cx)])
+            }));
+            pane.add_item(item.clone(), true, true, None, window, cx);
+            item
+        });
+        let preview_item_id =
+            |cx: &mut VisualTestContext| pane.read_with(cx, |pane, _| pane.preview_item_id());
+        let set_preview = |cx: &mut VisualTestContext| {
+            pane.update(cx, |pane, cx| {
+                pane.set_preview_item_id(Some(b.item_id()), cx)
+            });
+        };
+        let reopen_b = |cx: &mut VisualTestContext| {
+            pane.update_in(cx, |pane, window, cx| {
+                pane.open_item(
+                    Some(ProjectEntryId::from_proto(1)),
+                    b.project_path(cx).unwrap(),
+                    true,
+                    false,
+                    true,
+                    None,
+                    window,
+                    cx,
+                    Box::new(|_, _, _| unreachable!("the item is already open")),
+                );
+            });
+        };
+
+        // By default, editing and reopening a preview tab keep it open.
+        set_preview(cx);
+        pane.update(cx, |pane, cx| pane.handle_item_edit(b.item_id(), cx));
+        assert_eq!(preview_item_id(cx), None);
+        set_preview(cx);
+        reopen_b(cx);
+        assert_eq!(preview_item_id(cx), None);
+
+        set_preview_tabs_settings(
+            cx,
+            r#"{ "promote_on": { "edit": false, "tab_drag": false, "reopen": false } }"#,
+        );
+        set_preview(cx);
+        pane.update(cx, |pane, cx| pane.handle_item_edit(b.item_id(), cx));
+        assert_eq!(preview_item_id(cx), Some(b.item_id()));
+
+        reopen_b(cx);
+        assert_eq!(preview_item_id(cx), Some(b.item_id()));
+
+        pane.update_in(cx, |pane, window, cx| {
+            let dragged_tab = DraggedTab {
+                pane: cx.entity(),
+                item: b.boxed_clone(),
+                ix: 1,
+                detail: 0,
+                is_active: true,
+            };
+            pane.handle_tab_drop(&dragged_tab, 0, window, cx);
+        });
+        cx.run_until_parked();
+        assert_item_labels(&pane, ["B*", "A"], cx);
+        assert_eq!(preview_item_id(cx), Some(b.item_id()));
+    }
+
+    #[gpui::test]
+    async fn test_preview_tab_promotion_on_save(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        let item = add_labeled_item(&pane, "A", true, cx);
+        item.update(cx, |item, cx| {
+            item.project_items
+                .push(TestProjectItem::new_dirty(1, "A.txt", cx))
+        });
+        set_preview_tabs_settings(cx, r#"{ "promote_on": { "save": false } }"#);
+        pane.update(cx, |pane, cx| {
+            pane.set_preview_item_id(Some(item.item_id()), cx)
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.save_active_item(SaveIntent::Save, window, cx)
+            })
+            .await
+            .unwrap();
+        assert_eq!(item.read_with(cx, |item, _| item.save_count), 1);
+        assert_eq!(
+            pane.read_with(cx, |pane, _| pane.preview_item_id()),
+            Some(item.item_id())
+        );
+
+        set_preview_tabs_settings(cx, r#"{}"#);
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.save_active_item(SaveIntent::Save, window, cx)
+            })
+            .await
+            .unwrap();
+        assert_eq!(pane.read_with(cx, |pane, _| pane.preview_item_id()), None);
+    }
+
+    #[gpui::test]
+    async fn test_dirty_preview_tab_is_not_replaced(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        let [_, b] = set_labeled_items(&pane, ["A", "B*"], cx);
+        pane.update_in(cx, |pane, window, cx| {
+            pane.set_preview_item_id(Some(b.item_id()), cx);
+            assert_eq!(pane.close_current_preview_item(window, cx), Some(1));
+        });
+        assert_item_labels(&pane, ["A*"], cx);
+
+        let b = add_labeled_item(&pane, "B", true, cx);
+        pane.update_in(cx, |pane, window, cx| {
+            pane.set_preview_item_id(Some(b.item_id()), cx);
+            assert_eq!(pane.close_current_preview_item(window, cx), None);
+            assert_eq!(pane.preview_item_id(), None);
+        });
+        assert_item_labels(&pane, ["A", "B*^"], cx);
+    }
+
+    fn set_preview_tabs_settings(cx: &mut VisualTestContext, preview_tabs: &str) {
+        cx.update(|_, cx| {
+            cx.update_global(|store: &mut SettingsStore, cx| {
+                store
+                    .set_user_settings(&format!(r#"{{ "preview_tabs": {preview_tabs} }}"#), cx)
+                    .unwrap();
+            });
+        });
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
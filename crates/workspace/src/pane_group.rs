@@ -256,7 +256,8 @@ impl PaneLeaderDecorator for PaneRenderContext<'_> {
         };
 
         let mut leader_color;
-        let status_box;
+        let mut status_box;
+        let leader_name: SharedString;
         match leader_id {
             CollaboratorId::PeerId(peer_id) => {
                 let Some(leader) = self.active_call.as_ref().and_then(|call| {
@@ -265,6 +266,7 @@ impl PaneLeaderDecorator for PaneRenderContext<'_> {
                 }) else {
                     return LeaderDecoration::default();
                 };
+                leader_name = leader.user.github_login.clone().into();
 
                 let is_in_unshared_view = follower_state.active_view_id.is_some_and(|view_id| {
                     !follower_state
@@ -338,12 +340,45 @@ impl PaneLeaderDecorator for PaneRenderContext<'_> {
             }
             CollaboratorId::Agent => {
                 status_box = None;
+                leader_name = "the agent".into();
                 leader_color = cx.theme().players().agent().cursor;
             }
         }
 
+        if follower_state.scrolled_away {
+            let workspace = self.workspace.clone();
+            status_box = Some(
+                h_flex()
+                    .absolute()
+                    .bottom_3()
+                    .right_3()
+                    .gap_2()
+                    .elevation_2(cx)
+                    .py_1()
+                    .pl_2()
+                    .pr_1()
+                    .child(
+                        Label::new(format!("Scrolled away from {leader_name}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Button::new("resync-with-leader", "Re-sync")
+                            .label_size(LabelSize::Small)
+                            .on_click(move |_, window, cx| {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        workspace.resync_with_leader(leader_id, window, cx)
+                                    })
+                                    .ok();
+                            }),
+                    )
+                    .into_any_element(),
+            );
+        }
+
         let is_in_panel = follower_state.dock_pane.is_some();
-        if is_in_panel {
+        if is_in_panel || follower_state.scrolled_away {
             leader_color.fade_out(0.75);
         } else {
             leader_color.fade_out(0.3);
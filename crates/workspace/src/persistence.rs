@@ -294,6 +294,9 @@ define_connection! {
     //   display: Option<Uuid>, // Display id
     //   fullscreen: Option<bool>, // Is the window fullscreen?
     //   centered_layout: Option<bool>, // Is the Centered Layout mode activated?
+    //   zen_mode: Option<bool>, // Is the zen mode activated?
+    //   pinned: Option<bool>, // Is the project pinned in the recent projects picker?
+    //   tags: Option<String>, // Comma-separated tags assigned in the recent projects picker
     //   session_id: Option<String>, // Session id
     //   window_id: Option<u64>, // Window Id
     // )
@@ -542,6 +545,13 @@ define_connection! {
         ALTER TABLE breakpoints ADD COLUMN condition TEXT;
         ALTER TABLE breakpoints ADD COLUMN hit_condition TEXT;
     ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN zen_mode INTEGER; //bool
+    ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN pinned INTEGER; //bool
+        ALTER TABLE workspaces ADD COLUMN tags TEXT;
+    ),
     ];
 }
 
@@ -566,6 +576,7 @@ impl WorkspaceDb {
             window_bounds,
             display,
             centered_layout,
+            zen_mode,
             docks,
             window_id,
         ): (
@@ -575,6 +586,7 @@ impl WorkspaceDb {
             Option<SerializedWindowBounds>,
             Option<Uuid>,
             Option<bool>,
+            Option<bool>,
             DockStructure,
             Option<u64>,
         ) = self
@@ -590,6 +602,7 @@ impl WorkspaceDb {
                     window_height,
                     display,
                     centered_layout,
+                    zen_mode,
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
@@ -626,6 +639,7 @@ impl WorkspaceDb {
                 .log_err()?,
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
+            zen_mode: zen_mode.unwrap_or(false),
             display,
             docks,
             session_id: None,
@@ -638,11 +652,12 @@ impl WorkspaceDb {
         &self,
         ssh_project: &SerializedSshProject,
     ) -> Option<SerializedWorkspace> {
-        let (workspace_id, window_bounds, display, centered_layout, docks, window_id): (
+        let (workspace_id, window_bounds, display, centered_layout, zen_mode, docks, window_id): (
             WorkspaceId,
             Option<SerializedWindowBounds>,
             Option<Uuid>,
             Option<bool>,
+            Option<bool>,
             DockStructure,
             Option<u64>,
         ) = self
@@ -656,6 +671,7 @@ impl WorkspaceDb {
                     window_height,
                     display,
                     centered_layout,
+                    zen_mode,
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
@@ -683,6 +699,7 @@ impl WorkspaceDb {
                 .log_err()?,
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
+            zen_mode: zen_mode.unwrap_or(false),
             breakpoints: self.breakpoints(workspace_id),
             display,
             docks,
@@ -1020,6 +1037,42 @@ impl WorkspaceDb {
         }
     }
 
+    // Returns the workspaces that are pinned or tagged in the recent projects picker, with
+    // their comma-separated tags.
+    query! {
+        pub fn workspace_annotations() -> Result<Vec<(WorkspaceId, Option<bool>, Option<String>)>> {
+            SELECT workspace_id, pinned, tags
+            FROM workspaces
+            WHERE pinned = 1 OR tags IS NOT NULL
+        }
+    }
+
+    query! {
+        pub async fn set_workspace_pinned(id: WorkspaceId, pinned: bool) -> Result<()> {
+            UPDATE workspaces
+            SET pinned = ?2
+            WHERE workspace_id = ?1
+        }
+    }
+
+    query! {
+        pub async fn set_workspace_tags(id: WorkspaceId, tags: Option<String>) -> Result<()> {
+            UPDATE workspaces
+            SET tags = ?2
+            WHERE workspace_id = ?1
+        }
+    }
+
+    // Returns the workspaces that aren't pinned and haven't been opened for 90 days.
+    query! {
+        pub fn stale_workspace_ids() -> Result<Vec<WorkspaceId>> {
+            SELECT workspace_id
+            FROM workspaces
+            WHERE (pinned IS NULL OR pinned = 0)
+                AND julianday(CURRENT_TIMESTAMP) - julianday(timestamp) > 90
+        }
+    }
+
     pub async fn delete_workspace_by_dev_server_project_id(
         &self,
         id: DevServerProjectId,
@@ -1336,6 +1389,14 @@ impl WorkspaceDb {
         }
     }
 
+    query! {
+        pub(crate) async fn set_zen_mode(workspace_id: WorkspaceId, zen_mode: bool) -> Result<()> {
+            UPDATE workspaces
+            SET zen_mode = ?2
+            WHERE workspace_id = ?1
+        }
+    }
+
     pub async fn toolchain(
         &self,
         workspace_id: WorkspaceId,
@@ -1521,6 +1582,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: {
                 let mut map = collections::BTreeMap::default();
                 map.insert(
@@ -1674,6 +1736,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: {
                 let mut map = collections::BTreeMap::default();
                 map.insert(
@@ -1720,6 +1783,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: collections::BTreeMap::default(),
             session_id: None,
             window_id: None,
@@ -1814,6 +1878,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -1827,6 +1892,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -1936,6 +2002,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             session_id: None,
             window_id: Some(999),
         };
@@ -1971,6 +2038,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             session_id: None,
             window_id: Some(1),
         };
@@ -1983,6 +2051,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
@@ -2027,6 +2096,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             session_id: None,
             window_id: Some(3),
         };
@@ -2063,6 +2133,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
@@ -2076,6 +2147,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
@@ -2089,6 +2161,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
@@ -2102,6 +2175,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -2120,6 +2194,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
@@ -2137,6 +2212,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             session_id: Some("session-id-3".to_owned()),
             window_id: Some(60),
         };
@@ -2193,6 +2269,7 @@ mod tests {
             docks: Default::default(),
             breakpoints: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             session_id: None,
             window_id: None,
         }
@@ -2238,6 +2315,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
@@ -2331,6 +2409,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            zen_mode: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
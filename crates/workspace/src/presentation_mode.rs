@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use gpui::{Global, Keystroke, KeystrokeEvent, Subscription, Task};
+use settings::Settings;
+use ui::{prelude::*, text_for_keystroke};
+
+use crate::{TogglePresentationMode, WorkspaceSettings};
+
+/// How long keystrokes stay in the overlay after the last one was pressed.
+const KEYSTROKES_DURATION: Duration = Duration::from_secs(2);
+const MAX_KEYSTROKES: usize = 6;
+
+pub fn init(cx: &mut App) {
+    cx.on_action(|_: &TogglePresentationMode, cx| PresentationMode::toggle(cx));
+}
+
+/// Presentation mode scales up all fonts, shows the keystrokes being pressed, and hides panels
+/// that may show private information, until it is toggled off again.
+pub struct PresentationMode {
+    keystrokes: Vec<Keystroke>,
+    _clear_keystrokes: Task<()>,
+    _keystrokes_subscription: Option<Subscription>,
+}
+
+impl Global for PresentationMode {}
+
+impl PresentationMode {
+    pub fn is_active(cx: &App) -> bool {
+        cx.has_global::<Self>()
+    }
+
+    pub fn toggle(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            cx.remove_global::<Self>();
+            theme::reset_font_scale(cx);
+            return;
+        }
+
+        let settings = &WorkspaceSettings::get_global(cx).presentation_mode;
+        let font_scale = settings.font_scale;
+        let keystrokes_subscription = settings.show_keystrokes.then(|| {
+            cx.observe_keystrokes(|event: &KeystrokeEvent, _, cx| {
+                // Passwords and other secrets are typed into masked editors.
+                if event
+                    .context_stack
+                    .iter()
+                    .any(|context| context.contains("masked"))
+                {
+                    return;
+                }
+                Self::record_keystroke(event.keystroke.clone(), cx)
+            })
+        });
+        cx.set_global(Self {
+            keystrokes: Vec::new(),
+            _clear_keystrokes: Task::ready(()),
+            _keystrokes_subscription: keystrokes_subscription,
+        });
+        theme::set_font_scale(font_scale, cx);
+    }
+
+    /// Whether the panel with the given persistent name should be hidden while presenting.
+    pub(crate) fn hides_panel(persistent_name: &str, cx: &App) -> bool {
+        Self::is_active(cx)
+            && WorkspaceSettings::get_global(cx)
+                .presentation_mode
+                .hidden_panels
+                .iter()
+                .any(|name| name == persistent_name)
+    }
+
+    fn record_keystroke(keystroke: Keystroke, cx: &mut App) {
+        let clear_keystrokes = cx.spawn(async move |cx| {
+            cx.background_executor().timer(KEYSTROKES_DURATION).await;
+            cx.update(|cx| {
+                if let Some(presentation_mode) = cx.try_global::<Self>() {
+                    if !presentation_mode.keystrokes.is_empty() {
+                        cx.global_mut::<Self>().keystrokes.clear();
+                        cx.refresh_windows();
+                    }
+                }
+            })
+            .ok();
+        });
+        let presentation_mode = cx.global_mut::<Self>();
+        presentation_mode.keystrokes.push(keystroke);
+        let excess = presentation_mode
+            .keystrokes
+            .len()
+            .saturating_sub(MAX_KEYSTROKES);
+        presentation_mode.keystrokes.drain(..excess);
+        presentation_mode._clear_keystrokes = clear_keystrokes;
+        cx.refresh_windows();
+    }
+
+    pub(crate) fn render_keystrokes(cx: &App) -> Option<impl IntoElement> {
+        let keystrokes = &cx.try_global::<Self>()?.keystrokes;
+        if keystrokes.is_empty() {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .absolute()
+                .bottom_12()
+                .w_full()
+                .justify_center()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .elevation_3(cx)
+                        .children(keystrokes.iter().map(|keystroke| {
+                            Label::new(text_for_keystroke(keystroke, cx))
+                                .size(LabelSize::Large)
+                                .buffer_font(cx)
+                        })),
+                ),
+        )
+    }
+}
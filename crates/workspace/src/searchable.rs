@@ -121,6 +121,16 @@ pub trait SearchableItem: Item + EventEmitter<SearchEvent> {
             self.replace(item, query, window, cx);
         }
     }
+    /// Shows what each of the matches would be replaced with by `query`, or stops showing it
+    /// when there's no query.
+    fn preview_replacements(
+        &mut self,
+        _matches: &[Self::Match],
+        _query: Option<&SearchQuery>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
     fn match_index_for_direction(
         &mut self,
         matches: &[Self::Match],
@@ -192,6 +202,13 @@ pub trait SearchableItemHandle: ItemHandle {
         window: &mut Window,
         cx: &mut App,
     );
+    fn preview_replacements(
+        &self,
+        matches: &AnyVec<dyn Send>,
+        query: Option<&SearchQuery>,
+        window: &mut Window,
+        cx: &mut App,
+    );
     fn match_index_for_direction(
         &self,
         matches: &AnyVec<dyn Send>,
@@ -356,6 +373,19 @@ impl<T: SearchableItem> SearchableItemHandle for Entity<T> {
         })
     }
 
+    fn preview_replacements(
+        &self,
+        matches: &AnyVec<dyn Send>,
+        query: Option<&SearchQuery>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let matches = matches.downcast_ref().unwrap();
+        self.update(cx, |this, cx| {
+            this.preview_replacements(matches.as_slice(), query, window, cx)
+        });
+    }
+
     fn search_bar_visibility_changed(&self, visible: bool, window: &mut Window, cx: &mut App) {
         self.update(cx, |this, cx| {
             this.search_bar_visibility_changed(visible, window, cx)
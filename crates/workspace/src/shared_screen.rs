@@ -2,14 +2,18 @@ use crate::{
     ItemNavHistory, WorkspaceId,
     item::{Item, ItemEvent},
 };
-use call::{RemoteVideoTrack, RemoteVideoTrackView, Room};
+use call::{
+    RemoteVideoTrack, RemoteVideoTrackView, Room,
+    screen_annotations::{AnnotationStroke, StrokeKind},
+};
 use client::{User, proto::PeerId};
 use gpui::{
-    AppContext as _, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
-    ParentElement, Render, SharedString, Styled, div,
+    AppContext as _, Bounds, CursorStyle, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
+    InteractiveElement, MouseButton, MouseDownEvent, MouseMoveEvent, ParentElement, PathBuilder,
+    Pixels, Point, Render, SharedString, Size, Styled, canvas, div, fill, point, px, size,
 };
-use std::sync::Arc;
-use ui::{Icon, IconName, prelude::*};
+use std::{f32::consts::PI, sync::Arc, time::Instant};
+use ui::{Icon, IconButton, IconName, Tooltip, prelude::*};
 
 pub enum Event {
     Close,
@@ -20,7 +24,12 @@ pub struct SharedScreen {
     user: Arc<User>,
     nav_history: Option<ItemNavHistory>,
     view: Entity<RemoteVideoTrackView>,
+    room: Entity<Room>,
     focus: FocusHandle,
+    annotation_tool: Option<StrokeKind>,
+    active_stroke: Option<u64>,
+    /// Where the video was last painted, to map the mouse onto the shared screen.
+    content_bounds: Bounds<Pixels>,
 }
 
 impl SharedScreen {
@@ -42,6 +51,7 @@ impl SharedScreen {
             _ => {}
         })
         .detach();
+        Self::observe_annotations(&room, peer_id, cx);
 
         let view = cx.new(|cx| RemoteVideoTrackView::new(track.clone(), window, cx));
         cx.subscribe(&view, |_, _, ev, cx| match ev {
@@ -52,9 +62,179 @@ impl SharedScreen {
             view,
             peer_id,
             user,
+            room,
             nav_history: Default::default(),
             focus: cx.focus_handle(),
+            annotation_tool: None,
+            active_stroke: None,
+            content_bounds: Bounds::default(),
+        }
+    }
+
+    fn observe_annotations(room: &Entity<Room>, peer_id: PeerId, cx: &mut Context<Self>) {
+        cx.subscribe(room, move |_, _, ev, cx| {
+            if let call::room::Event::ScreenAnnotationsChanged { presenter_id } = ev {
+                if *presenter_id == peer_id {
+                    cx.notify();
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn toggle_annotation_tool(&mut self, tool: StrokeKind, cx: &mut Context<Self>) {
+        if self.annotation_tool == Some(tool) {
+            self.annotation_tool = None;
+        } else {
+            self.annotation_tool = Some(tool);
         }
+        self.active_stroke = None;
+        cx.notify();
+    }
+
+    fn clear_annotations(&mut self, cx: &mut Context<Self>) {
+        let presenter_id = self.peer_id;
+        self.room.update(cx, |room, cx| {
+            room.clear_screen_annotations(presenter_id, cx)
+        });
+    }
+
+    /// The mouse position as fractions of the shared screen's size.
+    fn screen_position(&self, position: Point<Pixels>) -> Point<f32> {
+        let bounds = self.content_bounds;
+        if bounds.size.width <= px(0.) || bounds.size.height <= px(0.) {
+            return point(0., 0.);
+        }
+        let offset = position - bounds.origin;
+        point(
+            (offset.x / bounds.size.width).clamp(0., 1.),
+            (offset.y / bounds.size.height).clamp(0., 1.),
+        )
+    }
+
+    fn start_stroke(&mut self, event: &MouseDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(kind) = self.annotation_tool else {
+            return;
+        };
+        let position = self.screen_position(event.position);
+        let presenter_id = self.peer_id;
+        let stroke_id = self.room.update(cx, |room, cx| {
+            let stroke_id = room.next_annotation_stroke_id();
+            room.annotate_screen(presenter_id, stroke_id, kind, vec![position], cx);
+            stroke_id
+        });
+        self.active_stroke = Some(stroke_id);
+    }
+
+    fn extend_stroke(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let (Some(kind), Some(stroke_id)) = (self.annotation_tool, self.active_stroke) else {
+            return;
+        };
+        if event.pressed_button != Some(MouseButton::Left) {
+            self.active_stroke = None;
+            return;
+        }
+        let position = self.screen_position(event.position);
+        let presenter_id = self.peer_id;
+        self.room.update(cx, |room, cx| {
+            room.annotate_screen(presenter_id, stroke_id, kind, vec![position], cx)
+        });
+    }
+
+    fn render_annotation_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let tool_button = |id, icon, tool, tooltip, cx: &mut Context<Self>| {
+            IconButton::new(id, icon)
+                .icon_size(IconSize::Small)
+                .toggle_state(self.annotation_tool == Some(tool))
+                .tooltip(Tooltip::text(tooltip))
+                .on_click(cx.listener(move |this, _, _, cx| this.toggle_annotation_tool(tool, cx)))
+        };
+        h_flex()
+            .absolute()
+            .top_2()
+            .right_2()
+            .p_0p5()
+            .gap_0p5()
+            .elevation_2(cx)
+            .child(tool_button(
+                "annotate-pen",
+                IconName::Pencil,
+                StrokeKind::Pen,
+                "Draw",
+                cx,
+            ))
+            .child(tool_button(
+                "annotate-arrow",
+                IconName::ArrowUpRight,
+                StrokeKind::Arrow,
+                "Draw Arrow",
+                cx,
+            ))
+            .child(
+                IconButton::new("clear-annotations", IconName::Eraser)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Clear Your Drawings"))
+                    .on_click(cx.listener(|this, _, _, cx| this.clear_annotations(cx))),
+            )
+    }
+}
+
+/// The part of `bounds` a frame with the given aspect ratio is drawn in,
+/// scaled to fit and centered.
+fn fit_to_aspect_ratio(bounds: Bounds<Pixels>, aspect_ratio: Option<f32>) -> Bounds<Pixels> {
+    let Some(aspect_ratio) = aspect_ratio else {
+        return bounds;
+    };
+    let width = bounds.size.width.min(bounds.size.height * aspect_ratio);
+    let height = width / aspect_ratio;
+    Bounds::centered_at(bounds.center(), size(width, height))
+}
+
+fn paint_stroke(
+    stroke: &AnnotationStroke,
+    bounds: Bounds<Pixels>,
+    color: Hsla,
+    window: &mut Window,
+) {
+    let to_window = |position: Point<f32>| {
+        bounds.origin
+            + point(
+                bounds.size.width * position.x,
+                bounds.size.height * position.y,
+            )
+    };
+    let mut builder = PathBuilder::stroke(px(3.));
+    match stroke.kind {
+        StrokeKind::Pen => {
+            let mut points = stroke.points.iter().copied().map(to_window);
+            let Some(first) = points.next() else {
+                return;
+            };
+            builder.move_to(first);
+            // A click without dragging still leaves a dot.
+            builder.line_to(first + point(px(0.5), px(0.5)));
+            for point in points {
+                builder.line_to(point);
+            }
+        }
+        StrokeKind::Arrow => {
+            let (Some(start), Some(end)) = (stroke.points.first(), stroke.points.last()) else {
+                return;
+            };
+            let (start, end) = (to_window(*start), to_window(*end));
+            let delta = end - start;
+            let angle = delta.y.0.atan2(delta.x.0);
+            builder.move_to(start);
+            builder.line_to(end);
+            for head_angle in [angle + PI * 0.85, angle - PI * 0.85] {
+                builder.move_to(end);
+                builder
+                    .line_to(end + point(px(head_angle.cos() * 14.), px(head_angle.sin() * 14.)));
+            }
+        }
+    }
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color.opacity(stroke.opacity(Instant::now())));
     }
 }
 
@@ -66,13 +246,104 @@ impl Focusable for SharedScreen {
     }
 }
 impl Render for SharedScreen {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let now = Instant::now();
+        let room = self.room.read(cx);
+        let players = cx.theme().players();
+        let annotations = room.screen_annotations(self.peer_id);
+        let strokes = annotations
+            .into_iter()
+            .flat_map(|annotations| annotations.strokes(now))
+            .map(|stroke| {
+                let color = match room.remote_participant_for_peer_id(stroke.author) {
+                    Some(participant) => {
+                        players.color_for_participant(participant.participant_index.0)
+                    }
+                    None => players.local(),
+                };
+                (stroke.clone(), color.cursor)
+            })
+            .collect::<Vec<_>>();
+        let pointer = annotations.and_then(|annotations| annotations.pointer(now));
+        if !strokes.is_empty() || pointer.is_some() {
+            // Keep painting while strokes fade out and the pointer times out.
+            window.request_animation_frame();
+        }
+        let pointer_color = cx.theme().status().error;
+        let aspect_ratio = self.view.read(cx).aspect_ratio();
+        let this = cx.entity().downgrade();
+
         div()
             .bg(cx.theme().colors().editor_background)
             .track_focus(&self.focus)
             .key_context("SharedScreen")
             .size_full()
+            .relative()
             .child(self.view.clone())
+            .child(
+                div()
+                    .id("screen-annotations")
+                    .absolute()
+                    .inset_0()
+                    .when(self.annotation_tool.is_some(), |overlay| {
+                        overlay
+                            .cursor(CursorStyle::Crosshair)
+                            .on_mouse_down(MouseButton::Left, cx.listener(Self::start_stroke))
+                            .on_mouse_move(cx.listener(Self::extend_stroke))
+                            .on_mouse_up(
+                                MouseButton::Left,
+                                cx.listener(|this, _, _, _| this.active_stroke = None),
+                            )
+                            .on_mouse_up_out(
+                                MouseButton::Left,
+                                cx.listener(|this, _, _, _| this.active_stroke = None),
+                            )
+                    })
+                    .child(
+                        canvas(
+                            move |bounds, _, cx| {
+                                let content_bounds = fit_to_aspect_ratio(bounds, aspect_ratio);
+                                this.update(cx, |this, _| this.content_bounds = content_bounds)
+                                    .ok();
+                                content_bounds
+                            },
+                            move |_, content_bounds, window, _| {
+                                for (stroke, color) in &strokes {
+                                    paint_stroke(stroke, content_bounds, *color, window);
+                                }
+                                if let Some(position) = pointer {
+                                    let center = content_bounds.origin
+                                        + point(
+                                            content_bounds.size.width * position.x,
+                                            content_bounds.size.height * position.y,
+                                        );
+                                    window.paint_quad(
+                                        fill(
+                                            Bounds::centered_at(
+                                                center,
+                                                Size::new(px(24.), px(24.)),
+                                            ),
+                                            pointer_color.opacity(0.25),
+                                        )
+                                        .corner_radii(px(12.)),
+                                    );
+                                    window.paint_quad(
+                                        fill(
+                                            Bounds::centered_at(
+                                                center,
+                                                Size::new(px(10.), px(10.)),
+                                            ),
+                                            pointer_color,
+                                        )
+                                        .corner_radii(px(5.)),
+                                    );
+                                }
+                            },
+                        )
+                        .size_full(),
+                    ),
+            )
+            .child(self.render_annotation_toolbar(cx))
     }
 }
 
@@ -116,12 +387,19 @@ impl Item for SharedScreen {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Entity<Self>> {
-        Some(cx.new(|cx| Self {
-            view: self.view.update(cx, |view, cx| view.clone(window, cx)),
-            peer_id: self.peer_id,
-            user: self.user.clone(),
-            nav_history: Default::default(),
-            focus: cx.focus_handle(),
+        Some(cx.new(|cx| {
+            Self::observe_annotations(&self.room, self.peer_id, cx);
+            Self {
+                view: self.view.update(cx, |view, cx| view.clone(window, cx)),
+                peer_id: self.peer_id,
+                user: self.user.clone(),
+                room: self.room.clone(),
+                nav_history: Default::default(),
+                focus: cx.focus_handle(),
+                annotation_tool: None,
+                active_stroke: None,
+                content_bounds: Bounds::default(),
+            }
         }))
     }
 
@@ -1,11 +1,17 @@
-use crate::{ItemHandle, Pane};
+use crate::{
+    ItemHandle, Pane, WorkspaceSettings,
+    workspace_settings::{CustomStatusItem, StatusBarSettings, StatusBarSide},
+};
+use anyhow::{Result, ensure};
 use gpui::{
     AnyView, App, Context, Decorations, Entity, IntoElement, ParentElement, Render, Styled,
-    Subscription, Window,
+    Subscription, Task, WeakEntity, Window, accesskit::Role,
 };
-use std::any::TypeId;
+use project::Project;
+use settings::{Settings, SettingsStore};
+use std::{any::TypeId, rc::Rc, time::Duration};
 use theme::CLIENT_SIDE_DECORATION_ROUNDING;
-use ui::{h_flex, prelude::*};
+use ui::{Tooltip, h_flex, prelude::*};
 use util::ResultExt;
 
 pub trait StatusItemView: Render {
@@ -15,6 +21,12 @@ pub trait StatusItemView: Render {
         window: &mut Window,
         cx: &mut Context<Self>,
     );
+
+    /// The id that the `status_bar` settings hide this item or move it around with, if they
+    /// can.
+    fn status_item_id(&self) -> Option<SharedString> {
+        None
+    }
 }
 
 trait StatusItemViewHandle: Send {
@@ -26,18 +38,27 @@ trait StatusItemViewHandle: Send {
         cx: &mut App,
     );
     fn item_type(&self) -> TypeId;
+    fn status_item_id(&self, cx: &App) -> Option<SharedString>;
 }
 
 pub struct StatusBar {
     left_items: Vec<Box<dyn StatusItemViewHandle>>,
     right_items: Vec<Box<dyn StatusItemViewHandle>>,
+    /// The items from the `custom_items` settings, which are kept apart so that they can be
+    /// replaced when the settings change.
+    custom_items: Vec<(StatusBarSide, Entity<ProvidedStatusItem>)>,
+    custom_item_settings: Vec<CustomStatusItem>,
     active_pane: Entity<Pane>,
+    project: WeakEntity<Project>,
     _observe_active_pane: Subscription,
+    _observe_settings: Subscription,
 }
 
 impl Render for StatusBar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (left_items, right_items) = self.arranged_items(cx);
         h_flex()
+            .accessibility(Role::ContentInfo, "Status Bar")
             .w_full()
             .justify_between()
             .gap(DynamicSpacing::Base08.rems(cx))
@@ -58,40 +79,104 @@ impl Render for StatusBar {
                     .border_b(px(1.0))
                     .border_color(cx.theme().colors().status_bar_background),
             })
-            .child(self.render_left_tools(cx))
-            .child(self.render_right_tools(cx))
+            .child(self.render_left_tools(left_items, cx))
+            .child(self.render_right_tools(right_items, cx))
     }
 }
 
 impl StatusBar {
-    fn render_left_tools(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_left_tools(&self, items: Vec<AnyView>, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
             .gap(DynamicSpacing::Base04.rems(cx))
             .overflow_x_hidden()
-            .children(self.left_items.iter().map(|item| item.to_any()))
+            .children(items)
     }
 
-    fn render_right_tools(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_right_tools(&self, items: Vec<AnyView>, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
             .gap(DynamicSpacing::Base04.rems(cx))
-            .children(self.right_items.iter().rev().map(|item| item.to_any()))
+            .children(items)
+    }
+
+    /// Returns the items to show on each side, in the order they are shown in.
+    fn arranged_items(&self, cx: &App) -> (Vec<AnyView>, Vec<AnyView>) {
+        let item_with_id =
+            |item: &dyn StatusItemViewHandle| (item.status_item_id(cx), item.to_any());
+        let custom_items_on = move |side: StatusBarSide| {
+            self.custom_items
+                .iter()
+                .filter(move |(item_side, _)| *item_side == side)
+                .map(move |(_, item)| (Some(item.read(cx).id.clone()), AnyView::from(item.clone())))
+        };
+        let left_items = self
+            .left_items
+            .iter()
+            .map(|item| item_with_id(item.as_ref()))
+            .chain(custom_items_on(StatusBarSide::Left))
+            .collect();
+        let right_items = custom_items_on(StatusBarSide::Right)
+            .chain(
+                self.right_items
+                    .iter()
+                    .rev()
+                    .map(|item| item_with_id(item.as_ref())),
+            )
+            .collect();
+        arrange_items(
+            left_items,
+            right_items,
+            &WorkspaceSettings::get_global(cx).status_bar,
+        )
     }
 }
 
 impl StatusBar {
-    pub fn new(active_pane: &Entity<Pane>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn new(
+        active_pane: &Entity<Pane>,
+        project: &Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
         let mut this = Self {
             left_items: Default::default(),
             right_items: Default::default(),
+            custom_items: Default::default(),
+            custom_item_settings: Default::default(),
             active_pane: active_pane.clone(),
+            project: project.downgrade(),
             _observe_active_pane: cx.observe_in(active_pane, window, |this, _, window, cx| {
                 this.update_active_pane_item(window, cx)
             }),
+            _observe_settings: cx.observe_global::<SettingsStore>(|this, cx| {
+                this.update_custom_items(cx);
+                cx.notify();
+            }),
         };
+        this.update_custom_items(cx);
         this.update_active_pane_item(window, cx);
         this
     }
 
+    /// Recreates the custom items when their settings changed.
+    fn update_custom_items(&mut self, cx: &mut Context<Self>) {
+        let settings = &WorkspaceSettings::get_global(cx).status_bar.custom_items;
+        if *settings == self.custom_item_settings {
+            return;
+        }
+        self.custom_item_settings = settings.clone();
+        self.custom_items = settings
+            .iter()
+            .map(|settings| {
+                let provider = CommandStatusItemProvider {
+                    settings: settings.clone(),
+                    project: self.project.clone(),
+                };
+                let item = cx.new(|cx| ProvidedStatusItem::new(settings.id.clone(), provider, cx));
+                (settings.position, item)
+            })
+            .collect();
+    }
+
     pub fn add_left_item<T>(&mut self, item: Entity<T>, window: &mut Window, cx: &mut Context<Self>)
     where
         T: 'static + StatusItemView,
@@ -212,6 +297,10 @@ impl<T: StatusItemView> StatusItemViewHandle for Entity<T> {
     fn item_type(&self) -> TypeId {
         TypeId::of::<T>()
     }
+
+    fn status_item_id(&self, cx: &App) -> Option<SharedString> {
+        self.read(cx).status_item_id()
+    }
 }
 
 impl From<&dyn StatusItemViewHandle> for AnyView {
@@ -219,3 +308,254 @@ impl From<&dyn StatusItemViewHandle> for AnyView {
         val.to_any().clone()
     }
 }
+
+/// Places the items on each side of the status bar as the settings ask, leaving out the
+/// hidden ones. Both sides' items are given and returned with their ids, in display order.
+fn arrange_items<T>(
+    left_items: Vec<(Option<SharedString>, T)>,
+    right_items: Vec<(Option<SharedString>, T)>,
+    settings: &StatusBarSettings,
+) -> (Vec<T>, Vec<T>) {
+    let is_hidden = |id: &Option<SharedString>| {
+        id.as_ref().is_some_and(|id| {
+            settings
+                .hidden_items
+                .iter()
+                .any(|hidden| hidden == id.as_ref())
+        })
+    };
+    let mut items = left_items
+        .into_iter()
+        .map(|(id, item)| (StatusBarSide::Left, id, Some(item)))
+        .chain(
+            right_items
+                .into_iter()
+                .map(|(id, item)| (StatusBarSide::Right, id, Some(item))),
+        )
+        .filter(|(_, id, _)| !is_hidden(id))
+        .collect::<Vec<_>>();
+
+    let mut take_listed = |ids: &[String]| {
+        ids.iter()
+            .filter_map(|listed_id| {
+                items
+                    .iter_mut()
+                    .find(|(_, id, item)| item.is_some() && id.as_deref() == Some(listed_id))
+                    .and_then(|(_, _, item)| item.take())
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut left = take_listed(&settings.left_items);
+    let mut right = take_listed(&settings.right_items);
+    for (side, _, item) in items {
+        if let Some(item) = item {
+            match side {
+                StatusBarSide::Left => left.push(item),
+                StatusBarSide::Right => right.push(item),
+            }
+        }
+    }
+    (left, right)
+}
+
+/// Provides the text of a status bar item that refreshes on an interval. See
+/// [`ProvidedStatusItem`].
+pub trait StatusItemProvider: 'static {
+    /// How long to wait after computing the text to compute it again.
+    fn refresh_interval(&self) -> Duration;
+
+    /// Computes the text to show. The item is hidden while its text is empty.
+    fn text(&self, cx: &mut App) -> Task<Result<SharedString>>;
+
+    /// The tooltip to show when hovering the item.
+    fn tooltip(&self) -> Option<SharedString> {
+        None
+    }
+}
+
+/// A status bar item that shows the text of a [`StatusItemProvider`].
+pub struct ProvidedStatusItem {
+    id: SharedString,
+    text: SharedString,
+    provider: Rc<dyn StatusItemProvider>,
+    _refresh_task: Task<()>,
+}
+
+impl ProvidedStatusItem {
+    pub fn new(
+        id: impl Into<SharedString>,
+        provider: impl StatusItemProvider,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let provider = Rc::new(provider);
+        let refresh_task = cx.spawn({
+            let provider = provider.clone();
+            async move |this, cx| {
+                loop {
+                    let Ok(text) = cx.update(|cx| provider.text(cx)) else {
+                        break;
+                    };
+                    let text = text.await.log_err().unwrap_or_default();
+                    let updated = this.update(cx, |this, cx| {
+                        if this.text != text {
+                            this.text = text;
+                            cx.notify();
+                        }
+                    });
+                    if updated.is_err() {
+                        break;
+                    }
+                    cx.background_executor()
+                        .timer(provider.refresh_interval())
+                        .await;
+                }
+            }
+        });
+        Self {
+            id: id.into(),
+            text: SharedString::default(),
+            provider,
+            _refresh_task: refresh_task,
+        }
+    }
+}
+
+impl Render for ProvidedStatusItem {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        if self.text.is_empty() {
+            return div().into_any_element();
+        }
+
+        Button::new(self.id.clone(), self.text.clone())
+            .label_size(LabelSize::Small)
+            .when_some(self.provider.tooltip(), |button, tooltip| {
+                button.tooltip(Tooltip::text(tooltip))
+            })
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for ProvidedStatusItem {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn status_item_id(&self) -> Option<SharedString> {
+        Some(self.id.clone())
+    }
+}
+
+/// Shows the first line that a command from the `custom_items` settings prints.
+struct CommandStatusItemProvider {
+    settings: CustomStatusItem,
+    project: WeakEntity<Project>,
+}
+
+impl StatusItemProvider for CommandStatusItemProvider {
+    fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.settings.interval_seconds.max(1))
+    }
+
+    fn text(&self, cx: &mut App) -> Task<Result<SharedString>> {
+        let working_directory = self.project.upgrade().and_then(|project| {
+            let project = project.read(cx);
+            if !project.is_local() {
+                return None;
+            }
+            project
+                .visible_worktrees(cx)
+                .next()
+                .map(|worktree| worktree.read(cx).abs_path())
+        });
+        let mut command = util::command::new_smol_command(&self.settings.command);
+        command.args(&self.settings.args);
+        if let Some(working_directory) = working_directory {
+            command.current_dir(working_directory);
+        }
+        let id = self.settings.id.clone();
+        cx.background_spawn(async move {
+            let output = command.output().await?;
+            ensure!(
+                output.status.success(),
+                "status bar item {id:?} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            Ok(stdout
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+                .into())
+        })
+    }
+
+    fn tooltip(&self) -> Option<SharedString> {
+        Some(
+            std::iter::once(&self.settings.command)
+                .chain(&self.settings.args)
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(" ")
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrange_items() {
+        let items = |ids: &[&'static str]| {
+            ids.iter()
+                .map(|id| (Some(SharedString::new_static(id)), *id))
+                .collect::<Vec<_>>()
+        };
+        let settings = |hidden: &[&str], left: &[&str], right: &[&str]| StatusBarSettings {
+            hidden_items: hidden.iter().map(|id| id.to_string()).collect(),
+            left_items: left.iter().map(|id| id.to_string()).collect(),
+            right_items: right.iter().map(|id| id.to_string()).collect(),
+            custom_items: Vec::new(),
+        };
+        let left = items(&["search", "diagnostics"]);
+        let mut right = items(&["language", "cursor_position"]);
+        right.push((None, "unnamed"));
+
+        assert_eq!(
+            arrange_items(left.clone(), right.clone(), &settings(&[], &[], &[])),
+            (
+                vec!["search", "diagnostics"],
+                vec!["language", "cursor_position", "unnamed"]
+            )
+        );
+        assert_eq!(
+            arrange_items(
+                left.clone(),
+                right.clone(),
+                &settings(
+                    &["search"],
+                    &["cursor_position", "unknown"],
+                    &["diagnostics"]
+                )
+            ),
+            (
+                vec!["cursor_position"],
+                vec!["diagnostics", "language", "unnamed"]
+            )
+        );
+        assert_eq!(
+            arrange_items(left, right, &settings(&[], &[], &["unnamed", "language"])),
+            (
+                vec!["search", "diagnostics"],
+                vec!["language", "cursor_position", "unnamed"]
+            )
+        );
+    }
+}
@@ -54,6 +54,14 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
+        if !self.ensure_trusted(cx) {
+            return;
+        }
+        if !self.project.read(cx).supports_terminal(cx) {
+            log::warn!("Cannot run tasks as a guest of a shared project");
+            return;
+        }
+
         let spawn_in_terminal = resolved_task.resolved.clone();
         if !omit_history {
             if let Some(debugger_provider) = self.debugger_provider.as_ref() {
@@ -72,9 +80,18 @@ impl Workspace {
         }
 
         if let Some(terminal_provider) = self.terminal_provider.as_ref() {
+            let room_task = self.shared_project_room(cx).map(|room| {
+                let label = spawn_in_terminal.label.clone().into();
+                let id = room.update(cx, |room, cx| room.task_started(label, cx));
+                (room, id)
+            });
             let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
-            cx.background_spawn(async move {
-                match task_status.await {
+            cx.spawn(async move |_, cx| {
+                let task_status = task_status.await;
+                if let Some((room, id)) = room_task {
+                    room.update(cx, |room, cx| room.task_finished(id, cx)).ok();
+                }
+                match task_status {
                     Some(Ok(status)) => {
                         if status.success() {
                             log::debug!("Task spawn succeeded");
@@ -109,6 +126,14 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) -> Task<Option<Result<ExitStatus>>> {
+        if !self.ensure_trusted(cx) {
+            return Task::ready(None);
+        }
+        if !self.project.read(cx).supports_terminal(cx) {
+            log::warn!("Cannot run tasks as a guest of a shared project");
+            return Task::ready(None);
+        }
+
         if let Some(terminal_provider) = self.terminal_provider.as_ref() {
             terminal_provider.spawn(spawn_in_terminal, window, cx)
         } else {
@@ -0,0 +1,70 @@
+/// What the variables of the `window_title` format are replaced with.
+#[derive(Debug, Default)]
+pub(crate) struct WindowTitleVariables<'a> {
+    pub project: &'a str,
+    pub path: &'a str,
+    pub filename: &'a str,
+    pub dirty: bool,
+    pub branch: &'a str,
+    pub collab: &'a str,
+}
+
+const SEPARATOR: &str = " — ";
+
+/// Formats a window title from the `window_title` setting. Parts between `${separator}`s that
+/// turn out empty are left out, along with the separators around them.
+pub(crate) fn format_window_title(format: &str, variables: &WindowTitleVariables) -> String {
+    format
+        .split("${separator}")
+        .map(|part| {
+            part.replace("${project}", variables.project)
+                .replace("${path}", variables.path)
+                .replace("${filename}", variables.filename)
+                .replace("${dirty}", if variables.dirty { "● " } else { "" })
+                .replace("${branch}", variables.branch)
+                .replace("${collab}", variables.collab)
+        })
+        .filter(|part| !part.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(SEPARATOR)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_window_title() {
+        let variables = WindowTitleVariables {
+            project: "zed",
+            path: "crates/workspace/src/workspace.rs",
+            filename: "workspace.rs",
+            dirty: true,
+            branch: "main",
+            collab: "",
+        };
+        assert_eq!(
+            format_window_title("${dirty}${filename}${separator}${project}", &variables),
+            "● workspace.rs — zed"
+        );
+        assert_eq!(
+            format_window_title("${project} [${branch}]: ${path}${collab}", &variables),
+            "zed [main]: crates/workspace/src/workspace.rs"
+        );
+
+        let variables = WindowTitleVariables {
+            project: "zed",
+            ..Default::default()
+        };
+        assert_eq!(
+            format_window_title(
+                "${dirty}${filename}${separator}${project}${separator}${branch}",
+                &variables
+            ),
+            "zed"
+        );
+        assert_eq!(format_window_title("${unknown}", &variables), "${unknown}");
+    }
+}
@@ -1,11 +1,15 @@
+pub mod bell;
 pub mod dock;
 pub mod history_manager;
 pub mod item;
+mod item_windows;
+mod layouts;
 mod modal_layer;
 pub mod notifications;
 pub mod pane;
 pub mod pane_group;
 mod persistence;
+mod presentation_mode;
 pub mod searchable;
 pub mod shared_screen;
 mod status_bar;
@@ -13,12 +17,15 @@ pub mod tasks;
 mod theme_preview;
 mod toast_layer;
 mod toolbar;
+mod window_title;
 mod workspace_settings;
+mod workspace_trust;
 
+pub use presentation_mode::PresentationMode;
 pub use toast_layer::{ToastAction, ToastLayer, ToastView};
 
 use anyhow::{Context as _, Result, anyhow};
-use call::{ActiveCall, call_settings::CallSettings};
+use call::{ActiveCall, Room, call_settings::CallSettings};
 use client::{
     ChannelId, Client, ErrorExt, Status, TypedEnvelope, UserStore,
     proto::{self, ErrorCode, PanelId, PeerId},
@@ -38,9 +45,10 @@ use gpui::{
     Action, AnyEntity, AnyView, AnyWeakView, App, AsyncApp, AsyncWindowContext, Bounds, Context,
     CursorStyle, Decorations, DragMoveEvent, Entity, EntityId, EventEmitter, FocusHandle,
     Focusable, Global, HitboxBehavior, Hsla, KeyContext, Keystroke, ManagedView, MouseButton,
-    PathPromptOptions, Point, PromptLevel, Render, ResizeEdge, Size, Stateful, Subscription, Task,
-    Tiling, WeakEntity, WindowBounds, WindowHandle, WindowId, WindowOptions, action_as, actions,
-    canvas, impl_action_as, impl_actions, point, relative, size, transparent_black,
+    MouseMoveEvent, PathPromptOptions, Point, PromptLevel, Render, ResizeEdge, Size, Stateful,
+    Subscription, Task, Tiling, WeakEntity, WindowBounds, WindowHandle, WindowId, WindowOptions,
+    accesskit::Role, action_as, actions, canvas, impl_action_as, impl_actions, point, relative,
+    size, transparent_black,
 };
 pub use history_manager::*;
 pub use item::{
@@ -49,6 +57,7 @@ pub use item::{
 };
 use itertools::Itertools;
 use language::{Buffer, LanguageRegistry, Rope};
+pub use layouts::{ApplyLayout, DeleteLayout, LayoutScope, SaveLayout, SavedLayout};
 pub use modal_layer::*;
 use node_runtime::NodeRuntime;
 use notifications::{
@@ -69,19 +78,20 @@ use postage::stream::Stream;
 use project::{
     DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
     debugger::{breakpoint_store::BreakpointStoreEvent, session::ThreadStatus},
+    git_store::GitStoreEvent,
 };
 use remote::{SshClientDelegate, SshConnectionOptions, ssh_session::ConnectionIdentifier};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use session::AppSession;
-use settings::Settings;
+use settings::{Settings, SettingsStore};
 use shared_screen::SharedScreen;
 use sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
     statement::Statement,
 };
 use status_bar::StatusBar;
-pub use status_bar::StatusItemView;
+pub use status_bar::{ProvidedStatusItem, StatusItemProvider, StatusItemView};
 use std::{
     any::TypeId,
     borrow::Cow,
@@ -90,6 +100,7 @@ use std::{
     collections::hash_map::DefaultHasher,
     env,
     hash::{Hash, Hasher},
+    mem,
     path::{Path, PathBuf},
     process::ExitStatus,
     rc::Rc,
@@ -97,7 +108,7 @@ use std::{
     time::Duration,
 };
 use task::{DebugScenario, SpawnInTerminal, TaskContext};
-use theme::{ActiveTheme, SystemAppearance, ThemeSettings};
+use theme::{ActiveTheme, AppearanceController};
 pub use toolbar::{Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 pub use ui;
 use ui::{Window, prelude::*};
@@ -115,6 +126,7 @@ use crate::persistence::{
 };
 
 pub const SERIALIZATION_THROTTLE_TIME: Duration = Duration::from_millis(200);
+const ZEN_MODE_DIM_OPACITY: f32 = 0.5;
 
 static ZED_WINDOW_SIZE: LazyLock<Option<Size<Pixels>>> = LazyLock::new(|| {
     env::var("ZED_WINDOW_SIZE")
@@ -177,9 +189,12 @@ actions!(
         CloseActiveDock,
         CloseAllDocks,
         CloseWindow,
+        EqualizePanes,
         Feedback,
         FollowNextCollaborator,
         MoveFocusedPanelToNextPosition,
+        MoveItemToMainWindow,
+        MoveItemToNewWindow,
         NewCenterTerminal,
         NewFile,
         NewFileSplitVertical,
@@ -192,6 +207,7 @@ actions!(
         OpenInTerminal,
         OpenComponentPreview,
         ReloadActiveItem,
+        RevokeProjectTrust,
         SaveAs,
         SaveWithoutFormat,
         ShutdownDebugAdapters,
@@ -199,8 +215,11 @@ actions!(
         ToggleBottomDock,
         ToggleCenteredLayout,
         ToggleLeftDock,
+        TogglePresentationMode,
         ToggleRightDock,
+        ToggleZenMode,
         ToggleZoom,
+        TrustProject,
         Unfollow,
         Welcome,
         RestoreBanner,
@@ -232,6 +251,20 @@ pub struct MoveItemToPaneInDirection {
     pub focus: bool,
 }
 
+/// Resizes the active pane, or the focused dock, by moving its edge in the given direction.
+#[derive(Clone, Deserialize, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ResizePane {
+    pub direction: SplitDirection,
+    /// How far to move the edge, in pixels.
+    #[serde(default = "default_resize_amount")]
+    pub amount: f32,
+}
+
+fn default_resize_amount() -> f32 {
+    10.
+}
+
 #[derive(Clone, PartialEq, Debug, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct SaveAll {
@@ -285,6 +318,7 @@ impl_actions!(
         MoveItemToPaneInDirection,
         OpenTerminal,
         Reload,
+        ResizePane,
         Save,
         SaveAll,
         SendKeystrokes,
@@ -428,6 +462,7 @@ pub fn init(app_state: Arc<AppState>, cx: &mut App) {
     component::init();
     theme_preview::init(cx);
     toast_layer::init(cx);
+    presentation_mode::init(cx);
     history_manager::init(cx);
 
     cx.on_action(Workspace::close_global);
@@ -777,7 +812,6 @@ impl AppState {
     pub fn test(cx: &mut App) -> Arc<Self> {
         use node_runtime::NodeRuntime;
         use session::Session;
-        use settings::SettingsStore;
 
         if !cx.has_global::<SettingsStore>() {
             let settings_store = SettingsStore::test(cx);
@@ -870,6 +904,8 @@ pub enum Event {
         item: Box<dyn ItemHandle>,
     },
     ItemRemoved,
+    /// A file in the project was opened in a pane, whether or not it was open already.
+    PathOpened(ProjectPath),
     ActiveItemChanged,
     UserSavedItem {
         pane: WeakEntity<Pane>,
@@ -884,6 +920,7 @@ pub enum Event {
         language: &'static str,
     },
     ZoomChanged,
+    ZenModeChanged,
     ModalOpened,
     ClearActivityIndicator,
 }
@@ -959,6 +996,14 @@ pub struct Workspace {
     pane_history_timestamp: Arc<AtomicUsize>,
     bounds: Bounds<Pixels>,
     pub centered_layout: bool,
+    pub zen_mode: bool,
+    /// The window that this one was opened from by moving an item to a new window, which
+    /// shares its project.
+    pub(crate) main_window: Option<WindowHandle<Workspace>>,
+    /// The panel that was moved to this window from another one sharing its project.
+    moved_panel: Option<SharedString>,
+    /// The tab being dragged, while it is dragged outside of the window.
+    tab_dragged_out: Option<DraggedTab>,
     bounds_save_task_queued: Option<Task<()>>,
     on_prompt_for_new_path: Option<PromptForNewPath>,
     on_prompt_for_open_path: Option<PromptForOpenPath>,
@@ -968,6 +1013,8 @@ pub struct Workspace {
     serialized_ssh_project: Option<SerializedSshProject>,
     _items_serializer: Task<Result<()>>,
     session_id: Option<String>,
+    trust_prompt: Option<Task<()>>,
+    folders_prompted_for_trust: HashSet<PathBuf>,
 }
 
 impl EventEmitter<Event> for Workspace {}
@@ -983,6 +1030,9 @@ pub struct FollowerState {
     dock_pane: Option<Entity<Pane>>,
     active_view_id: Option<ViewId>,
     items_by_leader_view_id: HashMap<ViewId, FollowerView>,
+    /// Whether the follower scrolled away from the leader's viewport, which pauses tracking the
+    /// leader until they re-sync.
+    scrolled_away: bool,
 }
 
 struct FollowerView {
@@ -1012,6 +1062,9 @@ impl Workspace {
                 }
 
                 project::Event::WorktreeRemoved(_) | project::Event::WorktreeAdded(_) => {
+                    if let project::Event::WorktreeAdded(_) = event {
+                        this.prompt_to_trust_folders(window, cx);
+                    }
                     this.update_window_title(window, cx);
                     this.serialize_workspace(window, cx);
                     // This event could be triggered by `AddFolderToProject` or `RemoveFromProject`.
@@ -1179,7 +1232,7 @@ impl Workspace {
         let bottom_dock_buttons = cx.new(|cx| PanelButtons::new(bottom_dock.clone(), cx));
         let right_dock_buttons = cx.new(|cx| PanelButtons::new(right_dock.clone(), cx));
         let status_bar = cx.new(|cx| {
-            let mut status_bar = StatusBar::new(&center_pane.clone(), window, cx);
+            let mut status_bar = StatusBar::new(&center_pane.clone(), &project, window, cx);
             status_bar.add_left_item(left_dock_buttons, window, cx);
             status_bar.add_right_item(right_dock_buttons, window, cx);
             status_bar.add_right_item(bottom_dock_buttons, window, cx);
@@ -1233,12 +1286,23 @@ impl Workspace {
                 cx.notify();
             }),
             cx.observe_window_appearance(window, |_, window, cx| {
-                let window_appearance = window.appearance();
-
-                *SystemAppearance::global_mut(cx) = SystemAppearance(window_appearance.into());
-
-                ThemeSettings::reload_current_theme(cx);
-                ThemeSettings::reload_current_icon_theme(cx);
+                AppearanceController::set_window_appearance(window.appearance().into(), cx);
+            }),
+            cx.subscribe_in(
+                project.read(cx).git_store(),
+                window,
+                |this, _, event, window, cx| match event {
+                    GitStoreEvent::ActiveRepositoryChanged(_)
+                    | GitStoreEvent::RepositoryUpdated(..) => {
+                        if WorkspaceSettings::get_global(cx).window_title.is_some() {
+                            this.update_window_title(window, cx);
+                        }
+                    }
+                    _ => {}
+                },
+            ),
+            cx.observe_global_in::<SettingsStore>(window, |this, window, cx| {
+                this.update_window_title(window, cx);
             }),
             cx.on_release(move |this, cx| {
                 this.app_state.workspace_store.update(cx, move |store, _| {
@@ -1291,6 +1355,10 @@ impl Workspace {
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
             centered_layout: false,
+            zen_mode: false,
+            main_window: None,
+            moved_panel: None,
+            tab_dragged_out: None,
             bounds_save_task_queued: None,
             on_prompt_for_new_path: None,
             on_prompt_for_open_path: None,
@@ -1300,6 +1368,8 @@ impl Workspace {
             _items_serializer,
             session_id: Some(session_id),
             serialized_ssh_project: None,
+            trust_prompt: None,
+            folders_prompted_for_trust: HashSet::default(),
         }
     }
 
@@ -1439,6 +1509,10 @@ impl Workspace {
                     .as_ref()
                     .map(|w| w.centered_layout)
                     .unwrap_or(false);
+                let zen_mode = serialized_workspace
+                    .as_ref()
+                    .map(|w| w.zen_mode)
+                    .unwrap_or(false);
                 cx.open_window(options, {
                     let app_state = app_state.clone();
                     let project_handle = project_handle.clone();
@@ -1452,6 +1526,7 @@ impl Workspace {
                                 cx,
                             );
                             workspace.centered_layout = centered_layout;
+                            workspace.zen_mode = zen_mode;
                             workspace
                         })
                     }
@@ -1459,6 +1534,13 @@ impl Workspace {
             };
 
             notify_if_database_failed(window, cx);
+            // The project's folders were added before the workspace could hear about them.
+            window
+                .update(cx, |workspace, window, cx| {
+                    workspace.prompt_to_trust_folders(window, cx)
+                })
+                .log_err();
+            let restoring = serialized_workspace.is_some();
             let opened_items = window
                 .update(cx, |_workspace, window, cx| {
                     open_items(serialized_workspace, project_paths, window, cx)
@@ -1470,6 +1552,9 @@ impl Workspace {
                 .update(cx, |workspace, window, cx| {
                     window.activate_window();
                     workspace.update_history(cx);
+                    if restoring {
+                        workspace.restore_item_windows(window, cx);
+                    }
                 })
                 .log_err();
             Ok((window, opened_items))
@@ -1541,9 +1626,24 @@ impl Workspace {
         let dock_position = panel.position(window, cx);
         let dock = self.dock_at_position(dock_position);
 
+        let panel_key = panel.read(cx).panel_key();
         dock.update(cx, |dock, cx| {
             dock.add_panel(panel, self.weak_self.clone(), window, cx)
         });
+        if self.moved_panel.as_ref() == Some(&panel_key) {
+            self.open_panel(&panel_key, window, cx);
+        }
+    }
+
+    pub fn remove_panel<T: Panel>(
+        &mut self,
+        panel: &Entity<T>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        for dock in self.all_docks() {
+            dock.update(cx, |dock, cx| dock.remove_panel(panel, window, cx));
+        }
     }
 
     pub fn status_bar(&self) -> &Entity<StatusBar> {
@@ -1670,12 +1770,34 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) -> Task<Result<()>> {
+        self.navigate_history_to(pane, mode, None, window, cx)
+    }
+
+    /// Navigates through the history of `pane` in the direction of `mode`, either to the first
+    /// entry that changes the pane's location, or straight to the entry with `target_timestamp`.
+    fn navigate_history_to(
+        &mut self,
+        pane: WeakEntity<Pane>,
+        mode: NavigationMode,
+        target_timestamp: Option<usize>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        let mut skipped_entries = Vec::new();
         let to_load = if let Some(pane) = pane.upgrade() {
             pane.update(cx, |pane, cx| {
                 window.focus(&pane.focus_handle(cx));
                 loop {
                     // Retrieve the weak item handle from the history.
-                    let entry = pane.nav_history_mut().pop(mode, cx)?;
+                    let entry = match target_timestamp {
+                        Some(timestamp) => {
+                            let (entry, skipped) =
+                                pane.nav_history_mut().pop_to(mode, timestamp, cx)?;
+                            skipped_entries = skipped;
+                            entry
+                        }
+                        None => pane.nav_history_mut().pop(mode, cx)?,
+                    };
 
                     // If the item is still present in this pane, then activate it.
                     if let Some(index) = entry
@@ -1692,17 +1814,24 @@ impl Workspace {
                         if let Some(data) = entry.data {
                             navigated |= pane.active_item()?.navigate(data, window, cx);
                         }
+                        pane.nav_history_mut()
+                            .push_skipped(mode, mem::take(&mut skipped_entries));
 
-                        if navigated {
+                        if navigated || target_timestamp.is_some() {
                             break None;
                         }
                     } else {
                         // If the item is no longer present in this pane, then retrieve its
                         // path info in order to reopen it.
-                        break pane
+                        let to_load = pane
                             .nav_history()
                             .path_for_item(entry.item.id())
                             .map(|(project_path, abs_path)| (project_path, abs_path, entry));
+                        if to_load.is_none() {
+                            pane.nav_history_mut()
+                                .push_skipped(mode, mem::take(&mut skipped_entries));
+                        }
+                        break to_load;
                     }
                 }
             })
@@ -1742,6 +1871,7 @@ impl Workspace {
                             if let Some(data) = entry.data {
                                 navigated |= item.navigate(data, window, cx);
                             }
+                            pane.nav_history_mut().push_skipped(mode, skipped_entries);
                         })?;
                     }
                     Err(open_by_project_path_e) => {
@@ -1766,6 +1896,7 @@ impl Workspace {
                                         if let Some(data) = entry.data {
                                             navigated |= item.navigate(data, window, cx);
                                         }
+                                        pane.nav_history_mut().push_skipped(mode, skipped_entries);
                                     })?;
                                 }
                                 Err(open_by_abs_path_e) => {
@@ -1776,7 +1907,7 @@ impl Workspace {
                     }
                 }
 
-                if !navigated {
+                if !navigated && target_timestamp.is_none() {
                     workspace
                         .update_in(cx, |workspace, window, cx| {
                             Self::navigate_history(workspace, pane, mode, window, cx)
@@ -1809,6 +1940,19 @@ impl Workspace {
         self.navigate_history(pane, NavigationMode::GoingForward, window, cx)
     }
 
+    /// Navigates back or forward in the history of `pane` to the entry with the given timestamp,
+    /// as if going back or forward repeatedly until reaching it.
+    pub fn go_to_history_entry(
+        &mut self,
+        pane: WeakEntity<Pane>,
+        mode: NavigationMode,
+        timestamp: usize,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        self.navigate_history_to(pane, mode, Some(timestamp), window, cx)
+    }
+
     pub fn reopen_closed_item(
         &mut self,
         window: &mut Window,
@@ -2039,9 +2183,15 @@ impl Workspace {
 
     pub fn close_window(&mut self, _: &CloseWindow, window: &mut Window, cx: &mut Context<Self>) {
         let prepare = self.prepare_to_close(CloseIntent::CloseWindow, window, cx);
+        let main_window = self.main_window(cx);
         cx.spawn_in(window, async move |_, cx| {
             if prepare.await? {
-                cx.update(|window, _cx| window.remove_window())?;
+                cx.update(|window, cx| {
+                    window.remove_window();
+                    if let Some(main_window) = main_window {
+                        item_windows::serialize_item_windows(main_window, cx);
+                    }
+                })?;
             }
             anyhow::Ok(())
         })
@@ -2777,7 +2927,12 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Entity<T>> {
-        let panel = self.focus_or_unfocus_panel::<T>(window, cx, |_, _, _| true)?;
+        let panel = self.focus_or_unfocus_panel(
+            window,
+            cx,
+            |dock| dock.panel_index_for_type::<T>(),
+            |_, _, _| true,
+        )?;
         panel.to_any().downcast().ok()
     }
 
@@ -2787,9 +2942,29 @@ impl Workspace {
         &mut self,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) -> bool {
+        self.toggle_panel_focus_at(window, cx, |dock| dock.panel_index_for_type::<T>())
+    }
+
+    /// Focus the panel with the given entity ID if it isn't already focused,
+    /// for panels whose type is shared by several instances.
+    pub fn toggle_panel_focus_for_id(
+        &mut self,
+        panel_id: EntityId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.toggle_panel_focus_at(window, cx, |dock| dock.panel_index_for_id(panel_id))
+    }
+
+    fn toggle_panel_focus_at(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        panel_index: impl Fn(&Dock) -> Option<usize>,
     ) -> bool {
         let mut did_focus_panel = false;
-        self.focus_or_unfocus_panel::<T>(window, cx, |panel, window, cx| {
+        self.focus_or_unfocus_panel(window, cx, panel_index, |panel, window, cx| {
             did_focus_panel = !panel.panel_focus_handle(cx).contains_focused(window, cx);
             did_focus_panel
         });
@@ -2822,17 +2997,18 @@ impl Workspace {
         panel
     }
 
-    /// Focus or unfocus the given panel type, depending on the given callback.
-    fn focus_or_unfocus_panel<T: Panel>(
+    /// Focus or unfocus the panel found by `panel_index`, depending on the given callback.
+    fn focus_or_unfocus_panel(
         &mut self,
         window: &mut Window,
         cx: &mut Context<Self>,
+        panel_index: impl Fn(&Dock) -> Option<usize>,
         mut should_focus: impl FnMut(&dyn PanelHandle, &mut Window, &mut Context<Dock>) -> bool,
     ) -> Option<Arc<dyn PanelHandle>> {
         let mut result_panel = None;
         let mut serialize = false;
         for dock in self.all_docks() {
-            if let Some(panel_index) = dock.read(cx).panel_index_for_type::<T>() {
+            if let Some(panel_index) = panel_index(dock.read(cx)) {
                 let mut focus_center = false;
                 let panel = dock.update(cx, |dock, cx| {
                     dock.activate_panel(panel_index, window, cx);
@@ -3108,12 +3284,13 @@ impl Workspace {
 
         let project_path = path.into();
         let task = self.load_path(project_path.clone(), window, cx);
+        let this = self.weak_self.clone();
         window.spawn(cx, async move |cx| {
             let (project_entry_id, build_item) = task.await?;
             let result = pane.update_in(cx, |pane, window, cx| {
                 pane.open_item(
                     project_entry_id,
-                    project_path,
+                    project_path.clone(),
                     focus_item,
                     allow_preview,
                     activate,
@@ -3123,6 +3300,10 @@ impl Workspace {
                     build_item,
                 )
             });
+            if result.is_ok() {
+                this.update(cx, |_, cx| cx.emit(Event::PathOpened(project_path)))
+                    .ok();
+            }
             result
         })
     }
@@ -3169,10 +3350,10 @@ impl Workspace {
                     window,
                     cx,
                 );
-                new_pane.update(cx, |new_pane, cx| {
-                    Some(new_pane.open_item(
+                let item = new_pane.update(cx, |new_pane, cx| {
+                    new_pane.open_item(
                         project_entry_id,
-                        project_path,
+                        project_path.clone(),
                         true,
                         allow_preview,
                         true,
@@ -3180,8 +3361,10 @@ impl Workspace {
                         window,
                         cx,
                         build_item,
-                    ))
-                })
+                    )
+                });
+                cx.emit(Event::PathOpened(project_path));
+                Some(item)
             })
             .map(|option| option.context("pane was dropped"))?
         })
@@ -3590,14 +3773,32 @@ impl Workspace {
             self.center
                 .resize(&self.active_pane, axis, amount, &self.bounds);
         }
+        self.serialize_workspace(window, cx);
         cx.notify();
     }
 
-    pub fn reset_pane_sizes(&mut self, cx: &mut Context<Self>) {
+    pub fn reset_pane_sizes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.center.reset_pane_sizes();
+        self.serialize_workspace(window, cx);
         cx.notify();
     }
 
+    fn resize_pane_action(
+        &mut self,
+        action: &ResizePane,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let amount = px(action.amount);
+        let (axis, amount) = match action.direction {
+            SplitDirection::Left => (gpui::Axis::Horizontal, -amount),
+            SplitDirection::Right => (gpui::Axis::Horizontal, amount),
+            SplitDirection::Up => (gpui::Axis::Vertical, -amount),
+            SplitDirection::Down => (gpui::Axis::Vertical, amount),
+        };
+        self.resize_pane(axis, amount, window, cx);
+    }
+
     fn handle_pane_focused(
         &mut self,
         pane: Entity<Pane>,
@@ -3902,6 +4103,18 @@ impl Workspace {
         &self.panes
     }
 
+    /// Whether the item is the active item of a center pane other than the active pane.
+    pub fn is_item_in_inactive_pane(&self, item_id: EntityId, cx: &App) -> bool {
+        self.panes.len() > 1
+            && self.panes.iter().any(|pane| {
+                pane != &self.active_pane
+                    && pane
+                        .read(cx)
+                        .active_item()
+                        .is_some_and(|item| item.item_id() == item_id)
+            })
+    }
+
     pub fn active_pane(&self) -> &Entity<Pane> {
         &self.active_pane
     }
@@ -3969,6 +4182,7 @@ impl Workspace {
                 dock_pane: None,
                 active_view_id: None,
                 items_by_leader_view_id: Default::default(),
+                scrolled_away: false,
             },
         );
         cx.notify();
@@ -4102,6 +4316,7 @@ impl Workspace {
         // if you're already following, find the right pane and focus it.
         if let Some(follower_state) = self.follower_states.get(&leader_id) {
             window.focus(&follower_state.pane().focus_handle(cx));
+            self.resync_with_leader(leader_id, window, cx);
 
             return;
         }
@@ -4142,6 +4357,54 @@ impl Workspace {
         Some(())
     }
 
+    /// Stops tracking the leader's viewport after the follower scrolled away from it, while
+    /// still following them.
+    pub fn scroll_away_from_leader(
+        &mut self,
+        leader_id: impl Into<CollaboratorId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(state) = self.follower_states.get_mut(&leader_id.into()) else {
+            return;
+        };
+        if state.scrolled_away {
+            return;
+        }
+        state.scrolled_away = true;
+        for item in state.items_by_leader_view_id.values() {
+            item.view.set_viewport_synced(false, window, cx);
+        }
+        cx.notify();
+    }
+
+    /// Jumps back to the leader's view and viewport after scrolling away from them.
+    pub fn resync_with_leader(
+        &mut self,
+        leader_id: impl Into<CollaboratorId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let leader_id = leader_id.into();
+        let Some(state) = self.follower_states.get_mut(&leader_id) else {
+            return;
+        };
+        if !state.scrolled_away {
+            return;
+        }
+        state.scrolled_away = false;
+        for item in state.items_by_leader_view_id.values() {
+            item.view.set_viewport_synced(true, window, cx);
+        }
+        self.leader_updated(leader_id, window, cx);
+    }
+
+    pub fn is_scrolled_away_from_leader(&self, leader_id: impl Into<CollaboratorId>) -> bool {
+        self.follower_states
+            .get(&leader_id.into())
+            .is_some_and(|state| state.scrolled_away)
+    }
+
     pub fn is_being_followed(&self, id: impl Into<CollaboratorId>) -> bool {
         self.follower_states.contains_key(&id.into())
     }
@@ -4156,6 +4419,12 @@ impl Workspace {
     }
 
     fn update_window_title(&mut self, window: &mut Window, cx: &mut App) {
+        if let Some(format) = WorkspaceSettings::get_global(cx).window_title.clone() {
+            let title = self.custom_window_title(&format, cx);
+            window.set_window_title(&title);
+            return;
+        }
+
         let project = self.project().read(cx);
         let mut title = String::new();
 
@@ -4199,6 +4468,49 @@ impl Workspace {
         window.set_window_title(&title);
     }
 
+    fn custom_window_title(&self, format: &str, cx: &App) -> String {
+        let project = self.project().read(cx);
+        let project_name = project
+            .worktree_root_names(cx)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let active_item = self.active_item(cx);
+        let path = active_item
+            .as_ref()
+            .and_then(|item| item.project_path(cx))
+            .map(|path| path.path.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let filename = Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let branch = project
+            .active_repository(cx)
+            .and_then(|repository| {
+                let branch = repository.read(cx).branch.as_ref()?;
+                Some(branch.name().to_string())
+            })
+            .unwrap_or_default();
+        let collab = if project.is_via_collab() {
+            "↙"
+        } else if project.is_shared() {
+            "↗"
+        } else {
+            ""
+        };
+        window_title::format_window_title(
+            format,
+            &window_title::WindowTitleVariables {
+                project: &project_name,
+                path: &path,
+                filename: &filename,
+                dirty: active_item.is_some_and(|item| item.is_dirty(cx)),
+                branch: &branch,
+                collab,
+            },
+        )
+    }
+
     fn update_window_edited(&mut self, window: &mut Window, cx: &mut App) {
         let is_edited = !self.project.read(cx).is_disconnected(cx) && !self.dirty_items.is_empty();
         if is_edited != self.window_edited {
@@ -4222,6 +4534,7 @@ impl Workspace {
         if was_dirty {
             self.dirty_items.remove(&item_id);
             self.update_window_edited(window, cx);
+            self.update_window_title(window, cx);
             return;
         }
         if let Some(window_handle) = window.window_handle().downcast::<Self>() {
@@ -4238,6 +4551,7 @@ impl Workspace {
             );
             self.dirty_items.insert(item_id, s);
             self.update_window_edited(window, cx);
+            self.update_window_title(window, cx);
         }
     }
 
@@ -4694,6 +5008,12 @@ impl Workspace {
         };
 
         let state = self.follower_states.get(&leader_id)?;
+        if state.scrolled_away {
+            return None;
+        }
+        if panel_id.is_some() && !WorkspaceSettings::get_global(cx).follow.panels {
+            return None;
+        }
         let mut transfer_focus = state.center_pane.read(cx).has_focus(window, cx);
         let pane;
         if let Some(panel_id) = panel_id {
@@ -4833,6 +5153,53 @@ impl Workspace {
         self.active_call.as_ref().map(|(call, _)| call)
     }
 
+    /// The call's room, if this workspace's project is shared in it, for
+    /// telling collaborators what's going on in the project.
+    pub(crate) fn shared_project_room(&self, cx: &App) -> Option<Entity<Room>> {
+        if !self.project.read(cx).is_shared() {
+            return None;
+        }
+        self.active_call()?.read(cx).room().cloned()
+    }
+
+    /// Shows collaborators that the local user is typing in the shared project.
+    pub fn report_typing(&self, cx: &mut App) {
+        if let Some(room) = self.shared_project_room(cx) {
+            room.update(cx, |room, cx| room.report_typing(cx));
+        }
+    }
+
+    /// Follows the mouse with the laser pointer while sharing the screen.
+    /// Shared screens are the primary display, so positions are relative to it.
+    fn update_laser_pointer(
+        &mut self,
+        event: &MouseMoveEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(room) = self
+            .active_call()
+            .and_then(|call| call.read(cx).room().cloned())
+        else {
+            return;
+        };
+        if !room.read(cx).is_laser_pointer_enabled() || !room.read(cx).is_screen_sharing() {
+            return;
+        }
+        let Some(display_bounds) = cx.primary_display().map(|display| display.bounds()) else {
+            return;
+        };
+        let position = window.bounds().origin + event.position;
+        let position = display_bounds.contains(&position).then(|| {
+            let offset = position - display_bounds.origin;
+            point(
+                offset.x / display_bounds.size.width,
+                offset.y / display_bounds.size.height,
+            )
+        });
+        room.update(cx, |room, cx| room.set_laser_pointer_position(position, cx));
+    }
+
     fn on_active_call_event(
         &mut self,
         _: &Entity<ActiveCall>,
@@ -4930,6 +5297,9 @@ impl Workspace {
     }
 
     fn serialize_workspace_internal(&self, window: &mut Window, cx: &mut App) -> Task<()> {
+        if let Some(main_window) = self.main_window(cx) {
+            item_windows::serialize_item_windows(main_window, cx);
+        }
         let Some(database_id) = self.database_id() else {
             return Task::ready(());
         };
@@ -4997,7 +5367,7 @@ impl Workspace {
             let left_visible = left_dock.is_open();
             let left_active_panel = left_dock
                 .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
+                .map(|panel| panel.panel_key(cx).to_string());
             let left_dock_zoom = left_dock
                 .active_panel()
                 .map(|panel| panel.is_zoomed(window, cx))
@@ -5007,7 +5377,7 @@ impl Workspace {
             let right_visible = right_dock.is_open();
             let right_active_panel = right_dock
                 .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
+                .map(|panel| panel.panel_key(cx).to_string());
             let right_dock_zoom = right_dock
                 .active_panel()
                 .map(|panel| panel.is_zoomed(window, cx))
@@ -5017,7 +5387,7 @@ impl Workspace {
             let bottom_visible = bottom_dock.is_open();
             let bottom_active_panel = bottom_dock
                 .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
+                .map(|panel| panel.panel_key(cx).to_string());
             let bottom_dock_zoom = bottom_dock
                 .active_panel()
                 .map(|panel| panel.is_zoomed(window, cx))
@@ -5061,6 +5431,7 @@ impl Workspace {
                 display: Default::default(),
                 docks,
                 centered_layout: self.centered_layout,
+                zen_mode: self.zen_mode,
                 session_id: self.session_id.clone(),
                 breakpoints,
                 window_id: Some(window.window_handle().window_id().as_u64()),
@@ -5399,6 +5770,20 @@ impl Workspace {
                 },
             ))
             .on_action(cx.listener(Workspace::toggle_centered_layout))
+            .on_action(cx.listener(Workspace::toggle_zen_mode))
+            .on_action(cx.listener(Workspace::trust_project))
+            .on_action(cx.listener(Workspace::revoke_project_trust))
+            .on_action(cx.listener(Workspace::resize_pane_action))
+            .on_action(
+                cx.listener(|workspace: &mut Workspace, _: &EqualizePanes, window, cx| {
+                    workspace.reset_pane_sizes(window, cx);
+                }),
+            )
+            .on_action(cx.listener(Workspace::move_item_to_new_window))
+            .on_action(cx.listener(Workspace::move_item_to_main_window))
+            .on_action(cx.listener(Workspace::save_layout_action))
+            .on_action(cx.listener(Workspace::apply_layout_action))
+            .on_action(cx.listener(Workspace::delete_layout_action))
             .on_action(cx.listener(Workspace::cancel))
     }
 
@@ -5493,6 +5878,25 @@ impl Workspace {
         cx.notify();
     }
 
+    /// Toggles the distraction-free zen mode, which centers the content, and hides or dims
+    /// the rest of the UI according to the `zen_mode` settings.
+    pub fn toggle_zen_mode(&mut self, _: &ToggleZenMode, _: &mut Window, cx: &mut Context<Self>) {
+        self.zen_mode = !self.zen_mode;
+        if let Some(database_id) = self.database_id() {
+            cx.background_spawn(DB.set_zen_mode(database_id, self.zen_mode))
+                .detach_and_log_err(cx);
+        }
+        cx.emit(Event::ZenModeChanged);
+        cx.notify();
+    }
+
+    /// Whether zen mode hides the tab bar of the given pane.
+    pub(crate) fn zen_mode_hides_tab_bar(&self, pane: &Entity<Pane>, cx: &App) -> bool {
+        self.zen_mode
+            && WorkspaceSettings::get_global(cx).zen_mode.hide_tab_bar
+            && self.center.panes().contains(&pane)
+    }
+
     fn adjust_padding(padding: Option<f32>) -> f32 {
         padding
             .unwrap_or(Self::DEFAULT_PADDING)
@@ -5509,6 +5913,16 @@ impl Workspace {
         if self.zoomed_position == Some(position) {
             return None;
         }
+        if self.zen_mode && WorkspaceSettings::get_global(cx).zen_mode.hide_panels {
+            return None;
+        }
+        if dock
+            .read(cx)
+            .active_panel()
+            .is_some_and(|panel| PresentationMode::hides_panel(panel.panel_key(cx).as_ref(), cx))
+        {
+            return None;
+        }
 
         let leader_border = dock.read(cx).active_panel().and_then(|panel| {
             let pane = panel.pane(cx)?;
@@ -5799,7 +6213,9 @@ impl Render for Workspace {
             }
         }
 
-        let centered_layout = self.centered_layout
+        let zen_mode = self.zen_mode && self.active_item(cx).is_some();
+        let centered_layout = !zen_mode
+            && self.centered_layout
             && self.center.panes().len() == 1
             && self.active_item(cx).is_some();
         let render_padding = |size| {
@@ -5811,7 +6227,16 @@ impl Render for Workspace {
                     .border_color(cx.theme().colors().pane_group_border)
             })
         };
-        let paddings = if centered_layout {
+        let paddings = if zen_mode {
+            let max_width = WorkspaceSettings::get_global(cx).zen_mode.max_width;
+            let width = self.bounds.size.width.0;
+            let padding = if width > max_width {
+                (width - max_width) / 2. / width
+            } else {
+                0.
+            };
+            (render_padding(padding), render_padding(padding))
+        } else if centered_layout {
             let settings = WorkspaceSettings::get_global(cx).centered_layout;
             (
                 render_padding(Self::adjust_padding(settings.left_padding)),
@@ -5820,6 +6245,8 @@ impl Render for Workspace {
         } else {
             (None, None)
         };
+        let dim_ui = self.zen_mode && WorkspaceSettings::get_global(cx).zen_mode.dim_inactive_ui;
+        let dimmed = |view: AnyView| div().w_full().opacity(ZEN_MODE_DIM_OPACITY).child(view);
         let ui_font = theme::setup_ui_font(window, cx);
 
         let theme = cx.theme().clone();
@@ -5833,6 +6260,13 @@ impl Render for Workspace {
         client_side_decorations(
             self.actions(div(), window, cx)
                 .key_context(context)
+                .on_drag_move(cx.listener(Self::handle_tab_drag_move))
+                .on_mouse_up_out(
+                    MouseButton::Left,
+                    cx.listener(|workspace, _, window, cx| {
+                        workspace.handle_tab_dropped_out(window, cx)
+                    }),
+                )
                 .relative()
                 .size_full()
                 .flex()
@@ -5843,12 +6277,16 @@ impl Render for Workspace {
                 .items_start()
                 .text_color(colors.text)
                 .overflow_hidden()
-                .children(self.titlebar_item.clone())
+                .map(|this| match self.titlebar_item.clone() {
+                    Some(titlebar) if dim_ui => this.child(dimmed(titlebar)),
+                    titlebar => this.children(titlebar),
+                })
                 .on_modifiers_changed(move |_, _, cx| {
                     for &id in &notification_entities {
                         cx.notify(id);
                     }
                 })
+                .on_mouse_move(cx.listener(Self::update_laser_pointer))
                 .child(
                     div()
                         .size_full()
@@ -5980,6 +6418,7 @@ impl Render for Workspace {
                                                             .overflow_hidden()
                                                             .child(
                                                                 h_flex()
+                                                                    .accessibility(Role::Main, "Editor Area")
                                                                     .flex_1()
                                                                     .when_some(
                                                                         paddings.0,
@@ -6051,6 +6490,7 @@ impl Render for Workspace {
                                                                     .overflow_hidden()
                                                                     .child(
                                                                         h_flex()
+                                                                            .accessibility(Role::Main, "Editor Area")
                                                                             .flex_1()
                                                                             .when_some(paddings.0, |this, p| this.child(p.border_r_1()))
                                                                             .child(self.center.render(
@@ -6113,6 +6553,7 @@ impl Render for Workspace {
                                                                     .overflow_hidden()
                                                                     .child(
                                                                         h_flex()
+                                                                            .accessibility(Role::Main, "Editor Area")
                                                                             .flex_1()
                                                                             .when_some(paddings.0, |this, p| this.child(p.border_r_1()))
                                                                             .child(self.center.render(
@@ -6159,6 +6600,7 @@ impl Render for Workspace {
                                                     .overflow_hidden()
                                                     .child(
                                                         h_flex()
+                                                            .accessibility(Role::Main, "Editor Area")
                                                             .flex_1()
                                                             .when_some(paddings.0, |this, p| {
                                                                 this.child(p.border_r_1())
@@ -6219,9 +6661,17 @@ impl Render for Workspace {
                                 }))
                                 .children(self.render_notifications(window, cx)),
                         )
-                        .child(self.status_bar.clone())
+                        .map(|this| {
+                            if dim_ui {
+                                this.child(dimmed(self.status_bar.clone().into()))
+                            } else {
+                                this.child(self.status_bar.clone())
+                            }
+                        })
                         .child(self.modal_layer.clone())
-                        .child(self.toast_layer.clone()),
+                        .child(self.toast_layer.clone())
+                        .children(PresentationMode::render_keystrokes(cx))
+                        .children(bell::render_flash(cx)),
                 ),
             window,
             cx,
@@ -7608,6 +8058,7 @@ pub struct WorkspacePosition {
     pub window_bounds: Option<WindowBounds>,
     pub display: Option<Uuid>,
     pub centered_layout: bool,
+    pub zen_mode: bool,
 }
 
 pub fn ssh_workspace_position_from_db(
@@ -7652,11 +8103,16 @@ pub fn ssh_workspace_position_from_db(
             .as_ref()
             .map(|w| w.centered_layout)
             .unwrap_or(false);
+        let zen_mode = serialized_workspace
+            .as_ref()
+            .map(|w| w.zen_mode)
+            .unwrap_or(false);
 
         Ok(WorkspacePosition {
             window_bounds,
             display,
             centered_layout,
+            zen_mode,
         })
     })
 }
@@ -8364,6 +8820,71 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_go_to_history_entry(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(1, "1.txt", cx)])
+        });
+        let pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+        });
+        for state in ["one", "two", "three"] {
+            item.update_in(cx, |item, _, cx| item.set_state(state.to_string(), cx));
+        }
+
+        let timestamps = |mode: NavigationMode, cx: &mut VisualTestContext| {
+            pane.read_with(cx, |pane, cx| {
+                let mut timestamps = Vec::new();
+                pane.nav_history()
+                    .for_each_entry_in_direction(mode, cx, |entry, _| {
+                        timestamps.push(entry.timestamp)
+                    });
+                timestamps
+            })
+        };
+        let backward = timestamps(NavigationMode::GoingBack, cx);
+        assert_eq!(backward.len(), 3);
+
+        // Jumping to the oldest entry skips the ones in between, which become the forward history.
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.go_to_history_entry(
+                    pane.downgrade(),
+                    NavigationMode::GoingBack,
+                    backward[2],
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        item.read_with(cx, |item, _| assert_eq!(item.state, ""));
+        assert_eq!(
+            timestamps(NavigationMode::GoingBack, cx),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            timestamps(NavigationMode::GoingForward, cx),
+            [backward[1], backward[0]]
+        );
+
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.go_forward(pane.downgrade(), window, cx)
+            })
+            .await
+            .unwrap();
+        item.read_with(cx, |item, _| assert_eq!(item.state, "one"));
+    }
+
     #[gpui::test]
     async fn test_toggle_docks_and_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -9768,6 +10289,80 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_resize_and_equalize_panes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let item = cx.new(TestItem::new);
+            workspace.add_item_to_active_pane(Box::new(item), None, true, window, cx);
+            workspace.split_pane(
+                workspace.active_pane().clone(),
+                SplitDirection::Right,
+                window,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        let flexes = |cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, _| match &workspace.center.root {
+                Member::Axis(axis) => axis.flexes.lock().clone(),
+                Member::Pane(_) => panic!("expected a split"),
+            })
+        };
+        assert_eq!(flexes(cx), [1., 1.]);
+
+        // The active pane is the one on the right, which grows to the right and shrinks to the
+        // left.
+        cx.dispatch_action(ResizePane {
+            direction: SplitDirection::Right,
+            amount: 100.,
+        });
+        let grown = flexes(cx);
+        assert!(grown[1] > grown[0], "{grown:?}");
+        cx.dispatch_action(ResizePane {
+            direction: SplitDirection::Left,
+            amount: 200.,
+        });
+        let shrunk = flexes(cx);
+        assert!(shrunk[1] < shrunk[0], "{shrunk:?}");
+
+        cx.dispatch_action(EqualizePanes);
+        assert_eq!(flexes(cx), [1., 1.]);
+
+        // When a dock is focused, it is resized instead.
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel, window, cx);
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+        let dock_size = |cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace
+                    .right_dock()
+                    .read(cx)
+                    .active_panel_size(window, cx)
+                    .unwrap()
+            })
+        };
+        let initial_size = dock_size(cx);
+        cx.dispatch_action(ResizePane {
+            direction: SplitDirection::Right,
+            amount: 50.,
+        });
+        assert_eq!(dock_size(cx), initial_size + px(50.));
+        cx.dispatch_action(ResizePane {
+            direction: SplitDirection::Left,
+            amount: 20.,
+        });
+        assert_eq!(dock_size(cx), initial_size + px(30.));
+        assert_eq!(flexes(cx), [1., 1.]);
+    }
+
     #[gpui::test]
     async fn test_moving_items_create_panes(cx: &mut TestAppContext) {
         init_test(cx);
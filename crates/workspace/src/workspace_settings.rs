@@ -15,20 +15,27 @@ pub struct WorkspaceSettings {
     pub pane_split_direction_horizontal: PaneSplitDirectionHorizontal,
     pub pane_split_direction_vertical: PaneSplitDirectionVertical,
     pub centered_layout: CenteredLayoutSettings,
+    pub zen_mode: ZenModeSettings,
+    pub presentation_mode: PresentationModeSettings,
+    pub bell: BellSettings,
+    pub status_bar: StatusBarSettings,
     pub confirm_quit: bool,
     pub show_call_status_icon: bool,
     pub autosave: AutosaveSetting,
     pub restore_on_startup: RestoreOnStartupBehavior,
     pub restore_on_file_reopen: bool,
+    pub restore_on_file_reopen_max_files: usize,
     pub drop_target_size: f32,
     pub use_system_path_prompts: bool,
     pub use_system_prompts: bool,
     pub command_aliases: HashMap<String, String>,
     pub max_tabs: Option<NonZeroUsize>,
+    pub window_title: Option<String>,
     pub when_closing_with_no_tabs: CloseWindowWhenNoItems,
     pub on_last_window_closed: OnLastWindowClosed,
     pub resize_all_panels_in_dock: Vec<DockPosition>,
     pub close_on_file_delete: bool,
+    pub follow: FollowSettings,
 }
 
 #[derive(Copy, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -66,6 +73,13 @@ pub struct ActivePanelModifiers {
     ///
     /// Default: `1.0`
     pub inactive_opacity: Option<f32>,
+    /// Saturation of the text and syntax colors of editors in inactive panes.
+    /// When set to 1.0, the inactive panes have the same colors as the active one.
+    /// If set to 0, the inactive panes are shown in grayscale.
+    /// Values are clamped to the [0.0, 1.0] range.
+    ///
+    /// Default: `1.0`
+    pub inactive_saturation: Option<f32>,
 }
 
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
@@ -134,6 +148,14 @@ pub struct WorkspaceSettingsContent {
     pub pane_split_direction_vertical: Option<PaneSplitDirectionVertical>,
     /// Centered layout related settings.
     pub centered_layout: Option<CenteredLayoutSettings>,
+    /// Settings for the distraction-free zen mode.
+    pub zen_mode: Option<ZenModeSettingsContent>,
+    /// Settings for presentation mode.
+    pub presentation_mode: Option<PresentationModeSettingsContent>,
+    /// How the bell rings for events, like tasks finishing.
+    pub bell: Option<BellSettingsContent>,
+    /// Which items the status bar shows, and in which order.
+    pub status_bar: Option<StatusBarSettingsContent>,
     /// Whether or not to prompt the user to confirm before closing the application.
     ///
     /// Default: false
@@ -159,6 +181,13 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: true
     pub restore_on_file_reopen: Option<bool>,
+    /// How many files to also remember the state of in the database, so that it's restored
+    /// when the file is opened in another pane, in another workspace, or after restarting.
+    /// The files that were used the longest time ago are forgotten first.
+    /// Set to 0 to only restore the state within the same pane.
+    ///
+    /// Default: 1000
+    pub restore_on_file_reopen_max_files: Option<usize>,
     /// The size of the workspace split drop targets on the outer edges.
     /// Given as a fraction that will be multiplied by the smaller dimension of the workspace.
     ///
@@ -181,7 +210,9 @@ pub struct WorkspaceSettingsContent {
     /// Default: true
     pub use_system_prompts: Option<bool>,
     /// Aliases for the command palette. When you type a key in this map,
-    /// it will be assumed to equal the value.
+    /// it will be assumed to equal the value. Aliases whose value is an action
+    /// name, such as `workspace::Save`, or a command as it is shown in the
+    /// palette, such as `workspace: save`, are also listed as commands.
     ///
     /// Default: true
     pub command_aliases: Option<HashMap<String, String>>,
@@ -190,6 +221,12 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: none
     pub max_tabs: Option<NonZeroUsize>,
+    /// The format of the window title, with `${project}`, `${path}`, `${filename}`,
+    /// `${dirty}`, `${branch}`, and `${collab}` replaced by their values. `${separator}`
+    /// becomes " — " between parts that aren't empty. Set to `None` for the default title.
+    ///
+    /// Default: none
+    pub window_title: Option<String>,
     /// What to do when the last window is closed
     ///
     /// Default: auto (nothing on macOS, "app quit" otherwise)
@@ -202,6 +239,27 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: false
     pub close_on_file_delete: Option<bool>,
+    /// Settings related to following collaborators and the agent.
+    pub follow: Option<FollowSettingsContent>,
+}
+
+#[derive(Deserialize)]
+pub struct FollowSettings {
+    pub smooth_scroll: bool,
+    pub panels: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FollowSettingsContent {
+    /// Whether the viewport scrolls smoothly to where the leader scrolled, rather than jumping.
+    ///
+    /// Default: true
+    pub smooth_scroll: Option<bool>,
+    /// Whether to follow the leader into the panels they focus, like the agent panel.
+    /// When disabled, following stays in the pane it started in.
+    ///
+    /// Default: true
+    pub panels: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -269,6 +327,173 @@ pub struct CenteredLayoutSettings {
     pub right_padding: Option<f32>,
 }
 
+#[derive(Deserialize)]
+pub struct ZenModeSettings {
+    pub max_width: f32,
+    pub hide_panels: bool,
+    pub hide_tab_bar: bool,
+    pub hide_gutter: bool,
+    pub dim_inactive_ui: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ZenModeSettingsContent {
+    /// The maximum width of the centered content, in pixels.
+    ///
+    /// Default: 900
+    pub max_width: Option<f32>,
+    /// Whether to hide the docks and their panels.
+    ///
+    /// Default: true
+    pub hide_panels: Option<bool>,
+    /// Whether to hide the tab bar of each pane.
+    ///
+    /// Default: true
+    pub hide_tab_bar: Option<bool>,
+    /// Whether to hide the editor gutter, with its line numbers, diff hunks, and other
+    /// decorations.
+    ///
+    /// Default: true
+    pub hide_gutter: Option<bool>,
+    /// Whether to dim the title bar and the status bar.
+    ///
+    /// Default: true
+    pub dim_inactive_ui: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct PresentationModeSettings {
+    pub font_scale: f32,
+    pub show_keystrokes: bool,
+    pub hidden_panels: Vec<String>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PresentationModeSettingsContent {
+    /// The factor to scale the UI, buffer, and agent font sizes by.
+    ///
+    /// Default: 1.5
+    pub font_scale: Option<f32>,
+    /// Whether to show the keystrokes being pressed in an overlay.
+    ///
+    /// Default: true
+    pub show_keystrokes: Option<bool>,
+    /// The panels to hide, by their persistent names.
+    ///
+    /// Default: ["TerminalPanel", "AgentPanel", "ChatPanel", "NotificationPanel"]
+    pub hidden_panels: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct BellSettings {
+    pub task_succeeded: BellStyle,
+    pub task_failed: BellStyle,
+    pub terminal: BellStyle,
+    pub agent_response: BellStyle,
+    pub agent_response_min_seconds: u64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BellSettingsContent {
+    /// How the bell rings when a task exits successfully.
+    ///
+    /// Default: none
+    pub task_succeeded: Option<BellStyle>,
+    /// How the bell rings when a task exits with an error, or when a task that keeps running rings
+    /// the terminal bell.
+    ///
+    /// Default: none
+    pub task_failed: Option<BellStyle>,
+    /// How the bell rings when a program in a terminal rings the terminal bell.
+    ///
+    /// Default: none
+    pub terminal: Option<BellStyle>,
+    /// How the bell rings when the agent finishes a long response.
+    ///
+    /// Default: none
+    pub agent_response: Option<BellStyle>,
+    /// How many seconds a response of the agent must take for the bell to ring when it
+    /// finishes.
+    ///
+    /// Default: 30
+    pub agent_response_min_seconds: Option<u64>,
+}
+
+/// How the bell rings.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BellStyle {
+    /// Don't ring the bell.
+    #[default]
+    None,
+    /// Play a sound.
+    Sound,
+    /// Flash the screen.
+    Flash,
+    /// Play a sound and flash the screen.
+    SoundAndFlash,
+}
+
+#[derive(Deserialize)]
+pub struct StatusBarSettings {
+    pub hidden_items: Vec<String>,
+    pub left_items: Vec<String>,
+    pub right_items: Vec<String>,
+    pub custom_items: Vec<CustomStatusItem>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct StatusBarSettingsContent {
+    /// The ids of the items to hide.
+    ///
+    /// Default: []
+    pub hidden_items: Option<Vec<String>>,
+    /// The ids of the items to show on the left, in order. Items that aren't listed here or
+    /// in `right_items` stay on their usual side, after the listed ones.
+    ///
+    /// Default: []
+    pub left_items: Option<Vec<String>>,
+    /// The ids of the items to show on the right, in order.
+    ///
+    /// Default: []
+    pub right_items: Option<Vec<String>>,
+    /// Items that show the first line of a command's output, refreshed on an interval.
+    ///
+    /// Default: []
+    pub custom_items: Option<Vec<CustomStatusItem>>,
+}
+
+/// A status bar item that shows the output of a command.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CustomStatusItem {
+    /// The id to hide or move the item with.
+    pub id: String,
+    /// The command to run, in the project's first folder when it is local.
+    pub command: String,
+    /// The arguments to pass to the command.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// How many seconds to wait after the command finished to run it again.
+    #[serde(default = "default_custom_status_item_interval")]
+    pub interval_seconds: u64,
+    /// Which side of the status bar the item goes on, unless `left_items` or `right_items` list it.
+    #[serde(default)]
+    pub position: StatusBarSide,
+}
+
+fn default_custom_status_item_interval() -> u64 {
+    10
+}
+
+/// A side of the status bar.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusBarSide {
+    Left,
+    #[default]
+    Right,
+}
+
 impl Settings for WorkspaceSettings {
     const KEY: Option<&'static str> = None;
 
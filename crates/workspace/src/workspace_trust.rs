@@ -0,0 +1,122 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use gpui::{Context, PromptLevel, Window};
+use project::TrustedFolders;
+use util::{ResultExt as _, paths::PathExt as _};
+
+use crate::{RevokeProjectTrust, Toast, TrustProject, Workspace, notifications::NotificationId};
+
+struct UntrustedProject;
+
+impl Workspace {
+    /// The paths that the project's untrusted folders are trusted by, along with the folders.
+    fn untrusted_folders(&self, cx: &Context<Self>) -> Vec<(PathBuf, Arc<Path>)> {
+        let project = self.project.read(cx);
+        project
+            .untrusted_worktrees(cx)
+            .filter_map(|worktree| {
+                let worktree = worktree.read(cx);
+                let trust_path = project.worktree_trust_path(worktree, cx)?;
+                Some((trust_path, worktree.abs_path()))
+            })
+            .collect()
+    }
+
+    /// Asks whether to trust the project's folders that aren't trusted yet, once per folder.
+    pub(crate) fn prompt_to_trust_folders(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let folders = self
+            .untrusted_folders(cx)
+            .into_iter()
+            .filter(|(trust_path, _)| !self.folders_prompted_for_trust.contains(trust_path))
+            .collect::<Vec<_>>();
+        if folders.is_empty() || self.trust_prompt.is_some() {
+            return;
+        }
+        self.folders_prompted_for_trust
+            .extend(folders.iter().map(|(trust_path, _)| trust_path.clone()));
+
+        let names = folders
+            .iter()
+            .map(|(_, folder)| folder.compact().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let detail = "Until you trust them, their tasks and debuggers can't be run, their \
+            project settings and language server binary overrides are ignored, and their own \
+            Prettier, direnv, and mise configurations aren't run.";
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &format!("Do you trust the authors of {names}?"),
+            Some(detail),
+            &["Trust", "Don't Trust"],
+            cx,
+        );
+        self.trust_prompt = Some(cx.spawn_in(window, async move |this, cx| {
+            let answer = answer.await.ok();
+            this.update(cx, |this, cx| {
+                this.trust_prompt = None;
+                if answer == Some(0) {
+                    TrustedFolders::trust(
+                        folders.into_iter().map(|(trust_path, _)| trust_path),
+                        cx,
+                    );
+                } else {
+                    this.show_untrusted_project_toast(cx);
+                }
+            })
+            .log_err();
+        }));
+    }
+
+    /// Returns whether all of the project's folders are trusted, showing a toast when they aren't.
+    pub fn ensure_trusted(&mut self, cx: &mut Context<Self>) -> bool {
+        if self.untrusted_folders(cx).is_empty() {
+            return true;
+        }
+        self.show_untrusted_project_toast(cx);
+        false
+    }
+
+    fn show_untrusted_project_toast(&mut self, cx: &mut Context<Self>) {
+        self.show_toast(
+            Toast::new(
+                NotificationId::unique::<UntrustedProject>(),
+                "This project isn't trusted, so its tasks can't be run and its settings are ignored",
+            )
+            .on_click("Trust", |window, cx| {
+                window.dispatch_action(Box::new(TrustProject), cx)
+            }),
+            cx,
+        );
+    }
+
+    pub(crate) fn trust_project(
+        &mut self,
+        _: &TrustProject,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let folders = self.untrusted_folders(cx);
+        if folders.is_empty() {
+            return;
+        }
+        TrustedFolders::trust(folders.into_iter().map(|(trust_path, _)| trust_path), cx);
+        self.dismiss_toast(&NotificationId::unique::<UntrustedProject>(), cx);
+    }
+
+    pub(crate) fn revoke_project_trust(
+        &mut self,
+        _: &RevokeProjectTrust,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.read(cx);
+        let folders = project
+            .visible_worktrees(cx)
+            .filter_map(|worktree| project.worktree_trust_path(worktree.read(cx), cx))
+            .collect::<Vec<_>>();
+        TrustedFolders::revoke(folders, cx);
+    }
+}
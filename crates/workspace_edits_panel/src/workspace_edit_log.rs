@@ -0,0 +1,257 @@
+use std::{collections::VecDeque, ops::Range, time::SystemTime};
+
+use gpui::{App, Entity, SharedString, WeakEntity};
+use language::{Buffer, Point, TransactionId};
+
+/// How many workspace edits are remembered.
+const MAX_EDITS: usize = 50;
+
+/// An edit that was applied to several places of the workspace at once, like a rename.
+pub struct WorkspaceEdit {
+    pub title: SharedString,
+    pub applied_at: SystemTime,
+    /// Sorted by path.
+    pub files: Vec<FileEdit>,
+}
+
+impl WorkspaceEdit {
+    pub fn is_reverted(&self) -> bool {
+        self.files.iter().all(|file| file.reverted)
+    }
+}
+
+/// The part of a workspace edit that changed one buffer.
+pub struct FileEdit {
+    pub buffer: WeakEntity<Buffer>,
+    pub path: SharedString,
+    pub transaction_id: TransactionId,
+    pub edit_count: usize,
+    pub reverted: bool,
+}
+
+impl FileEdit {
+    /// The ranges changed by the edit, or nothing once the buffer has been closed, as its undo
+    /// history went with it.
+    pub fn edited_ranges(&self, cx: &App) -> Vec<Range<Point>> {
+        self.buffer.upgrade().map_or(Vec::new(), |buffer| {
+            buffer
+                .read(cx)
+                .edited_ranges_for_transaction_id(self.transaction_id)
+                .collect()
+        })
+    }
+}
+
+/// The workspace edits applied in a project, newest first.
+#[derive(Default)]
+pub struct WorkspaceEditLog {
+    edits: VecDeque<WorkspaceEdit>,
+}
+
+impl WorkspaceEditLog {
+    pub fn edits(&self) -> &VecDeque<WorkspaceEdit> {
+        &self.edits
+    }
+
+    pub fn record(
+        &mut self,
+        title: SharedString,
+        transactions: &[(Entity<Buffer>, TransactionId)],
+        cx: &App,
+    ) {
+        let mut files = transactions
+            .iter()
+            .map(|(buffer, transaction_id)| {
+                let buffer_ref = buffer.read(cx);
+                FileEdit {
+                    buffer: buffer.downgrade(),
+                    path: buffer_ref
+                        .file()
+                        .map(|file| file.path().to_string_lossy().to_string())
+                        .unwrap_or_else(|| "untitled".to_string())
+                        .into(),
+                    transaction_id: *transaction_id,
+                    edit_count: buffer_ref
+                        .edited_ranges_for_transaction_id::<usize>(*transaction_id)
+                        .count(),
+                    reverted: false,
+                }
+            })
+            .collect::<Vec<_>>();
+        if files.is_empty() {
+            return;
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        // The edits a language server applies while running a command are recorded on their own
+        // first, and then again as part of the code action that ran the command.
+        self.edits.retain(|edit| {
+            !edit.files.iter().all(|file| {
+                files.iter().any(|new_file| {
+                    new_file.buffer == file.buffer && new_file.transaction_id == file.transaction_id
+                })
+            })
+        });
+        if self.edits.len() >= MAX_EDITS {
+            self.edits.pop_back();
+        }
+        self.edits.push_front(WorkspaceEdit {
+            title,
+            applied_at: SystemTime::now(),
+            files,
+        });
+    }
+
+    /// Undoes the changes a workspace edit made to one of its files, leaving the ones made
+    /// since then in place.
+    pub fn revert_file(&mut self, edit_ix: usize, file_ix: usize, cx: &mut App) {
+        let Some(file) = self
+            .edits
+            .get_mut(edit_ix)
+            .and_then(|edit| edit.files.get_mut(file_ix))
+        else {
+            return;
+        };
+        if file.reverted {
+            return;
+        }
+        let Some(buffer) = file.buffer.upgrade() else {
+            return;
+        };
+        file.reverted = buffer.update(cx, |buffer, cx| {
+            buffer.undo_transaction(file.transaction_id, cx)
+        });
+    }
+
+    pub fn revert_edit(&mut self, edit_ix: usize, cx: &mut App) {
+        let file_count = self.edits.get(edit_ix).map_or(0, |edit| edit.files.len());
+        for file_ix in 0..file_count {
+            self.revert_file(edit_ix, file_ix, cx);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.edits.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+
+    fn edit_buffer(
+        buffer: &Entity<Buffer>,
+        edits: &[(Range<usize>, &str)],
+        cx: &mut TestAppContext,
+    ) -> TransactionId {
+        buffer.update(cx, |buffer, cx| {
+            buffer.start_transaction();
+            buffer.edit(
+                edits.iter().map(|(range, text)| (range.clone(), *text)),
+                None,
+                cx,
+            );
+            let transaction_id = buffer.end_transaction(cx).unwrap();
+            // Like the edits of language servers, so that later edits aren't grouped with it.
+            buffer.finalize_last_transaction();
+            transaction_id
+        })
+    }
+
+    #[gpui::test]
+    fn test_revert_files_of_workspace_edit(cx: &mut TestAppContext) {
+        let buffer_a = cx.new(|cx| Buffer::local("let foo = foo + 1;", cx));
+        let buffer_b = cx.new(|cx| Buffer::local("foo();", cx));
+        let transaction_a = edit_buffer(&buffer_a, &[(4..7, "bar"), (10..13, "bar")], cx);
+        let transaction_b = edit_buffer(&buffer_b, &[(0..3, "bar")], cx);
+
+        let mut log = WorkspaceEditLog::default();
+        cx.update(|cx| {
+            log.record(
+                "Rename to bar".into(),
+                &[
+                    (buffer_a.clone(), transaction_a),
+                    (buffer_b.clone(), transaction_b),
+                ],
+                cx,
+            )
+        });
+        assert_eq!(log.edits().len(), 1);
+        let edit_counts = log.edits()[0]
+            .files
+            .iter()
+            .map(|file| file.edit_count)
+            .collect::<Vec<_>>();
+        assert_eq!(edit_counts, [2, 1]);
+
+        // An edit made after the workspace edit is kept when reverting it.
+        buffer_a.update(cx, |buffer, cx| {
+            buffer.edit([(18..18, " // done")], None, cx)
+        });
+        let file_ix = cx.update(|cx| {
+            log.edits()[0]
+                .files
+                .iter()
+                .position(|file| file.buffer == buffer_a.downgrade())
+                .unwrap()
+        });
+        cx.update(|cx| log.revert_file(0, file_ix, cx));
+        buffer_a.read_with(cx, |buffer, _| {
+            assert_eq!(buffer.text(), "let foo = foo + 1; // done");
+        });
+        buffer_b.read_with(cx, |buffer, _| assert_eq!(buffer.text(), "bar();"));
+        assert!(!log.edits()[0].is_reverted());
+
+        cx.update(|cx| log.revert_edit(0, cx));
+        buffer_b.read_with(cx, |buffer, _| assert_eq!(buffer.text(), "foo();"));
+        assert!(log.edits()[0].is_reverted());
+    }
+
+    #[gpui::test]
+    fn test_revert_undone_workspace_edit(cx: &mut TestAppContext) {
+        let buffer = cx.new(|cx| Buffer::local("foo();", cx));
+        let transaction = edit_buffer(&buffer, &[(0..3, "bar")], cx);
+
+        let mut log = WorkspaceEditLog::default();
+        cx.update(|cx| log.record("Rename to bar".into(), &[(buffer.clone(), transaction)], cx));
+
+        // Once the edit has been undone in the editor, reverting it doesn't change anything and
+        // leaves it listed as applied.
+        buffer.update(cx, |buffer, cx| buffer.undo(cx));
+        cx.update(|cx| log.revert_file(0, 0, cx));
+        buffer.read_with(cx, |buffer, _| assert_eq!(buffer.text(), "foo();"));
+        assert!(!log.edits()[0].is_reverted());
+    }
+
+    #[gpui::test]
+    fn test_workspace_edit_replaces_the_edits_it_contains(cx: &mut TestAppContext) {
+        let buffer_a = cx.new(|cx| Buffer::local("one", cx));
+        let buffer_b = cx.new(|cx| Buffer::local("two", cx));
+        let transaction_a = edit_buffer(&buffer_a, &[(0..0, "// ")], cx);
+        let transaction_b = edit_buffer(&buffer_b, &[(0..0, "// ")], cx);
+
+        let mut log = WorkspaceEditLog::default();
+        cx.update(|cx| {
+            log.record(
+                "Edit from the server".into(),
+                &[(buffer_b.clone(), transaction_b)],
+                cx,
+            );
+            log.record(
+                "Comment out".into(),
+                &[
+                    (buffer_a.clone(), transaction_a),
+                    (buffer_b.clone(), transaction_b),
+                ],
+                cx,
+            );
+        });
+        let titles = log
+            .edits()
+            .iter()
+            .map(|edit| edit.title.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, ["Comment out"]);
+    }
+}
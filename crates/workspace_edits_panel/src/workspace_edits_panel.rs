@@ -0,0 +1,534 @@
+mod workspace_edit_log;
+mod workspace_edits_panel_settings;
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{DEFAULT_MULTIBUFFER_CONTEXT, Editor, MultiBuffer, PathKey, scroll::Autoscroll};
+use gpui::{
+    Action, AnyElement, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, ScrollStrategy, Subscription, Task, UniformListScrollHandle, WeakEntity, Window,
+    actions, uniform_list,
+};
+use language::Capability;
+use project::{Fs, Project};
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use theme::ActiveTheme as _;
+use time::OffsetDateTime;
+use time_format::{TimestampFormat, format_local_timestamp};
+use ui::{IconButton, ListItem, ListItemSpacing, Tab, Tooltip, prelude::*};
+use util::{ResultExt as _, TryFutureExt as _};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+pub use workspace_edit_log::{FileEdit, WorkspaceEdit, WorkspaceEditLog};
+pub use workspace_edits_panel_settings::WorkspaceEditsPanelSettings;
+
+actions!(
+    workspace_edits_panel,
+    [ToggleFocus, RevertSelected, ClearHistory]
+);
+
+const WORKSPACE_EDITS_PANEL_KEY: &str = "WorkspaceEditsPanel";
+
+pub fn init(cx: &mut App) {
+    WorkspaceEditsPanelSettings::register(cx);
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<WorkspaceEditsPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Lists the edits that were applied across the workspace at once, like renames and code
+/// actions, so that they can be inspected and rolled back, as a whole or file by file.
+pub struct WorkspaceEditsPanel {
+    log: WorkspaceEditLog,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    width: Option<Pixels>,
+    entries: Vec<ListEntry>,
+    selected_index: Option<usize>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedWorkspaceEditsPanel {
+    width: Option<Pixels>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ListEntry {
+    Edit { edit_ix: usize },
+    File { edit_ix: usize, file_ix: usize },
+}
+
+impl WorkspaceEditsPanel {
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let weak_workspace = workspace.weak_handle();
+        cx.new(|cx| {
+            let subscriptions = vec![cx.subscribe(&project, |this, _, event, cx| {
+                if let project::Event::WorkspaceEditApplied {
+                    title,
+                    transactions,
+                } = event
+                {
+                    this.log.record(title.clone(), transactions, cx);
+                    this.update_entries(cx);
+                }
+            })];
+            Self {
+                log: WorkspaceEditLog::default(),
+                project,
+                workspace: weak_workspace,
+                fs,
+                width: None,
+                entries: Vec::new(),
+                selected_index: None,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = if let Some(panel) = cx
+                .background_spawn(
+                    async move { KEY_VALUE_STORE.read_kvp(WORKSPACE_EDITS_PANEL_KEY) },
+                )
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedWorkspaceEditsPanel>(
+                    &panel,
+                )?)
+            } else {
+                None
+            };
+
+            workspace.update(cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, _| {
+                        panel.width = serialized_panel.width.map(|w| w.round());
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        WORKSPACE_EDITS_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedWorkspaceEditsPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        self.entries.clear();
+        for (edit_ix, edit) in self.log.edits().iter().enumerate() {
+            self.entries.push(ListEntry::Edit { edit_ix });
+            self.entries
+                .extend((0..edit.files.len()).map(|file_ix| ListEntry::File { edit_ix, file_ix }));
+        }
+        // New edits are listed first, so the selection can't be kept on the same entry.
+        self.selected_index = None;
+        cx.notify();
+    }
+
+    fn clear_history(&mut self, _: &ClearHistory, _: &mut Window, cx: &mut Context<Self>) {
+        self.log.clear();
+        self.update_entries(cx);
+    }
+
+    fn revert_selected(&mut self, _: &RevertSelected, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry) = self.selected_index.and_then(|ix| self.entries.get(ix)) {
+            self.revert(*entry, cx);
+        }
+    }
+
+    fn revert(&mut self, entry: ListEntry, cx: &mut Context<Self>) {
+        match entry {
+            ListEntry::Edit { edit_ix } => self.log.revert_edit(edit_ix, cx),
+            ListEntry::File { edit_ix, file_ix } => self.log.revert_file(edit_ix, file_ix, cx),
+        }
+        cx.notify();
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next = self.selected_index.map_or(0, |ix| ix + 1);
+        if next < self.entries.len() {
+            self.select(next, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        if previous < self.entries.len() {
+            self.select(previous, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry) = self.selected_index.and_then(|ix| self.entries.get(ix)) {
+            self.open(*entry, window, cx);
+        }
+    }
+
+    fn open(&mut self, entry: ListEntry, window: &mut Window, cx: &mut Context<Self>) {
+        match entry {
+            ListEntry::Edit { edit_ix } => self.open_edit(edit_ix, window, cx),
+            ListEntry::File { edit_ix, file_ix } => self.open_file(edit_ix, file_ix, window, cx),
+        }
+    }
+
+    /// Opens the changes of every file of a workspace edit in a multibuffer.
+    fn open_edit(&mut self, edit_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(edit) = self.log.edits().get(edit_ix) else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let mut ranges_to_highlight = Vec::new();
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer =
+                MultiBuffer::new(Capability::ReadWrite).with_title(edit.title.to_string());
+            for file in &edit.files {
+                let Some(buffer) = file.buffer.upgrade() else {
+                    continue;
+                };
+                let edited_ranges = file.edited_ranges(cx);
+                let (ranges, _) = multibuffer.set_excerpts_for_path(
+                    PathKey::for_buffer(&buffer, cx),
+                    buffer,
+                    edited_ranges,
+                    DEFAULT_MULTIBUFFER_CONTEXT,
+                    cx,
+                );
+                ranges_to_highlight.extend(ranges);
+            }
+            multibuffer
+        });
+        if ranges_to_highlight.is_empty() {
+            return;
+        }
+        let project = self.project.clone();
+        workspace.update(cx, |workspace, cx| {
+            let editor =
+                cx.new(|cx| Editor::for_multibuffer(multibuffer, Some(project), window, cx));
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+            editor.update(cx, |editor, cx| {
+                editor.highlight_background::<Self>(
+                    &ranges_to_highlight,
+                    |theme| theme.editor_highlighted_line_background,
+                    cx,
+                );
+            });
+        });
+    }
+
+    /// Opens the file at the first place a workspace edit changed.
+    fn open_file(
+        &mut self,
+        edit_ix: usize,
+        file_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(file) = self
+            .log
+            .edits()
+            .get(edit_ix)
+            .and_then(|edit| edit.files.get(file_ix))
+        else {
+            return;
+        };
+        let Some(buffer) = file.buffer.upgrade() else {
+            return;
+        };
+        let first_edit = file.edited_ranges(cx).first().map(|range| range.start);
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let editor = workspace.update(cx, |workspace, cx| {
+            workspace.open_project_item::<Editor>(
+                workspace.active_pane().clone(),
+                buffer,
+                true,
+                true,
+                window,
+                cx,
+            )
+        });
+        if let Some(point) = first_edit {
+            editor.update(cx, |editor, cx| {
+                editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                    s.select_ranges([point..point])
+                });
+            });
+        }
+    }
+
+    fn render_entries(
+        &mut self,
+        range: Range<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| Some(self.render_entry(ix, *self.entries.get(ix)?, cx)))
+            .collect()
+    }
+
+    fn render_entry(&self, ix: usize, entry: ListEntry, cx: &Context<Self>) -> AnyElement {
+        let (label, detail, reverted, can_revert) = match entry {
+            ListEntry::Edit { edit_ix } => {
+                let edit = &self.log.edits()[edit_ix];
+                let applied_at = format_local_timestamp(
+                    OffsetDateTime::from(edit.applied_at),
+                    OffsetDateTime::now_utc(),
+                    TimestampFormat::Relative,
+                );
+                let file_count = edit.files.len();
+                let detail = format!(
+                    "{file_count} {}, {applied_at}",
+                    if file_count == 1 { "file" } else { "files" }
+                );
+                let can_revert = edit
+                    .files
+                    .iter()
+                    .any(|file| !file.reverted && file.buffer.upgrade().is_some());
+                (
+                    Label::new(edit.title.clone()).size(LabelSize::Small),
+                    detail,
+                    edit.is_reverted(),
+                    can_revert,
+                )
+            }
+            ListEntry::File { edit_ix, file_ix } => {
+                let file = &self.log.edits()[edit_ix].files[file_ix];
+                let detail = format!(
+                    "{} {}",
+                    file.edit_count,
+                    if file.edit_count == 1 {
+                        "change"
+                    } else {
+                        "changes"
+                    }
+                );
+                (
+                    Label::new(file.path.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    detail,
+                    file.reverted,
+                    !file.reverted && file.buffer.upgrade().is_some(),
+                )
+            }
+        };
+        let is_file = matches!(entry, ListEntry::File { .. });
+
+        ListItem::new(ix)
+            .spacing(ListItemSpacing::Sparse)
+            .inset(true)
+            .indent_level(if is_file { 1 } else { 0 })
+            .toggle_state(self.selected_index == Some(ix))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        label
+                            .when(reverted, |label| label.strikethrough())
+                            .truncate(),
+                    )
+                    .child(
+                        Label::new(if reverted {
+                            "reverted".to_string()
+                        } else {
+                            detail
+                        })
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                    ),
+            )
+            .when(can_revert, |this| {
+                this.end_slot(
+                    IconButton::new(("revert", ix), IconName::Undo)
+                        .icon_size(IconSize::Small)
+                        .tooltip(move |window, cx| {
+                            Tooltip::for_action(
+                                if is_file { "Revert File" } else { "Revert All" },
+                                &RevertSelected,
+                                window,
+                                cx,
+                            )
+                        })
+                        .on_click(cx.listener(move |this, _, _, cx| this.revert(entry, cx))),
+                )
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select(ix, cx);
+                this.open(entry, window, cx);
+            }))
+            .into_any_element()
+    }
+}
+
+impl Render for WorkspaceEditsPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("WorkspaceEditsPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::revert_selected))
+            .on_action(cx.listener(Self::clear_history))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("Workspace Edits"))
+                    .child(
+                        IconButton::new("clear-history", IconName::ListX)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.entries.is_empty())
+                            .tooltip(move |window, cx| {
+                                Tooltip::for_action("Clear History", &ClearHistory, window, cx)
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.clear_history(&ClearHistory, window, cx)
+                            })),
+                    ),
+            )
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("Renames and code actions that change several places at once are listed here.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "workspace-edit-entries",
+                            self.entries.len(),
+                            Self::render_entries,
+                        )
+                        .p_1()
+                        .track_scroll(self.scroll_handle.clone())
+                        .flex_grow(),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for WorkspaceEditsPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for WorkspaceEditsPanel {}
+
+impl Panel for WorkspaceEditsPanel {
+    fn persistent_name() -> &'static str {
+        "WorkspaceEditsPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        WorkspaceEditsPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<WorkspaceEditsPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| WorkspaceEditsPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        WorkspaceEditsPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::FileDiff)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Workspace Edits Panel".into())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        11
+    }
+}
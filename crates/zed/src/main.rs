@@ -38,8 +38,8 @@ use std::{
     sync::Arc,
 };
 use theme::{
-    ActiveTheme, IconThemeNotFoundError, SystemAppearance, ThemeNotFoundError, ThemeRegistry,
-    ThemeSettings,
+    ActiveTheme, AppearanceController, IconThemeNotFoundError, SystemAppearance,
+    ThemeNotFoundError, ThemeRegistry, ThemeSettings,
 };
 use util::{ConnectionResult, ResultExt, TryFutureExt, maybe};
 use uuid::Uuid;
@@ -365,6 +365,7 @@ Error: Running Zed as root or via sudo is unsupported.
         }
         settings::init(cx);
         zlog_settings::init(cx);
+        redaction::init(cx);
         handle_settings_file_changes(
             user_settings_file_rx,
             global_settings_file_rx,
@@ -503,6 +504,7 @@ Error: Running Zed as root or via sudo is unsupported.
 
         SystemAppearance::init(cx);
         theme::init(theme::LoadThemes::All(Box::new(Assets)), cx);
+        AppearanceController::init(cx);
         theme_extension::init(
             extension_host_proxy.clone(),
             ThemeRegistry::global(cx),
@@ -542,6 +544,7 @@ Error: Running Zed as root or via sudo is unsupported.
             false,
             cx,
         );
+        semantic_index::init(app_state.client.http_client(), cx);
         assistant_tools::init(app_state.client.http_client(), cx);
         repl::init(app_state.fs.clone(), cx);
         extension_host::init(
@@ -552,12 +555,14 @@ Error: Running Zed as root or via sudo is unsupported.
             cx,
         );
         recent_projects::init(cx);
+        sftp::init(cx);
 
         load_embedded_fonts(cx);
 
         app_state.languages.set_theme(cx.theme().clone());
         editor::init(cx);
         image_viewer::init(cx);
+        archive_viewer::init(cx);
         repl::notebook::init(cx);
         diagnostics::init(cx);
 
@@ -566,21 +571,37 @@ Error: Running Zed as root or via sudo is unsupported.
         ui_prompt::init(cx);
 
         go_to_line::init(cx);
+        align_lines::init(cx);
+        character_picker::init(cx);
+        command_filter::init(cx);
+        data_tools::init(cx);
         file_finder::init(cx);
         tab_switcher::init(cx);
+        navigation_history::init(cx);
         outline::init(cx);
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        bookmarks::init(cx);
+        file_set::init(cx);
+        comments::init(cx);
+        todo_panel::init(cx);
+        workspace_edits_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
+        clipboard_history::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
         search::init(cx);
         vim::init(cx);
         terminal_view::init(cx);
         journal::init(app_state.clone(), cx);
+        keyboard_macros::init(cx);
+        dictation::init(app_state.client.http_client(), cx);
         language_selector::init(cx);
         toolchain_selector::init(cx);
+        layout_selector::init(cx);
+        new_project::init(cx);
+        project::TrustedFolders::init(cx);
         theme_selector::init(cx);
         language_tools::init(cx);
         call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -40,7 +40,7 @@ use paths::{
     local_debug_file_relative_path, local_settings_file_relative_path,
     local_tasks_file_relative_path,
 };
-use project::{DirectoryLister, ProjectItem};
+use project::{DirectoryLister, ProjectItem, project_settings::ProjectSettings};
 use project_panel::ProjectPanel;
 use prompt_store::PromptBuilder;
 use quick_action_bar::QuickActionBar;
@@ -49,8 +49,8 @@ use release_channel::{AppCommitSha, ReleaseChannel};
 use rope::Rope;
 use search::project_search::ProjectSearchBar;
 use settings::{
-    DEFAULT_KEYMAP_PATH, InvalidSettingsError, KeymapFile, KeymapFileLoadResult, Settings,
-    SettingsStore, VIM_KEYMAP_PATH, initial_local_debug_tasks_content,
+    DEFAULT_KEYMAP_PATH, InvalidSettingsError, KeybindSource, KeymapFile, KeymapFileLoadResult,
+    Settings, SettingsLocation, SettingsStore, VIM_KEYMAP_PATH, initial_local_debug_tasks_content,
     initial_project_settings_content, initial_tasks_content, update_settings_file,
 };
 use std::path::PathBuf;
@@ -62,7 +62,7 @@ use ui::{PopoverMenuHandle, prelude::*};
 use util::markdown::MarkdownString;
 use util::{ResultExt, asset_str};
 use uuid::Uuid;
-use vim_mode_setting::VimModeSetting;
+use vim_mode_setting::{HelixModeSetting, VimModeSetting};
 use welcome::{BaseKeymap, DOCS_URL, MultibufferHint};
 use workspace::notifications::{NotificationId, dismiss_app_notification, show_app_notification};
 use workspace::{
@@ -84,6 +84,7 @@ actions!(
         HideOthers,
         Minimize,
         OpenDefaultSettings,
+        OpenProjectEnvironment,
         OpenProjectSettings,
         OpenProjectTasks,
         OpenProjectDebugTasks,
@@ -327,6 +328,9 @@ pub fn initialize_workspace(
         let active_toolchain_language =
             cx.new(|cx| toolchain_selector::ActiveToolchain::new(workspace, window, cx));
         let vim_mode_indicator = cx.new(|cx| vim::ModeIndicator::new(window, cx));
+        let macro_recording_indicator =
+            cx.new(keyboard_macros::recording_indicator::RecordingIndicator::new);
+        let dictation_indicator = cx.new(dictation::dictation_indicator::DictationIndicator::new);
         let image_info = cx.new(|_cx| ImageInfo::new(workspace));
         let cursor_position =
             cx.new(|_| go_to_line::cursor_position::CursorPosition::new(workspace));
@@ -337,6 +341,8 @@ pub fn initialize_workspace(
             status_bar.add_right_item(inline_completion_button, window, cx);
             status_bar.add_right_item(active_buffer_language, window, cx);
             status_bar.add_right_item(active_toolchain_language, window, cx);
+            status_bar.add_right_item(macro_recording_indicator, window, cx);
+            status_bar.add_right_item(dictation_indicator, window, cx);
             status_bar.add_right_item(vim_mode_indicator, window, cx);
             status_bar.add_right_item(cursor_position, window, cx);
             status_bar.add_right_item(image_info, window, cx);
@@ -480,6 +486,12 @@ fn initialize_panels(
             workspace_handle.clone(),
             cx.clone(),
         );
+        let bookmarks_panel = bookmarks::BookmarksPanel::load(workspace_handle.clone(), cx.clone());
+        let file_set_panel = file_set::FileSetPanel::load(workspace_handle.clone(), cx.clone());
+        let comments_panel = comments::CommentsPanel::load(workspace_handle.clone(), cx.clone());
+        let todo_panel = todo_panel::TodoPanel::load(workspace_handle.clone(), cx.clone());
+        let workspace_edits_panel =
+            workspace_edits_panel::WorkspaceEditsPanel::load(workspace_handle.clone(), cx.clone());
 
         let (
             project_panel,
@@ -488,6 +500,11 @@ fn initialize_panels(
             channels_panel,
             chat_panel,
             notification_panel,
+            bookmarks_panel,
+            file_set_panel,
+            comments_panel,
+            todo_panel,
+            workspace_edits_panel,
         ) = futures::try_join!(
             project_panel,
             outline_panel,
@@ -495,6 +512,11 @@ fn initialize_panels(
             channels_panel,
             chat_panel,
             notification_panel,
+            bookmarks_panel,
+            file_set_panel,
+            comments_panel,
+            todo_panel,
+            workspace_edits_panel,
         )?;
 
         workspace_handle.update_in(cx, |workspace, window, cx| {
@@ -504,6 +526,11 @@ fn initialize_panels(
             workspace.add_panel(channels_panel, window, cx);
             workspace.add_panel(chat_panel, window, cx);
             workspace.add_panel(notification_panel, window, cx);
+            workspace.add_panel(bookmarks_panel, window, cx);
+            workspace.add_panel(file_set_panel, window, cx);
+            workspace.add_panel(comments_panel, window, cx);
+            workspace.add_panel(todo_panel, window, cx);
+            workspace.add_panel(workspace_edits_panel, window, cx);
             cx.when_flag_enabled::<DebuggerFeatureFlag>(window, |_, window, cx| {
                 cx.spawn_in(
                     window,
@@ -777,6 +804,7 @@ fn register_actions(
         })
         .register_action(open_project_settings_file)
         .register_action(open_project_tasks_file)
+        .register_action(open_project_environment)
         .register_action(open_project_debug_tasks_file)
         .register_action(
             |workspace: &mut Workspace,
@@ -1222,14 +1250,15 @@ pub fn handle_keymap_file_changes(
 ) {
     BaseKeymap::register(cx);
     VimModeSetting::register(cx);
+    HelixModeSetting::register(cx);
 
     let (base_keymap_tx, mut base_keymap_rx) = mpsc::unbounded();
     let (keyboard_layout_tx, mut keyboard_layout_rx) = mpsc::unbounded();
     let mut old_base_keymap = *BaseKeymap::get_global(cx);
-    let mut old_vim_enabled = VimModeSetting::get_global(cx).0;
+    let mut old_vim_enabled = vim_keymap_enabled(cx);
     cx.observe_global::<SettingsStore>(move |cx| {
         let new_base_keymap = *BaseKeymap::get_global(cx);
-        let new_vim_enabled = VimModeSetting::get_global(cx).0;
+        let new_vim_enabled = vim_keymap_enabled(cx);
 
         if new_base_keymap != old_base_keymap || new_vim_enabled != old_vim_enabled {
             old_base_keymap = new_base_keymap;
@@ -1396,9 +1425,13 @@ fn show_markdown_app_notification<F>(
     .detach();
 }
 
-fn reload_keymaps(cx: &mut App, user_key_bindings: Vec<KeyBinding>) {
+fn reload_keymaps(cx: &mut App, mut user_key_bindings: Vec<KeyBinding>) {
     cx.clear_key_bindings();
     load_default_keymap(cx);
+
+    for key_binding in &mut user_key_bindings {
+        key_binding.set_meta(KeybindSource::User.meta());
+    }
     cx.bind_keys(user_key_bindings);
     cx.set_menus(app_menus());
     // On Windows, this is set in the `update_jump_list` method of the `HistoryManager`.
@@ -1415,17 +1448,29 @@ pub fn load_default_keymap(cx: &mut App) {
         return;
     }
 
-    cx.bind_keys(KeymapFile::load_asset(DEFAULT_KEYMAP_PATH, cx).unwrap());
+    let load_keymap = |asset_path: &str, source: KeybindSource, cx: &App| {
+        KeymapFile::load_asset(asset_path, cx)
+            .unwrap()
+            .into_iter()
+            .map(move |binding| binding.with_meta(source.meta()))
+    };
+
+    cx.bind_keys(load_keymap(DEFAULT_KEYMAP_PATH, KeybindSource::Default, cx));
 
     if let Some(asset_path) = base_keymap.asset_path() {
-        cx.bind_keys(KeymapFile::load_asset(asset_path, cx).unwrap());
+        cx.bind_keys(load_keymap(asset_path, KeybindSource::Base, cx));
     }
 
-    if VimModeSetting::get_global(cx).0 {
-        cx.bind_keys(KeymapFile::load_asset(VIM_KEYMAP_PATH, cx).unwrap());
+    if vim_keymap_enabled(cx) {
+        cx.bind_keys(load_keymap(VIM_KEYMAP_PATH, KeybindSource::Vim, cx));
     }
 }
 
+/// Helix mode is built on vim mode, so it needs the vim key bindings too.
+fn vim_keymap_enabled(cx: &App) -> bool {
+    VimModeSetting::get_global(cx).0 || HelixModeSetting::get_global(cx).0
+}
+
 pub fn handle_settings_changed(error: Option<anyhow::Error>, cx: &mut App) {
     struct SettingsParseErrorNotification;
     let id = NotificationId::unique::<SettingsParseErrorNotification>();
@@ -1668,6 +1713,106 @@ fn open_telemetry_log_file(
     }).detach();
 }
 
+/// Opens a read-only view of the environment variables that the project's terminals, tasks, and
+/// language servers get in each of its folders, with the values that look like secrets masked.
+fn open_project_environment(
+    workspace: &mut Workspace,
+    _: &OpenProjectEnvironment,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().clone();
+    let worktrees = project
+        .read(cx)
+        .visible_worktrees(cx)
+        .filter(|worktree| worktree.read(cx).is_local())
+        .map(|worktree| {
+            let worktree = worktree.read(cx);
+            let abs_path = worktree.abs_path();
+            let env_settings = &ProjectSettings::get(
+                Some(SettingsLocation {
+                    worktree_id: worktree.id(),
+                    path: Path::new(""),
+                }),
+                cx,
+            )
+            .env;
+            (abs_path, env_settings.profile.clone())
+        })
+        .collect::<Vec<_>>();
+    if worktrees.is_empty() {
+        struct NoLocalFolders;
+        workspace.show_toast(
+            Toast::new(
+                NotificationId::unique::<NoLocalFolders>(),
+                "The environment can only be shown for local folders",
+            )
+            .autohide(),
+            cx,
+        );
+        return;
+    }
+
+    let worktrees = worktrees
+        .into_iter()
+        .map(|(abs_path, profile)| {
+            let environment = project.update(cx, |project, cx| {
+                project.directory_environment(abs_path.clone(), cx)
+            });
+            (abs_path, profile, environment)
+        })
+        .collect::<Vec<_>>();
+    let languages = workspace.app_state().languages.clone();
+    cx.spawn_in(window, async move |workspace, cx| {
+        let redactor = redaction::current();
+        let mut content = String::new();
+        for (abs_path, profile, environment) in worktrees {
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(&format!("# {}\n", abs_path.display()));
+            if let Some(profile) = profile {
+                content.push_str(&format!("# Profile: {profile}\n"));
+            }
+            let Some(environment) = environment.await else {
+                content
+                    .push_str("# The environment couldn't be loaded. See the log for details.\n");
+                continue;
+            };
+            let mut variables = environment.into_iter().collect::<Vec<_>>();
+            variables.sort();
+            for (name, value) in variables {
+                let value = redactor.redact(&value).replace('\n', "\\n");
+                content.push_str(&format!("{name}={value}\n"));
+            }
+        }
+        let shell = languages.language_for_name("Shell Script").await.ok();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let project = workspace.project().clone();
+            let buffer = project.update(cx, |project, cx| {
+                project.create_local_buffer(&content, shell, cx)
+            });
+            let buffer = cx.new(|cx| {
+                MultiBuffer::singleton(buffer, cx).with_title("Project Environment".into())
+            });
+            workspace.add_item_to_active_pane(
+                Box::new(cx.new(|cx| {
+                    let mut editor = Editor::for_multibuffer(buffer, Some(project), window, cx);
+                    editor.set_read_only(true);
+                    editor.set_breadcrumb_header("Project Environment".into());
+                    editor
+                })),
+                None,
+                true,
+                window,
+                cx,
+            );
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 fn open_bundled_file(
     workspace: &Workspace,
     text: Cow<'static, str>,
@@ -4286,6 +4431,7 @@ mod tests {
             project_panel::init(cx);
             outline_panel::init(cx);
             terminal_view::init(cx);
+            keyboard_macros::init(cx);
             copilot::copilot_chat::init(app_state.fs.clone(), app_state.client.http_client(), cx);
             image_viewer::init(cx);
             language_model::init(app_state.client.clone(), cx);
@@ -148,6 +148,12 @@ pub mod git {
     action_with_deprecated_aliases!(git, Branch, ["branches::OpenRecent"]);
 }
 
+pub mod new_project {
+    use gpui::actions;
+
+    actions!(new_project, [Toggle]);
+}
+
 pub mod jj {
     use gpui::actions;
 
@@ -243,6 +249,8 @@ pub mod assistant {
     #[serde(deny_unknown_fields)]
     pub struct InlineAssist {
         pub prompt: Option<String>,
+        /// The title of a rule from the rules library to fill the prompt with.
+        pub rule: Option<String>,
     }
 
     impl_actions!(assistant, [InlineAssist]);